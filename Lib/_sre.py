@@ -335,6 +335,11 @@ class _State(object):
 
     def __init__(self, string, start, end, flags):
         self.string = string
+        # The characters that are matched, which for bytes are the ones of the same code points
+        if isinstance(string, (bytes, bytearray)):
+            self.chars = string.decode('latin-1')
+        else:
+            self.chars = string
         if start < 0:
             start = 0
         if end > len(string):
@@ -389,7 +394,7 @@ class _State(object):
             character = pattern_codes[1]
             while True:
                 while string_position < self.end \
-                        and ord(self.string[string_position]) != character:
+                        and ord(self.chars[string_position]) != character:
                     string_position += 1
                 if string_position >= self.end:
                     return False
@@ -426,7 +431,7 @@ class _State(object):
         string_position = self.string_position
         while string_position < self.end:
             while True:
-                if ord(self.string[string_position]) != prefix[i]:
+                if ord(self.chars[string_position]) != prefix[i]:
                     if i == 0:
                         break
                     else:
@@ -497,7 +502,7 @@ class _MatchContext(object):
         return child_context
 
     def peek_char(self, peek=0):
-        return self.state.string[self.string_position + peek]
+        return self.state.chars[self.string_position + peek]
 
     def skip_char(self, skip_count):
         self.string_position += skip_count
@@ -990,7 +995,7 @@ class _OpcodeDispatcher(_Dispatcher):
             return True
         while group_start < group_end:
             if ctx.at_end() or decorate(ord(ctx.peek_char())) \
-                                != decorate(ord(ctx.state.string[group_start])):
+                                != decorate(ord(ctx.state.chars[group_start])):
                 ctx.has_matched = False
                 return True
             group_start += 1
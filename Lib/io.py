@@ -1 +1,6 @@
 from _io import *
+
+# for seek()
+SEEK_SET = 0
+SEEK_CUR = 1
+SEEK_END = 2
@@ -137,7 +137,7 @@ def updatecache(filename, module_globals=None):
         # with tokenize.open(fullname) as fp:
         #    lines = fp.readlines()
         with open(fullname, "r") as fp:
-            lines = fp.read().split("\n")
+            lines = [line + '\n' for line in fp.read().splitlines()]
     except OSError:
         return []
     if lines and not lines[-1].endswith('\n'):
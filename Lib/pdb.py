@@ -0,0 +1,616 @@
+"""
+The Python Debugger Pdb
+=======================
+
+A minimal version of CPython's pdb, built on bdb and cmd.  It supports the
+commands most commonly used at a breakpoint:
+
+h(elp)                  Print the list of commands, or help for one of them.
+w(here)                 Print a stack trace, most recent frame last.
+u(p) / d(own)           Move the current frame up or down the stack.
+b(reak) [[file:]line | function]
+                        Set a breakpoint, or list the breakpoints.
+cl(ear) [bpnumber ...]  Clear breakpoints.
+s(tep)                  Execute the current line, stopping in called functions.
+n(ext)                  Continue until the next line in the current function.
+r(eturn)                Continue until the current function returns.
+c(ont(inue))            Continue until a breakpoint is hit.
+l(ist) [first[, last]]  List the source code of the current file.
+a(rgs)                  Print the arguments of the current function.
+p expression            Print the value of an expression.
+pp expression           Pretty-print the value of an expression.
+q(uit)                  Quit the debugger.
+
+Anything else is executed as a Python statement in the current frame; prefix
+a statement with "!" when it starts with a command name.
+
+Debugging stops at a `breakpoint()` call, since sys.breakpointhook calls
+`pdb.set_trace()` by default.
+"""
+
+import os
+import sys
+import bdb
+import cmd
+import linecache
+
+__all__ = ["run", "pm", "Pdb", "runeval", "runctx", "runcall", "set_trace",
+           "post_mortem", "help"]
+
+
+class Restart(Exception):
+    """Causes a debugger to be restarted for the debugged python program."""
+    pass
+
+
+def find_function(funcname, filename):
+    """Return (funcname, filename, lineno) of the def of funcname, or None."""
+    prefix = 'def %s(' % funcname
+    try:
+        lines = linecache.getlines(filename)
+    except OSError:
+        return None
+    for lineno, line in enumerate(lines, 1):
+        if line.lstrip().startswith(prefix):
+            return funcname, filename, lineno
+    return None
+
+
+line_prefix = '\n-> '
+
+
+class Pdb(bdb.Bdb, cmd.Cmd):
+
+    def __init__(self, completekey='tab', stdin=None, stdout=None, skip=None):
+        bdb.Bdb.__init__(self, skip=skip)
+        cmd.Cmd.__init__(self, completekey, stdin, stdout)
+        if stdout:
+            self.use_rawinput = 0
+        self.prompt = '(Pdb) '
+        self.aliases = {}
+        self.mainpyfile = ''
+        self._wait_for_mainpyfile = False
+        self._user_requested_quit = False
+        self.lineno = None
+        self.stack = []
+        self.curindex = 0
+        self.curframe = None
+        self.curframe_locals = None
+
+    def reset(self):
+        bdb.Bdb.reset(self)
+        self.forget()
+
+    def forget(self):
+        self.lineno = None
+        self.stack = []
+        self.curindex = 0
+        self.curframe = None
+
+    def setup(self, f, tb):
+        self.forget()
+        self.stack, self.curindex = self.get_stack(f, tb)
+        self.curframe = self.stack[self.curindex][0]
+        self.curframe_locals = self.curframe.f_locals
+
+    # Override Bdb methods
+
+    def user_call(self, frame, argument_list):
+        """This method is called when there is the remote possibility
+        that we ever need to stop in this function."""
+        if self._wait_for_mainpyfile:
+            return
+        if self.stop_here(frame):
+            self.message('--Call--')
+            self.interaction(frame, None)
+
+    def user_line(self, frame):
+        """This function is called when we stop or break at this line."""
+        if self._wait_for_mainpyfile:
+            if (self.mainpyfile != self.canonic(frame.f_code.co_filename)
+                    or frame.f_lineno <= 0):
+                return
+            self._wait_for_mainpyfile = False
+        self.interaction(frame, None)
+
+    def user_return(self, frame, return_value):
+        """This function is called when a return trap is set here."""
+        if self._wait_for_mainpyfile:
+            return
+        frame.f_locals['__return__'] = return_value
+        self.message('--Return--')
+        self.interaction(frame, None)
+
+    def user_exception(self, frame, exc_info):
+        """This function is called if an exception occurs,
+        but only if we are to stop at or just below this level."""
+        if self._wait_for_mainpyfile:
+            return
+        exc_type, exc_value, exc_traceback = exc_info
+        frame.f_locals['__exception__'] = exc_type, exc_value
+        self.message(self._format_exc(exc_value))
+        self.interaction(frame, exc_traceback)
+
+    def interaction(self, frame, traceback):
+        self.setup(frame, traceback)
+        self.print_stack_entry(self.stack[self.curindex])
+        self.cmdloop()
+        self.forget()
+
+    def _format_exc(self, exc):
+        lines = str(exc).splitlines()
+        message = lines[0].strip() if lines else ''
+        if message:
+            return '%s: %s' % (type(exc).__name__, message)
+        return type(exc).__name__
+
+    def message(self, msg):
+        print(msg, file=self.stdout)
+
+    def error(self, msg):
+        print('***', msg, file=self.stdout)
+
+    def default(self, line):
+        if line[:1] == '!':
+            line = line[1:]
+        globals = self.curframe.f_globals
+        locals = self.curframe_locals
+        try:
+            code = compile(line + '\n', '<stdin>', 'single')
+            exec(code, globals, locals)
+        except Exception as exc:
+            self.error(self._format_exc(exc))
+
+    def _getval(self, arg):
+        try:
+            return eval(arg, self.curframe.f_globals, self.curframe_locals)
+        except Exception as exc:
+            self.error(self._format_exc(exc))
+            raise
+
+    # Commands
+
+    def do_help(self, arg):
+        """h(elp)
+        Without argument, print the list of available commands.
+        With a command name as argument, print help about that command.
+        """
+        if not arg:
+            return cmd.Cmd.do_help(self, arg)
+        command = getattr(self, 'do_' + arg, None)
+        if command is None or command.__doc__ is None:
+            self.error('No help for %r' % arg)
+            return
+        self.message('\n'.join(line.strip() for line in
+                               command.__doc__.strip().splitlines()))
+
+    do_h = do_help
+
+    def do_break(self, arg, temporary=0):
+        """b(reak) [ ([filename:]lineno | function) ]
+        Without argument, list all breaks.
+        With a line number argument, set a break at this line in the
+        current file.  With a function name, set a break at the first
+        executable line of that function.
+        """
+        if not arg:
+            if self.breaks:
+                self.message('Num Type         Disp Enb   Where')
+                for bp in bdb.Breakpoint.bpbynumber:
+                    if bp:
+                        self.message(bp.bpformat())
+            return
+        filename = None
+        lineno = None
+        colon = arg.rfind(':')
+        if colon >= 0:
+            filename = arg[:colon].rstrip()
+            found = self.lookupmodule(filename)
+            if not found:
+                self.error('%r not found from sys.path' % filename)
+                return
+            filename = found
+            try:
+                lineno = int(arg[colon + 1:])
+            except ValueError:
+                self.error('Bad lineno: %s' % arg[colon + 1:])
+                return
+        else:
+            try:
+                lineno = int(arg)
+            except ValueError:
+                try:
+                    func = self._getval(arg)
+                except Exception:
+                    found = find_function(arg, self.defaultFile())
+                    if not found:
+                        return
+                    funcname, filename, lineno = found
+                else:
+                    code = getattr(func, '__code__', None)
+                    if code is None:
+                        self.error('%r is not a function' % arg)
+                        return
+                    filename = code.co_filename
+                    lineno = code.co_firstlineno + 1
+        if not filename:
+            filename = self.defaultFile()
+        line = linecache.getline(filename, lineno)
+        if not line.strip() or line.strip().startswith('#'):
+            self.error('Blank or comment')
+            return
+        err = self.set_break(filename, lineno, temporary)
+        if err:
+            self.error(err)
+        else:
+            bp = self.get_breaks(filename, lineno)[-1]
+            self.message('Breakpoint %d at %s:%d' %
+                         (bp.number, bp.file, bp.line))
+
+    do_b = do_break
+
+    def do_clear(self, arg):
+        """cl(ear) [bpnumber [bpnumber...]]
+        Without argument, clear all breaks (but first ask confirmation).
+        With a space separated list of breakpoint numbers, clear those
+        breakpoints.
+        """
+        if not arg:
+            try:
+                reply = input('Clear all breaks? ')
+            except EOFError:
+                reply = 'no'
+            reply = reply.strip().lower()
+            if reply in ('y', 'yes'):
+                bplist = [bp for bp in bdb.Breakpoint.bpbynumber if bp]
+                self.clear_all_breaks()
+                for bp in bplist:
+                    self.message('Deleted %s' % bp)
+            return
+        for i in arg.split():
+            try:
+                bp = self.get_bpbynumber(i)
+            except ValueError as err:
+                self.error(err)
+            else:
+                self.clear_bpbynumber(i)
+                self.message('Deleted %s' % bp)
+
+    do_cl = do_clear
+
+    def do_where(self, arg):
+        """w(here)
+        Print a stack trace, with the most recent frame at the bottom.
+        An arrow indicates the "current frame", which determines the
+        context of most commands.
+        """
+        for frame_lineno in self.stack:
+            self.print_stack_entry(frame_lineno)
+
+    do_w = do_where
+    do_bt = do_where
+
+    def _select_frame(self, number):
+        assert 0 <= number < len(self.stack)
+        self.curindex = number
+        self.curframe = self.stack[self.curindex][0]
+        self.curframe_locals = self.curframe.f_locals
+        self.print_stack_entry(self.stack[self.curindex])
+        self.lineno = None
+
+    def do_up(self, arg):
+        """u(p)
+        Move the current frame one level up in the stack trace
+        (to an older frame).
+        """
+        if self.curindex == 0:
+            self.error('Oldest frame')
+            return
+        self._select_frame(self.curindex - 1)
+
+    do_u = do_up
+
+    def do_down(self, arg):
+        """d(own)
+        Move the current frame one level down in the stack trace
+        (to a newer frame).
+        """
+        if self.curindex + 1 == len(self.stack):
+            self.error('Newest frame')
+            return
+        self._select_frame(self.curindex + 1)
+
+    do_d = do_down
+
+    def do_step(self, arg):
+        """s(tep)
+        Execute the current line, stop at the first possible occasion
+        (either in a function that is called or in the current
+        function).
+        """
+        self.set_step()
+        return 1
+
+    do_s = do_step
+
+    def do_next(self, arg):
+        """n(ext)
+        Continue execution until the next line in the current function
+        is reached or it returns.
+        """
+        self.set_next(self.curframe)
+        return 1
+
+    do_n = do_next
+
+    def do_return(self, arg):
+        """r(eturn)
+        Continue execution until the current function returns.
+        """
+        self.set_return(self.curframe)
+        return 1
+
+    do_r = do_return
+
+    def do_continue(self, arg):
+        """c(ont(inue))
+        Continue execution, only stop when a breakpoint is encountered.
+        """
+        self.set_continue()
+        return 1
+
+    do_c = do_cont = do_continue
+
+    def do_quit(self, arg):
+        """q(uit)
+        Quit from the debugger. The program being executed is aborted.
+        """
+        self._user_requested_quit = True
+        self.set_quit()
+        return 1
+
+    do_q = do_exit = do_quit
+
+    def do_EOF(self, arg):
+        """EOF
+        Handles the receipt of EOF as a command.
+        """
+        self.message('')
+        return self.do_quit(arg)
+
+    def do_args(self, arg):
+        """a(rgs)
+        Print the argument list of the current function.
+        """
+        co = self.curframe.f_code
+        n = co.co_argcount + co.co_kwonlyargcount
+        if co.co_flags & 4:
+            n = n + 1
+        if co.co_flags & 8:
+            n = n + 1
+        for name in co.co_varnames[:n]:
+            if name in self.curframe_locals:
+                self.message('%s = %r' % (name, self.curframe_locals[name]))
+            else:
+                self.message('%s = *** undefined ***' % (name,))
+
+    do_a = do_args
+
+    def do_p(self, arg):
+        """p expression
+        Print the value of the expression.
+        """
+        try:
+            self.message(repr(self._getval(arg)))
+        except Exception:
+            pass
+
+    def do_pp(self, arg):
+        """pp expression
+        Pretty-print the value of the expression.
+        """
+        import pprint
+        try:
+            self.message(pprint.pformat(self._getval(arg)))
+        except Exception:
+            pass
+
+    def do_list(self, arg):
+        """l(ist) [first [,last] | .]
+        List source code for the current file.  Without arguments,
+        list 11 lines around the current line or continue the previous
+        listing.  With . as argument, list 11 lines around the current
+        line.  With one argument, list 11 lines starting at that line.
+        With two arguments, list the given range; if the second
+        argument is less than the first, it is a count.
+        """
+        self.lastcmd = 'list'
+        last = None
+        if arg and arg != '.':
+            try:
+                if ',' in arg:
+                    first, last = arg.split(',')
+                    first = int(first.strip())
+                    last = int(last.strip())
+                    if last < first:
+                        # assume it's a count
+                        last = first + last
+                else:
+                    first = int(arg.strip())
+                    first = max(1, first - 5)
+            except ValueError:
+                self.error('Error in argument: %r' % arg)
+                return
+        elif self.lineno is None or arg == '.':
+            first = max(1, self.curframe.f_lineno - 5)
+        else:
+            first = self.lineno + 1
+        if last is None:
+            last = first + 10
+        filename = self.curframe.f_code.co_filename
+        breaklist = self.get_file_breaks(filename)
+        lines = linecache.getlines(filename, self.curframe.f_globals)
+        self._print_lines(lines[first - 1:last], first, breaklist,
+                          self.curframe)
+        self.lineno = min(last, len(lines))
+        if len(lines) < last:
+            self.message('[EOF]')
+
+    do_l = do_list
+
+    def _print_lines(self, lines, start, breaks=(), frame=None):
+        """Print a range of lines."""
+        current_lineno = frame.f_lineno if frame else -1
+        for lineno, line in enumerate(lines, start):
+            s = str(lineno).rjust(3)
+            if len(s) < 4:
+                s += ' '
+            if lineno in breaks:
+                s += 'B'
+            else:
+                s += ' '
+            if lineno == current_lineno:
+                s += '->'
+            self.message(s + '\t' + line.rstrip())
+
+    # Helpers
+
+    def print_stack_entry(self, frame_lineno, prompt_prefix=line_prefix):
+        frame, lineno = frame_lineno
+        if frame is self.curframe:
+            prefix = '> '
+        else:
+            prefix = '  '
+        self.message(prefix +
+                     self.format_stack_entry(frame_lineno, prompt_prefix))
+
+    def defaultFile(self):
+        """Produce a reasonable default."""
+        filename = self.curframe.f_code.co_filename
+        if filename == '<string>' and self.mainpyfile:
+            filename = self.mainpyfile
+        return filename
+
+    def lookupmodule(self, filename):
+        """Helper function for break/clear parsing -- may be overridden.
+
+        lookupmodule() translates (possibly incomplete) file or module name
+        into an absolute file name.
+        """
+        if os.path.isabs(filename) and os.path.exists(filename):
+            return filename
+        f = os.path.join(sys.path[0], filename)
+        if os.path.exists(f) and self.canonic(f) == self.mainpyfile:
+            return f
+        root, ext = os.path.splitext(filename)
+        if ext == '':
+            filename = filename + '.py'
+        if os.path.isabs(filename):
+            return filename
+        for dirname in sys.path:
+            while os.path.islink(dirname):
+                dirname = os.readlink(dirname)
+            fullname = os.path.join(dirname, filename)
+            if os.path.exists(fullname):
+                return fullname
+        return None
+
+    def _runscript(self, filename):
+        import __main__
+        __main__.__dict__.clear()
+        __main__.__dict__.update({"__name__": "__main__",
+                                  "__file__": filename,
+                                  "__builtins__": __builtins__,
+                                  })
+        self._wait_for_mainpyfile = True
+        self.mainpyfile = self.canonic(filename)
+        with open(filename, "rb") as fp:
+            statement = "exec(compile(%r, %r, 'exec'))" % \
+                        (fp.read().decode(), self.mainpyfile)
+        self.run(statement)
+
+
+# Simplified interface
+
+def run(statement, globals=None, locals=None):
+    Pdb().run(statement, globals, locals)
+
+
+def runeval(expression, globals=None, locals=None):
+    return Pdb().runeval(expression, globals, locals)
+
+
+def runctx(statement, globals, locals):
+    # B/W compatibility
+    run(statement, globals, locals)
+
+
+def runcall(*args, **kwds):
+    return Pdb().runcall(*args, **kwds)
+
+
+def set_trace(*, header=None):
+    pdb = Pdb()
+    if header is not None:
+        pdb.message(header)
+    pdb.set_trace(sys._getframe().f_back)
+
+
+# Post-Mortem interface
+
+def post_mortem(t=None):
+    # handling the default
+    if t is None:
+        # sys.exc_info() returns (type, value, traceback) if an exception is
+        # being handled, otherwise it returns None
+        t = sys.exc_info()[2]
+    if t is None:
+        raise ValueError("A valid traceback must be passed if no "
+                         "exception is being handled")
+
+    p = Pdb()
+    p.reset()
+    p.interaction(None, t)
+
+
+def pm():
+    post_mortem(sys.last_traceback)
+
+
+def help():
+    print(__doc__)
+
+
+_usage = """\
+usage: pdb.py pyfile [arg] ...
+
+Debug the Python program given by pyfile."""
+
+
+def main():
+    if not sys.argv[1:] or sys.argv[1] in ('-h', '--help'):
+        print(_usage)
+        sys.exit(2)
+
+    mainpyfile = sys.argv[1]
+    if not os.path.exists(mainpyfile):
+        print('Error:', mainpyfile, 'does not exist')
+        sys.exit(1)
+
+    # Hide "pdb.py" from argument list
+    del sys.argv[0]
+    sys.path[0] = os.path.dirname(mainpyfile)
+
+    pdb = Pdb()
+    try:
+        pdb._runscript(mainpyfile)
+    except SystemExit:
+        pass
+    if pdb._user_requested_quit:
+        return
+    print("The program finished")
+
+
+if __name__ == '__main__':
+    import pdb
+    pdb.main()
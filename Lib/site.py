@@ -102,6 +102,9 @@ def abs_paths():
         if (getattr(getattr(m, '__loader__', None), '__module__', None) not in
                 ('_frozen_importlib', '_frozen_importlib_external')):
             continue   # don't mess with a PEP 302-supplied __file__
+        # XXX RustPython: frozen modules have a __file__ that isn't a path
+        if getattr(getattr(m, '__spec__', None), 'origin', None) == 'frozen':
+            continue
         try:
             m.__file__ = os.path.abspath(m.__file__)
         except (AttributeError, OSError, TypeError):
@@ -0,0 +1,673 @@
+# Wrapper module for _ssl, providing the ssl API of CPython 3.7.
+#
+# XXX RustPython: there is no TLS implementation yet. The TLS connections are
+# left to a native _ssl module, which doesn't exist yet; once it does,
+# SSLContext.wrap_socket() and SSLContext.wrap_bio() call its wrap_socket()
+# and wrap_bio() functions with the context and their arguments, so that the
+# API of this module doesn't change.
+
+"""This module provides the API of CPython's ssl module, without TLS.
+
+The constants, the exceptions, SSLContext with its certificate and path
+settings, MemoryBIO and the certificate conversion functions all work, so that
+code which imports ssl or only sets up a context keeps working. Wrapping a
+socket or a BIO, which needs an actual TLS connection, raises
+NotImplementedError.
+
+Object types:
+
+  SSLSocket -- subtype of socket.socket which does SSL over the socket
+  SSLContext -- the settings shared by several SSL connections
+  MemoryBIO -- an in-memory buffer for SSLObject
+
+Exceptions:
+
+  SSLError -- exception raised for I/O errors
+  SSLCertVerificationError -- a certificate didn't verify
+
+Functions:
+
+  create_default_context -- a context with secure default settings
+  get_default_verify_paths -- the default locations of the CA certificates
+  DER_cert_to_PEM_cert, PEM_cert_to_DER_cert -- convert certificates
+"""
+
+import base64
+import os
+import sys
+import textwrap
+from collections import namedtuple
+from enum import Enum as _Enum, IntEnum as _IntEnum, IntFlag as _IntFlag
+from socket import socket, SOCK_STREAM
+
+try:
+    import _ssl as _backend
+except ImportError:
+    _backend = None
+
+_NO_BACKEND_MESSAGE = (
+    "RustPython doesn't implement TLS yet, so ssl can't wrap connections; "
+    "talk to the server without TLS (e.g. to a local SMTP relay without "
+    "starttls()) instead"
+)
+
+
+def _require_backend():
+    """Return the _ssl module, or raise NotImplementedError if there is none."""
+    if _backend is None:
+        raise NotImplementedError(_NO_BACKEND_MESSAGE)
+    return _backend
+
+
+OPENSSL_VERSION = getattr(_backend, "OPENSSL_VERSION", "no TLS implementation")
+OPENSSL_VERSION_INFO = getattr(_backend, "OPENSSL_VERSION_INFO", (0, 0, 0, 0, 0))
+OPENSSL_VERSION_NUMBER = getattr(_backend, "OPENSSL_VERSION_NUMBER", 0)
+
+HAS_SNI = getattr(_backend, "HAS_SNI", False)
+HAS_ECDH = getattr(_backend, "HAS_ECDH", False)
+HAS_NPN = getattr(_backend, "HAS_NPN", False)
+HAS_ALPN = getattr(_backend, "HAS_ALPN", False)
+HAS_TLSv1_3 = getattr(_backend, "HAS_TLSv1_3", False)
+HAS_NEVER_CHECK_COMMON_NAME = getattr(_backend, "HAS_NEVER_CHECK_COMMON_NAME", False)
+
+CHANNEL_BINDING_TYPES = []
+
+
+def _export(enum_class):
+    """Make the members of enum_class module constants, like CPython does."""
+    module = globals()
+    for name, member in enum_class.__members__.items():
+        module[name] = member
+    return enum_class
+
+
+@_export
+class _SSLMethod(_IntEnum):
+    PROTOCOL_TLS = 2
+    PROTOCOL_SSLv23 = 2
+    PROTOCOL_TLSv1 = 3
+    PROTOCOL_TLSv1_1 = 4
+    PROTOCOL_TLSv1_2 = 5
+    PROTOCOL_TLS_CLIENT = 16
+    PROTOCOL_TLS_SERVER = 17
+
+
+@_export
+class Options(_IntFlag):
+    OP_ALL = 0x80000054
+    OP_NO_SSLv2 = 0
+    OP_NO_SSLv3 = 0x2000000
+    OP_NO_TLSv1 = 0x4000000
+    OP_NO_TLSv1_1 = 0x10000000
+    OP_NO_TLSv1_2 = 0x8000000
+    OP_NO_TLSv1_3 = 0x20000000
+    OP_NO_COMPRESSION = 0x20000
+    OP_CIPHER_SERVER_PREFERENCE = 0x400000
+    OP_SINGLE_DH_USE = 0
+    OP_SINGLE_ECDH_USE = 0
+    OP_NO_TICKET = 0x4000
+    OP_NO_RENEGOTIATION = 0x40000000
+    OP_ENABLE_MIDDLEBOX_COMPAT = 0x100000
+
+
+@_export
+class AlertDescription(_IntEnum):
+    ALERT_DESCRIPTION_CLOSE_NOTIFY = 0
+    ALERT_DESCRIPTION_UNEXPECTED_MESSAGE = 10
+    ALERT_DESCRIPTION_BAD_RECORD_MAC = 20
+    ALERT_DESCRIPTION_RECORD_OVERFLOW = 22
+    ALERT_DESCRIPTION_DECOMPRESSION_FAILURE = 30
+    ALERT_DESCRIPTION_HANDSHAKE_FAILURE = 40
+    ALERT_DESCRIPTION_BAD_CERTIFICATE = 42
+    ALERT_DESCRIPTION_UNSUPPORTED_CERTIFICATE = 43
+    ALERT_DESCRIPTION_CERTIFICATE_REVOKED = 44
+    ALERT_DESCRIPTION_CERTIFICATE_EXPIRED = 45
+    ALERT_DESCRIPTION_CERTIFICATE_UNKNOWN = 46
+    ALERT_DESCRIPTION_ILLEGAL_PARAMETER = 47
+    ALERT_DESCRIPTION_UNKNOWN_CA = 48
+    ALERT_DESCRIPTION_ACCESS_DENIED = 49
+    ALERT_DESCRIPTION_DECODE_ERROR = 50
+    ALERT_DESCRIPTION_DECRYPT_ERROR = 51
+    ALERT_DESCRIPTION_PROTOCOL_VERSION = 70
+    ALERT_DESCRIPTION_INSUFFICIENT_SECURITY = 71
+    ALERT_DESCRIPTION_INTERNAL_ERROR = 80
+    ALERT_DESCRIPTION_USER_CANCELLED = 90
+    ALERT_DESCRIPTION_NO_RENEGOTIATION = 100
+    ALERT_DESCRIPTION_UNSUPPORTED_EXTENSION = 110
+    ALERT_DESCRIPTION_CERTIFICATE_UNOBTAINABLE = 111
+    ALERT_DESCRIPTION_UNRECOGNIZED_NAME = 112
+    ALERT_DESCRIPTION_BAD_CERTIFICATE_STATUS_RESPONSE = 113
+    ALERT_DESCRIPTION_BAD_CERTIFICATE_HASH_VALUE = 114
+    ALERT_DESCRIPTION_UNKNOWN_PSK_IDENTITY = 115
+
+
+@_export
+class SSLErrorNumber(_IntEnum):
+    SSL_ERROR_SSL = 1
+    SSL_ERROR_WANT_READ = 2
+    SSL_ERROR_WANT_WRITE = 3
+    SSL_ERROR_WANT_X509_LOOKUP = 4
+    SSL_ERROR_SYSCALL = 5
+    SSL_ERROR_ZERO_RETURN = 6
+    SSL_ERROR_WANT_CONNECT = 7
+    SSL_ERROR_EOF = 8
+    SSL_ERROR_INVALID_ERROR_CODE = 10
+
+
+@_export
+class VerifyFlags(_IntFlag):
+    VERIFY_DEFAULT = 0
+    VERIFY_CRL_CHECK_LEAF = 0x4
+    VERIFY_CRL_CHECK_CHAIN = 0xc
+    VERIFY_X509_STRICT = 0x20
+    VERIFY_X509_TRUSTED_FIRST = 0x8000
+
+
+@_export
+class VerifyMode(_IntEnum):
+    CERT_NONE = 0
+    CERT_OPTIONAL = 1
+    CERT_REQUIRED = 2
+
+
+class TLSVersion(_IntEnum):
+    MINIMUM_SUPPORTED = -2
+    SSLv3 = 0x300
+    TLSv1 = 0x301
+    TLSv1_1 = 0x302
+    TLSv1_2 = 0x303
+    TLSv1_3 = 0x304
+    MAXIMUM_SUPPORTED = -1
+
+
+class SSLError(OSError):
+    """An error occurred in the SSL implementation."""
+
+    library = None
+    reason = None
+
+    def __str__(self):
+        if self.args and len(self.args) > 1:
+            return str(self.args[1])
+        return super().__str__()
+
+
+class SSLZeroReturnError(SSLError):
+    """SSL/TLS session closed cleanly."""
+
+
+class SSLWantReadError(SSLError):
+    """Non-blocking SSL socket needs to read more data
+    before the requested operation can be completed."""
+
+
+class SSLWantWriteError(SSLError):
+    """Non-blocking SSL socket needs to write more data
+    before the requested operation can be completed."""
+
+
+class SSLSyscallError(SSLError):
+    """System error when attempting SSL operation."""
+
+
+class SSLEOFError(SSLError):
+    """SSL/TLS connection terminated abruptly."""
+
+
+class SSLCertVerificationError(SSLError, ValueError):
+    """A certificate could not be verified."""
+
+    verify_code = None
+    verify_message = None
+
+
+CertificateError = SSLCertVerificationError
+
+
+# The objects of the extended key usages that Purpose names
+_OBJECTS = {
+    '1.3.6.1.5.5.7.3.1': (129, 'serverAuth', 'TLS Web Server Authentication'),
+    '1.3.6.1.5.5.7.3.2': (130, 'clientAuth', 'TLS Web Client Authentication'),
+}
+
+
+class _ASN1Object(namedtuple("_ASN1Object", "nid shortname longname oid")):
+    """ASN.1 object identifier lookup
+    """
+    __slots__ = ()
+
+    def __new__(cls, oid):
+        try:
+            nid, shortname, longname = _OBJECTS[oid]
+        except KeyError:
+            raise ValueError("unknown object '{}'".format(oid)) from None
+        # XXX RustPython: super().__new__ doesn't work in the Enum subclass
+        return tuple.__new__(cls, (nid, shortname, longname, oid))
+
+
+class Purpose(_ASN1Object, _Enum):
+    """SSLContext purpose flags with X509v3 Extended Key Usage objects
+    """
+    SERVER_AUTH = '1.3.6.1.5.5.7.3.1'
+    CLIENT_AUTH = '1.3.6.1.5.5.7.3.2'
+
+
+DefaultVerifyPaths = namedtuple("DefaultVerifyPaths",
+    "cafile capath openssl_cafile_env openssl_cafile openssl_capath_env "
+    "openssl_capath")
+
+# The CA bundles and directories of the common Unix distributions, in the
+# order they are looked for when there is no TLS implementation to ask
+_DEFAULT_CAFILES = [
+    '/etc/ssl/certs/ca-certificates.crt',
+    '/etc/pki/tls/certs/ca-bundle.crt',
+    '/etc/ssl/cert.pem',
+    '/usr/local/etc/openssl/cert.pem',
+]
+_DEFAULT_CAPATHS = ['/etc/ssl/certs', '/etc/pki/tls/certs']
+
+
+def _default_verify_paths():
+    if _backend is not None:
+        return _backend.get_default_verify_paths()
+    cafile = next((f for f in _DEFAULT_CAFILES if os.path.isfile(f)),
+                  _DEFAULT_CAFILES[0])
+    capath = next((p for p in _DEFAULT_CAPATHS if os.path.isdir(p)),
+                  _DEFAULT_CAPATHS[0])
+    return ('SSL_CERT_FILE', cafile, 'SSL_CERT_DIR', capath)
+
+
+def get_default_verify_paths():
+    """Return paths to default cafile and capath.
+
+    The SSL_CERT_FILE and SSL_CERT_DIR environment variables override the
+    defaults; cafile and capath are None if the file or directory doesn't
+    exist.
+    """
+    parts = _default_verify_paths()
+
+    # environment vars shadow paths
+    cafile = os.environ.get(parts[0], parts[1])
+    capath = os.environ.get(parts[2], parts[3])
+
+    return DefaultVerifyPaths(cafile if os.path.isfile(cafile) else None,
+                              capath if os.path.isdir(capath) else None,
+                              *parts)
+
+
+def _check_readable(path):
+    """Raise the OSError of opening path, like loading it would."""
+    with open(os.fspath(path), 'rb'):
+        pass
+
+
+def _encode_protocols(protocols):
+    encoded = bytearray()
+    for protocol in protocols:
+        b = bytes(protocol, 'ascii')
+        if len(b) == 0 or len(b) > 255:
+            raise ValueError('NPN protocols must be 1 to 255 in length')
+        encoded.append(len(b))
+        encoded.extend(b)
+    return bytes(encoded)
+
+
+class SSLContext:
+    """An SSLContext holds various SSL-related configuration options and
+    data, such as certificates and possibly a private key."""
+
+    sslsocket_class = None  # SSLSocket is assigned later.
+    sslobject_class = None  # SSLObject is assigned later.
+
+    def __init__(self, protocol=PROTOCOL_TLS):
+        self._protocol = _SSLMethod(protocol)
+        self._options = Options(OP_ALL | OP_NO_SSLv2 | OP_NO_SSLv3 |
+                                OP_NO_COMPRESSION |
+                                OP_CIPHER_SERVER_PREFERENCE |
+                                OP_SINGLE_DH_USE | OP_SINGLE_ECDH_USE)
+        self.verify_flags = VERIFY_X509_TRUSTED_FIRST
+        self.minimum_version = TLSVersion.MINIMUM_SUPPORTED
+        self.maximum_version = TLSVersion.MAXIMUM_SUPPORTED
+        if self._protocol == PROTOCOL_TLS_CLIENT:
+            self._verify_mode = CERT_REQUIRED
+            self._check_hostname = True
+        else:
+            self._verify_mode = CERT_NONE
+            self._check_hostname = False
+        self.sni_callback = None
+        self._ca_files = []
+        self._ca_paths = []
+        self._ca_data = []
+        self._default_verify_paths = False
+        self._cert_chain = None
+        self._ciphers = None
+        self._alpn_protocols = None
+        self._npn_protocols = None
+        self._ecdh_curve = None
+        self._dh_params = None
+
+    @property
+    def protocol(self):
+        return self._protocol
+
+    @property
+    def options(self):
+        return self._options
+
+    @options.setter
+    def options(self, value):
+        self._options = Options(value)
+
+    @property
+    def verify_mode(self):
+        return self._verify_mode
+
+    @verify_mode.setter
+    def verify_mode(self, value):
+        value = VerifyMode(value)
+        if value == CERT_NONE and self._check_hostname:
+            raise ValueError("Cannot set verify_mode to CERT_NONE when "
+                             "check_hostname is enabled.")
+        self._verify_mode = value
+
+    @property
+    def check_hostname(self):
+        return self._check_hostname
+
+    @check_hostname.setter
+    def check_hostname(self, value):
+        value = bool(value)
+        if value and self._verify_mode == CERT_NONE:
+            self._verify_mode = CERT_REQUIRED
+        self._check_hostname = value
+
+    def load_verify_locations(self, cafile=None, capath=None, cadata=None):
+        if cafile is None and capath is None and cadata is None:
+            raise TypeError("cafile, capath and cadata cannot be all omitted")
+        if cadata is not None:
+            if isinstance(cadata, str):
+                cadata = cadata.encode('ascii')
+            elif isinstance(cadata, (bytes, bytearray, memoryview)):
+                cadata = bytes(cadata)
+            else:
+                raise TypeError("cadata should be an ASCII string or a "
+                                "bytes-like object")
+            self._ca_data.append(cadata)
+        if cafile is not None:
+            _check_readable(cafile)
+            self._ca_files.append(os.fspath(cafile))
+        if capath is not None:
+            self._ca_paths.append(os.fspath(capath))
+
+    def load_cert_chain(self, certfile, keyfile=None, password=None):
+        if password is not None and not callable(password) \
+                and not isinstance(password, (str, bytes, bytearray)):
+            raise TypeError("password should be a string or callable")
+        _check_readable(certfile)
+        if keyfile is not None:
+            _check_readable(keyfile)
+        self._cert_chain = (os.fspath(certfile),
+                            None if keyfile is None else os.fspath(keyfile),
+                            password)
+
+    def load_default_certs(self, purpose=Purpose.SERVER_AUTH):
+        if not isinstance(purpose, _ASN1Object):
+            raise TypeError(purpose)
+        self.set_default_verify_paths()
+
+    def set_default_verify_paths(self):
+        self._default_verify_paths = True
+
+    def load_dh_params(self, path):
+        _check_readable(path)
+        self._dh_params = os.fspath(path)
+
+    def set_ciphers(self, ciphers):
+        self._ciphers = ciphers
+
+    def set_alpn_protocols(self, alpn_protocols):
+        self._alpn_protocols = _encode_protocols(alpn_protocols)
+
+    def set_npn_protocols(self, npn_protocols):
+        self._npn_protocols = _encode_protocols(npn_protocols)
+
+    def set_ecdh_curve(self, name):
+        self._ecdh_curve = name
+
+    def set_servername_callback(self, server_name_callback):
+        if server_name_callback is not None and \
+                not callable(server_name_callback):
+            raise TypeError("not a callable object")
+        self.sni_callback = server_name_callback
+
+    def get_ca_certs(self, binary_form=False):
+        # XXX RustPython: the certificates aren't parsed without a TLS
+        # implementation
+        return []
+
+    def cert_store_stats(self):
+        return {'x509': 0, 'crl': 0, 'x509_ca': 0}
+
+    def session_stats(self):
+        return {name: 0 for name in (
+            'number', 'connect', 'connect_good', 'connect_renegotiate',
+            'accept', 'accept_good', 'accept_renegotiate', 'hits', 'misses',
+            'timeouts', 'cache_full')}
+
+    def _check_wrap_args(self, server_side, server_hostname):
+        if server_side and server_hostname:
+            raise ValueError("server_hostname can only be specified "
+                             "in client mode")
+        if self._check_hostname and not server_side and not server_hostname:
+            raise ValueError("check_hostname requires server_hostname")
+
+    def wrap_socket(self, sock, server_side=False,
+                    do_handshake_on_connect=True,
+                    suppress_ragged_eofs=True,
+                    server_hostname=None, session=None):
+        self._check_wrap_args(server_side, server_hostname)
+        return _require_backend().wrap_socket(
+            self, sock, server_side, do_handshake_on_connect,
+            suppress_ragged_eofs, server_hostname, session)
+
+    def wrap_bio(self, incoming, outgoing, server_side=False,
+                 server_hostname=None, session=None):
+        self._check_wrap_args(server_side, server_hostname)
+        return _require_backend().wrap_bio(
+            self, incoming, outgoing, server_side, server_hostname, session)
+
+
+def create_default_context(purpose=Purpose.SERVER_AUTH, *, cafile=None,
+                           capath=None, cadata=None):
+    """Create a SSLContext object with default settings.
+
+    NOTE: The protocol and settings may change anytime without prior
+          deprecation. The values represent a fair balance between maximum
+          compatibility and security.
+    """
+    if not isinstance(purpose, _ASN1Object):
+        raise TypeError(purpose)
+
+    context = SSLContext(PROTOCOL_TLS)
+
+    if purpose == Purpose.SERVER_AUTH:
+        # verify certs and host name in client mode
+        context.verify_mode = CERT_REQUIRED
+        context.check_hostname = True
+
+    if cafile or capath or cadata:
+        context.load_verify_locations(cafile, capath, cadata)
+    elif context.verify_mode != CERT_NONE:
+        # no explicit cafile, capath or cadata but the verify mode is
+        # CERT_OPTIONAL or CERT_REQUIRED. Let's try to load default system
+        # root CA certificates for the given purpose. This may fail silently.
+        context.load_default_certs(purpose)
+    return context
+
+
+def _create_unverified_context(protocol=PROTOCOL_TLS, *, cert_reqs=CERT_NONE,
+                           check_hostname=False, purpose=Purpose.SERVER_AUTH,
+                           certfile=None, keyfile=None,
+                           cafile=None, capath=None, cadata=None):
+    """Create a SSLContext object for Python stdlib modules
+
+    All Python stdlib modules shall use this function to create SSLContext
+    objects in order to keep common settings in one place. The configuration
+    is less restrict than create_default_context()'s to increase backward
+    compatibility.
+    """
+    if not isinstance(purpose, _ASN1Object):
+        raise TypeError(purpose)
+
+    context = SSLContext(protocol)
+
+    if not check_hostname:
+        context.check_hostname = False
+    if cert_reqs is not None:
+        context.verify_mode = cert_reqs
+    if check_hostname:
+        context.check_hostname = True
+
+    if keyfile and not certfile:
+        raise ValueError("certfile must be specified")
+    if certfile or keyfile:
+        context.load_cert_chain(certfile, keyfile)
+
+    # load CA root certs
+    if cafile or capath or cadata:
+        context.load_verify_locations(cafile, capath, cadata)
+    elif context.verify_mode != CERT_NONE:
+        context.load_default_certs(purpose)
+
+    return context
+
+
+# Used by http.client if no context is explicitly passed.
+_create_default_https_context = create_default_context
+
+
+# Backwards compatibility alias, even though it's not a public name.
+_create_stdlib_context = _create_unverified_context
+
+
+class MemoryBIO:
+    """A memory buffer, which an SSLObject reads its input from and writes
+    its output to."""
+
+    def __init__(self):
+        self._buffer = bytearray()
+        self._eof = False
+
+    @property
+    def pending(self):
+        """The number of bytes pending in the memory BIO."""
+        return len(self._buffer)
+
+    @property
+    def eof(self):
+        """Whether the memory BIO is at EOF."""
+        return self._eof and not self._buffer
+
+    def read(self, size=-1):
+        """Read up to size bytes from the memory BIO."""
+        if size < 0 or size > len(self._buffer):
+            size = len(self._buffer)
+        data = bytes(self._buffer[:size])
+        self._buffer = self._buffer[size:]
+        return data
+
+    def write(self, b):
+        """Write the bytes b to the memory BIO, returning their length."""
+        if self._eof:
+            raise SSLError("cannot write() after write_eof()")
+        data = memoryview(b).tobytes()
+        self._buffer.extend(data)
+        return len(data)
+
+    def write_eof(self):
+        """Write an EOF marker to the memory BIO."""
+        self._eof = True
+
+
+class SSLObject:
+    """This class implements an interface on top of a low-level SSL object,
+    which SSLContext.wrap_bio() returns."""
+
+    def __init__(self, *args, **kwargs):
+        raise TypeError(
+            f"{self.__class__.__name__} does not have a public "
+            f"constructor. Instances are returned by SSLContext.wrap_bio()."
+        )
+
+
+class SSLSocket(socket):
+    """This class implements a subtype of socket.socket that wraps
+    the underlying OS socket in an SSL context when necessary, and
+    provides read and write methods over that channel."""
+
+    def __init__(self, *args, **kwargs):
+        raise TypeError(
+            f"{self.__class__.__name__} does not have a public "
+            f"constructor. Instances are returned by "
+            f"SSLContext.wrap_socket()."
+        )
+
+
+SSLContext.sslsocket_class = SSLSocket
+SSLContext.sslobject_class = SSLObject
+
+
+def wrap_socket(sock, keyfile=None, certfile=None,
+                server_side=False, cert_reqs=CERT_NONE,
+                ssl_version=PROTOCOL_TLS, ca_certs=None,
+                do_handshake_on_connect=True,
+                suppress_ragged_eofs=True,
+                ciphers=None):
+
+    if server_side and not certfile:
+        raise ValueError("certfile must be specified for server-side "
+                         "operations")
+    if keyfile and not certfile:
+        raise ValueError("certfile must be specified")
+    context = SSLContext(ssl_version)
+    context.verify_mode = cert_reqs
+    if ca_certs:
+        context.load_verify_locations(ca_certs)
+    if certfile:
+        context.load_cert_chain(certfile, keyfile)
+    if ciphers:
+        context.set_ciphers(ciphers)
+    return context.wrap_socket(
+        sock=sock, server_side=server_side,
+        do_handshake_on_connect=do_handshake_on_connect,
+        suppress_ragged_eofs=suppress_ragged_eofs
+    )
+
+
+PEM_HEADER = "-----BEGIN CERTIFICATE-----"
+PEM_FOOTER = "-----END CERTIFICATE-----"
+
+
+def DER_cert_to_PEM_cert(der_cert_bytes):
+    """Takes a certificate in binary DER format and returns the
+    PEM version of it as a string."""
+
+    f = base64.standard_b64encode(der_cert_bytes).decode('ascii', 'strict')
+    ss = [PEM_HEADER]
+    ss += textwrap.wrap(f, 64)
+    ss.append(PEM_FOOTER + '\n')
+    return '\n'.join(ss)
+
+
+def PEM_cert_to_DER_cert(pem_cert_string):
+    """Takes a certificate in ASCII PEM format and returns the
+    DER-encoded version of it as a byte sequence"""
+
+    if not pem_cert_string.startswith(PEM_HEADER):
+        raise ValueError("Invalid PEM encoding; must start with %s"
+                         % PEM_HEADER)
+    if not pem_cert_string.strip().endswith(PEM_FOOTER):
+        raise ValueError("Invalid PEM encoding; must end with %s"
+                         % PEM_FOOTER)
+    d = pem_cert_string.strip()[len(PEM_HEADER):-len(PEM_FOOTER)]
+    return base64.decodebytes(d.encode('ascii'))
@@ -416,11 +416,17 @@ def _generate_posix_vars():
 
 def _init_posix(vars):
     """Initialize the module as appropriate for POSIX systems."""
-    # _sysconfigdata is generated at build time, see _generate_posix_vars()
-    name = _get_sysconfigdata_name()
-    _temp = __import__(name, globals(), locals(), ['build_time_vars'], 0)
-    build_time_vars = _temp.build_time_vars
-    vars.update(build_time_vars)
+    # XXX RustPython: there is no Makefile or _sysconfigdata to read the build
+    # time variables from, so only set the ones libraries commonly look at.
+    vars['LIBDEST'] = get_path('stdlib')
+    vars['BINLIBDEST'] = get_path('platstdlib')
+    vars['INCLUDEPY'] = get_path('include')
+    vars['EXT_SUFFIX'] = None
+    vars['SOABI'] = None
+    vars['EXE'] = ''
+    vars['VERSION'] = _PY_VERSION_SHORT
+    vars['BINDIR'] = os.path.dirname(_safe_realpath(sys.executable or _PROJECT_BASE))
+    vars['Py_ENABLE_SHARED'] = 0
 
 def _init_non_posix(vars):
     """Initialize the module as appropriate for NT"""
@@ -428,7 +434,8 @@ def _init_non_posix(vars):
     vars['LIBDEST'] = get_path('stdlib')
     vars['BINLIBDEST'] = get_path('platstdlib')
     vars['INCLUDEPY'] = get_path('include')
-    vars['EXT_SUFFIX'] = '.pyd'
+    # XXX RustPython: extension modules can't be loaded
+    vars['EXT_SUFFIX'] = None
     vars['EXE'] = '.exe'
     vars['VERSION'] = _PY_VERSION_SHORT_NO_DOT
     vars['BINDIR'] = os.path.dirname(_safe_realpath(sys.executable))
@@ -576,9 +583,11 @@ def get_config_vars(*args):
 
         # OS X platforms require special customization to handle
         # multi-architecture, multi-os-version installers
-        if sys.platform == 'darwin':
-            import _osx_support
-            _osx_support.customize_config_vars(_CONFIG_VARS)
+        # XXX RustPython: _osx_support isn't available, and there are no
+        # compiler flags to customize
+        # if sys.platform == 'darwin':
+        #     import _osx_support
+        #     _osx_support.customize_config_vars(_CONFIG_VARS)
 
     if args:
         vals = []
@@ -683,11 +692,13 @@ def get_platform():
         m = rel_re.match(release)
         if m:
             release = m.group()
-    elif osname[:6] == "darwin":
-        import _osx_support
-        osname, release, machine = _osx_support.get_platform_osx(
-                                            get_config_vars(),
-                                            osname, release, machine)
+    # XXX RustPython: _osx_support isn't available, so macOS is reported as
+    # darwin-<kernel release>-<machine>
+    # elif osname[:6] == "darwin":
+    #     import _osx_support
+    #     osname, release, machine = _osx_support.get_platform_osx(
+    #                                         get_config_vars(),
+    #                                         osname, release, machine)
 
     return "%s-%s-%s" % (osname, release, machine)
 
@@ -0,0 +1,80 @@
+"""Per-user directories for configuration, cache and data files.
+
+The directories follow the conventions of the platform:
+
+- on Unix, the XDG Base Directory Specification: $XDG_CONFIG_HOME,
+  $XDG_CACHE_HOME and $XDG_DATA_HOME, defaulting to ~/.config, ~/.cache and
+  ~/.local/share;
+- on Windows, the Known Folders RoamingAppData for configuration and
+  LocalAppData for data, found through %APPDATA% and %LOCALAPPDATA%, with the
+  cache in a Cache folder below the data;
+- on macOS, ~/Library/Preferences, ~/Library/Caches and
+  ~/Library/Application Support.
+
+Every function takes an optional application name, which is appended to the
+directory.  The directories are not created.
+"""
+
+import os
+import sys
+
+__all__ = ['user_config_dir', 'user_cache_dir', 'user_data_dir']
+
+
+def _xdg_dir(variable, default):
+    # The specification says relative paths in the variables must be ignored
+    path = os.environ.get(variable, '')
+    if not os.path.isabs(path):
+        path = os.path.expanduser(default)
+    return path
+
+
+def _windows_dir(variable, default):
+    return os.environ.get(variable) or os.path.expanduser(default)
+
+
+_XDG_DIRS = {
+    'config': ('XDG_CONFIG_HOME', '~/.config'),
+    'cache': ('XDG_CACHE_HOME', '~/.cache'),
+    'data': ('XDG_DATA_HOME', '~/.local/share'),
+}
+
+_MACOS_DIRS = {
+    'config': '~/Library/Preferences',
+    'cache': '~/Library/Caches',
+    'data': '~/Library/Application Support',
+}
+
+
+def _user_dir(kind, appname):
+    if os.name == 'nt':
+        if kind == 'config':
+            path = _windows_dir('APPDATA', '~\\AppData\\Roaming')
+        else:
+            path = _windows_dir('LOCALAPPDATA', '~\\AppData\\Local')
+    elif sys.platform == 'darwin':
+        path = os.path.expanduser(_MACOS_DIRS[kind])
+    else:
+        path = _xdg_dir(*_XDG_DIRS[kind])
+    if appname:
+        path = os.path.join(path, appname)
+    if os.name == 'nt' and kind == 'cache':
+        # There is no Known Folder for caches, so they go in a folder of
+        # their own below the data
+        path = os.path.join(path, 'Cache')
+    return os.path.abspath(path)
+
+
+def user_config_dir(appname=None):
+    """Return the directory for the user's configuration files."""
+    return _user_dir('config', appname)
+
+
+def user_cache_dir(appname=None):
+    """Return the directory for the user's non-essential cached files."""
+    return _user_dir('cache', appname)
+
+
+def user_data_dir(appname=None):
+    """Return the directory for the user's data files."""
+    return _user_dir('data', appname)
@@ -6,8 +6,10 @@ extern crate rustpython_vm;
 extern crate test;
 
 use rustpython_compiler::compile;
+use rustpython_vm::bytecode::FrozenModule;
 use rustpython_vm::pyobject::PyResult;
-use rustpython_vm::VirtualMachine;
+use rustpython_vm::{import, PySettings, VirtualMachine};
+use std::collections::HashMap;
 
 #[bench]
 fn bench_tokenization(b: &mut test::Bencher) {
@@ -124,3 +126,86 @@ fn bench_rustpy_mandelbrot(b: &mut test::Bencher) {
         assert!(res.is_ok());
     })
 }
+
+fn bench_rustpy_tight_loop_with(b: &mut test::Bencher, setup: &str) {
+    let source = include_str!("./benchmarks/tight_loop.py");
+
+    let vm = VirtualMachine::default();
+
+    let code = match vm.compile(source, compile::Mode::Exec, "<stdin>".to_string()) {
+        Ok(code) => code,
+        Err(e) => panic!("{:?}", e),
+    };
+    let setup = match vm.compile(setup, compile::Mode::Exec, "<setup>".to_string()) {
+        Ok(code) => code,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    b.iter(|| {
+        let scope = vm.new_scope_with_builtins();
+        vm.run_code_obj(setup.clone(), scope.clone()).unwrap();
+        let res: PyResult = vm.run_code_obj(code.clone(), scope);
+        assert!(res.is_ok());
+    })
+}
+
+#[bench]
+fn bench_rustpy_tight_loop(b: &mut test::Bencher) {
+    bench_rustpy_tight_loop_with(b, "")
+}
+
+#[bench]
+fn bench_rustpy_tight_loop_traced(b: &mut test::Bencher) {
+    // Every line of the loop sends an event to a trace function that ignores it.
+    bench_rustpy_tight_loop_with(
+        b,
+        "import sys\nsys.settrace(lambda frame, event, arg: lambda frame, event, arg: None)\n",
+    )
+}
+
+#[bench]
+fn bench_rustpy_frozen_attributes(b: &mut test::Bencher) {
+    // Two frozen modules with classes that use the same attribute names, so the code of one
+    // sets the attributes that the code of the other gets.
+    let modules = [
+        (
+            "shapes",
+            include_str!("./benchmarks/frozen_attributes/shapes.py"),
+        ),
+        (
+            "vectors",
+            include_str!("./benchmarks/frozen_attributes/vectors.py"),
+        ),
+    ];
+    let modules = modules
+        .iter()
+        .map(|&(name, source)| {
+            let code = compile::compile(source, compile::Mode::Exec, name.to_owned(), 0);
+            let module = FrozenModule {
+                code: code.unwrap().into(),
+                package: false,
+                namespace: false,
+                source: None,
+                source_path: None,
+                resources: HashMap::new(),
+            };
+            (name.to_owned(), module)
+        })
+        .collect();
+    let mut settings = PySettings::default();
+    settings.add_frozen_overwrite(modules);
+    let vm = VirtualMachine::new(settings);
+    import::init_importlib(&vm, false).unwrap();
+
+    let source = include_str!("./benchmarks/frozen_attributes.py");
+    let code = match vm.compile(source, compile::Mode::Exec, "<stdin>".to_string()) {
+        Ok(code) => code,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    b.iter(|| {
+        let scope = vm.new_scope_with_builtins();
+        let res: PyResult = vm.run_code_obj(code.clone(), scope);
+        assert!(res.is_ok());
+    })
+}
@@ -0,0 +1,23 @@
+# Getting and setting instance attributes, which live in the objects' __dict__, and dict items.
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+points = [Point(i, -i) for i in range(10000)]
+total = 0
+for _ in range(5):
+    for point in points:
+        point.x = point.x + point.y
+        total += point.x
+
+table = {}
+for i in range(100000):
+    table[i] = i
+for _ in range(2):
+    for i in range(100000):
+        table[i] = table[i] + 1
+
+print(total, len(table))
@@ -0,0 +1,14 @@
+# Attribute access across two modules that share the attribute names. When they are frozen, the
+# names are interned, so the instance dicts of both classes have the same key objects.
+
+import shapes
+import vectors
+
+point = shapes.Point(0, 0)
+step = vectors.Vector(1, -1)
+total = 0
+for _ in range(10000):
+    point = point.moved(step)
+    total += step.length() + point.x - point.y
+
+print(total)
@@ -0,0 +1,7 @@
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def moved(self, offset):
+        return Point(self.x + offset.x, self.y + offset.y)
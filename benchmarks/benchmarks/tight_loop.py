@@ -0,0 +1,10 @@
+# A loop of cheap instructions, where the per-instruction overhead of the frame loop shows most:
+# line tracing has to cost nothing here while no trace function is set.
+
+total = 0
+i = 0
+while i < 1000000:
+    total += i & 7
+    i += 1
+
+print(total)
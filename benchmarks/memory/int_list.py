@@ -0,0 +1,13 @@
+# How much resident memory a list of a million ints takes. This reads /proc, so it only runs on
+# Linux.
+
+def rss_kib():
+    with open("/proc/self/status") as status:
+        for line in status.read().splitlines():
+            if line.startswith("VmRSS:"):
+                return int(line.split()[1])
+
+
+before = rss_kib()
+ints = list(range(1000000))
+print((rss_kib() - before) // 1024, "MiB")
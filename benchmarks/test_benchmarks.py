@@ -18,6 +18,8 @@ pythons = [
 benchmarks = [
     ['benchmarks/nbody.py'],
     ['benchmarks/mandelbrot.py'],
+    ['benchmarks/attributes.py'],
+    ['benchmarks/tight_loop.py'],
 ]
 
 exe_ids = ['cpython', 'rustpython']
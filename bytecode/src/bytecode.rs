@@ -1,12 +1,36 @@
 //! Implement python as a virtual machine with bytecodes. This module
 //! implements bytecode structure.
 
+use crate::compact;
+use crate::static_code::StaticCodeObject;
 use bitflags::bitflags;
 use num_bigint::BigInt;
 use num_complex::Complex64;
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize, Serializer};
+use siphasher::sip128::{Hasher128, SipHasher13};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+
+/// The version of the serializations produced by `CodeObject::to_bytes` and `compact::encode`.
+/// Bump this whenever `CodeObject`, `Instruction` or `Constant` change in a way that makes
+/// previously serialized bytecode unreadable.
+pub const FORMAT_VERSION: u32 = 11;
+
+/// The magic number `CodeObject::to_bytes` starts with. It's followed by the `FORMAT_VERSION`
+/// and the flags of the serialization, both little endian `u32`s, and then the code object.
+pub const BYTES_MAGIC: [u8; 4] = *b"RPBC";
+
+const BYTES_HEADER_LEN: usize = 12;
+
+/// The flag of `CodeObject::to_bytes` for a code object compressed with lz4.
+const BYTES_LZ4: u32 = 0x1;
+
+/// The entry of `CodeObject::cache_slots` for an instruction without an inline cache.
+pub const NO_CACHE_SLOT: u32 = std::u32::MAX;
 
 /// Sourcode location.
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
@@ -29,22 +53,240 @@ impl Location {
     }
 }
 
+/// The line numbers of the instructions of a code object, encoded like CPython's `co_lnotab`:
+/// for every instruction where the line changes, a byte with the number of instructions since
+/// the previous change and a signed byte with the difference in line number. Lines can go back,
+/// e.g. to the decorators after a function definition. A larger difference is split over
+/// several pairs. Line 0 is for the instructions that don't have a line of their own.
+///
+/// The column of each instruction, which changes far more often, is kept in a byte per
+/// instruction beside it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LineTable {
+    deltas: Cow<'static, [u8]>,
+    /// The number of instructions.
+    len: usize,
+    /// The column of every instruction, counted from 1, with 0 for an unknown column or one
+    /// that doesn't fit in a byte. Empty when no instruction has a known column.
+    columns: Cow<'static, [u8]>,
+}
+
+/// The instructions `start..end` of a code object, which are all on `line`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineRange {
+    pub start: usize,
+    pub end: usize,
+    pub line: Option<usize>,
+}
+
+impl LineRange {
+    pub fn contains(&self, index: usize) -> bool {
+        self.start <= index && index < self.end
+    }
+}
+
+impl LineTable {
+    /// The table of instructions on `lines`, one for each instruction.
+    pub fn from_lines<I: IntoIterator<Item = Option<usize>>>(lines: I) -> Self {
+        let mut deltas = vec![];
+        let (mut last_index, mut last_line) = (0, 0);
+        let mut len = 0;
+        for (index, line) in lines.into_iter().enumerate() {
+            let line = line.unwrap_or(0);
+            if line != last_line {
+                let mut index_delta = index - last_index;
+                while index_delta > 255 {
+                    deltas.extend_from_slice(&[255, 0]);
+                    index_delta -= 255;
+                }
+                let mut line_delta = line as i64 - last_line as i64;
+                loop {
+                    let step = if line_delta < 0 {
+                        line_delta.max(-128)
+                    } else {
+                        line_delta.min(127)
+                    };
+                    deltas.extend_from_slice(&[index_delta as u8, step as i8 as u8]);
+                    index_delta = 0;
+                    line_delta -= step;
+                    if line_delta == 0 {
+                        break;
+                    }
+                }
+                last_index = index;
+                last_line = line;
+            }
+            len = index + 1;
+        }
+        LineTable {
+            deltas: deltas.into(),
+            len,
+            columns: Cow::Borrowed(&[]),
+        }
+    }
+
+    /// The table with the parts of `raw_parts`, borrowed from static data.
+    pub const fn from_static(deltas: &'static [u8], len: usize, columns: &'static [u8]) -> Self {
+        LineTable {
+            deltas: Cow::Borrowed(deltas),
+            len,
+            columns: Cow::Borrowed(columns),
+        }
+    }
+
+    /// The encoded line deltas, the number of instructions and the columns.
+    pub fn raw_parts(&self) -> (&[u8], usize, &[u8]) {
+        (&self.deltas, self.len, &self.columns)
+    }
+
+    /// The table of instructions at `locations`, one for each instruction. A row of 0 is for an
+    /// instruction without a line, and a column of 0 for one without a column.
+    pub fn from_locations<I: IntoIterator<Item = Location>>(locations: I) -> Self {
+        let (lines, columns): (Vec<_>, Vec<_>) = locations
+            .into_iter()
+            .map(|location| {
+                let line = Some(location.row()).filter(|&row| row != 0);
+                let column = u8::try_from(location.column()).unwrap_or(0);
+                (line, column)
+            })
+            .unzip();
+        let mut table = LineTable::from_lines(lines);
+        if columns.iter().any(|&column| column != 0) {
+            table.columns = columns.into();
+        }
+        table
+    }
+
+    /// The number of instructions.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The ranges of instructions on the same line, in order.
+    pub fn ranges(&self) -> LineRanges<'_> {
+        LineRanges {
+            deltas: self.deltas.chunks(2).peekable(),
+            len: self.len,
+            index: 0,
+            line: 0,
+        }
+    }
+
+    /// The range of instructions on the same line as the instruction at `index`.
+    pub fn range(&self, index: usize) -> Option<LineRange> {
+        self.ranges().find(|range| range.contains(index))
+    }
+
+    /// The line of the instruction at `index`, if it has one.
+    pub fn line(&self, index: usize) -> Option<usize> {
+        self.range(index).and_then(|range| range.line)
+    }
+
+    /// The line of every instruction.
+    pub fn lines<'a>(&'a self) -> impl Iterator<Item = Option<usize>> + 'a {
+        self.ranges()
+            .flat_map(|range| (range.start..range.end).map(move |_| range.line))
+    }
+
+    /// The column of the instruction at `index`, counted from 1, if it's known.
+    pub fn column(&self, index: usize) -> Option<usize> {
+        self.columns
+            .get(index)
+            .filter(|&&column| column != 0)
+            .map(|&column| usize::from(column))
+    }
+
+    /// The location of every instruction, with a row or column of 0 where it isn't known, as
+    /// `from_locations` takes them.
+    pub fn locations<'a>(&'a self) -> impl Iterator<Item = Location> + 'a {
+        self.lines().enumerate().map(move |(index, line)| {
+            Location::new(line.unwrap_or(0), self.column(index).unwrap_or(0))
+        })
+    }
+}
+
+pub struct LineRanges<'a> {
+    deltas: std::iter::Peekable<std::slice::Chunks<'a, u8>>,
+    len: usize,
+    /// The instruction the next range starts at.
+    index: usize,
+    /// The line of the instruction at `index`, as far as the deltas have been read.
+    line: usize,
+}
+
+impl LineRanges<'_> {
+    /// Apply the deltas that change the line at `index`.
+    fn read_line_deltas(&mut self) {
+        while let Some(&&[0, line_delta]) = self.deltas.peek() {
+            self.line = (self.line as i64 + i64::from(line_delta as i8)) as usize;
+            self.deltas.next();
+        }
+    }
+}
+
+impl Iterator for LineRanges<'_> {
+    type Item = LineRange;
+
+    fn next(&mut self) -> Option<LineRange> {
+        if self.index >= self.len {
+            return None;
+        }
+        self.read_line_deltas();
+        let (start, line) = (self.index, self.line);
+        while self.index < self.len && self.line == line {
+            match self.deltas.next() {
+                Some(&[index_delta, line_delta]) => {
+                    self.index += usize::from(index_delta);
+                    self.line = (self.line as i64 + i64::from(line_delta as i8)) as usize;
+                    self.read_line_deltas();
+                }
+                _ => self.index = self.len,
+            }
+        }
+        let end = self.index.min(self.len);
+        self.index = end;
+        Some(LineRange {
+            start,
+            end,
+            line: if line == 0 { None } else { Some(line) },
+        })
+    }
+}
+
 /// Primary container of a single code object. Each python function has
 /// a codeobject. Also a module has a codeobject.
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct CodeObject {
-    pub instructions: Vec<Instruction>,
+    /// Borrowed when the code object comes from a `StaticCodeObject`, like the other large
+    /// tables the VM reads as they are.
+    pub instructions: Cow<'static, [Instruction]>,
     /// Jump targets.
+    #[serde(serialize_with = "serialize_label_map")]
     pub label_map: HashMap<Label, usize>,
-    pub locations: Vec<Location>,
+    pub line_table: LineTable,
     pub flags: CodeFlags,
-    pub arg_names: Vec<String>, // Names of positional arguments
-    pub varargs: Varargs,       // *args or *
+    pub posonlyarg_count: usize, // Number of positional-only arguments
+    pub arg_names: Vec<String>,  // Names of positional arguments
+    pub varargs: Varargs,        // *args or *
     pub kwonlyarg_names: Vec<String>,
     pub varkeywords: Varargs, // **kwargs or **
     pub source_path: String,
     pub first_line_number: usize,
     pub obj_name: String, // Name of the object that created this code object
+    /// The constants that `LoadConst` loads by index, each of them once.
+    pub constants: Vec<Constant>,
+    /// The names of variables, attributes and modules the instructions refer to by index, each
+    /// of them once.
+    pub names: Vec<String>,
+    /// The inline cache slot of each instruction, numbered from 0 in the order of the
+    /// instructions that `Instruction::has_inline_cache`, and `NO_CACHE_SLOT` for the others.
+    /// Only the numbering is stored here; the caches themselves belong to the VM and start
+    /// out empty whenever a code object is loaded.
+    pub cache_slots: Cow<'static, [u32]>,
 }
 
 bitflags! {
@@ -56,6 +298,11 @@ bitflags! {
         const NEW_LOCALS = 0x08;
         const IS_GENERATOR = 0x10;
         const IS_COROUTINE = 0x20;
+        /// Compiled in eval mode: the code returns the value of its expression.
+        const IS_EXPRESSION = 0x40;
+        /// Compiled under `from __future__ import annotations`, which code compiled by this
+        /// code with `compile`, `exec` or `eval` inherits.
+        const FUTURE_ANNOTATIONS = 0x80;
     }
 }
 
@@ -65,11 +312,17 @@ impl Default for CodeFlags {
     }
 }
 
-#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Serialize, Debug, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Label(usize);
 
+/// The index of a constant in `CodeObject::constants`.
+pub type ConstIdx = usize;
+
+/// The index of a name in `CodeObject::names`.
+pub type NameIdx = usize;
+
 impl Label {
-    pub fn new(label: usize) -> Self {
+    pub const fn new(label: usize) -> Self {
         Label(label)
     }
 }
@@ -105,36 +358,36 @@ pub enum ConversionFlag {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Instruction {
     Import {
-        name: Option<String>,
-        symbols: Vec<String>,
+        name_idx: Option<NameIdx>,
+        symbols_idx: Cow<'static, [NameIdx]>,
         level: usize,
     },
     ImportStar,
     ImportFrom {
-        name: String,
+        idx: NameIdx,
     },
     LoadName {
-        name: String,
+        idx: NameIdx,
         scope: NameScope,
     },
     StoreName {
-        name: String,
+        idx: NameIdx,
         scope: NameScope,
     },
     DeleteName {
-        name: String,
+        idx: NameIdx,
     },
     Subscript,
     StoreSubscript,
     DeleteSubscript,
     StoreAttr {
-        name: String,
+        idx: NameIdx,
     },
     DeleteAttr {
-        name: String,
+        idx: NameIdx,
     },
     LoadConst {
-        value: Constant,
+        idx: ConstIdx,
     },
     UnaryOperation {
         op: UnaryOperator,
@@ -144,7 +397,7 @@ pub enum Instruction {
         inplace: bool,
     },
     LoadAttr {
-        name: String,
+        idx: NameIdx,
     },
     CompareOperation {
         op: ComparisonOperator,
@@ -155,6 +408,9 @@ pub enum Instruction {
     },
     Duplicate,
     GetIter,
+    /// Get the iterator of `yield from`, which is the iterable itself for a coroutine awaited in
+    /// a coroutine.
+    GetYieldFromIter,
     Continue,
     Break,
     Jump {
@@ -188,6 +444,9 @@ pub enum Instruction {
     ReturnValue,
     YieldValue,
     YieldFrom,
+    /// Make an empty `__annotations__` in the locals, unless there is one. Module and class
+    /// bodies with annotations start with this.
+    SetupAnnotations,
     SetupLoop {
         start: Label,
         end: Label,
@@ -230,6 +489,7 @@ pub enum Instruction {
     BuildTuple {
         size: usize,
         unpack: bool,
+        for_call: bool,
     },
     BuildList {
         size: usize,
@@ -265,9 +525,9 @@ pub enum Instruction {
         before: usize,
         after: usize,
     },
+    /// Format the value below the format spec on the stack.
     FormatValue {
         conversion: Option<ConversionFlag>,
-        spec: String,
     },
     PopException,
     Reverse {
@@ -291,7 +551,7 @@ pub enum CallType {
     Ex(bool),
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Constant {
     Integer { value: BigInt },
     Float { value: f64 },
@@ -305,6 +565,57 @@ pub enum Constant {
     Ellipsis,
 }
 
+/// Floats are compared by their bit pattern, like CPython does for code object constants, so
+/// that `0.0` and `-0.0` are not interchangeable and equal constants serialize identically.
+impl PartialEq for Constant {
+    fn eq(&self, other: &Constant) -> bool {
+        use Constant::*;
+        match (self, other) {
+            (Integer { value: a }, Integer { value: b }) => a == b,
+            (Float { value: a }, Float { value: b }) => a.to_bits() == b.to_bits(),
+            (Complex { value: a }, Complex { value: b }) => {
+                a.re.to_bits() == b.re.to_bits() && a.im.to_bits() == b.im.to_bits()
+            }
+            (Boolean { value: a }, Boolean { value: b }) => a == b,
+            (String { value: a }, String { value: b }) => a == b,
+            (Bytes { value: a }, Bytes { value: b }) => a == b,
+            (Code { code: a }, Code { code: b }) => a == b,
+            (Tuple { elements: a }, Tuple { elements: b }) => a == b,
+            (None, None) | (Ellipsis, Ellipsis) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Comparing floats by their bits makes every constant equal to itself.
+impl Eq for Constant {}
+
+/// Hashes the same parts that `eq` compares, but for code objects, of which only the name and
+/// line are hashed.
+impl Hash for Constant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        use Constant::*;
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Integer { value } => value.hash(state),
+            Float { value } => value.to_bits().hash(state),
+            Complex { value } => {
+                value.re.to_bits().hash(state);
+                value.im.to_bits().hash(state);
+            }
+            Boolean { value } => value.hash(state),
+            String { value } => value.hash(state),
+            Bytes { value } => value.hash(state),
+            Code { code } => {
+                code.obj_name.hash(state);
+                code.first_line_number.hash(state);
+            }
+            Tuple { elements } => elements.hash(state),
+            None | Ellipsis => {}
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ComparisonOperator {
     Greater,
@@ -364,6 +675,7 @@ impl CodeObject {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         flags: CodeFlags,
+        posonlyarg_count: usize,
         arg_names: Vec<String>,
         varargs: Varargs,
         kwonlyarg_names: Vec<String>,
@@ -373,10 +685,11 @@ impl CodeObject {
         obj_name: String,
     ) -> CodeObject {
         CodeObject {
-            instructions: Vec::new(),
+            instructions: Cow::Borrowed(&[]),
             label_map: HashMap::new(),
-            locations: Vec::new(),
+            line_table: LineTable::default(),
             flags,
+            posonlyarg_count,
             arg_names,
             varargs,
             kwonlyarg_names,
@@ -384,29 +697,157 @@ impl CodeObject {
             source_path,
             first_line_number,
             obj_name,
+            constants: Vec::new(),
+            names: Vec::new(),
+            cache_slots: Cow::Borrowed(&[]),
         }
     }
 
-    /// Load a code object from bytes
-    pub fn from_bytes(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
-        let data = lz4_compress::decompress(data)?;
-        bincode::deserialize::<Self>(&data).map_err(|e| e.into())
+    /// The index of `constant` in `constants`, where it's added unless there's an equal one.
+    pub fn add_constant(&mut self, constant: Constant) -> ConstIdx {
+        match self.constants.iter().position(|other| *other == constant) {
+            Some(idx) => idx,
+            None => {
+                self.constants.push(constant);
+                self.constants.len() - 1
+            }
+        }
+    }
+
+    /// The index of `name` in `names`, where it's added unless it's there already.
+    pub fn add_name(&mut self, name: &str) -> NameIdx {
+        match self.names.iter().position(|other| other == name) {
+            Some(idx) => idx,
+            None => {
+                self.names.push(name.to_owned());
+                self.names.len() - 1
+            }
+        }
     }
 
-    /// Serialize this bytecode to bytes.
+    /// Remove the constants that no instruction loads, like those of the instructions that were
+    /// optimized away, keeping the others in order.
+    pub fn remove_unused_constants(&mut self) {
+        let mut used = vec![false; self.constants.len()];
+        for instruction in self.instructions.iter() {
+            if let Instruction::LoadConst { idx } = instruction {
+                used[*idx] = true;
+            }
+        }
+        let mut new_indices = Vec::with_capacity(used.len());
+        let mut count = 0;
+        for &used in &used {
+            new_indices.push(count);
+            if used {
+                count += 1;
+            }
+        }
+        for instruction in self.instructions.to_mut() {
+            if let Instruction::LoadConst { idx } = instruction {
+                *idx = new_indices[*idx];
+            }
+        }
+        let mut used = used.into_iter();
+        self.constants.retain(|_| used.next().unwrap());
+    }
+
+    /// The number of inline cache slots the instructions use.
+    pub fn cache_slot_count(&self) -> usize {
+        self.cache_slots
+            .iter()
+            .filter(|&&slot| slot != NO_CACHE_SLOT)
+            .count()
+    }
+
+    /// Load a code object from the bytes of `to_bytes`, which must have been written with the
+    /// same `FORMAT_VERSION`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, DecodeError> {
+        if !data.starts_with(&BYTES_MAGIC) {
+            return Err(DecodeError::NotBytecode);
+        }
+        if data.len() < BYTES_HEADER_LEN {
+            return Err(DecodeError::Corrupt("truncated header".to_owned()));
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes(<[u8; 4]>::try_from(&data[offset..offset + 4]).unwrap())
+        };
+        let version = read_u32(4);
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::FormatVersion(version));
+        }
+        let flags = read_u32(8);
+        if flags & !BYTES_LZ4 != 0 {
+            return Err(DecodeError::UnknownFlags(flags));
+        }
+        let data = &data[BYTES_HEADER_LEN..];
+        let data = if flags & BYTES_LZ4 != 0 {
+            let data = lz4_compress::decompress(data)
+                .map_err(|err| DecodeError::Corrupt(err.to_string()))?;
+            Cow::Owned(data)
+        } else {
+            Cow::Borrowed(data)
+        };
+        bincode::deserialize::<Self>(&data).map_err(|err| DecodeError::Corrupt(err.to_string()))
+    }
+
+    /// Serialize this bytecode to bytes, in a format that only this `FORMAT_VERSION` reads.
     pub fn to_bytes(&self) -> Vec<u8> {
         let data = bincode::serialize(&self).expect("Code object must be serializable");
-        lz4_compress::compress(&data)
+        let mut bytes = BYTES_MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&BYTES_LZ4.to_le_bytes());
+        bytes.extend(lz4_compress::compress(&data));
+        bytes
     }
 
-    pub fn get_constants(&self) -> impl Iterator<Item = &Constant> {
-        self.instructions.iter().filter_map(|x| {
-            if let Instruction::LoadConst { value } = x {
-                Some(value)
-            } else {
-                None
-            }
-        })
+    /// Load a code object from the bytes of `to_compressed_bytes`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_compressed_bytes(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        bincode::deserialize_from(flate2::read::DeflateDecoder::new(data)).map_err(|e| e.into())
+    }
+
+    /// Serialize this bytecode to bytes that are deflated rather than lz4 compressed, which makes
+    /// them a lot smaller than those of `to_bytes`, but slower to load.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn to_compressed_bytes(&self) -> Vec<u8> {
+        use std::io::Write;
+        let data = bincode::serialize(&self).expect("Code object must be serializable");
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Compute a 128-bit digest of the logical content of this code object, including nested
+    /// code objects, suitable as a key for on-disk bytecode caches.
+    ///
+    /// The digest is computed over the same serialization as `to_bytes`, with jump targets in
+    /// sorted order, so it does not depend on `HashMap` iteration order, the host platform or
+    /// its endianness. It is stable for as long as the bytecode format is unchanged; any change
+    /// to `Instruction`, `Constant` or `CodeObject` may change every digest. Code objects that
+    /// compare equal have equal digests.
+    ///
+    /// When `include_source_info` is false, the source path and line numbers are left out, so that moving code around in a file does not change its digest.
+    pub fn content_hash(&self, include_source_info: bool) -> u128 {
+        let mut hasher = SipHasher13::new_with_keys(0, 0);
+        let result = if include_source_info {
+            bincode::serialize_into(HashWriter(&mut hasher), self)
+        } else {
+            let mut code = self.clone();
+            code.strip_source_info();
+            bincode::serialize_into(HashWriter(&mut hasher), &code)
+        };
+        result.expect("Code object must be serializable");
+        u128::from_le_bytes(hasher.finish128().as_bytes())
+    }
+
+    fn strip_source_info(&mut self) {
+        self.source_path = String::new();
+        self.first_line_number = 0;
+        self.line_table = LineTable::default();
+        for constant in &mut self.constants {
+            constant.strip_source_info();
+        }
     }
 
     fn display_inner(
@@ -416,6 +857,7 @@ impl CodeObject {
         level: usize,
     ) -> fmt::Result {
         let label_targets: HashSet<&usize> = self.label_map.values().collect();
+        let mut ranges = self.line_table.ranges().peekable();
         for (offset, instruction) in self.instructions.iter().enumerate() {
             let arrow = if label_targets.contains(&offset) {
                 ">>"
@@ -425,8 +867,19 @@ impl CodeObject {
             for _ in 0..level {
                 write!(f, "          ")?;
             }
+            // The line number, before the first instruction on it
+            match ranges.peek() {
+                Some(&range) if range.start == offset => {
+                    match range.line {
+                        Some(line) => write!(f, "{:4} ", line)?,
+                        None => write!(f, "   - ")?,
+                    }
+                    ranges.next();
+                }
+                _ => write!(f, "     ")?,
+            }
             write!(f, "{} {:5} ", arrow, offset)?;
-            instruction.fmt_dis(f, &self.label_map, expand_codeobjects, level)?;
+            instruction.fmt_dis(f, self, expand_codeobjects, level)?;
         }
         Ok(())
     }
@@ -449,13 +902,29 @@ impl fmt::Display for CodeObject {
 }
 
 impl Instruction {
+    /// Whether the VM keeps an inline cache for this instruction: looking up a global,
+    /// and getting or setting an attribute.
+    pub fn has_inline_cache(&self) -> bool {
+        match self {
+            Instruction::LoadName {
+                scope: NameScope::Global,
+                ..
+            }
+            | Instruction::LoadAttr { .. }
+            | Instruction::StoreAttr { .. } => true,
+            _ => false,
+        }
+    }
+
     fn fmt_dis(
         &self,
         f: &mut fmt::Formatter,
-        label_map: &HashMap<Label, usize>,
+        code: &CodeObject,
         expand_codeobjects: bool,
         level: usize,
     ) -> fmt::Result {
+        let label_map = &code.label_map;
+        let name = |idx: &NameIdx| &code.names[*idx];
         macro_rules! w {
             ($variant:ident) => {
                 write!(f, "{:20}\n", stringify!($variant))
@@ -480,41 +949,42 @@ impl Instruction {
 
         match self {
             Import {
-                name,
-                symbols,
+                name_idx,
+                symbols_idx,
                 level,
             } => w!(
                 Import,
-                format!("{:?}", name),
-                format!("{:?}", symbols),
+                format!("{:?}", name_idx.as_ref().map(name)),
+                format!("{:?}", symbols_idx.iter().map(name).collect::<Vec<_>>()),
                 level
             ),
             ImportStar => w!(ImportStar),
-            ImportFrom { name } => w!(ImportFrom, name),
-            LoadName { name, scope } => w!(LoadName, name, format!("{:?}", scope)),
-            StoreName { name, scope } => w!(StoreName, name, format!("{:?}", scope)),
-            DeleteName { name } => w!(DeleteName, name),
+            ImportFrom { idx } => w!(ImportFrom, name(idx)),
+            LoadName { idx, scope } => w!(LoadName, name(idx), format!("{:?}", scope)),
+            StoreName { idx, scope } => w!(StoreName, name(idx), format!("{:?}", scope)),
+            DeleteName { idx } => w!(DeleteName, name(idx)),
             Subscript => w!(Subscript),
             StoreSubscript => w!(StoreSubscript),
             DeleteSubscript => w!(DeleteSubscript),
-            StoreAttr { name } => w!(StoreAttr, name),
-            DeleteAttr { name } => w!(DeleteAttr, name),
-            LoadConst { value } => match value {
+            StoreAttr { idx } => w!(StoreAttr, name(idx)),
+            DeleteAttr { idx } => w!(DeleteAttr, name(idx)),
+            LoadConst { idx } => match &code.constants[*idx] {
                 Constant::Code { code } if expand_codeobjects => {
                     writeln!(f, "LoadConst ({:?}):", code)?;
                     code.display_inner(f, true, level + 1)?;
                     Ok(())
                 }
-                _ => w!(LoadConst, value),
+                value => w!(LoadConst, value),
             },
             UnaryOperation { op } => w!(UnaryOperation, format!("{:?}", op)),
             BinaryOperation { op, inplace } => w!(BinaryOperation, format!("{:?}", op), inplace),
-            LoadAttr { name } => w!(LoadAttr, name),
+            LoadAttr { idx } => w!(LoadAttr, name(idx)),
             CompareOperation { op } => w!(CompareOperation, format!("{:?}", op)),
             Pop => w!(Pop),
             Rotate { amount } => w!(Rotate, amount),
             Duplicate => w!(Duplicate),
             GetIter => w!(GetIter),
+            GetYieldFromIter => w!(GetYieldFromIter),
             Continue => w!(Continue),
             Break => w!(Break),
             Jump { target } => w!(Jump, label_map[target]),
@@ -528,6 +998,7 @@ impl Instruction {
             ReturnValue => w!(ReturnValue),
             YieldValue => w!(YieldValue),
             YieldFrom => w!(YieldFrom),
+            SetupAnnotations => w!(SetupAnnotations),
             SetupLoop { start, end } => w!(SetupLoop, label_map[start], label_map[end]),
             SetupExcept { handler } => w!(SetupExcept, label_map[handler]),
             SetupFinally { handler } => w!(SetupFinally, label_map[handler]),
@@ -541,7 +1012,11 @@ impl Instruction {
             PopBlock => w!(PopBlock),
             Raise { argc } => w!(Raise, argc),
             BuildString { size } => w!(BuildString, size),
-            BuildTuple { size, unpack } => w!(BuildTuple, size, unpack),
+            BuildTuple {
+                size,
+                unpack,
+                for_call,
+            } => w!(BuildTuple, size, unpack, for_call),
             BuildList { size, unpack } => w!(BuildList, size, unpack),
             BuildSet { size, unpack } => w!(BuildSet, size, unpack),
             BuildMap {
@@ -557,7 +1032,7 @@ impl Instruction {
             LoadBuildClass => w!(LoadBuildClass),
             UnpackSequence { size } => w!(UnpackSequence, size),
             UnpackEx { before, after } => w!(UnpackEx, before, after),
-            FormatValue { spec, .. } => w!(FormatValue, spec), // TODO: write conversion
+            FormatValue { conversion } => w!(FormatValue, format!("{:?}", conversion)),
             PopException => w!(PopException),
             Reverse { amount } => w!(Reverse, amount),
             GetAwaitable => w!(GetAwaitable),
@@ -567,6 +1042,43 @@ impl Instruction {
     }
 }
 
+impl Constant {
+    fn strip_source_info(&mut self) {
+        match self {
+            Constant::Code { code } => code.strip_source_info(),
+            Constant::Tuple { elements } => {
+                for element in elements {
+                    element.strip_source_info();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn serialize_label_map<S: Serializer>(
+    label_map: &HashMap<Label, usize>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    // Serialize in a fixed order so the output is identical for equal code objects.
+    let sorted: BTreeMap<_, _> = label_map.iter().collect();
+    sorted.serialize(serializer)
+}
+
+/// Adapter to feed serialized bytes straight into a hasher.
+struct HashWriter<'a, H: Hasher>(&'a mut H);
+
+impl<H: Hasher> io::Write for HashWriter<'_, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl fmt::Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -602,7 +1114,395 @@ impl fmt::Debug for CodeObject {
     }
 }
 
+/// Why `CodeObject::from_bytes` couldn't load a code object.
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    /// The data doesn't start with `BYTES_MAGIC`, so it's not from `CodeObject::to_bytes`.
+    NotBytecode,
+    /// The code object was serialized with another version of the bytecode format.
+    FormatVersion(u32),
+    /// The serialization has flags this version of the format doesn't know.
+    UnknownFlags(u32),
+    /// The data is cut short or can't be decompressed or deserialized.
+    Corrupt(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::NotBytecode => write!(f, "not a serialized code object"),
+            DecodeError::FormatVersion(version) => write!(
+                f,
+                "the code object has bytecode format version {}, but this build of RustPython \
+                 uses version {}",
+                version, FORMAT_VERSION
+            ),
+            DecodeError::UnknownFlags(flags) => {
+                write!(f, "unknown serialization flags {:#x}", flags)
+            }
+            DecodeError::Corrupt(reason) => write!(f, "corrupt code object: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The code of a frozen module. `py_compile_bytecode!` lays it out as static data, which the
+/// code object of the module borrows when it's imported, or, with `compact = true` or
+/// `compress = true`, leaves it serialized in the binary until then.
+#[derive(Clone)]
+pub enum FrozenCode {
+    Code(Box<CodeObject>),
+    Static(&'static StaticCodeObject),
+    /// The bytes of `CodeObject::to_bytes`.
+    Serialized(&'static [u8]),
+    /// The bytes of `CodeObject::to_compressed_bytes`. Decompressing them needs zlib, so this
+    /// isn't available on wasm.
+    #[cfg(not(target_arch = "wasm32"))]
+    Compressed(&'static [u8]),
+    /// The bytes of `compact::encode`, with those of the string table it was encoded with,
+    /// which is shared by the modules of a `py_compile_bytecode!`.
+    Compact {
+        code: &'static [u8],
+        strings: &'static [u8],
+    },
+}
+
+impl FrozenCode {
+    /// The code object, deserialized if it has to be. This is done anew every time, so it
+    /// should only be called when the module is about to be run.
+    pub fn try_decode(&self) -> Result<CodeObject, DecodeError> {
+        match self {
+            FrozenCode::Code(code) => Ok(CodeObject::clone(code)),
+            FrozenCode::Static(code) => Ok(code.to_code_object()),
+            FrozenCode::Serialized(bytes) => CodeObject::from_bytes(bytes),
+            #[cfg(not(target_arch = "wasm32"))]
+            FrozenCode::Compressed(bytes) => CodeObject::from_compressed_bytes(bytes)
+                .map_err(|err| DecodeError::Corrupt(err.to_string())),
+            FrozenCode::Compact { code, strings } => compact::decode(code, strings),
+        }
+    }
+
+    /// Like `try_decode`, for code that is known to be of this build, like the one
+    /// `py_compile_bytecode!` put in the binary.
+    pub fn decode(&self) -> CodeObject {
+        self.try_decode()
+            .unwrap_or_else(|err| panic!("Deserializing frozen CodeObject failed: {}", err))
+    }
+}
+
+impl From<CodeObject> for FrozenCode {
+    fn from(code: CodeObject) -> Self {
+        FrozenCode::Code(Box::new(code))
+    }
+}
+
+/// The source of a frozen module, embedded by `py_compile_bytecode!` with `embed_source = true`
+/// for tracebacks and `linecache` to show the lines of.
+#[derive(Clone)]
+pub enum FrozenSource {
+    Text(Cow<'static, str>),
+    /// The bytes of `FrozenSource::compress`, which needs zlib like `FrozenCode::Compressed`.
+    #[cfg(not(target_arch = "wasm32"))]
+    Compressed(&'static [u8]),
+}
+
+impl FrozenSource {
+    /// The source text, decompressed if it has to be.
+    pub fn decode(&self) -> Cow<'static, str> {
+        match self {
+            FrozenSource::Text(text) => text.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            FrozenSource::Compressed(bytes) => {
+                use std::io::Read;
+                let mut text = String::new();
+                flate2::read::DeflateDecoder::new(*bytes)
+                    .read_to_string(&mut text)
+                    .expect("Decompressing frozen source failed");
+                Cow::Owned(text)
+            }
+        }
+    }
+
+    /// Deflate `text` for `FrozenSource::Compressed`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn compress(text: &str) -> Vec<u8> {
+        use std::io::Write;
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+}
+
+#[derive(Clone)]
 pub struct FrozenModule {
-    pub code: CodeObject,
+    pub code: FrozenCode,
     pub package: bool,
+    /// Whether this is a namespace package (PEP 420), for a directory of modules without an
+    /// `__init__.py`. It is a `package` too, and its code does nothing.
+    pub namespace: bool,
+    /// The source the module was compiled from, if it was embedded.
+    pub source: Option<FrozenSource>,
+    /// The path of the file the module was frozen from, relative to the directory it was found
+    /// in and with `/` separators, or just the file name for a single file. The frozen importer
+    /// puts it into the `__file__` of the module as `<frozen {path}>`.
+    pub source_path: Option<String>,
+    /// The data files of a package, by file name, that `importlib.resources` reads through the
+    /// frozen importer. `py_compile_bytecode!` embeds them with `include_resources`.
+    pub resources: HashMap<String, Cow<'static, [u8]>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_code() -> CodeObject {
+        let mut code = CodeObject::new(
+            CodeFlags::NEW_LOCALS,
+            0,
+            vec!["x".to_string()],
+            Varargs::None,
+            vec![],
+            Varargs::None,
+            "sample.py".to_string(),
+            1,
+            "f".to_string(),
+        );
+        code.constants = vec![Constant::Float { value: -0.0 }, Constant::None];
+        code.instructions = vec![
+            LoadConst { idx: 0 },
+            JumpIfFalse {
+                target: Label::new(0),
+            },
+            LoadConst { idx: 1 },
+            ReturnValue,
+        ]
+        .into();
+        code.label_map.insert(Label::new(0), 2);
+        code.line_table = LineTable::from_lines(vec![Some(1); 4]);
+        code
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        // These values must be the same on every platform; if this test fails after a change to
+        // the bytecode format, update them.
+        let code = sample_code();
+        assert_eq!(
+            code.content_hash(true),
+            0x310c_1e08_544d_17e4_953d_8929_6185_2e65
+        );
+        assert_eq!(
+            code.content_hash(false),
+            0xd678_b152_3468_dad8_9455_3ad1_cb1e_529b
+        );
+    }
+
+    #[test]
+    fn test_line_table_round_trip() {
+        let corpus: Vec<Vec<Option<usize>>> = vec![
+            vec![],
+            vec![None, None],
+            vec![Some(1), Some(1), Some(2), Some(2), Some(2), Some(3)],
+            // Decorators are applied after the function is made, on the lines before it.
+            vec![Some(3), Some(4), Some(2), Some(1), Some(4)],
+            // A function starting far into a file, and lines with no line number in between.
+            vec![Some(5000), None, Some(5001), Some(200), None, None, Some(2)],
+            // Long lines, which take several pairs for the instruction count.
+            std::iter::repeat(Some(7))
+                .take(600)
+                .chain(vec![Some(8); 300])
+                .collect(),
+            (0..1000).map(|i| Some(i * 300 + 1)).collect(),
+        ];
+        for lines in corpus {
+            let table = LineTable::from_lines(lines.clone());
+            assert_eq!(table.len(), lines.len());
+            assert_eq!(table.lines().collect::<Vec<_>>(), lines);
+            for (index, &line) in lines.iter().enumerate() {
+                assert_eq!(table.line(index), line);
+            }
+            assert_eq!(table.line(lines.len()), None);
+
+            let mut code = sample_code();
+            code.line_table = table;
+            let decoded = CodeObject::from_bytes(&code.to_bytes()).unwrap();
+            assert_eq!(decoded.line_table, code.line_table);
+        }
+    }
+
+    #[test]
+    fn test_line_table_columns() {
+        let locations = vec![
+            Location::new(1, 1),
+            Location::new(1, 5),
+            Location::new(0, 0),
+            Location::new(2, 300),
+            Location::new(3, 9),
+        ];
+        let table = LineTable::from_locations(locations.clone());
+        assert_eq!(
+            table.lines().collect::<Vec<_>>(),
+            [Some(1), Some(1), None, Some(2), Some(3)]
+        );
+        let columns: Vec<_> = (0..locations.len()).map(|i| table.column(i)).collect();
+        // A column too far to the right for the table is left out.
+        assert_eq!(columns, [Some(1), Some(5), None, None, Some(9)]);
+        assert_eq!(
+            table.locations().collect::<Vec<_>>(),
+            [
+                Location::new(1, 1),
+                Location::new(1, 5),
+                Location::new(0, 0),
+                Location::new(2, 0),
+                Location::new(3, 9),
+            ]
+        );
+        assert_eq!(LineTable::from_locations(table.locations()), table);
+
+        // Without any column, the table is as small as one of lines only.
+        let table = LineTable::from_locations(vec![Location::new(1, 0); 3]);
+        assert_eq!(table, LineTable::from_lines(vec![Some(1); 3]));
+        assert_eq!(table.column(0), None);
+    }
+
+    #[test]
+    fn test_frozen_code_decode() {
+        let mut code = sample_code();
+        code.instructions = code
+            .instructions
+            .iter()
+            .cycle()
+            .take(2000)
+            .cloned()
+            .collect();
+        code.line_table = LineTable::from_lines(vec![Some(1); 2000]);
+        let bytes = code.to_compressed_bytes();
+        assert!(bytes.len() < code.to_bytes().len());
+        let frozen = FrozenCode::Compressed(Box::leak(bytes.into_boxed_slice()));
+        assert!(frozen.decode() == code);
+        let frozen = FrozenCode::Serialized(Box::leak(code.to_bytes().into_boxed_slice()));
+        assert!(frozen.decode() == code);
+        assert!(FrozenCode::from(code.clone()).decode() == code);
+    }
+
+    #[test]
+    fn test_bytes_header() {
+        let code = sample_code();
+        let bytes = code.to_bytes();
+        assert_eq!(bytes[..4], BYTES_MAGIC);
+        assert_eq!(CodeObject::from_bytes(&bytes), Ok(code.clone()));
+
+        assert_eq!(
+            CodeObject::from_bytes(&bytes[BYTES_HEADER_LEN..]),
+            Err(DecodeError::NotBytecode)
+        );
+        let is_corrupt = |data: &[u8]| match CodeObject::from_bytes(data) {
+            Err(DecodeError::Corrupt(_)) => true,
+            _ => false,
+        };
+        assert!(is_corrupt(&bytes[..6]));
+        // The lz4 data can end with an empty token, which isn't missed
+        assert!(is_corrupt(&bytes[..bytes.len() - 2]));
+
+        let mut other_version = bytes.clone();
+        other_version[4..8].copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+        assert_eq!(
+            CodeObject::from_bytes(&other_version),
+            Err(DecodeError::FormatVersion(FORMAT_VERSION - 1))
+        );
+        let frozen = FrozenCode::Serialized(Box::leak(other_version.into_boxed_slice()));
+        assert_eq!(
+            frozen.try_decode(),
+            Err(DecodeError::FormatVersion(FORMAT_VERSION - 1))
+        );
+
+        let mut unknown_flags = bytes;
+        unknown_flags[8] |= 0x80;
+        assert_eq!(
+            CodeObject::from_bytes(&unknown_flags),
+            Err(DecodeError::UnknownFlags(0x81))
+        );
+
+        // Without the lz4 flag, the code object is stored as it is
+        let mut uncompressed = BYTES_MAGIC.to_vec();
+        uncompressed.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        uncompressed.extend_from_slice(&0u32.to_le_bytes());
+        uncompressed.extend(bincode::serialize(&code).unwrap());
+        assert_eq!(CodeObject::from_bytes(&uncompressed), Ok(code));
+    }
+
+    #[test]
+    fn test_frozen_source_decode() {
+        let text = "def f():\n    return 1\n".repeat(100);
+        let bytes = FrozenSource::compress(&text);
+        assert!(bytes.len() < text.len());
+        let frozen = FrozenSource::Compressed(Box::leak(bytes.into_boxed_slice()));
+        assert_eq!(frozen.decode(), text);
+        assert_eq!(FrozenSource::Text(text.clone().into()).decode(), text);
+    }
+
+    #[test]
+    fn test_line_table_ranges() {
+        let lines = vec![Some(2), Some(2), None, Some(1), Some(1), Some(1)];
+        let table = LineTable::from_lines(lines);
+        assert_eq!(
+            table.ranges().collect::<Vec<_>>(),
+            [
+                LineRange {
+                    start: 0,
+                    end: 2,
+                    line: Some(2)
+                },
+                LineRange {
+                    start: 2,
+                    end: 3,
+                    line: None
+                },
+                LineRange {
+                    start: 3,
+                    end: 6,
+                    line: Some(1)
+                },
+            ]
+        );
+        assert_eq!(table.range(4).map(|range| range.start), Some(3));
+
+        // The pairs for long lines don't split the ranges.
+        let table = LineTable::from_lines(vec![Some(1); 1000]);
+        assert_eq!(table.ranges().count(), 1);
+        assert!(table.deltas.len() < 10);
+    }
+
+    #[test]
+    fn test_float_constants_compare_by_bits() {
+        let positive = Constant::Float { value: 0.0 };
+        let negative = Constant::Float { value: -0.0 };
+        assert_ne!(positive, negative);
+        let nan = Constant::Float {
+            value: std::f64::NAN,
+        };
+        assert_eq!(nan, nan.clone());
+    }
+
+    #[test]
+    fn test_add_constant_and_name() {
+        let mut code = sample_code();
+        assert_eq!(code.add_constant(Constant::None), 1);
+        assert_eq!(code.add_constant(Constant::Float { value: 0.0 }), 2);
+        let tuple = Constant::Tuple {
+            elements: vec![Constant::String {
+                value: "a".to_owned(),
+            }],
+        };
+        assert_eq!(code.add_constant(tuple.clone()), 3);
+        assert_eq!(code.add_constant(tuple), 3);
+        assert_eq!(code.constants.len(), 4);
+
+        assert_eq!(code.add_name("x"), 0);
+        assert_eq!(code.add_name("y"), 1);
+        assert_eq!(code.add_name("x"), 0);
+        assert_eq!(code.names, ["x", "y"]);
+    }
 }
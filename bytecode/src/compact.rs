@@ -0,0 +1,851 @@
+//! A compact encoding of code objects, for the ones `py_compile_bytecode!` puts in binaries.
+//!
+//! It's the serde data model of `CodeObject` like bincode's, but without the padding: integers
+//! are LEB128 varints (zigzagged when signed), enum variants and lengths are varints too, and
+//! every string is the index of an entry in a `StringTable`. All the code objects of a frozen
+//! stdlib share one table, so a name or constant used by many modules is stored once.
+//!
+//! The table starts with `TABLE_MAGIC`, the `FORMAT_VERSION` and the number of strings as little
+//! endian `u32`s, followed by the offset of each string and of the end of the last one, as
+//! `u32`s too, and then the strings themselves. With the offsets any string can be looked up
+//! without reading the table, so decoding a module costs no more than its own code.
+
+use crate::bytecode::{CodeObject, DecodeError, FORMAT_VERSION};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str;
+
+/// The magic number a `StringTable` starts with.
+pub const TABLE_MAGIC: [u8; 4] = *b"RPST";
+
+const TABLE_HEADER_LEN: usize = 12;
+
+/// Encode `code`, adding its strings to `strings`.
+pub fn encode(code: &CodeObject, strings: &mut StringTableBuilder) -> Vec<u8> {
+    let mut encoder = Encoder {
+        out: Vec::new(),
+        strings,
+    };
+    code.serialize(&mut encoder)
+        .expect("Code object must be serializable");
+    encoder.out
+}
+
+/// Decode the code object `encode` wrote, with the bytes of the `StringTable` it wrote to.
+pub fn decode(data: &[u8], strings: &[u8]) -> Result<CodeObject, DecodeError> {
+    let strings = StringTable::new(strings)?;
+    let mut decoder = Decoder {
+        data,
+        strings: &strings,
+    };
+    let code = serde::Deserialize::deserialize(&mut decoder)
+        .map_err(|Error(reason)| DecodeError::Corrupt(reason))?;
+    if !decoder.data.is_empty() {
+        return Err(DecodeError::Corrupt("trailing data".to_owned()));
+    }
+    Ok(code)
+}
+
+/// Collects the strings of the code objects `encode` writes, each of them once.
+#[derive(Default)]
+pub struct StringTableBuilder {
+    strings: Vec<String>,
+    indices: HashMap<String, u32>,
+}
+
+impl StringTableBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn intern(&mut self, string: &str) -> u32 {
+        if let Some(index) = self.indices.get(string) {
+            return *index;
+        }
+        let index = u32::try_from(self.strings.len()).expect("too many strings");
+        self.strings.push(string.to_owned());
+        self.indices.insert(string.to_owned(), index);
+        index
+    }
+
+    /// The table, for `decode`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = TABLE_MAGIC.to_vec();
+        let push_u32 = |out: &mut Vec<u8>, value: usize| {
+            let value = u32::try_from(value).expect("string table too large");
+            out.extend_from_slice(&value.to_le_bytes());
+        };
+        push_u32(&mut out, FORMAT_VERSION as usize);
+        push_u32(&mut out, self.strings.len());
+        let mut offset = 0;
+        for string in &self.strings {
+            push_u32(&mut out, offset);
+            offset += string.len();
+        }
+        push_u32(&mut out, offset);
+        for string in &self.strings {
+            out.extend_from_slice(string.as_bytes());
+        }
+        out
+    }
+}
+
+struct StringTable<'a> {
+    offsets: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> StringTable<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        if !bytes.starts_with(&TABLE_MAGIC) {
+            return Err(DecodeError::NotBytecode);
+        }
+        if bytes.len() < TABLE_HEADER_LEN {
+            return Err(DecodeError::Corrupt("truncated string table".to_owned()));
+        }
+        let version = read_u32(bytes, 4);
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::FormatVersion(version));
+        }
+        let count = read_u32(bytes, 8) as usize;
+        let offsets_end = (count + 1)
+            .checked_mul(4)
+            .and_then(|len| len.checked_add(TABLE_HEADER_LEN))
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(|| DecodeError::Corrupt("truncated string table".to_owned()))?;
+        Ok(StringTable {
+            offsets: &bytes[TABLE_HEADER_LEN..offsets_end],
+            data: &bytes[offsets_end..],
+        })
+    }
+
+    fn get(&self, index: u64) -> Result<&'a str, Error> {
+        let index = usize::try_from(index)
+            .ok()
+            .filter(|index| (index + 1) * 4 < self.offsets.len())
+            .ok_or_else(|| Error(format!("no string {}", index)))?;
+        let start = read_u32(self.offsets, index * 4) as usize;
+        let end = read_u32(self.offsets, (index + 1) * 4) as usize;
+        let bytes = self
+            .data
+            .get(start..end)
+            .ok_or_else(|| Error(format!("string {} is out of the table", index)))?;
+        str::from_utf8(bytes).map_err(|_| Error("invalid utf-8 string".to_owned()))
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(<[u8; 4]>::try_from(&bytes[offset..offset + 4]).unwrap())
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+struct Encoder<'a> {
+    out: Vec<u8>,
+    strings: &'a mut StringTableBuilder,
+}
+
+impl Encoder<'_> {
+    fn write_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.out.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.out.push(value as u8);
+    }
+
+    fn write_signed(&mut self, value: i64) {
+        self.write_varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn write_len(&mut self, len: Option<usize>) -> Result<(), Error> {
+        let len = len.ok_or_else(|| Error("sequences must have a length".to_owned()))?;
+        self.write_varint(len as u64);
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::Serializer for &'a mut Encoder<'b> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.out.push(v as u8);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.write_signed(v.into());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.write_signed(v.into());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.write_signed(v.into());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.write_signed(v);
+        Ok(())
+    }
+
+    // Bytes are as short as they get already, like the ones of a bytes constant
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.out.push(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.write_varint(v.into());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.write_varint(v.into());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.write_varint(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.out.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.out.extend_from_slice(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.write_varint(u64::from(v));
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        let index = self.strings.intern(v);
+        self.write_varint(index.into());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        self.write_varint(v.len() as u64);
+        self.out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.out.push(0);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        self.out.push(1);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        self.write_varint(variant_index.into());
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.write_varint(variant_index.into());
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, Error> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        self.write_varint(variant_index.into());
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, Error> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Error> {
+        self.write_varint(variant_index.into());
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_serialize_compound {
+    ($($trait:ident :: $method:ident),*) => {$(
+        impl<'a, 'b> ser::$trait for &'a mut Encoder<'b> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+    )*};
+}
+
+impl_serialize_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl<'a, 'b> ser::SerializeMap for &'a mut Encoder<'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStruct for &'a mut Encoder<'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b> ser::SerializeStructVariant for &'a mut Encoder<'b> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    strings: &'a StringTable<'a>,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if len > self.data.len() {
+            return Err(Error("unexpected end of data".to_owned()));
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_varint(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error("varint too long".to_owned()))
+    }
+
+    fn read_signed(&mut self) -> Result<i64, Error> {
+        let value = self.read_varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn read_int<T: TryFrom<u64>>(&mut self) -> Result<T, Error> {
+        T::try_from(self.read_varint()?).map_err(|_| Error("integer out of range".to_owned()))
+    }
+
+    fn read_signed_int<T: TryFrom<i64>>(&mut self) -> Result<T, Error> {
+        T::try_from(self.read_signed()?).map_err(|_| Error("integer out of range".to_owned()))
+    }
+
+    fn read_len(&mut self) -> Result<usize, Error> {
+        self.read_int()
+    }
+
+    fn read_array<A: Default + AsMut<[u8]>>(&mut self) -> Result<A, Error> {
+        let mut array = A::default();
+        let len = array.as_mut().len();
+        array.as_mut().copy_from_slice(self.take(len)?);
+        Ok(array)
+    }
+}
+
+macro_rules! impl_deserialize_int {
+    ($($method:ident => $visit:ident, $read:ident;)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            visitor.$visit(self.$read()?)
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for &mut Decoder<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(Error(
+            "the compact encoding needs to know the type of every value".to_owned(),
+        ))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            byte => Err(Error(format!("invalid bool {}", byte))),
+        }
+    }
+
+    impl_deserialize_int! {
+        deserialize_i8 => visit_i8, read_signed_int;
+        deserialize_i16 => visit_i16, read_signed_int;
+        deserialize_i32 => visit_i32, read_signed_int;
+        deserialize_i64 => visit_i64, read_signed;
+        deserialize_u8 => visit_u8, read_byte;
+        deserialize_u16 => visit_u16, read_int;
+        deserialize_u32 => visit_u32, read_int;
+        deserialize_u64 => visit_u64, read_varint;
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_f32(f32::from_bits(u32::from_le_bytes(self.read_array()?)))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_f64(f64::from_bits(u64::from_le_bytes(self.read_array()?)))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let c = std::char::from_u32(self.read_int()?)
+            .ok_or_else(|| Error("invalid char".to_owned()))?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let index = self.read_varint()?;
+        visitor.visit_str(self.strings.get(index)?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = self.read_len()?;
+        visitor.visit_borrowed_bytes(self.take(len)?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.read_byte()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            byte => Err(Error(format!("invalid option {}", byte))),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = self.read_len()?;
+        visitor.visit_seq(Access { decoder: self, len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Access { decoder: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let len = self.read_len()?;
+        visitor.visit_map(Access { decoder: self, len })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// The elements of a sequence, tuple or map of known length.
+struct Access<'a, 'de> {
+    decoder: &'a mut Decoder<'de>,
+    len: usize,
+}
+
+impl<'a, 'de> de::SeqAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'a, 'de> de::MapAccess<'de> for Access<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.decoder).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        seed.deserialize(&mut *self.decoder)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut Decoder<'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let index: u32 = self.read_int()?;
+        let variant = seed.deserialize(index.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Decoder<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::{CodeFlags, Constant, Instruction, Label, LineTable, Varargs};
+    use num_bigint::BigInt;
+    use num_complex::Complex64;
+
+    fn code_with_constants(name: &str, constants: Vec<Constant>) -> CodeObject {
+        let mut code = CodeObject::new(
+            CodeFlags::NEW_LOCALS | CodeFlags::HAS_DEFAULTS,
+            1,
+            vec!["x".to_string(), "y".to_string()],
+            Varargs::Named("args".to_string()),
+            vec!["key".to_string()],
+            Varargs::None,
+            "sample.py".to_string(),
+            300,
+            name.to_string(),
+        );
+        code.instructions = (0..constants.len())
+            .map(|idx| Instruction::LoadConst { idx })
+            .collect();
+        code.constants = constants;
+        code.instructions.to_mut().push(Instruction::JumpIfFalse {
+            target: Label::new(0),
+        });
+        code.instructions.to_mut().push(Instruction::ReturnValue);
+        code.label_map.insert(Label::new(0), 1);
+        code.line_table = LineTable::from_lines(vec![Some(300); code.instructions.len()]);
+        code
+    }
+
+    fn all_constants() -> Vec<Constant> {
+        vec![
+            Constant::Integer {
+                value: BigInt::from(-5),
+            },
+            Constant::Integer {
+                value: BigInt::from(1u128 << 100),
+            },
+            Constant::Float { value: -0.0 },
+            Constant::Complex {
+                value: Complex64::new(1.5, -2.0),
+            },
+            Constant::Boolean { value: true },
+            Constant::String {
+                value: "h\u{e9}llo".to_string(),
+            },
+            Constant::Bytes {
+                value: vec![0, 0x80, 0xff],
+            },
+            Constant::Tuple {
+                elements: vec![Constant::None, Constant::Ellipsis],
+            },
+            Constant::Code {
+                code: Box::new(code_with_constants("inner", vec![Constant::None])),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let code = code_with_constants("f", all_constants());
+        let mut strings = StringTableBuilder::new();
+        let data = encode(&code, &mut strings);
+        let strings = strings.to_bytes();
+        assert_eq!(decode(&data, &strings), Ok(code.clone()));
+        // It's meant to be smaller than the general serialization.
+        assert!(data.len() < code.to_bytes().len());
+    }
+
+    #[test]
+    fn test_shared_strings() {
+        let first = code_with_constants("f", vec![]);
+        let second = code_with_constants("g", vec![]);
+        let mut strings = StringTableBuilder::new();
+        let first_data = encode(&first, &mut strings);
+        let count = strings.strings.len();
+        let second_data = encode(&second, &mut strings);
+        // Only the name of the second function is new.
+        assert_eq!(strings.strings.len(), count + 1);
+        let strings = strings.to_bytes();
+        assert_eq!(decode(&first_data, &strings), Ok(first));
+        assert_eq!(decode(&second_data, &strings), Ok(second));
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        let code = code_with_constants("f", all_constants());
+        let mut builder = StringTableBuilder::new();
+        let data = encode(&code, &mut builder);
+        let strings = builder.to_bytes();
+
+        let is_corrupt = |data: &[u8], strings: &[u8]| match decode(data, strings) {
+            Err(DecodeError::Corrupt(_)) => true,
+            _ => false,
+        };
+        assert!(is_corrupt(&data[..data.len() - 1], &strings));
+        let mut trailing = data.clone();
+        trailing.push(0);
+        assert!(is_corrupt(&trailing, &strings));
+        // The strings of another table may not exist, or be other strings.
+        let other = StringTableBuilder::new().to_bytes();
+        assert!(decode(&data, &other).is_err());
+        assert!(is_corrupt(&data, &strings[..strings.len() - 1]));
+        assert!(is_corrupt(&data, &strings[..TABLE_HEADER_LEN]));
+
+        assert_eq!(decode(&data, &strings[4..]), Err(DecodeError::NotBytecode));
+        let mut other_version = strings;
+        other_version[4..8].copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+        assert_eq!(
+            decode(&data, &other_version),
+            Err(DecodeError::FormatVersion(FORMAT_VERSION - 1))
+        );
+    }
+}
@@ -0,0 +1,1139 @@
+//! Translate the bytecode in `.pyc` files compiled by CPython 3.7 into RustPython code objects,
+//! so that modules can be frozen without their sources.
+//!
+//! Most CPython instructions have a RustPython counterpart that does the same to the stack. The
+//! others are rewritten:
+//!
+//! - `IMPORT_NAME` takes its level and from-list from the two constants loaded before it.
+//! - `BUILD_CONST_KEY_MAP`, `DUP_TOP_TWO`, `FORMAT_VALUE` without a format spec and frozenset
+//!   constants become short sequences of other instructions.
+//! - RustPython looks free variables up by name rather than in cells, so `LOAD_CLOSURE` loads
+//!   `None`, the closure tuple is dropped again before `MAKE_FUNCTION`, and a class body doesn't
+//!   store `__classcell__`.
+//! - CPython takes a function's `__doc__` from its first constant, RustPython sets it after
+//!   `MakeFunction`.
+//! - CPython starts an exception handler with six values pushed on the stack, RustPython with
+//!   just the exception. The handler is given the three values its code looks at (with the
+//!   exception as both type and value), and `POP_EXCEPT` pops RustPython's handler block.
+//! - `END_FINALLY` ends three different things: it re-raises the exception when no `except`
+//!   clause matches it, ends a finally block, and ends a `with` block, which RustPython's
+//!   `WithCleanupFinish` already does by itself. The `LOAD_CONST None` that enters a finally or
+//!   with block when there's no exception becomes `EnterFinally`.
+//!
+//! `async for`, `async with`, async generators and the few instructions without any
+//! counterpart, like `DELETE_GLOBAL`, make the translation fail with `PycError::Untranslatable`.
+
+use crate::bytecode::{
+    BinaryOperator, CallType, CodeFlags, CodeObject, ComparisonOperator, Constant, ConversionFlag,
+    Instruction, Label, LineTable, NameIdx, NameScope, UnaryOperator, Varargs, NO_CACHE_SLOT,
+};
+use crate::pyc::{read_pyc, CodeLayout, MarshalCode, PycError, Value};
+use num_traits::ToPrimitive;
+use std::collections::{BTreeSet, HashMap};
+
+const CO_NEWLOCALS: u32 = 0x0002;
+const CO_VARARGS: u32 = 0x0004;
+const CO_VARKEYWORDS: u32 = 0x0008;
+const CO_GENERATOR: u32 = 0x0020;
+const CO_COROUTINE: u32 = 0x0080;
+const CO_ASYNC_GENERATOR: u32 = 0x0200;
+const CO_FUTURE_ANNOTATIONS: u32 = 0x0100_0000;
+
+const POP_TOP: u8 = 1;
+const ROT_TWO: u8 = 2;
+const ROT_THREE: u8 = 3;
+const DUP_TOP: u8 = 4;
+const DUP_TOP_TWO: u8 = 5;
+const NOP: u8 = 9;
+const UNARY_POSITIVE: u8 = 10;
+const UNARY_NEGATIVE: u8 = 11;
+const UNARY_NOT: u8 = 12;
+const UNARY_INVERT: u8 = 15;
+const BINARY_MATRIX_MULTIPLY: u8 = 16;
+const INPLACE_MATRIX_MULTIPLY: u8 = 17;
+const BINARY_POWER: u8 = 19;
+const BINARY_MULTIPLY: u8 = 20;
+const BINARY_MODULO: u8 = 22;
+const BINARY_ADD: u8 = 23;
+const BINARY_SUBTRACT: u8 = 24;
+const BINARY_SUBSCR: u8 = 25;
+const BINARY_FLOOR_DIVIDE: u8 = 26;
+const BINARY_TRUE_DIVIDE: u8 = 27;
+const INPLACE_FLOOR_DIVIDE: u8 = 28;
+const INPLACE_TRUE_DIVIDE: u8 = 29;
+const GET_AITER: u8 = 50;
+const GET_ANEXT: u8 = 51;
+const BEFORE_ASYNC_WITH: u8 = 52;
+const INPLACE_ADD: u8 = 55;
+const INPLACE_SUBTRACT: u8 = 56;
+const INPLACE_MULTIPLY: u8 = 57;
+const INPLACE_MODULO: u8 = 59;
+const STORE_SUBSCR: u8 = 60;
+const DELETE_SUBSCR: u8 = 61;
+const BINARY_LSHIFT: u8 = 62;
+const BINARY_RSHIFT: u8 = 63;
+const BINARY_AND: u8 = 64;
+const BINARY_XOR: u8 = 65;
+const BINARY_OR: u8 = 66;
+const INPLACE_POWER: u8 = 67;
+const GET_ITER: u8 = 68;
+const GET_YIELD_FROM_ITER: u8 = 69;
+const PRINT_EXPR: u8 = 70;
+const LOAD_BUILD_CLASS: u8 = 71;
+const YIELD_FROM: u8 = 72;
+const GET_AWAITABLE: u8 = 73;
+const INPLACE_LSHIFT: u8 = 75;
+const INPLACE_RSHIFT: u8 = 76;
+const INPLACE_AND: u8 = 77;
+const INPLACE_XOR: u8 = 78;
+const INPLACE_OR: u8 = 79;
+const BREAK_LOOP: u8 = 80;
+const WITH_CLEANUP_START: u8 = 81;
+const WITH_CLEANUP_FINISH: u8 = 82;
+const RETURN_VALUE: u8 = 83;
+const IMPORT_STAR: u8 = 84;
+const SETUP_ANNOTATIONS: u8 = 85;
+const YIELD_VALUE: u8 = 86;
+const POP_BLOCK: u8 = 87;
+const END_FINALLY: u8 = 88;
+const POP_EXCEPT: u8 = 89;
+const STORE_NAME: u8 = 90;
+const DELETE_NAME: u8 = 91;
+const UNPACK_SEQUENCE: u8 = 92;
+const FOR_ITER: u8 = 93;
+const UNPACK_EX: u8 = 94;
+const STORE_ATTR: u8 = 95;
+const DELETE_ATTR: u8 = 96;
+const STORE_GLOBAL: u8 = 97;
+const DELETE_GLOBAL: u8 = 98;
+const LOAD_CONST: u8 = 100;
+const LOAD_NAME: u8 = 101;
+const BUILD_TUPLE: u8 = 102;
+const BUILD_LIST: u8 = 103;
+const BUILD_SET: u8 = 104;
+const BUILD_MAP: u8 = 105;
+const LOAD_ATTR: u8 = 106;
+const COMPARE_OP: u8 = 107;
+const IMPORT_NAME: u8 = 108;
+const IMPORT_FROM: u8 = 109;
+const JUMP_FORWARD: u8 = 110;
+const JUMP_IF_FALSE_OR_POP: u8 = 111;
+const JUMP_IF_TRUE_OR_POP: u8 = 112;
+const JUMP_ABSOLUTE: u8 = 113;
+const POP_JUMP_IF_FALSE: u8 = 114;
+const POP_JUMP_IF_TRUE: u8 = 115;
+const LOAD_GLOBAL: u8 = 116;
+const CONTINUE_LOOP: u8 = 119;
+const SETUP_LOOP: u8 = 120;
+const SETUP_EXCEPT: u8 = 121;
+const SETUP_FINALLY: u8 = 122;
+const LOAD_FAST: u8 = 124;
+const STORE_FAST: u8 = 125;
+const DELETE_FAST: u8 = 126;
+const RAISE_VARARGS: u8 = 130;
+const CALL_FUNCTION: u8 = 131;
+const MAKE_FUNCTION: u8 = 132;
+const BUILD_SLICE: u8 = 133;
+const LOAD_CLOSURE: u8 = 135;
+const LOAD_DEREF: u8 = 136;
+const STORE_DEREF: u8 = 137;
+const DELETE_DEREF: u8 = 138;
+const CALL_FUNCTION_KW: u8 = 141;
+const CALL_FUNCTION_EX: u8 = 142;
+const SETUP_WITH: u8 = 143;
+const EXTENDED_ARG: u8 = 144;
+const LIST_APPEND: u8 = 145;
+const SET_ADD: u8 = 146;
+const MAP_ADD: u8 = 147;
+const LOAD_CLASSDEREF: u8 = 148;
+const BUILD_LIST_UNPACK: u8 = 149;
+const BUILD_MAP_UNPACK: u8 = 150;
+const BUILD_MAP_UNPACK_WITH_CALL: u8 = 151;
+const BUILD_TUPLE_UNPACK: u8 = 152;
+const BUILD_SET_UNPACK: u8 = 153;
+const SETUP_ASYNC_WITH: u8 = 154;
+const FORMAT_VALUE: u8 = 155;
+const BUILD_CONST_KEY_MAP: u8 = 156;
+const BUILD_STRING: u8 = 157;
+const BUILD_TUPLE_UNPACK_WITH_CALL: u8 = 158;
+const LOAD_METHOD: u8 = 160;
+const CALL_METHOD: u8 = 161;
+
+/// Translate a pyc file compiled by CPython 3.7 into a RustPython code object.
+pub fn translate_pyc(data: &[u8]) -> Result<CodeObject, PycError> {
+    // Check the version before reading the code, which would fail in a less telling way
+    if let Some(magic) = data.get(..4) {
+        let magic = u32::from_le_bytes([magic[0], magic[1], magic[2], magic[3]]);
+        if CodeLayout::from_magic(magic)? != CodeLayout::Python37 {
+            return Err(PycError::UnsupportedMagic(magic));
+        }
+    }
+    translate_code(&read_pyc(data)?.code)
+}
+
+/// A CPython instruction, with the `EXTENDED_ARG`s before it folded into its argument.
+struct RawInstruction {
+    /// Where the instruction starts, including its `EXTENDED_ARG`s, which is where jumps to it
+    /// go to.
+    offset: usize,
+    opcode: u8,
+    arg: usize,
+    /// Where the next instruction starts.
+    next: usize,
+}
+
+fn decode(code: &[u8]) -> Result<Vec<RawInstruction>, PycError> {
+    if code.len() & 1 != 0 {
+        return Err(PycError::Unexpected("bytecode of 2 byte instructions"));
+    }
+    let mut instructions = Vec::new();
+    let mut start = 0;
+    let mut arg = 0;
+    for (index, unit) in code.chunks(2).enumerate() {
+        arg = arg << 8 | unit[1] as usize;
+        if unit[0] == EXTENDED_ARG {
+            continue;
+        }
+        instructions.push(RawInstruction {
+            offset: start,
+            opcode: unit[0],
+            arg,
+            next: (index + 1) * 2,
+        });
+        start = (index + 1) * 2;
+        arg = 0;
+    }
+    Ok(instructions)
+}
+
+/// The line of every instruction of `code`, by offset / 2, from its `co_lnotab`: pairs of the
+/// (unsigned) number of bytes and (signed) number of lines to the next line change.
+fn line_numbers(code: &MarshalCode) -> Vec<usize> {
+    let mut lines = Vec::with_capacity(code.code.len() / 2);
+    let mut line = code.firstlineno as i64;
+    let mut offset = 0;
+    for pair in code.lnotab.chunks(2) {
+        let next_offset = offset + pair[0] as usize;
+        while lines.len() * 2 < next_offset {
+            lines.push(line as usize);
+        }
+        offset = next_offset;
+        if let Some(&delta) = pair.get(1) {
+            line += i64::from(delta as i8);
+        }
+    }
+    while lines.len() * 2 < code.code.len() {
+        lines.push(line as usize);
+    }
+    lines
+}
+
+/// What an `END_FINALLY` ends, by the handler the code in front of it belongs to.
+#[derive(Clone, Copy, PartialEq)]
+enum HandlerKind {
+    Except,
+    Finally,
+    With,
+}
+
+struct Translator<'a> {
+    code: &'a MarshalCode,
+    /// The code object being made, whose constants and names are added as they're used.
+    translated: CodeObject,
+    instructions: Vec<Instruction>,
+    lines: Vec<Option<usize>>,
+    /// The index of the first RustPython instruction of every CPython instruction, by offset.
+    positions: HashMap<usize, usize>,
+    /// The offsets jumped to, which need labels.
+    targets: BTreeSet<usize>,
+    /// The docstrings of the code objects loaded by `LoadConst`, by instruction index, for
+    /// `MAKE_FUNCTION` to set as `__doc__` like CPython does.
+    docstrings: HashMap<usize, String>,
+    line: Option<usize>,
+}
+
+fn translate_code(code: &MarshalCode) -> Result<CodeObject, PycError> {
+    let untranslatable =
+        |what: &str| PycError::Untranslatable(format!("{} in {}", what, code.name));
+    if code.flags & CO_ASYNC_GENERATOR != 0 {
+        return Err(untranslatable("async generator"));
+    }
+
+    let mut flags = CodeFlags::empty();
+    if code.flags & CO_NEWLOCALS != 0 {
+        flags |= CodeFlags::NEW_LOCALS;
+    }
+    if code.flags & CO_GENERATOR != 0 {
+        flags |= CodeFlags::IS_GENERATOR;
+    }
+    if code.flags & CO_COROUTINE != 0 {
+        flags |= CodeFlags::IS_COROUTINE;
+    }
+    if code.flags & CO_FUTURE_ANNOTATIONS != 0 {
+        flags |= CodeFlags::FUTURE_ANNOTATIONS;
+    }
+    let argcount = code.argcount as usize;
+    let kwonlyargcount = code.kwonlyargcount as usize;
+    let mut varnames = code.varnames.iter().cloned();
+    let arg_names: Vec<String> = varnames.by_ref().take(argcount).collect();
+    let kwonlyarg_names: Vec<String> = varnames.by_ref().take(kwonlyargcount).collect();
+    let mut named_varargs = |flag| {
+        if code.flags & flag == 0 {
+            Ok(Varargs::None)
+        } else {
+            varnames
+                .next()
+                .map(Varargs::Named)
+                .ok_or(PycError::Unexpected("a name for *args or **kwargs"))
+        }
+    };
+    let varargs = named_varargs(CO_VARARGS)?;
+    let varkeywords = named_varargs(CO_VARKEYWORDS)?;
+    if arg_names.len() < argcount || kwonlyarg_names.len() < kwonlyargcount {
+        return Err(PycError::Unexpected("a name for every argument"));
+    }
+
+    let raw = decode(&code.code)?;
+    let lines = line_numbers(code);
+    let loop_starts = loop_starts(&raw);
+    let mut handlers = HashMap::new();
+    for instruction in &raw {
+        let kind = match instruction.opcode {
+            SETUP_EXCEPT => HandlerKind::Except,
+            SETUP_FINALLY => HandlerKind::Finally,
+            SETUP_WITH => HandlerKind::With,
+            _ => continue,
+        };
+        handlers.insert(instruction.next + instruction.arg, kind);
+    }
+
+    let translated = CodeObject::new(
+        flags,
+        0,
+        arg_names,
+        varargs,
+        kwonlyarg_names,
+        varkeywords,
+        code.filename.clone(),
+        code.firstlineno as usize,
+        code.name.clone(),
+    );
+    let mut translator = Translator {
+        code,
+        translated,
+        instructions: Vec::new(),
+        lines: Vec::new(),
+        positions: HashMap::new(),
+        targets: BTreeSet::new(),
+        docstrings: HashMap::new(),
+        line: None,
+    };
+    // The handlers whose code the translation is in, innermost last.
+    let mut handler_stack = Vec::new();
+    let mut previous_opcode = None;
+    for instruction in &raw {
+        translator.line = lines.get(instruction.offset / 2).cloned();
+        translator
+            .positions
+            .insert(instruction.offset, translator.instructions.len());
+        if let Some(&kind) = handlers.get(&instruction.offset) {
+            handler_stack.push(kind);
+            if kind == HandlerKind::Except {
+                // The traceback, value and type CPython pushes; the exception saved from
+                // before is only ever dropped by POP_EXCEPT.
+                let load_none = translator.load_const(Constant::None);
+                translator.emit(load_none);
+                translator.emit(Instruction::Rotate { amount: 2 });
+                translator.emit(Instruction::Duplicate);
+            }
+        }
+        let enters_handler = handlers.contains_key(&instruction.next)
+            && handlers[&instruction.next] != HandlerKind::Except;
+        if instruction.opcode == LOAD_CONST
+            && enters_handler
+            && previous_opcode == Some(POP_BLOCK)
+            && code.consts.get(instruction.arg) == Some(&Value::None)
+        {
+            translator.emit(Instruction::EnterFinally);
+        } else if instruction.opcode == END_FINALLY {
+            match handler_stack.pop() {
+                Some(HandlerKind::Except) => translator.emit(Instruction::Raise { argc: 0 }),
+                Some(HandlerKind::Finally) => translator.emit(Instruction::EndFinally),
+                Some(HandlerKind::With) => {}
+                // What's left of a try statement after a return, whose setup the peephole
+                // optimizer removed as unreachable
+                None => translator.emit(Instruction::EndFinally),
+            }
+        } else if instruction.opcode == SETUP_LOOP {
+            let start = loop_starts[&instruction.offset];
+            let end = instruction.next + instruction.arg;
+            let (start, end) = (translator.label(start), translator.label(end));
+            translator.emit(Instruction::SetupLoop { start, end });
+        } else {
+            translator.translate(instruction)?;
+        }
+        previous_opcode = Some(instruction.opcode);
+    }
+    translator.finish()
+}
+
+/// Where `continue` goes for every `SETUP_LOOP`, by its offset: where a `CONTINUE_LOOP` in the
+/// loop jumps to, or else the instruction after the `SETUP_LOOP`, which is only wrong for `for`
+/// loops, and those have no `continue` that doesn't jump there by itself.
+fn loop_starts(raw: &[RawInstruction]) -> HashMap<usize, usize> {
+    let mut starts = HashMap::new();
+    // The loops the instruction is in, innermost last, with their end.
+    let mut loops: Vec<(usize, usize)> = Vec::new();
+    for instruction in raw {
+        while let Some(&(_, end)) = loops.last() {
+            if instruction.offset < end {
+                break;
+            }
+            loops.pop();
+        }
+        match instruction.opcode {
+            SETUP_LOOP => {
+                starts.insert(instruction.offset, instruction.next);
+                loops.push((instruction.offset, instruction.next + instruction.arg));
+            }
+            CONTINUE_LOOP => {
+                if let Some(&(setup, _)) = loops.last() {
+                    starts.insert(setup, instruction.arg);
+                }
+            }
+            _ => {}
+        }
+    }
+    starts
+}
+
+impl<'a> Translator<'a> {
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+        self.lines.push(self.line);
+    }
+
+    fn label(&mut self, offset: usize) -> Label {
+        self.targets.insert(offset);
+        Label::new(offset)
+    }
+
+    fn load_const(&mut self, constant: Constant) -> Instruction {
+        Instruction::LoadConst {
+            idx: self.translated.add_constant(constant),
+        }
+    }
+
+    fn untranslatable(&self, what: &str) -> PycError {
+        PycError::Untranslatable(format!("{} in {}", what, self.code.name))
+    }
+
+    fn name(&self, index: usize) -> Result<&'a str, PycError> {
+        self.code
+            .names
+            .get(index)
+            .map(String::as_str)
+            .ok_or(PycError::Unexpected("a valid name index"))
+    }
+
+    fn varname(&self, index: usize) -> Result<&'a str, PycError> {
+        self.code
+            .varnames
+            .get(index)
+            .map(String::as_str)
+            .ok_or(PycError::Unexpected("a valid local variable index"))
+    }
+
+    /// The name of a cell or free variable, and whether it's free.
+    fn deref_name(&self, index: usize) -> Result<(&'a str, bool), PycError> {
+        let code = self.code;
+        let cellvars = &code.cellvars;
+        if index < cellvars.len() {
+            Ok((&cellvars[index], false))
+        } else {
+            code.freevars
+                .get(index - cellvars.len())
+                .map(|name| (name.as_str(), true))
+                .ok_or(PycError::Unexpected("a valid cell or free variable index"))
+        }
+    }
+
+    /// The index of `name` in the names of the code object being made.
+    fn add_name(&mut self, name: &str) -> NameIdx {
+        self.translated.add_name(name)
+    }
+
+    fn constant(&self, value: &Value) -> Result<Constant, PycError> {
+        Ok(match value {
+            Value::None => Constant::None,
+            Value::Ellipsis => Constant::Ellipsis,
+            Value::Bool(value) => Constant::Boolean { value: *value },
+            Value::Int(value) => Constant::Integer {
+                value: value.clone(),
+            },
+            Value::Float(value) => Constant::Float { value: *value },
+            Value::Complex(value) => Constant::Complex { value: *value },
+            Value::Bytes(value) => Constant::Bytes {
+                value: value.clone(),
+            },
+            Value::Str(value) => Constant::String {
+                value: value.clone(),
+            },
+            Value::Tuple(elements) => Constant::Tuple {
+                elements: elements
+                    .iter()
+                    .map(|element| self.constant(element))
+                    .collect::<Result<_, _>>()?,
+            },
+            Value::Code(code) => Constant::Code {
+                code: Box::new(translate_code(code)?),
+            },
+            _ => return Err(self.untranslatable("a constant that isn't immutable")),
+        })
+    }
+
+    /// Remove the `LoadConst` just emitted and return its constant, for the instructions that
+    /// take their operands from constants loaded before them.
+    fn take_constant(&mut self, what: &'static str) -> Result<Constant, PycError> {
+        match self.instructions.pop() {
+            Some(Instruction::LoadConst { idx }) => {
+                self.lines.pop();
+                let len = self.instructions.len();
+                for position in self.positions.values_mut() {
+                    *position = (*position).min(len);
+                }
+                Ok(self.translated.constants[idx].clone())
+            }
+            _ => Err(PycError::Unexpected(what)),
+        }
+    }
+
+    fn translate(&mut self, instruction: &RawInstruction) -> Result<(), PycError> {
+        let arg = instruction.arg;
+        let relative_target = instruction.next + arg;
+        let binary = |op, inplace| Instruction::BinaryOperation { op, inplace };
+        let translated = match instruction.opcode {
+            NOP => return Ok(()),
+            POP_TOP => Instruction::Pop,
+            ROT_TWO => Instruction::Rotate { amount: 2 },
+            ROT_THREE => Instruction::Rotate { amount: 3 },
+            DUP_TOP => Instruction::Duplicate,
+            DUP_TOP_TWO => {
+                // a b -> b a -> b a a -> a b a -> a a b -> a a b b -> a b a b
+                self.emit(Instruction::Rotate { amount: 2 });
+                self.emit(Instruction::Duplicate);
+                self.emit(Instruction::Rotate { amount: 3 });
+                self.emit(Instruction::Rotate { amount: 3 });
+                self.emit(Instruction::Duplicate);
+                Instruction::Rotate { amount: 3 }
+            }
+            UNARY_POSITIVE => Instruction::UnaryOperation {
+                op: UnaryOperator::Plus,
+            },
+            UNARY_NEGATIVE => Instruction::UnaryOperation {
+                op: UnaryOperator::Minus,
+            },
+            UNARY_NOT => Instruction::UnaryOperation {
+                op: UnaryOperator::Not,
+            },
+            UNARY_INVERT => Instruction::UnaryOperation {
+                op: UnaryOperator::Invert,
+            },
+            BINARY_MATRIX_MULTIPLY => binary(BinaryOperator::MatrixMultiply, false),
+            INPLACE_MATRIX_MULTIPLY => binary(BinaryOperator::MatrixMultiply, true),
+            BINARY_POWER => binary(BinaryOperator::Power, false),
+            INPLACE_POWER => binary(BinaryOperator::Power, true),
+            BINARY_MULTIPLY => binary(BinaryOperator::Multiply, false),
+            INPLACE_MULTIPLY => binary(BinaryOperator::Multiply, true),
+            BINARY_MODULO => binary(BinaryOperator::Modulo, false),
+            INPLACE_MODULO => binary(BinaryOperator::Modulo, true),
+            BINARY_ADD => binary(BinaryOperator::Add, false),
+            INPLACE_ADD => binary(BinaryOperator::Add, true),
+            BINARY_SUBTRACT => binary(BinaryOperator::Subtract, false),
+            INPLACE_SUBTRACT => binary(BinaryOperator::Subtract, true),
+            BINARY_FLOOR_DIVIDE => binary(BinaryOperator::FloorDivide, false),
+            INPLACE_FLOOR_DIVIDE => binary(BinaryOperator::FloorDivide, true),
+            BINARY_TRUE_DIVIDE => binary(BinaryOperator::Divide, false),
+            INPLACE_TRUE_DIVIDE => binary(BinaryOperator::Divide, true),
+            BINARY_LSHIFT => binary(BinaryOperator::Lshift, false),
+            INPLACE_LSHIFT => binary(BinaryOperator::Lshift, true),
+            BINARY_RSHIFT => binary(BinaryOperator::Rshift, false),
+            INPLACE_RSHIFT => binary(BinaryOperator::Rshift, true),
+            BINARY_AND => binary(BinaryOperator::And, false),
+            INPLACE_AND => binary(BinaryOperator::And, true),
+            BINARY_XOR => binary(BinaryOperator::Xor, false),
+            INPLACE_XOR => binary(BinaryOperator::Xor, true),
+            BINARY_OR => binary(BinaryOperator::Or, false),
+            INPLACE_OR => binary(BinaryOperator::Or, true),
+            BINARY_SUBSCR => Instruction::Subscript,
+            STORE_SUBSCR => Instruction::StoreSubscript,
+            DELETE_SUBSCR => Instruction::DeleteSubscript,
+            GET_ITER => Instruction::GetIter,
+            GET_YIELD_FROM_ITER => Instruction::GetYieldFromIter,
+            GET_AWAITABLE => Instruction::GetAwaitable,
+            PRINT_EXPR => Instruction::PrintExpr,
+            LOAD_BUILD_CLASS => Instruction::LoadBuildClass,
+            YIELD_FROM => Instruction::YieldFrom,
+            YIELD_VALUE => Instruction::YieldValue,
+            RETURN_VALUE => Instruction::ReturnValue,
+            IMPORT_STAR => Instruction::ImportStar,
+            SETUP_ANNOTATIONS => Instruction::SetupAnnotations,
+            POP_BLOCK => Instruction::PopBlock,
+            POP_EXCEPT => Instruction::PopException,
+            BREAK_LOOP => Instruction::Break,
+            CONTINUE_LOOP => Instruction::Continue,
+            WITH_CLEANUP_START => Instruction::WithCleanupStart,
+            WITH_CLEANUP_FINISH => Instruction::WithCleanupFinish,
+            SETUP_EXCEPT => Instruction::SetupExcept {
+                handler: self.label(relative_target),
+            },
+            SETUP_FINALLY => Instruction::SetupFinally {
+                handler: self.label(relative_target),
+            },
+            SETUP_WITH => Instruction::SetupWith {
+                end: self.label(relative_target),
+            },
+            STORE_NAME => {
+                let name = self.name(arg)?;
+                // RustPython has no cells to give the class
+                if name == "__classcell__" {
+                    Instruction::Pop
+                } else {
+                    Instruction::StoreName {
+                        idx: self.add_name(name),
+                        scope: NameScope::Free,
+                    }
+                }
+            }
+            LOAD_NAME | LOAD_CLASSDEREF => {
+                let name = if instruction.opcode == LOAD_NAME {
+                    self.name(arg)?
+                } else {
+                    self.deref_name(arg)?.0
+                };
+                Instruction::LoadName {
+                    idx: self.add_name(name),
+                    scope: NameScope::Free,
+                }
+            }
+            DELETE_NAME => Instruction::DeleteName {
+                idx: self.add_name(self.name(arg)?),
+            },
+            LOAD_FAST => Instruction::LoadName {
+                idx: self.add_name(self.varname(arg)?),
+                scope: NameScope::Free,
+            },
+            STORE_FAST => Instruction::StoreName {
+                idx: self.add_name(self.varname(arg)?),
+                scope: NameScope::Free,
+            },
+            DELETE_FAST => Instruction::DeleteName {
+                idx: self.add_name(self.varname(arg)?),
+            },
+            LOAD_GLOBAL => Instruction::LoadName {
+                idx: self.add_name(self.name(arg)?),
+                scope: NameScope::Global,
+            },
+            STORE_GLOBAL => Instruction::StoreName {
+                idx: self.add_name(self.name(arg)?),
+                scope: NameScope::Global,
+            },
+            LOAD_DEREF => Instruction::LoadName {
+                idx: self.add_name(self.deref_name(arg)?.0),
+                scope: NameScope::Free,
+            },
+            STORE_DEREF => {
+                let (name, free) = self.deref_name(arg)?;
+                Instruction::StoreName {
+                    idx: self.add_name(name),
+                    scope: if free {
+                        NameScope::NonLocal
+                    } else {
+                        NameScope::Free
+                    },
+                }
+            }
+            LOAD_CLOSURE => self.load_const(Constant::None),
+            LOAD_ATTR | LOAD_METHOD => Instruction::LoadAttr {
+                idx: self.add_name(self.name(arg)?),
+            },
+            STORE_ATTR => Instruction::StoreAttr {
+                idx: self.add_name(self.name(arg)?),
+            },
+            DELETE_ATTR => Instruction::DeleteAttr {
+                idx: self.add_name(self.name(arg)?),
+            },
+            LOAD_CONST => {
+                let value = self
+                    .code
+                    .consts
+                    .get(arg)
+                    .ok_or(PycError::Unexpected("a valid constant index"))?;
+                match value {
+                    // Only made by CPython's peephole optimizer for `x in {...}`, where a set
+                    // does just as well
+                    Value::FrozenSet(elements) => {
+                        for element in elements {
+                            let value = self.constant(element)?;
+                            let load = self.load_const(value);
+                            self.emit(load);
+                        }
+                        Instruction::BuildSet {
+                            size: elements.len(),
+                            unpack: false,
+                        }
+                    }
+                    // The first constant of a function is its docstring, or None; comprehensions,
+                    // lambdas and class bodies have no docstring of their own.
+                    Value::Code(code)
+                        if code.flags & CO_NEWLOCALS != 0 && !code.name.starts_with('<') =>
+                    {
+                        if let Some(Value::Str(doc)) = code.consts.first() {
+                            self.docstrings.insert(self.instructions.len(), doc.clone());
+                        }
+                        let value = self.constant(value)?;
+                        self.load_const(value)
+                    }
+                    _ => {
+                        let value = self.constant(value)?;
+                        self.load_const(value)
+                    }
+                }
+            }
+            UNPACK_SEQUENCE => Instruction::UnpackSequence { size: arg },
+            UNPACK_EX => Instruction::UnpackEx {
+                before: arg & 0xff,
+                after: arg >> 8,
+            },
+            FOR_ITER => Instruction::ForIter {
+                target: self.label(relative_target),
+            },
+            BUILD_TUPLE | BUILD_TUPLE_UNPACK | BUILD_TUPLE_UNPACK_WITH_CALL => {
+                Instruction::BuildTuple {
+                    size: arg,
+                    unpack: instruction.opcode != BUILD_TUPLE,
+                    for_call: instruction.opcode == BUILD_TUPLE_UNPACK_WITH_CALL,
+                }
+            }
+            BUILD_LIST | BUILD_LIST_UNPACK => Instruction::BuildList {
+                size: arg,
+                unpack: instruction.opcode == BUILD_LIST_UNPACK,
+            },
+            BUILD_SET | BUILD_SET_UNPACK => Instruction::BuildSet {
+                size: arg,
+                unpack: instruction.opcode == BUILD_SET_UNPACK,
+            },
+            BUILD_MAP | BUILD_MAP_UNPACK | BUILD_MAP_UNPACK_WITH_CALL => Instruction::BuildMap {
+                size: arg,
+                unpack: instruction.opcode != BUILD_MAP,
+                for_call: instruction.opcode == BUILD_MAP_UNPACK_WITH_CALL,
+            },
+            BUILD_CONST_KEY_MAP => {
+                let keys = match self.take_constant("a tuple of keys before BUILD_CONST_KEY_MAP")? {
+                    Constant::Tuple { elements } if elements.len() == arg => elements,
+                    _ => return Err(PycError::Unexpected("a tuple of keys for every value")),
+                };
+                // Turn the values into dicts of one item from the last one on, moving each
+                // dict below the values left, and merge them
+                for (index, key) in keys.into_iter().enumerate().rev() {
+                    let load_key = self.load_const(key);
+                    self.emit(load_key);
+                    self.emit(Instruction::Rotate { amount: 2 });
+                    self.emit(Instruction::BuildMap {
+                        size: 1,
+                        unpack: false,
+                        for_call: false,
+                    });
+                    if index > 0 {
+                        self.emit(Instruction::Rotate { amount: index + 1 });
+                    }
+                }
+                if arg > 1 {
+                    self.emit(Instruction::Reverse { amount: arg });
+                }
+                Instruction::BuildMap {
+                    size: arg,
+                    unpack: true,
+                    for_call: false,
+                }
+            }
+            BUILD_SLICE => Instruction::BuildSlice { size: arg },
+            BUILD_STRING => Instruction::BuildString { size: arg },
+            LIST_APPEND => Instruction::ListAppend { i: arg },
+            SET_ADD => Instruction::SetAdd { i: arg },
+            MAP_ADD => Instruction::MapAdd { i: arg },
+            COMPARE_OP => Instruction::CompareOperation {
+                op: match arg {
+                    0 => ComparisonOperator::Less,
+                    1 => ComparisonOperator::LessOrEqual,
+                    2 => ComparisonOperator::Equal,
+                    3 => ComparisonOperator::NotEqual,
+                    4 => ComparisonOperator::Greater,
+                    5 => ComparisonOperator::GreaterOrEqual,
+                    6 => ComparisonOperator::In,
+                    7 => ComparisonOperator::NotIn,
+                    8 => ComparisonOperator::Is,
+                    9 => ComparisonOperator::IsNot,
+                    10 => ComparisonOperator::ExceptionMatch,
+                    _ => return Err(PycError::Unexpected("a valid comparison")),
+                },
+            },
+            IMPORT_NAME => {
+                let symbols_idx = match self.take_constant("a from-list before IMPORT_NAME")? {
+                    Constant::None => vec![],
+                    Constant::Tuple { elements } => elements
+                        .into_iter()
+                        .map(|element| match element {
+                            Constant::String { value } => Ok(self.add_name(&value)),
+                            _ => Err(PycError::Unexpected("a from-list of strings")),
+                        })
+                        .collect::<Result<_, _>>()?,
+                    _ => return Err(PycError::Unexpected("a from-list before IMPORT_NAME")),
+                };
+                let level = match self.take_constant("a level before IMPORT_NAME")? {
+                    Constant::Integer { value } => value
+                        .to_usize()
+                        .ok_or(PycError::Unexpected("a valid import level"))?,
+                    _ => return Err(PycError::Unexpected("a level before IMPORT_NAME")),
+                };
+                let name = self.name(arg)?;
+                Instruction::Import {
+                    name_idx: if name.is_empty() {
+                        None
+                    } else {
+                        Some(self.add_name(name))
+                    },
+                    symbols_idx: symbols_idx.into(),
+                    level,
+                }
+            }
+            IMPORT_FROM => Instruction::ImportFrom {
+                idx: self.add_name(self.name(arg)?),
+            },
+            JUMP_FORWARD => Instruction::Jump {
+                target: self.label(relative_target),
+            },
+            JUMP_ABSOLUTE => Instruction::Jump {
+                target: self.label(arg),
+            },
+            POP_JUMP_IF_FALSE => Instruction::JumpIfFalse {
+                target: self.label(arg),
+            },
+            POP_JUMP_IF_TRUE => Instruction::JumpIfTrue {
+                target: self.label(arg),
+            },
+            JUMP_IF_FALSE_OR_POP => Instruction::JumpIfFalseOrPop {
+                target: self.label(arg),
+            },
+            JUMP_IF_TRUE_OR_POP => Instruction::JumpIfTrueOrPop {
+                target: self.label(arg),
+            },
+            RAISE_VARARGS => Instruction::Raise { argc: arg },
+            CALL_FUNCTION | CALL_METHOD => Instruction::CallFunction {
+                typ: CallType::Positional(arg),
+            },
+            CALL_FUNCTION_KW => Instruction::CallFunction {
+                typ: CallType::Keyword(arg),
+            },
+            CALL_FUNCTION_EX => Instruction::CallFunction {
+                typ: CallType::Ex(arg & 1 != 0),
+            },
+            MAKE_FUNCTION => return self.make_function(arg),
+            FORMAT_VALUE => {
+                if arg & 4 == 0 {
+                    let load_empty = self.load_const(Constant::String {
+                        value: String::new(),
+                    });
+                    self.emit(load_empty);
+                }
+                Instruction::FormatValue {
+                    conversion: match arg & 3 {
+                        0 => None,
+                        1 => Some(ConversionFlag::Str),
+                        2 => Some(ConversionFlag::Repr),
+                        _ => Some(ConversionFlag::Ascii),
+                    },
+                }
+            }
+            GET_AITER | GET_ANEXT => return Err(self.untranslatable("async for")),
+            BEFORE_ASYNC_WITH | SETUP_ASYNC_WITH => return Err(self.untranslatable("async with")),
+            DELETE_GLOBAL => return Err(self.untranslatable("DELETE_GLOBAL")),
+            DELETE_DEREF => return Err(self.untranslatable("DELETE_DEREF")),
+            opcode => return Err(self.untranslatable(&format!("opcode {}", opcode))),
+        };
+        self.emit(translated);
+        Ok(())
+    }
+
+    /// RustPython's `MakeFunction` tells what it was given from the flags of the code object,
+    /// and doesn't take a closure.
+    fn make_function(&mut self, arg: usize) -> Result<(), PycError> {
+        let code_index = self
+            .instructions
+            .len()
+            .checked_sub(2)
+            .ok_or(PycError::Unexpected("a code object before MAKE_FUNCTION"))?;
+        let constant = match self.instructions[code_index] {
+            Instruction::LoadConst { idx } => Some(&mut self.translated.constants[idx]),
+            _ => None,
+        };
+        match constant {
+            Some(Constant::Code { code }) => {
+                if arg & 0x01 != 0 {
+                    code.flags |= CodeFlags::HAS_DEFAULTS;
+                }
+                if arg & 0x02 != 0 {
+                    code.flags |= CodeFlags::HAS_KW_ONLY_DEFAULTS;
+                }
+                if arg & 0x04 != 0 {
+                    code.flags |= CodeFlags::HAS_ANNOTATIONS;
+                }
+            }
+            _ => return Err(PycError::Unexpected("a code object before MAKE_FUNCTION")),
+        }
+        if arg & 0x08 != 0 {
+            // closure code qualname -> qualname closure code -> code qualname closure
+            self.emit(Instruction::Rotate { amount: 3 });
+            self.emit(Instruction::Rotate { amount: 3 });
+            self.emit(Instruction::Pop);
+        }
+        self.emit(Instruction::MakeFunction);
+        if let Some(doc) = self.docstrings.remove(&code_index) {
+            self.emit(Instruction::Duplicate);
+            let load_doc = self.load_const(Constant::String { value: doc });
+            self.emit(load_doc);
+            self.emit(Instruction::Rotate { amount: 2 });
+            let idx = self.add_name("__doc__");
+            self.emit(Instruction::StoreAttr { idx });
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<CodeObject, PycError> {
+        let mut translated = self.translated;
+        let code = self.code;
+        let mut positions = self.positions;
+        positions.insert(code.code.len(), self.instructions.len());
+        let mut label_map = HashMap::new();
+        for target in self.targets {
+            let position = positions
+                .get(&target)
+                .ok_or(PycError::Unexpected("jumps to the start of an instruction"))?;
+            label_map.insert(Label::new(target), *position);
+        }
+
+        let mut cache_slot_count = 0;
+        let cache_slots = self
+            .instructions
+            .iter()
+            .map(|instruction| {
+                if instruction.has_inline_cache() {
+                    cache_slot_count += 1;
+                    cache_slot_count - 1
+                } else {
+                    NO_CACHE_SLOT
+                }
+            })
+            .collect();
+
+        translated.instructions = self.instructions.into();
+        translated.label_map = label_map;
+        translated.line_table = LineTable::from_lines(self.lines);
+        translated.cache_slots = cache_slots;
+        // The operands of the instructions that take them from constants aren't loaded
+        translated.remove_unused_constants();
+        Ok(translated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pyc::write_pyc;
+
+    const MAGIC_37: u32 = 3394 | 0x0a0d << 16;
+    const MAGIC_38: u32 = 3413 | 0x0a0d << 16;
+
+    fn dis(code: &CodeObject) -> Vec<String> {
+        code.to_string()
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect()
+    }
+
+    fn function_code(code: &CodeObject) -> &CodeObject {
+        code.constants
+            .iter()
+            .find_map(|constant| match constant {
+                Constant::Code { code } => Some(code.as_ref()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    /// A module with just `code`, in a pyc file for `magic`.
+    fn module_pyc(magic: u32, code: Vec<u8>, consts: Vec<Value>, names: &[&str]) -> Vec<u8> {
+        let code = MarshalCode {
+            argcount: 0,
+            posonlyargcount: 0,
+            kwonlyargcount: 0,
+            stacksize: 2,
+            flags: 0x40,
+            code,
+            consts,
+            names: names.iter().map(|name| name.to_string()).collect(),
+            varnames: vec![],
+            freevars: vec![],
+            cellvars: vec![],
+            filename: "t.py".to_string(),
+            name: "<module>".to_string(),
+            qualname: "<module>".to_string(),
+            firstlineno: 1,
+            lnotab: vec![],
+        };
+        write_pyc(&code, magic, 0, 0).unwrap()
+    }
+
+    /// A module compiled by CPython 3.7:
+    ///
+    /// ```python
+    /// from os import path
+    ///
+    /// def f(a, b=2):
+    ///     "Doc."
+    ///     try:
+    ///         return {"a": a, "b": b}
+    ///     except KeyError as e:
+    ///         return e
+    /// ```
+    const CPYTHON_37_PYC: &[u8] = &[
+        0x42, 0x0d, 0x0d, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xe3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+        0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x73, 0x1a, 0x00, 0x00, 0x00, 0x64, 0x00, 0x64,
+        0x01, 0x6c, 0x00, 0x6d, 0x01, 0x5a, 0x01, 0x01, 0x00, 0x64, 0x06, 0x64, 0x03, 0x64, 0x04,
+        0x84, 0x01, 0x5a, 0x02, 0x64, 0x05, 0x53, 0x00, 0x29, 0x07, 0xe9, 0x00, 0x00, 0x00, 0x00,
+        0x29, 0x01, 0xda, 0x04, 0x70, 0x61, 0x74, 0x68, 0xe9, 0x02, 0x00, 0x00, 0x00, 0x63, 0x02,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00,
+        0x43, 0x00, 0x00, 0x00, 0x73, 0x32, 0x00, 0x00, 0x00, 0x79, 0x0a, 0x7c, 0x00, 0x7c, 0x01,
+        0x64, 0x01, 0x9c, 0x02, 0x53, 0x00, 0x04, 0x00, 0x74, 0x00, 0x6b, 0x0a, 0x72, 0x2c, 0x01,
+        0x00, 0x7d, 0x02, 0x01, 0x00, 0x7a, 0x04, 0x7c, 0x02, 0x53, 0x00, 0x64, 0x02, 0x7d, 0x02,
+        0x7e, 0x02, 0x58, 0x00, 0x59, 0x00, 0x6e, 0x02, 0x58, 0x00, 0x64, 0x02, 0x53, 0x00, 0x29,
+        0x03, 0x7a, 0x04, 0x44, 0x6f, 0x63, 0x2e, 0x29, 0x02, 0xda, 0x01, 0x61, 0xda, 0x01, 0x62,
+        0x4e, 0x29, 0x01, 0xda, 0x08, 0x4b, 0x65, 0x79, 0x45, 0x72, 0x72, 0x6f, 0x72, 0x29, 0x03,
+        0x72, 0x04, 0x00, 0x00, 0x00, 0x72, 0x05, 0x00, 0x00, 0x00, 0xda, 0x01, 0x65, 0xa9, 0x00,
+        0x72, 0x08, 0x00, 0x00, 0x00, 0xfa, 0x04, 0x74, 0x2e, 0x70, 0x79, 0xda, 0x01, 0x66, 0x03,
+        0x00, 0x00, 0x00, 0x73, 0x08, 0x00, 0x00, 0x00, 0x00, 0x02, 0x02, 0x01, 0x0a, 0x01, 0x10,
+        0x01, 0x72, 0x0a, 0x00, 0x00, 0x00, 0x4e, 0x29, 0x01, 0x72, 0x03, 0x00, 0x00, 0x00, 0x29,
+        0x03, 0xda, 0x02, 0x6f, 0x73, 0x72, 0x02, 0x00, 0x00, 0x00, 0x72, 0x0a, 0x00, 0x00, 0x00,
+        0x72, 0x08, 0x00, 0x00, 0x00, 0x72, 0x08, 0x00, 0x00, 0x00, 0x72, 0x08, 0x00, 0x00, 0x00,
+        0x72, 0x09, 0x00, 0x00, 0x00, 0xda, 0x08, 0x3c, 0x6d, 0x6f, 0x64, 0x75, 0x6c, 0x65, 0x3e,
+        0x01, 0x00, 0x00, 0x00, 0x73, 0x02, 0x00, 0x00, 0x00, 0x0c, 0x02,
+    ];
+
+    #[test]
+    fn test_translate_cpython_pyc() {
+        let code = translate_pyc(CPYTHON_37_PYC).unwrap();
+        assert_eq!(code.source_path, "t.py");
+        assert_eq!(
+            dis(&code),
+            [
+                "1 0 Import (Some(\"os\"), [\"path\"], 0)",
+                "1 ImportFrom (path)",
+                "2 StoreName (path, Free)",
+                "3 Pop",
+                "3 4 LoadConst ((2))",
+                "5 LoadConst (<code object f at ??? file \"t.py\", line 3>)",
+                "6 LoadConst (\"f\")",
+                "7 MakeFunction",
+                "8 Duplicate",
+                "9 LoadConst (\"Doc.\")",
+                "10 Rotate (2)",
+                "11 StoreAttr (__doc__)",
+                "12 StoreName (f, Free)",
+                "13 LoadConst (None)",
+                "14 ReturnValue",
+            ]
+        );
+
+        let function = function_code(&code);
+        assert_eq!(function.arg_names, vec!["a", "b"]);
+        assert_eq!(
+            function.flags,
+            CodeFlags::NEW_LOCALS | CodeFlags::HAS_DEFAULTS
+        );
+        assert_eq!(
+            dis(function),
+            [
+                "5 0 SetupExcept (13)",
+                "6 1 LoadName (a, Free)",
+                "2 LoadName (b, Free)",
+                "3 LoadConst (\"b\")",
+                "4 Rotate (2)",
+                "5 BuildMap (1, false, false)",
+                "6 Rotate (2)",
+                "7 LoadConst (\"a\")",
+                "8 Rotate (2)",
+                "9 BuildMap (1, false, false)",
+                "10 Reverse (2)",
+                "11 BuildMap (2, true, false)",
+                "12 ReturnValue",
+                "7 >> 13 LoadConst (None)",
+                "14 Rotate (2)",
+                "15 Duplicate",
+                "16 Duplicate",
+                "17 LoadName (KeyError, Global)",
+                "18 CompareOperation (ExceptionMatch)",
+                "19 JumpIfFalse (32)",
+                "20 Pop",
+                "21 StoreName (e, Free)",
+                "22 Pop",
+                "23 SetupFinally (26)",
+                "8 24 LoadName (e, Free)",
+                "25 ReturnValue",
+                ">> 26 LoadConst (None)",
+                "27 StoreName (e, Free)",
+                "28 DeleteName (e)",
+                "29 EndFinally",
+                "30 PopException",
+                "31 Jump (33)",
+                ">> 32 Raise (0)",
+                ">> 33 LoadConst (None)",
+                "34 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_untranslatable() {
+        // DELETE_GLOBAL x; LOAD_CONST None; RETURN_VALUE
+        let data = module_pyc(
+            MAGIC_37,
+            vec![98, 0, 100, 0, 83, 0],
+            vec![Value::None],
+            &["x"],
+        );
+        assert_eq!(
+            translate_pyc(&data),
+            Err(PycError::Untranslatable(
+                "DELETE_GLOBAL in <module>".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_only_python_37() {
+        // LOAD_CONST None; RETURN_VALUE
+        let data = module_pyc(MAGIC_38, vec![100, 0, 83, 0], vec![Value::None], &[]);
+        assert_eq!(
+            translate_pyc(&data),
+            Err(PycError::UnsupportedMagic(MAGIC_38))
+        );
+        let data = module_pyc(MAGIC_37, vec![100, 0, 83, 0], vec![Value::None], &[]);
+        let code = translate_pyc(&data).unwrap();
+        assert_eq!(
+            code.instructions,
+            vec![Instruction::LoadConst { idx: 0 }, Instruction::ReturnValue]
+        );
+        assert_eq!(code.constants, vec![Constant::None]);
+    }
+}
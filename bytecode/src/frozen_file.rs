@@ -0,0 +1,399 @@
+//! A file format for a set of frozen modules, for build scripts that freeze Python code without
+//! `py_compile_bytecode!`: the script writes the modules to `$OUT_DIR` with `write`, the crate
+//! `include_bytes!`es the file and gets the modules back with `read`.
+//!
+//! ```ignore
+//! static FROZEN: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/frozen.rpfrozen"));
+//! let modules = rustpython_vm::frozen_file::read(FROZEN).unwrap();
+//! ```
+//!
+//! The file is a header, the bytecode `FORMAT_VERSION` and the number of modules, followed by
+//! the modules. The code objects, sources and resources of the modules `read` returns borrow
+//! from the data, so nothing is copied or deserialized before a module is imported.
+//!
+//! All integers are little endian `u32`s, and strings and byte strings are prefixed with their
+//! length.
+
+use crate::bytecode::{FrozenCode, FrozenModule, FrozenSource, FORMAT_VERSION};
+use std::borrow::Cow;
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, Write};
+use std::str;
+
+const HEADER: &[u8] = b"RPFROZEN";
+
+const PACKAGE: u8 = 0x01;
+const CODE_COMPRESSED: u8 = 0x02;
+const HAS_SOURCE_PATH: u8 = 0x04;
+const HAS_SOURCE: u8 = 0x08;
+const SOURCE_COMPRESSED: u8 = 0x10;
+const NAMESPACE: u8 = 0x20;
+
+#[derive(Debug, PartialEq)]
+pub enum ReadError {
+    /// The data doesn't start with the header `write` puts in front of the modules.
+    NotFrozenModules,
+    /// The modules were written with another version of the bytecode format.
+    FormatVersion(u32),
+    /// The data ended in the middle of a module.
+    UnexpectedEof,
+    /// A module name, source path or source is not valid UTF-8.
+    InvalidUtf8,
+    /// The modules were written compressed, and decompressing them isn't supported on wasm.
+    Compressed,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::NotFrozenModules => write!(f, "not a file of frozen modules"),
+            ReadError::FormatVersion(version) => write!(
+                f,
+                "the frozen modules have bytecode format version {}, but this build of \
+                 RustPython uses version {}",
+                version, FORMAT_VERSION
+            ),
+            ReadError::UnexpectedEof => write!(f, "unexpected end of data"),
+            ReadError::InvalidUtf8 => write!(f, "invalid utf-8 string"),
+            ReadError::Compressed => write!(f, "compressed frozen modules aren't supported"),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// Write `modules` in the format `read` reads. With `compress` the code objects and sources
+/// are deflated, like `py_compile_bytecode!` does with `compress = true`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write<W: Write>(
+    modules: &[(String, FrozenModule)],
+    compress: bool,
+    mut out: W,
+) -> io::Result<()> {
+    out.write_all(HEADER)?;
+    write_u32(&mut out, FORMAT_VERSION)?;
+    write_len(&mut out, modules.len())?;
+    for (name, module) in modules {
+        let code = match (&module.code, compress) {
+            (FrozenCode::Serialized(bytes), false) | (FrozenCode::Compressed(bytes), true) => {
+                Cow::Borrowed(*bytes)
+            }
+            (code, false) => Cow::Owned(code.decode().to_bytes()),
+            (code, true) => Cow::Owned(code.decode().to_compressed_bytes()),
+        };
+        let source = module
+            .source
+            .as_ref()
+            .map(|source| match (source, compress) {
+                (FrozenSource::Compressed(bytes), true) => Cow::Borrowed(*bytes),
+                (source, true) => Cow::Owned(FrozenSource::compress(&source.decode())),
+                (source, false) => match source.decode() {
+                    Cow::Borrowed(text) => Cow::Borrowed(text.as_bytes()),
+                    Cow::Owned(text) => Cow::Owned(text.into_bytes()),
+                },
+            });
+
+        let mut flags = 0;
+        if module.package {
+            flags |= PACKAGE;
+        }
+        if module.namespace {
+            flags |= NAMESPACE;
+        }
+        if compress {
+            flags |= CODE_COMPRESSED;
+        }
+        if module.source_path.is_some() {
+            flags |= HAS_SOURCE_PATH;
+        }
+        if source.is_some() {
+            flags |= HAS_SOURCE;
+            if compress {
+                flags |= SOURCE_COMPRESSED;
+            }
+        }
+
+        write_bytes(&mut out, name.as_bytes())?;
+        out.write_all(&[flags])?;
+        if let Some(source_path) = &module.source_path {
+            write_bytes(&mut out, source_path.as_bytes())?;
+        }
+        if let Some(source) = &source {
+            write_bytes(&mut out, source)?;
+        }
+        write_bytes(&mut out, &code)?;
+        // sorted, so that the same modules are always written the same way
+        let mut resources: Vec<_> = module.resources.iter().collect();
+        resources.sort_by(|a, b| a.0.cmp(b.0));
+        write_len(&mut out, resources.len())?;
+        for (name, data) in resources {
+            write_bytes(&mut out, name.as_bytes())?;
+            write_bytes(&mut out, data)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_u32<W: Write>(out: &mut W, value: u32) -> io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_len<W: Write>(out: &mut W, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "too large for a file of frozen modules",
+        )
+    })?;
+    write_u32(out, len)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_bytes<W: Write>(out: &mut W, bytes: &[u8]) -> io::Result<()> {
+    write_len(out, bytes.len())?;
+    out.write_all(bytes)
+}
+
+/// Read the modules `write` wrote, in the order they were written in.
+pub fn read(data: &'static [u8]) -> Result<Vec<(String, FrozenModule)>, ReadError> {
+    let mut reader = Reader { data };
+    if reader.take(HEADER.len()) != Ok(HEADER) {
+        return Err(ReadError::NotFrozenModules);
+    }
+    let version = reader.read_u32()?;
+    if version != FORMAT_VERSION {
+        return Err(ReadError::FormatVersion(version));
+    }
+    let count = reader.read_u32()?;
+    let mut modules = Vec::new();
+    for _ in 0..count {
+        let name = reader.read_str()?.to_owned();
+        let flags = reader.take(1)?[0];
+        let source_path = if flags & HAS_SOURCE_PATH != 0 {
+            Some(reader.read_str()?.to_owned())
+        } else {
+            None
+        };
+        let source = if flags & HAS_SOURCE == 0 {
+            None
+        } else if flags & SOURCE_COMPRESSED != 0 {
+            Some(compressed_source(reader.read_bytes()?)?)
+        } else {
+            Some(FrozenSource::Text(Cow::Borrowed(reader.read_str()?)))
+        };
+        let code = reader.read_bytes()?;
+        let code = if flags & CODE_COMPRESSED != 0 {
+            compressed_code(code)?
+        } else {
+            FrozenCode::Serialized(code)
+        };
+        let mut resources = HashMap::new();
+        for _ in 0..reader.read_u32()? {
+            let name = reader.read_str()?.to_owned();
+            resources.insert(name, Cow::Borrowed(reader.read_bytes()?));
+        }
+        modules.push((
+            name,
+            FrozenModule {
+                code,
+                package: flags & PACKAGE != 0,
+                namespace: flags & NAMESPACE != 0,
+                source,
+                source_path,
+                resources,
+            },
+        ));
+    }
+    Ok(modules)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn compressed_code(bytes: &'static [u8]) -> Result<FrozenCode, ReadError> {
+    Ok(FrozenCode::Compressed(bytes))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn compressed_code(_bytes: &'static [u8]) -> Result<FrozenCode, ReadError> {
+    Err(ReadError::Compressed)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn compressed_source(bytes: &'static [u8]) -> Result<FrozenSource, ReadError> {
+    Ok(FrozenSource::Compressed(bytes))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn compressed_source(_bytes: &'static [u8]) -> Result<FrozenSource, ReadError> {
+    Err(ReadError::Compressed)
+}
+
+struct Reader {
+    data: &'static [u8],
+}
+
+impl Reader {
+    fn take(&mut self, len: usize) -> Result<&'static [u8], ReadError> {
+        if self.data.len() < len {
+            return Err(ReadError::UnexpectedEof);
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ReadError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_bytes(&mut self) -> Result<&'static [u8], ReadError> {
+        let len = self.read_u32()? as usize;
+        self.take(len)
+    }
+
+    fn read_str(&mut self) -> Result<&'static str, ReadError> {
+        str::from_utf8(self.read_bytes()?).map_err(|_| ReadError::InvalidUtf8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::{CodeFlags, CodeObject, Constant, Instruction, Varargs};
+
+    fn code(value: &str) -> CodeObject {
+        let mut code = CodeObject::new(
+            CodeFlags::default(),
+            0,
+            vec![],
+            Varargs::None,
+            vec![],
+            Varargs::None,
+            "frozen".to_owned(),
+            1,
+            "<module>".to_owned(),
+        );
+        code.constants = vec![Constant::String {
+            value: value.to_owned(),
+        }];
+        code.instructions =
+            vec![Instruction::LoadConst { idx: 0 }, Instruction::ReturnValue].into();
+        code
+    }
+
+    fn modules() -> Vec<(String, FrozenModule)> {
+        let mut resources = HashMap::new();
+        resources.insert("b.txt".to_owned(), Cow::Owned(b"b".to_vec()));
+        resources.insert("a.bin".to_owned(), Cow::Borrowed(&[0, 255][..]));
+        vec![
+            (
+                "pkg".to_owned(),
+                FrozenModule {
+                    code: code("pkg").into(),
+                    package: true,
+                    namespace: false,
+                    source: Some(FrozenSource::Text("'pkg'\n".into())),
+                    source_path: Some("pkg/__init__.py".to_owned()),
+                    resources,
+                },
+            ),
+            (
+                "pkg.mod".to_owned(),
+                FrozenModule {
+                    code: code("pkg.mod").into(),
+                    package: false,
+                    namespace: false,
+                    source: None,
+                    source_path: None,
+                    resources: HashMap::new(),
+                },
+            ),
+            (
+                "ns".to_owned(),
+                FrozenModule {
+                    code: code("ns").into(),
+                    package: true,
+                    namespace: true,
+                    source: None,
+                    source_path: None,
+                    resources: HashMap::new(),
+                },
+            ),
+        ]
+    }
+
+    fn round_trip(compress: bool) -> Vec<(String, FrozenModule)> {
+        let mut data = Vec::new();
+        write(&modules(), compress, &mut data).unwrap();
+        read(Box::leak(data.into_boxed_slice())).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &compress in &[false, true] {
+            let modules = round_trip(compress);
+            let names: Vec<_> = modules.iter().map(|(name, _)| name.as_str()).collect();
+            assert_eq!(names, ["pkg", "pkg.mod", "ns"]);
+
+            let pkg = &modules[0].1;
+            assert!(pkg.package);
+            assert!(!pkg.namespace);
+            assert!(pkg.code.decode() == code("pkg"));
+            assert_eq!(pkg.source.as_ref().unwrap().decode(), "'pkg'\n");
+            assert_eq!(pkg.source_path.as_ref().unwrap(), "pkg/__init__.py");
+            assert_eq!(pkg.resources.len(), 2);
+            assert_eq!(&pkg.resources["a.bin"][..], &[0, 255]);
+            assert_eq!(&pkg.resources["b.txt"][..], b"b");
+
+            let module = &modules[1].1;
+            assert!(!module.package);
+            assert!(!module.namespace);
+            assert!(module.code.decode() == code("pkg.mod"));
+            assert!(module.source.is_none());
+            assert!(module.source_path.is_none());
+            assert!(module.resources.is_empty());
+
+            let namespace = &modules[2].1;
+            assert!(namespace.package && namespace.namespace);
+
+            match (&pkg.code, compress) {
+                (FrozenCode::Serialized(_), false) | (FrozenCode::Compressed(_), true) => {}
+                _ => panic!("the code should be read as it was written"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let write_modules = || {
+            let mut data = Vec::new();
+            write(&modules(), false, &mut data).unwrap();
+            data
+        };
+        assert_eq!(write_modules(), write_modules());
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert_eq!(read(b"").err(), Some(ReadError::NotFrozenModules));
+        assert_eq!(read(b"RPFROZEX").err(), Some(ReadError::NotFrozenModules));
+        assert_eq!(
+            read(b"RPFROZEN\x01\0\0\0\0\0\0\0").err(),
+            Some(ReadError::FormatVersion(1))
+        );
+
+        let mut data = Vec::new();
+        write(&modules(), false, &mut data).unwrap();
+        data.pop();
+        let data = Box::leak(data.into_boxed_slice());
+        assert_eq!(read(data).err(), Some(ReadError::UnexpectedEof));
+    }
+}
@@ -2,3 +2,8 @@
 #![doc(html_root_url = "https://docs.rs/rustpython-bytecode/")]
 
 pub mod bytecode;
+pub mod compact;
+pub mod cpython;
+pub mod frozen_file;
+pub mod pyc;
+pub mod static_code;
@@ -0,0 +1,1153 @@
+//! Read and write CPython-style `.pyc` files.
+//!
+//! A pyc file is a 16 byte header (see PEP 552) followed by a code object in CPython's `marshal`
+//! format. RustPython instructions have no CPython equivalent, so every code object is written
+//! with a small stub as `co_code` (which just returns `None`), while constants, names, argument
+//! names, flags and source information are mapped onto their CPython counterparts. This is
+//! enough for tools like `marshal`, `dis.show_code` or `importlib` to load and inspect the file.
+//!
+//! The top-level code object additionally carries the exact RustPython bytecode as its last
+//! constant, so `from_pyc` can restore the original `CodeObject`.
+
+use crate::bytecode::{CodeFlags, CodeObject, Constant, Varargs, FORMAT_VERSION};
+use num_bigint::{BigInt, Sign};
+use num_complex::Complex64;
+use num_traits::ToPrimitive;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+const TYPE_NULL: u8 = b'0';
+const TYPE_NONE: u8 = b'N';
+const TYPE_FALSE: u8 = b'F';
+const TYPE_TRUE: u8 = b'T';
+const TYPE_STOPITER: u8 = b'S';
+const TYPE_ELLIPSIS: u8 = b'.';
+const TYPE_INT: u8 = b'i';
+const TYPE_FLOAT: u8 = b'f';
+const TYPE_BINARY_FLOAT: u8 = b'g';
+const TYPE_COMPLEX: u8 = b'x';
+const TYPE_BINARY_COMPLEX: u8 = b'y';
+const TYPE_LONG: u8 = b'l';
+const TYPE_STRING: u8 = b's';
+const TYPE_INTERNED: u8 = b't';
+const TYPE_REF: u8 = b'r';
+const TYPE_TUPLE: u8 = b'(';
+const TYPE_LIST: u8 = b'[';
+const TYPE_DICT: u8 = b'{';
+const TYPE_CODE: u8 = b'c';
+const TYPE_UNICODE: u8 = b'u';
+const TYPE_SET: u8 = b'<';
+const TYPE_FROZENSET: u8 = b'>';
+const TYPE_ASCII: u8 = b'a';
+const TYPE_ASCII_INTERNED: u8 = b'A';
+const TYPE_SMALL_TUPLE: u8 = b')';
+const TYPE_SHORT_ASCII: u8 = b'z';
+const TYPE_SHORT_ASCII_INTERNED: u8 = b'Z';
+const FLAG_REF: u8 = 0x80;
+
+const CO_NEWLOCALS: u32 = 0x0002;
+const CO_VARARGS: u32 = 0x0004;
+const CO_VARKEYWORDS: u32 = 0x0008;
+const CO_GENERATOR: u32 = 0x0020;
+const CO_COROUTINE: u32 = 0x0080;
+const CO_FUTURE_ANNOTATIONS: u32 = 0x0100_0000;
+
+/// `localspluskinds` value for a plain local variable (CPython 3.11+).
+const CO_FAST_LOCAL: u8 = 0x20;
+
+/// Marker in front of the serialized RustPython code stored in the top-level code object.
+const RUSTPYTHON_CODE_MARKER: &[u8] = b"rustpython-bytecode\0";
+
+/// The magic number of the pyc files RustPython caches modules in, and of its `marshal`. It's in
+/// the range of CPython 3.8, whose layout the files use, offset by `FORMAT_VERSION` so that files
+/// written with another bytecode format are recompiled rather than misread.
+pub const MAGIC_NUMBER: u32 = (3410 + FORMAT_VERSION) | 0x0a0d << 16;
+
+/// The layout of marshalled code objects, which changes between CPython versions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CodeLayout {
+    /// CPython 3.7
+    Python37,
+    /// CPython 3.8 to 3.10, which added `co_posonlyargcount`.
+    Python38,
+    /// CPython 3.11 and 3.12, which merged all local names into `co_localsplusnames`.
+    Python311,
+}
+
+impl CodeLayout {
+    pub(crate) fn from_magic(magic: u32) -> Result<Self, PycError> {
+        if magic >> 16 != 0x0a0d {
+            return Err(PycError::UnsupportedMagic(magic));
+        }
+        match magic & 0xffff {
+            3390..=3409 => Ok(CodeLayout::Python37),
+            3410..=3439 => Ok(CodeLayout::Python38),
+            3450..=3549 => Ok(CodeLayout::Python311),
+            _ => Err(PycError::UnsupportedMagic(magic)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PycError {
+    /// The magic number does not belong to a CPython version whose format we know.
+    UnsupportedMagic(u32),
+    /// A value does not fit in the field CPython uses for it.
+    TooLarge(&'static str),
+    /// The data ended in the middle of a value.
+    UnexpectedEof,
+    /// Reading the data failed.
+    Io(String),
+    /// An unknown marshal type code was found.
+    InvalidType(u8),
+    /// A back reference points to an object that was not read yet.
+    InvalidReference(u32),
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// The data is valid marshal data, but not in the shape we expected.
+    Unexpected(&'static str),
+    /// The file was not written by `to_pyc`, so there is no RustPython code in it.
+    NotRustPythonCode,
+    /// The embedded RustPython code could not be deserialized.
+    InvalidBytecode(String),
+    /// The CPython bytecode uses something `cpython::translate_pyc` can't translate.
+    Untranslatable(String),
+}
+
+impl fmt::Display for PycError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PycError::UnsupportedMagic(magic) => write!(f, "unsupported magic number {:#x}", magic),
+            PycError::TooLarge(what) => write!(f, "{} is too large for a pyc file", what),
+            PycError::UnexpectedEof => write!(f, "unexpected end of data"),
+            PycError::Io(err) => write!(f, "reading failed: {}", err),
+            PycError::InvalidType(code) => {
+                write!(f, "bad marshal data (unknown type code {:#x})", code)
+            }
+            PycError::InvalidReference(index) => {
+                write!(f, "bad marshal data (invalid reference {})", index)
+            }
+            PycError::InvalidUtf8 => write!(f, "bad marshal data (invalid utf-8 string)"),
+            PycError::Unexpected(what) => write!(f, "bad marshal data (expected {})", what),
+            PycError::NotRustPythonCode => {
+                write!(f, "pyc file does not contain RustPython bytecode")
+            }
+            PycError::InvalidBytecode(err) => write!(f, "invalid RustPython bytecode: {}", err),
+            PycError::Untranslatable(what) => {
+                write!(f, "cannot translate CPython bytecode: {}", what)
+            }
+        }
+    }
+}
+
+impl Error for PycError {}
+
+/// A value in CPython's marshal format.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    None,
+    StopIteration,
+    Ellipsis,
+    Bool(bool),
+    Int(BigInt),
+    Float(f64),
+    Complex(Complex64),
+    Bytes(Vec<u8>),
+    Str(String),
+    Tuple(Vec<Value>),
+    List(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+    Set(Vec<Value>),
+    FrozenSet(Vec<Value>),
+    Code(Box<MarshalCode>),
+}
+
+/// A marshalled code object. Fields that a CPython version doesn't have are left empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarshalCode {
+    pub argcount: u32,
+    pub posonlyargcount: u32,
+    pub kwonlyargcount: u32,
+    pub stacksize: u32,
+    pub flags: u32,
+    pub code: Vec<u8>,
+    pub consts: Vec<Value>,
+    pub names: Vec<String>,
+    /// `co_varnames`, or `co_localsplusnames` for CPython 3.11+.
+    pub varnames: Vec<String>,
+    pub freevars: Vec<String>,
+    pub cellvars: Vec<String>,
+    pub filename: String,
+    pub name: String,
+    pub qualname: String,
+    pub firstlineno: u32,
+    /// `co_lnotab`, or `co_linetable` for CPython 3.10+, which has a format of its own.
+    pub lnotab: Vec<u8>,
+}
+
+/// The contents of a pyc file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pyc {
+    pub magic: u32,
+    /// PEP 552 flags. When bit 0 is set, `mtime` and `size` hold the source hash instead.
+    pub flags: u32,
+    pub mtime: u32,
+    pub size: u32,
+    pub code: MarshalCode,
+}
+
+/// Write `code` as a pyc file for the CPython version identified by `magic`.
+///
+/// `magic` is the first four bytes of the file read as a little-endian integer, i.e.
+/// `int.from_bytes(importlib.util.MAGIC_NUMBER, 'little')`.
+pub fn to_pyc(code: &CodeObject, magic: u32, mtime: u32, size: u32) -> Result<Vec<u8>, PycError> {
+    let marshal_code = to_marshal_code_with_bytecode(code, CodeLayout::from_magic(magic)?)?;
+    write_pyc(&marshal_code, magic, mtime, size)
+}
+
+/// Write `code` like `marshal.dumps` of the CPython version identified by `magic` would, which is
+/// the part of `to_pyc` after the header.
+pub fn dump_code(code: &CodeObject, magic: u32) -> Result<Vec<u8>, PycError> {
+    let code = MarshalCode::from_code(code, magic)?;
+    dump_value(&Value::Code(Box::new(code)), magic)
+}
+
+/// Restore the RustPython code object from the output of `dump_code` for the same `magic`.
+pub fn load_code(data: &[u8], magic: u32) -> Result<CodeObject, PycError> {
+    match load_value(data, magic)?.0 {
+        Value::Code(code) => code.to_code(),
+        _ => Err(PycError::Unexpected("a code object")),
+    }
+}
+
+/// Write `value` like `marshal.dumps` of the CPython version identified by `magic` would.
+pub fn dump_value(value: &Value, magic: u32) -> Result<Vec<u8>, PycError> {
+    let mut output = Vec::new();
+    write_value(&mut output, value, CodeLayout::from_magic(magic)?)?;
+    Ok(output)
+}
+
+/// Read the value at the start of `data`, in the format of `dump_value` for the same `magic`,
+/// along with the number of bytes it takes up. Like `marshal.loads`, this ignores whatever
+/// follows it.
+pub fn load_value(data: &[u8], magic: u32) -> Result<(Value, usize), PycError> {
+    let mut reader = Reader::new(data, CodeLayout::from_magic(magic)?);
+    let value = reader.read_value()?;
+    Ok((value, reader.position))
+}
+
+/// Read a value like `load_value`, from `source`. Only the bytes of the value are read from it,
+/// like `marshal.load` does with a file.
+pub fn read_value<R: Read>(source: R, magic: u32) -> Result<Value, PycError> {
+    Reader::new(source, CodeLayout::from_magic(magic)?).read_value()
+}
+
+impl MarshalCode {
+    /// `code` for the CPython version identified by `magic`, carrying the RustPython bytecode
+    /// like the code objects of `to_pyc` and `dump_code` do.
+    pub fn from_code(code: &CodeObject, magic: u32) -> Result<Self, PycError> {
+        to_marshal_code_with_bytecode(code, CodeLayout::from_magic(magic)?)
+    }
+
+    /// The RustPython code object `from_code` embedded.
+    pub fn to_code(&self) -> Result<CodeObject, PycError> {
+        embedded_code(self)
+    }
+}
+
+fn to_marshal_code_with_bytecode(
+    code: &CodeObject,
+    layout: CodeLayout,
+) -> Result<MarshalCode, PycError> {
+    let mut marshal_code = to_marshal_code(code, layout)?;
+    let mut rustpython_code = RUSTPYTHON_CODE_MARKER.to_vec();
+    rustpython_code.extend(code.to_bytes());
+    marshal_code.consts.push(Value::Bytes(rustpython_code));
+    Ok(marshal_code)
+}
+
+/// Write a pyc file with `code` as it is.
+pub(crate) fn write_pyc(
+    code: &MarshalCode,
+    magic: u32,
+    mtime: u32,
+    size: u32,
+) -> Result<Vec<u8>, PycError> {
+    let layout = CodeLayout::from_magic(magic)?;
+    let mut output = Vec::new();
+    output.extend_from_slice(&magic.to_le_bytes());
+    output.extend_from_slice(&0u32.to_le_bytes());
+    output.extend_from_slice(&mtime.to_le_bytes());
+    output.extend_from_slice(&size.to_le_bytes());
+    write_code(&mut output, code, layout)?;
+    Ok(output)
+}
+
+/// Parse a pyc file, which may have been written by CPython 3.7 to 3.12 or by `to_pyc`.
+pub fn read_pyc(data: &[u8]) -> Result<Pyc, PycError> {
+    let mut reader = Reader::new(data, CodeLayout::Python37);
+    let magic = reader.read_u32()?;
+    reader.layout = CodeLayout::from_magic(magic)?;
+    let flags = reader.read_u32()?;
+    let mtime = reader.read_u32()?;
+    let size = reader.read_u32()?;
+    match reader.read_value()? {
+        Value::Code(code) => Ok(Pyc {
+            magic,
+            flags,
+            mtime,
+            size,
+            code: *code,
+        }),
+        _ => Err(PycError::Unexpected("a code object")),
+    }
+}
+
+/// Restore the RustPython code object from a pyc file written by `to_pyc`.
+pub fn from_pyc(data: &[u8]) -> Result<CodeObject, PycError> {
+    embedded_code(&read_pyc(data)?.code)
+}
+
+fn embedded_code(code: &MarshalCode) -> Result<CodeObject, PycError> {
+    match code.consts.last() {
+        Some(Value::Bytes(bytes)) if bytes.starts_with(RUSTPYTHON_CODE_MARKER) => {
+            CodeObject::from_bytes(&bytes[RUSTPYTHON_CODE_MARKER.len()..])
+                .map_err(|err| PycError::InvalidBytecode(err.to_string()))
+        }
+        _ => Err(PycError::NotRustPythonCode),
+    }
+}
+
+fn to_marshal_code(code: &CodeObject, layout: CodeLayout) -> Result<MarshalCode, PycError> {
+    let mut consts = code
+        .constants
+        .iter()
+        .map(|constant| to_marshal_value(constant, layout))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut flags = 0;
+    if code.flags.contains(CodeFlags::NEW_LOCALS) {
+        flags |= CO_NEWLOCALS;
+    }
+    if code.flags.contains(CodeFlags::IS_GENERATOR) {
+        flags |= CO_GENERATOR;
+    }
+    if code.flags.contains(CodeFlags::IS_COROUTINE) {
+        flags |= CO_COROUTINE;
+    }
+    if code.flags.contains(CodeFlags::FUTURE_ANNOTATIONS) {
+        flags |= CO_FUTURE_ANNOTATIONS;
+    }
+    let mut varnames = code.arg_names.clone();
+    varnames.extend(code.kwonlyarg_names.iter().cloned());
+    if let Varargs::Named(name) = &code.varargs {
+        flags |= CO_VARARGS;
+        varnames.push(name.clone());
+    }
+    if let Varargs::Named(name) = &code.varkeywords {
+        flags |= CO_VARKEYWORDS;
+        varnames.push(name.clone());
+    }
+
+    // The stub returns `None`, so make sure there is one to load.
+    let none_index = match consts.iter().position(|value| *value == Value::None) {
+        Some(index) => index,
+        None => {
+            consts.push(Value::None);
+            consts.len() - 1
+        }
+    };
+
+    Ok(MarshalCode {
+        argcount: to_u32(code.arg_names.len(), "argument count")?,
+        posonlyargcount: to_u32(code.posonlyarg_count, "positional-only argument count")?,
+        kwonlyargcount: to_u32(code.kwonlyarg_names.len(), "keyword-only argument count")?,
+        stacksize: 1,
+        flags,
+        code: stub_code(none_index, layout)?,
+        consts,
+        names: code.names.clone(),
+        varnames,
+        freevars: vec![],
+        cellvars: vec![],
+        filename: code.source_path.clone(),
+        name: code.obj_name.clone(),
+        qualname: code.obj_name.clone(),
+        firstlineno: to_u32(code.first_line_number, "line number")?,
+        lnotab: vec![],
+    })
+}
+
+/// CPython bytecode for `return consts[none_index]`.
+fn stub_code(none_index: usize, layout: CodeLayout) -> Result<Vec<u8>, PycError> {
+    const RESUME: u8 = 151;
+    const LOAD_CONST: u8 = 100;
+    const RETURN_VALUE: u8 = 83;
+    const EXTENDED_ARG: u8 = 144;
+
+    let mut code = Vec::new();
+    if layout == CodeLayout::Python311 {
+        code.extend_from_slice(&[RESUME, 0]);
+    }
+    let index = to_u32(none_index, "constant count")?;
+    for shift in &[24u32, 16, 8] {
+        if index >> shift != 0 {
+            code.extend_from_slice(&[EXTENDED_ARG, (index >> shift) as u8]);
+        }
+    }
+    code.extend_from_slice(&[LOAD_CONST, index as u8, RETURN_VALUE, 0]);
+    Ok(code)
+}
+
+fn to_marshal_value(constant: &Constant, layout: CodeLayout) -> Result<Value, PycError> {
+    Ok(match constant {
+        Constant::Integer { value } => Value::Int(value.clone()),
+        Constant::Float { value } => Value::Float(*value),
+        Constant::Complex { value } => Value::Complex(*value),
+        Constant::Boolean { value } => Value::Bool(*value),
+        Constant::String { value } => Value::Str(value.clone()),
+        Constant::Bytes { value } => Value::Bytes(value.clone()),
+        Constant::Code { code } => Value::Code(Box::new(to_marshal_code(code, layout)?)),
+        Constant::Tuple { elements } => Value::Tuple(
+            elements
+                .iter()
+                .map(|element| to_marshal_value(element, layout))
+                .collect::<Result<_, _>>()?,
+        ),
+        Constant::None => Value::None,
+        Constant::Ellipsis => Value::Ellipsis,
+    })
+}
+
+fn to_u32(value: usize, what: &'static str) -> Result<u32, PycError> {
+    u32::try_from(value)
+        .ok()
+        .filter(|value| *value <= i32::max_value() as u32)
+        .ok_or(PycError::TooLarge(what))
+}
+
+fn write_u32(output: &mut Vec<u8>, value: u32) {
+    output.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_size(output: &mut Vec<u8>, size: usize, what: &'static str) -> Result<(), PycError> {
+    write_u32(output, to_u32(size, what)?);
+    Ok(())
+}
+
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) -> Result<(), PycError> {
+    output.push(TYPE_STRING);
+    write_size(output, bytes.len(), "bytes object")?;
+    output.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn write_str(output: &mut Vec<u8>, value: &str) -> Result<(), PycError> {
+    if value.is_ascii() && value.len() < 256 {
+        output.push(TYPE_SHORT_ASCII);
+        output.push(value.len() as u8);
+    } else {
+        output.push(TYPE_UNICODE);
+        write_size(output, value.len(), "string")?;
+    }
+    output.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+fn write_tuple_header(output: &mut Vec<u8>, len: usize) -> Result<(), PycError> {
+    if len < 256 {
+        output.push(TYPE_SMALL_TUPLE);
+        output.push(len as u8);
+        Ok(())
+    } else {
+        output.push(TYPE_TUPLE);
+        write_size(output, len, "tuple")
+    }
+}
+
+fn write_str_tuple(output: &mut Vec<u8>, values: &[String]) -> Result<(), PycError> {
+    write_tuple_header(output, values.len())?;
+    for value in values {
+        write_str(output, value)?;
+    }
+    Ok(())
+}
+
+fn write_int(output: &mut Vec<u8>, value: &BigInt) -> Result<(), PycError> {
+    if let Some(value) = value.to_i32() {
+        output.push(TYPE_INT);
+        output.extend_from_slice(&value.to_le_bytes());
+        return Ok(());
+    }
+    // Arbitrary precision integers are stored as base 2**15 digits, least significant first.
+    let (sign, magnitude) = value.to_bytes_le();
+    let mut digits = Vec::new();
+    let mut accumulator = 0u32;
+    let mut bits = 0;
+    for byte in magnitude {
+        accumulator |= u32::from(byte) << bits;
+        bits += 8;
+        while bits >= 15 {
+            digits.push((accumulator & 0x7fff) as u16);
+            accumulator >>= 15;
+            bits -= 15;
+        }
+    }
+    if accumulator != 0 {
+        digits.push(accumulator as u16);
+    }
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    let len = to_u32(digits.len(), "integer")? as i32;
+    output.push(TYPE_LONG);
+    let len = if sign == Sign::Minus { -len } else { len };
+    output.extend_from_slice(&len.to_le_bytes());
+    for digit in digits {
+        output.extend_from_slice(&digit.to_le_bytes());
+    }
+    Ok(())
+}
+
+fn write_value(output: &mut Vec<u8>, value: &Value, layout: CodeLayout) -> Result<(), PycError> {
+    match value {
+        Value::None => output.push(TYPE_NONE),
+        Value::StopIteration => output.push(TYPE_STOPITER),
+        Value::Ellipsis => output.push(TYPE_ELLIPSIS),
+        Value::Bool(true) => output.push(TYPE_TRUE),
+        Value::Bool(false) => output.push(TYPE_FALSE),
+        Value::Int(value) => write_int(output, value)?,
+        Value::Float(value) => {
+            output.push(TYPE_BINARY_FLOAT);
+            output.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+        Value::Complex(value) => {
+            output.push(TYPE_BINARY_COMPLEX);
+            output.extend_from_slice(&value.re.to_bits().to_le_bytes());
+            output.extend_from_slice(&value.im.to_bits().to_le_bytes());
+        }
+        Value::Bytes(value) => write_bytes(output, value)?,
+        Value::Str(value) => write_str(output, value)?,
+        Value::Tuple(elements) => {
+            write_tuple_header(output, elements.len())?;
+            for element in elements {
+                write_value(output, element, layout)?;
+            }
+        }
+        Value::List(elements) | Value::Set(elements) | Value::FrozenSet(elements) => {
+            output.push(match value {
+                Value::List(_) => TYPE_LIST,
+                Value::Set(_) => TYPE_SET,
+                _ => TYPE_FROZENSET,
+            });
+            write_size(output, elements.len(), "collection")?;
+            for element in elements {
+                write_value(output, element, layout)?;
+            }
+        }
+        Value::Dict(items) => {
+            output.push(TYPE_DICT);
+            for (key, value) in items {
+                write_value(output, key, layout)?;
+                write_value(output, value, layout)?;
+            }
+            output.push(TYPE_NULL);
+        }
+        Value::Code(code) => write_code(output, code, layout)?,
+    }
+    Ok(())
+}
+
+fn write_code(
+    output: &mut Vec<u8>,
+    code: &MarshalCode,
+    layout: CodeLayout,
+) -> Result<(), PycError> {
+    output.push(TYPE_CODE);
+    write_u32(output, code.argcount);
+    if layout != CodeLayout::Python37 {
+        write_u32(output, code.posonlyargcount);
+    }
+    write_u32(output, code.kwonlyargcount);
+    if layout != CodeLayout::Python311 {
+        write_size(output, code.varnames.len(), "local variable count")?;
+    }
+    write_u32(output, code.stacksize);
+    write_u32(output, code.flags);
+    write_bytes(output, &code.code)?;
+    write_value(output, &Value::Tuple(code.consts.clone()), layout)?;
+    write_str_tuple(output, &code.names)?;
+    if layout == CodeLayout::Python311 {
+        let mut localsplusnames = code.varnames.clone();
+        localsplusnames.extend(code.cellvars.iter().cloned());
+        localsplusnames.extend(code.freevars.iter().cloned());
+        write_str_tuple(output, &localsplusnames)?;
+        write_bytes(output, &vec![CO_FAST_LOCAL; localsplusnames.len()])?;
+    } else {
+        write_str_tuple(output, &code.varnames)?;
+        write_str_tuple(output, &code.freevars)?;
+        write_str_tuple(output, &code.cellvars)?;
+    }
+    write_str(output, &code.filename)?;
+    write_str(output, &code.name)?;
+    if layout == CodeLayout::Python311 {
+        write_str(output, &code.qualname)?;
+    }
+    write_u32(output, code.firstlineno);
+    write_bytes(output, &code.lnotab)?;
+    // Empty exception table on 3.11+.
+    if layout == CodeLayout::Python311 {
+        write_bytes(output, &[])?;
+    }
+    Ok(())
+}
+
+fn read_error(err: io::Error) -> PycError {
+    match err.kind() {
+        io::ErrorKind::UnexpectedEof => PycError::UnexpectedEof,
+        _ => PycError::Io(err.to_string()),
+    }
+}
+
+struct Reader<R> {
+    source: R,
+    /// How many bytes were read so far.
+    position: usize,
+    layout: CodeLayout,
+    refs: Vec<Value>,
+}
+
+impl<R: Read> Reader<R> {
+    fn new(source: R, layout: CodeLayout) -> Self {
+        Reader {
+            source,
+            position: 0,
+            layout,
+            refs: Vec::new(),
+        }
+    }
+
+    fn read_exact(&mut self, bytes: &mut [u8]) -> Result<(), PycError> {
+        self.source.read_exact(bytes).map_err(read_error)?;
+        self.position += bytes.len();
+        Ok(())
+    }
+
+    /// The next `len` bytes. They are allocated as they are read, so a bad length in short data
+    /// doesn't allocate that much.
+    fn read_data(&mut self, len: usize) -> Result<Vec<u8>, PycError> {
+        let mut bytes = Vec::new();
+        self.source
+            .by_ref()
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(read_error)?;
+        self.position += bytes.len();
+        if bytes.len() < len {
+            return Err(PycError::UnexpectedEof);
+        }
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, PycError> {
+        let mut bytes = [0; 1];
+        self.read_exact(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, PycError> {
+        let mut bytes = [0; 2];
+        self.read_exact(&mut bytes)?;
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, PycError> {
+        let mut bytes = [0; 4];
+        self.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, PycError> {
+        let mut bytes = [0; 8];
+        self.read_exact(&mut bytes)?;
+        Ok(f64::from_bits(u64::from_le_bytes(bytes)))
+    }
+
+    fn read_utf8(&mut self, len: usize) -> Result<String, PycError> {
+        String::from_utf8(self.read_data(len)?).map_err(|_| PycError::InvalidUtf8)
+    }
+
+    /// Old style text floats, as written by marshal version 1.
+    fn read_text_float(&mut self) -> Result<f64, PycError> {
+        let len = self.read_u8()? as usize;
+        self.read_utf8(len)?
+            .parse()
+            .map_err(|_| PycError::Unexpected("a float"))
+    }
+
+    fn read_values(&mut self, len: usize) -> Result<Vec<Value>, PycError> {
+        (0..len).map(|_| self.read_value()).collect()
+    }
+
+    fn read_value(&mut self) -> Result<Value, PycError> {
+        let code = self.read_u8()?;
+        self.read_value_of_type(code)
+    }
+
+    /// Read the rest of a value, after its type `code`.
+    fn read_value_of_type(&mut self, code: u8) -> Result<Value, PycError> {
+        let flag = code & FLAG_REF != 0;
+        let typ = code & !FLAG_REF;
+        if typ == TYPE_REF {
+            let index = self.read_u32()?;
+            return self
+                .refs
+                .get(index as usize)
+                .cloned()
+                .ok_or(PycError::InvalidReference(index));
+        }
+        // Reserve the slot before reading the contents, to match the order CPython numbers them.
+        let ref_index = if flag {
+            self.refs.push(Value::None);
+            Some(self.refs.len() - 1)
+        } else {
+            None
+        };
+        let value = match typ {
+            TYPE_NONE => Value::None,
+            TYPE_STOPITER => Value::StopIteration,
+            TYPE_ELLIPSIS => Value::Ellipsis,
+            TYPE_TRUE => Value::Bool(true),
+            TYPE_FALSE => Value::Bool(false),
+            TYPE_INT => Value::Int((self.read_u32()? as i32).into()),
+            TYPE_LONG => {
+                let len = self.read_u32()? as i32;
+                let mut value = BigInt::from(0);
+                let mut digits = Vec::new();
+                for _ in 0..len.abs() {
+                    digits.push(self.read_u16()?);
+                }
+                for digit in digits.into_iter().rev() {
+                    value = (value << 15) + digit;
+                }
+                Value::Int(if len < 0 { -value } else { value })
+            }
+            TYPE_FLOAT => Value::Float(self.read_text_float()?),
+            TYPE_BINARY_FLOAT => Value::Float(self.read_f64()?),
+            TYPE_COMPLEX => {
+                let re = self.read_text_float()?;
+                Value::Complex(Complex64::new(re, self.read_text_float()?))
+            }
+            TYPE_BINARY_COMPLEX => {
+                let re = self.read_f64()?;
+                Value::Complex(Complex64::new(re, self.read_f64()?))
+            }
+            TYPE_STRING => {
+                let len = self.read_u32()? as usize;
+                Value::Bytes(self.read_data(len)?)
+            }
+            TYPE_UNICODE | TYPE_INTERNED | TYPE_ASCII | TYPE_ASCII_INTERNED => {
+                let len = self.read_u32()? as usize;
+                Value::Str(self.read_utf8(len)?)
+            }
+            TYPE_SHORT_ASCII | TYPE_SHORT_ASCII_INTERNED => {
+                let len = self.read_u8()? as usize;
+                Value::Str(self.read_utf8(len)?)
+            }
+            TYPE_SMALL_TUPLE => {
+                let len = self.read_u8()? as usize;
+                Value::Tuple(self.read_values(len)?)
+            }
+            TYPE_TUPLE => {
+                let len = self.read_u32()? as usize;
+                Value::Tuple(self.read_values(len)?)
+            }
+            TYPE_LIST => {
+                let len = self.read_u32()? as usize;
+                Value::List(self.read_values(len)?)
+            }
+            TYPE_SET => {
+                let len = self.read_u32()? as usize;
+                Value::Set(self.read_values(len)?)
+            }
+            TYPE_FROZENSET => {
+                let len = self.read_u32()? as usize;
+                Value::FrozenSet(self.read_values(len)?)
+            }
+            TYPE_DICT => {
+                // The items end where a key would start with TYPE_NULL
+                let mut items = Vec::new();
+                loop {
+                    let key_type = self.read_u8()?;
+                    if key_type == TYPE_NULL {
+                        break;
+                    }
+                    let key = self.read_value_of_type(key_type)?;
+                    items.push((key, self.read_value()?));
+                }
+                Value::Dict(items)
+            }
+            TYPE_CODE => Value::Code(Box::new(self.read_code()?)),
+            _ => return Err(PycError::InvalidType(code)),
+        };
+        if let Some(index) = ref_index {
+            self.refs[index] = value.clone();
+        }
+        Ok(value)
+    }
+
+    fn read_bytes(&mut self) -> Result<Vec<u8>, PycError> {
+        match self.read_value()? {
+            Value::Bytes(bytes) => Ok(bytes),
+            _ => Err(PycError::Unexpected("a bytes object")),
+        }
+    }
+
+    fn read_str(&mut self) -> Result<String, PycError> {
+        match self.read_value()? {
+            Value::Str(value) => Ok(value),
+            _ => Err(PycError::Unexpected("a string")),
+        }
+    }
+
+    fn read_tuple(&mut self) -> Result<Vec<Value>, PycError> {
+        match self.read_value()? {
+            Value::Tuple(elements) => Ok(elements),
+            _ => Err(PycError::Unexpected("a tuple")),
+        }
+    }
+
+    fn read_str_tuple(&mut self) -> Result<Vec<String>, PycError> {
+        self.read_tuple()?
+            .into_iter()
+            .map(|value| match value {
+                Value::Str(value) => Ok(value),
+                _ => Err(PycError::Unexpected("a tuple of strings")),
+            })
+            .collect()
+    }
+
+    fn read_code(&mut self) -> Result<MarshalCode, PycError> {
+        let layout = self.layout;
+        let argcount = self.read_u32()?;
+        let posonlyargcount = if layout == CodeLayout::Python37 {
+            0
+        } else {
+            self.read_u32()?
+        };
+        let kwonlyargcount = self.read_u32()?;
+        if layout != CodeLayout::Python311 {
+            // co_nlocals, which is implied by co_varnames.
+            self.read_u32()?;
+        }
+        let stacksize = self.read_u32()?;
+        let flags = self.read_u32()?;
+        let code = self.read_bytes()?;
+        let consts = self.read_tuple()?;
+        let names = self.read_str_tuple()?;
+        let (varnames, freevars, cellvars) = if layout == CodeLayout::Python311 {
+            let localsplusnames = self.read_str_tuple()?;
+            let kinds = self.read_bytes()?;
+            let mut varnames = Vec::new();
+            let mut freevars = Vec::new();
+            let mut cellvars = Vec::new();
+            for (name, kind) in localsplusnames.into_iter().zip(kinds) {
+                // CO_FAST_LOCAL = 0x20, CO_FAST_CELL = 0x40, CO_FAST_FREE = 0x80
+                if kind & 0x80 != 0 {
+                    freevars.push(name);
+                } else if kind & 0x40 != 0 && kind & CO_FAST_LOCAL == 0 {
+                    cellvars.push(name);
+                } else {
+                    varnames.push(name);
+                }
+            }
+            (varnames, freevars, cellvars)
+        } else {
+            let varnames = self.read_str_tuple()?;
+            let freevars = self.read_str_tuple()?;
+            (varnames, freevars, self.read_str_tuple()?)
+        };
+        let filename = self.read_str()?;
+        let name = self.read_str()?;
+        let qualname = if layout == CodeLayout::Python311 {
+            self.read_str()?
+        } else {
+            name.clone()
+        };
+        let firstlineno = self.read_u32()?;
+        let lnotab = self.read_bytes()?;
+        if layout == CodeLayout::Python311 {
+            self.read_bytes()?;
+        }
+        Ok(MarshalCode {
+            argcount,
+            posonlyargcount,
+            kwonlyargcount,
+            stacksize,
+            flags,
+            code,
+            consts,
+            names,
+            varnames,
+            freevars,
+            cellvars,
+            filename,
+            name,
+            qualname,
+            firstlineno,
+            lnotab,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::{Constant, Instruction, NameScope};
+
+    const MAGIC_37: u32 = 3394 | 0x0a0d << 16;
+    const MAGIC_38: u32 = 3413 | 0x0a0d << 16;
+    const MAGIC_311: u32 = 3495 | 0x0a0d << 16;
+
+    fn sample_code() -> CodeObject {
+        let mut inner = CodeObject::new(
+            CodeFlags::NEW_LOCALS | CodeFlags::IS_GENERATOR,
+            0,
+            vec!["a".to_string()],
+            Varargs::Named("args".to_string()),
+            vec!["b".to_string()],
+            Varargs::None,
+            "sample.py".to_string(),
+            2,
+            "f".to_string(),
+        );
+        inner.constants = vec![Constant::Integer {
+            value: BigInt::from(1) << 100,
+        }];
+        inner.instructions =
+            vec![Instruction::LoadConst { idx: 0 }, Instruction::ReturnValue].into();
+        let mut code = CodeObject::new(
+            CodeFlags::NEW_LOCALS,
+            0,
+            vec![],
+            Varargs::None,
+            vec![],
+            Varargs::None,
+            "sample.py".to_string(),
+            1,
+            "<module>".to_string(),
+        );
+        code.constants = vec![
+            Constant::Code {
+                code: Box::new(inner),
+            },
+            Constant::String {
+                value: "f".to_string(),
+            },
+            Constant::Float { value: 1.5 },
+        ];
+        code.names = vec!["f".to_string()];
+        code.instructions = vec![
+            Instruction::LoadConst { idx: 0 },
+            Instruction::LoadConst { idx: 1 },
+            Instruction::MakeFunction,
+            Instruction::StoreName {
+                idx: 0,
+                scope: NameScope::Global,
+            },
+            Instruction::LoadConst { idx: 2 },
+            Instruction::ReturnValue,
+        ]
+        .into();
+        code
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let code = sample_code();
+        for magic in &[MAGIC_37, MAGIC_38, MAGIC_311] {
+            let data = to_pyc(&code, *magic, 12, 34).unwrap();
+            assert_eq!(from_pyc(&data).unwrap(), code);
+
+            let pyc = read_pyc(&data).unwrap();
+            assert_eq!((pyc.magic, pyc.mtime, pyc.size), (*magic, 12, 34));
+            assert_eq!(pyc.code.name, "<module>");
+            assert_eq!(pyc.code.names, vec!["f".to_string()]);
+            match &pyc.code.consts[0] {
+                Value::Code(inner) => {
+                    assert_eq!(inner.varnames, vec!["a", "b", "args"]);
+                    assert_eq!((inner.argcount, inner.kwonlyargcount), (1, 1));
+                    assert_eq!(inner.flags, CO_NEWLOCALS | CO_VARARGS | CO_GENERATOR);
+                    assert_eq!(inner.consts[0], Value::Int(BigInt::from(1) << 100));
+                }
+                other => panic!("expected a code object, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_dump_and_load_code() {
+        let code = sample_code();
+        for magic in &[MAGIC_37, MAGIC_38, MAGIC_311, MAGIC_NUMBER] {
+            let data = dump_code(&code, *magic).unwrap();
+            assert_eq!(load_code(&data, *magic).unwrap(), code);
+            assert_eq!(to_pyc(&code, *magic, 0, 0).unwrap()[16..], data[..]);
+        }
+        assert_eq!(
+            load_code(&CPYTHON_38_PYC[16..], MAGIC_38),
+            Err(PycError::NotRustPythonCode)
+        );
+        // RustPython's own files use a layout this module knows.
+        assert_eq!(
+            CodeLayout::from_magic(MAGIC_NUMBER),
+            Ok(CodeLayout::Python38)
+        );
+    }
+
+    #[test]
+    fn test_float_constants() {
+        let mut code = sample_code();
+        code.constants.clear();
+        for value in &[0.0, -0.0, std::f64::NAN, std::f64::NAN, 0.0] {
+            code.add_constant(Constant::Float { value: *value });
+        }
+        // The signed zeros stay apart and the NaNs are shared, like in CPython
+        let marshal = to_marshal_code(&code, CodeLayout::Python38).unwrap();
+        let bits: Vec<_> = marshal
+            .consts
+            .iter()
+            .filter_map(|value| match value {
+                Value::Float(value) => Some(value.to_bits()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            bits,
+            vec![
+                0.0f64.to_bits(),
+                (-0.0f64).to_bits(),
+                std::f64::NAN.to_bits()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dump_and_load_values() {
+        let value = Value::List(vec![
+            Value::Int(BigInt::from(1)),
+            Value::Float(-2.5),
+            Value::Str("h\u{e9}".to_string()),
+            Value::Bytes(b"x".to_vec()),
+            Value::Tuple(vec![Value::None, Value::Ellipsis, Value::StopIteration]),
+            Value::Dict(vec![(
+                Value::Str("k".to_string()),
+                Value::FrozenSet(vec![]),
+            )]),
+            Value::Int(-(BigInt::from(1) << 100)),
+            Value::Complex(Complex64::new(0.0, 1.0)),
+            Value::Bool(true),
+            Value::Set(vec![Value::Int(BigInt::from(3))]),
+        ]);
+        // marshal.dumps of the same list by CPython 3.11, which adds back references.
+        assert_eq!(
+            load_value(CPYTHON_311_VALUE, MAGIC_311),
+            Ok((value.clone(), 95))
+        );
+
+        let mut data = dump_value(&value, MAGIC_NUMBER).unwrap();
+        let len = data.len();
+        data.extend_from_slice(b"trailing");
+        assert_eq!(load_value(&data, MAGIC_NUMBER), Ok((value.clone(), len)));
+        assert_eq!(
+            load_value(&data[..len - 1], MAGIC_NUMBER),
+            Err(PycError::UnexpectedEof)
+        );
+
+        // A reader is left right after the value
+        let mut source = io::Cursor::new(&data);
+        assert_eq!(read_value(&mut source, MAGIC_NUMBER), Ok(value));
+        assert_eq!(source.position(), len as u64);
+        assert_eq!(
+            read_value(&data[..len - 1], MAGIC_NUMBER),
+            Err(PycError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn test_unsupported_magic() {
+        let code = sample_code();
+        assert_eq!(
+            to_pyc(&code, 62211 | 0x0a0d << 16, 0, 0),
+            Err(PycError::UnsupportedMagic(62211 | 0x0a0d << 16))
+        );
+        assert_eq!(
+            to_pyc(&code, 3413, 0, 0),
+            Err(PycError::UnsupportedMagic(3413))
+        );
+    }
+
+    const CPYTHON_311_VALUE: &[u8] = &[
+        0xdb, 0x0a, 0x00, 0x00, 0x00, 0xe9, 0x01, 0x00, 0x00, 0x00, 0xe7, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x04, 0xc0, 0xf5, 0x03, 0x00, 0x00, 0x00, 0x68, 0xc3, 0xa9, 0xf3, 0x01, 0x00,
+        0x00, 0x00, 0x78, 0x29, 0x03, 0x4e, 0x2e, 0x53, 0x7b, 0xda, 0x01, 0x6b, 0x3e, 0x00, 0x00,
+        0x00, 0x00, 0x30, 0x6c, 0xf9, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0xf9, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, 0x54, 0x3c, 0x01, 0x00, 0x00, 0x00,
+        0xe9, 0x03, 0x00, 0x00, 0x00,
+    ];
+
+    /// `x = 1; x = "x"` compiled by CPython 3.8, which uses back references for repeated names.
+    const CPYTHON_38_PYC: &[u8] = &[
+        0x55, 0x0d, 0x0d, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xe3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x73, 0x0c, 0x00, 0x00,
+        0x00, 0x64, 0x00, 0x5a, 0x00, 0x64, 0x01, 0x5a, 0x00, 0x64, 0x02, 0x53, 0x00, 0x29, 0x03,
+        0xe9, 0x01, 0x00, 0x00, 0x00, 0xda, 0x01, 0x78, 0x4e, 0x29, 0x01, 0x72, 0x02, 0x00, 0x00,
+        0x00, 0xa9, 0x00, 0x72, 0x03, 0x00, 0x00, 0x00, 0x72, 0x03, 0x00, 0x00, 0x00, 0xfa, 0x04,
+        0x74, 0x2e, 0x70, 0x79, 0xda, 0x08, 0x3c, 0x6d, 0x6f, 0x64, 0x75, 0x6c, 0x65, 0x3e, 0x01,
+        0x00, 0x00, 0x00, 0x73, 0x02, 0x00, 0x00, 0x00, 0x04, 0x01,
+    ];
+
+    /// The same module compiled by CPython 3.11.
+    const CPYTHON_311_PYC: &[u8] = &[
+        0xa7, 0x0d, 0x0d, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xe3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf3, 0x0e, 0x00, 0x00, 0x00, 0x97, 0x00, 0x64,
+        0x00, 0x5a, 0x00, 0x64, 0x01, 0x5a, 0x00, 0x64, 0x02, 0x53, 0x00, 0x29, 0x03, 0xe9, 0x01,
+        0x00, 0x00, 0x00, 0xda, 0x01, 0x78, 0x4e, 0x29, 0x01, 0x72, 0x03, 0x00, 0x00, 0x00, 0xa9,
+        0x00, 0xf3, 0x00, 0x00, 0x00, 0x00, 0xfa, 0x04, 0x74, 0x2e, 0x70, 0x79, 0xfa, 0x08, 0x3c,
+        0x6d, 0x6f, 0x64, 0x75, 0x6c, 0x65, 0x3e, 0x72, 0x07, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x73, 0x13, 0x00, 0x00, 0x00, 0xf0, 0x03, 0x01, 0x01, 0x01, 0xd8, 0x04, 0x05, 0x80,
+        0x01, 0xd8, 0x04, 0x07, 0x80, 0x01, 0x80, 0x01, 0x80, 0x01, 0x72, 0x05, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_read_cpython_pyc() {
+        for data in &[CPYTHON_38_PYC, CPYTHON_311_PYC] {
+            let pyc = read_pyc(data).unwrap();
+            assert_eq!(pyc.code.name, "<module>");
+            assert_eq!(pyc.code.filename, "t.py");
+            assert_eq!(pyc.code.names, vec!["x".to_string()]);
+            assert_eq!(
+                pyc.code.consts,
+                vec![
+                    Value::Int(1.into()),
+                    Value::Str("x".to_string()),
+                    Value::None
+                ]
+            );
+            assert_eq!(from_pyc(data), Err(PycError::NotRustPythonCode));
+        }
+    }
+
+    #[test]
+    fn test_truncated_pyc() {
+        let data = to_pyc(&sample_code(), MAGIC_38, 0, 0).unwrap();
+        assert_eq!(
+            from_pyc(&data[..data.len() - 10]),
+            Err(PycError::UnexpectedEof)
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! Code objects laid out as static data, which `py_compile_bytecode!` emits for frozen modules.
+//!
+//! A `StaticCodeObject` has the fields of a `CodeObject`, with its tables as `&'static` slices,
+//! so that the macro can write it out as a `static` item the compiler lays out in the binary.
+//! Importing the module then decodes nothing: the instructions, line table and inline cache
+//! slots of the `CodeObject` borrow those of the `StaticCodeObject`, and its names and constants,
+//! which the VM turns into Python objects anyway, are copied from the static tables.
+
+use crate::bytecode::{CodeFlags, CodeObject, Constant, Instruction, Label, LineTable, Varargs};
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use std::borrow::Cow;
+
+pub struct StaticCodeObject {
+    pub instructions: &'static [Instruction],
+    pub label_map: &'static [(Label, usize)],
+    pub line_table: LineTable,
+    /// The bits of the `CodeFlags`.
+    pub flags: u8,
+    pub posonlyarg_count: usize,
+    pub arg_names: &'static [&'static str],
+    pub varargs: StaticVarargs,
+    pub kwonlyarg_names: &'static [&'static str],
+    pub varkeywords: StaticVarargs,
+    pub source_path: &'static str,
+    pub first_line_number: usize,
+    pub obj_name: &'static str,
+    pub constants: &'static [StaticConstant],
+    pub names: &'static [&'static str],
+    pub cache_slots: &'static [u32],
+}
+
+pub enum StaticVarargs {
+    None,
+    Unnamed,
+    Named(&'static str),
+}
+
+/// A `Constant`, with the values that can't be built in a `static` in a form that can: integers
+/// as the bytes of `BigInt::to_signed_bytes_le`, and floats as their bits.
+pub enum StaticConstant {
+    Integer { value: &'static [u8] },
+    Float { bits: u64 },
+    Complex { re_bits: u64, im_bits: u64 },
+    Boolean { value: bool },
+    String { value: &'static str },
+    Bytes { value: &'static [u8] },
+    Code { code: &'static StaticCodeObject },
+    Tuple { elements: &'static [StaticConstant] },
+    None,
+    Ellipsis,
+}
+
+fn to_strings(names: &[&str]) -> Vec<String> {
+    names.iter().map(|&name| name.to_owned()).collect()
+}
+
+impl StaticCodeObject {
+    /// The code object, which borrows the instructions, the line table and the cache slots.
+    pub fn to_code_object(&'static self) -> CodeObject {
+        CodeObject {
+            instructions: Cow::Borrowed(self.instructions),
+            label_map: self.label_map.iter().cloned().collect(),
+            line_table: self.line_table.clone(),
+            flags: CodeFlags::from_bits_truncate(self.flags),
+            posonlyarg_count: self.posonlyarg_count,
+            arg_names: to_strings(self.arg_names),
+            varargs: self.varargs.to_varargs(),
+            kwonlyarg_names: to_strings(self.kwonlyarg_names),
+            varkeywords: self.varkeywords.to_varargs(),
+            source_path: self.source_path.to_owned(),
+            first_line_number: self.first_line_number,
+            obj_name: self.obj_name.to_owned(),
+            constants: self
+                .constants
+                .iter()
+                .map(StaticConstant::to_constant)
+                .collect(),
+            names: to_strings(self.names),
+            cache_slots: Cow::Borrowed(self.cache_slots),
+        }
+    }
+}
+
+impl StaticVarargs {
+    fn to_varargs(&self) -> Varargs {
+        match self {
+            StaticVarargs::None => Varargs::None,
+            StaticVarargs::Unnamed => Varargs::Unnamed,
+            StaticVarargs::Named(name) => Varargs::Named((*name).to_owned()),
+        }
+    }
+}
+
+impl StaticConstant {
+    fn to_constant(&'static self) -> Constant {
+        match self {
+            StaticConstant::Integer { value } => Constant::Integer {
+                value: BigInt::from_signed_bytes_le(value),
+            },
+            StaticConstant::Float { bits } => Constant::Float {
+                value: f64::from_bits(*bits),
+            },
+            StaticConstant::Complex { re_bits, im_bits } => Constant::Complex {
+                value: Complex64::new(f64::from_bits(*re_bits), f64::from_bits(*im_bits)),
+            },
+            StaticConstant::Boolean { value } => Constant::Boolean { value: *value },
+            StaticConstant::String { value } => Constant::String {
+                value: (*value).to_owned(),
+            },
+            StaticConstant::Bytes { value } => Constant::Bytes {
+                value: value.to_vec(),
+            },
+            StaticConstant::Code { code } => Constant::Code {
+                code: Box::new(code.to_code_object()),
+            },
+            StaticConstant::Tuple { elements } => Constant::Tuple {
+                elements: elements.iter().map(StaticConstant::to_constant).collect(),
+            },
+            StaticConstant::None => Constant::None,
+            StaticConstant::Ellipsis => Constant::Ellipsis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bytecode::NameScope;
+
+    static FUNCTION: StaticCodeObject = StaticCodeObject {
+        instructions: &[
+            Instruction::LoadName {
+                idx: 0,
+                scope: NameScope::Local,
+            },
+            Instruction::ReturnValue,
+        ],
+        label_map: &[],
+        line_table: LineTable::from_static(&[0, 2], 2, &[]),
+        flags: 0x08,
+        posonlyarg_count: 0,
+        arg_names: &["x"],
+        varargs: StaticVarargs::Unnamed,
+        kwonlyarg_names: &[],
+        varkeywords: StaticVarargs::Named("kwargs"),
+        source_path: "static.py",
+        first_line_number: 2,
+        obj_name: "f",
+        constants: &[],
+        names: &["x"],
+        cache_slots: &[
+            crate::bytecode::NO_CACHE_SLOT,
+            crate::bytecode::NO_CACHE_SLOT,
+        ],
+    };
+
+    static MODULE: StaticCodeObject = StaticCodeObject {
+        instructions: &[
+            Instruction::Import {
+                name_idx: Some(0),
+                symbols_idx: Cow::Borrowed(&[1, 2]),
+                level: 0,
+            },
+            Instruction::Jump {
+                target: Label::new(0),
+            },
+            Instruction::LoadConst { idx: 0 },
+            Instruction::ReturnValue,
+        ],
+        label_map: &[(Label::new(0), 2)],
+        line_table: LineTable::from_static(&[0, 1], 4, &[1, 1, 5, 5]),
+        flags: 0x08,
+        posonlyarg_count: 0,
+        arg_names: &[],
+        varargs: StaticVarargs::None,
+        kwonlyarg_names: &[],
+        varkeywords: StaticVarargs::None,
+        source_path: "static.py",
+        first_line_number: 1,
+        obj_name: "<module>",
+        constants: &[
+            StaticConstant::Tuple {
+                elements: &[
+                    StaticConstant::Integer { value: &[0x80, 0] },
+                    StaticConstant::Integer { value: &[0xff] },
+                    StaticConstant::Float {
+                        bits: 0x8000_0000_0000_0000,
+                    },
+                    StaticConstant::Complex {
+                        re_bits: 0,
+                        im_bits: 0x3ff0_0000_0000_0000,
+                    },
+                    StaticConstant::String { value: "s" },
+                    StaticConstant::Bytes { value: b"b" },
+                    StaticConstant::Boolean { value: true },
+                    StaticConstant::None,
+                    StaticConstant::Ellipsis,
+                ],
+            },
+            StaticConstant::Code { code: &FUNCTION },
+        ],
+        names: &["os", "path", "sep"],
+        cache_slots: &[crate::bytecode::NO_CACHE_SLOT; 4],
+    };
+
+    #[test]
+    fn test_to_code_object() {
+        let code = MODULE.to_code_object();
+        assert!(match code.instructions {
+            Cow::Borrowed(instructions) => instructions.as_ptr() == MODULE.instructions.as_ptr(),
+            Cow::Owned(_) => false,
+        });
+        assert_eq!(code.label_map[&Label::new(0)], 2);
+        assert_eq!(
+            code.line_table.lines().collect::<Vec<_>>(),
+            vec![Some(1); 4]
+        );
+        assert_eq!(code.line_table.column(2), Some(5));
+        assert_eq!(code.flags, CodeFlags::NEW_LOCALS);
+        assert_eq!(code.names, vec!["os", "path", "sep"]);
+        match &code.constants[0] {
+            Constant::Tuple { elements } => assert_eq!(
+                elements.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                vec!["128", "-1", "-0", "0+1i", "\"s\"", "[98]", "true", "None", "Ellipsis"]
+            ),
+            constant => panic!("not a tuple: {}", constant),
+        }
+        let function = match &code.constants[1] {
+            Constant::Code { code } => code,
+            constant => panic!("not a code object: {}", constant),
+        };
+        assert_eq!(function.obj_name, "f");
+        assert_eq!(function.arg_names, vec!["x"]);
+        assert_eq!(function.varargs, Varargs::Unnamed);
+        assert_eq!(function.varkeywords, Varargs::Named("kwargs".to_owned()));
+        assert_eq!(function.cache_slot_count(), 0);
+    }
+}
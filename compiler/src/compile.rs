@@ -5,16 +5,20 @@
 //!   https://github.com/python/cpython/blob/master/Python/compile.c
 //!   https://github.com/micropython/micropython/blob/master/py/compile.c
 
-use crate::error::{CompileError, CompileErrorType};
+use crate::error::{CompileError, CompileErrorType, CompileWarning, CompileWarningType};
 pub use crate::mode::Mode;
 use crate::output_stream::{CodeObjectStream, OutputStream};
 use crate::peephole::PeepholeOptimizer;
 use crate::symboltable::{
-    make_symbol_table, statements_to_symbol_table, Symbol, SymbolScope, SymbolTable,
+    make_symbol_table_with_future, statements_to_symbol_table_with_future, Symbol, SymbolScope,
+    SymbolTable, SymbolTableType,
 };
+use crate::unparse::unparse_expression;
 use itertools::Itertools;
 use num_complex::Complex64;
-use rustpython_bytecode::bytecode::{self, CallType, CodeObject, Instruction, Label, Varargs};
+use rustpython_bytecode::bytecode::{
+    self, CallType, CodeFlags, CodeObject, Instruction, Label, Varargs,
+};
 use rustpython_parser::{ast, parser};
 
 type BasicOutputStream = PeepholeOptimizer<CodeObjectStream>;
@@ -29,6 +33,16 @@ struct Compiler<O: OutputStream = BasicOutputStream> {
     current_qualified_path: Option<String>,
     ctx: CompileContext,
     optimize: u8,
+    /// Whether a `from __future__ import` may still come, which it only may before any other
+    /// statement of the module but its docstring.
+    future_imports_allowed: bool,
+    /// Whether `from __future__ import annotations` is in effect, which keeps annotations as
+    /// strings of their source instead of evaluating them.
+    future_annotations: bool,
+    /// Whether the expression statements at the top level of the module print their values
+    /// through `sys.displayhook`, as in the REPL, which is what `Mode::Single` compiles for.
+    interactive: bool,
+    warnings: Vec<CompileWarning>,
 }
 
 #[derive(Clone, Copy)]
@@ -51,6 +65,13 @@ impl CompileContext {
             _ => true,
         }
     }
+
+    fn in_async_func(self) -> bool {
+        match self.func {
+            FunctionContext::AsyncFunction => true,
+            _ => false,
+        }
+    }
 }
 
 /// Compile a given sourcecode into a bytecode object.
@@ -60,18 +81,104 @@ pub fn compile(
     source_path: String,
     optimize: u8,
 ) -> Result<CodeObject, CompileError> {
-    match mode {
-        Mode::Exec => {
-            let ast = parser::parse_program(source)?;
-            compile_program(ast, source_path, optimize)
+    compile_with_flags(source, mode, source_path, optimize, CodeFlags::empty())
+}
+
+/// Compile a given sourcecode into a bytecode object, with the future features of `flags`, like
+/// `CodeFlags::FUTURE_ANNOTATIONS`, in effect from the start. Other flags are ignored.
+pub fn compile_with_flags(
+    source: &str,
+    mode: Mode,
+    source_path: String,
+    optimize: u8,
+    flags: CodeFlags,
+) -> Result<CodeObject, CompileError> {
+    compile_with_warnings(source, mode, source_path, optimize, flags).map(|(code, _)| code)
+}
+
+/// Like `compile_with_flags`, also returning the warnings about the source, like the
+/// SyntaxWarning for `is` with a literal, in the order CPython emits them.
+pub fn compile_with_warnings(
+    source: &str,
+    mode: Mode,
+    source_path: String,
+    optimize: u8,
+    flags: CodeFlags,
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
+    let (ast, lexical_warnings) = match mode {
+        Mode::Exec | Mode::Single => {
+            let (program, warnings) = parser::parse_program_with_warnings(source)?;
+            (ast::Top::Program(program), warnings)
         }
         Mode::Eval => {
-            let statement = parser::parse_statement(source)?;
-            compile_statement_eval(statement, source_path, optimize)
+            let (statement, warnings) = parser::parse_statement_with_warnings(source)?;
+            (ast::Top::Statement(statement), warnings)
+        }
+    };
+    let (code, compile_warnings) = compile_top(ast, mode, source_path, optimize, flags)?;
+    let warnings = lexical_warnings
+        .into_iter()
+        .map(CompileWarning::from)
+        .chain(compile_warnings)
+        .collect();
+    Ok((code, warnings))
+}
+
+/// Like `compile`, but report all the syntax errors of an exec or single mode source instead of
+/// only the first one, see `parser::parse_program_recovering`.
+pub fn compile_recovering(
+    source: &str,
+    mode: Mode,
+    source_path: String,
+    optimize: u8,
+) -> Result<CodeObject, Vec<CompileError>> {
+    let program = match mode {
+        Mode::Exec | Mode::Single => {
+            let (program, _) = parser::parse_program_recovering(source).map_err(|errors| {
+                errors
+                    .into_iter()
+                    .map(CompileError::from)
+                    .collect::<Vec<_>>()
+            })?;
+            program
+        }
+        Mode::Eval => return compile(source, mode, source_path, optimize).map_err(|err| vec![err]),
+    };
+    compile_top(
+        ast::Top::Program(program),
+        mode,
+        source_path,
+        optimize,
+        CodeFlags::empty(),
+    )
+    .map(|(code, _)| code)
+    .map_err(|err| vec![err])
+}
+
+/// Compile an already parsed program, like one built from the nodes of the `ast` module, into a
+/// bytecode object, with the warnings about it. `mode` tells an interactive program from a
+/// regular one.
+pub fn compile_top(
+    ast: ast::Top,
+    mode: Mode,
+    source_path: String,
+    optimize: u8,
+    flags: CodeFlags,
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
+    match ast {
+        ast::Top::Program(program) => match mode {
+            Mode::Single => compile_program_single(program, source_path, optimize, flags),
+            _ => compile_program(program, source_path, optimize, flags),
+        },
+        ast::Top::Statement(statement) => {
+            compile_statement_eval(statement, source_path, optimize, flags)
         }
-        Mode::Single => {
-            let ast = parser::parse_program(source)?;
-            compile_program_single(ast, source_path, optimize)
+        ast::Top::Expression(expression) => {
+            let statement = ast::Statement {
+                location: expression.location.clone(),
+                node: ast::StatementType::Expression { expression },
+            };
+            compile_statement_eval(vec![statement], source_path, optimize, flags)
         }
     }
 }
@@ -80,15 +187,17 @@ pub fn compile(
 fn with_compiler(
     source_path: String,
     optimize: u8,
+    flags: CodeFlags,
     f: impl FnOnce(&mut Compiler) -> Result<(), CompileError>,
-) -> Result<CodeObject, CompileError> {
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
     let mut compiler = Compiler::new(optimize);
     compiler.source_path = Some(source_path);
+    compiler.future_annotations = flags.contains(CodeFlags::FUTURE_ANNOTATIONS);
     compiler.push_new_code_object("<module>".to_string());
     f(&mut compiler)?;
     let code = compiler.pop_code_object();
     trace!("Compilation completed: {:?}", code);
-    Ok(code)
+    Ok((code, compiler.warnings))
 }
 
 /// Compile a standard Python program to bytecode
@@ -96,9 +205,10 @@ pub fn compile_program(
     ast: ast::Program,
     source_path: String,
     optimize: u8,
-) -> Result<CodeObject, CompileError> {
-    with_compiler(source_path, optimize, |compiler| {
-        let symbol_table = make_symbol_table(&ast)?;
+    flags: CodeFlags,
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
+    with_compiler(source_path, optimize, flags, |compiler| {
+        let symbol_table = make_symbol_table_with_future(&ast, compiler.future_annotations)?;
         compiler.compile_program(&ast, symbol_table)
     })
 }
@@ -108,11 +218,15 @@ pub fn compile_statement_eval(
     statement: Vec<ast::Statement>,
     source_path: String,
     optimize: u8,
-) -> Result<CodeObject, CompileError> {
-    with_compiler(source_path, optimize, |compiler| {
-        let symbol_table = statements_to_symbol_table(&statement)?;
+    flags: CodeFlags,
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
+    let (mut code, warnings) = with_compiler(source_path, optimize, flags, |compiler| {
+        let symbol_table =
+            statements_to_symbol_table_with_future(&statement, compiler.future_annotations)?;
         compiler.compile_statement_eval(&statement, symbol_table)
-    })
+    })?;
+    code.flags |= CodeFlags::IS_EXPRESSION;
+    Ok((code, warnings))
 }
 
 /// Compile a Python program to bytecode for the context of a REPL
@@ -120,9 +234,10 @@ pub fn compile_program_single(
     ast: ast::Program,
     source_path: String,
     optimize: u8,
-) -> Result<CodeObject, CompileError> {
-    with_compiler(source_path, optimize, |compiler| {
-        let symbol_table = make_symbol_table(&ast)?;
+    flags: CodeFlags,
+) -> Result<(CodeObject, Vec<CompileWarning>), CompileError> {
+    with_compiler(source_path, optimize, flags, |compiler| {
+        let symbol_table = make_symbol_table_with_future(&ast, compiler.future_annotations)?;
         compiler.compile_program_single(&ast, symbol_table)
     })
 }
@@ -150,6 +265,10 @@ impl<O: OutputStream> Compiler<O> {
                 func: FunctionContext::NoFunction,
             },
             optimize,
+            future_imports_allowed: false,
+            future_annotations: false,
+            interactive: false,
+            warnings: Vec::new(),
         }
     }
 
@@ -161,6 +280,7 @@ impl<O: OutputStream> Compiler<O> {
         let line_number = self.get_source_line_number();
         self.push_output(CodeObject::new(
             Default::default(),
+            0,
             Vec::new(),
             Varargs::None,
             Vec::new(),
@@ -172,7 +292,12 @@ impl<O: OutputStream> Compiler<O> {
     }
 
     fn pop_code_object(&mut self) -> CodeObject {
-        self.output_stack.pop().unwrap().into()
+        let mut code: CodeObject = self.output_stack.pop().unwrap().into();
+        // Future imports come first, so this is set before any code object is done.
+        if self.future_annotations {
+            code.flags |= CodeFlags::FUTURE_ANNOTATIONS;
+        }
+        code
     }
 
     fn compile_program(
@@ -183,13 +308,16 @@ impl<O: OutputStream> Compiler<O> {
         let size_before = self.output_stack.len();
         self.symbol_table_stack.push(symbol_table);
 
-        let (statements, doc) = get_doc(&program.statements);
+        let (statements, doc) = self.get_doc(&program.statements);
+        self.future_imports_allowed = true;
+        if find_ann(statements) {
+            self.emit(Instruction::SetupAnnotations);
+        }
         if let Some(value) = doc {
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::String { value },
-            });
+            self.emit_constant(bytecode::Constant::String { value });
+            let idx = self.name("__doc__");
             self.emit(Instruction::StoreName {
-                name: "__doc__".to_owned(),
+                idx,
                 scope: bytecode::NameScope::Global,
             });
         }
@@ -198,9 +326,7 @@ impl<O: OutputStream> Compiler<O> {
         assert_eq!(self.output_stack.len(), size_before);
 
         // Emit None at end:
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::None,
-        });
+        self.emit_constant(bytecode::Constant::None);
         self.emit(Instruction::ReturnValue);
         Ok(())
     }
@@ -211,32 +337,35 @@ impl<O: OutputStream> Compiler<O> {
         symbol_table: SymbolTable,
     ) -> Result<(), CompileError> {
         self.symbol_table_stack.push(symbol_table);
+        self.future_imports_allowed = true;
+        self.interactive = true;
+
+        if find_ann(&program.statements) {
+            self.emit(Instruction::SetupAnnotations);
+        }
 
         let mut emitted_return = false;
 
         for (i, statement) in program.statements.iter().enumerate() {
             let is_last = i == program.statements.len() - 1;
 
-            if let ast::StatementType::Expression { ref expression } = statement.node {
-                self.compile_expression(expression)?;
-
-                if is_last {
+            match &statement.node {
+                // The value of the last expression is also returned, for the REPLs that embed
+                // the VM; the code run by `exec` gives None anyway
+                ast::StatementType::Expression { expression } if is_last => {
+                    self.set_source_location(&statement.location);
+                    self.compile_expression(expression)?;
                     self.emit(Instruction::Duplicate);
                     self.emit(Instruction::PrintExpr);
                     self.emit(Instruction::ReturnValue);
                     emitted_return = true;
-                } else {
-                    self.emit(Instruction::PrintExpr);
                 }
-            } else {
-                self.compile_statement(&statement)?;
+                _ => self.compile_statement(statement)?,
             }
         }
 
         if !emitted_return {
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::None,
-            });
+            self.emit_constant(bytecode::Constant::None);
             self.emit(Instruction::ReturnValue);
         }
 
@@ -274,27 +403,41 @@ impl<O: OutputStream> Compiler<O> {
     fn scope_for_name(&self, name: &str) -> bytecode::NameScope {
         let symbol = self.lookup_name(name);
         match symbol.scope {
-            SymbolScope::Global => bytecode::NameScope::Global,
-            SymbolScope::Nonlocal => bytecode::NameScope::NonLocal,
-            SymbolScope::Unknown => bytecode::NameScope::Free,
-            SymbolScope::Local => bytecode::NameScope::Free,
+            SymbolScope::GlobalExplicit => bytecode::NameScope::Global,
+            // Names at the top level are looked up in the locals first, which may not be the
+            // globals with `exec`.
+            SymbolScope::GlobalImplicit => {
+                let symbol_table = self.symbol_table_stack.last().unwrap();
+                if symbol_table.typ == SymbolTableType::Module {
+                    bytecode::NameScope::Free
+                } else {
+                    bytecode::NameScope::Global
+                }
+            }
+            SymbolScope::Free if symbol.is_nonlocal => bytecode::NameScope::NonLocal,
+            // The locals of a function are only looked up in its frame, so that using one before
+            // it's assigned is an UnboundLocalError
+            SymbolScope::Local
+                if self.symbol_table_stack.last().unwrap().typ == SymbolTableType::Function =>
+            {
+                bytecode::NameScope::Local
+            }
+            SymbolScope::Free | SymbolScope::Local | SymbolScope::Cell | SymbolScope::Unknown => {
+                bytecode::NameScope::Free
+            }
         }
     }
 
     fn load_name(&mut self, name: &str) {
         let scope = self.scope_for_name(name);
-        self.emit(Instruction::LoadName {
-            name: name.to_string(),
-            scope,
-        });
+        let idx = self.name(name);
+        self.emit(Instruction::LoadName { idx, scope });
     }
 
     fn store_name(&mut self, name: &str) {
         let scope = self.scope_for_name(name);
-        self.emit(Instruction::StoreName {
-            name: name.to_string(),
-            scope,
-        });
+        let idx = self.name(name);
+        self.emit(Instruction::StoreName { idx, scope });
     }
 
     fn compile_statement(&mut self, statement: &ast::Statement) -> Result<(), CompileError> {
@@ -302,20 +445,29 @@ impl<O: OutputStream> Compiler<O> {
         self.set_source_location(&statement.location);
         use ast::StatementType::*;
 
+        match &statement.node {
+            ImportFrom {
+                level: 0,
+                module: Some(module),
+                names,
+            } if module == "__future__" => self.compile_future_features(statement, names)?,
+            _ => self.future_imports_allowed = false,
+        }
+
         match &statement.node {
             Import { names } => {
                 // import a, b, c as d
                 for name in names {
+                    let name_idx = Some(self.name(&name.symbol));
                     self.emit(Instruction::Import {
-                        name: Some(name.symbol.clone()),
-                        symbols: vec![],
+                        name_idx,
+                        symbols_idx: vec![].into(),
                         level: 0,
                     });
                     if let Some(alias) = &name.alias {
                         for part in name.symbol.split('.').skip(1) {
-                            self.emit(Instruction::LoadAttr {
-                                name: part.to_owned(),
-                            });
+                            let idx = self.name(part);
+                            self.emit(Instruction::LoadAttr { idx });
                         }
                         self.store_name(alias);
                     } else {
@@ -332,29 +484,31 @@ impl<O: OutputStream> Compiler<O> {
 
                 if import_star {
                     // from .... import *
+                    let name_idx = module.as_ref().map(|module| self.name(module));
+                    let symbols_idx = vec![self.name("*")];
                     self.emit(Instruction::Import {
-                        name: module.clone(),
-                        symbols: vec!["*".to_owned()],
+                        name_idx,
+                        symbols_idx: symbols_idx.into(),
                         level: *level,
                     });
                     self.emit(Instruction::ImportStar);
                 } else {
                     // from mod import a, b as c
                     // First, determine the fromlist (for import lib):
-                    let from_list = names.iter().map(|n| n.symbol.clone()).collect();
+                    let symbols_idx = names.iter().map(|n| self.name(&n.symbol)).collect();
 
                     // Load module once:
+                    let name_idx = module.as_ref().map(|module| self.name(module));
                     self.emit(Instruction::Import {
-                        name: module.clone(),
-                        symbols: from_list,
+                        name_idx,
+                        symbols_idx,
                         level: *level,
                     });
 
                     for name in names {
                         // import symbol from module:
-                        self.emit(Instruction::ImportFrom {
-                            name: name.symbol.to_string(),
-                        });
+                        let idx = self.name(&name.symbol);
+                        self.emit(Instruction::ImportFrom { idx });
 
                         // Store module under proper name:
                         if let Some(alias) = &name.alias {
@@ -371,8 +525,12 @@ impl<O: OutputStream> Compiler<O> {
             Expression { expression } => {
                 self.compile_expression(expression)?;
 
-                // Pop result of stack, since we not use it:
-                self.emit(Instruction::Pop);
+                if self.interactive && self.output_stack.len() == 1 {
+                    self.emit(Instruction::PrintExpr);
+                } else {
+                    // Pop result of stack, since we not use it:
+                    self.emit(Instruction::Pop);
+                }
             }
             Global { .. } | Nonlocal { .. } => {
                 // Handled during symbol table construction.
@@ -405,6 +563,7 @@ impl<O: OutputStream> Compiler<O> {
                 is_async,
                 items,
                 body,
+                ..
             } => {
                 let is_async = *is_async;
 
@@ -417,9 +576,7 @@ impl<O: OutputStream> Compiler<O> {
                         if is_async {
                             self.emit(Instruction::BeforeAsyncWith);
                             self.emit(Instruction::GetAwaitable);
-                            self.emit(Instruction::LoadConst {
-                                value: bytecode::Constant::None,
-                            });
+                            self.emit_constant(bytecode::Constant::None);
                             self.emit(Instruction::YieldFrom);
                             self.emit(Instruction::SetupAsyncWith { end: end_label });
                         } else {
@@ -448,9 +605,7 @@ impl<O: OutputStream> Compiler<O> {
 
                     if is_async {
                         self.emit(Instruction::GetAwaitable);
-                        self.emit(Instruction::LoadConst {
-                            value: bytecode::Constant::None,
-                        });
+                        self.emit_constant(bytecode::Constant::None);
                         self.emit(Instruction::YieldFrom);
                     }
 
@@ -463,6 +618,7 @@ impl<O: OutputStream> Compiler<O> {
                 iter,
                 body,
                 orelse,
+                ..
             } => self.compile_for(target, iter, body, orelse, *is_async)?,
             Raise { exception, cause } => match exception {
                 Some(value) => {
@@ -494,6 +650,7 @@ impl<O: OutputStream> Compiler<O> {
                 body,
                 decorator_list,
                 returns,
+                ..
             } => {
                 self.compile_function_def(name, args, body, decorator_list, returns, *is_async)?;
             }
@@ -509,8 +666,9 @@ impl<O: OutputStream> Compiler<O> {
                 if self.optimize == 0 {
                     let end_label = self.new_label();
                     self.compile_jump_if(test, true, end_label)?;
+                    let idx = self.name("AssertionError");
                     self.emit(Instruction::LoadName {
-                        name: String::from("AssertionError"),
+                        idx,
                         scope: bytecode::NameScope::Global,
                     });
                     match msg {
@@ -560,15 +718,13 @@ impl<O: OutputStream> Compiler<O> {
                         self.compile_expression(v)?;
                     }
                     None => {
-                        self.emit(Instruction::LoadConst {
-                            value: bytecode::Constant::None,
-                        });
+                        self.emit_constant(bytecode::Constant::None);
                     }
                 }
 
                 self.emit(Instruction::ReturnValue);
             }
-            Assign { targets, value } => {
+            Assign { targets, value, .. } => {
                 self.compile_expression(value)?;
 
                 for (i, target) in targets.iter().enumerate() {
@@ -603,18 +759,53 @@ impl<O: OutputStream> Compiler<O> {
         Ok(())
     }
 
+    /// Turn on the features of a `from __future__ import`. The statement still runs like any
+    /// other import.
+    fn compile_future_features(
+        &mut self,
+        statement: &ast::Statement,
+        names: &[ast::ImportSymbol],
+    ) -> Result<(), CompileError> {
+        let error = |message: String| CompileError {
+            error: CompileErrorType::SyntaxError(message),
+            location: statement.location.clone(),
+        };
+        if !self.future_imports_allowed {
+            return Err(error(
+                "from __future__ imports must occur at the beginning of the file".to_owned(),
+            ));
+        }
+        for name in names {
+            match name.symbol.as_str() {
+                "annotations" => self.future_annotations = true,
+                // These are always on.
+                "nested_scopes" | "generators" | "division" | "absolute_import"
+                | "with_statement" | "print_function" | "unicode_literals" | "generator_stop" => {}
+                "braces" => return Err(error("not a chance".to_owned())),
+                feature => {
+                    return Err(error(format!("future feature {} is not defined", feature)));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn compile_delete(&mut self, expression: &ast::Expression) -> Result<(), CompileError> {
         match &expression.node {
-            ast::ExpressionType::Identifier { name } => {
-                self.emit(Instruction::DeleteName {
-                    name: name.to_string(),
+            ast::ExpressionType::Identifier { name } if name == "__debug__" => {
+                return Err(CompileError {
+                    error: CompileErrorType::Delete("__debug__"),
+                    location: self.current_source_location.clone(),
                 });
             }
+            ast::ExpressionType::Identifier { name } => {
+                let idx = self.name(name);
+                self.emit(Instruction::DeleteName { idx });
+            }
             ast::ExpressionType::Attribute { value, name } => {
                 self.compile_expression(value)?;
-                self.emit(Instruction::DeleteAttr {
-                    name: name.to_string(),
-                });
+                let idx = self.name(name);
+                self.emit(Instruction::DeleteAttr { idx });
             }
             ast::ExpressionType::Subscript { a, b } => {
                 self.compile_expression(a)?;
@@ -647,16 +838,15 @@ impl<O: OutputStream> Compiler<O> {
             self.emit(Instruction::BuildTuple {
                 size,
                 unpack: false,
+                for_call: false,
             });
         }
 
         let mut num_kw_only_defaults = 0;
         for (kw, default) in args.kwonlyargs.iter().zip(&args.kw_defaults) {
             if let Some(default) = default {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::String {
-                        value: kw.arg.clone(),
-                    },
+                self.emit_constant(bytecode::Constant::String {
+                    value: kw.arg.clone(),
                 });
                 self.compile_expression(default)?;
                 num_kw_only_defaults += 1;
@@ -681,7 +871,12 @@ impl<O: OutputStream> Compiler<O> {
         let line_number = self.get_source_line_number();
         self.push_output(CodeObject::new(
             flags,
-            args.args.iter().map(|a| a.arg.clone()).collect(),
+            args.posonlyargs.len(),
+            args.posonlyargs
+                .iter()
+                .chain(&args.args)
+                .map(|a| a.arg.clone())
+                .collect(),
             compile_varargs(&args.vararg),
             args.kwonlyargs.iter().map(|a| a.arg.clone()).collect(),
             compile_varargs(&args.kwarg),
@@ -855,7 +1050,7 @@ impl<O: OutputStream> Compiler<O> {
 
         self.enter_function(name, args)?;
 
-        let (body, doc_str) = get_doc(body);
+        let (body, doc_str) = self.get_doc(body);
 
         self.compile_statements(body)?;
 
@@ -865,9 +1060,7 @@ impl<O: OutputStream> Compiler<O> {
                 // the last instruction is a ReturnValue already, we don't need to emit it
             }
             _ => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::None,
-                });
+                self.emit_constant(bytecode::Constant::None);
                 self.emit(Instruction::ReturnValue);
             }
         }
@@ -875,34 +1068,35 @@ impl<O: OutputStream> Compiler<O> {
         let mut code = self.pop_code_object();
         self.leave_scope();
 
-        // Prepare type annotations:
+        // Prepare type annotations, in the order CPython puts them in `__annotations__`:
         let mut num_annotations = 0;
 
-        // Return annotation:
-        if let Some(annotation) = returns {
+        fn named(varargs: &ast::Varargs) -> Option<&ast::Parameter> {
+            match varargs {
+                ast::Varargs::Named(parameter) => Some(parameter),
+                _ => None,
+            }
+        }
+        let parameters = args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .chain(named(&args.vararg))
+            .chain(&args.kwonlyargs)
+            .chain(named(&args.kwarg));
+        let annotations = parameters
+            .filter_map(|arg| Some((arg.arg.as_str(), arg.annotation.as_deref()?)))
+            .chain(returns.as_ref().map(|annotation| ("return", annotation)));
+        for (name, annotation) in annotations {
             // key:
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::String {
-                    value: "return".to_string(),
-                },
+            self.emit_constant(bytecode::Constant::String {
+                value: name.to_owned(),
             });
             // value:
-            self.compile_expression(annotation)?;
+            self.compile_annotation(annotation)?;
             num_annotations += 1;
         }
 
-        for arg in args.args.iter() {
-            if let Some(annotation) = &arg.annotation {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::String {
-                        value: arg.arg.to_string(),
-                    },
-                });
-                self.compile_expression(&annotation)?;
-                num_annotations += 1;
-            }
-        }
-
         if num_annotations > 0 {
             code.flags |= bytecode::CodeFlags::HAS_ANNOTATIONS;
             self.emit(Instruction::BuildMap {
@@ -916,15 +1110,11 @@ impl<O: OutputStream> Compiler<O> {
             code.flags |= bytecode::CodeFlags::IS_COROUTINE;
         }
 
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::Code {
-                code: Box::new(code),
-            },
+        self.emit_constant(bytecode::Constant::Code {
+            code: Box::new(code),
         });
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::String {
-                value: qualified_name,
-            },
+        self.emit_constant(bytecode::Constant::String {
+            value: qualified_name,
         });
 
         // Turn code object into function object:
@@ -962,6 +1152,7 @@ impl<O: OutputStream> Compiler<O> {
         let line_number = self.get_source_line_number();
         self.push_output(CodeObject::new(
             Default::default(),
+            0,
             vec![],
             Varargs::None,
             vec![],
@@ -972,53 +1163,49 @@ impl<O: OutputStream> Compiler<O> {
         ));
         self.enter_scope();
 
-        let (new_body, doc_str) = get_doc(body);
+        let (new_body, doc_str) = self.get_doc(body);
 
+        if find_ann(new_body) {
+            self.emit(Instruction::SetupAnnotations);
+        }
+        let idx = self.name("__name__");
         self.emit(Instruction::LoadName {
-            name: "__name__".to_string(),
+            idx,
             scope: bytecode::NameScope::Global,
         });
+        let idx = self.name("__module__");
         self.emit(Instruction::StoreName {
-            name: "__module__".to_string(),
+            idx,
             scope: bytecode::NameScope::Free,
         });
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::String {
-                value: qualified_name.clone(),
-            },
+        self.emit_constant(bytecode::Constant::String {
+            value: qualified_name.clone(),
         });
+        let idx = self.name("__qualname__");
         self.emit(Instruction::StoreName {
-            name: "__qualname__".to_string(),
+            idx,
             scope: bytecode::NameScope::Free,
         });
         self.compile_statements(new_body)?;
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::None,
-        });
+        self.emit_constant(bytecode::Constant::None);
         self.emit(Instruction::ReturnValue);
 
         let mut code = self.pop_code_object();
         code.flags &= !bytecode::CodeFlags::NEW_LOCALS;
         self.leave_scope();
 
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::Code {
-                code: Box::new(code),
-            },
+        self.emit_constant(bytecode::Constant::Code {
+            code: Box::new(code),
         });
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::String {
-                value: name.to_string(),
-            },
+        self.emit_constant(bytecode::Constant::String {
+            value: name.to_string(),
         });
 
         // Turn code object into function object:
         self.emit(Instruction::MakeFunction);
 
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::String {
-                value: qualified_name,
-            },
+        self.emit_constant(bytecode::Constant::String {
+            value: qualified_name,
         });
 
         for base in bases {
@@ -1039,10 +1226,8 @@ impl<O: OutputStream> Compiler<O> {
                 self.compile_expression(&keyword.value)?;
             }
 
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::Tuple {
-                    elements: kwarg_names,
-                },
+            self.emit_constant(bytecode::Constant::Tuple {
+                elements: kwarg_names,
             });
             self.emit(Instruction::CallFunction {
                 typ: CallType::Keyword(2 + keywords.len() + bases.len()),
@@ -1062,24 +1247,32 @@ impl<O: OutputStream> Compiler<O> {
         Ok(())
     }
 
+    /// Split the docstring off `body`, like `get_doc`. From optimize level 2, like `-OO` in
+    /// CPython, the docstring is dropped and the body gets no docstring at all.
+    fn get_doc<'a>(&self, body: &'a [ast::Statement]) -> (&'a [ast::Statement], Option<String>) {
+        let (body, doc) = get_doc(body);
+        if self.optimize >= 2 {
+            (body, None)
+        } else {
+            (body, doc)
+        }
+    }
+
     fn store_docstring(&mut self, doc_str: Option<String>) {
         // Duplicate top of stack (the function or class object)
         self.emit(Instruction::Duplicate);
 
         // Doc string value:
-        self.emit(Instruction::LoadConst {
-            value: match doc_str {
-                Some(doc) => bytecode::Constant::String {
-                    value: doc.to_string(),
-                },
-                None => bytecode::Constant::None, // set docstring None if not declared
+        self.emit_constant(match doc_str {
+            Some(doc) => bytecode::Constant::String {
+                value: doc.to_string(),
             },
+            None => bytecode::Constant::None, // set docstring None if not declared
         });
 
         self.emit(Instruction::Rotate { amount: 2 });
-        self.emit(Instruction::StoreAttr {
-            name: "__doc__".to_string(),
-        });
+        let idx = self.name("__doc__");
+        self.emit(Instruction::StoreAttr { idx });
     }
 
     fn compile_while(
@@ -1138,39 +1331,13 @@ impl<O: OutputStream> Compiler<O> {
         self.compile_expression(iter)?;
 
         if is_async {
-            let check_asynciter_label = self.new_label();
-            let body_label = self.new_label();
-
             self.emit(Instruction::GetAIter);
 
             self.set_label(start_label);
-            self.emit(Instruction::SetupExcept {
-                handler: check_asynciter_label,
-            });
-            self.emit(Instruction::GetANext);
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::None,
-            });
-            self.emit(Instruction::YieldFrom);
-            self.compile_store(target)?;
-            self.emit(Instruction::PopBlock);
-            self.emit(Instruction::Jump { target: body_label });
-
-            self.set_label(check_asynciter_label);
-            self.emit(Instruction::Duplicate);
-            self.emit(Instruction::LoadName {
-                name: "StopAsyncIteration".to_string(),
-                scope: bytecode::NameScope::Global,
-            });
-            self.emit(Instruction::CompareOperation {
-                op: bytecode::ComparisonOperator::ExceptionMatch,
-            });
-            self.emit(Instruction::JumpIfTrue { target: else_label });
-            self.emit(Instruction::Raise { argc: 0 });
+            self.compile_async_next(target, else_label)?;
 
             let was_in_loop = self.ctx.in_loop;
             self.ctx.in_loop = true;
-            self.set_label(body_label);
             self.compile_statements(body)?;
             self.ctx.in_loop = was_in_loop;
         } else {
@@ -1198,9 +1365,50 @@ impl<O: OutputStream> Compiler<O> {
             self.compile_statements(orelse)?;
         }
         self.set_label(end_label);
-        if is_async {
-            self.emit(Instruction::Pop);
-        }
+        Ok(())
+    }
+
+    /// Await the next item of the asynchronous iterator on top of the stack and store it in
+    /// `target`, jumping to `exhausted` (with the iterator still on the stack) once it raises
+    /// `StopAsyncIteration`.
+    fn compile_async_next(
+        &mut self,
+        target: &ast::Expression,
+        exhausted: Label,
+    ) -> Result<(), CompileError> {
+        let check_asynciter_label = self.new_label();
+        let stop_label = self.new_label();
+        let body_label = self.new_label();
+
+        self.emit(Instruction::SetupExcept {
+            handler: check_asynciter_label,
+        });
+        self.emit(Instruction::GetANext);
+        self.emit_constant(bytecode::Constant::None);
+        self.emit(Instruction::YieldFrom);
+        self.compile_store(target)?;
+        self.emit(Instruction::PopBlock);
+        self.emit(Instruction::Jump { target: body_label });
+
+        self.set_label(check_asynciter_label);
+        self.emit(Instruction::Duplicate);
+        let idx = self.name("StopAsyncIteration");
+        self.emit(Instruction::LoadName {
+            idx,
+            scope: bytecode::NameScope::Global,
+        });
+        self.emit(Instruction::CompareOperation {
+            op: bytecode::ComparisonOperator::ExceptionMatch,
+        });
+        self.emit(Instruction::JumpIfTrue { target: stop_label });
+        self.emit(Instruction::Raise { argc: 0 });
+
+        // Leave the handler so the exception doesn't outlive the loop:
+        self.set_label(stop_label);
+        self.emit(Instruction::PopException);
+        self.emit(Instruction::Jump { target: exhausted });
+
+        self.set_label(body_label);
         Ok(())
     }
 
@@ -1212,6 +1420,27 @@ impl<O: OutputStream> Compiler<O> {
         assert!(!ops.is_empty());
         assert_eq!(vals.len(), ops.len() + 1);
 
+        // Like CPython, warn about the first `is` with a literal, which compares identity where
+        // equality is meant.
+        let is_literal_comparison = ops.iter().enumerate().find_map(|(i, op)| {
+            let negated = match op {
+                ast::Comparison::Is => false,
+                ast::Comparison::IsNot => true,
+                _ => return None,
+            };
+            if is_literal(&vals[i]) || is_literal(&vals[i + 1]) {
+                Some(negated)
+            } else {
+                None
+            }
+        });
+        if let Some(negated) = is_literal_comparison {
+            self.warnings.push(CompileWarning {
+                warning: CompileWarningType::IsLiteral { negated },
+                location: vals[0].location.clone(),
+            });
+        }
+
         let to_operator = |op: &ast::Comparison| match op {
             ast::Comparison::Equal => bytecode::ComparisonOperator::Equal,
             ast::Comparison::NotEqual => bytecode::ComparisonOperator::NotEqual,
@@ -1280,24 +1509,60 @@ impl<O: OutputStream> Compiler<O> {
         annotation: &ast::Expression,
         value: &Option<ast::Expression>,
     ) -> Result<(), CompileError> {
+        let error = |message: &str| CompileError {
+            error: CompileErrorType::SyntaxError(message.to_owned()),
+            location: target.location.clone(),
+        };
+        match &target.node {
+            ast::ExpressionType::Identifier { .. }
+            | ast::ExpressionType::Attribute { .. }
+            | ast::ExpressionType::Subscript { .. } => {}
+            ast::ExpressionType::Tuple { .. } => {
+                return Err(error("only single target (not tuple) can be annotated"));
+            }
+            ast::ExpressionType::List { .. } => {
+                return Err(error("only single target (not list) can be annotated"));
+            }
+            _ => return Err(error("illegal target for annotation")),
+        }
+
         if let Some(value) = value {
             self.compile_expression(value)?;
             self.compile_store(target)?;
+        } else {
+            // Without a value, the parts of the target are still evaluated:
+            match &target.node {
+                ast::ExpressionType::Attribute { value, .. } => {
+                    self.compile_expression(value)?;
+                    self.emit(Instruction::Pop);
+                }
+                ast::ExpressionType::Subscript { a, b } => {
+                    self.compile_expression(a)?;
+                    self.emit(Instruction::Pop);
+                    self.compile_expression(b)?;
+                    self.emit(Instruction::Pop);
+                }
+                _ => {}
+            }
+        }
+
+        // Like CPython, the annotations of function locals are never evaluated:
+        if self.ctx.in_func() {
+            return Ok(());
         }
 
         // Compile annotation:
-        self.compile_expression(annotation)?;
+        self.compile_annotation(annotation)?;
 
         if let ast::ExpressionType::Identifier { name } = &target.node {
             // Store as dict entry in __annotations__ dict:
+            let idx = self.name("__annotations__");
             self.emit(Instruction::LoadName {
-                name: String::from("__annotations__"),
+                idx,
                 scope: bytecode::NameScope::Local,
             });
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::String {
-                    value: name.to_string(),
-                },
+            self.emit_constant(bytecode::Constant::String {
+                value: name.to_string(),
             });
             self.emit(Instruction::StoreSubscript);
         } else {
@@ -1307,8 +1572,27 @@ impl<O: OutputStream> Compiler<O> {
         Ok(())
     }
 
+    /// Compile the value of an annotation: its source as a string under `from __future__ import
+    /// annotations`, and the value of the expression otherwise.
+    fn compile_annotation(&mut self, annotation: &ast::Expression) -> Result<(), CompileError> {
+        if self.future_annotations {
+            self.emit_constant(bytecode::Constant::String {
+                value: unparse_expression(annotation),
+            });
+            Ok(())
+        } else {
+            self.compile_expression(annotation)
+        }
+    }
+
     fn compile_store(&mut self, target: &ast::Expression) -> Result<(), CompileError> {
         match &target.node {
+            ast::ExpressionType::Identifier { name } if name == "__debug__" => {
+                return Err(CompileError {
+                    error: CompileErrorType::Assign("__debug__"),
+                    location: self.current_source_location.clone(),
+                });
+            }
             ast::ExpressionType::Identifier { name } => {
                 self.store_name(name);
             }
@@ -1319,9 +1603,8 @@ impl<O: OutputStream> Compiler<O> {
             }
             ast::ExpressionType::Attribute { value, name } => {
                 self.compile_expression(value)?;
-                self.emit(Instruction::StoreAttr {
-                    name: name.to_string(),
-                });
+                let idx = self.name(name);
+                self.emit(Instruction::StoreAttr { idx });
             }
             ast::ExpressionType::List { elements } | ast::ExpressionType::Tuple { elements } => {
                 let mut seen_star = false;
@@ -1358,6 +1641,14 @@ impl<O: OutputStream> Compiler<O> {
                     }
                 }
             }
+            ast::ExpressionType::Starred { .. } => {
+                return Err(CompileError {
+                    error: CompileErrorType::SyntaxError(
+                        "starred assignment target must be in a list or tuple".to_owned(),
+                    ),
+                    location: self.current_source_location.clone(),
+                });
+            }
             _ => {
                 return Err(CompileError {
                     error: CompileErrorType::Assign(target.name()),
@@ -1588,9 +1879,8 @@ impl<O: OutputStream> Compiler<O> {
             }
             Attribute { value, name } => {
                 self.compile_expression(value)?;
-                self.emit(Instruction::LoadAttr {
-                    name: name.to_string(),
-                });
+                let idx = self.name(name);
+                self.emit(Instruction::LoadAttr { idx });
             }
             Compare { vals, ops } => {
                 self.compile_chained_comparison(vals, ops)?;
@@ -1605,7 +1895,7 @@ impl<O: OutputStream> Compiler<O> {
                         value: Complex64::new(*real, *imag),
                     },
                 };
-                self.emit(Instruction::LoadConst { value: const_value });
+                self.emit_constant(const_value);
             }
             List { elements } => {
                 let size = elements.len();
@@ -1621,6 +1911,7 @@ impl<O: OutputStream> Compiler<O> {
                 self.emit(Instruction::BuildTuple {
                     size,
                     unpack: must_unpack,
+                    for_call: false,
                 });
             }
             Set { elements } => {
@@ -1651,57 +1942,68 @@ impl<O: OutputStream> Compiler<O> {
                 self.mark_generator();
                 match value {
                     Some(expression) => self.compile_expression(expression)?,
-                    Option::None => self.emit(Instruction::LoadConst {
-                        value: bytecode::Constant::None,
-                    }),
+                    Option::None => self.emit_constant(bytecode::Constant::None),
                 };
                 self.emit(Instruction::YieldValue);
             }
             Await { value } => {
+                if !self.ctx.in_async_func() {
+                    return Err(CompileError {
+                        error: CompileErrorType::InvalidAwait,
+                        location: self.current_source_location.clone(),
+                    });
+                }
                 self.compile_expression(value)?;
                 self.emit(Instruction::GetAwaitable);
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::None,
-                });
+                self.emit_constant(bytecode::Constant::None);
                 self.emit(Instruction::YieldFrom);
             }
             YieldFrom { value } => {
+                match self.ctx.func {
+                    FunctionContext::NoFunction => {
+                        return Err(CompileError {
+                            error: CompileErrorType::InvalidYield,
+                            location: self.current_source_location.clone(),
+                        });
+                    }
+                    FunctionContext::AsyncFunction => {
+                        return Err(CompileError {
+                            error: CompileErrorType::AsyncYieldFrom,
+                            location: self.current_source_location.clone(),
+                        });
+                    }
+                    FunctionContext::Function => {}
+                }
                 self.mark_generator();
                 self.compile_expression(value)?;
-                self.emit(Instruction::GetIter);
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::None,
-                });
+                self.emit(Instruction::GetYieldFromIter);
+                self.emit_constant(bytecode::Constant::None);
                 self.emit(Instruction::YieldFrom);
             }
             True => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Boolean { value: true },
-                });
+                self.emit_constant(bytecode::Constant::Boolean { value: true });
             }
             False => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Boolean { value: false },
-                });
+                self.emit_constant(bytecode::Constant::Boolean { value: false });
             }
             None => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::None,
-                });
+                self.emit_constant(bytecode::Constant::None);
             }
             Ellipsis => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Ellipsis,
-                });
+                self.emit_constant(bytecode::Constant::Ellipsis);
             }
             String { value } => {
                 self.compile_string(value)?;
             }
             Bytes { value } => {
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Bytes {
-                        value: value.clone(),
-                    },
+                self.emit_constant(bytecode::Constant::Bytes {
+                    value: value.clone(),
+                });
+            }
+            // Like in CPython, `__debug__` is a constant fixed by the optimization level.
+            Identifier { name } if name == "__debug__" => {
+                self.emit_constant(bytecode::Constant::Boolean {
+                    value: self.optimize == 0,
                 });
             }
             Identifier { name } => {
@@ -1720,14 +2022,10 @@ impl<O: OutputStream> Compiler<O> {
                 self.emit(Instruction::ReturnValue);
                 let code = self.pop_code_object();
                 self.leave_scope();
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Code {
-                        code: Box::new(code),
-                    },
-                });
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::String { value: name },
+                self.emit_constant(bytecode::Constant::Code {
+                    code: Box::new(code),
                 });
+                self.emit_constant(bytecode::Constant::String { value: name });
                 // Turn code object into function object:
                 self.emit(Instruction::MakeFunction);
 
@@ -1739,11 +2037,16 @@ impl<O: OutputStream> Compiler<O> {
             Starred { .. } => {
                 return Err(CompileError {
                     error: CompileErrorType::SyntaxError(std::string::String::from(
-                        "Invalid starred expression",
+                        "can't use starred expression here",
                     )),
                     location: self.current_source_location.clone(),
                 });
             }
+            NamedExpression { target, value } => {
+                self.compile_expression(value)?;
+                self.emit(Instruction::Duplicate);
+                self.compile_store(target)?;
+            }
             IfExpression { test, body, orelse } => {
                 let no_label = self.new_label();
                 let end_label = self.new_label();
@@ -1773,10 +2076,8 @@ impl<O: OutputStream> Compiler<O> {
                 let mut subsize = 0;
                 for keyword in subkeywords {
                     if let Some(name) = &keyword.name {
-                        self.emit(Instruction::LoadConst {
-                            value: bytecode::Constant::String {
-                                value: name.to_string(),
-                            },
+                        self.emit_constant(bytecode::Constant::String {
+                            value: name.to_string(),
                         });
                         self.compile_expression(&keyword.value)?;
                         subsize += 1;
@@ -1818,6 +2119,7 @@ impl<O: OutputStream> Compiler<O> {
             self.emit(Instruction::BuildTuple {
                 size: args.len(),
                 unpack: must_unpack,
+                for_call: must_unpack,
             });
 
             // Create an optional map with kw-args:
@@ -1847,10 +2149,8 @@ impl<O: OutputStream> Compiler<O> {
                     self.compile_expression(&keyword.value)?;
                 }
 
-                self.emit(Instruction::LoadConst {
-                    value: bytecode::Constant::Tuple {
-                        elements: kwarg_names,
-                    },
+                self.emit_constant(bytecode::Constant::Tuple {
+                    elements: kwarg_names,
                 });
                 self.emit(Instruction::CallFunction {
                     typ: CallType::Keyword(count),
@@ -1885,6 +2185,7 @@ impl<O: OutputStream> Compiler<O> {
                     self.emit(Instruction::BuildTuple {
                         size: 1,
                         unpack: false,
+                        for_call: false,
                     });
                 }
             }
@@ -1901,6 +2202,14 @@ impl<O: OutputStream> Compiler<O> {
         // We must have at least one generator:
         assert!(!generators.is_empty());
 
+        let has_async_generator = generators.iter().any(|generator| generator.is_async);
+        if has_async_generator && !self.ctx.in_async_func() {
+            return Err(CompileError {
+                error: CompileErrorType::InvalidAsyncComprehension,
+                location: self.current_source_location.clone(),
+            });
+        }
+
         let name = match kind {
             ast::ComprehensionKind::GeneratorExpression { .. } => "<genexpr>",
             ast::ComprehensionKind::List { .. } => "<listcomp>",
@@ -1913,6 +2222,7 @@ impl<O: OutputStream> Compiler<O> {
         // Create magnificent function <listcomp>:
         self.push_output(CodeObject::new(
             Default::default(),
+            0,
             vec![".0".to_string()],
             Varargs::None,
             vec![],
@@ -1949,14 +2259,24 @@ impl<O: OutputStream> Compiler<O> {
 
         let mut loop_labels = vec![];
         for generator in generators {
+            let start_label = self.new_label();
+            let end_label = self.new_label();
+            loop_labels.push((start_label, end_label));
+
+            // An async loop keeps its iterator on the stack when exhausted, so set up the loop
+            // block first to have it popped along with the block:
             if generator.is_async {
-                unimplemented!("async for comprehensions");
+                self.emit(Instruction::SetupLoop {
+                    start: start_label,
+                    end: end_label,
+                });
             }
 
-            if loop_labels.is_empty() {
+            if loop_labels.len() == 1 {
                 // Load iterator onto stack (passed as first argument):
+                let idx = self.name(".0");
                 self.emit(Instruction::LoadName {
-                    name: String::from(".0"),
+                    idx,
                     scope: bytecode::NameScope::Local,
                 });
             } else {
@@ -1964,21 +2284,27 @@ impl<O: OutputStream> Compiler<O> {
                 self.compile_expression(&generator.iter)?;
 
                 // Get iterator / turn item into an iterator
-                self.emit(Instruction::GetIter);
+                if generator.is_async {
+                    self.emit(Instruction::GetAIter);
+                } else {
+                    self.emit(Instruction::GetIter);
+                }
             }
 
-            // Setup for loop:
-            let start_label = self.new_label();
-            let end_label = self.new_label();
-            loop_labels.push((start_label, end_label));
-            self.emit(Instruction::SetupLoop {
-                start: start_label,
-                end: end_label,
-            });
-            self.set_label(start_label);
-            self.emit(Instruction::ForIter { target: end_label });
+            if generator.is_async {
+                self.set_label(start_label);
+                self.compile_async_next(&generator.target, end_label)?;
+            } else {
+                // Setup for loop:
+                self.emit(Instruction::SetupLoop {
+                    start: start_label,
+                    end: end_label,
+                });
+                self.set_label(start_label);
+                self.emit(Instruction::ForIter { target: end_label });
 
-            self.compile_store(&generator.target)?;
+                self.compile_store(&generator.target)?;
+            }
 
             // Now evaluate the ifs:
             for if_condition in &generator.ifs {
@@ -2030,22 +2356,31 @@ impl<O: OutputStream> Compiler<O> {
         self.emit(Instruction::ReturnValue);
 
         // Fetch code for listcomp function:
-        let code = self.pop_code_object();
+        let mut code = self.pop_code_object();
+
+        // Awaiting inside the comprehension makes it a coroutine, to be awaited in turn:
+        let is_async =
+            has_async_generator || code.instructions.contains(&Instruction::GetAwaitable);
+        if is_async {
+            if let ast::ComprehensionKind::GeneratorExpression { .. } = kind {
+                return Err(CompileError {
+                    error: CompileErrorType::AsyncGeneratorExpression,
+                    location: self.current_source_location.clone(),
+                });
+            }
+            code.flags |= bytecode::CodeFlags::IS_COROUTINE;
+        }
 
         // Pop scope
         self.leave_scope();
 
         // List comprehension code:
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::Code {
-                code: Box::new(code),
-            },
+        self.emit_constant(bytecode::Constant::Code {
+            code: Box::new(code),
         });
 
         // List comprehension function name:
-        self.emit(Instruction::LoadConst {
-            value: bytecode::Constant::String { value: name },
-        });
+        self.emit_constant(bytecode::Constant::String { value: name });
 
         // Turn code object into function object:
         self.emit(Instruction::MakeFunction);
@@ -2054,20 +2389,28 @@ impl<O: OutputStream> Compiler<O> {
         self.compile_expression(&generators[0].iter)?;
 
         // Get iterator / turn item into an iterator
-        self.emit(Instruction::GetIter);
+        if generators[0].is_async {
+            self.emit(Instruction::GetAIter);
+        } else {
+            self.emit(Instruction::GetIter);
+        }
 
         // Call just created <listcomp> function:
         self.emit(Instruction::CallFunction {
             typ: CallType::Positional(1),
         });
+
+        if is_async {
+            self.emit(Instruction::GetAwaitable);
+            self.emit_constant(bytecode::Constant::None);
+            self.emit(Instruction::YieldFrom);
+        }
         Ok(())
     }
 
     fn compile_string(&mut self, string: &ast::StringGroup) -> Result<(), CompileError> {
         if let Some(value) = try_get_constant_string(string) {
-            self.emit(Instruction::LoadConst {
-                value: bytecode::Constant::String { value },
-            });
+            self.emit_constant(bytecode::Constant::String { value });
         } else {
             match string {
                 ast::StringGroup::Joined { values } => {
@@ -2077,10 +2420,8 @@ impl<O: OutputStream> Compiler<O> {
                     self.emit(Instruction::BuildString { size: values.len() })
                 }
                 ast::StringGroup::Constant { value } => {
-                    self.emit(Instruction::LoadConst {
-                        value: bytecode::Constant::String {
-                            value: value.to_string(),
-                        },
+                    self.emit_constant(bytecode::Constant::String {
+                        value: value.to_string(),
                     });
                 }
                 ast::StringGroup::FormattedValue {
@@ -2089,9 +2430,14 @@ impl<O: OutputStream> Compiler<O> {
                     spec,
                 } => {
                     self.compile_expression(value)?;
+                    match spec {
+                        Some(spec) => self.compile_string(spec)?,
+                        None => self.emit_constant(bytecode::Constant::String {
+                            value: String::new(),
+                        }),
+                    }
                     self.emit(Instruction::FormatValue {
                         conversion: conversion.map(compile_conversion_flag),
-                        spec: spec.clone(),
                     });
                 }
             }
@@ -2133,6 +2479,16 @@ impl<O: OutputStream> Compiler<O> {
         self.current_output().emit(instruction, location);
     }
 
+    fn emit_constant(&mut self, constant: bytecode::Constant) {
+        let idx = self.current_output().add_constant(constant);
+        self.emit(Instruction::LoadConst { idx })
+    }
+
+    /// The index of `name` in the names of the code object being compiled.
+    fn name(&mut self, name: &str) -> bytecode::NameIdx {
+        self.current_output().add_name(name)
+    }
+
     fn current_output(&mut self) -> &mut O {
         self.output_stack
             .last_mut()
@@ -2185,6 +2541,34 @@ fn get_doc(body: &[ast::Statement]) -> (&[ast::Statement], Option<String>) {
     (body, None)
 }
 
+/// Whether `body` has an annotated assignment that needs `__annotations__`, outside of nested
+/// function and class definitions.
+fn find_ann(body: &[ast::Statement]) -> bool {
+    use ast::StatementType::*;
+
+    let option_find_ann =
+        |suite: &Option<ast::Suite>| suite.as_ref().map_or(false, |s| find_ann(s));
+    body.iter().any(|statement| match &statement.node {
+        AnnAssign { .. } => true,
+        If { body, orelse, .. } | While { body, orelse, .. } | For { body, orelse, .. } => {
+            find_ann(body) || option_find_ann(orelse)
+        }
+        With { body, .. } => find_ann(body),
+        Try {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+        } => {
+            find_ann(body)
+                || handlers.iter().any(|handler| find_ann(&handler.body))
+                || option_find_ann(orelse)
+                || option_find_ann(finalbody)
+        }
+        _ => false,
+    })
+}
+
 fn try_get_constant_string(string: &ast::StringGroup) -> Option<String> {
     fn get_constant_string_inner(out_string: &mut String, string: &ast::StringGroup) -> bool {
         match string {
@@ -2206,6 +2590,25 @@ fn try_get_constant_string(string: &ast::StringGroup) -> Option<String> {
     }
 }
 
+/// Whether `expression` is a literal, which CPython folds into a constant, other than `None`,
+/// `True`, `False` and `...`, whose identity is well-defined.
+fn is_literal(expression: &ast::Expression) -> bool {
+    use ast::ExpressionType::*;
+    match &expression.node {
+        Number { .. } | Bytes { .. } => true,
+        String { value } => try_get_constant_string(value).is_some(),
+        Unop { op, a } => match op {
+            ast::UnaryOperator::Not => false,
+            _ => is_literal(a),
+        },
+        Tuple { elements } => elements.iter().all(|element| match element.node {
+            True | False | None | Ellipsis => true,
+            _ => is_literal(element),
+        }),
+        _ => false,
+    }
+}
+
 fn compile_location(location: &ast::Location) -> bytecode::Location {
     bytecode::Location::new(location.row(), location.column())
 }
@@ -2228,12 +2631,21 @@ fn compile_conversion_flag(conversion_flag: ast::ConversionFlag) -> bytecode::Co
 
 #[cfg(test)]
 mod tests {
-    use super::Compiler;
-    use crate::symboltable::make_symbol_table;
+    use super::{ast, Compiler, Mode};
+    use crate::error::CompileError;
+    use crate::output_stream::{CodeObjectStream, OutputStream};
+    use crate::symboltable::{make_symbol_table, SymbolScope, SymbolTable};
     use rustpython_bytecode::bytecode::Constant::*;
     use rustpython_bytecode::bytecode::Instruction::*;
-    use rustpython_bytecode::bytecode::{CodeObject, Label};
+    use rustpython_bytecode::bytecode::{
+        CodeFlags, CodeObject, ConstIdx, Constant, Instruction, Label, Location, NameIdx,
+        NameScope, NO_CACHE_SLOT,
+    };
+    use rustpython_bytecode::pyc::{self, PycError};
     use rustpython_parser::parser;
+    use std::fs;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
 
     fn compile_exec(source: &str) -> CodeObject {
         let mut compiler: Compiler = Default::default();
@@ -2245,33 +2657,125 @@ mod tests {
         compiler.pop_code_object()
     }
 
+    #[test]
+    fn test_symbol_scopes() {
+        let source = "\
+def f(x):
+    y = 1
+    class C:
+        y = 2
+        def g(self):
+            nonlocal y
+            return x, __class__
+    return C
+";
+        let ast = parser::parse_program(source).unwrap();
+        let table = make_symbol_table(&ast).unwrap();
+        let f = &table.sub_tables[0];
+        let class = &f.sub_tables[0];
+        let g = &class.sub_tables[0];
+        let scope = |table: &SymbolTable, name| table.lookup(name).unwrap().scope;
+        assert_eq!(scope(&table, "f"), SymbolScope::Local);
+        assert_eq!(scope(f, "x"), SymbolScope::Cell);
+        assert_eq!(scope(f, "y"), SymbolScope::Cell);
+        assert_eq!(scope(class, "x"), SymbolScope::Free);
+        assert_eq!(scope(class, "y"), SymbolScope::Local);
+        assert_eq!(scope(g, "x"), SymbolScope::Free);
+        assert_eq!(scope(g, "y"), SymbolScope::Free);
+        assert_eq!(scope(g, "__class__"), SymbolScope::Free);
+        assert!(class.lookup("__class__").is_none());
+        assert!(g.lookup("y").unwrap().is_nonlocal);
+
+        // Assigning to an attribute or an item of a name doesn't make it a local
+        let ast = parser::parse_program("def h():\n    a.b = 1\n    del c[d]\n").unwrap();
+        let table = make_symbol_table(&ast).unwrap();
+        let h = &table.sub_tables[0];
+        for name in &["a", "c", "d"] {
+            assert_eq!(scope(h, name), SymbolScope::GlobalImplicit, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_symbol_errors() {
+        let error = |source: &str| {
+            let ast = parser::parse_program(source).unwrap();
+            let error = CompileError::from(make_symbol_table(&ast).unwrap_err());
+            (error.error.to_string(), error.location.row())
+        };
+        let cases = [
+            (
+                "nonlocal x\n",
+                "nonlocal declaration not allowed at module level",
+                1,
+            ),
+            (
+                "def f(x):\n    global x\n",
+                "name 'x' is parameter and global",
+                2,
+            ),
+            (
+                "def f(x):\n    nonlocal x\n",
+                "name 'x' is parameter and nonlocal",
+                2,
+            ),
+            (
+                "def f():\n    x = 1\n    global x\n",
+                "name 'x' is assigned to before global declaration",
+                3,
+            ),
+            (
+                "def f():\n    print(x)\n    global x\n",
+                "name 'x' is used prior to global declaration",
+                3,
+            ),
+            (
+                "def f():\n    x = 1\n    def g():\n        global x\n        nonlocal x\n",
+                "name 'x' is nonlocal and global",
+                5,
+            ),
+        ];
+        for (source, message, row) in cases.iter() {
+            assert_eq!(error(source), (message.to_string(), *row));
+        }
+
+        // A nonlocal name has to be bound in a function around it, but not before.
+        for source in &[
+            "def f():\n    nonlocal x\n",
+            "x = 1\ndef f():\n    nonlocal x\n",
+            "def f():\n    x = 1\n    class C:\n        def g():\n            global x\n            def h():\n                nonlocal x\n",
+        ] {
+            assert_eq!(error(source).0, "no binding for nonlocal 'x' found");
+        }
+        let ast = parser::parse_program("def f():\n    def g():\n        nonlocal x\n    x = 1\n")
+            .unwrap();
+        assert!(make_symbol_table(&ast).is_ok());
+    }
+
     #[test]
     fn test_if_ors() {
         let code = compile_exec("if True or False or False:\n pass\n");
         assert_eq!(
             vec![
-                LoadConst {
-                    value: Boolean { value: true }
-                },
+                LoadConst { idx: 0 },
                 JumpIfTrue {
                     target: Label::new(1)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfTrue {
                     target: Label::new(1)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst { value: None },
+                LoadConst { idx: 2 },
                 ReturnValue
             ],
-            code.instructions
+            *code.instructions
+        );
+        assert_eq!(
+            code.constants,
+            [Boolean { value: true }, Boolean { value: false }, None]
         );
     }
 
@@ -2280,28 +2784,26 @@ mod tests {
         let code = compile_exec("if True and False and False:\n pass\n");
         assert_eq!(
             vec![
-                LoadConst {
-                    value: Boolean { value: true }
-                },
+                LoadConst { idx: 0 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst { value: None },
+                LoadConst { idx: 2 },
                 ReturnValue
             ],
-            code.instructions
+            *code.instructions
+        );
+        assert_eq!(
+            code.constants,
+            [Boolean { value: true }, Boolean { value: false }, None]
         );
     }
 
@@ -2310,34 +2812,30 @@ mod tests {
         let code = compile_exec("if (True and False) or (False and True):\n pass\n");
         assert_eq!(
             vec![
-                LoadConst {
-                    value: Boolean { value: true }
-                },
+                LoadConst { idx: 0 },
                 JumpIfFalse {
                     target: Label::new(2)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfTrue {
                     target: Label::new(1)
                 },
-                LoadConst {
-                    value: Boolean { value: false }
-                },
+                LoadConst { idx: 1 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst {
-                    value: Boolean { value: true }
-                },
+                LoadConst { idx: 0 },
                 JumpIfFalse {
                     target: Label::new(0)
                 },
-                LoadConst { value: None },
+                LoadConst { idx: 2 },
                 ReturnValue
             ],
-            code.instructions
+            *code.instructions
+        );
+        assert_eq!(
+            code.constants,
+            [Boolean { value: true }, Boolean { value: false }, None]
         );
     }
 
@@ -2347,17 +2845,969 @@ mod tests {
         assert_eq!(
             code.instructions,
             vec![
-                LoadConst {
-                    value: Integer { value: 10.into() }
-                },
+                LoadConst { idx: 0 },
                 Pop,
-                LoadConst {
-                    value: Float { value: 3.75 }
-                },
+                LoadConst { idx: 1 },
                 Pop,
-                LoadConst { value: None },
+                LoadConst { idx: 2 },
                 ReturnValue,
             ]
         );
+        // The constants that were folded are left out
+        assert_eq!(
+            code.constants,
+            [Integer { value: 10.into() }, Float { value: 3.75 }, None]
+        );
+    }
+
+    #[test]
+    fn test_constants_and_names_are_shared() {
+        let code = compile_exec("x = 'spam'\ny = 'spam'\nx.y = (1, 2)\nz = (1, 2)\n");
+        assert_eq!(
+            code.constants,
+            [
+                String {
+                    value: "spam".to_owned()
+                },
+                Tuple {
+                    elements: vec![Integer { value: 1.into() }, Integer { value: 2.into() }]
+                },
+                None
+            ]
+        );
+        // An attribute has the same entry as a variable of its name
+        assert_eq!(code.names, ["x", "y", "z"]);
+        assert_eq!(code.instructions[0], code.instructions[2]);
+    }
+
+    /// The disassembly of `code`, a line per instruction with the spacing collapsed.
+    fn dis(code: &CodeObject) -> Vec<std::string::String> {
+        code.to_string()
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect()
+    }
+
+    #[test]
+    fn test_fold_power() {
+        assert_eq!(
+            dis(&compile_exec("x = 2 ** 10\n")),
+            [
+                "1 0 LoadConst (1024)",
+                "1 StoreName (x, Free)",
+                "2 LoadConst (None)",
+                "3 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_division_by_zero_not_folded() {
+        assert_eq!(
+            dis(&compile_exec("1 / 0\n")),
+            [
+                "1 0 LoadConst (1)",
+                "1 LoadConst (0)",
+                "2 BinaryOperation (Divide, false)",
+                "3 Pop",
+                "4 LoadConst (None)",
+                "5 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        let folds = vec![
+            (
+                "60 * 60 * 24",
+                Integer {
+                    value: 86400.into(),
+                },
+            ),
+            ("-1", Integer { value: (-1).into() }),
+            ("~5", Integer { value: (-6).into() }),
+            ("-7 // 2", Integer { value: (-4).into() }),
+            ("-7 % 3", Integer { value: 2.into() }),
+            ("1 << 100 >> 99", Integer { value: 2.into() }),
+            ("10 / 4", Float { value: 2.5 }),
+            ("0.1 + 0.2", Float { value: 0.1 + 0.2 }),
+            ("-0.0", Float { value: -0.0 }),
+            ("2 * 1.5", Float { value: 3.0 }),
+            (
+                "1 - 2j",
+                Complex {
+                    value: num_complex::Complex64::new(1.0, -2.0),
+                },
+            ),
+            (
+                "'ab' + 'cd'",
+                String {
+                    value: "abcd".to_owned(),
+                },
+            ),
+            (
+                "'ab' * -1",
+                String {
+                    value: "".to_owned(),
+                },
+            ),
+            (
+                "2 * b'ab'",
+                Bytes {
+                    value: b"abab".to_vec(),
+                },
+            ),
+            (
+                "(1, 'a', (2.5, -3))",
+                Tuple {
+                    elements: vec![
+                        Integer { value: 1.into() },
+                        String {
+                            value: "a".to_owned(),
+                        },
+                        Tuple {
+                            elements: vec![Float { value: 2.5 }, Integer { value: (-3).into() }],
+                        },
+                    ],
+                },
+            ),
+        ];
+        for (source, value) in folds {
+            let code = compile_exec(&format!("x = {}\n", source));
+            assert_eq!(code.instructions[0], LoadConst { idx: 0 }, "{}", source);
+            assert_eq!(code.instructions.len(), 4, "{}", source);
+            assert_eq!(code.constants, [value, None], "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_constant_folding_leaves_runtime_behavior() {
+        let sources = [
+            // These raise
+            "1 // 0",
+            "1 % 0",
+            "1.0 / -0.0",
+            "0.0 ** 2",
+            "1 << -1",
+            "'a' * (1 << 100)",
+            // The VM rounds the big int or raises if it doesn't fit in a float
+            "(1 << 60) * 1.0",
+            // Too big to keep as constants
+            "2 ** 1000",
+            "'a' * 10000",
+            "2 ** -1",
+            "True + 1",
+            "not 1",
+        ];
+        for source in &sources {
+            let code = compile_exec(&format!("x = {}\n", source));
+            assert!(code.instructions.len() > 4, "{}\n{}", source, code);
+        }
+    }
+
+    #[test]
+    fn test_constant_folding_respects_jump_targets() {
+        // A jump lands between the constants, so `+ 2` isn't only applied to 1
+        assert_eq!(
+            dis(&compile_exec("x = (c or 1) + 2\n")),
+            [
+                "1 0 LoadName (c, Free)",
+                "1 JumpIfTrueOrPop (3)",
+                "2 LoadConst (1)",
+                ">> 3 LoadConst (2)",
+                "4 BinaryOperation (Add, false)",
+                "5 StoreName (x, Free)",
+                "6 LoadConst (None)",
+                "7 ReturnValue",
+            ]
+        );
+        // The same goes for unpacking a tuple right after it's built
+        let code = compile_exec("a, b = c if d else (e, f)\n");
+        assert!(code
+            .instructions
+            .iter()
+            .any(|instruction| *instruction == UnpackSequence { size: 2 }));
+    }
+
+    /// The code of the first function defined in `source`.
+    fn compile_function(source: &str) -> CodeObject {
+        compile_exec(source)
+            .constants
+            .iter()
+            .find_map(|constant| match constant {
+                Code { code } => Some(*code.clone()),
+                _ => std::option::Option::None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_jumps_while_true_break() {
+        // The jump back to the start after `break` never runs
+        assert_eq!(
+            dis(&compile_exec("while True:\n    break\n")),
+            [
+                "1 0 SetupLoop (1, 5)",
+                ">> 1 LoadConst (true)",
+                "2 JumpIfFalse (4)",
+                "2 3 Break",
+                ">> 4 PopBlock",
+                ">> 5 LoadConst (None)",
+                "6 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_code_after_return() {
+        let code = compile_function("def f():\n    return 1\n    x = 2\n");
+        assert_eq!(dis(&code), ["2 0 LoadConst (1)", "1 ReturnValue"]);
+        assert_eq!(code.line_table.len(), 2);
+        assert_eq!(code.constants, [Integer { value: 1.into() }]);
+
+        // Only up to where the else branch starts, and the inline caches are numbered again
+        let code = compile_function(
+            "def f(a):\n    if a:\n        return 1\n        g.x\n    return h.y\n",
+        );
+        assert_eq!(
+            dis(&code),
+            [
+                "2 0 LoadName (a, Local)",
+                "1 JumpIfFalse (4)",
+                "3 2 LoadConst (1)",
+                "3 ReturnValue",
+                "5 >> 4 LoadName (h, Global)",
+                "5 LoadAttr (y)",
+                "6 ReturnValue",
+            ]
+        );
+        assert_eq!(
+            *code.cache_slots,
+            [
+                NO_CACHE_SLOT,
+                NO_CACHE_SLOT,
+                NO_CACHE_SLOT,
+                NO_CACHE_SLOT,
+                0,
+                1,
+                NO_CACHE_SLOT
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jump_chains() {
+        // The end of the inner if jumps straight to the end of the outer one
+        assert_eq!(
+            dis(&compile_exec(
+                "if a:\n    if b:\n        c\n    else:\n        d\nelse:\n    e\n"
+            )),
+            [
+                "1 0 LoadName (a, Free)",
+                "1 JumpIfFalse (10)",
+                "2 2 LoadName (b, Free)",
+                "3 JumpIfFalse (7)",
+                "3 4 LoadName (c, Free)",
+                "5 Pop",
+                "6 Jump (12)",
+                "5 >> 7 LoadName (d, Free)",
+                "8 Pop",
+                ">> 9 Jump (12)",
+                "7 >> 10 LoadName (e, Free)",
+                "11 Pop",
+                ">> 12 LoadConst (None)",
+                "13 ReturnValue",
+            ]
+        );
+        // A jump to the next instruction goes
+        assert_eq!(
+            dis(&compile_exec("if a:\n    pass\nelse:\n    pass\n")),
+            [
+                "1 0 LoadName (a, Free)",
+                "1 JumpIfFalse (2)",
+                "4 >> 2 LoadConst (None)",
+                "3 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unreachable_code_keeps_handlers() {
+        let code = compile_function(
+            "def f():\n    try:\n        return 1\n    except E:\n        return 2\n    finally:\n        g()\n",
+        );
+        assert_eq!(
+            dis(&code),
+            [
+                "2 0 SetupFinally (14)",
+                "1 SetupExcept (4)",
+                "3 2 LoadConst (1)",
+                "3 ReturnValue",
+                ">> 4 Duplicate",
+                "4 5 LoadName (E, Global)",
+                "6 CompareOperation (ExceptionMatch)",
+                "7 JumpIfFalse (11)",
+                "8 Pop",
+                "5 9 LoadConst (2)",
+                "10 ReturnValue",
+                ">> 11 Raise (0)",
+                ">> 12 PopBlock",
+                "13 EnterFinally",
+                "7 >> 14 LoadName (g, Global)",
+                "15 CallFunction (Positional(0))",
+                "16 Pop",
+                "17 EndFinally",
+                "18 LoadConst (None)",
+                "19 ReturnValue",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optimize_strips_docstrings() {
+        let source = "'''module doc'''\ndef f():\n    '''function doc'''\n";
+        let has_docstring = |optimize, doc: &str| {
+            let code = super::compile(source, Mode::Exec, "source_path".to_string(), optimize);
+            code.unwrap().constants.contains(&String {
+                value: doc.to_string(),
+            })
+        };
+        assert!(has_docstring(1, "module doc"));
+        assert!(has_docstring(1, "function doc"));
+        assert!(!has_docstring(2, "module doc"));
+        assert!(!has_docstring(2, "function doc"));
+    }
+
+    #[test]
+    fn test_optimize_removes_asserts() {
+        let compile = |source, optimize| {
+            super::compile(source, Mode::Exec, "source_path".to_string(), optimize).unwrap()
+        };
+        let raises = |code: &CodeObject| {
+            code.instructions
+                .iter()
+                .any(|instruction| match instruction {
+                    Raise { .. } => true,
+                    _ => false,
+                })
+        };
+        assert!(raises(&compile("assert x", 0)));
+        assert!(!raises(&compile("assert x", 1)));
+
+        let debug = |optimize| {
+            let code = compile("__debug__", optimize);
+            match code.instructions[0] {
+                LoadConst { idx } => code.constants[idx].clone(),
+                ref instruction => panic!("{:?} loads no constant", instruction),
+            }
+        };
+        assert_eq!(debug(0), Boolean { value: true });
+        assert_eq!(debug(1), Boolean { value: false });
+
+        for source in &["__debug__ = 1", "del __debug__"] {
+            let code = super::compile(source, Mode::Exec, "source_path".to_string(), 0);
+            assert!(code.is_err());
+        }
+    }
+
+    fn nested_code(code: &CodeObject) -> &CodeObject {
+        code.constants
+            .iter()
+            .find_map(|constant| match constant {
+                Code { code } => Some(code.as_ref()),
+                _ => Option::None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_setup_annotations() {
+        let code = compile_exec("if x:\n    y: int = 1\n");
+        assert_eq!(code.instructions[0], SetupAnnotations);
+
+        let code = compile_exec("class C:\n    '''doc'''\n    y: int\n");
+        assert!(!code.instructions.contains(&SetupAnnotations));
+        let class_code = nested_code(&code);
+        assert_eq!(class_code.instructions[0], SetupAnnotations);
+
+        // Annotations in functions are never stored, and neither is the annotation evaluated
+        let code = compile_exec("def f():\n    y: int = 1\n");
+        assert!(!code.instructions.contains(&SetupAnnotations));
+        let function_code = nested_code(&code);
+        assert!(!function_code.instructions.contains(&SetupAnnotations));
+        assert!(!function_code
+            .instructions
+            .iter()
+            .any(|instruction| match instruction {
+                LoadName { idx, .. } => {
+                    let name = &function_code.names[*idx];
+                    name == "int" || name == "__annotations__"
+                }
+                _ => false,
+            }));
+    }
+
+    #[test]
+    fn test_future_annotations() {
+        let code = compile_exec(
+            "from __future__ import annotations\nx: List[int]\ndef f(a: lambda: 0) -> x.y: pass\n",
+        );
+        let loads_string = |value: &str| {
+            code.constants.contains(&String {
+                value: value.to_owned(),
+            })
+        };
+        assert!(loads_string("List[int]"));
+        assert!(loads_string("lambda: 0"));
+        assert!(loads_string("x.y"));
+        // No annotation is evaluated.
+        assert!(!code
+            .instructions
+            .iter()
+            .any(|instruction| match instruction {
+                LoadName { idx, .. } => code.names[*idx] == "List" || code.names[*idx] == "x",
+                _ => false,
+            }));
+
+        let result = super::compile(
+            "x = 1\nfrom __future__ import annotations\n",
+            Mode::Exec,
+            "source_path".to_string(),
+            0,
+        );
+        assert!(result.is_err());
+
+        // Every code object is marked, and the feature may be turned on from the start.
+        assert!(code.flags.contains(CodeFlags::FUTURE_ANNOTATIONS));
+        assert!(nested_code(&code)
+            .flags
+            .contains(CodeFlags::FUTURE_ANNOTATIONS));
+        let code = super::compile_with_flags(
+            "def f(a: lambda: 0): pass\n",
+            Mode::Exec,
+            "source_path".to_string(),
+            0,
+            CodeFlags::FUTURE_ANNOTATIONS,
+        )
+        .unwrap();
+        assert!(code.constants.contains(&String {
+            value: "lambda: 0".to_owned()
+        }));
+        assert!(nested_code(&code)
+            .flags
+            .contains(CodeFlags::FUTURE_ANNOTATIONS));
+        let code = super::compile("x: int\n", Mode::Exec, "source_path".to_string(), 0).unwrap();
+        assert!(!code.flags.contains(CodeFlags::FUTURE_ANNOTATIONS));
+    }
+
+    #[test]
+    fn test_named_expression_in_comprehension() {
+        let stores = |code: &CodeObject| {
+            code.instructions
+                .iter()
+                .filter_map(|instruction| match instruction {
+                    StoreName { idx, scope } if code.names[*idx] == "y" => Some(scope.clone()),
+                    _ => Option::None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let code = compile_exec("[y for x in z if (y := x)]\n");
+        assert_eq!(stores(nested_code(&code)), vec![NameScope::Global]);
+
+        let code = compile_exec("def f():\n    [y := x for x in z]\n    return y\n");
+        let function_code = nested_code(&code);
+        assert_eq!(
+            stores(nested_code(function_code)),
+            vec![NameScope::NonLocal]
+        );
+    }
+
+    #[test]
+    fn test_comprehension_nested_scopes() {
+        // The functions in a comprehension take the symbol tables in the order they're compiled
+        let loads = |code: &CodeObject| {
+            code.instructions
+                .iter()
+                .filter_map(|instruction| match instruction {
+                    LoadName { idx, scope } => Some((code.names[*idx].clone(), scope.clone())),
+                    _ => Option::None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let code = compile_exec("[lambda: z for x in a if (lambda y: y)(x)]\n");
+        let functions: Vec<_> = nested_code(&code)
+            .constants
+            .iter()
+            .filter_map(|constant| match constant {
+                Code { code } => Some(code.as_ref()),
+                _ => Option::None,
+            })
+            .collect();
+        assert_eq!(
+            loads(functions[0]),
+            vec![("y".to_string(), NameScope::Local)]
+        );
+        assert_eq!(
+            loads(functions[1]),
+            vec![("z".to_string(), NameScope::Global)]
+        );
+
+        compile_exec("{(lambda: k): (lambda: 1) for k in a}\n");
+    }
+
+    #[test]
+    fn test_named_expression_errors() {
+        let sources = [
+            "[i := 0 for i in range(3)]",
+            "[x for x in range(3) if (x := 1)]",
+            "[[(j := 0) for i in range(3)] for j in range(3)]",
+            "class C:\n    [(y := x) for x in range(3)]",
+        ];
+        for source in sources.iter() {
+            let result = super::compile(source, Mode::Exec, "source_path".to_string(), 0);
+            assert!(result.is_err(), "{:?} should not compile", source);
+        }
+    }
+
+    #[test]
+    fn test_async_comprehension() {
+        let code = compile_exec("async def f():\n    return [x async for x in y]\n");
+        let function_code = nested_code(&code);
+        assert!(function_code.flags.contains(CodeFlags::IS_COROUTINE));
+        let comprehension = nested_code(function_code);
+        assert!(comprehension.flags.contains(CodeFlags::IS_COROUTINE));
+        assert!(comprehension.instructions.contains(&GetANext));
+        // The comprehension gets an async iterator and is awaited:
+        let call = function_code
+            .instructions
+            .iter()
+            .position(|instruction| match instruction {
+                CallFunction { .. } => true,
+                _ => false,
+            })
+            .unwrap();
+        assert_eq!(function_code.instructions[call - 1], GetAIter);
+        assert_eq!(function_code.instructions[call + 1], GetAwaitable);
+
+        // Awaiting in a plain comprehension makes it a coroutine too:
+        let code = compile_exec("async def f():\n    return [await x for x in y]\n");
+        assert!(nested_code(nested_code(&code))
+            .flags
+            .contains(CodeFlags::IS_COROUTINE));
+        let code = compile_exec("async def f():\n    return [x for x in y]\n");
+        assert!(!nested_code(nested_code(&code))
+            .flags
+            .contains(CodeFlags::IS_COROUTINE));
+    }
+
+    #[test]
+    fn test_async_errors() {
+        let sources = [
+            "await x",
+            "def f():\n    await x",
+            "[x async for x in y]",
+            "def f():\n    return [x async for x in y]",
+            "async def f():\n    return (x async for x in y)",
+            "yield from x",
+            "async def f():\n    yield from x",
+        ];
+        for source in sources.iter() {
+            let result = super::compile(source, Mode::Exec, "source_path".to_string(), 0);
+            assert!(result.is_err(), "{:?} should not compile", source);
+        }
+    }
+
+    #[test]
+    fn test_annotated_assign_targets() {
+        // Attribute and subscript targets evaluate their parts even without a value.
+        let code = compile_exec("a.b: int\nc[d]: int\n");
+        let loaded: Vec<&str> = code
+            .instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                LoadName { idx, .. } => Some(code.names[*idx].as_str()),
+                _ => Option::None,
+            })
+            .collect();
+        assert_eq!(loaded, vec!["a", "int", "c", "d", "int"]);
+
+        for source in ["(a, b): int", "[a, b]: int", "f(): int"].iter() {
+            let result = super::compile(source, Mode::Exec, "source_path".to_string(), 0);
+            assert!(result.is_err(), "{:?} should not compile", source);
+        }
+    }
+
+    #[test]
+    fn test_starred_targets() {
+        let code = compile_exec("(a, *b), c = x\nfor d, *e, f in y: pass\n");
+        let unpacks: Vec<&Instruction> = code
+            .instructions
+            .iter()
+            .filter(|instruction| match instruction {
+                UnpackEx { .. } | UnpackSequence { .. } => true,
+                _ => false,
+            })
+            .collect();
+        assert_eq!(
+            unpacks,
+            vec![
+                &UnpackSequence { size: 2 },
+                &UnpackEx {
+                    before: 1,
+                    after: 0
+                },
+                &UnpackEx {
+                    before: 1,
+                    after: 1
+                },
+            ]
+        );
+
+        let errors = [
+            (
+                "a, *b, *c = x",
+                "multiple starred expressions in assignment",
+            ),
+            (
+                "[*a, (b, *c, *d)] = x",
+                "multiple starred expressions in assignment",
+            ),
+            (
+                "*a = x",
+                "starred assignment target must be in a list or tuple",
+            ),
+            (
+                "for *a in x: pass",
+                "starred assignment target must be in a list or tuple",
+            ),
+            ("a = *b", "can't use starred expression here"),
+        ];
+        for (source, message) in errors.iter() {
+            let error =
+                super::compile(source, Mode::Exec, "source_path".to_string(), 0).expect_err(source);
+            assert!(error.to_string().starts_with(message), "{}", error);
+        }
+        // A starred value is fine in a tuple
+        assert!(super::compile("a = *b, c", Mode::Exec, "source_path".to_string(), 0).is_ok());
+    }
+
+    #[test]
+    fn test_content_hash_is_deterministic() {
+        let source =
+            "def f(x):\n    for i in x:\n        if i:\n            break\n    return lambda: x\n";
+        let first = compile_exec(source);
+        let second = compile_exec(source);
+        assert_eq!(first.to_bytes(), second.to_bytes());
+        assert_eq!(first.content_hash(true), second.content_hash(true));
+        assert_eq!(first.content_hash(false), second.content_hash(false));
+    }
+
+    #[test]
+    fn test_content_hash_nested_code() {
+        let first = compile_exec("def f():\n    return 1\n");
+        let second = compile_exec("def f():\n    return 2\n");
+        assert_ne!(first.content_hash(false), second.content_hash(false));
+    }
+
+    #[test]
+    fn test_content_hash_source_info() {
+        let first = compile_exec("def f():\n    return 1\n");
+        let second = compile_exec("\n\ndef f():\n    return 1\n");
+        assert_ne!(first.content_hash(true), second.content_hash(true));
+        assert_eq!(first.content_hash(false), second.content_hash(false));
+    }
+
+    #[test]
+    fn test_cache_slots() {
+        let code = compile_exec("x.y = z.w\n");
+        assert_eq!(code.cache_slots.len(), code.instructions.len());
+        assert_eq!(
+            vec![
+                NO_CACHE_SLOT,
+                0,
+                NO_CACHE_SLOT,
+                1,
+                NO_CACHE_SLOT,
+                NO_CACHE_SLOT
+            ],
+            *code.cache_slots
+        );
+        assert_eq!(code.cache_slot_count(), 2);
+    }
+
+    #[test]
+    fn test_expression_flag() {
+        let is_expression = |source, mode| -> bool {
+            let code = super::compile(source, mode, "source_path".to_owned(), 0).unwrap();
+            code.flags.contains(CodeFlags::IS_EXPRESSION)
+        };
+        assert!(is_expression("1 + 2", Mode::Eval));
+        assert!(!is_expression("1 + 2", Mode::Exec));
+        assert!(!is_expression("1 + 2", Mode::Single));
+        // Only the code of the expression itself
+        let code = super::compile("lambda: 0", Mode::Eval, "source_path".to_owned(), 0).unwrap();
+        assert!(!nested_code(&code).flags.contains(CodeFlags::IS_EXPRESSION));
+    }
+
+    #[test]
+    fn test_compile_top() {
+        let compile_top = |top, mode| {
+            super::compile_top(top, mode, "source_path".to_owned(), 0, CodeFlags::empty())
+                .unwrap()
+                .0
+        };
+        let source = "x = [1, 2]\nx[0] + 1\n";
+        let expected = super::compile(source, Mode::Exec, "source_path".to_owned(), 0).unwrap();
+        let code = compile_top(
+            parser::parse(source, parser::Mode::Program).unwrap(),
+            Mode::Exec,
+        );
+        assert_eq!(code.instructions, expected.instructions);
+
+        let expected = super::compile(source, Mode::Single, "source_path".to_owned(), 0).unwrap();
+        let code = compile_top(
+            parser::parse(source, parser::Mode::Program).unwrap(),
+            Mode::Single,
+        );
+        assert_eq!(code.instructions, expected.instructions);
+
+        let expected = super::compile("1 + 2", Mode::Eval, "source_path".to_owned(), 0).unwrap();
+        let expression = parser::parse_expression("1 + 2").unwrap();
+        let code = compile_top(ast::Top::Expression(expression), Mode::Eval);
+        assert_eq!(code.instructions, expected.instructions);
+        assert!(code.flags.contains(CodeFlags::IS_EXPRESSION));
+    }
+
+    #[test]
+    fn test_compile_recovering() {
+        let compile = |source| {
+            super::compile_recovering(source, Mode::Exec, "source_path".to_owned(), 0).map_err(
+                |errors| {
+                    errors
+                        .iter()
+                        .map(|err| err.location.row())
+                        .collect::<Vec<_>>()
+                },
+            )
+        };
+        assert_eq!(compile("x = = 1\ny = 2\nz = *\n").unwrap_err(), vec![1, 3]);
+        assert_eq!(compile("x = 1\ndel 1\n").unwrap_err(), vec![2]);
+        let code = compile("x = 1\n").unwrap();
+        let expected = super::compile("x = 1\n", Mode::Exec, "source_path".to_owned(), 0).unwrap();
+        assert_eq!(code.instructions, expected.instructions);
+    }
+
+    #[test]
+    fn test_compile_warnings() {
+        let warnings = |source| {
+            let flags = CodeFlags::empty();
+            let (_, warnings) = super::compile_with_warnings(
+                source,
+                Mode::Exec,
+                "source_path".to_owned(),
+                0,
+                flags,
+            )
+            .unwrap();
+            warnings
+                .into_iter()
+                .map(|warning| {
+                    let message = warning.warning.to_string();
+                    (warning.category(), message, warning.location.row())
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            warnings("x = '\\d'\nif x is 1 or 'a' is not x:\n    y = x is -1 is ()\n"),
+            vec![
+                (
+                    "DeprecationWarning",
+                    "invalid escape sequence '\\d'".to_owned(),
+                    1
+                ),
+                (
+                    "SyntaxWarning",
+                    "\"is\" with a literal. Did you mean \"==\"?".to_owned(),
+                    2
+                ),
+                (
+                    "SyntaxWarning",
+                    "\"is not\" with a literal. Did you mean \"!=\"?".to_owned(),
+                    2
+                ),
+                (
+                    "SyntaxWarning",
+                    "\"is\" with a literal. Did you mean \"==\"?".to_owned(),
+                    3
+                ),
+            ]
+        );
+        assert!(warnings("x is None or x is ... or x is (y, 1) or x == 1").is_empty());
+    }
+
+    #[test]
+    fn test_single_mode_prints_top_level_expressions() {
+        let code = super::compile(
+            "if x:\n    y\nclass C:\n    z\ndef f():\n    w\nx\n",
+            Mode::Single,
+            "source_path".to_owned(),
+            0,
+        )
+        .unwrap();
+        let print_count = |code: &CodeObject| {
+            code.instructions
+                .iter()
+                .filter(|instruction| **instruction == PrintExpr)
+                .count()
+        };
+        // The expression in the `if` and the last one, but none in the class or function body
+        assert_eq!(print_count(&code), 2);
+        let nested: Vec<&CodeObject> = code
+            .constants
+            .iter()
+            .filter_map(|constant| match constant {
+                Code { code } => Some(code.as_ref()),
+                _ => Option::None,
+            })
+            .collect();
+        assert_eq!(nested.len(), 2);
+        assert!(nested.iter().all(|code| print_count(code) == 0));
+
+        let code = super::compile("x\n", Mode::Exec, "source_path".to_owned(), 0).unwrap();
+        assert_eq!(print_count(&code), 0);
+    }
+
+    #[test]
+    fn test_pyc_accepted_by_cpython() {
+        // Only checked when there is a python3 on the PATH whose pyc format we can write.
+        let magic = match Command::new("python3")
+            .args(&[
+                "-c",
+                "import importlib.util; print(int.from_bytes(importlib.util.MAGIC_NUMBER, 'little'))",
+            ])
+            .output()
+        {
+            Ok(output) if output.status.success() => std::string::String::from_utf8(output.stdout)
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap(),
+            _ => return,
+        };
+        let sources = [
+            "x = 1\n",
+            "import os\nprint(os.name, 2 ** 100, -1.5, 1j, b'data', (1, 'a', None))\n",
+            "def f(a, *args, b=2, **kwargs):\n    return a\nclass C:\n    def g(self):\n        yield self\n",
+            "async def h():\n    await h()\nlambda: ...\n",
+        ];
+        for source in sources.iter() {
+            let code = compile_exec(source);
+            let data = match pyc::to_pyc(&code, magic, 0, source.len() as u32) {
+                Ok(data) => data,
+                Err(PycError::UnsupportedMagic(_)) => return,
+                Err(err) => panic!("{}", err),
+            };
+            assert_eq!(pyc::from_pyc(&data).unwrap(), code);
+
+            let mut child = Command::new("python3")
+                .args(&[
+                    "-c",
+                    "import marshal, sys\n\
+                     code = marshal.loads(sys.stdin.buffer.read()[16:])\n\
+                     exec(code, {})\n\
+                     print(code.co_filename, code.co_name)",
+                ])
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            child.stdin.take().unwrap().write_all(&data).unwrap();
+            let output = child.wait_with_output().unwrap();
+            assert!(output.status.success(), "CPython rejected {:?}", source);
+            assert_eq!(output.stdout, b"source_path <module>\n");
+        }
+    }
+
+    /// Emits like `CodeObjectStream`, and checks that the line table of the code object has the
+    /// location of every instruction as it was emitted.
+    struct LocationCheckingStream {
+        inner: CodeObjectStream,
+        locations: Vec<Location>,
+    }
+
+    impl From<CodeObject> for LocationCheckingStream {
+        fn from(code: CodeObject) -> Self {
+            let locations = code.line_table.locations().collect();
+            LocationCheckingStream {
+                inner: code.into(),
+                locations,
+            }
+        }
+    }
+
+    impl From<LocationCheckingStream> for CodeObject {
+        fn from(stream: LocationCheckingStream) -> Self {
+            let code: CodeObject = stream.inner.into();
+            let locations: Vec<_> = code.line_table.locations().collect();
+            assert_eq!(locations, stream.locations, "the locations of {:?}", code);
+            code
+        }
+    }
+
+    impl OutputStream for LocationCheckingStream {
+        fn emit(&mut self, instruction: Instruction, location: Location) {
+            // Columns that don't fit in the table are left out.
+            let column = if location.column() > 255 {
+                0
+            } else {
+                location.column()
+            };
+            self.locations.push(Location::new(location.row(), column));
+            self.inner.emit(instruction, location);
+        }
+        fn set_label(&mut self, label: Label) {
+            self.inner.set_label(label);
+        }
+        fn mark_generator(&mut self) {
+            self.inner.mark_generator();
+        }
+        fn add_constant(&mut self, constant: Constant) -> ConstIdx {
+            self.inner.add_constant(constant)
+        }
+        fn constant(&self, idx: ConstIdx) -> &Constant {
+            self.inner.constant(idx)
+        }
+        fn add_name(&mut self, name: &str) -> NameIdx {
+            self.inner.add_name(name)
+        }
+    }
+
+    #[test]
+    fn test_line_table_of_snippets() {
+        // Tracebacks and line events get the same line and column for every instruction from
+        // the line table as from the location it was compiled with.
+        let snippets = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/snippets");
+        let mut compiled = 0;
+        for entry in fs::read_dir(snippets).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().map_or(true, |extension| extension != "py") {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            let ast = match parser::parse_program(&source) {
+                Ok(ast) => ast,
+                Err(_) => continue,
+            };
+            let symbol_scope = make_symbol_table(&ast).unwrap();
+            let mut compiler = Compiler::<LocationCheckingStream>::new(0);
+            compiler.source_path = Some(path.to_string_lossy().into_owned());
+            compiler.push_new_code_object("<module>".to_string());
+            if compiler.compile_program(&ast, symbol_scope).is_ok() {
+                compiler.pop_code_object();
+                compiled += 1;
+            }
+        }
+        assert!(compiled > 100);
     }
 }
@@ -0,0 +1,285 @@
+//! Decoding the bytes of a Python source file into text, as described in PEP 263: a file is
+//! UTF-8 unless one of its first two lines declares another encoding with a comment like
+//! `# -*- coding: latin-1 -*-`, and a UTF-8 byte order mark at its start is dropped.
+
+use crate::error::{CompileError, CompileErrorType};
+use rustpython_parser::location::Location;
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// The source encodings that can be declared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SourceEncoding {
+    Utf8,
+    Latin1,
+    Ascii,
+}
+
+impl SourceEncoding {
+    /// Look up an encoding by any of the names Python accepts for it.
+    fn from_name(name: &str) -> Option<SourceEncoding> {
+        let name = name.to_ascii_lowercase().replace('_', "-");
+        // Like CPython's `get_normal_name`, anything that merely starts with a UTF-8 or latin-1
+        // name, like `utf-8-unix`, counts as that name too.
+        let is = |names: &[&str]| {
+            names.iter().any(|known| {
+                name == *known || (name.starts_with(known) && name[known.len()..].starts_with('-'))
+            })
+        };
+        if is(&["utf-8", "utf8", "u8", "utf", "cp65001"]) {
+            Some(SourceEncoding::Utf8)
+        } else if is(&[
+            "latin-1",
+            "latin1",
+            "latin",
+            "l1",
+            "iso-8859-1",
+            "iso8859-1",
+            "iso-latin-1",
+            "8859",
+            "cp819",
+        ]) {
+            Some(SourceEncoding::Latin1)
+        } else if ["ascii", "us-ascii", "us", "646"].contains(&name.as_str()) {
+            Some(SourceEncoding::Ascii)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            SourceEncoding::Utf8 => "utf-8",
+            SourceEncoding::Latin1 => "latin-1",
+            SourceEncoding::Ascii => "ascii",
+        }
+    }
+}
+
+/// Decode the contents of a source file: drop a UTF-8 byte order mark, and decode the rest with
+/// the encoding declared in its first two lines, or UTF-8. A declaration of an unknown encoding,
+/// one that contradicts the byte order mark, and bytes that aren't valid in the encoding are all
+/// syntax errors, located at the line they're on.
+pub fn decode_source(source: &[u8]) -> Result<String, CompileError> {
+    let (has_bom, source) = if source.starts_with(UTF8_BOM) {
+        (true, &source[UTF8_BOM.len()..])
+    } else {
+        (false, source)
+    };
+
+    let declared = match find_declaration(source) {
+        Some((name, row)) => {
+            let encoding = SourceEncoding::from_name(name)
+                .ok_or_else(|| error(format!("unknown encoding: {}", name), row))?;
+            if has_bom && encoding != SourceEncoding::Utf8 {
+                return Err(error(format!("encoding problem: {} with BOM", name), row));
+            }
+            Some(encoding)
+        }
+        None => None,
+    };
+
+    match declared.unwrap_or(SourceEncoding::Utf8) {
+        SourceEncoding::Utf8 => match std::str::from_utf8(source) {
+            Ok(text) => Ok(text.to_owned()),
+            Err(err) => {
+                let position = err.valid_up_to();
+                let message = if declared.is_some() {
+                    invalid_byte_message(SourceEncoding::Utf8, source[position])
+                } else {
+                    format!(
+                        "Non-UTF-8 code starting with '\\x{:02x}', but no encoding declared; \
+                         see https://www.python.org/dev/peps/pep-0263/ for details",
+                        source[position]
+                    )
+                };
+                Err(error(message, line_of(source, position)))
+            }
+        },
+        SourceEncoding::Latin1 => Ok(source.iter().map(|&byte| char::from(byte)).collect()),
+        SourceEncoding::Ascii => match source.iter().position(|byte| !byte.is_ascii()) {
+            Some(position) => Err(error(
+                invalid_byte_message(SourceEncoding::Ascii, source[position]),
+                line_of(source, position),
+            )),
+            None => Ok(String::from_utf8(source.to_vec()).unwrap()),
+        },
+    }
+}
+
+/// Find the name of the encoding declared in the first two lines of `source`, and the line it's
+/// declared on. Like in CPython, the second line only counts if the first one is empty or a
+/// comment, so that the declaration can follow a `#!` line.
+fn find_declaration(source: &[u8]) -> Option<(&str, usize)> {
+    let mut lines = source.split(|&byte| byte == b'\n');
+    for row in 1..=2 {
+        let line = lines.next()?;
+        if let Some(name) = declared_encoding(line) {
+            return Some((name, row));
+        }
+        let code = trim_start(line);
+        if !(code.is_empty() || code[0] == b'#' || code[0] == b'\r') {
+            return None;
+        }
+    }
+    None
+}
+
+/// The encoding name in a line matching `^[ \t\f]*#.*?coding[:=][ \t]*([-\w.]+)`.
+fn declared_encoding(line: &[u8]) -> Option<&str> {
+    let comment = trim_start(line);
+    if comment.first() != Some(&b'#') {
+        return None;
+    }
+    let mut rest = comment;
+    while let Some(index) = find(rest, b"coding") {
+        rest = &rest[index + b"coding".len()..];
+        if let Some(b':') | Some(b'=') = rest.first() {
+            let value = trim_start_matches(&rest[1..], |byte| byte == b' ' || byte == b'\t');
+            let length = value
+                .iter()
+                .take_while(|&&byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte))
+                .count();
+            if length > 0 {
+                return std::str::from_utf8(&value[..length]).ok();
+            }
+        }
+    }
+    None
+}
+
+fn trim_start(line: &[u8]) -> &[u8] {
+    trim_start_matches(line, |byte| {
+        byte == b' ' || byte == b'\t' || byte == b'\x0c'
+    })
+}
+
+fn trim_start_matches(bytes: &[u8], predicate: impl Fn(u8) -> bool) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|&byte| !predicate(byte))
+        .unwrap_or_else(|| bytes.len());
+    &bytes[start..]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn line_of(source: &[u8], position: usize) -> usize {
+    source[..position]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count()
+        + 1
+}
+
+fn invalid_byte_message(encoding: SourceEncoding, byte: u8) -> String {
+    format!(
+        "(unicode error) '{}' codec can't decode byte 0x{:02x}",
+        encoding.name(),
+        byte
+    )
+}
+
+fn error(message: String, row: usize) -> CompileError {
+    CompileError {
+        error: CompileErrorType::SyntaxError(message),
+        location: Location::new(row, 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_error(source: &[u8]) -> (String, usize) {
+        let err = decode_source(source).unwrap_err();
+        match err.error {
+            CompileErrorType::SyntaxError(message) => (message, err.location.row()),
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_utf8_by_default() {
+        assert_eq!(decode_source("x = 'é'\n".as_bytes()).unwrap(), "x = 'é'\n");
+        assert_eq!(
+            decode_error(b"x = 1\ny = '\xe9'\n"),
+            (
+                "Non-UTF-8 code starting with '\\xe9', but no encoding declared; see \
+                 https://www.python.org/dev/peps/pep-0263/ for details"
+                    .to_string(),
+                2
+            )
+        );
+    }
+
+    #[test]
+    fn test_bom_is_dropped() {
+        assert_eq!(decode_source(b"\xef\xbb\xbfx = 1\n").unwrap(), "x = 1\n");
+        assert_eq!(
+            decode_source(b"\xef\xbb\xbf# coding: utf-8\nx = 1\n").unwrap(),
+            "# coding: utf-8\nx = 1\n"
+        );
+        assert_eq!(
+            decode_error(b"\xef\xbb\xbf# coding: latin-1\nx = 1\n"),
+            ("encoding problem: latin-1 with BOM".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn test_declared_latin1() {
+        for declaration in &[
+            "# -*- coding: latin-1 -*-",
+            "# vim: set fileencoding=iso-8859-1 :",
+            "#coding:latin1",
+            "  # coding=ISO_8859_1",
+        ] {
+            let source = [declaration.as_bytes(), b"\nx = '\xe9\xe8'\n"].concat();
+            let expected = format!("{}\nx = '\u{e9}\u{e8}'\n", declaration);
+            assert_eq!(decode_source(&source).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_declaration_on_second_line() {
+        assert_eq!(
+            decode_source(b"#!/usr/bin/env python\n# coding: latin-1\nx = '\xe9'\n").unwrap(),
+            "#!/usr/bin/env python\n# coding: latin-1\nx = '\u{e9}'\n"
+        );
+        assert_eq!(
+            decode_source(b"\n# coding: latin-1\nx = '\xe9'\n").unwrap(),
+            "\n# coding: latin-1\nx = '\u{e9}'\n"
+        );
+        // The second line doesn't count after a line of code, and the third never does.
+        assert_eq!(decode_error(b"x = 1\n# coding: latin-1\n'\xe9'\n").1, 3);
+        assert_eq!(decode_error(b"#\n#\n# coding: latin-1\n'\xe9'\n").1, 4);
+        // A declaration must be in a comment.
+        assert_eq!(decode_error(b"coding = 'latin-1'\n'\xe9'\n").1, 2);
+    }
+
+    #[test]
+    fn test_invalid_declarations() {
+        assert_eq!(
+            decode_error(b"# coding: klingon\n"),
+            ("unknown encoding: klingon".to_string(), 1)
+        );
+        assert_eq!(
+            decode_error(b"# coding: ascii\nx = '\xe9'\n"),
+            (
+                "(unicode error) 'ascii' codec can't decode byte 0xe9".to_string(),
+                2
+            )
+        );
+        assert_eq!(
+            decode_error(b"# coding: utf-8\n\nx = '\xe9'\n"),
+            (
+                "(unicode error) 'utf-8' codec can't decode byte 0xe9".to_string(),
+                3
+            )
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use rustpython_parser::error::{LexicalErrorType, ParseError, ParseErrorType};
+use rustpython_parser::error::{
+    LexicalErrorType, LexicalWarning, LexicalWarningType, ParseError, ParseErrorType,
+};
 use rustpython_parser::location::Location;
 use rustpython_parser::token::Tok;
 
@@ -39,6 +41,10 @@ pub enum CompileErrorType {
     InvalidContinue,
     InvalidReturn,
     InvalidYield,
+    InvalidAwait,
+    AsyncYieldFrom,
+    InvalidAsyncComprehension,
+    AsyncGeneratorExpression,
 }
 
 impl CompileError {
@@ -68,23 +74,40 @@ impl CompileError {
     }
 }
 
-impl fmt::Display for CompileError {
+impl fmt::Display for CompileErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self.error {
+        match self {
             CompileErrorType::Assign(target) => write!(f, "can't assign to {}", target),
             CompileErrorType::Delete(target) => write!(f, "can't delete {}", target),
             CompileErrorType::ExpectExpr => write!(f, "Expecting expression, got statement"),
             CompileErrorType::Parse(err) => write!(f, "{}", err),
             CompileErrorType::SyntaxError(err) => write!(f, "{}", err),
-            CompileErrorType::StarArgs => write!(f, "Two starred expressions in assignment"),
+            CompileErrorType::StarArgs => {
+                write!(f, "multiple starred expressions in assignment")
+            }
             CompileErrorType::InvalidBreak => write!(f, "'break' outside loop"),
             CompileErrorType::InvalidContinue => write!(f, "'continue' outside loop"),
             CompileErrorType::InvalidReturn => write!(f, "'return' outside function"),
             CompileErrorType::InvalidYield => write!(f, "'yield' outside function"),
-        }?;
+            CompileErrorType::InvalidAwait => write!(f, "'await' outside async function"),
+            CompileErrorType::AsyncYieldFrom => write!(f, "'yield from' inside async function"),
+            CompileErrorType::InvalidAsyncComprehension => {
+                write!(
+                    f,
+                    "asynchronous comprehension outside of an asynchronous function"
+                )
+            }
+            CompileErrorType::AsyncGeneratorExpression => {
+                write!(f, "asynchronous generator expressions are not supported")
+            }
+        }
+    }
+}
 
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Print line number:
-        write!(f, " at {}", self.location)
+        write!(f, "{} at {}", self.error, self.location)
     }
 }
 
@@ -93,3 +116,53 @@ impl Error for CompileError {
         None
     }
 }
+
+/// A warning about code that compiles, which CPython reports through the `warnings` module.
+#[derive(Debug)]
+pub struct CompileWarning {
+    pub warning: CompileWarningType,
+    pub location: Location,
+}
+
+impl From<LexicalWarning> for CompileWarning {
+    fn from(warning: LexicalWarning) -> Self {
+        CompileWarning {
+            warning: CompileWarningType::Lexical(warning.warning),
+            location: warning.location,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CompileWarningType {
+    /// Lexer warning
+    Lexical(LexicalWarningType),
+    /// `is` or `is not` comparing with a literal, where `==` or `!=` is meant.
+    IsLiteral { negated: bool },
+}
+
+impl CompileWarning {
+    /// The name of the builtin warning category, like `"SyntaxWarning"`.
+    pub fn category(&self) -> &'static str {
+        match self.warning {
+            CompileWarningType::Lexical(LexicalWarningType::InvalidEscapeSequence(_)) => {
+                "DeprecationWarning"
+            }
+            CompileWarningType::IsLiteral { .. } => "SyntaxWarning",
+        }
+    }
+}
+
+impl fmt::Display for CompileWarningType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileWarningType::Lexical(warning) => write!(f, "{}", warning),
+            CompileWarningType::IsLiteral { negated: false } => {
+                write!(f, "\"is\" with a literal. Did you mean \"==\"?")
+            }
+            CompileWarningType::IsLiteral { negated: true } => {
+                write!(f, "\"is not\" with a literal. Did you mean \"!=\"?")
+            }
+        }
+    }
+}
@@ -0,0 +1,1040 @@
+//! Support for freezing trees of Python source code: finding the modules and data files of a
+//! package directory, and the manifest that `rustpython-compile` writes next to its precompiled
+//! bytecode files and `py_compile_bytecode!(precompiled_dir = "...")` reads back.
+//!
+//! Build scripts can also freeze a directory themselves with `freeze_dir`, and write the modules
+//! to `$OUT_DIR` with `write_out_dir` for the crate to `include_bytes!`:
+//!
+//! ```ignore
+//! // build.rs
+//! let modules = rustpython_compiler::freeze_dir("Lib".as_ref(), &Default::default()).unwrap();
+//! rustpython_compiler::frozen::write_out_dir("lib.rpfrozen", &modules, false).unwrap();
+//! println!("cargo:rerun-if-changed=Lib");
+//!
+//! // src/main.rs
+//! static LIB: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/lib.rpfrozen"));
+//! let modules = rustpython_vm::frozen_file::read(LIB).unwrap();
+//! ```
+//!
+//! Unlike `py_compile_bytecode!`, this doesn't hold up the crates that depend on the frozen
+//! code while it's compiled, and it doesn't run again for every crate that freezes the same code.
+
+use crate::{compile, encoding};
+use rustpython_bytecode::bytecode::{FrozenModule, FrozenSource, FORMAT_VERSION};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file in a precompiled bytecode directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.txt";
+
+/// The extension of the bytecode files in a precompiled bytecode directory.
+pub const BYTECODE_EXTENSION: &str = "rpbc";
+
+const MANIFEST_HEADER: &str = "rustpython-compile manifest";
+
+/// A Python source file found in a package directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceModule {
+    /// The dotted module name, relative to the directory that was searched.
+    pub name: String,
+    pub path: PathBuf,
+    /// `path` relative to the directory that was searched, with `/` separators, like
+    /// `a/b/__init__.py`. For a module found through a `.pylink` file, this is where the
+    /// module would be if the file were its target.
+    pub relative_path: String,
+    /// Whether this is the `__init__.py` of a package.
+    pub package: bool,
+}
+
+/// Find all the `.py` files below `dir` and name them after their path relative to it, so that
+/// `a/b/c.py` is `a.b.c` and `a/b/__init__.py` is the package `a.b`. The result is sorted by
+/// module name, so it doesn't depend on the order the file system lists directories in. An
+/// `__init__.py` directly in `dir` doesn't belong to any package and is skipped.
+///
+/// A `.pylink` file stands for the file or directory whose path is its first line, relative to
+/// the directory the link is in, absolute, or relative to `$CARGO_MANIFEST_DIR` if it starts
+/// with that. `vendor/six.pylink` pointing to a file is the module `vendor.six`, and
+/// `vendor/attr.pylink` pointing to a directory is the package `vendor.attr` with all the
+/// modules below it, so that Python code outside of the crate can be frozen without copying it.
+/// Resources aren't looked for in linked directories.
+pub fn find_modules(dir: &Path) -> io::Result<Vec<SourceModule>> {
+    find_modules_filtered(dir, &ModuleFilter::default())
+}
+
+/// Glob patterns that pick the modules to take from a directory or an archive, matched against
+/// the paths relative to it; see `find_resources` for the syntax.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModuleFilter {
+    /// Only the `.py` files matching one of these are taken, or all of them if there are none.
+    pub include: Vec<String>,
+    /// The files and directories matching one of these are left out. Nothing below an excluded
+    /// directory is looked at, so `**/test` skips every `test` package.
+    pub exclude: Vec<String>,
+}
+
+impl ModuleFilter {
+    fn includes(&self, relative_path: &str) -> bool {
+        self.include.is_empty() || glob_match_any(&self.include, relative_path)
+    }
+
+    fn excludes(&self, relative_path: &str) -> bool {
+        glob_match_any(&self.exclude, relative_path)
+    }
+}
+
+/// Like `find_modules`, but only take the modules `filter` picks.
+pub fn find_modules_filtered(dir: &Path, filter: &ModuleFilter) -> io::Result<Vec<SourceModule>> {
+    let mut modules = Vec::new();
+    find_modules_in(dir, "", "", filter, &mut modules)?;
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(modules)
+}
+
+fn find_modules_in(
+    dir: &Path,
+    parent: &str,
+    relative_dir: &str,
+    filter: &ModuleFilter,
+    modules: &mut Vec<SourceModule>,
+) -> io::Result<()> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        io::Error::new(err.kind(), format!("Error listing dir {:?}: {}", dir, err))
+    })?;
+    for entry in entries {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap().to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid UTF-8 in file name {:?}", path),
+            )
+        })?;
+        let relative_path = join_relative_path(relative_dir, file_name);
+        if filter.excludes(&relative_path) {
+            continue;
+        }
+        if path.is_dir() {
+            let name = join_module_name(parent, file_name);
+            find_modules_in(&path, &name, &relative_path, filter, modules)?;
+        } else if file_name.ends_with(".py") && filter.includes(&relative_path) {
+            let stem = &file_name[..file_name.len() - ".py".len()];
+            modules.extend(source_module(path.clone(), parent, stem, relative_path));
+        } else if file_name.ends_with(".pylink") {
+            let stem = &file_name[..file_name.rfind('.').unwrap()];
+            let target = read_pylink(&path)?;
+            if target.is_dir() {
+                let relative_path = join_relative_path(relative_dir, stem);
+                if !filter.excludes(&relative_path) {
+                    let name = join_module_name(parent, stem);
+                    find_modules_in(&target, &name, &relative_path, filter, modules)?;
+                }
+            } else {
+                let relative_path = join_relative_path(relative_dir, &format!("{}.py", stem));
+                if !filter.excludes(&relative_path) && filter.includes(&relative_path) {
+                    modules.extend(source_module(target, parent, stem, relative_path));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The module of the file `stem.py` in the package `parent`, unless it's an `__init__.py`
+/// outside of any package.
+fn source_module(
+    path: PathBuf,
+    parent: &str,
+    stem: &str,
+    relative_path: String,
+) -> Option<SourceModule> {
+    let package = stem == "__init__";
+    if package && parent.is_empty() {
+        return None;
+    }
+    let name = if package {
+        parent.to_string()
+    } else {
+        join_module_name(parent, stem)
+    };
+    Some(SourceModule {
+        name,
+        path,
+        relative_path,
+        package,
+    })
+}
+
+/// The path a `.pylink` file points to; see `find_modules`.
+fn read_pylink(path: &Path) -> io::Result<PathBuf> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        io::Error::new(
+            err.kind(),
+            format!("Error reading file {:?}: {}", path, err),
+        )
+    })?;
+    let line = text.lines().next().unwrap_or("").trim();
+    if line.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{:?} doesn't contain a path", path),
+        ));
+    }
+    let target = match Path::new(line).strip_prefix("$CARGO_MANIFEST_DIR") {
+        Ok(rest) => {
+            let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{:?} needs $CARGO_MANIFEST_DIR, which isn't set", path),
+                )
+            })?;
+            Path::new(&manifest_dir).join(rest)
+        }
+        // joining an absolute path replaces the directory of the link
+        Err(_) => path.parent().unwrap().join(line),
+    };
+    if !target.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{:?} points to {:?}, which doesn't exist", path, target),
+        ));
+    }
+    Ok(target)
+}
+
+fn join_relative_path(relative_dir: &str, file_name: &str) -> String {
+    if relative_dir.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{}/{}", relative_dir, file_name)
+    }
+}
+
+/// Like `find_modules_filtered`, but for the files of an archive: `entries` are the paths of
+/// its entries, with `/` separators, and only those below `prefix` are looked at, named after
+/// their path relative to it. The `path` of the modules is the one of their entry. It's an error
+/// for two entries to be the same module, like `a.py` and `a/__init__.py`.
+pub fn find_archive_modules(
+    entries: &[String],
+    prefix: &str,
+    filter: &ModuleFilter,
+) -> Result<Vec<SourceModule>, String> {
+    let prefix = prefix.trim_end_matches('/');
+    let mut modules: Vec<SourceModule> = Vec::new();
+    for entry in entries {
+        let relative_path = if prefix.is_empty() {
+            entry.as_str()
+        } else if entry.starts_with(prefix) && entry[prefix.len()..].starts_with('/') {
+            &entry[prefix.len() + 1..]
+        } else {
+            continue;
+        };
+        if !relative_path.ends_with(".py") || !filter.includes(relative_path) {
+            continue;
+        }
+        // an excluded directory leaves out everything below it, as in find_modules_filtered
+        let excluded = relative_path
+            .match_indices('/')
+            .map(|(index, _)| &relative_path[..index])
+            .chain(Some(relative_path))
+            .any(|path| filter.excludes(path));
+        if excluded {
+            continue;
+        }
+        let (parent, file_name) = match relative_path.rfind('/') {
+            Some(index) => (&relative_path[..index], &relative_path[index + 1..]),
+            None => ("", relative_path),
+        };
+        let parent = parent.replace('/', ".");
+        let stem = &file_name[..file_name.len() - ".py".len()];
+        let package = stem == "__init__";
+        if package && parent.is_empty() {
+            continue;
+        }
+        let name = if package {
+            parent
+        } else {
+            join_module_name(&parent, stem)
+        };
+        if let Some(other) = modules.iter().find(|module| module.name == name) {
+            return Err(format!(
+                "Duplicate module {}: {:?} and {:?}",
+                name,
+                other.path.to_string_lossy(),
+                entry
+            ));
+        }
+        modules.push(SourceModule {
+            name,
+            path: PathBuf::from(entry),
+            relative_path: relative_path.to_string(),
+            package,
+        });
+    }
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(modules)
+}
+
+/// A data file found in a package directory by `find_resources`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceResource {
+    /// The dotted name of the package the file is in.
+    pub package: String,
+    /// The file name, which `importlib.resources` knows the resource by.
+    pub name: String,
+    pub path: PathBuf,
+    /// `path` relative to the directory that was searched, with `/` separators.
+    pub relative_path: String,
+}
+
+/// Find the files below `dir` that aren't `.py` files and whose path relative to `dir` matches
+/// the glob `pattern`, sorted by that path. In the pattern `*` matches any part of a file or
+/// directory name, `?` any one character, `**/` any number of directories and a `/**` at the
+/// end everything below a directory, so `pkg/*.txt` matches `pkg/a.txt`, `**/*.json` matches
+/// every `.json` file and `pkg/**` every file in `pkg` and its subdirectories.
+///
+/// A resource belongs to the package of the directory it's in, so it's an error for a matching
+/// file not to be next to an `__init__.py`. `__pycache__` directories are skipped.
+pub fn find_resources(dir: &Path, pattern: &str) -> io::Result<Vec<SourceResource>> {
+    let mut files = Vec::new();
+    find_files_in(dir, "", &mut files)?;
+    files.sort();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let mut resources = Vec::new();
+    for (relative_path, path) in files {
+        if relative_path.ends_with(".py") {
+            continue;
+        }
+        let chars: Vec<char> = relative_path.chars().collect();
+        if !glob_match(&pattern, &chars) {
+            continue;
+        }
+        let (package_dir, name) = match relative_path.rfind('/') {
+            Some(index) => (&relative_path[..index], &relative_path[index + 1..]),
+            None => ("", relative_path.as_str()),
+        };
+        if package_dir.is_empty() || !path.with_file_name("__init__.py").is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Resource {:?} isn't in a package directory", path),
+            ));
+        }
+        resources.push(SourceResource {
+            package: package_dir.replace('/', "."),
+            name: name.to_string(),
+            path,
+            relative_path,
+        });
+    }
+    Ok(resources)
+}
+
+fn find_files_in(
+    dir: &Path,
+    relative_dir: &str,
+    files: &mut Vec<(String, PathBuf)>,
+) -> io::Result<()> {
+    let entries = fs::read_dir(dir).map_err(|err| {
+        io::Error::new(err.kind(), format!("Error listing dir {:?}: {}", dir, err))
+    })?;
+    for entry in entries {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap().to_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid UTF-8 in file name {:?}", path),
+            )
+        })?;
+        let relative_path = if relative_dir.is_empty() {
+            file_name.to_string()
+        } else {
+            format!("{}/{}", relative_dir, file_name)
+        };
+        if path.is_dir() {
+            if file_name != "__pycache__" {
+                find_files_in(&path, &relative_path, files)?;
+            }
+        } else {
+            files.push((relative_path, path));
+        }
+    }
+    Ok(())
+}
+
+fn glob_match_any(patterns: &[String], path: &str) -> bool {
+    let path: Vec<char> = path.chars().collect();
+    patterns.iter().any(|pattern| {
+        let pattern: Vec<char> = pattern.chars().collect();
+        glob_match(&pattern, &path)
+    })
+}
+
+/// Whether one of the glob `patterns` matches `relative_path` or one of the directories it's in,
+/// the way a directory that `ModuleFilter::exclude` matches leaves out everything below it.
+pub fn glob_matches_path(patterns: &[String], relative_path: &str) -> bool {
+    relative_path
+        .match_indices('/')
+        .map(|(index, _)| &relative_path[..index])
+        .chain(std::iter::once(relative_path))
+        .any(|path| glob_match_any(patterns, path))
+}
+
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    if pattern == ['/', '*', '*'] {
+        return path.len() > 1 && path[0] == '/';
+    }
+    if pattern.starts_with(&['*', '*', '/']) {
+        return glob_match(&pattern[3..], path)
+            || match path.iter().position(|&c| c == '/') {
+                Some(index) => glob_match(pattern, &path[index + 1..]),
+                None => false,
+            };
+    }
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') => {
+            let name_len = path
+                .iter()
+                .position(|&c| c == '/')
+                .unwrap_or_else(|| path.len());
+            (0..=name_len).any(|skip| glob_match(&pattern[1..], &path[skip..]))
+        }
+        Some('?') => match path.first() {
+            Some(&c) if c != '/' => glob_match(&pattern[1..], &path[1..]),
+            _ => false,
+        },
+        Some(c) => path.first() == Some(c) && glob_match(&pattern[1..], &path[1..]),
+    }
+}
+
+fn join_module_name(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+/// How `freeze_dir` compiles a directory, like the arguments of `py_compile_bytecode!` with
+/// `dir`.
+#[derive(Clone)]
+pub struct FreezeOptions {
+    pub mode: compile::Mode,
+    /// Like `-O` given that many times to CPython: 1 leaves out asserts and 2 also docstrings.
+    pub optimize: u8,
+    /// The modules to take from the directory.
+    pub filter: ModuleFilter,
+    /// Globs of the data files to freeze as resources of their packages; see `find_resources`.
+    pub resources: Vec<String>,
+    /// Whether to keep the sources of the modules, for tracebacks and `linecache`.
+    pub embed_source: bool,
+}
+
+impl Default for FreezeOptions {
+    fn default() -> Self {
+        FreezeOptions {
+            mode: compile::Mode::Exec,
+            optimize: 0,
+            filter: ModuleFilter::default(),
+            resources: Vec::new(),
+            embed_source: false,
+        }
+    }
+}
+
+/// Compile the modules below `dir` the way `py_compile_bytecode!(dir = "...")` does, sorted by
+/// their names, along with the namespace packages they're in. A resource of a package that isn't
+/// frozen is left out.
+pub fn freeze_dir(dir: &Path, opts: &FreezeOptions) -> Result<Vec<(String, FrozenModule)>, String> {
+    let mut resources: HashMap<String, HashMap<String, Cow<'static, [u8]>>> = HashMap::new();
+    for pattern in &opts.resources {
+        for resource in find_resources(dir, pattern).map_err(|err| err.to_string())? {
+            let data = fs::read(&resource.path)
+                .map_err(|err| format!("Error reading file {:?}: {}", resource.path, err))?;
+            resources
+                .entry(resource.package)
+                .or_default()
+                .insert(resource.name, Cow::Owned(data));
+        }
+    }
+
+    let modules = find_modules_filtered(dir, &opts.filter).map_err(|err| err.to_string())?;
+    let mut frozen = Vec::with_capacity(modules.len());
+    for module in modules {
+        let source = fs::read(&module.path)
+            .map_err(|err| format!("Error reading file {:?}: {}", module.path, err))?;
+        let source = encoding::decode_source(&source)
+            .map_err(|err| format!("Error decoding file {:?}: {}", module.path, err))?;
+        let code = compile::compile(&source, opts.mode, module.name.clone(), opts.optimize)
+            .map_err(|err| format!("Compile error in {:?}: {}", module.path, err))?;
+        let resources = resources.remove(&module.name).unwrap_or_default();
+        frozen.push((
+            module.name,
+            FrozenModule {
+                code: code.into(),
+                package: module.package,
+                namespace: false,
+                source: if opts.embed_source {
+                    Some(FrozenSource::Text(source.into()))
+                } else {
+                    None
+                },
+                source_path: Some(module.relative_path),
+                resources,
+            },
+        ));
+    }
+    let namespaces = namespace_packages(frozen.iter().map(|(name, _)| name.as_str()));
+    frozen.extend(
+        namespaces
+            .into_iter()
+            .map(|name| (name.clone(), namespace_package(&name))),
+    );
+    frozen.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(frozen)
+}
+
+/// The namespace packages (PEP 420) that the modules named `names` are in: the packages above
+/// them that aren't among them, like `a` and `a.b` for `a.b.c` frozen from a directory without
+/// `a/__init__.py` and `a/b/__init__.py`. The result is sorted.
+pub fn namespace_packages<'a, I: IntoIterator<Item = &'a str>>(names: I) -> Vec<String> {
+    let names: Vec<&str> = names.into_iter().collect();
+    let frozen: HashSet<&str> = names.iter().cloned().collect();
+    let mut packages = BTreeSet::new();
+    for name in names {
+        for (index, _) in name.match_indices('.') {
+            let parent = &name[..index];
+            if !frozen.contains(parent) {
+                packages.insert(parent.to_owned());
+            }
+        }
+    }
+    packages.into_iter().collect()
+}
+
+/// The frozen module for the namespace package `name`, which has no file and whose code does
+/// nothing.
+pub fn namespace_package(name: &str) -> FrozenModule {
+    let code = compile::compile("", compile::Mode::Exec, name.to_owned(), 0)
+        .expect("Compiling an empty module failed");
+    FrozenModule {
+        code: code.into(),
+        package: true,
+        namespace: true,
+        source: None,
+        source_path: None,
+        resources: HashMap::new(),
+    }
+}
+
+/// Write `modules` with `rustpython_bytecode::frozen_file::write` to `file_name` in the
+/// `$OUT_DIR` of the build script that calls this, and return the path of the file.
+pub fn write_out_dir(
+    file_name: &str,
+    modules: &[(String, FrozenModule)],
+    compress: bool,
+) -> io::Result<PathBuf> {
+    let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "OUT_DIR isn't set; write_out_dir is meant to be called from a build script",
+        )
+    })?;
+    let path = Path::new(&out_dir).join(file_name);
+    let mut data = Vec::new();
+    rustpython_bytecode::frozen_file::write(modules, compress, &mut data)?;
+    // Leave the file alone if it's unchanged, so cargo doesn't rebuild what include_bytes! it
+    if fs::read(&path).ok().as_ref() != Some(&data) {
+        fs::write(&path, data).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("Error writing file {:?}: {}", path, err),
+            )
+        })?;
+    }
+    Ok(path)
+}
+
+/// The name of the bytecode file for `module_name` in a precompiled bytecode directory.
+pub fn bytecode_file_name(module_name: &str) -> String {
+    format!("{}.{}", module_name, BYTECODE_EXTENSION)
+}
+
+/// A module stored in a precompiled bytecode directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub package: bool,
+    /// The path of the source file the module was compiled from, relative to the input directory
+    /// it was found in, or the file name of an input file; see `FrozenModule::source_path`.
+    pub source_path: String,
+    /// `CodeObject::content_hash(true)` of the stored code object.
+    pub hash: u128,
+}
+
+/// The table of contents of a precompiled bytecode directory.
+///
+/// The manifest is a text file: a header line, a line with the bytecode format version, and
+/// one tab-separated `name package hash source_path` line per module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    /// The `FORMAT_VERSION` of the bytecode the directory was written with.
+    pub format_version: u32,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Manifest {
+            format_version: FORMAT_VERSION,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{}", MANIFEST_HEADER)?;
+        writeln!(out, "format_version {}", self.format_version)?;
+        for entry in &self.entries {
+            writeln!(
+                out,
+                "{}\t{}\t{:032x}\t{}",
+                entry.name, entry.package as u8, entry.hash, entry.source_path
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(MANIFEST_HEADER) {
+            return Err("Not a rustpython-compile manifest".to_string());
+        }
+        let format_version = lines
+            .next()
+            .filter(|line| line.starts_with("format_version "))
+            .and_then(|line| line["format_version ".len()..].parse().ok())
+            .ok_or_else(|| "Missing bytecode format version in manifest".to_string())?;
+        let entries = lines
+            .enumerate()
+            .map(|(index, line)| {
+                let invalid = || format!("Invalid manifest entry on line {}", index + 3);
+                let mut fields = line.splitn(4, '\t');
+                let name = fields.next().ok_or_else(invalid)?.to_string();
+                let package = match fields.next() {
+                    Some("0") => false,
+                    Some("1") => true,
+                    _ => return Err(invalid()),
+                };
+                let hash = fields
+                    .next()
+                    .and_then(|hash| u128::from_str_radix(hash, 16).ok())
+                    .ok_or_else(invalid)?;
+                let source_path = fields.next().ok_or_else(invalid)?.to_string();
+                Ok(ManifestEntry {
+                    name,
+                    package,
+                    source_path,
+                    hash,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Manifest {
+            format_version,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let manifest = Manifest {
+            format_version: FORMAT_VERSION,
+            entries: vec![
+                ManifestEntry {
+                    name: "pkg".to_string(),
+                    package: true,
+                    source_path: "pkg/__init__.py".to_string(),
+                    hash: 0x0123_4567_89ab_cdef,
+                },
+                ManifestEntry {
+                    name: "pkg.mod".to_string(),
+                    package: false,
+                    source_path: "pkg/with\ttab.py".to_string(),
+                    hash: u128::max_value(),
+                },
+            ],
+        };
+        let mut text = Vec::new();
+        manifest.write(&mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert_eq!(Manifest::parse(&text), Ok(manifest));
+    }
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        glob_match(&pattern, &path)
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(matches("pkg/data.txt", "pkg/data.txt"));
+        assert!(!matches("pkg/data.txt", "pkg/data.txt2"));
+        assert!(matches("pkg/*.txt", "pkg/data.txt"));
+        assert!(matches("pkg/*", "pkg/data.txt"));
+        assert!(!matches("pkg/*.txt", "pkg/sub/data.txt"));
+        assert!(!matches("*.txt", "pkg/data.txt"));
+        assert!(matches("pkg/?ata.txt", "pkg/data.txt"));
+        assert!(!matches("pkg?data.txt", "pkg/data.txt"));
+        assert!(matches("**/*.txt", "data.txt"));
+        assert!(matches("**/*.txt", "pkg/sub/data.txt"));
+        assert!(matches("pkg/**/data.txt", "pkg/data.txt"));
+        assert!(matches("pkg/**/data.txt", "pkg/a/b/data.txt"));
+        assert!(!matches("pkg/**/data.txt", "other/a/data.txt"));
+        assert!(matches("pkg/**", "pkg/data.txt"));
+        assert!(matches("pkg/**", "pkg/a/b/data.txt"));
+        assert!(!matches("pkg/**", "pkg"));
+        assert!(!matches("pkg/**", "pkg2/data.txt"));
+        assert!(matches("**/test/**", "a/test/b/c.py"));
+
+        let patterns = ["nt".to_owned(), "*path.py".to_owned()];
+        assert!(glob_matches_path(&patterns, "nt/__init__.py"));
+        assert!(glob_matches_path(&patterns, "nt/sub/mod.py"));
+        assert!(glob_matches_path(&patterns, "ntpath.py"));
+        assert!(!glob_matches_path(&patterns, "pkg/ntpath.py"));
+        assert!(!glob_matches_path(&patterns, "ntx/mod.py"));
+    }
+
+    #[test]
+    fn test_find_resources() {
+        let dir = std::env::temp_dir().join(format!("rustpython-resources-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pkg/sub")).unwrap();
+        fs::create_dir_all(dir.join("loose")).unwrap();
+        fs::create_dir_all(dir.join("pkg/__pycache__")).unwrap();
+        for file in &[
+            "pkg/__init__.py",
+            "pkg/schema.json",
+            "pkg/mod.py",
+            "pkg/__pycache__/mod.pyc",
+            "pkg/sub/__init__.py",
+            "pkg/sub/b.txt",
+            "pkg/sub/a.txt",
+            "loose/c.txt",
+            "top.txt",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let resources = find_resources(&dir, "pkg/**/*").unwrap();
+        let names: Vec<_> = resources
+            .iter()
+            .map(|resource| (resource.package.as_str(), resource.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("pkg", "schema.json"),
+                ("pkg.sub", "a.txt"),
+                ("pkg.sub", "b.txt")
+            ]
+        );
+        assert_eq!(resources[1].relative_path, "pkg/sub/a.txt");
+        assert_eq!(resources[1].path, dir.join("pkg/sub/a.txt"));
+
+        assert!(find_resources(&dir, "*.rs").unwrap().is_empty());
+        assert!(find_resources(&dir, "loose/*.txt").is_err());
+        assert!(find_resources(&dir, "top.txt").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_modules_filtered() {
+        let dir = std::env::temp_dir().join(format!("rustpython-exclude-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pkg/test")).unwrap();
+        fs::create_dir_all(dir.join("idlelib")).unwrap();
+        for file in &[
+            "pkg/__init__.py",
+            "pkg/mod.py",
+            "pkg/test_mod.py",
+            "pkg/test/__init__.py",
+            "pkg/test/test_thing.py",
+            "idlelib/__init__.py",
+            "top.py",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+
+        let filtered = |include: &[&str], exclude: &[&str]| {
+            let filter = ModuleFilter {
+                include: include.iter().map(|&pattern| pattern.to_owned()).collect(),
+                exclude: exclude.iter().map(|&pattern| pattern.to_owned()).collect(),
+            };
+            find_modules_filtered(&dir, &filter)
+                .unwrap()
+                .into_iter()
+                .map(|module| module.name)
+                .collect::<Vec<_>>()
+        };
+        let names = |exclude: &[&str]| filtered(&[], exclude);
+        assert_eq!(
+            names(&[]),
+            [
+                "idlelib",
+                "pkg",
+                "pkg.mod",
+                "pkg.test",
+                "pkg.test.test_thing",
+                "pkg.test_mod",
+                "top"
+            ]
+        );
+        assert_eq!(
+            names(&["**/test", "**/test_*.py", "idlelib"]),
+            ["pkg", "pkg.mod", "top"]
+        );
+        assert_eq!(
+            names(&["*.py"]),
+            [
+                "idlelib",
+                "pkg",
+                "pkg.mod",
+                "pkg.test",
+                "pkg.test.test_thing",
+                "pkg.test_mod"
+            ]
+        );
+        assert_eq!(names(&["pkg/test/**"]), names(&["pkg/test"]));
+        assert_eq!(
+            filtered(&["pkg/*.py"], &[]),
+            ["pkg", "pkg.mod", "pkg.test_mod"]
+        );
+        assert_eq!(
+            filtered(&["**/*.py"], &["**/test_*.py", "idlelib/**"]),
+            ["pkg", "pkg.mod", "pkg.test", "top"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pylink() {
+        let root = std::env::temp_dir().join(format!("rustpython-pylink-{}", std::process::id()));
+        let dir = root.join("lib");
+        let vendor = root.join("vendor");
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        fs::create_dir_all(vendor.join("attr/test")).unwrap();
+        for file in &[
+            "lib/pkg/__init__.py",
+            "vendor/six.py",
+            "vendor/attr/__init__.py",
+            "vendor/attr/_make.py",
+            "vendor/attr/test/__init__.py",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        fs::write(dir.join("six.pylink"), "../vendor/six.py\n").unwrap();
+        fs::write(
+            dir.join("pkg/attr.pylink"),
+            vendor.join("attr").to_str().unwrap(),
+        )
+        .unwrap();
+
+        let filter = ModuleFilter {
+            include: Vec::new(),
+            exclude: vec!["**/test".to_owned()],
+        };
+        let modules: Vec<_> = find_modules_filtered(&dir, &filter)
+            .unwrap()
+            .into_iter()
+            .map(|module| (module.name, module.relative_path, module.path))
+            .collect();
+        let module = |name: &str, relative_path: &str, path: PathBuf| {
+            (name.to_owned(), relative_path.to_owned(), path)
+        };
+        assert_eq!(
+            modules,
+            [
+                module("pkg", "pkg/__init__.py", dir.join("pkg/__init__.py")),
+                module(
+                    "pkg.attr",
+                    "pkg/attr/__init__.py",
+                    vendor.join("attr/__init__.py")
+                ),
+                module(
+                    "pkg.attr._make",
+                    "pkg/attr/_make.py",
+                    vendor.join("attr/_make.py")
+                ),
+                module("six", "six.py", dir.join("../vendor/six.py")),
+            ]
+        );
+
+        fs::write(dir.join("six.pylink"), "../vendor/missing.py").unwrap();
+        let err = find_modules(&dir).unwrap_err().to_string();
+        assert!(err.contains("which doesn't exist"), "{}", err);
+        fs::write(dir.join("six.pylink"), "\n").unwrap();
+        let err = find_modules(&dir).unwrap_err().to_string();
+        assert!(err.contains("doesn't contain a path"), "{}", err);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_find_archive_modules() {
+        let entries: Vec<String> = [
+            "purelib/",
+            "purelib/pkg/__init__.py",
+            "purelib/pkg/mod.py",
+            "purelib/pkg/data.txt",
+            "purelib/pkg/test/__init__.py",
+            "purelib/pkg/test/test_thing.py",
+            "purelib/__init__.py",
+            "purelib/top.py",
+            "purelibx/other.py",
+            "scripts/run.py",
+        ]
+        .iter()
+        .map(|&entry| entry.to_owned())
+        .collect();
+        let modules = |prefix: &str, exclude: &[&str]| {
+            let filter = ModuleFilter {
+                include: Vec::new(),
+                exclude: exclude.iter().map(|&pattern| pattern.to_owned()).collect(),
+            };
+            find_archive_modules(&entries, prefix, &filter)
+                .unwrap()
+                .into_iter()
+                .map(|module| (module.name, module.relative_path, module.package))
+                .collect::<Vec<_>>()
+        };
+        let module = |name: &str, relative_path: &str, package| {
+            (name.to_owned(), relative_path.to_owned(), package)
+        };
+        assert_eq!(
+            modules("purelib/", &["**/test"]),
+            [
+                module("pkg", "pkg/__init__.py", true),
+                module("pkg.mod", "pkg/mod.py", false),
+                module("top", "top.py", false),
+            ]
+        );
+        assert_eq!(
+            modules("purelib", &["pkg"]),
+            [module("top", "top.py", false)]
+        );
+        assert_eq!(
+            modules("", &["purelib*"])
+                .into_iter()
+                .map(|module| module.0)
+                .collect::<Vec<_>>(),
+            ["scripts.run"]
+        );
+
+        let filter = ModuleFilter {
+            include: vec!["**/__init__.py".to_owned()],
+            exclude: Vec::new(),
+        };
+        let included: Vec<_> = find_archive_modules(&entries, "purelib", &filter)
+            .unwrap()
+            .into_iter()
+            .map(|module| module.name)
+            .collect();
+        assert_eq!(included, ["pkg", "pkg.test"]);
+
+        let entries = vec!["a.py".to_owned(), "a/__init__.py".to_owned()];
+        let err = find_archive_modules(&entries, "", &ModuleFilter::default()).unwrap_err();
+        assert_eq!(err, "Duplicate module a: \"a.py\" and \"a/__init__.py\"");
+    }
+
+    #[test]
+    fn test_freeze_dir() {
+        let dir = std::env::temp_dir().join(format!("rustpython-freeze-{}", std::process::id()));
+        fs::create_dir_all(dir.join("pkg/test")).unwrap();
+        fs::write(dir.join("pkg/__init__.py"), "assert False\n").unwrap();
+        fs::write(dir.join("pkg/data.txt"), "data").unwrap();
+        fs::write(dir.join("pkg/test/__init__.py"), "").unwrap();
+        fs::write(dir.join("pkg/test/data.txt"), "test data").unwrap();
+        fs::write(dir.join("top.py"), b"# coding: latin-1\nname = '\xe9'\n").unwrap();
+        fs::create_dir_all(dir.join("ns/sub")).unwrap();
+        fs::write(dir.join("ns/sub/mod.py"), "").unwrap();
+
+        let opts = FreezeOptions {
+            optimize: 1,
+            filter: ModuleFilter {
+                include: Vec::new(),
+                exclude: vec!["**/test".to_owned()],
+            },
+            resources: vec!["**/*.txt".to_owned()],
+            embed_source: true,
+            ..Default::default()
+        };
+        let modules = freeze_dir(&dir, &opts).unwrap();
+        let names: Vec<_> = modules.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["ns", "ns.sub", "ns.sub.mod", "pkg", "top"]);
+
+        let (_, ns) = &modules[1];
+        assert!(ns.package && ns.namespace);
+        assert!(ns.source_path.is_none());
+        assert!(!modules[2].1.namespace);
+
+        let (_, pkg) = &modules[3];
+        assert!(pkg.package);
+        assert_eq!(pkg.source_path.as_ref().unwrap(), "pkg/__init__.py");
+        assert_eq!(
+            pkg.code.decode(),
+            compile::compile("assert False", compile::Mode::Exec, "pkg".to_owned(), 1).unwrap()
+        );
+        assert_eq!(pkg.resources.len(), 1);
+        assert_eq!(&pkg.resources["data.txt"][..], b"data");
+
+        let (_, top) = &modules[4];
+        assert!(!top.package);
+        assert_eq!(
+            top.source.as_ref().unwrap().decode(),
+            "# coding: latin-1\nname = '\u{e9}'\n"
+        );
+
+        fs::write(dir.join("top.py"), "name = \n").unwrap();
+        let err = freeze_dir(&dir, &FreezeOptions::default()).err().unwrap();
+        assert!(err.starts_with("Compile error in "), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_namespace_packages() {
+        let names = [
+            "a.b.c",
+            "a.b.d",
+            "pkg",
+            "pkg.mod",
+            "pkg.sub.mod",
+            "mod",
+            "mod.x",
+        ];
+        assert_eq!(
+            namespace_packages(names.iter().cloned()),
+            ["a", "a.b", "pkg.sub"]
+        );
+        assert!(namespace_packages(vec!["top", "pkg", "pkg.mod"]).is_empty());
+    }
+
+    #[test]
+    fn test_manifest_rejects_garbage() {
+        assert!(Manifest::parse("").is_err());
+        assert!(Manifest::parse("rustpython-compile manifest\nformat_version x\n").is_err());
+        assert!(Manifest::parse(
+            "rustpython-compile manifest\nformat_version 1\npkg\t2\t0\tp.py\n"
+        )
+        .is_err());
+    }
+}
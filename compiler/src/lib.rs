@@ -7,8 +7,13 @@
 extern crate log;
 
 pub mod compile;
+pub mod encoding;
 pub mod error;
+pub mod frozen;
 pub mod mode;
 pub(crate) mod output_stream;
 pub mod peephole;
 pub mod symboltable;
+pub(crate) mod unparse;
+
+pub use crate::frozen::{freeze_dir, FreezeOptions};
@@ -1,4 +1,8 @@
-use rustpython_bytecode::bytecode::{CodeFlags, CodeObject, Instruction, Label, Location};
+use rustpython_bytecode::bytecode::{
+    CodeFlags, CodeObject, ConstIdx, Constant, Instruction, Label, LineTable, Location, NameIdx,
+    NO_CACHE_SLOT,
+};
+use std::collections::HashMap;
 
 pub trait OutputStream: From<CodeObject> + Into<CodeObject> {
     /// Output an instruction
@@ -7,27 +11,59 @@ pub trait OutputStream: From<CodeObject> + Into<CodeObject> {
     fn set_label(&mut self, label: Label);
     /// Mark the inner CodeObject as a generator
     fn mark_generator(&mut self);
+    /// Add a constant to the inner CodeObject, unless it has an equal one, and return its index
+    fn add_constant(&mut self, constant: Constant) -> ConstIdx;
+    /// The constant at `idx` of the inner CodeObject
+    fn constant(&self, idx: ConstIdx) -> &Constant;
+    /// Add a name to the inner CodeObject, unless it has it, and return its index
+    fn add_name(&mut self, name: &str) -> NameIdx;
 }
 
 pub struct CodeObjectStream {
     code: CodeObject,
+    cache_slot_count: u32,
+    /// The location of each instruction, which go into the line table at the end.
+    locations: Vec<Location>,
+    /// The index of every constant and name of the code object, to find the equal ones fast.
+    constant_indices: HashMap<Constant, ConstIdx>,
+    name_indices: HashMap<String, NameIdx>,
 }
 
 impl From<CodeObject> for CodeObjectStream {
     fn from(code: CodeObject) -> Self {
-        CodeObjectStream { code }
+        let cache_slot_count = code.cache_slot_count() as u32;
+        let locations = code.line_table.locations().collect();
+        let constant_indices = code.constants.iter().cloned().zip(0..).collect();
+        let name_indices = code.names.iter().cloned().zip(0..).collect();
+        CodeObjectStream {
+            code,
+            cache_slot_count,
+            locations,
+            constant_indices,
+            name_indices,
+        }
     }
 }
 impl From<CodeObjectStream> for CodeObject {
     fn from(stream: CodeObjectStream) -> Self {
-        stream.code
+        let mut code = stream.code;
+        code.line_table = LineTable::from_locations(stream.locations);
+        code
     }
 }
 
 impl OutputStream for CodeObjectStream {
     fn emit(&mut self, instruction: Instruction, location: Location) {
-        self.code.instructions.push(instruction);
-        self.code.locations.push(location);
+        let cache_slot = if instruction.has_inline_cache() {
+            self.cache_slot_count += 1;
+            self.cache_slot_count - 1
+        } else {
+            NO_CACHE_SLOT
+        };
+        self.code.cache_slots.to_mut().push(cache_slot);
+        self.code.instructions.to_mut().push(instruction);
+        // Row 0 is for the instructions that don't belong to any line.
+        self.locations.push(location);
     }
     fn set_label(&mut self, label: Label) {
         let position = self.code.instructions.len();
@@ -36,4 +72,25 @@ impl OutputStream for CodeObjectStream {
     fn mark_generator(&mut self) {
         self.code.flags |= CodeFlags::IS_GENERATOR;
     }
+    fn add_constant(&mut self, constant: Constant) -> ConstIdx {
+        if let Some(&idx) = self.constant_indices.get(&constant) {
+            return idx;
+        }
+        self.code.constants.push(constant.clone());
+        let idx = self.code.constants.len() - 1;
+        self.constant_indices.insert(constant, idx);
+        idx
+    }
+    fn constant(&self, idx: ConstIdx) -> &Constant {
+        &self.code.constants[idx]
+    }
+    fn add_name(&mut self, name: &str) -> NameIdx {
+        if let Some(&idx) = self.name_indices.get(name) {
+            return idx;
+        }
+        self.code.names.push(name.to_owned());
+        let idx = self.code.names.len() - 1;
+        self.name_indices.insert(name.to_owned(), idx);
+        idx
+    }
 }
@@ -1,7 +1,10 @@
 use crate::output_stream::OutputStream;
 use arrayvec::ArrayVec;
-use rustpython_bytecode::bytecode::{CodeObject, Instruction, Label, Location};
+use rustpython_bytecode::bytecode::{
+    CodeObject, ConstIdx, Constant, Instruction, Label, Location, NameIdx,
+};
 
+mod jumps;
 pub mod optimizations;
 
 const PEEPHOLE_BUFFER_SIZE: usize = 20;
@@ -45,7 +48,11 @@ impl<O: OutputStream> From<CodeObject> for PeepholeOptimizer<O> {
 impl<O: OutputStream> From<PeepholeOptimizer<O>> for CodeObject {
     fn from(mut peep: PeepholeOptimizer<O>) -> Self {
         peep.flush();
-        peep.inner.into()
+        let mut code: CodeObject = peep.inner.into();
+        jumps::optimize(&mut code);
+        // Folding leaves behind the constants it folded, and removed code those it loaded
+        code.remove_unused_constants();
+        code
     }
 }
 
@@ -92,7 +99,7 @@ impl<O: OutputStream> PeepholeOptimizer<O> {
     }
 
     fn optimize(&mut self) {
-        apply_optimizations!(self, operator, unpack);
+        apply_optimizations!(self, operator, unary, tuple, unpack);
     }
 }
 
@@ -112,6 +119,15 @@ where
     fn mark_generator(&mut self) {
         self.inner.mark_generator()
     }
+    fn add_constant(&mut self, constant: Constant) -> ConstIdx {
+        self.inner.add_constant(constant)
+    }
+    fn constant(&self, idx: ConstIdx) -> &Constant {
+        self.inner.constant(idx)
+    }
+    fn add_name(&mut self, name: &str) -> NameIdx {
+        self.inner.add_name(name)
+    }
 }
 
 impl<O: OutputStream> OptimizationBuffer for PeepholeOptimizer<O> {
@@ -121,9 +137,27 @@ impl<O: OutputStream> OptimizationBuffer for PeepholeOptimizer<O> {
     fn pop(&mut self) -> (Instruction, InstructionMetadata) {
         self.pop()
     }
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+    fn constant(&self, idx: ConstIdx) -> &Constant {
+        self.inner.constant(idx)
+    }
+    fn add_constant(&mut self, constant: Constant) -> ConstIdx {
+        self.inner.add_constant(constant)
+    }
 }
 
 pub trait OptimizationBuffer {
     fn emit(&mut self, instruction: Instruction, meta: InstructionMetadata);
     fn pop(&mut self) -> (Instruction, InstructionMetadata);
+    /// How many instructions there are to pop.
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The constant at `idx` of the code object.
+    fn constant(&self, idx: ConstIdx) -> &Constant;
+    /// Add a constant to the code object, like `OutputStream::add_constant`.
+    fn add_constant(&mut self, constant: Constant) -> ConstIdx;
 }
@@ -0,0 +1,135 @@
+//! Optimizations of the control flow, over the whole code object once all of its instructions
+//! are emitted, since jumps go forward as well as back: jumps to jumps go straight to where the
+//! last one goes, then the instructions that can't run and the jumps to the next instruction
+//! are removed.
+//!
+//! Every jump target is kept, including the handlers and loop ends of the `Setup*`
+//! instructions, so code is only ever removed up to the next one.
+
+use std::collections::HashSet;
+
+use rustpython_bytecode::bytecode::{CodeObject, Instruction, Label, LineTable, NO_CACHE_SLOT};
+
+pub fn optimize(code: &mut CodeObject) {
+    retarget_jump_chains(code);
+    loop {
+        let removed = removable_instructions(code);
+        if !removed.contains(&true) {
+            break;
+        }
+        remove_instructions(code, &removed);
+    }
+}
+
+/// The target of a jump that goes to the same place if it goes to where the jump at its target
+/// goes. The targets of the `Setup*` instructions aren't, since they also delimit the block.
+fn jump_target(instruction: &mut Instruction) -> Option<&mut Label> {
+    match instruction {
+        Instruction::Jump { target }
+        | Instruction::JumpIfTrue { target }
+        | Instruction::JumpIfFalse { target }
+        | Instruction::JumpIfTrueOrPop { target }
+        | Instruction::JumpIfFalseOrPop { target }
+        | Instruction::ForIter { target } => Some(target),
+        _ => None,
+    }
+}
+
+/// Whether the instruction after this one only runs when something jumps to it.
+fn is_terminal(instruction: &Instruction) -> bool {
+    match instruction {
+        Instruction::ReturnValue
+        | Instruction::Raise { .. }
+        | Instruction::Jump { .. }
+        | Instruction::Break
+        | Instruction::Continue => true,
+        _ => false,
+    }
+}
+
+fn retarget_jump_chains(code: &mut CodeObject) {
+    for index in 0..code.instructions.len() {
+        let mut target = match jump_target(&mut code.instructions.to_mut()[index]) {
+            Some(target) => *target,
+            None => continue,
+        };
+        // A loop of jumps is left to loop forever where it starts
+        let mut seen = HashSet::new();
+        seen.insert(index);
+        while let Some(Instruction::Jump { target: next }) =
+            code.instructions.get(code.label_map[&target])
+        {
+            if !seen.insert(code.label_map[&target]) {
+                break;
+            }
+            target = *next;
+        }
+        *jump_target(&mut code.instructions.to_mut()[index]).unwrap() = target;
+    }
+}
+
+/// Which instructions can go: the ones after a return, raise or jump, up to the next jump
+/// target, and the jumps past only those.
+fn removable_instructions(code: &CodeObject) -> Vec<bool> {
+    let len = code.instructions.len();
+    let targets: HashSet<usize> = code.label_map.values().cloned().collect();
+    let mut removed = vec![false; len];
+    let mut index = 0;
+    while index < len {
+        let terminal = is_terminal(&code.instructions[index]);
+        index += 1;
+        if terminal {
+            while index < len && !targets.contains(&index) {
+                removed[index] = true;
+                index += 1;
+            }
+        }
+    }
+    for index in 0..len {
+        if let Instruction::Jump { target } = &code.instructions[index] {
+            let target = code.label_map[target];
+            if !removed[index] && target > index && removed[index + 1..target].iter().all(|&r| r) {
+                removed[index] = true;
+            }
+        }
+    }
+    removed
+}
+
+/// Removes the instructions along with their locations, and moves the jump targets to where
+/// the instructions they were before went. The inline cache slots are numbered again, so that
+/// there are as many as there are instructions with one.
+fn remove_instructions(code: &mut CodeObject, removed: &[bool]) {
+    let mut new_positions = Vec::with_capacity(removed.len() + 1);
+    let mut position = 0;
+    for &removed in removed {
+        new_positions.push(position);
+        if !removed {
+            position += 1;
+        }
+    }
+    new_positions.push(position);
+    for position in code.label_map.values_mut() {
+        *position = new_positions[*position];
+    }
+
+    let mut kept = removed.iter().map(|&removed| !removed);
+    code.instructions.to_mut().retain(|_| kept.next().unwrap());
+    let locations: Vec<_> = code
+        .line_table
+        .locations()
+        .zip(removed)
+        .filter(|(_, &removed)| !removed)
+        .map(|(location, _)| location)
+        .collect();
+    code.line_table = LineTable::from_locations(locations);
+    let mut kept = removed.iter().map(|&removed| !removed);
+    code.cache_slots.to_mut().retain(|_| kept.next().unwrap());
+    let mut next_slot = 0;
+    for slot in code.cache_slots.to_mut() {
+        if *slot != NO_CACHE_SLOT {
+            *slot = next_slot;
+            next_slot += 1;
+        }
+    }
+}
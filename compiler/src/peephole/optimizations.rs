@@ -1,4 +1,8 @@
-use rustpython_bytecode::bytecode::{self, Instruction};
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_integer::Integer;
+use num_traits::{Pow, Signed, ToPrimitive, Zero};
+use rustpython_bytecode::bytecode::{self, BinaryOperator, Constant, Instruction, UnaryOperator};
 
 use super::{InstructionMetadata, OptimizationBuffer};
 
@@ -7,23 +11,31 @@ macro_rules! metas {
         InstructionMetadata::from(vec![$($metas),*])
     };
 }
-macro_rules! lc {
-    ($name:ident {$($field:tt)*}) => {
-        Instruction::LoadConst {
-            value: bytecode::Constant::$name {$($field)*},
+
+/// Like CPython, results that would make big constants aren't folded: ints of more bits than
+/// this, and strings and bytes longer than `MAX_SEQUENCE_LEN`.
+const MAX_INT_BITS: u64 = 128;
+const MAX_SEQUENCE_LEN: usize = 4096;
+
+/// A constant that an instruction loads, unless a jump lands right after it, in which case the
+/// value on the stack there doesn't always come from this instruction.
+fn folding_constant(
+    buf: &impl OptimizationBuffer,
+    instruction: &Instruction,
+    meta: &InstructionMetadata,
+) -> Option<Constant> {
+    match instruction {
+        Instruction::LoadConst { idx } if meta.labels.is_empty() => {
+            Some(buf.constant(*idx).clone())
         }
-    };
-    ($name:ident, $($value:tt)*) => {
-        lc!($name { value: $($value)* })
-    };
+        _ => None,
+    }
 }
-macro_rules! emitconst {
-    ($buf:expr, [$($metas:expr),*$(,)?], $($arg:tt)*) => {
-        $buf.emit(
-            lc!($($arg)*),
-            metas![$($metas),*],
-        )
-    };
+
+fn load_const(buf: &mut impl OptimizationBuffer, constant: Constant) -> Instruction {
+    Instruction::LoadConst {
+        idx: buf.add_constant(constant),
+    }
 }
 
 pub fn operator(buf: &mut impl OptimizationBuffer) {
@@ -31,38 +43,19 @@ pub fn operator(buf: &mut impl OptimizationBuffer) {
     if let Instruction::BinaryOperation { op, inplace } = instruction {
         let (rhs, rhs_meta) = buf.pop();
         let (lhs, lhs_meta) = buf.pop();
-        macro_rules! op {
-            ($op:ident) => {
-                bytecode::BinaryOperator::$op
-            };
-        }
-        match (op, lhs, rhs) {
-            (op!(Add), lc!(Integer, lhs), lc!(Integer, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Integer, lhs + rhs)
+        let folded = match (
+            folding_constant(buf, &lhs, &lhs_meta),
+            folding_constant(buf, &rhs, &rhs_meta),
+        ) {
+            (Some(lhs), Some(rhs)) => fold_binary(&op, &lhs, &rhs),
+            _ => None,
+        };
+        match folded {
+            Some(value) => {
+                let load = load_const(buf, value);
+                buf.emit(load, metas![lhs_meta, rhs_meta, meta])
             }
-            (op!(Subtract), lc!(Integer, lhs), lc!(Integer, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Integer, lhs - rhs)
-            }
-            (op!(Add), lc!(Float, lhs), lc!(Float, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Float, lhs + rhs)
-            }
-            (op!(Subtract), lc!(Float, lhs), lc!(Float, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Float, lhs - rhs)
-            }
-            (op!(Multiply), lc!(Float, lhs), lc!(Float, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Float, lhs * rhs)
-            }
-            (op!(Divide), lc!(Float, lhs), lc!(Float, rhs)) if rhs != 0.0 => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Float, lhs / rhs)
-            }
-            (op!(Power), lc!(Float, lhs), lc!(Float, rhs)) => {
-                emitconst!(buf, [lhs_meta, rhs_meta], Float, lhs.powf(rhs))
-            }
-            (op!(Add), lc!(String, mut lhs), lc!(String, rhs)) => {
-                lhs.push_str(&rhs);
-                emitconst!(buf, [lhs_meta, rhs_meta], String, lhs);
-            }
-            (op, lhs, rhs) => {
+            None => {
                 buf.emit(lhs, lhs_meta);
                 buf.emit(rhs, rhs_meta);
                 buf.emit(Instruction::BinaryOperation { op, inplace }, meta);
@@ -73,6 +66,64 @@ pub fn operator(buf: &mut impl OptimizationBuffer) {
     }
 }
 
+pub fn unary(buf: &mut impl OptimizationBuffer) {
+    let (instruction, meta) = buf.pop();
+    if let Instruction::UnaryOperation { op } = instruction {
+        let (operand, operand_meta) = buf.pop();
+        let folded = folding_constant(buf, &operand, &operand_meta)
+            .and_then(|value| fold_unary(&op, &value));
+        match folded {
+            Some(value) => {
+                let load = load_const(buf, value);
+                buf.emit(load, metas![operand_meta, meta])
+            }
+            None => {
+                buf.emit(operand, operand_meta);
+                buf.emit(Instruction::UnaryOperation { op }, meta);
+            }
+        }
+    } else {
+        buf.emit(instruction, meta)
+    }
+}
+
+/// Turns a tuple of constants into a constant tuple. Only the tuples whose elements are all still
+/// in the buffer are folded.
+pub fn tuple(buf: &mut impl OptimizationBuffer) {
+    let (instruction, meta) = buf.pop();
+    match instruction {
+        Instruction::BuildTuple {
+            size,
+            unpack: false,
+            ..
+        } if size <= buf.len() => {
+            let mut elements: Vec<_> = (0..size).map(|_| buf.pop()).collect();
+            elements.reverse();
+            let constants: Option<Vec<_>> = elements
+                .iter()
+                .map(|(element, meta)| folding_constant(buf, element, meta))
+                .collect();
+            if let Some(constants) = constants {
+                let mut metas: Vec<_> = elements.into_iter().map(|(_, meta)| meta).collect();
+                metas.push(meta);
+                let load = load_const(
+                    buf,
+                    Constant::Tuple {
+                        elements: constants,
+                    },
+                );
+                buf.emit(load, metas.into());
+            } else {
+                for (element, meta) in elements {
+                    buf.emit(element, meta);
+                }
+                buf.emit(instruction, meta);
+            }
+        }
+        instruction => buf.emit(instruction, meta),
+    }
+}
+
 pub fn unpack(buf: &mut impl OptimizationBuffer) {
     let (instruction, meta) = buf.pop();
     if let Instruction::UnpackSequence { size } = instruction {
@@ -81,7 +132,8 @@ pub fn unpack(buf: &mut impl OptimizationBuffer) {
             Instruction::BuildTuple {
                 size: tup_size,
                 unpack,
-            } if !unpack && tup_size == size => {
+                ..
+            } if !unpack && tup_size == size && arg_meta.labels.is_empty() => {
                 buf.emit(
                     Instruction::Reverse { amount: size },
                     vec![arg_meta, meta].into(),
@@ -96,3 +148,156 @@ pub fn unpack(buf: &mut impl OptimizationBuffer) {
         buf.emit(instruction, meta)
     }
 }
+
+/// The result of a binary operation on two constants, when computing it now gives the same value
+/// that the VM would. Operations that raise, like dividing by zero, are left to raise at runtime.
+fn fold_binary(op: &BinaryOperator, lhs: &Constant, rhs: &Constant) -> Option<Constant> {
+    use bytecode::BinaryOperator::*;
+    match (lhs, rhs) {
+        (Constant::Integer { value: lhs }, Constant::Integer { value: rhs }) => {
+            fold_int(op, lhs, rhs)
+        }
+        (Constant::String { value: lhs }, Constant::String { value: rhs }) => match op {
+            Add if lhs.len() + rhs.len() <= MAX_SEQUENCE_LEN => Some(Constant::String {
+                value: lhs.clone() + rhs,
+            }),
+            _ => None,
+        },
+        (Constant::String { value }, Constant::Integer { value: count })
+        | (Constant::Integer { value: count }, Constant::String { value }) => match op {
+            Multiply => repeat_count(value.len(), count).map(|count| Constant::String {
+                value: value.repeat(count),
+            }),
+            _ => None,
+        },
+        (Constant::Bytes { value: lhs }, Constant::Bytes { value: rhs }) => match op {
+            Add if lhs.len() + rhs.len() <= MAX_SEQUENCE_LEN => Some(Constant::Bytes {
+                value: [lhs.as_slice(), rhs.as_slice()].concat(),
+            }),
+            _ => None,
+        },
+        (Constant::Bytes { value }, Constant::Integer { value: count })
+        | (Constant::Integer { value: count }, Constant::Bytes { value }) => match op {
+            Multiply => repeat_count(value.len(), count).map(|count| Constant::Bytes {
+                value: value.repeat(count),
+            }),
+            _ => None,
+        },
+        (Constant::Complex { .. }, _) | (_, Constant::Complex { .. }) => {
+            fold_complex(op, as_complex(lhs)?, as_complex(rhs)?)
+        }
+        _ => fold_float(op, as_float(lhs)?, as_float(rhs)?),
+    }
+}
+
+fn fold_int(op: &BinaryOperator, lhs: &BigInt, rhs: &BigInt) -> Option<Constant> {
+    use bytecode::BinaryOperator::*;
+    let value = match op {
+        Add => lhs + rhs,
+        Subtract => lhs - rhs,
+        Multiply if lhs.bits() + rhs.bits() <= MAX_INT_BITS as usize => lhs * rhs,
+        FloorDivide if !rhs.is_zero() => lhs.div_floor(rhs),
+        Modulo if !rhs.is_zero() => lhs.mod_floor(rhs),
+        // A negative exponent makes a float, or raises for 0
+        Power if !rhs.is_negative() => {
+            let exponent = rhs.to_u64()?;
+            if (lhs.bits() as u64).saturating_mul(exponent) > MAX_INT_BITS {
+                return None;
+            }
+            Pow::pow(lhs, exponent)
+        }
+        Lshift if !rhs.is_negative() => {
+            let shift = rhs.to_usize()?;
+            if (lhs.bits() as u64).saturating_add(shift as u64) > MAX_INT_BITS {
+                return None;
+            }
+            lhs << shift
+        }
+        Rshift if !rhs.is_negative() => lhs >> rhs.to_usize()?,
+        And => lhs & rhs,
+        Or => lhs | rhs,
+        Xor => lhs ^ rhs,
+        Divide if !rhs.is_zero() => {
+            return Some(Constant::Float {
+                value: exact_float(lhs)? / exact_float(rhs)?,
+            });
+        }
+        _ => return None,
+    };
+    Some(Constant::Integer { value })
+}
+
+fn fold_float(op: &BinaryOperator, lhs: f64, rhs: f64) -> Option<Constant> {
+    use bytecode::BinaryOperator::*;
+    let value = match op {
+        Add => lhs + rhs,
+        Subtract => lhs - rhs,
+        Multiply => lhs * rhs,
+        Divide if rhs != 0.0 => lhs / rhs,
+        // The VM raises for any power of 0.0
+        Power if lhs != 0.0 => lhs.powf(rhs),
+        _ => return None,
+    };
+    Some(Constant::Float { value })
+}
+
+fn fold_complex(op: &BinaryOperator, lhs: Complex64, rhs: Complex64) -> Option<Constant> {
+    use bytecode::BinaryOperator::*;
+    let value = match op {
+        Add => lhs + rhs,
+        Subtract => lhs - rhs,
+        Multiply => lhs * rhs,
+        _ => return None,
+    };
+    Some(Constant::Complex { value })
+}
+
+fn fold_unary(op: &UnaryOperator, operand: &Constant) -> Option<Constant> {
+    use bytecode::UnaryOperator::*;
+    match (op, operand) {
+        (Minus, Constant::Integer { value }) => Some(Constant::Integer { value: -value }),
+        (Minus, Constant::Float { value }) => Some(Constant::Float { value: -value }),
+        (Minus, Constant::Complex { value }) => Some(Constant::Complex { value: -value }),
+        (Plus, Constant::Integer { .. })
+        | (Plus, Constant::Float { .. })
+        | (Plus, Constant::Complex { .. }) => Some(operand.clone()),
+        (Invert, Constant::Integer { value }) => Some(Constant::Integer { value: !value }),
+        _ => None,
+    }
+}
+
+/// An int as a float, if it has the same value, which is what the VM computes with when it mixes
+/// ints and floats. Bigger ints are left for the VM to round, or to raise for.
+fn exact_float(value: &BigInt) -> Option<f64> {
+    if value.bits() <= 53 {
+        value.to_f64()
+    } else {
+        None
+    }
+}
+
+fn as_float(constant: &Constant) -> Option<f64> {
+    match constant {
+        Constant::Float { value } => Some(*value),
+        Constant::Integer { value } => exact_float(value),
+        _ => None,
+    }
+}
+
+fn as_complex(constant: &Constant) -> Option<Complex64> {
+    match constant {
+        Constant::Complex { value } => Some(*value),
+        _ => as_float(constant).map(|value| Complex64::new(value, 0.0)),
+    }
+}
+
+/// How many times to repeat a string or bytes of `len` for `count`: none for a negative count,
+/// like in Python. A count that the VM can't take as an `isize` raises there.
+fn repeat_count(len: usize, count: &BigInt) -> Option<usize> {
+    let count = count.to_isize()?.max(0) as usize;
+    if len.checked_mul(count)? <= MAX_SEQUENCE_LEN {
+        Some(count)
+    } else {
+        None
+    }
+}
@@ -9,21 +9,45 @@ Inspirational file: https://github.com/python/cpython/blob/master/Python/symtabl
 
 use crate::error::{CompileError, CompileErrorType};
 use indexmap::map::IndexMap;
+use indexmap::set::IndexSet;
 use rustpython_parser::ast;
 use rustpython_parser::location::Location;
 use std::fmt;
 
 pub fn make_symbol_table(program: &ast::Program) -> Result<SymbolTable, SymbolTableError> {
-    let mut builder: SymbolTableBuilder = Default::default();
+    make_symbol_table_with_future(program, false)
+}
+
+pub fn statements_to_symbol_table(
+    statements: &[ast::Statement],
+) -> Result<SymbolTable, SymbolTableError> {
+    statements_to_symbol_table_with_future(statements, false)
+}
+
+/// Like `make_symbol_table`, for code that starts out with `from __future__ import annotations`
+/// in effect if `future_annotations` is set, like code compiled by such code.
+pub fn make_symbol_table_with_future(
+    program: &ast::Program,
+    future_annotations: bool,
+) -> Result<SymbolTable, SymbolTableError> {
+    let mut builder = SymbolTableBuilder {
+        future_annotations,
+        ..Default::default()
+    };
     builder.prepare();
     builder.scan_program(program)?;
     builder.finish()
 }
 
-pub fn statements_to_symbol_table(
+/// Like `statements_to_symbol_table`, see `make_symbol_table_with_future`.
+pub fn statements_to_symbol_table_with_future(
     statements: &[ast::Statement],
+    future_annotations: bool,
 ) -> Result<SymbolTable, SymbolTableError> {
-    let mut builder: SymbolTableBuilder = Default::default();
+    let mut builder = SymbolTableBuilder {
+        future_annotations,
+        ..Default::default()
+    };
     builder.prepare();
     builder.scan_statements(statements)?;
     builder.finish()
@@ -78,14 +102,21 @@ impl fmt::Display for SymbolTableType {
     }
 }
 
-/// Indicator for a single symbol what the scope of this symbol is.
+/// Indicator for a single symbol what the scope of this symbol is, like in CPython.
 /// The scope can be unknown, which is unfortunate, but not impossible.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SymbolScope {
-    Global,
-    Nonlocal,
-    Local,
     Unknown,
+    /// Bound in this scope.
+    Local,
+    /// Declared `global` in this scope.
+    GlobalExplicit,
+    /// Not bound in this scope nor in any function around it, so a global or a builtin.
+    GlobalImplicit,
+    /// Bound in a function around this scope.
+    Free,
+    /// Bound in this scope, and used by a scope nested in it.
+    Cell,
 }
 
 /// A single symbol in a table. Has various properties such as the scope
@@ -95,11 +126,12 @@ pub struct Symbol {
     pub name: String,
     // pub table: SymbolTableRef,
     pub scope: SymbolScope,
-    pub is_param: bool,
     pub is_referenced: bool,
     pub is_assigned: bool,
     pub is_parameter: bool,
-    pub is_free: bool,
+    /// Whether the symbol is declared `nonlocal`, or is the target of an assignment expression
+    /// in a comprehension that binds it in a function around it.
+    pub is_nonlocal: bool,
 }
 
 impl Symbol {
@@ -108,29 +140,35 @@ impl Symbol {
             name: name.to_string(),
             // table,
             scope: SymbolScope::Unknown,
-            is_param: false,
             is_referenced: false,
             is_assigned: false,
             is_parameter: false,
-            is_free: false,
+            is_nonlocal: false,
         }
     }
 
     pub fn is_global(&self) -> bool {
-        if let SymbolScope::Global = self.scope {
-            true
-        } else {
-            false
+        match self.scope {
+            SymbolScope::GlobalExplicit | SymbolScope::GlobalImplicit => true,
+            _ => false,
         }
     }
 
     pub fn is_local(&self) -> bool {
-        if let SymbolScope::Local = self.scope {
-            true
-        } else {
-            false
+        match self.scope {
+            SymbolScope::Local | SymbolScope::Cell => true,
+            _ => false,
         }
     }
+
+    pub fn is_free(&self) -> bool {
+        self.scope == SymbolScope::Free
+    }
+
+    /// Whether the symbol is bound in its scope.
+    fn is_bound(&self) -> bool {
+        self.is_assigned || self.is_parameter
+    }
 }
 
 #[derive(Debug)]
@@ -171,97 +209,118 @@ impl std::fmt::Debug for SymbolTable {
   See also: https://github.com/python/cpython/blob/master/Python/symtable.c#L410
 */
 fn analyze_symbol_table(symbol_table: &mut SymbolTable) -> SymbolTableResult {
-    let mut analyzer = SymbolTableAnalyzer::default();
-    analyzer.analyze_symbol_table(symbol_table)
+    analyze_table(symbol_table, &IndexSet::new())?;
+    Ok(())
 }
 
-/// Symbol table analysis. Can be used to analyze a fully
-/// build symbol table structure. It will mark variables
-/// as local variables for example.
-#[derive(Default)]
-struct SymbolTableAnalyzer<'a> {
-    tables: Vec<(&'a mut IndexMap<String, Symbol>, SymbolTableType)>,
-}
-
-impl<'a> SymbolTableAnalyzer<'a> {
-    fn analyze_symbol_table(&mut self, symbol_table: &'a mut SymbolTable) -> SymbolTableResult {
-        let symbols = &mut symbol_table.symbols;
-        let sub_tables = &mut symbol_table.sub_tables;
+/// Determine the scope of every symbol of `table` and the tables nested in it, where `bound` are
+/// the names bound by the functions around it. Returns the names that are free in the table,
+/// which the scopes around it have to provide.
+fn analyze_table(
+    table: &mut SymbolTable,
+    bound: &IndexSet<String>,
+) -> Result<IndexSet<String>, SymbolTableError> {
+    for symbol in table.symbols.values_mut() {
+        analyze_symbol(symbol, table.typ, bound)?;
+    }
 
-        self.tables.push((symbols, symbol_table.typ));
-        // Analyze sub scopes:
-        for sub_table in sub_tables {
-            self.analyze_symbol_table(sub_table)?;
+    // The names a nested scope can use from this one and the scopes around it. A class body
+    // doesn't provide its names, except for the `__class__` its methods can use:
+    // https://docs.python.org/3/reference/datamodel.html?highlight=__class__#creating-the-class-object
+    let mut nested_bound = bound.clone();
+    match table.typ {
+        SymbolTableType::Module => {}
+        SymbolTableType::Class => {
+            nested_bound.insert("__class__".to_string());
         }
-        let (symbols, _) = self.tables.pop().unwrap();
-
-        // Analyze symbols:
-        for symbol in symbols.values_mut() {
-            self.analyze_symbol(symbol)?;
+        SymbolTableType::Function => {
+            for symbol in table.symbols.values() {
+                match symbol.scope {
+                    SymbolScope::Local | SymbolScope::Free => {
+                        nested_bound.insert(symbol.name.clone());
+                    }
+                    SymbolScope::GlobalExplicit => {
+                        nested_bound.swap_remove(&symbol.name);
+                    }
+                    _ => {}
+                }
+            }
         }
+    }
 
-        Ok(())
+    let mut nested_free = IndexSet::new();
+    for sub_table in &mut table.sub_tables {
+        nested_free.extend(analyze_table(sub_table, &nested_bound)?);
     }
 
-    fn analyze_symbol(&self, symbol: &mut Symbol) -> SymbolTableResult {
-        match symbol.scope {
-            SymbolScope::Nonlocal => {
-                // check if name is defined in parent table!
-                let parent_symbol_table = self.tables.last();
-                // symbol.table.borrow().parent.clone();
-
-                if let Some((symbols, _)) = parent_symbol_table {
-                    let scope_depth = self.tables.len();
-                    if !symbols.contains_key(&symbol.name) || scope_depth < 2 {
-                        return Err(SymbolTableError {
-                            error: format!("no binding for nonlocal '{}' found", symbol.name),
-                            location: Default::default(),
-                        });
-                    }
-                } else {
-                    return Err(SymbolTableError {
-                        error: format!(
-                            "nonlocal {} defined at place without an enclosing scope",
-                            symbol.name
-                        ),
-                        location: Default::default(),
-                    });
-                }
-            }
-            SymbolScope::Global => {
-                // TODO: add more checks for globals?
-            }
-            SymbolScope::Local => {
-                // all is well
-            }
-            SymbolScope::Unknown => {
-                // Try hard to figure out what the scope of this symbol is.
+    // A name free in a nested scope is a cell in the function that binds it, and passes through
+    // the scopes in between as a free name.
+    let mut free: IndexSet<String> = table
+        .symbols
+        .values()
+        .filter(|symbol| symbol.is_free())
+        .map(|symbol| symbol.name.clone())
+        .collect();
+    for name in nested_free {
+        let symbol = table.symbols.get_mut(&name);
+        match table.typ {
+            SymbolTableType::Module => {}
+            SymbolTableType::Class if name == "__class__" => {}
+            SymbolTableType::Class => {
+                if symbol.is_none() {
+                    let mut symbol = Symbol::new(&name);
+                    symbol.scope = SymbolScope::Free;
+                    table.symbols.insert(name.clone(), symbol);
+                }
+                free.insert(name);
+            }
+            SymbolTableType::Function => match symbol {
+                Some(symbol) if symbol.is_local() => symbol.scope = SymbolScope::Cell,
+                Some(_) => {
+                    free.insert(name);
+                }
+                None => {
+                    let mut symbol = Symbol::new(&name);
+                    symbol.scope = SymbolScope::Free;
+                    table.symbols.insert(name.clone(), symbol);
+                    free.insert(name);
+                }
+            },
+        }
+    }
+    Ok(free)
+}
 
-                if symbol.is_assigned || symbol.is_parameter {
-                    symbol.scope = SymbolScope::Local;
-                } else {
-                    // Interesting stuff about the __class__ variable:
-                    // https://docs.python.org/3/reference/datamodel.html?highlight=__class__#creating-the-class-object
-                    let found_in_outer_scope = symbol.name == "__class__"
-                        || self.tables.iter().skip(1).any(|(symbols, typ)| {
-                            *typ != SymbolTableType::Class && symbols.contains_key(&symbol.name)
-                        });
-
-                    if found_in_outer_scope {
-                        // Symbol is in some outer scope.
-                        symbol.is_free = true;
-                    } else if self.tables.is_empty() {
-                        // Don't make assumptions when we don't know.
-                        symbol.scope = SymbolScope::Unknown;
-                    } else {
-                        // If there are scopes above we can assume global.
-                        symbol.scope = SymbolScope::Global;
-                    }
-                }
-            }
+fn analyze_symbol(
+    symbol: &mut Symbol,
+    typ: SymbolTableType,
+    bound: &IndexSet<String>,
+) -> SymbolTableResult {
+    if symbol.scope == SymbolScope::GlobalExplicit {
+        return Ok(());
+    }
+    if symbol.is_nonlocal {
+        if typ == SymbolTableType::Module {
+            return Err(SymbolTableError {
+                error: "nonlocal declaration not allowed at module level".to_string(),
+                location: Default::default(),
+            });
         }
-        Ok(())
+        if !bound.contains(&symbol.name) {
+            return Err(SymbolTableError {
+                error: format!("no binding for nonlocal '{}' found", symbol.name),
+                location: Default::default(),
+            });
+        }
+        symbol.scope = SymbolScope::Free;
+    } else if symbol.is_bound() {
+        symbol.scope = SymbolScope::Local;
+    } else if typ != SymbolTableType::Module && bound.contains(&symbol.name) {
+        symbol.scope = SymbolScope::Free;
+    } else {
+        symbol.scope = SymbolScope::GlobalImplicit;
     }
+    Ok(())
 }
 
 #[derive(Debug, Clone)]
@@ -277,6 +336,13 @@ enum SymbolUsage {
 struct SymbolTableBuilder {
     // Scope stack.
     tables: Vec<SymbolTable>,
+    /// For every scope on `tables`, the iteration variables if it's a comprehension.
+    comprehension_targets: Vec<Option<Vec<String>>>,
+    /// Whether `from __future__ import annotations` was seen, after which annotations are never
+    /// evaluated, so their names and scopes aren't part of the table.
+    future_annotations: bool,
+    /// The location of the statement being scanned.
+    location: Location,
 }
 
 /// Enum to indicate in what mode an expression
@@ -304,10 +370,12 @@ impl SymbolTableBuilder {
     fn enter_scope(&mut self, name: &str, typ: SymbolTableType, line_number: usize) {
         let table = SymbolTable::new(name.to_string(), typ, line_number);
         self.tables.push(table);
+        self.comprehension_targets.push(None);
     }
 
     /// Pop symbol table and add to sub table of parent table.
     fn leave_scope(&mut self) {
+        self.comprehension_targets.pop();
         let table = self.tables.pop().unwrap();
         self.tables.last_mut().unwrap().sub_tables.push(table);
     }
@@ -344,13 +412,33 @@ impl SymbolTableBuilder {
 
     fn scan_parameter_annotation(&mut self, parameter: &ast::Parameter) -> SymbolTableResult {
         if let Some(annotation) = &parameter.annotation {
-            self.scan_expression(&annotation, &ExpressionContext::Load)?;
+            self.scan_annotation(annotation)?;
         }
         Ok(())
     }
 
+    fn scan_annotation(&mut self, annotation: &ast::Expression) -> SymbolTableResult {
+        if self.future_annotations {
+            Ok(())
+        } else {
+            self.scan_expression(annotation, &ExpressionContext::Load)
+        }
+    }
+
     fn scan_statement(&mut self, statement: &ast::Statement) -> SymbolTableResult {
         use ast::StatementType::*;
+        self.location = statement.location.clone();
+        // The compiler checks that future imports come first.
+        if let ImportFrom {
+            level: 0,
+            module: Some(module),
+            names,
+        } = &statement.node
+        {
+            if module == "__future__" && names.iter().any(|name| name.symbol == "annotations") {
+                self.future_annotations = true;
+            }
+        }
         match &statement.node {
             Global { names } => {
                 for name in names {
@@ -373,7 +461,7 @@ impl SymbolTableBuilder {
                 self.scan_expressions(decorator_list, &ExpressionContext::Load)?;
                 self.register_name(name, SymbolUsage::Assigned)?;
                 if let Some(expression) = returns {
-                    self.scan_expression(expression, &ExpressionContext::Load)?;
+                    self.scan_annotation(expression)?;
                 }
                 self.enter_function(name, args, statement.location.row())?;
                 self.scan_statements(body)?;
@@ -460,7 +548,7 @@ impl SymbolTableBuilder {
             Delete { targets } => {
                 self.scan_expressions(targets, &ExpressionContext::Delete)?;
             }
-            Assign { targets, value } => {
+            Assign { targets, value, .. } => {
                 self.scan_expressions(targets, &ExpressionContext::Store)?;
                 self.scan_expression(value, &ExpressionContext::Load)?;
             }
@@ -474,7 +562,7 @@ impl SymbolTableBuilder {
                 value,
             } => {
                 self.scan_expression(target, &ExpressionContext::Store)?;
-                self.scan_expression(annotation, &ExpressionContext::Load)?;
+                self.scan_annotation(annotation)?;
                 if let Some(value) = value {
                     self.scan_expression(value, &ExpressionContext::Load)?;
                 }
@@ -551,12 +639,13 @@ impl SymbolTableBuilder {
             Compare { vals, .. } => {
                 self.scan_expressions(vals, context)?;
             }
+            // Assigning to an item or an attribute only loads the names in it
             Subscript { a, b } => {
-                self.scan_expression(a, context)?;
-                self.scan_expression(b, context)?;
+                self.scan_expression(a, &ExpressionContext::Load)?;
+                self.scan_expression(b, &ExpressionContext::Load)?;
             }
             Attribute { value, .. } => {
-                self.scan_expression(value, context)?;
+                self.scan_expression(value, &ExpressionContext::Load)?;
             }
             Dict { elements } => {
                 for (key, value) in elements {
@@ -609,32 +698,39 @@ impl SymbolTableBuilder {
                 // Register the passed argument to the generator function as the name ".0"
                 self.register_name(".0", SymbolUsage::Parameter)?;
 
-                match **kind {
-                    ast::ComprehensionKind::GeneratorExpression { ref element }
-                    | ast::ComprehensionKind::List { ref element }
-                    | ast::ComprehensionKind::Set { ref element } => {
-                        self.scan_expression(element, &ExpressionContext::Load)?;
-                    }
-                    ast::ComprehensionKind::Dict { ref key, ref value } => {
-                        self.scan_expression(&key, &ExpressionContext::Load)?;
-                        self.scan_expression(&value, &ExpressionContext::Load)?;
-                    }
+                let mut targets = vec![];
+                for generator in generators {
+                    target_names(&generator.target, &mut targets);
                 }
+                *self.comprehension_targets.last_mut().unwrap() = Some(targets);
 
+                // Scan in the order of compilation, which takes the tables of nested scopes in turn:
                 let mut is_first_generator = true;
                 for generator in generators {
-                    self.scan_expression(&generator.target, &ExpressionContext::Store)?;
                     if is_first_generator {
                         is_first_generator = false;
                     } else {
                         self.scan_expression(&generator.iter, &ExpressionContext::Load)?;
                     }
+                    self.scan_expression(&generator.target, &ExpressionContext::Store)?;
 
                     for if_expr in &generator.ifs {
                         self.scan_expression(if_expr, &ExpressionContext::Load)?;
                     }
                 }
 
+                match **kind {
+                    ast::ComprehensionKind::GeneratorExpression { ref element }
+                    | ast::ComprehensionKind::List { ref element }
+                    | ast::ComprehensionKind::Set { ref element } => {
+                        self.scan_expression(element, &ExpressionContext::Load)?;
+                    }
+                    ast::ComprehensionKind::Dict { ref key, ref value } => {
+                        self.scan_expression(&value, &ExpressionContext::Load)?;
+                        self.scan_expression(&key, &ExpressionContext::Load)?;
+                    }
+                }
+
                 self.leave_scope();
 
                 // The first iterable is passed as an argument into the created function:
@@ -674,6 +770,14 @@ impl SymbolTableBuilder {
                 self.scan_expression(body, &ExpressionContext::Load)?;
                 self.leave_scope();
             }
+            NamedExpression { target, value } => {
+                self.scan_expression(value, &ExpressionContext::Load)?;
+                if let Identifier { name } = &target.node {
+                    self.register_named_expression_target(name, &target.location)?;
+                } else {
+                    self.scan_expression(target, &ExpressionContext::Store)?;
+                }
+            }
             IfExpression { test, body, orelse } => {
                 self.scan_expression(test, &ExpressionContext::Load)?;
                 self.scan_expression(body, &ExpressionContext::Load)?;
@@ -683,6 +787,62 @@ impl SymbolTableBuilder {
         Ok(())
     }
 
+    /// Register the target of an assignment expression. Like in CPython, an assignment
+    /// expression in a comprehension binds its target in the scope around the comprehension.
+    fn register_named_expression_target(
+        &mut self,
+        name: &str,
+        location: &Location,
+    ) -> SymbolTableResult {
+        // Find the innermost scope that is not a comprehension:
+        let mut index = self.tables.len() - 1;
+        while let Some(targets) = &self.comprehension_targets[index] {
+            if targets.iter().any(|target| target == name) {
+                return Err(SymbolTableError {
+                    error: format!(
+                        "assignment expression cannot rebind comprehension iteration variable '{}'",
+                        name
+                    ),
+                    location: location.clone(),
+                });
+            }
+            index -= 1;
+        }
+        if index == self.tables.len() - 1 {
+            return self.register_name(name, SymbolUsage::Assigned);
+        }
+
+        let (enclosing, comprehensions) = self.tables[index..].split_first_mut().unwrap();
+        if enclosing.typ == SymbolTableType::Class {
+            return Err(SymbolTableError {
+                error:
+                    "assignment expression within a comprehension cannot be used in a class body"
+                        .to_string(),
+                location: location.clone(),
+            });
+        }
+        let symbol = enclosing
+            .symbols
+            .entry(name.to_string())
+            .or_insert_with(|| Symbol::new(name));
+        symbol.is_assigned = true;
+        let is_global =
+            enclosing.typ == SymbolTableType::Module || symbol.scope == SymbolScope::GlobalExplicit;
+        for table in comprehensions {
+            let symbol = table
+                .symbols
+                .entry(name.to_string())
+                .or_insert_with(|| Symbol::new(name));
+            if is_global {
+                symbol.scope = SymbolScope::GlobalExplicit;
+            } else {
+                symbol.is_nonlocal = true;
+            }
+            symbol.is_assigned = true;
+        }
+        Ok(())
+    }
+
     fn enter_function(
         &mut self,
         name: &str,
@@ -698,6 +858,7 @@ impl SymbolTableBuilder {
         }
 
         // Annotations are scanned in outer scope:
+        self.scan_parameters_annotations(&args.posonlyargs)?;
         self.scan_parameters_annotations(&args.args)?;
         self.scan_parameters_annotations(&args.kwonlyargs)?;
         if let ast::Varargs::Named(name) = &args.vararg {
@@ -710,6 +871,7 @@ impl SymbolTableBuilder {
         self.enter_scope(name, SymbolTableType::Function, line_number);
 
         // Fill scope with parameter names:
+        self.scan_parameters(&args.posonlyargs)?;
         self.scan_parameters(&args.args)?;
         self.scan_parameters(&args.kwonlyargs)?;
         if let ast::Varargs::Named(name) = &args.vararg {
@@ -724,8 +886,11 @@ impl SymbolTableBuilder {
     fn scan_string_group(&mut self, group: &ast::StringGroup) -> SymbolTableResult {
         match group {
             ast::StringGroup::Constant { .. } => {}
-            ast::StringGroup::FormattedValue { value, .. } => {
+            ast::StringGroup::FormattedValue { value, spec, .. } => {
                 self.scan_expression(value, &ExpressionContext::Load)?;
+                if let Some(spec) = spec {
+                    self.scan_string_group(spec)?;
+                }
             }
             ast::StringGroup::Joined { values } => {
                 for subgroup in values {
@@ -736,72 +901,54 @@ impl SymbolTableBuilder {
         Ok(())
     }
 
-    #[allow(clippy::single_match)]
     fn register_name(&mut self, name: &str, role: SymbolUsage) -> SymbolTableResult {
         let scope_depth = self.tables.len();
         let table = self.tables.last_mut().unwrap();
-        let location = Default::default();
-
-        // Some checks:
-        let containing = table.symbols.contains_key(name);
-        if containing {
-            // Role already set..
-            match role {
-                SymbolUsage::Global => {
-                    let symbol = table.symbols.get(name).unwrap();
-                    if let SymbolScope::Global = symbol.scope {
-                        // Ok
-                    } else {
-                        return Err(SymbolTableError {
-                            error: format!("name '{}' is used prior to global declaration", name),
-                            location,
-                        });
-                    }
-                }
-                SymbolUsage::Nonlocal => {
-                    return Err(SymbolTableError {
-                        error: format!("name '{}' is used prior to nonlocal declaration", name),
-                        location,
-                    })
-                }
-                _ => {
-                    // Ok?
-                }
-            }
-        }
+        let location = self.location.clone();
+        let error = |error| Err(SymbolTableError { error, location });
 
-        // Some more checks:
-        match role {
-            SymbolUsage::Nonlocal => {
-                if scope_depth < 2 {
-                    return Err(SymbolTableError {
-                        error: format!("cannot define nonlocal '{}' at top level.", name),
-                        location,
-                    });
-                }
-            }
-            _ => {
-                // Ok!
+        if let SymbolUsage::Nonlocal = role {
+            if scope_depth < 2 {
+                return error("nonlocal declaration not allowed at module level".to_string());
             }
         }
 
-        // Insert symbol when required:
-        if !containing {
-            let symbol = Symbol::new(name);
-            table.symbols.insert(name.to_string(), symbol);
-        }
+        let symbol = table
+            .symbols
+            .entry(name.to_string())
+            .or_insert_with(|| Symbol::new(name));
 
         // Set proper flags on symbol:
-        let symbol = table.symbols.get_mut(name).unwrap();
         match role {
-            SymbolUsage::Nonlocal => {
-                if let SymbolScope::Unknown = symbol.scope {
-                    symbol.scope = SymbolScope::Nonlocal;
+            SymbolUsage::Global | SymbolUsage::Nonlocal => {
+                let is_global = if let SymbolUsage::Global = role {
+                    true
+                } else {
+                    false
+                };
+                let declaration = if is_global { "global" } else { "nonlocal" };
+                let is_declared_global = symbol.scope == SymbolScope::GlobalExplicit;
+                if symbol.is_parameter {
+                    return error(format!("name '{}' is parameter and {}", name, declaration));
+                } else if symbol.is_nonlocal && is_global || is_declared_global && !is_global {
+                    return error(format!("name '{}' is nonlocal and global", name));
+                } else if symbol.is_nonlocal || is_declared_global {
+                    // Declared again
+                } else if symbol.is_assigned {
+                    return error(format!(
+                        "name '{}' is assigned to before {} declaration",
+                        name, declaration
+                    ));
+                } else if symbol.is_referenced {
+                    return error(format!(
+                        "name '{}' is used prior to {} declaration",
+                        name, declaration
+                    ));
+                }
+                if is_global {
+                    symbol.scope = SymbolScope::GlobalExplicit;
                 } else {
-                    return Err(SymbolTableError {
-                        error: format!("Symbol {} scope cannot be set to nonlocal, since its scope was already determined otherwise.", name),
-                        location,
-                    });
+                    symbol.is_nonlocal = true;
                 }
             }
             SymbolUsage::Parameter => {
@@ -810,18 +957,6 @@ impl SymbolTableBuilder {
             SymbolUsage::Assigned => {
                 symbol.is_assigned = true;
             }
-            SymbolUsage::Global => {
-                if let SymbolScope::Unknown = symbol.scope {
-                    symbol.scope = SymbolScope::Global;
-                } else if let SymbolScope::Global = symbol.scope {
-                    // Global scope can be set to global
-                } else {
-                    return Err(SymbolTableError {
-                        error: format!("Symbol {} scope cannot be set to global, since its scope was already determined otherwise.", name),
-                        location,
-                    });
-                }
-            }
             SymbolUsage::Used => {
                 symbol.is_referenced = true;
             }
@@ -830,3 +965,18 @@ impl SymbolTableBuilder {
         Ok(())
     }
 }
+
+/// Collect the names that are assigned by the assignment target `target`.
+fn target_names(target: &ast::Expression, names: &mut Vec<String>) {
+    use ast::ExpressionType::*;
+    match &target.node {
+        Identifier { name } => names.push(name.clone()),
+        Tuple { elements } | List { elements } => {
+            for element in elements {
+                target_names(element, names);
+            }
+        }
+        Starred { value } => target_names(value, names),
+        _ => {}
+    }
+}
@@ -0,0 +1,685 @@
+//! Turn expressions back into source code, for the annotations that `from __future__ import
+//! annotations` keeps as strings.
+//!
+//! Inspirational code:
+//!   https://github.com/python/cpython/blob/master/Python/ast_unparse.c
+
+use rustpython_parser::ast;
+use std::fmt::Write;
+
+// The precedence levels of the expression grammar, from loosest to tightest. An expression is
+// parenthesized when it's unparsed at a level it binds looser than.
+const PR_TUPLE: u8 = 0;
+const PR_TEST: u8 = 1; // `if`-`else` and `lambda`
+const PR_OR: u8 = 2;
+const PR_AND: u8 = 3;
+const PR_NOT: u8 = 4;
+const PR_CMP: u8 = 5;
+const PR_EXPR: u8 = 6;
+const PR_BOR: u8 = PR_EXPR;
+const PR_BXOR: u8 = 7;
+const PR_BAND: u8 = 8;
+const PR_SHIFT: u8 = 9;
+const PR_ARITH: u8 = 10;
+const PR_TERM: u8 = 11;
+const PR_FACTOR: u8 = 12;
+const PR_POWER: u8 = 13;
+const PR_AWAIT: u8 = 14;
+const PR_ATOM: u8 = 15;
+
+/// The source of `expression`, like CPython writes it for a postponed annotation.
+pub fn unparse_expression(expression: &ast::Expression) -> String {
+    let mut unparser = Unparser {
+        source: String::new(),
+    };
+    unparser.expression(expression, PR_TEST);
+    unparser.source
+}
+
+struct Unparser {
+    source: String,
+}
+
+impl Unparser {
+    fn push(&mut self, text: &str) {
+        self.source.push_str(text);
+    }
+
+    fn push_if(&mut self, condition: bool, text: &str) {
+        if condition {
+            self.push(text);
+        }
+    }
+
+    fn expressions(&mut self, expressions: &[ast::Expression], level: u8) {
+        for (i, expression) in expressions.iter().enumerate() {
+            self.push_if(i > 0, ", ");
+            self.expression(expression, level);
+        }
+    }
+
+    fn expression(&mut self, expression: &ast::Expression, level: u8) {
+        use ast::ExpressionType::*;
+        match &expression.node {
+            BoolOp { op, values } => {
+                let (operator, precedence) = match op {
+                    ast::BooleanOperator::And => (" and ", PR_AND),
+                    ast::BooleanOperator::Or => (" or ", PR_OR),
+                };
+                self.push_if(level > precedence, "(");
+                for (i, value) in values.iter().enumerate() {
+                    self.push_if(i > 0, operator);
+                    self.expression(value, precedence + 1);
+                }
+                self.push_if(level > precedence, ")");
+            }
+            Binop { a, op, b } => {
+                let (operator, precedence) = binary_operator(op);
+                let right_associative = *op == ast::Operator::Pow;
+                self.push_if(level > precedence, "(");
+                self.expression(a, precedence + right_associative as u8);
+                self.push(operator);
+                self.expression(b, precedence + !right_associative as u8);
+                self.push_if(level > precedence, ")");
+            }
+            Unop { op, a } => {
+                let (operator, precedence) = match op {
+                    ast::UnaryOperator::Not => ("not ", PR_NOT),
+                    ast::UnaryOperator::Inv => ("~", PR_FACTOR),
+                    ast::UnaryOperator::Pos => ("+", PR_FACTOR),
+                    ast::UnaryOperator::Neg => ("-", PR_FACTOR),
+                };
+                self.push_if(level > precedence, "(");
+                self.push(operator);
+                self.expression(a, precedence);
+                self.push_if(level > precedence, ")");
+            }
+            Lambda { args, body } => {
+                self.push_if(level > PR_TEST, "(");
+                self.push("lambda");
+                if has_parameters(args) {
+                    self.push(" ");
+                    self.parameters(args);
+                }
+                self.push(": ");
+                self.expression(body, PR_TEST);
+                self.push_if(level > PR_TEST, ")");
+            }
+            IfExpression { test, body, orelse } => {
+                self.push_if(level > PR_TEST, "(");
+                self.expression(body, PR_TEST + 1);
+                self.push(" if ");
+                self.expression(test, PR_TEST + 1);
+                self.push(" else ");
+                self.expression(orelse, PR_TEST);
+                self.push_if(level > PR_TEST, ")");
+            }
+            NamedExpression { target, value } => {
+                self.push_if(level > PR_TUPLE, "(");
+                self.expression(target, PR_ATOM);
+                self.push(" := ");
+                self.expression(value, PR_ATOM);
+                self.push_if(level > PR_TUPLE, ")");
+            }
+            Compare { vals, ops } => {
+                self.push_if(level > PR_CMP, "(");
+                self.expression(&vals[0], PR_CMP + 1);
+                for (op, value) in ops.iter().zip(&vals[1..]) {
+                    self.push(comparison(op));
+                    self.expression(value, PR_CMP + 1);
+                }
+                self.push_if(level > PR_CMP, ")");
+            }
+            Await { value } => {
+                self.push_if(level > PR_AWAIT, "(");
+                self.push("await ");
+                self.expression(value, PR_ATOM);
+                self.push_if(level > PR_AWAIT, ")");
+            }
+            Yield { value } => {
+                self.push("(yield");
+                if let Some(value) = value {
+                    self.push(" ");
+                    self.expression(value, PR_TEST);
+                }
+                self.push(")");
+            }
+            YieldFrom { value } => {
+                self.push("(yield from ");
+                self.expression(value, PR_TEST);
+                self.push(")");
+            }
+            Attribute { value, name } => {
+                self.expression(value, PR_ATOM);
+                // `1.real` would be read as a float followed by a name.
+                if let Number {
+                    value: ast::Number::Integer { .. },
+                } = &value.node
+                {
+                    self.push(" ");
+                }
+                self.push(".");
+                self.push(name);
+            }
+            Subscript { a, b } => {
+                self.expression(a, PR_ATOM);
+                self.push("[");
+                match &b.node {
+                    Tuple { elements } if !elements.is_empty() => {
+                        for (i, element) in elements.iter().enumerate() {
+                            self.push_if(i > 0, ", ");
+                            self.subscript(element);
+                        }
+                        self.push_if(elements.len() == 1, ",");
+                    }
+                    _ => self.subscript(b),
+                }
+                self.push("]");
+            }
+            Slice { .. } => self.subscript(expression),
+            Call {
+                function,
+                args,
+                keywords,
+            } => {
+                self.expression(function, PR_ATOM);
+                if let [argument] = args.as_slice() {
+                    if let (Comprehension { kind, .. }, true) =
+                        (&argument.node, keywords.is_empty())
+                    {
+                        if let ast::ComprehensionKind::GeneratorExpression { .. } = **kind {
+                            // The generator's own parentheses are enough.
+                            self.expression(argument, PR_TEST);
+                            return;
+                        }
+                    }
+                }
+                self.push("(");
+                self.expressions(args, PR_TEST);
+                for (i, keyword) in keywords.iter().enumerate() {
+                    self.push_if(i > 0 || !args.is_empty(), ", ");
+                    if let Some(name) = &keyword.name {
+                        self.push(name);
+                        self.push("=");
+                    } else {
+                        self.push("**");
+                    }
+                    self.expression(&keyword.value, PR_TEST);
+                }
+                self.push(")");
+            }
+            Starred { value } => {
+                self.push("*");
+                self.expression(value, PR_EXPR);
+            }
+            List { elements } => {
+                self.push("[");
+                self.expressions(elements, PR_TEST);
+                self.push("]");
+            }
+            Tuple { elements } => {
+                if elements.is_empty() {
+                    self.push("()");
+                } else {
+                    self.push_if(level > PR_TUPLE, "(");
+                    self.expressions(elements, PR_TEST);
+                    self.push_if(elements.len() == 1, ",");
+                    self.push_if(level > PR_TUPLE, ")");
+                }
+            }
+            Set { elements } => {
+                self.push("{");
+                self.expressions(elements, PR_TEST);
+                self.push("}");
+            }
+            Dict { elements } => {
+                self.push("{");
+                for (i, (key, value)) in elements.iter().enumerate() {
+                    self.push_if(i > 0, ", ");
+                    if let Some(key) = key {
+                        self.expression(key, PR_TEST);
+                        self.push(": ");
+                        self.expression(value, PR_TEST);
+                    } else {
+                        self.push("**");
+                        self.expression(value, PR_EXPR);
+                    }
+                }
+                self.push("}");
+            }
+            Comprehension { kind, generators } => {
+                let close = match **kind {
+                    ast::ComprehensionKind::GeneratorExpression { ref element } => {
+                        self.push("(");
+                        self.expression(element, PR_TEST);
+                        ")"
+                    }
+                    ast::ComprehensionKind::List { ref element } => {
+                        self.push("[");
+                        self.expression(element, PR_TEST);
+                        "]"
+                    }
+                    ast::ComprehensionKind::Set { ref element } => {
+                        self.push("{");
+                        self.expression(element, PR_TEST);
+                        "}"
+                    }
+                    ast::ComprehensionKind::Dict { ref key, ref value } => {
+                        self.push("{");
+                        self.expression(key, PR_TEST);
+                        self.push(": ");
+                        self.expression(value, PR_TEST);
+                        "}"
+                    }
+                };
+                for generator in generators {
+                    self.push(if generator.is_async {
+                        " async for "
+                    } else {
+                        " for "
+                    });
+                    self.expression(&generator.target, PR_TUPLE);
+                    self.push(" in ");
+                    self.expression(&generator.iter, PR_TEST + 1);
+                    for condition in &generator.ifs {
+                        self.push(" if ");
+                        self.expression(condition, PR_TEST + 1);
+                    }
+                }
+                self.push(close);
+            }
+            Number { value } => self.number(value),
+            String { value } => self.string(value),
+            Bytes { value } => self.push(&bytes_repr(value)),
+            Identifier { name } => self.push(name),
+            True => self.push("True"),
+            False => self.push("False"),
+            None => self.push("None"),
+            Ellipsis => self.push("..."),
+        }
+    }
+
+    /// An index or slice between the brackets of a subscript.
+    fn subscript(&mut self, expression: &ast::Expression) {
+        if let ast::ExpressionType::Slice { elements } = &expression.node {
+            // The parser fills in the missing parts of a slice with `None`s.
+            let part = |unparser: &mut Self, element: &ast::Expression| {
+                if element.node != ast::ExpressionType::None {
+                    unparser.expression(element, PR_TEST);
+                }
+            };
+            part(self, &elements[0]);
+            self.push(":");
+            part(self, &elements[1]);
+            if let Some(step) = elements.get(2) {
+                if step.node != ast::ExpressionType::None {
+                    self.push(":");
+                    part(self, step);
+                }
+            }
+        } else {
+            self.expression(expression, PR_TUPLE);
+        }
+    }
+
+    fn parameters(&mut self, parameters: &ast::Parameters) {
+        let mut first = true;
+        let mut separator = |unparser: &mut Self| {
+            if !first {
+                unparser.push(", ");
+            }
+            first = false;
+        };
+
+        let positional = parameters.posonlyargs.len() + parameters.args.len();
+        let first_default = positional - parameters.defaults.len();
+        let positional_parameters = parameters.posonlyargs.iter().chain(&parameters.args);
+        for (i, parameter) in positional_parameters.enumerate() {
+            separator(self);
+            self.parameter(parameter);
+            if i >= first_default {
+                self.push("=");
+                self.expression(&parameters.defaults[i - first_default], PR_TEST);
+            }
+            if i + 1 == parameters.posonlyargs.len() {
+                self.push(", /");
+            }
+        }
+
+        match &parameters.vararg {
+            ast::Varargs::Named(parameter) => {
+                separator(self);
+                self.push("*");
+                self.parameter(parameter);
+            }
+            ast::Varargs::Unnamed => {
+                separator(self);
+                self.push("*");
+            }
+            ast::Varargs::None => {}
+        }
+
+        for (parameter, default) in parameters.kwonlyargs.iter().zip(&parameters.kw_defaults) {
+            separator(self);
+            self.parameter(parameter);
+            if let Some(default) = default {
+                self.push("=");
+                self.expression(default, PR_TEST);
+            }
+        }
+
+        if let ast::Varargs::Named(parameter) = &parameters.kwarg {
+            separator(self);
+            self.push("**");
+            self.parameter(parameter);
+        }
+    }
+
+    fn parameter(&mut self, parameter: &ast::Parameter) {
+        self.push(&parameter.arg);
+        if let Some(annotation) = &parameter.annotation {
+            self.push(": ");
+            self.expression(annotation, PR_TEST);
+        }
+    }
+
+    fn number(&mut self, number: &ast::Number) {
+        match number {
+            ast::Number::Integer { value } => self.push(&value.to_string()),
+            ast::Number::Float { value } => self.push(&float_repr(*value, true)),
+            ast::Number::Complex { real, imag } => {
+                if *real == 0.0 {
+                    self.push(&float_repr(*imag, false));
+                    self.push("j");
+                } else {
+                    let sign = if *imag < 0.0 { "-" } else { "+" };
+                    let real = float_repr(*real, false);
+                    let imag = float_repr(imag.abs(), false);
+                    write!(self.source, "({}{}{}j)", real, sign, imag).unwrap();
+                }
+            }
+        }
+    }
+
+    fn string(&mut self, string: &ast::StringGroup) {
+        match constant_string(string) {
+            Some(value) => self.push(&string_repr(&value)),
+            None => {
+                let mut body = Unparser {
+                    source: String::new(),
+                };
+                body.formatted_string(string);
+                self.push("f");
+                self.push(&string_repr(&body.source));
+            }
+        }
+    }
+
+    /// The contents of an f-string, between its quotes.
+    fn formatted_string(&mut self, string: &ast::StringGroup) {
+        match string {
+            ast::StringGroup::Constant { value } => {
+                self.push(&value.replace('{', "{{").replace('}', "}}"))
+            }
+            ast::StringGroup::FormattedValue {
+                value,
+                conversion,
+                spec,
+            } => {
+                let mut expression = Unparser {
+                    source: String::new(),
+                };
+                expression.expression(value, PR_TEST + 1);
+                // `{{` would be read as an escaped brace.
+                self.push(if expression.source.starts_with('{') {
+                    "{ "
+                } else {
+                    "{"
+                });
+                self.push(&expression.source);
+                match conversion {
+                    Some(ast::ConversionFlag::Str) => self.push("!s"),
+                    Some(ast::ConversionFlag::Ascii) => self.push("!a"),
+                    Some(ast::ConversionFlag::Repr) => self.push("!r"),
+                    None => {}
+                }
+                if let Some(spec) = spec {
+                    self.push(":");
+                    self.formatted_spec(spec);
+                }
+                self.push("}");
+            }
+            ast::StringGroup::Joined { values } => {
+                for value in values {
+                    self.formatted_string(value);
+                }
+            }
+        }
+    }
+
+    /// A format spec, where braces always delimit a nested replacement field.
+    fn formatted_spec(&mut self, spec: &ast::StringGroup) {
+        match spec {
+            ast::StringGroup::Constant { value } => self.push(value),
+            ast::StringGroup::FormattedValue { .. } => self.formatted_string(spec),
+            ast::StringGroup::Joined { values } => {
+                for value in values {
+                    self.formatted_spec(value);
+                }
+            }
+        }
+    }
+}
+
+fn has_parameters(parameters: &ast::Parameters) -> bool {
+    !parameters.posonlyargs.is_empty()
+        || !parameters.args.is_empty()
+        || !parameters.kwonlyargs.is_empty()
+        || parameters.vararg != ast::Varargs::None
+        || parameters.kwarg != ast::Varargs::None
+}
+
+fn binary_operator(op: &ast::Operator) -> (&'static str, u8) {
+    use ast::Operator::*;
+    match op {
+        Add => (" + ", PR_ARITH),
+        Sub => (" - ", PR_ARITH),
+        Mult => (" * ", PR_TERM),
+        MatMult => (" @ ", PR_TERM),
+        Div => (" / ", PR_TERM),
+        Mod => (" % ", PR_TERM),
+        FloorDiv => (" // ", PR_TERM),
+        Pow => (" ** ", PR_POWER),
+        LShift => (" << ", PR_SHIFT),
+        RShift => (" >> ", PR_SHIFT),
+        BitOr => (" | ", PR_BOR),
+        BitXor => (" ^ ", PR_BXOR),
+        BitAnd => (" & ", PR_BAND),
+    }
+}
+
+fn comparison(op: &ast::Comparison) -> &'static str {
+    use ast::Comparison::*;
+    match op {
+        Equal => " == ",
+        NotEqual => " != ",
+        Less => " < ",
+        LessOrEqual => " <= ",
+        Greater => " > ",
+        GreaterOrEqual => " >= ",
+        In => " in ",
+        NotIn => " not in ",
+        Is => " is ",
+        IsNot => " is not ",
+    }
+}
+
+fn constant_string(string: &ast::StringGroup) -> Option<String> {
+    match string {
+        ast::StringGroup::Constant { value } => Some(value.clone()),
+        ast::StringGroup::FormattedValue { .. } => None,
+        ast::StringGroup::Joined { values } => values.iter().map(constant_string).collect(),
+    }
+}
+
+/// `repr(value)` of a float, which ends in `.0` if it's integral and `force_point` is given, like
+/// in Python's `repr` of floats but not in that of complex numbers.
+fn float_repr(value: f64, force_point: bool) -> String {
+    if value.is_infinite() {
+        // Like CPython, spell infinity as a literal that overflows to it.
+        return if value > 0.0 { "1e309" } else { "-1e309" }.to_owned();
+    }
+    if value.is_nan() {
+        return "nan".to_owned();
+    }
+    // Rust writes the shortest digits that round-trip, like Python, but Python switches to
+    // scientific notation at different magnitudes.
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific.split_at(scientific.find('e').unwrap());
+    let exponent: i32 = exponent[1..].parse().unwrap();
+    if -4 <= exponent && exponent < 16 {
+        let mut text = value.to_string();
+        if force_point && !text.contains('.') {
+            text.push_str(".0");
+        }
+        text
+    } else {
+        let sign = if exponent < 0 { "-" } else { "+" };
+        format!("{}e{}{:02}", mantissa, sign, exponent.abs())
+    }
+}
+
+fn string_repr(value: &str) -> String {
+    let quote = if value.contains('\'') && !value.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    let mut repr = String::with_capacity(value.len() + 2);
+    repr.push(quote);
+    for c in value.chars() {
+        match c {
+            '\\' => repr.push_str("\\\\"),
+            '\n' => repr.push_str("\\n"),
+            '\r' => repr.push_str("\\r"),
+            '\t' => repr.push_str("\\t"),
+            c if c == quote => {
+                repr.push('\\');
+                repr.push(c);
+            }
+            c if c.is_control() => {
+                let code = c as u32;
+                if code < 0x100 {
+                    write!(repr, "\\x{:02x}", code).unwrap();
+                } else if code < 0x10000 {
+                    write!(repr, "\\u{:04x}", code).unwrap();
+                } else {
+                    write!(repr, "\\U{:08x}", code).unwrap();
+                }
+            }
+            c => repr.push(c),
+        }
+    }
+    repr.push(quote);
+    repr
+}
+
+fn bytes_repr(value: &[u8]) -> String {
+    let quote = if value.contains(&b'\'') && !value.contains(&b'"') {
+        b'"'
+    } else {
+        b'\''
+    };
+    let mut repr = String::with_capacity(value.len() + 3);
+    repr.push('b');
+    repr.push(char::from(quote));
+    for &byte in value {
+        match byte {
+            b'\\' => repr.push_str("\\\\"),
+            b'\n' => repr.push_str("\\n"),
+            b'\r' => repr.push_str("\\r"),
+            b'\t' => repr.push_str("\\t"),
+            byte if byte == quote => {
+                repr.push('\\');
+                repr.push(char::from(byte));
+            }
+            0x20..=0x7e => repr.push(char::from(byte)),
+            byte => write!(repr, "\\x{:02x}", byte).unwrap(),
+        }
+    }
+    repr.push(char::from(quote));
+    repr
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustpython_parser::parser;
+
+    fn roundtrip(source: &str) -> String {
+        let statements = parser::parse_statement(source).unwrap();
+        match &statements[0].node {
+            ast::StatementType::Expression { expression } => unparse_expression(expression),
+            node => panic!("not an expression: {:?}", node),
+        }
+    }
+
+    #[test]
+    fn test_unparse_roundtrips() {
+        for source in &[
+            "List[int]",
+            "Dict[str, Tuple[int, ...]]",
+            "Tuple[()]",
+            "x[1:2, ::3]",
+            "x[a:b:c]",
+            "Callable[[int, str], None]",
+            "'forward' | None",
+            "a and (b or c)",
+            "not a == b",
+            "(a + b) * c - d / e",
+            "(a ** b) ** c",
+            "-(a + b)",
+            "await x",
+            "f(a, *args, b=1, **kwargs)",
+            "f(x for x in y)",
+            "[x for x in y if x async for z in x]",
+            "{1, 2}",
+            "{'a': 1, **rest}",
+            "(yield x)",
+            "lambda: 0",
+            "lambda a, /, b=1, *c, d, e=2, **f: a",
+            "a if b else c",
+            "(a if b else c).d",
+            "1 .real",
+            "1.5.real",
+            "(x := 1)",
+            "b'bytes\\x00'",
+            "\"it's\"",
+            "'tab\\there'",
+            "f'{x!r:>10} {{braces}}'",
+            "f'{ {1: 2}[1]}'",
+            "f'{x:{width}.{prec}f} {y!a:{ {1}}}'",
+        ] {
+            assert_eq!(roundtrip(source), *source);
+        }
+    }
+
+    #[test]
+    fn test_unparse_normalizes() {
+        assert_eq!(roundtrip("(a)"), "a");
+        assert_eq!(roundtrip("a ** -b"), "a ** (-b)");
+        assert_eq!(
+            roundtrip("{k: v for (k, v) in items}"),
+            "{k: v for k, v in items}"
+        );
+        assert_eq!(roundtrip("a , b"), "(a, b)");
+        assert_eq!(roundtrip("'con' \"cat\""), "'concat'");
+        assert_eq!(roundtrip("0x10"), "16");
+        assert_eq!(roundtrip("1e100"), "1e+100");
+        assert_eq!(roundtrip("0.00001"), "1e-05");
+        assert_eq!(roundtrip("1e16"), "1e+16");
+        assert_eq!(roundtrip("100.0"), "100.0");
+        assert_eq!(roundtrip("2J"), "2j");
+        assert_eq!(roundtrip("1e400"), "1e309");
+    }
+}
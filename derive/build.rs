@@ -0,0 +1,49 @@
+use siphasher::sip128::{Hasher128, SipHasher13};
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+
+/// The crates that decide what a source compiles to, relative to this one. The cache of
+/// `py_compile_bytecode!` keys its entries by a hash of their files, so that the code objects
+/// compiled by another version of them aren't reused. Outside of the workspace, like when this
+/// crate is built from crates.io, they aren't there, and the version of this crate stands for
+/// them.
+const COMPILER_CRATES: &[&str] = &["../compiler", "../parser", "../bytecode"];
+
+fn main() {
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    for dir in COMPILER_CRATES {
+        for part in &["Cargo.toml", "src"] {
+            let path = Path::new(dir).join(part);
+            println!("cargo:rerun-if-changed={}", path.display());
+            let mut files = Vec::new();
+            find_files(&path, &mut files);
+            files.sort();
+            for file in files {
+                let contents = fs::read(&file).unwrap_or_default();
+                let name = file.to_string_lossy();
+                hasher.write_usize(name.len());
+                hasher.write(name.as_bytes());
+                hasher.write_usize(contents.len());
+                hasher.write(&contents);
+            }
+        }
+    }
+    println!(
+        "cargo:rustc-env=RUSTPYTHON_COMPILER_HASH={:032x}",
+        u128::from_le_bytes(hasher.finish128().as_bytes())
+    );
+}
+
+/// The files at `path`, which is a file or a directory to search recursively.
+fn find_files(path: &Path, files: &mut Vec<PathBuf>) {
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                find_files(&entry.path(), files);
+            }
+        }
+        Err(_) if path.is_file() => files.push(path.to_owned()),
+        Err(_) => {}
+    }
+}
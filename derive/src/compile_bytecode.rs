@@ -5,154 +5,633 @@
 //!     source = "python_source_code",
 //!     // or
 //!     file = "file/path/relative/to/$CARGO_MANIFEST_DIR",
+//!     // or, where `.pylink` files can stand for files and directories elsewhere; see
+//!     // `frozen::find_modules` in the compiler
+//!     dir = "package/dir/relative/to/$CARGO_MANIFEST_DIR",
+//!     // or, the output directory of `rustpython-compile`; this may also start with `$OUT_DIR`
+//!     precompiled_dir = "bytecode/dir/relative/to/$CARGO_MANIFEST_DIR",
+//!     // or, the `.py` files of a zip archive, named like those of a `dir`
+//!     zip = "archive/relative/to/$CARGO_MANIFEST_DIR.zip",
+//!     // or, a module compiled by CPython 3.7, whose bytecode is translated to RustPython's
+//!     pyc = "module/relative/to/$CARGO_MANIFEST_DIR.cpython-37.pyc",
+//!
+//!     // with `zip`: only take the entries below this directory of the archive
+//!     zip_prefix = "purelib/",
 //!
 //!     // the mode to compile the code in
 //!     mode = "exec", // or "eval" or "single"
 //!     // the path put into the CodeObject, defaults to "frozen"
 //!     module_name = "frozen",
+//!     // reuse the code objects compiled by earlier expansions from unchanged sources; the
+//!     // cache is kept in $OUT_DIR, or $CARGO_TARGET_DIR if there is no build script, or else
+//!     // the target directory of the workspace; defaults to true for `dir` and `zip`
+//!     cache = true,
+//!     // the optimize level to compile with, like `-O` given that many times to CPython: 1
+//!     // leaves out asserts and 2 also docstrings; defaults to 0
+//!     optimize = 2,
+//!     // with `dir` or `zip`: only take the modules matching a glob relative to the dir (or the
+//!     // `zip_prefix`); may be a list of globs, and be given more than once
+//!     include = "**/*.py",
+//!     // with `dir` or `zip`: leave out the modules and packages matching a glob, like `include`
+//!     exclude = ["**/test", "**/_bootstrap*.py"],
+//!     // with `dir`: embed the data files matching a glob relative to the dir as resources of
+//!     // their packages; may be given more than once
+//!     include_resources = "pkg/*.txt",
+//!     // deflate the code objects in the binary, which makes them a lot smaller but slower to
+//!     // load; not available on wasm
+//!     compress = true,
+//!     // or, encode them compactly, which makes them smaller but has to be decoded
+//!     compact = true,
+//!     // with `file`, `source`, `dir` or `zip`: embed the sources of the modules as well, for
+//!     // tracebacks and `linecache` to show their lines; deflated too with `compress`
+//!     embed_source = true,
+//!     // leave out the modules whose source path (see below) matches a glob, or is below a
+//!     // directory that does, unless the predicate holds where the macro is expanded; may be a
+//!     // list of globs, and a module matched by several predicates needs all of them
+//!     cfg(windows) => ["ntpath.py", "nt"],
+//!     cfg(not(windows)) => "posixpath.py",
 //! )
 //! ```
+//!
+//! Besides string literals, the string arguments may be `concat!`, `env!` and `include_str!`,
+//! nested in each other as in `file = concat!(env!("OUT_DIR"), "/gen.py")`. They're evaluated
+//! when the macro is expanded; `include_str!` paths are relative to `$CARGO_MANIFEST_DIR`, like
+//! those of `file` and `dir`, since a proc macro can't tell which file it's expanded in.
+//!
+//! The modules record where they were compiled from in `FrozenModule::source_path`: the file
+//! name of a `file` or `pyc`, the path of each module below a `dir` (or the directory `rustpython-compile`
+//! was given, or the `zip_prefix` of a `zip`), and nothing for `source`.
+//!
+//! The directories below a `dir`, `precompiled_dir` or `zip` that have modules but no
+//! `__init__.py` are frozen as namespace packages (PEP 420), with no source path, so that their
+//! modules can be imported like they would be from `sys.path`.
+//!
+//! A `pyc` isn't compiled again, so `mode`, `cache` and `optimize` don't apply to it. Its
+//! translation fails on the few things RustPython's bytecode can't express, like `async for`;
+//! see `rustpython_bytecode::cpython`.
+//!
+//! The code objects are laid out as static data, as `FrozenCode::Static`, which the code object
+//! of a module borrows its instructions from when it's imported, so importing decodes nothing;
+//! see `rustpython_bytecode::static_code`. With `compact`, they're serialized instead, and the
+//! modules of one expansion share the table of the strings of their code, so the names and
+//! constants many of them use are only in the binary once; see `rustpython_bytecode::compact`.
+//!
+//! Resources are read by `importlib.resources` through the frozen importer; see
+//! `frozen::find_resources` in the compiler for the glob syntax. They're `include_bytes!`ed into
+//! the binary, so they're all in memory while it runs, however large they are.
 
+use crate::compile_cache::CompileCache;
+use crate::static_code;
+use crate::zip_archive::ZipArchive;
 use crate::{extract_spans, Diagnostic};
-use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::quote;
-use rustpython_bytecode::bytecode::{CodeObject, FrozenModule};
-use rustpython_compiler::compile;
-use std::collections::HashMap;
+use proc_macro2::{Delimiter, Group, Span, TokenStream as TokenStream2};
+use quote::{quote, ToTokens};
+use rustpython_bytecode::bytecode::{CodeObject, FrozenModule, FrozenSource, FORMAT_VERSION};
+use rustpython_bytecode::compact::{self, StringTableBuilder};
+use rustpython_bytecode::cpython;
+use rustpython_compiler::error::CompileError;
+use rustpython_compiler::frozen::{self, Manifest, ModuleFilter, SourceResource};
+use rustpython_compiler::{compile, encoding};
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use syn::parse::{Parse, ParseStream, Result as ParseResult};
-use syn::{self, parse2, Lit, LitByteStr, LitStr, Meta, Token};
+use syn::parse::{Parse, ParseStream, Parser, Result as ParseResult};
+use syn::punctuated::Punctuated;
+use syn::{
+    self, parenthesized, parse2, token, Expr, ExprLit, Ident, Lit, LitByteStr, LitStr, Token,
+};
 
 enum CompilationSourceKind {
     File(PathBuf),
     SourceCode(String),
     Dir(PathBuf),
+    PrecompiledDir(PathBuf),
+    Zip(PathBuf),
+    Pyc(PathBuf),
 }
 
 struct CompilationSource {
     kind: CompilationSourceKind,
     span: (Span, Span),
+    cache: Option<CompileCache>,
+    optimize: u8,
+    filter: ModuleFilter,
+    zip_prefix: String,
+    embed_source: bool,
 }
 
 impl CompilationSource {
+    /// What to embed of `source` for `FrozenModule::source`.
+    fn embedded_source(&self, source: &str) -> Option<FrozenSource> {
+        if self.embed_source {
+            Some(FrozenSource::Text(source.to_owned().into()))
+        } else {
+            None
+        }
+    }
+
+    /// Compile `source`, which was read from `origin`; that's what compile errors point to. All
+    /// the syntax errors in the source are reported, not only the first one.
     fn compile_string(
         &self,
         source: &str,
         mode: compile::Mode,
         module_name: String,
+        origin: &str,
     ) -> Result<CodeObject, Diagnostic> {
-        compile::compile(source, mode, module_name, 0)
-            .map_err(|err| Diagnostic::spans_error(self.span, format!("Compile error: {}", err)))
+        let compile = || {
+            compile::compile_recovering(source, mode, module_name.clone(), self.optimize).map_err(
+                |errors| {
+                    let diagnostics = errors
+                        .iter()
+                        .map(|err| {
+                            let message = compile_error_message(origin, source, err);
+                            Diagnostic::spans_error(self.span, message)
+                        })
+                        .collect();
+                    // There's always at least one error
+                    Diagnostic::from_vec(diagnostics).unwrap_err()
+                },
+            )
+        };
+        match &self.cache {
+            Some(cache) => cache.get_or_compile(source, mode, &module_name, self.optimize, compile),
+            None => compile(),
+        }
+    }
+
+    /// Read a source file, decoding it according to its byte order mark or coding declaration.
+    fn read_source(&self, path: &Path) -> Result<String, Diagnostic> {
+        let bytes = fs::read(path).map_err(|err| {
+            Diagnostic::spans_error(self.span, format!("Error reading file {:?}: {}", path, err))
+        })?;
+        encoding::decode_source(&bytes).map_err(|err| {
+            Diagnostic::spans_error(
+                self.span,
+                format!("Error decoding file {:?}: {}", path, err),
+            )
+        })
     }
 
+    /// Compile the modules, and return them along with the source files they were compiled from.
     fn compile(
         &self,
         mode: compile::Mode,
         module_name: String,
-    ) -> Result<HashMap<String, FrozenModule>, Diagnostic> {
+    ) -> Result<(BTreeMap<String, FrozenModule>, Vec<PathBuf>), Diagnostic> {
         Ok(match &self.kind {
             CompilationSourceKind::File(rel_path) => {
-                let mut path = PathBuf::from(
-                    env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not present"),
-                );
-                path.push(rel_path);
-                let source = fs::read_to_string(&path).map_err(|err| {
-                    Diagnostic::spans_error(
-                        self.span,
-                        format!("Error reading file {:?}: {}", path, err),
-                    )
-                })?;
-                hashmap! {
+                let path = resolve_path(rel_path);
+                let source = self.read_source(&path)?;
+                let file_name = path.file_name().and_then(|file_name| file_name.to_str());
+                let origin = path.display().to_string();
+                let code_map = btreemap! {
                     module_name.clone() => FrozenModule {
-                        code: self.compile_string(&source, mode, module_name.clone())?,
+                        code: self.compile_string(&source, mode, module_name.clone(), &origin)?.into(),
                         package: false,
+                        namespace: false,
+                        source: self.embedded_source(&source),
+                        source_path: file_name.map(ToOwned::to_owned),
+                        resources: HashMap::new(),
                     },
-                }
+                };
+                (code_map, vec![path])
             }
             CompilationSourceKind::SourceCode(code) => {
-                hashmap! {
+                let code_map = btreemap! {
                     module_name.clone() => FrozenModule {
-                        code: self.compile_string(code, mode, module_name.clone())?,
+                        code: self.compile_string(code, mode, module_name.clone(), "<source>")?.into(),
                         package: false,
+                        namespace: false,
+                        source: self.embedded_source(code),
+                        source_path: None,
+                        resources: HashMap::new(),
                     },
-                }
+                };
+                (code_map, Vec::new())
             }
             CompilationSourceKind::Dir(rel_path) => {
-                let mut path = PathBuf::from(
-                    env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not present"),
-                );
-                path.push(rel_path);
-                self.compile_dir(&path, String::new(), mode)?
+                let (mut code_map, source_files) =
+                    self.compile_dir(&resolve_path(rel_path), mode)?;
+                add_namespace_packages(&mut code_map);
+                (code_map, source_files)
+            }
+            CompilationSourceKind::PrecompiledDir(rel_path) => {
+                let mut code_map = self.load_precompiled_dir(&resolve_path(rel_path))?;
+                add_namespace_packages(&mut code_map);
+                (code_map, Vec::new())
+            }
+            CompilationSourceKind::Zip(rel_path) => {
+                let path = resolve_path(rel_path);
+                let mut code_map = self.compile_zip(&path, mode)?;
+                add_namespace_packages(&mut code_map);
+                (code_map, vec![path])
+            }
+            CompilationSourceKind::Pyc(rel_path) => {
+                let path = resolve_path(rel_path);
+                let file_name = path.file_name().and_then(|file_name| file_name.to_str());
+                let code_map = btreemap! {
+                    module_name => FrozenModule {
+                        code: self.translate_pyc(&path)?.into(),
+                        package: false,
+                        namespace: false,
+                        source: None,
+                        source_path: file_name.map(ToOwned::to_owned),
+                        resources: HashMap::new(),
+                    },
+                };
+                (code_map, vec![path])
             }
         })
     }
 
+    fn translate_pyc(&self, path: &Path) -> Result<CodeObject, Diagnostic> {
+        fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| cpython::translate_pyc(&bytes).map_err(|err| err.to_string()))
+            .map_err(|err| {
+                Diagnostic::spans_error(self.span, format!("Error translating {:?}: {}", path, err))
+            })
+    }
+
+    fn compile_zip(
+        &self,
+        path: &Path,
+        mode: compile::Mode,
+    ) -> Result<BTreeMap<String, FrozenModule>, Diagnostic> {
+        let zip_error = |err| {
+            Diagnostic::spans_error(
+                self.span,
+                format!("Error reading zip archive {:?}: {}", path, err),
+            )
+        };
+        let data = fs::read(path).map_err(|err| zip_error(err.to_string()))?;
+        let archive = ZipArchive::parse(&data).map_err(zip_error)?;
+        let entry_names: Vec<String> = archive
+            .entries
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+        let modules = frozen::find_archive_modules(&entry_names, &self.zip_prefix, &self.filter)
+            .map_err(zip_error)?;
+        let mut code_map = BTreeMap::new();
+        for module in modules {
+            let entry = archive
+                .entries
+                .iter()
+                .find(|entry| Path::new(&entry.name) == module.path)
+                .unwrap();
+            let bytes = archive.read(entry).map_err(zip_error)?;
+            let source = encoding::decode_source(&bytes)
+                .map_err(|err| zip_error(format!("Error decoding {:?}: {}", entry.name, err)))?;
+            let origin = path.join(&entry.name).display().to_string();
+            code_map.insert(
+                module.name.clone(),
+                FrozenModule {
+                    code: self
+                        .compile_string(&source, mode, module.name, &origin)?
+                        .into(),
+                    package: module.package,
+                    namespace: false,
+                    source: self.embedded_source(&source),
+                    source_path: Some(module.relative_path),
+                    resources: HashMap::new(),
+                },
+            );
+        }
+        Ok(code_map)
+    }
+
     fn compile_dir(
         &self,
         path: &Path,
-        parent: String,
         mode: compile::Mode,
-    ) -> Result<HashMap<String, FrozenModule>, Diagnostic> {
-        let mut code_map = HashMap::new();
-        let paths = fs::read_dir(&path).map_err(|err| {
-            Diagnostic::spans_error(self.span, format!("Error listing dir {:?}: {}", path, err))
+    ) -> Result<(BTreeMap<String, FrozenModule>, Vec<PathBuf>), Diagnostic> {
+        let modules = frozen::find_modules_filtered(path, &self.filter)
+            .map_err(|err| Diagnostic::spans_error(self.span, err.to_string()))?;
+        let mut code_map = BTreeMap::new();
+        let mut source_files = Vec::new();
+        // Go on after a module that doesn't compile, to report the errors of all of them
+        let mut diagnostics = Vec::new();
+        for module in modules {
+            let source = self.read_source(&module.path)?;
+            let origin = module.path.display().to_string();
+            let code = match self.compile_string(&source, mode, module.name.clone(), &origin) {
+                Ok(code) => code,
+                Err(diagnostic) => {
+                    diagnostics.push(diagnostic);
+                    continue;
+                }
+            };
+            code_map.insert(
+                module.name,
+                FrozenModule {
+                    code: code.into(),
+                    package: module.package,
+                    namespace: false,
+                    source: self.embedded_source(&source),
+                    source_path: Some(module.relative_path),
+                    resources: HashMap::new(),
+                },
+            );
+            source_files.push(module.path);
+        }
+        Diagnostic::from_vec(diagnostics)?;
+        Ok((code_map, source_files))
+    }
+
+    fn load_precompiled_dir(
+        &self,
+        path: &Path,
+    ) -> Result<BTreeMap<String, FrozenModule>, Diagnostic> {
+        let manifest_path = path.join(frozen::MANIFEST_FILE_NAME);
+        let manifest = fs::read_to_string(&manifest_path).map_err(|err| {
+            Diagnostic::spans_error(
+                self.span,
+                format!("Error reading manifest {:?}: {}", manifest_path, err),
+            )
         })?;
-        for path in paths {
-            let path = path.map_err(|err| {
-                Diagnostic::spans_error(self.span, format!("Failed to list file: {}", err))
-            })?;
-            let path = path.path();
-            let file_name = path.file_name().unwrap().to_str().ok_or_else(|| {
-                Diagnostic::spans_error(self.span, format!("Invalid UTF-8 in file name {:?}", path))
-            })?;
-            if path.is_dir() {
-                code_map.extend(self.compile_dir(
-                    &path,
-                    format!("{}{}", parent, file_name),
-                    mode,
-                )?);
-            } else if file_name.ends_with(".py") {
-                let source = fs::read_to_string(&path).map_err(|err| {
+        let manifest = Manifest::parse(&manifest).map_err(|err| {
+            Diagnostic::spans_error(self.span, format!("{:?}: {}", manifest_path, err))
+        })?;
+        if manifest.format_version != FORMAT_VERSION {
+            return Err(Diagnostic::spans_error(
+                self.span,
+                format!(
+                    "{:?} holds bytecode format version {}, but this build of RustPython uses \
+                     version {}; recompile it with rustpython-compile",
+                    path, manifest.format_version, FORMAT_VERSION
+                ),
+            ));
+        }
+        let mut code_map = BTreeMap::new();
+        for entry in manifest.entries {
+            let bytecode_path = path.join(frozen::bytecode_file_name(&entry.name));
+            let code = fs::read(&bytecode_path)
+                .map_err(|err| err.to_string())
+                .and_then(|bytes| CodeObject::from_bytes(&bytes).map_err(|err| err.to_string()))
+                .map_err(|err| {
                     Diagnostic::spans_error(
                         self.span,
-                        format!("Error reading file {:?}: {}", path, err),
+                        format!("Error loading bytecode {:?}: {}", bytecode_path, err),
                     )
                 })?;
-                let stem = path.file_stem().unwrap().to_str().unwrap();
-                let is_init = stem == "__init__";
-                let module_name = if is_init {
-                    parent.clone()
-                } else if parent.is_empty() {
-                    stem.to_string()
-                } else {
-                    format!("{}.{}", parent, stem)
-                };
-                code_map.insert(
-                    module_name.clone(),
-                    FrozenModule {
-                        code: self.compile_string(&source, mode, module_name)?,
-                        package: is_init,
-                    },
-                );
+            if code.content_hash(true) != entry.hash {
+                return Err(Diagnostic::spans_error(
+                    self.span,
+                    format!(
+                        "{:?} doesn't match the hash in the manifest; recompile it with \
+                         rustpython-compile",
+                        bytecode_path
+                    ),
+                ));
             }
+            code_map.insert(
+                entry.name,
+                FrozenModule {
+                    code: code.into(),
+                    package: entry.package,
+                    namespace: false,
+                    source: None,
+                    source_path: Some(entry.source_path),
+                    resources: HashMap::new(),
+                },
+            );
         }
         Ok(code_map)
     }
+
+    fn find_resources(&self, patterns: &[String]) -> Result<Vec<SourceResource>, Diagnostic> {
+        let rel_path = match &self.kind {
+            CompilationSourceKind::Dir(rel_path) => rel_path,
+            _ => {
+                return Err(Diagnostic::spans_error(
+                    self.span,
+                    "include_resources only works with dir",
+                ))
+            }
+        };
+        let path = resolve_path(rel_path);
+        let mut resources = Vec::new();
+        for pattern in patterns {
+            for resource in frozen::find_resources(&path, pattern)
+                .map_err(|err| Diagnostic::spans_error(self.span, err.to_string()))?
+            {
+                if !resources.contains(&resource) {
+                    resources.push(resource);
+                }
+            }
+        }
+        Ok(resources)
+    }
+}
+
+/// Add the namespace packages the modules of a directory or an archive are in, for the
+/// directories without an `__init__.py`.
+fn add_namespace_packages(code_map: &mut BTreeMap<String, FrozenModule>) {
+    for name in frozen::namespace_packages(code_map.keys().map(String::as_str)) {
+        let module = frozen::namespace_package(&name);
+        code_map.insert(name, module);
+    }
+}
+
+/// Resolve a path given to the macro: relative to `$CARGO_MANIFEST_DIR`, or to `$OUT_DIR` if it
+/// starts with that, to pick up files generated by a build script.
+fn resolve_path(rel_path: &Path) -> PathBuf {
+    if let Ok(rest) = rel_path.strip_prefix("$OUT_DIR") {
+        let mut path = PathBuf::from(
+            env::var_os("OUT_DIR").expect("OUT_DIR is not present, is there a build script?"),
+        );
+        path.push(rest);
+        return path;
+    }
+    let mut path = PathBuf::from(
+        env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not present"),
+    );
+    path.push(rel_path);
+    path
+}
+
+/// Describe a compile error in `source` like Python does, with the line it's on and a caret
+/// below the column, since the span of the macro argument can't point into the file.
+fn compile_error_message(origin: &str, source: &str, err: &CompileError) -> String {
+    let (row, column) = (err.location.row(), err.location.column());
+    let mut message = format!(
+        "Compile error in {} at line {}, column {}: {}",
+        origin, row, column, err.error
+    );
+    if let Some(line) = source.lines().nth(row.saturating_sub(1)) {
+        // Keep the tabs before the column, so the caret lines up with it
+        let indent: String = line
+            .chars()
+            .take(column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        message.push_str(&format!("\n    {}\n    {}^", line, indent));
+    }
+    message
+}
+
+/// Evaluate a string argument: a string literal, or one of the macros that expand to one.
+fn eval_str(expr: &Expr) -> Result<String, Diagnostic> {
+    let mac = match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => return Ok(s.value()),
+        Expr::Macro(mac) => &mac.mac,
+        _ => bail_span!(
+            expr,
+            "expected a string literal, concat!, env! or include_str!"
+        ),
+    };
+    let args = Punctuated::<Expr, Token![,]>::parse_terminated
+        .parse2(mac.tts.clone())
+        .map_err(|err| err_span!(mac, "{}", err))?;
+    let name = match mac.path.segments.last() {
+        Some(segment) => segment.into_value().ident.to_string(),
+        None => bail_span!(mac.path, "expected a macro name"),
+    };
+    match name.as_str() {
+        "concat" => {
+            let mut value = String::new();
+            for arg in &args {
+                match arg {
+                    Expr::Lit(ExprLit { lit, .. }) => match lit {
+                        Lit::Str(s) => value.push_str(&s.value()),
+                        Lit::Char(c) => value.push(c.value()),
+                        Lit::Int(i) => value.push_str(&i.value().to_string()),
+                        Lit::Bool(b) => value.push_str(&b.value.to_string()),
+                        _ => bail_span!(lit, "concat! can't take this literal here"),
+                    },
+                    _ => value.push_str(&eval_str(arg)?),
+                }
+            }
+            Ok(value)
+        }
+        "env" => {
+            if args.is_empty() || args.len() > 2 {
+                bail_span!(mac, "env! takes 1 or 2 arguments")
+            }
+            let var_name = eval_str(&args[0])?;
+            env::var(&var_name).or_else(|_| match args.iter().nth(1) {
+                Some(message) => Err(err_span!(mac, "{}", eval_str(message)?)),
+                None => Err(err_span!(
+                    mac,
+                    "environment variable `{}` not defined",
+                    var_name
+                )),
+            })
+        }
+        "include_str" => {
+            if args.len() != 1 {
+                bail_span!(mac, "include_str! takes 1 argument")
+            }
+            let path = resolve_path(Path::new(&eval_str(&args[0])?));
+            fs::read_to_string(&path)
+                .map_err(|err| err_span!(mac, "couldn't read {:?}: {}", path, err))
+        }
+        _ => bail_span!(
+            mac.path,
+            "only string literals, concat!, env! and include_str! are supported here, not {}!",
+            name
+        ),
+    }
 }
 
-/// This is essentially just a comma-separated list of Meta nodes, aka the inside of a MetaList.
+/// A `name = value` argument, or a `cfg(predicate) => value` one.
+struct PyCompileArg {
+    ident: Ident,
+    /// The predicate of a `cfg(...) => ...` argument.
+    predicate: Option<TokenStream2>,
+    /// The `=`, or the `=>` after the predicate.
+    separator: TokenStream2,
+    value: Expr,
+}
+
+impl PyCompileArg {
+    fn str_value(&self) -> Result<String, Diagnostic> {
+        eval_str(&self.value)
+    }
+
+    /// The value of an argument that takes a string or a list of them.
+    fn str_values(&self) -> Result<Vec<String>, Diagnostic> {
+        match &self.value {
+            Expr::Array(array) => array.elems.iter().map(eval_str).collect(),
+            _ => Ok(vec![self.str_value()?]),
+        }
+    }
+
+    fn lit(&self) -> Option<&Lit> {
+        match &self.value {
+            Expr::Lit(ExprLit { lit, .. }) => Some(lit),
+            _ => None,
+        }
+    }
+}
+
+impl Parse for PyCompileArg {
+    fn parse(input: ParseStream) -> ParseResult<Self> {
+        let ident: Ident = input.parse()?;
+        if ident == "cfg" && input.peek(token::Paren) {
+            let predicate;
+            let paren = parenthesized!(predicate in input);
+            let predicate: TokenStream2 = predicate.parse()?;
+            let arrow: Token![=>] = input.parse()?;
+            let mut group = Group::new(Delimiter::Parenthesis, predicate.clone());
+            group.set_span(paren.span);
+            return Ok(PyCompileArg {
+                ident,
+                predicate: Some(predicate),
+                separator: quote!(#group #arrow),
+                value: input.parse()?,
+            });
+        }
+        let eq_token: Token![=] = input.parse()?;
+        Ok(PyCompileArg {
+            ident,
+            predicate: None,
+            separator: eq_token.into_token_stream(),
+            value: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for PyCompileArg {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.ident.to_tokens(tokens);
+        self.separator.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+/// This is essentially just a comma-separated list of `name = value` arguments.
 struct PyCompileInput {
     span: Span,
-    metas: Vec<Meta>,
+    args: Vec<PyCompileArg>,
 }
 
 impl PyCompileInput {
-    fn compile(&self) -> Result<HashMap<String, FrozenModule>, Diagnostic> {
+    #[allow(clippy::type_complexity)]
+    fn compile(
+        &self,
+    ) -> Result<
+        (
+            BTreeMap<String, FrozenModule>,
+            Vec<PathBuf>,
+            Vec<SourceResource>,
+        ),
+        Diagnostic,
+    > {
         let mut module_name = None;
         let mut mode = None;
         let mut source: Option<CompilationSource> = None;
+        let mut cache = None;
+        let mut optimize = 0;
+        let mut resource_patterns = Vec::new();
+        let mut filter = ModuleFilter::default();
+        let mut zip_prefix = None;
+        let mut embed_source = None;
 
         fn assert_source_empty(source: &Option<CompilationSource>) -> Result<(), Diagnostic> {
             if let Some(source) = source {
@@ -165,109 +644,867 @@ impl PyCompileInput {
             }
         }
 
-        for meta in &self.metas {
-            if let Meta::NameValue(name_value) = meta {
-                if name_value.ident == "mode" {
-                    match &name_value.lit {
-                        Lit::Str(s) => match s.value().parse() {
-                            Ok(mode_val) => mode = Some(mode_val),
-                            Err(e) => bail_span!(s, "{}", e),
-                        },
-                        _ => bail_span!(name_value.lit, "mode must be a string"),
-                    }
-                } else if name_value.ident == "module_name" {
-                    module_name = Some(match &name_value.lit {
-                        Lit::Str(s) => s.value(),
-                        _ => bail_span!(name_value.lit, "module_name must be string"),
-                    })
-                } else if name_value.ident == "source" {
-                    assert_source_empty(&source)?;
-                    let code = match &name_value.lit {
-                        Lit::Str(s) => s.value(),
-                        _ => bail_span!(name_value.lit, "source must be a string"),
-                    };
-                    source = Some(CompilationSource {
-                        kind: CompilationSourceKind::SourceCode(code),
-                        span: extract_spans(&name_value).unwrap(),
-                    });
-                } else if name_value.ident == "file" {
-                    assert_source_empty(&source)?;
-                    let path = match &name_value.lit {
-                        Lit::Str(s) => PathBuf::from(s.value()),
-                        _ => bail_span!(name_value.lit, "source must be a string"),
-                    };
-                    source = Some(CompilationSource {
-                        kind: CompilationSourceKind::File(path),
-                        span: extract_spans(&name_value).unwrap(),
-                    });
-                } else if name_value.ident == "dir" {
-                    assert_source_empty(&source)?;
-                    let path = match &name_value.lit {
-                        Lit::Str(s) => PathBuf::from(s.value()),
-                        _ => bail_span!(name_value.lit, "source must be a string"),
-                    };
-                    source = Some(CompilationSource {
-                        kind: CompilationSourceKind::Dir(path),
-                        span: extract_spans(&name_value).unwrap(),
-                    });
+        for arg in &self.args {
+            let ident = &arg.ident;
+            let source_kind = if ident == "mode" {
+                match arg.str_value()?.parse() {
+                    Ok(mode_val) => mode = Some(mode_val),
+                    Err(e) => bail_span!(arg.value, "{}", e),
                 }
+                None
+            } else if ident == "module_name" {
+                module_name = Some(arg.str_value()?);
+                None
+            } else if ident == "cache" {
+                cache = match arg.lit() {
+                    Some(Lit::Bool(b)) => Some(b.value),
+                    _ => bail_span!(arg.value, "cache must be a bool"),
+                };
+                None
+            } else if ident == "optimize" {
+                optimize = match arg.lit() {
+                    Some(Lit::Int(i)) if i.value() <= u64::from(std::u8::MAX) => i.value() as u8,
+                    _ => bail_span!(arg.value, "optimize must be an integer"),
+                };
+                None
+            } else if ident == "embed_source" {
+                embed_source = match arg.lit() {
+                    Some(Lit::Bool(b)) => Some((b.value, extract_spans(arg).unwrap())),
+                    _ => bail_span!(arg.value, "embed_source must be a bool"),
+                };
+                None
+            } else if ident == "include" {
+                filter.include.extend(arg.str_values()?);
+                None
+            } else if ident == "exclude" {
+                filter.exclude.extend(arg.str_values()?);
+                None
+            } else if ident == "include_resources" {
+                resource_patterns.push(arg.str_value()?);
+                None
+            } else if ident == "zip_prefix" {
+                zip_prefix = Some((arg.str_value()?, extract_spans(arg).unwrap()));
+                None
+            } else if ident == "source" {
+                Some(CompilationSourceKind::SourceCode(arg.str_value()?))
+            } else if ident == "file" {
+                Some(CompilationSourceKind::File(arg.str_value()?.into()))
+            } else if ident == "dir" {
+                Some(CompilationSourceKind::Dir(arg.str_value()?.into()))
+            } else if ident == "precompiled_dir" {
+                Some(CompilationSourceKind::PrecompiledDir(
+                    arg.str_value()?.into(),
+                ))
+            } else if ident == "zip" {
+                Some(CompilationSourceKind::Zip(arg.str_value()?.into()))
+            } else if ident == "pyc" {
+                Some(CompilationSourceKind::Pyc(arg.str_value()?.into()))
+            } else {
+                None
+            };
+            if let Some(kind) = source_kind {
+                assert_source_empty(&source)?;
+                source = Some(CompilationSource {
+                    kind,
+                    span: extract_spans(arg).unwrap(),
+                    cache: None,
+                    optimize: 0,
+                    filter: ModuleFilter::default(),
+                    zip_prefix: String::new(),
+                    embed_source: false,
+                });
             }
         }
 
-        source
-            .ok_or_else(|| {
-                Diagnostic::span_error(
-                    self.span,
-                    "Must have either file or source in py_compile_bytecode!()",
-                )
-            })?
-            .compile(
-                mode.unwrap_or(compile::Mode::Exec),
-                module_name.unwrap_or_else(|| "frozen".to_string()),
+        let mut source = source.ok_or_else(|| {
+            Diagnostic::span_error(
+                self.span,
+                "Must have either file, source, dir, precompiled_dir, zip or pyc in \
+                 py_compile_bytecode!()",
             )
+        })?;
+        match cache {
+            Some(true) => {
+                source.cache = Some(CompileCache::for_current_crate().ok_or_else(|| {
+                    Diagnostic::span_error(
+                        self.span,
+                        "cache = true needs $OUT_DIR, $CARGO_TARGET_DIR or $CARGO_MANIFEST_DIR \
+                         to store the cache in",
+                    )
+                })?);
+            }
+            Some(false) => {}
+            // Directories and archives are what's slow to compile, so they're cached whenever
+            // there's somewhere to keep the cache
+            None => {
+                if let CompilationSourceKind::Dir(_) | CompilationSourceKind::Zip(_) = source.kind {
+                    source.cache = CompileCache::for_current_crate();
+                }
+            }
+        }
+        source.optimize = optimize;
+        if filter != ModuleFilter::default() {
+            match source.kind {
+                CompilationSourceKind::Dir(_) | CompilationSourceKind::Zip(_) => {
+                    source.filter = filter
+                }
+                _ => {
+                    return Err(Diagnostic::spans_error(
+                        source.span,
+                        "include and exclude only work with dir or zip",
+                    ))
+                }
+            }
+        }
+        if let Some((embed_source, span)) = embed_source {
+            match source.kind {
+                CompilationSourceKind::PrecompiledDir(_) | CompilationSourceKind::Pyc(_) => {
+                    return Err(Diagnostic::spans_error(
+                        span,
+                        "embed_source only works with file, source, dir or zip",
+                    ))
+                }
+                _ => source.embed_source = embed_source,
+            }
+        }
+        if let Some((prefix, span)) = zip_prefix {
+            match source.kind {
+                CompilationSourceKind::Zip(_) => source.zip_prefix = prefix,
+                _ => {
+                    return Err(Diagnostic::spans_error(
+                        span,
+                        "zip_prefix only works with zip",
+                    ))
+                }
+            }
+        }
+        let resources = if resource_patterns.is_empty() {
+            Vec::new()
+        } else {
+            source.find_resources(&resource_patterns)?
+        };
+        let (code_map, source_files) = source.compile(
+            mode.unwrap_or(compile::Mode::Exec),
+            module_name.unwrap_or_else(|| "frozen".to_string()),
+        )?;
+        Ok((code_map, source_files, resources))
     }
 }
 
+impl PyCompileInput {
+    /// The `#[cfg(...)]` attributes of the modules the globs of `cfg(...) => ...` arguments
+    /// match. Every glob has to match a module, so that a misspelled one doesn't go unnoticed.
+    fn module_cfgs(
+        &self,
+        code_map: &BTreeMap<String, FrozenModule>,
+    ) -> Result<HashMap<String, Vec<TokenStream2>>, Diagnostic> {
+        let mut cfgs: HashMap<String, Vec<TokenStream2>> = HashMap::new();
+        for arg in self.args.iter().filter(|arg| arg.ident == "cfg") {
+            let predicate = match &arg.predicate {
+                Some(predicate) => predicate,
+                None => bail_span!(arg, "cfg takes the form cfg(predicate) => \"glob\""),
+            };
+            for pattern in arg.str_values()? {
+                let patterns = [pattern];
+                let mut matched = false;
+                for (name, module) in code_map {
+                    let source_path = match &module.source_path {
+                        Some(source_path) => source_path,
+                        None => continue,
+                    };
+                    if frozen::glob_matches_path(&patterns, source_path) {
+                        cfgs.entry(name.clone())
+                            .or_default()
+                            .push(quote!(#[cfg(#predicate)]));
+                        matched = true;
+                    }
+                }
+                if !matched {
+                    bail_span!(arg.value, "{:?} doesn't match any module", patterns[0])
+                }
+            }
+        }
+        Ok(cfgs)
+    }
+
+    /// The value of the bool argument `name`, false if it isn't given.
+    fn bool_arg(&self, name: &str) -> Result<bool, Diagnostic> {
+        let mut value = false;
+        for arg in self.args.iter().filter(|arg| arg.ident == name) {
+            value = match arg.lit() {
+                Some(Lit::Bool(b)) => b.value,
+                _ => bail_span!(arg.value, "{} must be a bool", name),
+            };
+        }
+        Ok(value)
+    }
+
+    /// How the code objects are put in the binary, by the `compress` and `compact` arguments.
+    fn layout(&self) -> Result<CodeLayout, Diagnostic> {
+        match (self.bool_arg("compress")?, self.bool_arg("compact")?) {
+            (false, false) => Ok(CodeLayout::Static),
+            (false, true) => Ok(CodeLayout::Compact),
+            (true, false) => Ok(CodeLayout::Compressed),
+            (true, true) => Err(Diagnostic::span_error(
+                self.span,
+                "compress and compact can't be combined",
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CodeLayout {
+    /// `FrozenCode::Static`
+    Static,
+    /// `FrozenCode::Compact`
+    Compact,
+    /// `FrozenCode::Compressed`
+    Compressed,
+}
+
 impl Parse for PyCompileInput {
     fn parse(input: ParseStream) -> ParseResult<Self> {
         let span = input.cursor().span();
-        let metas = input
-            .parse_terminated::<Meta, Token![,]>(Meta::parse)?
+        let args = input
+            .parse_terminated::<PyCompileArg, Token![,]>(PyCompileArg::parse)?
             .into_iter()
             .collect();
-        Ok(PyCompileInput { span, metas })
+        Ok(PyCompileInput { span, args })
     }
 }
 
 pub fn impl_py_compile_bytecode(input: TokenStream2) -> Result<TokenStream2, Diagnostic> {
     let input: PyCompileInput = parse2(input)?;
 
-    let code_map = input.compile()?;
-
-    let modules = code_map
-        .into_iter()
-        .map(|(module_name, FrozenModule { code, package })| {
-            let module_name = LitStr::new(&module_name, Span::call_site());
-            let bytes = code.to_bytes();
-            let bytes = LitByteStr::new(&bytes, Span::call_site());
-            quote! {
-                #module_name.into() => ::rustpython_vm::bytecode::FrozenModule {
-                    code: ::rustpython_vm::bytecode::CodeObject::from_bytes(
-                        #bytes
-                    ).expect("Deserializing CodeObject failed"),
-                    package: #package,
+    let (code_map, source_files, resources) = input.compile()?;
+    let layout = input.layout()?;
+    let compress = layout == CodeLayout::Compressed;
+    let mut module_cfgs = input.module_cfgs(&code_map)?;
+    let mut package_resources: HashMap<String, Vec<SourceResource>> = HashMap::new();
+    for resource in resources {
+        package_resources
+            .entry(resource.package.clone())
+            .or_default()
+            .push(resource);
+    }
+
+    // The modules are in order of their names, so that the same sources always expand to the
+    // same tokens, and builds are reproducible; that includes the order of the string table
+    let mut strings = StringTableBuilder::new();
+    let modules = code_map.into_iter().map(|(module_name, module)| {
+        let cfgs = module_cfgs.remove(&module_name).unwrap_or_default();
+        let FrozenModule {
+            code,
+            package,
+            namespace,
+            source,
+            source_path,
+            ..
+        } = module;
+        let resources = package_resources
+            .remove(&module_name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|resource| {
+                let name = LitStr::new(&resource.name, Span::call_site());
+                // include_bytes! also makes cargo rebuild when the file changes
+                let path = LitStr::new(&resource.path.to_string_lossy(), Span::call_site());
+                quote! {
+                    #name.into() => ::std::borrow::Cow::Borrowed(&include_bytes!(#path)[..])
                 }
+            });
+        let module_name = LitStr::new(&module_name, Span::call_site());
+        let code = match layout {
+            CodeLayout::Static => {
+                let code = static_code::code_object(&code.decode());
+                quote!(::rustpython_vm::bytecode::FrozenCode::Static({
+                    static CODE: StaticCodeObject = #code;
+                    &CODE
+                }))
             }
-        });
+            CodeLayout::Compact => {
+                let bytes = compact::encode(&code.decode(), &mut strings);
+                let bytes = LitByteStr::new(&bytes, Span::call_site());
+                quote!(::rustpython_vm::bytecode::FrozenCode::Compact {
+                    code: #bytes,
+                    strings: STRINGS,
+                })
+            }
+            CodeLayout::Compressed => {
+                let bytes =
+                    LitByteStr::new(&code.decode().to_compressed_bytes(), Span::call_site());
+                quote!(::rustpython_vm::bytecode::FrozenCode::Compressed(#bytes))
+            }
+        };
+        let source = match source.map(|source| source.decode()) {
+            Some(text) if compress => {
+                let bytes = LitByteStr::new(&FrozenSource::compress(&text), Span::call_site());
+                quote!(Some(::rustpython_vm::bytecode::FrozenSource::Compressed(#bytes)))
+            }
+            Some(text) => {
+                let text = LitStr::new(&text, Span::call_site());
+                quote!(Some(::rustpython_vm::bytecode::FrozenSource::Text(
+                    ::std::borrow::Cow::Borrowed(#text)
+                )))
+            }
+            None => quote!(None),
+        };
+        let source_path = match source_path {
+            Some(path) => {
+                let path = LitStr::new(&path, Span::call_site());
+                quote!(Some(#path.to_owned()))
+            }
+            None => quote!(None),
+        };
+        quote! {
+            #(#cfgs)*
+            modules.insert(#module_name.into(), ::rustpython_vm::bytecode::FrozenModule {
+                code: #code,
+                package: #package,
+                namespace: #namespace,
+                source: #source,
+                source_path: #source_path,
+                resources: hashmap! {
+                    #(#resources),*
+                },
+            });
+        }
+    });
+    // The string table is complete once every module is encoded
+    let modules: Vec<_> = modules.collect();
+
+    // include_bytes! of the sources makes cargo rebuild when one of them changes, even when
+    // they're outside the crate; the bytes aren't used, so they don't end up in the binary
+    let source_files = source_files
+        .iter()
+        .map(|path| LitStr::new(&path.to_string_lossy(), Span::call_site()));
+
+    let layout_items = match layout {
+        CodeLayout::Static => Some(static_code::imports()),
+        CodeLayout::Compact => {
+            let bytes = LitByteStr::new(&strings.to_bytes(), Span::call_site());
+            // Unused if every module is left out by its cfg
+            Some(quote! {
+                #[allow(dead_code)]
+                const STRINGS: &[u8] = #bytes;
+            })
+        }
+        CodeLayout::Compressed => None,
+    };
 
     let output = quote! {
         ({
             use ::rustpython_vm::__exports::hashmap;
-            hashmap! {
-                #(#modules),*
-            }
+            #(const _: &[u8] = include_bytes!(#source_files);)*
+            #layout_items
+            let mut modules: ::std::collections::HashMap<::std::string::String, _> =
+                ::std::collections::HashMap::new();
+            #(#modules)*
+            modules
         })
     };
 
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    /// Expand `py_compile_bytecode!` with `input`, and return the error message if it fails
+    fn expand(input: TokenStream2) -> Result<String, String> {
+        impl_py_compile_bytecode(input)
+            .map(|tokens| tokens.to_string())
+            .map_err(|diagnostic| diagnostic.into_token_stream().to_string())
+    }
+
+    /// The names of the modules `input` compiles to, along with their source paths
+    fn compile(input: TokenStream2) -> Vec<(String, Option<String>)> {
+        let input: PyCompileInput = parse2(input).unwrap();
+        let (code_map, _, _) = match input.compile() {
+            Ok(compiled) => compiled,
+            Err(diagnostic) => panic!("{}", diagnostic.into_token_stream()),
+        };
+        let mut modules: Vec<_> = code_map
+            .into_iter()
+            .map(|(name, module)| (name, module.source_path))
+            .collect();
+        modules.sort();
+        modules
+    }
+
+    #[test]
+    fn test_macro_arguments() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-args-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("gen.py"), "x = 1\n").unwrap();
+        let dir_var = format!("RUSTPYTHON_COMPILE_ARGS_DIR_{}", process::id());
+        env::set_var(&dir_var, &dir);
+
+        assert_eq!(
+            compile(quote!(source = "x = 1")),
+            vec![("frozen".to_owned(), None)]
+        );
+        assert_eq!(
+            compile(quote!(
+                source = concat!("x = ", 1, "\n"),
+                module_name = concat!("a", "b")
+            )),
+            vec![("ab".to_owned(), None)]
+        );
+        assert_eq!(
+            compile(quote!(file = concat!(env!(#dir_var), "/gen.py"))),
+            vec![("frozen".to_owned(), Some("gen.py".to_owned()))]
+        );
+        assert_eq!(
+            compile(quote!(
+                source = include_str!(concat!(env!(#dir_var), "/gen.py"))
+            )),
+            vec![("frozen".to_owned(), None)]
+        );
+        assert_eq!(
+            compile(quote!(dir = env!(#dir_var), mode = "exec")),
+            vec![("gen".to_owned(), Some("gen.py".to_owned()))]
+        );
+        assert_eq!(
+            compile(quote!(
+                module_name = std::concat!("fro", "zen"),
+                source = ""
+            )),
+            vec![("frozen".to_owned(), None)]
+        );
+        assert!(expand(quote!(source = concat!("x = ", "1"))).is_ok());
+
+        let err = expand(quote!(source = format!("x = {}", 1))).unwrap_err();
+        assert!(err.contains("not format!"), "{}", err);
+        let err = expand(quote!(source = env!("RUSTPYTHON_COMPILE_ARGS_UNSET"))).unwrap_err();
+        assert!(
+            err.contains("environment variable `RUSTPYTHON_COMPILE_ARGS_UNSET` not defined"),
+            "{}",
+            err
+        );
+        let err = expand(quote!(
+            source = env!("RUSTPYTHON_COMPILE_ARGS_UNSET", "no source")
+        ))
+        .unwrap_err();
+        assert!(err.contains("no source"), "{}", err);
+        let err = expand(quote!(
+            file = include_str!(concat!(env!(#dir_var), "/missing.py"))
+        ))
+        .unwrap_err();
+        assert!(err.contains("couldn't read"), "{}", err);
+        let err = expand(quote!(source = 1)).unwrap_err();
+        assert!(err.contains("expected a string literal"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_optimize() {
+        let constants = |input: TokenStream2| {
+            let input: PyCompileInput = parse2(input).unwrap();
+            let (code_map, _, _) = input.compile().unwrap_or_else(|diagnostic| {
+                panic!("{}", diagnostic.into_token_stream());
+            });
+            format!("{:?}", code_map["frozen"].code.decode().constants)
+        };
+        let source = "'the docstring'\nassert x, 'the message'\n";
+
+        let plain = constants(quote!(source = #source));
+        assert!(plain.contains("the docstring") && plain.contains("the message"));
+        let no_asserts = constants(quote!(source = #source, optimize = 1));
+        assert!(no_asserts.contains("the docstring") && !no_asserts.contains("the message"));
+        let no_docstrings = constants(quote!(source = #source, optimize = 2));
+        assert!(!no_docstrings.contains("the docstring") && !no_docstrings.contains("the message"));
+
+        let err = expand(quote!(source = "", optimize = "2")).unwrap_err();
+        assert!(err.contains("optimize must be an integer"), "{}", err);
+    }
+
+    #[test]
+    fn test_include_exclude() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-filter-{}", process::id()));
+        fs::create_dir_all(dir.join("pkg/test")).unwrap();
+        for file in &[
+            "pkg/__init__.py",
+            "pkg/_bootstrap_external.py",
+            "pkg/mod.py",
+            "pkg/test/__init__.py",
+            "pkg/test/test_mod.py",
+            "top.py",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let dir = dir.to_str().unwrap();
+        let names = |input: TokenStream2| {
+            compile(input)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            names(quote!(
+                dir = #dir,
+                include = "**/*.py",
+                exclude = ["pkg/test/**", "**/_bootstrap*.py"],
+            )),
+            ["pkg", "pkg.mod", "top"]
+        );
+        assert_eq!(
+            names(quote!(dir = #dir, include = ["pkg/*.py"], include = "top.py")),
+            ["pkg", "pkg._bootstrap_external", "pkg.mod", "top"]
+        );
+        assert_eq!(
+            names(quote!(dir = #dir, exclude = "pkg/test", exclude = ["top.py"])),
+            ["pkg", "pkg._bootstrap_external", "pkg.mod"]
+        );
+
+        let err = expand(quote!(source = "", include = "*.py")).unwrap_err();
+        assert!(
+            err.contains("include and exclude only work with dir or zip"),
+            "{}",
+            err
+        );
+        let err = expand(quote!(dir = #dir, exclude = ["pkg", 1])).unwrap_err();
+        assert!(err.contains("expected a string literal"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_namespace_packages() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-ns-{}", process::id()));
+        fs::create_dir_all(dir.join("ns/sub")).unwrap();
+        fs::create_dir_all(dir.join("pkg/portion")).unwrap();
+        for file in &["ns/sub/mod.py", "pkg/__init__.py", "pkg/portion/mod.py"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let dir_str = dir.to_str().unwrap();
+
+        let module = |name: &str, source_path: Option<&str>| {
+            (name.to_owned(), source_path.map(ToOwned::to_owned))
+        };
+        assert_eq!(
+            compile(quote!(dir = #dir_str, cache = false)),
+            [
+                module("ns", None),
+                module("ns.sub", None),
+                module("ns.sub.mod", Some("ns/sub/mod.py")),
+                module("pkg", Some("pkg/__init__.py")),
+                module("pkg.portion", None),
+                module("pkg.portion.mod", Some("pkg/portion/mod.py")),
+            ]
+        );
+        let expanded = expand(quote!(dir = #dir_str, cache = false)).unwrap();
+        assert_eq!(expanded.matches("namespace : true").count(), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_deterministic_order() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-order-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let names = ["m0", "m1", "m2", "m3", "m4", "m5", "m6", "m7", "m8", "m9"];
+        for name in &names {
+            fs::write(dir.join(format!("{}.py", name)), "").unwrap();
+        }
+        let dir_str = dir.to_str().unwrap();
+
+        let expanded = expand(quote!(dir = #dir_str, cache = false)).unwrap();
+        for _ in 0..5 {
+            assert_eq!(
+                expand(quote!(dir = #dir_str, cache = false)).unwrap(),
+                expanded
+            );
+        }
+        let positions: Vec<usize> = names
+            .iter()
+            .map(|name| expanded.find(&format!("\"{}\" . into ( )", name)).unwrap())
+            .collect();
+        let mut sorted = positions.clone();
+        sorted.sort();
+        assert_eq!(positions, sorted);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cfg() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-cfg-{}", process::id()));
+        fs::create_dir_all(dir.join("nt")).unwrap();
+        for file in &["nt/__init__.py", "ntpath.py", "posixpath.py", "os.py"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let dir = dir.to_str().unwrap();
+
+        let expanded = expand(quote!(
+            dir = #dir,
+            cfg(windows) => ["ntpath.py", "nt"],
+            cfg(not(windows)) => "posixpath.py",
+            cfg(target_os = "linux") => "posix*.py",
+        ))
+        .unwrap();
+        let insert = |cfgs: &str, name: &str| format!("{}modules . insert ( \"{}\"", cfgs, name);
+        assert!(expanded.contains(&insert("; ", "os")), "{}", expanded);
+        assert!(expanded.contains(&insert("# [ cfg ( windows ) ] ", "nt")));
+        assert!(expanded.contains(&insert("# [ cfg ( windows ) ] ", "ntpath")));
+        assert!(expanded.contains(&insert(
+            "# [ cfg ( not ( windows ) ) ] # [ cfg ( target_os = \"linux\" ) ] ",
+            "posixpath"
+        )));
+
+        let err = expand(quote!(dir = #dir, cfg(windows) => "ntpth.py")).unwrap_err();
+        assert!(
+            err.contains("\\\"ntpth.py\\\" doesn't match any module"),
+            "{}",
+            err
+        );
+        let err = expand(quote!(dir = #dir, cfg = "ntpath.py")).unwrap_err();
+        assert!(err.contains("cfg takes the form"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compress() {
+        let source = (0..100)
+            .map(|i| format!("def f{}(x):\n    return x + {}\n", i, i))
+            .collect::<String>();
+        let plain = expand(quote!(source = #source)).unwrap();
+        assert!(plain.contains("FrozenCode :: Static"), "{}", plain);
+        assert!(plain.contains("Instruction :: ReturnValue"), "{}", plain);
+        let compact = expand(quote!(source = #source, compact = true)).unwrap();
+        assert!(compact.contains("FrozenCode :: Compact"), "{}", compact);
+        assert!(compact.len() < plain.len() / 2);
+        let compressed = expand(quote!(source = #source, compress = true)).unwrap();
+        assert!(compressed.contains("FrozenCode :: Compressed"));
+        assert!(compressed.len() < compact.len());
+        assert_eq!(
+            expand(quote!(source = #source, compress = false, compact = false)).unwrap(),
+            plain
+        );
+
+        let err = expand(quote!(source = "", compress = "yes")).unwrap_err();
+        assert!(err.contains("compress must be a bool"), "{}", err);
+        let err = expand(quote!(source = "", compress = true, compact = true)).unwrap_err();
+        assert!(
+            err.contains("compress and compact can't be combined"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_embed_source() {
+        let source = "x = 'embedded'\n";
+        let plain = expand(quote!(source = #source)).unwrap();
+        assert!(plain.contains("source : None"), "{}", plain);
+        let embedded = expand(quote!(source = #source, embed_source = true)).unwrap();
+        assert!(embedded.contains("FrozenSource :: Text"), "{}", embedded);
+        assert!(embedded.contains("\"x = 'embedded'\\n\""), "{}", embedded);
+        let compressed = expand(quote!(
+            source = #source,
+            embed_source = true,
+            compress = true
+        ))
+        .unwrap();
+        assert!(compressed.contains("FrozenSource :: Compressed"));
+        assert!(!compressed.contains("embedded"));
+
+        let err = expand(quote!(source = "", embed_source = 1)).unwrap_err();
+        assert!(err.contains("embed_source must be a bool"), "{}", err);
+        let err = expand(quote!(
+            precompiled_dir = "../precompile/integration/precompiled",
+            embed_source = true
+        ))
+        .unwrap_err();
+        assert!(
+            err.contains("embed_source only works with file, source, dir or zip"),
+            "{}",
+            err
+        );
+    }
+
+    /// A zip archive of stored entries, with a wrong checksum unless `valid_crc`
+    fn zip(entries: &[(&[u8], &[u8])], valid_crc: bool) -> Vec<u8> {
+        let u16_bytes = |n: usize| (n as u16).to_le_bytes().to_vec();
+        let u32_bytes = |n: u32| n.to_le_bytes().to_vec();
+        let mut data = Vec::new();
+        let mut central_dir = Vec::new();
+        for (name, contents) in entries {
+            let mut hasher = crc32fast::Hasher::new();
+            hasher.update(contents);
+            let crc = if valid_crc { hasher.finalize() } else { 0 };
+            // version, flags, method, time, date, crc, sizes, name length, extra length
+            let fields = [
+                u16_bytes(20),
+                u16_bytes(0),
+                u16_bytes(0),
+                u16_bytes(0),
+                u16_bytes(0),
+                u32_bytes(crc),
+                u32_bytes(contents.len() as u32),
+                u32_bytes(contents.len() as u32),
+                u16_bytes(name.len()),
+                u16_bytes(0),
+            ]
+            .concat();
+            central_dir.extend(u32_bytes(0x0201_4b50));
+            central_dir.extend(u16_bytes(20));
+            central_dir.extend(&fields);
+            // comment length, disk, attributes, offset of the local header
+            central_dir.extend(&[0; 10]);
+            central_dir.extend(u32_bytes(data.len() as u32));
+            central_dir.extend(*name);
+            data.extend(u32_bytes(0x0403_4b50));
+            data.extend(&fields);
+            data.extend(*name);
+            data.extend(*contents);
+        }
+        let central_dir_offset = data.len() as u32;
+        let central_dir_len = central_dir.len() as u32;
+        data.extend(central_dir);
+        data.extend(u32_bytes(0x0605_4b50));
+        data.extend(&[0; 4]);
+        data.extend(u16_bytes(entries.len()));
+        data.extend(u16_bytes(entries.len()));
+        data.extend(u32_bytes(central_dir_len));
+        data.extend(u32_bytes(central_dir_offset));
+        data.extend(u16_bytes(0));
+        data
+    }
+
+    #[test]
+    fn test_zip() {
+        assert_eq!(
+            compile(quote!(
+                zip = "../precompile/integration/fixture.zip",
+                zip_prefix = "purelib",
+                exclude = "**/tests",
+            )),
+            vec![
+                ("zipped".to_owned(), Some("zipped/__init__.py".to_owned())),
+                (
+                    "zipped.inner".to_owned(),
+                    Some("zipped/inner/__init__.py".to_owned())
+                ),
+                (
+                    "zipped.inner.leaf".to_owned(),
+                    Some("zipped/inner/leaf.py".to_owned())
+                ),
+            ]
+        );
+
+        let dir = env::temp_dir().join(format!("rustpython-compile-zip-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let expand_zip = |data: &[u8]| {
+            let path = dir.join("archive.zip");
+            fs::write(&path, data).unwrap();
+            let path = path.to_str().unwrap();
+            expand(quote!(zip = #path))
+        };
+
+        let valid = zip(&[(b"pkg/__init__.py", b"x = 1"), (b"top.py", b"")], true);
+        let ok = expand_zip(&valid);
+        assert!(ok.is_ok(), "{:?}", ok);
+        for truncated in &[&valid[..10], &valid[..valid.len() - 30]] {
+            let err = expand_zip(truncated).unwrap_err();
+            assert!(err.contains("Error reading zip archive"), "{}", err);
+        }
+        let err = expand_zip(&zip(&[(b"a.py", b"x = 1")], false)).unwrap_err();
+        assert!(err.contains("\\\"a.py\\\" is corrupt"), "{}", err);
+        let err = expand_zip(&zip(&[(b"\xff.py", b"")], true)).unwrap_err();
+        assert!(err.contains("invalid UTF-8 in the entry name"), "{}", err);
+        let err = expand_zip(&zip(&[(b"a.py", b""), (b"a/__init__.py", b"")], true)).unwrap_err();
+        assert!(err.contains("Duplicate module a"), "{}", err);
+        let err = expand_zip(&zip(&[(b"a.py", b"x = ")], true)).unwrap_err();
+        assert!(err.contains("Compile error"), "{}", err);
+
+        let err = expand(quote!(source = "", zip_prefix = "purelib")).unwrap_err();
+        assert!(err.contains("zip_prefix only works with zip"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-error-{}", process::id()));
+        fs::create_dir_all(dir.join("pkg")).unwrap();
+        fs::write(dir.join("pkg/__init__.py"), "").unwrap();
+        fs::write(dir.join("pkg/bad.py"), "x = 1\nif x:\n\ty = = 2\nz = *\n").unwrap();
+        fs::write(dir.join("pkg/worse.py"), "del 1\n").unwrap();
+        let dir_str = dir.to_str().unwrap();
+
+        let err = expand(quote!(dir = #dir_str)).unwrap_err();
+        let path = dir.join("pkg/bad.py").display().to_string();
+        assert!(
+            err.contains(&format!("Compile error in {} at line 3", path)),
+            "{}",
+            err
+        );
+        assert!(err.contains("\\n    \\ty = = 2\\n    \\t    ^"), "{}", err);
+        // All the syntax errors in a file and the errors of all files are reported
+        assert!(
+            err.contains(&format!("Compile error in {} at line 4", path)),
+            "{}",
+            err
+        );
+        let path = dir.join("pkg/worse.py").display().to_string();
+        assert!(
+            err.contains(&format!("Compile error in {} at line 1", path)),
+            "{}",
+            err
+        );
+
+        let err = expand(quote!(source = "x = (")).unwrap_err();
+        assert!(
+            err.contains("Compile error in <source> at line 1"),
+            "{}",
+            err
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// `x = 1` compiled by CPython 3.7 from gen.py.
+    const CPYTHON_37_PYC: &[u8] = &[
+        0x42, 0x0d, 0x0d, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0xe3, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x73, 0x08, 0x00, 0x00, 0x00, 0x64, 0x00, 0x5a,
+        0x00, 0x64, 0x01, 0x53, 0x00, 0x29, 0x02, 0xe9, 0x01, 0x00, 0x00, 0x00, 0x4e, 0x29, 0x01,
+        0xda, 0x01, 0x78, 0xa9, 0x00, 0x72, 0x03, 0x00, 0x00, 0x00, 0x72, 0x03, 0x00, 0x00, 0x00,
+        0xfa, 0x06, 0x67, 0x65, 0x6e, 0x2e, 0x70, 0x79, 0xda, 0x08, 0x3c, 0x6d, 0x6f, 0x64, 0x75,
+        0x6c, 0x65, 0x3e, 0x01, 0x00, 0x00, 0x00, 0xf3, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_pyc() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-pyc-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("gen.cpython-37.pyc");
+        fs::write(&path, CPYTHON_37_PYC).unwrap();
+        let path = path.to_str().unwrap();
+
+        assert_eq!(
+            compile(quote!(pyc = #path, module_name = "gen")),
+            vec![("gen".to_owned(), Some("gen.cpython-37.pyc".to_owned()))]
+        );
+        let err = expand(quote!(pyc = #path, source = "")).unwrap_err();
+        assert!(err.contains("Cannot have more than one source"), "{}", err);
+
+        fs::write(&path, &CPYTHON_37_PYC[..50]).unwrap();
+        let err = expand(quote!(pyc = #path)).unwrap_err();
+        assert!(err.contains("Error translating"), "{}", err);
+        let mut newer = CPYTHON_37_PYC.to_vec();
+        newer[0] = 0x55;
+        fs::write(&path, &newer).unwrap();
+        let err = expand(quote!(pyc = #path)).unwrap_err();
+        assert!(err.contains("unsupported magic"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
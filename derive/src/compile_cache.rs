@@ -0,0 +1,204 @@
+//! An on-disk cache of compiled code objects for `py_compile_bytecode!(cache = true)`, so that
+//! expanding the macro again doesn't recompile sources that haven't changed.
+//!
+//! Entries are keyed by a hash of everything that goes into compiling a module: the source, the
+//! module name, the mode, the optimization level, the bytecode format version and the compiler
+//! itself, by a hash of the sources of the compiler, parser and bytecode crates that the build
+//! script of this crate takes, so that changing the compiler makes the entries of the old one
+//! unused.
+
+use rustpython_bytecode::bytecode::{CodeObject, FORMAT_VERSION};
+use rustpython_compiler::compile;
+use siphasher::sip128::{Hasher128, SipHasher13};
+use std::env;
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const CACHE_DIR_NAME: &str = "py_compile_bytecode_cache";
+
+pub struct CompileCache {
+    dir: PathBuf,
+}
+
+impl CompileCache {
+    pub fn new(dir: PathBuf) -> Self {
+        CompileCache { dir }
+    }
+
+    /// The cache of the crate being compiled: in its `$OUT_DIR` if it has a build script, or
+    /// else in `$CARGO_TARGET_DIR`, or else in the `target` directory of its workspace.
+    pub fn for_current_crate() -> Option<Self> {
+        env::var_os("OUT_DIR")
+            .or_else(|| env::var_os("CARGO_TARGET_DIR"))
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var_os("CARGO_MANIFEST_DIR").map(|dir| workspace_target_dir(dir.as_ref()))
+            })
+            .map(|dir| CompileCache::new(dir.join(CACHE_DIR_NAME)))
+    }
+
+    /// Look up the code object for `source`, or compile it with `compile_fn` and store it.
+    /// Unreadable or corrupted entries are compiled again, and failing to store an entry isn't
+    /// an error.
+    pub fn get_or_compile<E>(
+        &self,
+        source: &str,
+        mode: compile::Mode,
+        module_name: &str,
+        optimize: u8,
+        compile_fn: impl FnOnce() -> Result<CodeObject, E>,
+    ) -> Result<CodeObject, E> {
+        let path = self.dir.join(format!(
+            "{:032x}.rpbc",
+            cache_key(source, mode, module_name, optimize)
+        ));
+        if let Some(code) = load_entry(&path) {
+            return Ok(code);
+        }
+        let code = compile_fn()?;
+        let _ = store_entry(&path, &code);
+        Ok(code)
+    }
+}
+
+/// The `target` directory next to the `Cargo.lock` of the workspace `manifest_dir` is in, or in
+/// `manifest_dir` itself if there's no `Cargo.lock` above it yet.
+fn workspace_target_dir(manifest_dir: &Path) -> PathBuf {
+    let root = manifest_dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.lock").is_file())
+        .unwrap_or(manifest_dir);
+    root.join("target")
+}
+
+fn cache_key(source: &str, mode: compile::Mode, module_name: &str, optimize: u8) -> u128 {
+    let mode = match mode {
+        compile::Mode::Exec => "exec",
+        compile::Mode::Eval => "eval",
+        compile::Mode::Single => "single",
+    };
+    let mut hasher = SipHasher13::new_with_keys(0, 0);
+    hasher.write_u32(FORMAT_VERSION);
+    for part in &[
+        env!("CARGO_PKG_VERSION"),
+        env!("RUSTPYTHON_COMPILER_HASH"),
+        mode,
+        module_name,
+        source,
+    ] {
+        hasher.write_usize(part.len());
+        hasher.write(part.as_bytes());
+    }
+    hasher.write_u8(optimize);
+    u128::from_le_bytes(hasher.finish128().as_bytes())
+}
+
+/// An entry is the content hash of the code object followed by its serialization, so that a
+/// damaged file isn't mistaken for valid bytecode.
+fn load_entry(path: &Path) -> Option<CodeObject> {
+    let data = fs::read(path).ok()?;
+    if data.len() < 16 {
+        return None;
+    }
+    let (hash, bytes) = data.split_at(16);
+    let code = CodeObject::from_bytes(bytes).ok()?;
+    if code.content_hash(true).to_le_bytes() == hash {
+        Some(code)
+    } else {
+        None
+    }
+}
+
+/// Write the entry to a file of its own first and rename it into place, so that concurrent
+/// expansions never see a partially written entry.
+fn store_entry(path: &Path, code: &CodeObject) -> std::io::Result<()> {
+    static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    let mut data = code.content_hash(true).to_le_bytes().to_vec();
+    data.extend(code.to_bytes());
+    let temp_path = path.with_extension(format!(
+        "tmp.{}.{}",
+        process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, path).map_err(|err| {
+        let _ = fs::remove_file(&temp_path);
+        err
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustpython_compiler::frozen;
+    use std::cell::Cell;
+
+    /// Compile every module in `dir` through the cache, and return how many were compiled
+    fn compile_dir(cache: &CompileCache, dir: &Path) -> usize {
+        let compiled = Cell::new(0);
+        for module in frozen::find_modules(dir).unwrap() {
+            let source = fs::read_to_string(&module.path).unwrap();
+            let code = cache
+                .get_or_compile(&source, compile::Mode::Exec, &module.name, 0, || {
+                    compiled.set(compiled.get() + 1);
+                    compile::compile(&source, compile::Mode::Exec, module.name.clone(), 0)
+                })
+                .unwrap();
+            assert_eq!(code.source_path, module.name);
+        }
+        compiled.get()
+    }
+
+    #[test]
+    fn test_only_changed_sources_are_recompiled() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-cache-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let sources = dir.join("sources");
+        fs::create_dir_all(sources.join("pkg")).unwrap();
+        fs::write(sources.join("pkg").join("__init__.py"), "").unwrap();
+        for i in 0..10 {
+            let source = format!("x = {}\n", i);
+            fs::write(sources.join("pkg").join(format!("mod{}.py", i)), source).unwrap();
+        }
+        let cache = CompileCache::new(dir.join("cache"));
+
+        assert_eq!(compile_dir(&cache, &sources), 11);
+        assert_eq!(compile_dir(&cache, &sources), 0);
+
+        fs::write(sources.join("pkg").join("mod3.py"), "x = 'changed'\n").unwrap();
+        assert_eq!(compile_dir(&cache, &sources), 1);
+        assert_eq!(compile_dir(&cache, &sources), 0);
+
+        // The same sources under other names compile to different code objects
+        fs::rename(sources.join("pkg"), sources.join("other")).unwrap();
+        assert_eq!(compile_dir(&cache, &sources), 11);
+
+        // Corrupted entries are compiled again and replaced
+        for entry in fs::read_dir(dir.join("cache")).unwrap() {
+            fs::write(entry.unwrap().path(), b"garbage").unwrap();
+        }
+        assert_eq!(compile_dir(&cache, &sources), 11);
+        assert_eq!(compile_dir(&cache, &sources), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_target_dir() {
+        let dir = env::temp_dir().join(format!("rustpython-compile-target-{}", process::id()));
+        let member = dir.join("workspace").join("member");
+        fs::create_dir_all(&member).unwrap();
+        assert_eq!(workspace_target_dir(&member), member.join("target"));
+        fs::write(dir.join("workspace").join("Cargo.lock"), "").unwrap();
+        assert_eq!(
+            workspace_target_dir(&member),
+            dir.join("workspace").join("target")
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
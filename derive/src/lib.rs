@@ -10,8 +10,12 @@ extern crate maplit;
 #[macro_use]
 mod error;
 mod compile_bytecode;
+mod compile_cache;
 mod from_args;
 mod pyclass;
+mod pymodule;
+mod static_code;
+mod zip_archive;
 
 use error::{extract_spans, Diagnostic};
 use proc_macro::TokenStream;
@@ -53,6 +57,13 @@ pub fn pystruct_sequence(attr: TokenStream, item: TokenStream) -> TokenStream {
     result_to_tokens(pyclass::impl_pystruct_sequence(attr, item))
 }
 
+#[proc_macro_attribute]
+pub fn pymodule(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as AttributeArgs);
+    let item = parse_macro_input!(item as Item);
+    result_to_tokens(pymodule::impl_pymodule(attr, item))
+}
+
 #[cfg_attr(feature = "proc-macro-hack", proc_macro_hack::proc_macro_hack)]
 #[cfg_attr(not(feature = "proc-macro-hack"), proc_macro)]
 pub fn py_compile_bytecode(input: TokenStream) -> TokenStream {
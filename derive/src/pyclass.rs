@@ -61,8 +61,8 @@ impl Class {
         let mut attr_idxs = Vec::new();
         for (i, meta) in attrs
             .iter()
-            .filter_map(|attr| attr.parse_meta().ok())
             .enumerate()
+            .filter_map(|(i, attr)| Some((i, attr.parse_meta().ok()?)))
         {
             let meta_span = meta.span();
             let name = meta.name();
@@ -149,24 +149,40 @@ impl Class {
                         NestedMeta::Literal(_) => continue,
                     };
                     match meta {
-                        Meta::NameValue(name_value) => {
-                            if name_value.ident == "name" {
-                                if let Lit::Str(s) = &name_value.lit {
-                                    py_name = Some(s.value());
-                                } else {
-                                    bail_span!(
-                                        &sig.ident,
-                                        "#[pyproperty(name = ...)] must be a string"
-                                    );
-                                }
-                            }
-                        }
-                        Meta::Word(ident) => {
-                            if ident == "setter" {
-                                setter = true;
+                        Meta::NameValue(ref name_value) if name_value.ident == "name" => {
+                            if let Lit::Str(s) = &name_value.lit {
+                                py_name = Some(s.value());
+                            } else {
+                                bail_span!(
+                                    &sig.ident,
+                                    "#[pyproperty(name = ...)] must be a string"
+                                );
                             }
                         }
-                        _ => {}
+                        Meta::Word(ref ident) if ident == "setter" => setter = true,
+                        other => bail_span!(
+                            other,
+                            "#[pyproperty] only accepts `name = \"...\"` and `setter`"
+                        ),
+                    }
+                }
+                // the receiver and the optional trailing &VirtualMachine, plus the new value
+                // for a setter
+                let (min_args, max_args) = if setter { (2, 3) } else { (1, 2) };
+                let arg_count = sig.decl.inputs.len();
+                if arg_count < min_args || arg_count > max_args {
+                    if setter {
+                        bail_span!(
+                            &sig.ident,
+                            "A #[pyproperty(setter)] fn must take the object, the new value \
+                             and optionally a &VirtualMachine"
+                        )
+                    } else {
+                        bail_span!(
+                            &sig.ident,
+                            "A #[pyproperty] getter fn must take the object and optionally a \
+                             &VirtualMachine"
+                        )
                     }
                 }
                 let py_name = match py_name {
@@ -227,7 +243,7 @@ impl Class {
                 attr_idxs.push(i);
             }
         }
-        for idx in attr_idxs {
+        for idx in attr_idxs.into_iter().rev() {
             attrs.remove(idx);
         }
         Ok(())
@@ -283,7 +299,7 @@ pub fn impl_pyimpl(_attr: AttributeArgs, item: Item) -> Result<TokenStream2, Dia
                     push_err_span!(
                         diagnostics,
                         prop.1.unwrap(),
-                        "Property {:?} is missing a getter",
+                        "Property setter {:?} has no #[pyproperty] getter with the same name",
                         name
                     );
                     return TokenStream2::new();
@@ -306,7 +322,7 @@ pub fn impl_pyimpl(_attr: AttributeArgs, item: Item) -> Result<TokenStream2, Dia
             item_ident,
             py_name,
         } => Some(quote! {
-            class.set_str_attr(#py_name, ctx.new_rustfunc(Self::#item_ident));
+            class.set_str_attr(#py_name, ctx.new_rustfunc_named(Self::#item_ident, #py_name, None));
         }),
         ClassItem::ClassMethod {
             item_ident,
@@ -342,6 +358,26 @@ pub fn impl_pyimpl(_attr: AttributeArgs, item: Item) -> Result<TokenStream2, Dia
     Ok(ret)
 }
 
+/// Joins the lines of the `///` comments (or `#[doc = "..."]` attributes) of an item.
+pub(crate) fn extract_doc(attrs: &[Attribute]) -> Option<String> {
+    let mut doc: Option<Vec<String>> = None;
+    for attr in attrs.iter() {
+        if attr.path.is_ident("doc") {
+            let meta = attr.parse_meta().expect("expected doc attr to be a meta");
+            if let Meta::NameValue(name_value) = meta {
+                if let Lit::Str(s) = name_value.lit {
+                    let val = s.value().trim().to_string();
+                    match doc {
+                        Some(ref mut doc) => doc.push(val),
+                        None => doc = Some(vec![val]),
+                    }
+                }
+            }
+        }
+    }
+    doc.map(|doc| doc.join("\n"))
+}
+
 fn generate_class_def(
     ident: &Ident,
     attr_name: &'static str,
@@ -368,26 +404,8 @@ fn generate_class_def(
     }
     let class_name = class_name.unwrap_or_else(|| ident.to_string());
 
-    let mut doc: Option<Vec<String>> = None;
-    for attr in attrs.iter() {
-        if attr.path.is_ident("doc") {
-            let meta = attr.parse_meta().expect("expected doc attr to be a meta");
-            if let Meta::NameValue(name_value) = meta {
-                if let Lit::Str(s) = name_value.lit {
-                    let val = s.value().trim().to_string();
-                    match doc {
-                        Some(ref mut doc) => doc.push(val),
-                        None => doc = Some(vec![val]),
-                    }
-                }
-            }
-        }
-    }
-    let doc = match doc {
-        Some(doc) => {
-            let doc = doc.join("\n");
-            quote!(Some(#doc))
-        }
+    let doc = match extract_doc(attrs) {
+        Some(doc) => quote!(Some(#doc)),
         None => quote!(None),
     };
 
@@ -0,0 +1,469 @@
+use super::Diagnostic;
+use crate::pyclass::extract_doc;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::collections::HashMap;
+use syn::{
+    parse_quote, spanned::Spanned, Attribute, AttributeArgs, FnArg, Ident, Item, ItemFn, Lit, Meta,
+    NestedMeta, Type,
+};
+
+/// The most arguments a native function can take besides the `&VirtualMachine`, see
+/// `into_py_native_func_tuple!` in the vm crate.
+const MAX_FUNCTION_ARGS: usize = 5;
+
+enum ModuleItem {
+    Function {
+        item_ident: Ident,
+        py_name: String,
+        doc: Option<String>,
+    },
+    /// A `const` or `static`, converted with `IntoPyObject`.
+    Constant { item_ident: Ident, py_name: String },
+    /// A `fn(&VirtualMachine)` that's called once to create the attribute.
+    Attribute { item_ident: Ident, py_name: String },
+}
+
+#[derive(Default)]
+struct Module {
+    items: Vec<ModuleItem>,
+    names: HashMap<String, Span>,
+}
+
+impl Module {
+    fn add_item(&mut self, item: ModuleItem, span: Span) -> Result<(), Diagnostic> {
+        let py_name = match &item {
+            ModuleItem::Function { py_name, .. }
+            | ModuleItem::Constant { py_name, .. }
+            | ModuleItem::Attribute { py_name, .. } => py_name,
+        };
+        if self.names.insert(py_name.clone(), span).is_some() {
+            return Err(Diagnostic::span_error(
+                span,
+                format!("Duplicate name {:?} in #[pymodule]", py_name),
+            ));
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn extract_item(&mut self, item: &mut Item) -> Result<(), Diagnostic> {
+        let (attrs, ident) = match item {
+            Item::Fn(func) => (&mut func.attrs, func.ident.clone()),
+            Item::Const(konst) => (&mut konst.attrs, konst.ident.clone()),
+            Item::Static(statik) => (&mut statik.attrs, statik.ident.clone()),
+            other => {
+                for attr in item_attrs(other) {
+                    if attr.path.is_ident("pyfunction") || attr.path.is_ident("pyattr") {
+                        bail_span!(
+                            attr,
+                            "#[pyfunction] and #[pyattr] can only be on a fn, const or static"
+                        )
+                    }
+                }
+                return Ok(());
+            }
+        };
+
+        let mut py_attr = None;
+        let mut attr_idxs = Vec::new();
+        for (i, attr) in attrs.iter().enumerate() {
+            let kind = if attr.path.is_ident("pyfunction") {
+                "pyfunction"
+            } else if attr.path.is_ident("pyattr") {
+                "pyattr"
+            } else {
+                continue;
+            };
+            if py_attr.is_some() {
+                bail_span!(attr, "Only one #[pyfunction] or #[pyattr] per item")
+            }
+            py_attr = Some((kind, parse_name(attr, kind)?, attr.span()));
+            attr_idxs.push(i);
+        }
+        for idx in attr_idxs.into_iter().rev() {
+            attrs.remove(idx);
+        }
+        let (kind, py_name, span) = match py_attr {
+            Some(py_attr) => py_attr,
+            None => return Ok(()),
+        };
+        let py_name = py_name.unwrap_or_else(|| ident.to_string());
+
+        let module_item = match (kind, item) {
+            ("pyfunction", Item::Fn(func)) => {
+                check_function_sig(func)?;
+                ModuleItem::Function {
+                    item_ident: ident,
+                    py_name,
+                    doc: extract_doc(&func.attrs),
+                }
+            }
+            ("pyfunction", other) => {
+                bail_span!(other, "#[pyfunction] can only be on a fn")
+            }
+            ("pyattr", Item::Fn(func)) => {
+                if !func.decl.generics.params.is_empty() || func.decl.inputs.len() != 1 {
+                    bail_span!(
+                        func.ident,
+                        "A #[pyattr] fn must take only a `&VirtualMachine`"
+                    )
+                }
+                ModuleItem::Attribute {
+                    item_ident: ident,
+                    py_name,
+                }
+            }
+            _ => ModuleItem::Constant {
+                item_ident: ident,
+                py_name,
+            },
+        };
+        self.add_item(module_item, span)
+    }
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::ExternCrate(item) => &item.attrs,
+        Item::Use(item) => &item.attrs,
+        Item::Mod(item) => &item.attrs,
+        Item::ForeignMod(item) => &item.attrs,
+        Item::Type(item) => &item.attrs,
+        Item::Existential(item) => &item.attrs,
+        Item::Struct(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        Item::Trait(item) => &item.attrs,
+        Item::TraitAlias(item) => &item.attrs,
+        Item::Impl(item) => &item.attrs,
+        Item::Macro(item) => &item.attrs,
+        Item::Macro2(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Parses the `name = "..."` out of `#[pyfunction(name = "...")]` or `#[pyattr(name = "...")]`.
+fn parse_name(attr: &Attribute, kind: &str) -> Result<Option<String>, Diagnostic> {
+    let meta = attr.parse_meta().map_err(|_| {
+        err_span!(
+            attr,
+            "#[{}] must be of the form #[{}(name = \"...\")]",
+            kind,
+            kind
+        )
+    })?;
+    let nesteds = match meta {
+        Meta::Word(_) => return Ok(None),
+        Meta::List(list) => list.nested,
+        Meta::NameValue(_) => bail_span!(
+            meta,
+            "#[{} = \"...\"] cannot be a name/value, you probably meant #[{}(name = \"...\")]",
+            kind,
+            kind
+        ),
+    };
+    let mut py_name = None;
+    for nested in nesteds {
+        match nested {
+            NestedMeta::Meta(Meta::NameValue(ref name_value)) if name_value.ident == "name" => {
+                if let Lit::Str(s) = &name_value.lit {
+                    py_name = Some(s.value());
+                } else {
+                    bail_span!(name_value.lit, "#[{}(name = ...)] must be a string", kind);
+                }
+            }
+            other => bail_span!(other, "#[{}] only accepts `name = \"...\"`", kind),
+        }
+    }
+    Ok(py_name)
+}
+
+/// Checks that a fn can be turned into a native function with `IntoPyNativeFunc`.
+fn check_function_sig(func: &ItemFn) -> Result<(), Diagnostic> {
+    if func.constness.is_some()
+        || func.unsafety.is_some()
+        || func.asyncness.is_some()
+        || func.abi.is_some()
+    {
+        bail_span!(
+            func.ident,
+            "A #[pyfunction] fn can't be const, unsafe, async or extern"
+        )
+    }
+    if !func.decl.generics.params.is_empty() {
+        bail_span!(func.decl.generics, "A #[pyfunction] fn can't be generic")
+    }
+    if let Some(variadic) = &func.decl.variadic {
+        bail_span!(
+            variadic,
+            "A #[pyfunction] fn can't be variadic, use `Args` instead"
+        )
+    }
+    let mut arg_types = Vec::new();
+    for arg in func.decl.inputs.iter() {
+        match arg {
+            FnArg::Captured(arg) => arg_types.push(&arg.ty),
+            FnArg::Ignored(ty) => arg_types.push(ty),
+            other => bail_span!(other, "A #[pyfunction] fn can't take self"),
+        }
+    }
+    let is_ref = |ty: &Type| match ty {
+        Type::Reference(_) => true,
+        _ => false,
+    };
+    // either `fn(args..., vm: &VirtualMachine)` or `fn(vm: &VirtualMachine, args: PyFuncArgs)`
+    let raw = arg_types.len() == 2 && is_ref(arg_types[0]) && !is_ref(arg_types[1]);
+    if !raw && !arg_types.last().map_or(false, |ty| is_ref(ty)) {
+        bail_span!(
+            func.ident,
+            "A #[pyfunction] fn must take `vm: &VirtualMachine` as its last argument"
+        )
+    }
+    if arg_types.len() > MAX_FUNCTION_ARGS + 1 {
+        bail_span!(
+            func.decl.inputs,
+            "A #[pyfunction] fn can take at most {} arguments besides the vm, use a \
+             #[derive(FromArgs)] struct to take more",
+            MAX_FUNCTION_ARGS
+        )
+    }
+    Ok(())
+}
+
+pub fn impl_pymodule(attr: AttributeArgs, item: Item) -> Result<TokenStream2, Diagnostic> {
+    let mut module = match item {
+        Item::Mod(module) => module,
+        other => bail_span!(other, "#[pymodule] can only be on a mod"),
+    };
+    let mut module_name = None;
+    for attr in attr {
+        match attr {
+            NestedMeta::Meta(Meta::NameValue(ref name_value)) if name_value.ident == "name" => {
+                if let Lit::Str(s) = &name_value.lit {
+                    module_name = Some(s.value());
+                } else {
+                    bail_span!(name_value.lit, "#[pymodule(name = ...)] must be a string");
+                }
+            }
+            other => bail_span!(other, "#[pymodule] only accepts `name = \"...\"`"),
+        }
+    }
+    let module_name = module_name.unwrap_or_else(|| module.ident.to_string());
+    let set_doc = extract_doc(&module.attrs).map(|doc| {
+        quote! {
+            vm.set_attr(&module, "__doc__", vm.new_str(#doc.to_owned())).unwrap();
+        }
+    });
+
+    let items = match &mut module.content {
+        Some((_, items)) => items,
+        None => bail_span!(
+            module.ident,
+            "#[pymodule] can only be on a mod with a body, not on `mod name;`"
+        ),
+    };
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut py_module = Module::default();
+    for item in items.iter_mut() {
+        push_diag_result!(diagnostics, py_module.extract_item(item));
+    }
+    Diagnostic::from_vec(diagnostics)?;
+
+    let set_attrs = py_module.items.iter().map(|item| {
+        let (py_name, value) = match item {
+            ModuleItem::Function {
+                item_ident,
+                py_name,
+                doc,
+            } => {
+                let doc = match doc {
+                    Some(doc) => quote!(Some(#doc)),
+                    None => quote!(None),
+                };
+                (
+                    py_name,
+                    quote!(vm.ctx.new_rustfunc_named(#item_ident, #py_name, #doc)),
+                )
+            }
+            ModuleItem::Constant {
+                item_ident,
+                py_name,
+            } => (
+                py_name,
+                quote!(::rustpython_vm::pyobject::IntoPyObject::into_pyobject(#item_ident, vm)
+                    .unwrap()),
+            ),
+            ModuleItem::Attribute {
+                item_ident,
+                py_name,
+            } => (
+                py_name,
+                quote!(::rustpython_vm::pyobject::IntoPyObject::into_pyobject(#item_ident(vm), vm)
+                    .unwrap()),
+            ),
+        };
+        quote! {
+            vm.set_attr(module, #py_name, #value).unwrap();
+        }
+    });
+
+    let generated: Vec<Item> = vec![
+        parse_quote! {
+            /// Adds the functions and attributes of this module to `module`.
+            pub fn extend_module(
+                vm: &::rustpython_vm::vm::VirtualMachine,
+                module: &::rustpython_vm::pyobject::PyObjectRef,
+            ) {
+                #(#set_attrs)*
+            }
+        },
+        parse_quote! {
+            pub fn make_module(
+                vm: &::rustpython_vm::vm::VirtualMachine,
+            ) -> ::rustpython_vm::pyobject::PyObjectRef {
+                let module = vm.new_module(#module_name, vm.ctx.new_dict());
+                #set_doc
+                extend_module(vm, &module);
+                module
+            }
+        },
+    ];
+    items.extend(generated);
+
+    Ok(quote!(#module))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::impl_pymodule;
+    use quote::ToTokens;
+    use syn::{parse_quote, Item};
+
+    fn expand(item: Item) -> String {
+        match impl_pymodule(Vec::new(), item) {
+            Ok(tokens) => tokens.to_string(),
+            Err(diagnostic) => diagnostic.into_token_stream().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_expansion() {
+        let expanded = expand(parse_quote! {
+            /// The docs
+            mod sample {
+                /// Adds
+                #[pyfunction]
+                fn add(a: i32, b: OptionalArg<i32>, vm: &VirtualMachine) -> i32 {
+                    a + b.unwrap_or(1)
+                }
+                #[pyfunction(name = "raw")]
+                fn raw_fn(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
+                    Ok(vm.get_none())
+                }
+                #[pyattr]
+                const ANSWER: i32 = 42;
+                #[pyattr(name = "words")]
+                fn make_words(vm: &VirtualMachine) -> PyObjectRef {
+                    vm.ctx.new_list(vec![])
+                }
+                fn helper() {}
+            }
+        })
+        .replace(' ', "");
+        assert!(!expanded.contains("compile_error"), "{}", expanded);
+        assert!(!expanded.contains("pyfunction"), "{}", expanded);
+        assert!(!expanded.contains("pyattr"), "{}", expanded);
+        for expected in &[
+            "new_rustfunc_named(add,\"add\",Some(\"Adds\"))",
+            "new_rustfunc_named(raw_fn,\"raw\",None)",
+            "\"ANSWER\"",
+            "into_pyobject(make_words(vm),vm)",
+            "new_module(\"sample\"",
+            "\"__doc__\",vm.new_str(\"Thedocs\"",
+            "fnmake_module",
+            "fnextend_module",
+        ] {
+            assert!(
+                expanded.contains(expected),
+                "{:?} in {}",
+                expected,
+                expanded
+            );
+        }
+        assert!(!expanded.contains("\"helper\""), "{}", expanded);
+    }
+
+    fn assert_error(item: Item, message: &str) {
+        let expanded = expand(item);
+        assert!(expanded.contains("compile_error"), "{}", expanded);
+        assert!(expanded.contains(message), "{:?} in {}", message, expanded);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_error(
+            parse_quote!(
+                fn not_a_mod() {}
+            ),
+            "#[pymodule] can only be on a mod",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyfunction]
+                    fn f(a: i32) {}
+                }
+            },
+            "must take `vm: &VirtualMachine` as its last argument",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyfunction]
+                    fn f<T>(a: T, vm: &VirtualMachine) {}
+                }
+            },
+            "can't be generic",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyfunction]
+                    fn f(a: A, b: B, c: C, d: D, e: E, f: F, vm: &VirtualMachine) {}
+                }
+            },
+            "at most 5 arguments",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyfunction]
+                    fn f(vm: &VirtualMachine) {}
+                    #[pyfunction(name = "f")]
+                    fn g(vm: &VirtualMachine) {}
+                }
+            },
+            "Duplicate name \\\"f\\\"",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyattr]
+                    struct S;
+                }
+            },
+            "can only be on a fn, const or static",
+        );
+        assert_error(
+            parse_quote! {
+                mod m {
+                    #[pyfunction(setter)]
+                    fn f(vm: &VirtualMachine) {}
+                }
+            },
+            "only accepts `name = \\\"...\\\"`",
+        );
+    }
+}
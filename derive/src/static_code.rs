@@ -0,0 +1,421 @@
+//! The tokens of the `StaticCodeObject` of a code object, which `py_compile_bytecode!` puts in a
+//! `static`; see `rustpython_bytecode::static_code`.
+//!
+//! The instructions are written out through their `Serialize` impls, so that this doesn't have
+//! to follow every change to `Instruction`: a `Tokens` serializer turns the serde data model of
+//! an instruction back into the Rust expression that builds it. The names of the types it
+//! refers to are imported by `imports`.
+
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use rustpython_bytecode::bytecode::{CodeObject, Constant, Varargs};
+use serde::ser::{self, Serialize};
+use std::fmt;
+use syn::{Ident, LitByteStr, LitStr};
+
+/// The `use` items for the names of the tokens of `code_object`, to put in the block the tokens
+/// are expanded in.
+pub fn imports() -> TokenStream2 {
+    quote! {
+        #[allow(unused_imports)]
+        use ::rustpython_vm::bytecode::{
+            BinaryOperator, CallType, ComparisonOperator, ConversionFlag, Instruction, Label,
+            LineTable, NameScope, UnaryOperator,
+        };
+        use ::rustpython_vm::static_code::{StaticCodeObject, StaticConstant, StaticVarargs};
+    }
+}
+
+/// The `StaticCodeObject` expression of `code`.
+pub fn code_object(code: &CodeObject) -> TokenStream2 {
+    let instructions = code.instructions.iter().map(|instruction| {
+        instruction
+            .serialize(Tokens)
+            .expect("Instructions must be serializable to tokens")
+    });
+    // The label map is a HashMap, so it's sorted for the same code to expand to the same tokens
+    let mut label_map: Vec<_> = code.label_map.iter().collect();
+    label_map.sort();
+    let label_map = label_map.into_iter().map(|(label, index)| {
+        let label = label
+            .serialize(Tokens)
+            .expect("Labels must be serializable to tokens");
+        quote!((#label, #index))
+    });
+    let (deltas, len, columns) = code.line_table.raw_parts();
+    let deltas = LitByteStr::new(deltas, Span::call_site());
+    let columns = LitByteStr::new(columns, Span::call_site());
+    let flags = code.flags.bits();
+    let posonlyarg_count = code.posonlyarg_count;
+    let arg_names = strs(&code.arg_names);
+    let varargs = varargs_tokens(&code.varargs);
+    let kwonlyarg_names = strs(&code.kwonlyarg_names);
+    let varkeywords = varargs_tokens(&code.varkeywords);
+    let source_path = &code.source_path;
+    let first_line_number = code.first_line_number;
+    let obj_name = &code.obj_name;
+    let constants = code.constants.iter().map(constant_tokens);
+    let names = strs(&code.names);
+    let cache_slots = code.cache_slots.iter();
+    quote! {
+        StaticCodeObject {
+            instructions: &[#(#instructions),*],
+            label_map: &[#(#label_map),*],
+            line_table: LineTable::from_static(#deltas, #len, #columns),
+            flags: #flags,
+            posonlyarg_count: #posonlyarg_count,
+            arg_names: #arg_names,
+            varargs: #varargs,
+            kwonlyarg_names: #kwonlyarg_names,
+            varkeywords: #varkeywords,
+            source_path: #source_path,
+            first_line_number: #first_line_number,
+            obj_name: #obj_name,
+            constants: &[#(#constants),*],
+            names: #names,
+            cache_slots: &[#(#cache_slots),*],
+        }
+    }
+}
+
+fn strs(strings: &[String]) -> TokenStream2 {
+    quote!(&[#(#strings),*])
+}
+
+fn varargs_tokens(varargs: &Varargs) -> TokenStream2 {
+    match varargs {
+        Varargs::None => quote!(StaticVarargs::None),
+        Varargs::Unnamed => quote!(StaticVarargs::Unnamed),
+        Varargs::Named(name) => quote!(StaticVarargs::Named(#name)),
+    }
+}
+
+fn constant_tokens(constant: &Constant) -> TokenStream2 {
+    match constant {
+        Constant::Integer { value } => {
+            let value = LitByteStr::new(&value.to_signed_bytes_le(), Span::call_site());
+            quote!(StaticConstant::Integer { value: #value })
+        }
+        Constant::Float { value } => {
+            let bits = value.to_bits();
+            quote!(StaticConstant::Float { bits: #bits })
+        }
+        Constant::Complex { value } => {
+            let (re_bits, im_bits) = (value.re.to_bits(), value.im.to_bits());
+            quote!(StaticConstant::Complex {
+                re_bits: #re_bits,
+                im_bits: #im_bits,
+            })
+        }
+        Constant::Boolean { value } => quote!(StaticConstant::Boolean { value: #value }),
+        Constant::String { value } => {
+            let value = LitStr::new(value, Span::call_site());
+            quote!(StaticConstant::String { value: #value })
+        }
+        Constant::Bytes { value } => {
+            let value = LitByteStr::new(value, Span::call_site());
+            quote!(StaticConstant::Bytes { value: #value })
+        }
+        Constant::Code { code } => {
+            let code = code_object(code);
+            quote!(StaticConstant::Code { code: &#code })
+        }
+        Constant::Tuple { elements } => {
+            let elements = elements.iter().map(constant_tokens);
+            quote!(StaticConstant::Tuple {
+                elements: &[#(#elements),*],
+            })
+        }
+        Constant::None => quote!(StaticConstant::None),
+        Constant::Ellipsis => quote!(StaticConstant::Ellipsis),
+    }
+}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+fn unsupported<T>(what: &str) -> Result<T, Error> {
+    Err(Error(format!("{} can't be written as tokens", what)))
+}
+
+/// Serializes the parts of instructions into the expressions that build them: enums by the name
+/// of their type, which `imports` brings into scope, newtype structs like `Label` with their
+/// `const fn new`, and sequences, which are `Cow`s in instructions, as borrowed slices.
+struct Tokens;
+
+impl ser::Serializer for Tokens {
+    type Ok = TokenStream2;
+    type Error = Error;
+    type SerializeSeq = Seq;
+    type SerializeTuple = Seq;
+    type SerializeTupleStruct = ser::Impossible<TokenStream2, Error>;
+    type SerializeTupleVariant = ser::Impossible<TokenStream2, Error>;
+    type SerializeMap = ser::Impossible<TokenStream2, Error>;
+    type SerializeStruct = ser::Impossible<TokenStream2, Error>;
+    type SerializeStructVariant = StructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<TokenStream2, Error> {
+        Ok(quote!(#v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<TokenStream2, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<TokenStream2, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<TokenStream2, Error> {
+        self.serialize_i64(v.into())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<TokenStream2, Error> {
+        let literal = Literal::u64_unsuffixed(v.wrapping_abs() as u64);
+        Ok(if v < 0 {
+            quote!(-#literal)
+        } else {
+            quote!(#literal)
+        })
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<TokenStream2, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<TokenStream2, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<TokenStream2, Error> {
+        self.serialize_u64(v.into())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<TokenStream2, Error> {
+        let literal = Literal::u64_unsuffixed(v);
+        Ok(quote!(#literal))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<TokenStream2, Error> {
+        unsupported("a float")
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<TokenStream2, Error> {
+        unsupported("a float")
+    }
+
+    fn serialize_char(self, v: char) -> Result<TokenStream2, Error> {
+        Ok(quote!(#v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<TokenStream2, Error> {
+        Ok(quote!(#v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<TokenStream2, Error> {
+        let bytes = LitByteStr::new(v, Span::call_site());
+        Ok(quote!(#bytes))
+    }
+
+    fn serialize_none(self) -> Result<TokenStream2, Error> {
+        Ok(quote!(None))
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<TokenStream2, Error> {
+        let value = value.serialize(Tokens)?;
+        Ok(quote!(Some(#value)))
+    }
+
+    fn serialize_unit(self) -> Result<TokenStream2, Error> {
+        Ok(quote!(()))
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<TokenStream2, Error> {
+        let name = ident(name);
+        Ok(quote!(#name))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<TokenStream2, Error> {
+        let (name, variant) = (ident(name), ident(variant));
+        Ok(quote!(#name::#variant))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<TokenStream2, Error> {
+        let name = ident(name);
+        let value = value.serialize(Tokens)?;
+        Ok(quote!(#name::new(#value)))
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<TokenStream2, Error> {
+        let (name, variant) = (ident(name), ident(variant));
+        let value = value.serialize(Tokens)?;
+        Ok(quote!(#name::#variant(#value)))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Seq, Error> {
+        Ok(Seq {
+            elements: Vec::new(),
+            borrowed: true,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Seq, Error> {
+        Ok(Seq {
+            elements: Vec::new(),
+            borrowed: false,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        unsupported(name)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        unsupported(name)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        unsupported("a map")
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        unsupported(name)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructVariant, Error> {
+        Ok(StructVariant {
+            path: {
+                let (name, variant) = (ident(name), ident(variant));
+                quote!(#name::#variant)
+            },
+            fields: Vec::new(),
+        })
+    }
+}
+
+fn ident(name: &str) -> Ident {
+    Ident::new(name, Span::call_site())
+}
+
+/// A sequence, as a borrowed slice, or a tuple.
+struct Seq {
+    elements: Vec<TokenStream2>,
+    borrowed: bool,
+}
+
+impl Seq {
+    fn push<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.elements.push(value.serialize(Tokens)?);
+        Ok(())
+    }
+
+    fn tokens(self) -> TokenStream2 {
+        let elements = self.elements;
+        if self.borrowed {
+            quote!(::std::borrow::Cow::Borrowed(&[#(#elements),*]))
+        } else {
+            quote!((#(#elements,)*))
+        }
+    }
+}
+
+impl ser::SerializeSeq for Seq {
+    type Ok = TokenStream2;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<TokenStream2, Error> {
+        Ok(self.tokens())
+    }
+}
+
+impl ser::SerializeTuple for Seq {
+    type Ok = TokenStream2;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<TokenStream2, Error> {
+        Ok(self.tokens())
+    }
+}
+
+struct StructVariant {
+    path: TokenStream2,
+    fields: Vec<TokenStream2>,
+}
+
+impl ser::SerializeStructVariant for StructVariant {
+    type Ok = TokenStream2;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        let key = ident(key);
+        let value = value.serialize(Tokens)?;
+        self.fields.push(quote!(#key: #value));
+        Ok(())
+    }
+
+    fn end(self) -> Result<TokenStream2, Error> {
+        let StructVariant { path, fields } = self;
+        Ok(quote!(#path { #(#fields),* }))
+    }
+}
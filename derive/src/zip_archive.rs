@@ -0,0 +1,150 @@
+//! Just enough of the zip format to read the Python sources out of an archive: the entries of
+//! the central directory, stored or deflated, without encryption, zip64 or multiple disks.
+
+use flate2::read::DeflateDecoder;
+use std::io::Read;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0605_4b50;
+const END_OF_CENTRAL_DIR_SIZE: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+const FLAG_ENCRYPTED: u16 = 1;
+
+pub struct ZipEntry {
+    /// The path of the entry in the archive, with `/` separators.
+    pub name: String,
+    method: u16,
+    crc32: u32,
+    compressed_size: usize,
+    size: usize,
+    local_header_offset: usize,
+}
+
+pub struct ZipArchive<'a> {
+    data: &'a [u8],
+    pub entries: Vec<ZipEntry>,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from(bytes[0]) | u16::from(bytes[1]) << 8)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(
+        u32::from(bytes[0])
+            | u32::from(bytes[1]) << 8
+            | u32::from(bytes[2]) << 16
+            | u32::from(bytes[3]) << 24,
+    )
+}
+
+fn truncated() -> String {
+    "the archive is truncated or corrupt".to_string()
+}
+
+impl<'a> ZipArchive<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, String> {
+        // the end of central directory record is followed by a comment of up to 64KiB
+        let search_start = data
+            .len()
+            .saturating_sub(END_OF_CENTRAL_DIR_SIZE + usize::from(std::u16::MAX));
+        let end = (search_start..=data.len().saturating_sub(END_OF_CENTRAL_DIR_SIZE))
+            .rev()
+            .find(|&offset| read_u32(data, offset) == Some(END_OF_CENTRAL_DIR_SIGNATURE))
+            .ok_or_else(|| "not a zip archive".to_string())?;
+        let disk = read_u16(data, end + 4).ok_or_else(truncated)?;
+        let entry_count = read_u16(data, end + 10).ok_or_else(truncated)?;
+        let central_dir_offset = read_u32(data, end + 16).ok_or_else(truncated)?;
+        if disk != 0 {
+            return Err("archives split over multiple disks aren't supported".to_string());
+        }
+        if entry_count == std::u16::MAX || central_dir_offset == std::u32::MAX {
+            return Err("zip64 archives aren't supported".to_string());
+        }
+
+        let mut entries = Vec::with_capacity(usize::from(entry_count));
+        let mut offset = central_dir_offset as usize;
+        for _ in 0..entry_count {
+            if read_u32(data, offset) != Some(CENTRAL_HEADER_SIGNATURE) {
+                return Err(truncated());
+            }
+            let field_u16 = |at| read_u16(data, offset + at).ok_or_else(truncated);
+            let field_u32 = |at| read_u32(data, offset + at).ok_or_else(truncated);
+            let flags = field_u16(8)?;
+            let method = field_u16(10)?;
+            let crc32 = field_u32(16)?;
+            let compressed_size = field_u32(20)? as usize;
+            let size = field_u32(24)? as usize;
+            let name_len = usize::from(field_u16(28)?);
+            let extra_len = usize::from(field_u16(30)?);
+            let comment_len = usize::from(field_u16(32)?);
+            let local_header_offset = field_u32(42)? as usize;
+            let name = data
+                .get(offset + 46..offset + 46 + name_len)
+                .ok_or_else(truncated)?;
+            let name = String::from_utf8(name.to_vec()).map_err(|err| {
+                format!(
+                    "invalid UTF-8 in the entry name {:?}",
+                    String::from_utf8_lossy(err.as_bytes())
+                )
+            })?;
+            if flags & FLAG_ENCRYPTED != 0 {
+                return Err(format!("{:?} is encrypted", name));
+            }
+            entries.push(ZipEntry {
+                name,
+                method,
+                crc32,
+                compressed_size,
+                size,
+                local_header_offset,
+            });
+            offset += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(ZipArchive { data, entries })
+    }
+
+    /// Decompress an entry, checking it against its size and checksum.
+    pub fn read(&self, entry: &ZipEntry) -> Result<Vec<u8>, String> {
+        let corrupt = || format!("{:?} is corrupt", entry.name);
+        let offset = entry.local_header_offset;
+        if read_u32(self.data, offset) != Some(LOCAL_HEADER_SIGNATURE) {
+            return Err(corrupt());
+        }
+        let name_len = usize::from(read_u16(self.data, offset + 26).ok_or_else(corrupt)?);
+        let extra_len = usize::from(read_u16(self.data, offset + 28).ok_or_else(corrupt)?);
+        let start = offset + 30 + name_len + extra_len;
+        let compressed = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(corrupt)?;
+        let contents = match entry.method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATED => {
+                let mut contents = Vec::with_capacity(entry.size);
+                DeflateDecoder::new(compressed)
+                    .read_to_end(&mut contents)
+                    .map_err(|err| format!("{:?} is corrupt: {}", entry.name, err))?;
+                contents
+            }
+            method => {
+                return Err(format!(
+                    "{:?} uses compression method {}, only stored and deflated entries are \
+                     supported",
+                    entry.name, method
+                ))
+            }
+        };
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&contents);
+        if contents.len() != entry.size || hasher.finalize() != entry.crc32 {
+            return Err(corrupt());
+        }
+        Ok(contents)
+    }
+}
@@ -21,6 +21,13 @@ pub struct Program {
     pub statements: Suite,
 }
 
+/// A `# type: ignore` comment, with the text after `ignore` as its tag.
+#[derive(Debug, PartialEq)]
+pub struct TypeIgnore {
+    pub lineno: usize,
+    pub tag: String,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct ImportSymbol {
     pub symbol: String,
@@ -75,6 +82,8 @@ pub enum StatementType {
     Assign {
         targets: Vec<Expression>,
         value: Expression,
+        /// The `# type:` comment after the assignment, when parsing with type comments.
+        type_comment: Option<String>,
     },
 
     /// Augmented assignment.
@@ -121,6 +130,7 @@ pub enum StatementType {
         is_async: bool,
         items: Vec<WithItem>,
         body: Suite,
+        type_comment: Option<String>,
     },
 
     /// A [`for`](https://docs.python.org/3/reference/compound_stmts.html#the-for-statement) statement.
@@ -131,6 +141,7 @@ pub enum StatementType {
         iter: Box<Expression>,
         body: Suite,
         orelse: Option<Suite>,
+        type_comment: Option<String>,
     },
 
     /// A `raise` statement.
@@ -166,6 +177,7 @@ pub enum StatementType {
         body: Suite,
         decorator_list: Vec<Expression>,
         returns: Option<Expression>,
+        type_comment: Option<String>,
     },
 }
 
@@ -302,6 +314,12 @@ pub enum ExpressionType {
         body: Box<Expression>,
     },
 
+    /// An assignment expression, like `(name := value)`. The target is always an identifier.
+    NamedExpression {
+        target: Box<Expression>,
+        value: Box<Expression>,
+    },
+
     /// An if-expression.
     IfExpression {
         test: Box<Expression>,
@@ -359,8 +377,9 @@ impl Expression {
             | String {
                 value: FormattedValue { .. },
             } => "f-string expression",
-            Identifier { .. } => "named expression",
+            Identifier { .. } => "name",
             Lambda { .. } => "lambda",
+            NamedExpression { .. } => "named expression",
             IfExpression { .. } => "conditional expression",
             True | False | None => "keyword",
             Ellipsis => "ellipsis",
@@ -374,6 +393,7 @@ impl Expression {
 /// distinguish between function parameters and actual call arguments.
 #[derive(Debug, PartialEq, Default)]
 pub struct Parameters {
+    pub posonlyargs: Vec<Parameter>,
     pub args: Vec<Parameter>,
     pub kwonlyargs: Vec<Parameter>,
     pub vararg: Varargs, // Optionally we handle optionally named '*args' or '*'
@@ -505,7 +525,8 @@ pub enum StringGroup {
     FormattedValue {
         value: Box<Expression>,
         conversion: Option<ConversionFlag>,
-        spec: String,
+        /// The format spec, which may contain replacement fields itself.
+        spec: Option<Box<StringGroup>>,
     },
     Joined {
         values: Vec<StringGroup>,
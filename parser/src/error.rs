@@ -24,8 +24,20 @@ pub enum LexicalErrorType {
     TabError,
     DefaultArgumentError,
     PositionalArgumentError,
+    /// A positional argument after a `**` argument in a call.
+    UnpackedPositionalArgumentError,
+    /// A `*` argument after a `**` argument in a call.
+    UnpackedIterableArgumentError,
+    /// A bare `*` in a parameter list without keyword-only parameters after it.
+    BareStarError,
     DuplicateKeywordArgumentError,
-    UnrecognizedToken { tok: char },
+    /// A bytes literal next to a str literal, or the other way around.
+    MixedBytesError,
+    /// A function with a type comment both on the line of its `def` and before its body.
+    DuplicateTypeCommentError,
+    UnrecognizedToken {
+        tok: char,
+    },
     FStringError(FStringErrorType),
     OtherError(String),
 }
@@ -34,7 +46,7 @@ impl fmt::Display for LexicalErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             LexicalErrorType::StringError => write!(f, "Got unexpected string"),
-            LexicalErrorType::FStringError(error) => write!(f, "Got error in f-string: {}", error),
+            LexicalErrorType::FStringError(error) => write!(f, "f-string: {}", error),
             LexicalErrorType::UnicodeError => write!(f, "Got unexpected unicode"),
             LexicalErrorType::NestingError => write!(f, "Got unexpected nesting"),
             LexicalErrorType::IndentationError => {
@@ -52,6 +64,20 @@ impl fmt::Display for LexicalErrorType {
             LexicalErrorType::PositionalArgumentError => {
                 write!(f, "positional argument follows keyword argument")
             }
+            LexicalErrorType::UnpackedPositionalArgumentError => {
+                write!(f, "positional argument follows keyword argument unpacking")
+            }
+            LexicalErrorType::UnpackedIterableArgumentError => write!(
+                f,
+                "iterable argument unpacking follows keyword argument unpacking"
+            ),
+            LexicalErrorType::BareStarError => write!(f, "named arguments must follow bare *"),
+            LexicalErrorType::MixedBytesError => {
+                write!(f, "cannot mix bytes and nonbytes literals")
+            }
+            LexicalErrorType::DuplicateTypeCommentError => {
+                write!(f, "Cannot have two type comments on def")
+            }
             LexicalErrorType::UnrecognizedToken { tok } => {
                 write!(f, "Got unexpected token {}", tok)
             }
@@ -66,6 +92,30 @@ impl From<LexicalError> for LalrpopError<Location, Tok, LexicalError> {
     }
 }
 
+/// Represents a warning about source code the lexer accepts, which CPython reports through the
+/// `warnings` module.
+#[derive(Debug, PartialEq)]
+pub struct LexicalWarning {
+    pub warning: LexicalWarningType,
+    pub location: Location,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LexicalWarningType {
+    /// A backslash in a string that doesn't start an escape sequence, which is kept as it is.
+    InvalidEscapeSequence(char),
+}
+
+impl fmt::Display for LexicalWarningType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexicalWarningType::InvalidEscapeSequence(c) => {
+                write!(f, "invalid escape sequence '\\{}'", c)
+            }
+        }
+    }
+}
+
 // TODO: consolidate these with ParseError
 #[derive(Debug, PartialEq)]
 pub struct FStringError {
@@ -80,20 +130,35 @@ pub enum FStringErrorType {
     InvalidExpression(Box<ParseErrorType>),
     InvalidConversionFlag,
     EmptyExpression,
-    MismatchedDelimiter,
+    /// A closing delimiter that doesn't match the opening one, like `(]`.
+    MismatchedDelimiter(char, char),
+    /// A closing delimiter without an opening one.
+    UnmatchedDelimiter(char),
+    ExpectedRbrace,
+    ExpressionNestedTooDeeply,
 }
 
 impl fmt::Display for FStringErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            FStringErrorType::UnclosedLbrace => write!(f, "Unclosed '('"),
-            FStringErrorType::UnopenedRbrace => write!(f, "Unopened ')'"),
-            FStringErrorType::InvalidExpression(error) => {
-                write!(f, "Invalid expression: {}", error)
+            FStringErrorType::UnclosedLbrace | FStringErrorType::ExpectedRbrace => {
+                write!(f, "expecting '}}'")
+            }
+            FStringErrorType::UnopenedRbrace => write!(f, "single '}}' is not allowed"),
+            FStringErrorType::InvalidExpression(error) => write!(f, "{}", error),
+            FStringErrorType::InvalidConversionFlag => {
+                write!(f, "invalid conversion character: expected 's', 'r', or 'a'")
+            }
+            FStringErrorType::EmptyExpression => write!(f, "empty expression not allowed"),
+            FStringErrorType::MismatchedDelimiter(open, close) => write!(
+                f,
+                "closing parenthesis '{}' does not match opening parenthesis '{}'",
+                close, open
+            ),
+            FStringErrorType::UnmatchedDelimiter(close) => write!(f, "unmatched '{}'", close),
+            FStringErrorType::ExpressionNestedTooDeeply => {
+                write!(f, "expressions nested too deeply")
             }
-            FStringErrorType::InvalidConversionFlag => write!(f, "Invalid conversion flag"),
-            FStringErrorType::EmptyExpression => write!(f, "Empty expression"),
-            FStringErrorType::MismatchedDelimiter => write!(f, "Mismatched delimiter"),
         }
     }
 }
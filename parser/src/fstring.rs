@@ -5,115 +5,179 @@ use std::str;
 use crate::ast::{ConversionFlag, StringGroup};
 use crate::error::{FStringError, FStringErrorType};
 use crate::location::Location;
-use crate::parser::parse_expression;
+use crate::parser::parse_located_expression;
 
 use self::FStringErrorType::*;
 use self::StringGroup::*;
 
 struct FStringParser<'a> {
     chars: iter::Peekable<str::Chars<'a>>,
+    /// The position of the next character in the source code.
+    location: Location,
 }
 
 impl<'a> FStringParser<'a> {
-    fn new(source: &'a str) -> Self {
+    fn new(source: &'a str, location: Location) -> Self {
         Self {
             chars: source.chars().peekable(),
+            location,
+        }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        if ch == '\n' {
+            self.location.newline();
+        } else {
+            self.location.go_right();
         }
+        Some(ch)
     }
 
-    fn parse_formatted_value(&mut self) -> Result<StringGroup, FStringErrorType> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().cloned()
+    }
+
+    /// Read the source of the expression of a replacement field, up to the `!`, `:` or `}` that
+    /// ends it.
+    fn parse_expression_source(&mut self) -> Result<String, FStringErrorType> {
         let mut expression = String::new();
-        let mut spec = String::new();
         let mut delims = Vec::new();
-        let mut conversion = None;
 
-        while let Some(ch) = self.chars.next() {
+        while let Some(ch) = self.peek() {
             match ch {
-                '!' if delims.is_empty() => {
-                    conversion = Some(match self.chars.next() {
-                        Some('s') => ConversionFlag::Str,
-                        Some('a') => ConversionFlag::Ascii,
-                        Some('r') => ConversionFlag::Repr,
-                        Some(_) => {
-                            return Err(InvalidConversionFlag);
-                        }
-                        None => {
-                            break;
-                        }
-                    })
+                // `!=` is a comparison rather than a conversion
+                '!' if delims.is_empty() && self.chars.clone().nth(1) != Some('=') => {
+                    return Ok(expression);
                 }
-                ':' if delims.is_empty() => {
-                    while let Some(&next) = self.chars.peek() {
-                        if next != '}' {
-                            spec.push(next);
-                            self.chars.next();
-                        } else {
-                            break;
-                        }
-                    }
+                ':' | '}' if delims.is_empty() => {
+                    return Ok(expression);
                 }
                 '(' | '{' | '[' => {
-                    expression.push(ch);
                     delims.push(ch);
                 }
-                ')' => {
-                    if delims.pop() != Some('(') {
-                        return Err(MismatchedDelimiter);
-                    }
-                    expression.push(ch);
-                }
-                ']' => {
-                    if delims.pop() != Some('[') {
-                        return Err(MismatchedDelimiter);
-                    }
-                    expression.push(ch);
-                }
-                '}' if !delims.is_empty() => {
-                    if delims.pop() != Some('{') {
-                        return Err(MismatchedDelimiter);
-                    }
-                    expression.push(ch);
-                }
-                '}' => {
-                    if expression.is_empty() {
-                        return Err(EmptyExpression);
+                ')' | ']' | '}' => {
+                    let open = match ch {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    match delims.pop() {
+                        Some(delim) if delim == open => {}
+                        Some(delim) => return Err(MismatchedDelimiter(delim, ch)),
+                        None => return Err(UnmatchedDelimiter(ch)),
                     }
-                    return Ok(FormattedValue {
-                        value: Box::new(
-                            parse_expression(expression.trim())
-                                .map_err(|e| InvalidExpression(Box::new(e.error)))?,
-                        ),
-                        conversion,
-                        spec,
-                    });
                 }
                 '"' | '\'' => {
                     expression.push(ch);
-                    while let Some(next) = self.chars.next() {
+                    self.next_char();
+                    while let Some(next) = self.next_char() {
                         expression.push(next);
                         if next == ch {
                             break;
                         }
                     }
+                    continue;
+                }
+                _ => {}
+            }
+            expression.push(ch);
+            self.next_char();
+        }
+
+        Err(UnclosedLbrace)
+    }
+
+    /// Parse a replacement field, after its opening `{`. Only the format spec of a top level
+    /// field may contain replacement fields itself.
+    fn parse_formatted_value(&mut self, nested: bool) -> Result<StringGroup, FStringErrorType> {
+        let start = self.location.clone();
+        let expression = self.parse_expression_source()?;
+        if expression.trim().is_empty() {
+            return Err(EmptyExpression);
+        }
+        // like CPython, parenthesize the expression so that it may span several lines, with the
+        // parenthesis just before where the expression is
+        let location = Location::new(start.row(), start.column() - 1);
+        let value = parse_located_expression(&format!("({})", expression), location)
+            .map_err(|e| InvalidExpression(Box::new(e.error)))?;
+
+        let mut conversion = None;
+        if self.peek() == Some('!') {
+            self.next_char();
+            conversion = Some(match self.next_char() {
+                Some('s') => ConversionFlag::Str,
+                Some('a') => ConversionFlag::Ascii,
+                Some('r') => ConversionFlag::Repr,
+                Some(_) => return Err(InvalidConversionFlag),
+                None => return Err(UnclosedLbrace),
+            });
+        }
+
+        let mut spec = None;
+        if self.peek() == Some(':') {
+            self.next_char();
+            spec = Some(Box::new(self.parse_spec(nested)?));
+        }
+
+        match self.next_char() {
+            Some('}') => Ok(FormattedValue {
+                value: Box::new(value),
+                conversion,
+                spec,
+            }),
+            Some(_) => Err(ExpectedRbrace),
+            None => Err(UnclosedLbrace),
+        }
+    }
+
+    /// Parse a format spec up to the `}` closing its replacement field.
+    fn parse_spec(&mut self, nested: bool) -> Result<StringGroup, FStringErrorType> {
+        let mut content = String::new();
+        let mut values = vec![];
+
+        while let Some(ch) = self.peek() {
+            match ch {
+                '{' => {
+                    if nested {
+                        return Err(ExpressionNestedTooDeeply);
+                    }
+                    self.next_char();
+                    if !content.is_empty() {
+                        values.push(Constant {
+                            value: mem::replace(&mut content, String::new()),
+                        });
+                    }
+                    values.push(self.parse_formatted_value(true)?);
                 }
+                '}' => break,
                 _ => {
-                    expression.push(ch);
+                    content.push(ch);
+                    self.next_char();
                 }
             }
         }
 
-        Err(UnclosedLbrace)
+        if !content.is_empty() {
+            values.push(Constant { value: content })
+        }
+        Ok(join(values))
     }
 
-    fn parse(mut self) -> Result<StringGroup, FStringErrorType> {
+    fn parse(mut self) -> Result<StringGroup, FStringError> {
         let mut content = String::new();
         let mut values = vec![];
 
-        while let Some(ch) = self.chars.next() {
+        loop {
+            let start = self.location.clone();
+            let ch = match self.next_char() {
+                Some(ch) => ch,
+                None => break,
+            };
             match ch {
                 '{' => {
-                    if let Some('{') = self.chars.peek() {
-                        self.chars.next();
+                    if let Some('{') = self.peek() {
+                        self.next_char();
                         content.push('{');
                     } else {
                         if !content.is_empty() {
@@ -122,15 +186,26 @@ impl<'a> FStringParser<'a> {
                             });
                         }
 
-                        values.push(self.parse_formatted_value()?);
+                        // point at the opening brace of a field that is never closed
+                        let value = self.parse_formatted_value(false).map_err(|error| {
+                            let location = match error {
+                                UnclosedLbrace => start,
+                                _ => self.location.clone(),
+                            };
+                            FStringError { error, location }
+                        })?;
+                        values.push(value);
                     }
                 }
                 '}' => {
-                    if let Some('}') = self.chars.peek() {
-                        self.chars.next();
+                    if let Some('}') = self.peek() {
+                        self.next_char();
                         content.push('}');
                     } else {
-                        return Err(UnopenedRbrace);
+                        return Err(FStringError {
+                            error: UnopenedRbrace,
+                            location: start,
+                        });
                     }
                 }
                 _ => {
@@ -143,19 +218,18 @@ impl<'a> FStringParser<'a> {
             values.push(Constant { value: content })
         }
 
-        Ok(match values.len() {
-            0 => Constant {
-                value: String::new(),
-            },
-            1 => values.into_iter().next().unwrap(),
-            _ => Joined { values },
-        })
+        Ok(join(values))
     }
 }
 
-/// Parse an f-string into a string group.
-fn parse_fstring(source: &str) -> Result<StringGroup, FStringErrorType> {
-    FStringParser::new(source).parse()
+fn join(values: Vec<StringGroup>) -> StringGroup {
+    match values.len() {
+        0 => Constant {
+            value: String::new(),
+        },
+        1 => values.into_iter().next().unwrap(),
+        _ => Joined { values },
+    }
 }
 
 /// Parse an fstring from a string, located at a certain position in the sourcecode.
@@ -164,7 +238,7 @@ pub fn parse_located_fstring(
     source: &str,
     location: Location,
 ) -> Result<StringGroup, FStringError> {
-    parse_fstring(source).map_err(|error| FStringError { error, location })
+    FStringParser::new(source, location).parse()
 }
 
 #[cfg(test)]
@@ -173,6 +247,10 @@ mod tests {
 
     use super::*;
 
+    fn parse_fstring(source: &str) -> Result<StringGroup, FStringErrorType> {
+        parse_located_fstring(source, Location::new(1, 1)).map_err(|e| e.error)
+    }
+
     fn mk_ident(name: &str, row: usize, col: usize) -> ast::Expression {
         ast::Expression {
             location: ast::Location::new(row, col),
@@ -192,14 +270,14 @@ mod tests {
             Joined {
                 values: vec![
                     FormattedValue {
-                        value: Box::new(mk_ident("a", 1, 1)),
+                        value: Box::new(mk_ident("a", 1, 2)),
                         conversion: None,
-                        spec: String::new(),
+                        spec: None,
                     },
                     FormattedValue {
-                        value: Box::new(mk_ident("b", 1, 1)),
+                        value: Box::new(mk_ident("b", 1, 6)),
                         conversion: None,
-                        spec: String::new(),
+                        spec: None,
                     },
                     Constant {
                         value: "{foo}".to_owned()
@@ -209,6 +287,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_fstring_nested_spec() {
+        let parse_ast = parse_fstring("{a!r:>{b}.{ {c} }f}").unwrap();
+
+        assert_eq!(
+            parse_ast,
+            FormattedValue {
+                value: Box::new(mk_ident("a", 1, 2)),
+                conversion: Some(ConversionFlag::Repr),
+                spec: Some(Box::new(Joined {
+                    values: vec![
+                        Constant {
+                            value: ">".to_owned()
+                        },
+                        FormattedValue {
+                            value: Box::new(mk_ident("b", 1, 8)),
+                            conversion: None,
+                            spec: None,
+                        },
+                        Constant {
+                            value: ".".to_owned()
+                        },
+                        FormattedValue {
+                            value: Box::new(ast::Expression {
+                                location: ast::Location::new(1, 13),
+                                node: ast::ExpressionType::Set {
+                                    elements: vec![mk_ident("c", 1, 14)],
+                                },
+                            }),
+                            conversion: None,
+                            spec: None,
+                        },
+                        Constant {
+                            value: "f".to_owned()
+                        },
+                    ]
+                })),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_located_fstring() {
+        let parse_ast = parse_located_fstring("ab\ncd {x:{y}}", Location::new(3, 8)).unwrap();
+
+        assert_eq!(
+            parse_ast,
+            Joined {
+                values: vec![
+                    Constant {
+                        value: "ab\ncd ".to_owned()
+                    },
+                    FormattedValue {
+                        value: Box::new(mk_ident("x", 4, 5)),
+                        conversion: None,
+                        spec: Some(Box::new(FormattedValue {
+                            value: Box::new(mk_ident("y", 4, 8)),
+                            conversion: None,
+                            spec: None,
+                        })),
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_multiline_fstring() {
+        let parse_ast = parse_fstring("{a\n}").unwrap();
+        assert_eq!(
+            parse_ast,
+            FormattedValue {
+                value: Box::new(mk_ident("a", 1, 2)),
+                conversion: None,
+                spec: None,
+            }
+        );
+        assert!(parse_fstring("{a +\n b}").is_ok());
+    }
+
     #[test]
     fn test_parse_empty_fstring() {
         assert_eq!(
@@ -223,8 +381,35 @@ mod tests {
     fn test_parse_invalid_fstring() {
         assert_eq!(parse_fstring("{"), Err(UnclosedLbrace));
         assert_eq!(parse_fstring("}"), Err(UnopenedRbrace));
+        assert_eq!(parse_fstring("{a!x}"), Err(InvalidConversionFlag));
+        assert_eq!(parse_fstring("{a!rx}"), Err(ExpectedRbrace));
+        assert_eq!(parse_fstring("{ }"), Err(EmptyExpression));
+        assert_eq!(parse_fstring("{a:{b:{c}}}"), Err(ExpressionNestedTooDeeply));
+        assert_eq!(parse_fstring("{a:{b}"), Err(UnclosedLbrace));
+        assert_eq!(parse_fstring("{a)}"), Err(UnmatchedDelimiter(')')));
+        assert_eq!(parse_fstring("{(a]}"), Err(MismatchedDelimiter('(', ']')));
+        assert_eq!(parse_fstring("{(a}"), Err(MismatchedDelimiter('(', '}')));
 
         // TODO: check for InvalidExpression enum?
         assert!(parse_fstring("{class}").is_err());
     }
+
+    #[test]
+    fn test_parse_invalid_fstring_location() {
+        let location = Location::new(3, 8);
+        assert_eq!(
+            parse_located_fstring("ab\ncd {x:{y}", location.clone()),
+            Err(FStringError {
+                error: UnclosedLbrace,
+                location: Location::new(4, 4),
+            })
+        );
+        assert_eq!(
+            parse_located_fstring("abc}", location),
+            Err(FStringError {
+                error: UnopenedRbrace,
+                location: Location::new(3, 11),
+            })
+        );
+    }
 }
@@ -3,15 +3,46 @@ use std::collections::HashSet;
 use crate::ast;
 use crate::error::{LexicalError, LexicalErrorType};
 
-type ParameterDefs = (Vec<ast::Parameter>, Vec<ast::Expression>);
+type ParameterDefs = (
+    Vec<ast::Parameter>,
+    Vec<ast::Parameter>,
+    Vec<ast::Expression>,
+);
 type ParameterDef = (ast::Parameter, Option<ast::Expression>);
 
+/// An entry in the positional part of a parameter list.
+pub enum PositionalParameter {
+    Parameter(ParameterDef),
+    /// The `/` after the positional-only parameters.
+    Slash(ast::Location),
+}
+
+/// Splits the positional parameters into the positional-only ones and the
+/// others, and collects their defaults.
 #[allow(clippy::collapsible_if)]
-pub fn parse_params(params: Vec<ParameterDef>) -> Result<ParameterDefs, LexicalError> {
+pub fn parse_params(params: Vec<PositionalParameter>) -> Result<ParameterDefs, LexicalError> {
+    let mut posonly_count = None;
     let mut names = vec![];
     let mut defaults = vec![];
 
-    for (name, default) in params {
+    for param in params {
+        let (name, default) = match param {
+            PositionalParameter::Parameter(param) => param,
+            PositionalParameter::Slash(location) => {
+                // The `/` must follow at least one parameter, and only once.
+                if names.is_empty() || posonly_count.is_some() {
+                    return Err(LexicalError {
+                        error: LexicalErrorType::OtherError("invalid syntax".to_string()),
+                        location,
+                    });
+                }
+                posonly_count = Some(names.len());
+                continue;
+            }
+        };
+
+        // The defaults of the positional-only parameters and those of the others
+        // form a single list, so the `/` doesn't matter here.
         if let Some(default) = default {
             defaults.push(default);
         } else {
@@ -27,7 +58,14 @@ pub fn parse_params(params: Vec<ParameterDef>) -> Result<ParameterDefs, LexicalE
         names.push(name);
     }
 
-    Ok((names, defaults))
+    let (posonly_names, names) = match posonly_count {
+        Some(count) => {
+            let names_after_slash = names.split_off(count);
+            (names, names_after_slash)
+        }
+        None => (vec![], names),
+    };
+    Ok((posonly_names, names, defaults))
 }
 
 type FunctionArgument = (Option<Option<String>>, ast::Expression);
@@ -54,10 +92,24 @@ pub fn parse_args(func_args: Vec<FunctionArgument>) -> Result<ast::ArgumentList,
                 keywords.push(ast::Keyword { name: n, value });
             }
             None => {
-                // Allow starred args after keyword arguments.
-                if !keywords.is_empty() && !is_starred(&value) {
+                // Allow starred args after keyword arguments, but not after `**` ones.
+                let after_kwargs = keywords.iter().any(|keyword| keyword.name.is_none());
+                let error = if is_starred(&value) {
+                    if after_kwargs {
+                        Some(LexicalErrorType::UnpackedIterableArgumentError)
+                    } else {
+                        None
+                    }
+                } else if after_kwargs {
+                    Some(LexicalErrorType::UnpackedPositionalArgumentError)
+                } else if !keywords.is_empty() {
+                    Some(LexicalErrorType::PositionalArgumentError)
+                } else {
+                    None
+                };
+                if let Some(error) = error {
                     return Err(LexicalError {
-                        error: LexicalErrorType::PositionalArgumentError,
+                        error,
                         location: value.location.clone(),
                     });
                 }
@@ -3,18 +3,22 @@
 //! This means source code is translated into separate tokens.
 
 extern crate unic_emoji_char;
+extern crate unic_normal;
+extern crate unicode_names2;
 extern crate unicode_xid;
 
 pub use super::token::Tok;
-use crate::error::{LexicalError, LexicalErrorType};
+use crate::ast;
+use crate::error::{LexicalError, LexicalErrorType, LexicalWarning, LexicalWarningType};
 use crate::location::Location;
 use num_bigint::BigInt;
 use num_traits::identities::Zero;
 use num_traits::Num;
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::str::FromStr;
+use std::str::{Chars, FromStr};
 use unic_emoji_char::is_emoji_presentation;
+use unic_normal::StrNormalForm;
 use unicode_xid::UnicodeXID;
 use wtf8;
 
@@ -68,6 +72,9 @@ pub struct Lexer<T: Iterator<Item = char>> {
     chr2: Option<char>,
     location: Location,
     keywords: HashMap<String, Tok>,
+    warnings: Vec<LexicalWarning>,
+    type_comments: bool,
+    type_ignores: Vec<ast::TypeIgnore>,
 }
 
 pub fn get_keywords() -> HashMap<String, Tok> {
@@ -118,9 +125,13 @@ pub type Spanned = (Location, Tok, Location);
 pub type LexResult = Result<Spanned, LexicalError>;
 
 pub fn make_tokenizer<'a>(source: &'a str) -> impl Iterator<Item = LexResult> + 'a {
+    make_lexer(source)
+}
+
+/// Like `make_tokenizer`, for a lexer whose warnings can be taken after lexing.
+pub fn make_lexer(source: &str) -> Lexer<NewlineHandler<Chars<'_>>> {
     let nlh = NewlineHandler::new(source.chars());
-    let lch = LineContinationHandler::new(nlh);
-    Lexer::new(lch)
+    Lexer::new(nlh)
 }
 
 // The newline handler is an iterator which collapses different newline
@@ -180,61 +191,6 @@ where
     }
 }
 
-// Glues \ and \n into a single line:
-pub struct LineContinationHandler<T: Iterator<Item = char>> {
-    source: T,
-    chr0: Option<char>,
-    chr1: Option<char>,
-}
-
-impl<T> LineContinationHandler<T>
-where
-    T: Iterator<Item = char>,
-{
-    pub fn new(source: T) -> Self {
-        let mut nlh = LineContinationHandler {
-            source,
-            chr0: None,
-            chr1: None,
-        };
-        nlh.shift();
-        nlh.shift();
-        nlh
-    }
-
-    fn shift(&mut self) -> Option<char> {
-        let result = self.chr0;
-        self.chr0 = self.chr1;
-        self.chr1 = self.source.next();
-        result
-    }
-}
-
-impl<T> Iterator for LineContinationHandler<T>
-where
-    T: Iterator<Item = char>,
-{
-    type Item = char;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        // Collapse \r\n into \n
-        loop {
-            if self.chr0 == Some('\\') && self.chr1 == Some('\n') {
-                // Skip backslash and newline
-                self.shift();
-                self.shift();
-            // Idea: insert trailing newline here:
-            // } else if self.chr0 != Some('\n') && self.chr1.is_none() {
-            //     self.chr1 = Some('\n');
-            } else {
-                break;
-            }
-        }
-
-        self.shift()
-    }
-}
-
 impl<T> Lexer<T>
 where
     T: Iterator<Item = char>,
@@ -251,6 +207,9 @@ where
             chr1: None,
             chr2: None,
             keywords: get_keywords(),
+            warnings: Vec::new(),
+            type_comments: false,
+            type_ignores: Vec::new(),
         };
         lxr.next_char();
         lxr.next_char();
@@ -260,6 +219,29 @@ where
         lxr
     }
 
+    /// Take the warnings about the source lexed so far.
+    pub fn take_warnings(&mut self) -> Vec<LexicalWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Lex the `# type:` comments of PEP 484 into tokens, rather than skipping them.
+    pub fn with_type_comments(mut self) -> Self {
+        self.type_comments = true;
+        self
+    }
+
+    /// Locate the tokens as if the source started at `location`, like the expressions of an
+    /// f-string do within the source code around it.
+    pub fn starting_at(mut self, location: Location) -> Self {
+        self.location = location;
+        self
+    }
+
+    /// Take the `# type: ignore` comments of the source lexed so far.
+    pub fn take_type_ignores(&mut self) -> Vec<ast::TypeIgnore> {
+        std::mem::take(&mut self.type_ignores)
+    }
+
     // Lexer helper functions:
     fn lex_identifier(&mut self) -> LexResult {
         let mut name = String::new();
@@ -305,6 +287,7 @@ where
         if self.keywords.contains_key(&name) {
             Ok((start_pos, self.keywords[&name].clone(), end_pos))
         } else {
+            let name = normalize_identifier(name);
             Ok((start_pos, Tok::Name { name }, end_pos))
         }
     }
@@ -485,17 +468,44 @@ where
         }
     }
 
-    /// Skip everything until end of line
+    /// Skip everything until end of line, unless it's a type comment we're asked for.
     fn lex_comment(&mut self) {
+        let tok_start = self.get_pos();
         self.next_char();
+        let mut comment = String::new();
         loop {
             match self.chr0 {
-                Some('\n') => return,
-                Some(_) => {}
-                None => return,
+                Some('\n') | None => break,
+                Some(c) => comment.push(c),
             }
             self.next_char();
         }
+        if self.type_comments {
+            self.lex_type_comment(tok_start, &comment);
+        }
+    }
+
+    /// Emit `comment` as a type comment if it is one. The type comments inside brackets are
+    /// skipped, and the `# type: ignore` ones are only kept aside.
+    fn lex_type_comment(&mut self, tok_start: Location, comment: &str) {
+        let is_whitespace = |c| c == ' ' || c == '\t';
+        let comment = comment.trim_start_matches(is_whitespace);
+        if !comment.starts_with("type:") {
+            return;
+        }
+        let value = comment["type:".len()..].trim_start_matches(is_whitespace);
+        let is_ignore = value.starts_with("ignore")
+            && !value["ignore".len()..].starts_with(|c: char| c.is_ascii_alphanumeric());
+        if is_ignore {
+            self.type_ignores.push(ast::TypeIgnore {
+                lineno: tok_start.row(),
+                tag: value["ignore".len()..].to_owned(),
+            });
+        } else if self.nesting == 0 {
+            let tok_end = self.get_pos();
+            let value = value.to_owned();
+            self.emit((tok_start, Tok::TypeComment { value }, tok_end));
+        }
     }
 
     fn unicode_literal(&mut self, literal_number: usize) -> Result<char, LexicalError> {
@@ -519,6 +529,41 @@ where
         }
     }
 
+    /// The character of an octal escape, of up to three digits from `first` on.
+    fn octal_literal(&mut self, first: char) -> char {
+        let mut value = first.to_digit(8).unwrap();
+        for _ in 1..3 {
+            match self.chr0.and_then(|c| c.to_digit(8)) {
+                Some(digit) => {
+                    value = value * 8 + digit;
+                    self.next_char();
+                }
+                None => break,
+            }
+        }
+        std::char::from_u32(value).unwrap()
+    }
+
+    /// The character of a `\N{name}` escape.
+    fn unicode_name(&mut self) -> Result<char, LexicalError> {
+        let unicode_error = LexicalError {
+            error: LexicalErrorType::UnicodeError,
+            location: self.get_pos(),
+        };
+        if self.next_char() != Some('{') {
+            return Err(unicode_error);
+        }
+        let mut name = String::new();
+        loop {
+            match self.next_char() {
+                Some('}') => break,
+                Some(c) if c.is_ascii_alphanumeric() || c == ' ' || c == '-' => name.push(c),
+                _ => return Err(unicode_error),
+            }
+        }
+        unicode_names2::character(&name).ok_or(unicode_error)
+    }
+
     fn lex_string(
         &mut self,
         is_bytes: bool,
@@ -529,6 +574,8 @@ where
         let quote_char = self.next_char().unwrap();
         let mut string_content = String::new();
         let start_pos = self.get_pos();
+        // Like CPython, only the first invalid escape sequence of a string is warned about.
+        let mut invalid_escape = None;
 
         // If the next two characters are also the quote character, then we have a triple-quoted
         // string; consume those two characters and ensure that we require a triple-quote to close
@@ -543,10 +590,9 @@ where
         loop {
             match self.next_char() {
                 Some('\\') => {
-                    if self.chr0 == Some(quote_char) {
-                        string_content.push(quote_char);
-                        self.next_char();
-                    } else if is_raw {
+                    if is_raw {
+                        // The backslash stays, but the quote or line end after it doesn't end the
+                        // string.
                         string_content.push('\\');
                         if let Some(c) = self.next_char() {
                             string_content.push(c)
@@ -576,11 +622,22 @@ where
                             Some('t') => {
                                 string_content.push('\t');
                             }
-                            Some('u') => string_content.push(self.unicode_literal(4)?),
-                            Some('U') => string_content.push(self.unicode_literal(8)?),
-                            Some('x') if !is_bytes => string_content.push(self.unicode_literal(2)?),
                             Some('v') => string_content.push('\x0b'),
+                            Some(c) if c.is_digit(8) => string_content.push(self.octal_literal(c)),
+                            Some('x') => string_content.push(self.unicode_literal(2)?),
+                            Some('u') if !is_bytes => string_content.push(self.unicode_literal(4)?),
+                            Some('U') if !is_bytes => string_content.push(self.unicode_literal(8)?),
+                            Some('N') if !is_bytes => string_content.push(self.unicode_name()?),
                             Some(c) => {
+                                if is_bytes && !c.is_ascii() {
+                                    return Err(LexicalError {
+                                        error: LexicalErrorType::StringError,
+                                        location: self.get_pos(),
+                                    });
+                                }
+                                if invalid_escape.is_none() {
+                                    invalid_escape = Some(c);
+                                }
                                 string_content.push('\\');
                                 string_content.push(c);
                             }
@@ -609,7 +666,7 @@ where
                             break;
                         }
                     } else {
-                        if c == '\n' && !triple_quoted {
+                        if (c == '\n' && !triple_quoted) || (is_bytes && !c.is_ascii()) {
                             return Err(LexicalError {
                                 error: LexicalErrorType::StringError,
                                 location: self.get_pos(),
@@ -627,23 +684,17 @@ where
             }
         }
         let end_pos = self.get_pos();
+        if let Some(c) = invalid_escape {
+            self.warnings.push(LexicalWarning {
+                warning: LexicalWarningType::InvalidEscapeSequence(c),
+                location: start_pos.clone(),
+            });
+        }
 
         let tok = if is_bytes {
-            if string_content.is_ascii() {
-                let value = if is_raw {
-                    string_content.into_bytes()
-                } else {
-                    lex_byte(string_content).map_err(|error| LexicalError {
-                        error,
-                        location: self.get_pos(),
-                    })?
-                };
-                Tok::Bytes { value }
-            } else {
-                return Err(LexicalError {
-                    error: LexicalErrorType::StringError,
-                    location: self.get_pos(),
-                });
+            // Every character stands for a byte here, the escapes can't go above `\xff`.
+            Tok::Bytes {
+                value: string_content.chars().map(|c| c as u8).collect(),
             }
         } else {
             Tok::String {
@@ -1100,7 +1151,16 @@ where
                 self.nesting -= 1;
             }
             ':' => {
-                self.eat_single_char(Tok::Colon);
+                let tok_start = self.get_pos();
+                self.next_char();
+                if let Some('=') = self.chr0 {
+                    self.next_char();
+                    let tok_end = self.get_pos();
+                    self.emit((tok_start, Tok::ColonEqual, tok_end));
+                } else {
+                    let tok_end = self.get_pos();
+                    self.emit((tok_start, Tok::Colon, tok_end));
+                }
             }
             ';' => {
                 self.eat_single_char(Tok::Semi);
@@ -1198,6 +1258,11 @@ where
                     self.emit((tok_start, Tok::Newline, tok_end));
                 }
             }
+            '\\' if self.chr1 == Some('\n') => {
+                // A line continuation, the next line goes on with this one
+                self.next_char();
+                self.next_char();
+            }
             ' ' | '\t' | '\x0C' => {
                 // Skip whitespaces
                 self.next_char();
@@ -1207,10 +1272,11 @@ where
                 }
             }
             _ => {
+                let location = self.get_pos();
                 let c = self.next_char();
                 return Err(LexicalError {
                     error: LexicalErrorType::UnrecognizedToken { tok: c.unwrap() },
-                    location: self.get_pos(),
+                    location,
                 });
             } // Ignore all the rest..
         }
@@ -1281,57 +1347,19 @@ where
     }
 }
 
-fn lex_byte(s: String) -> Result<Vec<u8>, LexicalErrorType> {
-    let mut res = vec![];
-    let mut escape = false; //flag if previous was \
-    let mut hex_on = false; // hex mode on or off
-    let mut hex_value = String::new();
-
-    for c in s.chars() {
-        if hex_on {
-            if c.is_ascii_hexdigit() {
-                if hex_value.is_empty() {
-                    hex_value.push(c);
-                    continue;
-                } else {
-                    hex_value.push(c);
-                    res.push(u8::from_str_radix(&hex_value, 16).unwrap());
-                    hex_on = false;
-                    hex_value.clear();
-                }
-            } else {
-                return Err(LexicalErrorType::StringError);
-            }
-        } else {
-            match (c, escape) {
-                ('\\', true) => res.push(b'\\'),
-                ('\\', false) => {
-                    escape = true;
-                    continue;
-                }
-                ('x', true) => hex_on = true,
-                ('x', false) => res.push(b'x'),
-                ('t', true) => res.push(b'\t'),
-                ('t', false) => res.push(b't'),
-                ('n', true) => res.push(b'\n'),
-                ('n', false) => res.push(b'n'),
-                ('r', true) => res.push(b'\r'),
-                ('r', false) => res.push(b'r'),
-                (x, true) => {
-                    res.push(b'\\');
-                    res.push(x as u8);
-                }
-                (x, false) => res.push(x as u8),
-            }
-            escape = false;
-        }
+/// Identifiers are compared in their NFKC form (PEP 3131), so that for example
+/// `ﬁ` and `fi`, or a composed and a decomposed `é`, name the same variable.
+fn normalize_identifier(name: String) -> String {
+    if name.is_ascii() {
+        name
+    } else {
+        name.nfkc().collect()
     }
-    Ok(res)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{make_tokenizer, NewlineHandler, Tok};
+    use super::{make_lexer, make_tokenizer, LexicalErrorType, Location, NewlineHandler, Tok};
     use num_bigint::BigInt;
 
     const WINDOWS_EOL: &str = "\r\n";
@@ -1373,6 +1401,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_unicode_identifiers() {
+        let source = "\u{3b1}\u{3b2} caf\u{e9} cafe\u{301} \u{fb01}le \u{ff49}\u{ff46}";
+        let tokens = lex_source(source);
+        assert_eq!(
+            tokens,
+            vec![
+                Tok::Name {
+                    name: "\u{3b1}\u{3b2}".to_string()
+                },
+                Tok::Name {
+                    name: "caf\u{e9}".to_string()
+                },
+                Tok::Name {
+                    name: "caf\u{e9}".to_string()
+                },
+                Tok::Name {
+                    name: "file".to_string()
+                },
+                // Only the ASCII spelling is a keyword.
+                Tok::Name {
+                    name: "if".to_string()
+                },
+                Tok::Newline,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_character_location() {
+        let source = "abc = \u{20ac}";
+        let error = make_tokenizer(source)
+            .find_map(Result::err)
+            .expect("the tokenizer must fail");
+        assert_eq!(
+            error.error,
+            LexicalErrorType::UnrecognizedToken { tok: '\u{20ac}' }
+        );
+        assert_eq!(error.location, Location::new(1, 7));
+    }
+
+    #[test]
+    fn test_line_continuation_location() {
+        let source = "x = 1 + \\\n    2\ny";
+        let locations: Vec<(Location, Location)> = make_tokenizer(source)
+            .map(|result| {
+                let (start, _, end) = result.unwrap();
+                (start, end)
+            })
+            .collect();
+        // The `+`, the `2` on the continued line, its newline and the `y` after it.
+        assert_eq!(
+            locations[3..7],
+            [
+                (Location::new(1, 7), Location::new(1, 8)),
+                (Location::new(2, 5), Location::new(2, 6)),
+                (Location::new(2, 6), Location::new(3, 1)),
+                (Location::new(3, 1), Location::new(3, 2)),
+            ]
+        );
+    }
+
     #[test]
     fn test_numbers() {
         let source = "0x2f 0b1101 0 123 0.2 2j 2.2j";
@@ -1686,7 +1776,7 @@ mod tests {
                     is_fstring: false,
                 },
                 Tok::String {
-                    value: String::from("raw\'"),
+                    value: String::from("raw\\'"),
                     is_fstring: false,
                 },
                 Tok::Newline,
@@ -1694,6 +1784,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_escape_warnings() {
+        let source = "'\\g'\nr'\\g' b'\\x41\\0'\n'''\n\\d \\w''' b'\\N'\n";
+        let mut lexer = make_lexer(source);
+        assert!(lexer.by_ref().all(|result| result.is_ok()));
+        let warnings: Vec<_> = lexer
+            .take_warnings()
+            .into_iter()
+            .map(|warning| (warning.warning.to_string(), warning.location.row()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                ("invalid escape sequence '\\g'".to_string(), 1),
+                ("invalid escape sequence '\\d'".to_string(), 3),
+                ("invalid escape sequence '\\N'".to_string(), 4),
+            ]
+        );
+    }
+
     macro_rules! test_string_continuation {
         ($($name:ident: $eol:expr,)*) => {
         $(
@@ -1763,4 +1873,55 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_byte_literal_escapes() {
+        let source = r#"b"\0\101\777\\x41\u0041" Br'\'' bR"\"""#;
+        let tokens = lex_source(source);
+        assert_eq!(
+            tokens,
+            vec![
+                Tok::Bytes {
+                    value: b"\0A\xff\\x41\\u0041".to_vec()
+                },
+                Tok::Bytes {
+                    value: b"\\'".to_vec()
+                },
+                Tok::Bytes {
+                    value: b"\\\"".to_vec()
+                },
+                Tok::Newline
+            ]
+        )
+    }
+
+    #[test]
+    fn test_non_ascii_byte_literal() {
+        for source in &["b'caf\u{e9}'", "b'\\\u{e9}'"] {
+            let error = make_tokenizer(source)
+                .find_map(Result::err)
+                .expect("the tokenizer must fail");
+            assert_eq!(error.error, LexicalErrorType::StringError);
+        }
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let source = r#"'\0\101\x41\N{DIGIT ONE}\N{latin small letter e with acute}' rf"{x}\n""#;
+        let tokens = lex_source(source);
+        assert_eq!(
+            tokens,
+            vec![
+                Tok::String {
+                    value: "\0AA1\u{e9}".to_string(),
+                    is_fstring: false,
+                },
+                Tok::String {
+                    value: "{x}\\n".to_string(),
+                    is_fstring: true,
+                },
+                Tok::Newline
+            ]
+        )
+    }
 }
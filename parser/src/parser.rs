@@ -8,8 +8,9 @@
 use std::iter;
 
 use crate::ast;
-use crate::error::ParseError;
+use crate::error::{LexicalWarning, ParseError, ParseErrorType};
 use crate::lexer;
+use crate::location::Location;
 pub use crate::mode::Mode;
 use crate::python;
 use crate::token;
@@ -21,16 +22,16 @@ use crate::token;
  */
 
 macro_rules! do_lalr_parsing {
-    ($input: expr, $pat: ident, $tok: ident) => {{
-        let lxr = lexer::make_tokenizer($input);
+    ($lexer: expr, $pat: ident, $tok: ident) => {{
+        let mut lxr = $lexer;
         let marker_token = (Default::default(), token::Tok::$tok, Default::default());
-        let tokenizer = iter::once(Ok(marker_token)).chain(lxr);
+        let tokenizer = iter::once(Ok(marker_token)).chain(lxr.by_ref());
 
         match python::TopParser::new().parse(tokenizer) {
             Err(err) => Err(ParseError::from(err)),
             Ok(top) => {
                 if let ast::Top::$pat(x) = top {
-                    Ok(x)
+                    Ok((x, lxr.take_warnings()))
                 } else {
                     unreachable!()
                 }
@@ -41,12 +42,157 @@ macro_rules! do_lalr_parsing {
 
 /// Parse a full python program, containing usually multiple lines.
 pub fn parse_program(source: &str) -> Result<ast::Program, ParseError> {
-    do_lalr_parsing!(source, Program, StartProgram)
+    parse_program_with_warnings(source).map(|(program, _)| program)
+}
+
+/// Like `parse_program`, also returning the warnings about the source, like the one for an
+/// invalid escape sequence in a string.
+pub fn parse_program_with_warnings(
+    source: &str,
+) -> Result<(ast::Program, Vec<LexicalWarning>), ParseError> {
+    do_lalr_parsing!(lexer::make_lexer(source), Program, StartProgram)
+}
+
+/// Like `parse_program`, also parsing the `# type:` comments of PEP 484 into the
+/// `type_comment` of the statements they go with, and returning the `# type: ignore` comments.
+///
+/// Type comments anywhere else are a syntax error, except in brackets, where they are ignored.
+pub fn parse_program_with_type_comments(
+    source: &str,
+) -> Result<(ast::Program, Vec<ast::TypeIgnore>), ParseError> {
+    let mut lxr = lexer::make_lexer(source).with_type_comments();
+    let marker_token = (
+        Default::default(),
+        token::Tok::StartProgram,
+        Default::default(),
+    );
+    let tokenizer = iter::once(Ok(marker_token)).chain(lxr.by_ref());
+
+    match python::TopParser::new().parse(tokenizer) {
+        Err(err) => Err(ParseError::from(err)),
+        Ok(ast::Top::Program(program)) => Ok((program, lxr.take_type_ignores())),
+        Ok(_) => unreachable!(),
+    }
+}
+
+/// Like `parse_program_with_warnings`, but instead of stopping at the first syntax error, skip
+/// to the next statement and go on parsing from there, to report all syntax errors at once.
+///
+/// The tokens after the end of the statement with the error are parsed as if they were at the
+/// top level, so the statements after an error in a block don't report bogus indentation errors.
+/// An error from the lexer ends the parsing, since its state can't be trusted anymore.
+pub fn parse_program_recovering(
+    source: &str,
+) -> Result<(ast::Program, Vec<LexicalWarning>), Vec<ParseError>> {
+    let mut lxr = lexer::make_lexer(source);
+    let mut tokens = Vec::new();
+    let mut lex_error = None;
+    for result in lxr.by_ref() {
+        match result {
+            Ok(token) => tokens.push(token),
+            Err(err) => {
+                lex_error = Some(err);
+                break;
+            }
+        }
+    }
+    let warnings = lxr.take_warnings();
+
+    let mut errors = vec![];
+    let mut start = 0;
+    loop {
+        let marker_token = (
+            Default::default(),
+            token::Tok::StartProgram,
+            Default::default(),
+        );
+        let segment = if start == 0 {
+            tokens.clone()
+        } else {
+            resync_tokens(&tokens[start..])
+        };
+        // The lexer error is taken once the parser gets to it, which is where it's reported
+        let segment = iter::once(marker_token)
+            .chain(segment)
+            .map(Ok)
+            .chain(iter::from_fn(|| lex_error.take().map(Err)));
+
+        let err = match python::TopParser::new().parse(segment) {
+            Ok(ast::Top::Program(program)) => {
+                if errors.is_empty() {
+                    return Ok((program, warnings));
+                }
+                break;
+            }
+            Ok(_) => unreachable!(),
+            Err(err) => ParseError::from(err),
+        };
+
+        let is_eof = err.error == ParseErrorType::EOF;
+        let location = (err.location.row(), err.location.column());
+        errors.push(err);
+        if is_eof {
+            break;
+        }
+        // Go on after the first newline at or after the error
+        let next = tokens[start..]
+            .iter()
+            .position(|(location_start, _, _)| {
+                (location_start.row(), location_start.column()) >= location
+            })
+            .and_then(|index| {
+                tokens[start + index..]
+                    .iter()
+                    .position(|(_, tok, _)| *tok == token::Tok::Newline)
+                    .map(|newline| start + index + newline + 1)
+            });
+        match next {
+            Some(next) if next < tokens.len() => start = next,
+            _ => break,
+        }
+    }
+    if let Some(err) = lex_error {
+        errors.push(ParseError {
+            error: ParseErrorType::Lexical(err.error),
+            location: err.location,
+        });
+    }
+    Err(errors)
+}
+
+/// The tokens from somewhere inside of a program, with the indentation of the first statement
+/// removed, and without the dedents that would go below it.
+fn resync_tokens(tokens: &[lexer::Spanned]) -> Vec<lexer::Spanned> {
+    let mut depth = 0usize;
+    tokens
+        .iter()
+        .skip_while(|(_, tok, _)| *tok == token::Tok::Indent)
+        .filter(|(_, tok, _)| match tok {
+            token::Tok::Indent => {
+                depth += 1;
+                true
+            }
+            token::Tok::Dedent if depth == 0 => false,
+            token::Tok::Dedent => {
+                depth -= 1;
+                true
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
 }
 
 /// Parse a single statement.
 pub fn parse_statement(source: &str) -> Result<Vec<ast::Statement>, ParseError> {
-    do_lalr_parsing!(source, Statement, StartStatement)
+    parse_statement_with_warnings(source).map(|(statement, _)| statement)
+}
+
+/// Like `parse_statement`, see `parse_program_with_warnings`.
+pub fn parse_statement_with_warnings(
+    source: &str,
+) -> Result<(Vec<ast::Statement>, Vec<LexicalWarning>), ParseError> {
+    do_lalr_parsing!(lexer::make_lexer(source), Statement, StartStatement)
 }
 
 /// Parses a python expression
@@ -80,7 +226,16 @@ pub fn parse_statement(source: &str) -> Result<Vec<ast::Statement>, ParseError>
 ///
 /// ```
 pub fn parse_expression(source: &str) -> Result<ast::Expression, ParseError> {
-    do_lalr_parsing!(source, Expression, StartExpression)
+    parse_located_expression(source, Location::new(1, 1))
+}
+
+/// Like `parse_expression`, for an expression that starts at `location` in the source code.
+pub fn parse_located_expression(
+    source: &str,
+    location: Location,
+) -> Result<ast::Expression, ParseError> {
+    let lxr = lexer::make_lexer(source).starting_at(location);
+    do_lalr_parsing!(lxr, Expression, StartExpression).map(|(expression, _)| expression)
 }
 
 // Parse a given source code
@@ -102,7 +257,10 @@ mod tests {
     use super::ast;
     use super::parse_expression;
     use super::parse_program;
+    use super::parse_program_recovering;
+    use super::parse_program_with_type_comments;
     use super::parse_statement;
+    use crate::error::{LexicalErrorType, ParseErrorType};
     use num_bigint::BigInt;
 
     fn mk_ident(name: &str, row: usize, col: usize) -> ast::Expression {
@@ -258,6 +416,7 @@ mod tests {
                 location: ast::Location::new(1, 1),
                 node: ast::ExpressionType::Lambda {
                     args: Box::new(ast::Parameters {
+                        posonlyargs: vec![],
                         args: vec![
                             ast::Parameter {
                                 location: ast::Location::new(1, 8),
@@ -289,6 +448,45 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_positional_only_parameters() {
+        let source = String::from("lambda x, y=1, /, z=2: 0");
+        let parse_ast = parse_expression(&source).unwrap();
+        assert_eq!(
+            parse_ast,
+            ast::Expression {
+                location: ast::Location::new(1, 1),
+                node: ast::ExpressionType::Lambda {
+                    args: Box::new(ast::Parameters {
+                        posonlyargs: vec![
+                            ast::Parameter {
+                                location: ast::Location::new(1, 8),
+                                arg: String::from("x"),
+                                annotation: None,
+                            },
+                            ast::Parameter {
+                                location: ast::Location::new(1, 11),
+                                arg: String::from("y"),
+                                annotation: None,
+                            }
+                        ],
+                        args: vec![ast::Parameter {
+                            location: ast::Location::new(1, 19),
+                            arg: String::from("z"),
+                            annotation: None,
+                        }],
+                        kwonlyargs: vec![],
+                        vararg: ast::Varargs::None,
+                        kwarg: ast::Varargs::None,
+                        defaults: vec![make_int(1, 1, 13), make_int(2, 1, 21)],
+                        kw_defaults: vec![],
+                    }),
+                    body: Box::new(make_int(0, 1, 24)),
+                }
+            }
+        );
+    }
+
     #[test]
     fn test_parse_tuples() {
         let source = String::from("a, b = 4, 5");
@@ -309,7 +507,8 @@ mod tests {
                         node: ast::ExpressionType::Tuple {
                             elements: vec![make_int(4, 1, 8), make_int(5, 1, 11),]
                         }
-                    }
+                    },
+                    type_comment: None,
                 }
             }])
         )
@@ -335,6 +534,7 @@ mod tests {
                                 is_async: false,
                                 name: String::from("__init__"),
                                 args: Box::new(ast::Parameters {
+                                    posonlyargs: vec![],
                                     args: vec![ast::Parameter {
                                         location: ast::Location::new(2, 15),
                                         arg: String::from("self"),
@@ -352,6 +552,7 @@ mod tests {
                                 }],
                                 decorator_list: vec![],
                                 returns: None,
+                                type_comment: None,
                             }
                         },
                         ast::Statement {
@@ -360,6 +561,7 @@ mod tests {
                                 is_async: false,
                                 name: String::from("method_with_default"),
                                 args: Box::new(ast::Parameters {
+                                    posonlyargs: vec![],
                                     args: vec![
                                         ast::Parameter {
                                             location: ast::Location::new(4, 26),
@@ -384,6 +586,7 @@ mod tests {
                                 }],
                                 decorator_list: vec![],
                                 returns: None,
+                                type_comment: None,
                             }
                         }
                     ],
@@ -469,4 +672,283 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_named_expression() {
+        let source = String::from("if (n := len(a)) > 10: pass");
+        let parse_ast = parse_statement(&source).unwrap();
+        assert_eq!(
+            parse_ast,
+            vec![ast::Statement {
+                location: ast::Location::new(1, 1),
+                node: ast::StatementType::If {
+                    test: ast::Expression {
+                        location: ast::Location::new(1, 18),
+                        node: ast::ExpressionType::Compare {
+                            vals: vec![
+                                ast::Expression {
+                                    location: ast::Location::new(1, 5),
+                                    node: ast::ExpressionType::NamedExpression {
+                                        target: Box::new(mk_ident("n", 1, 5)),
+                                        value: Box::new(ast::Expression {
+                                            location: ast::Location::new(1, 13),
+                                            node: ast::ExpressionType::Call {
+                                                function: Box::new(mk_ident("len", 1, 10)),
+                                                args: vec![mk_ident("a", 1, 14)],
+                                                keywords: vec![],
+                                            },
+                                        }),
+                                    },
+                                },
+                                make_int(10, 1, 20),
+                            ],
+                            ops: vec![ast::Comparison::Greater],
+                        },
+                    },
+                    body: vec![ast::Statement {
+                        location: ast::Location::new(1, 24),
+                        node: ast::StatementType::Pass,
+                    }],
+                    orelse: None,
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_named_expression_positions() {
+        let allowed = [
+            "while chunk := f.read(8192): pass",
+            "if x := 1: pass\nelif y := 2: pass",
+            "(x := 1)",
+            "(x := 1, *y)",
+            "[y := f(x), y ** 2]",
+            "{y := 1}",
+            "[y for x in data if (y := f(x))]",
+            "[y := f(x) for x in data]",
+            "f(x := 1)",
+            "f(x := 1 for _ in y)",
+        ];
+        for source in allowed.iter() {
+            assert!(parse_program(source).is_ok(), "{:?} should parse", source);
+        }
+        let forbidden = [
+            "x := 1",
+            "x = y := 1",
+            "(a.b := 1)",
+            "((a, b) := 1)",
+            "[x for x in y if x := 1]",
+            "f(a=x := 1)",
+            "def f(x := 1): pass",
+        ];
+        for source in forbidden.iter() {
+            assert!(
+                parse_program(source).is_err(),
+                "{:?} should not parse",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_keyword_only_parameters() {
+        let program = parse_program("def f(a, *, b, c=1, d): pass").unwrap();
+        match &program.statements[0].node {
+            ast::StatementType::FunctionDef { args, .. } => {
+                let names: Vec<_> = args.kwonlyargs.iter().map(|arg| arg.arg.as_str()).collect();
+                assert_eq!(names, vec!["b", "c", "d"]);
+                assert_eq!(args.kw_defaults[0], None);
+                assert_eq!(args.kw_defaults[1], Some(make_int(1, 1, 18)));
+                assert_eq!(args.kw_defaults[2], None);
+                assert_eq!(args.vararg, ast::Varargs::Unnamed);
+            }
+            node => panic!("{:?}", node),
+        }
+
+        let error = |source| match parse_program(source).unwrap_err().error {
+            ParseErrorType::Lexical(error) => Some(error),
+            _ => None,
+        };
+        assert_eq!(
+            error("def f(*): pass"),
+            Some(LexicalErrorType::BareStarError)
+        );
+        assert_eq!(
+            error("def f(*, **k): pass"),
+            Some(LexicalErrorType::BareStarError)
+        );
+        assert_eq!(error("lambda *: 0"), Some(LexicalErrorType::BareStarError));
+        assert_eq!(error("def f(**): pass"), None);
+        assert_eq!(
+            error("f(**a, *b)"),
+            Some(LexicalErrorType::UnpackedIterableArgumentError)
+        );
+        assert_eq!(
+            error("f(**a, b)"),
+            Some(LexicalErrorType::UnpackedPositionalArgumentError)
+        );
+        assert_eq!(
+            error("f(a=1, b)"),
+            Some(LexicalErrorType::PositionalArgumentError)
+        );
+        for source in ["f(a=1, *b)", "f(*a, b, **c, d=1, **e)", "f(**a, b=1)"].iter() {
+            assert!(parse_program(source).is_ok(), "{:?} should parse", source);
+        }
+    }
+
+    #[test]
+    fn test_parse_decorators() {
+        let source = "@a[0]\n@b(c).d\nasync def f(): pass\n";
+        let program = parse_program(source).unwrap();
+        match &program.statements[0].node {
+            ast::StatementType::FunctionDef {
+                is_async,
+                decorator_list,
+                ..
+            } => {
+                assert!(is_async);
+                match (&decorator_list[0].node, &decorator_list[1].node) {
+                    (
+                        ast::ExpressionType::Subscript { .. },
+                        ast::ExpressionType::Attribute { .. },
+                    ) => {}
+                    nodes => panic!("{:?}", nodes),
+                }
+            }
+            node => panic!("{:?}", node),
+        }
+    }
+
+    #[test]
+    fn test_parse_string_concatenation() {
+        let expression = parse_expression(r#"b'a' rb"\b" Br'c'"#).unwrap();
+        assert_eq!(
+            expression.node,
+            ast::ExpressionType::Bytes {
+                value: b"a\\bc".to_vec()
+            }
+        );
+
+        let expression = parse_expression(r#"'a' r'\b' Rf"{c}\d""#).unwrap();
+        match expression.node {
+            ast::ExpressionType::String {
+                value: ast::StringGroup::Joined { values },
+            } => {
+                assert_eq!(values.len(), 3);
+                assert_eq!(
+                    values[1],
+                    ast::StringGroup::Constant {
+                        value: "\\b".to_string()
+                    }
+                );
+            }
+            node => panic!("{:?}", node),
+        }
+
+        for source in ["'a' b'b'", "b'a' f'b'", "b'a' 'b' b'c'"].iter() {
+            let error = parse_expression(source).unwrap_err();
+            assert_eq!(
+                error.error,
+                ParseErrorType::Lexical(LexicalErrorType::MixedBytesError)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_type_comments() {
+        let source = "\
+x = y = 1  # type: int
+def f(a):  # type: (int) -> None
+    for b in a:  #type:str
+        pass
+def g():
+    # type: () -> int
+    with h() as c:  # type: ignore[misc]
+        return c  # type: ignore
+";
+        let (program, type_ignores) = parse_program_with_type_comments(source).unwrap();
+        let type_comment = |statement: &ast::Statement| match &statement.node {
+            ast::StatementType::Assign { type_comment, .. }
+            | ast::StatementType::For { type_comment, .. }
+            | ast::StatementType::With { type_comment, .. }
+            | ast::StatementType::FunctionDef { type_comment, .. } => type_comment.clone(),
+            node => panic!("{:?}", node),
+        };
+        let comments: Vec<_> = program.statements.iter().map(type_comment).collect();
+        assert_eq!(
+            comments,
+            vec![
+                Some("int".to_owned()),
+                Some("(int) -> None".to_owned()),
+                Some("() -> int".to_owned()),
+            ]
+        );
+        if let ast::StatementType::FunctionDef { body, .. } = &program.statements[1].node {
+            assert_eq!(type_comment(&body[0]), Some("str".to_owned()));
+        }
+        if let ast::StatementType::FunctionDef { body, .. } = &program.statements[2].node {
+            assert_eq!(type_comment(&body[0]), None);
+        }
+        assert_eq!(
+            type_ignores,
+            vec![
+                ast::TypeIgnore {
+                    lineno: 7,
+                    tag: "[misc]".to_owned()
+                },
+                ast::TypeIgnore {
+                    lineno: 8,
+                    tag: "".to_owned()
+                },
+            ]
+        );
+
+        // Without asking for them, type comments are just comments
+        assert_eq!(
+            parse_program(source).unwrap().statements.len(),
+            program.statements.len()
+        );
+
+        for source in ["x + 1  # type: int\n", "x = 1\n# type: int\n"].iter() {
+            assert!(parse_program_with_type_comments(source).is_err());
+        }
+        let error = parse_program_with_type_comments(
+            "def f():  # type: () -> None\n    # type: int\n    pass\n",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.error,
+            ParseErrorType::Lexical(LexicalErrorType::DuplicateTypeCommentError)
+        );
+        assert_eq!(error.location.row(), 2);
+    }
+
+    #[test]
+    fn test_parse_program_recovering() {
+        let source = "\
+x = 1 +
+y = 2 +
+def f(a):
+    b = = 1
+    if a
+        c = 1
+    return a
+z = 3
+";
+        let errors = parse_program_recovering(source).unwrap_err();
+        let rows: Vec<_> = errors.iter().map(|err| err.location.row()).collect();
+        assert_eq!(rows, vec![1, 2, 4, 5], "{:?}", errors);
+
+        // The lexer can't go on after an unterminated string
+        let errors = parse_program_recovering("x = 1 +\ny = 'abc\nz = = 1\n").unwrap_err();
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_eq!(
+            errors[1].error,
+            ParseErrorType::Lexical(LexicalErrorType::StringError)
+        );
+
+        let source = "def f():\n    return 1\nx = f()\n";
+        let (program, _) = parse_program_recovering(source).unwrap();
+        assert_eq!(program, parse_program(source).unwrap());
+    }
 }
@@ -12,6 +12,7 @@ pub enum Tok {
     Complex { real: f64, imag: f64 },
     String { value: String, is_fstring: bool },
     Bytes { value: Vec<u8> },
+    TypeComment { value: String },
     Newline,
     Indent,
     Dedent,
@@ -24,6 +25,7 @@ pub enum Tok {
     Lsqb,
     Rsqb,
     Colon,
+    ColonEqual,
     Comma,
     Semi,
     Plus,
@@ -134,6 +136,7 @@ impl fmt::Display for Tok {
                 }
                 f.write_str("\"")
             }
+            TypeComment { value } => write!(f, "'# type: {}'", value),
             Newline => f.write_str("Newline"),
             Indent => f.write_str("Indent"),
             Dedent => f.write_str("Dedent"),
@@ -146,6 +149,7 @@ impl fmt::Display for Tok {
             Lsqb => f.write_str("'['"),
             Rsqb => f.write_str("']'"),
             Colon => f.write_str("':'"),
+            ColonEqual => f.write_str("':='"),
             Comma => f.write_str("','"),
             Semi => f.write_str("';'"),
             Plus => f.write_str("'+'"),
@@ -0,0 +1,21 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let output = Path::new(&out_dir).join("fixture");
+    let args = vec![
+        "rustpython-compile".to_string(),
+        "--output".to_string(),
+        output.to_string_lossy().into_owned(),
+        "fixture".to_string(),
+    ];
+    let matches = rustpython_compile::app().get_matches_from(args);
+    rustpython_compile::run(&matches).expect("Compiling the fixture failed");
+
+    let modules = rustpython_compiler::freeze_dir(Path::new("fixture"), &Default::default())
+        .expect("Freezing the fixture failed");
+    rustpython_compiler::frozen::write_out_dir("fixture.rpfrozen", &modules, true)
+        .expect("Writing the frozen fixture failed");
+    println!("cargo:rerun-if-changed=fixture");
+}
@@ -0,0 +1,7 @@
+from __future__ import annotations
+
+answer: Answer = 42
+
+
+class Answer(int):
+    pass
@@ -0,0 +1,3 @@
+# -*- coding: latin-1 -*-
+# This file is encoded in latin-1, not UTF-8.
+greeting = "hllo frm ltin-1"
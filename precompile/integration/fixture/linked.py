@@ -0,0 +1 @@
+pkg/sub/answer.py
\ No newline at end of file
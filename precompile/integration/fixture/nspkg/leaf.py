@@ -0,0 +1 @@
+name = __name__
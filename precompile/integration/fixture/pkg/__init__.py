@@ -0,0 +1 @@
+greeting = "hello from " + __name__
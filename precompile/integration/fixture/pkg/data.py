@@ -0,0 +1,24 @@
+import importlib.resources
+
+
+def greeting():
+    return importlib.resources.read_text(__package__, "greeting.txt")
+
+
+def blob():
+    return importlib.resources.read_binary("pkg.sub", "blob.bin")
+
+
+def contents():
+    return sorted(importlib.resources.contents(__package__))
+
+
+def is_resource(name):
+    return importlib.resources.is_resource(__package__, name)
+
+
+def missing():
+    try:
+        importlib.resources.read_binary(__package__, "missing.txt")
+    except FileNotFoundError as err:
+        return str(err)
@@ -0,0 +1,5 @@
+from pkg import greeting
+
+
+def answer():
+    return 6 * 7
@@ -0,0 +1,2 @@
+# Frozen after `annotated`, whose `from __future__ import annotations` doesn't carry over.
+name: str = __name__
@@ -0,0 +1,259 @@
+//! Freezes the fixture tree that the build script compiles with `rustpython-compile`, and checks
+//! that its modules can be imported and are the same as what the macro compiles itself, and as
+//! what the build script freezes with `rustpython_compiler::freeze_dir`. The
+//! macro also freezes the tree with its data files, which the modules read as resources.
+//!
+//! `fixture.zip` holds the package `zipped` below `purelib/`, along with a `tests` package and a
+//! module outside of `purelib/` that raise when they're imported, so they mustn't be frozen.
+
+#[cfg(test)]
+mod tests {
+    use rustpython_vm::bytecode::FrozenModule;
+    use rustpython_vm::obj::{objbool, objbytes, objint, objstr};
+    use rustpython_vm::pyobject::ItemProtocol;
+    use rustpython_vm::{
+        frozen_file, import, print_exception, py_compile_bytecode, PySettings, VirtualMachine,
+    };
+    use std::collections::HashMap;
+
+    fn new_vm() -> VirtualMachine {
+        let vm = VirtualMachine::default();
+        vm.frozen
+            .borrow_mut()
+            .extend(py_compile_bytecode!(precompiled_dir = "$OUT_DIR/fixture"));
+        import::init_importlib(&vm, false).unwrap();
+        vm
+    }
+
+    #[test]
+    fn test_precompiled_matches_compiled() {
+        let precompiled: HashMap<String, FrozenModule> =
+            py_compile_bytecode!(precompiled_dir = "$OUT_DIR/fixture");
+        let compiled: HashMap<String, FrozenModule> =
+            py_compile_bytecode!(dir = "fixture", cache = true);
+        assert_eq!(precompiled.len(), 10);
+        for (name, module) in compiled {
+            assert!(
+                precompiled[&name].code.decode() == module.code.decode(),
+                "{} differs",
+                name
+            );
+            assert_eq!(precompiled[&name].package, module.package);
+            assert_eq!(precompiled[&name].source_path, module.source_path);
+        }
+    }
+
+    #[test]
+    fn test_build_script_frozen_matches_compiled() {
+        static FROZEN: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/fixture.rpfrozen"));
+        let frozen = frozen_file::read(FROZEN).unwrap();
+        let compiled: HashMap<String, FrozenModule> = py_compile_bytecode!(dir = "fixture");
+        assert_eq!(frozen.len(), compiled.len());
+        for (name, module) in frozen {
+            assert!(
+                compiled[&name].code.decode() == module.code.decode(),
+                "{} differs",
+                name
+            );
+            assert_eq!(compiled[&name].package, module.package);
+            assert_eq!(compiled[&name].source_path, module.source_path);
+        }
+
+        let vm = VirtualMachine::default();
+        vm.add_frozen_overwrite(frozen_file::read(FROZEN).unwrap().into_iter().collect());
+        import::init_importlib(&vm, false).unwrap();
+        let module = vm.import("toplevel", &[], 0).unwrap();
+        let name = vm.get_attribute(module, "name").unwrap();
+        assert_eq!(objstr::get_value(&name), "toplevel");
+    }
+
+    #[test]
+    fn test_import_module() {
+        let vm = new_vm();
+        let module = vm.import("toplevel", &[], 0).unwrap();
+        let name = vm.get_attribute(module, "name").unwrap();
+        assert_eq!(objstr::get_value(&name), "toplevel");
+    }
+
+    #[test]
+    fn test_import_package() {
+        let vm = new_vm();
+        let module = vm.import("pkg.sub.answer", &[], 0).unwrap();
+        let module = vm.get_attribute(module, "sub").unwrap();
+        let module = vm.get_attribute(module, "answer").unwrap();
+
+        let greeting = vm.get_attribute(module.clone(), "greeting").unwrap();
+        assert_eq!(objstr::get_value(&greeting), "hello from pkg");
+        let answer = vm.get_attribute(module, "answer").unwrap();
+        let answer = vm.invoke(&answer, vec![]).unwrap();
+        assert_eq!(*objint::get_value(&answer), 42.into());
+    }
+
+    #[test]
+    fn test_import_namespace_package() {
+        let vm = new_vm();
+        let module = vm.import("nspkg.leaf", &[], 0).unwrap();
+        let leaf = vm.get_attribute(module.clone(), "leaf").unwrap();
+        let name = vm.get_attribute(leaf, "name").unwrap();
+        assert_eq!(objstr::get_value(&name), "nspkg.leaf");
+        assert!(vm.get_attribute(module, "__file__").is_err());
+        assert_eq!(
+            file_and_origin(&vm, "nspkg.leaf"),
+            (
+                "<frozen nspkg/leaf.py>".to_owned(),
+                "frozen".to_owned(),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn test_import_module_with_declared_encoding() {
+        let vm = new_vm();
+        let module = vm.import("legacy", &[], 0).unwrap();
+        let greeting = vm.get_attribute(module, "greeting").unwrap();
+        assert_eq!(
+            objstr::get_value(&greeting),
+            "h\u{e9}llo fr\u{f6}m l\u{e0}tin-1"
+        );
+    }
+
+    #[test]
+    fn test_future_annotations_are_per_module() {
+        let mut settings = PySettings::default();
+        // the future import still imports `__future__`, which isn't frozen
+        settings
+            .path_list
+            .push(concat!(env!("CARGO_MANIFEST_DIR"), "/../../Lib").to_owned());
+        settings.add_frozen_overwrite(py_compile_bytecode!(dir = "fixture"));
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, true).unwrap();
+        let annotations = |name: &str| {
+            let module = vm.import(name, &[], 0).unwrap();
+            let annotations = vm.get_attribute(module, "__annotations__").unwrap();
+            vm.to_repr(&annotations).unwrap().as_str().to_owned()
+        };
+        assert_eq!(annotations("annotated"), "{'answer': 'Answer'}");
+        assert_eq!(annotations("toplevel"), "{'name': <class 'str'>}");
+    }
+
+    fn file_and_origin(vm: &VirtualMachine, name: &str) -> (String, String, bool) {
+        vm.import(name, &[], 0).unwrap();
+        let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+        let module = sys_modules.get_item(name, vm).unwrap();
+        let file = vm.get_attribute(module.clone(), "__file__").unwrap();
+        let spec = vm.get_attribute(module, "__spec__").unwrap();
+        let origin = vm.get_attribute(spec.clone(), "origin").unwrap();
+        let has_location = vm.get_attribute(spec, "has_location").unwrap();
+        (
+            objstr::get_value(&file),
+            objstr::get_value(&origin),
+            objbool::get_value(&has_location),
+        )
+    }
+
+    #[test]
+    fn test_module_file() {
+        let vm = new_vm();
+        let frozen = |file: &str| (file.to_owned(), "frozen".to_owned(), false);
+        assert_eq!(
+            file_and_origin(&vm, "toplevel"),
+            frozen("<frozen toplevel.py>")
+        );
+        assert_eq!(
+            file_and_origin(&vm, "pkg"),
+            frozen("<frozen pkg/__init__.py>")
+        );
+        assert_eq!(
+            file_and_origin(&vm, "pkg.sub"),
+            frozen("<frozen pkg/sub/__init__.py>")
+        );
+        assert_eq!(
+            file_and_origin(&vm, "pkg.sub.answer"),
+            frozen("<frozen pkg/sub/answer.py>")
+        );
+        // a symlinked source file records the path it was found at, not the one it points to
+        assert_eq!(file_and_origin(&vm, "linked"), frozen("<frozen linked.py>"));
+    }
+
+    #[test]
+    fn test_resources() {
+        let mut settings = PySettings::default();
+        // importlib.resources isn't frozen
+        settings
+            .path_list
+            .push(concat!(env!("CARGO_MANIFEST_DIR"), "/../../Lib").to_owned());
+        settings.add_frozen_overwrite(py_compile_bytecode!(
+            dir = "fixture",
+            include_resources = "pkg/*.txt",
+            include_resources = "**/*.bin",
+        ));
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, true).unwrap();
+
+        let frozen = vm.frozen.borrow();
+        assert_eq!(frozen["pkg"].resources.len(), 1);
+        assert_eq!(frozen["pkg.sub"].resources.len(), 1);
+        assert!(frozen["pkg.sub.answer"].resources.is_empty());
+        drop(frozen);
+
+        let module = vm.import("pkg.data", &["greeting".to_owned()], 0).unwrap();
+        let call = |name: &str, args: Vec<_>| {
+            let function = vm.get_attribute(module.clone(), name).unwrap();
+            vm.invoke(&function, args).unwrap_or_else(|exc| {
+                print_exception(&vm, &exc);
+                panic!("{} raised an exception", name)
+            })
+        };
+
+        let greeting = call("greeting", vec![]);
+        assert_eq!(objstr::get_value(&greeting), "h\u{e9}llo from a resource\n");
+        let blob = call("blob", vec![]);
+        assert_eq!(
+            objbytes::get_value(&blob).to_vec(),
+            (0..=255).collect::<Vec<u8>>()
+        );
+        let contents = call("contents", vec![]);
+        assert_eq!(vm.to_repr(&contents).unwrap().as_str(), "['greeting.txt']");
+        let is_resource = call("is_resource", vec![vm.new_str("greeting.txt".to_owned())]);
+        assert!(objbool::get_value(&is_resource));
+        let is_resource = call("is_resource", vec![vm.new_str("data.py".to_owned())]);
+        assert!(!objbool::get_value(&is_resource));
+        let missing = call("missing", vec![]);
+        assert_eq!(
+            objstr::get_value(&missing),
+            "'missing.txt' resource not found in 'pkg'"
+        );
+    }
+
+    #[test]
+    fn test_zip() {
+        let modules: HashMap<String, FrozenModule> = py_compile_bytecode!(
+            zip = "fixture.zip",
+            zip_prefix = "purelib/",
+            exclude = "**/tests",
+        );
+        let mut names: Vec<_> = modules.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, ["zipped", "zipped.inner", "zipped.inner.leaf"]);
+        assert!(modules["zipped"].package);
+        assert!(modules["zipped.inner"].package);
+        assert!(!modules["zipped.inner.leaf"].package);
+
+        let vm = VirtualMachine::default();
+        vm.add_frozen_overwrite(modules);
+        import::init_importlib(&vm, false).unwrap();
+        let module = vm.import("zipped.inner.leaf", &[], 0).unwrap();
+        let leaf = vm.get_attribute(module, "inner").unwrap();
+        let leaf = vm.get_attribute(leaf, "leaf").unwrap();
+        let parent_name = vm.get_attribute(leaf.clone(), "parent_name").unwrap();
+        assert_eq!(objstr::get_value(&parent_name), "zipped");
+        let answer = vm.get_attribute(leaf, "answer").unwrap();
+        let answer = vm.invoke(&answer, vec![]).unwrap();
+        assert_eq!(*objint::get_value(&answer), 42.into());
+        assert_eq!(
+            file_and_origin(&vm, "zipped.inner.leaf").0,
+            "<frozen zipped/inner/leaf.py>"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Compile python sources ahead of time into the bytecode files read by
+//! `py_compile_bytecode!(precompiled_dir = "...")`, so that freezing a large tree of python code
+//! doesn't have to compile it inside the proc macro on every build.
+//!
+//! Every input file or package directory is compiled exactly like the `file` and `dir` sources
+//! of the macro would compile it. The output directory gets one `<module>.rpbc` file holding the
+//! `CodeObject::to_bytes()` of each module, plus a manifest listing the modules.
+
+#[macro_use]
+extern crate clap;
+
+use clap::{App, Arg, ArgMatches};
+use rustpython_compiler::frozen::{self, Manifest, ManifestEntry, SourceModule};
+use rustpython_compiler::{compile, encoding};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+pub fn app() -> App<'static, 'static> {
+    App::new("rustpython-compile")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Compiles python files and package directories into RustPython bytecode files.")
+        .arg(
+            Arg::with_name("inputs")
+                .help("Python files, or directories to compile all the modules below")
+                .multiple(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("The directory to write the bytecode files and the manifest to")
+                .long("output")
+                .short("o")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .help("The mode to compile the sources in")
+                .long("mode")
+                .short("m")
+                .default_value("exec")
+                .possible_values(&["exec", "single", "eval"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("module_name")
+                .help("The module name of a single input file; defaults to the file's stem")
+                .long("module-name")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("optimize")
+                .help("The amount of optimization to apply to the compiled bytecode")
+                .long("optimize")
+                .short("O")
+                .multiple(true),
+        )
+}
+
+/// Compile the inputs named by `matches`, which must come from `app()`, and write the output
+/// directory.
+pub fn run(matches: &ArgMatches) -> Result<(), Box<dyn Error>> {
+    let mode: compile::Mode = matches.value_of_lossy("mode").unwrap().parse().unwrap();
+    let optimize = matches.occurrences_of("optimize") as u8;
+    let output = Path::new(matches.value_of_os("output").unwrap());
+    let inputs: Vec<&Path> = matches
+        .values_of_os("inputs")
+        .unwrap()
+        .map(Path::new)
+        .collect();
+    let module_name = matches.value_of("module_name");
+    if module_name.is_some() && (inputs.len() != 1 || inputs[0].is_dir()) {
+        return Err("--module-name requires exactly one input file".into());
+    }
+
+    let mut modules = Vec::new();
+    for input in inputs {
+        if input.is_dir() {
+            modules.extend(frozen::find_modules(input)?);
+        } else {
+            let name = match module_name {
+                Some(name) => name.to_string(),
+                None => input
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .ok_or_else(|| format!("Can't derive a module name from {:?}", input))?
+                    .to_string(),
+            };
+            let file_name = input
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| format!("Invalid UTF-8 in file name {:?}", input))?;
+            modules.push(SourceModule {
+                name,
+                path: input.to_path_buf(),
+                relative_path: file_name.to_string(),
+                package: false,
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(module) = modules.iter().find(|module| !seen.insert(&module.name)) {
+        return Err(format!("Module {:?} is given more than once", module.name).into());
+    }
+
+    fs::create_dir_all(output)
+        .map_err(|err| format!("Error creating dir {:?}: {}", output, err))?;
+    let mut manifest = Manifest::default();
+    for module in modules {
+        let source = fs::read(&module.path)
+            .map_err(|err| format!("Error reading file {:?}: {}", module.path, err))?;
+        let source = encoding::decode_source(&source)
+            .map_err(|err| format!("Error decoding file {:?}: {}", module.path, err))?;
+        let code = compile::compile(&source, mode, module.name.clone(), optimize)
+            .map_err(|err| format!("Compile error in {:?}: {}", module.path, err))?;
+        let bytecode_path = output.join(frozen::bytecode_file_name(&module.name));
+        fs::write(&bytecode_path, code.to_bytes())
+            .map_err(|err| format!("Error writing file {:?}: {}", bytecode_path, err))?;
+        manifest.entries.push(ManifestEntry {
+            hash: code.content_hash(true),
+            name: module.name,
+            package: module.package,
+            source_path: module.relative_path,
+        });
+    }
+
+    let manifest_path = output.join(frozen::MANIFEST_FILE_NAME);
+    let mut text = Vec::new();
+    manifest.write(&mut text)?;
+    fs::write(&manifest_path, text)
+        .map_err(|err| format!("Error writing file {:?}: {}", manifest_path, err))?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+/// Compile python source files and package trees into RustPython bytecode files, which can then
+/// be frozen into a binary with `py_compile_bytecode!(precompiled_dir = "...")`.
+///
+/// example usage:
+/// $ cargo run --bin rustpython-compile -- --output frozen Lib/
+use std::process;
+
+fn main() {
+    let matches = rustpython_compile::app().get_matches();
+    if let Err(err) = rustpython_compile::run(&matches) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -5,14 +5,14 @@ extern crate env_logger;
 extern crate log;
 
 use clap::{App, AppSettings, Arg, ArgMatches};
-use rustpython_compiler::compile;
+use rustpython_compiler::encoding;
 use rustpython_vm::{
-    import, match_class,
+    match_class,
     obj::{objint::PyInt, objtuple::PyTuple, objtype},
     print_exception,
     pyobject::{ItemProtocol, PyResult},
     scope::Scope,
-    util, PySettings, VirtualMachine,
+    InterpreterBuilder, PySettings, VirtualMachine,
 };
 
 use std::convert::TryInto;
@@ -29,10 +29,11 @@ fn main() {
     env_logger::init();
     let app = App::new("RustPython");
     let matches = parse_arguments(app);
-    let settings = create_settings(&matches);
-    let vm = VirtualMachine::new(settings);
+    let interpreter = InterpreterBuilder::new()
+        .settings(create_settings(&matches))
+        .build();
 
-    let res = run_rustpython(&vm, &matches);
+    let res = interpreter.enter(|vm| run_rustpython(vm, &matches));
 
     #[cfg(feature = "flame-it")]
     {
@@ -44,35 +45,40 @@ fn main() {
 
     // See if any exception leaked out:
     if let Err(err) = res {
-        if objtype::isinstance(&err, &vm.ctx.exceptions.system_exit) {
-            let args = vm.get_attribute(err.clone(), "args").unwrap();
-            let args = args.downcast::<PyTuple>().expect("'args' must be a tuple");
-            match args.elements.len() {
-                0 => return,
-                1 => match_class!(match args.elements[0].clone() {
-                    i @ PyInt => {
-                        use num_traits::cast::ToPrimitive;
-                        process::exit(i.as_bigint().to_i32().unwrap());
-                    }
-                    arg => {
-                        if vm.is_none(&arg) {
-                            return;
+        interpreter.enter(|vm| {
+            if objtype::isinstance(&err, &vm.ctx.exceptions.system_exit) {
+                let args = vm.get_attribute(err.clone(), "args").unwrap();
+                let args = args.downcast::<PyTuple>().expect("'args' must be a tuple");
+                match args.elements.len() {
+                    0 => return,
+                    1 => match_class!(match args.elements[0].clone() {
+                        i @ PyInt => {
+                            use num_traits::cast::ToPrimitive;
+                            let code = i.as_bigint().to_i32().unwrap();
+                            vm.shutdown();
+                            process::exit(code);
                         }
-                        if let Ok(s) = vm.to_str(&arg) {
-                            println!("{}", s);
+                        arg => {
+                            if vm.is_none(&arg) {
+                                return;
+                            }
+                            if let Ok(s) = vm.to_str(&arg) {
+                                println!("{}", s);
+                            }
+                        }
+                    }),
+                    _ => {
+                        if let Ok(r) = vm.to_repr(args.as_object()) {
+                            println!("{}", r);
                         }
-                    }
-                }),
-                _ => {
-                    if let Ok(r) = vm.to_repr(args.as_object()) {
-                        println!("{}", r);
                     }
                 }
+            } else {
+                print_exception(&vm, &err);
             }
-        } else {
-            print_exception(&vm, &err);
-        }
-        process::exit(1);
+            vm.shutdown();
+            process::exit(1);
+        });
     }
 }
 
@@ -232,6 +238,14 @@ fn create_settings(matches: &ArgMatches) -> PySettings {
         settings.dont_write_bytecode = true;
     }
 
+    if !ignore_environment && env::var_os("RUSTPYTHON_PREFER_PATH").is_some() {
+        settings.prefer_path = true;
+    }
+
+    if !ignore_environment && env::var_os("RUSTPYTHON_NO_INLINE_CACHES").is_some() {
+        settings.no_inline_caches = true;
+    }
+
     let argv = if let Some(script) = matches.values_of("script") {
         script.map(ToOwned::to_owned).collect()
     } else if let Some(module) = matches.values_of("m") {
@@ -322,9 +336,6 @@ fn write_profile(matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>>
 }
 
 fn run_rustpython(vm: &VirtualMachine, matches: &ArgMatches) -> PyResult<()> {
-    // We only include the standard library bytecode in WASI
-    import::init_importlib(&vm, cfg!(not(target_os = "wasi")))?;
-
     if let Some(paths) = option_env!("BUILDTIME_RUSTPYTHONPATH") {
         let sys_path = vm.get_attribute(vm.sys_module.clone(), "path")?;
         for (i, path) in std::env::split_paths(paths).enumerate() {
@@ -343,11 +354,8 @@ fn run_rustpython(vm: &VirtualMachine, matches: &ArgMatches) -> PyResult<()> {
         }
     }
 
-    let scope = vm.new_scope_with_builtins();
-    let main_module = vm.new_module("__main__", scope.globals.clone());
-
-    vm.get_attribute(vm.sys_module.clone(), "modules")?
-        .set_item("__main__", main_module, vm)?;
+    let scope = Scope::with_module_defaults(&vm, "__main__", None);
+    vm.new_registered_module("__main__", scope.globals.clone())?;
 
     let site_result = vm.import("site", &[], 0);
 
@@ -358,6 +366,8 @@ fn run_rustpython(vm: &VirtualMachine, matches: &ArgMatches) -> PyResult<()> {
         );
     }
 
+    shell::install_input(&vm)?;
+
     // Figure out if a -c option was given:
     if let Some(command) = matches.value_of("c") {
         run_command(&vm, scope, command.to_string())?;
@@ -373,14 +383,10 @@ fn run_rustpython(vm: &VirtualMachine, matches: &ArgMatches) -> PyResult<()> {
 }
 
 fn _run_string(vm: &VirtualMachine, scope: Scope, source: &str, source_path: String) -> PyResult {
-    let code_obj = vm
-        .compile(source, compile::Mode::Exec, source_path.clone())
-        .map_err(|err| vm.new_syntax_error(&err))?;
-    // trace!("Code object: {:?}", code_obj.borrow());
     scope
         .globals
-        .set_item("__file__", vm.new_str(source_path), vm)?;
-    vm.run_code_obj(code_obj, scope)
+        .set_item("__file__", vm.new_str(source_path.clone()), vm)?;
+    vm.run_code_string(scope, source, source_path)
 }
 
 fn run_command(vm: &VirtualMachine, scope: Scope, source: String) -> PyResult<()> {
@@ -426,8 +432,10 @@ fn run_script(vm: &VirtualMachine, scope: Scope, script_file: &str) -> PyResult<
     let sys_path = vm.get_attribute(vm.sys_module.clone(), "path").unwrap();
     vm.call_method(&sys_path, "insert", vec![vm.new_int(0), vm.new_str(dir)])?;
 
-    match util::read_file(&file_path) {
+    match std::fs::read(&file_path) {
         Ok(source) => {
+            let source =
+                encoding::decode_source(&source).map_err(|err| vm.new_syntax_error(&err))?;
             _run_string(vm, scope, &source, file_path.to_str().unwrap().to_string())?;
         }
         Err(err) => {
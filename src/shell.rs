@@ -5,13 +5,16 @@ mod rustyline_helper;
 use rustpython_compiler::{compile, error::CompileError, error::CompileErrorType};
 use rustpython_parser::error::ParseErrorType;
 use rustpython_vm::{
+    function::OptionalArg,
     obj::objtype,
     print_exception,
-    pyobject::{ItemProtocol, PyObjectRef, PyResult},
+    pyobject::{PyObjectRef, PyResult},
     scope::Scope,
     VirtualMachine,
 };
 
+use std::cell::RefCell;
+
 use readline::{Readline, ReadlineResult};
 
 enum ShellExecResult {
@@ -23,15 +26,9 @@ enum ShellExecResult {
 fn shell_exec(vm: &VirtualMachine, source: &str, scope: Scope) -> ShellExecResult {
     match vm.compile(source, compile::Mode::Single, "<stdin>".to_string()) {
         Ok(code) => {
-            match vm.run_code_obj(code, scope.clone()) {
-                Ok(value) => {
-                    // Save non-None values as "_"
-                    if !vm.is_none(&value) {
-                        let key = "_";
-                        scope.globals.set_item(key, value, vm).unwrap();
-                    }
-                    ShellExecResult::Ok
-                }
+            // sys.displayhook prints the values of expressions and keeps them in `_`
+            match vm.run_code_obj(code, scope) {
+                Ok(_) => ShellExecResult::Ok,
                 Err(err) => ShellExecResult::PyErr(err),
             }
         }
@@ -152,3 +149,55 @@ pub fn run_shell(vm: &VirtualMachine, scope: Scope) -> PyResult<()> {
 
     Ok(())
 }
+
+thread_local! {
+    static INPUT_HISTORY: RefCell<Vec<String>> = RefCell::new(vec![]);
+}
+
+/// Make the `input` builtin read through the REPL's line editor when running in a terminal,
+/// so that e.g. pdb prompts get line editing and history.
+pub fn install_input(vm: &VirtualMachine) -> PyResult<()> {
+    if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) {
+        vm.set_attr(&vm.builtins, "input", vm.ctx.new_rustfunc(readline_input))?;
+    }
+    Ok(())
+}
+
+fn readline_input(prompt: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult<String> {
+    let prompt = match prompt {
+        OptionalArg::Present(prompt) => vm.to_str(&prompt)?.as_str().to_owned(),
+        OptionalArg::Missing => String::new(),
+    };
+    let scope = match vm.current_frame() {
+        Some(frame) => frame.scope.clone(),
+        None => vm.new_scope_with_builtins(),
+    };
+
+    let mut repl = Readline::new(vm, scope);
+    INPUT_HISTORY.with(|history| {
+        for entry in history.borrow().iter() {
+            repl.add_history_entry(entry).unwrap();
+        }
+    });
+    match repl.readline(&prompt) {
+        ReadlineResult::Line(line) => {
+            if !line.trim().is_empty() {
+                INPUT_HISTORY.with(|history| history.borrow_mut().push(line.clone()));
+            }
+            Ok(line)
+        }
+        ReadlineResult::EOF => Err(vm.new_exception(
+            vm.ctx.exceptions.eof_error.clone(),
+            "EOF when reading a line".to_string(),
+        )),
+        ReadlineResult::Interrupt => {
+            Err(vm.new_empty_exception(vm.ctx.exceptions.keyboard_interrupt.clone())?)
+        }
+        ReadlineResult::EncodingError => Err(vm.new_exception(
+            vm.ctx.exceptions.unicode_error.clone(),
+            "Invalid UTF-8 entered".to_string(),
+        )),
+        ReadlineResult::IO(err) => Err(vm.new_os_error(err.to_string())),
+        ReadlineResult::Other(err) => Err(vm.new_os_error(err.to_string())),
+    }
+}
@@ -0,0 +1,136 @@
+# Regenerates snippets/cpython_error_messages.py, the expected exceptions that
+# snippets/error_messages.py checks RustPython against. Run it with CPython 3.7
+# (the version RustPython targets), e.g. `python3.7 -I error_messages_gen.py`;
+# newer versions have changed the wording of several of these messages.
+
+import os
+import sys
+
+# One value of each builtin type, written as the source that creates it.
+VALUES = [
+    "1",
+    "1.5",
+    "1j",
+    "True",
+    "None",
+    "'a'",
+    "b'a'",
+    "bytearray(b'a')",
+    "[1]",
+    "(1,)",
+    "{1: 1}",
+    "{1}",
+    "frozenset({1})",
+    "range(1)",
+]
+
+BINARY_OPERATORS = [
+    "+", "-", "*", "/", "//", "%", "**", "@", "<<", ">>", "&", "|", "^",
+    "<", "<=", ">", ">=",
+]
+
+UNARY_OPERATORS = ["-", "+", "~"]
+
+PROTOCOL_SNIPPETS = [
+    # iteration
+    "iter({})",
+    "for x in {}: pass",
+    "a, b = {}",
+    "[*{}]",
+    # calls
+    "{}()",
+    # subscripts
+    "{}[0]",
+    "{}['a']",
+    "{}[0] = 1",
+    "del {}[0]",
+    # len()
+    "len({})",
+]
+
+EXTRA_SNIPPETS = [
+    "[1][5]",
+    "(1,)[5]",
+    "'a'[5]",
+    "[1][5] = 1",
+    "{}['a']",
+    "{}[[]]",
+    "{[]: 1}",
+    "{[]}",
+    "hash([])",
+    "[1].index(2)",
+    "'a'.index('b')",
+    "[].pop()",
+    "{}.popitem()",
+    "set().pop()",
+    "set().remove(1)",
+    "1 / 0",
+    "1 // 0",
+    "1 % 0",
+    "1.5 / 0",
+    "divmod(1, 0)",
+    "int('a')",
+    "float('a')",
+    "ord('ab')",
+    "chr(-1)",
+]
+
+SIGNATURES = [
+    ("def f(): pass", ["f(1)", "f(1, 2)", "f(a=1)"]),
+    ("def f(a): pass", ["f()", "f(1, 2)", "f(b=1)", "f(1, a=1)"]),
+    ("def f(a, b): pass", ["f()", "f(1)", "f(1, 2, 3)", "f(b=1)"]),
+    ("def f(a, b, c): pass", ["f()", "f(1)"]),
+    ("def f(a, b=1): pass", ["f()", "f(1, 2, 3)"]),
+    ("def f(*, a): pass", ["f()", "f(b=1)"]),
+    ("def f(*, a, b): pass", ["f()"]),
+    ("def f(a, *args): pass", ["f()", "f(a=1, b=2)"]),
+    ("def f(**kwargs): pass", ["f(1)"]),
+    ("class C:\n def m(self, a): pass", ["C().m()", "C().m(1, 2)"]),
+    ("class C:\n def __init__(self, a): pass", ["C()", "C(1, 2)"]),
+]
+
+
+def snippets():
+    for left in VALUES:
+        for op in BINARY_OPERATORS:
+            for right in VALUES:
+                yield f"{left} {op} {right}"
+    for op in UNARY_OPERATORS:
+        for value in VALUES:
+            yield f"{op}{value}"
+    for template in PROTOCOL_SNIPPETS:
+        for value in VALUES:
+            yield template.format(value)
+    yield from EXTRA_SNIPPETS
+    for definition, calls in SIGNATURES:
+        for call in calls:
+            yield f"{definition}\n{call}"
+
+
+def expected_error(source):
+    try:
+        exec(source, {})
+    except Exception as e:
+        return (source, type(e).__name__, str(e))
+    return None
+
+
+def main():
+    if sys.version_info[:2] != (3, 7):
+        print("warning: expected messages should come from CPython 3.7", file=sys.stderr)
+    path = os.path.join(os.path.dirname(__file__), "snippets", "cpython_error_messages.py")
+    with open(path, "w") as output:
+        output.write(
+            "# This file was generated by tests/error_messages_gen.py; do not edit it by hand.\n"
+        )
+        output.write("# (source, exception type, message)\n")
+        output.write("EXPECTED_ERRORS = [\n")
+        for source in snippets():
+            error = expected_error(source)
+            if error is not None:
+                output.write(f"    {error!r},\n")
+        output.write("]\n")
+
+
+if __name__ == "__main__":
+    main()
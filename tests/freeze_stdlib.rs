@@ -0,0 +1,65 @@
+//! Run the `rustpython` binary built with the `freeze-stdlib` feature somewhere there's no Lib
+//! directory to import the standard library from.
+//!
+//! ```sh
+//! cargo test --features freeze-stdlib --test freeze_stdlib
+//! ```
+#![cfg(feature = "freeze-stdlib")]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+const SCRIPT: &str = r#"
+import collections, functools, json, os, re, string, textwrap, types
+
+assert os.__file__ == "<frozen os.py>", os.__file__
+assert collections.__file__ == "<frozen collections/__init__.py>", collections.__file__
+assert textwrap.dedent.__doc__ is None
+
+assert os.path.join("a", "b") == "a/b"
+assert re.sub("a+", "b", "caaat") == "cbt"
+assert json.loads(json.dumps({"a": [1, 2]})) == {"a": [1, 2]}
+assert textwrap.dedent("  x\n  y\n") == "x\ny\n"
+assert collections.OrderedDict(a=1)["a"] == 1
+assert functools.reduce(lambda a, b: a + b, [1, 2, 3]) == 6
+assert string.ascii_lowercase[:3] == "abc"
+assert isinstance(types.SimpleNamespace(), types.SimpleNamespace)
+print("ok")
+"#;
+
+/// The binary is built next to the directory of the test executables.
+fn rustpython_binary() -> PathBuf {
+    let mut path = env::current_exe().unwrap();
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.join(format!("rustpython{}", env::consts::EXE_SUFFIX))
+}
+
+#[test]
+fn test_stdlib_imports_without_lib_dir() {
+    let dir = env::temp_dir().join(format!("rustpython-freeze-stdlib-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let output = Command::new(rustpython_binary())
+        .arg("-c")
+        .arg(SCRIPT)
+        .current_dir(&dir)
+        .env_remove("RUSTPYTHONPATH")
+        .env_remove("PYTHONPATH")
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "ok\n",
+        "{}",
+        stderr
+    );
+}
@@ -0,0 +1,91 @@
+import ast
+
+from testutils import assert_raises
+
+# Nodes have their fields and location attributes on the class
+assert ast.BinOp._fields == ("left", "op", "right")
+assert ast.Name._fields == ("id", "ctx")
+assert "lineno" in ast.stmt._attributes
+assert "col_offset" in ast.expr._attributes
+assert issubclass(ast.FunctionDef, ast.stmt)
+assert issubclass(ast.Add, ast.operator)
+
+node = ast.Name("x", ast.Load())
+assert node.id == "x"
+assert isinstance(node.ctx, ast.Load)
+node = ast.Name(id="y", ctx=ast.Store(), lineno=3)
+assert node.id == "y"
+assert node.lineno == 3
+with assert_raises(TypeError):
+    ast.Name("x", ast.Load(), "too many")
+
+tree = ast.parse("x = 1\ny = x + 2\n")
+assert isinstance(tree, ast.Module)
+assign = tree.body[1]
+assert isinstance(assign, ast.Assign)
+assert (assign.lineno, assign.col_offset) == (2, 0)
+assert isinstance(assign.targets[0].ctx, ast.Store)
+assert isinstance(assign.value.op, ast.Add)
+assert isinstance(assign.value.left.ctx, ast.Load)
+
+expression = ast.parse("1 + 2", mode="eval")
+assert isinstance(expression, ast.Expression)
+assert isinstance(expression.body, ast.BinOp)
+interactive = ast.parse("1 + 2", mode="single")
+assert isinstance(interactive, ast.Interactive)
+
+with assert_raises(SyntaxError):
+    ast.parse("x = = 1")
+
+# Compiling a tree from the parser
+namespace = {}
+exec(compile(tree, "<ast>", "exec"), namespace)
+assert namespace["y"] == 3
+assert eval(compile(expression, "<ast>", "eval")) == 3
+
+# Compiling a tree that is changed or built by hand
+class RenameX(ast.NodeTransformer):
+    def visit_Name(self, node):
+        if node.id == "x":
+            node.id = "z"
+        return node
+
+tree = RenameX().visit(ast.parse("def f(a, *, b=2):\n    return a * x + b\n"))
+namespace = {"z": 10}
+exec(compile(tree, "<ast>", "exec"), namespace)
+assert namespace["f"](3) == 32
+
+call = ast.Call(
+    func=ast.Name(id="len", ctx=ast.Load()),
+    args=[ast.Constant(value="abc")],
+    keywords=[],
+)
+tree = ast.fix_missing_locations(ast.Expression(body=call))
+assert eval(compile(tree, "<ast>", "eval")) == 3
+
+source = """
+def gen(n):
+    for i in range(n):
+        if i % 2:
+            yield i ** 2
+values = [v for v in gen(6) if v > 1]
+text = f"{values!r:>12}"
+try:
+    1 / 0
+except ZeroDivisionError as e:
+    caught = type(e).__name__
+"""
+namespace = {}
+exec(compile(ast.parse(source), "<ast>", "exec"), namespace)
+assert namespace["values"] == [9, 25]
+assert namespace["text"] == "     [9, 25]"
+assert namespace["caught"] == "ZeroDivisionError"
+
+# The tree has to fit the mode
+with assert_raises(TypeError):
+    compile(ast.parse("1"), "<ast>", "eval")
+with assert_raises(TypeError):
+    compile(ast.Module(body=[ast.Expr(value=ast.Name(id="x", ctx=ast.Load()))], type_ignores=[]), "<ast>", "exec")
+
+# Asking for the tree of a tree returns it
+assert compile(tree, "<ast>", "eval", ast.PyCF_ONLY_AST) is tree
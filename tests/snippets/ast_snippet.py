@@ -38,3 +38,37 @@ assert i.module is None
 assert i.names[0].name == 'a'
 assert i.names[0].asname is None
 
+
+source = """
+x = y = 1  # type: int
+def f(a):  # type: (int) -> None
+    for b in a:  # type: str
+        pass
+async def g():
+    # type: () -> int
+    async with h() as c:  # type: ignore[misc]
+        return c
+"""
+n = ast.parse(source, type_comments=True)
+assert n.body[0].type_comment == 'int'
+assert n.body[1].type_comment == '(int) -> None'
+assert n.body[1].body[0].type_comment == 'str'
+assert n.body[2].type_comment == '() -> int'
+assert n.body[2].body[0].type_comment is None
+assert len(n.type_ignores) == 1
+assert n.type_ignores[0].lineno == 8
+assert n.type_ignores[0].tag == '[misc]'
+
+# Type comments are only comments unless asked for
+n = ast.parse(source)
+assert n.body[0].type_comment is None
+assert n.type_ignores == []
+
+for source in ["x + 1  # type: int\n", "x = 1\n# type: int\n"]:
+    ast.parse(source)
+    try:
+        ast.parse(source, type_comments=True)
+    except SyntaxError:
+        pass
+    else:
+        assert False, source
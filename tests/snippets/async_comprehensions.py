@@ -0,0 +1,82 @@
+from testutils import assert_raises
+
+
+class AIterWrap:
+    def __init__(self, obj):
+        self._it = iter(obj)
+
+    def __aiter__(self):
+        return self
+
+    async def __anext__(self):
+        try:
+            return next(self._it)
+        except StopIteration:
+            raise StopAsyncIteration
+
+
+async def double(x):
+    return x * 2
+
+
+def run(coro):
+    # A minimal scheduler: nothing here ever suspends, so one send finishes it.
+    try:
+        coro.send(None)
+    except StopIteration as stop:
+        return stop.value
+    raise AssertionError("coroutine suspended")
+
+
+async def comprehensions():
+    assert [x async for x in AIterWrap(range(3))] == [0, 1, 2]
+    assert {x % 2 async for x in AIterWrap(range(5))} == {0, 1}
+    assert {x: x * x async for x in AIterWrap(range(3))} == {0: 0, 1: 1, 2: 4}
+    assert [x async for x in AIterWrap(range(6)) if x % 2] == [1, 3, 5]
+    assert [(x, y) for x in range(2) async for y in AIterWrap("ab")] == [
+        (0, "a"),
+        (0, "b"),
+        (1, "a"),
+        (1, "b"),
+    ]
+    assert [await double(x) for x in range(3)] == [0, 2, 4]
+    assert [await double(x) async for x in AIterWrap(range(2))] == [0, 2]
+    return "done"
+
+
+assert run(comprehensions()) == "done"
+
+
+async def loop_and_break():
+    found = []
+    for _ in range(2):
+        async for x in AIterWrap(range(5)):
+            if x == 2:
+                break
+            found.append(x)
+        else:
+            found.append("unreachable")
+    async for x in AIterWrap(range(2)):
+        found.append(x)
+    else:
+        found.append("else")
+    return found
+
+
+assert run(loop_and_break()) == [0, 1, 0, 1, 0, 1, "else"]
+
+
+async def failing():
+    return [x async for x in AIterWrap([1, 0]) if 1 / x]
+
+
+with assert_raises(ZeroDivisionError):
+    run(failing())
+
+for source in [
+    "await x",
+    "def f():\n    await x",
+    "def f():\n    return [x async for x in y]",
+]:
+    with assert_raises(SyntaxError):
+        compile(source, "<test>", "exec")
@@ -0,0 +1,78 @@
+from testutils import assert_raises
+
+# Bytes are decoded like the contents of a source file: as UTF-8, unless a
+# coding declaration in the first two lines says otherwise
+ns = {}
+exec(compile("s = 'café'\n".encode('utf-8'), '<utf-8>', 'exec'), ns)
+assert ns['s'] == 'café'
+
+ns = {}
+source = "# -*- coding: latin-1 -*-\ns = 'café'\n".encode('latin-1')
+exec(compile(source, '<latin-1>', 'exec'), ns)
+assert ns['s'] == 'café'
+
+ns = {}
+source = "#!/usr/bin/env python\n# vim: set fileencoding=iso-8859-1 :\ns = 'é'\n"
+exec(compile(source.encode('latin-1'), '<latin-1>', 'exec'), ns)
+assert ns['s'] == 'é'
+
+# A UTF-8 byte order mark is dropped
+ns = {}
+exec(compile(b"\xef\xbb\xbfs = 'bom'\n", '<bom>', 'exec'), ns)
+assert ns['s'] == 'bom'
+assert eval(compile(b"\xef\xbb\xbf1 + 1", '<bom>', 'eval')) == 2
+
+# A declaration in a str is ignored, as the text is already decoded
+ns = {}
+exec(compile("# coding: latin-1\ns = 'é'\n", '<str>', 'exec'), ns)
+assert ns['s'] == 'é'
+
+with assert_raises(SyntaxError):
+    compile(b"s = '\xe9'\n", '<invalid>', 'exec')
+with assert_raises(SyntaxError):
+    compile(b"# coding: no-such-encoding\n", '<unknown>', 'exec')
+with assert_raises(SyntaxError):
+    compile(b"\xef\xbb\xbf# coding: latin-1\n", '<bom>', 'exec')
+
+# The optimization level drops asserts from 1, with __debug__ false, and
+# docstrings from 2
+exec(compile("assert False", '<optimize>', 'exec', optimize=1))
+with assert_raises(AssertionError):
+    exec(compile("assert False", '<optimize>', 'exec', optimize=0))
+assert eval(compile("__debug__", '<optimize>', 'eval', optimize=0)) is True
+assert eval(compile("__debug__", '<optimize>', 'eval', optimize=1)) is False
+
+source = "def f():\n    'doc'\n"
+for optimize, doc in [(1, 'doc'), (2, None)]:
+    ns = {}
+    exec(compile(source, '<optimize>', 'exec', optimize=optimize), ns)
+    assert ns['f'].__doc__ == doc
+
+with assert_raises(ValueError):
+    compile("1", '<optimize>', 'eval', optimize=3)
+with assert_raises(SyntaxError):
+    compile("__debug__ = 1", '<optimize>', 'exec')
+
+# mode and flags are validated before the source is looked at
+with assert_raises(ValueError):
+    compile("1", '<mode>', 'bad')
+with assert_raises(ValueError):
+    compile(1, '<mode>', 'bad')
+with assert_raises(ValueError):
+    compile("1", '<flags>', 'eval', 0x10000000)
+with assert_raises(ValueError):
+    compile("1", '<flags>', 'eval', 0x8000)
+with assert_raises(TypeError):
+    compile("1", '<flags>', 'eval', '0')
+assert eval(compile("1", '<flags>', 'eval', 0x200, dont_inherit=1)) == 1
+
+import ast
+
+tree = compile("x = 1", '<ast>', 'exec', ast.PyCF_ONLY_AST)
+assert isinstance(tree, ast.Module)
+assert compile(tree, '<ast>', 'exec', ast.PyCF_ONLY_AST) is tree
+ns = {}
+exec(compile(tree, '<ast>', 'exec'), ns)
+assert ns['x'] == 1
+with assert_raises(TypeError):
+    compile(tree, '<ast>', 'eval')
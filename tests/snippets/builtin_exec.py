@@ -64,3 +64,41 @@ def f():
     exec("assert 'g' not in locals()", {})
 
 f()
+
+# In single mode, the values of expression statements go to sys.displayhook
+import builtins
+import sys
+
+shown = []
+old_displayhook = sys.displayhook
+sys.displayhook = shown.append
+try:
+    g = {}
+    exec(compile('1 + 1', '<stdin>', 'single'), g)
+    exec(compile('y = 3', '<stdin>', 'single'), g)
+    exec(compile('None', '<stdin>', 'single'), g)
+    exec(compile('if y:\n    y\n    "a"\nelse:\n    0\n', '<stdin>', 'single'), g)
+    exec(compile('for i in range(2): i\n', '<stdin>', 'single'), g)
+    exec(compile('def f():\n    "not shown"\n    return 5\n', '<stdin>', 'single'), g)
+    exec(compile('class C:\n    "not shown"\n    7\n', '<stdin>', 'single'), g)
+    exec(compile('f()', '<stdin>', 'single'), g)
+    exec(compile('1 + 1', '<string>', 'exec'), g)
+finally:
+    sys.displayhook = old_displayhook
+assert shown == [2, None, 3, 'a', 0, 1, 5], shown
+
+# The default displayhook prints the repr, skips None and keeps the value in builtins._
+import io
+
+old_stdout = sys.stdout
+sys.stdout = io.StringIO()
+try:
+    sys.__displayhook__('text')
+    sys.__displayhook__(None)
+    assert builtins._ == 'text'
+    sys.__displayhook__([1, 2])
+    printed = sys.stdout.getvalue()
+finally:
+    sys.stdout = old_stdout
+assert printed == "'text'\n[1, 2]\n", printed
+assert builtins._ == [1, 2]
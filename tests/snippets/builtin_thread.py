@@ -1,3 +1,36 @@
 import _thread
 
 assert _thread.TIMEOUT_MAX in [9223372036.0, 4294967.0]
+
+from testutils import assert_raises
+
+lock = _thread.allocate_lock()
+assert isinstance(lock, _thread.LockType)
+assert lock.acquire()
+assert lock.locked()
+assert not lock.acquire(False)
+assert not lock.acquire(timeout=0.1)
+assert_raises(ValueError, lambda: lock.acquire(False, 1))
+assert_raises(ValueError, lambda: lock.acquire(timeout=-2))
+lock.release()
+assert not lock.locked()
+assert_raises(RuntimeError, lock.release)
+with lock:
+    assert lock.locked()
+assert not lock.locked()
+
+rlock = _thread.RLock()
+assert rlock.acquire()
+assert rlock.acquire(False)
+assert rlock._is_owned()
+rlock.release()
+rlock.release()
+assert not rlock._is_owned()
+assert_raises(RuntimeError, rlock.release)
+
+def interrupted():
+    _thread.interrupt_main()
+    for _ in range(1000):
+        pass
+
+assert_raises(KeyboardInterrupt, interrupted)
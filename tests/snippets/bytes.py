@@ -62,6 +62,8 @@ assert list(bytes(3)) == [0, 0, 0]
 
 # add
 assert a + b == b"abcdab"
+assert type(a + b) == bytes
+assert type(a + bytearray(b)) == bytes
 
 # contains
 assert b"ab" in b"abcd"
@@ -608,3 +610,4 @@ assert b'\xc2\xae\x75\x73\x74'.decode('ascii', 'ignore') == 'ust'
 assert b'\xc2\xae\x75\x73\x74'.decode('utf-8') == '®ust'
 assert b'\xc2\xae\x75\x73\x74'.decode() == '®ust'
 assert b'\xe4\xb8\xad\xe6\x96\x87\xe5\xad\x97'.decode('utf-8') == '中文字'
+assert b'caf\xe9'.decode('latin-1') == 'caf\xe9'
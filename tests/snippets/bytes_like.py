@@ -0,0 +1,77 @@
+import array
+import base64
+import binascii
+import hashlib
+import os
+import struct
+import zlib
+
+from testutils import assert_raises
+
+DATA = b"\x01\x02ab"
+
+bytes_likes = [
+    DATA,
+    bytearray(DATA),
+    memoryview(DATA),
+    memoryview(bytearray(DATA)),
+    array.array("B", DATA),
+]
+
+functions = [
+    (binascii.hexlify, b"01026162"),
+    (binascii.crc32, 1160132229),
+    (binascii.b2a_base64, b"AQJhYg==\n"),
+    (lambda d: binascii.unhexlify(binascii.hexlify(d)), DATA),
+    (lambda d: hashlib.md5(d).hexdigest(), "b398859be846c26cb04f982a912b9a59"),
+    (lambda d: hashlib.new("sha1", d).hexdigest(),
+     "b55486dd9f478a889c54b7d85d258fb7ac8f826f"),
+    (base64.b64encode, b"AQJhYg=="),
+    (lambda d: base64.b64decode(base64.b64encode(d)), DATA),
+    (lambda d: struct.unpack(">BBH", d), (1, 2, 0x6162)),
+    (zlib.crc32, 1160132229),
+    (zlib.adler32, 20054215),
+    (lambda d: zlib.decompress(zlib.compress(d)), DATA),
+    (lambda d: int.from_bytes(d, "big"), 0x01026162),
+    (lambda d: int.from_bytes(d, "little", signed=True), 0x62610201),
+    (lambda d: b"-".join([d, d]), b"\x01\x02ab-\x01\x02ab"),
+]
+
+
+def update(d):
+    h = hashlib.sha256()
+    h.update(d)
+    return h.hexdigest()
+
+
+functions.append((update, hashlib.sha256(DATA).hexdigest()))
+
+for f, expected in functions:
+    for data in bytes_likes:
+        result = f(data)
+        assert result == expected, (f, data, result, expected)
+
+    with assert_raises(TypeError):
+        f("\x01\x02ab")
+
+
+with assert_raises(TypeError) as cm:
+    binascii.hexlify("abc")
+assert str(cm.exception) == "a bytes-like object is required, not 'str'"
+
+with assert_raises(TypeError):
+    b"".join([b"a", "b"])
+
+
+path = "bytes_like_{}.tmp".format(os.getpid())
+fd = os.open(path, os.O_WRONLY | os.O_CREAT)
+try:
+    for data in bytes_likes:
+        assert os.write(fd, data) == len(DATA)
+finally:
+    os.close(fd)
+try:
+    with open(path, "rb") as f:
+        assert f.read() == DATA * len(bytes_likes)
+finally:
+    os.remove(path)
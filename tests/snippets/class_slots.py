@@ -0,0 +1,188 @@
+import sys
+import weakref
+
+from testutils import assert_raises
+
+
+class Point:
+    __slots__ = ('x', 'y')
+
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+p = Point(1, 2)
+assert (p.x, p.y) == (1, 2)
+p.x = 3
+assert p.x == 3
+assert not hasattr(p, '__dict__')
+assert repr(Point.x) == "<member 'x' of 'Point' objects>"
+assert Point.x.__name__ == 'x'
+assert Point.x.__objclass__ is Point
+
+with assert_raises(AttributeError) as cm:
+    p.z = 5
+assert str(cm.exception) == "'Point' object has no attribute 'z'"
+
+del p.y
+assert not hasattr(p, 'y')
+with assert_raises(AttributeError):
+    p.y
+with assert_raises(AttributeError):
+    del p.y
+p.y = 4
+assert p.y == 4
+
+# Each instance has its own storage.
+q = Point(5, 6)
+assert (p.x, p.y, q.x, q.y) == (3, 4, 5, 6)
+
+# A single string is a single slot.
+class Single:
+    __slots__ = 'value'
+
+    def __init__(self):
+        self.value = 1
+
+
+assert Single().value == 1
+assert 'value' in Single.__dict__
+
+with assert_raises(TypeError):
+    Point.x.__get__(Single())
+
+with assert_raises(ValueError):
+    class Conflicting:
+        __slots__ = ('x',)
+        x = 1
+
+with assert_raises(TypeError):
+    class NotAString:
+        __slots__ = (1,)
+
+with assert_raises(TypeError):
+    class NotAnIdentifier:
+        __slots__ = ('not valid',)
+
+
+# Listing __dict__ brings the dict back.
+class WithDict:
+    __slots__ = ('x', '__dict__')
+
+
+w = WithDict()
+w.x = 1
+w.y = 2
+assert w.__dict__ == {'y': 2}
+
+# A subclass without __slots__ gets a __dict__, and keeps the slots of its base.
+class Point3D(Point):
+    pass
+
+
+p3 = Point3D(1, 2)
+p3.z = 3
+assert p3.__dict__ == {'z': 3}
+assert (p3.x, p3.y, p3.z) == (1, 2, 3)
+
+# A subclass with __slots__ adds to those of its base.
+class Point4D(Point):
+    __slots__ = ('z', 'w')
+
+
+p4 = Point4D(1, 2)
+p4.z = 3
+p4.w = 4
+assert (p4.x, p4.y, p4.z, p4.w) == (1, 2, 3, 4)
+assert not hasattr(p4, '__dict__')
+
+with assert_raises(TypeError):
+    class DictTwice(Point3D):
+        __slots__ = ('__dict__',)
+
+
+# Slotted instances can only be weakly referenced with __weakref__ in their slots.
+with assert_raises(TypeError):
+    weakref.ref(p)
+
+
+class Weak:
+    __slots__ = ('x', '__weakref__')
+
+
+weak = Weak()
+assert weakref.ref(weak)() is weak
+assert weakref.ref(p3)() is p3
+
+
+# Two bases that both store slots can't be combined...
+class A:
+    __slots__ = ('a',)
+
+
+class B:
+    __slots__ = ('b',)
+
+
+with assert_raises(TypeError) as cm:
+    class AB(A, B):
+        pass
+assert str(cm.exception) == 'multiple bases have instance lay-out conflict'
+
+
+# ...but a base that stores nothing, or a subclass of the other, can be.
+class Empty:
+    __slots__ = ()
+
+
+class SubA(A):
+    __slots__ = ('c',)
+
+
+class Mixed(SubA, A, Empty):
+    __slots__ = ('d',)
+
+
+m = Mixed()
+m.a = 1
+m.c = 2
+m.d = 3
+assert (m.a, m.c, m.d) == (1, 2, 3)
+assert not hasattr(m, '__dict__')
+
+
+# Builtin types that can't store slots still take empty ones.
+class MyInt(int):
+    __slots__ = ()
+
+
+assert MyInt(3) + 1 == 4
+assert not hasattr(MyInt(3), '__dict__')
+
+
+# ...and store the values of nonempty ones alongside their own.
+class TaggedList(list):
+    __slots__ = ('tag',)
+
+
+tagged = TaggedList([1, 2])
+tagged.tag = 'x'
+assert (tagged + [3], tagged.tag) == ([1, 2, 3], 'x')
+
+
+with assert_raises(TypeError) as cm:
+    class TaggedA(A, TaggedList):
+        pass
+
+
+# Slots take less memory than a __dict__.
+class Plain:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+plain = Plain(1, 2)
+slotted = Point(1, 2)
+assert sys.getsizeof(slotted) < sys.getsizeof(plain) + sys.getsizeof(plain.__dict__)
@@ -0,0 +1,31 @@
+import warnings
+
+from testutils import assert_raises
+
+with warnings.catch_warnings(record=True) as caught:
+    warnings.simplefilter("always")
+    compile("x = 1\nif x is 1:\n    pass\n", "is_literal.py", "exec")
+    compile("'\\d'", "escape.py", "eval")
+
+assert len(caught) == 2
+is_literal, escape = caught
+assert is_literal.category is SyntaxWarning
+assert str(is_literal.message) == '"is" with a literal. Did you mean "=="?'
+assert is_literal.filename == "is_literal.py"
+assert is_literal.lineno == 2
+assert escape.category is DeprecationWarning
+assert str(escape.message) == "invalid escape sequence '\\d'"
+assert escape.filename == "escape.py"
+assert escape.lineno == 1
+
+# Without a literal there is nothing to warn about
+with warnings.catch_warnings(record=True) as caught:
+    warnings.simplefilter("always")
+    compile("x is None or x is not y", "<string>", "eval")
+assert caught == []
+
+# A warning turned into an error is a syntax error
+with warnings.catch_warnings():
+    warnings.simplefilter("error")
+    with assert_raises(SyntaxError):
+        compile("x is 1", "<string>", "eval")
@@ -1,3 +1,4 @@
+from testutils import assert_raises
 
 x = [1, 2, 3]
 
@@ -41,3 +42,34 @@ assert 'b' not in locals()
 assert 'c' not in locals()
 assert 'b' not in globals()
 assert 'c' not in globals()
+
+# nested scopes in a comprehension each get their own names
+assert [f() for f in [lambda: x for x in range(3) if (lambda y: y % 2 == 0)(x)]] == [2, 2]
+assert [k() for k in {(lambda: k): (lambda: 1) for k in 'ab'}] == ['b', 'b']
+assert [[x for x in range(y)] for y in range(3)] == [[], [0], [0, 1]]
+
+# only the outermost iterable of a generator expression is evaluated right away
+def numbers():
+    yield 1
+    yield 2
+    raise AssertionError('consumed too far')
+
+# inner iterables are only evaluated when the generator runs
+g = (x * x for x in numbers() for _ in [undefined_name])
+with assert_raises(NameError):
+    next(g)
+
+g = (x * x for x in numbers())
+assert next(g) == 1
+assert next(g) == 4
+
+# the class body is only seen by the outermost iterable
+class C:
+    n = 3
+    firsts = [z for z in [n]]
+    try:
+        [n for _ in range(1)]
+    except NameError:
+        hidden = True
+assert C.firsts == [3]
+assert C.hidden
@@ -0,0 +1,44 @@
+import math
+from testutils import assert_raises
+
+# Constant expressions are folded by the compiler, which must give what running them gives
+assert 60 * 60 * 24 == 86400
+assert 2 ** 10 == 1024
+assert 2 ** 100 == 1267650600228229401496703205376
+assert -7 // 2 == -4
+assert -7 % 3 == 2
+assert ~5 == -6
+assert 10 / 4 == 2.5
+assert 0.1 + 0.2 == 0.30000000000000004
+assert math.copysign(1, -0.0) == -1
+assert math.copysign(1, 0.0 * -1) == -1
+assert 1 - 2j == complex(1, -2)
+assert "ab" + "cd" == "abcd"
+assert "ab" * -1 == ""
+assert 2 * b"ab" == b"abab"
+assert (1, "a", (2.5, -3)) == (1, "a", (2.5, -3))
+assert type(10 / 5) is float
+
+with assert_raises(ZeroDivisionError):
+    1 / 0
+with assert_raises(ZeroDivisionError):
+    1 // 0
+with assert_raises(ZeroDivisionError):
+    1 % 0
+with assert_raises(ValueError):
+    1 << -1
+
+# A jump target between the constants keeps them apart
+c = 5
+assert (c or 1) + 2 == 7
+c = 0
+assert (c or 1) + 2 == 3
+
+
+def pair(d):
+    a, b = (1, 2) if d else (3, 4)
+    return a, b
+
+
+assert pair(True) == (1, 2)
+assert pair(False) == (3, 4)
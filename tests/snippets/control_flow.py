@@ -31,3 +31,59 @@ assert list(primes(2)) == [2]
 assert list(primes(10)) == [2, 3, 5, 7]
 assert list(primes(13)) == [2, 3, 5, 7, 11, 13]
 
+
+
+# code after return, raise, break and continue, which the compiler leaves out
+
+def first_even(numbers):
+    for n in numbers:
+        if n % 2 == 0:
+            return n
+            print("unreachable")
+    return None
+
+assert first_even([1, 3, 4, 6]) == 4
+assert first_even([1, 3]) is None
+
+count = 0
+while True:
+    count += 1
+    if count < 3:
+        continue
+    break
+    count = -1
+assert count == 3
+
+def nested(a, b):
+    if a:
+        if b:
+            result = "ab"
+        else:
+            result = "a"
+    else:
+        result = "-"
+    return result
+
+assert [nested(1, 1), nested(1, 0), nested(0, 1)] == ["ab", "a", "-"]
+
+log = []
+def handlers(fail):
+    try:
+        if fail:
+            raise ValueError
+            log.append("unreachable")
+        return "returned"
+    except ValueError:
+        return "caught"
+    finally:
+        log.append("finally")
+
+assert handlers(False) == "returned"
+assert handlers(True) == "caught"
+assert log == ["finally", "finally"]
+
+for x in range(3):
+    pass
+else:
+    x = "else"
+assert x == "else"
@@ -0,0 +1,3258 @@
+# This file was generated by tests/error_messages_gen.py; do not edit it by hand.
+# (source, exception type, message)
+EXPECTED_ERRORS = [
+    ('1 + None', 'TypeError', "unsupported operand type(s) for +: 'int' and 'NoneType'"),
+    ("1 + 'a'", 'TypeError', "unsupported operand type(s) for +: 'int' and 'str'"),
+    ("1 + b'a'", 'TypeError', "unsupported operand type(s) for +: 'int' and 'bytes'"),
+    ("1 + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'int' and 'bytearray'"),
+    ('1 + [1]', 'TypeError', "unsupported operand type(s) for +: 'int' and 'list'"),
+    ('1 + (1,)', 'TypeError', "unsupported operand type(s) for +: 'int' and 'tuple'"),
+    ('1 + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'int' and 'dict'"),
+    ('1 + {1}', 'TypeError', "unsupported operand type(s) for +: 'int' and 'set'"),
+    ('1 + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'int' and 'frozenset'"),
+    ('1 + range(1)', 'TypeError', "unsupported operand type(s) for +: 'int' and 'range'"),
+    ('1 - None', 'TypeError', "unsupported operand type(s) for -: 'int' and 'NoneType'"),
+    ("1 - 'a'", 'TypeError', "unsupported operand type(s) for -: 'int' and 'str'"),
+    ("1 - b'a'", 'TypeError', "unsupported operand type(s) for -: 'int' and 'bytes'"),
+    ("1 - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'int' and 'bytearray'"),
+    ('1 - [1]', 'TypeError', "unsupported operand type(s) for -: 'int' and 'list'"),
+    ('1 - (1,)', 'TypeError', "unsupported operand type(s) for -: 'int' and 'tuple'"),
+    ('1 - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'int' and 'dict'"),
+    ('1 - {1}', 'TypeError', "unsupported operand type(s) for -: 'int' and 'set'"),
+    ('1 - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'int' and 'frozenset'"),
+    ('1 - range(1)', 'TypeError', "unsupported operand type(s) for -: 'int' and 'range'"),
+    ('1 * None', 'TypeError', "unsupported operand type(s) for *: 'int' and 'NoneType'"),
+    ('1 * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'int' and 'dict'"),
+    ('1 * {1}', 'TypeError', "unsupported operand type(s) for *: 'int' and 'set'"),
+    ('1 * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'int' and 'frozenset'"),
+    ('1 * range(1)', 'TypeError', "unsupported operand type(s) for *: 'int' and 'range'"),
+    ('1 / None', 'TypeError', "unsupported operand type(s) for /: 'int' and 'NoneType'"),
+    ("1 / 'a'", 'TypeError', "unsupported operand type(s) for /: 'int' and 'str'"),
+    ("1 / b'a'", 'TypeError', "unsupported operand type(s) for /: 'int' and 'bytes'"),
+    ("1 / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'int' and 'bytearray'"),
+    ('1 / [1]', 'TypeError', "unsupported operand type(s) for /: 'int' and 'list'"),
+    ('1 / (1,)', 'TypeError', "unsupported operand type(s) for /: 'int' and 'tuple'"),
+    ('1 / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'int' and 'dict'"),
+    ('1 / {1}', 'TypeError', "unsupported operand type(s) for /: 'int' and 'set'"),
+    ('1 / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'int' and 'frozenset'"),
+    ('1 / range(1)', 'TypeError', "unsupported operand type(s) for /: 'int' and 'range'"),
+    ('1 // 1j', 'TypeError', "can't take floor of complex number."),
+    ('1 // None', 'TypeError', "unsupported operand type(s) for //: 'int' and 'NoneType'"),
+    ("1 // 'a'", 'TypeError', "unsupported operand type(s) for //: 'int' and 'str'"),
+    ("1 // b'a'", 'TypeError', "unsupported operand type(s) for //: 'int' and 'bytes'"),
+    ("1 // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'int' and 'bytearray'"),
+    ('1 // [1]', 'TypeError', "unsupported operand type(s) for //: 'int' and 'list'"),
+    ('1 // (1,)', 'TypeError', "unsupported operand type(s) for //: 'int' and 'tuple'"),
+    ('1 // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'int' and 'dict'"),
+    ('1 // {1}', 'TypeError', "unsupported operand type(s) for //: 'int' and 'set'"),
+    ('1 // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'int' and 'frozenset'"),
+    ('1 // range(1)', 'TypeError', "unsupported operand type(s) for //: 'int' and 'range'"),
+    ('1 % 1j', 'TypeError', "can't mod complex numbers."),
+    ('1 % None', 'TypeError', "unsupported operand type(s) for %: 'int' and 'NoneType'"),
+    ("1 % 'a'", 'TypeError', "unsupported operand type(s) for %: 'int' and 'str'"),
+    ("1 % b'a'", 'TypeError', "unsupported operand type(s) for %: 'int' and 'bytes'"),
+    ("1 % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'int' and 'bytearray'"),
+    ('1 % [1]', 'TypeError', "unsupported operand type(s) for %: 'int' and 'list'"),
+    ('1 % (1,)', 'TypeError', "unsupported operand type(s) for %: 'int' and 'tuple'"),
+    ('1 % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'int' and 'dict'"),
+    ('1 % {1}', 'TypeError', "unsupported operand type(s) for %: 'int' and 'set'"),
+    ('1 % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'int' and 'frozenset'"),
+    ('1 % range(1)', 'TypeError', "unsupported operand type(s) for %: 'int' and 'range'"),
+    ('1 ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'NoneType'"),
+    ("1 ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'str'"),
+    ("1 ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'bytes'"),
+    ("1 ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'bytearray'"),
+    ('1 ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'list'"),
+    ('1 ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'tuple'"),
+    ('1 ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'dict'"),
+    ('1 ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'set'"),
+    ('1 ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'frozenset'"),
+    ('1 ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'int' and 'range'"),
+    ('1 @ 1', 'TypeError', "unsupported operand type(s) for @: 'int' and 'int'"),
+    ('1 @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'int' and 'float'"),
+    ('1 @ 1j', 'TypeError', "unsupported operand type(s) for @: 'int' and 'complex'"),
+    ('1 @ True', 'TypeError', "unsupported operand type(s) for @: 'int' and 'bool'"),
+    ('1 @ None', 'TypeError', "unsupported operand type(s) for @: 'int' and 'NoneType'"),
+    ("1 @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'int' and 'str'"),
+    ("1 @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'int' and 'bytes'"),
+    ("1 @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'int' and 'bytearray'"),
+    ('1 @ [1]', 'TypeError', "unsupported operand type(s) for @: 'int' and 'list'"),
+    ('1 @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'int' and 'tuple'"),
+    ('1 @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'int' and 'dict'"),
+    ('1 @ {1}', 'TypeError', "unsupported operand type(s) for @: 'int' and 'set'"),
+    ('1 @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'int' and 'frozenset'"),
+    ('1 @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'int' and 'range'"),
+    ('1 << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'float'"),
+    ('1 << 1j', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'complex'"),
+    ('1 << None', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'NoneType'"),
+    ("1 << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'int' and 'str'"),
+    ("1 << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'int' and 'bytes'"),
+    ("1 << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'int' and 'bytearray'"),
+    ('1 << [1]', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'list'"),
+    ('1 << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'tuple'"),
+    ('1 << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'dict'"),
+    ('1 << {1}', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'set'"),
+    ('1 << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'frozenset'"),
+    ('1 << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'int' and 'range'"),
+    ('1 >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'float'"),
+    ('1 >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'complex'"),
+    ('1 >> None', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'NoneType'"),
+    ("1 >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'int' and 'str'"),
+    ("1 >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'int' and 'bytes'"),
+    ("1 >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'int' and 'bytearray'"),
+    ('1 >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'list'"),
+    ('1 >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'tuple'"),
+    ('1 >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'dict'"),
+    ('1 >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'set'"),
+    ('1 >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'frozenset'"),
+    ('1 >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'int' and 'range'"),
+    ('1 & 1.5', 'TypeError', "unsupported operand type(s) for &: 'int' and 'float'"),
+    ('1 & 1j', 'TypeError', "unsupported operand type(s) for &: 'int' and 'complex'"),
+    ('1 & None', 'TypeError', "unsupported operand type(s) for &: 'int' and 'NoneType'"),
+    ("1 & 'a'", 'TypeError', "unsupported operand type(s) for &: 'int' and 'str'"),
+    ("1 & b'a'", 'TypeError', "unsupported operand type(s) for &: 'int' and 'bytes'"),
+    ("1 & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'int' and 'bytearray'"),
+    ('1 & [1]', 'TypeError', "unsupported operand type(s) for &: 'int' and 'list'"),
+    ('1 & (1,)', 'TypeError', "unsupported operand type(s) for &: 'int' and 'tuple'"),
+    ('1 & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'int' and 'dict'"),
+    ('1 & {1}', 'TypeError', "unsupported operand type(s) for &: 'int' and 'set'"),
+    ('1 & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'int' and 'frozenset'"),
+    ('1 & range(1)', 'TypeError', "unsupported operand type(s) for &: 'int' and 'range'"),
+    ('1 | 1.5', 'TypeError', "unsupported operand type(s) for |: 'int' and 'float'"),
+    ('1 | 1j', 'TypeError', "unsupported operand type(s) for |: 'int' and 'complex'"),
+    ('1 | None', 'TypeError', "unsupported operand type(s) for |: 'int' and 'NoneType'"),
+    ("1 | 'a'", 'TypeError', "unsupported operand type(s) for |: 'int' and 'str'"),
+    ("1 | b'a'", 'TypeError', "unsupported operand type(s) for |: 'int' and 'bytes'"),
+    ("1 | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'int' and 'bytearray'"),
+    ('1 | [1]', 'TypeError', "unsupported operand type(s) for |: 'int' and 'list'"),
+    ('1 | (1,)', 'TypeError', "unsupported operand type(s) for |: 'int' and 'tuple'"),
+    ('1 | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'int' and 'dict'"),
+    ('1 | {1}', 'TypeError', "unsupported operand type(s) for |: 'int' and 'set'"),
+    ('1 | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'int' and 'frozenset'"),
+    ('1 | range(1)', 'TypeError', "unsupported operand type(s) for |: 'int' and 'range'"),
+    ('1 ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'float'"),
+    ('1 ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'complex'"),
+    ('1 ^ None', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'NoneType'"),
+    ("1 ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'int' and 'str'"),
+    ("1 ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'int' and 'bytes'"),
+    ("1 ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'int' and 'bytearray'"),
+    ('1 ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'list'"),
+    ('1 ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'tuple'"),
+    ('1 ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'dict'"),
+    ('1 ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'set'"),
+    ('1 ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'frozenset'"),
+    ('1 ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'int' and 'range'"),
+    ('1 < 1j', 'TypeError', "'<' not supported between instances of 'int' and 'complex'"),
+    ('1 < None', 'TypeError', "'<' not supported between instances of 'int' and 'NoneType'"),
+    ("1 < 'a'", 'TypeError', "'<' not supported between instances of 'int' and 'str'"),
+    ("1 < b'a'", 'TypeError', "'<' not supported between instances of 'int' and 'bytes'"),
+    ("1 < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'int' and 'bytearray'"),
+    ('1 < [1]', 'TypeError', "'<' not supported between instances of 'int' and 'list'"),
+    ('1 < (1,)', 'TypeError', "'<' not supported between instances of 'int' and 'tuple'"),
+    ('1 < {1: 1}', 'TypeError', "'<' not supported between instances of 'int' and 'dict'"),
+    ('1 < {1}', 'TypeError', "'<' not supported between instances of 'int' and 'set'"),
+    ('1 < frozenset({1})', 'TypeError', "'<' not supported between instances of 'int' and 'frozenset'"),
+    ('1 < range(1)', 'TypeError', "'<' not supported between instances of 'int' and 'range'"),
+    ('1 <= 1j', 'TypeError', "'<=' not supported between instances of 'int' and 'complex'"),
+    ('1 <= None', 'TypeError', "'<=' not supported between instances of 'int' and 'NoneType'"),
+    ("1 <= 'a'", 'TypeError', "'<=' not supported between instances of 'int' and 'str'"),
+    ("1 <= b'a'", 'TypeError', "'<=' not supported between instances of 'int' and 'bytes'"),
+    ("1 <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'int' and 'bytearray'"),
+    ('1 <= [1]', 'TypeError', "'<=' not supported between instances of 'int' and 'list'"),
+    ('1 <= (1,)', 'TypeError', "'<=' not supported between instances of 'int' and 'tuple'"),
+    ('1 <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'int' and 'dict'"),
+    ('1 <= {1}', 'TypeError', "'<=' not supported between instances of 'int' and 'set'"),
+    ('1 <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'int' and 'frozenset'"),
+    ('1 <= range(1)', 'TypeError', "'<=' not supported between instances of 'int' and 'range'"),
+    ('1 > 1j', 'TypeError', "'>' not supported between instances of 'int' and 'complex'"),
+    ('1 > None', 'TypeError', "'>' not supported between instances of 'int' and 'NoneType'"),
+    ("1 > 'a'", 'TypeError', "'>' not supported between instances of 'int' and 'str'"),
+    ("1 > b'a'", 'TypeError', "'>' not supported between instances of 'int' and 'bytes'"),
+    ("1 > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'int' and 'bytearray'"),
+    ('1 > [1]', 'TypeError', "'>' not supported between instances of 'int' and 'list'"),
+    ('1 > (1,)', 'TypeError', "'>' not supported between instances of 'int' and 'tuple'"),
+    ('1 > {1: 1}', 'TypeError', "'>' not supported between instances of 'int' and 'dict'"),
+    ('1 > {1}', 'TypeError', "'>' not supported between instances of 'int' and 'set'"),
+    ('1 > frozenset({1})', 'TypeError', "'>' not supported between instances of 'int' and 'frozenset'"),
+    ('1 > range(1)', 'TypeError', "'>' not supported between instances of 'int' and 'range'"),
+    ('1 >= 1j', 'TypeError', "'>=' not supported between instances of 'int' and 'complex'"),
+    ('1 >= None', 'TypeError', "'>=' not supported between instances of 'int' and 'NoneType'"),
+    ("1 >= 'a'", 'TypeError', "'>=' not supported between instances of 'int' and 'str'"),
+    ("1 >= b'a'", 'TypeError', "'>=' not supported between instances of 'int' and 'bytes'"),
+    ("1 >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'int' and 'bytearray'"),
+    ('1 >= [1]', 'TypeError', "'>=' not supported between instances of 'int' and 'list'"),
+    ('1 >= (1,)', 'TypeError', "'>=' not supported between instances of 'int' and 'tuple'"),
+    ('1 >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'int' and 'dict'"),
+    ('1 >= {1}', 'TypeError', "'>=' not supported between instances of 'int' and 'set'"),
+    ('1 >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'int' and 'frozenset'"),
+    ('1 >= range(1)', 'TypeError', "'>=' not supported between instances of 'int' and 'range'"),
+    ('1.5 + None', 'TypeError', "unsupported operand type(s) for +: 'float' and 'NoneType'"),
+    ("1.5 + 'a'", 'TypeError', "unsupported operand type(s) for +: 'float' and 'str'"),
+    ("1.5 + b'a'", 'TypeError', "unsupported operand type(s) for +: 'float' and 'bytes'"),
+    ("1.5 + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'float' and 'bytearray'"),
+    ('1.5 + [1]', 'TypeError', "unsupported operand type(s) for +: 'float' and 'list'"),
+    ('1.5 + (1,)', 'TypeError', "unsupported operand type(s) for +: 'float' and 'tuple'"),
+    ('1.5 + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'float' and 'dict'"),
+    ('1.5 + {1}', 'TypeError', "unsupported operand type(s) for +: 'float' and 'set'"),
+    ('1.5 + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'float' and 'frozenset'"),
+    ('1.5 + range(1)', 'TypeError', "unsupported operand type(s) for +: 'float' and 'range'"),
+    ('1.5 - None', 'TypeError', "unsupported operand type(s) for -: 'float' and 'NoneType'"),
+    ("1.5 - 'a'", 'TypeError', "unsupported operand type(s) for -: 'float' and 'str'"),
+    ("1.5 - b'a'", 'TypeError', "unsupported operand type(s) for -: 'float' and 'bytes'"),
+    ("1.5 - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'float' and 'bytearray'"),
+    ('1.5 - [1]', 'TypeError', "unsupported operand type(s) for -: 'float' and 'list'"),
+    ('1.5 - (1,)', 'TypeError', "unsupported operand type(s) for -: 'float' and 'tuple'"),
+    ('1.5 - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'float' and 'dict'"),
+    ('1.5 - {1}', 'TypeError', "unsupported operand type(s) for -: 'float' and 'set'"),
+    ('1.5 - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'float' and 'frozenset'"),
+    ('1.5 - range(1)', 'TypeError', "unsupported operand type(s) for -: 'float' and 'range'"),
+    ('1.5 * None', 'TypeError', "unsupported operand type(s) for *: 'float' and 'NoneType'"),
+    ("1.5 * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ("1.5 * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ("1.5 * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ('1.5 * [1]', 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ('1.5 * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ('1.5 * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'float' and 'dict'"),
+    ('1.5 * {1}', 'TypeError', "unsupported operand type(s) for *: 'float' and 'set'"),
+    ('1.5 * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'float' and 'frozenset'"),
+    ('1.5 * range(1)', 'TypeError', "unsupported operand type(s) for *: 'float' and 'range'"),
+    ('1.5 / None', 'TypeError', "unsupported operand type(s) for /: 'float' and 'NoneType'"),
+    ("1.5 / 'a'", 'TypeError', "unsupported operand type(s) for /: 'float' and 'str'"),
+    ("1.5 / b'a'", 'TypeError', "unsupported operand type(s) for /: 'float' and 'bytes'"),
+    ("1.5 / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'float' and 'bytearray'"),
+    ('1.5 / [1]', 'TypeError', "unsupported operand type(s) for /: 'float' and 'list'"),
+    ('1.5 / (1,)', 'TypeError', "unsupported operand type(s) for /: 'float' and 'tuple'"),
+    ('1.5 / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'float' and 'dict'"),
+    ('1.5 / {1}', 'TypeError', "unsupported operand type(s) for /: 'float' and 'set'"),
+    ('1.5 / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'float' and 'frozenset'"),
+    ('1.5 / range(1)', 'TypeError', "unsupported operand type(s) for /: 'float' and 'range'"),
+    ('1.5 // 1j', 'TypeError', "can't take floor of complex number."),
+    ('1.5 // None', 'TypeError', "unsupported operand type(s) for //: 'float' and 'NoneType'"),
+    ("1.5 // 'a'", 'TypeError', "unsupported operand type(s) for //: 'float' and 'str'"),
+    ("1.5 // b'a'", 'TypeError', "unsupported operand type(s) for //: 'float' and 'bytes'"),
+    ("1.5 // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'float' and 'bytearray'"),
+    ('1.5 // [1]', 'TypeError', "unsupported operand type(s) for //: 'float' and 'list'"),
+    ('1.5 // (1,)', 'TypeError', "unsupported operand type(s) for //: 'float' and 'tuple'"),
+    ('1.5 // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'float' and 'dict'"),
+    ('1.5 // {1}', 'TypeError', "unsupported operand type(s) for //: 'float' and 'set'"),
+    ('1.5 // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'float' and 'frozenset'"),
+    ('1.5 // range(1)', 'TypeError', "unsupported operand type(s) for //: 'float' and 'range'"),
+    ('1.5 % 1j', 'TypeError', "can't mod complex numbers."),
+    ('1.5 % None', 'TypeError', "unsupported operand type(s) for %: 'float' and 'NoneType'"),
+    ("1.5 % 'a'", 'TypeError', "unsupported operand type(s) for %: 'float' and 'str'"),
+    ("1.5 % b'a'", 'TypeError', "unsupported operand type(s) for %: 'float' and 'bytes'"),
+    ("1.5 % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'float' and 'bytearray'"),
+    ('1.5 % [1]', 'TypeError', "unsupported operand type(s) for %: 'float' and 'list'"),
+    ('1.5 % (1,)', 'TypeError', "unsupported operand type(s) for %: 'float' and 'tuple'"),
+    ('1.5 % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'float' and 'dict'"),
+    ('1.5 % {1}', 'TypeError', "unsupported operand type(s) for %: 'float' and 'set'"),
+    ('1.5 % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'float' and 'frozenset'"),
+    ('1.5 % range(1)', 'TypeError', "unsupported operand type(s) for %: 'float' and 'range'"),
+    ('1.5 ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'NoneType'"),
+    ("1.5 ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'str'"),
+    ("1.5 ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'bytes'"),
+    ("1.5 ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'bytearray'"),
+    ('1.5 ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'list'"),
+    ('1.5 ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'tuple'"),
+    ('1.5 ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'dict'"),
+    ('1.5 ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'set'"),
+    ('1.5 ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'frozenset'"),
+    ('1.5 ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'float' and 'range'"),
+    ('1.5 @ 1', 'TypeError', "unsupported operand type(s) for @: 'float' and 'int'"),
+    ('1.5 @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'float' and 'float'"),
+    ('1.5 @ 1j', 'TypeError', "unsupported operand type(s) for @: 'float' and 'complex'"),
+    ('1.5 @ True', 'TypeError', "unsupported operand type(s) for @: 'float' and 'bool'"),
+    ('1.5 @ None', 'TypeError', "unsupported operand type(s) for @: 'float' and 'NoneType'"),
+    ("1.5 @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'float' and 'str'"),
+    ("1.5 @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'float' and 'bytes'"),
+    ("1.5 @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'float' and 'bytearray'"),
+    ('1.5 @ [1]', 'TypeError', "unsupported operand type(s) for @: 'float' and 'list'"),
+    ('1.5 @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'float' and 'tuple'"),
+    ('1.5 @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'float' and 'dict'"),
+    ('1.5 @ {1}', 'TypeError', "unsupported operand type(s) for @: 'float' and 'set'"),
+    ('1.5 @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'float' and 'frozenset'"),
+    ('1.5 @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'float' and 'range'"),
+    ('1.5 << 1', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'int'"),
+    ('1.5 << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'float'"),
+    ('1.5 << 1j', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'complex'"),
+    ('1.5 << True', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'bool'"),
+    ('1.5 << None', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'NoneType'"),
+    ("1.5 << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'float' and 'str'"),
+    ("1.5 << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'float' and 'bytes'"),
+    ("1.5 << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'float' and 'bytearray'"),
+    ('1.5 << [1]', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'list'"),
+    ('1.5 << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'tuple'"),
+    ('1.5 << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'dict'"),
+    ('1.5 << {1}', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'set'"),
+    ('1.5 << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'frozenset'"),
+    ('1.5 << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'float' and 'range'"),
+    ('1.5 >> 1', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'int'"),
+    ('1.5 >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'float'"),
+    ('1.5 >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'complex'"),
+    ('1.5 >> True', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'bool'"),
+    ('1.5 >> None', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'NoneType'"),
+    ("1.5 >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'float' and 'str'"),
+    ("1.5 >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'float' and 'bytes'"),
+    ("1.5 >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'float' and 'bytearray'"),
+    ('1.5 >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'list'"),
+    ('1.5 >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'tuple'"),
+    ('1.5 >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'dict'"),
+    ('1.5 >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'set'"),
+    ('1.5 >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'frozenset'"),
+    ('1.5 >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'float' and 'range'"),
+    ('1.5 & 1', 'TypeError', "unsupported operand type(s) for &: 'float' and 'int'"),
+    ('1.5 & 1.5', 'TypeError', "unsupported operand type(s) for &: 'float' and 'float'"),
+    ('1.5 & 1j', 'TypeError', "unsupported operand type(s) for &: 'float' and 'complex'"),
+    ('1.5 & True', 'TypeError', "unsupported operand type(s) for &: 'float' and 'bool'"),
+    ('1.5 & None', 'TypeError', "unsupported operand type(s) for &: 'float' and 'NoneType'"),
+    ("1.5 & 'a'", 'TypeError', "unsupported operand type(s) for &: 'float' and 'str'"),
+    ("1.5 & b'a'", 'TypeError', "unsupported operand type(s) for &: 'float' and 'bytes'"),
+    ("1.5 & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'float' and 'bytearray'"),
+    ('1.5 & [1]', 'TypeError', "unsupported operand type(s) for &: 'float' and 'list'"),
+    ('1.5 & (1,)', 'TypeError', "unsupported operand type(s) for &: 'float' and 'tuple'"),
+    ('1.5 & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'float' and 'dict'"),
+    ('1.5 & {1}', 'TypeError', "unsupported operand type(s) for &: 'float' and 'set'"),
+    ('1.5 & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'float' and 'frozenset'"),
+    ('1.5 & range(1)', 'TypeError', "unsupported operand type(s) for &: 'float' and 'range'"),
+    ('1.5 | 1', 'TypeError', "unsupported operand type(s) for |: 'float' and 'int'"),
+    ('1.5 | 1.5', 'TypeError', "unsupported operand type(s) for |: 'float' and 'float'"),
+    ('1.5 | 1j', 'TypeError', "unsupported operand type(s) for |: 'float' and 'complex'"),
+    ('1.5 | True', 'TypeError', "unsupported operand type(s) for |: 'float' and 'bool'"),
+    ('1.5 | None', 'TypeError', "unsupported operand type(s) for |: 'float' and 'NoneType'"),
+    ("1.5 | 'a'", 'TypeError', "unsupported operand type(s) for |: 'float' and 'str'"),
+    ("1.5 | b'a'", 'TypeError', "unsupported operand type(s) for |: 'float' and 'bytes'"),
+    ("1.5 | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'float' and 'bytearray'"),
+    ('1.5 | [1]', 'TypeError', "unsupported operand type(s) for |: 'float' and 'list'"),
+    ('1.5 | (1,)', 'TypeError', "unsupported operand type(s) for |: 'float' and 'tuple'"),
+    ('1.5 | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'float' and 'dict'"),
+    ('1.5 | {1}', 'TypeError', "unsupported operand type(s) for |: 'float' and 'set'"),
+    ('1.5 | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'float' and 'frozenset'"),
+    ('1.5 | range(1)', 'TypeError', "unsupported operand type(s) for |: 'float' and 'range'"),
+    ('1.5 ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'int'"),
+    ('1.5 ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'float'"),
+    ('1.5 ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'complex'"),
+    ('1.5 ^ True', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'bool'"),
+    ('1.5 ^ None', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'NoneType'"),
+    ("1.5 ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'float' and 'str'"),
+    ("1.5 ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'float' and 'bytes'"),
+    ("1.5 ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'float' and 'bytearray'"),
+    ('1.5 ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'list'"),
+    ('1.5 ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'tuple'"),
+    ('1.5 ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'dict'"),
+    ('1.5 ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'set'"),
+    ('1.5 ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'frozenset'"),
+    ('1.5 ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'float' and 'range'"),
+    ('1.5 < 1j', 'TypeError', "'<' not supported between instances of 'float' and 'complex'"),
+    ('1.5 < None', 'TypeError', "'<' not supported between instances of 'float' and 'NoneType'"),
+    ("1.5 < 'a'", 'TypeError', "'<' not supported between instances of 'float' and 'str'"),
+    ("1.5 < b'a'", 'TypeError', "'<' not supported between instances of 'float' and 'bytes'"),
+    ("1.5 < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'float' and 'bytearray'"),
+    ('1.5 < [1]', 'TypeError', "'<' not supported between instances of 'float' and 'list'"),
+    ('1.5 < (1,)', 'TypeError', "'<' not supported between instances of 'float' and 'tuple'"),
+    ('1.5 < {1: 1}', 'TypeError', "'<' not supported between instances of 'float' and 'dict'"),
+    ('1.5 < {1}', 'TypeError', "'<' not supported between instances of 'float' and 'set'"),
+    ('1.5 < frozenset({1})', 'TypeError', "'<' not supported between instances of 'float' and 'frozenset'"),
+    ('1.5 < range(1)', 'TypeError', "'<' not supported between instances of 'float' and 'range'"),
+    ('1.5 <= 1j', 'TypeError', "'<=' not supported between instances of 'float' and 'complex'"),
+    ('1.5 <= None', 'TypeError', "'<=' not supported between instances of 'float' and 'NoneType'"),
+    ("1.5 <= 'a'", 'TypeError', "'<=' not supported between instances of 'float' and 'str'"),
+    ("1.5 <= b'a'", 'TypeError', "'<=' not supported between instances of 'float' and 'bytes'"),
+    ("1.5 <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'float' and 'bytearray'"),
+    ('1.5 <= [1]', 'TypeError', "'<=' not supported between instances of 'float' and 'list'"),
+    ('1.5 <= (1,)', 'TypeError', "'<=' not supported between instances of 'float' and 'tuple'"),
+    ('1.5 <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'float' and 'dict'"),
+    ('1.5 <= {1}', 'TypeError', "'<=' not supported between instances of 'float' and 'set'"),
+    ('1.5 <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'float' and 'frozenset'"),
+    ('1.5 <= range(1)', 'TypeError', "'<=' not supported between instances of 'float' and 'range'"),
+    ('1.5 > 1j', 'TypeError', "'>' not supported between instances of 'float' and 'complex'"),
+    ('1.5 > None', 'TypeError', "'>' not supported between instances of 'float' and 'NoneType'"),
+    ("1.5 > 'a'", 'TypeError', "'>' not supported between instances of 'float' and 'str'"),
+    ("1.5 > b'a'", 'TypeError', "'>' not supported between instances of 'float' and 'bytes'"),
+    ("1.5 > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'float' and 'bytearray'"),
+    ('1.5 > [1]', 'TypeError', "'>' not supported between instances of 'float' and 'list'"),
+    ('1.5 > (1,)', 'TypeError', "'>' not supported between instances of 'float' and 'tuple'"),
+    ('1.5 > {1: 1}', 'TypeError', "'>' not supported between instances of 'float' and 'dict'"),
+    ('1.5 > {1}', 'TypeError', "'>' not supported between instances of 'float' and 'set'"),
+    ('1.5 > frozenset({1})', 'TypeError', "'>' not supported between instances of 'float' and 'frozenset'"),
+    ('1.5 > range(1)', 'TypeError', "'>' not supported between instances of 'float' and 'range'"),
+    ('1.5 >= 1j', 'TypeError', "'>=' not supported between instances of 'float' and 'complex'"),
+    ('1.5 >= None', 'TypeError', "'>=' not supported between instances of 'float' and 'NoneType'"),
+    ("1.5 >= 'a'", 'TypeError', "'>=' not supported between instances of 'float' and 'str'"),
+    ("1.5 >= b'a'", 'TypeError', "'>=' not supported between instances of 'float' and 'bytes'"),
+    ("1.5 >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'float' and 'bytearray'"),
+    ('1.5 >= [1]', 'TypeError', "'>=' not supported between instances of 'float' and 'list'"),
+    ('1.5 >= (1,)', 'TypeError', "'>=' not supported between instances of 'float' and 'tuple'"),
+    ('1.5 >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'float' and 'dict'"),
+    ('1.5 >= {1}', 'TypeError', "'>=' not supported between instances of 'float' and 'set'"),
+    ('1.5 >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'float' and 'frozenset'"),
+    ('1.5 >= range(1)', 'TypeError', "'>=' not supported between instances of 'float' and 'range'"),
+    ('1j + None', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'NoneType'"),
+    ("1j + 'a'", 'TypeError', "unsupported operand type(s) for +: 'complex' and 'str'"),
+    ("1j + b'a'", 'TypeError', "unsupported operand type(s) for +: 'complex' and 'bytes'"),
+    ("1j + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'complex' and 'bytearray'"),
+    ('1j + [1]', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'list'"),
+    ('1j + (1,)', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'tuple'"),
+    ('1j + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'dict'"),
+    ('1j + {1}', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'set'"),
+    ('1j + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'frozenset'"),
+    ('1j + range(1)', 'TypeError', "unsupported operand type(s) for +: 'complex' and 'range'"),
+    ('1j - None', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'NoneType'"),
+    ("1j - 'a'", 'TypeError', "unsupported operand type(s) for -: 'complex' and 'str'"),
+    ("1j - b'a'", 'TypeError', "unsupported operand type(s) for -: 'complex' and 'bytes'"),
+    ("1j - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'complex' and 'bytearray'"),
+    ('1j - [1]', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'list'"),
+    ('1j - (1,)', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'tuple'"),
+    ('1j - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'dict'"),
+    ('1j - {1}', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'set'"),
+    ('1j - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'frozenset'"),
+    ('1j - range(1)', 'TypeError', "unsupported operand type(s) for -: 'complex' and 'range'"),
+    ('1j * None', 'TypeError', "unsupported operand type(s) for *: 'complex' and 'NoneType'"),
+    ("1j * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ("1j * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ("1j * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ('1j * [1]', 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ('1j * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ('1j * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'complex' and 'dict'"),
+    ('1j * {1}', 'TypeError', "unsupported operand type(s) for *: 'complex' and 'set'"),
+    ('1j * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'complex' and 'frozenset'"),
+    ('1j * range(1)', 'TypeError', "unsupported operand type(s) for *: 'complex' and 'range'"),
+    ('1j / None', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'NoneType'"),
+    ("1j / 'a'", 'TypeError', "unsupported operand type(s) for /: 'complex' and 'str'"),
+    ("1j / b'a'", 'TypeError', "unsupported operand type(s) for /: 'complex' and 'bytes'"),
+    ("1j / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'complex' and 'bytearray'"),
+    ('1j / [1]', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'list'"),
+    ('1j / (1,)', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'tuple'"),
+    ('1j / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'dict'"),
+    ('1j / {1}', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'set'"),
+    ('1j / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'frozenset'"),
+    ('1j / range(1)', 'TypeError', "unsupported operand type(s) for /: 'complex' and 'range'"),
+    ('1j // 1', 'TypeError', "can't take floor of complex number."),
+    ('1j // 1.5', 'TypeError', "can't take floor of complex number."),
+    ('1j // 1j', 'TypeError', "can't take floor of complex number."),
+    ('1j // True', 'TypeError', "can't take floor of complex number."),
+    ('1j // None', 'TypeError', "can't take floor of complex number."),
+    ("1j // 'a'", 'TypeError', "can't take floor of complex number."),
+    ("1j // b'a'", 'TypeError', "can't take floor of complex number."),
+    ("1j // bytearray(b'a')", 'TypeError', "can't take floor of complex number."),
+    ('1j // [1]', 'TypeError', "can't take floor of complex number."),
+    ('1j // (1,)', 'TypeError', "can't take floor of complex number."),
+    ('1j // {1: 1}', 'TypeError', "can't take floor of complex number."),
+    ('1j // {1}', 'TypeError', "can't take floor of complex number."),
+    ('1j // frozenset({1})', 'TypeError', "can't take floor of complex number."),
+    ('1j // range(1)', 'TypeError', "can't take floor of complex number."),
+    ('1j % 1', 'TypeError', "can't mod complex numbers."),
+    ('1j % 1.5', 'TypeError', "can't mod complex numbers."),
+    ('1j % 1j', 'TypeError', "can't mod complex numbers."),
+    ('1j % True', 'TypeError', "can't mod complex numbers."),
+    ('1j % None', 'TypeError', "can't mod complex numbers."),
+    ("1j % 'a'", 'TypeError', "can't mod complex numbers."),
+    ("1j % b'a'", 'TypeError', "can't mod complex numbers."),
+    ("1j % bytearray(b'a')", 'TypeError', "can't mod complex numbers."),
+    ('1j % [1]', 'TypeError', "can't mod complex numbers."),
+    ('1j % (1,)', 'TypeError', "can't mod complex numbers."),
+    ('1j % {1: 1}', 'TypeError', "can't mod complex numbers."),
+    ('1j % {1}', 'TypeError', "can't mod complex numbers."),
+    ('1j % frozenset({1})', 'TypeError', "can't mod complex numbers."),
+    ('1j % range(1)', 'TypeError', "can't mod complex numbers."),
+    ('1j ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'NoneType'"),
+    ("1j ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'str'"),
+    ("1j ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'bytes'"),
+    ("1j ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'bytearray'"),
+    ('1j ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'list'"),
+    ('1j ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'tuple'"),
+    ('1j ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'dict'"),
+    ('1j ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'set'"),
+    ('1j ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'frozenset'"),
+    ('1j ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'complex' and 'range'"),
+    ('1j @ 1', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'int'"),
+    ('1j @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'float'"),
+    ('1j @ 1j', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'complex'"),
+    ('1j @ True', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'bool'"),
+    ('1j @ None', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'NoneType'"),
+    ("1j @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'complex' and 'str'"),
+    ("1j @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'complex' and 'bytes'"),
+    ("1j @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'complex' and 'bytearray'"),
+    ('1j @ [1]', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'list'"),
+    ('1j @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'tuple'"),
+    ('1j @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'dict'"),
+    ('1j @ {1}', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'set'"),
+    ('1j @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'frozenset'"),
+    ('1j @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'complex' and 'range'"),
+    ('1j << 1', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'int'"),
+    ('1j << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'float'"),
+    ('1j << 1j', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'complex'"),
+    ('1j << True', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'bool'"),
+    ('1j << None', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'NoneType'"),
+    ("1j << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'str'"),
+    ("1j << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'bytes'"),
+    ("1j << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'bytearray'"),
+    ('1j << [1]', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'list'"),
+    ('1j << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'tuple'"),
+    ('1j << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'dict'"),
+    ('1j << {1}', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'set'"),
+    ('1j << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'frozenset'"),
+    ('1j << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'complex' and 'range'"),
+    ('1j >> 1', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'int'"),
+    ('1j >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'float'"),
+    ('1j >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'complex'"),
+    ('1j >> True', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'bool'"),
+    ('1j >> None', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'NoneType'"),
+    ("1j >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'str'"),
+    ("1j >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'bytes'"),
+    ("1j >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'bytearray'"),
+    ('1j >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'list'"),
+    ('1j >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'tuple'"),
+    ('1j >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'dict'"),
+    ('1j >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'set'"),
+    ('1j >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'frozenset'"),
+    ('1j >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'complex' and 'range'"),
+    ('1j & 1', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'int'"),
+    ('1j & 1.5', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'float'"),
+    ('1j & 1j', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'complex'"),
+    ('1j & True', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'bool'"),
+    ('1j & None', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'NoneType'"),
+    ("1j & 'a'", 'TypeError', "unsupported operand type(s) for &: 'complex' and 'str'"),
+    ("1j & b'a'", 'TypeError', "unsupported operand type(s) for &: 'complex' and 'bytes'"),
+    ("1j & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'complex' and 'bytearray'"),
+    ('1j & [1]', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'list'"),
+    ('1j & (1,)', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'tuple'"),
+    ('1j & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'dict'"),
+    ('1j & {1}', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'set'"),
+    ('1j & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'frozenset'"),
+    ('1j & range(1)', 'TypeError', "unsupported operand type(s) for &: 'complex' and 'range'"),
+    ('1j | 1', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'int'"),
+    ('1j | 1.5', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'float'"),
+    ('1j | 1j', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'complex'"),
+    ('1j | True', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'bool'"),
+    ('1j | None', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'NoneType'"),
+    ("1j | 'a'", 'TypeError', "unsupported operand type(s) for |: 'complex' and 'str'"),
+    ("1j | b'a'", 'TypeError', "unsupported operand type(s) for |: 'complex' and 'bytes'"),
+    ("1j | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'complex' and 'bytearray'"),
+    ('1j | [1]', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'list'"),
+    ('1j | (1,)', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'tuple'"),
+    ('1j | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'dict'"),
+    ('1j | {1}', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'set'"),
+    ('1j | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'frozenset'"),
+    ('1j | range(1)', 'TypeError', "unsupported operand type(s) for |: 'complex' and 'range'"),
+    ('1j ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'int'"),
+    ('1j ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'float'"),
+    ('1j ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'complex'"),
+    ('1j ^ True', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'bool'"),
+    ('1j ^ None', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'NoneType'"),
+    ("1j ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'str'"),
+    ("1j ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'bytes'"),
+    ("1j ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'bytearray'"),
+    ('1j ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'list'"),
+    ('1j ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'tuple'"),
+    ('1j ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'dict'"),
+    ('1j ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'set'"),
+    ('1j ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'frozenset'"),
+    ('1j ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'complex' and 'range'"),
+    ('1j < 1', 'TypeError', "'<' not supported between instances of 'complex' and 'int'"),
+    ('1j < 1.5', 'TypeError', "'<' not supported between instances of 'complex' and 'float'"),
+    ('1j < 1j', 'TypeError', "'<' not supported between instances of 'complex' and 'complex'"),
+    ('1j < True', 'TypeError', "'<' not supported between instances of 'complex' and 'bool'"),
+    ('1j < None', 'TypeError', "'<' not supported between instances of 'complex' and 'NoneType'"),
+    ("1j < 'a'", 'TypeError', "'<' not supported between instances of 'complex' and 'str'"),
+    ("1j < b'a'", 'TypeError', "'<' not supported between instances of 'complex' and 'bytes'"),
+    ("1j < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'complex' and 'bytearray'"),
+    ('1j < [1]', 'TypeError', "'<' not supported between instances of 'complex' and 'list'"),
+    ('1j < (1,)', 'TypeError', "'<' not supported between instances of 'complex' and 'tuple'"),
+    ('1j < {1: 1}', 'TypeError', "'<' not supported between instances of 'complex' and 'dict'"),
+    ('1j < {1}', 'TypeError', "'<' not supported between instances of 'complex' and 'set'"),
+    ('1j < frozenset({1})', 'TypeError', "'<' not supported between instances of 'complex' and 'frozenset'"),
+    ('1j < range(1)', 'TypeError', "'<' not supported between instances of 'complex' and 'range'"),
+    ('1j <= 1', 'TypeError', "'<=' not supported between instances of 'complex' and 'int'"),
+    ('1j <= 1.5', 'TypeError', "'<=' not supported between instances of 'complex' and 'float'"),
+    ('1j <= 1j', 'TypeError', "'<=' not supported between instances of 'complex' and 'complex'"),
+    ('1j <= True', 'TypeError', "'<=' not supported between instances of 'complex' and 'bool'"),
+    ('1j <= None', 'TypeError', "'<=' not supported between instances of 'complex' and 'NoneType'"),
+    ("1j <= 'a'", 'TypeError', "'<=' not supported between instances of 'complex' and 'str'"),
+    ("1j <= b'a'", 'TypeError', "'<=' not supported between instances of 'complex' and 'bytes'"),
+    ("1j <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'complex' and 'bytearray'"),
+    ('1j <= [1]', 'TypeError', "'<=' not supported between instances of 'complex' and 'list'"),
+    ('1j <= (1,)', 'TypeError', "'<=' not supported between instances of 'complex' and 'tuple'"),
+    ('1j <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'complex' and 'dict'"),
+    ('1j <= {1}', 'TypeError', "'<=' not supported between instances of 'complex' and 'set'"),
+    ('1j <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'complex' and 'frozenset'"),
+    ('1j <= range(1)', 'TypeError', "'<=' not supported between instances of 'complex' and 'range'"),
+    ('1j > 1', 'TypeError', "'>' not supported between instances of 'complex' and 'int'"),
+    ('1j > 1.5', 'TypeError', "'>' not supported between instances of 'complex' and 'float'"),
+    ('1j > 1j', 'TypeError', "'>' not supported between instances of 'complex' and 'complex'"),
+    ('1j > True', 'TypeError', "'>' not supported between instances of 'complex' and 'bool'"),
+    ('1j > None', 'TypeError', "'>' not supported between instances of 'complex' and 'NoneType'"),
+    ("1j > 'a'", 'TypeError', "'>' not supported between instances of 'complex' and 'str'"),
+    ("1j > b'a'", 'TypeError', "'>' not supported between instances of 'complex' and 'bytes'"),
+    ("1j > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'complex' and 'bytearray'"),
+    ('1j > [1]', 'TypeError', "'>' not supported between instances of 'complex' and 'list'"),
+    ('1j > (1,)', 'TypeError', "'>' not supported between instances of 'complex' and 'tuple'"),
+    ('1j > {1: 1}', 'TypeError', "'>' not supported between instances of 'complex' and 'dict'"),
+    ('1j > {1}', 'TypeError', "'>' not supported between instances of 'complex' and 'set'"),
+    ('1j > frozenset({1})', 'TypeError', "'>' not supported between instances of 'complex' and 'frozenset'"),
+    ('1j > range(1)', 'TypeError', "'>' not supported between instances of 'complex' and 'range'"),
+    ('1j >= 1', 'TypeError', "'>=' not supported between instances of 'complex' and 'int'"),
+    ('1j >= 1.5', 'TypeError', "'>=' not supported between instances of 'complex' and 'float'"),
+    ('1j >= 1j', 'TypeError', "'>=' not supported between instances of 'complex' and 'complex'"),
+    ('1j >= True', 'TypeError', "'>=' not supported between instances of 'complex' and 'bool'"),
+    ('1j >= None', 'TypeError', "'>=' not supported between instances of 'complex' and 'NoneType'"),
+    ("1j >= 'a'", 'TypeError', "'>=' not supported between instances of 'complex' and 'str'"),
+    ("1j >= b'a'", 'TypeError', "'>=' not supported between instances of 'complex' and 'bytes'"),
+    ("1j >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'complex' and 'bytearray'"),
+    ('1j >= [1]', 'TypeError', "'>=' not supported between instances of 'complex' and 'list'"),
+    ('1j >= (1,)', 'TypeError', "'>=' not supported between instances of 'complex' and 'tuple'"),
+    ('1j >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'complex' and 'dict'"),
+    ('1j >= {1}', 'TypeError', "'>=' not supported between instances of 'complex' and 'set'"),
+    ('1j >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'complex' and 'frozenset'"),
+    ('1j >= range(1)', 'TypeError', "'>=' not supported between instances of 'complex' and 'range'"),
+    ('True + None', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'NoneType'"),
+    ("True + 'a'", 'TypeError', "unsupported operand type(s) for +: 'bool' and 'str'"),
+    ("True + b'a'", 'TypeError', "unsupported operand type(s) for +: 'bool' and 'bytes'"),
+    ("True + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'bool' and 'bytearray'"),
+    ('True + [1]', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'list'"),
+    ('True + (1,)', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'tuple'"),
+    ('True + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'dict'"),
+    ('True + {1}', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'set'"),
+    ('True + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'frozenset'"),
+    ('True + range(1)', 'TypeError', "unsupported operand type(s) for +: 'bool' and 'range'"),
+    ('True - None', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'NoneType'"),
+    ("True - 'a'", 'TypeError', "unsupported operand type(s) for -: 'bool' and 'str'"),
+    ("True - b'a'", 'TypeError', "unsupported operand type(s) for -: 'bool' and 'bytes'"),
+    ("True - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'bool' and 'bytearray'"),
+    ('True - [1]', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'list'"),
+    ('True - (1,)', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'tuple'"),
+    ('True - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'dict'"),
+    ('True - {1}', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'set'"),
+    ('True - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'frozenset'"),
+    ('True - range(1)', 'TypeError', "unsupported operand type(s) for -: 'bool' and 'range'"),
+    ('True * None', 'TypeError', "unsupported operand type(s) for *: 'bool' and 'NoneType'"),
+    ('True * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'bool' and 'dict'"),
+    ('True * {1}', 'TypeError', "unsupported operand type(s) for *: 'bool' and 'set'"),
+    ('True * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'bool' and 'frozenset'"),
+    ('True * range(1)', 'TypeError', "unsupported operand type(s) for *: 'bool' and 'range'"),
+    ('True / None', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'NoneType'"),
+    ("True / 'a'", 'TypeError', "unsupported operand type(s) for /: 'bool' and 'str'"),
+    ("True / b'a'", 'TypeError', "unsupported operand type(s) for /: 'bool' and 'bytes'"),
+    ("True / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'bool' and 'bytearray'"),
+    ('True / [1]', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'list'"),
+    ('True / (1,)', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'tuple'"),
+    ('True / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'dict'"),
+    ('True / {1}', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'set'"),
+    ('True / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'frozenset'"),
+    ('True / range(1)', 'TypeError', "unsupported operand type(s) for /: 'bool' and 'range'"),
+    ('True // 1j', 'TypeError', "can't take floor of complex number."),
+    ('True // None', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'NoneType'"),
+    ("True // 'a'", 'TypeError', "unsupported operand type(s) for //: 'bool' and 'str'"),
+    ("True // b'a'", 'TypeError', "unsupported operand type(s) for //: 'bool' and 'bytes'"),
+    ("True // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'bool' and 'bytearray'"),
+    ('True // [1]', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'list'"),
+    ('True // (1,)', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'tuple'"),
+    ('True // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'dict'"),
+    ('True // {1}', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'set'"),
+    ('True // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'frozenset'"),
+    ('True // range(1)', 'TypeError', "unsupported operand type(s) for //: 'bool' and 'range'"),
+    ('True % 1j', 'TypeError', "can't mod complex numbers."),
+    ('True % None', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'NoneType'"),
+    ("True % 'a'", 'TypeError', "unsupported operand type(s) for %: 'bool' and 'str'"),
+    ("True % b'a'", 'TypeError', "unsupported operand type(s) for %: 'bool' and 'bytes'"),
+    ("True % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'bool' and 'bytearray'"),
+    ('True % [1]', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'list'"),
+    ('True % (1,)', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'tuple'"),
+    ('True % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'dict'"),
+    ('True % {1}', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'set'"),
+    ('True % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'frozenset'"),
+    ('True % range(1)', 'TypeError', "unsupported operand type(s) for %: 'bool' and 'range'"),
+    ('True ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'NoneType'"),
+    ("True ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'str'"),
+    ("True ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'bytes'"),
+    ("True ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'bytearray'"),
+    ('True ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'list'"),
+    ('True ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'tuple'"),
+    ('True ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'dict'"),
+    ('True ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'set'"),
+    ('True ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'frozenset'"),
+    ('True ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'bool' and 'range'"),
+    ('True @ 1', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'int'"),
+    ('True @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'float'"),
+    ('True @ 1j', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'complex'"),
+    ('True @ True', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'bool'"),
+    ('True @ None', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'NoneType'"),
+    ("True @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'bool' and 'str'"),
+    ("True @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'bool' and 'bytes'"),
+    ("True @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'bool' and 'bytearray'"),
+    ('True @ [1]', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'list'"),
+    ('True @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'tuple'"),
+    ('True @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'dict'"),
+    ('True @ {1}', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'set'"),
+    ('True @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'frozenset'"),
+    ('True @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'bool' and 'range'"),
+    ('True << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'float'"),
+    ('True << 1j', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'complex'"),
+    ('True << None', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'NoneType'"),
+    ("True << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'str'"),
+    ("True << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'bytes'"),
+    ("True << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'bytearray'"),
+    ('True << [1]', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'list'"),
+    ('True << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'tuple'"),
+    ('True << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'dict'"),
+    ('True << {1}', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'set'"),
+    ('True << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'frozenset'"),
+    ('True << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'bool' and 'range'"),
+    ('True >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'float'"),
+    ('True >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'complex'"),
+    ('True >> None', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'NoneType'"),
+    ("True >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'str'"),
+    ("True >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'bytes'"),
+    ("True >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'bytearray'"),
+    ('True >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'list'"),
+    ('True >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'tuple'"),
+    ('True >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'dict'"),
+    ('True >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'set'"),
+    ('True >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'frozenset'"),
+    ('True >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'bool' and 'range'"),
+    ('True & 1.5', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'float'"),
+    ('True & 1j', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'complex'"),
+    ('True & None', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'NoneType'"),
+    ("True & 'a'", 'TypeError', "unsupported operand type(s) for &: 'bool' and 'str'"),
+    ("True & b'a'", 'TypeError', "unsupported operand type(s) for &: 'bool' and 'bytes'"),
+    ("True & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'bool' and 'bytearray'"),
+    ('True & [1]', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'list'"),
+    ('True & (1,)', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'tuple'"),
+    ('True & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'dict'"),
+    ('True & {1}', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'set'"),
+    ('True & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'frozenset'"),
+    ('True & range(1)', 'TypeError', "unsupported operand type(s) for &: 'bool' and 'range'"),
+    ('True | 1.5', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'float'"),
+    ('True | 1j', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'complex'"),
+    ('True | None', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'NoneType'"),
+    ("True | 'a'", 'TypeError', "unsupported operand type(s) for |: 'bool' and 'str'"),
+    ("True | b'a'", 'TypeError', "unsupported operand type(s) for |: 'bool' and 'bytes'"),
+    ("True | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'bool' and 'bytearray'"),
+    ('True | [1]', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'list'"),
+    ('True | (1,)', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'tuple'"),
+    ('True | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'dict'"),
+    ('True | {1}', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'set'"),
+    ('True | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'frozenset'"),
+    ('True | range(1)', 'TypeError', "unsupported operand type(s) for |: 'bool' and 'range'"),
+    ('True ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'float'"),
+    ('True ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'complex'"),
+    ('True ^ None', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'NoneType'"),
+    ("True ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'str'"),
+    ("True ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'bytes'"),
+    ("True ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'bytearray'"),
+    ('True ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'list'"),
+    ('True ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'tuple'"),
+    ('True ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'dict'"),
+    ('True ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'set'"),
+    ('True ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'frozenset'"),
+    ('True ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'bool' and 'range'"),
+    ('True < 1j', 'TypeError', "'<' not supported between instances of 'bool' and 'complex'"),
+    ('True < None', 'TypeError', "'<' not supported between instances of 'bool' and 'NoneType'"),
+    ("True < 'a'", 'TypeError', "'<' not supported between instances of 'bool' and 'str'"),
+    ("True < b'a'", 'TypeError', "'<' not supported between instances of 'bool' and 'bytes'"),
+    ("True < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'bool' and 'bytearray'"),
+    ('True < [1]', 'TypeError', "'<' not supported between instances of 'bool' and 'list'"),
+    ('True < (1,)', 'TypeError', "'<' not supported between instances of 'bool' and 'tuple'"),
+    ('True < {1: 1}', 'TypeError', "'<' not supported between instances of 'bool' and 'dict'"),
+    ('True < {1}', 'TypeError', "'<' not supported between instances of 'bool' and 'set'"),
+    ('True < frozenset({1})', 'TypeError', "'<' not supported between instances of 'bool' and 'frozenset'"),
+    ('True < range(1)', 'TypeError', "'<' not supported between instances of 'bool' and 'range'"),
+    ('True <= 1j', 'TypeError', "'<=' not supported between instances of 'bool' and 'complex'"),
+    ('True <= None', 'TypeError', "'<=' not supported between instances of 'bool' and 'NoneType'"),
+    ("True <= 'a'", 'TypeError', "'<=' not supported between instances of 'bool' and 'str'"),
+    ("True <= b'a'", 'TypeError', "'<=' not supported between instances of 'bool' and 'bytes'"),
+    ("True <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'bool' and 'bytearray'"),
+    ('True <= [1]', 'TypeError', "'<=' not supported between instances of 'bool' and 'list'"),
+    ('True <= (1,)', 'TypeError', "'<=' not supported between instances of 'bool' and 'tuple'"),
+    ('True <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'bool' and 'dict'"),
+    ('True <= {1}', 'TypeError', "'<=' not supported between instances of 'bool' and 'set'"),
+    ('True <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'bool' and 'frozenset'"),
+    ('True <= range(1)', 'TypeError', "'<=' not supported between instances of 'bool' and 'range'"),
+    ('True > 1j', 'TypeError', "'>' not supported between instances of 'bool' and 'complex'"),
+    ('True > None', 'TypeError', "'>' not supported between instances of 'bool' and 'NoneType'"),
+    ("True > 'a'", 'TypeError', "'>' not supported between instances of 'bool' and 'str'"),
+    ("True > b'a'", 'TypeError', "'>' not supported between instances of 'bool' and 'bytes'"),
+    ("True > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'bool' and 'bytearray'"),
+    ('True > [1]', 'TypeError', "'>' not supported between instances of 'bool' and 'list'"),
+    ('True > (1,)', 'TypeError', "'>' not supported between instances of 'bool' and 'tuple'"),
+    ('True > {1: 1}', 'TypeError', "'>' not supported between instances of 'bool' and 'dict'"),
+    ('True > {1}', 'TypeError', "'>' not supported between instances of 'bool' and 'set'"),
+    ('True > frozenset({1})', 'TypeError', "'>' not supported between instances of 'bool' and 'frozenset'"),
+    ('True > range(1)', 'TypeError', "'>' not supported between instances of 'bool' and 'range'"),
+    ('True >= 1j', 'TypeError', "'>=' not supported between instances of 'bool' and 'complex'"),
+    ('True >= None', 'TypeError', "'>=' not supported between instances of 'bool' and 'NoneType'"),
+    ("True >= 'a'", 'TypeError', "'>=' not supported between instances of 'bool' and 'str'"),
+    ("True >= b'a'", 'TypeError', "'>=' not supported between instances of 'bool' and 'bytes'"),
+    ("True >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'bool' and 'bytearray'"),
+    ('True >= [1]', 'TypeError', "'>=' not supported between instances of 'bool' and 'list'"),
+    ('True >= (1,)', 'TypeError', "'>=' not supported between instances of 'bool' and 'tuple'"),
+    ('True >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'bool' and 'dict'"),
+    ('True >= {1}', 'TypeError', "'>=' not supported between instances of 'bool' and 'set'"),
+    ('True >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'bool' and 'frozenset'"),
+    ('True >= range(1)', 'TypeError', "'>=' not supported between instances of 'bool' and 'range'"),
+    ('None + 1', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'int'"),
+    ('None + 1.5', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'float'"),
+    ('None + 1j', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'complex'"),
+    ('None + True', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'bool'"),
+    ('None + None', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'NoneType'"),
+    ("None + 'a'", 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'str'"),
+    ("None + b'a'", 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'bytes'"),
+    ("None + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'bytearray'"),
+    ('None + [1]', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'list'"),
+    ('None + (1,)', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'tuple'"),
+    ('None + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'dict'"),
+    ('None + {1}', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'set'"),
+    ('None + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'frozenset'"),
+    ('None + range(1)', 'TypeError', "unsupported operand type(s) for +: 'NoneType' and 'range'"),
+    ('None - 1', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'int'"),
+    ('None - 1.5', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'float'"),
+    ('None - 1j', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'complex'"),
+    ('None - True', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'bool'"),
+    ('None - None', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'NoneType'"),
+    ("None - 'a'", 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'str'"),
+    ("None - b'a'", 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'bytes'"),
+    ("None - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'bytearray'"),
+    ('None - [1]', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'list'"),
+    ('None - (1,)', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'tuple'"),
+    ('None - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'dict'"),
+    ('None - {1}', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'set'"),
+    ('None - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'frozenset'"),
+    ('None - range(1)', 'TypeError', "unsupported operand type(s) for -: 'NoneType' and 'range'"),
+    ('None * 1', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'int'"),
+    ('None * 1.5', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'float'"),
+    ('None * 1j', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'complex'"),
+    ('None * True', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'bool'"),
+    ('None * None', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'NoneType'"),
+    ("None * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("None * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("None * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ('None * [1]', 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ('None * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ('None * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'dict'"),
+    ('None * {1}', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'set'"),
+    ('None * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'frozenset'"),
+    ('None * range(1)', 'TypeError', "unsupported operand type(s) for *: 'NoneType' and 'range'"),
+    ('None / 1', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'int'"),
+    ('None / 1.5', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'float'"),
+    ('None / 1j', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'complex'"),
+    ('None / True', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'bool'"),
+    ('None / None', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'NoneType'"),
+    ("None / 'a'", 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'str'"),
+    ("None / b'a'", 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'bytes'"),
+    ("None / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'bytearray'"),
+    ('None / [1]', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'list'"),
+    ('None / (1,)', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'tuple'"),
+    ('None / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'dict'"),
+    ('None / {1}', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'set'"),
+    ('None / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'frozenset'"),
+    ('None / range(1)', 'TypeError', "unsupported operand type(s) for /: 'NoneType' and 'range'"),
+    ('None // 1', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'int'"),
+    ('None // 1.5', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'float'"),
+    ('None // 1j', 'TypeError', "can't take floor of complex number."),
+    ('None // True', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'bool'"),
+    ('None // None', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'NoneType'"),
+    ("None // 'a'", 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'str'"),
+    ("None // b'a'", 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'bytes'"),
+    ("None // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'bytearray'"),
+    ('None // [1]', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'list'"),
+    ('None // (1,)', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'tuple'"),
+    ('None // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'dict'"),
+    ('None // {1}', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'set'"),
+    ('None // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'frozenset'"),
+    ('None // range(1)', 'TypeError', "unsupported operand type(s) for //: 'NoneType' and 'range'"),
+    ('None % 1', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'int'"),
+    ('None % 1.5', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'float'"),
+    ('None % 1j', 'TypeError', "can't mod complex numbers."),
+    ('None % True', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'bool'"),
+    ('None % None', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'NoneType'"),
+    ("None % 'a'", 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'str'"),
+    ("None % b'a'", 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'bytes'"),
+    ("None % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'bytearray'"),
+    ('None % [1]', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'list'"),
+    ('None % (1,)', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'tuple'"),
+    ('None % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'dict'"),
+    ('None % {1}', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'set'"),
+    ('None % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'frozenset'"),
+    ('None % range(1)', 'TypeError', "unsupported operand type(s) for %: 'NoneType' and 'range'"),
+    ('None ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'int'"),
+    ('None ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'float'"),
+    ('None ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'complex'"),
+    ('None ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'bool'"),
+    ('None ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'NoneType'"),
+    ("None ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'str'"),
+    ("None ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'bytes'"),
+    ("None ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'bytearray'"),
+    ('None ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'list'"),
+    ('None ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'tuple'"),
+    ('None ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'dict'"),
+    ('None ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'set'"),
+    ('None ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'frozenset'"),
+    ('None ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'NoneType' and 'range'"),
+    ('None @ 1', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'int'"),
+    ('None @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'float'"),
+    ('None @ 1j', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'complex'"),
+    ('None @ True', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'bool'"),
+    ('None @ None', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'NoneType'"),
+    ("None @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'str'"),
+    ("None @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'bytes'"),
+    ("None @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'bytearray'"),
+    ('None @ [1]', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'list'"),
+    ('None @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'tuple'"),
+    ('None @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'dict'"),
+    ('None @ {1}', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'set'"),
+    ('None @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'frozenset'"),
+    ('None @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'NoneType' and 'range'"),
+    ('None << 1', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'int'"),
+    ('None << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'float'"),
+    ('None << 1j', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'complex'"),
+    ('None << True', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'bool'"),
+    ('None << None', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'NoneType'"),
+    ("None << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'str'"),
+    ("None << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'bytes'"),
+    ("None << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'bytearray'"),
+    ('None << [1]', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'list'"),
+    ('None << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'tuple'"),
+    ('None << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'dict'"),
+    ('None << {1}', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'set'"),
+    ('None << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'frozenset'"),
+    ('None << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'NoneType' and 'range'"),
+    ('None >> 1', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'int'"),
+    ('None >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'float'"),
+    ('None >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'complex'"),
+    ('None >> True', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'bool'"),
+    ('None >> None', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'NoneType'"),
+    ("None >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'str'"),
+    ("None >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'bytes'"),
+    ("None >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'bytearray'"),
+    ('None >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'list'"),
+    ('None >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'tuple'"),
+    ('None >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'dict'"),
+    ('None >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'set'"),
+    ('None >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'frozenset'"),
+    ('None >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'NoneType' and 'range'"),
+    ('None & 1', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'int'"),
+    ('None & 1.5', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'float'"),
+    ('None & 1j', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'complex'"),
+    ('None & True', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'bool'"),
+    ('None & None', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'NoneType'"),
+    ("None & 'a'", 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'str'"),
+    ("None & b'a'", 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'bytes'"),
+    ("None & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'bytearray'"),
+    ('None & [1]', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'list'"),
+    ('None & (1,)', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'tuple'"),
+    ('None & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'dict'"),
+    ('None & {1}', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'set'"),
+    ('None & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'frozenset'"),
+    ('None & range(1)', 'TypeError', "unsupported operand type(s) for &: 'NoneType' and 'range'"),
+    ('None | 1', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'int'"),
+    ('None | 1.5', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'float'"),
+    ('None | 1j', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'complex'"),
+    ('None | True', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'bool'"),
+    ('None | None', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'NoneType'"),
+    ("None | 'a'", 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'str'"),
+    ("None | b'a'", 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'bytes'"),
+    ("None | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'bytearray'"),
+    ('None | [1]', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'list'"),
+    ('None | (1,)', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'tuple'"),
+    ('None | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'dict'"),
+    ('None | {1}', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'set'"),
+    ('None | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'frozenset'"),
+    ('None | range(1)', 'TypeError', "unsupported operand type(s) for |: 'NoneType' and 'range'"),
+    ('None ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'int'"),
+    ('None ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'float'"),
+    ('None ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'complex'"),
+    ('None ^ True', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'bool'"),
+    ('None ^ None', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'NoneType'"),
+    ("None ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'str'"),
+    ("None ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'bytes'"),
+    ("None ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'bytearray'"),
+    ('None ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'list'"),
+    ('None ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'tuple'"),
+    ('None ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'dict'"),
+    ('None ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'set'"),
+    ('None ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'frozenset'"),
+    ('None ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'NoneType' and 'range'"),
+    ('None < 1', 'TypeError', "'<' not supported between instances of 'NoneType' and 'int'"),
+    ('None < 1.5', 'TypeError', "'<' not supported between instances of 'NoneType' and 'float'"),
+    ('None < 1j', 'TypeError', "'<' not supported between instances of 'NoneType' and 'complex'"),
+    ('None < True', 'TypeError', "'<' not supported between instances of 'NoneType' and 'bool'"),
+    ('None < None', 'TypeError', "'<' not supported between instances of 'NoneType' and 'NoneType'"),
+    ("None < 'a'", 'TypeError', "'<' not supported between instances of 'NoneType' and 'str'"),
+    ("None < b'a'", 'TypeError', "'<' not supported between instances of 'NoneType' and 'bytes'"),
+    ("None < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'NoneType' and 'bytearray'"),
+    ('None < [1]', 'TypeError', "'<' not supported between instances of 'NoneType' and 'list'"),
+    ('None < (1,)', 'TypeError', "'<' not supported between instances of 'NoneType' and 'tuple'"),
+    ('None < {1: 1}', 'TypeError', "'<' not supported between instances of 'NoneType' and 'dict'"),
+    ('None < {1}', 'TypeError', "'<' not supported between instances of 'NoneType' and 'set'"),
+    ('None < frozenset({1})', 'TypeError', "'<' not supported between instances of 'NoneType' and 'frozenset'"),
+    ('None < range(1)', 'TypeError', "'<' not supported between instances of 'NoneType' and 'range'"),
+    ('None <= 1', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'int'"),
+    ('None <= 1.5', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'float'"),
+    ('None <= 1j', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'complex'"),
+    ('None <= True', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'bool'"),
+    ('None <= None', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'NoneType'"),
+    ("None <= 'a'", 'TypeError', "'<=' not supported between instances of 'NoneType' and 'str'"),
+    ("None <= b'a'", 'TypeError', "'<=' not supported between instances of 'NoneType' and 'bytes'"),
+    ("None <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'NoneType' and 'bytearray'"),
+    ('None <= [1]', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'list'"),
+    ('None <= (1,)', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'tuple'"),
+    ('None <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'dict'"),
+    ('None <= {1}', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'set'"),
+    ('None <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'frozenset'"),
+    ('None <= range(1)', 'TypeError', "'<=' not supported between instances of 'NoneType' and 'range'"),
+    ('None > 1', 'TypeError', "'>' not supported between instances of 'NoneType' and 'int'"),
+    ('None > 1.5', 'TypeError', "'>' not supported between instances of 'NoneType' and 'float'"),
+    ('None > 1j', 'TypeError', "'>' not supported between instances of 'NoneType' and 'complex'"),
+    ('None > True', 'TypeError', "'>' not supported between instances of 'NoneType' and 'bool'"),
+    ('None > None', 'TypeError', "'>' not supported between instances of 'NoneType' and 'NoneType'"),
+    ("None > 'a'", 'TypeError', "'>' not supported between instances of 'NoneType' and 'str'"),
+    ("None > b'a'", 'TypeError', "'>' not supported between instances of 'NoneType' and 'bytes'"),
+    ("None > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'NoneType' and 'bytearray'"),
+    ('None > [1]', 'TypeError', "'>' not supported between instances of 'NoneType' and 'list'"),
+    ('None > (1,)', 'TypeError', "'>' not supported between instances of 'NoneType' and 'tuple'"),
+    ('None > {1: 1}', 'TypeError', "'>' not supported between instances of 'NoneType' and 'dict'"),
+    ('None > {1}', 'TypeError', "'>' not supported between instances of 'NoneType' and 'set'"),
+    ('None > frozenset({1})', 'TypeError', "'>' not supported between instances of 'NoneType' and 'frozenset'"),
+    ('None > range(1)', 'TypeError', "'>' not supported between instances of 'NoneType' and 'range'"),
+    ('None >= 1', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'int'"),
+    ('None >= 1.5', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'float'"),
+    ('None >= 1j', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'complex'"),
+    ('None >= True', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'bool'"),
+    ('None >= None', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'NoneType'"),
+    ("None >= 'a'", 'TypeError', "'>=' not supported between instances of 'NoneType' and 'str'"),
+    ("None >= b'a'", 'TypeError', "'>=' not supported between instances of 'NoneType' and 'bytes'"),
+    ("None >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'NoneType' and 'bytearray'"),
+    ('None >= [1]', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'list'"),
+    ('None >= (1,)', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'tuple'"),
+    ('None >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'dict'"),
+    ('None >= {1}', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'set'"),
+    ('None >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'frozenset'"),
+    ('None >= range(1)', 'TypeError', "'>=' not supported between instances of 'NoneType' and 'range'"),
+    ("'a' + 1", 'TypeError', 'can only concatenate str (not "int") to str'),
+    ("'a' + 1.5", 'TypeError', 'can only concatenate str (not "float") to str'),
+    ("'a' + 1j", 'TypeError', 'can only concatenate str (not "complex") to str'),
+    ("'a' + True", 'TypeError', 'can only concatenate str (not "bool") to str'),
+    ("'a' + None", 'TypeError', 'can only concatenate str (not "NoneType") to str'),
+    ("'a' + b'a'", 'TypeError', 'can only concatenate str (not "bytes") to str'),
+    ("'a' + bytearray(b'a')", 'TypeError', 'can only concatenate str (not "bytearray") to str'),
+    ("'a' + [1]", 'TypeError', 'can only concatenate str (not "list") to str'),
+    ("'a' + (1,)", 'TypeError', 'can only concatenate str (not "tuple") to str'),
+    ("'a' + {1: 1}", 'TypeError', 'can only concatenate str (not "dict") to str'),
+    ("'a' + {1}", 'TypeError', 'can only concatenate str (not "set") to str'),
+    ("'a' + frozenset({1})", 'TypeError', 'can only concatenate str (not "frozenset") to str'),
+    ("'a' + range(1)", 'TypeError', 'can only concatenate str (not "range") to str'),
+    ("'a' - 1", 'TypeError', "unsupported operand type(s) for -: 'str' and 'int'"),
+    ("'a' - 1.5", 'TypeError', "unsupported operand type(s) for -: 'str' and 'float'"),
+    ("'a' - 1j", 'TypeError', "unsupported operand type(s) for -: 'str' and 'complex'"),
+    ("'a' - True", 'TypeError', "unsupported operand type(s) for -: 'str' and 'bool'"),
+    ("'a' - None", 'TypeError', "unsupported operand type(s) for -: 'str' and 'NoneType'"),
+    ("'a' - 'a'", 'TypeError', "unsupported operand type(s) for -: 'str' and 'str'"),
+    ("'a' - b'a'", 'TypeError', "unsupported operand type(s) for -: 'str' and 'bytes'"),
+    ("'a' - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'str' and 'bytearray'"),
+    ("'a' - [1]", 'TypeError', "unsupported operand type(s) for -: 'str' and 'list'"),
+    ("'a' - (1,)", 'TypeError', "unsupported operand type(s) for -: 'str' and 'tuple'"),
+    ("'a' - {1: 1}", 'TypeError', "unsupported operand type(s) for -: 'str' and 'dict'"),
+    ("'a' - {1}", 'TypeError', "unsupported operand type(s) for -: 'str' and 'set'"),
+    ("'a' - frozenset({1})", 'TypeError', "unsupported operand type(s) for -: 'str' and 'frozenset'"),
+    ("'a' - range(1)", 'TypeError', "unsupported operand type(s) for -: 'str' and 'range'"),
+    ("'a' * 1.5", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ("'a' * 1j", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ("'a' * None", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("'a' * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'str'"),
+    ("'a' * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'bytes'"),
+    ("'a' * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'bytearray'"),
+    ("'a' * [1]", 'TypeError', "can't multiply sequence by non-int of type 'list'"),
+    ("'a' * (1,)", 'TypeError', "can't multiply sequence by non-int of type 'tuple'"),
+    ("'a' * {1: 1}", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ("'a' * {1}", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ("'a' * frozenset({1})", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ("'a' * range(1)", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ("'a' / 1", 'TypeError', "unsupported operand type(s) for /: 'str' and 'int'"),
+    ("'a' / 1.5", 'TypeError', "unsupported operand type(s) for /: 'str' and 'float'"),
+    ("'a' / 1j", 'TypeError', "unsupported operand type(s) for /: 'str' and 'complex'"),
+    ("'a' / True", 'TypeError', "unsupported operand type(s) for /: 'str' and 'bool'"),
+    ("'a' / None", 'TypeError', "unsupported operand type(s) for /: 'str' and 'NoneType'"),
+    ("'a' / 'a'", 'TypeError', "unsupported operand type(s) for /: 'str' and 'str'"),
+    ("'a' / b'a'", 'TypeError', "unsupported operand type(s) for /: 'str' and 'bytes'"),
+    ("'a' / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'str' and 'bytearray'"),
+    ("'a' / [1]", 'TypeError', "unsupported operand type(s) for /: 'str' and 'list'"),
+    ("'a' / (1,)", 'TypeError', "unsupported operand type(s) for /: 'str' and 'tuple'"),
+    ("'a' / {1: 1}", 'TypeError', "unsupported operand type(s) for /: 'str' and 'dict'"),
+    ("'a' / {1}", 'TypeError', "unsupported operand type(s) for /: 'str' and 'set'"),
+    ("'a' / frozenset({1})", 'TypeError', "unsupported operand type(s) for /: 'str' and 'frozenset'"),
+    ("'a' / range(1)", 'TypeError', "unsupported operand type(s) for /: 'str' and 'range'"),
+    ("'a' // 1", 'TypeError', "unsupported operand type(s) for //: 'str' and 'int'"),
+    ("'a' // 1.5", 'TypeError', "unsupported operand type(s) for //: 'str' and 'float'"),
+    ("'a' // 1j", 'TypeError', "can't take floor of complex number."),
+    ("'a' // True", 'TypeError', "unsupported operand type(s) for //: 'str' and 'bool'"),
+    ("'a' // None", 'TypeError', "unsupported operand type(s) for //: 'str' and 'NoneType'"),
+    ("'a' // 'a'", 'TypeError', "unsupported operand type(s) for //: 'str' and 'str'"),
+    ("'a' // b'a'", 'TypeError', "unsupported operand type(s) for //: 'str' and 'bytes'"),
+    ("'a' // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'str' and 'bytearray'"),
+    ("'a' // [1]", 'TypeError', "unsupported operand type(s) for //: 'str' and 'list'"),
+    ("'a' // (1,)", 'TypeError', "unsupported operand type(s) for //: 'str' and 'tuple'"),
+    ("'a' // {1: 1}", 'TypeError', "unsupported operand type(s) for //: 'str' and 'dict'"),
+    ("'a' // {1}", 'TypeError', "unsupported operand type(s) for //: 'str' and 'set'"),
+    ("'a' // frozenset({1})", 'TypeError', "unsupported operand type(s) for //: 'str' and 'frozenset'"),
+    ("'a' // range(1)", 'TypeError', "unsupported operand type(s) for //: 'str' and 'range'"),
+    ("'a' % 1", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % 1.5", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % 1j", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % True", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % None", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % 'a'", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % (1,)", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % {1}", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' % frozenset({1})", 'TypeError', 'not all arguments converted during string formatting'),
+    ("'a' ** 1", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'int'"),
+    ("'a' ** 1.5", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'float'"),
+    ("'a' ** 1j", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'complex'"),
+    ("'a' ** True", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'bool'"),
+    ("'a' ** None", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'NoneType'"),
+    ("'a' ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'str'"),
+    ("'a' ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'bytes'"),
+    ("'a' ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'bytearray'"),
+    ("'a' ** [1]", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'list'"),
+    ("'a' ** (1,)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'tuple'"),
+    ("'a' ** {1: 1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'dict'"),
+    ("'a' ** {1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'set'"),
+    ("'a' ** frozenset({1})", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'frozenset'"),
+    ("'a' ** range(1)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'str' and 'range'"),
+    ("'a' @ 1", 'TypeError', "unsupported operand type(s) for @: 'str' and 'int'"),
+    ("'a' @ 1.5", 'TypeError', "unsupported operand type(s) for @: 'str' and 'float'"),
+    ("'a' @ 1j", 'TypeError', "unsupported operand type(s) for @: 'str' and 'complex'"),
+    ("'a' @ True", 'TypeError', "unsupported operand type(s) for @: 'str' and 'bool'"),
+    ("'a' @ None", 'TypeError', "unsupported operand type(s) for @: 'str' and 'NoneType'"),
+    ("'a' @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'str' and 'str'"),
+    ("'a' @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'str' and 'bytes'"),
+    ("'a' @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'str' and 'bytearray'"),
+    ("'a' @ [1]", 'TypeError', "unsupported operand type(s) for @: 'str' and 'list'"),
+    ("'a' @ (1,)", 'TypeError', "unsupported operand type(s) for @: 'str' and 'tuple'"),
+    ("'a' @ {1: 1}", 'TypeError', "unsupported operand type(s) for @: 'str' and 'dict'"),
+    ("'a' @ {1}", 'TypeError', "unsupported operand type(s) for @: 'str' and 'set'"),
+    ("'a' @ frozenset({1})", 'TypeError', "unsupported operand type(s) for @: 'str' and 'frozenset'"),
+    ("'a' @ range(1)", 'TypeError', "unsupported operand type(s) for @: 'str' and 'range'"),
+    ("'a' << 1", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'int'"),
+    ("'a' << 1.5", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'float'"),
+    ("'a' << 1j", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'complex'"),
+    ("'a' << True", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'bool'"),
+    ("'a' << None", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'NoneType'"),
+    ("'a' << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'str'"),
+    ("'a' << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'bytes'"),
+    ("'a' << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'bytearray'"),
+    ("'a' << [1]", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'list'"),
+    ("'a' << (1,)", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'tuple'"),
+    ("'a' << {1: 1}", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'dict'"),
+    ("'a' << {1}", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'set'"),
+    ("'a' << frozenset({1})", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'frozenset'"),
+    ("'a' << range(1)", 'TypeError', "unsupported operand type(s) for <<: 'str' and 'range'"),
+    ("'a' >> 1", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'int'"),
+    ("'a' >> 1.5", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'float'"),
+    ("'a' >> 1j", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'complex'"),
+    ("'a' >> True", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'bool'"),
+    ("'a' >> None", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'NoneType'"),
+    ("'a' >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'str'"),
+    ("'a' >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'bytes'"),
+    ("'a' >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'bytearray'"),
+    ("'a' >> [1]", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'list'"),
+    ("'a' >> (1,)", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'tuple'"),
+    ("'a' >> {1: 1}", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'dict'"),
+    ("'a' >> {1}", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'set'"),
+    ("'a' >> frozenset({1})", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'frozenset'"),
+    ("'a' >> range(1)", 'TypeError', "unsupported operand type(s) for >>: 'str' and 'range'"),
+    ("'a' & 1", 'TypeError', "unsupported operand type(s) for &: 'str' and 'int'"),
+    ("'a' & 1.5", 'TypeError', "unsupported operand type(s) for &: 'str' and 'float'"),
+    ("'a' & 1j", 'TypeError', "unsupported operand type(s) for &: 'str' and 'complex'"),
+    ("'a' & True", 'TypeError', "unsupported operand type(s) for &: 'str' and 'bool'"),
+    ("'a' & None", 'TypeError', "unsupported operand type(s) for &: 'str' and 'NoneType'"),
+    ("'a' & 'a'", 'TypeError', "unsupported operand type(s) for &: 'str' and 'str'"),
+    ("'a' & b'a'", 'TypeError', "unsupported operand type(s) for &: 'str' and 'bytes'"),
+    ("'a' & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'str' and 'bytearray'"),
+    ("'a' & [1]", 'TypeError', "unsupported operand type(s) for &: 'str' and 'list'"),
+    ("'a' & (1,)", 'TypeError', "unsupported operand type(s) for &: 'str' and 'tuple'"),
+    ("'a' & {1: 1}", 'TypeError', "unsupported operand type(s) for &: 'str' and 'dict'"),
+    ("'a' & {1}", 'TypeError', "unsupported operand type(s) for &: 'str' and 'set'"),
+    ("'a' & frozenset({1})", 'TypeError', "unsupported operand type(s) for &: 'str' and 'frozenset'"),
+    ("'a' & range(1)", 'TypeError', "unsupported operand type(s) for &: 'str' and 'range'"),
+    ("'a' | 1", 'TypeError', "unsupported operand type(s) for |: 'str' and 'int'"),
+    ("'a' | 1.5", 'TypeError', "unsupported operand type(s) for |: 'str' and 'float'"),
+    ("'a' | 1j", 'TypeError', "unsupported operand type(s) for |: 'str' and 'complex'"),
+    ("'a' | True", 'TypeError', "unsupported operand type(s) for |: 'str' and 'bool'"),
+    ("'a' | None", 'TypeError', "unsupported operand type(s) for |: 'str' and 'NoneType'"),
+    ("'a' | 'a'", 'TypeError', "unsupported operand type(s) for |: 'str' and 'str'"),
+    ("'a' | b'a'", 'TypeError', "unsupported operand type(s) for |: 'str' and 'bytes'"),
+    ("'a' | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'str' and 'bytearray'"),
+    ("'a' | [1]", 'TypeError', "unsupported operand type(s) for |: 'str' and 'list'"),
+    ("'a' | (1,)", 'TypeError', "unsupported operand type(s) for |: 'str' and 'tuple'"),
+    ("'a' | {1: 1}", 'TypeError', "unsupported operand type(s) for |: 'str' and 'dict'"),
+    ("'a' | {1}", 'TypeError', "unsupported operand type(s) for |: 'str' and 'set'"),
+    ("'a' | frozenset({1})", 'TypeError', "unsupported operand type(s) for |: 'str' and 'frozenset'"),
+    ("'a' | range(1)", 'TypeError', "unsupported operand type(s) for |: 'str' and 'range'"),
+    ("'a' ^ 1", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'int'"),
+    ("'a' ^ 1.5", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'float'"),
+    ("'a' ^ 1j", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'complex'"),
+    ("'a' ^ True", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'bool'"),
+    ("'a' ^ None", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'NoneType'"),
+    ("'a' ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'str'"),
+    ("'a' ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'bytes'"),
+    ("'a' ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'bytearray'"),
+    ("'a' ^ [1]", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'list'"),
+    ("'a' ^ (1,)", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'tuple'"),
+    ("'a' ^ {1: 1}", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'dict'"),
+    ("'a' ^ {1}", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'set'"),
+    ("'a' ^ frozenset({1})", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'frozenset'"),
+    ("'a' ^ range(1)", 'TypeError', "unsupported operand type(s) for ^: 'str' and 'range'"),
+    ("'a' < 1", 'TypeError', "'<' not supported between instances of 'str' and 'int'"),
+    ("'a' < 1.5", 'TypeError', "'<' not supported between instances of 'str' and 'float'"),
+    ("'a' < 1j", 'TypeError', "'<' not supported between instances of 'str' and 'complex'"),
+    ("'a' < True", 'TypeError', "'<' not supported between instances of 'str' and 'bool'"),
+    ("'a' < None", 'TypeError', "'<' not supported between instances of 'str' and 'NoneType'"),
+    ("'a' < b'a'", 'TypeError', "'<' not supported between instances of 'str' and 'bytes'"),
+    ("'a' < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'str' and 'bytearray'"),
+    ("'a' < [1]", 'TypeError', "'<' not supported between instances of 'str' and 'list'"),
+    ("'a' < (1,)", 'TypeError', "'<' not supported between instances of 'str' and 'tuple'"),
+    ("'a' < {1: 1}", 'TypeError', "'<' not supported between instances of 'str' and 'dict'"),
+    ("'a' < {1}", 'TypeError', "'<' not supported between instances of 'str' and 'set'"),
+    ("'a' < frozenset({1})", 'TypeError', "'<' not supported between instances of 'str' and 'frozenset'"),
+    ("'a' < range(1)", 'TypeError', "'<' not supported between instances of 'str' and 'range'"),
+    ("'a' <= 1", 'TypeError', "'<=' not supported between instances of 'str' and 'int'"),
+    ("'a' <= 1.5", 'TypeError', "'<=' not supported between instances of 'str' and 'float'"),
+    ("'a' <= 1j", 'TypeError', "'<=' not supported between instances of 'str' and 'complex'"),
+    ("'a' <= True", 'TypeError', "'<=' not supported between instances of 'str' and 'bool'"),
+    ("'a' <= None", 'TypeError', "'<=' not supported between instances of 'str' and 'NoneType'"),
+    ("'a' <= b'a'", 'TypeError', "'<=' not supported between instances of 'str' and 'bytes'"),
+    ("'a' <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'str' and 'bytearray'"),
+    ("'a' <= [1]", 'TypeError', "'<=' not supported between instances of 'str' and 'list'"),
+    ("'a' <= (1,)", 'TypeError', "'<=' not supported between instances of 'str' and 'tuple'"),
+    ("'a' <= {1: 1}", 'TypeError', "'<=' not supported between instances of 'str' and 'dict'"),
+    ("'a' <= {1}", 'TypeError', "'<=' not supported between instances of 'str' and 'set'"),
+    ("'a' <= frozenset({1})", 'TypeError', "'<=' not supported between instances of 'str' and 'frozenset'"),
+    ("'a' <= range(1)", 'TypeError', "'<=' not supported between instances of 'str' and 'range'"),
+    ("'a' > 1", 'TypeError', "'>' not supported between instances of 'str' and 'int'"),
+    ("'a' > 1.5", 'TypeError', "'>' not supported between instances of 'str' and 'float'"),
+    ("'a' > 1j", 'TypeError', "'>' not supported between instances of 'str' and 'complex'"),
+    ("'a' > True", 'TypeError', "'>' not supported between instances of 'str' and 'bool'"),
+    ("'a' > None", 'TypeError', "'>' not supported between instances of 'str' and 'NoneType'"),
+    ("'a' > b'a'", 'TypeError', "'>' not supported between instances of 'str' and 'bytes'"),
+    ("'a' > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'str' and 'bytearray'"),
+    ("'a' > [1]", 'TypeError', "'>' not supported between instances of 'str' and 'list'"),
+    ("'a' > (1,)", 'TypeError', "'>' not supported between instances of 'str' and 'tuple'"),
+    ("'a' > {1: 1}", 'TypeError', "'>' not supported between instances of 'str' and 'dict'"),
+    ("'a' > {1}", 'TypeError', "'>' not supported between instances of 'str' and 'set'"),
+    ("'a' > frozenset({1})", 'TypeError', "'>' not supported between instances of 'str' and 'frozenset'"),
+    ("'a' > range(1)", 'TypeError', "'>' not supported between instances of 'str' and 'range'"),
+    ("'a' >= 1", 'TypeError', "'>=' not supported between instances of 'str' and 'int'"),
+    ("'a' >= 1.5", 'TypeError', "'>=' not supported between instances of 'str' and 'float'"),
+    ("'a' >= 1j", 'TypeError', "'>=' not supported between instances of 'str' and 'complex'"),
+    ("'a' >= True", 'TypeError', "'>=' not supported between instances of 'str' and 'bool'"),
+    ("'a' >= None", 'TypeError', "'>=' not supported between instances of 'str' and 'NoneType'"),
+    ("'a' >= b'a'", 'TypeError', "'>=' not supported between instances of 'str' and 'bytes'"),
+    ("'a' >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'str' and 'bytearray'"),
+    ("'a' >= [1]", 'TypeError', "'>=' not supported between instances of 'str' and 'list'"),
+    ("'a' >= (1,)", 'TypeError', "'>=' not supported between instances of 'str' and 'tuple'"),
+    ("'a' >= {1: 1}", 'TypeError', "'>=' not supported between instances of 'str' and 'dict'"),
+    ("'a' >= {1}", 'TypeError', "'>=' not supported between instances of 'str' and 'set'"),
+    ("'a' >= frozenset({1})", 'TypeError', "'>=' not supported between instances of 'str' and 'frozenset'"),
+    ("'a' >= range(1)", 'TypeError', "'>=' not supported between instances of 'str' and 'range'"),
+    ("b'a' + 1", 'TypeError', "can't concat int to bytes"),
+    ("b'a' + 1.5", 'TypeError', "can't concat float to bytes"),
+    ("b'a' + 1j", 'TypeError', "can't concat complex to bytes"),
+    ("b'a' + True", 'TypeError', "can't concat bool to bytes"),
+    ("b'a' + None", 'TypeError', "can't concat NoneType to bytes"),
+    ("b'a' + 'a'", 'TypeError', "can't concat str to bytes"),
+    ("b'a' + [1]", 'TypeError', "can't concat list to bytes"),
+    ("b'a' + (1,)", 'TypeError', "can't concat tuple to bytes"),
+    ("b'a' + {1: 1}", 'TypeError', "can't concat dict to bytes"),
+    ("b'a' + {1}", 'TypeError', "can't concat set to bytes"),
+    ("b'a' + frozenset({1})", 'TypeError', "can't concat frozenset to bytes"),
+    ("b'a' + range(1)", 'TypeError', "can't concat range to bytes"),
+    ("b'a' - 1", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'int'"),
+    ("b'a' - 1.5", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'float'"),
+    ("b'a' - 1j", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'complex'"),
+    ("b'a' - True", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'bool'"),
+    ("b'a' - None", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'NoneType'"),
+    ("b'a' - 'a'", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'str'"),
+    ("b'a' - b'a'", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'bytes'"),
+    ("b'a' - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'bytearray'"),
+    ("b'a' - [1]", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'list'"),
+    ("b'a' - (1,)", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'tuple'"),
+    ("b'a' - {1: 1}", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'dict'"),
+    ("b'a' - {1}", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'set'"),
+    ("b'a' - frozenset({1})", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'frozenset'"),
+    ("b'a' - range(1)", 'TypeError', "unsupported operand type(s) for -: 'bytes' and 'range'"),
+    ("b'a' * 1.5", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ("b'a' * 1j", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ("b'a' * None", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("b'a' * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'str'"),
+    ("b'a' * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'bytes'"),
+    ("b'a' * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'bytearray'"),
+    ("b'a' * [1]", 'TypeError', "can't multiply sequence by non-int of type 'list'"),
+    ("b'a' * (1,)", 'TypeError', "can't multiply sequence by non-int of type 'tuple'"),
+    ("b'a' * {1: 1}", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ("b'a' * {1}", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ("b'a' * frozenset({1})", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ("b'a' * range(1)", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ("b'a' / 1", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'int'"),
+    ("b'a' / 1.5", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'float'"),
+    ("b'a' / 1j", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'complex'"),
+    ("b'a' / True", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'bool'"),
+    ("b'a' / None", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'NoneType'"),
+    ("b'a' / 'a'", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'str'"),
+    ("b'a' / b'a'", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'bytes'"),
+    ("b'a' / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'bytearray'"),
+    ("b'a' / [1]", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'list'"),
+    ("b'a' / (1,)", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'tuple'"),
+    ("b'a' / {1: 1}", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'dict'"),
+    ("b'a' / {1}", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'set'"),
+    ("b'a' / frozenset({1})", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'frozenset'"),
+    ("b'a' / range(1)", 'TypeError', "unsupported operand type(s) for /: 'bytes' and 'range'"),
+    ("b'a' // 1", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'int'"),
+    ("b'a' // 1.5", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'float'"),
+    ("b'a' // 1j", 'TypeError', "can't take floor of complex number."),
+    ("b'a' // True", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'bool'"),
+    ("b'a' // None", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'NoneType'"),
+    ("b'a' // 'a'", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'str'"),
+    ("b'a' // b'a'", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'bytes'"),
+    ("b'a' // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'bytearray'"),
+    ("b'a' // [1]", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'list'"),
+    ("b'a' // (1,)", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'tuple'"),
+    ("b'a' // {1: 1}", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'dict'"),
+    ("b'a' // {1}", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'set'"),
+    ("b'a' // frozenset({1})", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'frozenset'"),
+    ("b'a' // range(1)", 'TypeError', "unsupported operand type(s) for //: 'bytes' and 'range'"),
+    ("b'a' % 1", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % 1.5", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % 1j", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % True", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % None", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % 'a'", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % b'a'", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % bytearray(b'a')", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % (1,)", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % {1}", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' % frozenset({1})", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("b'a' ** 1", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'int'"),
+    ("b'a' ** 1.5", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'float'"),
+    ("b'a' ** 1j", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'complex'"),
+    ("b'a' ** True", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'bool'"),
+    ("b'a' ** None", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'NoneType'"),
+    ("b'a' ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'str'"),
+    ("b'a' ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'bytes'"),
+    ("b'a' ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'bytearray'"),
+    ("b'a' ** [1]", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'list'"),
+    ("b'a' ** (1,)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'tuple'"),
+    ("b'a' ** {1: 1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'dict'"),
+    ("b'a' ** {1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'set'"),
+    ("b'a' ** frozenset({1})", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'frozenset'"),
+    ("b'a' ** range(1)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytes' and 'range'"),
+    ("b'a' @ 1", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'int'"),
+    ("b'a' @ 1.5", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'float'"),
+    ("b'a' @ 1j", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'complex'"),
+    ("b'a' @ True", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'bool'"),
+    ("b'a' @ None", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'NoneType'"),
+    ("b'a' @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'str'"),
+    ("b'a' @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'bytes'"),
+    ("b'a' @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'bytearray'"),
+    ("b'a' @ [1]", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'list'"),
+    ("b'a' @ (1,)", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'tuple'"),
+    ("b'a' @ {1: 1}", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'dict'"),
+    ("b'a' @ {1}", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'set'"),
+    ("b'a' @ frozenset({1})", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'frozenset'"),
+    ("b'a' @ range(1)", 'TypeError', "unsupported operand type(s) for @: 'bytes' and 'range'"),
+    ("b'a' << 1", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'int'"),
+    ("b'a' << 1.5", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'float'"),
+    ("b'a' << 1j", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'complex'"),
+    ("b'a' << True", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'bool'"),
+    ("b'a' << None", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'NoneType'"),
+    ("b'a' << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'str'"),
+    ("b'a' << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'bytes'"),
+    ("b'a' << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'bytearray'"),
+    ("b'a' << [1]", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'list'"),
+    ("b'a' << (1,)", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'tuple'"),
+    ("b'a' << {1: 1}", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'dict'"),
+    ("b'a' << {1}", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'set'"),
+    ("b'a' << frozenset({1})", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'frozenset'"),
+    ("b'a' << range(1)", 'TypeError', "unsupported operand type(s) for <<: 'bytes' and 'range'"),
+    ("b'a' >> 1", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'int'"),
+    ("b'a' >> 1.5", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'float'"),
+    ("b'a' >> 1j", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'complex'"),
+    ("b'a' >> True", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'bool'"),
+    ("b'a' >> None", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'NoneType'"),
+    ("b'a' >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'str'"),
+    ("b'a' >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'bytes'"),
+    ("b'a' >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'bytearray'"),
+    ("b'a' >> [1]", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'list'"),
+    ("b'a' >> (1,)", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'tuple'"),
+    ("b'a' >> {1: 1}", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'dict'"),
+    ("b'a' >> {1}", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'set'"),
+    ("b'a' >> frozenset({1})", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'frozenset'"),
+    ("b'a' >> range(1)", 'TypeError', "unsupported operand type(s) for >>: 'bytes' and 'range'"),
+    ("b'a' & 1", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'int'"),
+    ("b'a' & 1.5", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'float'"),
+    ("b'a' & 1j", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'complex'"),
+    ("b'a' & True", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'bool'"),
+    ("b'a' & None", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'NoneType'"),
+    ("b'a' & 'a'", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'str'"),
+    ("b'a' & b'a'", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'bytes'"),
+    ("b'a' & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'bytearray'"),
+    ("b'a' & [1]", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'list'"),
+    ("b'a' & (1,)", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'tuple'"),
+    ("b'a' & {1: 1}", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'dict'"),
+    ("b'a' & {1}", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'set'"),
+    ("b'a' & frozenset({1})", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'frozenset'"),
+    ("b'a' & range(1)", 'TypeError', "unsupported operand type(s) for &: 'bytes' and 'range'"),
+    ("b'a' | 1", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'int'"),
+    ("b'a' | 1.5", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'float'"),
+    ("b'a' | 1j", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'complex'"),
+    ("b'a' | True", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'bool'"),
+    ("b'a' | None", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'NoneType'"),
+    ("b'a' | 'a'", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'str'"),
+    ("b'a' | b'a'", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'bytes'"),
+    ("b'a' | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'bytearray'"),
+    ("b'a' | [1]", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'list'"),
+    ("b'a' | (1,)", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'tuple'"),
+    ("b'a' | {1: 1}", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'dict'"),
+    ("b'a' | {1}", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'set'"),
+    ("b'a' | frozenset({1})", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'frozenset'"),
+    ("b'a' | range(1)", 'TypeError', "unsupported operand type(s) for |: 'bytes' and 'range'"),
+    ("b'a' ^ 1", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'int'"),
+    ("b'a' ^ 1.5", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'float'"),
+    ("b'a' ^ 1j", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'complex'"),
+    ("b'a' ^ True", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'bool'"),
+    ("b'a' ^ None", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'NoneType'"),
+    ("b'a' ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'str'"),
+    ("b'a' ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'bytes'"),
+    ("b'a' ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'bytearray'"),
+    ("b'a' ^ [1]", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'list'"),
+    ("b'a' ^ (1,)", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'tuple'"),
+    ("b'a' ^ {1: 1}", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'dict'"),
+    ("b'a' ^ {1}", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'set'"),
+    ("b'a' ^ frozenset({1})", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'frozenset'"),
+    ("b'a' ^ range(1)", 'TypeError', "unsupported operand type(s) for ^: 'bytes' and 'range'"),
+    ("b'a' < 1", 'TypeError', "'<' not supported between instances of 'bytes' and 'int'"),
+    ("b'a' < 1.5", 'TypeError', "'<' not supported between instances of 'bytes' and 'float'"),
+    ("b'a' < 1j", 'TypeError', "'<' not supported between instances of 'bytes' and 'complex'"),
+    ("b'a' < True", 'TypeError', "'<' not supported between instances of 'bytes' and 'bool'"),
+    ("b'a' < None", 'TypeError', "'<' not supported between instances of 'bytes' and 'NoneType'"),
+    ("b'a' < 'a'", 'TypeError', "'<' not supported between instances of 'bytes' and 'str'"),
+    ("b'a' < [1]", 'TypeError', "'<' not supported between instances of 'bytes' and 'list'"),
+    ("b'a' < (1,)", 'TypeError', "'<' not supported between instances of 'bytes' and 'tuple'"),
+    ("b'a' < {1: 1}", 'TypeError', "'<' not supported between instances of 'bytes' and 'dict'"),
+    ("b'a' < {1}", 'TypeError', "'<' not supported between instances of 'bytes' and 'set'"),
+    ("b'a' < frozenset({1})", 'TypeError', "'<' not supported between instances of 'bytes' and 'frozenset'"),
+    ("b'a' < range(1)", 'TypeError', "'<' not supported between instances of 'bytes' and 'range'"),
+    ("b'a' <= 1", 'TypeError', "'<=' not supported between instances of 'bytes' and 'int'"),
+    ("b'a' <= 1.5", 'TypeError', "'<=' not supported between instances of 'bytes' and 'float'"),
+    ("b'a' <= 1j", 'TypeError', "'<=' not supported between instances of 'bytes' and 'complex'"),
+    ("b'a' <= True", 'TypeError', "'<=' not supported between instances of 'bytes' and 'bool'"),
+    ("b'a' <= None", 'TypeError', "'<=' not supported between instances of 'bytes' and 'NoneType'"),
+    ("b'a' <= 'a'", 'TypeError', "'<=' not supported between instances of 'bytes' and 'str'"),
+    ("b'a' <= [1]", 'TypeError', "'<=' not supported between instances of 'bytes' and 'list'"),
+    ("b'a' <= (1,)", 'TypeError', "'<=' not supported between instances of 'bytes' and 'tuple'"),
+    ("b'a' <= {1: 1}", 'TypeError', "'<=' not supported between instances of 'bytes' and 'dict'"),
+    ("b'a' <= {1}", 'TypeError', "'<=' not supported between instances of 'bytes' and 'set'"),
+    ("b'a' <= frozenset({1})", 'TypeError', "'<=' not supported between instances of 'bytes' and 'frozenset'"),
+    ("b'a' <= range(1)", 'TypeError', "'<=' not supported between instances of 'bytes' and 'range'"),
+    ("b'a' > 1", 'TypeError', "'>' not supported between instances of 'bytes' and 'int'"),
+    ("b'a' > 1.5", 'TypeError', "'>' not supported between instances of 'bytes' and 'float'"),
+    ("b'a' > 1j", 'TypeError', "'>' not supported between instances of 'bytes' and 'complex'"),
+    ("b'a' > True", 'TypeError', "'>' not supported between instances of 'bytes' and 'bool'"),
+    ("b'a' > None", 'TypeError', "'>' not supported between instances of 'bytes' and 'NoneType'"),
+    ("b'a' > 'a'", 'TypeError', "'>' not supported between instances of 'bytes' and 'str'"),
+    ("b'a' > [1]", 'TypeError', "'>' not supported between instances of 'bytes' and 'list'"),
+    ("b'a' > (1,)", 'TypeError', "'>' not supported between instances of 'bytes' and 'tuple'"),
+    ("b'a' > {1: 1}", 'TypeError', "'>' not supported between instances of 'bytes' and 'dict'"),
+    ("b'a' > {1}", 'TypeError', "'>' not supported between instances of 'bytes' and 'set'"),
+    ("b'a' > frozenset({1})", 'TypeError', "'>' not supported between instances of 'bytes' and 'frozenset'"),
+    ("b'a' > range(1)", 'TypeError', "'>' not supported between instances of 'bytes' and 'range'"),
+    ("b'a' >= 1", 'TypeError', "'>=' not supported between instances of 'bytes' and 'int'"),
+    ("b'a' >= 1.5", 'TypeError', "'>=' not supported between instances of 'bytes' and 'float'"),
+    ("b'a' >= 1j", 'TypeError', "'>=' not supported between instances of 'bytes' and 'complex'"),
+    ("b'a' >= True", 'TypeError', "'>=' not supported between instances of 'bytes' and 'bool'"),
+    ("b'a' >= None", 'TypeError', "'>=' not supported between instances of 'bytes' and 'NoneType'"),
+    ("b'a' >= 'a'", 'TypeError', "'>=' not supported between instances of 'bytes' and 'str'"),
+    ("b'a' >= [1]", 'TypeError', "'>=' not supported between instances of 'bytes' and 'list'"),
+    ("b'a' >= (1,)", 'TypeError', "'>=' not supported between instances of 'bytes' and 'tuple'"),
+    ("b'a' >= {1: 1}", 'TypeError', "'>=' not supported between instances of 'bytes' and 'dict'"),
+    ("b'a' >= {1}", 'TypeError', "'>=' not supported between instances of 'bytes' and 'set'"),
+    ("b'a' >= frozenset({1})", 'TypeError', "'>=' not supported between instances of 'bytes' and 'frozenset'"),
+    ("b'a' >= range(1)", 'TypeError', "'>=' not supported between instances of 'bytes' and 'range'"),
+    ("bytearray(b'a') + 1", 'TypeError', "can't concat int to bytearray"),
+    ("bytearray(b'a') + 1.5", 'TypeError', "can't concat float to bytearray"),
+    ("bytearray(b'a') + 1j", 'TypeError', "can't concat complex to bytearray"),
+    ("bytearray(b'a') + True", 'TypeError', "can't concat bool to bytearray"),
+    ("bytearray(b'a') + None", 'TypeError', "can't concat NoneType to bytearray"),
+    ("bytearray(b'a') + 'a'", 'TypeError', "can't concat str to bytearray"),
+    ("bytearray(b'a') + [1]", 'TypeError', "can't concat list to bytearray"),
+    ("bytearray(b'a') + (1,)", 'TypeError', "can't concat tuple to bytearray"),
+    ("bytearray(b'a') + {1: 1}", 'TypeError', "can't concat dict to bytearray"),
+    ("bytearray(b'a') + {1}", 'TypeError', "can't concat set to bytearray"),
+    ("bytearray(b'a') + frozenset({1})", 'TypeError', "can't concat frozenset to bytearray"),
+    ("bytearray(b'a') + range(1)", 'TypeError', "can't concat range to bytearray"),
+    ("bytearray(b'a') - 1", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'int'"),
+    ("bytearray(b'a') - 1.5", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'float'"),
+    ("bytearray(b'a') - 1j", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') - True", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') - None", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') - 'a'", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'str'"),
+    ("bytearray(b'a') - b'a'", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') - [1]", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'list'"),
+    ("bytearray(b'a') - (1,)", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') - {1: 1}", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') - {1}", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'set'"),
+    ("bytearray(b'a') - frozenset({1})", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') - range(1)", 'TypeError', "unsupported operand type(s) for -: 'bytearray' and 'range'"),
+    ("bytearray(b'a') * 1.5", 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ("bytearray(b'a') * 1j", 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ("bytearray(b'a') * None", 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("bytearray(b'a') * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'str'"),
+    ("bytearray(b'a') * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'bytes'"),
+    ("bytearray(b'a') * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'bytearray'"),
+    ("bytearray(b'a') * [1]", 'TypeError', "can't multiply sequence by non-int of type 'list'"),
+    ("bytearray(b'a') * (1,)", 'TypeError', "can't multiply sequence by non-int of type 'tuple'"),
+    ("bytearray(b'a') * {1: 1}", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ("bytearray(b'a') * {1}", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ("bytearray(b'a') * frozenset({1})", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ("bytearray(b'a') * range(1)", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ("bytearray(b'a') / 1", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'int'"),
+    ("bytearray(b'a') / 1.5", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'float'"),
+    ("bytearray(b'a') / 1j", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') / True", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') / None", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') / 'a'", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'str'"),
+    ("bytearray(b'a') / b'a'", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') / [1]", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'list'"),
+    ("bytearray(b'a') / (1,)", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') / {1: 1}", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') / {1}", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'set'"),
+    ("bytearray(b'a') / frozenset({1})", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') / range(1)", 'TypeError', "unsupported operand type(s) for /: 'bytearray' and 'range'"),
+    ("bytearray(b'a') // 1", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'int'"),
+    ("bytearray(b'a') // 1.5", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'float'"),
+    ("bytearray(b'a') // 1j", 'TypeError', "can't take floor of complex number."),
+    ("bytearray(b'a') // True", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') // None", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') // 'a'", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'str'"),
+    ("bytearray(b'a') // b'a'", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') // [1]", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'list'"),
+    ("bytearray(b'a') // (1,)", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') // {1: 1}", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') // {1}", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'set'"),
+    ("bytearray(b'a') // frozenset({1})", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') // range(1)", 'TypeError', "unsupported operand type(s) for //: 'bytearray' and 'range'"),
+    ("bytearray(b'a') % 1", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % 1.5", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % 1j", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % True", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % None", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % 'a'", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % b'a'", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % bytearray(b'a')", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % (1,)", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % {1}", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') % frozenset({1})", 'TypeError', 'not all arguments converted during bytes formatting'),
+    ("bytearray(b'a') ** 1", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'int'"),
+    ("bytearray(b'a') ** 1.5", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'float'"),
+    ("bytearray(b'a') ** 1j", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'complex'"),
+    ("bytearray(b'a') ** True", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'bool'"),
+    ("bytearray(b'a') ** None", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'str'"),
+    ("bytearray(b'a') ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') ** [1]", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'list'"),
+    ("bytearray(b'a') ** (1,)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') ** {1: 1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'dict'"),
+    ("bytearray(b'a') ** {1}", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'set'"),
+    ("bytearray(b'a') ** frozenset({1})", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') ** range(1)", 'TypeError', "unsupported operand type(s) for ** or pow(): 'bytearray' and 'range'"),
+    ("bytearray(b'a') @ 1", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'int'"),
+    ("bytearray(b'a') @ 1.5", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'float'"),
+    ("bytearray(b'a') @ 1j", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') @ True", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') @ None", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'str'"),
+    ("bytearray(b'a') @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') @ [1]", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'list'"),
+    ("bytearray(b'a') @ (1,)", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') @ {1: 1}", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') @ {1}", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'set'"),
+    ("bytearray(b'a') @ frozenset({1})", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') @ range(1)", 'TypeError', "unsupported operand type(s) for @: 'bytearray' and 'range'"),
+    ("bytearray(b'a') << 1", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'int'"),
+    ("bytearray(b'a') << 1.5", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'float'"),
+    ("bytearray(b'a') << 1j", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') << True", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') << None", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'str'"),
+    ("bytearray(b'a') << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') << [1]", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'list'"),
+    ("bytearray(b'a') << (1,)", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') << {1: 1}", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') << {1}", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'set'"),
+    ("bytearray(b'a') << frozenset({1})", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') << range(1)", 'TypeError', "unsupported operand type(s) for <<: 'bytearray' and 'range'"),
+    ("bytearray(b'a') >> 1", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'int'"),
+    ("bytearray(b'a') >> 1.5", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'float'"),
+    ("bytearray(b'a') >> 1j", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') >> True", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') >> None", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'str'"),
+    ("bytearray(b'a') >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') >> [1]", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'list'"),
+    ("bytearray(b'a') >> (1,)", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') >> {1: 1}", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') >> {1}", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'set'"),
+    ("bytearray(b'a') >> frozenset({1})", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') >> range(1)", 'TypeError', "unsupported operand type(s) for >>: 'bytearray' and 'range'"),
+    ("bytearray(b'a') & 1", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'int'"),
+    ("bytearray(b'a') & 1.5", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'float'"),
+    ("bytearray(b'a') & 1j", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') & True", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') & None", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') & 'a'", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'str'"),
+    ("bytearray(b'a') & b'a'", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') & [1]", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'list'"),
+    ("bytearray(b'a') & (1,)", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') & {1: 1}", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') & {1}", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'set'"),
+    ("bytearray(b'a') & frozenset({1})", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') & range(1)", 'TypeError', "unsupported operand type(s) for &: 'bytearray' and 'range'"),
+    ("bytearray(b'a') | 1", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'int'"),
+    ("bytearray(b'a') | 1.5", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'float'"),
+    ("bytearray(b'a') | 1j", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') | True", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') | None", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') | 'a'", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'str'"),
+    ("bytearray(b'a') | b'a'", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') | [1]", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'list'"),
+    ("bytearray(b'a') | (1,)", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') | {1: 1}", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') | {1}", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'set'"),
+    ("bytearray(b'a') | frozenset({1})", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') | range(1)", 'TypeError', "unsupported operand type(s) for |: 'bytearray' and 'range'"),
+    ("bytearray(b'a') ^ 1", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'int'"),
+    ("bytearray(b'a') ^ 1.5", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'float'"),
+    ("bytearray(b'a') ^ 1j", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'complex'"),
+    ("bytearray(b'a') ^ True", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'bool'"),
+    ("bytearray(b'a') ^ None", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'str'"),
+    ("bytearray(b'a') ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'bytes'"),
+    ("bytearray(b'a') ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'bytearray'"),
+    ("bytearray(b'a') ^ [1]", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'list'"),
+    ("bytearray(b'a') ^ (1,)", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') ^ {1: 1}", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'dict'"),
+    ("bytearray(b'a') ^ {1}", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'set'"),
+    ("bytearray(b'a') ^ frozenset({1})", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') ^ range(1)", 'TypeError', "unsupported operand type(s) for ^: 'bytearray' and 'range'"),
+    ("bytearray(b'a') < 1", 'TypeError', "'<' not supported between instances of 'bytearray' and 'int'"),
+    ("bytearray(b'a') < 1.5", 'TypeError', "'<' not supported between instances of 'bytearray' and 'float'"),
+    ("bytearray(b'a') < 1j", 'TypeError', "'<' not supported between instances of 'bytearray' and 'complex'"),
+    ("bytearray(b'a') < True", 'TypeError', "'<' not supported between instances of 'bytearray' and 'bool'"),
+    ("bytearray(b'a') < None", 'TypeError', "'<' not supported between instances of 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') < 'a'", 'TypeError', "'<' not supported between instances of 'bytearray' and 'str'"),
+    ("bytearray(b'a') < [1]", 'TypeError', "'<' not supported between instances of 'bytearray' and 'list'"),
+    ("bytearray(b'a') < (1,)", 'TypeError', "'<' not supported between instances of 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') < {1: 1}", 'TypeError', "'<' not supported between instances of 'bytearray' and 'dict'"),
+    ("bytearray(b'a') < {1}", 'TypeError', "'<' not supported between instances of 'bytearray' and 'set'"),
+    ("bytearray(b'a') < frozenset({1})", 'TypeError', "'<' not supported between instances of 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') < range(1)", 'TypeError', "'<' not supported between instances of 'bytearray' and 'range'"),
+    ("bytearray(b'a') <= 1", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'int'"),
+    ("bytearray(b'a') <= 1.5", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'float'"),
+    ("bytearray(b'a') <= 1j", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'complex'"),
+    ("bytearray(b'a') <= True", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'bool'"),
+    ("bytearray(b'a') <= None", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') <= 'a'", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'str'"),
+    ("bytearray(b'a') <= [1]", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'list'"),
+    ("bytearray(b'a') <= (1,)", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') <= {1: 1}", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'dict'"),
+    ("bytearray(b'a') <= {1}", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'set'"),
+    ("bytearray(b'a') <= frozenset({1})", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') <= range(1)", 'TypeError', "'<=' not supported between instances of 'bytearray' and 'range'"),
+    ("bytearray(b'a') > 1", 'TypeError', "'>' not supported between instances of 'bytearray' and 'int'"),
+    ("bytearray(b'a') > 1.5", 'TypeError', "'>' not supported between instances of 'bytearray' and 'float'"),
+    ("bytearray(b'a') > 1j", 'TypeError', "'>' not supported between instances of 'bytearray' and 'complex'"),
+    ("bytearray(b'a') > True", 'TypeError', "'>' not supported between instances of 'bytearray' and 'bool'"),
+    ("bytearray(b'a') > None", 'TypeError', "'>' not supported between instances of 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') > 'a'", 'TypeError', "'>' not supported between instances of 'bytearray' and 'str'"),
+    ("bytearray(b'a') > [1]", 'TypeError', "'>' not supported between instances of 'bytearray' and 'list'"),
+    ("bytearray(b'a') > (1,)", 'TypeError', "'>' not supported between instances of 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') > {1: 1}", 'TypeError', "'>' not supported between instances of 'bytearray' and 'dict'"),
+    ("bytearray(b'a') > {1}", 'TypeError', "'>' not supported between instances of 'bytearray' and 'set'"),
+    ("bytearray(b'a') > frozenset({1})", 'TypeError', "'>' not supported between instances of 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') > range(1)", 'TypeError', "'>' not supported between instances of 'bytearray' and 'range'"),
+    ("bytearray(b'a') >= 1", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'int'"),
+    ("bytearray(b'a') >= 1.5", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'float'"),
+    ("bytearray(b'a') >= 1j", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'complex'"),
+    ("bytearray(b'a') >= True", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'bool'"),
+    ("bytearray(b'a') >= None", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'NoneType'"),
+    ("bytearray(b'a') >= 'a'", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'str'"),
+    ("bytearray(b'a') >= [1]", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'list'"),
+    ("bytearray(b'a') >= (1,)", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'tuple'"),
+    ("bytearray(b'a') >= {1: 1}", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'dict'"),
+    ("bytearray(b'a') >= {1}", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'set'"),
+    ("bytearray(b'a') >= frozenset({1})", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'frozenset'"),
+    ("bytearray(b'a') >= range(1)", 'TypeError', "'>=' not supported between instances of 'bytearray' and 'range'"),
+    ('[1] + 1', 'TypeError', 'can only concatenate list (not "int") to list'),
+    ('[1] + 1.5', 'TypeError', 'can only concatenate list (not "float") to list'),
+    ('[1] + 1j', 'TypeError', 'can only concatenate list (not "complex") to list'),
+    ('[1] + True', 'TypeError', 'can only concatenate list (not "bool") to list'),
+    ('[1] + None', 'TypeError', 'can only concatenate list (not "NoneType") to list'),
+    ("[1] + 'a'", 'TypeError', 'can only concatenate list (not "str") to list'),
+    ("[1] + b'a'", 'TypeError', 'can only concatenate list (not "bytes") to list'),
+    ("[1] + bytearray(b'a')", 'TypeError', 'can only concatenate list (not "bytearray") to list'),
+    ('[1] + (1,)', 'TypeError', 'can only concatenate list (not "tuple") to list'),
+    ('[1] + {1: 1}', 'TypeError', 'can only concatenate list (not "dict") to list'),
+    ('[1] + {1}', 'TypeError', 'can only concatenate list (not "set") to list'),
+    ('[1] + frozenset({1})', 'TypeError', 'can only concatenate list (not "frozenset") to list'),
+    ('[1] + range(1)', 'TypeError', 'can only concatenate list (not "range") to list'),
+    ('[1] - 1', 'TypeError', "unsupported operand type(s) for -: 'list' and 'int'"),
+    ('[1] - 1.5', 'TypeError', "unsupported operand type(s) for -: 'list' and 'float'"),
+    ('[1] - 1j', 'TypeError', "unsupported operand type(s) for -: 'list' and 'complex'"),
+    ('[1] - True', 'TypeError', "unsupported operand type(s) for -: 'list' and 'bool'"),
+    ('[1] - None', 'TypeError', "unsupported operand type(s) for -: 'list' and 'NoneType'"),
+    ("[1] - 'a'", 'TypeError', "unsupported operand type(s) for -: 'list' and 'str'"),
+    ("[1] - b'a'", 'TypeError', "unsupported operand type(s) for -: 'list' and 'bytes'"),
+    ("[1] - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'list' and 'bytearray'"),
+    ('[1] - [1]', 'TypeError', "unsupported operand type(s) for -: 'list' and 'list'"),
+    ('[1] - (1,)', 'TypeError', "unsupported operand type(s) for -: 'list' and 'tuple'"),
+    ('[1] - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'list' and 'dict'"),
+    ('[1] - {1}', 'TypeError', "unsupported operand type(s) for -: 'list' and 'set'"),
+    ('[1] - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'list' and 'frozenset'"),
+    ('[1] - range(1)', 'TypeError', "unsupported operand type(s) for -: 'list' and 'range'"),
+    ('[1] * 1.5', 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ('[1] * 1j', 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ('[1] * None', 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("[1] * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'str'"),
+    ("[1] * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'bytes'"),
+    ("[1] * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'bytearray'"),
+    ('[1] * [1]', 'TypeError', "can't multiply sequence by non-int of type 'list'"),
+    ('[1] * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'tuple'"),
+    ('[1] * {1: 1}', 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ('[1] * {1}', 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ('[1] * frozenset({1})', 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ('[1] * range(1)', 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ('[1] / 1', 'TypeError', "unsupported operand type(s) for /: 'list' and 'int'"),
+    ('[1] / 1.5', 'TypeError', "unsupported operand type(s) for /: 'list' and 'float'"),
+    ('[1] / 1j', 'TypeError', "unsupported operand type(s) for /: 'list' and 'complex'"),
+    ('[1] / True', 'TypeError', "unsupported operand type(s) for /: 'list' and 'bool'"),
+    ('[1] / None', 'TypeError', "unsupported operand type(s) for /: 'list' and 'NoneType'"),
+    ("[1] / 'a'", 'TypeError', "unsupported operand type(s) for /: 'list' and 'str'"),
+    ("[1] / b'a'", 'TypeError', "unsupported operand type(s) for /: 'list' and 'bytes'"),
+    ("[1] / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'list' and 'bytearray'"),
+    ('[1] / [1]', 'TypeError', "unsupported operand type(s) for /: 'list' and 'list'"),
+    ('[1] / (1,)', 'TypeError', "unsupported operand type(s) for /: 'list' and 'tuple'"),
+    ('[1] / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'list' and 'dict'"),
+    ('[1] / {1}', 'TypeError', "unsupported operand type(s) for /: 'list' and 'set'"),
+    ('[1] / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'list' and 'frozenset'"),
+    ('[1] / range(1)', 'TypeError', "unsupported operand type(s) for /: 'list' and 'range'"),
+    ('[1] // 1', 'TypeError', "unsupported operand type(s) for //: 'list' and 'int'"),
+    ('[1] // 1.5', 'TypeError', "unsupported operand type(s) for //: 'list' and 'float'"),
+    ('[1] // 1j', 'TypeError', "can't take floor of complex number."),
+    ('[1] // True', 'TypeError', "unsupported operand type(s) for //: 'list' and 'bool'"),
+    ('[1] // None', 'TypeError', "unsupported operand type(s) for //: 'list' and 'NoneType'"),
+    ("[1] // 'a'", 'TypeError', "unsupported operand type(s) for //: 'list' and 'str'"),
+    ("[1] // b'a'", 'TypeError', "unsupported operand type(s) for //: 'list' and 'bytes'"),
+    ("[1] // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'list' and 'bytearray'"),
+    ('[1] // [1]', 'TypeError', "unsupported operand type(s) for //: 'list' and 'list'"),
+    ('[1] // (1,)', 'TypeError', "unsupported operand type(s) for //: 'list' and 'tuple'"),
+    ('[1] // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'list' and 'dict'"),
+    ('[1] // {1}', 'TypeError', "unsupported operand type(s) for //: 'list' and 'set'"),
+    ('[1] // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'list' and 'frozenset'"),
+    ('[1] // range(1)', 'TypeError', "unsupported operand type(s) for //: 'list' and 'range'"),
+    ('[1] % 1', 'TypeError', "unsupported operand type(s) for %: 'list' and 'int'"),
+    ('[1] % 1.5', 'TypeError', "unsupported operand type(s) for %: 'list' and 'float'"),
+    ('[1] % 1j', 'TypeError', "can't mod complex numbers."),
+    ('[1] % True', 'TypeError', "unsupported operand type(s) for %: 'list' and 'bool'"),
+    ('[1] % None', 'TypeError', "unsupported operand type(s) for %: 'list' and 'NoneType'"),
+    ("[1] % 'a'", 'TypeError', "unsupported operand type(s) for %: 'list' and 'str'"),
+    ("[1] % b'a'", 'TypeError', "unsupported operand type(s) for %: 'list' and 'bytes'"),
+    ("[1] % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'list' and 'bytearray'"),
+    ('[1] % [1]', 'TypeError', "unsupported operand type(s) for %: 'list' and 'list'"),
+    ('[1] % (1,)', 'TypeError', "unsupported operand type(s) for %: 'list' and 'tuple'"),
+    ('[1] % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'list' and 'dict'"),
+    ('[1] % {1}', 'TypeError', "unsupported operand type(s) for %: 'list' and 'set'"),
+    ('[1] % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'list' and 'frozenset'"),
+    ('[1] % range(1)', 'TypeError', "unsupported operand type(s) for %: 'list' and 'range'"),
+    ('[1] ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'int'"),
+    ('[1] ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'float'"),
+    ('[1] ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'complex'"),
+    ('[1] ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'bool'"),
+    ('[1] ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'NoneType'"),
+    ("[1] ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'str'"),
+    ("[1] ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'bytes'"),
+    ("[1] ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'bytearray'"),
+    ('[1] ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'list'"),
+    ('[1] ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'tuple'"),
+    ('[1] ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'dict'"),
+    ('[1] ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'set'"),
+    ('[1] ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'frozenset'"),
+    ('[1] ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'list' and 'range'"),
+    ('[1] @ 1', 'TypeError', "unsupported operand type(s) for @: 'list' and 'int'"),
+    ('[1] @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'list' and 'float'"),
+    ('[1] @ 1j', 'TypeError', "unsupported operand type(s) for @: 'list' and 'complex'"),
+    ('[1] @ True', 'TypeError', "unsupported operand type(s) for @: 'list' and 'bool'"),
+    ('[1] @ None', 'TypeError', "unsupported operand type(s) for @: 'list' and 'NoneType'"),
+    ("[1] @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'list' and 'str'"),
+    ("[1] @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'list' and 'bytes'"),
+    ("[1] @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'list' and 'bytearray'"),
+    ('[1] @ [1]', 'TypeError', "unsupported operand type(s) for @: 'list' and 'list'"),
+    ('[1] @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'list' and 'tuple'"),
+    ('[1] @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'list' and 'dict'"),
+    ('[1] @ {1}', 'TypeError', "unsupported operand type(s) for @: 'list' and 'set'"),
+    ('[1] @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'list' and 'frozenset'"),
+    ('[1] @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'list' and 'range'"),
+    ('[1] << 1', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'int'"),
+    ('[1] << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'float'"),
+    ('[1] << 1j', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'complex'"),
+    ('[1] << True', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'bool'"),
+    ('[1] << None', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'NoneType'"),
+    ("[1] << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'list' and 'str'"),
+    ("[1] << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'list' and 'bytes'"),
+    ("[1] << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'list' and 'bytearray'"),
+    ('[1] << [1]', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'list'"),
+    ('[1] << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'tuple'"),
+    ('[1] << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'dict'"),
+    ('[1] << {1}', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'set'"),
+    ('[1] << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'frozenset'"),
+    ('[1] << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'list' and 'range'"),
+    ('[1] >> 1', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'int'"),
+    ('[1] >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'float'"),
+    ('[1] >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'complex'"),
+    ('[1] >> True', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'bool'"),
+    ('[1] >> None', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'NoneType'"),
+    ("[1] >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'list' and 'str'"),
+    ("[1] >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'list' and 'bytes'"),
+    ("[1] >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'list' and 'bytearray'"),
+    ('[1] >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'list'"),
+    ('[1] >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'tuple'"),
+    ('[1] >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'dict'"),
+    ('[1] >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'set'"),
+    ('[1] >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'frozenset'"),
+    ('[1] >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'list' and 'range'"),
+    ('[1] & 1', 'TypeError', "unsupported operand type(s) for &: 'list' and 'int'"),
+    ('[1] & 1.5', 'TypeError', "unsupported operand type(s) for &: 'list' and 'float'"),
+    ('[1] & 1j', 'TypeError', "unsupported operand type(s) for &: 'list' and 'complex'"),
+    ('[1] & True', 'TypeError', "unsupported operand type(s) for &: 'list' and 'bool'"),
+    ('[1] & None', 'TypeError', "unsupported operand type(s) for &: 'list' and 'NoneType'"),
+    ("[1] & 'a'", 'TypeError', "unsupported operand type(s) for &: 'list' and 'str'"),
+    ("[1] & b'a'", 'TypeError', "unsupported operand type(s) for &: 'list' and 'bytes'"),
+    ("[1] & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'list' and 'bytearray'"),
+    ('[1] & [1]', 'TypeError', "unsupported operand type(s) for &: 'list' and 'list'"),
+    ('[1] & (1,)', 'TypeError', "unsupported operand type(s) for &: 'list' and 'tuple'"),
+    ('[1] & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'list' and 'dict'"),
+    ('[1] & {1}', 'TypeError', "unsupported operand type(s) for &: 'list' and 'set'"),
+    ('[1] & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'list' and 'frozenset'"),
+    ('[1] & range(1)', 'TypeError', "unsupported operand type(s) for &: 'list' and 'range'"),
+    ('[1] | 1', 'TypeError', "unsupported operand type(s) for |: 'list' and 'int'"),
+    ('[1] | 1.5', 'TypeError', "unsupported operand type(s) for |: 'list' and 'float'"),
+    ('[1] | 1j', 'TypeError', "unsupported operand type(s) for |: 'list' and 'complex'"),
+    ('[1] | True', 'TypeError', "unsupported operand type(s) for |: 'list' and 'bool'"),
+    ('[1] | None', 'TypeError', "unsupported operand type(s) for |: 'list' and 'NoneType'"),
+    ("[1] | 'a'", 'TypeError', "unsupported operand type(s) for |: 'list' and 'str'"),
+    ("[1] | b'a'", 'TypeError', "unsupported operand type(s) for |: 'list' and 'bytes'"),
+    ("[1] | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'list' and 'bytearray'"),
+    ('[1] | [1]', 'TypeError', "unsupported operand type(s) for |: 'list' and 'list'"),
+    ('[1] | (1,)', 'TypeError', "unsupported operand type(s) for |: 'list' and 'tuple'"),
+    ('[1] | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'list' and 'dict'"),
+    ('[1] | {1}', 'TypeError', "unsupported operand type(s) for |: 'list' and 'set'"),
+    ('[1] | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'list' and 'frozenset'"),
+    ('[1] | range(1)', 'TypeError', "unsupported operand type(s) for |: 'list' and 'range'"),
+    ('[1] ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'int'"),
+    ('[1] ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'float'"),
+    ('[1] ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'complex'"),
+    ('[1] ^ True', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'bool'"),
+    ('[1] ^ None', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'NoneType'"),
+    ("[1] ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'list' and 'str'"),
+    ("[1] ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'list' and 'bytes'"),
+    ("[1] ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'list' and 'bytearray'"),
+    ('[1] ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'list'"),
+    ('[1] ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'tuple'"),
+    ('[1] ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'dict'"),
+    ('[1] ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'set'"),
+    ('[1] ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'frozenset'"),
+    ('[1] ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'list' and 'range'"),
+    ('[1] < 1', 'TypeError', "'<' not supported between instances of 'list' and 'int'"),
+    ('[1] < 1.5', 'TypeError', "'<' not supported between instances of 'list' and 'float'"),
+    ('[1] < 1j', 'TypeError', "'<' not supported between instances of 'list' and 'complex'"),
+    ('[1] < True', 'TypeError', "'<' not supported between instances of 'list' and 'bool'"),
+    ('[1] < None', 'TypeError', "'<' not supported between instances of 'list' and 'NoneType'"),
+    ("[1] < 'a'", 'TypeError', "'<' not supported between instances of 'list' and 'str'"),
+    ("[1] < b'a'", 'TypeError', "'<' not supported between instances of 'list' and 'bytes'"),
+    ("[1] < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'list' and 'bytearray'"),
+    ('[1] < (1,)', 'TypeError', "'<' not supported between instances of 'list' and 'tuple'"),
+    ('[1] < {1: 1}', 'TypeError', "'<' not supported between instances of 'list' and 'dict'"),
+    ('[1] < {1}', 'TypeError', "'<' not supported between instances of 'list' and 'set'"),
+    ('[1] < frozenset({1})', 'TypeError', "'<' not supported between instances of 'list' and 'frozenset'"),
+    ('[1] < range(1)', 'TypeError', "'<' not supported between instances of 'list' and 'range'"),
+    ('[1] <= 1', 'TypeError', "'<=' not supported between instances of 'list' and 'int'"),
+    ('[1] <= 1.5', 'TypeError', "'<=' not supported between instances of 'list' and 'float'"),
+    ('[1] <= 1j', 'TypeError', "'<=' not supported between instances of 'list' and 'complex'"),
+    ('[1] <= True', 'TypeError', "'<=' not supported between instances of 'list' and 'bool'"),
+    ('[1] <= None', 'TypeError', "'<=' not supported between instances of 'list' and 'NoneType'"),
+    ("[1] <= 'a'", 'TypeError', "'<=' not supported between instances of 'list' and 'str'"),
+    ("[1] <= b'a'", 'TypeError', "'<=' not supported between instances of 'list' and 'bytes'"),
+    ("[1] <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'list' and 'bytearray'"),
+    ('[1] <= (1,)', 'TypeError', "'<=' not supported between instances of 'list' and 'tuple'"),
+    ('[1] <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'list' and 'dict'"),
+    ('[1] <= {1}', 'TypeError', "'<=' not supported between instances of 'list' and 'set'"),
+    ('[1] <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'list' and 'frozenset'"),
+    ('[1] <= range(1)', 'TypeError', "'<=' not supported between instances of 'list' and 'range'"),
+    ('[1] > 1', 'TypeError', "'>' not supported between instances of 'list' and 'int'"),
+    ('[1] > 1.5', 'TypeError', "'>' not supported between instances of 'list' and 'float'"),
+    ('[1] > 1j', 'TypeError', "'>' not supported between instances of 'list' and 'complex'"),
+    ('[1] > True', 'TypeError', "'>' not supported between instances of 'list' and 'bool'"),
+    ('[1] > None', 'TypeError', "'>' not supported between instances of 'list' and 'NoneType'"),
+    ("[1] > 'a'", 'TypeError', "'>' not supported between instances of 'list' and 'str'"),
+    ("[1] > b'a'", 'TypeError', "'>' not supported between instances of 'list' and 'bytes'"),
+    ("[1] > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'list' and 'bytearray'"),
+    ('[1] > (1,)', 'TypeError', "'>' not supported between instances of 'list' and 'tuple'"),
+    ('[1] > {1: 1}', 'TypeError', "'>' not supported between instances of 'list' and 'dict'"),
+    ('[1] > {1}', 'TypeError', "'>' not supported between instances of 'list' and 'set'"),
+    ('[1] > frozenset({1})', 'TypeError', "'>' not supported between instances of 'list' and 'frozenset'"),
+    ('[1] > range(1)', 'TypeError', "'>' not supported between instances of 'list' and 'range'"),
+    ('[1] >= 1', 'TypeError', "'>=' not supported between instances of 'list' and 'int'"),
+    ('[1] >= 1.5', 'TypeError', "'>=' not supported between instances of 'list' and 'float'"),
+    ('[1] >= 1j', 'TypeError', "'>=' not supported between instances of 'list' and 'complex'"),
+    ('[1] >= True', 'TypeError', "'>=' not supported between instances of 'list' and 'bool'"),
+    ('[1] >= None', 'TypeError', "'>=' not supported between instances of 'list' and 'NoneType'"),
+    ("[1] >= 'a'", 'TypeError', "'>=' not supported between instances of 'list' and 'str'"),
+    ("[1] >= b'a'", 'TypeError', "'>=' not supported between instances of 'list' and 'bytes'"),
+    ("[1] >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'list' and 'bytearray'"),
+    ('[1] >= (1,)', 'TypeError', "'>=' not supported between instances of 'list' and 'tuple'"),
+    ('[1] >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'list' and 'dict'"),
+    ('[1] >= {1}', 'TypeError', "'>=' not supported between instances of 'list' and 'set'"),
+    ('[1] >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'list' and 'frozenset'"),
+    ('[1] >= range(1)', 'TypeError', "'>=' not supported between instances of 'list' and 'range'"),
+    ('(1,) + 1', 'TypeError', 'can only concatenate tuple (not "int") to tuple'),
+    ('(1,) + 1.5', 'TypeError', 'can only concatenate tuple (not "float") to tuple'),
+    ('(1,) + 1j', 'TypeError', 'can only concatenate tuple (not "complex") to tuple'),
+    ('(1,) + True', 'TypeError', 'can only concatenate tuple (not "bool") to tuple'),
+    ('(1,) + None', 'TypeError', 'can only concatenate tuple (not "NoneType") to tuple'),
+    ("(1,) + 'a'", 'TypeError', 'can only concatenate tuple (not "str") to tuple'),
+    ("(1,) + b'a'", 'TypeError', 'can only concatenate tuple (not "bytes") to tuple'),
+    ("(1,) + bytearray(b'a')", 'TypeError', 'can only concatenate tuple (not "bytearray") to tuple'),
+    ('(1,) + [1]', 'TypeError', 'can only concatenate tuple (not "list") to tuple'),
+    ('(1,) + {1: 1}', 'TypeError', 'can only concatenate tuple (not "dict") to tuple'),
+    ('(1,) + {1}', 'TypeError', 'can only concatenate tuple (not "set") to tuple'),
+    ('(1,) + frozenset({1})', 'TypeError', 'can only concatenate tuple (not "frozenset") to tuple'),
+    ('(1,) + range(1)', 'TypeError', 'can only concatenate tuple (not "range") to tuple'),
+    ('(1,) - 1', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'int'"),
+    ('(1,) - 1.5', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'float'"),
+    ('(1,) - 1j', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'complex'"),
+    ('(1,) - True', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'bool'"),
+    ('(1,) - None', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'NoneType'"),
+    ("(1,) - 'a'", 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'str'"),
+    ("(1,) - b'a'", 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'bytes'"),
+    ("(1,) - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'bytearray'"),
+    ('(1,) - [1]', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'list'"),
+    ('(1,) - (1,)', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'tuple'"),
+    ('(1,) - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'dict'"),
+    ('(1,) - {1}', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'set'"),
+    ('(1,) - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'frozenset'"),
+    ('(1,) - range(1)', 'TypeError', "unsupported operand type(s) for -: 'tuple' and 'range'"),
+    ('(1,) * 1.5', 'TypeError', "can't multiply sequence by non-int of type 'float'"),
+    ('(1,) * 1j', 'TypeError', "can't multiply sequence by non-int of type 'complex'"),
+    ('(1,) * None', 'TypeError', "can't multiply sequence by non-int of type 'NoneType'"),
+    ("(1,) * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'str'"),
+    ("(1,) * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'bytes'"),
+    ("(1,) * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'bytearray'"),
+    ('(1,) * [1]', 'TypeError', "can't multiply sequence by non-int of type 'list'"),
+    ('(1,) * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'tuple'"),
+    ('(1,) * {1: 1}', 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ('(1,) * {1}', 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ('(1,) * frozenset({1})', 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ('(1,) * range(1)', 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ('(1,) / 1', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'int'"),
+    ('(1,) / 1.5', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'float'"),
+    ('(1,) / 1j', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'complex'"),
+    ('(1,) / True', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'bool'"),
+    ('(1,) / None', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'NoneType'"),
+    ("(1,) / 'a'", 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'str'"),
+    ("(1,) / b'a'", 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'bytes'"),
+    ("(1,) / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'bytearray'"),
+    ('(1,) / [1]', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'list'"),
+    ('(1,) / (1,)', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'tuple'"),
+    ('(1,) / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'dict'"),
+    ('(1,) / {1}', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'set'"),
+    ('(1,) / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'frozenset'"),
+    ('(1,) / range(1)', 'TypeError', "unsupported operand type(s) for /: 'tuple' and 'range'"),
+    ('(1,) // 1', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'int'"),
+    ('(1,) // 1.5', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'float'"),
+    ('(1,) // 1j', 'TypeError', "can't take floor of complex number."),
+    ('(1,) // True', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'bool'"),
+    ('(1,) // None', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'NoneType'"),
+    ("(1,) // 'a'", 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'str'"),
+    ("(1,) // b'a'", 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'bytes'"),
+    ("(1,) // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'bytearray'"),
+    ('(1,) // [1]', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'list'"),
+    ('(1,) // (1,)', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'tuple'"),
+    ('(1,) // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'dict'"),
+    ('(1,) // {1}', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'set'"),
+    ('(1,) // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'frozenset'"),
+    ('(1,) // range(1)', 'TypeError', "unsupported operand type(s) for //: 'tuple' and 'range'"),
+    ('(1,) % 1', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'int'"),
+    ('(1,) % 1.5', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'float'"),
+    ('(1,) % 1j', 'TypeError', "can't mod complex numbers."),
+    ('(1,) % True', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'bool'"),
+    ('(1,) % None', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'NoneType'"),
+    ("(1,) % 'a'", 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'str'"),
+    ("(1,) % b'a'", 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'bytes'"),
+    ("(1,) % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'bytearray'"),
+    ('(1,) % [1]', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'list'"),
+    ('(1,) % (1,)', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'tuple'"),
+    ('(1,) % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'dict'"),
+    ('(1,) % {1}', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'set'"),
+    ('(1,) % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'frozenset'"),
+    ('(1,) % range(1)', 'TypeError', "unsupported operand type(s) for %: 'tuple' and 'range'"),
+    ('(1,) ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'int'"),
+    ('(1,) ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'float'"),
+    ('(1,) ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'complex'"),
+    ('(1,) ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'bool'"),
+    ('(1,) ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'NoneType'"),
+    ("(1,) ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'str'"),
+    ("(1,) ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'bytes'"),
+    ("(1,) ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'bytearray'"),
+    ('(1,) ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'list'"),
+    ('(1,) ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'tuple'"),
+    ('(1,) ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'dict'"),
+    ('(1,) ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'set'"),
+    ('(1,) ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'frozenset'"),
+    ('(1,) ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'tuple' and 'range'"),
+    ('(1,) @ 1', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'int'"),
+    ('(1,) @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'float'"),
+    ('(1,) @ 1j', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'complex'"),
+    ('(1,) @ True', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'bool'"),
+    ('(1,) @ None', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'NoneType'"),
+    ("(1,) @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'str'"),
+    ("(1,) @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'bytes'"),
+    ("(1,) @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'bytearray'"),
+    ('(1,) @ [1]', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'list'"),
+    ('(1,) @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'tuple'"),
+    ('(1,) @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'dict'"),
+    ('(1,) @ {1}', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'set'"),
+    ('(1,) @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'frozenset'"),
+    ('(1,) @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'tuple' and 'range'"),
+    ('(1,) << 1', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'int'"),
+    ('(1,) << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'float'"),
+    ('(1,) << 1j', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'complex'"),
+    ('(1,) << True', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'bool'"),
+    ('(1,) << None', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'NoneType'"),
+    ("(1,) << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'str'"),
+    ("(1,) << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'bytes'"),
+    ("(1,) << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'bytearray'"),
+    ('(1,) << [1]', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'list'"),
+    ('(1,) << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'tuple'"),
+    ('(1,) << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'dict'"),
+    ('(1,) << {1}', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'set'"),
+    ('(1,) << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'frozenset'"),
+    ('(1,) << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'tuple' and 'range'"),
+    ('(1,) >> 1', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'int'"),
+    ('(1,) >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'float'"),
+    ('(1,) >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'complex'"),
+    ('(1,) >> True', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'bool'"),
+    ('(1,) >> None', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'NoneType'"),
+    ("(1,) >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'str'"),
+    ("(1,) >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'bytes'"),
+    ("(1,) >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'bytearray'"),
+    ('(1,) >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'list'"),
+    ('(1,) >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'tuple'"),
+    ('(1,) >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'dict'"),
+    ('(1,) >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'set'"),
+    ('(1,) >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'frozenset'"),
+    ('(1,) >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'tuple' and 'range'"),
+    ('(1,) & 1', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'int'"),
+    ('(1,) & 1.5', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'float'"),
+    ('(1,) & 1j', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'complex'"),
+    ('(1,) & True', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'bool'"),
+    ('(1,) & None', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'NoneType'"),
+    ("(1,) & 'a'", 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'str'"),
+    ("(1,) & b'a'", 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'bytes'"),
+    ("(1,) & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'bytearray'"),
+    ('(1,) & [1]', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'list'"),
+    ('(1,) & (1,)', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'tuple'"),
+    ('(1,) & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'dict'"),
+    ('(1,) & {1}', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'set'"),
+    ('(1,) & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'frozenset'"),
+    ('(1,) & range(1)', 'TypeError', "unsupported operand type(s) for &: 'tuple' and 'range'"),
+    ('(1,) | 1', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'int'"),
+    ('(1,) | 1.5', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'float'"),
+    ('(1,) | 1j', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'complex'"),
+    ('(1,) | True', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'bool'"),
+    ('(1,) | None', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'NoneType'"),
+    ("(1,) | 'a'", 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'str'"),
+    ("(1,) | b'a'", 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'bytes'"),
+    ("(1,) | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'bytearray'"),
+    ('(1,) | [1]', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'list'"),
+    ('(1,) | (1,)', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'tuple'"),
+    ('(1,) | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'dict'"),
+    ('(1,) | {1}', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'set'"),
+    ('(1,) | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'frozenset'"),
+    ('(1,) | range(1)', 'TypeError', "unsupported operand type(s) for |: 'tuple' and 'range'"),
+    ('(1,) ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'int'"),
+    ('(1,) ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'float'"),
+    ('(1,) ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'complex'"),
+    ('(1,) ^ True', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'bool'"),
+    ('(1,) ^ None', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'NoneType'"),
+    ("(1,) ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'str'"),
+    ("(1,) ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'bytes'"),
+    ("(1,) ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'bytearray'"),
+    ('(1,) ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'list'"),
+    ('(1,) ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'tuple'"),
+    ('(1,) ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'dict'"),
+    ('(1,) ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'set'"),
+    ('(1,) ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'frozenset'"),
+    ('(1,) ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'tuple' and 'range'"),
+    ('(1,) < 1', 'TypeError', "'<' not supported between instances of 'tuple' and 'int'"),
+    ('(1,) < 1.5', 'TypeError', "'<' not supported between instances of 'tuple' and 'float'"),
+    ('(1,) < 1j', 'TypeError', "'<' not supported between instances of 'tuple' and 'complex'"),
+    ('(1,) < True', 'TypeError', "'<' not supported between instances of 'tuple' and 'bool'"),
+    ('(1,) < None', 'TypeError', "'<' not supported between instances of 'tuple' and 'NoneType'"),
+    ("(1,) < 'a'", 'TypeError', "'<' not supported between instances of 'tuple' and 'str'"),
+    ("(1,) < b'a'", 'TypeError', "'<' not supported between instances of 'tuple' and 'bytes'"),
+    ("(1,) < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'tuple' and 'bytearray'"),
+    ('(1,) < [1]', 'TypeError', "'<' not supported between instances of 'tuple' and 'list'"),
+    ('(1,) < {1: 1}', 'TypeError', "'<' not supported between instances of 'tuple' and 'dict'"),
+    ('(1,) < {1}', 'TypeError', "'<' not supported between instances of 'tuple' and 'set'"),
+    ('(1,) < frozenset({1})', 'TypeError', "'<' not supported between instances of 'tuple' and 'frozenset'"),
+    ('(1,) < range(1)', 'TypeError', "'<' not supported between instances of 'tuple' and 'range'"),
+    ('(1,) <= 1', 'TypeError', "'<=' not supported between instances of 'tuple' and 'int'"),
+    ('(1,) <= 1.5', 'TypeError', "'<=' not supported between instances of 'tuple' and 'float'"),
+    ('(1,) <= 1j', 'TypeError', "'<=' not supported between instances of 'tuple' and 'complex'"),
+    ('(1,) <= True', 'TypeError', "'<=' not supported between instances of 'tuple' and 'bool'"),
+    ('(1,) <= None', 'TypeError', "'<=' not supported between instances of 'tuple' and 'NoneType'"),
+    ("(1,) <= 'a'", 'TypeError', "'<=' not supported between instances of 'tuple' and 'str'"),
+    ("(1,) <= b'a'", 'TypeError', "'<=' not supported between instances of 'tuple' and 'bytes'"),
+    ("(1,) <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'tuple' and 'bytearray'"),
+    ('(1,) <= [1]', 'TypeError', "'<=' not supported between instances of 'tuple' and 'list'"),
+    ('(1,) <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'tuple' and 'dict'"),
+    ('(1,) <= {1}', 'TypeError', "'<=' not supported between instances of 'tuple' and 'set'"),
+    ('(1,) <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'tuple' and 'frozenset'"),
+    ('(1,) <= range(1)', 'TypeError', "'<=' not supported between instances of 'tuple' and 'range'"),
+    ('(1,) > 1', 'TypeError', "'>' not supported between instances of 'tuple' and 'int'"),
+    ('(1,) > 1.5', 'TypeError', "'>' not supported between instances of 'tuple' and 'float'"),
+    ('(1,) > 1j', 'TypeError', "'>' not supported between instances of 'tuple' and 'complex'"),
+    ('(1,) > True', 'TypeError', "'>' not supported between instances of 'tuple' and 'bool'"),
+    ('(1,) > None', 'TypeError', "'>' not supported between instances of 'tuple' and 'NoneType'"),
+    ("(1,) > 'a'", 'TypeError', "'>' not supported between instances of 'tuple' and 'str'"),
+    ("(1,) > b'a'", 'TypeError', "'>' not supported between instances of 'tuple' and 'bytes'"),
+    ("(1,) > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'tuple' and 'bytearray'"),
+    ('(1,) > [1]', 'TypeError', "'>' not supported between instances of 'tuple' and 'list'"),
+    ('(1,) > {1: 1}', 'TypeError', "'>' not supported between instances of 'tuple' and 'dict'"),
+    ('(1,) > {1}', 'TypeError', "'>' not supported between instances of 'tuple' and 'set'"),
+    ('(1,) > frozenset({1})', 'TypeError', "'>' not supported between instances of 'tuple' and 'frozenset'"),
+    ('(1,) > range(1)', 'TypeError', "'>' not supported between instances of 'tuple' and 'range'"),
+    ('(1,) >= 1', 'TypeError', "'>=' not supported between instances of 'tuple' and 'int'"),
+    ('(1,) >= 1.5', 'TypeError', "'>=' not supported between instances of 'tuple' and 'float'"),
+    ('(1,) >= 1j', 'TypeError', "'>=' not supported between instances of 'tuple' and 'complex'"),
+    ('(1,) >= True', 'TypeError', "'>=' not supported between instances of 'tuple' and 'bool'"),
+    ('(1,) >= None', 'TypeError', "'>=' not supported between instances of 'tuple' and 'NoneType'"),
+    ("(1,) >= 'a'", 'TypeError', "'>=' not supported between instances of 'tuple' and 'str'"),
+    ("(1,) >= b'a'", 'TypeError', "'>=' not supported between instances of 'tuple' and 'bytes'"),
+    ("(1,) >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'tuple' and 'bytearray'"),
+    ('(1,) >= [1]', 'TypeError', "'>=' not supported between instances of 'tuple' and 'list'"),
+    ('(1,) >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'tuple' and 'dict'"),
+    ('(1,) >= {1}', 'TypeError', "'>=' not supported between instances of 'tuple' and 'set'"),
+    ('(1,) >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'tuple' and 'frozenset'"),
+    ('(1,) >= range(1)', 'TypeError', "'>=' not supported between instances of 'tuple' and 'range'"),
+    ('{1: 1} + 1', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'int'"),
+    ('{1: 1} + 1.5', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'float'"),
+    ('{1: 1} + 1j', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'complex'"),
+    ('{1: 1} + True', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'bool'"),
+    ('{1: 1} + None', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'NoneType'"),
+    ("{1: 1} + 'a'", 'TypeError', "unsupported operand type(s) for +: 'dict' and 'str'"),
+    ("{1: 1} + b'a'", 'TypeError', "unsupported operand type(s) for +: 'dict' and 'bytes'"),
+    ("{1: 1} + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'dict' and 'bytearray'"),
+    ('{1: 1} + [1]', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'list'"),
+    ('{1: 1} + (1,)', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'tuple'"),
+    ('{1: 1} + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'dict'"),
+    ('{1: 1} + {1}', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'set'"),
+    ('{1: 1} + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'frozenset'"),
+    ('{1: 1} + range(1)', 'TypeError', "unsupported operand type(s) for +: 'dict' and 'range'"),
+    ('{1: 1} - 1', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'int'"),
+    ('{1: 1} - 1.5', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'float'"),
+    ('{1: 1} - 1j', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'complex'"),
+    ('{1: 1} - True', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'bool'"),
+    ('{1: 1} - None', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'NoneType'"),
+    ("{1: 1} - 'a'", 'TypeError', "unsupported operand type(s) for -: 'dict' and 'str'"),
+    ("{1: 1} - b'a'", 'TypeError', "unsupported operand type(s) for -: 'dict' and 'bytes'"),
+    ("{1: 1} - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'dict' and 'bytearray'"),
+    ('{1: 1} - [1]', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'list'"),
+    ('{1: 1} - (1,)', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'tuple'"),
+    ('{1: 1} - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'dict'"),
+    ('{1: 1} - {1}', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'set'"),
+    ('{1: 1} - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'frozenset'"),
+    ('{1: 1} - range(1)', 'TypeError', "unsupported operand type(s) for -: 'dict' and 'range'"),
+    ('{1: 1} * 1', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'int'"),
+    ('{1: 1} * 1.5', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'float'"),
+    ('{1: 1} * 1j', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'complex'"),
+    ('{1: 1} * True', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'bool'"),
+    ('{1: 1} * None', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'NoneType'"),
+    ("{1: 1} * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ("{1: 1} * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ("{1: 1} * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ('{1: 1} * [1]', 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ('{1: 1} * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'dict'"),
+    ('{1: 1} * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'dict'"),
+    ('{1: 1} * {1}', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'set'"),
+    ('{1: 1} * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'frozenset'"),
+    ('{1: 1} * range(1)', 'TypeError', "unsupported operand type(s) for *: 'dict' and 'range'"),
+    ('{1: 1} / 1', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'int'"),
+    ('{1: 1} / 1.5', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'float'"),
+    ('{1: 1} / 1j', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'complex'"),
+    ('{1: 1} / True', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'bool'"),
+    ('{1: 1} / None', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'NoneType'"),
+    ("{1: 1} / 'a'", 'TypeError', "unsupported operand type(s) for /: 'dict' and 'str'"),
+    ("{1: 1} / b'a'", 'TypeError', "unsupported operand type(s) for /: 'dict' and 'bytes'"),
+    ("{1: 1} / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'dict' and 'bytearray'"),
+    ('{1: 1} / [1]', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'list'"),
+    ('{1: 1} / (1,)', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'tuple'"),
+    ('{1: 1} / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'dict'"),
+    ('{1: 1} / {1}', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'set'"),
+    ('{1: 1} / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'frozenset'"),
+    ('{1: 1} / range(1)', 'TypeError', "unsupported operand type(s) for /: 'dict' and 'range'"),
+    ('{1: 1} // 1', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'int'"),
+    ('{1: 1} // 1.5', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'float'"),
+    ('{1: 1} // 1j', 'TypeError', "can't take floor of complex number."),
+    ('{1: 1} // True', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'bool'"),
+    ('{1: 1} // None', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'NoneType'"),
+    ("{1: 1} // 'a'", 'TypeError', "unsupported operand type(s) for //: 'dict' and 'str'"),
+    ("{1: 1} // b'a'", 'TypeError', "unsupported operand type(s) for //: 'dict' and 'bytes'"),
+    ("{1: 1} // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'dict' and 'bytearray'"),
+    ('{1: 1} // [1]', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'list'"),
+    ('{1: 1} // (1,)', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'tuple'"),
+    ('{1: 1} // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'dict'"),
+    ('{1: 1} // {1}', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'set'"),
+    ('{1: 1} // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'frozenset'"),
+    ('{1: 1} // range(1)', 'TypeError', "unsupported operand type(s) for //: 'dict' and 'range'"),
+    ('{1: 1} % 1', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'int'"),
+    ('{1: 1} % 1.5', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'float'"),
+    ('{1: 1} % 1j', 'TypeError', "can't mod complex numbers."),
+    ('{1: 1} % True', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'bool'"),
+    ('{1: 1} % None', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'NoneType'"),
+    ("{1: 1} % 'a'", 'TypeError', "unsupported operand type(s) for %: 'dict' and 'str'"),
+    ("{1: 1} % b'a'", 'TypeError', "unsupported operand type(s) for %: 'dict' and 'bytes'"),
+    ("{1: 1} % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'dict' and 'bytearray'"),
+    ('{1: 1} % [1]', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'list'"),
+    ('{1: 1} % (1,)', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'tuple'"),
+    ('{1: 1} % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'dict'"),
+    ('{1: 1} % {1}', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'set'"),
+    ('{1: 1} % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'frozenset'"),
+    ('{1: 1} % range(1)', 'TypeError', "unsupported operand type(s) for %: 'dict' and 'range'"),
+    ('{1: 1} ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'int'"),
+    ('{1: 1} ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'float'"),
+    ('{1: 1} ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'complex'"),
+    ('{1: 1} ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'bool'"),
+    ('{1: 1} ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'NoneType'"),
+    ("{1: 1} ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'str'"),
+    ("{1: 1} ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'bytes'"),
+    ("{1: 1} ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'bytearray'"),
+    ('{1: 1} ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'list'"),
+    ('{1: 1} ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'tuple'"),
+    ('{1: 1} ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'dict'"),
+    ('{1: 1} ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'set'"),
+    ('{1: 1} ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'frozenset'"),
+    ('{1: 1} ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'dict' and 'range'"),
+    ('{1: 1} @ 1', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'int'"),
+    ('{1: 1} @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'float'"),
+    ('{1: 1} @ 1j', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'complex'"),
+    ('{1: 1} @ True', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'bool'"),
+    ('{1: 1} @ None', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'NoneType'"),
+    ("{1: 1} @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'dict' and 'str'"),
+    ("{1: 1} @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'dict' and 'bytes'"),
+    ("{1: 1} @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'dict' and 'bytearray'"),
+    ('{1: 1} @ [1]', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'list'"),
+    ('{1: 1} @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'tuple'"),
+    ('{1: 1} @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'dict'"),
+    ('{1: 1} @ {1}', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'set'"),
+    ('{1: 1} @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'frozenset'"),
+    ('{1: 1} @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'dict' and 'range'"),
+    ('{1: 1} << 1', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'int'"),
+    ('{1: 1} << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'float'"),
+    ('{1: 1} << 1j', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'complex'"),
+    ('{1: 1} << True', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'bool'"),
+    ('{1: 1} << None', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'NoneType'"),
+    ("{1: 1} << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'str'"),
+    ("{1: 1} << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'bytes'"),
+    ("{1: 1} << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'bytearray'"),
+    ('{1: 1} << [1]', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'list'"),
+    ('{1: 1} << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'tuple'"),
+    ('{1: 1} << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'dict'"),
+    ('{1: 1} << {1}', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'set'"),
+    ('{1: 1} << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'frozenset'"),
+    ('{1: 1} << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'dict' and 'range'"),
+    ('{1: 1} >> 1', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'int'"),
+    ('{1: 1} >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'float'"),
+    ('{1: 1} >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'complex'"),
+    ('{1: 1} >> True', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'bool'"),
+    ('{1: 1} >> None', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'NoneType'"),
+    ("{1: 1} >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'str'"),
+    ("{1: 1} >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'bytes'"),
+    ("{1: 1} >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'bytearray'"),
+    ('{1: 1} >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'list'"),
+    ('{1: 1} >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'tuple'"),
+    ('{1: 1} >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'dict'"),
+    ('{1: 1} >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'set'"),
+    ('{1: 1} >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'frozenset'"),
+    ('{1: 1} >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'dict' and 'range'"),
+    ('{1: 1} & 1', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'int'"),
+    ('{1: 1} & 1.5', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'float'"),
+    ('{1: 1} & 1j', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'complex'"),
+    ('{1: 1} & True', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'bool'"),
+    ('{1: 1} & None', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'NoneType'"),
+    ("{1: 1} & 'a'", 'TypeError', "unsupported operand type(s) for &: 'dict' and 'str'"),
+    ("{1: 1} & b'a'", 'TypeError', "unsupported operand type(s) for &: 'dict' and 'bytes'"),
+    ("{1: 1} & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'dict' and 'bytearray'"),
+    ('{1: 1} & [1]', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'list'"),
+    ('{1: 1} & (1,)', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'tuple'"),
+    ('{1: 1} & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'dict'"),
+    ('{1: 1} & {1}', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'set'"),
+    ('{1: 1} & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'frozenset'"),
+    ('{1: 1} & range(1)', 'TypeError', "unsupported operand type(s) for &: 'dict' and 'range'"),
+    ('{1: 1} | 1', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'int'"),
+    ('{1: 1} | 1.5', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'float'"),
+    ('{1: 1} | 1j', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'complex'"),
+    ('{1: 1} | True', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'bool'"),
+    ('{1: 1} | None', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'NoneType'"),
+    ("{1: 1} | 'a'", 'TypeError', "unsupported operand type(s) for |: 'dict' and 'str'"),
+    ("{1: 1} | b'a'", 'TypeError', "unsupported operand type(s) for |: 'dict' and 'bytes'"),
+    ("{1: 1} | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'dict' and 'bytearray'"),
+    ('{1: 1} | [1]', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'list'"),
+    ('{1: 1} | (1,)', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'tuple'"),
+    ('{1: 1} | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'dict'"),
+    ('{1: 1} | {1}', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'set'"),
+    ('{1: 1} | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'frozenset'"),
+    ('{1: 1} | range(1)', 'TypeError', "unsupported operand type(s) for |: 'dict' and 'range'"),
+    ('{1: 1} ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'int'"),
+    ('{1: 1} ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'float'"),
+    ('{1: 1} ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'complex'"),
+    ('{1: 1} ^ True', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'bool'"),
+    ('{1: 1} ^ None', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'NoneType'"),
+    ("{1: 1} ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'str'"),
+    ("{1: 1} ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'bytes'"),
+    ("{1: 1} ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'bytearray'"),
+    ('{1: 1} ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'list'"),
+    ('{1: 1} ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'tuple'"),
+    ('{1: 1} ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'dict'"),
+    ('{1: 1} ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'set'"),
+    ('{1: 1} ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'frozenset'"),
+    ('{1: 1} ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'dict' and 'range'"),
+    ('{1: 1} < 1', 'TypeError', "'<' not supported between instances of 'dict' and 'int'"),
+    ('{1: 1} < 1.5', 'TypeError', "'<' not supported between instances of 'dict' and 'float'"),
+    ('{1: 1} < 1j', 'TypeError', "'<' not supported between instances of 'dict' and 'complex'"),
+    ('{1: 1} < True', 'TypeError', "'<' not supported between instances of 'dict' and 'bool'"),
+    ('{1: 1} < None', 'TypeError', "'<' not supported between instances of 'dict' and 'NoneType'"),
+    ("{1: 1} < 'a'", 'TypeError', "'<' not supported between instances of 'dict' and 'str'"),
+    ("{1: 1} < b'a'", 'TypeError', "'<' not supported between instances of 'dict' and 'bytes'"),
+    ("{1: 1} < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'dict' and 'bytearray'"),
+    ('{1: 1} < [1]', 'TypeError', "'<' not supported between instances of 'dict' and 'list'"),
+    ('{1: 1} < (1,)', 'TypeError', "'<' not supported between instances of 'dict' and 'tuple'"),
+    ('{1: 1} < {1: 1}', 'TypeError', "'<' not supported between instances of 'dict' and 'dict'"),
+    ('{1: 1} < {1}', 'TypeError', "'<' not supported between instances of 'dict' and 'set'"),
+    ('{1: 1} < frozenset({1})', 'TypeError', "'<' not supported between instances of 'dict' and 'frozenset'"),
+    ('{1: 1} < range(1)', 'TypeError', "'<' not supported between instances of 'dict' and 'range'"),
+    ('{1: 1} <= 1', 'TypeError', "'<=' not supported between instances of 'dict' and 'int'"),
+    ('{1: 1} <= 1.5', 'TypeError', "'<=' not supported between instances of 'dict' and 'float'"),
+    ('{1: 1} <= 1j', 'TypeError', "'<=' not supported between instances of 'dict' and 'complex'"),
+    ('{1: 1} <= True', 'TypeError', "'<=' not supported between instances of 'dict' and 'bool'"),
+    ('{1: 1} <= None', 'TypeError', "'<=' not supported between instances of 'dict' and 'NoneType'"),
+    ("{1: 1} <= 'a'", 'TypeError', "'<=' not supported between instances of 'dict' and 'str'"),
+    ("{1: 1} <= b'a'", 'TypeError', "'<=' not supported between instances of 'dict' and 'bytes'"),
+    ("{1: 1} <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'dict' and 'bytearray'"),
+    ('{1: 1} <= [1]', 'TypeError', "'<=' not supported between instances of 'dict' and 'list'"),
+    ('{1: 1} <= (1,)', 'TypeError', "'<=' not supported between instances of 'dict' and 'tuple'"),
+    ('{1: 1} <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'dict' and 'dict'"),
+    ('{1: 1} <= {1}', 'TypeError', "'<=' not supported between instances of 'dict' and 'set'"),
+    ('{1: 1} <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'dict' and 'frozenset'"),
+    ('{1: 1} <= range(1)', 'TypeError', "'<=' not supported between instances of 'dict' and 'range'"),
+    ('{1: 1} > 1', 'TypeError', "'>' not supported between instances of 'dict' and 'int'"),
+    ('{1: 1} > 1.5', 'TypeError', "'>' not supported between instances of 'dict' and 'float'"),
+    ('{1: 1} > 1j', 'TypeError', "'>' not supported between instances of 'dict' and 'complex'"),
+    ('{1: 1} > True', 'TypeError', "'>' not supported between instances of 'dict' and 'bool'"),
+    ('{1: 1} > None', 'TypeError', "'>' not supported between instances of 'dict' and 'NoneType'"),
+    ("{1: 1} > 'a'", 'TypeError', "'>' not supported between instances of 'dict' and 'str'"),
+    ("{1: 1} > b'a'", 'TypeError', "'>' not supported between instances of 'dict' and 'bytes'"),
+    ("{1: 1} > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'dict' and 'bytearray'"),
+    ('{1: 1} > [1]', 'TypeError', "'>' not supported between instances of 'dict' and 'list'"),
+    ('{1: 1} > (1,)', 'TypeError', "'>' not supported between instances of 'dict' and 'tuple'"),
+    ('{1: 1} > {1: 1}', 'TypeError', "'>' not supported between instances of 'dict' and 'dict'"),
+    ('{1: 1} > {1}', 'TypeError', "'>' not supported between instances of 'dict' and 'set'"),
+    ('{1: 1} > frozenset({1})', 'TypeError', "'>' not supported between instances of 'dict' and 'frozenset'"),
+    ('{1: 1} > range(1)', 'TypeError', "'>' not supported between instances of 'dict' and 'range'"),
+    ('{1: 1} >= 1', 'TypeError', "'>=' not supported between instances of 'dict' and 'int'"),
+    ('{1: 1} >= 1.5', 'TypeError', "'>=' not supported between instances of 'dict' and 'float'"),
+    ('{1: 1} >= 1j', 'TypeError', "'>=' not supported between instances of 'dict' and 'complex'"),
+    ('{1: 1} >= True', 'TypeError', "'>=' not supported between instances of 'dict' and 'bool'"),
+    ('{1: 1} >= None', 'TypeError', "'>=' not supported between instances of 'dict' and 'NoneType'"),
+    ("{1: 1} >= 'a'", 'TypeError', "'>=' not supported between instances of 'dict' and 'str'"),
+    ("{1: 1} >= b'a'", 'TypeError', "'>=' not supported between instances of 'dict' and 'bytes'"),
+    ("{1: 1} >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'dict' and 'bytearray'"),
+    ('{1: 1} >= [1]', 'TypeError', "'>=' not supported between instances of 'dict' and 'list'"),
+    ('{1: 1} >= (1,)', 'TypeError', "'>=' not supported between instances of 'dict' and 'tuple'"),
+    ('{1: 1} >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'dict' and 'dict'"),
+    ('{1: 1} >= {1}', 'TypeError', "'>=' not supported between instances of 'dict' and 'set'"),
+    ('{1: 1} >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'dict' and 'frozenset'"),
+    ('{1: 1} >= range(1)', 'TypeError', "'>=' not supported between instances of 'dict' and 'range'"),
+    ('{1} + 1', 'TypeError', "unsupported operand type(s) for +: 'set' and 'int'"),
+    ('{1} + 1.5', 'TypeError', "unsupported operand type(s) for +: 'set' and 'float'"),
+    ('{1} + 1j', 'TypeError', "unsupported operand type(s) for +: 'set' and 'complex'"),
+    ('{1} + True', 'TypeError', "unsupported operand type(s) for +: 'set' and 'bool'"),
+    ('{1} + None', 'TypeError', "unsupported operand type(s) for +: 'set' and 'NoneType'"),
+    ("{1} + 'a'", 'TypeError', "unsupported operand type(s) for +: 'set' and 'str'"),
+    ("{1} + b'a'", 'TypeError', "unsupported operand type(s) for +: 'set' and 'bytes'"),
+    ("{1} + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'set' and 'bytearray'"),
+    ('{1} + [1]', 'TypeError', "unsupported operand type(s) for +: 'set' and 'list'"),
+    ('{1} + (1,)', 'TypeError', "unsupported operand type(s) for +: 'set' and 'tuple'"),
+    ('{1} + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'set' and 'dict'"),
+    ('{1} + {1}', 'TypeError', "unsupported operand type(s) for +: 'set' and 'set'"),
+    ('{1} + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'set' and 'frozenset'"),
+    ('{1} + range(1)', 'TypeError', "unsupported operand type(s) for +: 'set' and 'range'"),
+    ('{1} - 1', 'TypeError', "unsupported operand type(s) for -: 'set' and 'int'"),
+    ('{1} - 1.5', 'TypeError', "unsupported operand type(s) for -: 'set' and 'float'"),
+    ('{1} - 1j', 'TypeError', "unsupported operand type(s) for -: 'set' and 'complex'"),
+    ('{1} - True', 'TypeError', "unsupported operand type(s) for -: 'set' and 'bool'"),
+    ('{1} - None', 'TypeError', "unsupported operand type(s) for -: 'set' and 'NoneType'"),
+    ("{1} - 'a'", 'TypeError', "unsupported operand type(s) for -: 'set' and 'str'"),
+    ("{1} - b'a'", 'TypeError', "unsupported operand type(s) for -: 'set' and 'bytes'"),
+    ("{1} - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'set' and 'bytearray'"),
+    ('{1} - [1]', 'TypeError', "unsupported operand type(s) for -: 'set' and 'list'"),
+    ('{1} - (1,)', 'TypeError', "unsupported operand type(s) for -: 'set' and 'tuple'"),
+    ('{1} - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'set' and 'dict'"),
+    ('{1} - range(1)', 'TypeError', "unsupported operand type(s) for -: 'set' and 'range'"),
+    ('{1} * 1', 'TypeError', "unsupported operand type(s) for *: 'set' and 'int'"),
+    ('{1} * 1.5', 'TypeError', "unsupported operand type(s) for *: 'set' and 'float'"),
+    ('{1} * 1j', 'TypeError', "unsupported operand type(s) for *: 'set' and 'complex'"),
+    ('{1} * True', 'TypeError', "unsupported operand type(s) for *: 'set' and 'bool'"),
+    ('{1} * None', 'TypeError', "unsupported operand type(s) for *: 'set' and 'NoneType'"),
+    ("{1} * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ("{1} * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ("{1} * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ('{1} * [1]', 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ('{1} * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'set'"),
+    ('{1} * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'set' and 'dict'"),
+    ('{1} * {1}', 'TypeError', "unsupported operand type(s) for *: 'set' and 'set'"),
+    ('{1} * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'set' and 'frozenset'"),
+    ('{1} * range(1)', 'TypeError', "unsupported operand type(s) for *: 'set' and 'range'"),
+    ('{1} / 1', 'TypeError', "unsupported operand type(s) for /: 'set' and 'int'"),
+    ('{1} / 1.5', 'TypeError', "unsupported operand type(s) for /: 'set' and 'float'"),
+    ('{1} / 1j', 'TypeError', "unsupported operand type(s) for /: 'set' and 'complex'"),
+    ('{1} / True', 'TypeError', "unsupported operand type(s) for /: 'set' and 'bool'"),
+    ('{1} / None', 'TypeError', "unsupported operand type(s) for /: 'set' and 'NoneType'"),
+    ("{1} / 'a'", 'TypeError', "unsupported operand type(s) for /: 'set' and 'str'"),
+    ("{1} / b'a'", 'TypeError', "unsupported operand type(s) for /: 'set' and 'bytes'"),
+    ("{1} / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'set' and 'bytearray'"),
+    ('{1} / [1]', 'TypeError', "unsupported operand type(s) for /: 'set' and 'list'"),
+    ('{1} / (1,)', 'TypeError', "unsupported operand type(s) for /: 'set' and 'tuple'"),
+    ('{1} / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'set' and 'dict'"),
+    ('{1} / {1}', 'TypeError', "unsupported operand type(s) for /: 'set' and 'set'"),
+    ('{1} / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'set' and 'frozenset'"),
+    ('{1} / range(1)', 'TypeError', "unsupported operand type(s) for /: 'set' and 'range'"),
+    ('{1} // 1', 'TypeError', "unsupported operand type(s) for //: 'set' and 'int'"),
+    ('{1} // 1.5', 'TypeError', "unsupported operand type(s) for //: 'set' and 'float'"),
+    ('{1} // 1j', 'TypeError', "can't take floor of complex number."),
+    ('{1} // True', 'TypeError', "unsupported operand type(s) for //: 'set' and 'bool'"),
+    ('{1} // None', 'TypeError', "unsupported operand type(s) for //: 'set' and 'NoneType'"),
+    ("{1} // 'a'", 'TypeError', "unsupported operand type(s) for //: 'set' and 'str'"),
+    ("{1} // b'a'", 'TypeError', "unsupported operand type(s) for //: 'set' and 'bytes'"),
+    ("{1} // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'set' and 'bytearray'"),
+    ('{1} // [1]', 'TypeError', "unsupported operand type(s) for //: 'set' and 'list'"),
+    ('{1} // (1,)', 'TypeError', "unsupported operand type(s) for //: 'set' and 'tuple'"),
+    ('{1} // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'set' and 'dict'"),
+    ('{1} // {1}', 'TypeError', "unsupported operand type(s) for //: 'set' and 'set'"),
+    ('{1} // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'set' and 'frozenset'"),
+    ('{1} // range(1)', 'TypeError', "unsupported operand type(s) for //: 'set' and 'range'"),
+    ('{1} % 1', 'TypeError', "unsupported operand type(s) for %: 'set' and 'int'"),
+    ('{1} % 1.5', 'TypeError', "unsupported operand type(s) for %: 'set' and 'float'"),
+    ('{1} % 1j', 'TypeError', "can't mod complex numbers."),
+    ('{1} % True', 'TypeError', "unsupported operand type(s) for %: 'set' and 'bool'"),
+    ('{1} % None', 'TypeError', "unsupported operand type(s) for %: 'set' and 'NoneType'"),
+    ("{1} % 'a'", 'TypeError', "unsupported operand type(s) for %: 'set' and 'str'"),
+    ("{1} % b'a'", 'TypeError', "unsupported operand type(s) for %: 'set' and 'bytes'"),
+    ("{1} % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'set' and 'bytearray'"),
+    ('{1} % [1]', 'TypeError', "unsupported operand type(s) for %: 'set' and 'list'"),
+    ('{1} % (1,)', 'TypeError', "unsupported operand type(s) for %: 'set' and 'tuple'"),
+    ('{1} % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'set' and 'dict'"),
+    ('{1} % {1}', 'TypeError', "unsupported operand type(s) for %: 'set' and 'set'"),
+    ('{1} % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'set' and 'frozenset'"),
+    ('{1} % range(1)', 'TypeError', "unsupported operand type(s) for %: 'set' and 'range'"),
+    ('{1} ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'int'"),
+    ('{1} ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'float'"),
+    ('{1} ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'complex'"),
+    ('{1} ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'bool'"),
+    ('{1} ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'NoneType'"),
+    ("{1} ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'str'"),
+    ("{1} ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'bytes'"),
+    ("{1} ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'bytearray'"),
+    ('{1} ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'list'"),
+    ('{1} ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'tuple'"),
+    ('{1} ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'dict'"),
+    ('{1} ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'set'"),
+    ('{1} ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'frozenset'"),
+    ('{1} ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'set' and 'range'"),
+    ('{1} @ 1', 'TypeError', "unsupported operand type(s) for @: 'set' and 'int'"),
+    ('{1} @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'set' and 'float'"),
+    ('{1} @ 1j', 'TypeError', "unsupported operand type(s) for @: 'set' and 'complex'"),
+    ('{1} @ True', 'TypeError', "unsupported operand type(s) for @: 'set' and 'bool'"),
+    ('{1} @ None', 'TypeError', "unsupported operand type(s) for @: 'set' and 'NoneType'"),
+    ("{1} @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'set' and 'str'"),
+    ("{1} @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'set' and 'bytes'"),
+    ("{1} @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'set' and 'bytearray'"),
+    ('{1} @ [1]', 'TypeError', "unsupported operand type(s) for @: 'set' and 'list'"),
+    ('{1} @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'set' and 'tuple'"),
+    ('{1} @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'set' and 'dict'"),
+    ('{1} @ {1}', 'TypeError', "unsupported operand type(s) for @: 'set' and 'set'"),
+    ('{1} @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'set' and 'frozenset'"),
+    ('{1} @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'set' and 'range'"),
+    ('{1} << 1', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'int'"),
+    ('{1} << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'float'"),
+    ('{1} << 1j', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'complex'"),
+    ('{1} << True', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'bool'"),
+    ('{1} << None', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'NoneType'"),
+    ("{1} << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'set' and 'str'"),
+    ("{1} << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'set' and 'bytes'"),
+    ("{1} << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'set' and 'bytearray'"),
+    ('{1} << [1]', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'list'"),
+    ('{1} << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'tuple'"),
+    ('{1} << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'dict'"),
+    ('{1} << {1}', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'set'"),
+    ('{1} << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'frozenset'"),
+    ('{1} << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'set' and 'range'"),
+    ('{1} >> 1', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'int'"),
+    ('{1} >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'float'"),
+    ('{1} >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'complex'"),
+    ('{1} >> True', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'bool'"),
+    ('{1} >> None', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'NoneType'"),
+    ("{1} >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'set' and 'str'"),
+    ("{1} >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'set' and 'bytes'"),
+    ("{1} >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'set' and 'bytearray'"),
+    ('{1} >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'list'"),
+    ('{1} >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'tuple'"),
+    ('{1} >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'dict'"),
+    ('{1} >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'set'"),
+    ('{1} >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'frozenset'"),
+    ('{1} >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'set' and 'range'"),
+    ('{1} & 1', 'TypeError', "unsupported operand type(s) for &: 'set' and 'int'"),
+    ('{1} & 1.5', 'TypeError', "unsupported operand type(s) for &: 'set' and 'float'"),
+    ('{1} & 1j', 'TypeError', "unsupported operand type(s) for &: 'set' and 'complex'"),
+    ('{1} & True', 'TypeError', "unsupported operand type(s) for &: 'set' and 'bool'"),
+    ('{1} & None', 'TypeError', "unsupported operand type(s) for &: 'set' and 'NoneType'"),
+    ("{1} & 'a'", 'TypeError', "unsupported operand type(s) for &: 'set' and 'str'"),
+    ("{1} & b'a'", 'TypeError', "unsupported operand type(s) for &: 'set' and 'bytes'"),
+    ("{1} & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'set' and 'bytearray'"),
+    ('{1} & [1]', 'TypeError', "unsupported operand type(s) for &: 'set' and 'list'"),
+    ('{1} & (1,)', 'TypeError', "unsupported operand type(s) for &: 'set' and 'tuple'"),
+    ('{1} & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'set' and 'dict'"),
+    ('{1} & range(1)', 'TypeError', "unsupported operand type(s) for &: 'set' and 'range'"),
+    ('{1} | 1', 'TypeError', "unsupported operand type(s) for |: 'set' and 'int'"),
+    ('{1} | 1.5', 'TypeError', "unsupported operand type(s) for |: 'set' and 'float'"),
+    ('{1} | 1j', 'TypeError', "unsupported operand type(s) for |: 'set' and 'complex'"),
+    ('{1} | True', 'TypeError', "unsupported operand type(s) for |: 'set' and 'bool'"),
+    ('{1} | None', 'TypeError', "unsupported operand type(s) for |: 'set' and 'NoneType'"),
+    ("{1} | 'a'", 'TypeError', "unsupported operand type(s) for |: 'set' and 'str'"),
+    ("{1} | b'a'", 'TypeError', "unsupported operand type(s) for |: 'set' and 'bytes'"),
+    ("{1} | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'set' and 'bytearray'"),
+    ('{1} | [1]', 'TypeError', "unsupported operand type(s) for |: 'set' and 'list'"),
+    ('{1} | (1,)', 'TypeError', "unsupported operand type(s) for |: 'set' and 'tuple'"),
+    ('{1} | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'set' and 'dict'"),
+    ('{1} | range(1)', 'TypeError', "unsupported operand type(s) for |: 'set' and 'range'"),
+    ('{1} ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'int'"),
+    ('{1} ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'float'"),
+    ('{1} ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'complex'"),
+    ('{1} ^ True', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'bool'"),
+    ('{1} ^ None', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'NoneType'"),
+    ("{1} ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'set' and 'str'"),
+    ("{1} ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'set' and 'bytes'"),
+    ("{1} ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'set' and 'bytearray'"),
+    ('{1} ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'list'"),
+    ('{1} ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'tuple'"),
+    ('{1} ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'dict'"),
+    ('{1} ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'set' and 'range'"),
+    ('{1} < 1', 'TypeError', "'<' not supported between instances of 'set' and 'int'"),
+    ('{1} < 1.5', 'TypeError', "'<' not supported between instances of 'set' and 'float'"),
+    ('{1} < 1j', 'TypeError', "'<' not supported between instances of 'set' and 'complex'"),
+    ('{1} < True', 'TypeError', "'<' not supported between instances of 'set' and 'bool'"),
+    ('{1} < None', 'TypeError', "'<' not supported between instances of 'set' and 'NoneType'"),
+    ("{1} < 'a'", 'TypeError', "'<' not supported between instances of 'set' and 'str'"),
+    ("{1} < b'a'", 'TypeError', "'<' not supported between instances of 'set' and 'bytes'"),
+    ("{1} < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'set' and 'bytearray'"),
+    ('{1} < [1]', 'TypeError', "'<' not supported between instances of 'set' and 'list'"),
+    ('{1} < (1,)', 'TypeError', "'<' not supported between instances of 'set' and 'tuple'"),
+    ('{1} < {1: 1}', 'TypeError', "'<' not supported between instances of 'set' and 'dict'"),
+    ('{1} < range(1)', 'TypeError', "'<' not supported between instances of 'set' and 'range'"),
+    ('{1} <= 1', 'TypeError', "'<=' not supported between instances of 'set' and 'int'"),
+    ('{1} <= 1.5', 'TypeError', "'<=' not supported between instances of 'set' and 'float'"),
+    ('{1} <= 1j', 'TypeError', "'<=' not supported between instances of 'set' and 'complex'"),
+    ('{1} <= True', 'TypeError', "'<=' not supported between instances of 'set' and 'bool'"),
+    ('{1} <= None', 'TypeError', "'<=' not supported between instances of 'set' and 'NoneType'"),
+    ("{1} <= 'a'", 'TypeError', "'<=' not supported between instances of 'set' and 'str'"),
+    ("{1} <= b'a'", 'TypeError', "'<=' not supported between instances of 'set' and 'bytes'"),
+    ("{1} <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'set' and 'bytearray'"),
+    ('{1} <= [1]', 'TypeError', "'<=' not supported between instances of 'set' and 'list'"),
+    ('{1} <= (1,)', 'TypeError', "'<=' not supported between instances of 'set' and 'tuple'"),
+    ('{1} <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'set' and 'dict'"),
+    ('{1} <= range(1)', 'TypeError', "'<=' not supported between instances of 'set' and 'range'"),
+    ('{1} > 1', 'TypeError', "'>' not supported between instances of 'set' and 'int'"),
+    ('{1} > 1.5', 'TypeError', "'>' not supported between instances of 'set' and 'float'"),
+    ('{1} > 1j', 'TypeError', "'>' not supported between instances of 'set' and 'complex'"),
+    ('{1} > True', 'TypeError', "'>' not supported between instances of 'set' and 'bool'"),
+    ('{1} > None', 'TypeError', "'>' not supported between instances of 'set' and 'NoneType'"),
+    ("{1} > 'a'", 'TypeError', "'>' not supported between instances of 'set' and 'str'"),
+    ("{1} > b'a'", 'TypeError', "'>' not supported between instances of 'set' and 'bytes'"),
+    ("{1} > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'set' and 'bytearray'"),
+    ('{1} > [1]', 'TypeError', "'>' not supported between instances of 'set' and 'list'"),
+    ('{1} > (1,)', 'TypeError', "'>' not supported between instances of 'set' and 'tuple'"),
+    ('{1} > {1: 1}', 'TypeError', "'>' not supported between instances of 'set' and 'dict'"),
+    ('{1} > range(1)', 'TypeError', "'>' not supported between instances of 'set' and 'range'"),
+    ('{1} >= 1', 'TypeError', "'>=' not supported between instances of 'set' and 'int'"),
+    ('{1} >= 1.5', 'TypeError', "'>=' not supported between instances of 'set' and 'float'"),
+    ('{1} >= 1j', 'TypeError', "'>=' not supported between instances of 'set' and 'complex'"),
+    ('{1} >= True', 'TypeError', "'>=' not supported between instances of 'set' and 'bool'"),
+    ('{1} >= None', 'TypeError', "'>=' not supported between instances of 'set' and 'NoneType'"),
+    ("{1} >= 'a'", 'TypeError', "'>=' not supported between instances of 'set' and 'str'"),
+    ("{1} >= b'a'", 'TypeError', "'>=' not supported between instances of 'set' and 'bytes'"),
+    ("{1} >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'set' and 'bytearray'"),
+    ('{1} >= [1]', 'TypeError', "'>=' not supported between instances of 'set' and 'list'"),
+    ('{1} >= (1,)', 'TypeError', "'>=' not supported between instances of 'set' and 'tuple'"),
+    ('{1} >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'set' and 'dict'"),
+    ('{1} >= range(1)', 'TypeError', "'>=' not supported between instances of 'set' and 'range'"),
+    ('frozenset({1}) + 1', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'int'"),
+    ('frozenset({1}) + 1.5', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'float'"),
+    ('frozenset({1}) + 1j', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'complex'"),
+    ('frozenset({1}) + True', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'bool'"),
+    ('frozenset({1}) + None', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) + 'a'", 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'str'"),
+    ("frozenset({1}) + b'a'", 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) + [1]', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'list'"),
+    ('frozenset({1}) + (1,)', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'dict'"),
+    ('frozenset({1}) + {1}', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'set'"),
+    ('frozenset({1}) + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) + range(1)', 'TypeError', "unsupported operand type(s) for +: 'frozenset' and 'range'"),
+    ('frozenset({1}) - 1', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'int'"),
+    ('frozenset({1}) - 1.5', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'float'"),
+    ('frozenset({1}) - 1j', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'complex'"),
+    ('frozenset({1}) - True', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'bool'"),
+    ('frozenset({1}) - None', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) - 'a'", 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'str'"),
+    ("frozenset({1}) - b'a'", 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) - [1]', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'list'"),
+    ('frozenset({1}) - (1,)', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'dict'"),
+    ('frozenset({1}) - range(1)', 'TypeError', "unsupported operand type(s) for -: 'frozenset' and 'range'"),
+    ('frozenset({1}) * 1', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'int'"),
+    ('frozenset({1}) * 1.5', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'float'"),
+    ('frozenset({1}) * 1j', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'complex'"),
+    ('frozenset({1}) * True', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'bool'"),
+    ('frozenset({1}) * None', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ("frozenset({1}) * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ("frozenset({1}) * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ('frozenset({1}) * [1]', 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ('frozenset({1}) * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'frozenset'"),
+    ('frozenset({1}) * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'dict'"),
+    ('frozenset({1}) * {1}', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'set'"),
+    ('frozenset({1}) * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) * range(1)', 'TypeError', "unsupported operand type(s) for *: 'frozenset' and 'range'"),
+    ('frozenset({1}) / 1', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'int'"),
+    ('frozenset({1}) / 1.5', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'float'"),
+    ('frozenset({1}) / 1j', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'complex'"),
+    ('frozenset({1}) / True', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'bool'"),
+    ('frozenset({1}) / None', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) / 'a'", 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'str'"),
+    ("frozenset({1}) / b'a'", 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) / [1]', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'list'"),
+    ('frozenset({1}) / (1,)', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'dict'"),
+    ('frozenset({1}) / {1}', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'set'"),
+    ('frozenset({1}) / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) / range(1)', 'TypeError', "unsupported operand type(s) for /: 'frozenset' and 'range'"),
+    ('frozenset({1}) // 1', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'int'"),
+    ('frozenset({1}) // 1.5', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'float'"),
+    ('frozenset({1}) // 1j', 'TypeError', "can't take floor of complex number."),
+    ('frozenset({1}) // True', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'bool'"),
+    ('frozenset({1}) // None', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) // 'a'", 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'str'"),
+    ("frozenset({1}) // b'a'", 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) // [1]', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'list'"),
+    ('frozenset({1}) // (1,)', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'dict'"),
+    ('frozenset({1}) // {1}', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'set'"),
+    ('frozenset({1}) // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) // range(1)', 'TypeError', "unsupported operand type(s) for //: 'frozenset' and 'range'"),
+    ('frozenset({1}) % 1', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'int'"),
+    ('frozenset({1}) % 1.5', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'float'"),
+    ('frozenset({1}) % 1j', 'TypeError', "can't mod complex numbers."),
+    ('frozenset({1}) % True', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'bool'"),
+    ('frozenset({1}) % None', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) % 'a'", 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'str'"),
+    ("frozenset({1}) % b'a'", 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) % [1]', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'list'"),
+    ('frozenset({1}) % (1,)', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'dict'"),
+    ('frozenset({1}) % {1}', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'set'"),
+    ('frozenset({1}) % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) % range(1)', 'TypeError', "unsupported operand type(s) for %: 'frozenset' and 'range'"),
+    ('frozenset({1}) ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'int'"),
+    ('frozenset({1}) ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'float'"),
+    ('frozenset({1}) ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'complex'"),
+    ('frozenset({1}) ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'bool'"),
+    ('frozenset({1}) ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'str'"),
+    ("frozenset({1}) ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'bytes'"),
+    ("frozenset({1}) ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'list'"),
+    ('frozenset({1}) ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'tuple'"),
+    ('frozenset({1}) ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'dict'"),
+    ('frozenset({1}) ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'set'"),
+    ('frozenset({1}) ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'frozenset' and 'range'"),
+    ('frozenset({1}) @ 1', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'int'"),
+    ('frozenset({1}) @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'float'"),
+    ('frozenset({1}) @ 1j', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'complex'"),
+    ('frozenset({1}) @ True', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'bool'"),
+    ('frozenset({1}) @ None', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'str'"),
+    ("frozenset({1}) @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) @ [1]', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'list'"),
+    ('frozenset({1}) @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'dict'"),
+    ('frozenset({1}) @ {1}', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'set'"),
+    ('frozenset({1}) @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'frozenset' and 'range'"),
+    ('frozenset({1}) << 1', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'int'"),
+    ('frozenset({1}) << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'float'"),
+    ('frozenset({1}) << 1j', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'complex'"),
+    ('frozenset({1}) << True', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'bool'"),
+    ('frozenset({1}) << None', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'str'"),
+    ("frozenset({1}) << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) << [1]', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'list'"),
+    ('frozenset({1}) << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'dict'"),
+    ('frozenset({1}) << {1}', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'set'"),
+    ('frozenset({1}) << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'frozenset' and 'range'"),
+    ('frozenset({1}) >> 1', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'int'"),
+    ('frozenset({1}) >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'float'"),
+    ('frozenset({1}) >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'complex'"),
+    ('frozenset({1}) >> True', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'bool'"),
+    ('frozenset({1}) >> None', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'str'"),
+    ("frozenset({1}) >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'list'"),
+    ('frozenset({1}) >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'dict'"),
+    ('frozenset({1}) >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'set'"),
+    ('frozenset({1}) >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'frozenset'"),
+    ('frozenset({1}) >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'frozenset' and 'range'"),
+    ('frozenset({1}) & 1', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'int'"),
+    ('frozenset({1}) & 1.5', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'float'"),
+    ('frozenset({1}) & 1j', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'complex'"),
+    ('frozenset({1}) & True', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'bool'"),
+    ('frozenset({1}) & None', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) & 'a'", 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'str'"),
+    ("frozenset({1}) & b'a'", 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) & [1]', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'list'"),
+    ('frozenset({1}) & (1,)', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'dict'"),
+    ('frozenset({1}) & range(1)', 'TypeError', "unsupported operand type(s) for &: 'frozenset' and 'range'"),
+    ('frozenset({1}) | 1', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'int'"),
+    ('frozenset({1}) | 1.5', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'float'"),
+    ('frozenset({1}) | 1j', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'complex'"),
+    ('frozenset({1}) | True', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'bool'"),
+    ('frozenset({1}) | None', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) | 'a'", 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'str'"),
+    ("frozenset({1}) | b'a'", 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) | [1]', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'list'"),
+    ('frozenset({1}) | (1,)', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'dict'"),
+    ('frozenset({1}) | range(1)', 'TypeError', "unsupported operand type(s) for |: 'frozenset' and 'range'"),
+    ('frozenset({1}) ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'int'"),
+    ('frozenset({1}) ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'float'"),
+    ('frozenset({1}) ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'complex'"),
+    ('frozenset({1}) ^ True', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'bool'"),
+    ('frozenset({1}) ^ None', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'str'"),
+    ("frozenset({1}) ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'bytes'"),
+    ("frozenset({1}) ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'list'"),
+    ('frozenset({1}) ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'tuple'"),
+    ('frozenset({1}) ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'dict'"),
+    ('frozenset({1}) ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'frozenset' and 'range'"),
+    ('frozenset({1}) < 1', 'TypeError', "'<' not supported between instances of 'frozenset' and 'int'"),
+    ('frozenset({1}) < 1.5', 'TypeError', "'<' not supported between instances of 'frozenset' and 'float'"),
+    ('frozenset({1}) < 1j', 'TypeError', "'<' not supported between instances of 'frozenset' and 'complex'"),
+    ('frozenset({1}) < True', 'TypeError', "'<' not supported between instances of 'frozenset' and 'bool'"),
+    ('frozenset({1}) < None', 'TypeError', "'<' not supported between instances of 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) < 'a'", 'TypeError', "'<' not supported between instances of 'frozenset' and 'str'"),
+    ("frozenset({1}) < b'a'", 'TypeError', "'<' not supported between instances of 'frozenset' and 'bytes'"),
+    ("frozenset({1}) < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) < [1]', 'TypeError', "'<' not supported between instances of 'frozenset' and 'list'"),
+    ('frozenset({1}) < (1,)', 'TypeError', "'<' not supported between instances of 'frozenset' and 'tuple'"),
+    ('frozenset({1}) < {1: 1}', 'TypeError', "'<' not supported between instances of 'frozenset' and 'dict'"),
+    ('frozenset({1}) < range(1)', 'TypeError', "'<' not supported between instances of 'frozenset' and 'range'"),
+    ('frozenset({1}) <= 1', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'int'"),
+    ('frozenset({1}) <= 1.5', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'float'"),
+    ('frozenset({1}) <= 1j', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'complex'"),
+    ('frozenset({1}) <= True', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'bool'"),
+    ('frozenset({1}) <= None', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) <= 'a'", 'TypeError', "'<=' not supported between instances of 'frozenset' and 'str'"),
+    ("frozenset({1}) <= b'a'", 'TypeError', "'<=' not supported between instances of 'frozenset' and 'bytes'"),
+    ("frozenset({1}) <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) <= [1]', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'list'"),
+    ('frozenset({1}) <= (1,)', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'tuple'"),
+    ('frozenset({1}) <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'dict'"),
+    ('frozenset({1}) <= range(1)', 'TypeError', "'<=' not supported between instances of 'frozenset' and 'range'"),
+    ('frozenset({1}) > 1', 'TypeError', "'>' not supported between instances of 'frozenset' and 'int'"),
+    ('frozenset({1}) > 1.5', 'TypeError', "'>' not supported between instances of 'frozenset' and 'float'"),
+    ('frozenset({1}) > 1j', 'TypeError', "'>' not supported between instances of 'frozenset' and 'complex'"),
+    ('frozenset({1}) > True', 'TypeError', "'>' not supported between instances of 'frozenset' and 'bool'"),
+    ('frozenset({1}) > None', 'TypeError', "'>' not supported between instances of 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) > 'a'", 'TypeError', "'>' not supported between instances of 'frozenset' and 'str'"),
+    ("frozenset({1}) > b'a'", 'TypeError', "'>' not supported between instances of 'frozenset' and 'bytes'"),
+    ("frozenset({1}) > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) > [1]', 'TypeError', "'>' not supported between instances of 'frozenset' and 'list'"),
+    ('frozenset({1}) > (1,)', 'TypeError', "'>' not supported between instances of 'frozenset' and 'tuple'"),
+    ('frozenset({1}) > {1: 1}', 'TypeError', "'>' not supported between instances of 'frozenset' and 'dict'"),
+    ('frozenset({1}) > range(1)', 'TypeError', "'>' not supported between instances of 'frozenset' and 'range'"),
+    ('frozenset({1}) >= 1', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'int'"),
+    ('frozenset({1}) >= 1.5', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'float'"),
+    ('frozenset({1}) >= 1j', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'complex'"),
+    ('frozenset({1}) >= True', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'bool'"),
+    ('frozenset({1}) >= None', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'NoneType'"),
+    ("frozenset({1}) >= 'a'", 'TypeError', "'>=' not supported between instances of 'frozenset' and 'str'"),
+    ("frozenset({1}) >= b'a'", 'TypeError', "'>=' not supported between instances of 'frozenset' and 'bytes'"),
+    ("frozenset({1}) >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'frozenset' and 'bytearray'"),
+    ('frozenset({1}) >= [1]', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'list'"),
+    ('frozenset({1}) >= (1,)', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'tuple'"),
+    ('frozenset({1}) >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'dict'"),
+    ('frozenset({1}) >= range(1)', 'TypeError', "'>=' not supported between instances of 'frozenset' and 'range'"),
+    ('range(1) + 1', 'TypeError', "unsupported operand type(s) for +: 'range' and 'int'"),
+    ('range(1) + 1.5', 'TypeError', "unsupported operand type(s) for +: 'range' and 'float'"),
+    ('range(1) + 1j', 'TypeError', "unsupported operand type(s) for +: 'range' and 'complex'"),
+    ('range(1) + True', 'TypeError', "unsupported operand type(s) for +: 'range' and 'bool'"),
+    ('range(1) + None', 'TypeError', "unsupported operand type(s) for +: 'range' and 'NoneType'"),
+    ("range(1) + 'a'", 'TypeError', "unsupported operand type(s) for +: 'range' and 'str'"),
+    ("range(1) + b'a'", 'TypeError', "unsupported operand type(s) for +: 'range' and 'bytes'"),
+    ("range(1) + bytearray(b'a')", 'TypeError', "unsupported operand type(s) for +: 'range' and 'bytearray'"),
+    ('range(1) + [1]', 'TypeError', "unsupported operand type(s) for +: 'range' and 'list'"),
+    ('range(1) + (1,)', 'TypeError', "unsupported operand type(s) for +: 'range' and 'tuple'"),
+    ('range(1) + {1: 1}', 'TypeError', "unsupported operand type(s) for +: 'range' and 'dict'"),
+    ('range(1) + {1}', 'TypeError', "unsupported operand type(s) for +: 'range' and 'set'"),
+    ('range(1) + frozenset({1})', 'TypeError', "unsupported operand type(s) for +: 'range' and 'frozenset'"),
+    ('range(1) + range(1)', 'TypeError', "unsupported operand type(s) for +: 'range' and 'range'"),
+    ('range(1) - 1', 'TypeError', "unsupported operand type(s) for -: 'range' and 'int'"),
+    ('range(1) - 1.5', 'TypeError', "unsupported operand type(s) for -: 'range' and 'float'"),
+    ('range(1) - 1j', 'TypeError', "unsupported operand type(s) for -: 'range' and 'complex'"),
+    ('range(1) - True', 'TypeError', "unsupported operand type(s) for -: 'range' and 'bool'"),
+    ('range(1) - None', 'TypeError', "unsupported operand type(s) for -: 'range' and 'NoneType'"),
+    ("range(1) - 'a'", 'TypeError', "unsupported operand type(s) for -: 'range' and 'str'"),
+    ("range(1) - b'a'", 'TypeError', "unsupported operand type(s) for -: 'range' and 'bytes'"),
+    ("range(1) - bytearray(b'a')", 'TypeError', "unsupported operand type(s) for -: 'range' and 'bytearray'"),
+    ('range(1) - [1]', 'TypeError', "unsupported operand type(s) for -: 'range' and 'list'"),
+    ('range(1) - (1,)', 'TypeError', "unsupported operand type(s) for -: 'range' and 'tuple'"),
+    ('range(1) - {1: 1}', 'TypeError', "unsupported operand type(s) for -: 'range' and 'dict'"),
+    ('range(1) - {1}', 'TypeError', "unsupported operand type(s) for -: 'range' and 'set'"),
+    ('range(1) - frozenset({1})', 'TypeError', "unsupported operand type(s) for -: 'range' and 'frozenset'"),
+    ('range(1) - range(1)', 'TypeError', "unsupported operand type(s) for -: 'range' and 'range'"),
+    ('range(1) * 1', 'TypeError', "unsupported operand type(s) for *: 'range' and 'int'"),
+    ('range(1) * 1.5', 'TypeError', "unsupported operand type(s) for *: 'range' and 'float'"),
+    ('range(1) * 1j', 'TypeError', "unsupported operand type(s) for *: 'range' and 'complex'"),
+    ('range(1) * True', 'TypeError', "unsupported operand type(s) for *: 'range' and 'bool'"),
+    ('range(1) * None', 'TypeError', "unsupported operand type(s) for *: 'range' and 'NoneType'"),
+    ("range(1) * 'a'", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ("range(1) * b'a'", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ("range(1) * bytearray(b'a')", 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ('range(1) * [1]', 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ('range(1) * (1,)', 'TypeError', "can't multiply sequence by non-int of type 'range'"),
+    ('range(1) * {1: 1}', 'TypeError', "unsupported operand type(s) for *: 'range' and 'dict'"),
+    ('range(1) * {1}', 'TypeError', "unsupported operand type(s) for *: 'range' and 'set'"),
+    ('range(1) * frozenset({1})', 'TypeError', "unsupported operand type(s) for *: 'range' and 'frozenset'"),
+    ('range(1) * range(1)', 'TypeError', "unsupported operand type(s) for *: 'range' and 'range'"),
+    ('range(1) / 1', 'TypeError', "unsupported operand type(s) for /: 'range' and 'int'"),
+    ('range(1) / 1.5', 'TypeError', "unsupported operand type(s) for /: 'range' and 'float'"),
+    ('range(1) / 1j', 'TypeError', "unsupported operand type(s) for /: 'range' and 'complex'"),
+    ('range(1) / True', 'TypeError', "unsupported operand type(s) for /: 'range' and 'bool'"),
+    ('range(1) / None', 'TypeError', "unsupported operand type(s) for /: 'range' and 'NoneType'"),
+    ("range(1) / 'a'", 'TypeError', "unsupported operand type(s) for /: 'range' and 'str'"),
+    ("range(1) / b'a'", 'TypeError', "unsupported operand type(s) for /: 'range' and 'bytes'"),
+    ("range(1) / bytearray(b'a')", 'TypeError', "unsupported operand type(s) for /: 'range' and 'bytearray'"),
+    ('range(1) / [1]', 'TypeError', "unsupported operand type(s) for /: 'range' and 'list'"),
+    ('range(1) / (1,)', 'TypeError', "unsupported operand type(s) for /: 'range' and 'tuple'"),
+    ('range(1) / {1: 1}', 'TypeError', "unsupported operand type(s) for /: 'range' and 'dict'"),
+    ('range(1) / {1}', 'TypeError', "unsupported operand type(s) for /: 'range' and 'set'"),
+    ('range(1) / frozenset({1})', 'TypeError', "unsupported operand type(s) for /: 'range' and 'frozenset'"),
+    ('range(1) / range(1)', 'TypeError', "unsupported operand type(s) for /: 'range' and 'range'"),
+    ('range(1) // 1', 'TypeError', "unsupported operand type(s) for //: 'range' and 'int'"),
+    ('range(1) // 1.5', 'TypeError', "unsupported operand type(s) for //: 'range' and 'float'"),
+    ('range(1) // 1j', 'TypeError', "can't take floor of complex number."),
+    ('range(1) // True', 'TypeError', "unsupported operand type(s) for //: 'range' and 'bool'"),
+    ('range(1) // None', 'TypeError', "unsupported operand type(s) for //: 'range' and 'NoneType'"),
+    ("range(1) // 'a'", 'TypeError', "unsupported operand type(s) for //: 'range' and 'str'"),
+    ("range(1) // b'a'", 'TypeError', "unsupported operand type(s) for //: 'range' and 'bytes'"),
+    ("range(1) // bytearray(b'a')", 'TypeError', "unsupported operand type(s) for //: 'range' and 'bytearray'"),
+    ('range(1) // [1]', 'TypeError', "unsupported operand type(s) for //: 'range' and 'list'"),
+    ('range(1) // (1,)', 'TypeError', "unsupported operand type(s) for //: 'range' and 'tuple'"),
+    ('range(1) // {1: 1}', 'TypeError', "unsupported operand type(s) for //: 'range' and 'dict'"),
+    ('range(1) // {1}', 'TypeError', "unsupported operand type(s) for //: 'range' and 'set'"),
+    ('range(1) // frozenset({1})', 'TypeError', "unsupported operand type(s) for //: 'range' and 'frozenset'"),
+    ('range(1) // range(1)', 'TypeError', "unsupported operand type(s) for //: 'range' and 'range'"),
+    ('range(1) % 1', 'TypeError', "unsupported operand type(s) for %: 'range' and 'int'"),
+    ('range(1) % 1.5', 'TypeError', "unsupported operand type(s) for %: 'range' and 'float'"),
+    ('range(1) % 1j', 'TypeError', "can't mod complex numbers."),
+    ('range(1) % True', 'TypeError', "unsupported operand type(s) for %: 'range' and 'bool'"),
+    ('range(1) % None', 'TypeError', "unsupported operand type(s) for %: 'range' and 'NoneType'"),
+    ("range(1) % 'a'", 'TypeError', "unsupported operand type(s) for %: 'range' and 'str'"),
+    ("range(1) % b'a'", 'TypeError', "unsupported operand type(s) for %: 'range' and 'bytes'"),
+    ("range(1) % bytearray(b'a')", 'TypeError', "unsupported operand type(s) for %: 'range' and 'bytearray'"),
+    ('range(1) % [1]', 'TypeError', "unsupported operand type(s) for %: 'range' and 'list'"),
+    ('range(1) % (1,)', 'TypeError', "unsupported operand type(s) for %: 'range' and 'tuple'"),
+    ('range(1) % {1: 1}', 'TypeError', "unsupported operand type(s) for %: 'range' and 'dict'"),
+    ('range(1) % {1}', 'TypeError', "unsupported operand type(s) for %: 'range' and 'set'"),
+    ('range(1) % frozenset({1})', 'TypeError', "unsupported operand type(s) for %: 'range' and 'frozenset'"),
+    ('range(1) % range(1)', 'TypeError', "unsupported operand type(s) for %: 'range' and 'range'"),
+    ('range(1) ** 1', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'int'"),
+    ('range(1) ** 1.5', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'float'"),
+    ('range(1) ** 1j', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'complex'"),
+    ('range(1) ** True', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'bool'"),
+    ('range(1) ** None', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'NoneType'"),
+    ("range(1) ** 'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'str'"),
+    ("range(1) ** b'a'", 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'bytes'"),
+    ("range(1) ** bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'bytearray'"),
+    ('range(1) ** [1]', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'list'"),
+    ('range(1) ** (1,)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'tuple'"),
+    ('range(1) ** {1: 1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'dict'"),
+    ('range(1) ** {1}', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'set'"),
+    ('range(1) ** frozenset({1})', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'frozenset'"),
+    ('range(1) ** range(1)', 'TypeError', "unsupported operand type(s) for ** or pow(): 'range' and 'range'"),
+    ('range(1) @ 1', 'TypeError', "unsupported operand type(s) for @: 'range' and 'int'"),
+    ('range(1) @ 1.5', 'TypeError', "unsupported operand type(s) for @: 'range' and 'float'"),
+    ('range(1) @ 1j', 'TypeError', "unsupported operand type(s) for @: 'range' and 'complex'"),
+    ('range(1) @ True', 'TypeError', "unsupported operand type(s) for @: 'range' and 'bool'"),
+    ('range(1) @ None', 'TypeError', "unsupported operand type(s) for @: 'range' and 'NoneType'"),
+    ("range(1) @ 'a'", 'TypeError', "unsupported operand type(s) for @: 'range' and 'str'"),
+    ("range(1) @ b'a'", 'TypeError', "unsupported operand type(s) for @: 'range' and 'bytes'"),
+    ("range(1) @ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for @: 'range' and 'bytearray'"),
+    ('range(1) @ [1]', 'TypeError', "unsupported operand type(s) for @: 'range' and 'list'"),
+    ('range(1) @ (1,)', 'TypeError', "unsupported operand type(s) for @: 'range' and 'tuple'"),
+    ('range(1) @ {1: 1}', 'TypeError', "unsupported operand type(s) for @: 'range' and 'dict'"),
+    ('range(1) @ {1}', 'TypeError', "unsupported operand type(s) for @: 'range' and 'set'"),
+    ('range(1) @ frozenset({1})', 'TypeError', "unsupported operand type(s) for @: 'range' and 'frozenset'"),
+    ('range(1) @ range(1)', 'TypeError', "unsupported operand type(s) for @: 'range' and 'range'"),
+    ('range(1) << 1', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'int'"),
+    ('range(1) << 1.5', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'float'"),
+    ('range(1) << 1j', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'complex'"),
+    ('range(1) << True', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'bool'"),
+    ('range(1) << None', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'NoneType'"),
+    ("range(1) << 'a'", 'TypeError', "unsupported operand type(s) for <<: 'range' and 'str'"),
+    ("range(1) << b'a'", 'TypeError', "unsupported operand type(s) for <<: 'range' and 'bytes'"),
+    ("range(1) << bytearray(b'a')", 'TypeError', "unsupported operand type(s) for <<: 'range' and 'bytearray'"),
+    ('range(1) << [1]', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'list'"),
+    ('range(1) << (1,)', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'tuple'"),
+    ('range(1) << {1: 1}', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'dict'"),
+    ('range(1) << {1}', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'set'"),
+    ('range(1) << frozenset({1})', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'frozenset'"),
+    ('range(1) << range(1)', 'TypeError', "unsupported operand type(s) for <<: 'range' and 'range'"),
+    ('range(1) >> 1', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'int'"),
+    ('range(1) >> 1.5', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'float'"),
+    ('range(1) >> 1j', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'complex'"),
+    ('range(1) >> True', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'bool'"),
+    ('range(1) >> None', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'NoneType'"),
+    ("range(1) >> 'a'", 'TypeError', "unsupported operand type(s) for >>: 'range' and 'str'"),
+    ("range(1) >> b'a'", 'TypeError', "unsupported operand type(s) for >>: 'range' and 'bytes'"),
+    ("range(1) >> bytearray(b'a')", 'TypeError', "unsupported operand type(s) for >>: 'range' and 'bytearray'"),
+    ('range(1) >> [1]', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'list'"),
+    ('range(1) >> (1,)', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'tuple'"),
+    ('range(1) >> {1: 1}', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'dict'"),
+    ('range(1) >> {1}', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'set'"),
+    ('range(1) >> frozenset({1})', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'frozenset'"),
+    ('range(1) >> range(1)', 'TypeError', "unsupported operand type(s) for >>: 'range' and 'range'"),
+    ('range(1) & 1', 'TypeError', "unsupported operand type(s) for &: 'range' and 'int'"),
+    ('range(1) & 1.5', 'TypeError', "unsupported operand type(s) for &: 'range' and 'float'"),
+    ('range(1) & 1j', 'TypeError', "unsupported operand type(s) for &: 'range' and 'complex'"),
+    ('range(1) & True', 'TypeError', "unsupported operand type(s) for &: 'range' and 'bool'"),
+    ('range(1) & None', 'TypeError', "unsupported operand type(s) for &: 'range' and 'NoneType'"),
+    ("range(1) & 'a'", 'TypeError', "unsupported operand type(s) for &: 'range' and 'str'"),
+    ("range(1) & b'a'", 'TypeError', "unsupported operand type(s) for &: 'range' and 'bytes'"),
+    ("range(1) & bytearray(b'a')", 'TypeError', "unsupported operand type(s) for &: 'range' and 'bytearray'"),
+    ('range(1) & [1]', 'TypeError', "unsupported operand type(s) for &: 'range' and 'list'"),
+    ('range(1) & (1,)', 'TypeError', "unsupported operand type(s) for &: 'range' and 'tuple'"),
+    ('range(1) & {1: 1}', 'TypeError', "unsupported operand type(s) for &: 'range' and 'dict'"),
+    ('range(1) & {1}', 'TypeError', "unsupported operand type(s) for &: 'range' and 'set'"),
+    ('range(1) & frozenset({1})', 'TypeError', "unsupported operand type(s) for &: 'range' and 'frozenset'"),
+    ('range(1) & range(1)', 'TypeError', "unsupported operand type(s) for &: 'range' and 'range'"),
+    ('range(1) | 1', 'TypeError', "unsupported operand type(s) for |: 'range' and 'int'"),
+    ('range(1) | 1.5', 'TypeError', "unsupported operand type(s) for |: 'range' and 'float'"),
+    ('range(1) | 1j', 'TypeError', "unsupported operand type(s) for |: 'range' and 'complex'"),
+    ('range(1) | True', 'TypeError', "unsupported operand type(s) for |: 'range' and 'bool'"),
+    ('range(1) | None', 'TypeError', "unsupported operand type(s) for |: 'range' and 'NoneType'"),
+    ("range(1) | 'a'", 'TypeError', "unsupported operand type(s) for |: 'range' and 'str'"),
+    ("range(1) | b'a'", 'TypeError', "unsupported operand type(s) for |: 'range' and 'bytes'"),
+    ("range(1) | bytearray(b'a')", 'TypeError', "unsupported operand type(s) for |: 'range' and 'bytearray'"),
+    ('range(1) | [1]', 'TypeError', "unsupported operand type(s) for |: 'range' and 'list'"),
+    ('range(1) | (1,)', 'TypeError', "unsupported operand type(s) for |: 'range' and 'tuple'"),
+    ('range(1) | {1: 1}', 'TypeError', "unsupported operand type(s) for |: 'range' and 'dict'"),
+    ('range(1) | {1}', 'TypeError', "unsupported operand type(s) for |: 'range' and 'set'"),
+    ('range(1) | frozenset({1})', 'TypeError', "unsupported operand type(s) for |: 'range' and 'frozenset'"),
+    ('range(1) | range(1)', 'TypeError', "unsupported operand type(s) for |: 'range' and 'range'"),
+    ('range(1) ^ 1', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'int'"),
+    ('range(1) ^ 1.5', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'float'"),
+    ('range(1) ^ 1j', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'complex'"),
+    ('range(1) ^ True', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'bool'"),
+    ('range(1) ^ None', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'NoneType'"),
+    ("range(1) ^ 'a'", 'TypeError', "unsupported operand type(s) for ^: 'range' and 'str'"),
+    ("range(1) ^ b'a'", 'TypeError', "unsupported operand type(s) for ^: 'range' and 'bytes'"),
+    ("range(1) ^ bytearray(b'a')", 'TypeError', "unsupported operand type(s) for ^: 'range' and 'bytearray'"),
+    ('range(1) ^ [1]', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'list'"),
+    ('range(1) ^ (1,)', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'tuple'"),
+    ('range(1) ^ {1: 1}', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'dict'"),
+    ('range(1) ^ {1}', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'set'"),
+    ('range(1) ^ frozenset({1})', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'frozenset'"),
+    ('range(1) ^ range(1)', 'TypeError', "unsupported operand type(s) for ^: 'range' and 'range'"),
+    ('range(1) < 1', 'TypeError', "'<' not supported between instances of 'range' and 'int'"),
+    ('range(1) < 1.5', 'TypeError', "'<' not supported between instances of 'range' and 'float'"),
+    ('range(1) < 1j', 'TypeError', "'<' not supported between instances of 'range' and 'complex'"),
+    ('range(1) < True', 'TypeError', "'<' not supported between instances of 'range' and 'bool'"),
+    ('range(1) < None', 'TypeError', "'<' not supported between instances of 'range' and 'NoneType'"),
+    ("range(1) < 'a'", 'TypeError', "'<' not supported between instances of 'range' and 'str'"),
+    ("range(1) < b'a'", 'TypeError', "'<' not supported between instances of 'range' and 'bytes'"),
+    ("range(1) < bytearray(b'a')", 'TypeError', "'<' not supported between instances of 'range' and 'bytearray'"),
+    ('range(1) < [1]', 'TypeError', "'<' not supported between instances of 'range' and 'list'"),
+    ('range(1) < (1,)', 'TypeError', "'<' not supported between instances of 'range' and 'tuple'"),
+    ('range(1) < {1: 1}', 'TypeError', "'<' not supported between instances of 'range' and 'dict'"),
+    ('range(1) < {1}', 'TypeError', "'<' not supported between instances of 'range' and 'set'"),
+    ('range(1) < frozenset({1})', 'TypeError', "'<' not supported between instances of 'range' and 'frozenset'"),
+    ('range(1) < range(1)', 'TypeError', "'<' not supported between instances of 'range' and 'range'"),
+    ('range(1) <= 1', 'TypeError', "'<=' not supported between instances of 'range' and 'int'"),
+    ('range(1) <= 1.5', 'TypeError', "'<=' not supported between instances of 'range' and 'float'"),
+    ('range(1) <= 1j', 'TypeError', "'<=' not supported between instances of 'range' and 'complex'"),
+    ('range(1) <= True', 'TypeError', "'<=' not supported between instances of 'range' and 'bool'"),
+    ('range(1) <= None', 'TypeError', "'<=' not supported between instances of 'range' and 'NoneType'"),
+    ("range(1) <= 'a'", 'TypeError', "'<=' not supported between instances of 'range' and 'str'"),
+    ("range(1) <= b'a'", 'TypeError', "'<=' not supported between instances of 'range' and 'bytes'"),
+    ("range(1) <= bytearray(b'a')", 'TypeError', "'<=' not supported between instances of 'range' and 'bytearray'"),
+    ('range(1) <= [1]', 'TypeError', "'<=' not supported between instances of 'range' and 'list'"),
+    ('range(1) <= (1,)', 'TypeError', "'<=' not supported between instances of 'range' and 'tuple'"),
+    ('range(1) <= {1: 1}', 'TypeError', "'<=' not supported between instances of 'range' and 'dict'"),
+    ('range(1) <= {1}', 'TypeError', "'<=' not supported between instances of 'range' and 'set'"),
+    ('range(1) <= frozenset({1})', 'TypeError', "'<=' not supported between instances of 'range' and 'frozenset'"),
+    ('range(1) <= range(1)', 'TypeError', "'<=' not supported between instances of 'range' and 'range'"),
+    ('range(1) > 1', 'TypeError', "'>' not supported between instances of 'range' and 'int'"),
+    ('range(1) > 1.5', 'TypeError', "'>' not supported between instances of 'range' and 'float'"),
+    ('range(1) > 1j', 'TypeError', "'>' not supported between instances of 'range' and 'complex'"),
+    ('range(1) > True', 'TypeError', "'>' not supported between instances of 'range' and 'bool'"),
+    ('range(1) > None', 'TypeError', "'>' not supported between instances of 'range' and 'NoneType'"),
+    ("range(1) > 'a'", 'TypeError', "'>' not supported between instances of 'range' and 'str'"),
+    ("range(1) > b'a'", 'TypeError', "'>' not supported between instances of 'range' and 'bytes'"),
+    ("range(1) > bytearray(b'a')", 'TypeError', "'>' not supported between instances of 'range' and 'bytearray'"),
+    ('range(1) > [1]', 'TypeError', "'>' not supported between instances of 'range' and 'list'"),
+    ('range(1) > (1,)', 'TypeError', "'>' not supported between instances of 'range' and 'tuple'"),
+    ('range(1) > {1: 1}', 'TypeError', "'>' not supported between instances of 'range' and 'dict'"),
+    ('range(1) > {1}', 'TypeError', "'>' not supported between instances of 'range' and 'set'"),
+    ('range(1) > frozenset({1})', 'TypeError', "'>' not supported between instances of 'range' and 'frozenset'"),
+    ('range(1) > range(1)', 'TypeError', "'>' not supported between instances of 'range' and 'range'"),
+    ('range(1) >= 1', 'TypeError', "'>=' not supported between instances of 'range' and 'int'"),
+    ('range(1) >= 1.5', 'TypeError', "'>=' not supported between instances of 'range' and 'float'"),
+    ('range(1) >= 1j', 'TypeError', "'>=' not supported between instances of 'range' and 'complex'"),
+    ('range(1) >= True', 'TypeError', "'>=' not supported between instances of 'range' and 'bool'"),
+    ('range(1) >= None', 'TypeError', "'>=' not supported between instances of 'range' and 'NoneType'"),
+    ("range(1) >= 'a'", 'TypeError', "'>=' not supported between instances of 'range' and 'str'"),
+    ("range(1) >= b'a'", 'TypeError', "'>=' not supported between instances of 'range' and 'bytes'"),
+    ("range(1) >= bytearray(b'a')", 'TypeError', "'>=' not supported between instances of 'range' and 'bytearray'"),
+    ('range(1) >= [1]', 'TypeError', "'>=' not supported between instances of 'range' and 'list'"),
+    ('range(1) >= (1,)', 'TypeError', "'>=' not supported between instances of 'range' and 'tuple'"),
+    ('range(1) >= {1: 1}', 'TypeError', "'>=' not supported between instances of 'range' and 'dict'"),
+    ('range(1) >= {1}', 'TypeError', "'>=' not supported between instances of 'range' and 'set'"),
+    ('range(1) >= frozenset({1})', 'TypeError', "'>=' not supported between instances of 'range' and 'frozenset'"),
+    ('range(1) >= range(1)', 'TypeError', "'>=' not supported between instances of 'range' and 'range'"),
+    ('-None', 'TypeError', "bad operand type for unary -: 'NoneType'"),
+    ("-'a'", 'TypeError', "bad operand type for unary -: 'str'"),
+    ("-b'a'", 'TypeError', "bad operand type for unary -: 'bytes'"),
+    ("-bytearray(b'a')", 'TypeError', "bad operand type for unary -: 'bytearray'"),
+    ('-[1]', 'TypeError', "bad operand type for unary -: 'list'"),
+    ('-(1,)', 'TypeError', "bad operand type for unary -: 'tuple'"),
+    ('-{1: 1}', 'TypeError', "bad operand type for unary -: 'dict'"),
+    ('-{1}', 'TypeError', "bad operand type for unary -: 'set'"),
+    ('-frozenset({1})', 'TypeError', "bad operand type for unary -: 'frozenset'"),
+    ('-range(1)', 'TypeError', "bad operand type for unary -: 'range'"),
+    ('+None', 'TypeError', "bad operand type for unary +: 'NoneType'"),
+    ("+'a'", 'TypeError', "bad operand type for unary +: 'str'"),
+    ("+b'a'", 'TypeError', "bad operand type for unary +: 'bytes'"),
+    ("+bytearray(b'a')", 'TypeError', "bad operand type for unary +: 'bytearray'"),
+    ('+[1]', 'TypeError', "bad operand type for unary +: 'list'"),
+    ('+(1,)', 'TypeError', "bad operand type for unary +: 'tuple'"),
+    ('+{1: 1}', 'TypeError', "bad operand type for unary +: 'dict'"),
+    ('+{1}', 'TypeError', "bad operand type for unary +: 'set'"),
+    ('+frozenset({1})', 'TypeError', "bad operand type for unary +: 'frozenset'"),
+    ('+range(1)', 'TypeError', "bad operand type for unary +: 'range'"),
+    ('~1.5', 'TypeError', "bad operand type for unary ~: 'float'"),
+    ('~1j', 'TypeError', "bad operand type for unary ~: 'complex'"),
+    ('~None', 'TypeError', "bad operand type for unary ~: 'NoneType'"),
+    ("~'a'", 'TypeError', "bad operand type for unary ~: 'str'"),
+    ("~b'a'", 'TypeError', "bad operand type for unary ~: 'bytes'"),
+    ("~bytearray(b'a')", 'TypeError', "bad operand type for unary ~: 'bytearray'"),
+    ('~[1]', 'TypeError', "bad operand type for unary ~: 'list'"),
+    ('~(1,)', 'TypeError', "bad operand type for unary ~: 'tuple'"),
+    ('~{1: 1}', 'TypeError', "bad operand type for unary ~: 'dict'"),
+    ('~{1}', 'TypeError', "bad operand type for unary ~: 'set'"),
+    ('~frozenset({1})', 'TypeError', "bad operand type for unary ~: 'frozenset'"),
+    ('~range(1)', 'TypeError', "bad operand type for unary ~: 'range'"),
+    ('iter(1)', 'TypeError', "'int' object is not iterable"),
+    ('iter(1.5)', 'TypeError', "'float' object is not iterable"),
+    ('iter(1j)', 'TypeError', "'complex' object is not iterable"),
+    ('iter(True)', 'TypeError', "'bool' object is not iterable"),
+    ('iter(None)', 'TypeError', "'NoneType' object is not iterable"),
+    ('for x in 1: pass', 'TypeError', "'int' object is not iterable"),
+    ('for x in 1.5: pass', 'TypeError', "'float' object is not iterable"),
+    ('for x in 1j: pass', 'TypeError', "'complex' object is not iterable"),
+    ('for x in True: pass', 'TypeError', "'bool' object is not iterable"),
+    ('for x in None: pass', 'TypeError', "'NoneType' object is not iterable"),
+    ('a, b = 1', 'TypeError', 'cannot unpack non-iterable int object'),
+    ('a, b = 1.5', 'TypeError', 'cannot unpack non-iterable float object'),
+    ('a, b = 1j', 'TypeError', 'cannot unpack non-iterable complex object'),
+    ('a, b = True', 'TypeError', 'cannot unpack non-iterable bool object'),
+    ('a, b = None', 'TypeError', 'cannot unpack non-iterable NoneType object'),
+    ("a, b = 'a'", 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ("a, b = b'a'", 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ("a, b = bytearray(b'a')", 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = [1]', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = (1,)', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = {1: 1}', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = {1}', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = frozenset({1})', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('a, b = range(1)', 'ValueError', 'not enough values to unpack (expected 2, got 1)'),
+    ('[*1]', 'TypeError', "'int' object is not iterable"),
+    ('[*1.5]', 'TypeError', "'float' object is not iterable"),
+    ('[*1j]', 'TypeError', "'complex' object is not iterable"),
+    ('[*True]', 'TypeError', "'bool' object is not iterable"),
+    ('[*None]', 'TypeError', "'NoneType' object is not iterable"),
+    ('1()', 'TypeError', "'int' object is not callable"),
+    ('1.5()', 'TypeError', "'float' object is not callable"),
+    ('1j()', 'TypeError', "'complex' object is not callable"),
+    ('True()', 'TypeError', "'bool' object is not callable"),
+    ('None()', 'TypeError', "'NoneType' object is not callable"),
+    ("'a'()", 'TypeError', "'str' object is not callable"),
+    ("b'a'()", 'TypeError', "'bytes' object is not callable"),
+    ("bytearray(b'a')()", 'TypeError', "'bytearray' object is not callable"),
+    ('[1]()', 'TypeError', "'list' object is not callable"),
+    ('(1,)()', 'TypeError', "'tuple' object is not callable"),
+    ('{1: 1}()', 'TypeError', "'dict' object is not callable"),
+    ('{1}()', 'TypeError', "'set' object is not callable"),
+    ('frozenset({1})()', 'TypeError', "'frozenset' object is not callable"),
+    ('range(1)()', 'TypeError', "'range' object is not callable"),
+    ('1[0]', 'TypeError', "'int' object is not subscriptable"),
+    ('1.5[0]', 'TypeError', "'float' object is not subscriptable"),
+    ('1j[0]', 'TypeError', "'complex' object is not subscriptable"),
+    ('True[0]', 'TypeError', "'bool' object is not subscriptable"),
+    ('None[0]', 'TypeError', "'NoneType' object is not subscriptable"),
+    ('{1: 1}[0]', 'KeyError', '0'),
+    ('{1}[0]', 'TypeError', "'set' object is not subscriptable"),
+    ('frozenset({1})[0]', 'TypeError', "'frozenset' object is not subscriptable"),
+    ("1['a']", 'TypeError', "'int' object is not subscriptable"),
+    ("1.5['a']", 'TypeError', "'float' object is not subscriptable"),
+    ("1j['a']", 'TypeError', "'complex' object is not subscriptable"),
+    ("True['a']", 'TypeError', "'bool' object is not subscriptable"),
+    ("None['a']", 'TypeError', "'NoneType' object is not subscriptable"),
+    ("'a'['a']", 'TypeError', 'string indices must be integers'),
+    ("b'a'['a']", 'TypeError', 'byte indices must be integers or slices, not str'),
+    ("bytearray(b'a')['a']", 'TypeError', 'bytearray indices must be integers or slices, not str'),
+    ("[1]['a']", 'TypeError', 'list indices must be integers or slices, not str'),
+    ("(1,)['a']", 'TypeError', 'tuple indices must be integers or slices, not str'),
+    ("{1: 1}['a']", 'KeyError', "'a'"),
+    ("{1}['a']", 'TypeError', "'set' object is not subscriptable"),
+    ("frozenset({1})['a']", 'TypeError', "'frozenset' object is not subscriptable"),
+    ("range(1)['a']", 'TypeError', 'range indices must be integers or slices, not str'),
+    ('1[0] = 1', 'TypeError', "'int' object does not support item assignment"),
+    ('1.5[0] = 1', 'TypeError', "'float' object does not support item assignment"),
+    ('1j[0] = 1', 'TypeError', "'complex' object does not support item assignment"),
+    ('True[0] = 1', 'TypeError', "'bool' object does not support item assignment"),
+    ('None[0] = 1', 'TypeError', "'NoneType' object does not support item assignment"),
+    ("'a'[0] = 1", 'TypeError', "'str' object does not support item assignment"),
+    ("b'a'[0] = 1", 'TypeError', "'bytes' object does not support item assignment"),
+    ('(1,)[0] = 1', 'TypeError', "'tuple' object does not support item assignment"),
+    ('{1}[0] = 1', 'TypeError', "'set' object does not support item assignment"),
+    ('frozenset({1})[0] = 1', 'TypeError', "'frozenset' object does not support item assignment"),
+    ('range(1)[0] = 1', 'TypeError', "'range' object does not support item assignment"),
+    ('del 1[0]', 'TypeError', "'int' object does not support item deletion"),
+    ('del 1.5[0]', 'TypeError', "'float' object does not support item deletion"),
+    ('del 1j[0]', 'TypeError', "'complex' object does not support item deletion"),
+    ('del True[0]', 'TypeError', "'bool' object does not support item deletion"),
+    ('del None[0]', 'TypeError', "'NoneType' object does not support item deletion"),
+    ("del 'a'[0]", 'TypeError', "'str' object doesn't support item deletion"),
+    ("del b'a'[0]", 'TypeError', "'bytes' object doesn't support item deletion"),
+    ('del (1,)[0]', 'TypeError', "'tuple' object doesn't support item deletion"),
+    ('del {1: 1}[0]', 'KeyError', '0'),
+    ('del {1}[0]', 'TypeError', "'set' object doesn't support item deletion"),
+    ('del frozenset({1})[0]', 'TypeError', "'frozenset' object doesn't support item deletion"),
+    ('del range(1)[0]', 'TypeError', "'range' object doesn't support item deletion"),
+    ('len(1)', 'TypeError', "object of type 'int' has no len()"),
+    ('len(1.5)', 'TypeError', "object of type 'float' has no len()"),
+    ('len(1j)', 'TypeError', "object of type 'complex' has no len()"),
+    ('len(True)', 'TypeError', "object of type 'bool' has no len()"),
+    ('len(None)', 'TypeError', "object of type 'NoneType' has no len()"),
+    ('[1][5]', 'IndexError', 'list index out of range'),
+    ('(1,)[5]', 'IndexError', 'tuple index out of range'),
+    ("'a'[5]", 'IndexError', 'string index out of range'),
+    ('[1][5] = 1', 'IndexError', 'list assignment index out of range'),
+    ("{}['a']", 'KeyError', "'a'"),
+    ('{}[[]]', 'TypeError', "unhashable type: 'list'"),
+    ('{[]: 1}', 'TypeError', "unhashable type: 'list'"),
+    ('{[]}', 'TypeError', "unhashable type: 'list'"),
+    ('hash([])', 'TypeError', "unhashable type: 'list'"),
+    ('[1].index(2)', 'ValueError', '2 is not in list'),
+    ("'a'.index('b')", 'ValueError', 'substring not found'),
+    ('[].pop()', 'IndexError', 'pop from empty list'),
+    ('{}.popitem()', 'KeyError', "'popitem(): dictionary is empty'"),
+    ('set().pop()', 'KeyError', "'pop from an empty set'"),
+    ('set().remove(1)', 'KeyError', '1'),
+    ('1 / 0', 'ZeroDivisionError', 'division by zero'),
+    ('1 // 0', 'ZeroDivisionError', 'integer division or modulo by zero'),
+    ('1 % 0', 'ZeroDivisionError', 'integer division or modulo by zero'),
+    ('1.5 / 0', 'ZeroDivisionError', 'float division by zero'),
+    ('divmod(1, 0)', 'ZeroDivisionError', 'integer division or modulo by zero'),
+    ("int('a')", 'ValueError', "invalid literal for int() with base 10: 'a'"),
+    ("float('a')", 'ValueError', "could not convert string to float: 'a'"),
+    ("ord('ab')", 'TypeError', 'ord() expected a character, but string of length 2 found'),
+    ('chr(-1)', 'ValueError', 'chr() arg not in range(0x110000)'),
+    ('def f(): pass\nf(1)', 'TypeError', 'f() takes 0 positional arguments but 1 was given'),
+    ('def f(): pass\nf(1, 2)', 'TypeError', 'f() takes 0 positional arguments but 2 were given'),
+    ('def f(): pass\nf(a=1)', 'TypeError', "f() got an unexpected keyword argument 'a'"),
+    ('def f(a): pass\nf()', 'TypeError', "f() missing 1 required positional argument: 'a'"),
+    ('def f(a): pass\nf(1, 2)', 'TypeError', 'f() takes 1 positional argument but 2 were given'),
+    ('def f(a): pass\nf(b=1)', 'TypeError', "f() got an unexpected keyword argument 'b'"),
+    ('def f(a): pass\nf(1, a=1)', 'TypeError', "f() got multiple values for argument 'a'"),
+    ('def f(a, b): pass\nf()', 'TypeError', "f() missing 2 required positional arguments: 'a' and 'b'"),
+    ('def f(a, b): pass\nf(1)', 'TypeError', "f() missing 1 required positional argument: 'b'"),
+    ('def f(a, b): pass\nf(1, 2, 3)', 'TypeError', 'f() takes 2 positional arguments but 3 were given'),
+    ('def f(a, b): pass\nf(b=1)', 'TypeError', "f() missing 1 required positional argument: 'a'"),
+    ('def f(a, b, c): pass\nf()', 'TypeError', "f() missing 3 required positional arguments: 'a', 'b', and 'c'"),
+    ('def f(a, b, c): pass\nf(1)', 'TypeError', "f() missing 2 required positional arguments: 'b' and 'c'"),
+    ('def f(a, b=1): pass\nf()', 'TypeError', "f() missing 1 required positional argument: 'a'"),
+    ('def f(a, b=1): pass\nf(1, 2, 3)', 'TypeError', 'f() takes from 1 to 2 positional arguments but 3 were given'),
+    ('def f(*, a): pass\nf()', 'TypeError', "f() missing 1 required keyword-only argument: 'a'"),
+    ('def f(*, a): pass\nf(b=1)', 'TypeError', "f() got an unexpected keyword argument 'b'"),
+    ('def f(*, a, b): pass\nf()', 'TypeError', "f() missing 2 required keyword-only arguments: 'a' and 'b'"),
+    ('def f(a, *args): pass\nf()', 'TypeError', "f() missing 1 required positional argument: 'a'"),
+    ('def f(a, *args): pass\nf(a=1, b=2)', 'TypeError', "f() got an unexpected keyword argument 'b'"),
+    ('def f(**kwargs): pass\nf(1)', 'TypeError', 'f() takes 0 positional arguments but 1 was given'),
+    ('class C:\n def m(self, a): pass\nC().m()', 'TypeError', "m() missing 1 required positional argument: 'a'"),
+    ('class C:\n def m(self, a): pass\nC().m(1, 2)', 'TypeError', 'm() takes 2 positional arguments but 3 were given'),
+    ('class C:\n def __init__(self, a): pass\nC()', 'TypeError', "__init__() missing 1 required positional argument: 'a'"),
+    ('class C:\n def __init__(self, a): pass\nC(1, 2)', 'TypeError', '__init__() takes 2 positional arguments but 3 were given'),
+]
@@ -36,3 +36,42 @@ def func():
 	pass
 
 assert func() == 42
+
+
+decorators = [lambda func: lambda: 'first', lambda func: lambda: 'second']
+
+
+def wrap(value):
+    def decorator(func):
+        func.value = value
+        return func
+    return decorator
+
+
+@decorators[1]
+def subscripted():
+    pass
+
+assert subscripted() == 'second'
+
+
+@wrap(1).__call__
+@(lambda func: func)
+def called():
+    pass
+
+assert called.value == 1
+
+
+@decorators[0]
+async def coroutine():
+    pass
+
+assert coroutine() == 'first'
+
+
+@wrap('class')
+class B:
+    pass
+
+assert B.value == 'class'
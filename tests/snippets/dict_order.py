@@ -0,0 +1,107 @@
+from collections import namedtuple
+
+from testutils import assert_raises
+
+d = {'b': 1, 'a': 2, 'c': 3}
+assert list(d) == ['b', 'a', 'c']
+assert list(d.keys()) == ['b', 'a', 'c']
+assert list(d.values()) == [1, 2, 3]
+assert list(d.items()) == [('b', 1), ('a', 2), ('c', 3)]
+
+# Assigning to an existing key keeps its place, deleting it and adding it again
+# moves it to the end.
+d['b'] = 4
+assert list(d) == ['b', 'a', 'c']
+del d['b']
+assert list(d) == ['a', 'c']
+d['b'] = 5
+assert list(d.items()) == [('a', 2), ('c', 3), ('b', 5)]
+assert d.pop('c') == 3
+d['c'] = 6
+assert list(d) == ['a', 'b', 'c']
+
+# popitem() takes the last item.
+assert d.popitem() == ('c', 6)
+assert d.popitem() == ('b', 5)
+d['z'] = 0
+assert d.popitem() == ('z', 0)
+assert d.popitem() == ('a', 2)
+with assert_raises(KeyError):
+    d.popitem()
+
+# Literals with unpacking keep the order they're written in.
+rest = {'y': 2, 'x': 1}
+assert list({'c': 0, **rest, 'a': 3, 'x': 4}.items()) == [
+    ('c', 0), ('y', 2), ('x', 4), ('a', 3)]
+assert list(dict(rest, z=1, b=2)) == ['y', 'x', 'z', 'b']
+assert list(dict([('q', 1), ('p', 2)])) == ['q', 'p']
+assert list(dict.fromkeys('zyx')) == ['z', 'y', 'x']
+
+
+# **kwargs is in the order the keywords were passed in.
+def kwargs(**kw):
+    return list(kw.items())
+
+
+assert kwargs(z=1, a=2, m=3) == [('z', 1), ('a', 2), ('m', 3)]
+assert kwargs(z=1, **{'b': 2, 'a': 3}, c=4) == [('z', 1), ('b', 2), ('a', 3), ('c', 4)]
+
+
+def kwargs_after_named(first, **kw):
+    return list(kw)
+
+
+assert kwargs_after_named(q=1, first=0, p=2) == ['q', 'p']
+
+Point = namedtuple('Point', 'x y')
+assert list(Point(y=2, x=1)._asdict()) == ['x', 'y']
+
+
+# Compare against a model of an ordered dict over many random operations.
+class Random:
+    def __init__(self, seed):
+        self.state = seed
+
+    def below(self, n):
+        self.state = (self.state * 1103515245 + 12345) % 2 ** 31
+        return self.state % n
+
+
+random = Random(2019)
+model_keys = []
+model_values = []
+d = {}
+for step in range(4000):
+    op = random.below(10)
+    key = random.below(200)
+    if op < 5:
+        if key in model_keys:
+            model_values[model_keys.index(key)] = step
+        else:
+            model_keys.append(key)
+            model_values.append(step)
+        d[key] = step
+    elif op < 8:
+        if key in model_keys:
+            index = model_keys.index(key)
+            del model_keys[index]
+            del model_values[index]
+            del d[key]
+        else:
+            assert key not in d
+    elif op < 9:
+        if model_keys:
+            item = (model_keys.pop(), model_values.pop())
+            assert d.popitem() == item
+    else:
+        expected = None
+        if key in model_keys:
+            index = model_keys.index(key)
+            del model_keys[index]
+            expected = model_values.pop(index)
+        assert d.pop(key, None) == expected
+    assert len(d) == len(model_keys)
+
+assert list(d) == model_keys
+assert list(d.values()) == model_values
+assert list(d.items()) == list(zip(model_keys, model_values))
@@ -0,0 +1,96 @@
+"""Check that exceptions raised by the VM match CPython's type and message.
+
+The expected errors are generated from CPython 3.7 by tests/error_messages_gen.py.
+"""
+
+import sys
+
+from cpython_error_messages import EXPECTED_ERRORS
+
+# Sources whose error intentionally (or for now) differs from CPython's.
+# Remove an entry once it matches: entries that no longer diverge fail the test
+# too, so that fixed messages cannot regress.
+ALLOWED_DIVERGENCES = {
+    # bytes formatting with `%` is not implemented
+    "b'a' % 1",
+    "b'a' % 1.5",
+    "b'a' % 1j",
+    "b'a' % True",
+    "b'a' % None",
+    "b'a' % 'a'",
+    "b'a' % b'a'",
+    "b'a' % bytearray(b'a')",
+    "b'a' % (1,)",
+    "b'a' % {1}",
+    "b'a' % frozenset({1})",
+    "bytearray(b'a') % 1",
+    "bytearray(b'a') % 1.5",
+    "bytearray(b'a') % 1j",
+    "bytearray(b'a') % True",
+    "bytearray(b'a') % None",
+    "bytearray(b'a') % 'a'",
+    "bytearray(b'a') % b'a'",
+    "bytearray(b'a') % bytearray(b'a')",
+    "bytearray(b'a') % (1,)",
+    "bytearray(b'a') % {1}",
+    "bytearray(b'a') % frozenset({1})",
+    # bytes and bytearray accept lists of ints as the other operand
+    "b'a' + [1]",
+    "b'a' < [1]",
+    "b'a' <= [1]",
+    "b'a' > [1]",
+    "b'a' >= [1]",
+    "bytearray(b'a') + [1]",
+    "bytearray(b'a') < [1]",
+    "bytearray(b'a') <= [1]",
+    "bytearray(b'a') > [1]",
+    "bytearray(b'a') >= [1]",
+    "[1] < b'a'",
+    "[1] <= b'a'",
+    "[1] > b'a'",
+    "[1] >= b'a'",
+    "[1] < bytearray(b'a')",
+    "[1] <= bytearray(b'a')",
+    "[1] > bytearray(b'a')",
+    "[1] >= bytearray(b'a')",
+    # the index type error doesn't name the sequence type
+    "b'a'['a']",
+    "bytearray(b'a')['a']",
+    "range(1)['a']",
+}
+
+
+def actual_error(source):
+    try:
+        exec(source, {})
+    except Exception as e:
+        return type(e).__name__, str(e)
+    return None, None
+
+
+def mismatches():
+    for source, expected_type, expected_message in EXPECTED_ERRORS:
+        actual = actual_error(source)
+        if actual != (expected_type, expected_message):
+            yield source, (expected_type, expected_message), actual
+
+
+is_cpython = sys.implementation.name == "cpython"
+
+# Newer CPython versions have reworded some of these messages.
+if not is_cpython or sys.version_info[:2] == (3, 7):
+    found = {source: (expected, actual) for source, expected, actual in mismatches()}
+
+    unexpected = sorted(set(found) - set(ALLOWED_DIVERGENCES))
+    for source in unexpected:
+        expected, actual = found[source]
+        print("{!r}: expected {}, got {}".format(source, expected, actual))
+
+    # The allowlist only applies to RustPython; CPython matches itself.
+    stale = [] if is_cpython else sorted(set(ALLOWED_DIVERGENCES) - set(found))
+    for source in stale:
+        print("{!r} now matches CPython; remove it from ALLOWED_DIVERGENCES".format(source))
+
+    assert not unexpected and not stale, "{} unexpected errors, {} stale allowlist entries".format(
+        len(unexpected), len(stale)
+    )
@@ -0,0 +1,144 @@
+import sys
+import traceback
+
+# explicit chaining: `raise ... from ...`
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError as ex:
+        first = ex
+        raise KeyError('key') from ex
+except KeyError as ex:
+    assert ex.__cause__ is first
+    assert ex.__context__ is first
+    assert ex.__suppress_context__ is True
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError as ex:
+        first = ex
+        raise KeyError('key') from None
+except KeyError as ex:
+    assert ex.__cause__ is None
+    assert ex.__context__ is first
+    assert ex.__suppress_context__ is True
+
+try:
+    raise KeyError from ValueError
+except KeyError as ex:
+    assert type(ex.__cause__) is ValueError
+    assert ex.__context__ is None
+    assert ex.__suppress_context__ is True
+
+# implicit chaining, both from a raise statement and from an operation failing
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError as ex:
+        first = ex
+        raise KeyError('key')
+except KeyError as ex:
+    assert ex.__cause__ is None
+    assert ex.__context__ is first
+    assert ex.__suppress_context__ is False
+
+def fail():
+    return {}['missing']
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError as ex:
+        first = ex
+        fail()
+except KeyError as ex:
+    assert ex.__cause__ is None
+    assert ex.__context__ is first
+
+# a bare raise keeps the exception as it was
+try:
+    try:
+        raise ValueError
+    except ValueError:
+        raise
+except ValueError as ex:
+    assert ex.__context__ is None
+
+# re-raising the handled exception doesn't chain it to itself
+try:
+    try:
+        raise ValueError
+    except ValueError as ex:
+        raise ex
+except ValueError as ex:
+    assert ex.__context__ is None
+
+# the new exception is cut out of the context chain, so that it doesn't loop
+try:
+    try:
+        raise ValueError
+    except ValueError as ex:
+        inner = ex
+        try:
+            raise KeyError
+        except KeyError as ex:
+            assert ex.__context__ is inner
+            outer = ex
+            raise inner
+except ValueError as ex:
+    assert ex is inner
+    assert inner.__context__ is outer
+    assert outer.__context__ is None
+
+# the sections of a rendered chain, the oldest exception first
+
+def sections(exc):
+    lines = traceback.format_exception(type(exc), exc, exc.__traceback__)
+    return [line.strip() for line in lines if not line.startswith(' ')]
+
+try:
+    try:
+        try:
+            1 / 0
+        except ZeroDivisionError as ex:
+            raise KeyError('key') from ex
+    except KeyError:
+        raise ValueError('value')
+except ValueError as ex:
+    assert sections(ex) == [
+        'Traceback (most recent call last):',
+        'ZeroDivisionError: division by zero',
+        'The above exception was the direct cause of the following exception:',
+        'Traceback (most recent call last):',
+        "KeyError: 'key'",
+        'During handling of the above exception, another exception occurred:',
+        'Traceback (most recent call last):',
+        'ValueError: value',
+    ], sections(ex)
+
+try:
+    try:
+        1 / 0
+    except ZeroDivisionError:
+        raise KeyError('key') from None
+except KeyError as ex:
+    assert sections(ex) == [
+        'Traceback (most recent call last):',
+        "KeyError: 'key'",
+    ]
+
+# a chain with a cycle is only rendered once around
+a = ValueError('a')
+b = KeyError('b')
+a.__context__ = b
+b.__context__ = a
+assert sections(a) == [
+    "KeyError: 'b'",
+    'During handling of the above exception, another exception occurred:',
+    'ValueError: a',
+]
+
+assert sys.excepthook is sys.__excepthook__
@@ -33,6 +33,14 @@ exc = KeyError(A())
 assert str(exc) == 'A()'
 assert round_trip_repr(exc)
 
+# Other exceptions use str() for a single argument
+exc = ValueError('message')
+assert str(exc) == 'message'
+exc = ValueError(A())
+assert str(exc) == 'str'
+exc = ValueError('message', 'another message')
+assert str(exc) == "('message', 'another message')"
+
 # ImportError / ModuleNotFoundError
 exc = ImportError()
 assert exc.name is None
@@ -45,9 +53,129 @@ assert exc.name is None
 assert exc.path is None
 assert exc.msg == 'hello'
 assert exc.args == ('hello',)
+assert str(exc) == 'hello'
 
 exc = ImportError('hello', name='name', path='path')
 assert exc.name == 'name'
 assert exc.path == 'path'
 assert exc.msg == 'hello'
 assert exc.args == ('hello',)
+
+# Subclasses raised through native code paths keep their identity
+class MyKeyError(KeyError):
+    pass
+
+class MissingDict(dict):
+    def __missing__(self, key):
+        raise MyKeyError(key)
+
+try:
+    MissingDict()['x']
+except KeyError as e:
+    assert type(e) is MyKeyError
+    assert e.args == ('x',)
+    assert str(e) == "'x'"
+else:
+    assert False, 'MyKeyError not raised'
+
+try:
+    {}[('a', 1)]
+except KeyError as e:
+    assert e.args == (('a', 1),)
+
+# StopIteration.value
+exc = StopIteration()
+assert exc.value is None
+exc = StopIteration(5, 6)
+assert exc.value == 5
+
+try:
+    next(iter([]))
+except StopIteration as e:
+    assert e.args == ()
+    assert e.value is None
+
+def gen():
+    return 3
+    yield
+
+try:
+    next(gen())
+except StopIteration as e:
+    assert e.args == (3,)
+    assert e.value == 3
+
+# OSError attributes
+exc = OSError(2, 'No such file or directory')
+assert exc.errno == 2
+assert exc.strerror == 'No such file or directory'
+assert exc.filename is None
+assert str(exc) == '[Errno 2] No such file or directory'
+
+class MyOSError(OSError):
+    pass
+
+exc = MyOSError(13, 'Permission denied', 'a', None, 'b')
+assert exc.args == (13, 'Permission denied')
+assert exc.filename == 'a'
+assert exc.filename2 == 'b'
+assert str(exc) == "[Errno 13] Permission denied: 'a' -> 'b'"
+
+exc = OSError('message')
+assert exc.errno is None
+assert exc.args == ('message',)
+assert str(exc) == 'message'
+
+try:
+    open('/this/file/does/not/exist')
+except FileNotFoundError as e:
+    assert isinstance(e, OSError)
+    assert e.errno == 2
+    assert e.args == (2, e.strerror)
+else:
+    assert False, 'FileNotFoundError not raised'
+
+# Copying preserves the type, args and extra attributes
+import copy
+
+exc = copy.copy(MyKeyError('a'))
+assert type(exc) is MyKeyError
+assert exc.args == ('a',)
+
+exc = MyOSError(13, 'Permission denied', 'f')
+exc.extra = 1
+for copied in (copy.copy(exc), copy.deepcopy(exc)):
+    assert type(copied) is MyOSError
+    assert copied.args == (13, 'Permission denied')
+    assert copied.errno == 13
+    assert copied.filename == 'f'
+    assert copied.extra == 1
+
+assert ValueError('a').__reduce__() == (ValueError, ('a',))
+
+# Throwing exception instances into generators
+def catcher():
+    try:
+        yield
+    except KeyError as e:
+        yield e
+
+g = catcher()
+next(g)
+exc = MyKeyError('thrown')
+assert g.throw(exc) is exc
+
+g = catcher()
+next(g)
+caught = g.throw(KeyError, 'value')
+assert type(caught) is KeyError
+assert caught.args == ('value',)
+
+g = catcher()
+next(g)
+try:
+    g.throw(1)
+except TypeError:
+    pass
+else:
+    assert False, 'TypeError not raised'
@@ -0,0 +1,101 @@
+from testutils import assert_raises
+import itertools
+
+# PEP 3132: one starred target, anywhere in the target list
+first, *rest = [1, 2, 3]
+assert (first, rest) == (1, [2, 3])
+*init, last = (1, 2, 3)
+assert (init, last) == ([1, 2], 3)
+first, *mid, last = 'abcde'
+assert (first, mid, last) == ('a', ['b', 'c', 'd'], 'e')
+first, *mid, last = 'ab'
+assert mid == []
+*everything, = range(3)
+assert everything == [0, 1, 2]
+[a, *b] = (1, 2)
+assert (a, b) == (1, [2])
+
+# The starred part is always a list
+*b, = ()
+assert b == []
+a, *b = (1, 2, 3)
+assert type(b) is list
+
+# Nested targets
+(a, *b), c = 'xyz', 4
+assert (a, b, c) == ('x', ['y', 'z'], 4)
+a, (b, *c), *d = 1, [2, 3, 4], 5, 6
+assert (a, b, c, d) == (1, 2, [3, 4], [5, 6])
+
+# In for loops, comprehensions and with statements
+pairs = []
+for head, *tail in [(1, 2, 3), (4,)]:
+    pairs.append((head, tail))
+assert pairs == [(1, [2, 3]), (4, [])]
+assert [tail for _, *tail in ['ab', 'cde']] == [['b'], ['d', 'e']]
+
+
+class Context:
+    def __enter__(self):
+        return (1, 2, 3)
+
+    def __exit__(self, *args):
+        pass
+
+
+with Context() as (a, *b):
+    assert (a, b) == (1, [2, 3])
+with Context() as [*a, b]:
+    assert (a, b) == ([1, 2], 3)
+
+# A starred value on the right of an assignment
+b = [2, 3]
+a = *b, 4
+assert a == (2, 3, 4)
+
+# Generators, which unpacking runs to the end when there is a starred target
+def numbers(n):
+    yield from range(n)
+
+
+a, *b, c = numbers(5)
+assert (a, b, c) == (0, [1, 2, 3], 4)
+
+# Too few values
+with assert_raises(ValueError) as cm:
+    a, *b, c = [1]
+assert str(cm.exception) == 'not enough values to unpack (expected at least 2, got 1)'
+with assert_raises(ValueError) as cm:
+    (a, *b), c = [], 1
+assert str(cm.exception) == 'not enough values to unpack (expected at least 1, got 0)'
+with assert_raises(ValueError) as cm:
+    a, *b, c = numbers(1)
+assert str(cm.exception) == 'not enough values to unpack (expected at least 2, got 1)'
+with assert_raises(ValueError) as cm:
+    a, b, c = [1, 2]
+assert str(cm.exception) == 'not enough values to unpack (expected 3, got 2)'
+
+# Too many values: without a starred target only one element more than there are targets is
+# taken from an iterator, so this fails instead of running forever
+with assert_raises(ValueError) as cm:
+    a, b = itertools.count()
+assert str(cm.exception) == 'too many values to unpack (expected 2)'
+counter = itertools.count()
+with assert_raises(ValueError):
+    a, b = counter
+assert next(counter) == 3
+
+with assert_raises(TypeError):
+    a, *b = 1
+
+# Syntax errors
+for source, message in [
+    ('a, *b, *c = x', 'multiple starred expressions in assignment'),
+    ('*a, (b, *c, *d) = x', 'multiple starred expressions in assignment'),
+    ('*a = x', 'starred assignment target must be in a list or tuple'),
+    ('for *a in x: pass', 'starred assignment target must be in a list or tuple'),
+    ('a = *b', "can't use starred expression here"),
+]:
+    with assert_raises(SyntaxError) as cm:
+        compile(source, '<test>', 'exec')
+    assert message in str(cm.exception), (source, str(cm.exception))
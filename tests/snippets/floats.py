@@ -82,6 +82,8 @@ except TypeError:
 
 assert math.isnan(float('nan'))
 assert math.isnan(float('NaN'))
+assert repr(float('nan')) == 'nan'
+assert str(-float('nan')) == 'nan'
 assert math.isnan(float('+NaN'))
 assert math.isnan(float('-NaN'))
 
@@ -19,7 +19,8 @@ assert f"{{{(lambda x: f'hello, {x}')('world}')}" == '{hello, world}'
 # Normally `!` cannot appear outside of delimiters in the expression but
 # cpython makes an exception for `!=`, so we should too.
 
-# assert f'{1 != 2}' == 'True'
+assert f'{1 != 2}' == 'True'
+assert f'{1!=2!r:>6}' == '  True'
 
 
 # conversion flags
@@ -53,3 +54,53 @@ assert f'>{v}' == '>\u262e'
 assert f'>{v!r}' == ">'\u262e'"
 assert f'>{v!s}' == '>\u262e'
 assert f'>{v!a}' == r">'\u262e'"
+
+# nested replacement fields, conversions and escaping, as (source, expected output)
+
+width = 10
+precision = 3
+value = 3.14159
+name = 'spam'
+snowman = '\u262e'
+
+cases = [
+    ('f"{value:{width}.{precision}f}"', '     3.142'),
+    ('f"{value:{width}}"', '   3.14159'),
+    ('f"{value:>{width}.{precision}}"', '      3.14'),
+    ('f"{name:{\'*\'}^{width}}"', '***spam***'),
+    ('f"{name:{ {1: \'<\'}[1]}{width}}"', 'spam      '),
+    ('f"{name!r:>{width}}"', "    'spam'"),
+    ('f"{name!s:_<8}"', 'spam____'),
+    ('f"{snowman!a:>10}"', "  '\\u262e'"),
+    ('f"{width!r:{precision}}"', '10 '),
+    ('f"{{name}} {name}"', '{name} spam'),
+    ('f"{{{name}}}"', '{spam}'),
+    ('f"}}{{"', '}{'),
+    ('f"{ {name: 1}[name]}"', '1'),
+    ('f"{\'{}\'}"', '{}'),
+    ('f"{name:{width!r}}"', 'spam      '),
+    ('f"""{value\n+ 1:.2f}"""', '4.14'),
+    ('f"""{(\n  name\n)!r}"""', "'spam'"),
+]
+
+for source, expected in cases:
+    assert eval(source) == expected, source
+
+for source, message in [
+    ('f"{"', "f-string: expecting '}'"),
+    ('f"}"', "f-string: single '}' is not allowed"),
+    ('f"{}"', "f-string: empty expression not allowed"),
+    ('f"{ }"', "f-string: empty expression not allowed"),
+    ('f"{name!x}"', "f-string: invalid conversion character: expected 's', 'r', or 'a'"),
+    ('f"{name!rx}"', "f-string: expecting '}'"),
+    ('f"{name:{width:{precision}}}"', "f-string: expressions nested too deeply"),
+    ('f"{name:{width}"', "f-string: expecting '}'"),
+    ('f"{name)}"', "f-string: unmatched ')'"),
+    ('f"{(name]}"', "f-string: closing parenthesis ']' does not match opening parenthesis '('"),
+]:
+    try:
+        compile(source, '<fstring>', 'eval')
+    except SyntaxError as error:
+        assert str(error).startswith(message), (source, str(error))
+    else:
+        assert False, source
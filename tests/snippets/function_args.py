@@ -114,3 +114,63 @@ x = {'a': 1}
 y = {'a': 2}
 with assert_raises(TypeError):
     f(**x, **y)
+
+
+def kwonly(a, *, b, c=3):
+    return (a, b, c)
+
+assert kwonly.__code__.co_kwonlyargcount == 2
+assert kwonly(1, b=2) == (1, 2, 3)
+assert kwonly(1, c=4, b=2) == (1, 2, 4)
+assert (lambda *, x: x)(x=1) == 1
+
+with assert_raises(TypeError) as cm:
+    kwonly(1, 2)
+assert str(cm.exception).endswith("takes 1 positional argument but 2 were given")
+with assert_raises(TypeError) as cm:
+    kwonly(1)
+assert str(cm.exception).endswith("missing 1 required keyword-only argument: 'b'")
+with assert_raises(TypeError) as cm:
+    kwonly(1, b=2, d=4)
+assert str(cm.exception).endswith("got an unexpected keyword argument 'd'")
+
+for source in ["def f(*): pass", "def f(*, **kw): pass", "lambda *: 0", "def f(**): pass"]:
+    with assert_raises(SyntaxError):
+        compile(source, '<args>', 'exec')
+
+# Calls mixing positional, * and ** arguments
+def args_kwargs(*args, **kwargs):
+    return args, kwargs
+
+assert args_kwargs(1, *[2, 3], 4, *(5,), a=1, **{'b': 2}, c=3, **{'d': 4}) == \
+    ((1, 2, 3, 4, 5), {'a': 1, 'b': 2, 'c': 3, 'd': 4})
+
+class Mapping:
+    def keys(self):
+        return ['x', 'y']
+
+    def __getitem__(self, key):
+        return key * 2
+
+assert args_kwargs(**Mapping()) == ((), {'x': 'xx', 'y': 'yy'})
+assert args_kwargs(a=1, **Mapping()) == ((), {'a': 1, 'x': 'xx', 'y': 'yy'})
+
+for source in ["f(**a, *b)", "f(**a, b)", "f(a=1, b)"]:
+    with assert_raises(SyntaxError):
+        compile(source, '<args>', 'exec')
+
+with assert_raises(TypeError) as cm:
+    args_kwargs(*1)
+assert "args_kwargs() argument after * must be an iterable, not int" in str(cm.exception)
+with assert_raises(TypeError) as cm:
+    args_kwargs(**1)
+assert "args_kwargs() argument after ** must be a mapping, not int" in str(cm.exception)
+with assert_raises(TypeError) as cm:
+    args_kwargs(a=1, **[])
+assert "args_kwargs() argument after ** must be a mapping, not list" in str(cm.exception)
+with assert_raises(TypeError) as cm:
+    args_kwargs(a=1, **{'a': 2})
+assert "args_kwargs() got multiple values for keyword argument 'a'" in str(cm.exception)
+with assert_raises(TypeError) as cm:
+    args_kwargs(**{1: 2})
+assert "keywords must be strings" in str(cm.exception)
@@ -0,0 +1,108 @@
+from __future__ import annotations
+
+import __future__
+
+
+# Annotations are kept as the strings of their source, so they may refer to
+# names that aren't defined yet, or use things that fail when evaluated.
+def function(a: Node, b: list[int] = [], *args: Tuple[int, ...], c: 'str' = 'c', **kwargs: 1 + 2) -> Node | None:
+    return None
+
+
+assert function.__annotations__ == {
+    'a': 'Node',
+    'b': 'list[int]',
+    'args': 'Tuple[int, ...]',
+    'c': "'str'",
+    'kwargs': '1 + 2',
+    'return': 'Node | None',
+}
+
+x: Undefined
+y: dict[str, Callable[[int], None]] = {}
+assert __annotations__ == {'x': 'Undefined', 'y': 'dict[str, Callable[[int], None]]'}
+assert y == {}
+
+
+class Node:
+    parent: Optional[Node] = None
+    children: List[Node]
+
+    def add(self, child: Node) -> None:
+        pass
+
+    # Nested scopes use the flag of their module too.
+    def nested(self):
+        def inner(value: self.parent) -> ~(a @ b):
+            pass
+
+        class Inner:
+            attribute: lambda: (yield)
+
+        return inner, Inner
+
+
+assert Node.__annotations__ == {'parent': 'Optional[Node]', 'children': 'List[Node]'}
+assert Node.add.__annotations__ == {'child': 'Node', 'return': 'None'}
+inner, Inner = Node().nested()
+assert inner.__annotations__ == {'value': 'self.parent', 'return': '~(a @ b)'}
+assert Inner.__annotations__ == {'attribute': 'lambda: (yield)'}
+
+# The strings can be evaluated later, once the names exist, like
+# `typing.get_type_hints` does.
+def get_type_hints(obj):
+    return {name: eval(hint, globals()) for name, hint in obj.__annotations__.items()}
+
+
+assert get_type_hints(Node.add) == {'child': Node, 'return': None}
+assert eval(function.__annotations__['kwargs']) == 3
+
+# Code compiled separately isn't affected. Its annotations are evaluated.
+namespace = {}
+code = compile("x: int = 1\ndef f(a: int) -> str: pass\n", "<string>", "exec", dont_inherit=True)
+exec(code, namespace)
+assert namespace['__annotations__'] == {'x': int}
+assert namespace['f'].__annotations__ == {'a': int, 'return': str}
+
+# Otherwise compile, exec and eval inherit the future import from this module,
+namespace = {}
+exec("x: int = 1\ndef f(a: int) -> str: pass\n", namespace)
+assert namespace['__annotations__'] == {'x': 'int'}
+assert namespace['f'].__annotations__ == {'a': 'int', 'return': 'str'}
+code = compile("def f(a: int): pass\n", "<string>", "exec")
+assert code.co_flags & __future__.annotations.compiler_flag
+namespace = {}
+exec(code, namespace)
+assert namespace['f'].__annotations__ == {'a': 'int'}
+assert function.__code__.co_flags & __future__.annotations.compiler_flag
+
+# and compile turns it on with its compiler_flag.
+code = compile(
+    "def f(a: int): pass\n",
+    "<string>",
+    "exec",
+    flags=__future__.annotations.compiler_flag,
+    dont_inherit=True,
+)
+namespace = {}
+exec(code, namespace)
+assert namespace['f'].__annotations__ == {'a': 'int'}
+
+# Future imports must name known features, and come first in a module.
+for source, message in [
+    ("from __future__ import spam", "future feature spam is not defined"),
+    ("from __future__ import braces", "not a chance"),
+    ("import os\nfrom __future__ import annotations", "from __future__ imports must occur at the beginning of the file"),
+    ("def f():\n from __future__ import annotations", "from __future__ imports must occur at the beginning of the file"),
+]:
+    try:
+        exec(source, {})
+    except SyntaxError as e:
+        assert str(e).startswith(message), e
+    else:
+        assert False, source
+
+# A docstring and other future imports may come before.
+namespace = {}
+exec('"""Docstring."""\nfrom __future__ import generator_stop\nfrom __future__ import annotations\nx: y\n', namespace)
+assert namespace['__annotations__'] == {'x': 'y'}
@@ -0,0 +1,132 @@
+import inspect
+import sys
+
+from testutils import assert_raises
+
+
+def lineno():
+    return sys._getframe(1).f_lineno
+
+
+# A generator through its lifecycle
+def counter():
+    total = 0
+    total += 1
+    yield total  # first yield
+    total += 1
+    yield total  # second yield
+
+first_yield = lineno() - 4
+second_yield = first_yield + 2
+
+g = counter()
+assert g.gi_code is counter.__code__
+assert inspect.getgeneratorstate(g) == inspect.GEN_CREATED
+assert not g.gi_running
+assert g.gi_yieldfrom is None
+
+assert next(g) == 1
+assert inspect.getgeneratorstate(g) == inspect.GEN_SUSPENDED
+assert g.gi_frame.f_lineno == first_yield
+assert g.gi_frame.f_locals == {'total': 1}
+assert inspect.getgeneratorlocals(g) == {'total': 1}
+
+assert next(g) == 2
+assert g.gi_frame.f_lineno == second_yield
+assert g.gi_frame.f_locals == {'total': 2}
+
+with assert_raises(StopIteration):
+    next(g)
+assert inspect.getgeneratorstate(g) == inspect.GEN_CLOSED
+assert g.gi_frame is None
+assert not g.gi_running
+with assert_raises(StopIteration):
+    next(g)
+with assert_raises(KeyError):
+    g.throw(KeyError)
+
+# Closed before it started, or while suspended
+g = counter()
+g.close()
+assert inspect.getgeneratorstate(g) == inspect.GEN_CLOSED
+with assert_raises(StopIteration):
+    next(g)
+
+g = counter()
+next(g)
+g.close()
+assert inspect.getgeneratorstate(g) == inspect.GEN_CLOSED
+
+with assert_raises(TypeError):
+    counter().send(1)
+
+
+# A generator can see itself running, but can't resume itself
+def introspect():
+    yield inspect.getgeneratorstate(me), me.gi_running
+
+me = introspect()
+assert next(me) == (inspect.GEN_RUNNING, True)
+
+
+def reentrant(callback):
+    yield callback()
+
+def resume():
+    with assert_raises(ValueError) as cm:
+        next(gen)
+    return str(cm.exception)
+
+gen = reentrant(resume)
+assert next(gen) == 'generator already executing'
+# The failed attempt didn't break the generator
+assert inspect.getgeneratorstate(gen) == inspect.GEN_SUSPENDED
+with assert_raises(StopIteration):
+    next(gen)
+
+
+def sub():
+    yield 1
+    return 'done'
+
+def delegate():
+    result = yield from inner
+    yield result
+
+inner = sub()
+outer = delegate()
+next(outer)
+assert outer.gi_yieldfrom is inner
+assert next(outer) == 'done'
+assert outer.gi_yieldfrom is None
+
+
+# An awaited coroutine
+class Suspend:
+    def __await__(self):
+        value = yield 'suspended'
+        return value
+
+async def waiter():
+    awaitable = Suspend()
+    return await awaitable
+
+c = waiter()
+assert c.cr_code is waiter.__code__
+assert inspect.getcoroutinestate(c) == inspect.CORO_CREATED
+assert c.cr_await is None
+
+assert c.send(None) == 'suspended'
+assert inspect.getcoroutinestate(c) == inspect.CORO_SUSPENDED
+assert c.cr_await is not None
+assert not c.cr_running
+assert 'awaitable' in c.cr_frame.f_locals
+
+with assert_raises(StopIteration) as cm:
+    c.send(42)
+assert cm.exception.value == 42
+assert inspect.getcoroutinestate(c) == inspect.CORO_CLOSED
+assert c.cr_frame is None
+assert c.cr_await is None
+with assert_raises(RuntimeError):
+    c.send(None)
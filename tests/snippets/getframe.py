@@ -19,3 +19,161 @@ class TestClass():
         assert sys._getframe().f_locals['self'] == self
 
 TestClass()
+
+
+# Walking f_back from nested calls
+def innermost():
+    return sys._getframe()
+
+
+def middle():
+    return innermost(), sys._getframe()
+
+
+def outer():
+    return middle() + (sys._getframe(),)
+
+
+inner_frame, middle_frame, outer_frame = outer()
+assert [inner_frame.f_code.co_name, middle_frame.f_code.co_name,
+        outer_frame.f_code.co_name] == ['innermost', 'middle', 'outer']
+
+
+def walk():
+    frame = sys._getframe()
+    names = []
+    while frame is not None:
+        names.append(frame.f_code.co_name)
+        frame = frame.f_back
+    return names
+
+
+def call_walk():
+    return walk()
+
+
+assert call_walk() == ['walk', 'call_walk', '<module>']
+assert sys._getframe().f_back is None
+
+
+def depth_two():
+    return sys._getframe(2).f_code.co_name
+
+
+def depth_one():
+    return depth_two()
+
+
+assert depth_one() == '<module>'
+assert sys._getframe(-1) is sys._getframe()
+
+try:
+    sys._getframe(1000)
+except ValueError as e:
+    assert str(e) == 'call stack is not deep enough'
+else:
+    assert False
+
+this_frame = sys._getframe()
+assert this_frame.f_globals is globals()
+assert this_frame.f_locals is globals()
+assert this_frame.f_builtins['len'] is len
+assert repr(this_frame).startswith('<frame')
+
+
+# f_locals is a snapshot in functions, and brought up to date on each access.
+def snapshot():
+    frame = sys._getframe()
+    a = 1
+    first = frame.f_locals
+    assert first['a'] == 1
+    assert 'b' not in first
+    b = 2
+    del a
+    second = frame.f_locals
+    assert second['b'] == 2
+    assert 'a' not in second
+    # Writes don't change the variables.
+    second['b'] = 5
+    assert b == 2
+    return frame
+
+
+f = snapshot()
+assert f.f_locals['b'] == 2
+
+
+# The variables of enclosing functions are there too.
+def enclosing():
+    captured = 'outer value'
+    unused = 'not used'
+
+    def nested():
+        value = captured
+        return sys._getframe().f_locals
+
+    return nested()
+
+
+nested_locals = enclosing()
+assert nested_locals['captured'] == 'outer value'
+assert nested_locals['value'] == 'outer value'
+assert 'unused' not in nested_locals
+
+
+# Inside except blocks
+def in_except():
+    try:
+        raise KeyError('k')
+    except KeyError as err:
+        return dict(sys._getframe().f_locals)
+
+
+assert isinstance(in_except()['err'], KeyError)
+
+
+# In suspended generators
+def gen():
+    x = 1
+    yield
+    x = 2
+    y = 3
+    yield
+
+
+g = gen()
+next(g)
+assert g.gi_frame.f_locals == {'x': 1}
+next(g)
+assert g.gi_frame.f_locals == {'x': 2, 'y': 3}
+
+
+# f_lineno follows the lines of a traced function.
+lines = []
+
+
+def tracer(frame, event, arg):
+    if frame.f_code.co_name == 'traced':
+        lines.append(frame.f_lineno - frame.f_code.co_firstlineno)
+    return tracer
+
+
+def traced():
+    a = 1
+    b = 2
+    return a + b
+
+
+sys.settrace(tracer)
+traced()
+sys.settrace(None)
+assert lines == [0, 1, 2, 3, 3], lines
+
+
+def lineno_in_calls():
+    first = sys._getframe().f_lineno
+    second = sys._getframe().f_lineno
+    return second - first
+
+
+assert lineno_in_calls() == 1
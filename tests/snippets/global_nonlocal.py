@@ -108,4 +108,29 @@ def a():
     b = locals()
     assert b['x'] == 0
 
-a()
\ No newline at end of file
+a()
+
+# A nonlocal name can be bound further out than the function around it
+def outer():
+    x = 1
+    def middle():
+        def inner():
+            nonlocal x
+            x = 2
+        inner()
+    middle()
+    return x
+
+assert outer() == 2
+
+for src, message in [
+    ("def f(x):\n    global x\n", "name 'x' is parameter and global"),
+    ("def f(x):\n    nonlocal x\n", "name 'x' is parameter and nonlocal"),
+    ("def f():\n    x = 1\n    global x\n", "name 'x' is assigned to before global declaration"),
+    ("def f():\n    x = 1\n    def g():\n        global x\n        nonlocal x\n", "name 'x' is nonlocal and global"),
+    ("nonlocal x\n", "nonlocal declaration not allowed at module level"),
+    ("x = 1\ndef f():\n    nonlocal x\n", "no binding for nonlocal 'x' found"),
+]:
+    with assert_raises(SyntaxError) as ex:
+        compile(src, '<string>', 'exec')
+    assert ex.exception.msg == message, ex.exception.msg
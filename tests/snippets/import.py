@@ -22,13 +22,41 @@ assert STAR_IMPORT == '123'
 try:
     from import_target import func, unknown_name
     raise AssertionError('`unknown_name` does not cause an exception')
-except ImportError:
-    pass
+except ImportError as exc:
+    assert type(exc) is ImportError
+    assert exc.name == 'import_target'
+    assert exc.path == import_target.__file__
+    assert str(exc) == "cannot import name 'unknown_name' from 'import_target' ({})".format(import_target.__file__)
 
 try:
     import mymodule
+    raise AssertionError('`mymodule` does not cause an exception')
 except ModuleNotFoundError as exc:
     assert exc.name == 'mymodule'
+    assert exc.path is None
+    assert str(exc) == "No module named 'mymodule'"
+
+try:
+    import importlib.nosuchsubmodule
+    raise AssertionError('`importlib.nosuchsubmodule` does not cause an exception')
+except ModuleNotFoundError as exc:
+    assert exc.name == 'importlib.nosuchsubmodule'
+    assert exc.path is None
+    assert str(exc) == "No module named 'importlib.nosuchsubmodule'"
+
+assert issubclass(ModuleNotFoundError, ImportError)
+
+import types
+from importlib.machinery import ModuleSpec
+partial = types.ModuleType('partial')
+partial.__spec__ = ModuleSpec('partial', None)
+partial.__spec__._initializing = True
+partial.__file__ = 'partial.py'
+try:
+    partial.missing
+    raise AssertionError('`partial.missing` does not cause an exception')
+except AttributeError as exc:
+    assert str(exc) == "partially initialized module 'partial' has no attribute 'missing' (most likely due to a circular import)"
 
 
 test = __import__("import_target")
@@ -0,0 +1,180 @@
+import builtins
+import types
+
+from testutils import assert_raises
+
+# Every lookup below runs at least twice from the same instruction, once to fill its inline
+# cache and once after changing what it depends on.
+
+# globals and builtins
+def get_x():
+    return x
+
+x = 1
+assert get_x() == 1
+x = 2
+assert get_x() == 2
+del x
+with assert_raises(NameError):
+    get_x()
+x = 3
+assert get_x() == 3
+
+
+def get_len():
+    return len
+
+assert get_len() is builtins.len
+len = lambda obj: 42
+assert get_len()("abc") == 42
+del len
+assert get_len() is builtins.len
+
+original_len = builtins.len
+builtins.len = lambda obj: -1
+try:
+    assert get_len()("abc") == -1
+finally:
+    builtins.len = original_len
+assert get_len()("abc") == 3
+
+# the same code object with other globals
+code = get_x.__code__
+for value in range(3):
+    namespace = {"x": value}
+    exec("def f():\n    return x\n", namespace)
+    assert namespace["f"]() == value
+    assert eval(code, {"x": value * 10}) == value * 10
+
+# attributes of instances
+class Base:
+    attr = "base"
+
+    def method(self):
+        return "base method"
+
+
+class Derived(Base):
+    pass
+
+
+obj = Derived()
+
+def get_attr(o):
+    return o.attr
+
+def call_method(o):
+    return o.method()
+
+assert get_attr(obj) == "base"
+assert call_method(obj) == "base method"
+
+Base.attr = "changed"
+Base.method = lambda self: "new method"
+assert get_attr(obj) == "changed"
+assert call_method(obj) == "new method"
+
+Derived.attr = "derived"
+assert get_attr(obj) == "derived"
+del Derived.attr
+assert get_attr(obj) == "changed"
+
+obj.attr = "instance"
+obj.method = lambda: "instance method"
+assert get_attr(obj) == "instance"
+assert call_method(obj) == "instance method"
+del obj.attr
+del obj.method
+assert get_attr(obj) == "changed"
+assert call_method(obj) == "new method"
+
+del Base.attr
+with assert_raises(AttributeError):
+    get_attr(obj)
+Base.__getattr__ = lambda self, name: "from __getattr__"
+assert get_attr(obj) == "from __getattr__"
+obj.attr = "instance"
+assert get_attr(obj) == "instance"
+
+# a data descriptor added to the type takes precedence over the instance dict
+Derived.attr = property(lambda self: "property")
+assert get_attr(obj) == "property"
+
+# and a class attribute turning into a descriptor after the lookup was cached
+class Descriptor:
+    pass
+
+
+class Holder:
+    attr = Descriptor()
+
+
+holder = Holder()
+assert isinstance(get_attr(holder), Descriptor)
+Descriptor.__get__ = lambda self, obj, cls: "descriptor"
+assert get_attr(holder) == "descriptor"
+
+# one instruction seeing objects of several types
+class A:
+    attr = "a"
+
+
+class B:
+    def __init__(self):
+        self.attr = "b"
+
+
+for _ in range(2):
+    assert [get_attr(o) for o in [A(), B(), A(), types.SimpleNamespace(attr="c")]] == [
+        "a",
+        "b",
+        "a",
+        "c",
+    ]
+
+# attributes of modules
+module = types.ModuleType("module")
+module.attr = "module"
+assert get_attr(module) == "module"
+module.attr = "changed"
+assert get_attr(module) == "changed"
+del module.attr
+with assert_raises(AttributeError):
+    get_attr(module)
+other = types.ModuleType("other")
+other.attr = "other"
+assert get_attr(other) == "other"
+
+# attributes of types, which aren't cached
+assert get_attr(A) == "a"
+A.attr = "A"
+assert get_attr(A) == "A"
+
+# setting attributes
+def set_attr(o, value):
+    o.attr = value
+
+class Plain:
+    pass
+
+
+plain = Plain()
+set_attr(plain, 1)
+set_attr(plain, 2)
+assert plain.attr == 2
+
+stored = []
+Plain.attr = property(lambda self: "property", lambda self, value: stored.append(value))
+set_attr(plain, 3)
+assert stored == [3]
+assert plain.attr == "property"
+assert plain.__dict__["attr"] == 2
+
+del Plain.attr
+Plain.__setattr__ = lambda self, name, value: stored.append((name, value))
+set_attr(plain, 4)
+assert stored == [3, ("attr", 4)]
+assert plain.attr == 2
+
+set_attr(module, "set")
+assert module.attr == "set"
@@ -1,3 +1,5 @@
+from testutils import assert_raises
+
 # Integer literals
 assert 0b101010 == 42
 assert 0B101010 == 42
@@ -6,3 +8,17 @@ assert 0O777 == 511
 assert 0xcafebabe == 3405691582
 assert 0Xcafebabe == 3405691582
 assert 0xCAFEBABE == 3405691582
+
+# String and bytes literals
+assert b'\0\101\x41\\x41' == bytes([0, 65, 65, 92, 120, 52, 49])
+assert b'\u0041' == b'\\u0041'
+assert rb'\'' == Br'\'' == b"\\'"
+assert '\0\101\N{DIGIT ONE}' == '\x00A1'
+assert r'\'' == "\\'"
+assert 'a' r'\b' f'{1}' rf'{2}\n' == 'a\\b12\\n'
+assert b'a' rb'\b' == b'a\\b'
+
+with assert_raises(SyntaxError):
+    compile("'a' b'b'", '<test>', 'eval')
+with assert_raises(SyntaxError):
+    compile("b'caf\xe9'", '<test>', 'eval')
@@ -29,3 +29,13 @@ assert_raises(TypeError, lambda: memoryview((1, 2, 3)))
 assert_raises(TypeError, lambda: memoryview({}))
 assert_raises(TypeError, lambda: memoryview('string'))
 assert_raises(TypeError, lambda: memoryview(C()))
+
+assert memoryview(b"abc").tobytes() == b"abc"
+assert type(memoryview(bytearray(b"abc")).tobytes()) == bytes
+assert memoryview(memoryview(b"abc")).tobytes() == b"abc"
+assert memoryview(array.array('B', [1, 2])).tobytes() == b"\x01\x02"
+
+assert memoryview(b"abc").format == "B"
+assert memoryview(array.array('i', [1])).format == "i"
+assert memoryview(memoryview(bytearray(b"abc"))).format == "B"
+assert memoryview(b"abc").ndim == 1
@@ -0,0 +1,55 @@
+from testutils import assert_raises
+
+data = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]
+if (n := len(data)) > 10:
+    assert n == 11
+else:
+    assert False
+
+chunks = iter([b'ab', b'c', b''])
+read = []
+while chunk := next(chunks):
+    read.append(chunk)
+assert read == [b'ab', b'c']
+assert chunk == b''
+
+assert (y := 5) == 5
+assert y == 5
+assert [z := 1, z + 1] == [1, 2]
+assert max(w := 3, 2) == 3
+assert w == 3
+
+
+# In comprehensions, the target is bound in the containing scope:
+values = [y for x in data if (y := x * 2) > 20]
+assert values == [22]
+assert y == 22
+assert 'x' not in globals()
+
+assert any((last := x) > 4 for x in data)
+assert last == 5
+
+
+def f():
+    total = 0
+    partial = [total := total + x for x in range(4)]
+    assert partial == [0, 1, 3, 6]
+    assert total == 6
+    return [found for x in ['a', 'bb'] if len(found := x) > 1], found
+
+
+assert f() == (['bb'], 'bb')
+
+
+for src in [
+    "x := 1",
+    "x = y := 1",
+    "(a.b := 1)",
+    "(a[0] := 1)",
+    "[i := 0 for i in range(3)]",
+    "[x for x in range(3) if (x := 1)]",
+    "[[(j := 0) for i in range(3)] for j in range(3)]",
+    "class C:\n    [(y := x) for x in range(3)]",
+]:
+    with assert_raises(SyntaxError):
+        compile(src, '<test>', 'exec')
@@ -0,0 +1,40 @@
+# The compiler folds constant expressions and drops code that can't run, so the code objects
+# only keep the constants that are left
+
+
+def hours():
+    return 2 * 60 * 60
+
+
+assert hours() == 7200
+assert 7200 in hours.__code__.co_consts
+assert 60 not in hours.__code__.co_consts
+
+
+def minus_one():
+    return -1
+
+
+assert minus_one() == -1
+assert -1 in minus_one.__code__.co_consts
+assert 1 not in minus_one.__code__.co_consts
+
+
+def returns_early(x):
+    if x:
+        return "early"
+    return "late"
+    return "unreachable"
+
+
+assert returns_early(True) == "early"
+assert returns_early(False) == "late"
+assert "unreachable" not in returns_early.__code__.co_consts
+
+
+def raises():
+    raise ValueError
+    return "unreachable"
+
+
+assert "unreachable" not in raises.__code__.co_consts
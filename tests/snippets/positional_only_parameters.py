@@ -0,0 +1,140 @@
+import inspect
+
+from testutils import assert_raises
+
+
+def f(a, b, /, c, *, d):
+    return a, b, c, d
+
+
+assert f(1, 2, 3, d=4) == (1, 2, 3, 4)
+assert f(1, 2, c=3, d=4) == (1, 2, 3, 4)
+
+with assert_raises(TypeError):
+    f(1, b=2, c=3, d=4)
+
+try:
+    f(a=1, b=2, c=3, d=4)
+except TypeError as e:
+    assert str(e) == "f() got some positional-only arguments passed as keyword arguments: 'a, b'"
+else:
+    assert False, "positional-only arguments passed as keywords"
+
+assert f.__code__.co_posonlyargcount == 2
+assert f.__code__.co_argcount == 3
+assert f.__code__.co_kwonlyargcount == 1
+assert f.__code__.co_varnames[:4] == ('a', 'b', 'c', 'd')
+
+
+# Defaults
+def with_defaults(a, b=2, /, c=3):
+    return a, b, c
+
+
+assert with_defaults(1) == (1, 2, 3)
+assert with_defaults(1, 5) == (1, 5, 3)
+assert with_defaults(1, 5, 6) == (1, 5, 6)
+assert with_defaults(1, c=6) == (1, 2, 6)
+assert with_defaults.__defaults__ == (2, 3)
+
+with assert_raises(TypeError):
+    with_defaults(1, b=5)
+
+try:
+    with_defaults()
+except TypeError as e:
+    assert str(e) == "with_defaults() missing 1 required positional argument: 'a'"
+else:
+    assert False, "missing positional-only argument"
+
+try:
+    with_defaults(1, 2, 3, 4)
+except TypeError as e:
+    assert str(e) == "with_defaults() takes from 1 to 3 positional arguments but 4 were given"
+else:
+    assert False, "too many positional arguments"
+
+
+# *args and **kwargs
+def varargs(a, /, *args, **kwargs):
+    return a, args, kwargs
+
+
+assert varargs(1) == (1, (), {})
+assert varargs(1, 2, 3) == (1, (2, 3), {})
+# **kwargs receives keywords named like a positional-only parameter.
+assert varargs(1, a=2) == (1, (), {'a': 2})
+assert varargs(1, 2, a=3, b=4) == (1, (2,), {'a': 3, 'b': 4})
+
+with assert_raises(TypeError):
+    varargs(a=1)
+
+
+def only_posonly(a, /):
+    return a
+
+
+assert only_posonly(1) == 1
+
+with assert_raises(TypeError):
+    only_posonly(a=1)
+
+try:
+    only_posonly(1, b=2)
+except TypeError as e:
+    assert str(e) == "only_posonly() got an unexpected keyword argument 'b'"
+else:
+    assert False, "unexpected keyword argument"
+
+
+# Methods and lambdas
+class C:
+    def method(self, a, /, b):
+        return a, b
+
+
+assert C().method(1, 2) == (1, 2)
+assert C().method(1, b=2) == (1, 2)
+
+with assert_raises(TypeError):
+    C().method(a=1, b=2)
+
+l = lambda a, /, b=2: (a, b)
+assert l(1) == (1, 2)
+assert l(1, b=3) == (1, 3)
+
+with assert_raises(TypeError):
+    l(a=1)
+
+
+# Introspection
+def annotated(a: int, b=1, /, c=2, *args, d, **kwargs):
+    pass
+
+
+parameters = inspect.signature(annotated).parameters
+assert [(p.name, p.kind) for p in parameters.values()] == [
+    ('a', inspect.Parameter.POSITIONAL_ONLY),
+    ('b', inspect.Parameter.POSITIONAL_ONLY),
+    ('c', inspect.Parameter.POSITIONAL_OR_KEYWORD),
+    ('args', inspect.Parameter.VAR_POSITIONAL),
+    ('d', inspect.Parameter.KEYWORD_ONLY),
+    ('kwargs', inspect.Parameter.VAR_KEYWORD),
+]
+assert parameters['a'].annotation is int
+assert parameters['b'].default == 1
+assert str(inspect.signature(annotated)) == "(a: int, b=1, /, c=2, *args, d, **kwargs)"
+assert annotated.__annotations__ == {'a': int}
+
+
+# Defaults must still be given in order across the `/`, which must follow
+# the positional parameters once.
+for source in [
+    "def g(a=1, /, b): pass",
+    "def g(a, /, b=1, c): pass",
+    "def g(/, a): pass",
+    "def g(a, /, b, /): pass",
+    "def g(*, a, /): pass",
+]:
+    with assert_raises(SyntaxError):
+        exec(source)
@@ -14,3 +14,7 @@ except:
 buf = io.StringIO()
 print('hello, world', file=buf)
 assert buf.getvalue() == 'hello, world\n', buf.getvalue()
+
+buf = io.StringIO()
+print(1, None, [2], sep='-', end='.', file=buf)
+assert buf.getvalue() == '1-None-[2].', buf.getvalue()
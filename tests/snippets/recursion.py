@@ -0,0 +1,117 @@
+import sys
+from testutils import assert_raises
+
+
+def depth():
+    """The number of frames on the stack, not counting this one."""
+    frame = sys._getframe(1)
+    n = 0
+    while frame is not None:
+        n += 1
+        frame = frame.f_back
+    return n
+
+
+def recurse_forever(n=0):
+    return recurse_forever(n + 1)
+
+
+def max_depth():
+    """How deep recursion goes before the RecursionError."""
+    reached = 0
+
+    def recurse():
+        nonlocal reached
+        reached = max(reached, depth())
+        recurse()
+
+    with assert_raises(RecursionError):
+        recurse()
+    return reached
+
+
+# Infinite recursion is a RecursionError, which can be caught, and recursion works again after it
+try:
+    recurse_forever()
+except RecursionError as exc:
+    assert str(exc).startswith('maximum recursion depth exceeded'), str(exc)
+else:
+    assert False, 'no RecursionError'
+
+with assert_raises(RecursionError):
+    recurse_forever()
+
+limit = sys.getrecursionlimit()
+assert limit > 100
+
+# Lowering the limit takes effect immediately
+sys.setrecursionlimit(depth() + 60)
+reached = max_depth()
+assert depth() + 50 < reached <= depth() + 60, reached
+sys.setrecursionlimit(depth() + 100)
+reached = max_depth()
+assert depth() + 90 < reached <= depth() + 100, reached
+
+
+# The code handling a RecursionError at the limit can still call functions to clean up
+def cleanup(n):
+    if n > 0:
+        cleanup(n - 1)
+    return 'cleaned up'
+
+
+cleanups = []
+
+
+def recurse_with_cleanup():
+    try:
+        recurse_with_cleanup()
+    except RecursionError:
+        if not cleanups:
+            cleanups.append(cleanup(10))
+        raise
+    finally:
+        if len(cleanups) == 1:
+            cleanups.append(cleanup(10))
+
+
+with assert_raises(RecursionError):
+    recurse_with_cleanup()
+assert cleanups == ['cleaned up', 'cleaned up']
+
+
+# Python code called by native code counts toward the same limit
+class Nested:
+    def __init__(self, inner):
+        self.inner = inner
+
+    def __repr__(self):
+        return 'Nested(%r)' % (self.inner,)
+
+
+nested = None
+for _ in range(sys.getrecursionlimit() * 2):
+    nested = Nested(nested)
+with assert_raises(RecursionError):
+    repr(nested)
+
+nested = []
+for _ in range(sys.getrecursionlimit() * 2):
+    nested = [nested]
+with assert_raises(RecursionError):
+    repr(nested)
+
+assert repr(Nested(Nested(None))) == 'Nested(Nested(None))'
+
+# The limit has to be positive, and above the current depth
+with assert_raises(ValueError):
+    sys.setrecursionlimit(0)
+with assert_raises(ValueError):
+    sys.setrecursionlimit(-5)
+with assert_raises(TypeError):
+    sys.setrecursionlimit(100.0)
+with assert_raises(RecursionError):
+    sys.setrecursionlimit(depth())
+
+sys.setrecursionlimit(limit)
+assert sys.getrecursionlimit() == limit
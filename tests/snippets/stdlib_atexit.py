@@ -0,0 +1,17 @@
+import atexit
+from testutils import assert_raises
+
+
+def handler(*args, **kwargs):
+    pass
+
+
+count = atexit._ncallbacks()
+assert atexit.register(handler, 1, key="value") is handler
+assert atexit.register(handler) is handler
+assert atexit._ncallbacks() == count + 2
+atexit.unregister(handler)
+atexit.unregister(handler)
+
+with assert_raises(TypeError):
+    atexit.register(1)
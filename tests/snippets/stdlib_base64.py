@@ -0,0 +1,68 @@
+import base64
+import binascii
+from testutils import assert_raises
+
+# A subset of CPython's test_base64, on top of the binascii module
+
+# encodebytes / decodebytes
+assert base64.encodebytes(b"www.python.org") == b"d3d3LnB5dGhvbi5vcmc=\n"
+assert base64.encodebytes(b"a") == b"YQ==\n"
+assert base64.encodebytes(b"") == b""
+assert base64.encodebytes(b"abcdefghijklmnopqrstuvwxyz"
+                          b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+                          b"0123456789!@#0^&*();:<>,. []{}") == (
+    b"YWJjZGVmZ2hpamtsbW5vcHFyc3R1dnd4eXpBQkNERUZHSElKS0xNTk9QUVJTVFV"
+    b"WV1hZWjAxMjM0\nNTY3ODkhQCMwXiYqKCk7Ojw+LC4gW117fQ==\n")
+assert base64.encodebytes(bytearray(b'abc')) == b'YWJj\n'
+assert base64.decodebytes(b"d3d3LnB5dGhvbi5vcmc=\n") == b"www.python.org"
+assert base64.decodebytes(b"YQ==\n") == b"a"
+assert base64.decodebytes(b"") == b""
+assert base64.decodebytes(bytearray(b'YWJj\n')) == b'abc'
+with assert_raises(TypeError):
+    base64.encodebytes("")
+
+# b64encode / b64decode
+assert base64.b64encode(b"www.python.org") == b"d3d3LnB5dGhvbi5vcmc="
+assert base64.b64encode(b'\x00') == b'AA=='
+assert base64.b64encode(b"ab") == b"YWI="
+assert base64.b64encode(b"") == b""
+assert base64.b64encode(b'\xd3V\xbeo\xf7\x1d', altchars=b'*$') == b'01a*b$cd'
+assert base64.b64encode(memoryview(b'\xd3V\xbeo\xf7\x1d'), altchars=b'*$') == b'01a*b$cd'
+assert base64.standard_b64encode(b"www.python.org") == b"d3d3LnB5dGhvbi5vcmc="
+assert base64.urlsafe_b64encode(b'\xd3V\xbeo\xf7\x1d') == b'01a-b_cd'
+with assert_raises(TypeError):
+    base64.b64encode("")
+
+assert base64.b64decode(b"d3d3LnB5dGhvbi5vcmc=") == b"www.python.org"
+assert base64.b64decode("d3d3LnB5dGhvbi5vcmc=") == b"www.python.org"
+assert base64.b64decode(b'AA==') == b'\x00'
+assert base64.b64decode(b"YWI=") == b"ab"
+assert base64.b64decode(b"") == b""
+assert base64.b64decode(b'01a*b$cd', altchars=b'*$') == b'\xd3V\xbeo\xf7\x1d'
+assert base64.b64decode('01a*b$cd', altchars='*$') == b'\xd3V\xbeo\xf7\x1d'
+assert base64.standard_b64decode(b"YWI=") == b"ab"
+assert base64.urlsafe_b64decode(b'01a-b_cd') == b'\xd3V\xbeo\xf7\x1d'
+assert base64.urlsafe_b64decode('01a-b_cd') == b'\xd3V\xbeo\xf7\x1d'
+# Characters outside the alphabet are skipped
+assert base64.b64decode(b'%3d==') == b'\xdd'
+assert base64.b64decode(b'$3d==') == b'\xdd'
+assert base64.b64decode(b'[==') == b''
+
+with assert_raises(binascii.Error):
+    base64.b64decode(b'abc')
+with assert_raises(binascii.Error):
+    base64.b64decode('abc')
+
+# b32encode / b32decode
+assert base64.b32encode(b'') == b''
+assert base64.b32encode(b'\x00') == b'AA======'
+assert base64.b32encode(b'a') == b'ME======'
+assert base64.b32encode(b'abcde') == b'MFRGGZDF'
+assert base64.b32decode(b'MFRGGZDF') == b'abcde'
+assert base64.b32decode('me======', casefold=True) == b'a'
+with assert_raises(binascii.Error):
+    base64.b32decode(b'me======')
+
+# b16encode
+assert base64.b16encode(b'\x01\x02\xab\xcd\xef') == b'0102ABCDEF'
+assert base64.b16encode(b'\x00') == b'00'
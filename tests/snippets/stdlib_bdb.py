@@ -0,0 +1,51 @@
+import bdb
+import sys
+
+
+def compute(a):
+    b = a * 2
+    c = b + 1
+    return c
+
+
+class Recorder(bdb.Bdb):
+    def __init__(self):
+        super().__init__()
+        self.events = []
+
+    def user_line(self, frame):
+        line = frame.f_lineno - compute.__code__.co_firstlineno
+        self.events.append((frame.f_code.co_name, 'line', line))
+        self.set_step()
+
+    def user_return(self, frame, value):
+        self.events.append((frame.f_code.co_name, 'return', value))
+
+
+recorder = Recorder()
+assert recorder.runcall(compute, 3) == 7
+assert recorder.events == [
+    ('compute', 'line', 1),
+    ('compute', 'line', 2),
+    ('compute', 'line', 3),
+    ('compute', 'return', 7),
+], recorder.events
+assert sys.gettrace() is None
+
+
+class Breaker(bdb.Bdb):
+    def __init__(self):
+        super().__init__()
+        self.stops = []
+
+    def user_line(self, frame):
+        if self.break_here(frame):
+            self.stops.append((frame.f_lineno, dict(frame.f_locals)))
+        self.set_continue()
+
+
+breaker = Breaker()
+breaker.set_break(__file__, compute.__code__.co_firstlineno + 2)
+assert breaker.runcall(compute, 5) == 11
+assert breaker.stops == [(compute.__code__.co_firstlineno + 2, {'a': 5, 'b': 10})], breaker.stops
+breaker.clear_all_breaks()
@@ -7,8 +7,8 @@ h = binascii.hexlify
 
 assert h(b"abc") == b"616263"
 assert h(1000 * b"x") == 1000 * b"78"
-# bytearray not supported yet
-# assert h(bytearray(b"a")) = b"61"
+assert h(bytearray(b"a")) == b"61"
+assert h(b"\x0a\xff") == b"0aff"
 assert binascii.b2a_hex(b"aa") == b"6161"
 
 with assert_raises(TypeError):
@@ -37,3 +37,38 @@ with assert_raises(ValueError):
 assert binascii.crc32(b"hello world") == 222957957
 assert binascii.crc32(b"hello world", 555555) == 1216827162
 assert binascii.crc32(b"goodbye interesting world",777777) == 1885538403
+
+
+# base64 tests
+assert binascii.b2a_base64(b"abc") == b"YWJj\n"
+assert binascii.b2a_base64(b"ab", newline=False) == b"YWI="
+assert binascii.a2b_base64(b"YWJj\n") == b"abc"
+assert binascii.a2b_base64(b"YW\nJj") == b"abc"
+assert binascii.a2b_base64(b"YWI=") == b"ab"
+assert binascii.a2b_base64(b"") == b""
+# bytes outside the alphabet are skipped, and bits that don't make a byte dropped
+assert binascii.a2b_base64(b"%3d==") == b"\xdd"
+assert binascii.a2b_base64(b"Y*W I=") == b"ab"
+# padding ends the data, but a single '=' after two characters doesn't
+assert binascii.a2b_base64(b"YQ==YWJj") == b"a"
+assert binascii.a2b_base64(b"YW=I=") == b"ab"
+
+with assert_raises(binascii.Error):
+    binascii.a2b_base64(b"YWI")  # Incorrect padding
+
+with assert_raises(binascii.Error):
+    binascii.a2b_base64(b"YWJjZ")
+
+# the a2b functions take ASCII strings as well
+assert uh("abcd") == b"\xab\xcd"
+assert binascii.a2b_base64("YWJj\n") == b"abc"
+
+with assert_raises(ValueError):
+    uh("€€")  # Non-ASCII string
+
+with assert_raises(TypeError):
+    binascii.a2b_base64(42)
+
+assert issubclass(binascii.Error, ValueError)
+assert issubclass(binascii.Incomplete, Exception)
+assert not issubclass(binascii.Incomplete, ValueError)
@@ -0,0 +1,108 @@
+import cProfile
+import io
+import os
+import pstats
+import sys
+
+
+def fib(n):
+    return n if n < 2 else fib(n - 1) + fib(n - 2)
+
+
+def work():
+    total = 0
+    for _ in range(3):
+        total += fib(10)
+    return total + len([1])
+
+
+def key(func):
+    code = func.__code__
+    return code.co_filename, code.co_firstlineno, code.co_name
+
+
+prof = cProfile.Profile()
+prof.enable()
+assert sys.getprofile() is not None
+assert work() == 166
+prof.disable()
+assert sys.getprofile() is None
+
+prof.create_stats()
+stats = prof.stats
+
+# fib(10) makes 177 calls, of which the 3 made from work aren't recursive
+cc, nc, tt, ct, callers = stats[key(fib)]
+assert cc == 3 and nc == 3 * 177, (cc, nc)
+assert 0 <= tt <= ct
+assert callers[key(work)][:2] == (3, 3), callers[key(work)]
+assert callers[key(fib)][0] == 3 * 176, callers[key(fib)]
+
+cc, nc, tt, ct, callers = stats[key(work)]
+assert cc == nc == 1
+assert stats[key(fib)][3] <= ct
+
+builtins = [name for (filename, line, name) in stats if filename == '~']
+assert any('len' in name for name in builtins), builtins
+
+# The entries the profiler itself gives
+entries = {entry.code: entry for entry in prof.getstats()}
+entry = entries[fib.__code__]
+assert entry.callcount == 3 * 177 and entry.reccallcount == 3 * 176
+assert entry.calls[0].code is fib.__code__
+
+prof.clear()
+assert prof.getstats() == []
+
+# Builtins aren't profiled when asked not to
+prof = cProfile.Profile(builtins=False)
+prof.enable()
+len([])
+prof.disable()
+assert all(not isinstance(entry.code, str) or 'disable' in entry.code
+           for entry in prof.getstats())
+
+# A custom timer that counts its calls
+ticks = 0
+def timer():
+    global ticks
+    ticks += 1
+    return ticks
+
+prof = cProfile.Profile(timer)
+assert prof.runcall(fib, 3) == 2
+prof.create_stats()
+cc, nc, tt, ct, callers = prof.stats[key(fib)]
+assert nc == 5 and cc == 1
+assert tt > 0 and ct >= tt
+assert ticks > 0
+
+with cProfile.Profile() as prof:
+    fib(4)
+prof.create_stats()
+assert prof.stats[key(fib)][1] == 9
+
+# Reports
+stream = io.StringIO()
+pstats.Stats(prof, stream=stream).sort_stats('cumulative').print_stats()
+report = stream.getvalue()
+assert 'function calls' in report, report
+assert 'fib' in report, report
+
+stream = io.StringIO()
+pstats.Stats(prof, stream=stream).sort_stats(pstats.SortKey.CALLS).print_callers('fib')
+assert 'fib' in stream.getvalue()
+
+assert pstats.SortKey('ncalls') is pstats.SortKey.CALLS
+assert pstats.SortKey.TIME == 'time'
+
+# cProfile.run writes the stats to a file that pstats reads
+filename = os.path.join(os.path.dirname(os.path.abspath(__file__)), 'fib.prof')
+try:
+    cProfile.run('fib(5)', filename)
+    stats = pstats.Stats(filename)
+    assert stats.stats[key(fib)][1] == 15
+    assert stats.total_calls >= 15
+finally:
+    if os.path.exists(filename):
+        os.remove(filename)
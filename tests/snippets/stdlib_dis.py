@@ -0,0 +1,104 @@
+import dis
+import inspect
+import io
+
+from testutils import assert_raises
+
+
+def f(x):
+    if x:
+        return x + 1
+    return None
+
+
+instructions = list(dis.get_instructions(f))
+assert all(isinstance(instr, dis.Instruction) for instr in instructions)
+offsets = [instr.offset for instr in instructions]
+assert offsets == sorted(set(offsets))
+
+first_line = f.__code__.co_firstlineno
+lines = {instr.starts_line for instr in instructions if instr.starts_line is not None}
+assert {first_line + 1, first_line + 2, first_line + 3} <= lines
+shifted = list(dis.get_instructions(f, first_line=100))
+assert [instr.starts_line for instr in shifted] == [
+    None if line is None else line - first_line + 100
+    for line in (instr.starts_line for instr in instructions)
+]
+
+# Constants are resolved, along with their index in co_consts
+constants = [instr for instr in instructions if instr.opname == 'LOAD_CONST']
+assert 1 in [instr.argval for instr in constants]
+for instr in constants:
+    assert f.__code__.co_consts[instr.arg] == instr.argval
+    assert instr.argrepr == repr(instr.argval)
+
+assert any(instr.argval == 'x' and instr.argrepr == 'x' for instr in instructions)
+
+# Jumps point at the offsets of jump targets
+targets = {instr.argval for instr in instructions if instr.argrepr.startswith('to ')}
+assert targets
+assert targets <= {instr.offset for instr in instructions if instr.is_jump_target}
+
+out = io.StringIO()
+dis.dis(f, file=out)
+text = out.getvalue()
+assert '>>' in text
+assert '(to ' in text
+assert '(1)' in text
+
+bytecode = dis.Bytecode(f)
+assert [instr.offset for instr in bytecode] == offsets
+assert bytecode.codeobj is f.__code__
+assert bytecode.first_line == first_line
+assert repr(bytecode).startswith('Bytecode(<function f')
+assert bytecode.dis() == text
+assert '-->' in dis.Bytecode(f, current_offset=offsets[1]).dis()
+
+info = bytecode.info()
+assert 'Name:              f' in info
+assert 'NEWLOCALS' in info
+assert dis.code_info(f) == info
+assert dis.pretty_flags(0x23) == 'OPTIMIZED, NEWLOCALS, GENERATOR'
+assert inspect.CO_GENERATOR == 0x20
+
+
+# Nested code objects are disassembled too
+out = io.StringIO()
+dis.dis("def g():\n    return 1\n", file=out)
+assert 'Disassembly of <code object g' in out.getvalue()
+
+
+def gen():
+    yield 1
+
+
+assert dis.Bytecode(gen()).codeobj.co_name == 'gen'
+
+
+class A:
+    def m(self):
+        return self
+
+
+out = io.StringIO()
+dis.dis(A, file=out)
+assert 'Disassembly of m:' in out.getvalue()
+
+
+def divide(a, b):
+    return a / b
+
+
+try:
+    divide(1, 0)
+except ZeroDivisionError as e:
+    tb = e.__traceback__
+out = io.StringIO()
+dis.distb(tb.tb_next, file=out)
+current = [line for line in out.getvalue().splitlines() if '-->' in line]
+assert len(current) == 1
+assert 'BINARY' in current[0], current
+assert dis.Bytecode.from_traceback(tb).dis() == out.getvalue()
+
+with assert_raises(TypeError):
+    dis.dis(42)
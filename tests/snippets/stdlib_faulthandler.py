@@ -0,0 +1,97 @@
+import faulthandler
+import os
+import signal
+import subprocess
+import sys
+import time
+
+from testutils import assert_raises
+
+
+def function_names(output):
+    return [line.rsplit(" in ", 1)[1] for line in output.splitlines() if line.startswith("  File ")]
+
+
+def outer(file, all_threads):
+    return inner(file, all_threads)
+
+
+def inner(file, all_threads):
+    faulthandler.dump_traceback(file, all_threads)
+
+
+path = "faulthandler_test_%d" % os.getpid()
+try:
+    with open(path, "w") as f:
+        outer(f, False)
+        outer(f.fileno(), True)
+    with open(path) as f:
+        output = f.read()
+finally:
+    os.remove(path)
+
+stack, threads = output.split("Stack (most recent call first):\n")[1].split("Current thread 0x")
+assert function_names(stack) == ["inner", "outer", "<module>"], output
+assert threads.split("\n")[0].endswith(" (most recent call first):"), output
+assert function_names(threads) == ["inner", "outer", "<module>"], output
+assert '.py", line 20 in inner' in stack, output
+
+with assert_raises(ValueError):
+    faulthandler.dump_traceback(-1)
+with assert_raises(ValueError):
+    faulthandler.dump_traceback_later(0)
+
+assert not faulthandler.is_enabled()
+faulthandler.enable()
+assert faulthandler.is_enabled()
+assert faulthandler.disable()
+assert not faulthandler.is_enabled()
+assert not faulthandler.disable()
+
+faulthandler.dump_traceback_later(60)
+faulthandler.cancel_dump_traceback_later()
+faulthandler.cancel_dump_traceback_later()
+
+if "win" not in sys.platform:
+    # A fatal signal dumps the stack of what was running, here wait(). The child creates the
+    # marker file right before it sleeps, so that the signal doesn't arrive sooner.
+    marker = "faulthandler_marker_%d" % os.getpid()
+    source = """\
+import faulthandler, time
+faulthandler.enable()
+def wait():
+    open(%r, "w")
+    time.sleep(10)
+wait()
+""" % marker
+    child = subprocess.Popen(
+        [sys.executable, "-c", source], stdout=subprocess.PIPE, stderr=subprocess.PIPE
+    )
+    while not os.path.exists(marker):
+        time.sleep(0.05)
+    os.remove(marker)
+    subprocess.Popen(["kill", "-SEGV", str(child.pid)]).wait()
+    stdout, stderr = child.communicate(timeout=15)
+    child.wait()
+    assert child.returncode == -signal.SIGSEGV, stderr
+    stderr = stderr.decode()
+    assert stderr.startswith("Fatal Python error: Segmentation fault\n\n"), stderr
+    assert function_names(stderr) == ["wait", "<module>"], stderr
+
+    # The watchdog dumps the stack from its own thread, and exit=True ends the process
+    source = """\
+import faulthandler, time
+faulthandler.dump_traceback_later(0.2, exit=True)
+def wait():
+    time.sleep(10)
+wait()
+"""
+    child = subprocess.Popen(
+        [sys.executable, "-c", source], stdout=subprocess.PIPE, stderr=subprocess.PIPE
+    )
+    stdout, stderr = child.communicate(timeout=15)
+    child.wait()
+    assert child.returncode == 1, stderr
+    stderr = stderr.decode()
+    assert stderr.startswith("Timeout (0:00:00.200000)!\nThread 0x"), stderr
+    assert function_names(stderr) == ["wait", "<module>"], stderr
@@ -0,0 +1,146 @@
+import gc
+import weakref
+
+from testutils import assert_raises
+
+assert gc.isenabled()
+gc.disable()
+assert not gc.isenabled()
+gc.enable()
+assert gc.isenabled()
+
+
+class Node:
+    pass
+
+
+# Objects that reference each other are freed by a collection
+a = Node()
+b = Node()
+a.other = b
+b.other = a
+ref = weakref.ref(a)
+del a, b
+assert gc.collect() >= 2
+assert ref() is None
+
+holder = Node()
+holder.items = [holder, (holder,), {'self': holder}]
+ref = weakref.ref(holder)
+del holder
+gc.collect()
+assert ref() is None
+
+# A frame, its exception and the traceback
+def make_cycle():
+    marker = Node()
+    try:
+        raise ValueError
+    except ValueError as e:
+        exc = e
+    return weakref.ref(marker)
+
+
+ref = make_cycle()
+gc.collect()
+assert ref() is None
+
+# Cycles that are still referenced are kept
+kept = Node()
+kept.self = kept
+ref = weakref.ref(kept)
+gc.collect()
+assert ref() is kept
+del kept
+
+# __del__ is called once, and can resurrect the object
+deleted = []
+
+
+class Finalized:
+    def __del__(self):
+        deleted.append(self)
+
+
+f = Finalized()
+f.self = f
+del f
+gc.collect()
+assert len(deleted) == 1
+f = deleted.pop()
+ref = weakref.ref(f)
+del f
+gc.collect()
+assert deleted == []
+assert ref() is None
+
+# Weak reference callbacks are called
+called = []
+n = Node()
+n.self = n
+r = weakref.ref(n, called.append)
+del n
+gc.collect()
+assert called == [r]
+
+# Thresholds and counts
+old = gc.get_threshold()
+assert len(old) == 3
+gc.set_threshold(100, 5)
+assert gc.get_threshold() == (100, 5, old[2])
+gc.set_threshold(*old)
+assert gc.get_threshold() == old
+assert len(gc.get_count()) == 3
+
+# Making objects collects the youngest generation when it passes its threshold
+gc.collect()
+gc.set_threshold(10)
+n = Node()
+n.self = n
+ref = weakref.ref(n)
+del n
+lists = [[] for _ in range(100)]
+assert ref() is None
+gc.set_threshold(*old)
+
+with assert_raises(ValueError):
+    gc.collect(3)
+with assert_raises(ValueError):
+    gc.collect(-1)
+assert gc.collect(0) >= 0
+
+# Introspection
+n = Node()
+assert any(obj is n for obj in gc.get_objects())
+with assert_raises(ValueError):
+    gc.get_objects(3)
+
+x = []
+container = [x]
+assert any(obj is x for obj in gc.get_referents(container))
+assert any(obj is container for obj in gc.get_referrers(x))
+assert gc.get_referents(1) == []
+
+assert gc.is_tracked([])
+assert gc.is_tracked(Node())
+assert not gc.is_tracked(1)
+assert not gc.is_tracked('a')
+
+# Debugging flags
+assert gc.DEBUG_LEAK == gc.DEBUG_COLLECTABLE | gc.DEBUG_UNCOLLECTABLE | gc.DEBUG_SAVEALL
+assert gc.get_debug() == 0
+gc.set_debug(gc.DEBUG_SAVEALL)
+assert gc.get_debug() == gc.DEBUG_SAVEALL
+
+
+class Saved:
+    pass
+
+
+saved = Saved()
+saved.self = saved
+del saved
+gc.collect()
+gc.set_debug(0)
+assert any(isinstance(obj, Saved) for obj in gc.garbage)
+gc.garbage.clear()
@@ -1,5 +1,6 @@
 
 import hashlib
+from testutils import assert_raises
 
 # print(hashlib.md5)
 h = hashlib.md5()
@@ -31,3 +32,46 @@ assert h.hexdigest() == 'a0e1ad0c123c9c65e8ef850db2ce4b5cef2c35b06527c615b015435
 h.update(b'bla')
 print(h.hexdigest())
 assert h.hexdigest() == '25738bfe4cc104131e1b45bece4dfd4e7e1d6f0dffda1211e996e9d5d3b66e81'
+
+# block_size and copy
+assert hashlib.md5().block_size == 64
+assert hashlib.sha1().block_size == 64
+assert hashlib.sha256().block_size == 64
+assert hashlib.sha512().block_size == 128
+assert hashlib.sha3_256().block_size == 136
+
+h = hashlib.sha1(b'abc')
+c = h.copy()
+c.update(b'def')
+assert c.name == 'sha1'
+assert h.hexdigest() == 'a9993e364706816aba3e25717850c26c9cd0d89d'
+assert c.hexdigest() == hashlib.sha1(b'abcdef').hexdigest()
+h.update(memoryview(b'def'))
+assert h.hexdigest() == c.hexdigest()
+
+# hashlib.new takes the names in any case, and the data as a keyword
+assert hashlib.new('SHA224').hexdigest() == hashlib.sha224().hexdigest()
+assert hashlib.new('sha384', b'abc').hexdigest() == hashlib.sha384(b'abc').hexdigest()
+assert hashlib.md5(b'abc', usedforsecurity=False).hexdigest() == '900150983cd24fb0d6963f7d28e17f72'
+
+with assert_raises(ValueError):
+    hashlib.new('unknown')
+
+assert {'md5', 'sha1', 'sha224', 'sha256', 'sha384', 'sha512'} <= hashlib.algorithms_guaranteed
+assert hashlib.algorithms_guaranteed <= hashlib.algorithms_available
+
+# Updating in pieces, with any bytes-like object, is the same as hashing all at once
+data = bytes(range(256)) * 1000
+for name in ['md5', 'sha1', 'sha224', 'sha256', 'sha384', 'sha512']:
+    whole = hashlib.new(name, data)
+    pieces = hashlib.new(name)
+    view = memoryview(data)
+    for i in range(0, len(data), 1000):
+        pieces.update(view[i:i + 1000])
+    assert pieces.digest() == whole.digest(), name
+    incremental = hashlib.new(name)
+    incremental.update(bytearray(data[:12345]))
+    incremental.update(data[12345:])
+    assert incremental.hexdigest() == whole.hexdigest(), name
+
+assert hashlib.sha256(b'a' * 1000000).hexdigest() == 'cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0'
@@ -0,0 +1,67 @@
+import hmac
+import hashlib
+from testutils import assert_raises
+
+# Test vectors from RFC 2202 (md5, sha1) and RFC 4231 (sha2), as in CPython's test_hmac
+def check(digestmod, key, data, digest):
+    h = hmac.HMAC(key, data, digestmod=digestmod)
+    assert h.hexdigest().upper() == digest.upper(), (digestmod, key, data)
+    assert h.digest() == bytes.fromhex(digest)
+    assert hmac.digest(key, data, digestmod) == bytes.fromhex(digest)
+    assert hmac.new(key, data, digestmod).hexdigest() == digest.lower()
+
+check('md5', b'\x0b' * 16, b'Hi There', '9294727A3638BB1C13F48EF8158BFC9D')
+check('md5', b'Jefe', b'what do ya want for nothing?', '750c783e6ab0b503eaa86e310a5db738')
+check('md5', b'\xaa' * 16, b'\xdd' * 50, '56be34521d144c88dbb8c733f0e8b3f6')
+check('md5', b'\xaa' * 80, b'Test Using Larger Than Block-Size Key - Hash Key First',
+      '6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd')
+
+check(hashlib.sha1, b'\x0b' * 20, b'Hi There', 'b617318655057264e28bc0b6fb378c8ef146be00')
+check(hashlib.sha1, b'Jefe', b'what do ya want for nothing?',
+      'effcdf6ae5eb2fa2d27416d5f184df9c259a7c79')
+check(hashlib.sha1, b'\xaa' * 80,
+      b'Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data',
+      'e8e99d0f45237d786d6bbaa7965c7808bbff1a91')
+
+check('sha224', b'\x0b' * 20, b'Hi There',
+      '896fb1128abbdf196832107cd49df33f47b4b1169912ba4f53684b22')
+check('sha256', b'\x0b' * 20, b'Hi There',
+      'b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7')
+check('sha384', b'Jefe', b'what do ya want for nothing?',
+      'af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e'
+      '8e2240ca5e69e2c78b3239ecfab21649')
+check('sha512', b'\xaa' * 131, b'Test Using Larger Than Block-Size Key - Hash Key First',
+      '80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352'
+      '6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598')
+
+# The object API
+h = hmac.new(b'key', digestmod='sha256')
+assert h.name == 'hmac-sha256'
+assert h.digest_size == 32
+assert h.block_size == 64
+h.update(b'The quick brown fox ')
+c = h.copy()
+h.update(b'jumps over the lazy dog')
+assert h.hexdigest() == 'f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8'
+assert c.digest() != h.digest()
+c.update(memoryview(b'jumps over the lazy dog'))
+assert c.digest() == h.digest()
+
+with assert_raises(TypeError):
+    hmac.new(b'key')  # digestmod is required
+with assert_raises(TypeError):
+    hmac.new('key', digestmod='md5')
+
+# compare_digest
+assert hmac.compare_digest(b'abc', b'abc')
+assert not hmac.compare_digest(b'abc', b'abd')
+assert not hmac.compare_digest(b'abc', b'ab')
+assert hmac.compare_digest(bytearray(b'abc'), memoryview(b'abc'))
+assert hmac.compare_digest('abc', 'abc')
+assert not hmac.compare_digest('abc', 'xbc')
+with assert_raises(TypeError):
+    hmac.compare_digest('abc', b'abc')
+with assert_raises(TypeError):
+    hmac.compare_digest(1, 1)
+with assert_raises(TypeError):
+    hmac.compare_digest('€', '€')
@@ -0,0 +1,16 @@
+import keyword
+
+from testutils import assert_raises
+
+assert keyword.iskeyword('if')
+assert keyword.iskeyword('None')
+assert not keyword.iskeyword('print')
+assert not keyword.iskeyword('If')
+with assert_raises(TypeError):
+    keyword.iskeyword()
+
+assert isinstance(keyword.__doc__, str)
+assert keyword.kwlist == sorted(keyword.kwlist)
+for word in ['False', 'None', 'True', 'and', 'async', 'await', 'while', 'yield']:
+    assert word in keyword.kwlist
+    assert keyword.iskeyword(word)
@@ -0,0 +1,96 @@
+import functools
+import locale
+import sys
+
+words = ["zebra", "Apple", "éclair", "apple", "Zebra", "eclair", "Éclair", "banana", "Banana"]
+
+
+def sign(n):
+    return (n > 0) - (n < 0)
+
+
+def check_consistent(pairs):
+    # strcoll orders strings the same way as their strxfrm keys do
+    for a, b in pairs:
+        ka, kb = locale.strxfrm(a), locale.strxfrm(b)
+        assert sign(locale.strcoll(a, b)) == (ka > kb) - (ka < kb), (a, b)
+        assert (locale.strcoll(a, b) == 0) == (a == b), (a, b)
+
+
+def random_words(seed, alphabet="aAbBeEéÉèzZ \x01"):
+    # The same pseudo-random words everywhere
+    while True:
+        seed = (seed * 1103515245 + 12345) % 2 ** 31
+        length = seed % 4
+        word = ""
+        for _ in range(length):
+            seed = (seed * 1103515245 + 12345) % 2 ** 31
+            word += alphabet[seed % len(alphabet)]
+        yield word
+
+
+words_a, words_b = random_words(1), random_words(2)
+pairs = [(next(words_a), next(words_b)) for _ in range(500)]
+
+# Programs start in the "C" locale, where the order is the same as without a key
+assert locale.setlocale(locale.LC_COLLATE) == "C"
+assert sorted(words, key=locale.strxfrm) == sorted(words)
+assert sorted(words) == [
+    "Apple", "Banana", "Zebra", "apple", "banana", "eclair", "zebra", "Éclair", "éclair",
+]
+assert locale.strcoll("a", "b") < 0
+assert locale.strcoll("b", "a") > 0
+assert locale.strcoll("a", "a") == 0
+check_consistent(pairs)
+
+for func, args in [(locale.strcoll, ("a\x00", "a")), (locale.strxfrm, ("a\x00",))]:
+    try:
+        func(*args)
+    except ValueError:
+        pass
+    else:
+        assert False, "strings with a null character can't be collated"
+
+assert locale.setlocale(locale.LC_ALL, "C") == "C"
+assert locale.setlocale(locale.LC_ALL, "POSIX") == "C"
+assert locale.localeconv()["decimal_point"] == "."
+
+if sys.implementation.name == "rustpython":
+    # Any other locale collates with the table: letters, then accents, then case
+    expected = [
+        "apple", "Apple", "banana", "Banana", "eclair", "éclair", "Éclair", "zebra", "Zebra",
+    ]
+    assert locale.setlocale(locale.LC_COLLATE, "en_US.UTF-8") == "en_US.UTF-8"
+    assert sorted(words, key=locale.strxfrm) == expected
+    assert sorted(reversed(words), key=locale.strxfrm) == expected
+    assert sorted(words, key=functools.cmp_to_key(locale.strcoll)) == expected
+    assert locale.strcoll("éclair", "Eclair") > 0
+    assert locale.strcoll("Eclair", "éclair") < 0
+    assert locale.strcoll("éclair", "éclair") == 0
+    check_consistent(pairs)
+
+    assert locale.setlocale(locale.LC_ALL) == (
+        "LC_CTYPE=C;LC_NUMERIC=C;LC_TIME=C;LC_COLLATE=en_US.UTF-8;LC_MONETARY=C;LC_MESSAGES=C"
+    )
+    try:
+        locale.setlocale(locale.LC_NUMERIC, "de_DE.UTF-8")
+    except locale.Error:
+        pass
+    else:
+        assert False, "only the collation of other locales is known"
+    assert locale.setlocale(locale.LC_NUMERIC) == "C"
+
+    # collation_key is the table whatever the locale is
+    locale.setlocale(locale.LC_ALL, "C")
+    assert sorted(words) != expected
+    assert sorted(words, key=locale.collation_key) == expected
+    assert locale.collation_key("Straße") > locale.collation_key("strasse")
+    assert locale.collation_key("a\x00") > locale.collation_key("a")
+
+    # The threads share the locale, like in C
+    import threading
+    thread = threading.Thread(target=locale.setlocale, args=(locale.LC_COLLATE, "en_US.UTF-8"))
+    thread.start()
+    thread.join()
+    assert locale.setlocale(locale.LC_COLLATE) == "en_US.UTF-8"
+    locale.setlocale(locale.LC_ALL, "C")
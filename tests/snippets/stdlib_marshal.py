@@ -5,3 +5,52 @@ dumped = marshal.dumps(orig)
 loaded = marshal.loads(dumped)
 
 assert eval(loaded) == eval(orig)
+
+import io
+
+from testutils import assert_raises
+
+values = [
+    None, True, False, ..., StopIteration,
+    0, -1, 2 ** 31, -(2 ** 100), 1.5, float('inf'), -0.0, 1 - 2j,
+    '', 'h\xe9llo', b'bytes',
+    (), (1, 'a'), [1, [2, [3]]], {'a': 1, 2: (3,)},
+    set(), {1, 2}, frozenset({'x'}),
+]
+for value in values:
+    loaded = marshal.loads(marshal.dumps(value))
+    assert loaded == value and type(loaded) is type(value), (value, loaded)
+
+assert str(marshal.loads(marshal.dumps(-0.0))) == '-0.0'
+assert marshal.loads(marshal.dumps(bytearray(b'ab'))) == b'ab'
+assert isinstance(marshal.version, int)
+
+# Code objects nested in other values
+loaded = marshal.loads(marshal.dumps({'code': (orig,)}))
+assert eval(loaded['code'][0]) == 2
+
+# loads ignores what follows the value
+assert marshal.loads(marshal.dumps(5) + b'junk') == 5
+
+f = io.BytesIO()
+marshal.dump([1, 2], f)
+marshal.dump('second', f)
+f.seek(0)
+assert marshal.load(f) == [1, 2]
+assert marshal.load(f) == 'second'
+with assert_raises(EOFError):
+    marshal.load(f)
+
+# load reads no further than the value
+f = io.BytesIO(marshal.dumps({'key': (1.5, None)}) + b'rest')
+assert marshal.load(f) == {'key': (1.5, None)}
+assert f.read() == b'rest'
+
+with assert_raises(ValueError):
+    marshal.dumps(object())
+with assert_raises(ValueError):
+    marshal.dumps(type('MyInt', (int,), {})(1))
+with assert_raises(EOFError):
+    marshal.loads(marshal.dumps('abc')[:-1])
+with assert_raises(ValueError):
+    marshal.loads(b'\x01')
@@ -0,0 +1,92 @@
+import os
+import pathlib
+import subprocess
+import sys
+
+from testutils import assert_raises
+
+
+class Path:
+    def __init__(self, path):
+        self.path = path
+
+    def __fspath__(self):
+        return self.path
+
+
+class BadPath:
+    def __fspath__(self):
+        return 42
+
+
+assert os.fspath("a") == "a"
+assert os.fspath(b"a") == b"a"
+assert os.fspath(Path("a")) == "a"
+assert os.fspath(Path(b"a")) == b"a"
+assert os.fspath(pathlib.Path("a")) == "a"
+
+with assert_raises(TypeError) as cm:
+    os.fspath(BadPath())
+assert str(cm.exception) == "expected BadPath.__fspath__() to return str or bytes, not int"
+with assert_raises(TypeError) as cm:
+    os.fspath(42)
+assert str(cm.exception) == "expected str, bytes or os.PathLike object, not int"
+
+assert isinstance(Path("a"), os.PathLike)
+assert isinstance(pathlib.Path("a"), os.PathLike)
+assert not isinstance("a", os.PathLike)
+
+base = "fspath_test_%d" % os.getpid()
+os.mkdir(Path(base))
+try:
+    assert os.path.isdir(Path(base))
+    data = os.path.join(Path(base), Path("data"))
+    assert data == os.path.join(base, "data")
+
+    with open(Path(data), "w") as f:
+        f.write("content")
+    with open(pathlib.Path(data)) as f:
+        assert f.read() == "content"
+
+    assert os.path.exists(Path(data))
+    assert os.stat(Path(data)).st_size == 7
+    assert os.lstat(pathlib.Path(data)).st_size == 7
+    assert os.access(Path(data), os.R_OK)
+    os.chmod(Path(data), 0o600)
+    assert os.stat(data).st_mode & 0o777 == 0o600
+
+    assert os.listdir(Path(base)) == ["data"]
+    assert os.listdir(Path(base.encode())) == [b"data"]
+    entries = list(os.scandir(Path(base.encode())))
+    assert [e.name for e in entries] == [b"data"]
+    assert os.fspath(entries[0]) == os.path.join(base, "data").encode()
+    assert os.stat(entries[0]).st_size == 7
+
+    moved = os.path.join(base, "moved")
+    os.rename(Path(data), Path(moved))
+    assert os.listdir(base) == ["moved"]
+
+    if "win" not in sys.platform:
+        link = os.path.join(base, "link")
+        os.symlink(Path("moved"), Path(link))
+        assert os.readlink(Path(link)) == "moved"
+        assert os.readlink(Path(link.encode())) == b"moved"
+        os.remove(Path(link))
+
+        child = subprocess.Popen([Path("ls")], cwd=Path(base), stdout=subprocess.PIPE)
+        assert child.communicate()[0] == b"moved\n"
+
+    cwd = os.getcwd()
+    os.chdir(Path(base))
+    try:
+        assert os.listdir() == ["moved"]
+    finally:
+        os.chdir(cwd)
+
+    for func in [open, os.stat, os.listdir, os.mkdir, os.remove, os.chdir]:
+        assert_raises(TypeError, lambda: func(BadPath()))
+        assert_raises(TypeError, lambda: func(1.5))
+
+    os.remove(Path(moved))
+finally:
+    os.rmdir(Path(base))
@@ -0,0 +1,51 @@
+import importlib.util
+import marshal
+import os
+import py_compile
+import sys
+
+from testutils import assert_raises
+
+# The module and its cache are written next to this file, and removed at the end
+tmp = os.path.join(os.path.dirname(os.path.abspath(__file__)), "py_compile_data")
+os.mkdir(tmp)
+source = os.path.join(tmp, "pyc_module.py")
+try:
+    with open(source, "w") as f:
+        f.write("def double(x):\n    return 2 * x\n\nvalue = double(21)\n")
+
+    cfile = py_compile.compile(source, doraise=True)
+    assert cfile == importlib.util.cache_from_source(source)
+    assert os.path.basename(os.path.dirname(cfile)) == "__pycache__"
+
+    with open(cfile, "rb") as f:
+        data = f.read()
+    assert data[:4] == importlib.util.MAGIC_NUMBER
+    assert int.from_bytes(data[4:8], "little") == 0
+    assert int.from_bytes(data[12:16], "little") == os.stat(source).st_size
+
+    namespace = {}
+    exec(marshal.loads(data[16:]), namespace)
+    assert namespace["value"] == 42
+    exec(marshal.loads(memoryview(data)[16:]), namespace)
+    assert namespace["value"] == 42
+
+    # The cached module is what the import system loads.
+    sys.path.insert(0, tmp)
+    try:
+        import pyc_module
+        assert pyc_module.value == 42
+        assert pyc_module.__cached__ == cfile
+    finally:
+        sys.path.remove(tmp)
+finally:
+    cache = os.path.join(tmp, "__pycache__")
+    if os.path.isdir(cache):
+        for name in os.listdir(cache):
+            os.remove(os.path.join(cache, name))
+        os.rmdir(cache)
+    os.remove(source)
+    os.rmdir(tmp)
+
+with assert_raises(ValueError):
+    marshal.loads(b"\x00garbage")
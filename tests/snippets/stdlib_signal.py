@@ -40,5 +40,72 @@ if "win" not in sys.platform:
 
 	assert signals == [signal.SIGALRM, signal.SIGALRM]
 
+# The default SIGINT handler raises KeyboardInterrupt
+assert signal.getsignal(signal.SIGINT) is signal.default_int_handler
+with assert_raises(KeyboardInterrupt):
+	signal.raise_signal(signal.SIGINT)
 
+# Handlers get the signal number and the frame that was running
+frames = []
 
+def frame_handler(signum, frame):
+	frames.append((signum, frame))
+
+signal.signal(signal.SIGINT, frame_handler)
+signal.raise_signal(signal.SIGINT)
+assert len(frames) == 1
+assert frames[0][0] == signal.SIGINT
+assert frames[0][1] is sys._getframe()
+
+# A handler may set handlers, including its own
+def once_handler(signum, frame):
+	signals.append('once')
+	signal.signal(signum, signal.SIG_IGN)
+
+signal.signal(signal.SIGINT, once_handler)
+signal.raise_signal(signal.SIGINT)
+signal.raise_signal(signal.SIGINT)
+assert signals[-1] == 'once' and signals.count('once') == 1
+assert signal.getsignal(signal.SIGINT) is signal.SIG_IGN
+
+signal.signal(signal.SIGINT, signal.default_int_handler)
+
+if "win" not in sys.platform:
+	import os
+	import threading
+
+	signal.signal(signal.SIGUSR1, signal.SIG_IGN)
+	signal.raise_signal(signal.SIGUSR1)
+
+	# A blocking read goes on after a handler that returns, and ends with the exception of
+	# one that raises
+	class Alarm(Exception):
+		pass
+
+	def raising_handler(signum, frame):
+		raise Alarm
+
+	r, w = os.pipe()
+	try:
+		def write_later():
+			time.sleep(1.5)
+			os.write(w, b'x')
+
+		writer = threading.Thread(target=write_later)
+		signal.signal(signal.SIGALRM, handler)
+		signal.alarm(1)
+		writer.start()
+		del signals[:]
+		assert os.read(r, 1) == b'x'
+		assert signals == [signal.SIGALRM]
+		writer.join()
+
+		signal.signal(signal.SIGALRM, raising_handler)
+		signal.alarm(1)
+		with assert_raises(Alarm):
+			os.read(r, 1)
+	finally:
+		signal.alarm(0)
+		signal.signal(signal.SIGALRM, signal.SIG_DFL)
+		os.close(r)
+		os.close(w)
@@ -0,0 +1,237 @@
+import base64
+import smtplib
+import sys
+
+from testutils import assert_raises
+
+
+class FakeSocket:
+    """Plays the server side of an SMTP session from a script of replies."""
+
+    def __init__(self, greeting, replies):
+        self.replies = {"DATA": "354 go ahead", "QUIT": "221 bye"}
+        self.replies.update(replies)
+        self.sent = []
+        self.pending = b""
+        self.output = b""
+        self.in_body = False
+        self.closed = False
+        self.reply(greeting)
+
+    def reply(self, reply):
+        self.output += (reply + "\r\n").encode("ascii")
+
+    def sendall(self, data):
+        self.pending += data
+        while b"\r\n" in self.pending:
+            line, self.pending = self.pending.split(b"\r\n", 1)
+            self.handle(line.decode("ascii"))
+
+    def handle(self, line):
+        self.sent.append(line)
+        if self.in_body:
+            if line == ".":
+                self.in_body = False
+                self.reply(self.replies.get(".", "250 OK"))
+            return
+        command = line.split(" ", 1)[0].upper()
+        reply = self.replies.get(line, self.replies.get(command, "250 OK"))
+        if callable(reply):
+            reply = reply(line)
+        if command == "DATA" and reply.startswith("354"):
+            self.in_body = True
+        self.reply(reply)
+
+    def recv(self, size):
+        data, self.output = self.output[:size], self.output[size:]
+        return data
+
+    def makefile(self, mode):
+        return FakeFile(self)
+
+    def close(self):
+        self.closed = True
+
+
+class FakeFile:
+    def __init__(self, sock):
+        self.sock = sock
+
+    def readline(self, size=-1):
+        output = self.sock.output
+        end = output.find(b"\n") + 1 or len(output)
+        if size >= 0:
+            end = min(end, size)
+        line, self.sock.output = output[:end], output[end:]
+        return line
+
+    def close(self):
+        pass
+
+
+EHLO_REPLY = "250-fake.example.com\r\n250-SIZE 1000\r\n250-AUTH PLAIN LOGIN\r\n250 8BITMIME"
+
+
+def connect(replies=None, greeting="220 fake.example.com ESMTP"):
+    sock = FakeSocket(greeting, dict(replies or {}))
+
+    class SMTP(smtplib.SMTP):
+        def _get_socket(self, host, port, timeout):
+            return sock
+
+    return SMTP("fake.example.com", 25, local_hostname="client.example.com"), sock
+
+
+# greeting
+server, sock = connect()
+assert server.sock is sock
+server.close()
+assert sock.closed
+
+with assert_raises(smtplib.SMTPConnectError):
+    connect(greeting="554 go away")
+
+# EHLO
+server, sock = connect({"EHLO": EHLO_REPLY})
+assert server.ehlo() == (250, b"fake.example.com\nSIZE 1000\nAUTH PLAIN LOGIN\n8BITMIME")
+assert sock.sent == ["ehlo client.example.com"]
+assert server.does_esmtp
+assert server.has_extn("size")
+assert server.has_extn("8bitmime")
+assert not server.has_extn("starttls")
+assert server.esmtp_features["size"] == "1000"
+assert server.esmtp_features["auth"] == " PLAIN LOGIN"
+assert server.noop() == (250, b"OK")
+assert server.quit() == (221, b"bye")
+assert sock.closed
+
+# HELO fallback
+server, sock = connect({"EHLO": "502 no", "HELO": "250 hello"})
+server.ehlo_or_helo_if_needed()
+assert sock.sent == ["ehlo client.example.com", "helo client.example.com"]
+assert not server.does_esmtp
+server.close()
+
+server, sock = connect({"EHLO": "502 no", "HELO": "501 no"})
+with assert_raises(smtplib.SMTPHeloError):
+    server.ehlo_or_helo_if_needed()
+server.close()
+
+# sendmail
+server, sock = connect({"EHLO": EHLO_REPLY, ".": "250 queued"})
+message = "Subject: hi\r\n\r\nline one\r\n.starts with a dot\r\nlast"
+refused = server.sendmail("Me <me@example.com>", ["a@example.com", "<b@example.com>"], message)
+assert refused == {}
+assert sock.sent == [
+    "ehlo client.example.com",
+    "mail FROM:<me@example.com> size=%d" % len(message),
+    "rcpt TO:<a@example.com>",
+    "rcpt TO:<b@example.com>",
+    "data",
+    "Subject: hi",
+    "",
+    "line one",
+    "..starts with a dot",
+    "last",
+    ".",
+], sock.sent
+server.close()
+
+# some recipients refused
+server, sock = connect({
+    "HELO": "250 hello",
+    "EHLO": "502 no",
+    "RCPT": lambda line: "550 no such user" if "bad" in line else "250 OK",
+})
+refused = server.sendmail("me@example.com", ["bad@example.com", "good@example.com"], b"body")
+assert refused == {"bad@example.com": (550, b"no such user")}
+assert sock.sent[-1] == "."
+server.close()
+
+# all recipients refused
+server, sock = connect({"EHLO": "502 no", "RCPT": "550 no such user"})
+with assert_raises(smtplib.SMTPRecipientsRefused) as cm:
+    server.sendmail("me@example.com", "bad@example.com", "body")
+assert cm.exception.recipients == {"bad@example.com": (550, b"no such user")}
+assert sock.sent[-1] == "rset"
+server.close()
+
+# sender refused
+server, sock = connect({"EHLO": "502 no", "MAIL": "553 bad sender"})
+with assert_raises(smtplib.SMTPSenderRefused) as cm:
+    server.sendmail("me@example.com", "a@example.com", "body")
+assert cm.exception.smtp_code == 553
+assert cm.exception.sender == "me@example.com"
+assert sock.sent[-1] == "rset"
+server.close()
+
+# data refused
+server, sock = connect({"EHLO": "502 no", "DATA": "451 try later"})
+with assert_raises(smtplib.SMTPDataError) as cm:
+    server.sendmail("me@example.com", "a@example.com", "body")
+assert cm.exception.smtp_code == 451
+assert sock.sent[-1] == "data"
+server.close()
+
+server, sock = connect({"EHLO": "502 no", ".": "452 out of space"})
+with assert_raises(smtplib.SMTPDataError):
+    server.sendmail("me@example.com", "a@example.com", "body")
+assert sock.sent[-1] == "rset"
+server.close()
+
+server, sock = connect({"EHLO": "502 no", ".": "421 closing"})
+with assert_raises(smtplib.SMTPDataError):
+    server.sendmail("me@example.com", "a@example.com", "body")
+assert server.sock is None
+assert sock.closed
+
+# AUTH PLAIN
+expected = base64.b64encode(b"\x00user\x00secret").decode("ascii")
+server, sock = connect({"EHLO": EHLO_REPLY, "AUTH": "235 welcome"})
+assert server.login("user", "secret") == (235, b"welcome")
+assert sock.sent[-1] == "AUTH PLAIN " + expected
+server.close()
+
+# AUTH LOGIN, with and without the initial response
+user = base64.b64encode(b"user").decode("ascii")
+password = base64.b64encode(b"secret").decode("ascii")
+password_prompt = "334 " + base64.b64encode(b"Password:").decode("ascii")
+user_prompt = "334 " + base64.b64encode(b"Username:").decode("ascii")
+
+server, sock = connect({"EHLO": EHLO_REPLY, "AUTH": password_prompt, password: "235 welcome"})
+server.ehlo()
+server.user, server.password = "user", "secret"
+assert server.auth("LOGIN", server.auth_login) == (235, b"welcome")
+assert sock.sent[-2:] == ["AUTH LOGIN " + user, password]
+server.close()
+
+server, sock = connect({"EHLO": EHLO_REPLY, "AUTH": user_prompt, user: password_prompt, password: "235 welcome"})
+server.ehlo()
+server.user, server.password = "user", "secret"
+assert server.auth("LOGIN", server.auth_login, initial_response_ok=False) == (235, b"welcome")
+assert sock.sent[-3:] == ["AUTH LOGIN", user, password]
+server.close()
+
+server, sock = connect({"EHLO": EHLO_REPLY, "AUTH": "535 bad credentials"})
+with assert_raises(smtplib.SMTPAuthenticationError) as cm:
+    server.login("user", "wrong")
+assert cm.exception.smtp_code == 535
+server.close()
+
+server, sock = connect({"EHLO": "250 fake.example.com"})
+with assert_raises(smtplib.SMTPNotSupportedError):
+    server.login("user", "secret")
+server.close()
+
+# STARTTLS needs a TLS implementation, which RustPython doesn't have yet
+if sys.implementation.name == "rustpython":
+    server, sock = connect({"EHLO": "250-fake.example.com\r\n250 STARTTLS"})
+    with assert_raises(NotImplementedError):
+        server.starttls()
+    assert sock.sent == ["ehlo client.example.com"]
+    server.close()
+
+assert smtplib.quoteaddr("Me <me@example.com>") == "<me@example.com>"
+assert smtplib.quoteaddr("me@example.com") == "<me@example.com>"
+assert smtplib.quoteaddr("<>") == "<>"
+assert smtplib.quotedata("a\n.b\r\n") == "a\r\n..b\r\n"
@@ -23,6 +23,27 @@ recv_b = connector.recv(len(MESSAGE_B))
 assert recv_a == MESSAGE_A
 assert recv_b == MESSAGE_B
 
+# bytes-like objects
+connector.send(bytearray(MESSAGE_A))
+connection.send(memoryview(MESSAGE_B))
+recv_a = connection.recv(len(MESSAGE_A))
+recv_b = connector.recv(len(MESSAGE_B))
+assert recv_a == MESSAGE_A
+assert recv_b == MESSAGE_B
+
+with assert_raises(TypeError):
+	connector.send("aaaa")
+
+connector.sendall(MESSAGE_A * 1000)
+recv_a = b""
+while len(recv_a) < len(MESSAGE_A) * 1000:
+	recv_a += connection.recv(1024)
+assert recv_a == MESSAGE_A * 1000
+
+# recv returns what has arrived instead of waiting for bufsize bytes
+connector.send(MESSAGE_A)
+assert connection.recv(1024) == MESSAGE_A
+
 # fileno
 if os.name == "posix":
 	connector_fd = connector.fileno()
@@ -0,0 +1,119 @@
+import os
+import socket
+import ssl
+import sys
+
+from testutils import assert_raises
+
+# constants
+assert ssl.CERT_NONE == 0
+assert ssl.CERT_OPTIONAL == 1
+assert ssl.CERT_REQUIRED == 2
+assert isinstance(ssl.CERT_REQUIRED, ssl.VerifyMode)
+assert ssl.PROTOCOL_TLS == ssl.PROTOCOL_SSLv23
+assert ssl.OP_NO_SSLv2 == ssl.Options.OP_NO_SSLv2
+assert isinstance(ssl.HAS_SNI, bool)
+assert isinstance(ssl.OPENSSL_VERSION, str)
+
+# exceptions
+assert issubclass(ssl.SSLError, OSError)
+for exc in [ssl.SSLZeroReturnError, ssl.SSLWantReadError, ssl.SSLWantWriteError,
+            ssl.SSLSyscallError, ssl.SSLEOFError, ssl.SSLCertVerificationError]:
+    assert issubclass(exc, ssl.SSLError)
+assert issubclass(ssl.SSLCertVerificationError, ValueError)
+assert ssl.CertificateError is ssl.SSLCertVerificationError
+assert str(ssl.SSLError(1, "bad record")) == "bad record"
+
+# purposes
+assert ssl.Purpose.SERVER_AUTH.oid == "1.3.6.1.5.5.7.3.1"
+assert ssl.Purpose.SERVER_AUTH.shortname == "serverAuth"
+assert ssl.Purpose.CLIENT_AUTH.oid == "1.3.6.1.5.5.7.3.2"
+
+# contexts
+ctx = ssl.SSLContext(ssl.PROTOCOL_TLS_CLIENT)
+assert ctx.protocol == ssl.PROTOCOL_TLS_CLIENT
+assert ctx.verify_mode == ssl.CERT_REQUIRED
+assert ctx.check_hostname
+with assert_raises(ValueError):
+    ctx.verify_mode = ssl.CERT_NONE
+ctx.check_hostname = False
+ctx.verify_mode = ssl.CERT_NONE
+assert ctx.verify_mode == ssl.CERT_NONE
+ctx.check_hostname = True
+assert ctx.verify_mode == ssl.CERT_REQUIRED
+
+ctx = ssl.create_default_context()
+assert ctx.verify_mode == ssl.CERT_REQUIRED
+assert ctx.check_hostname
+assert ctx.options & ssl.OP_NO_COMPRESSION
+
+ctx = ssl._create_unverified_context()
+assert ctx.verify_mode == ssl.CERT_NONE
+assert not ctx.check_hostname
+
+ctx = ssl.SSLContext(ssl.PROTOCOL_TLS_CLIENT)
+with assert_raises(TypeError):
+    ctx.load_verify_locations()
+with assert_raises(TypeError):
+    ctx.load_verify_locations(cadata=42)
+with assert_raises(FileNotFoundError):
+    ctx.load_verify_locations("does-not-exist.pem")
+with assert_raises(FileNotFoundError):
+    ctx.load_cert_chain("does-not-exist.pem")
+with assert_raises(TypeError):
+    ctx.load_cert_chain(__file__, password=42)
+
+# verify paths
+paths = ssl.get_default_verify_paths()
+assert isinstance(paths, ssl.DefaultVerifyPaths)
+assert paths.cafile is None or os.path.isfile(paths.cafile)
+assert paths.capath is None or os.path.isdir(paths.capath)
+
+os.environ["SSL_CERT_FILE"] = __file__
+os.environ["SSL_CERT_DIR"] = "does-not-exist"
+try:
+    paths = ssl.get_default_verify_paths()
+    assert paths.cafile == __file__
+    assert paths.capath is None
+    assert paths.openssl_cafile_env == "SSL_CERT_FILE"
+    assert paths.openssl_capath_env == "SSL_CERT_DIR"
+finally:
+    del os.environ["SSL_CERT_FILE"]
+    del os.environ["SSL_CERT_DIR"]
+
+# wrapping
+ctx = ssl.create_default_context()
+sock = socket.socket(socket.AF_INET, socket.SOCK_STREAM)
+with assert_raises(ValueError):
+    ctx.wrap_socket(sock)
+with assert_raises(ValueError):
+    ctx.wrap_socket(sock, server_side=True, server_hostname="example.com")
+if sys.implementation.name == "rustpython":
+    # there is no TLS implementation to wrap with yet
+    with assert_raises(NotImplementedError):
+        ctx.wrap_socket(sock, server_hostname="example.com")
+sock.close()
+
+# memory BIOs
+bio = ssl.MemoryBIO()
+assert bio.pending == 0
+assert not bio.eof
+bio.write(b"hello ")
+bio.write(bytearray(b"world"))
+assert bio.pending == 11
+assert bio.read(5) == b"hello"
+assert bio.read() == b" world"
+bio.write_eof()
+assert bio.eof
+with assert_raises(ssl.SSLError):
+    bio.write(b"more")
+
+# certificates
+der = bytes(range(256)) * 2
+pem = ssl.DER_cert_to_PEM_cert(der)
+assert pem.startswith(ssl.PEM_HEADER + "\n")
+assert pem.endswith(ssl.PEM_FOOTER + "\n")
+assert all(len(line) <= 64 for line in pem.splitlines())
+assert ssl.PEM_cert_to_DER_cert(pem) == der
+with assert_raises(ValueError):
+    ssl.PEM_cert_to_DER_cert("not a certificate")
@@ -41,3 +41,217 @@ data = struct.pack('B1B', 65, 66)
 
 with assert_raises(Exception):
   struct.pack('<IH', "14", 12)
+
+
+# Adapted from CPython's test_struct.
+
+# Every format code round trips in every byte order.
+for prefix in ('', '@', '=', '<', '>', '!'):
+    fmt = prefix + 'xcbB?hHiIlLqQefd5s5p'
+    values = (b'a', -1, 255, True, -300, 60000, -70000, 4000000000, -5, 5,
+              -2 ** 63, 2 ** 64 - 1, 1.5, 0.25, 1e100, b'hello', b'hi')
+    packed = struct.pack(fmt, *values)
+    assert len(packed) == struct.calcsize(fmt), fmt
+    assert struct.unpack(fmt, packed) == values, fmt
+
+assert struct.calcsize('@n') == struct.calcsize('@N') == struct.calcsize('@P')
+assert struct.unpack('@nNP', struct.pack('@nNP', -1, 2, 3)) == (-1, 2, 3)
+assert struct.unpack('@n', struct.pack('@n', -7)) == (-7,)
+
+# Standard sizes
+for code, size in zip('xcbB?hHiIlLqQefd', (1, 1, 1, 1, 1, 2, 2, 4, 4, 4, 4, 8, 8, 2, 4, 8)):
+    for prefix in '=<>!':
+        assert struct.calcsize(prefix + code) == size, prefix + code
+
+assert struct.pack('<hH', 1, 2) == b'\x01\x00\x02\x00'
+assert struct.pack('>hH', 1, 2) == b'\x00\x01\x00\x02'
+assert struct.pack('!i', -2) == b'\xff\xff\xff\xfe'
+assert struct.pack('<q', 2 ** 62) == b'\x00\x00\x00\x00\x00\x00\x00@'
+assert struct.pack('>Q', 1) == b'\x00' * 7 + b'\x01'
+
+# Native alignment, which standard sizes don't have
+assert struct.calcsize('@bi') == 8
+assert struct.calcsize('=bi') == 5
+assert struct.calcsize('@ib') == 5
+assert struct.calcsize('@bq0i') == 16
+assert struct.calcsize('@b0q') == 8
+assert struct.calcsize('@hd') == 16
+assert struct.calcsize('bi') == struct.calcsize('@bi')
+assert struct.pack('@bi', 1, 2)[1:4] == b'\x00\x00\x00'
+assert struct.unpack('@bi', struct.pack('@bi', 1, 2)) == (1, 2)
+
+# Whitespace between items is ignored.
+assert struct.pack('<i  h ', 1, 2) == struct.pack('<ih', 1, 2)
+
+# Strings
+assert struct.pack('3s', b'a') == b'a\x00\x00'
+assert struct.pack('3s', b'abcdef') == b'abc'
+assert struct.pack('0s', b'a') == b''
+assert struct.pack('s', bytearray(b'ab')) == b'a'
+assert struct.unpack('3s', b'abc') == (b'abc',)
+assert struct.pack('5p', b'abcdefg') == b'\x04abcd'
+assert struct.pack('5p', b'ab') == b'\x02ab\x00\x00'
+assert struct.pack('p', b'abc') == b'\x00'
+assert struct.pack('0p', b'abc') == b''
+assert struct.unpack('5p', b'\x09abcd') == (b'abcd',)
+assert struct.pack('256p', b'a' * 300)[:2] == b'\xffa'
+assert struct.pack('2c', b'a', b'b') == b'ab'
+assert struct.unpack('2c', b'ab') == (b'a', b'b')
+
+# Padding
+assert struct.pack('x') == b'\x00'
+assert struct.pack('3x') == b'\x00\x00\x00'
+assert struct.pack('<bxh', 1, 2) == b'\x01\x00\x02\x00'
+assert struct.unpack('<bxh', b'\x01\xff\x02\x00') == (1, 2)
+assert struct.unpack('x', b'\x05') == ()
+
+# Bools pack any truthy value.
+assert struct.pack('<???', 0, [], 'x') == b'\x00\x00\x01'
+assert struct.unpack('<??', b'\x00\x05') == (False, True)
+
+
+# Integers can be anything with an __index__.
+class Index:
+    def __index__(self):
+        return 7
+
+
+assert struct.pack('<i', Index()) == b'\x07\x00\x00\x00'
+
+# Floats
+assert struct.pack('<f', 1) == b'\x00\x00\x80?'
+assert struct.unpack('<d', struct.pack('<d', -0.1)) == (-0.1,)
+
+# Half precision floats
+assert struct.unpack('<e', b'\x00\x3c') == (1.0,)
+assert struct.pack('<e', 1.5) == b'\x00>'
+assert struct.pack('>e', -2.0) == b'\xc0\x00'
+assert struct.pack('<e', float('inf')) == b'\x00|'
+assert struct.pack('<e', float('nan')) == b'\x00~'
+assert struct.pack('<e', 5.960464477539063e-08) == b'\x01\x00'
+assert struct.pack('<e', 65504.0) == b'\xff{'
+assert struct.pack('<e', 1e-10) == b'\x00\x00'
+for value in (0.0, -0.0, 1.0, -1.0, 0.5, 65504.0, 6.103515625e-05, 2 ** -24):
+    assert struct.unpack('<e', struct.pack('<e', value)) == (value,)
+# Rounding is half to even.
+assert struct.unpack('<e', struct.pack('<e', 1 + 2 ** -11)) == (1.0,)
+assert struct.unpack('<e', struct.pack('<e', 1 + 3 * 2 ** -11)) == (1 + 2 ** -9,)
+assert str(struct.unpack('<e', b'\x00\x7e')[0]) == 'nan'
+assert struct.unpack('>e', b'\xfc\x00') == (float('-inf'),)
+
+
+def assert_error(message, func, *args):
+    with assert_raises(struct.error) as cm:
+        func(*args)
+    assert str(cm.exception) == message, str(cm.exception)
+
+
+# Out of range values
+assert_error('byte format requires -128 <= number <= 127', struct.pack, 'b', 200)
+assert_error('ubyte format requires 0 <= number <= 255', struct.pack, '<B', -1)
+assert_error('short format requires -32768 <= number <= 32767', struct.pack, 'h', 40000)
+assert_error('ushort format requires 0 <= number <= 65535', struct.pack, '<H', 70000)
+assert_error("'i' format requires -2147483648 <= number <= 2147483647", struct.pack, '<i', 2 ** 31)
+assert_error("'I' format requires 0 <= number <= 4294967295", struct.pack, '<I', 2 ** 32)
+assert_error("'l' format requires -2147483648 <= number <= 2147483647", struct.pack, '<l', 2 ** 31)
+assert_error('argument out of range', struct.pack, '<I', -1)
+assert_error('argument out of range', struct.pack, '<q', 2 ** 63)
+assert_error('argument out of range', struct.pack, '<Q', -1)
+assert_error('argument out of range', struct.pack, '<Q', 2 ** 64)
+assert_error('argument out of range', struct.pack, '<i', 2 ** 70)
+assert_error('required argument is not an integer', struct.pack, 'b', 'x')
+assert_error('required argument is not an integer', struct.pack, '<i', 1.5)
+assert_error('required argument is not a float', struct.pack, '<d', '1')
+assert_error('char format requires a bytes object of length 1', struct.pack, 'c', b'ab')
+assert_error('char format requires a bytes object of length 1', struct.pack, 'c', 'a')
+assert_error("argument for 's' must be a bytes object", struct.pack, 's', 1)
+with assert_raises(OverflowError):
+    struct.pack('<e', 65520.0)
+with assert_raises(OverflowError):
+    struct.pack('<f', 1e300)
+
+# Bad formats
+assert_error('bad char in struct format', struct.pack, '3z')
+assert_error('bad char in struct format', struct.calcsize, '<n')
+assert_error('bad char in struct format', struct.calcsize, '=P')
+assert_error('repeat count given without format specifier', struct.pack, 'i 2', 1)
+assert_error('total struct size too long', struct.calcsize, '99999999999999999999i')
+
+# Size mismatches
+assert_error('pack expected 2 items for packing (got 1)', struct.pack, 'ii', 1)
+assert_error('pack expected 1 items for packing (got 2)', struct.pack, 'i', 1, 2)
+assert_error('unpack requires a buffer of 4 bytes', struct.unpack, 'i', b'abc')
+assert_error('unpack requires a buffer of 4 bytes', struct.unpack, '<i', b'abcde')
+with assert_raises(TypeError):
+    struct.pack(1)
+
+# unpack_from
+assert struct.unpack_from('<h', b'\x00\x01\x02\x00', 2) == (2,)
+assert struct.unpack_from('<h', b'\x01\x00\x02\x00') == (1,)
+assert struct.unpack_from('<h', b'\x01\x00\x02\x00', offset=-2) == (2,)
+assert struct.unpack_from('<h', memoryview(b'\x01\x00\x02\x00'), 2) == (2,)
+assert_error('unpack_from requires a buffer of at least 4 bytes for unpacking 4 bytes '
+             'at offset 0 (actual buffer size is 3)', struct.unpack_from, 'i', b'abc')
+assert_error('unpack_from requires a buffer of at least 6 bytes for unpacking 4 bytes '
+             'at offset 2 (actual buffer size is 4)', struct.unpack_from, 'i', b'abcd', 2)
+assert_error('offset -5 out of range for 4-byte buffer', struct.unpack_from, 'i', b'abcd', -5)
+assert_error('not enough data to unpack 4 bytes at offset -3', struct.unpack_from, 'i',
+             b'abcdefgh', -3)
+
+# pack_into works on writable buffers.
+buffer = bytearray(b'\xff' * 8)
+struct.pack_into('<hxb', buffer, 2, 1, 2)
+assert buffer == bytearray(b'\xff\xff\x01\x00\x00\x02\xff\xff')
+struct.pack_into('<h', buffer, -2, 3)
+assert buffer[-2:] == b'\x03\x00'
+view = memoryview(buffer)
+struct.pack_into('<i', view, 0, -1)
+assert buffer[:4] == b'\xff\xff\xff\xff'
+assert_error('pack_into expected 2 items for packing (got 1)', struct.pack_into, 'ii',
+             bytearray(8), 0, 1)
+assert_error('pack_into requires a buffer of at least 4 bytes for packing 4 bytes at offset 0 '
+             '(actual buffer size is 3)', struct.pack_into, 'i', bytearray(3), 0, 1)
+assert_error('pack_into requires a buffer of at least 8 bytes for packing 4 bytes at offset 4 '
+             '(actual buffer size is 6)', struct.pack_into, 'i', bytearray(6), 4, 1)
+assert_error('no space to pack 4 bytes at offset -3', struct.pack_into, 'i', bytearray(8), -3, 1)
+assert_error('offset -7 out of range for 6-byte buffer', struct.pack_into, 'i', bytearray(6),
+             -7, 1)
+with assert_raises(TypeError):
+    struct.pack_into('i', b'abcd', 0, 1)
+# The buffer keeps its size.
+buffer = bytearray(4)
+struct.pack_into('<i', buffer, 0, 1)
+buffer.append(0)
+assert buffer == bytearray(b'\x01\x00\x00\x00\x00')
+
+# iter_unpack
+it = struct.iter_unpack('<h', b'\x01\x00\x02\x00\x03\x00')
+assert it.__length_hint__() == 3
+assert next(it) == (1,)
+assert it.__length_hint__() == 2
+assert list(it) == [(2,), (3,)]
+assert list(it) == []
+assert_error('iterative unpacking requires a buffer of a multiple of 4 bytes',
+             struct.iter_unpack, 'i', b'abc')
+assert_error('cannot iteratively unpack with a struct of length 0', struct.iter_unpack, '',
+             b'abc')
+
+# Struct objects
+s = struct.Struct('<2hx')
+assert s.format == '<2hx'
+assert s.size == 5
+assert s.pack(1, 2) == b'\x01\x00\x02\x00\x00'
+assert s.unpack(b'\x01\x00\x02\x00\x00') == (1, 2)
+assert s.unpack_from(b'\x00\x01\x00\x02\x00\x00', 1) == (1, 2)
+buffer = bytearray(5)
+s.pack_into(buffer, 0, 3, 4)
+assert buffer == bytearray(b'\x03\x00\x04\x00\x00')
+assert list(s.iter_unpack(bytes(10))) == [(0, 0), (0, 0)]
+assert struct.Struct(b'<i').format == '<i'
+assert struct.Struct(b'<i').size == 4
+with assert_raises(TypeError):
+    struct.Struct(1)
+with assert_raises(struct.error):
+    struct.Struct('z')
+
+assert issubclass(struct.error, Exception)
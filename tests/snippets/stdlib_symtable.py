@@ -0,0 +1,44 @@
+import symtable
+
+from testutils import assert_raises
+
+source = """\
+def f(x):
+    y = 1
+    class C:
+        y = 2
+        def g(self):
+            nonlocal y
+            return x, len
+    return C
+"""
+
+top = symtable.symtable(source, "<string>", "exec")
+assert top.get_type() == "module"
+f, = top.get_children()
+assert f.get_name() == "f"
+assert f.get_type() == "function"
+assert f.is_optimized()
+assert f.get_parameters() == ("x",)
+assert set(f.get_locals()) == {"x", "y", "C"}
+assert f.lookup("C").is_namespace()
+
+c, = f.get_children()
+assert c.get_type() == "class"
+assert c.get_methods() == ("g",)
+assert c.lookup("x").is_free()
+assert c.lookup("y").is_local()
+
+g, = c.get_children()
+assert g.get_lineno() == 5
+assert g.get_nonlocals() == ("y",)
+assert set(g.get_frees()) == {"x", "y"}
+assert g.get_globals() == ("len",)
+assert g.lookup("len").is_global()
+assert not g.lookup("len").is_declared_global()
+
+with assert_raises(KeyError):
+    g.lookup("z")
+
+with assert_raises(SyntaxError):
+    symtable.symtable("def f():\n    nonlocal x\n", "<string>", "exec")
@@ -43,6 +43,102 @@ sys.settrace(trc)
 demo(5)
 sys.settrace(None)
 
+events = []
+
+def tracer(frame, event, arg):
+    line = frame.f_lineno - frame.f_code.co_firstlineno
+    events.append((frame.f_code.co_name, event, line, arg))
+    return tracer
+
+def traced(x):
+    y = x + 1
+    return y
+
+sys.settrace(tracer)
+traced(1)
+sys.settrace(None)
+assert events == [
+    ('traced', 'call', 0, None),
+    ('traced', 'line', 1, None),
+    ('traced', 'line', 2, None),
+    ('traced', 'return', 2, 2),
+], events
+
+# The return value of the global trace function decides about local tracing
+events = []
+
+def call_only(frame, event, arg):
+    events.append(event)
+
+sys.settrace(call_only)
+traced(1)
+sys.settrace(None)
+assert events == ['call']
+
+def raising():
+    raise ValueError('traced')
+
+events = []
+sys.settrace(tracer)
+try:
+    raising()
+except ValueError:
+    pass
+sys.settrace(None)
+assert [event[1] for event in events] == ['call', 'line', 'exception', 'return']
+exc_type, exc, tb = events[2][3]
+assert exc_type is ValueError
+assert exc.args == ('traced',)
+
+assert sys.gettrace() is None
+
+# Frames
+assert sys._getframe(0).f_back is None
+
+def outer_frame():
+    return sys._getframe().f_back
+
+assert outer_frame() is sys._getframe()
+
+frame = sys._getframe()
+assert frame.f_trace is None
+frame.f_trace = tracer
+assert frame.f_trace is tracer
+del frame.f_trace
+assert frame.f_trace is None
+
+def lineno():
+    return sys._getframe().f_back.f_lineno
+
+assert lineno() == 113
+
+def generator():
+    yield
+assert generator.__code__.co_flags & 0x20
+assert not lineno.__code__.co_flags & 0x20
+
+def args(a, b=1, *c, d, **e):
+    pass
+assert args.__code__.co_varnames == ('a', 'b', 'd', 'c', 'e')
+
+# breakpoint() calls sys.breakpointhook
+assert sys.breakpointhook is sys.__breakpointhook__
+
+hook_calls = []
+
+def hook(*args, **kwargs):
+    hook_calls.append((args, kwargs))
+    return 'hooked'
+
+sys.breakpointhook = hook
+assert breakpoint(1, a=2) == 'hooked'
+assert hook_calls == [((1,), {'a': 2})]
+
+del sys.breakpointhook
+with assert_raises(RuntimeError):
+    breakpoint()
+sys.breakpointhook = sys.__breakpointhook__
+
 assert sys.exc_info() == (None, None, None)
 
 try:
@@ -64,3 +160,25 @@ assert sys.getrecursionlimit() == 200
 
 with assert_raises(RecursionError):
     recursive_call(300)
+
+
+# Interning:
+
+name = ''.join(['intern', 'ed'])
+assert sys.intern(name) is sys.intern('interned')
+assert sys.intern('an attribute') is sys.intern(' '.join(['an', 'attribute']))
+
+class Name(str):
+    pass
+
+with assert_raises(TypeError):
+    sys.intern(Name('interned'))
+
+
+class Interned:
+    pass
+
+obj = Interned()
+setattr(obj, name, 1)
+assert obj.interned == 1
+assert getattr(obj, name) == 1
@@ -0,0 +1,39 @@
+import os
+import sys
+import sysconfig
+
+assert os.path.isabs(sys.executable)
+assert os.path.isabs(sys.prefix)
+assert os.path.isabs(sys.exec_prefix)
+if sys.implementation.name == 'rustpython':
+    # The prefixes follow the binary, which is laid out as
+    # <prefix>/bin/rustpython, or else is in the prefix itself
+    exe_dir = os.path.dirname(sys.executable)
+    if os.path.basename(exe_dir) == 'bin':
+        assert sys.prefix == os.path.dirname(exe_dir)
+    else:
+        assert sys.prefix == exe_dir
+    assert sys.exec_prefix == sys.prefix
+    assert sys.base_prefix == sys.prefix
+    assert sys.base_exec_prefix == sys.exec_prefix
+
+assert sysconfig.get_config_var('prefix') == os.path.normpath(sys.prefix)
+assert sysconfig.get_config_var('exec_prefix') == os.path.normpath(sys.exec_prefix)
+assert sysconfig.get_config_var('py_version_short') == '%d.%d' % sys.version_info[:2]
+if sys.implementation.name == 'rustpython':
+    # There are no extension modules
+    assert sysconfig.get_config_var('EXT_SUFFIX') is None
+assert sysconfig.get_config_var('no such variable') is None
+assert sysconfig.get_python_version() == sysconfig.get_config_var('py_version_short')
+
+paths = sysconfig.get_paths()
+for name in ('stdlib', 'purelib', 'platlib', 'scripts', 'data'):
+    assert os.path.isabs(paths[name]), name
+    assert paths[name].startswith(os.path.normpath(sys.prefix)), name
+assert paths['data'] == os.path.normpath(sys.prefix)
+if os.name == 'posix':
+    assert paths['scripts'] == os.path.join(os.path.normpath(sys.prefix), 'bin')
+assert sysconfig.get_path('purelib') == paths['purelib']
+assert 'site-packages' in paths['purelib']
+
+assert isinstance(sysconfig.get_platform(), str)
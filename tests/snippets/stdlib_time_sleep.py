@@ -0,0 +1,66 @@
+import os
+import signal
+import subprocess
+import sys
+import threading
+import time
+
+from testutils import assert_raises
+
+start = time.monotonic()
+time.sleep(0)
+time.sleep(0.1)
+assert time.monotonic() - start >= 0.1
+assert_raises(ValueError, lambda: time.sleep(-1))
+assert_raises(TypeError, lambda: time.sleep("1"))
+
+# Waiting for an event that isn't set lasts for the whole timeout
+event = threading.Event()
+start = time.monotonic()
+assert not event.wait(0.3)
+assert time.monotonic() - start >= 0.3
+
+condition = threading.Condition()
+with condition:
+    start = time.monotonic()
+    assert not condition.wait(0.3)
+    assert time.monotonic() - start >= 0.3
+
+if "win" not in sys.platform:
+    # SIGINT interrupts a sleep with KeyboardInterrupt right away. The child creates the
+    # marker file once it's started, so that the signal doesn't arrive during startup.
+    marker = "sleep_marker_%d" % os.getpid()
+    child = subprocess.Popen(
+        [sys.executable, "-c", "import time; open(%r, 'w').write(''); time.sleep(10)" % marker],
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+    )
+    while not os.path.exists(marker):
+        time.sleep(0.05)
+    os.remove(marker)
+    time.sleep(1)
+    start = time.monotonic()
+    subprocess.Popen(["kill", "-INT", str(child.pid)]).wait()
+    stdout, stderr = child.communicate(timeout=5)
+    assert time.monotonic() - start < 2
+    assert child.returncode != 0
+    assert b"KeyboardInterrupt" in stdout + stderr, (stdout, stderr)
+
+    # A handled signal runs its handler, and the sleep goes on for the time that was left
+    signals = []
+    signal.signal(signal.SIGUSR1, lambda signum, frame: signals.append(signum))
+    killer = subprocess.Popen(["sh", "-c", "sleep 0.5; kill -USR1 %d" % os.getpid()])
+    start = time.monotonic()
+    time.sleep(1.5)
+    assert time.monotonic() - start >= 1.5
+    assert signals == [signal.SIGUSR1]
+    killer.wait()
+
+    # Setting an event from a signal handler ends the wait for it
+    event = threading.Event()
+    signal.signal(signal.SIGALRM, lambda signum, frame: event.set())
+    signal.alarm(1)
+    start = time.monotonic()
+    assert event.wait(5)
+    assert time.monotonic() - start < 3
+    signal.signal(signal.SIGALRM, signal.SIG_DFL)
@@ -0,0 +1,78 @@
+import io
+import tokenize
+from token import (COMMENT, DEDENT, ENCODING, ENDMARKER, INDENT, NAME, NEWLINE,
+                   NL, NUMBER, OP, STRING)
+
+from testutils import assert_raises
+
+
+def tokens(source):
+    return list(tokenize.generate_tokens(io.StringIO(source).readline))
+
+
+source = 'if x:\n    y = rb"a" + 1.5  # c\n\nz\n'
+assert [tuple(token) for token in tokens(source)] == [
+    (NAME, 'if', (1, 0), (1, 2), 'if x:\n'),
+    (NAME, 'x', (1, 3), (1, 4), 'if x:\n'),
+    (OP, ':', (1, 4), (1, 5), 'if x:\n'),
+    (NEWLINE, '\n', (1, 5), (1, 6), 'if x:\n'),
+    (INDENT, '    ', (2, 0), (2, 4), '    y = rb"a" + 1.5  # c\n'),
+    (NAME, 'y', (2, 4), (2, 5), '    y = rb"a" + 1.5  # c\n'),
+    (OP, '=', (2, 6), (2, 7), '    y = rb"a" + 1.5  # c\n'),
+    (STRING, 'rb"a"', (2, 8), (2, 13), '    y = rb"a" + 1.5  # c\n'),
+    (OP, '+', (2, 14), (2, 15), '    y = rb"a" + 1.5  # c\n'),
+    (NUMBER, '1.5', (2, 16), (2, 19), '    y = rb"a" + 1.5  # c\n'),
+    (COMMENT, '# c', (2, 21), (2, 24), '    y = rb"a" + 1.5  # c\n'),
+    (NEWLINE, '\n', (2, 24), (2, 25), '    y = rb"a" + 1.5  # c\n'),
+    (NL, '\n', (3, 0), (3, 1), '\n'),
+    (DEDENT, '', (4, 0), (4, 0), 'z\n'),
+    (NAME, 'z', (4, 0), (4, 1), 'z\n'),
+    (NEWLINE, '\n', (4, 1), (4, 2), 'z\n'),
+    (ENDMARKER, '', (5, 0), (5, 0), ''),
+]
+
+# Line continuations, multi-line strings and brackets
+source = 'x = (1,\n     2) + \\\n    """a\nb"""\n'
+assert [(token.type, token.string, token.start) for token in tokens(source)] == [
+    (NAME, 'x', (1, 0)),
+    (OP, '=', (1, 2)),
+    (OP, '(', (1, 4)),
+    (NUMBER, '1', (1, 5)),
+    (OP, ',', (1, 6)),
+    (NL, '\n', (1, 7)),
+    (NUMBER, '2', (2, 5)),
+    (OP, ')', (2, 6)),
+    (OP, '+', (2, 8)),
+    (STRING, '"""a\nb"""', (3, 4)),
+    (NEWLINE, '\n', (4, 4)),
+    (ENDMARKER, '', (5, 0)),
+]
+
+# A source without a final newline still ends its statement
+assert [(token.type, token.string, token.start, token.end) for token in tokens('x')] == [
+    (NAME, 'x', (1, 0), (1, 1)),
+    (NEWLINE, '', (1, 1), (1, 2)),
+    (ENDMARKER, '', (2, 0), (2, 0)),
+]
+
+token = tokens('a += 1\n')[1]
+assert token.type == OP
+assert token.exact_type == tokenize.EXACT_TOKEN_TYPES['+=']
+
+# tokenize() decodes bytes and starts with the encoding
+source = '# -*- coding: latin-1 -*-\ns = "\xe9"\n'.encode('latin-1')
+result = list(tokenize.tokenize(io.BytesIO(source).readline))
+assert result[0] == (ENCODING, 'iso-8859-1', (0, 0), (0, 0), '')
+assert result[5].string == '"\xe9"'
+assert tokenize.detect_encoding(io.BytesIO(source).readline) == (
+    'iso-8859-1', [b'# -*- coding: latin-1 -*-\n'])
+assert tokenize.detect_encoding(io.BytesIO(b'\xef\xbb\xbfx = 1\n').readline)[0] == 'utf-8-sig'
+
+# untokenize() gives back the source from full tokens
+source = 'def f(a, *b):\n    return a[0] ** 2  # square\n\n\nprint(f(3))\n'
+assert tokenize.untokenize(tokens(source)) == source
+source_bytes = source.encode()
+assert tokenize.untokenize(tokenize.tokenize(io.BytesIO(source_bytes).readline)) == source_bytes
+
+with assert_raises(tokenize.TokenError):
+    tokens('s = """abc\n')
@@ -25,3 +25,20 @@ try:
 except ZeroDivisionError as ex2:
 	tb = traceback.extract_tb(ex2.__traceback__)
 	assert len(tb) == 1
+
+
+# The line numbers of the frames, also far apart and going back to earlier lines:
+
+def tb_lines(source):
+	namespace = {}
+	try:
+		exec(compile(source, "<lines>", "exec"), namespace)
+	except ZeroDivisionError as ex:
+		return [frame.lineno for frame in traceback.extract_tb(ex.__traceback__)][1:]
+	assert False, "no exception"
+
+source = "def f():\n" + "\n" * 300 + "\treturn 1/0\n" + "\n" * 1000 + "f()\n"
+assert tb_lines(source) == [1303, 302], tb_lines(source)
+
+source = "x = 1\n" * 130 + "def g():\n\treturn 1/0\n\n" + "def f():\n\tg()\n" + "f()\n"
+assert tb_lines(source) == [136, 135, 132], tb_lines(source)
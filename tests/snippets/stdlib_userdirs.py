@@ -0,0 +1,48 @@
+import os
+import sys
+
+if sys.implementation.name != 'rustpython':
+    # userdirs is only in RustPython's library
+    sys.path.insert(0, os.path.join(os.path.dirname(__file__), '..', '..', 'Lib'))
+import userdirs
+
+functions = [userdirs.user_config_dir, userdirs.user_cache_dir, userdirs.user_data_dir]
+
+dirs = [f() for f in functions]
+assert all(os.path.isabs(d) for d in dirs), dirs
+assert len(set(dirs)) == 3, dirs
+
+app_dirs = [f('myapp') for f in functions]
+assert all(os.path.isabs(d) for d in app_dirs), app_dirs
+assert len(set(app_dirs)) == 3, app_dirs
+assert all('myapp' in d for d in app_dirs)
+
+if os.name != 'nt' and sys.platform != 'darwin':
+    saved = {name: os.environ.get(name) for name in
+             ('XDG_CONFIG_HOME', 'XDG_CACHE_HOME', 'XDG_DATA_HOME', 'HOME')}
+
+    os.environ['HOME'] = '/home/someone'
+    for name in ('XDG_CONFIG_HOME', 'XDG_CACHE_HOME', 'XDG_DATA_HOME'):
+        os.environ.pop(name, None)
+    assert userdirs.user_config_dir() == '/home/someone/.config'
+    assert userdirs.user_cache_dir() == '/home/someone/.cache'
+    assert userdirs.user_data_dir('myapp') == '/home/someone/.local/share/myapp'
+
+    os.environ['XDG_CONFIG_HOME'] = '/xdg/config'
+    os.environ['XDG_CACHE_HOME'] = '/xdg/cache'
+    os.environ['XDG_DATA_HOME'] = '/xdg/data'
+    assert userdirs.user_config_dir('myapp') == '/xdg/config/myapp'
+    assert userdirs.user_cache_dir('myapp') == '/xdg/cache/myapp'
+    assert userdirs.user_data_dir('myapp') == '/xdg/data/myapp'
+
+    # Relative paths in the variables are ignored, as the specification says
+    os.environ['XDG_CONFIG_HOME'] = 'relative/config'
+    assert userdirs.user_config_dir() == '/home/someone/.config'
+    os.environ['XDG_CACHE_HOME'] = ''
+    assert userdirs.user_cache_dir() == '/home/someone/.cache'
+
+    for name, value in saved.items():
+        if value is None:
+            os.environ.pop(name, None)
+        else:
+            os.environ[name] = value
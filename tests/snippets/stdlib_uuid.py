@@ -0,0 +1,90 @@
+import uuid
+from testutils import assert_raises
+
+# A subset of CPython's test_uuid
+
+u = uuid.UUID('{12345678-1234-5678-1234-567812345678}')
+assert str(u) == '12345678-1234-5678-1234-567812345678'
+assert repr(u) == "UUID('12345678-1234-5678-1234-567812345678')"
+assert u.hex == '12345678123456781234567812345678'
+assert u.int == 0x12345678123456781234567812345678
+assert u.bytes == b'\x12\x34\x56\x78\x12\x34\x56\x78\x12\x34\x56\x78\x12\x34\x56\x78'
+assert u.bytes_le == b'\x78\x56\x34\x12\x34\x12\x78\x56\x12\x34\x56\x78\x12\x34\x56\x78'
+assert u.fields == (0x12345678, 0x1234, 0x5678, 0x12, 0x34, 0x567812345678)
+assert u.urn == 'urn:uuid:12345678-1234-5678-1234-567812345678'
+assert u.variant == uuid.RESERVED_NCS
+assert u.version is None
+
+# The same UUID from each of the forms the constructor takes
+for other in [
+    uuid.UUID('12345678123456781234567812345678'),
+    uuid.UUID('urn:uuid:12345678-1234-5678-1234-567812345678'),
+    uuid.UUID(bytes=u.bytes),
+    uuid.UUID(bytes_le=u.bytes_le),
+    uuid.UUID(fields=u.fields),
+    uuid.UUID(int=u.int),
+]:
+    assert other == u
+    assert hash(other) == hash(u)
+
+u = uuid.UUID('00010203-0405-0607-0809-0a0b0c0d0e0f')
+assert u.time_low == 0x00010203
+assert u.time_mid == 0x0405
+assert u.time_hi_version == 0x0607
+assert u.clock_seq_hi_variant == 0x08
+assert u.clock_seq_low == 0x09
+assert u.node == 0x0a0b0c0d0e0f
+assert u.time == 0x0607040500010203
+assert u.clock_seq == 0x0809
+assert u < uuid.UUID('00010203-0405-0607-0809-0a0b0c0d0e10')
+
+with assert_raises(ValueError):
+    uuid.UUID('')
+with assert_raises(ValueError):
+    uuid.UUID('123')
+with assert_raises(ValueError):
+    uuid.UUID('1234567812345678123456781234567')
+with assert_raises(ValueError):
+    uuid.UUID('123456781234567812345678z2345678')
+with assert_raises(ValueError):
+    uuid.UUID(bytes=b'\0' * 15)
+with assert_raises(ValueError):
+    uuid.UUID(int=-1)
+with assert_raises(ValueError):
+    uuid.UUID(int=1 << 128)
+with assert_raises(TypeError):
+    uuid.UUID()
+with assert_raises(TypeError):
+    uuid.UUID(u.hex, int=u.int)
+with assert_raises(TypeError):
+    u.int = 0
+
+# The name based UUIDs, which hash with md5 and sha1
+assert uuid.uuid3(uuid.NAMESPACE_DNS, 'python.org') == uuid.UUID('6fa459ea-ee8a-3ca4-894e-db77e160355e')
+assert uuid.uuid3(uuid.NAMESPACE_URL, 'http://python.org/') == uuid.UUID('9fe8e8c4-aaa8-32a9-a55c-4535a88b748d')
+assert uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org') == uuid.UUID('886313e1-3b8a-5372-9b90-0c9aee199e5d')
+assert uuid.uuid5(uuid.NAMESPACE_OID, '1.3.6.1') == uuid.UUID('1447fa61-5277-5fef-a9b3-fbc6e44f4af3')
+assert uuid.uuid3(uuid.NAMESPACE_DNS, 'python.org').version == 3
+assert uuid.uuid5(uuid.NAMESPACE_DNS, 'python.org').variant == uuid.RFC_4122
+
+# The random UUIDs
+seen = set()
+for _ in range(10):
+    u = uuid.uuid4()
+    assert u.variant == uuid.RFC_4122
+    assert u.version == 4
+    seen.add(u)
+assert len(seen) == 10
+
+# The time based UUIDs, with the node given so that no hardware address is looked up
+u = uuid.uuid1(node=0x123456789abc)
+assert u.variant == uuid.RFC_4122
+assert u.version == 1
+assert u.node == 0x123456789abc
+u = uuid.uuid1(node=0x123456789abc, clock_seq=0x1234)
+assert u.clock_seq == 0x1234
+assert uuid.uuid1(node=1) != uuid.uuid1(node=1)
+
+assert uuid.SafeUUID.safe.value == 0
+assert uuid.SafeUUID(None) is uuid.SafeUUID.unknown
+assert uuid.UUID(int=0).is_safe is uuid.SafeUUID.unknown
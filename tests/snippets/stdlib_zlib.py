@@ -60,3 +60,112 @@ for text in compressed_lorem_list:
 
 assert_raises(zlib.error, lambda: zlib.compress(b"123", -40))
 assert_raises(zlib.error, lambda: zlib.compress(b"123", 10))
+
+# checksums take any int as their start, and are always unsigned
+assert zlib.crc32(b"123", 2 ** 32 + 1) == zlib.crc32(b"123", 1)
+assert zlib.crc32(b"123", -1) == zlib.crc32(b"123", 2 ** 32 - 1)
+assert zlib.adler32(b"456", 2 ** 40 + 5) == zlib.adler32(b"456", 5)
+assert zlib.crc32(b"\xff" * 10) > 0
+
+# round trips
+data = b"".join(
+    str(i).encode() + b" lines of some text that repeats a little\n" for i in range(2000)
+)
+for level in range(-1, 10):
+    compressed = zlib.compress(data, level)
+    assert zlib.decompress(compressed) == data
+    assert zlib.decompress(compressed, bufsize=1) == data
+assert len(zlib.compress(data, 9)) < len(zlib.compress(data, 0))
+
+with assert_raises(zlib.error):
+    zlib.decompress(b"not compressed at all")
+with assert_raises(zlib.error):
+    zlib.decompress(zlib.compress(data)[:-10])
+
+# streaming compression
+co = zlib.compressobj(5)
+chunks = [co.compress(data[i:i + 1000]) for i in range(0, len(data), 1000)]
+chunks.append(co.flush(zlib.Z_SYNC_FLUSH))
+chunks.append(co.compress(b"tail"))
+chunks.append(co.flush())
+assert zlib.decompress(b"".join(chunks)) == data + b"tail"
+with assert_raises(zlib.error):
+    co.compress(b"more")
+
+# streaming decompression, in small pieces
+compressed = zlib.compress(data)
+do = zlib.decompressobj()
+out = b""
+for i in range(0, len(compressed), 7):
+    out += do.decompress(compressed[i:i + 7])
+out += do.flush()
+assert out == data
+assert do.eof
+assert do.unused_data == b""
+assert do.unconsumed_tail == b""
+
+# with a limit on how much comes out at a time
+do = zlib.decompressobj()
+out = do.decompress(compressed, 100)
+assert len(out) == 100
+while do.unconsumed_tail:
+    piece = do.decompress(do.unconsumed_tail, 100)
+    assert len(piece) <= 100
+    out += piece
+out += do.flush()
+assert out == data
+assert do.eof
+
+with assert_raises(ValueError):
+    zlib.decompressobj().decompress(compressed, -1)
+
+# whatever follows the end of the stream is left over
+do = zlib.decompressobj()
+assert not do.eof
+assert do.decompress(zlib.compress(b"abc") + b"extra") == b"abc"
+assert do.eof
+assert do.unused_data == b"extra"
+do.decompress(b"more")
+assert do.unused_data == b"extramore"
+
+# raw deflate streams and gzip wrapped ones
+raw = zlib.compressobj(wbits=-15)
+raw_data = raw.compress(data) + raw.flush()
+assert zlib.decompress(raw_data, -15) == data
+assert zlib.decompress(compressed[2:], -15) == data
+
+gz = zlib.compressobj(wbits=31)
+gz_data = gz.compress(data) + gz.flush()
+assert gz_data[:3] == b"\x1f\x8b\x08"
+assert zlib.decompress(gz_data, 31) == data
+assert zlib.decompress(zlib.compress(data, wbits=31), 31) == data
+
+# 32 + wbits detects either header
+assert zlib.decompress(gz_data, 47) == data
+assert zlib.decompress(compressed, 47) == data
+
+do = zlib.decompressobj(31)
+out = b""
+for i in range(0, len(gz_data), 3):
+    out += do.decompress(gz_data[i:i + 3])
+assert out == data
+assert do.eof
+
+with assert_raises(zlib.error):
+    zlib.decompress(gz_data[:-8] + b"\x00" * 8, 31)
+with assert_raises(zlib.error):
+    zlib.decompress(compressed, 31)
+
+with assert_raises(ValueError):
+    zlib.decompressobj(100)
+with assert_raises(ValueError):
+    zlib.compressobj(wbits=-8)
+with assert_raises(ValueError):
+    zlib.compressobj(12)
+
+# the gzip module works on top of zlib
+import gzip
+
+assert gzip.decompress(gzip.compress(data)) == data
+assert gzip.decompress(gz_data) == data
+assert gzip.decompress(gzip.compress(b"one") + gzip.compress(b"two")) == b"onetwo"
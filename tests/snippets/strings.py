@@ -256,6 +256,14 @@ for s, b, e in zip(ss, bs, ['u8', 'U8', 'utf-8', 'UTF-8', 'utf_8']):
     assert s.encode(e) == b
     # assert s.encode(encoding=e) == b
 
+assert "Hello".encode("ascii") == b"Hello"
+assert "caf\xe9".encode("latin-1") == b"caf\xe9"
+assert "caf\xe9".encode("iso-8859-1") == b"caf\xe9"
+with assert_raises(UnicodeEncodeError) as cm:
+    "caf\xe9".encode("ascii")
+assert str(cm.exception) == "'ascii' codec can't encode character '\\xe9' in position 3: ordinal not in range(128)"
+assert_raises(UnicodeEncodeError, lambda: "\u4e2d".encode("latin-1"))
+
 # str.isisprintable
 assert "".isprintable()
 assert " ".isprintable()
@@ -334,3 +342,9 @@ assert "a1".islower()
 assert "1a".islower()
 assert "가나다a".islower()
 assert "가나다A".isupper()
+
+assert 'a\r\nb\rc\n\nd'.splitlines() == ['a', 'b', 'c', '', 'd']
+assert 'a\r\nb\n'.splitlines(True) == ['a\r\n', 'b\n']
+assert 'a\x0bb\x1cc\u2028d'.splitlines(keepends=False) == ['a', 'b', 'c', 'd']
+assert ''.splitlines() == []
+assert '\n'.splitlines() == ['']
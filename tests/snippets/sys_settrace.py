@@ -0,0 +1,269 @@
+import sys
+
+from testutils import assert_raises
+
+
+class LineCounter:
+    """Records the (event, line) pairs of the frames of `code`, with lines relative
+    to the first line of its function, and counts how often each line ran."""
+
+    def __init__(self, code):
+        self.code = code
+        self.events = []
+        self.counts = {}
+
+    def __call__(self, frame, event, arg):
+        if frame.f_code is not self.code:
+            # Not tracing the frame at all: no line events are sent for it.
+            return None
+        line = frame.f_lineno - self.code.co_firstlineno
+        self.events.append((event, line))
+        if event == 'line':
+            self.counts[line] = self.counts.get(line, 0) + 1
+        # Calls made by the trace function itself are never traced.
+        helper(0)
+        return self
+
+    def run(self, function, *args):
+        sys.settrace(self)
+        try:
+            return function(*args)
+        finally:
+            sys.settrace(None)
+
+
+def helper(x):
+    return x * 2
+
+
+def loop(n):
+    total = 0
+    for i in range(n):
+        total += helper(i)
+    try:
+        1 / 0
+    except ZeroDivisionError:
+        total = -total
+    return total
+
+
+counter = LineCounter(loop.__code__)
+assert counter.run(loop, 3) == -6
+assert counter.events == [
+    ('call', 0),
+    ('line', 1),
+    ('line', 2),
+    ('line', 3),
+    ('line', 2),
+    ('line', 3),
+    ('line', 2),
+    ('line', 3),
+    # The end of the loop doesn't start line 3 again.
+    ('line', 2),
+    ('line', 4),
+    ('line', 5),
+    ('exception', 5),
+    ('line', 6),
+    ('line', 7),
+    ('line', 8),
+    ('return', 8),
+], counter.events
+assert counter.counts == {1: 1, 2: 4, 3: 3, 4: 1, 5: 1, 6: 1, 7: 1, 8: 1}
+assert sys.gettrace() is None
+
+
+def branches(n):
+    i = 0
+    while i < n:
+        if i % 2:
+            i = helper(i)
+        else:
+            i += 1
+    return i
+
+
+counter = LineCounter(branches.__code__)
+assert counter.run(branches, 4) == 6
+assert counter.events == [
+    ('call', 0),
+    ('line', 1),
+    ('line', 2),
+    ('line', 3),
+    ('line', 6),
+    ('line', 2),
+    ('line', 3),
+    ('line', 4),
+    ('line', 2),
+    ('line', 3),
+    ('line', 6),
+    ('line', 2),
+    ('line', 3),
+    ('line', 4),
+    ('line', 2),
+    ('line', 7),
+    ('return', 7),
+], counter.events
+
+
+# Generators get a call and a return event each time they're resumed and suspended.
+def generator():
+    yield 1
+    yield 2
+
+
+counter = LineCounter(generator.__code__)
+assert counter.run(list, generator()) == [1, 2]
+assert counter.events == [
+    ('call', 0),
+    ('line', 1),
+    ('return', 1),
+    ('call', 1),
+    ('line', 2),
+    ('return', 2),
+    ('call', 2),
+    ('return', 2),
+], counter.events
+
+
+# Exceptions send an event in every frame they pass through, and the return
+# event of a frame left by an exception has None as its argument.
+def raiser():
+    raise KeyError('k')
+
+
+def catcher():
+    try:
+        raiser()
+    except KeyError:
+        return 'caught'
+
+
+events = []
+
+
+def tracer(frame, event, arg):
+    name = frame.f_code.co_name
+    if name not in ('raiser', 'catcher'):
+        return None
+    if event == 'exception':
+        arg = arg[0]
+    events.append((name, event, frame.f_lineno - frame.f_code.co_firstlineno, arg))
+    return tracer
+
+
+sys.settrace(tracer)
+catcher()
+sys.settrace(None)
+assert events == [
+    ('catcher', 'call', 0, None),
+    ('catcher', 'line', 1, None),
+    ('catcher', 'line', 2, None),
+    ('raiser', 'call', 0, None),
+    ('raiser', 'line', 1, None),
+    ('raiser', 'exception', 1, KeyError),
+    ('raiser', 'return', 1, None),
+    ('catcher', 'exception', 2, KeyError),
+    ('catcher', 'line', 3, None),
+    ('catcher', 'line', 4, None),
+    ('catcher', 'return', 4, 'caught'),
+], events
+
+
+# The global trace function only gets call events. What it returns is the
+# frame's own trace function, which gets the rest and can be replaced.
+events = []
+
+
+def local_trace(frame, event, arg):
+    events.append(('local', event))
+    if event == 'line':
+        return second_local_trace
+    return None
+
+
+def second_local_trace(frame, event, arg):
+    events.append(('second', event))
+
+
+def global_trace(frame, event, arg):
+    if frame.f_code is helper.__code__:
+        events.append(('global', event))
+        return local_trace
+
+
+sys.settrace(global_trace)
+helper(1)
+sys.settrace(None)
+assert events == [('global', 'call'), ('local', 'line'), ('second', 'return')], events
+
+
+# A trace function that raises is removed, and its exception propagates.
+def failing_trace(frame, event, arg):
+    raise RuntimeError('trace failed')
+
+
+def call_traced():
+    sys.settrace(failing_trace)
+    helper(1)
+
+
+with assert_raises(RuntimeError):
+    call_traced()
+assert sys.gettrace() is None
+
+
+# f_trace_lines and f_trace_opcodes choose which of the per-instruction events
+# a frame's trace function gets.
+def two_lines():
+    x = 1
+    return x + 1
+
+
+def run_traced(setup):
+    events = []
+
+    def local_trace(frame, event, arg):
+        events.append(event)
+        return local_trace
+
+    def global_trace(frame, event, arg):
+        if frame.f_code is two_lines.__code__:
+            assert frame.f_trace_lines is True
+            assert frame.f_trace_opcodes is False
+            setup(frame)
+            return local_trace
+
+    sys.settrace(global_trace)
+    two_lines()
+    sys.settrace(None)
+    return events
+
+
+assert run_traced(lambda frame: None) == ['line', 'line', 'return']
+
+
+def no_lines(frame):
+    frame.f_trace_lines = False
+
+
+assert run_traced(no_lines) == ['return']
+
+
+def opcodes(frame):
+    frame.f_trace_opcodes = True
+
+
+events = run_traced(opcodes)
+assert events[0] == 'line' and events[1] == 'opcode' and events[-1] == 'return', events
+assert events.count('line') == 2
+# Each line has at least a load and a store or return
+assert events.count('opcode') >= 4
+
+
+def lines_off_opcodes_on(frame):
+    frame.f_trace_lines = False
+    frame.f_trace_opcodes = True
+
+
+events = run_traced(lines_off_opcodes_on)
+assert 'line' not in events and events.count('opcode') >= 4, events
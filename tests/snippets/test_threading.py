@@ -19,3 +19,146 @@ x.join()
 output.append("Main    : all done")
 
 assert len(output) == 6, output
+
+import _thread
+import signal
+
+from testutils import assert_raises
+
+# Threads run at the same time while they sleep
+start = time.monotonic()
+threads = [threading.Thread(target=time.sleep, args=(0.5,)) for _ in range(4)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+assert time.monotonic() - start < 1.5
+
+# ...and take turns running Python code
+lock = threading.Lock()
+counter = [0]
+
+
+def count_up():
+    for _ in range(1000):
+        with lock:
+            counter[0] += 1
+
+
+threads = [threading.Thread(target=count_up) for _ in range(4)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+assert counter[0] == 4000
+
+# A thread that never blocks doesn't keep the others from running
+stop = threading.Event()
+spins = [0]
+
+
+def spin():
+    while not stop.is_set():
+        spins[0] += 1
+
+
+spinner = threading.Thread(target=spin)
+spinner.start()
+time.sleep(0.1)
+stop.set()
+spinner.join()
+assert spins[0] > 0
+
+# Waiting on each other
+results = []
+results_lock = threading.Lock()
+
+
+def worker(n):
+    with results_lock:
+        results.append((n, threading.get_ident()))
+
+
+threads = [threading.Thread(target=worker, args=(n,)) for n in range(3)]
+for thread in threads:
+    thread.start()
+for thread in threads:
+    thread.join()
+got = sorted(results)
+assert [n for n, _ in got] == [0, 1, 2]
+idents = {ident for _, ident in got}
+assert threading.get_ident() not in idents
+
+condition = threading.Condition()
+ready = []
+
+
+def notify_later():
+    time.sleep(0.1)
+    with condition:
+        ready.append(True)
+        condition.notify()
+
+
+with condition:
+    threading.Thread(target=notify_later).start()
+    assert condition.wait_for(lambda: ready, timeout=5)
+
+# An RLock held by another thread has to be waited for
+rlock = threading.RLock()
+held = threading.Event()
+release = threading.Event()
+
+
+def hold():
+    with rlock:
+        held.set()
+        release.wait(5)
+
+
+holder = threading.Thread(target=hold)
+holder.start()
+held.wait(5)
+assert not rlock.acquire(timeout=0.1)
+assert_raises(RuntimeError, rlock.release)
+release.set()
+assert rlock.acquire(timeout=5)
+rlock.release()
+holder.join()
+
+# _thread directly, with keyword arguments
+done = _thread.allocate_lock()
+done.acquire()
+received = []
+
+
+def with_kwargs(a, b=None):
+    received.append((a, b))
+    done.release()
+
+
+ident = _thread.start_new_thread(with_kwargs, (1,), {"b": 2})
+assert isinstance(ident, int)
+assert done.acquire(timeout=5)
+assert received == [(1, 2)]
+assert_raises(TypeError, lambda: _thread.start_new_thread(with_kwargs, [1]))
+assert_raises(TypeError, lambda: _thread.start_new_thread(with_kwargs, (1,), [2]))
+
+# Only the main thread handles signals
+errors = []
+
+
+def set_handler():
+    try:
+        signal.signal(signal.SIGINT, signal.default_int_handler)
+    except ValueError as e:
+        errors.append(e)
+
+
+thread = threading.Thread(target=set_handler)
+thread.start()
+thread.join()
+assert len(errors) == 1
+
+assert threading.active_count() == 1
+assert threading.main_thread() is threading.current_thread()
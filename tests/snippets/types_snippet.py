@@ -50,10 +50,7 @@ assert B.__bases__ == (A,)
 
 del D
 
-try: # gc sweep is needed here for CPython...
-    import gc; gc.collect()
-except: # ...while RustPython doesn't have `gc` yet.
-    pass
+import gc; gc.collect()
 
 assert B.__subclasses__() == []
 assert C.__subclasses__() == []
@@ -0,0 +1,66 @@
+from testutils import assert_raises
+
+# Identifiers may use any letters, not only ASCII ones.
+α = 1
+β = 2
+λ = lambda x: x * 2
+assert λ(α + β) == 6
+
+def ενέργεια(μάζα, c=3):
+    return μάζα * c ** 2
+
+assert ενέργεια(2) == 18
+assert ενέργεια(μάζα=1, c=2) == 4
+
+名前 = "name"
+assert globals()["名前"] == "name"
+
+
+class Ünïcödé:
+    ταχύτητα = 5
+
+    def __init__(self):
+        self.θέση = 0
+
+
+obj = Ünïcödé()
+obj.θέση += Ünïcödé.ταχύτητα
+assert obj.θέση == 5
+assert getattr(obj, "θέση") == 5
+assert Ünïcödé.__name__ == "Ünïcödé"
+
+# Identifiers are normalized to NFKC, so different spellings of the same name
+# refer to the same binding. The sources use escapes to make the spellings visible.
+composed = "caf\u00e9"
+decomposed = "cafe\u0301"
+
+namespace = {}
+exec(decomposed + " = 1", namespace)
+assert namespace[composed] == 1
+assert decomposed not in namespace
+exec(composed + " += 1", namespace)
+assert eval(decomposed, namespace) == 2
+
+# The "fi" ligature and the long s are compatibility characters.
+namespace = {}
+exec("\ufb01le = 'ligature'\n\u017fpam = 'long s'", namespace)
+assert namespace["file"] == "ligature"
+assert namespace["spam"] == "long s"
+
+# Attributes and keyword arguments are normalized in the same way.
+namespace = {}
+exec(
+    "class C: pass\n"
+    "c = C()\n"
+    "c." + decomposed + " = 'attribute'\n"
+    "def f(" + composed + "): return " + composed + "\n"
+    "result = f(" + decomposed + "=3)\n",
+    namespace,
+)
+assert getattr(namespace["c"], composed) == "attribute"
+assert namespace["c"].__dict__ == {composed: "attribute"}
+assert namespace["result"] == 3
+
+# Characters that can't be part of an identifier are still rejected.
+with assert_raises(SyntaxError):
+    exec("abc = \u20ac")
@@ -0,0 +1,89 @@
+from testutils import assert_raises
+
+evaluated = []
+
+
+def note(value):
+    evaluated.append(value)
+    return value
+
+
+# Module level annotations are evaluated and collected in order
+x: note(int) = note(5)
+y: note(str)
+assert x == 5
+assert "y" not in globals()
+assert __annotations__["x"] is int
+assert __annotations__["y"] is str
+assert evaluated == [5, int, str]
+
+
+# So are class level ones, in the class' own __annotations__
+class Point:
+    x: int = 0
+    y: int
+    label: "str"
+
+
+assert Point.__annotations__ == {"x": int, "y": int, "label": "str"}
+assert Point.x == 0
+assert not hasattr(Point, "y")
+
+
+class Plain:
+    pass
+
+
+assert "__annotations__" not in Plain.__dict__
+
+
+# Annotations in functions are never evaluated, but make the name local
+def function():
+    undefined: does_not_exist
+    value: does_not_exist = 1
+    return value
+
+
+assert function() == 1
+assert function.__annotations__ == {}
+
+
+def unbound():
+    name: int
+    return name
+
+
+with assert_raises(UnboundLocalError):
+    unbound()
+
+
+# Attribute and subscript targets evaluate their parts, but aren't collected
+class Namespace:
+    pass
+
+
+evaluated.clear()
+namespace = Namespace()
+note(namespace).attribute: note(int)
+note({})[note("key")]: note(str)
+assert evaluated == [namespace, int, {}, "key", str]
+assert not hasattr(namespace, "attribute")
+items = {}
+items["key"]: int = 1
+namespace.attribute: int = 2
+assert items == {"key": 1}
+assert namespace.attribute == 2
+assert "key" not in __annotations__
+assert "attribute" not in __annotations__
+
+for source, message in [
+    ("(a, b): int", "only single target (not tuple) can be annotated"),
+    ("[a, b]: int", "only single target (not list) can be annotated"),
+    ("f(): int", "illegal target for annotation"),
+]:
+    try:
+        compile(source, "<annotation>", "exec")
+    except SyntaxError as error:
+        assert str(error).startswith(message), (source, str(error))
+    else:
+        assert False, source
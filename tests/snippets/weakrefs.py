@@ -11,6 +11,15 @@ b = ref(a)
 
 assert callable(b)
 assert b() is a
+assert b.__callback__ is None
+
+
+def callback(r):
+    pass
+
+
+assert ref(a, callback).__callback__ is callback
+assert ref(a, None).__callback__ is None
 
 
 class G:
@@ -0,0 +1,165 @@
+from testutils import assert_raises
+
+
+class Error(Exception):
+    pass
+
+
+# Values sent and the return value go through the delegating generator
+def accumulate():
+    total = 0
+    while True:
+        value = yield total
+        if value is None:
+            return total
+        total += value
+
+
+def delegate(log):
+    while True:
+        total = yield from accumulate()
+        log.append(total)
+
+
+log = []
+g = delegate(log)
+assert next(g) == 0
+assert g.send(1) == 1
+assert g.send(2) == 3
+assert next(g) == 0
+assert log == [3]
+
+
+# Thrown exceptions are handled by the subgenerator when it can
+def handling():
+    while True:
+        try:
+            yield "waiting"
+        except Error:
+            yield "handled"
+
+
+def outer():
+    yield from handling()
+
+
+g = outer()
+next(g)
+assert g.throw(Error) == "handled"
+assert next(g) == "waiting"
+
+
+# and otherwise propagate into the delegating generator
+def finishing():
+    try:
+        yield 1
+    except Error:
+        return "caught"
+
+
+def catching():
+    try:
+        result = yield from finishing()
+    except Error:
+        result = "outer"
+    yield result
+
+
+g = catching()
+next(g)
+assert g.throw(Error) == "caught"
+
+
+def reraising():
+    try:
+        yield 1
+    except Error:
+        raise KeyError("from inner")
+
+
+def catching_key_error():
+    try:
+        yield from reraising()
+    except KeyError as error:
+        yield error.args[0]
+
+
+g = catching_key_error()
+next(g)
+assert g.throw(Error) == "from inner"
+
+
+# Iterators without throw get the exception raised at the yield from
+def over_iterator():
+    try:
+        yield from iter([1, 2])
+    except Error:
+        yield "raised here"
+
+
+g = over_iterator()
+next(g)
+assert g.throw(Error) == "raised here"
+
+
+# close closes the subgenerator and raises GeneratorExit in the delegator
+closed = []
+
+
+def closing_inner():
+    try:
+        yield 1
+    finally:
+        closed.append("inner")
+
+
+def closing_outer():
+    try:
+        yield from closing_inner()
+    finally:
+        closed.append("outer")
+
+
+g = closing_outer()
+next(g)
+g.close()
+assert closed == ["inner", "outer"]
+with assert_raises(StopIteration):
+    next(g)
+
+
+# An error closing the subgenerator replaces GeneratorExit
+def bad_close():
+    try:
+        yield 1
+    finally:
+        raise Error
+
+
+def delegating_bad_close():
+    yield from bad_close()
+
+
+g = delegating_bad_close()
+next(g)
+with assert_raises(Error):
+    g.close()
+
+
+# Coroutines can't be delegated to from a plain generator
+async def coro():
+    pass
+
+
+def yield_from_coro(awaitable):
+    yield from awaitable
+
+
+c = coro()
+with assert_raises(TypeError):
+    next(yield_from_coro(c))
+c.close()
+
+for source in ["yield from x", "async def f():\n    yield from x"]:
+    with assert_raises(SyntaxError):
+        compile(source, "<test>", "exec")
@@ -0,0 +1,136 @@
+# Drive a debugging session of RustPython over a pseudo terminal, so that
+# breakpoint(), pdb and the line editor used by input() are tested together.
+
+import os
+import select
+import subprocess
+import sys
+import tempfile
+import time
+import unittest
+
+ROOT_DIR = ".."
+RUSTPYTHON_LIB_DIR = os.path.abspath(os.path.join(ROOT_DIR, "Lib"))
+
+PROGRAM = """\
+def compute(n):
+    total = 0
+    for i in range(n):
+        total += i
+    return total
+
+x = 1
+breakpoint()
+y = x + 1
+z = y * 10
+print("result", compute(z))
+"""
+
+# The cursor position query the line editor sends, and an answer to it
+CURSOR_POSITION_QUERY = b"\x1b[6n"
+CURSOR_POSITION_REPORT = b"\x1b[1;1R"
+
+
+@unittest.skipUnless(sys.platform != "win32", "requires a pseudo terminal")
+class PdbSessionTestCase(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        subprocess.check_call(["cargo", "build", "--release"])
+
+    def setUp(self):
+        import pty
+
+        fd, self.script = tempfile.mkstemp(suffix=".py")
+        with os.fdopen(fd, "w") as f:
+            f.write(PROGRAM)
+
+        env = os.environ.copy()
+        env["PYTHONPATH"] = RUSTPYTHON_LIB_DIR
+        env.pop("PYTHONBREAKPOINT", None)
+        binary = os.path.abspath(os.path.join(ROOT_DIR, "target", "release", "rustpython"))
+
+        self.master, slave = pty.openpty()
+        self.process = subprocess.Popen(
+            [binary, self.script], stdin=slave, stdout=slave, stderr=slave, env=env
+        )
+        os.close(slave)
+        self.output = b""
+
+    def tearDown(self):
+        if self.process.poll() is None:
+            self.process.kill()
+            self.process.wait()
+        os.close(self.master)
+        os.unlink(self.script)
+
+    def read_until(self, expected, timeout=60):
+        """ Read the terminal output until `expected` shows up, and return it """
+        deadline = time.time() + timeout
+        while expected not in self.output:
+            remaining = deadline - time.time()
+            ready, _, _ = select.select([self.master], [], [], max(remaining, 0))
+            if not ready:
+                self.fail("timed out waiting for {!r}, got {!r}".format(expected, self.output))
+            try:
+                chunk = os.read(self.master, 1024)
+            except OSError:
+                chunk = b""
+            if not chunk:
+                self.fail("output ended waiting for {!r}, got {!r}".format(expected, self.output))
+            if CURSOR_POSITION_QUERY in chunk:
+                os.write(self.master, CURSOR_POSITION_REPORT)
+            self.output += chunk
+        end = self.output.index(expected) + len(expected)
+        seen, self.output = self.output[:end], self.output[end:]
+        return seen
+
+    def command(self, line, expected):
+        self.read_until(b"(Pdb) ")
+        os.write(self.master, line + b"\r")
+        return self.read_until(expected)
+
+    def test_session(self):
+        self.read_until(b"-> y = x + 1")
+        self.command(b"s", b"-> z = y * 10")
+        self.command(b"s", b'-> print("result", compute(z))')
+        self.command(b"p y", b"\r\n2\r\n")
+        self.command(b"c", b"result 190\r\n")
+        self.assertEqual(self.process.wait(60), 0)
+
+
+class BreakpointHookTestCase(unittest.TestCase):
+    @classmethod
+    def setUpClass(cls):
+        subprocess.check_call(["cargo", "build", "--release"])
+
+    def run_breakpoint(self, hook):
+        env = os.environ.copy()
+        env["PYTHONPATH"] = RUSTPYTHON_LIB_DIR
+        env["PYTHONBREAKPOINT"] = hook
+        binary = os.path.abspath(os.path.join(ROOT_DIR, "target", "release", "rustpython"))
+        source = "breakpoint('hook argument'); print('done')"
+        return subprocess.run(
+            [binary, "-c", source],
+            env=env,
+            stdin=subprocess.DEVNULL,
+            stdout=subprocess.PIPE,
+            stderr=subprocess.PIPE,
+            check=True,
+        )
+
+    def test_disabled(self):
+        result = self.run_breakpoint("0")
+        self.assertEqual(result.stdout, b"done\n")
+
+    def test_custom_hook(self):
+        result = self.run_breakpoint("builtins.print")
+        self.assertEqual(result.stdout, b"hook argument\ndone\n")
+
+    def test_unimportable_hook(self):
+        result = self.run_breakpoint("nonexistent_module.hook")
+        self.assertEqual(result.stdout, b"done\n")
+        self.assertIn(b"Ignoring unimportable $PYTHONBREAKPOINT", result.stderr)
+
+
+if __name__ == "__main__":
+    unittest.main()
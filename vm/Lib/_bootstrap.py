@@ -230,7 +230,10 @@ def _verbose_message(message, *args, verbosity=1):
 def _requires_builtin(fxn):
     """Decorator to verify the named module is built-in."""
     def _requires_builtin_wrapper(self, fullname):
-        if fullname not in sys.builtin_module_names:
+        # XXX RustPython: an embedder can add built-in modules after
+        # sys.builtin_module_names is made, like wasm does.
+        if (fullname not in sys.builtin_module_names and
+                not _imp.is_builtin(fullname)):
             raise ImportError('{!r} is not a built-in module'.format(fullname),
                               name=fullname)
         return fxn(self, fullname)
@@ -746,7 +749,9 @@ class BuiltinImporter:
     @classmethod
     def create_module(self, spec):
         """Create a built-in module"""
-        if spec.name not in sys.builtin_module_names:
+        # XXX RustPython: see _requires_builtin.
+        if (spec.name not in sys.builtin_module_names and
+                not _imp.is_builtin(spec.name)):
             raise ImportError('{!r} is not a built-in module'.format(spec.name),
                               name=spec.name)
         return _call_with_frames_removed(_imp.create_builtin, spec)
@@ -800,7 +805,15 @@ class FrozenImporter:
     @classmethod
     def find_spec(cls, fullname, path=None, target=None):
         if _imp.is_frozen(fullname):
-            return spec_from_loader(fullname, cls, origin=cls._ORIGIN)
+            # XXX RustPython: namespace packages have no origin, like the ones
+            # PathFinder finds
+            origin = None if _imp.is_frozen_namespace(fullname) else cls._ORIGIN
+            spec = spec_from_loader(fullname, cls, origin=origin)
+            # XXX RustPython: give frozen packages a __path__ entry, which
+            # FrozenPathFinder finds the submodules of
+            if spec.submodule_search_locations is not None:
+                spec.submodule_search_locations.append(_frozen_path(fullname))
+            return spec
         else:
             return None
 
@@ -824,6 +837,11 @@ class FrozenImporter:
             raise ImportError('{!r} is not a frozen module'.format(name),
                               name=name)
         code = _call_with_frames_removed(_imp.get_frozen_object, name)
+        # XXX RustPython: frozen modules remember the file they were frozen
+        # from; the spec has no location, as the file isn't there to load
+        file = _imp.get_frozen_file(name)
+        if file is not None:
+            module.__file__ = file
         exec(code, module.__dict__)
 
     @classmethod
@@ -844,8 +862,11 @@ class FrozenImporter:
     @classmethod
     @_requires_frozen
     def get_source(cls, fullname):
-        """Return None as frozen modules do not have source code."""
-        return None
+        """Return the source code of the frozen module, or None if it wasn't
+        frozen with it."""
+        # XXX RustPython: py_compile_bytecode! embeds the source with
+        # embed_source = true
+        return _imp.get_frozen_source(fullname)
 
     @classmethod
     @_requires_frozen
@@ -853,6 +874,129 @@ class FrozenImporter:
         """Return True if the frozen module is a package."""
         return _imp.is_frozen_package(fullname)
 
+    # XXX RustPython: frozen packages can carry data files, which
+    # importlib.resources reads through this
+    @classmethod
+    def get_resource_reader(cls, fullname):
+        """Return a resource reader for the frozen package, or None if it
+        isn't one."""
+        if _imp.is_frozen(fullname) and _imp.is_frozen_package(fullname):
+            return _FrozenResourceReader(fullname)
+        return None
+
+
+# XXX RustPython: implements importlib.abc.ResourceReader over the resources
+# embedded with a frozen package
+class _FrozenResourceReader:
+
+    """Resource reader for the data files of a frozen package."""
+
+    def __init__(self, package):
+        self.package = package
+
+    def open_resource(self, resource):
+        data = _imp.get_frozen_resource(self.package, resource)
+        try:
+            import _io
+        except ImportError:
+            # There is no _io on wasm
+            return _FrozenResourceFile(data)
+        return _io.BytesIO(data)
+
+    def resource_path(self, resource):
+        # The resources are in memory, not in files
+        raise FileNotFoundError(resource)
+
+    def is_resource(self, name):
+        return name in _imp.get_frozen_resources(self.package)
+
+    def contents(self):
+        return iter(_imp.get_frozen_resources(self.package))
+
+
+# XXX RustPython: a read-only binary file over the data of a resource, for
+# where there is no _io.BytesIO
+class _FrozenResourceFile:
+
+    """In-memory binary file returned by _FrozenResourceReader.open_resource."""
+
+    def __init__(self, data):
+        self._data = data
+        self._pos = 0
+        self.closed = False
+
+    def read(self, size=-1):
+        if self.closed:
+            raise ValueError('I/O operation on closed file.')
+        end = len(self._data) if size is None or size < 0 else self._pos + size
+        data = self._data[self._pos:end]
+        self._pos += len(data)
+        return data
+
+    def readable(self):
+        return True
+
+    def close(self):
+        self.closed = True
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *args):
+        self.close()
+
+
+# XXX RustPython: the __path__ entries of frozen packages, in the same format as
+# the file names of frozen code objects
+def _frozen_path(package):
+    return '<frozen {}>'.format(package)
+
+
+class FrozenPathFinder:
+
+    """Path entry finder for the __path__ entries of frozen packages.
+
+    The class itself is the path hook: it raises ImportError for paths that
+    don't belong to a frozen package.
+
+    """
+
+    def __init__(self, path):
+        if not (isinstance(path, str) and path.startswith('<frozen ')
+                and path.endswith('>')):
+            raise ImportError('not a frozen package path', path=path)
+        package = path[len('<frozen '):-1]
+        if not (_imp.is_frozen(package) and _imp.is_frozen_package(package)):
+            raise ImportError('not a frozen package path', path=path)
+        self.package = package
+        self.path = path
+
+    def __repr__(self):
+        return 'FrozenPathFinder({!r})'.format(self.path)
+
+    def find_spec(self, fullname, target=None):
+        if fullname.rpartition('.')[0] != self.package:
+            return None
+        return FrozenImporter.find_spec(fullname)
+
+    def find_module(self, fullname):
+        """Find a frozen submodule of the package.
+
+        This method is deprecated.  Use find_spec() instead.
+
+        """
+        spec = self.find_spec(fullname)
+        return None if spec is None else spec.loader
+
+    def invalidate_caches(self):
+        pass
+
+    def iter_modules(self, prefix=''):
+        """Yield the (name, ispkg) pairs of the submodules of the package, as
+        pkgutil.iter_modules() expects."""
+        for name, ispkg in _imp.get_frozen_children(self.package):
+            yield prefix + name, ispkg
+
 
 # Import itself ###############################################################
 
@@ -1144,7 +1288,11 @@ def _setup(sys_module, _imp_module):
                 loader = FrozenImporter
             else:
                 continue
-            spec = _spec_from_module(module, loader)
+            # XXX RustPython: frozen modules have a __file__, but no location
+            if loader is FrozenImporter:
+                spec = FrozenImporter.find_spec(name)
+            else:
+                spec = _spec_from_module(module, loader)
             _init_module_attrs(spec, module)
 
     # Directly load built-in modules needed during bootstrap.
@@ -1163,6 +1311,8 @@ def _install(sys_module, _imp_module):
 
     sys.meta_path.append(BuiltinImporter)
     sys.meta_path.append(FrozenImporter)
+    # XXX RustPython
+    sys.path_hooks.append(FrozenPathFinder)
 
 
 def _install_external_importers():
@@ -924,7 +924,7 @@ class SourceLoader(_LoaderBasics):
                 source_mtime is not None):
             if hash_based:
                 if source_hash is None:
-                    source_hash = _imp.source_hash(source_bytes)
+                    source_hash = _imp.source_hash(_RAW_MAGIC_NUMBER, source_bytes)
                 data = _code_to_hash_pyc(code_object, source_hash, check_source)
             else:
                 data = _code_to_timestamp_pyc(code_object, source_mtime,
@@ -1599,6 +1599,12 @@ def _setup(_bootstrap_module):
     thread_module = _bootstrap._builtin_from_name('_thread')
     setattr(self_module, '_thread', thread_module)
 
+    # XXX Changed to fit RustPython!!!
+    # The magic number follows the bytecode format of the running interpreter.
+    global MAGIC_NUMBER, _RAW_MAGIC_NUMBER
+    _RAW_MAGIC_NUMBER = _imp.pyc_magic_number_token
+    MAGIC_NUMBER = _RAW_MAGIC_NUMBER.to_bytes(4, 'little')
+
     # Directly load the _weakref module (needed during bootstrap).
     weakref_module = _bootstrap._builtin_from_name('_weakref')
     setattr(self_module, '_weakref', weakref_module)
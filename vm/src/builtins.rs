@@ -5,12 +5,11 @@
 use std::cell::Cell;
 use std::char;
 use std::io::{self, Write};
-use std::str;
 
 use num_bigint::Sign;
 use num_traits::{Signed, ToPrimitive, Zero};
 #[cfg(feature = "rustpython-compiler")]
-use rustpython_compiler::compile;
+use rustpython_compiler::{compile, encoding};
 
 use crate::function::{single_or_tuple_any, Args, KwArgs, OptionalArg, PyFuncArgs};
 use crate::obj::objbool::{self, IntoPyBool};
@@ -94,14 +93,22 @@ fn builtin_bin(x: PyIntRef, _vm: &VirtualMachine) -> String {
     }
 }
 
-// builtin_breakpoint
+fn builtin_breakpoint(args: PyFuncArgs, vm: &VirtualMachine) -> PyResult {
+    match vm.get_attribute(vm.sys_module.clone(), "breakpointhook") {
+        Ok(hook) => vm.invoke(&hook, args),
+        Err(_) => Err(vm.new_exception(
+            vm.ctx.exceptions.runtime_error.clone(),
+            "lost sys.breakpointhook".to_string(),
+        )),
+    }
+}
 
 fn builtin_callable(obj: PyObjectRef, vm: &VirtualMachine) -> bool {
     vm.is_callable(&obj)
 }
 
-fn builtin_chr(i: u32, vm: &VirtualMachine) -> PyResult<String> {
-    match char::from_u32(i) {
+fn builtin_chr(i: PyIntRef, vm: &VirtualMachine) -> PyResult<String> {
+    match i.as_bigint().to_u32().and_then(char::from_u32) {
         Some(value) => Ok(value.to_string()),
         None => Err(vm.new_value_error("chr() arg not in range(0x110000)".to_string())),
     }
@@ -111,7 +118,7 @@ fn builtin_chr(i: u32, vm: &VirtualMachine) -> PyResult<String> {
 #[allow(dead_code)]
 struct CompileArgs {
     #[pyarg(positional_only, optional = false)]
-    source: Either<PyStringRef, PyBytesRef>,
+    source: PyObjectRef,
     #[pyarg(positional_only, optional = false)]
     filename: PyStringRef,
     #[pyarg(positional_only, optional = false)]
@@ -124,18 +131,61 @@ struct CompileArgs {
     optimize: OptionalArg<PyIntRef>,
 }
 
-fn builtin_compile(args: CompileArgs, vm: &VirtualMachine) -> PyResult {
-    // TODO: compile::compile should probably get bytes
-    let source = match &args.source {
-        Either::A(string) => string.as_str(),
-        Either::B(bytes) => str::from_utf8(bytes).unwrap(),
-    };
+/// Decode source code given as bytes like the contents of a source file, according to its byte
+/// order mark and coding declaration.
+#[cfg(feature = "rustpython-compiler")]
+fn decode_source(source: &[u8], vm: &VirtualMachine) -> PyResult<String> {
+    encoding::decode_source(source).map_err(|err| vm.new_syntax_error(&err))
+}
 
-    let mode_str = args.mode.as_str();
+/// Without the compiler, only UTF-8 source code can be decoded.
+#[cfg(not(feature = "rustpython-compiler"))]
+fn decode_source(source: &[u8], vm: &VirtualMachine) -> PyResult<String> {
+    String::from_utf8(source.to_vec()).map_err(|err| {
+        vm.new_exception(
+            vm.ctx.exceptions.syntax_error.clone(),
+            format!("source code is not valid UTF-8: {}", err),
+        )
+    })
+}
+
+/// The flags compile() accepts: the future feature flags, the obsolete `CO_NESTED` and the
+/// `PyCF_*` compiler flags.
+const COMPILE_FLAGS_MASK: i32 = 0x01fe_0000 | 0x0010 | 0x0200 | 0x0400 | 0x1000 | 0x2000 | 0x4000;
 
+fn builtin_compile(args: CompileArgs, vm: &VirtualMachine) -> PyResult {
     let flags = args
         .flags
         .map_or(Ok(0), |v| i32::try_from_object(vm, v.into_object()))?;
+    if flags & !COMPILE_FLAGS_MASK != 0 {
+        return Err(vm.new_value_error("compile(): unrecognised flags".to_owned()));
+    }
+
+    let mode_str = args.mode.as_str();
+    if !["exec", "eval", "single"].contains(&mode_str) {
+        return Err(
+            vm.new_value_error("compile() mode must be 'exec', 'eval' or 'single'".to_owned())
+        );
+    }
+
+    let is_ast = objtype::isinstance(&args.source, &vm.class("_ast", "AST"));
+    if is_ast && !(flags & ast::PY_COMPILE_FLAG_AST_ONLY).is_zero() {
+        return Ok(args.source);
+    }
+    let source = if is_ast {
+        None
+    } else {
+        let source = Either::<PyStringRef, PyBytesRef>::try_from_object(vm, args.source.clone())
+            .map_err(|_| {
+                vm.new_type_error(
+                    "compile() arg 1 must be a string, bytes or AST object".to_owned(),
+                )
+            })?;
+        Some(match source {
+            Either::A(string) => string.as_str().to_owned(),
+            Either::B(bytes) => decode_source(&bytes, vm)?,
+        })
+    };
 
     if (flags & ast::PY_COMPILE_FLAG_AST_ONLY).is_zero() {
         #[cfg(feature = "rustpython-compiler")]
@@ -144,8 +194,41 @@ fn builtin_compile(args: CompileArgs, vm: &VirtualMachine) -> PyResult {
                 .parse::<compile::Mode>()
                 .map_err(|err| vm.new_value_error(err.to_string()))?;
 
-            vm.compile(&source, mode, args.filename.as_str().to_string())
-                .map(|o| o.into_object())
+            use crate::bytecode::CodeFlags;
+            use crate::obj::objcode::CO_FUTURE_ANNOTATIONS;
+
+            // Future features are inherited from the calling code unless `dont_inherit` is set.
+            let mut future_flags = if args.dont_inherit.unwrap_or(false) {
+                CodeFlags::empty()
+            } else {
+                vm.current_future_flags()
+            };
+            if flags as u32 & CO_FUTURE_ANNOTATIONS != 0 {
+                future_flags |= CodeFlags::FUTURE_ANNOTATIONS;
+            }
+
+            // An optimization level of -1 is the one the interpreter runs with.
+            let optimize = match args.optimize {
+                OptionalArg::Present(optimize) => optimize.as_bigint().to_i32().unwrap_or(i32::MAX),
+                OptionalArg::Missing => -1,
+            };
+            let optimize = match optimize {
+                -1 => vm.settings.optimize,
+                0..=2 => optimize as u8,
+                _ => return Err(vm.new_value_error("compile(): invalid optimize value".to_owned())),
+            };
+
+            let source_path = args.filename.as_str().to_string();
+            let code = match source {
+                Some(source) => {
+                    vm.compile_with_flags(&source, mode, source_path, optimize, future_flags)
+                }
+                None => {
+                    let top = ast::node_to_top(vm, &args.source, mode_str)?;
+                    vm.compile_ast(top, mode, source_path, optimize, future_flags)
+                }
+            };
+            code.map(|o| o.into_object())
                 .map_err(|err| vm.new_syntax_error(&err))
         }
         #[cfg(not(feature = "rustpython-compiler"))]
@@ -153,11 +236,8 @@ fn builtin_compile(args: CompileArgs, vm: &VirtualMachine) -> PyResult {
             Err(vm.new_value_error("PyCF_ONLY_AST flag is required without compiler support"))
         }
     } else {
-        use rustpython_parser::parser;
-        let mode = mode_str
-            .parse::<parser::Mode>()
-            .map_err(|err| vm.new_value_error(err.to_string()))?;
-        ast::parse(&vm, &source, mode)
+        let type_comments = flags & ast::PY_COMPILE_FLAG_TYPE_COMMENTS != 0;
+        ast::parse(&vm, &source.unwrap(), mode_str, type_comments)
     }
 }
 
@@ -180,7 +260,7 @@ fn builtin_divmod(a: PyObjectRef, b: PyObjectRef, vm: &VirtualMachine) -> PyResu
         b.clone(),
         "__divmod__",
         "__rdivmod__",
-        |vm, a, b| Err(vm.new_unsupported_operand_error(a, b, "divmod")),
+        |vm, a, b| Err(vm.new_unsupported_operand_error(a, b, "divmod()")),
     )
 }
 
@@ -227,7 +307,13 @@ fn run_code(
     // Determine code object:
     let code_obj = match source {
         Either::A(string) => vm
-            .compile(string.as_str(), mode, "<string>".to_string())
+            .compile_with_flags(
+                string.as_str(),
+                mode,
+                "<string>".to_string(),
+                vm.settings.optimize,
+                vm.current_future_flags(),
+            )
             .map_err(|err| vm.new_syntax_error(&err))?,
         Either::B(code_obj) => code_obj,
     };
@@ -252,7 +338,7 @@ fn make_scope(vm: &VirtualMachine, scope: ScopeArgs) -> PyResult<Scope> {
     let globals = match globals {
         Some(dict) => {
             if !dict.contains_key("__builtins__", vm) {
-                let builtins_dict = vm.builtins.dict.as_ref().unwrap().as_object();
+                let builtins_dict = vm.builtins.dict().unwrap().as_object();
                 dict.set_item("__builtins__", builtins_dict.clone(), vm)
                     .unwrap();
             }
@@ -339,7 +425,31 @@ fn builtin_id(obj: PyObjectRef, _vm: &VirtualMachine) -> usize {
     obj.get_id()
 }
 
-// builtin_input
+fn builtin_input(prompt: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult<String> {
+    if let OptionalArg::Present(prompt) = prompt {
+        let prompt = vm.to_str(&prompt)?;
+        let mut stdout = io::stdout();
+        write!(stdout, "{}", prompt.as_str()).unwrap();
+        stdout.flush().unwrap();
+    }
+    let mut line = String::new();
+    match io::stdin().read_line(&mut line) {
+        Ok(0) => Err(vm.new_exception(
+            vm.ctx.exceptions.eof_error.clone(),
+            "EOF when reading a line".to_string(),
+        )),
+        Ok(_) => {
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            Ok(line)
+        }
+        Err(err) => Err(vm.new_os_error(err.to_string())),
+    }
+}
 
 fn builtin_isinstance(obj: PyObjectRef, typ: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
     single_or_tuple_any(
@@ -373,8 +483,20 @@ fn builtin_issubclass(
     )
 }
 
-fn builtin_iter(iter_target: PyObjectRef, vm: &VirtualMachine) -> PyResult {
-    objiter::get_iter(vm, &iter_target)
+fn builtin_iter(
+    iter_target: PyObjectRef,
+    sentinel: OptionalArg<PyObjectRef>,
+    vm: &VirtualMachine,
+) -> PyResult {
+    if let OptionalArg::Present(sentinel) = sentinel {
+        if !vm.is_callable(&iter_target) {
+            return Err(vm.new_type_error("iter(v, w): v must be callable".to_owned()));
+        }
+        let iterator = objiter::PyCallableIterator::new(iter_target, sentinel);
+        Ok(iterator.into_ref(vm).into_object())
+    } else {
+        objiter::get_iter(vm, &iter_target)
+    }
 }
 
 fn builtin_len(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult {
@@ -551,7 +673,7 @@ fn builtin_pow(
     match mod_value {
         OptionalArg::Missing => {
             vm.call_or_reflection(x.clone(), y.clone(), "__pow__", "__rpow__", |vm, x, y| {
-                Err(vm.new_unsupported_operand_error(x, y, "pow"))
+                Err(vm.new_unsupported_operand_error(x, y, "** or pow()"))
             })
         }
         OptionalArg::Present(m) => {
@@ -598,7 +720,8 @@ trait Printer {
 
 impl Printer for &'_ PyObjectRef {
     fn write(&mut self, vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<()> {
-        vm.call_method(self, "write", vec![obj])?;
+        let s = vm.to_str(&obj)?;
+        vm.call_method(self, "write", vec![s.into_object()])?;
         Ok(())
     }
 
@@ -762,13 +885,13 @@ pub fn make_module(vm: &VirtualMachine, module: PyObjectRef) {
     #[cfg(target_arch = "wasm32")]
     let open = vm.ctx.none();
     #[cfg(not(target_arch = "wasm32"))]
-    let open = vm.ctx.new_rustfunc(io_open);
+    let open = vm.ctx.new_rustfunc_named(io_open, "open", None);
 
     #[cfg(feature = "rustpython-compiler")]
     {
         extend_module!(vm, module, {
-            "eval" => ctx.new_rustfunc(builtin_eval),
-            "exec" => ctx.new_rustfunc(builtin_exec),
+            "eval" => ctx.new_rustfunc_named(builtin_eval, "eval", None),
+            "exec" => ctx.new_rustfunc_named(builtin_exec, "exec", None),
         });
     }
 
@@ -778,73 +901,75 @@ pub fn make_module(vm: &VirtualMachine, module: PyObjectRef) {
         //set __name__ fixes: https://github.com/RustPython/RustPython/issues/146
         "__name__" => ctx.new_str(String::from("__main__")),
 
-        "abs" => ctx.new_rustfunc(builtin_abs),
-        "all" => ctx.new_rustfunc(builtin_all),
-        "any" => ctx.new_rustfunc(builtin_any),
-        "ascii" => ctx.new_rustfunc(builtin_ascii),
-        "bin" => ctx.new_rustfunc(builtin_bin),
+        "abs" => ctx.new_rustfunc_named(builtin_abs, "abs", None),
+        "all" => ctx.new_rustfunc_named(builtin_all, "all", None),
+        "any" => ctx.new_rustfunc_named(builtin_any, "any", None),
+        "ascii" => ctx.new_rustfunc_named(builtin_ascii, "ascii", None),
+        "bin" => ctx.new_rustfunc_named(builtin_bin, "bin", None),
         "bool" => ctx.bool_type(),
+        "breakpoint" => ctx.new_rustfunc_named(builtin_breakpoint, "breakpoint", None),
         "bytearray" => ctx.bytearray_type(),
         "bytes" => ctx.bytes_type(),
-        "callable" => ctx.new_rustfunc(builtin_callable),
-        "chr" => ctx.new_rustfunc(builtin_chr),
+        "callable" => ctx.new_rustfunc_named(builtin_callable, "callable", None),
+        "chr" => ctx.new_rustfunc_named(builtin_chr, "chr", None),
         "classmethod" => ctx.classmethod_type(),
-        "compile" => ctx.new_rustfunc(builtin_compile),
+        "compile" => ctx.new_rustfunc_named(builtin_compile, "compile", None),
         "complex" => ctx.complex_type(),
-        "delattr" => ctx.new_rustfunc(builtin_delattr),
+        "delattr" => ctx.new_rustfunc_named(builtin_delattr, "delattr", None),
         "dict" => ctx.dict_type(),
-        "divmod" => ctx.new_rustfunc(builtin_divmod),
-        "dir" => ctx.new_rustfunc(builtin_dir),
+        "divmod" => ctx.new_rustfunc_named(builtin_divmod, "divmod", None),
+        "dir" => ctx.new_rustfunc_named(builtin_dir, "dir", None),
         "enumerate" => ctx.enumerate_type(),
         "float" => ctx.float_type(),
         "frozenset" => ctx.frozenset_type(),
         "filter" => ctx.filter_type(),
-        "format" => ctx.new_rustfunc(builtin_format),
-        "getattr" => ctx.new_rustfunc(builtin_getattr),
-        "globals" => ctx.new_rustfunc(builtin_globals),
-        "hasattr" => ctx.new_rustfunc(builtin_hasattr),
-        "hash" => ctx.new_rustfunc(builtin_hash),
-        "hex" => ctx.new_rustfunc(builtin_hex),
-        "id" => ctx.new_rustfunc(builtin_id),
+        "format" => ctx.new_rustfunc_named(builtin_format, "format", None),
+        "getattr" => ctx.new_rustfunc_named(builtin_getattr, "getattr", None),
+        "globals" => ctx.new_rustfunc_named(builtin_globals, "globals", None),
+        "hasattr" => ctx.new_rustfunc_named(builtin_hasattr, "hasattr", None),
+        "hash" => ctx.new_rustfunc_named(builtin_hash, "hash", None),
+        "hex" => ctx.new_rustfunc_named(builtin_hex, "hex", None),
+        "id" => ctx.new_rustfunc_named(builtin_id, "id", None),
+        "input" => ctx.new_rustfunc_named(builtin_input, "input", None),
         "int" => ctx.int_type(),
-        "isinstance" => ctx.new_rustfunc(builtin_isinstance),
-        "issubclass" => ctx.new_rustfunc(builtin_issubclass),
-        "iter" => ctx.new_rustfunc(builtin_iter),
-        "len" => ctx.new_rustfunc(builtin_len),
+        "isinstance" => ctx.new_rustfunc_named(builtin_isinstance, "isinstance", None),
+        "issubclass" => ctx.new_rustfunc_named(builtin_issubclass, "issubclass", None),
+        "iter" => ctx.new_rustfunc_named(builtin_iter, "iter", None),
+        "len" => ctx.new_rustfunc_named(builtin_len, "len", None),
         "list" => ctx.list_type(),
-        "locals" => ctx.new_rustfunc(builtin_locals),
+        "locals" => ctx.new_rustfunc_named(builtin_locals, "locals", None),
         "map" => ctx.map_type(),
-        "max" => ctx.new_rustfunc(builtin_max),
+        "max" => ctx.new_rustfunc_named(builtin_max, "max", None),
         "memoryview" => ctx.memoryview_type(),
-        "min" => ctx.new_rustfunc(builtin_min),
+        "min" => ctx.new_rustfunc_named(builtin_min, "min", None),
         "object" => ctx.object(),
-        "oct" => ctx.new_rustfunc(builtin_oct),
+        "oct" => ctx.new_rustfunc_named(builtin_oct, "oct", None),
         "open" => open,
-        "ord" => ctx.new_rustfunc(builtin_ord),
-        "next" => ctx.new_rustfunc(builtin_next),
-        "pow" => ctx.new_rustfunc(builtin_pow),
-        "print" => ctx.new_rustfunc(builtin_print),
+        "ord" => ctx.new_rustfunc_named(builtin_ord, "ord", None),
+        "next" => ctx.new_rustfunc_named(builtin_next, "next", None),
+        "pow" => ctx.new_rustfunc_named(builtin_pow, "pow", None),
+        "print" => ctx.new_rustfunc_named(builtin_print, "print", None),
         "property" => ctx.property_type(),
         "range" => ctx.range_type(),
-        "repr" => ctx.new_rustfunc(builtin_repr),
-        "reversed" => ctx.new_rustfunc(builtin_reversed),
-        "round" => ctx.new_rustfunc(builtin_round),
+        "repr" => ctx.new_rustfunc_named(builtin_repr, "repr", None),
+        "reversed" => ctx.new_rustfunc_named(builtin_reversed, "reversed", None),
+        "round" => ctx.new_rustfunc_named(builtin_round, "round", None),
         "set" => ctx.set_type(),
-        "setattr" => ctx.new_rustfunc(builtin_setattr),
-        "sorted" => ctx.new_rustfunc(builtin_sorted),
+        "setattr" => ctx.new_rustfunc_named(builtin_setattr, "setattr", None),
+        "sorted" => ctx.new_rustfunc_named(builtin_sorted, "sorted", None),
         "slice" => ctx.slice_type(),
         "staticmethod" => ctx.staticmethod_type(),
         "str" => ctx.str_type(),
-        "sum" => ctx.new_rustfunc(builtin_sum),
+        "sum" => ctx.new_rustfunc_named(builtin_sum, "sum", None),
         "super" => ctx.super_type(),
         "tuple" => ctx.tuple_type(),
         "type" => ctx.type_type(),
-        "vars" => ctx.new_rustfunc(builtin_vars),
+        "vars" => ctx.new_rustfunc_named(builtin_vars, "vars", None),
         "zip" => ctx.zip_type(),
-        "exit" => ctx.new_rustfunc(builtin_exit),
-        "quit" => ctx.new_rustfunc(builtin_exit),
-        "__import__" => ctx.new_rustfunc(builtin_import),
-        "__build_class__" => ctx.new_rustfunc(builtin_build_class_),
+        "exit" => ctx.new_rustfunc_named(builtin_exit, "exit", None),
+        "quit" => ctx.new_rustfunc_named(builtin_exit, "quit", None),
+        "__import__" => ctx.new_rustfunc_named(builtin_import, "__import__", None),
+        "__build_class__" => ctx.new_rustfunc_named(builtin_build_class_, "__build_class__", None),
 
         // Constants
         "NotImplemented" => ctx.not_implemented(),
@@ -856,7 +981,9 @@ pub fn make_module(vm: &VirtualMachine, module: PyObjectRef) {
         "ArithmeticError" => ctx.exceptions.arithmetic_error.clone(),
         "AssertionError" => ctx.exceptions.assertion_error.clone(),
         "AttributeError" => ctx.exceptions.attribute_error.clone(),
+        "BufferError" => ctx.exceptions.buffer_error.clone(),
         "NameError" => ctx.exceptions.name_error.clone(),
+        "UnboundLocalError" => ctx.exceptions.unbound_local_error.clone(),
         "OverflowError" => ctx.exceptions.overflow_error.clone(),
         "RuntimeError" => ctx.exceptions.runtime_error.clone(),
         "ReferenceError" => ctx.exceptions.reference_error.clone(),
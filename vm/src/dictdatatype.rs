@@ -1,6 +1,6 @@
 use crate::obj::objstr::PyString;
 use crate::pyhash;
-use crate::pyobject::{IdProtocol, IntoPyObject, PyObjectRef, PyResult};
+use crate::pyobject::{IdProtocol, IntoPyObject, PyObjectRef, PyResult, TypeProtocol};
 use crate::vm::VirtualMachine;
 use num_bigint::ToBigInt;
 /// Ordered dictionary implementation.
@@ -10,6 +10,7 @@ use num_bigint::ToBigInt;
 use std::collections::{hash_map::DefaultHasher, HashMap};
 use std::hash::{Hash, Hasher};
 use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// hash value of an object returned by __hash__
 type HashValue = pyhash::PyHash;
@@ -18,11 +19,19 @@ type HashIndex = pyhash::PyHash;
 /// entry index mapped in indices
 type EntryIndex = usize;
 
-#[derive(Clone)]
 pub struct Dict<T = PyObjectRef> {
     size: usize,
     indices: HashMap<HashIndex, EntryIndex>,
     entries: Vec<Option<DictEntry<T>>>,
+    version: u64,
+}
+
+/// Dict versions are handed out from a single counter, so that no two dicts, nor two states of
+/// the same dict, ever have the same version.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+fn next_version() -> u64 {
+    NEXT_VERSION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl<T> Default for Dict<T> {
@@ -31,6 +40,18 @@ impl<T> Default for Dict<T> {
             size: 0,
             indices: HashMap::new(),
             entries: Vec::new(),
+            version: next_version(),
+        }
+    }
+}
+
+impl<T: Clone> Clone for Dict<T> {
+    fn clone(&self) -> Self {
+        Dict {
+            size: self.size,
+            indices: self.indices.clone(),
+            entries: self.entries.clone(),
+            version: next_version(),
         }
     }
 }
@@ -84,11 +105,19 @@ impl<T: Clone> Dict<T> {
         self.entries.push(Some(entry));
         self.indices.insert(hash_index, entry_index);
         self.size += 1;
+        self.version = next_version();
     }
 
     fn unchecked_delete(&mut self, entry_index: EntryIndex) {
         self.entries[entry_index] = None;
         self.size -= 1;
+        self.version = next_version();
+    }
+
+    /// A number that changes whenever a key is added, removed or assigned to, and that no other
+    /// dict shares. Inline caches use it to tell whether a lookup can give a different result.
+    pub fn version(&self) -> u64 {
+        self.version
     }
 
     /// Store a key
@@ -106,6 +135,7 @@ impl<T: Clone> Dict<T> {
                 // Update existing key
                 if let Some(ref mut entry) = self.entries[index] {
                     entry.value = value;
+                    self.version = next_version();
                     Ok(())
                 } else {
                     panic!("Lookup returned invalid index into entries!");
@@ -151,7 +181,8 @@ impl<T: Clone> Dict<T> {
     pub fn clear(&mut self) {
         self.entries.clear();
         self.indices.clear();
-        self.size = 0
+        self.size = 0;
+        self.version = next_version();
     }
 
     /// Delete a key
@@ -236,7 +267,9 @@ impl<T: Clone> Dict<T> {
             if self.indices.contains_key(&hash_index) {
                 // Now we have an index, lets check the key.
                 let index = self.indices[&hash_index];
-                if let Some(entry) = &self.entries[index] {
+                // The index of an entry that `pop_back` dropped may be past the end, or taken
+                // by a later entry, which is then found from here too
+                if let Some(Some(entry)) = self.entries.get(index) {
                     // Okay, we have an entry at this place
                     if key.do_is(&entry.key) {
                         // Literally the same object
@@ -297,6 +330,19 @@ impl<T: Clone> Dict<T> {
         }
     }
 
+    /// Retrieve and delete the most recently inserted key, which `dict.popitem()` returns.
+    /// The deleted entries at the end are dropped with it, so that draining the dict this way
+    /// doesn't pass over them again each time.
+    pub fn pop_back(&mut self) -> Option<(PyObjectRef, T)> {
+        while let Some(None) = self.entries.last() {
+            self.entries.pop();
+        }
+        let entry = self.entries.pop()??;
+        self.size -= 1;
+        self.version = next_version();
+        Some((entry.key, entry.value))
+    }
+
     pub fn sizeof(&self) -> usize {
         size_of::<Self>() + self.size * size_of::<DictEntry<T>>()
     }
@@ -324,7 +370,14 @@ pub trait DictKey {
 /// to index dictionaries.
 impl DictKey for &PyObjectRef {
     fn do_hash(self, vm: &VirtualMachine) -> PyResult<HashValue> {
-        let raw_hash = vm._hash(self)?;
+        // An exact str has its hash cached, and calling `__hash__` would only return that, like
+        // for the names interned by code objects.
+        let raw_hash = match self.payload::<PyString>() {
+            Some(string) if self.class().is(&vm.ctx.str_type()) => {
+                pyhash::hash_bigint(&string.hash_value().to_bigint().unwrap())
+            }
+            _ => vm._hash(self)?,
+        };
         let mut hasher = DefaultHasher::new();
         raw_hash.hash(&mut hasher);
         Ok(hasher.finish() as HashValue)
@@ -397,7 +450,7 @@ impl DictKey for &String {
 
 #[cfg(test)]
 mod tests {
-    use super::{Dict, DictKey, VirtualMachine};
+    use super::{Dict, DictKey, IdProtocol, VirtualMachine};
 
     #[test]
     fn test_insert() {
@@ -432,6 +485,79 @@ mod tests {
             .expect("retrieved value must be equal to inserted value.");
     }
 
+    #[test]
+    fn test_version() {
+        let vm: VirtualMachine = Default::default();
+        let mut dict = Dict::default();
+        let key = vm.new_str("x".to_string());
+        let mut versions = vec![dict.version()];
+
+        dict.insert(&vm, &key, vm.get_none()).unwrap();
+        versions.push(dict.version());
+        dict.insert(&vm, &key, vm.new_int(1)).unwrap();
+        versions.push(dict.version());
+        assert!(dict.get(&vm, "x").unwrap().is_some());
+        assert!(!dict
+            .delete_if_exists(&vm, &vm.new_str("y".to_string()))
+            .unwrap());
+        assert_eq!(*versions.last().unwrap(), dict.version());
+        versions.push(dict.clone().version());
+        dict.delete(&vm, &key).unwrap();
+        versions.push(dict.version());
+        dict.clear();
+        versions.push(dict.version());
+        versions.push(Dict::<()>::default().version());
+
+        let count = versions.len();
+        versions.sort();
+        versions.dedup();
+        assert_eq!(count, versions.len());
+    }
+
+    #[test]
+    fn test_order() {
+        let vm: VirtualMachine = Default::default();
+        let mut dict = Dict::default();
+        let keys: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|key| vm.new_str(key.to_string()))
+            .collect();
+        for key in &keys {
+            dict.insert(&vm, key, vm.get_none()).unwrap();
+        }
+        dict.delete(&vm, &keys[1]).unwrap();
+        dict.insert(&vm, &keys[1], vm.get_none()).unwrap();
+        dict.insert(&vm, &keys[0], vm.new_int(1)).unwrap();
+
+        let order = |dict: &Dict| -> Vec<String> {
+            dict.keys()
+                .map(|key| crate::obj::objstr::get_value(&key))
+                .collect()
+        };
+        assert_eq!(order(&dict), vec!["a", "c", "d", "b"]);
+
+        let (key, _) = dict.pop_back().unwrap();
+        assert!(key.is(&keys[1]));
+        assert_eq!(order(&dict), vec!["a", "c", "d"]);
+        dict.delete(&vm, &keys[3]).unwrap();
+        let (key, _) = dict.pop_back().unwrap();
+        assert!(key.is(&keys[2]));
+        assert_eq!(order(&dict), vec!["a"]);
+
+        // The popped entries' places are taken by new ones
+        dict.insert(&vm, &keys[3], vm.new_int(3)).unwrap();
+        dict.insert(&vm, &keys[1], vm.new_int(1)).unwrap();
+        assert_eq!(order(&dict), vec!["a", "d", "b"]);
+        assert!(!dict.contains(&vm, &keys[2]).unwrap());
+        assert_eq!(dict.len(), 3);
+        for key in &keys[..2] {
+            assert!(dict.contains(&vm, key).unwrap());
+        }
+        dict.delete(&vm, &keys[1]).unwrap();
+        assert!(!dict.contains(&vm, &keys[1]).unwrap());
+        assert_eq!(order(&dict), vec!["a", "d"]);
+    }
+
     macro_rules! hash_tests {
         ($($name:ident: $example_hash:expr,)*) => {
             $(
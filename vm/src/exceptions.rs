@@ -1,14 +1,24 @@
+use crate::frame::Frame;
 use crate::function::PyFuncArgs;
+use crate::obj::objbool;
+use crate::obj::objdict::PyDictRef;
+use crate::obj::objstr::{self, PyString};
 use crate::obj::objtraceback::PyTracebackRef;
 use crate::obj::objtuple::{PyTuple, PyTupleRef};
 use crate::obj::objtype;
 use crate::obj::objtype::PyClassRef;
-use crate::pyobject::{IdProtocol, PyContext, PyObjectRef, PyResult, TypeProtocol};
+use crate::pyobject::{
+    IdProtocol, ItemProtocol, PyContext, PyObjectRef, PyResult, TryFromObject, TypeProtocol,
+};
 use crate::types::create_type;
 use crate::vm::VirtualMachine;
 use itertools::Itertools;
+use std::borrow::Cow;
+#[cfg(not(target_arch = "wasm32"))]
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{self, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufRead, BufReader};
 
 fn exception_init(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
     let exc_self = args.args[0].clone();
@@ -28,36 +38,96 @@ pub fn print_exception(vm: &VirtualMachine, exc: &PyObjectRef) {
     let _ = write_exception(io::stdout(), vm, exc);
 }
 
+/// Write the traceback of an exception to `output`, preceded by those of the exceptions it was
+/// chained to, the oldest first like CPython does.
 pub fn write_exception<W: Write>(
     mut output: W,
     vm: &VirtualMachine,
     exc: &PyObjectRef,
 ) -> io::Result<()> {
-    let mut had_cause = false;
-    if let Ok(cause) = vm.get_attribute(exc.clone(), "__cause__") {
-        if !vm.get_none().is(&cause) {
-            had_cause = true;
-            print_exception(vm, &cause);
-            writeln!(
-                output,
-                "\nThe above exception was the direct cause of the following exception:\n"
-            )?;
+    let mut chain = vec![exc.clone()];
+    let mut messages = vec![];
+    while let Some((older, message)) = chained_exception(vm, chain.last().unwrap(), &chain) {
+        chain.push(older);
+        messages.push(message);
+    }
+
+    for (i, exc) in chain.iter().enumerate().rev() {
+        print_exception_inner(&mut output, vm, exc)?;
+        if i > 0 {
+            writeln!(output, "\n{}\n", messages[i - 1])?;
         }
     }
-    if !had_cause {
-        if let Ok(context) = vm.get_attribute(exc.clone(), "__context__") {
-            if !vm.get_none().is(&context) {
-                print_exception(vm, &context);
-                writeln!(
-                    output,
-                    "\nDuring handling of the above exception, another exception occurred:\n"
-                )?;
-            }
+    Ok(())
+}
+
+/// The exception `exc` was chained to, with the line separating their tracebacks. Exceptions
+/// already `seen` in the chain are left out so that a cycle can't be printed forever.
+fn chained_exception(
+    vm: &VirtualMachine,
+    exc: &PyObjectRef,
+    seen: &[PyObjectRef],
+) -> Option<(PyObjectRef, &'static str)> {
+    let chained = |name| {
+        vm.get_attribute(exc.clone(), name)
+            .ok()
+            .filter(|obj| objtype::isinstance(obj, &vm.ctx.exceptions.base_exception_type))
+    };
+    let (older, message) = if let Some(cause) = chained("__cause__") {
+        (
+            cause,
+            "The above exception was the direct cause of the following exception:",
+        )
+    } else {
+        let suppress_context = vm
+            .get_attribute(exc.clone(), "__suppress_context__")
+            .and_then(|suppress| objbool::boolval(vm, suppress))
+            .unwrap_or(false);
+        if suppress_context {
+            return None;
+        }
+        (
+            chained("__context__")?,
+            "During handling of the above exception, another exception occurred:",
+        )
+    };
+    if seen.iter().any(|exc| exc.is(&older)) {
+        None
+    } else {
+        Some((older, message))
+    }
+}
+
+/// Set the `__context__` of an exception being raised to the one currently handled, if any.
+/// Like CPython, the new exception is cut out of the context chain first, so that it never
+/// loops.
+pub(crate) fn chain_to_current_exception(vm: &VirtualMachine, exc: &PyObjectRef) -> PyResult<()> {
+    let context = match vm.current_exception() {
+        Some(context) if !context.is(exc) => context,
+        _ => return Ok(()),
+    };
+    let mut link = context.clone();
+    let mut visited = vec![];
+    loop {
+        let next = match vm.get_attribute(link.clone(), "__context__") {
+            Ok(next) => next,
+            Err(_) => break,
+        };
+        if vm.is_none(&next) || visited.iter().any(|obj: &PyObjectRef| obj.is(&next)) {
+            break;
+        }
+        if next.is(exc) {
+            vm.set_attr(&link, "__context__", vm.get_none())?;
+            break;
         }
+        visited.push(link);
+        link = next;
     }
-    print_exception_inner(output, vm, exc)
+    vm.set_attr(exc, "__context__", context)?;
+    Ok(())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn print_source_line<W: Write>(mut output: W, filename: &str, lineno: usize) -> io::Result<()> {
     // TODO: use io.open() method instead, when available, according to https://github.com/python/cpython/blob/master/Python/traceback.c#L393
     // TODO: support different encodings
@@ -72,8 +142,7 @@ fn print_source_line<W: Write>(mut output: W, filename: &str, lineno: usize) ->
     for (i, line) in file.lines().enumerate() {
         if i + 1 == lineno {
             if let Ok(line) = line {
-                // Indented with 4 spaces
-                writeln!(output, "    {}", line.trim_start())?;
+                write_source_line(&mut output, &line)?;
             }
             return Ok(());
         }
@@ -82,15 +151,51 @@ fn print_source_line<W: Write>(mut output: W, filename: &str, lineno: usize) ->
     Ok(())
 }
 
+/// There are no source files to show the lines of on wasm, where the code is frozen or compiled
+/// from a string.
+#[cfg(target_arch = "wasm32")]
+fn print_source_line<W: Write>(_output: W, _filename: &str, _lineno: usize) -> io::Result<()> {
+    Ok(())
+}
+
+fn write_source_line<W: Write>(mut output: W, line: &str) -> io::Result<()> {
+    // Indented with 4 spaces
+    writeln!(output, "    {}", line.trim_start())
+}
+
+/// The source embedded with the frozen module whose code runs in `frame`, which is found by
+/// the `__name__` of its globals.
+fn frozen_source(vm: &VirtualMachine, frame: &Frame) -> Option<Cow<'static, str>> {
+    let name = frame.scope.globals.get_item_option("__name__", vm).ok()??;
+    let name = name.payload::<PyString>()?;
+    let frozen = vm.frozen.borrow();
+    frozen
+        .get(name.as_str())?
+        .source
+        .as_ref()
+        .map(|source| source.decode())
+}
+
 /// Print exception occurrence location from traceback element
-fn print_traceback_entry<W: Write>(mut output: W, tb_entry: &PyTracebackRef) -> io::Result<()> {
+fn print_traceback_entry<W: Write>(
+    mut output: W,
+    vm: &VirtualMachine,
+    tb_entry: &PyTracebackRef,
+) -> io::Result<()> {
     let filename = tb_entry.frame.code.source_path.to_string();
     writeln!(
         output,
         r##"  File "{}", line {}, in {}"##,
         filename, tb_entry.lineno, tb_entry.frame.code.obj_name
     )?;
-    print_source_line(output, &filename, tb_entry.lineno)?;
+    match frozen_source(vm, &tb_entry.frame) {
+        Some(source) => {
+            if let Some(line) = source.lines().nth(tb_entry.lineno.wrapping_sub(1)) {
+                write_source_line(output, line)?;
+            }
+        }
+        None => print_source_line(output, &filename, tb_entry.lineno)?,
+    }
 
     Ok(())
 }
@@ -106,7 +211,7 @@ pub fn print_exception_inner<W: Write>(
             writeln!(output, "Traceback (most recent call last):")?;
             let mut tb: PyTracebackRef = tb.downcast().expect(" must be a traceback object");
             loop {
-                print_traceback_entry(&mut output, &tb)?;
+                print_traceback_entry(&mut output, vm, &tb)?;
                 tb = match &tb.next {
                     Some(tb) => tb.clone(),
                     None => break,
@@ -172,12 +277,26 @@ fn exception_str(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
         args,
         required = [(exc, Some(vm.ctx.exceptions.exception_type.clone()))]
     );
+    exception_args_joined(vm, exc, true)
+}
+
+fn key_error_str(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(exc, Some(vm.ctx.exceptions.key_error.clone()))]
+    );
+    // A lone key is shown by its repr, so that `KeyError('')` doesn't look empty.
+    exception_args_joined(vm, exc, false)
+}
+
+fn exception_args_joined(vm: &VirtualMachine, exc: &PyObjectRef, str_single: bool) -> PyResult {
     let args = vm
         .get_attribute(exc.clone(), "args")
         .unwrap()
         .downcast::<PyTuple>()
         .expect("'args' must be a tuple");
-    let args_str = exception_args_as_string(vm, args, false);
+    let args_str = exception_args_as_string(vm, args, str_single);
     let joined_str = match args_str.len() {
         0 => "".to_string(),
         1 => args_str.into_iter().next().unwrap(),
@@ -208,6 +327,79 @@ fn exception_repr(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.new_str(joined_str))
 }
 
+/// Attributes that every exception has, which are not part of its pickled state.
+const BASE_EXCEPTION_ATTRS: &[&str] = &[
+    "args",
+    "__traceback__",
+    "__cause__",
+    "__context__",
+    "__suppress_context__",
+];
+
+fn exception_reduce(zelf: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let args = vm.get_attribute(zelf.clone(), "args")?;
+    let mut reduced = vec![zelf.class().into_object(), args];
+    if let Some(dict) = zelf.dict() {
+        let mut state = vec![];
+        for (key, value) in dict {
+            if !BASE_EXCEPTION_ATTRS.contains(&vm.to_pystr(&key)?.as_str()) {
+                state.push((key, value));
+            }
+        }
+        if !state.is_empty() {
+            let state_dict = vm.ctx.new_dict();
+            for (key, value) in state {
+                state_dict.set_item(&key, value, vm)?;
+            }
+            reduced.push(state_dict.into_object());
+        }
+    }
+    Ok(vm.ctx.new_tuple(reduced))
+}
+
+fn exception_setstate(zelf: PyObjectRef, state: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    if !vm.is_none(&state) {
+        let state = PyDictRef::try_from_object(vm, state)
+            .map_err(|_| vm.new_type_error("state is not a dictionary".to_string()))?;
+        for (key, value) in state {
+            let attr = objstr::PyStringRef::try_from_object(vm, key)?;
+            vm.set_attr(&zelf, attr, value)?;
+        }
+    }
+    Ok(vm.get_none())
+}
+
+/// Turn the `(type, value)` pair given to e.g. `generator.throw()` into an exception instance,
+/// like CPython's `PyErr_NormalizeException`. Exception instances are passed through as is, so
+/// subclasses keep their identity.
+pub fn normalize(exc_type: PyObjectRef, exc_val: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    if let Ok(cls) = PyClassRef::try_from_object(vm, exc_type.clone()) {
+        if objtype::issubclass(&cls, &vm.ctx.exceptions.base_exception_type) {
+            if objtype::isinstance(&exc_val, &cls) {
+                return Ok(exc_val);
+            }
+            let args = if vm.is_none(&exc_val) {
+                vec![]
+            } else if let Some(tuple) = exc_val.payload::<PyTuple>() {
+                tuple.elements.clone()
+            } else {
+                vec![exc_val]
+            };
+            return vm.new_exception_obj(cls, args);
+        }
+    } else if objtype::isinstance(&exc_type, &vm.ctx.exceptions.base_exception_type) {
+        return if vm.is_none(&exc_val) {
+            Ok(exc_type)
+        } else {
+            Err(vm.new_type_error("instance exception may not have a separate value".to_string()))
+        };
+    }
+    Err(vm.new_type_error(format!(
+        "exceptions must be classes or instances deriving from BaseException, not {}",
+        exc_type.class().name
+    )))
+}
+
 fn exception_with_traceback(
     zelf: PyObjectRef,
     tb: Option<PyTracebackRef>,
@@ -227,6 +419,7 @@ pub struct ExceptionZoo {
     pub assertion_error: PyClassRef,
     pub attribute_error: PyClassRef,
     pub base_exception_type: PyClassRef,
+    pub buffer_error: PyClassRef,
     pub exception_type: PyClassRef,
     pub file_not_found_error: PyClassRef,
     pub file_exists_error: PyClassRef,
@@ -236,6 +429,7 @@ pub struct ExceptionZoo {
     pub lookup_error: PyClassRef,
     pub module_not_found_error: PyClassRef,
     pub name_error: PyClassRef,
+    pub unbound_local_error: PyClassRef,
     pub not_implemented_error: PyClassRef,
     pub recursion_error: PyClassRef,
     pub os_error: PyClassRef,
@@ -284,6 +478,7 @@ impl ExceptionZoo {
         let arithmetic_error = create_type("ArithmeticError", &type_type, &exception_type);
         let assertion_error = create_type("AssertionError", &type_type, &exception_type);
         let attribute_error = create_type("AttributeError", &type_type, &exception_type);
+        let buffer_error = create_type("BufferError", &type_type, &exception_type);
         let import_error = create_type("ImportError", &type_type, &exception_type);
         let index_error = create_type("IndexError", &type_type, &exception_type);
         let key_error = create_type("KeyError", &type_type, &exception_type);
@@ -303,6 +498,7 @@ impl ExceptionZoo {
         let module_not_found_error = create_type("ModuleNotFoundError", &type_type, &import_error);
         let not_implemented_error = create_type("NotImplementedError", &type_type, &runtime_error);
         let recursion_error = create_type("RecursionError", &type_type, &runtime_error);
+        let unbound_local_error = create_type("UnboundLocalError", &type_type, &name_error);
         let file_not_found_error = create_type("FileNotFoundError", &type_type, &os_error);
         let permission_error = create_type("PermissionError", &type_type, &os_error);
         let file_exists_error = create_type("FileExistsError", &type_type, &os_error);
@@ -338,6 +534,7 @@ impl ExceptionZoo {
             assertion_error,
             attribute_error,
             base_exception_type,
+            buffer_error,
             exception_type,
             file_not_found_error,
             file_exists_error,
@@ -347,6 +544,7 @@ impl ExceptionZoo {
             lookup_error,
             module_not_found_error,
             name_error,
+            unbound_local_error,
             not_implemented_error,
             recursion_error,
             os_error,
@@ -416,10 +614,75 @@ fn import_error_init(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
     Ok(vm.get_none())
 }
 
+fn stop_iteration_init(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
+    exception_init(vm, args.clone())?;
+
+    let exc_self = args.args[0].clone();
+    vm.set_attr(
+        &exc_self,
+        "value",
+        args.args.get(1).cloned().unwrap_or_else(|| vm.get_none()),
+    )?;
+    Ok(vm.get_none())
+}
+
+fn os_error_init(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
+    exception_init(vm, args.clone())?;
+
+    let exc_self = args.args[0].clone();
+    let exc_args = &args.args[1..];
+    let arg = |i: usize| {
+        if exc_args.len() >= 2 && exc_args.len() <= 5 {
+            exc_args.get(i).cloned()
+        } else {
+            None
+        }
+        .unwrap_or_else(|| vm.get_none())
+    };
+    vm.set_attr(&exc_self, "errno", arg(0))?;
+    vm.set_attr(&exc_self, "strerror", arg(1))?;
+    vm.set_attr(&exc_self, "filename", arg(2))?;
+    vm.set_attr(&exc_self, "filename2", arg(4))?;
+    if exc_args.len() >= 3 && exc_args.len() <= 5 {
+        // Like CPython, `args` only keeps errno and strerror when a filename is given.
+        vm.set_attr(&exc_self, "args", vm.ctx.new_tuple(exc_args[..2].to_vec()))?;
+    }
+    Ok(vm.get_none())
+}
+
+fn os_error_str(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
+    arg_check!(
+        vm,
+        args,
+        required = [(exc, Some(vm.ctx.exceptions.os_error.clone()))]
+    );
+    let errno = vm.get_attribute(exc.clone(), "errno")?;
+    let strerror = vm.get_attribute(exc.clone(), "strerror")?;
+    if vm.is_none(&errno) || vm.is_none(&strerror) {
+        return exception_args_joined(vm, exc, true);
+    }
+    let mut msg = format!(
+        "[Errno {}] {}",
+        vm.to_pystr(&errno)?,
+        vm.to_pystr(&strerror)?
+    );
+    let filename = vm.get_attribute(exc.clone(), "filename")?;
+    if !vm.is_none(&filename) {
+        msg.push_str(&format!(": {}", vm.to_repr(&filename)?.as_str()));
+        let filename2 = vm.get_attribute(exc.clone(), "filename2")?;
+        if !vm.is_none(&filename2) {
+            msg.push_str(&format!(" -> {}", vm.to_repr(&filename2)?.as_str()));
+        }
+    }
+    Ok(vm.new_str(msg))
+}
+
 pub fn init(context: &PyContext) {
     let base_exception_type = &context.exceptions.base_exception_type;
     extend_class!(context, base_exception_type, {
         "__init__" => context.new_rustfunc(exception_init),
+        "__reduce__" => context.new_rustfunc(exception_reduce),
+        "__setstate__" => context.new_rustfunc(exception_setstate),
         "with_traceback" => context.new_rustfunc(exception_with_traceback)
     });
 
@@ -429,6 +692,22 @@ pub fn init(context: &PyContext) {
         "__repr__" => context.new_rustfunc(exception_repr),
     });
 
+    let key_error_type = &context.exceptions.key_error;
+    extend_class!(context, key_error_type, {
+        "__str__" => context.new_rustfunc(key_error_str),
+    });
+
+    let stop_iteration_type = &context.exceptions.stop_iteration;
+    extend_class!(context, stop_iteration_type, {
+        "__init__" => context.new_rustfunc(stop_iteration_init),
+    });
+
+    let os_error_type = &context.exceptions.os_error;
+    extend_class!(context, os_error_type, {
+        "__init__" => context.new_rustfunc(os_error_init),
+        "__str__" => context.new_rustfunc(os_error_str),
+    });
+
     let import_error_type = &context.exceptions.import_error;
     extend_class!(context, import_error_type, {
         "__init__" => context.new_rustfunc(import_error_init)
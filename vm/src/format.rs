@@ -298,6 +298,9 @@ impl FormatSpec {
             Some(FormatType::HexLower) => 4,
             Some(FormatType::HexUpper) => 4,
             Some(FormatType::Number) => 3,
+            Some(FormatType::FixedPointLower) | Some(FormatType::FixedPointUpper) => 3,
+            Some(FormatType::GeneralFormatLower) | Some(FormatType::GeneralFormatUpper) => 3,
+            Some(FormatType::ExponentLower) | Some(FormatType::ExponentUpper) => 3,
             None => 3,
             _ => panic!("Separators only valid for numbers!"),
         }
@@ -320,7 +323,6 @@ impl FormatSpec {
     }
 
     pub fn format_int(&self, num: &BigInt) -> Result<String, &'static str> {
-        let fill_char = self.fill.unwrap_or(' ');
         let magnitude = num.abs();
         let prefix = if self.alternate_form {
             match self.format_type {
@@ -374,11 +376,6 @@ impl FormatSpec {
             prefix,
             self.add_magnitude_separators(raw_magnitude_string_result.unwrap())
         );
-        let align = self.align.unwrap_or(FormatAlign::Right);
-
-        // Use the byte length as the string length since we're in ascii
-        let num_chars = magnitude_string.len();
-
         let format_sign = self.sign.unwrap_or(FormatSign::Minus);
         let sign_str = match num.sign() {
             Sign::Minus => "-",
@@ -388,11 +385,151 @@ impl FormatSpec {
                 FormatSign::MinusOrSpace => " ",
             },
         };
+        Ok(self.format_sign_and_align(&magnitude_string, sign_str, FormatAlign::Right))
+    }
+
+    pub fn format_float(&self, num: f64) -> Result<String, &'static str> {
+        let precision = self.precision.unwrap_or(6);
+        let magnitude = num.abs();
+        let raw_magnitude_string_result: Result<String, &'static str> = match self.format_type {
+            Some(FormatType::FixedPointUpper) => Ok(format_fixed(precision, magnitude, true)),
+            Some(FormatType::FixedPointLower) => Ok(format_fixed(precision, magnitude, false)),
+            Some(FormatType::ExponentUpper) => Ok(format_exponent(precision, magnitude, true)),
+            Some(FormatType::ExponentLower) => Ok(format_exponent(precision, magnitude, false)),
+            Some(FormatType::GeneralFormatUpper) => Ok(format_general(
+                precision,
+                magnitude,
+                true,
+                self.alternate_form,
+            )),
+            Some(FormatType::GeneralFormatLower) | Some(FormatType::Number) => Ok(format_general(
+                precision,
+                magnitude,
+                false,
+                self.alternate_form,
+            )),
+            Some(FormatType::String) => Err("Unknown format code 's' for object of type 'float'"),
+            Some(FormatType::Binary) => Err("Unknown format code 'b' for object of type 'float'"),
+            Some(FormatType::Character) => {
+                Err("Unknown format code 'c' for object of type 'float'")
+            }
+            Some(FormatType::Decimal) => Err("Unknown format code 'd' for object of type 'float'"),
+            Some(FormatType::Octal) => Err("Unknown format code 'o' for object of type 'float'"),
+            Some(FormatType::HexLower) => Err("Unknown format code 'x' for object of type 'float'"),
+            Some(FormatType::HexUpper) => Err("Unknown format code 'X' for object of type 'float'"),
+            // Like the general format, but with at least one digit after the point of a fixed
+            // point number, and as many digits as `repr` without a precision.
+            None => match self.precision {
+                Some(precision) => {
+                    let general = format_general(precision, magnitude, false, self.alternate_form);
+                    if magnitude.is_finite() && !general.contains(|c| c == '.' || c == 'e') {
+                        Ok(format!("{}.0", general))
+                    } else {
+                        Ok(general)
+                    }
+                }
+                None => Ok(format_shortest(magnitude)),
+            },
+        };
+        let raw_magnitude_string = raw_magnitude_string_result?;
+        // Only the digits before the point are grouped.
+        let integer_digits = raw_magnitude_string
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| raw_magnitude_string.len());
+        let magnitude_string = format!(
+            "{}{}",
+            self.add_magnitude_separators(raw_magnitude_string[..integer_digits].to_string()),
+            &raw_magnitude_string[integer_digits..]
+        );
+
+        let format_sign = self.sign.unwrap_or(FormatSign::Minus);
+        let sign_str = if num.is_sign_negative() && !num.is_nan() {
+            "-"
+        } else {
+            match format_sign {
+                FormatSign::Plus => "+",
+                FormatSign::Minus => "",
+                FormatSign::MinusOrSpace => " ",
+            }
+        };
+        Ok(self.format_sign_and_align(&magnitude_string, sign_str, FormatAlign::Right))
+    }
+
+    pub fn format_string(&self, text: &str) -> Result<String, &'static str> {
+        match self.format_type {
+            Some(FormatType::String) | None => {}
+            Some(FormatType::Binary) => {
+                return Err("Unknown format code 'b' for object of type 'str'")
+            }
+            Some(FormatType::Character) => {
+                return Err("Unknown format code 'c' for object of type 'str'")
+            }
+            Some(FormatType::Decimal) => {
+                return Err("Unknown format code 'd' for object of type 'str'")
+            }
+            Some(FormatType::Octal) => {
+                return Err("Unknown format code 'o' for object of type 'str'")
+            }
+            Some(FormatType::HexLower) => {
+                return Err("Unknown format code 'x' for object of type 'str'")
+            }
+            Some(FormatType::HexUpper) => {
+                return Err("Unknown format code 'X' for object of type 'str'")
+            }
+            Some(FormatType::Number) => {
+                return Err("Unknown format code 'n' for object of type 'str'")
+            }
+            Some(FormatType::ExponentLower) => {
+                return Err("Unknown format code 'e' for object of type 'str'")
+            }
+            Some(FormatType::ExponentUpper) => {
+                return Err("Unknown format code 'E' for object of type 'str'")
+            }
+            Some(FormatType::GeneralFormatLower) => {
+                return Err("Unknown format code 'g' for object of type 'str'")
+            }
+            Some(FormatType::GeneralFormatUpper) => {
+                return Err("Unknown format code 'G' for object of type 'str'")
+            }
+            Some(FormatType::FixedPointLower) => {
+                return Err("Unknown format code 'f' for object of type 'str'")
+            }
+            Some(FormatType::FixedPointUpper) => {
+                return Err("Unknown format code 'F' for object of type 'str'")
+            }
+        }
+        if self.sign.is_some() {
+            return Err("Sign not allowed in string format specifier");
+        }
+        if self.alternate_form {
+            return Err("Alternate form (#) not allowed in string format specifier");
+        }
+        if self.align == Some(FormatAlign::AfterSign) {
+            return Err("'=' alignment not allowed in string format specifier");
+        }
+        let text = match self.precision {
+            Some(precision) => text.chars().take(precision).collect(),
+            None => text.to_string(),
+        };
+        Ok(self.format_sign_and_align(&text, "", FormatAlign::Left))
+    }
+
+    /// Fill `magnitude_string`, with `sign_str` in front of it, up to the width, aligned like
+    /// the spec says or else like `default_align`.
+    fn format_sign_and_align(
+        &self,
+        magnitude_string: &str,
+        sign_str: &str,
+        default_align: FormatAlign,
+    ) -> String {
+        let fill_char = self.fill.unwrap_or(' ');
+        let align = self.align.unwrap_or(default_align);
+        let num_chars = magnitude_string.chars().count();
 
         let fill_chars_needed: i32 = self.width.map_or(0, |w| {
             cmp::max(0, (w as i32) - (num_chars as i32) - (sign_str.len() as i32))
         });
-        Ok(match align {
+        match align {
             FormatAlign::Left => format!(
                 "{}{}{}",
                 sign_str,
@@ -423,7 +560,94 @@ impl FormatSpec {
                     left_fill_string, sign_str, magnitude_string, right_fill_string
                 )
             }
-        })
+        }
+    }
+}
+
+/// `inf` or `nan` for a float that isn't finite, in upper case with `upper`.
+fn format_nonfinite(magnitude: f64, upper: bool) -> Option<String> {
+    let text = if magnitude.is_nan() {
+        "nan"
+    } else if magnitude.is_infinite() {
+        "inf"
+    } else {
+        return None;
+    };
+    Some(if upper {
+        text.to_uppercase()
+    } else {
+        text.to_string()
+    })
+}
+
+fn format_fixed(precision: usize, magnitude: f64, upper: bool) -> String {
+    format_nonfinite(magnitude, upper).unwrap_or_else(|| format!("{:.*}", precision, magnitude))
+}
+
+/// Like `1.500000e+00`, with an exponent of at least two digits.
+fn format_exponent(precision: usize, magnitude: f64, upper: bool) -> String {
+    if let Some(text) = format_nonfinite(magnitude, upper) {
+        return text;
+    }
+    let text = format!("{:.*e}", precision, magnitude);
+    let position = text.find('e').unwrap();
+    let exponent = text[position + 1..].parse::<i32>().unwrap();
+    let e = if upper { 'E' } else { 'e' };
+    format!("{}{}{:+03}", &text[..position], e, exponent)
+}
+
+/// Fixed point or with an exponent, whichever suits the magnitude, with `precision` significant
+/// digits of which the trailing zeros are removed, unless it's the alternate form.
+fn format_general(precision: usize, magnitude: f64, upper: bool, alternate_form: bool) -> String {
+    if let Some(text) = format_nonfinite(magnitude, upper) {
+        return text;
+    }
+    let precision = cmp::max(precision, 1);
+    let exponent = {
+        let text = format!("{:.*e}", precision - 1, magnitude);
+        text[text.find('e').unwrap() + 1..].parse::<i64>().unwrap()
+    };
+    let text = if -4 <= exponent && exponent < precision as i64 {
+        format!(
+            "{:.*}",
+            (precision as i64 - 1 - exponent) as usize,
+            magnitude
+        )
+    } else {
+        format_exponent(precision - 1, magnitude, upper)
+    };
+    if alternate_form {
+        return text;
+    }
+    let (mantissa, exponent) = match text.find(|c| c == 'e' || c == 'E') {
+        Some(position) => text.split_at(position),
+        None => (text.as_str(), ""),
+    };
+    let mantissa = if mantissa.contains('.') {
+        mantissa.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        mantissa
+    };
+    format!("{}{}", mantissa, exponent)
+}
+
+/// The shortest digits that give back the same float, like `repr` does.
+fn format_shortest(magnitude: f64) -> String {
+    if let Some(text) = format_nonfinite(magnitude, false) {
+        return text;
+    }
+    let text = format!("{:e}", magnitude);
+    let position = text.find('e').unwrap();
+    let exponent = text[position + 1..].parse::<i32>().unwrap();
+    if -4 <= exponent && exponent < 16 {
+        let text = magnitude.to_string();
+        if text.contains('.') {
+            text
+        } else {
+            format!("{}.0", text)
+        }
+    } else {
+        format!("{}e{:+03}", &text[..position], exponent)
     }
 }
 
@@ -692,6 +916,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_float() {
+        let format = |spec, value| parse_format_spec(spec).format_float(value);
+        assert_eq!(format("", 3.0), Ok("3.0".to_string()));
+        assert_eq!(format("", 1e16), Ok("1e+16".to_string()));
+        assert_eq!(format("10.3f", 3.14159), Ok("     3.142".to_string()));
+        assert_eq!(format(">10.3", 3.14159), Ok("      3.14".to_string()));
+        assert_eq!(format(".3", 3.0), Ok("3.0".to_string()));
+        assert_eq!(format(".3", 1234.5), Ok("1.23e+03".to_string()));
+        assert_eq!(format("g", 0.00001), Ok("1e-05".to_string()));
+        assert_eq!(format("#g", 2.0), Ok("2.00000".to_string()));
+        assert_eq!(format(".2E", -1500.0), Ok("-1.50E+03".to_string()));
+        assert_eq!(format("+08.2f", 3.14159), Ok("+0003.14".to_string()));
+        assert_eq!(format(",.1f", 1234567.25), Ok("1,234,567.2".to_string()));
+        assert_eq!(format("F", std::f64::INFINITY), Ok("INF".to_string()));
+        assert_eq!(format("f", -std::f64::NAN), Ok("nan".to_string()));
+        assert_eq!(
+            format("d", 1.0),
+            Err("Unknown format code 'd' for object of type 'float'")
+        );
+    }
+
+    #[test]
+    fn test_format_string() {
+        let format = |spec, value| parse_format_spec(spec).format_string(value);
+        assert_eq!(format("", "spam"), Ok("spam".to_string()));
+        assert_eq!(format("*^10", "spam"), Ok("***spam***".to_string()));
+        assert_eq!(format(">6.2", "spam"), Ok("    sp".to_string()));
+        assert_eq!(format("_<8", "\u{262e}"), Ok("\u{262e}_______".to_string()));
+        assert_eq!(
+            format("+", "spam"),
+            Err("Sign not allowed in string format specifier")
+        );
+        assert_eq!(
+            format("=5", "spam"),
+            Err("'=' alignment not allowed in string format specifier")
+        );
+    }
+
     #[test]
     fn test_format_parse() {
         let expected = Ok(FormatString {
@@ -1,20 +1,27 @@
 use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::fmt;
 
 use indexmap::IndexMap;
 use itertools::Itertools;
 
 use crate::bytecode;
+use crate::exceptions;
 use crate::function::{single_or_tuple_any, PyFuncArgs};
+use crate::gc;
+use crate::inline_cache::{self, InlineCache};
 use crate::obj::objbool;
+use crate::obj::objbuiltinfunc::PyBuiltinFunction;
 use crate::obj::objcode::PyCodeRef;
 use crate::obj::objcoroutine::PyCoroutine;
 use crate::obj::objdict::{PyDict, PyDictRef};
+use crate::obj::objfunction::PyMethod;
 use crate::obj::objgenerator::PyGenerator;
 use crate::obj::objiter;
 use crate::obj::objlist;
+use crate::obj::objmodule;
 use crate::obj::objslice::PySlice;
-use crate::obj::objstr::{self, PyString};
+use crate::obj::objstr::{self, PyString, PyStringRef};
 use crate::obj::objtraceback::{PyTraceback, PyTracebackRef};
 use crate::obj::objtuple::PyTuple;
 use crate::obj::objtype::{self, PyClassRef};
@@ -22,7 +29,7 @@ use crate::pyobject::{
     IdProtocol, ItemProtocol, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject, TypeProtocol,
 };
 use crate::scope::{NameProtocol, Scope};
-use crate::vm::VirtualMachine;
+use crate::vm::{TraceEvent, VirtualMachine};
 
 #[derive(Clone, Debug)]
 struct Block {
@@ -86,12 +93,73 @@ pub struct Frame {
     pub scope: Scope,
     /// index of last instruction ran
     pub lasti: Cell<usize>,
+    /// The local trace function, `frame.f_trace` in Python
+    pub trace: RefCell<Option<PyObjectRef>>,
+    /// Whether the trace function gets `line` events, `frame.f_trace_lines` in Python
+    pub trace_lines: Cell<bool>,
+    /// Whether the trace function gets an `opcode` event before each instruction,
+    /// `frame.f_trace_opcodes` in Python
+    pub trace_opcodes: Cell<bool>,
+    /// While the frame is traced, the line of the last `line` event and the index of the last
+    /// instruction executed
+    current_line: Cell<Option<(usize, usize)>>,
+    /// The range of instructions on the line `trace_line` last looked up
+    line_range: Cell<Option<bytecode::LineRange>>,
+    /// The dict `f_locals` gives for a function, and the names of the variables in it
+    locals_snapshot: RefCell<Option<(PyDictRef, Vec<PyObjectRef>)>>,
 }
 
 impl PyValue for Frame {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.frame_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(self.code.as_object());
+        if let Ok(stack) = self.stack.try_borrow() {
+            stack.iter().for_each(&mut *visit);
+        }
+        if let Ok(blocks) = self.blocks.try_borrow() {
+            for block in blocks.iter() {
+                match &block.typ {
+                    BlockType::FinallyHandler {
+                        reason: Some(UnwindReason::Returning { value }),
+                    } => visit(value),
+                    BlockType::FinallyHandler {
+                        reason: Some(UnwindReason::Raising { exception }),
+                    } => visit(exception),
+                    _ => {}
+                }
+            }
+        }
+        self.scope.traverse(visit);
+        if let Ok(trace) = self.trace.try_borrow() {
+            trace.iter().for_each(&mut *visit);
+        }
+        if let Ok(snapshot) = self.locals_snapshot.try_borrow() {
+            if let Some((locals, names)) = &*snapshot {
+                visit(locals.as_object());
+                names.iter().for_each(visit);
+            }
+        }
+    }
+
+    fn clear_references(&self) {
+        if let Ok(mut stack) = self.stack.try_borrow_mut() {
+            stack.clear();
+        }
+        if let Ok(mut blocks) = self.blocks.try_borrow_mut() {
+            blocks.clear();
+        }
+        if let Ok(mut trace) = self.trace.try_borrow_mut() {
+            trace.take();
+        }
+        if let Ok(mut snapshot) = self.locals_snapshot.try_borrow_mut() {
+            snapshot.take();
+        }
+    }
 }
 
 // Running a frame can result in one of the below:
@@ -156,16 +224,41 @@ impl Frame {
             // globals: locals.clone(),
             scope,
             lasti: Cell::new(0),
+            trace: RefCell::new(None),
+            trace_lines: Cell::new(true),
+            trace_opcodes: Cell::new(false),
+            current_line: Cell::new(None),
+            line_range: Cell::new(None),
+            locals_snapshot: RefCell::new(None),
         }
     }
 
     // #[cfg_attr(feature = "flame-it", flame("Frame"))]
     pub fn run(&self, vm: &VirtualMachine) -> PyResult<ExecutionResult> {
         flame_guard!(format!("Frame::run({})", self.code.obj_name));
+        vm.trace_event(TraceEvent::Call, vm.get_none())?;
+        let result = self.run_instructions(vm);
+        let return_value = match result {
+            Ok(ExecutionResult::Return(ref value)) | Ok(ExecutionResult::Yield(ref value)) => {
+                value.clone()
+            }
+            Err(_) => vm.get_none(),
+        };
+        vm.trace_event(TraceEvent::Return, return_value)?;
+        result
+    }
+
+    fn run_instructions(&self, vm: &VirtualMachine) -> PyResult<ExecutionResult> {
         // Execute until return or exception:
         loop {
-            let lineno = self.get_lineno();
-            let result = self.execute_instruction(vm);
+            let lasti = self.lasti.get();
+            // Nothing but this check is added to each instruction when the frame isn't traced.
+            let result = if self.trace.borrow().is_some() {
+                self.trace_instruction(vm, lasti)
+                    .and_then(|()| self.execute_instruction(vm))
+            } else {
+                self.execute_instruction(vm)
+            };
             match result {
                 Ok(None) => {}
                 Ok(Some(value)) => {
@@ -181,29 +274,37 @@ impl Frame {
                         &vm.ctx.exceptions.base_exception_type
                     ));
 
-                    let traceback = vm
-                        .get_attribute(exception.clone(), "__traceback__")
-                        .unwrap();
-
-                    let next = if vm.is_none(&traceback) {
-                        None
-                    } else {
-                        let traceback: PyTracebackRef = traceback
-                            .downcast()
-                            .expect("next must be a traceback object");
-                        Some(traceback)
+                    // An exception whose __init__ doesn't call the base one has no
+                    // __traceback__ yet.
+                    let next = match vm.get_attribute(exception.clone(), "__traceback__") {
+                        Ok(traceback) if !vm.is_none(&traceback) => {
+                            let traceback: PyTracebackRef = traceback
+                                .downcast()
+                                .expect("next must be a traceback object");
+                            Some(traceback)
+                        }
+                        _ => None,
                     };
 
-                    let new_traceback = PyTraceback::new(
-                        next,
-                        self.clone().into_ref(vm),
-                        self.lasti.get(),
-                        lineno.row(),
-                    );
+                    // A new exception is chained to the one being handled, if any.
+                    if next.is_none() {
+                        let _ = exceptions::chain_to_current_exception(vm, &exception);
+                    }
+
+                    let lineno = self.line_of(lasti);
+                    // The instruction that raised, like f_lasti, for dis.distb to point at
+                    let new_traceback =
+                        PyTraceback::new(next, self.clone().into_ref(vm), lasti, lineno);
                     vm.set_attr(&exception, "__traceback__", new_traceback.into_ref(vm))
                         .unwrap();
                     vm_trace!("Adding to traceback: {:?} {:?}", new_traceback, lineno);
 
+                    // An exception raised by the trace function replaces the original one
+                    let exception = match self.trace_exception(vm, &exception) {
+                        Ok(()) => exception,
+                        Err(trace_exception) => trace_exception,
+                    };
+
                     match self.unwind_blocks(vm, UnwindReason::Raising { exception }) {
                         Ok(None) => {}
                         Ok(Some(result)) => {
@@ -223,32 +324,240 @@ impl Frame {
     pub(crate) fn gen_throw(
         &self,
         vm: &VirtualMachine,
-        exc_type: PyClassRef,
+        exc_type: PyObjectRef,
         exc_val: PyObjectRef,
         exc_tb: PyObjectRef,
     ) -> PyResult<ExecutionResult> {
         if let bytecode::Instruction::YieldFrom = self.code.instructions[self.lasti.get()] {
+            // Like PEP 380 specifies, `GeneratorExit` closes the subiterator and is then raised
+            // here, and anything else is thrown into the subiterator if it has a `throw`.
             let coro = self.last_value();
-            vm.call_method(
-                &coro,
-                "throw",
-                vec![exc_type.into_object(), exc_val, exc_tb],
-            )
-            .or_else(|err| {
-                self.pop_value();
-                self.lasti.set(self.lasti.get() + 1);
-                let val = objiter::stop_iter_value(vm, &err)?;
-                self._send(coro, val, vm)
-            })
-            .map(ExecutionResult::Yield)
+            let is_exit = match PyClassRef::try_from_object(vm, exc_type.clone()) {
+                Ok(cls) => objtype::issubclass(&cls, &vm.ctx.exceptions.generator_exit),
+                Err(_) => objtype::isinstance(&exc_type, &vm.ctx.exceptions.generator_exit),
+            };
+            let delegated = if is_exit {
+                match vm.get_method(coro.clone(), "close") {
+                    Some(close) => close.and_then(|close| vm.invoke(&close, vec![])).err(),
+                    None => None,
+                }
+            } else if let Some(throw) = vm.get_method(coro.clone(), "throw") {
+                match throw.and_then(|throw| vm.invoke(&throw, vec![exc_type, exc_val, exc_tb])) {
+                    Ok(value) => return Ok(ExecutionResult::Yield(value)),
+                    Err(err) => {
+                        // The subiterator is done with, continue after the `yield from`:
+                        self.pop_value();
+                        self.lasti.set(self.lasti.get() + 1);
+                        if objtype::isinstance(&err, &vm.ctx.exceptions.stop_iteration) {
+                            let value = objiter::stop_iter_value(vm, &err)?;
+                            self.push_value(value);
+                            return self.run(vm);
+                        }
+                        return self.gen_raise(vm, err);
+                    }
+                }
+            } else {
+                None
+            };
+            self.pop_value();
+            self.lasti.set(self.lasti.get() + 1);
+            match delegated {
+                Some(err) => self.gen_raise(vm, err),
+                None => {
+                    let exception = exceptions::normalize(exc_type, exc_val, vm)?;
+                    self.gen_raise(vm, exception)
+                }
+            }
         } else {
-            let exception = vm.new_exception_obj(exc_type, vec![exc_val])?;
-            match self.unwind_blocks(vm, UnwindReason::Raising { exception }) {
-                Ok(None) => self.run(vm),
-                Ok(Some(result)) => Ok(result),
-                Err(exception) => Err(exception),
+            let exception = exceptions::normalize(exc_type, exc_val, vm)?;
+            self.gen_raise(vm, exception)
+        }
+    }
+
+    /// Raise `exception` at the current position of a suspended generator, resuming it if the
+    /// exception is handled.
+    fn gen_raise(&self, vm: &VirtualMachine, exception: PyObjectRef) -> PyResult<ExecutionResult> {
+        match self.unwind_blocks(vm, UnwindReason::Raising { exception }) {
+            Ok(None) => self.run(vm),
+            Ok(Some(result)) => Ok(result),
+            Err(exception) => Err(exception),
+        }
+    }
+
+    /// Send the events that come before the instruction at `lasti` to the trace function: a
+    /// `line` event if it starts a line, and an `opcode` event if `f_trace_opcodes` is set.
+    fn trace_instruction(&self, vm: &VirtualMachine, lasti: usize) -> PyResult<()> {
+        self.trace_line(vm, lasti)?;
+        if self.trace_opcodes.get() && self.trace.borrow().is_some() {
+            vm.trace_event(TraceEvent::Opcode, vm.get_none())?;
+        }
+        Ok(())
+    }
+
+    /// Send a `line` event to the trace function before the instruction at `lasti` if it's the
+    /// first one of a line, or if it was jumped back to. Like in CPython, jumping forwards into
+    /// the middle of a line, like at the end of a loop, doesn't start it again. The line is
+    /// kept track of even when `f_trace_lines` is unset, for `f_lineno`.
+    fn trace_line(&self, vm: &VirtualMachine, lasti: usize) -> PyResult<()> {
+        // Mostly the next instruction is on the same line, so the range is looked up only once
+        // for all of them.
+        let range = match self.line_range.get() {
+            Some(range) if range.contains(lasti) => range,
+            _ => {
+                let range = self.code.line_table.range(lasti);
+                self.line_range.set(range);
+                range.expect("instruction outside of the line table")
+            }
+        };
+        let line = range.line.unwrap_or(self.code.first_line_number);
+        let previous = self.current_line.get();
+        let starts_line = lasti == range.start;
+        let jumped_back = match previous {
+            Some((_, previous_lasti)) => lasti < previous_lasti,
+            None => false,
+        };
+        if starts_line || jumped_back {
+            self.current_line.set(Some((line, lasti)));
+            if self.trace_lines.get() {
+                vm.trace_event(TraceEvent::Line, vm.get_none())?;
+            }
+        } else {
+            let event_line = previous.map_or(line, |(event_line, _)| event_line);
+            self.current_line.set(Some((event_line, lasti)));
+        }
+        Ok(())
+    }
+
+    fn trace_exception(&self, vm: &VirtualMachine, exception: &PyObjectRef) -> PyResult<()> {
+        if self.trace.borrow().is_none() {
+            return Ok(());
+        }
+        let exc_info = vm.ctx.new_tuple(vec![
+            exception.class().into_object(),
+            exception.clone(),
+            vm.get_attribute(exception.clone(), "__traceback__")?,
+        ]);
+        vm.trace_event(TraceEvent::Exception, exc_info)
+    }
+
+    /// The line being executed, or the first line of the code before it started running. While
+    /// the frame is traced, this is the line of the last `line` event, as in CPython.
+    pub fn current_lineno(&self) -> usize {
+        // This is called from faulthandler's signal handlers, which mustn't panic.
+        let traced = self
+            .trace
+            .try_borrow()
+            .map_or(false, |trace| trace.is_some());
+        match (traced, self.current_line.get(), self.lasti.get()) {
+            (true, Some((line, _)), _) => line,
+            (_, _, 0) => self.code.first_line_number,
+            // `lasti` is past the instruction being executed.
+            (_, _, lasti) => self.line_of(lasti - 1),
+        }
+    }
+
+    /// The line of the instruction at `index`. The few instructions that don't have a line of
+    /// their own are counted to the first line of the code.
+    fn line_of(&self, index: usize) -> usize {
+        self.code
+            .line_table
+            .line(index)
+            .unwrap_or(self.code.first_line_number)
+    }
+
+    /// The variables of the frame, as `frame.f_locals`. In a function this is a snapshot of them
+    /// that also has the variables it uses from enclosing functions, so like in CPython,
+    /// changing it doesn't change the variables. It's the same dict every time, brought up to
+    /// date with the variables, and keeps anything else that is stored in it.
+    pub fn locals(&self, vm: &VirtualMachine) -> PyResult<PyDictRef> {
+        let locals = self.scope.get_locals();
+        if !self.code.flags.contains(bytecode::CodeFlags::NEW_LOCALS)
+            || locals.is(&self.scope.globals)
+        {
+            return Ok(locals);
+        }
+
+        let mut snapshot = self.locals_snapshot.borrow_mut();
+        let (dict, names) = snapshot.get_or_insert_with(|| (vm.ctx.new_dict(), vec![]));
+        // Variables that were deleted since the last time are gone.
+        for name in names.drain(..) {
+            if dict.contains_key(name.clone(), vm) {
+                dict.del_item(&name, vm)?;
             }
         }
+        for (name, value) in &locals {
+            dict.set_item(&name, value, vm)?;
+            names.push(name);
+        }
+        for name in self.free_var_names() {
+            if locals.contains_key(name, vm) {
+                continue;
+            }
+            let name = vm.ctx.intern_string(name);
+            if let Some(value) = self.scope.load_cell(vm, &name) {
+                let name = name.into_object();
+                dict.set_item(&name, value, vm)?;
+                names.push(name);
+            }
+        }
+        Ok(dict.clone())
+    }
+
+    /// The names the code uses from enclosing scopes: the ones it loads but never binds itself.
+    fn free_var_names(&self) -> Vec<&str> {
+        let names = &self.code.code.names;
+        let mut loaded = vec![];
+        let mut bound = HashSet::new();
+        for instruction in self.code.instructions.iter() {
+            match instruction {
+                bytecode::Instruction::LoadName { idx, scope }
+                | bytecode::Instruction::StoreName { idx, scope }
+                    if *scope == bytecode::NameScope::NonLocal =>
+                {
+                    loaded.push(names[*idx].as_str())
+                }
+                bytecode::Instruction::LoadName {
+                    idx,
+                    scope: bytecode::NameScope::Free,
+                } => loaded.push(names[*idx].as_str()),
+                bytecode::Instruction::StoreName {
+                    idx,
+                    scope: bytecode::NameScope::Free,
+                }
+                | bytecode::Instruction::StoreName {
+                    idx,
+                    scope: bytecode::NameScope::Local,
+                }
+                | bytecode::Instruction::DeleteName { idx } => {
+                    bound.insert(names[*idx].as_str());
+                }
+                _ => {}
+            }
+        }
+        loaded.retain(|name| !bound.contains(name));
+        loaded.into_iter().unique().collect()
+    }
+
+    /// The iterator a `yield from` or `await` this frame is suspended in delegates to.
+    pub(crate) fn yield_from_target(&self) -> Option<PyObjectRef> {
+        match self.code.instructions.get(self.lasti.get()) {
+            Some(bytecode::Instruction::YieldFrom) => self.stack.borrow().last().cloned(),
+            _ => None,
+        }
+    }
+
+    /// The inline cache of the instruction being executed, if it has one and they are enabled.
+    fn inline_cache(&self, vm: &VirtualMachine) -> Option<&RefCell<InlineCache>> {
+        if vm.settings.no_inline_caches {
+            return None;
+        }
+        let slot = *self.code.cache_slots.get(self.lasti.get() - 1)?;
+        self.code.caches.get(slot as usize)
+    }
+
+    /// The interned name of the code that `idx` refers to, see `CodeObject::names`.
+    fn interned_name(&self, idx: bytecode::NameIdx) -> &PyStringRef {
+        &self.code.names.names[idx]
     }
 
     pub fn fetch_instruction(&self) -> &bytecode::Instruction {
@@ -260,6 +569,8 @@ impl Frame {
     /// Execute a single instruction.
     fn execute_instruction(&self, vm: &VirtualMachine) -> FrameResult {
         vm.check_signals()?;
+        vm.gil.switch_if_requested();
+        gc::collect_if_pending(vm);
 
         let instruction = self.fetch_instruction();
 
@@ -279,27 +590,28 @@ impl Frame {
         }
 
         match instruction {
-            bytecode::Instruction::LoadConst { ref value } => {
-                let obj = vm.ctx.unwrap_constant(value);
-                self.push_value(obj);
+            bytecode::Instruction::LoadConst { idx } => {
+                self.push_value(self.code.constants[*idx].clone());
                 Ok(None)
             }
             bytecode::Instruction::Import {
-                ref name,
-                ref symbols,
+                ref name_idx,
+                ref symbols_idx,
                 ref level,
-            } => self.import(vm, name, symbols, *level),
+            } => self.import(vm, name_idx, symbols_idx, *level),
             bytecode::Instruction::ImportStar => self.import_star(vm),
-            bytecode::Instruction::ImportFrom { ref name } => self.import_from(vm, name),
-            bytecode::Instruction::LoadName {
-                ref name,
-                ref scope,
-            } => self.load_name(vm, name, scope),
-            bytecode::Instruction::StoreName {
-                ref name,
-                ref scope,
-            } => self.store_name(vm, name, scope),
-            bytecode::Instruction::DeleteName { ref name } => self.delete_name(vm, name),
+            bytecode::Instruction::ImportFrom { idx } => {
+                self.import_from(vm, self.interned_name(*idx))
+            }
+            bytecode::Instruction::LoadName { idx, ref scope } => {
+                self.load_name(vm, self.interned_name(*idx), scope)
+            }
+            bytecode::Instruction::StoreName { idx, ref scope } => {
+                self.store_name(vm, self.interned_name(*idx), scope)
+            }
+            bytecode::Instruction::DeleteName { idx } => {
+                self.delete_name(vm, self.interned_name(*idx))
+            }
             bytecode::Instruction::Subscript => self.execute_subscript(vm),
             bytecode::Instruction::StoreSubscript => self.execute_store_subscript(vm),
             bytecode::Instruction::DeleteSubscript => self.execute_delete_subscript(vm),
@@ -341,7 +653,27 @@ impl Frame {
                 self.push_value(py_obj);
                 Ok(None)
             }
-            bytecode::Instruction::BuildTuple { size, unpack } => {
+            bytecode::Instruction::BuildTuple {
+                size,
+                unpack,
+                for_call,
+            } => {
+                if *for_call {
+                    // The `*` arguments of a call, below which the function is
+                    let func = self.nth_value(*size);
+                    for i in 0..*size {
+                        let iterable = self.nth_value(i);
+                        if !objtype::class_has_attr(&iterable.class(), "__iter__")
+                            && !objtype::class_has_attr(&iterable.class(), "__getitem__")
+                        {
+                            return Err(vm.new_type_error(format!(
+                                "{}() argument after * must be an iterable, not {}",
+                                func_name(vm, &func),
+                                iterable.class().name
+                            )));
+                        }
+                    }
+                }
                 let elements = self.get_elements(vm, *size, *unpack)?;
                 let list_obj = vm.ctx.new_tuple(elements);
                 self.push_value(list_obj);
@@ -375,9 +707,13 @@ impl Frame {
             bytecode::Instruction::BinaryOperation { ref op, inplace } => {
                 self.execute_binop(vm, op, *inplace)
             }
-            bytecode::Instruction::LoadAttr { ref name } => self.load_attr(vm, name),
-            bytecode::Instruction::StoreAttr { ref name } => self.store_attr(vm, name),
-            bytecode::Instruction::DeleteAttr { ref name } => self.delete_attr(vm, name),
+            bytecode::Instruction::LoadAttr { idx } => self.load_attr(vm, self.interned_name(*idx)),
+            bytecode::Instruction::StoreAttr { idx } => {
+                self.store_attr(vm, self.interned_name(*idx))
+            }
+            bytecode::Instruction::DeleteAttr { idx } => {
+                self.delete_attr(vm, self.interned_name(*idx))
+            }
             bytecode::Instruction::UnaryOperation { ref op } => self.execute_unop(vm, op),
             bytecode::Instruction::CompareOperation { ref op } => self.execute_compare(vm, op),
             bytecode::Instruction::ReturnValue => {
@@ -389,6 +725,13 @@ impl Frame {
                 Ok(Some(ExecutionResult::Yield(value)))
             }
             bytecode::Instruction::YieldFrom => self.execute_yield_from(vm),
+            bytecode::Instruction::SetupAnnotations => {
+                let locals = self.scope.get_locals();
+                if !locals.contains_key("__annotations__", vm) {
+                    locals.set_item("__annotations__", vm.ctx.new_dict().into_object(), vm)?;
+                }
+                Ok(None)
+            }
             bytecode::Instruction::SetupLoop { start, end } => {
                 self.push_block(BlockType::Loop {
                     start: *start,
@@ -495,6 +838,22 @@ impl Frame {
                 self.pop_block();
                 Ok(None)
             }
+            bytecode::Instruction::GetYieldFromIter => {
+                let iterable = self.pop_value();
+                let iter = if iterable.payload_is::<PyCoroutine>() {
+                    if !self.code.flags.contains(bytecode::CodeFlags::IS_COROUTINE) {
+                        return Err(vm.new_type_error(
+                            "cannot 'yield from' a coroutine object in a non-coroutine generator"
+                                .to_string(),
+                        ));
+                    }
+                    iterable
+                } else {
+                    objiter::get_iter(vm, &iterable)?
+                };
+                self.push_value(iter);
+                Ok(None)
+            }
             bytecode::Instruction::GetIter => {
                 let iterated_obj = self.pop_value();
                 let iter_obj = objiter::get_iter(vm, &iterated_obj)?;
@@ -588,13 +947,10 @@ impl Frame {
             bytecode::Instruction::Continue => self.unwind_blocks(vm, UnwindReason::Continue),
             bytecode::Instruction::PrintExpr => {
                 let expr = self.pop_value();
-                if !expr.is(&vm.get_none()) {
-                    let repr = vm.to_repr(&expr)?;
-                    // TODO: implement sys.displayhook
-                    if let Ok(ref print) = vm.get_attribute(vm.builtins.clone(), "print") {
-                        vm.invoke(print, vec![repr.into_object()])?;
-                    }
-                }
+                let displayhook = vm
+                    .get_attribute(vm.sys_module.clone(), "displayhook")
+                    .map_err(|_| vm.new_runtime_error("lost sys.displayhook".to_owned()))?;
+                vm.invoke(&displayhook, vec![expr])?;
                 Ok(None)
             }
             bytecode::Instruction::LoadBuildClass => {
@@ -603,9 +959,16 @@ impl Frame {
             }
             bytecode::Instruction::UnpackSequence { size } => {
                 let value = self.pop_value();
-                let elements = vm.extract_elements(&value)?;
-                if elements.len() != *size {
-                    Err(vm.new_value_error("Wrong number of values to unpack".to_string()))
+                let elements = self.extract_unpacked_elements(vm, &value, Some(size + 1))?;
+                if elements.len() < *size {
+                    Err(vm.new_value_error(format!(
+                        "not enough values to unpack (expected {}, got {})",
+                        size,
+                        elements.len()
+                    )))
+                } else if elements.len() > *size {
+                    Err(vm
+                        .new_value_error(format!("too many values to unpack (expected {})", size)))
                 } else {
                     for element in elements.into_iter().rev() {
                         self.push_value(element);
@@ -616,8 +979,9 @@ impl Frame {
             bytecode::Instruction::UnpackEx { before, after } => {
                 self.execute_unpack_ex(vm, *before, *after)
             }
-            bytecode::Instruction::FormatValue { conversion, spec } => {
+            bytecode::Instruction::FormatValue { conversion } => {
                 use bytecode::ConversionFlag::*;
+                let spec = self.pop_value();
                 let value = match conversion {
                     Some(Str) => vm.to_str(&self.pop_value())?.into_object(),
                     Some(Repr) => vm.to_repr(&self.pop_value())?.into_object(),
@@ -625,7 +989,6 @@ impl Frame {
                     None => self.pop_value(),
                 };
 
-                let spec = vm.new_str(spec.clone());
                 let formatted = vm.call_method(&value, "__format__", vec![spec])?;
                 self.push_value(formatted);
                 Ok(None)
@@ -671,39 +1034,96 @@ impl Frame {
     fn import(
         &self,
         vm: &VirtualMachine,
-        module: &Option<String>,
-        symbols: &[String],
+        module: &Option<bytecode::NameIdx>,
+        symbols: &[bytecode::NameIdx],
         level: usize,
     ) -> FrameResult {
-        let module = module.clone().unwrap_or_default();
-        let module = vm.import(&module, symbols, level)?;
+        let names = &self.code.code.names;
+        let module = module.map_or("", |idx| names[idx].as_str());
+        let symbols: Vec<String> = symbols.iter().map(|&idx| names[idx].clone()).collect();
+        let module = vm.import(module, &symbols, level)?;
 
         self.push_value(module);
         Ok(None)
     }
 
     #[cfg_attr(feature = "flame-it", flame("Frame"))]
-    fn import_from(&self, vm: &VirtualMachine, name: &str) -> FrameResult {
+    fn import_from(&self, vm: &VirtualMachine, name: &PyStringRef) -> FrameResult {
         let module = self.last_value();
         // Load attribute, and transform any error into import error.
-        let obj = vm
-            .get_attribute(module, name)
-            .map_err(|_| vm.new_import_error(format!("cannot import name '{}'", name)))?;
+        let obj = match vm.get_attribute(module.clone(), name.clone()) {
+            Ok(obj) => obj,
+            Err(_) => self.import_from_submodule(vm, &module, name.as_str())?,
+        };
         self.push_value(obj);
         Ok(None)
     }
 
+    /// Fall back to `sys.modules["<module>.<name>"]`, which may be set before the submodule is
+    /// bound on its parent package (e.g. during circular imports).
+    fn import_from_submodule(
+        &self,
+        vm: &VirtualMachine,
+        module: &PyObjectRef,
+        name: &str,
+    ) -> PyResult {
+        let module_name = vm
+            .get_attribute(module.clone(), "__name__")
+            .ok()
+            .filter(|module_name| objtype::isinstance(module_name, &vm.ctx.str_type()));
+        if let Some(module_name) = &module_name {
+            let full_name = format!("{}.{}", objstr::get_value(module_name), name);
+            let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules")?;
+            if let Ok(submodule) = sys_modules.get_item(&full_name, vm) {
+                return Ok(submodule);
+            }
+        }
+
+        let module_path = vm
+            .get_attribute(module.clone(), "__file__")
+            .ok()
+            .filter(|path| objtype::isinstance(path, &vm.ctx.str_type()));
+        let module_repr = match &module_name {
+            Some(module_name) => vm.to_repr(module_name)?.as_str().to_owned(),
+            None => "'<unknown module name>'".to_owned(),
+        };
+        let msg = match &module_path {
+            Some(path) if objmodule::is_initializing(module, vm) => format!(
+                "cannot import name '{}' from partially initialized module {} \
+                 (most likely due to a circular import) ({})",
+                name,
+                module_repr,
+                objstr::get_value(path)
+            ),
+            Some(path) => format!(
+                "cannot import name '{}' from {} ({})",
+                name,
+                module_repr,
+                objstr::get_value(path)
+            ),
+            None => format!(
+                "cannot import name '{}' from {} (unknown location)",
+                name, module_repr
+            ),
+        };
+        Err(vm.new_import_error_with_name(
+            vm.ctx.exceptions.import_error.clone(),
+            msg,
+            module_name.unwrap_or_else(|| vm.get_none()),
+            module_path.unwrap_or_else(|| vm.get_none()),
+        ))
+    }
+
     #[cfg_attr(feature = "flame-it", flame("Frame"))]
     fn import_star(&self, vm: &VirtualMachine) -> FrameResult {
         let module = self.pop_value();
 
         // Grab all the names from the module and put them in the context
-        if let Some(dict) = &module.dict {
+        if let Some(dict) = module.dict() {
             for (k, v) in dict {
                 let k = vm.to_str(&k)?;
-                let k = k.as_str();
-                if !k.starts_with('_') {
-                    self.scope.store_name(&vm, k, v);
+                if !k.as_str().starts_with('_') {
+                    self.scope.store_name(&vm, &vm.ctx.intern_string_ref(k), v);
                 }
             }
         }
@@ -774,7 +1194,7 @@ impl Frame {
     fn store_name(
         &self,
         vm: &VirtualMachine,
-        name: &str,
+        name: &PyStringRef,
         name_scope: &bytecode::NameScope,
     ) -> FrameResult {
         let obj = self.pop_value();
@@ -795,7 +1215,7 @@ impl Frame {
         Ok(None)
     }
 
-    fn delete_name(&self, vm: &VirtualMachine, name: &str) -> FrameResult {
+    fn delete_name(&self, vm: &VirtualMachine, name: &PyStringRef) -> FrameResult {
         match self.scope.delete_name(vm, name) {
             Ok(_) => Ok(None),
             Err(_) => Err(vm.new_name_error(format!("name '{}' is not defined", name))),
@@ -806,11 +1226,14 @@ impl Frame {
     fn load_name(
         &self,
         vm: &VirtualMachine,
-        name: &str,
+        name: &PyStringRef,
         name_scope: &bytecode::NameScope,
     ) -> FrameResult {
         let optional_value = match name_scope {
-            bytecode::NameScope::Global => self.scope.load_global(vm, name),
+            bytecode::NameScope::Global => match self.inline_cache(vm) {
+                Some(cache) => inline_cache::load_global(vm, cache, &self.scope, name),
+                None => self.scope.load_global(vm, name),
+            },
             bytecode::NameScope::NonLocal => self.scope.load_cell(vm, name),
             bytecode::NameScope::Local => self.scope.load_local(&vm, name),
             bytecode::NameScope::Free => self.scope.load_name(&vm, name),
@@ -818,6 +1241,12 @@ impl Frame {
 
         let value = match optional_value {
             Some(value) => value,
+            None if *name_scope == bytecode::NameScope::Local => {
+                return Err(vm.new_exception(
+                    vm.ctx.exceptions.unbound_local_error.clone(),
+                    format!("local variable '{}' referenced before assignment", name),
+                ));
+            }
             None => {
                 return Err(vm.new_name_error(format!("name '{}' is not defined", name)));
             }
@@ -883,27 +1312,24 @@ impl Frame {
         for_call: bool,
     ) -> FrameResult {
         let map_obj = vm.ctx.new_dict();
-        if unpack {
+        if for_call {
+            // The `**` arguments of a call, below which are the function and its positional
+            // arguments
+            let func = self.nth_value(size + 1);
+            for obj in self.pop_multiple(size) {
+                merge_call_kwargs(vm, &func, &map_obj, obj)?;
+            }
+        } else if unpack {
             for obj in self.pop_multiple(size) {
                 // Take all key-value pairs from the dict:
                 let dict: PyDictRef = obj.downcast().expect("Need a dictionary to build a map.");
                 for (key, value) in dict {
-                    if for_call {
-                        if map_obj.contains_key(&key, vm) {
-                            let key_repr = vm.to_repr(&key)?;
-                            let msg = format!(
-                                "got multiple values for keyword argument {}",
-                                key_repr.as_str()
-                            );
-                            return Err(vm.new_type_error(msg));
-                        }
-                    }
-                    map_obj.set_item(&key, value, vm).unwrap();
+                    map_obj.set_item(&key, value, vm)?;
                 }
             }
         } else {
             for (key, value) in self.pop_multiple(2 * size).into_iter().tuples() {
-                map_obj.set_item(&key, value, vm).unwrap();
+                map_obj.set_item(&key, value, vm)?;
             }
         }
 
@@ -954,12 +1380,27 @@ impl Frame {
             }
             bytecode::CallType::Ex(has_kwargs) => {
                 let kwargs = if *has_kwargs {
-                    let kw_dict: PyDictRef =
-                        self.pop_value().downcast().expect("Kwargs must be a dict.");
+                    let kwargs = self.pop_value();
+                    let func = self.nth_value(1);
+                    // A single `**` argument isn't merged into a dict by BuildMap
+                    let kw_dict = match kwargs.downcast::<PyDict>() {
+                        Ok(kw_dict) => kw_dict,
+                        Err(mapping) => {
+                            let kw_dict = vm.ctx.new_dict();
+                            merge_call_kwargs(vm, &func, &kw_dict, mapping)?;
+                            kw_dict
+                        }
+                    };
                     kw_dict
                         .into_iter()
-                        .map(|elem| (objstr::get_value(&elem.0), elem.1))
-                        .collect()
+                        .map(|(key, value)| match key.payload::<PyString>() {
+                            Some(key) => Ok((key.as_str().to_owned(), value)),
+                            None => Err(vm.new_type_error(format!(
+                                "{}() keywords must be strings",
+                                func_name(vm, &func)
+                            ))),
+                        })
+                        .collect::<PyResult<_>>()?
                 } else {
                     IndexMap::new()
                 };
@@ -971,15 +1412,30 @@ impl Frame {
 
         // Call function:
         let func_ref = self.pop_value();
-        let value = vm.invoke(&func_ref, args)?;
+        let value = match builtin_callee(&func_ref) {
+            // Like in CPython, the profiler sees the builtins the code calls, but not the ones
+            // the VM calls for it, like the methods of operators
+            Some(builtin) => {
+                vm.trace_event(TraceEvent::CCall, builtin.clone())?;
+                let value = vm.invoke(&func_ref, args);
+                let event = if value.is_ok() {
+                    TraceEvent::CReturn
+                } else {
+                    TraceEvent::CException
+                };
+                vm.trace_event(event, builtin)?;
+                value?
+            }
+            None => vm.invoke(&func_ref, args)?,
+        };
         self.push_value(value);
         Ok(None)
     }
 
     fn execute_raise(&self, vm: &VirtualMachine, argc: usize) -> FrameResult {
         let cause = match argc {
-            2 => self.get_exception(vm, true)?,
-            _ => vm.get_none(),
+            2 => Some(self.get_exception(vm, true)?),
+            _ => None,
         };
         let exception = match argc {
             0 => match vm.current_exception() {
@@ -995,19 +1451,15 @@ impl Frame {
             3 => panic!("Not implemented!"),
             _ => panic!("Invalid parameter for RAISE_VARARGS, must be between 0 to 3"),
         };
-        let context = match argc {
-            0 => vm.get_none(), // We have already got the exception,
-            _ => match vm.current_exception() {
-                Some(exc) => exc,
-                None => vm.get_none(),
-            },
-        };
-        info!(
-            "Exception raised: {:?} with cause: {:?} and context: {:?}",
-            exception, cause, context
-        );
-        vm.set_attr(&exception, vm.new_str("__cause__".to_string()), cause)?;
-        vm.set_attr(&exception, vm.new_str("__context__".to_string()), context)?;
+        info!("Exception raised: {:?} with cause: {:?}", exception, cause);
+        // A bare `raise` re-raises the handled exception as it is.
+        if argc > 0 {
+            exceptions::chain_to_current_exception(vm, &exception)?;
+        }
+        if let Some(cause) = cause {
+            vm.set_attr(&exception, "__cause__", cause)?;
+            vm.set_attr(&exception, "__suppress_context__", vm.new_bool(true))?;
+        }
         Err(exception)
     }
 
@@ -1049,11 +1501,11 @@ impl Frame {
 
     fn execute_unpack_ex(&self, vm: &VirtualMachine, before: usize, after: usize) -> FrameResult {
         let value = self.pop_value();
-        let elements = vm.extract_elements::<PyObjectRef>(&value)?;
+        let elements = self.extract_unpacked_elements(vm, &value, None)?;
         let min_expected = before + after;
         if elements.len() < min_expected {
             Err(vm.new_value_error(format!(
-                "Not enough values to unpack (expected at least {}, got {}",
+                "not enough values to unpack (expected at least {}, got {})",
                 min_expected,
                 elements.len()
             )))
@@ -1078,6 +1530,41 @@ impl Frame {
         }
     }
 
+    /// The elements of `value` to unpack. An iterator is only advanced `limit` times when there
+    /// is one: like in CPython, unpacking into `n` targets takes at most `n + 1` elements, so
+    /// that unpacking an endless iterator into too few targets fails instead of hanging.
+    fn extract_unpacked_elements(
+        &self,
+        vm: &VirtualMachine,
+        value: &PyObjectRef,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<PyObjectRef>> {
+        let cls = value.class();
+        if !objtype::class_has_attr(&cls, "__iter__")
+            && !objtype::class_has_attr(&cls, "__getitem__")
+        {
+            return Err(
+                vm.new_type_error(format!("cannot unpack non-iterable {} object", cls.name))
+            );
+        }
+        let is_sequence = objtype::isinstance(value, &vm.ctx.tuple_type())
+            || objtype::isinstance(value, &vm.ctx.list_type());
+        match limit {
+            Some(limit) if !is_sequence => {
+                let iter = objiter::get_iter(vm, value)?;
+                let mut elements = vec![];
+                while elements.len() < limit {
+                    match objiter::get_next_object(vm, &iter)? {
+                        Some(element) => elements.push(element),
+                        None => break,
+                    }
+                }
+                Ok(elements)
+            }
+            _ => vm.extract_elements(value),
+        }
+    }
+
     fn jump(&self, label: bytecode::Label) {
         let target_pc = self.code.label_map[&label];
         #[cfg(feature = "vm-tracing-logging")]
@@ -1221,10 +1708,14 @@ impl Frame {
     #[cfg_attr(feature = "flame-it", flame("Frame"))]
     fn execute_unop(&self, vm: &VirtualMachine, op: &bytecode::UnaryOperator) -> FrameResult {
         let a = self.pop_value();
+        let call_unary = |method_name: &str, op: &str| match vm.get_method(a.clone(), method_name) {
+            Some(method) => vm.invoke(&method?, vec![]),
+            None => Err(vm.new_unsupported_unary_error(&a, op)),
+        };
         let value = match *op {
-            bytecode::UnaryOperator::Minus => vm.call_method(&a, "__neg__", vec![])?,
-            bytecode::UnaryOperator::Plus => vm.call_method(&a, "__pos__", vec![])?,
-            bytecode::UnaryOperator::Invert => vm.call_method(&a, "__invert__", vec![])?,
+            bytecode::UnaryOperator::Minus => call_unary("__neg__", "-")?,
+            bytecode::UnaryOperator::Plus => call_unary("__pos__", "+")?,
+            bytecode::UnaryOperator::Invert => call_unary("__invert__", "~")?,
             bytecode::UnaryOperator::Not => {
                 let value = objbool::boolval(vm, a)?;
                 vm.ctx.new_bool(!value)
@@ -1312,29 +1803,36 @@ impl Frame {
         Ok(None)
     }
 
-    fn load_attr(&self, vm: &VirtualMachine, attr_name: &str) -> FrameResult {
+    fn load_attr(&self, vm: &VirtualMachine, attr_name: &PyStringRef) -> FrameResult {
         let parent = self.pop_value();
-        let obj = vm.get_attribute(parent, attr_name)?;
+        let obj = match self.inline_cache(vm) {
+            Some(cache) => inline_cache::load_attr(vm, cache, parent, attr_name)?,
+            None => vm.get_attribute(parent, attr_name.clone())?,
+        };
         self.push_value(obj);
         Ok(None)
     }
 
-    fn store_attr(&self, vm: &VirtualMachine, attr_name: &str) -> FrameResult {
+    fn store_attr(&self, vm: &VirtualMachine, attr_name: &PyStringRef) -> FrameResult {
         let parent = self.pop_value();
         let value = self.pop_value();
-        vm.set_attr(&parent, vm.new_str(attr_name.to_string()), value)?;
+        match self.inline_cache(vm) {
+            Some(cache) => inline_cache::store_attr(vm, cache, &parent, attr_name, value)?,
+            None => {
+                vm.set_attr(&parent, attr_name.clone(), value)?;
+            }
+        }
         Ok(None)
     }
 
-    fn delete_attr(&self, vm: &VirtualMachine, attr_name: &str) -> FrameResult {
+    fn delete_attr(&self, vm: &VirtualMachine, attr_name: &PyStringRef) -> FrameResult {
         let parent = self.pop_value();
-        let name = vm.ctx.new_str(attr_name.to_string());
-        vm.del_attr(&parent, name)?;
+        vm.del_attr(&parent, attr_name.clone().into_object())?;
         Ok(None)
     }
 
-    pub fn get_lineno(&self) -> bytecode::Location {
-        self.code.locations[self.lasti.get()].clone()
+    pub fn get_lineno(&self) -> usize {
+        self.line_of(self.lasti.get())
     }
 
     fn push_block(&self, typ: BlockType) {
@@ -1408,6 +1906,71 @@ impl Frame {
     }
 }
 
+/// The builtin function that calling `func` runs, if it's one or a method bound to one.
+fn builtin_callee(func: &PyObjectRef) -> Option<PyObjectRef> {
+    let function = match func.payload::<PyMethod>() {
+        Some(method) => &method.function,
+        None => func,
+    };
+    if function.payload_is::<PyBuiltinFunction>() {
+        Some(function.clone())
+    } else {
+        None
+    }
+}
+
+/// The name of a called function, for the messages of errors about its arguments.
+fn func_name(vm: &VirtualMachine, func: &PyObjectRef) -> String {
+    vm.get_attribute(func.clone(), "__name__")
+        .ok()
+        .and_then(|name| {
+            name.payload::<PyString>()
+                .map(|name| name.as_str().to_owned())
+        })
+        .unwrap_or_else(|| func.class().name.clone())
+}
+
+/// Add the items of `mapping`, a `**` argument of a call of `func`, to the keyword arguments in
+/// `kwargs`, none of which it may hold already.
+fn merge_call_kwargs(
+    vm: &VirtualMachine,
+    func: &PyObjectRef,
+    kwargs: &PyDictRef,
+    mapping: PyObjectRef,
+) -> PyResult<()> {
+    let items: Vec<(PyObjectRef, PyObjectRef)> = match mapping.downcast::<PyDict>() {
+        Ok(dict) => dict.into_iter().collect(),
+        Err(mapping) => {
+            if !objtype::class_has_attr(&mapping.class(), "keys") {
+                return Err(vm.new_type_error(format!(
+                    "{}() argument after ** must be a mapping, not {}",
+                    func_name(vm, func),
+                    mapping.class().name
+                )));
+            }
+            let keys = vm.call_method(&mapping, "keys", vec![])?;
+            vm.extract_elements(&keys)?
+                .into_iter()
+                .map(|key: PyObjectRef| {
+                    let value = mapping.get_item(&key, vm)?;
+                    Ok((key, value))
+                })
+                .collect::<PyResult<_>>()?
+        }
+    };
+    for (key, value) in items {
+        if kwargs.contains_key(&key, vm) {
+            return Err(vm.new_type_error(format!(
+                "{}() got multiple values for keyword argument {}",
+                func_name(vm, func),
+                vm.to_repr(&key)?.as_str()
+            )));
+        }
+        kwargs.set_item(&key, value, vm)?;
+    }
+    Ok(())
+}
+
 impl fmt::Debug for Frame {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let stack_str = self
@@ -1,5 +1,95 @@
-use crate::bytecode::FrozenModule;
+use crate::bytecode::{CodeObject, DecodeError, FrozenModule};
+use crate::pyobject::{PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// The error of registering frozen modules under names that are already taken, by other
+/// frozen modules or by modules that were already imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrozenModuleCollision {
+    /// The names that are already taken, sorted.
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for FrozenModuleCollision {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "frozen module names already taken: {}",
+            self.names.join(", ")
+        )
+    }
+}
+
+impl Error for FrozenModuleCollision {}
+
+/// The error of `VirtualMachine::run_frozen`.
+#[derive(Debug)]
+pub enum RunFrozenError {
+    /// There is no frozen module with this name.
+    NotFound(String),
+    /// The code of the module can't be deserialized.
+    InvalidBytecode(DecodeError),
+    /// The code raised this exception.
+    Exception(PyObjectRef),
+}
+
+impl fmt::Display for RunFrozenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunFrozenError::NotFound(name) => write!(f, "no frozen module named '{}'", name),
+            RunFrozenError::InvalidBytecode(err) => write!(f, "{}", err),
+            RunFrozenError::Exception(_) => write!(f, "the frozen code raised an exception"),
+        }
+    }
+}
+
+impl Error for RunFrozenError {}
+
+/// Add `modules` to `registry`. A module whose name is already registered, or `is_taken`, is
+/// an error, and then none of the modules are added.
+pub(crate) fn merge_modules(
+    registry: &mut HashMap<String, FrozenModule>,
+    modules: HashMap<String, FrozenModule>,
+    is_taken: impl Fn(&str) -> bool,
+) -> Result<(), FrozenModuleCollision> {
+    let mut names: Vec<String> = modules
+        .keys()
+        .filter(|name| registry.contains_key(*name) || is_taken(name))
+        .cloned()
+        .collect();
+    if !names.is_empty() {
+        names.sort();
+        return Err(FrozenModuleCollision { names });
+    }
+    registry.extend(modules);
+    Ok(())
+}
+
+/// The `__file__` of a frozen module: `<frozen {path}>`, where `path` is its
+/// `FrozenModule::source_path`, like `<frozen pkg/__init__.py>` for the package `pkg` frozen from
+/// a directory. Modules frozen from a source string have no file.
+pub(crate) fn module_file(module: &FrozenModule) -> Option<String> {
+    module
+        .source_path
+        .as_ref()
+        .map(|path| format!("<frozen {}>", path))
+}
+
+/// The code object of the frozen module `name`, with an `ImportError` for code that can't be
+/// deserialized, like bytecode frozen with another version of RustPython.
+pub(crate) fn module_code(
+    vm: &VirtualMachine,
+    name: &str,
+    module: &FrozenModule,
+) -> PyResult<CodeObject> {
+    module
+        .code
+        .try_decode()
+        .map_err(|err| vm.new_import_error(format!("can't load frozen module '{}': {}", name, err)))
+}
 
 pub fn get_module_inits() -> HashMap<String, FrozenModule> {
     let mut modules = HashMap::new();
@@ -24,9 +114,26 @@ pub fn get_module_inits() -> HashMap<String, FrozenModule> {
         module_name = "__reducelib",
     ));
 
+    // The standard library, so that it can be imported with no Lib directory around. The
+    // packages that are only of use with the sources at hand, or with a GUI, are left out, and
+    // docstrings are dropped to keep the binary smaller, like `-OO` does in CPython.
     #[cfg(feature = "freeze-stdlib")]
     {
-        modules.extend(py_compile_bytecode!(dir = "../Lib/"));
+        modules.extend(py_compile_bytecode!(
+            dir = "../Lib/",
+            exclude = "**/__pycache__",
+            exclude = "**/test",
+            exclude = "**/tests",
+            exclude = "**/test_*.py",
+            exclude = "ensurepip",
+            exclude = "idlelib",
+            exclude = "lib2to3",
+            exclude = "pydoc_data",
+            exclude = "tkinter",
+            exclude = "turtledemo",
+            optimize = 2,
+            cache = true,
+        ));
     }
 
     modules
@@ -0,0 +1,406 @@
+//! The cycle collector, which frees the objects that only reference cycles keep alive.
+//!
+//! Objects are reference counted, so an object is freed as soon as nothing references it, but
+//! objects that reference each other keep each other alive. The objects that can be part of
+//! such a cycle are tracked: those with a `__dict__`, and those whose payload sets
+//! `PyValue::HAVE_TRAVERSE`. Like in CPython, they are in three generations. Objects start in
+//! the youngest, and move to the next one when they survive a collection of theirs, so that the
+//! long-lived ones are looked at less often.
+//!
+//! A collection of a generation, along with the younger ones, works like CPython's. The
+//! references from the objects in them to each other (see `PyValue::traverse`) are subtracted
+//! from the reference counts of those objects. An object with references left is referenced
+//! from elsewhere, like an older generation, an untracked object or Rust code, so it is
+//! reachable, and so is everything it references. The rest are garbage: their `__del__` is
+//! called, and then the references they hold are dropped (see `PyValue::clear_references`),
+//! which breaks the cycles.
+//!
+//! Each thread has its own collector, with its own settings, for the objects it made. When a
+//! thread ends, the next collection of another thread of the interpreter takes its objects over.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::obj::objtype;
+use crate::pyobject::{IdProtocol, PyObject, PyObjectPayload, PyObjectRef, TypeProtocol};
+use crate::vm::VirtualMachine;
+
+pub const GENERATIONS: usize = 3;
+
+pub const DEBUG_STATS: u32 = 1;
+pub const DEBUG_COLLECTABLE: u32 = 2;
+pub const DEBUG_UNCOLLECTABLE: u32 = 4;
+pub const DEBUG_SAVEALL: u32 = 32;
+pub const DEBUG_LEAK: u32 = DEBUG_COLLECTABLE | DEBUG_UNCOLLECTABLE | DEBUG_SAVEALL;
+
+/// How many entries a generation has at least before the dead ones are removed from it.
+const PRUNE_MIN: usize = 1000;
+
+struct Tracked {
+    object: Weak<PyObject<dyn PyObjectPayload>>,
+    /// Whether the collector called the `__del__` of the object, which it does only once.
+    finalized: bool,
+}
+
+#[derive(Default)]
+struct Generation {
+    objects: Vec<Tracked>,
+    /// How many of `objects` were alive when the dead ones were last removed.
+    live: usize,
+}
+
+impl Generation {
+    /// The memory of a dead object is only freed along with the weak reference to it, so the
+    /// dead ones are removed whenever the generation has doubled since they last were.
+    fn push(&mut self, tracked: Tracked) {
+        if self.objects.len() >= 2 * self.live.max(PRUNE_MIN) {
+            self.objects
+                .retain(|tracked| tracked.object.strong_count() > 0);
+            self.live = self.objects.len();
+        }
+        self.objects.push(tracked);
+    }
+}
+
+struct State {
+    generations: [Generation; GENERATIONS],
+    /// For the youngest generation, how many objects were tracked since it was last collected,
+    /// and for the others, how many times the generation before was collected since they were.
+    counts: [usize; GENERATIONS],
+    thresholds: [usize; GENERATIONS],
+    enabled: bool,
+    debug: u32,
+    collecting: bool,
+    /// How many objects were in the oldest generation after it was last collected, and how
+    /// many were moved into it since. It is only collected once the latter is a quarter of the
+    /// former, so that collections of it don't take quadratic time as the program grows.
+    long_lived_total: usize,
+    long_lived_pending: usize,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            generations: Default::default(),
+            counts: [0; GENERATIONS],
+            thresholds: [700, 10, 10],
+            enabled: true,
+            debug: 0,
+            collecting: false,
+            long_lived_total: 0,
+            long_lived_pending: 0,
+        }
+    }
+}
+
+/// The objects of the threads that ended, see `release_thread`.
+#[derive(Default)]
+pub(crate) struct Orphans {
+    objects: RefCell<Vec<Tracked>>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State::default());
+}
+
+/// Set when the youngest generation of a thread passes its threshold, so that the frames can
+/// check for a collection to do as cheaply as for signals.
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Whether the collector tracks `obj`, which is decided when it is made.
+pub fn is_tracked(obj: &PyObjectRef) -> bool {
+    obj.dict().is_some() || obj.payload.have_traverse()
+}
+
+/// Start tracking a new object, if it can be part of a cycle.
+pub(crate) fn track(obj: &PyObjectRef) {
+    if !is_tracked(obj) {
+        return;
+    }
+    STATE.with(|state| {
+        let mut state = state.borrow_mut();
+        state.generations[0].push(Tracked {
+            object: Rc::downgrade(obj),
+            finalized: false,
+        });
+        state.counts[0] += 1;
+        if state.enabled && state.thresholds[0] > 0 && state.counts[0] > state.thresholds[0] {
+            PENDING.store(true, Ordering::Relaxed);
+        }
+    })
+}
+
+/// Hand the objects tracked by this thread over to the other threads of the interpreter, as it
+/// is about to end. This has to be done while it holds the global interpreter lock, as it
+/// couldn't drop the weak references to them once it doesn't.
+pub(crate) fn release_thread(vm: &VirtualMachine) {
+    let mut state = STATE.with(|state| state.replace(State::default()));
+    let mut orphans = vm.gc_orphans.objects.borrow_mut();
+    for generation in state.generations.iter_mut() {
+        orphans.extend(
+            generation
+                .objects
+                .drain(..)
+                .filter(|tracked| tracked.object.strong_count() > 0),
+        );
+    }
+}
+
+/// Call `visit` with each reference `obj` holds, if it is tracked.
+pub fn traverse(obj: &PyObjectRef, visit: &mut dyn FnMut(&PyObjectRef)) {
+    if !is_tracked(obj) {
+        return;
+    }
+    visit(obj.typ.as_object());
+    if let Some(dict) = obj.dict() {
+        visit(dict.as_object());
+    }
+    obj.payload.traverse(visit);
+}
+
+/// The live objects in `generation`, or in all of them.
+pub fn get_objects(generation: Option<usize>) -> Vec<PyObjectRef> {
+    STATE.with(|state| {
+        let state = state.borrow();
+        let generations = match generation {
+            Some(generation) => &state.generations[generation..=generation],
+            None => &state.generations[..],
+        };
+        generations
+            .iter()
+            .flat_map(|generation| &generation.objects)
+            .filter_map(|tracked| tracked.object.upgrade())
+            .collect()
+    })
+}
+
+pub fn is_enabled() -> bool {
+    STATE.with(|state| state.borrow().enabled)
+}
+
+pub fn set_enabled(enabled: bool) {
+    STATE.with(|state| state.borrow_mut().enabled = enabled)
+}
+
+pub fn get_debug() -> u32 {
+    STATE.with(|state| state.borrow().debug)
+}
+
+pub fn set_debug(flags: u32) {
+    STATE.with(|state| state.borrow_mut().debug = flags)
+}
+
+pub fn get_count() -> [usize; GENERATIONS] {
+    STATE.with(|state| state.borrow().counts)
+}
+
+pub fn get_threshold() -> [usize; GENERATIONS] {
+    STATE.with(|state| state.borrow().thresholds)
+}
+
+/// Set the thresholds, of which a threshold of 0 for the youngest generation disables the
+/// automatic collections.
+pub fn set_threshold(thresholds: [usize; GENERATIONS]) {
+    STATE.with(|state| state.borrow_mut().thresholds = thresholds)
+}
+
+/// Do the collection that the youngest generation passing its threshold calls for, if it did:
+/// of the oldest generation whose count passed its threshold.
+pub(crate) fn collect_if_pending(vm: &VirtualMachine) {
+    if !PENDING.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    let generation = STATE.with(|state| {
+        let state = state.borrow();
+        if !state.enabled || state.collecting || state.thresholds[0] == 0 {
+            return None;
+        }
+        (0..GENERATIONS).rev().find(|&generation| {
+            state.counts[generation] > state.thresholds[generation]
+                && (generation < GENERATIONS - 1
+                    || state.long_lived_pending > state.long_lived_total / 4)
+        })
+    });
+    if let Some(generation) = generation {
+        collect(vm, generation);
+    }
+}
+
+/// Collect `generation` and the younger ones, and return how many objects were garbage.
+pub fn collect(vm: &VirtualMachine, generation: usize) -> usize {
+    let (mut tracked, debug) = match STATE.with(|state| state.borrow_mut().start(generation)) {
+        Some(started) => started,
+        None => return 0,
+    };
+    tracked.append(&mut vm.gc_orphans.objects.borrow_mut());
+    let objects: Vec<_> = tracked
+        .into_iter()
+        .filter_map(|tracked| Some((tracked.object.upgrade()?, tracked.finalized)))
+        .collect();
+    let (mut survivors, mut garbage) = partition_garbage(objects);
+
+    if !garbage.is_empty() && !vm.is_finalized() {
+        let mut finalized_any = false;
+        for (obj, finalized) in garbage.iter_mut().filter(|(_, finalized)| !*finalized) {
+            if let Some(del) = objtype::class_get_attr(&obj.class(), "__del__") {
+                *finalized = true;
+                finalized_any = true;
+                if let Err(exc) = vm.invoke(&del, vec![obj.clone()]) {
+                    vm.print_unraisable(&del, &exc);
+                }
+            }
+        }
+        // A `__del__` can make garbage reachable again, by storing a reference to it.
+        if finalized_any {
+            let (resurrected, rest) = partition_garbage(garbage);
+            survivors.extend(resurrected);
+            garbage = rest;
+        }
+    }
+
+    let found = garbage.len();
+    if debug & DEBUG_COLLECTABLE != 0 {
+        for (obj, _) in &garbage {
+            eprintln!("gc: collectable {}", describe(obj));
+        }
+    }
+    let mut uncollectable = 0;
+    if debug & DEBUG_SAVEALL != 0 {
+        vm.gc_garbage
+            .elements
+            .borrow_mut()
+            .extend(garbage.iter().map(|(obj, _)| obj.clone()));
+        survivors.extend(garbage);
+    } else {
+        for (obj, _) in &garbage {
+            obj.payload.clear_references();
+        }
+        let cleared: Vec<_> = garbage
+            .into_iter()
+            .map(|(obj, finalized)| (Rc::downgrade(&obj), finalized))
+            .collect();
+        // The cycles that go through references that can't be dropped are left.
+        for (obj, finalized) in cleared {
+            if let Some(obj) = obj.upgrade() {
+                uncollectable += 1;
+                if debug & DEBUG_UNCOLLECTABLE != 0 {
+                    eprintln!("gc: uncollectable {}", describe(&obj));
+                    vm.gc_garbage.elements.borrow_mut().push(obj.clone());
+                }
+                survivors.push((obj, finalized));
+            }
+        }
+    }
+
+    STATE.with(|state| state.borrow_mut().finish(generation, survivors));
+    if debug & DEBUG_STATS != 0 {
+        eprintln!(
+            "gc: done, {} unreachable, {} uncollectable",
+            found, uncollectable
+        );
+    }
+    vm.run_weakref_callbacks();
+    found
+}
+
+impl State {
+    /// Take the objects of `generation` and the younger ones, unless a collection is running.
+    fn start(&mut self, generation: usize) -> Option<(Vec<Tracked>, u32)> {
+        if self.collecting {
+            return None;
+        }
+        self.collecting = true;
+        if self.debug & DEBUG_STATS != 0 {
+            eprintln!("gc: collecting generation {}...", generation);
+            let sizes: Vec<_> = self
+                .generations
+                .iter()
+                .map(|generation| generation.objects.len().to_string())
+                .collect();
+            eprintln!("gc: objects in each generation: {}", sizes.join(" "));
+        }
+        if generation + 1 < GENERATIONS {
+            self.counts[generation + 1] += 1;
+        }
+        let mut tracked = vec![];
+        for younger in 0..=generation {
+            self.counts[younger] = 0;
+            tracked.append(&mut mem::take(&mut self.generations[younger]).objects);
+        }
+        Some((tracked, self.debug))
+    }
+
+    /// Move the objects that survived the collection of `generation` to the next one.
+    fn finish(&mut self, generation: usize, survivors: Vec<(PyObjectRef, bool)>) {
+        let older = (generation + 1).min(GENERATIONS - 1);
+        if generation == GENERATIONS - 1 {
+            self.long_lived_total = survivors.len();
+            self.long_lived_pending = 0;
+        } else if older == GENERATIONS - 1 {
+            self.long_lived_pending += survivors.len();
+        }
+        for (obj, finalized) in survivors {
+            self.generations[older].push(Tracked {
+                object: Rc::downgrade(&obj),
+                finalized,
+            });
+        }
+        self.collecting = false;
+    }
+}
+
+/// Split `objects` into the ones that are referenced from elsewhere, directly or through each
+/// other, and the garbage.
+#[allow(clippy::type_complexity)]
+fn partition_garbage(
+    objects: Vec<(PyObjectRef, bool)>,
+) -> (Vec<(PyObjectRef, bool)>, Vec<(PyObjectRef, bool)>) {
+    let index: HashMap<usize, usize> = objects
+        .iter()
+        .enumerate()
+        .map(|(i, (obj, _))| (obj.get_id(), i))
+        .collect();
+    // The reference `objects` holds doesn't count.
+    let mut outside: Vec<usize> = objects
+        .iter()
+        .map(|(obj, _)| Rc::strong_count(obj) - 1)
+        .collect();
+    for (obj, _) in &objects {
+        traverse(obj, &mut |referent| {
+            if let Some(&i) = index.get(&referent.get_id()) {
+                outside[i] = outside[i].saturating_sub(1);
+            }
+        });
+    }
+
+    let mut reachable: Vec<bool> = outside.iter().map(|&count| count > 0).collect();
+    let mut pending: Vec<usize> = (0..objects.len()).filter(|&i| reachable[i]).collect();
+    while let Some(i) = pending.pop() {
+        traverse(&objects[i].0, &mut |referent| {
+            if let Some(&j) = index.get(&referent.get_id()) {
+                if !reachable[j] {
+                    reachable[j] = true;
+                    pending.push(j);
+                }
+            }
+        });
+    }
+
+    let mut survivors = vec![];
+    let mut garbage = vec![];
+    for (object, reachable) in objects.into_iter().zip(reachable) {
+        if reachable {
+            survivors.push(object);
+        } else {
+            garbage.push(object);
+        }
+    }
+    (survivors, garbage)
+}
+
+fn describe(obj: &PyObjectRef) -> String {
+    format!("<{} {:#x}>", obj.class().name, obj.get_id())
+}
@@ -0,0 +1,178 @@
+//! The global interpreter lock, which a thread holds while it runs Python code.
+//!
+//! Objects are reference counted with `Rc`, and their payloads use `Cell` and `RefCell`, none of
+//! which can be used from two threads at once. So only the thread that holds the lock of an
+//! interpreter may touch its objects, and that's what makes it sound to share them between the
+//! threads `_thread.start_new_thread` starts. The thread that creates a `VirtualMachine` holds
+//! its lock from the start, which is all there is to it as long as no threads are started.
+//!
+//! The lock is released around blocking calls, see `VirtualMachine::allow_threads`, and a thread
+//! that has been waiting for it for a while asks for it, like CPython's `sys.getswitchinterval`,
+//! and the thread that holds it hands it over at the next instruction boundary.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How long a thread waits for the lock before it asks for it.
+const SWITCH_INTERVAL: Duration = Duration::from_millis(5);
+
+struct State {
+    locked: bool,
+    /// How many times the lock was taken, to tell whether it changed hands.
+    switches: u64,
+    /// How many times `notify` was called.
+    events: u64,
+}
+
+pub(crate) struct Gil {
+    state: Mutex<State>,
+    /// Signalled when the lock is released.
+    released: Condvar,
+    /// Signalled when the lock is taken.
+    taken: Condvar,
+    /// Signalled by `notify`.
+    event: Condvar,
+    /// Whether a thread asked the one that holds the lock to release it.
+    drop_request: AtomicBool,
+    /// How many threads were started and haven't ended yet, not counting the one that created
+    /// the interpreter.
+    threads: AtomicUsize,
+}
+
+impl Gil {
+    /// A lock that's held by the current thread.
+    pub fn new_locked() -> Self {
+        Gil {
+            state: Mutex::new(State {
+                locked: true,
+                switches: 0,
+                events: 0,
+            }),
+            released: Condvar::new(),
+            taken: Condvar::new(),
+            event: Condvar::new(),
+            drop_request: AtomicBool::new(false),
+            threads: AtomicUsize::new(0),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<State> {
+        // The state is only changed where nothing can panic
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Wait for the lock and take it.
+    pub fn acquire(&self) {
+        self.take(self.state());
+    }
+
+    fn take(&self, mut state: MutexGuard<State>) {
+        while state.locked {
+            let switches = state.switches;
+            let (guard, result) = self
+                .released
+                .wait_timeout(state, SWITCH_INTERVAL)
+                .unwrap_or_else(|err| err.into_inner());
+            state = guard;
+            if result.timed_out() && state.locked && state.switches == switches {
+                self.drop_request.store(true, Ordering::Relaxed);
+            }
+        }
+        state.locked = true;
+        state.switches += 1;
+        self.drop_request.store(false, Ordering::Relaxed);
+        self.taken.notify_all();
+    }
+
+    pub fn release(&self) {
+        let mut state = self.state();
+        state.locked = false;
+        self.released.notify_one();
+    }
+
+    /// Hand the lock over if another thread asked for it, and wait to get it back. This is
+    /// called between instructions, so it only costs an atomic load when nobody's waiting.
+    pub fn switch_if_requested(&self) {
+        if !self.drop_request.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut state = self.state();
+        let switches = state.switches;
+        state.locked = false;
+        self.released.notify_one();
+        // Without waiting for the other thread to take it, this one could take it right back
+        while state.switches == switches {
+            state = self
+                .taken
+                .wait(state)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        self.take(state);
+    }
+
+    /// Wake the threads waiting in `wait_for_event`.
+    pub fn notify(&self) {
+        let mut state = self.state();
+        state.events += 1;
+        self.event.notify_all();
+    }
+
+    /// Release the lock until `notify` is called or `timeout` has passed, and take it back. The
+    /// lock must be held, so a `notify` that comes right after the caller checked what it's
+    /// waiting for isn't missed.
+    pub fn wait_for_event(&self, timeout: Duration) {
+        let mut state = self.state();
+        let events = state.events;
+        state.locked = false;
+        self.released.notify_one();
+        let deadline = Instant::now().checked_add(timeout);
+        while state.events == events {
+            let now = Instant::now();
+            let timeout = match deadline {
+                Some(deadline) if deadline <= now => break,
+                Some(deadline) => deadline - now,
+                None => timeout,
+            };
+            state = self
+                .event
+                .wait_timeout(state, timeout)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+        }
+        self.take(state);
+    }
+
+    pub fn thread_started(&self) {
+        self.threads.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn thread_ended(&self) {
+        self.threads.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// How many started threads are running.
+    pub fn thread_count(&self) -> usize {
+        self.threads.load(Ordering::SeqCst)
+    }
+}
+
+/// Releases the lock while it exists, and takes it back when it's dropped, even when a panic
+/// unwinds through it, so that the objects dropped while unwinding are dropped with the lock
+/// held.
+pub(crate) struct Released<'a> {
+    gil: &'a Gil,
+}
+
+impl<'a> Released<'a> {
+    pub fn new(gil: &'a Gil) -> Self {
+        gil.release();
+        Released { gil }
+    }
+}
+
+impl Drop for Released<'_> {
+    fn drop(&mut self) {
+        self.gil.acquire();
+    }
+}
@@ -4,6 +4,7 @@
 use rand::Rng;
 
 use crate::bytecode::CodeObject;
+use crate::frozen;
 use crate::obj::objtraceback::{PyTraceback, PyTracebackRef};
 use crate::obj::{objcode, objtype};
 use crate::pyobject::{ItemProtocol, PyObjectRef, PyResult, PyValue};
@@ -13,6 +14,9 @@ use crate::vm::VirtualMachine;
 #[cfg(feature = "rustpython-compiler")]
 use rustpython_compiler::compile;
 
+/// Set up importlib, with the path based finders that import from `sys.path` if `external` is
+/// set. Those need a file system, so on wasm `external` has no effect, and only the frozen and
+/// built-in modules can be imported.
 pub fn init_importlib(vm: &VirtualMachine, external: bool) -> PyResult {
     flame_guard!("init importlib");
     let importlib = import_frozen(vm, "_frozen_importlib")?;
@@ -21,11 +25,18 @@ pub fn init_importlib(vm: &VirtualMachine, external: bool) -> PyResult {
     vm.invoke(&install, vec![vm.sys_module.clone(), impmod])?;
     vm.import_func
         .replace(vm.get_attribute(importlib.clone(), "__import__")?);
-    if external && cfg!(feature = "rustpython-compiler") {
+    if external && cfg!(feature = "rustpython-compiler") && cfg!(not(target_arch = "wasm32")) {
         flame_guard!("install_external");
         let install_external =
             vm.get_attribute(importlib.clone(), "_install_external_importers")?;
         vm.invoke(&install_external, vec![])?;
+        if vm.settings.prefer_path {
+            // Consult the path based finder, which was just appended, before the frozen modules
+            let meta_path = vm.get_attribute(vm.sys_module.clone(), "meta_path")?;
+            let frozen_importer = vm.get_attribute(importlib.clone(), "FrozenImporter")?;
+            vm.call_method(&meta_path, "remove", vec![frozen_importer.clone()])?;
+            vm.call_method(&meta_path, "append", vec![frozen_importer])?;
+        }
         // Set pyc magic number to commit hash. Should be changed when bytecode will be more stable.
         let importlib_external = vm.import("_frozen_importlib_external", &[], 0)?;
         let mut magic = get_git_revision().into_bytes();
@@ -42,15 +53,19 @@ pub fn import_frozen(vm: &VirtualMachine, module_name: &str) -> PyResult {
     vm.frozen
         .borrow()
         .get(module_name)
-        .ok_or_else(|| vm.new_import_error(format!("Cannot import frozen module {}", module_name)))
-        .and_then(|frozen| import_codeobj(vm, module_name, frozen.code.clone(), false))
+        .ok_or_else(|| vm.new_module_not_found_error(module_name))
+        .and_then(|frozen| {
+            let file = frozen::module_file(frozen);
+            let code = frozen::module_code(vm, module_name, frozen)?;
+            exec_module(vm, module_name, code, file)
+        })
 }
 
 pub fn import_builtin(vm: &VirtualMachine, module_name: &str) -> PyResult {
     vm.stdlib_inits
         .borrow()
         .get(module_name)
-        .ok_or_else(|| vm.new_import_error(format!("Cannot import bultin module {}", module_name)))
+        .ok_or_else(|| vm.new_module_not_found_error(module_name))
         .and_then(|make_module_func| {
             let module = make_module_func(vm);
             let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules")?;
@@ -82,20 +97,41 @@ pub fn import_codeobj(
     code_obj: CodeObject,
     set_file_attr: bool,
 ) -> PyResult {
-    let attrs = vm.ctx.new_dict();
-    attrs.set_item("__name__", vm.new_str(module_name.to_string()), vm)?;
-    if set_file_attr {
-        attrs.set_item("__file__", vm.new_str(code_obj.source_path.to_owned()), vm)?;
-    }
-    let module = vm.new_module(module_name, attrs.clone());
+    let file = if set_file_attr {
+        Some(code_obj.source_path.clone())
+    } else {
+        None
+    };
+    exec_module(vm, module_name, code_obj, file)
+}
 
-    // Store module in cache to prevent infinite loop with mutual importing libs:
+/// Run `code_obj` in the module `module_name`, with `file` as its `__file__` if it has one.
+fn exec_module(
+    vm: &VirtualMachine,
+    module_name: &str,
+    code_obj: CodeObject,
+    file: Option<String>,
+) -> PyResult {
     let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules")?;
-    sys_modules.set_item(module_name, module.clone(), vm)?;
+    // Like CPython, run the code in the module that is already imported, if there is one, so
+    // that importing it again reloads it in place
+    let module = match sys_modules.get_item(module_name, vm) {
+        Ok(module) if objtype::isinstance(&module, &vm.ctx.types.module_type) => module,
+        _ => {
+            let module = vm.new_module(module_name, vm.ctx.new_dict());
+            // Store module in cache to prevent infinite loop with mutual importing libs:
+            sys_modules.set_item(module_name, module.clone(), vm)?;
+            module
+        }
+    };
+    let attrs = module.dict().cloned().expect("modules have a dict");
+    if let Some(file) = file {
+        attrs.set_item("__file__", vm.new_str(file), vm)?;
+    }
 
     // Execute main code in module:
     vm.run_code_obj(
-        objcode::PyCode::new(code_obj).into_ref(vm),
+        objcode::PyCode::new(code_obj, &vm.ctx).into_ref(vm),
         Scope::with_builtins(None, attrs, vm),
     )?;
     Ok(module)
@@ -0,0 +1,314 @@
+//! Inline caches for looking up globals and attributes.
+//!
+//! Every instruction that `Instruction::has_inline_cache` gets a slot in the `PyCode` it belongs
+//! to, which remembers what the instruction found the last time along with the versions of the
+//! dicts and types that the result depended on (see `Dict::version` and `PyClass::version_tag`).
+//! As long as those versions are unchanged the remembered result is used instead of doing the
+//! lookup again, so a cache never changes what the program sees. The globals and module
+//! attributes are only weak references, so that a cache doesn't keep alive what the program let
+//! go of, which `VirtualMachine::shutdown` relies on to tell the last reference to an object.
+//!
+//! Only the common cases are cached: globals and builtins, and attributes of objects whose type
+//! uses the generic `object.__getattribute__` and `object.__setattr__`, or is a module. The
+//! others take the usual path every time, as does everything when `PySettings::no_inline_caches`
+//! is set.
+
+use crate::obj::objdict::PyDictRef;
+use crate::obj::objstr::PyStringRef;
+use crate::obj::objtype::{self, PyClassRef};
+use crate::pyobject::{
+    IdProtocol, ItemProtocol, PyObject, PyObjectPayload, PyObjectRef, PyResult, TypeProtocol,
+};
+use crate::scope::{NameProtocol, Scope};
+use crate::vm::VirtualMachine;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+#[derive(Clone)]
+pub enum InlineCache {
+    Empty,
+    /// A global that was found in the globals, or in the builtins when the globals don't have
+    /// it.
+    Global {
+        globals_version: u64,
+        builtins_version: u64,
+        builtins_type_version: u64,
+        value: Weak<PyObject<dyn PyObjectPayload>>,
+    },
+    /// An attribute that a module has in its dict.
+    ModuleAttr {
+        type_version: u64,
+        dict_version: u64,
+        value: Weak<PyObject<dyn PyObjectPayload>>,
+    },
+    /// Getting an attribute of an object whose type has the given version. Its dict is looked
+    /// at first, and when the attribute isn't there it is `class_attr`, if any.
+    LoadAttr {
+        type_version: u64,
+        class_attr: Option<ClassAttr>,
+    },
+    /// Setting an attribute in the dict of an object whose type has the given version, where
+    /// the type's own attribute of that name, if any, is `class_attr`.
+    StoreAttr {
+        type_version: u64,
+        class_attr: Option<ClassAttr>,
+    },
+}
+
+/// What looking up an attribute on a type found, as far as the caches are concerned.
+enum TypeLookup {
+    /// The type's attribute of that name, if it has one.
+    Cacheable(Option<ClassAttr>),
+    /// A descriptor that the caches don't handle.
+    NotCacheable,
+}
+
+/// An attribute of a type that isn't a data descriptor.
+#[derive(Clone)]
+pub struct ClassAttr {
+    value: PyObjectRef,
+    /// The version of the value's type, which decides whether the value is a descriptor.
+    type_version: u64,
+    /// Whether the value is a function, which is bound to the object it is looked up on.
+    bind: bool,
+}
+
+impl ClassAttr {
+    fn lookup(vm: &VirtualMachine, cls: &PyClassRef, name: &str) -> TypeLookup {
+        let value = match objtype::class_get_attr(cls, name) {
+            Some(value) => value,
+            None => return TypeLookup::Cacheable(None),
+        };
+        let value_type = value.class();
+        if objtype::class_has_attr(&value_type, "__set__") {
+            return TypeLookup::NotCacheable;
+        }
+        let bind = objtype::class_has_attr(&value_type, "__get__");
+        if bind
+            && !value_type.is(&vm.ctx.function_type())
+            && !value_type.is(&vm.ctx.builtin_function_or_method_type())
+        {
+            return TypeLookup::NotCacheable;
+        }
+        TypeLookup::Cacheable(Some(ClassAttr {
+            type_version: value_type.version_tag(),
+            value,
+            bind,
+        }))
+    }
+
+    fn is_current(&self) -> bool {
+        self.value.class().version_tag() == self.type_version
+    }
+
+    fn get(&self, vm: &VirtualMachine, obj: PyObjectRef) -> PyObjectRef {
+        if self.bind {
+            vm.ctx.new_bound_method(self.value.clone(), obj)
+        } else {
+            self.value.clone()
+        }
+    }
+}
+
+fn builtins_dict(vm: &VirtualMachine) -> &PyDictRef {
+    vm.builtins
+        .dict()
+        .expect("builtins module must have a dict")
+}
+
+fn is_plain_dict(vm: &VirtualMachine, dict: &PyDictRef) -> bool {
+    dict.as_object().class().is(&vm.ctx.dict_type())
+}
+
+/// Whether `cls` uses the same `name` special method as `base`.
+fn inherits_method(cls: &PyClassRef, base: &PyClassRef, name: &str) -> bool {
+    match (
+        objtype::class_get_attr(cls, name),
+        objtype::class_get_attr(base, name),
+    ) {
+        (Some(method), Some(base_method)) => method.is(&base_method),
+        _ => false,
+    }
+}
+
+pub fn load_global(
+    vm: &VirtualMachine,
+    cache: &RefCell<InlineCache>,
+    scope: &Scope,
+    name: &PyStringRef,
+) -> Option<PyObjectRef> {
+    let builtins = builtins_dict(vm);
+    if let InlineCache::Global {
+        globals_version,
+        builtins_version,
+        builtins_type_version,
+        ref value,
+    } = *cache.borrow()
+    {
+        if scope.globals.version() == globals_version
+            && builtins.version() == builtins_version
+            && vm.builtins.class().version_tag() == builtins_type_version
+        {
+            if let Some(value) = value.upgrade() {
+                return Some(value);
+            }
+        }
+    }
+
+    let (value, cacheable) = if !is_plain_dict(vm, &scope.globals) || !is_plain_dict(vm, builtins) {
+        (scope.load_global(vm, name), false)
+    } else if let Some(value) = scope
+        .globals
+        .get_item_option(name.as_object(), vm)
+        .ok()
+        .flatten()
+    {
+        (Some(value), true)
+    } else if objtype::class_has_attr(&vm.builtins.class(), name.as_str()) {
+        (scope.load_global(vm, name), false)
+    } else {
+        let value = builtins
+            .get_item_option(name.as_object(), vm)
+            .ok()
+            .flatten();
+        let cacheable = value.is_some();
+        (value, cacheable)
+    };
+    *cache.borrow_mut() = match value {
+        Some(ref value) if cacheable => InlineCache::Global {
+            globals_version: scope.globals.version(),
+            builtins_version: builtins.version(),
+            builtins_type_version: vm.builtins.class().version_tag(),
+            value: Rc::downgrade(value),
+        },
+        _ => InlineCache::Empty,
+    };
+    value
+}
+
+pub fn load_attr(
+    vm: &VirtualMachine,
+    cache: &RefCell<InlineCache>,
+    obj: PyObjectRef,
+    name: &PyStringRef,
+) -> PyResult {
+    // Cloned, so that the cache isn't borrowed while looking in the object's dict, which may run
+    // Python code that ends up here again.
+    let cached = cache.borrow().clone();
+    match cached {
+        InlineCache::ModuleAttr {
+            type_version,
+            dict_version,
+            value,
+        } => {
+            if obj.class().version_tag() == type_version
+                && obj.dict().map(PyDictRef::version) == Some(dict_version)
+            {
+                if let Some(value) = value.upgrade() {
+                    return Ok(value);
+                }
+            }
+        }
+        InlineCache::LoadAttr {
+            type_version,
+            class_attr,
+        } => {
+            if obj.class().version_tag() == type_version {
+                if let Some(dict) = obj.dict() {
+                    if let Some(value) = dict.get_item_option(name.as_object(), vm)? {
+                        return Ok(value);
+                    }
+                }
+                if let Some(class_attr) = class_attr {
+                    if class_attr.is_current() {
+                        return Ok(class_attr.get(vm, obj));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let value = vm.get_attribute(obj.clone(), name.clone())?;
+    *cache.borrow_mut() = load_attr_cache(vm, &obj, name);
+    Ok(value)
+}
+
+fn load_attr_cache(vm: &VirtualMachine, obj: &PyObjectRef, name: &PyStringRef) -> InlineCache {
+    let cls = obj.class();
+    if inherits_method(&cls, &vm.ctx.module_type(), "__getattribute__") {
+        if objtype::class_has_attr(&cls, name.as_str()) {
+            return InlineCache::Empty;
+        }
+        match obj.dict() {
+            Some(dict) if is_plain_dict(vm, dict) => {
+                match dict.get_item_option(name.as_object(), vm).ok().flatten() {
+                    Some(value) => InlineCache::ModuleAttr {
+                        type_version: cls.version_tag(),
+                        dict_version: dict.version(),
+                        value: Rc::downgrade(&value),
+                    },
+                    None => InlineCache::Empty,
+                }
+            }
+            _ => InlineCache::Empty,
+        }
+    } else if inherits_method(&cls, &vm.ctx.object(), "__getattribute__") {
+        match ClassAttr::lookup(vm, &cls, name.as_str()) {
+            TypeLookup::Cacheable(class_attr) => InlineCache::LoadAttr {
+                type_version: cls.version_tag(),
+                class_attr,
+            },
+            TypeLookup::NotCacheable => InlineCache::Empty,
+        }
+    } else {
+        InlineCache::Empty
+    }
+}
+
+pub fn store_attr(
+    vm: &VirtualMachine,
+    cache: &RefCell<InlineCache>,
+    obj: &PyObjectRef,
+    name: &PyStringRef,
+    value: PyObjectRef,
+) -> PyResult<()> {
+    let cached = cache.borrow().clone();
+    if let InlineCache::StoreAttr {
+        type_version,
+        class_attr,
+    } = cached
+    {
+        if obj.class().version_tag() == type_version
+            && class_attr.map_or(true, |class_attr| class_attr.is_current())
+        {
+            if let Some(dict) = obj.dict() {
+                dict.set_item(name.as_object(), value, vm)?;
+                return Ok(());
+            }
+        }
+    }
+
+    vm.set_attr(obj, name.clone(), value)?;
+    *cache.borrow_mut() = store_attr_cache(vm, obj, name);
+    Ok(())
+}
+
+fn store_attr_cache(vm: &VirtualMachine, obj: &PyObjectRef, name: &PyStringRef) -> InlineCache {
+    let cls = obj.class();
+    match obj.dict() {
+        Some(dict)
+            if is_plain_dict(vm, dict)
+                && inherits_method(&cls, &vm.ctx.object(), "__setattr__") =>
+        {
+            match ClassAttr::lookup(vm, &cls, name.as_str()) {
+                TypeLookup::Cacheable(class_attr) => InlineCache::StoreAttr {
+                    type_version: cls.version_tag(),
+                    class_attr,
+                },
+                TypeLookup::NotCacheable => InlineCache::Empty,
+            }
+        }
+        _ => InlineCache::Empty,
+    }
+}
@@ -0,0 +1,290 @@
+//! The way to embed the interpreter: configure it with an `InterpreterBuilder`, and run code in
+//! the `Interpreter` it builds.
+//!
+//! ```no_run
+//! use rustpython_vm::InterpreterBuilder;
+//!
+//! let interpreter = InterpreterBuilder::new()
+//!     .argv(vec!["embedded".to_owned()])
+//!     .add_path("/opt/app/lib")
+//!     .build();
+//! interpreter.enter(|vm| {
+//!     let sys_argv = vm.get_attribute(vm.sys_module.clone(), "argv").unwrap();
+//!     // ...
+//! });
+//! ```
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::bytecode::FrozenModule;
+use crate::frozen::{self, FrozenModuleCollision};
+use crate::import;
+use crate::vm::{PySettings, VirtualMachine};
+
+/// A function the builder runs on every interpreter it builds, once it is initialized.
+type InitHook = Rc<dyn Fn(&VirtualMachine)>;
+
+/// The configuration of an interpreter. Every `build` makes a new interpreter, independent of
+/// the ones built before.
+pub struct InterpreterBuilder {
+    settings: PySettings,
+    external_importers: bool,
+    init_hooks: Vec<InitHook>,
+}
+
+impl Default for InterpreterBuilder {
+    fn default() -> Self {
+        InterpreterBuilder {
+            settings: PySettings::default(),
+            external_importers: true,
+            init_hooks: vec![],
+        }
+    }
+}
+
+impl InterpreterBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Start from `settings`, instead of the defaults. This replaces everything that was set
+    /// before, including the frozen modules.
+    pub fn settings(mut self, settings: PySettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Add frozen modules. A name that is taken, by the modules added before or by the frozen
+    /// modules the interpreter comes with, like `_frozen_importlib`, is an error: replacing
+    /// those takes `frozen_modules_overwrite`.
+    pub fn frozen_modules(
+        mut self,
+        modules: HashMap<String, FrozenModule>,
+    ) -> Result<Self, FrozenModuleCollision> {
+        let builtin = frozen::get_module_inits();
+        frozen::merge_modules(&mut self.settings.frozen_modules, modules, |name| {
+            builtin.contains_key(name)
+        })?;
+        Ok(self)
+    }
+
+    /// Add frozen modules, replacing any with the same name, also the ones the interpreter comes
+    /// with.
+    pub fn frozen_modules_overwrite(mut self, modules: HashMap<String, FrozenModule>) -> Self {
+        self.settings.add_frozen_overwrite(modules);
+        self
+    }
+
+    /// Set `sys.argv`.
+    pub fn argv(mut self, argv: Vec<String>) -> Self {
+        self.settings.argv = argv;
+        self
+    }
+
+    /// Add an entry to the end of the initial `sys.path`.
+    pub fn add_path(mut self, path: impl Into<String>) -> Self {
+        self.settings.path_list.push(path.into());
+        self
+    }
+
+    /// Like `-E`: ignore the `PYTHON*` environment variables.
+    pub fn ignore_environment(mut self, ignore_environment: bool) -> Self {
+        self.settings.ignore_environment = ignore_environment;
+        self
+    }
+
+    /// Whether importlib gets the path based finders, which import modules from `sys.path`. They
+    /// are there by default; without them only the frozen and built-in modules can be imported.
+    pub fn external_importers(mut self, external_importers: bool) -> Self {
+        self.external_importers = external_importers;
+        self
+    }
+
+    /// Run `hook` on each interpreter once it is initialized, before `build` returns it. Hooks
+    /// run in the order they were added.
+    pub fn init_hook<F: Fn(&VirtualMachine) + 'static>(mut self, hook: F) -> Self {
+        self.init_hooks.push(Rc::new(hook));
+        self
+    }
+
+    /// Make a new interpreter with importlib set up. Not being able to set it up is a bug in
+    /// the frozen modules, and panics after printing the exception.
+    pub fn build(&self) -> Interpreter {
+        let vm = VirtualMachine::new(self.settings.clone());
+        if let Err(exc) = import::init_importlib(&vm, self.external_importers) {
+            crate::exceptions::print_exception(&vm, &exc);
+            panic!("couldn't initialize importlib");
+        }
+        for hook in &self.init_hooks {
+            hook(&vm);
+        }
+        Interpreter { vm }
+    }
+}
+
+/// An initialized interpreter. Dropping it shuts it down.
+pub struct Interpreter {
+    vm: VirtualMachine,
+}
+
+impl Interpreter {
+    /// Run `f` with the virtual machine of the interpreter.
+    pub fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&VirtualMachine) -> R,
+    {
+        f(&self.vm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InterpreterBuilder;
+    use crate::bytecode::FrozenModule;
+    use crate::frozen::FrozenModuleCollision;
+    use crate::pyobject::PyResult;
+    use crate::vm::VirtualMachine;
+    use rustpython_compiler::compile;
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    fn frozen_module(name: &str, source: &str) -> HashMap<String, FrozenModule> {
+        let code = compile::compile(source, compile::Mode::Exec, name.to_owned(), 0).unwrap();
+        let module = FrozenModule {
+            code: code.into(),
+            package: false,
+            namespace: false,
+            source: None,
+            source_path: None,
+            resources: HashMap::new(),
+        };
+        vec![(name.to_owned(), module)].into_iter().collect()
+    }
+
+    fn eval(vm: &VirtualMachine, source: &str) -> PyResult<String> {
+        let scope = vm.new_scope_with_builtins();
+        let code = vm
+            .compile(source, compile::Mode::Eval, "<test>".to_owned())
+            .unwrap();
+        let value = vm.run_code_obj(code, scope)?;
+        Ok(vm.to_repr(&value)?.as_str().to_owned())
+    }
+
+    #[test]
+    fn test_differently_configured_interpreters() {
+        let first = InterpreterBuilder::new()
+            .external_importers(false)
+            .argv(vec!["first".to_owned()])
+            .add_path("/first")
+            .frozen_modules(frozen_module("config", "name = 'first'"))
+            .unwrap()
+            .build();
+        let second = InterpreterBuilder::new()
+            .external_importers(false)
+            .argv(vec!["second".to_owned(), "-v".to_owned()])
+            .ignore_environment(true)
+            .frozen_modules(frozen_module("config", "name = 'second'"))
+            .unwrap()
+            .build();
+
+        first.enter(|vm| {
+            assert_eq!(eval(vm, "__import__('config').name").unwrap(), "'first'");
+            assert_eq!(eval(vm, "__import__('sys').argv").unwrap(), "['first']");
+            assert_eq!(
+                eval(vm, "'/first' in __import__('sys').path").unwrap(),
+                "True"
+            );
+            assert_eq!(
+                eval(vm, "__import__('sys').flags.ignore_environment").unwrap(),
+                "False"
+            );
+        });
+        second.enter(|vm| {
+            assert_eq!(eval(vm, "__import__('config').name").unwrap(), "'second'");
+            assert_eq!(
+                eval(vm, "__import__('sys').argv").unwrap(),
+                "['second', '-v']"
+            );
+            assert_eq!(
+                eval(vm, "'/first' in __import__('sys').path").unwrap(),
+                "False"
+            );
+            assert_eq!(
+                eval(vm, "__import__('sys').flags.ignore_environment").unwrap(),
+                "True"
+            );
+        });
+    }
+
+    #[test]
+    fn test_build_makes_independent_interpreters() {
+        let hook_runs = Rc::new(Cell::new(0));
+        let builder = InterpreterBuilder::new()
+            .external_importers(false)
+            .frozen_modules(frozen_module("config", "name = 'initial'"))
+            .unwrap()
+            .init_hook({
+                let hook_runs = hook_runs.clone();
+                move |vm| {
+                    hook_runs.set(hook_runs.get() + 1);
+                    assert_eq!(eval(vm, "__import__('config').name").unwrap(), "'initial'");
+                }
+            });
+        let first = builder.build();
+        let second = builder.build();
+        assert_eq!(hook_runs.get(), 2);
+
+        first.enter(|vm| {
+            let code = vm
+                .compile(
+                    "import config\nconfig.name = 'changed'",
+                    compile::Mode::Exec,
+                    "<test>".to_owned(),
+                )
+                .unwrap();
+            vm.run_code_obj(code, vm.new_scope_with_builtins()).unwrap();
+            assert_eq!(eval(vm, "__import__('config').name").unwrap(), "'changed'");
+        });
+        second.enter(|vm| {
+            assert_eq!(eval(vm, "__import__('config').name").unwrap(), "'initial'");
+        });
+    }
+
+    #[test]
+    fn test_frozen_module_collisions() {
+        // The frozen modules the interpreter comes with are only replaced on purpose
+        let collision = InterpreterBuilder::new().frozen_modules(frozen_module("__hello__", ""));
+        assert_eq!(
+            collision.err(),
+            Some(FrozenModuleCollision {
+                names: vec!["__hello__".to_owned()]
+            })
+        );
+        let interpreter = InterpreterBuilder::new()
+            .external_importers(false)
+            .frozen_modules_overwrite(frozen_module("__hello__", "name = 'replaced'"))
+            .build();
+
+        // Once a module is imported, a frozen module can't take its name
+        interpreter.enter(|vm| {
+            assert_eq!(
+                eval(vm, "__import__('__hello__').name").unwrap(),
+                "'replaced'"
+            );
+            let collision = vm.add_frozen(frozen_module("_imp", "name = 'frozen'"));
+            assert_eq!(
+                collision,
+                Err(FrozenModuleCollision {
+                    names: vec!["_imp".to_owned()]
+                })
+            );
+            vm.add_frozen_overwrite(frozen_module("_imp", "name = 'frozen'"));
+            assert_eq!(
+                eval(vm, "hasattr(__import__('_imp'), 'name')").unwrap(),
+                "False"
+            );
+        });
+    }
+}
@@ -58,7 +58,11 @@ pub mod format;
 mod frame;
 mod frozen;
 pub mod function;
+mod gc;
+mod gil;
 pub mod import;
+mod inline_cache;
+mod interpreter;
 pub mod obj;
 pub mod py_serde;
 mod pyhash;
@@ -73,6 +77,8 @@ mod vm;
 
 // pub use self::pyobject::Executor;
 pub use self::exceptions::{print_exception, write_exception};
+pub use self::frozen::{FrozenModuleCollision, RunFrozenError};
+pub use self::interpreter::{Interpreter, InterpreterBuilder};
 pub use self::vm::{PySettings, VirtualMachine};
 pub use rustpython_bytecode::*;
 
@@ -8,6 +8,7 @@ pub mod objbytes;
 pub mod objclassmethod;
 pub mod objcode;
 pub mod objcomplex;
+pub mod objcoroinner;
 pub mod objcoroutine;
 pub mod objdict;
 pub mod objellipsis;
@@ -22,6 +23,7 @@ pub mod objiter;
 pub mod objlist;
 pub mod objmap;
 pub mod objmappingproxy;
+pub mod objmemberdescr;
 pub mod objmemory;
 pub mod objmodule;
 pub mod objnamespace;
@@ -2,12 +2,14 @@ use std::fmt;
 
 use crate::function::PyNativeFunc;
 use crate::obj::objtype::PyClassRef;
-use crate::pyobject::PyValue;
+use crate::pyobject::{PyResult, PyValue};
 use crate::vm::VirtualMachine;
 
 pub struct PyBuiltinFunction {
     // TODO: shouldn't be public
     pub value: PyNativeFunc,
+    name: Option<String>,
+    doc: Option<String>,
 }
 
 impl PyValue for PyBuiltinFunction {
@@ -24,6 +26,30 @@ impl fmt::Debug for PyBuiltinFunction {
 
 impl PyBuiltinFunction {
     pub fn new(value: PyNativeFunc) -> Self {
-        Self { value }
+        Self {
+            value,
+            name: None,
+            doc: None,
+        }
+    }
+
+    pub fn with_name(self, name: String, doc: Option<String>) -> Self {
+        Self {
+            name: Some(name),
+            doc,
+            ..self
+        }
+    }
+
+    pub(crate) fn name(&self, vm: &VirtualMachine) -> PyResult<String> {
+        self.name.clone().ok_or_else(|| {
+            vm.new_attribute_error(
+                "'builtin_function_or_method' object has no attribute '__name__'".to_string(),
+            )
+        })
+    }
+
+    pub(crate) fn doc(&self, _vm: &VirtualMachine) -> Option<String> {
+        self.doc.clone()
     }
 }
@@ -1,5 +1,5 @@
 //! Implementation of the python bytearray object.
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, RefCell, RefMut};
 use std::convert::TryFrom;
 
 use super::objbyteinner::{
@@ -9,6 +9,7 @@ use super::objbyteinner::{
 };
 use super::objint::PyIntRef;
 use super::objiter;
+use super::objsequence;
 use super::objslice::PySliceRef;
 use super::objstr::PyStringRef;
 use super::objtuple::PyTupleRef;
@@ -36,20 +37,41 @@ use std::mem::size_of;
 #[derive(Clone, Debug)]
 pub struct PyByteArray {
     pub inner: RefCell<PyByteInner>,
+    /// The number of `ArgBytesLike`s borrowing the contents, which can't be resized meanwhile.
+    exports: Cell<usize>,
 }
 pub type PyByteArrayRef = PyRef<PyByteArray>;
 
 impl PyByteArray {
     pub fn new(data: Vec<u8>) -> Self {
-        PyByteArray {
-            inner: RefCell::new(PyByteInner { elements: data }),
-        }
+        PyByteArray::from_inner(PyByteInner { elements: data })
     }
 
     pub fn from_inner(inner: PyByteInner) -> Self {
         PyByteArray {
             inner: RefCell::new(inner),
+            exports: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn export(&self) {
+        self.exports.set(self.exports.get() + 1);
+    }
+
+    pub(crate) fn release(&self) {
+        self.exports.set(self.exports.get() - 1);
+    }
+
+    /// Borrow the contents for a change of their length, which isn't allowed while they're
+    /// exported.
+    fn resizable(&self, vm: &VirtualMachine) -> PyResult<RefMut<PyByteInner>> {
+        if self.exports.get() > 0 {
+            return Err(vm.new_exception(
+                vm.ctx.exceptions.buffer_error.clone(),
+                "Existing exports of data: object cannot be re-sized".to_string(),
+            ));
         }
+        Ok(self.inner.borrow_mut())
     }
 
     // pub fn get_value(&self) -> Vec<u8> {
@@ -140,7 +162,7 @@ impl PyByteArrayRef {
 
     #[pymethod(name = "__hash__")]
     fn hash(self, vm: &VirtualMachine) -> PyResult<()> {
-        Err(vm.new_type_error("unhashable type: bytearray".to_string()))
+        Err(vm.new_type_error("unhashable type: 'bytearray'".to_string()))
     }
 
     #[pymethod(name = "__iter__")]
@@ -181,7 +203,19 @@ impl PyByteArrayRef {
         value: PyObjectRef,
         vm: &VirtualMachine,
     ) -> PyResult {
-        self.inner.borrow_mut().setitem(needle, value, vm)
+        match needle {
+            Either::B(slice) if self.exports.get() > 0 => {
+                // Assigning to a slice may change the length, so try it on a copy first
+                let mut inner = self.inner.borrow().clone();
+                let result = inner.setitem(Either::B(slice), value, vm)?;
+                if inner.len() != self.inner.borrow().len() {
+                    self.resizable(vm)?;
+                }
+                *self.inner.borrow_mut() = inner;
+                Ok(result)
+            }
+            needle => self.inner.borrow_mut().setitem(needle, value, vm),
+        }
     }
 
     #[pymethod(name = "isalnum")]
@@ -344,7 +378,7 @@ impl PyByteArrayRef {
     fn remove(self, x: PyIntRef, vm: &VirtualMachine) -> PyResult<()> {
         let x = x.as_bigint().byte_or(vm)?;
 
-        let bytes = &mut self.inner.borrow_mut().elements;
+        let bytes = &mut self.resizable(vm)?.elements;
         let pos = bytes
             .iter()
             .position(|b| *b == x)
@@ -471,8 +505,9 @@ impl PyByteArrayRef {
     }
 
     #[pymethod(name = "clear")]
-    fn clear(self, _vm: &VirtualMachine) {
-        self.inner.borrow_mut().elements.clear();
+    fn clear(self, vm: &VirtualMachine) -> PyResult<()> {
+        self.resizable(vm)?.elements.clear();
+        Ok(())
     }
 
     #[pymethod(name = "copy")]
@@ -482,16 +517,14 @@ impl PyByteArrayRef {
 
     #[pymethod(name = "append")]
     fn append(self, x: PyIntRef, vm: &VirtualMachine) -> PyResult<()> {
-        self.inner
-            .borrow_mut()
-            .elements
-            .push(x.as_bigint().byte_or(vm)?);
+        let x = x.as_bigint().byte_or(vm)?;
+        self.resizable(vm)?.elements.push(x);
         Ok(())
     }
 
     #[pymethod(name = "extend")]
     fn extend(self, iterable_of_ints: PyIterable, vm: &VirtualMachine) -> PyResult<()> {
-        let mut inner = self.inner.borrow_mut();
+        let mut inner = self.resizable(vm)?;
 
         for x in iterable_of_ints.iter(vm)? {
             let x = x?;
@@ -505,7 +538,7 @@ impl PyByteArrayRef {
 
     #[pymethod(name = "insert")]
     fn insert(self, mut index: isize, x: PyIntRef, vm: &VirtualMachine) -> PyResult<()> {
-        let bytes = &mut self.inner.borrow_mut().elements;
+        let bytes = &mut self.resizable(vm)?.elements;
         let len = isize::try_from(bytes.len())
             .map_err(|_e| vm.new_overflow_error("bytearray too big".to_string()))?;
 
@@ -531,7 +564,7 @@ impl PyByteArrayRef {
 
     #[pymethod(name = "pop")]
     fn pop(self, vm: &VirtualMachine) -> PyResult<u8> {
-        let bytes = &mut self.inner.borrow_mut().elements;
+        let bytes = &mut self.resizable(vm)?.elements;
         bytes
             .pop()
             .ok_or_else(|| vm.new_index_error("pop from empty bytearray".to_string()))
@@ -543,18 +576,21 @@ impl PyByteArrayRef {
     }
 
     #[pymethod(name = "__mul__")]
-    fn repeat(self, n: isize, vm: &VirtualMachine) -> PyResult {
-        Ok(vm.ctx.new_bytearray(self.inner.borrow().repeat(n, vm)?))
+    fn repeat(self, n: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match objsequence::repeat_count(vm, &n)? {
+            Some(n) => Ok(vm.ctx.new_bytearray(self.inner.borrow().repeat(n, vm)?)),
+            None => Ok(vm.ctx.not_implemented()),
+        }
     }
 
     #[pymethod(name = "__rmul__")]
-    fn rmul(self, n: isize, vm: &VirtualMachine) -> PyResult {
+    fn rmul(self, n: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.repeat(n, vm)
     }
 
     #[pymethod(name = "__imul__")]
     fn irepeat(self, n: isize, vm: &VirtualMachine) -> PyResult<()> {
-        self.inner.borrow_mut().irepeat(n, vm)
+        self.resizable(vm)?.irepeat(n, vm)
     }
 
     #[pymethod(name = "reverse")]
@@ -18,6 +18,7 @@ use super::objtuple::PyTupleRef;
 use crate::function::OptionalArg;
 use crate::pyhash;
 use crate::pyobject::{Either, PyIterable, PyObjectRef, PyResult, TryFromObject, TypeProtocol};
+use crate::stdlib::array::PyArray;
 use crate::vm::VirtualMachine;
 
 #[derive(Debug, Default, Clone)]
@@ -314,10 +315,37 @@ impl ByteInnerSplitlinesOptions {
 impl PyByteInner {
     pub fn from_string(value: &str, encoding: &str, vm: &VirtualMachine) -> PyResult<Self> {
         let normalized = normalize_encoding(encoding);
-        if normalized == "utf_8" || normalized == "utf8" || normalized == "u8" {
-            Ok(PyByteInner {
-                elements: value.as_bytes().to_vec(),
-            })
+        let limit = match normalized.as_str() {
+            "utf_8" | "utf8" | "u8" => {
+                return Ok(PyByteInner {
+                    elements: value.as_bytes().to_vec(),
+                })
+            }
+            "ascii" | "us_ascii" => Some(("ascii", 128)),
+            "latin_1" | "latin1" | "iso8859_1" | "iso_8859_1" | "l1" => Some(("latin-1", 256)),
+            _ => None,
+        };
+        if let Some((codec, limit)) = limit {
+            let mut elements = Vec::with_capacity(value.len());
+            for (position, c) in value.chars().enumerate() {
+                let ordinal = c as u32;
+                if ordinal >= limit {
+                    let escaped = if ordinal < 0x100 {
+                        format!("\\x{:02x}", ordinal)
+                    } else if ordinal < 0x10000 {
+                        format!("\\u{:04x}", ordinal)
+                    } else {
+                        format!("\\U{:08x}", ordinal)
+                    };
+                    return Err(vm.new_unicode_encode_error(format!(
+                        "'{}' codec can't encode character '{}' in position {}: ordinal not in \
+                         range({})",
+                        codec, escaped, position, limit
+                    )));
+                }
+                elements.push(c as u8);
+            }
+            Ok(PyByteInner { elements })
         } else {
             // TODO: different encoding
             Err(
@@ -725,9 +753,19 @@ impl PyByteInner {
 
     pub fn join(&self, iter: PyIterable, vm: &VirtualMachine) -> PyResult {
         let mut refs = vec![];
-        for v in iter.iter(vm)? {
+        for (i, v) in iter.iter(vm)?.enumerate() {
             let v = v?;
-            refs.extend(PyByteInner::try_from_object(vm, v)?.elements)
+            let class_name = v.class().name.clone();
+            let bytes = ArgBytesLike::try_from_object(vm, v).map_err(|_| {
+                vm.new_type_error(format!(
+                    "sequence item {}: expected a bytes-like object, {} found",
+                    i, class_name
+                ))
+            })?;
+            if i > 0 {
+                refs.extend_from_slice(&self.elements);
+            }
+            bytes.with_ref(|bytes| refs.extend_from_slice(bytes));
         }
 
         Ok(vm.ctx.new_bytes(refs))
@@ -1304,33 +1342,109 @@ fn split_slice_reverse<'a>(slice: &'a [u8], sep: &[u8], maxsplit: i32) -> Vec<&'
     splitted
 }
 
-pub enum PyBytesLike {
+/// An argument that can be any bytes-like object: bytes, bytearray, memoryview or array.array.
+/// A bytearray stays exported for as long as the argument lives, so it can't be resized while
+/// the function uses its contents. The contents of an array are copied.
+#[derive(Debug)]
+pub struct ArgBytesLike(BytesLike);
+
+#[derive(Debug)]
+enum BytesLike {
     Bytes(PyBytesRef),
     Bytearray(PyByteArrayRef),
     Vec(Vec<u8>),
 }
 
-impl TryFromObject for PyBytesLike {
+impl TryFromObject for ArgBytesLike {
+    fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
+        let bytes_like = match_class!(match obj {
+            b @ PyBytes => BytesLike::Bytes(b),
+            b @ PyByteArray => {
+                b.export();
+                BytesLike::Bytearray(b)
+            }
+            m @ PyMemoryView => return ArgBytesLike::try_from_object(vm, m.get_obj()),
+            a @ PyArray => BytesLike::Vec(a.tobytes(vm)),
+            obj => {
+                return Err(vm.new_type_error(format!(
+                    "a bytes-like object is required, not '{}'",
+                    obj.class().name
+                )));
+            }
+        });
+        Ok(ArgBytesLike(bytes_like))
+    }
+}
+
+impl Drop for ArgBytesLike {
+    fn drop(&mut self) {
+        if let BytesLike::Bytearray(b) = &self.0 {
+            b.release();
+        }
+    }
+}
+
+impl ArgBytesLike {
+    /// Call `f` with the contents.
+    pub fn with_ref<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        match &self.0 {
+            BytesLike::Bytes(b) => f(b.get_value()),
+            BytesLike::Bytearray(b) => f(&b.inner.borrow().elements),
+            BytesLike::Vec(v) => f(v),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.with_ref(|bytes| bytes.to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with_ref(|bytes| bytes.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An argument that can be any writable bytes-like object: a bytearray or a memoryview of one.
+/// Like with `ArgBytesLike`, the bytearray can't be resized while the argument lives.
+#[derive(Debug)]
+pub struct ArgMutBytesLike(PyByteArrayRef);
+
+impl TryFromObject for ArgMutBytesLike {
     fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
         match_class!(match obj {
-            b @ PyBytes => Ok(PyBytesLike::Bytes(b)),
-            b @ PyByteArray => Ok(PyBytesLike::Bytearray(b)),
-            m @ PyMemoryView => Ok(PyBytesLike::Vec(m.get_obj_value().unwrap())),
-            l @ PyList => Ok(PyBytesLike::Vec(l.get_byte_inner(vm)?.elements)),
+            b @ PyByteArray => {
+                b.export();
+                Ok(ArgMutBytesLike(b))
+            }
+            m @ PyMemoryView => ArgMutBytesLike::try_from_object(vm, m.get_obj()),
             obj => Err(vm.new_type_error(format!(
-                "a bytes-like object is required, not {}",
-                obj.class()
+                "argument must be read-write bytes-like object, not {}",
+                obj.class().name
             ))),
         })
     }
 }
 
-impl PyBytesLike {
-    pub fn to_cow(&self) -> std::borrow::Cow<[u8]> {
-        match self {
-            PyBytesLike::Bytes(b) => b.get_value().into(),
-            PyBytesLike::Bytearray(b) => b.inner.borrow().elements.clone().into(),
-            PyBytesLike::Vec(b) => b.as_slice().into(),
-        }
+impl Drop for ArgMutBytesLike {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+impl ArgMutBytesLike {
+    /// Call `f` with the contents, which it can change but not resize.
+    pub fn with_mut<R>(&self, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        f(&mut self.0.inner.borrow_mut().elements)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.inner.borrow().elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
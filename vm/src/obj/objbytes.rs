@@ -11,6 +11,7 @@ use super::objbyteinner::{
 };
 use super::objint::PyIntRef;
 use super::objiter;
+use super::objsequence;
 use super::objslice::PySliceRef;
 use super::objstr::PyStringRef;
 use super::objtuple::PyTupleRef;
@@ -158,7 +159,7 @@ impl PyBytesRef {
     #[pymethod(name = "__add__")]
     fn add(self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         if let Ok(other) = PyByteInner::try_from_object(vm, other) {
-            Ok(vm.ctx.new_bytearray(self.inner.add(other)))
+            Ok(vm.ctx.new_bytes(self.inner.add(other)))
         } else {
             Ok(vm.ctx.not_implemented())
         }
@@ -427,12 +428,15 @@ impl PyBytesRef {
     }
 
     #[pymethod(name = "__mul__")]
-    fn repeat(self, n: isize, vm: &VirtualMachine) -> PyResult {
-        Ok(vm.ctx.new_bytes(self.inner.repeat(n, vm)?))
+    fn repeat(self, n: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match objsequence::repeat_count(vm, &n)? {
+            Some(n) => Ok(vm.ctx.new_bytes(self.inner.repeat(n, vm)?)),
+            None => Ok(vm.ctx.not_implemented()),
+        }
     }
 
     #[pymethod(name = "__rmul__")]
-    fn rmul(self, n: isize, vm: &VirtualMachine) -> PyResult {
+    fn rmul(self, n: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.repeat(n, vm)
     }
 
@@ -442,7 +446,7 @@ impl PyBytesRef {
     /// Other possible values are 'ignore', 'replace'
     /// For a list of possible encodings,
     /// see https://docs.python.org/3/library/codecs.html#standard-encodings
-    /// currently, only 'utf-8' and 'ascii' emplemented
+    /// currently, only 'utf-8', 'ascii' and 'latin-1' emplemented
     #[pymethod(name = "decode")]
     fn decode(
         self,
@@ -483,6 +487,9 @@ impl PyBytesRef {
                     }
                 }
             }
+            "latin-1" | "latin1" | "iso-8859-1" => {
+                decode_content.extend(self.get_value().iter().map(|&b| b as char));
+            }
             "utf-8" | "utf8" | "" => {
                 let mut p: u32 = 0u32;
                 let mut remaining_bytes = 0;
@@ -2,11 +2,15 @@
 
 */
 
+use std::cell::RefCell;
 use std::fmt;
 use std::ops::Deref;
 
+use super::objiter;
+use super::objstr::PyStringRef;
 use super::objtype::PyClassRef;
 use crate::bytecode;
+use crate::inline_cache::InlineCache;
 use crate::pyobject::{IdProtocol, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
 use crate::vm::VirtualMachine;
 
@@ -14,6 +18,54 @@ pub type PyCodeRef = PyRef<PyCode>;
 
 pub struct PyCode {
     pub code: bytecode::CodeObject,
+    /// The inline caches of the instructions, by `CodeObject::cache_slots`.
+    pub(crate) caches: Box<[RefCell<InlineCache>]>,
+    /// The objects of `CodeObject::constants`, made once, so that every `LoadConst` of a
+    /// constant loads the same object.
+    pub(crate) constants: Box<[PyObjectRef]>,
+    pub(crate) names: InternedNames,
+}
+
+/// The names a code object uses, as the strings interned by `PyContext::intern_string`, so that
+/// the namespaces it stores them in and looks them up in share their key objects with all the
+/// other code.
+pub(crate) struct InternedNames {
+    /// The names of `CodeObject::names`, which the instructions refer to by index.
+    pub names: Box<[PyStringRef]>,
+    pub arg_names: Box<[PyStringRef]>,
+    pub kwonlyarg_names: Box<[PyStringRef]>,
+}
+
+impl InternedNames {
+    fn new(code: &bytecode::CodeObject, ctx: &PyContext) -> Self {
+        let intern_all =
+            |names: &[String]| names.iter().map(|name| ctx.intern_string(name)).collect();
+        InternedNames {
+            names: intern_all(&code.names),
+            arg_names: intern_all(&code.arg_names),
+            kwonlyarg_names: intern_all(&code.kwonlyarg_names),
+        }
+    }
+}
+
+/// The object of a constant, with identifier-like strings interned, like an attribute name
+/// passed to `getattr`.
+fn constant_object(constant: &bytecode::Constant, ctx: &PyContext) -> PyObjectRef {
+    match constant {
+        bytecode::Constant::String { value } if is_identifier_like(value) => {
+            ctx.intern_string(value).into_object()
+        }
+        constant => ctx.unwrap_constant(constant),
+    }
+}
+
+/// Whether a string constant is worth interning, like CPython, which interns the ones that only
+/// consist of ASCII letters, digits and underscores.
+fn is_identifier_like(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
 }
 
 impl Deref for PyCode {
@@ -24,8 +76,22 @@ impl Deref for PyCode {
 }
 
 impl PyCode {
-    pub fn new(code: bytecode::CodeObject) -> PyCode {
-        PyCode { code }
+    pub fn new(code: bytecode::CodeObject, ctx: &PyContext) -> PyCode {
+        let caches = (0..code.cache_slot_count())
+            .map(|_| RefCell::new(InlineCache::Empty))
+            .collect();
+        let constants = code
+            .constants
+            .iter()
+            .map(|constant| constant_object(constant, ctx))
+            .collect();
+        let names = InternedNames::new(&code, ctx);
+        PyCode {
+            code,
+            caches,
+            constants,
+            names,
+        }
     }
 }
 
@@ -70,24 +136,133 @@ impl PyCodeRef {
         self.code.first_line_number
     }
 
+    fn co_posonlyargcount(self, _vm: &VirtualMachine) -> usize {
+        self.code.posonlyarg_count
+    }
+
     fn co_kwonlyargcount(self, _vm: &VirtualMachine) -> usize {
         self.code.kwonlyarg_names.len()
     }
 
     fn co_consts(self, vm: &VirtualMachine) -> PyObjectRef {
-        let consts = self
-            .code
-            .get_constants()
-            .map(|x| vm.ctx.unwrap_constant(x))
+        vm.ctx.new_tuple(self.constants.to_vec())
+    }
+
+    /// The names of the globals, attributes and imported modules the code uses, and, since
+    /// RustPython looks them up by name too, of its local variables.
+    fn co_names(self, vm: &VirtualMachine) -> PyObjectRef {
+        let names = self
+            .names
+            .names
+            .iter()
+            .map(|name| name.clone().into_object())
             .collect();
-        vm.ctx.new_tuple(consts)
+        vm.ctx.new_tuple(names)
     }
 
     fn co_name(self, _vm: &VirtualMachine) -> String {
         self.code.obj_name.clone()
     }
+
+    /// The argument names, in CPython's order. Other local variables aren't known up front.
+    fn co_varnames(self, vm: &VirtualMachine) -> PyObjectRef {
+        let code = &self.code;
+        let mut names: Vec<_> = self
+            .names
+            .arg_names
+            .iter()
+            .chain(self.names.kwonlyarg_names.iter())
+            .map(|name| name.clone().into_object())
+            .collect();
+        for varargs in &[&code.varargs, &code.varkeywords] {
+            if let bytecode::Varargs::Named(name) = varargs {
+                names.push(vm.ctx.intern_string(name).into_object());
+            }
+        }
+        vm.ctx.new_tuple(names)
+    }
+
+    /// The line of the instructions, as `(start, end, line)` for each range of instructions
+    /// `start..end` on the same line, with `None` for the instructions without a line.
+    fn co_lines(self, vm: &VirtualMachine) -> PyResult {
+        let ranges = self
+            .code
+            .line_table
+            .ranges()
+            .map(|range| {
+                vm.ctx.new_tuple(vec![
+                    vm.ctx.new_int(range.start),
+                    vm.ctx.new_int(range.end),
+                    range
+                        .line
+                        .map_or_else(|| vm.get_none(), |line| vm.ctx.new_int(line)),
+                ])
+            })
+            .collect();
+        objiter::get_iter(vm, &vm.ctx.new_list(ranges))
+    }
+
+    /// The location of every instruction, as `(line, end_line, column, end_column)` with the
+    /// column counted from 0, or `None` where it isn't known. Where an instruction's source ends
+    /// isn't kept.
+    fn co_positions(self, vm: &VirtualMachine) -> PyResult {
+        let table = &self.code.line_table;
+        let optional_int = |value: Option<usize>| {
+            value.map_or_else(|| vm.get_none(), |value| vm.ctx.new_int(value))
+        };
+        let positions = table
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                vm.ctx.new_tuple(vec![
+                    optional_int(line),
+                    vm.get_none(),
+                    optional_int(table.column(index).map(|column| column - 1)),
+                    vm.get_none(),
+                ])
+            })
+            .collect();
+        objiter::get_iter(vm, &vm.ctx.new_list(positions))
+    }
+
+    /// The flags in CPython's numbering, so `inspect.CO_*` style checks work.
+    fn co_flags(self, _vm: &VirtualMachine) -> u32 {
+        let code = &self.code;
+        let mut flags = 0;
+        if code.flags.contains(bytecode::CodeFlags::NEW_LOCALS) {
+            flags |= CO_OPTIMIZED | CO_NEWLOCALS;
+        }
+        if code.varargs != bytecode::Varargs::None {
+            flags |= CO_VARARGS;
+        }
+        if code.varkeywords != bytecode::Varargs::None {
+            flags |= CO_VARKEYWORDS;
+        }
+        let is_generator = code.flags.contains(bytecode::CodeFlags::IS_GENERATOR);
+        let is_coroutine = code.flags.contains(bytecode::CodeFlags::IS_COROUTINE);
+        flags |= match (is_generator, is_coroutine) {
+            (true, true) => CO_ASYNC_GENERATOR,
+            (true, false) => CO_GENERATOR,
+            (false, true) => CO_COROUTINE,
+            (false, false) => 0,
+        };
+        if code.flags.contains(bytecode::CodeFlags::FUTURE_ANNOTATIONS) {
+            flags |= CO_FUTURE_ANNOTATIONS;
+        }
+        flags
+    }
 }
 
+const CO_OPTIMIZED: u32 = 0x0001;
+const CO_NEWLOCALS: u32 = 0x0002;
+const CO_VARARGS: u32 = 0x0004;
+const CO_VARKEYWORDS: u32 = 0x0008;
+const CO_GENERATOR: u32 = 0x0020;
+const CO_COROUTINE: u32 = 0x0080;
+const CO_ASYNC_GENERATOR: u32 = 0x0200;
+/// The `compiler_flag` of `__future__.annotations`, also accepted by `compile`.
+pub(crate) const CO_FUTURE_ANNOTATIONS: u32 = 0x0100_0000;
+
 pub fn init(context: &PyContext) {
     extend_class!(context, &context.types.code_type, {
         (slot new) => PyCodeRef::new,
@@ -97,7 +272,13 @@ pub fn init(context: &PyContext) {
         "co_consts" => context.new_property(PyCodeRef::co_consts),
         "co_filename" => context.new_property(PyCodeRef::co_filename),
         "co_firstlineno" => context.new_property(PyCodeRef::co_firstlineno),
+        "co_flags" => context.new_property(PyCodeRef::co_flags),
         "co_kwonlyargcount" => context.new_property(PyCodeRef::co_kwonlyargcount),
+        "co_lines" => context.new_rustfunc(PyCodeRef::co_lines),
         "co_name" => context.new_property(PyCodeRef::co_name),
+        "co_names" => context.new_property(PyCodeRef::co_names),
+        "co_positions" => context.new_rustfunc(PyCodeRef::co_positions),
+        "co_posonlyargcount" => context.new_property(PyCodeRef::co_posonlyargcount),
+        "co_varnames" => context.new_property(PyCodeRef::co_varnames),
     });
 }
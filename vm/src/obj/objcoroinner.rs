@@ -0,0 +1,188 @@
+//! The state shared by generators and coroutines: the suspended frame, and whether it's
+//! currently running or already finished.
+
+use super::objiter::new_stop_iteration;
+use super::objtype::isinstance;
+use crate::exceptions;
+use crate::frame::{ExecutionResult, FrameRef};
+use crate::pyobject::{PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
+
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    Gen,
+    Coroutine,
+}
+
+impl Variant {
+    fn name(self) -> &'static str {
+        match self {
+            Variant::Gen => "generator",
+            Variant::Coroutine => "coroutine",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Coro {
+    frame: FrameRef,
+    running: Cell<bool>,
+    closed: Cell<bool>,
+    variant: Variant,
+}
+
+impl Coro {
+    pub fn new(frame: FrameRef, variant: Variant) -> Self {
+        Coro {
+            frame,
+            running: Cell::new(false),
+            closed: Cell::new(false),
+            variant,
+        }
+    }
+
+    pub(crate) fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(self.frame.as_object());
+    }
+
+    pub fn frame(&self) -> FrameRef {
+        self.frame.clone()
+    }
+
+    pub fn running(&self) -> bool {
+        self.running.get()
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed.get()
+    }
+
+    fn started(&self) -> bool {
+        self.frame.lasti.get() > 0
+    }
+
+    /// Run the frame through `func`, refusing to resume a frame that is already running, and
+    /// marking the frame as finished once it returns or raises.
+    fn run<F>(&self, vm: &VirtualMachine, func: F) -> PyResult<ExecutionResult>
+    where
+        F: FnOnce(FrameRef) -> PyResult<ExecutionResult>,
+    {
+        if self.running.get() {
+            return Err(vm.new_value_error(format!("{} already executing", self.variant.name())));
+        }
+        self.running.set(true);
+        let result = func(self.frame.clone());
+        self.running.set(false);
+        let finished = match result {
+            Ok(ExecutionResult::Yield(_)) => false,
+            _ => true,
+        };
+        if finished {
+            self.closed.set(true);
+        }
+        result
+    }
+
+    fn finished_error(&self, vm: &VirtualMachine) -> PyObjectRef {
+        match self.variant {
+            Variant::Gen => new_stop_iteration(vm),
+            Variant::Coroutine => vm.new_exception(
+                vm.ctx.exceptions.runtime_error.clone(),
+                "cannot reuse already awaited coroutine".to_string(),
+            ),
+        }
+    }
+
+    pub fn send(&self, value: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        if self.running.get() {
+            return Err(vm.new_value_error(format!("{} already executing", self.variant.name())));
+        }
+        if self.closed.get() {
+            return Err(self.finished_error(vm));
+        }
+        if !self.started() && !vm.is_none(&value) {
+            return Err(vm.new_type_error(format!(
+                "can't send non-None value to a just-started {}",
+                self.variant.name()
+            )));
+        }
+        self.run(vm, |frame| {
+            frame.push_value(value);
+            vm.run_frame(frame)
+        })?
+        .into_result(vm)
+    }
+
+    pub fn throw(
+        &self,
+        exc_type: PyObjectRef,
+        exc_val: PyObjectRef,
+        exc_tb: PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        if self.closed.get() {
+            return Err(exceptions::normalize(exc_type, exc_val, vm)?);
+        }
+        self.run(vm, |frame| {
+            vm.with_frame(frame, |frame| {
+                frame.gen_throw(vm, exc_type, exc_val, exc_tb)
+            })
+        })?
+        .into_result(vm)
+    }
+
+    pub fn close(&self, vm: &VirtualMachine) -> PyResult<()> {
+        if self.closed.get() {
+            return Ok(());
+        }
+        if !self.started() {
+            self.closed.set(true);
+            return Ok(());
+        }
+        let result = self.run(vm, |frame| {
+            vm.with_frame(frame, |frame| {
+                frame.gen_throw(
+                    vm,
+                    vm.ctx.exceptions.generator_exit.clone().into_object(),
+                    vm.get_none(),
+                    vm.get_none(),
+                )
+            })
+        });
+        match result {
+            Ok(ExecutionResult::Yield(_)) => Err(vm.new_exception(
+                vm.ctx.exceptions.runtime_error.clone(),
+                format!("{} ignored GeneratorExit", self.variant.name()),
+            )),
+            Err(e) => {
+                if isinstance(&e, &vm.ctx.exceptions.generator_exit) {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The frame as `gi_frame` and `cr_frame` show it, `None` once finished.
+    pub fn frame_attr(&self, vm: &VirtualMachine) -> PyObjectRef {
+        if self.closed.get() {
+            vm.get_none()
+        } else {
+            self.frame.clone().into_object()
+        }
+    }
+
+    /// The iterator the suspended frame is delegating to with `yield from` or `await`.
+    pub fn yield_from_target(&self, vm: &VirtualMachine) -> PyObjectRef {
+        if self.closed.get() || self.running.get() {
+            return vm.get_none();
+        }
+        self.frame
+            .yield_from_target()
+            .unwrap_or_else(|| vm.get_none())
+    }
+}
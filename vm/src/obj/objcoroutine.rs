@@ -1,5 +1,7 @@
-use super::objtype::{isinstance, issubclass, PyClassRef};
-use crate::frame::{ExecutionResult, FrameRef};
+use super::objcode::PyCodeRef;
+use super::objcoroinner::{Coro, Variant};
+use super::objtype::PyClassRef;
+use crate::frame::FrameRef;
 use crate::function::OptionalArg;
 use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue};
 use crate::vm::VirtualMachine;
@@ -9,79 +11,74 @@ pub type PyCoroutineRef = PyRef<PyCoroutine>;
 #[pyclass(name = "coroutine")]
 #[derive(Debug)]
 pub struct PyCoroutine {
-    frame: FrameRef,
+    inner: Coro,
 }
 
 impl PyValue for PyCoroutine {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.types.coroutine_type.clone()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        self.inner.traverse(visit);
+    }
 }
 
 #[pyimpl]
 impl PyCoroutine {
     pub fn new(frame: FrameRef, vm: &VirtualMachine) -> PyCoroutineRef {
-        PyCoroutine { frame }.into_ref(vm)
+        PyCoroutine {
+            inner: Coro::new(frame, Variant::Coroutine),
+        }
+        .into_ref(vm)
     }
 
     #[pymethod]
     pub(crate) fn send(&self, value: PyObjectRef, vm: &VirtualMachine) -> PyResult {
-        self.frame.push_value(value.clone());
-
-        vm.run_frame(self.frame.clone())?.into_result(vm)
+        self.inner.send(value, vm)
     }
 
     #[pymethod]
     fn throw(
         &self,
-        exc_type: PyClassRef,
+        exc_type: PyObjectRef,
         exc_val: OptionalArg,
         exc_tb: OptionalArg,
         vm: &VirtualMachine,
     ) -> PyResult {
-        // TODO what should we do with the other parameters? CPython normalises them with
-        //      PyErr_NormalizeException, do we want to do the same.
-        if !issubclass(&exc_type, &vm.ctx.exceptions.base_exception_type) {
-            return Err(vm.new_type_error("Can't throw non exception".to_string()));
-        }
-        vm.frames.borrow_mut().push(self.frame.clone());
-        let result = self
-            .frame
-            .gen_throw(
-                vm,
-                exc_type,
-                exc_val.unwrap_or(vm.get_none()),
-                exc_tb.unwrap_or(vm.get_none()),
-            )
-            .and_then(|res| res.into_result(vm));
-        vm.frames.borrow_mut().pop();
-        result
+        self.inner.throw(
+            exc_type,
+            exc_val.unwrap_or(vm.get_none()),
+            exc_tb.unwrap_or(vm.get_none()),
+            vm,
+        )
     }
 
     #[pymethod]
     fn close(&self, vm: &VirtualMachine) -> PyResult<()> {
-        vm.frames.borrow_mut().push(self.frame.clone());
-        let result = self.frame.gen_throw(
-            vm,
-            vm.ctx.exceptions.generator_exit.clone(),
-            vm.get_none(),
-            vm.get_none(),
-        );
-        vm.frames.borrow_mut().pop();
-        match result {
-            Ok(ExecutionResult::Yield(_)) => Err(vm.new_exception(
-                vm.ctx.exceptions.runtime_error.clone(),
-                "generator ignored GeneratorExit".to_string(),
-            )),
-            Err(e) => {
-                if isinstance(&e, &vm.ctx.exceptions.generator_exit) {
-                    Ok(())
-                } else {
-                    Err(e)
-                }
-            }
-            _ => Ok(()),
-        }
+        self.inner.close(vm)
+    }
+
+    #[pyproperty]
+    fn cr_code(&self, _vm: &VirtualMachine) -> PyCodeRef {
+        self.inner.frame().code.clone()
+    }
+
+    #[pyproperty]
+    fn cr_frame(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.inner.frame_attr(vm)
+    }
+
+    #[pyproperty]
+    fn cr_running(&self, _vm: &VirtualMachine) -> bool {
+        self.inner.running()
+    }
+
+    #[pyproperty]
+    fn cr_await(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.inner.yield_from_target(vm)
     }
 
     #[pymethod(name = "__await__")]
@@ -97,9 +94,15 @@ pub struct PyCoroutineWrapper {
 }
 
 impl PyValue for PyCoroutineWrapper {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.types.coroutine_wrapper_type.clone()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(self.coro.as_object());
+    }
 }
 
 #[pyimpl]
@@ -122,7 +125,7 @@ impl PyCoroutineWrapper {
     #[pymethod]
     fn throw(
         &self,
-        exc_type: PyClassRef,
+        exc_type: PyObjectRef,
         exc_val: OptionalArg,
         exc_tb: OptionalArg,
         vm: &VirtualMachine,
@@ -5,7 +5,7 @@ use super::objiter;
 use super::objstr;
 use super::objtype::{self, PyClassRef};
 use crate::dictdatatype::{self, DictKey};
-use crate::function::{KwArgs, OptionalArg};
+use crate::function::{KwArgs, OptionalArg, PyFuncArgs};
 use crate::pyobject::{
     IdProtocol, IntoPyObject, ItemProtocol, PyAttributes, PyClassImpl, PyContext, PyIterable,
     PyObjectRef, PyRef, PyResult, PyValue,
@@ -30,27 +30,44 @@ impl fmt::Debug for PyDict {
 }
 
 impl PyValue for PyDict {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.dict_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        if let Ok(entries) = self.entries.try_borrow() {
+            let mut position = 0;
+            while let Some((key, value)) = entries.next_entry(&mut position) {
+                visit(key);
+                visit(value);
+            }
+        }
+    }
+
+    fn clear_references(&self) {
+        if let Ok(mut entries) = self.entries.try_borrow_mut() {
+            entries.clear();
+        }
+    }
 }
 
 // Python dict methods:
 impl PyDictRef {
-    fn new(
-        class: PyClassRef,
+    fn new(class: PyClassRef, _args: PyFuncArgs, vm: &VirtualMachine) -> PyResult<PyDictRef> {
+        PyDict::default().into_ref_with_type(vm, class)
+    }
+
+    /// The items are added here rather than in `__new__`, as in CPython, so that subclasses
+    /// overriding `__init__` (like `OrderedDict`) get to store them their own way.
+    fn init(
+        self,
         dict_obj: OptionalArg<PyObjectRef>,
         kwargs: KwArgs,
         vm: &VirtualMachine,
-    ) -> PyResult<PyDictRef> {
-        let dict = DictContentType::default();
-
-        let entries = RefCell::new(dict);
-        // it's unfortunate that we can't abstract over RefCall, as we should be able to use dict
-        // directly here, but that would require generic associated types
-        PyDictRef::merge(&entries, dict_obj, kwargs, vm)?;
-
-        PyDict { entries }.into_ref_with_type(vm, class)
+    ) -> PyResult<()> {
+        PyDictRef::merge(&self.entries, dict_obj, kwargs, vm)
     }
 
     fn merge(
@@ -191,7 +208,7 @@ impl PyDictRef {
         self.entries.borrow_mut().delete(vm, &key)
     }
 
-    fn clear(self, _vm: &VirtualMachine) {
+    pub(crate) fn clear(self, _vm: &VirtualMachine) {
         self.entries.borrow_mut().clear()
     }
 
@@ -319,7 +336,7 @@ impl PyDictRef {
 
     fn popitem(self, vm: &VirtualMachine) -> PyResult {
         let mut entries = self.entries.borrow_mut();
-        if let Some((key, value)) = entries.pop_front() {
+        if let Some((key, value)) = entries.pop_back() {
             Ok(vm.ctx.new_tuple(vec![key, value]))
         } else {
             let err_msg = vm.new_str("popitem(): dictionary is empty".to_string());
@@ -349,7 +366,7 @@ impl PyDictRef {
     }
 
     fn hash(self, vm: &VirtualMachine) -> PyResult<()> {
-        Err(vm.new_type_error("unhashable type".to_string()))
+        Err(vm.new_type_error("unhashable type: 'dict'".to_string()))
     }
 
     pub fn contains_key<T: IntoPyObject>(&self, key: T, vm: &VirtualMachine) -> bool {
@@ -361,6 +378,11 @@ impl PyDictRef {
         self.entries.borrow().size()
     }
 
+    /// See `Dict::version`.
+    pub fn version(&self) -> u64 {
+        self.entries.borrow().version()
+    }
+
     /// This function can be used to get an item without raising the
     /// KeyError, so we can simply check upon the result being Some
     /// python value, or None.
@@ -597,6 +619,7 @@ pub fn init(context: &PyContext) {
         "__getitem__" => context.new_rustfunc(PyDictRef::inner_getitem),
         "__iter__" => context.new_rustfunc(PyDictRef::iter),
         (slot new) => PyDictRef::new,
+        "__init__" => context.new_rustfunc(PyDictRef::init),
         "__repr__" => context.new_rustfunc(PyDictRef::repr),
         "__setitem__" => context.new_rustfunc(PyDictRef::inner_setitem),
         "__hash__" => context.new_rustfunc(PyDictRef::hash),
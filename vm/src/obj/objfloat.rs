@@ -7,6 +7,7 @@ use super::objbytes;
 use super::objint::{self, PyIntRef};
 use super::objstr::{self, PyStringRef};
 use super::objtype::{self, PyClassRef};
+use crate::format::FormatSpec;
 use crate::function::{OptionalArg, OptionalOption};
 use crate::pyhash;
 use crate::pyobject::{
@@ -414,8 +415,20 @@ impl PyFloat {
         )
     }
 
+    #[pymethod(name = "__format__")]
+    fn format(&self, spec: PyStringRef, vm: &VirtualMachine) -> PyResult<String> {
+        let format_spec = FormatSpec::parse(spec.as_str());
+        match format_spec.format_float(self.value) {
+            Ok(string) => Ok(string),
+            Err(err) => Err(vm.new_value_error(err.to_string())),
+        }
+    }
+
     #[pymethod(name = "__repr__")]
     fn repr(&self, vm: &VirtualMachine) -> String {
+        if self.value.is_nan() {
+            return "nan".to_string();
+        }
         let value = format!("{:e}", self.value);
         if let Some(position) = value.find('e') {
             let significand = &value[..position];
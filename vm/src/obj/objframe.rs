@@ -4,12 +4,23 @@
 
 use super::objcode::PyCodeRef;
 use super::objdict::PyDictRef;
+use super::objproperty::PropertyBuilder;
 use crate::frame::FrameRef;
-use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyResult};
+use crate::pyobject::{IdProtocol, PyClassImpl, PyContext, PyObjectRef, PyResult};
 use crate::vm::VirtualMachine;
 
 pub fn init(context: &PyContext) {
     FrameRef::extend_class(context, &context.types.frame_type);
+
+    // f_trace can be deleted too, which #[pyproperty] doesn't support
+    extend_class!(context, &context.types.frame_type, {
+        "f_trace" =>
+        PropertyBuilder::new(context)
+            .add_getter(FrameRef::f_trace)
+            .add_setter(FrameRef::set_f_trace)
+            .add_deleter(FrameRef::del_f_trace)
+            .create(),
+    });
 }
 
 #[pyimpl]
@@ -21,7 +32,7 @@ impl FrameRef {
 
     #[pymethod(name = "__repr__")]
     fn repr(self, _vm: &VirtualMachine) -> String {
-        "<frame object at .. >".to_string()
+        format!("<frame object at {:#x}>", self.get_id())
     }
 
     #[pyproperty]
@@ -30,8 +41,14 @@ impl FrameRef {
     }
 
     #[pyproperty]
-    fn f_locals(self, _vm: &VirtualMachine) -> PyDictRef {
-        self.scope.get_locals()
+    fn f_locals(self, vm: &VirtualMachine) -> PyResult<PyDictRef> {
+        self.locals(vm)
+    }
+
+    /// The builtins the frame's code sees, which are always the ones of the interpreter.
+    #[pyproperty]
+    fn f_builtins(self, vm: &VirtualMachine) -> PyDictRef {
+        vm.builtins.dict().unwrap().clone()
     }
 
     #[pyproperty]
@@ -41,12 +58,63 @@ impl FrameRef {
 
     #[pyproperty]
     fn f_back(self, vm: &VirtualMachine) -> PyObjectRef {
-        // TODO: how to retrieve the upper stack frame??
-        vm.ctx.none()
+        let frames = vm.frames.borrow();
+        let position = frames.iter().position(|frame| frame.is(&self));
+        match position {
+            Some(index) if index > 0 => frames[index - 1].clone().into_object(),
+            _ => vm.get_none(),
+        }
+    }
+
+    #[pyproperty]
+    fn f_lineno(self, _vm: &VirtualMachine) -> usize {
+        self.current_lineno()
+    }
+
+    fn f_trace(self, vm: &VirtualMachine) -> PyObjectRef {
+        self.trace.borrow().clone().unwrap_or_else(|| vm.get_none())
+    }
+
+    fn set_f_trace(self, value: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let value = if vm.is_none(&value) {
+            None
+        } else {
+            Some(value)
+        };
+        self.trace.replace(value);
+        Ok(vm.get_none())
+    }
+
+    fn del_f_trace(self, vm: &VirtualMachine) -> PyResult {
+        self.trace.replace(None);
+        Ok(vm.get_none())
+    }
+
+    #[pyproperty]
+    fn f_trace_lines(self, _vm: &VirtualMachine) -> bool {
+        self.trace_lines.get()
+    }
+
+    #[pyproperty(setter)]
+    fn set_f_trace_lines(self, value: bool, vm: &VirtualMachine) -> PyResult {
+        self.trace_lines.set(value);
+        Ok(vm.get_none())
+    }
+
+    #[pyproperty]
+    fn f_trace_opcodes(self, _vm: &VirtualMachine) -> bool {
+        self.trace_opcodes.get()
+    }
+
+    #[pyproperty(setter)]
+    fn set_f_trace_opcodes(self, value: bool, vm: &VirtualMachine) -> PyResult {
+        self.trace_opcodes.set(value);
+        Ok(vm.get_none())
     }
 
+    /// The index of the last instruction executed, or -1 before the frame started running.
     #[pyproperty]
-    fn f_lasti(self, vm: &VirtualMachine) -> PyObjectRef {
-        vm.ctx.new_int(self.lasti.get())
+    fn f_lasti(self, _vm: &VirtualMachine) -> isize {
+        self.lasti.get() as isize - 1
     }
 }
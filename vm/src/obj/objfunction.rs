@@ -1,3 +1,4 @@
+use super::objbuiltinfunc::PyBuiltinFunction;
 use super::objcode::PyCodeRef;
 use super::objdict::PyDictRef;
 use super::objstr::PyStringRef;
@@ -36,9 +37,22 @@ impl PyFunction {
 }
 
 impl PyValue for PyFunction {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.function_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(self.code.as_object());
+        self.scope.traverse(visit);
+        if let Some(defaults) = &self.defaults {
+            visit(defaults.as_object());
+        }
+        if let Some(kw_only_defaults) = &self.kw_only_defaults {
+            visit(kw_only_defaults.as_object());
+        }
+    }
 }
 
 impl PyFunctionRef {
@@ -57,6 +71,15 @@ impl PyFunctionRef {
     fn kwdefaults(self, _vm: &VirtualMachine) -> Option<PyDictRef> {
         self.kw_only_defaults.clone()
     }
+
+    fn repr(self, vm: &VirtualMachine) -> PyResult<String> {
+        let qualname = vm.get_attribute(self.as_object().clone(), "__qualname__")?;
+        Ok(format!(
+            "<function {} at 0x{:x}>",
+            vm.to_str(&qualname)?.as_str(),
+            self.get_id()
+        ))
+    }
 }
 
 #[derive(Debug)]
@@ -77,9 +100,16 @@ impl PyMethod {
 }
 
 impl PyValue for PyMethod {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.bound_method_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(&self.object);
+        visit(&self.function);
+    }
 }
 
 pub fn init(context: &PyContext) {
@@ -90,12 +120,15 @@ pub fn init(context: &PyContext) {
         "__code__" => context.new_property(PyFunctionRef::code),
         "__defaults__" => context.new_property(PyFunctionRef::defaults),
         "__kwdefaults__" => context.new_property(PyFunctionRef::kwdefaults),
+        "__repr__" => context.new_rustfunc(PyFunctionRef::repr),
     });
 
     let builtin_function_or_method_type = &context.types.builtin_function_or_method_type;
     extend_class!(context, builtin_function_or_method_type, {
         "__get__" => context.new_rustfunc(bind_method),
         "__call__" => context.new_rustfunc(PyFunctionRef::call),
+        "__name__" => context.new_property(PyBuiltinFunction::name),
+        "__doc__" => context.new_property(PyBuiltinFunction::doc),
     });
 
     let method_type = &context.types.bound_method_type;
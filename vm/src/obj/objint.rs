@@ -7,7 +7,7 @@ use num_integer::Integer;
 use num_traits::{Num, One, Pow, Signed, ToPrimitive, Zero};
 
 use super::objbool::IntoPyBool;
-use super::objbyteinner::PyByteInner;
+use super::objbyteinner::{ArgBytesLike, PyByteInner};
 use super::objbytes::PyBytes;
 use super::objfloat;
 use super::objint;
@@ -145,7 +145,7 @@ fn inner_pow(int1: &PyInt, int2: &PyInt, vm: &VirtualMachine) -> PyResult {
 
 fn inner_mod(int1: &PyInt, int2: &PyInt, vm: &VirtualMachine) -> PyResult {
     if int2.value.is_zero() {
-        Err(vm.new_zero_division_error("integer modulo by zero".to_string()))
+        Err(vm.new_zero_division_error("integer division or modulo by zero".to_string()))
     } else {
         Ok(vm.ctx.new_int(int1.value.mod_floor(&int2.value)))
     }
@@ -153,7 +153,7 @@ fn inner_mod(int1: &PyInt, int2: &PyInt, vm: &VirtualMachine) -> PyResult {
 
 fn inner_floordiv(int1: &PyInt, int2: &PyInt, vm: &VirtualMachine) -> PyResult {
     if int2.value.is_zero() {
-        Err(vm.new_zero_division_error("integer division by zero".to_string()))
+        Err(vm.new_zero_division_error("integer division or modulo by zero".to_string()))
     } else {
         Ok(vm.ctx.new_int(int1.value.div_floor(&int2.value)))
     }
@@ -598,14 +598,19 @@ impl PyInt {
             false
         };
 
+        // Besides bytes-like objects, CPython accepts any iterable of ints
+        let bytes = match ArgBytesLike::try_from_object(vm, args.bytes.clone()) {
+            Ok(bytes) => bytes.to_vec(),
+            Err(_) => PyByteInner::try_from_object(vm, args.bytes)?.elements,
+        };
         let x = match args.byteorder.as_str() {
             "big" => match signed {
-                true => BigInt::from_signed_bytes_be(&args.bytes.elements),
-                false => BigInt::from_bytes_be(Sign::Plus, &args.bytes.elements),
+                true => BigInt::from_signed_bytes_be(&bytes),
+                false => BigInt::from_bytes_be(Sign::Plus, &bytes),
             },
             "little" => match signed {
-                true => BigInt::from_signed_bytes_le(&args.bytes.elements),
-                false => BigInt::from_bytes_le(Sign::Plus, &args.bytes.elements),
+                true => BigInt::from_signed_bytes_le(&bytes),
+                false => BigInt::from_bytes_le(Sign::Plus, &bytes),
             },
             _ => {
                 return Err(
@@ -734,7 +739,7 @@ impl IntOptions {
 #[derive(FromArgs)]
 struct IntFromByteArgs {
     #[pyarg(positional_or_keyword)]
-    bytes: PyByteInner,
+    bytes: PyObjectRef,
     #[pyarg(positional_or_keyword)]
     byteorder: PyStringRef,
     #[pyarg(keyword_only, optional = true)]
@@ -893,7 +898,7 @@ pub fn get_float_value(obj: &PyObjectRef, vm: &VirtualMachine) -> PyResult<f64>
 #[inline]
 fn div_ints(vm: &VirtualMachine, i1: &BigInt, i2: &BigInt) -> PyResult {
     if i2.is_zero() {
-        return Err(vm.new_zero_division_error("integer division by zero".to_string()));
+        return Err(vm.new_zero_division_error("division by zero".to_string()));
     }
 
     if let (Some(f1), Some(f2)) = (i1.to_f64(), i2.to_f64()) {
@@ -4,7 +4,6 @@
 
 use std::cell::Cell;
 
-use super::objtuple::PyTuple;
 use super::objtype::{self, PyClassRef};
 use crate::pyobject::{
     PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject, TypeProtocol,
@@ -22,7 +21,7 @@ pub fn get_iter(vm: &VirtualMachine, iter_target: &PyObjectRef) -> PyResult {
         vm.invoke(&method, vec![])
     } else {
         vm.get_method_or_type_error(iter_target.clone(), "__getitem__", || {
-            format!("Cannot iterate over {}", iter_target.class().name)
+            format!("'{}' object is not iterable", iter_target.class().name)
         })?;
         let obj_iterator = PySequenceIterator {
             position: Cell::new(0),
@@ -70,18 +69,14 @@ pub fn get_all<T: TryFromObject>(vm: &VirtualMachine, iter_obj: &PyObjectRef) ->
 
 pub fn new_stop_iteration(vm: &VirtualMachine) -> PyObjectRef {
     let stop_iteration_type = vm.ctx.exceptions.stop_iteration.clone();
-    vm.new_exception(stop_iteration_type, "End of iterator".to_string())
+    vm.new_empty_exception(stop_iteration_type).unwrap()
 }
 
 pub fn stop_iter_value(vm: &VirtualMachine, exc: &PyObjectRef) -> PyResult {
-    let args = vm.get_attribute(exc.clone(), "args")?;
-    let args: &PyTuple = args.payload().unwrap();
-    let val = args
-        .elements
-        .first()
-        .cloned()
-        .unwrap_or_else(|| vm.get_none());
-    Ok(val)
+    // Subclasses that don't call `StopIteration.__init__` have no value, like `None` in CPython.
+    Ok(vm
+        .get_attribute(exc.clone(), "value")
+        .unwrap_or_else(|_| vm.get_none()))
 }
 
 #[pyclass]
@@ -127,6 +122,53 @@ impl PySequenceIterator {
     }
 }
 
+/// The iterator of `iter(callable, sentinel)`, which calls `callable` until it returns
+/// `sentinel`.
+#[pyclass(name = "callable_iterator")]
+#[derive(Debug)]
+pub struct PyCallableIterator {
+    callable: PyObjectRef,
+    sentinel: PyObjectRef,
+    done: Cell<bool>,
+}
+
+impl PyValue for PyCallableIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.ctx.types.callable_iterator_type.clone()
+    }
+}
+
+#[pyimpl]
+impl PyCallableIterator {
+    pub fn new(callable: PyObjectRef, sentinel: PyObjectRef) -> Self {
+        PyCallableIterator {
+            callable,
+            sentinel,
+            done: Cell::new(false),
+        }
+    }
+
+    #[pymethod(name = "__next__")]
+    fn next(&self, vm: &VirtualMachine) -> PyResult {
+        if self.done.get() {
+            return Err(new_stop_iteration(vm));
+        }
+        let value = vm.invoke(&self.callable, vec![])?;
+        if vm.bool_eq(value.clone(), self.sentinel.clone())? {
+            self.done.set(true);
+            Err(new_stop_iteration(vm))
+        } else {
+            Ok(value)
+        }
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyRef<Self> {
+        zelf
+    }
+}
+
 pub fn init(context: &PyContext) {
     PySequenceIterator::extend_class(context, &context.types.iter_type);
+    PyCallableIterator::extend_class(context, &context.types.callable_iterator_type);
 }
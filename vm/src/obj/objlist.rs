@@ -11,7 +11,8 @@ use super::objbyteinner;
 use super::objint::PyIntRef;
 use super::objiter;
 use super::objsequence::{
-    get_elements_list, get_item, seq_equal, seq_ge, seq_gt, seq_le, seq_lt, seq_mul, SequenceIndex,
+    get_elements_list, get_item, repeat_count, seq_equal, seq_ge, seq_gt, seq_le, seq_lt, seq_mul,
+    SequenceIndex,
 };
 use super::objslice::PySliceRef;
 use super::objtype::{self, PyClassRef};
@@ -44,9 +45,23 @@ impl From<Vec<PyObjectRef>> for PyList {
 }
 
 impl PyValue for PyList {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.list_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        if let Ok(elements) = self.elements.try_borrow() {
+            elements.iter().for_each(visit);
+        }
+    }
+
+    fn clear_references(&self) {
+        if let Ok(mut elements) = self.elements.try_borrow_mut() {
+            elements.clear();
+        }
+    }
 }
 
 impl PyList {
@@ -162,7 +177,7 @@ impl PyListRef {
             let elements = e1.iter().chain(e2.iter()).cloned().collect();
             Ok(vm.ctx.new_list(elements))
         } else {
-            Err(vm.new_type_error(format!("Cannot add {} and {}", self.as_object(), other)))
+            Ok(vm.ctx.not_implemented())
         }
     }
 
@@ -427,17 +442,21 @@ impl PyListRef {
     }
 
     fn hash(self, vm: &VirtualMachine) -> PyResult<()> {
-        Err(vm.new_type_error("unhashable type".to_string()))
+        Err(vm.new_type_error("unhashable type: 'list'".to_string()))
     }
 
-    fn mul(self, counter: isize, vm: &VirtualMachine) -> PyObjectRef {
+    fn mul(self, counter: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let counter = match repeat_count(vm, &counter)? {
+            Some(counter) => counter,
+            None => return Ok(vm.ctx.not_implemented()),
+        };
         let new_elements = seq_mul(&self.elements.borrow().as_slice(), counter)
             .cloned()
             .collect();
-        vm.ctx.new_list(new_elements)
+        Ok(vm.ctx.new_list(new_elements))
     }
 
-    fn rmul(self, counter: isize, vm: &VirtualMachine) -> PyObjectRef {
+    fn rmul(self, counter: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.mul(counter, &vm)
     }
 
@@ -475,8 +494,8 @@ impl PyListRef {
                 return Ok(index);
             }
         }
-        let needle_str = vm.to_str(&needle)?;
-        Err(vm.new_value_error(format!("'{}' is not in list", needle_str.as_str())))
+        let needle_repr = vm.to_repr(&needle)?;
+        Err(vm.new_value_error(format!("{} is not in list", needle_repr.as_str())))
     }
 
     fn pop(self, i: OptionalArg<isize>, vm: &VirtualMachine) -> PyResult {
@@ -507,8 +526,7 @@ impl PyListRef {
             self.elements.borrow_mut().remove(index);
             Ok(())
         } else {
-            let needle_str = vm.to_str(&needle)?;
-            Err(vm.new_value_error(format!("'{}' is not in list", needle_str.as_str())))
+            Err(vm.new_value_error("list.remove(x): x not in list".to_string()))
         }
     }
 
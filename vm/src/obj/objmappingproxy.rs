@@ -80,33 +80,35 @@ impl PyMappingProxy {
         }
     }
 
-    #[pymethod(name = "__iter__")]
-    pub fn iter(&self, vm: &VirtualMachine) -> PyResult {
+    /// The mapping itself, or for a class a dict of its attributes.
+    fn as_mapping(&self, vm: &VirtualMachine) -> PyResult {
         match &self.mapping {
-            MappingProxyInner::Dict(d) => objiter::get_iter(vm, d),
-            MappingProxyInner::Class(_c) => Err(vm.new_type_error("Can't get iter".to_string())),
+            MappingProxyInner::Dict(d) => Ok(d.clone()),
+            MappingProxyInner::Class(c) => {
+                let dict = vm.ctx.new_dict();
+                for (name, value) in c.attributes.borrow().iter() {
+                    dict.set_item(name, value.clone(), vm)?;
+                }
+                Ok(dict.into_object())
+            }
         }
     }
+
+    #[pymethod(name = "__iter__")]
+    pub fn iter(&self, vm: &VirtualMachine) -> PyResult {
+        objiter::get_iter(vm, &self.as_mapping(vm)?)
+    }
     #[pymethod]
     pub fn items(&self, vm: &VirtualMachine) -> PyResult {
-        match &self.mapping {
-            MappingProxyInner::Dict(d) => vm.call_method(d, "items", vec![]),
-            MappingProxyInner::Class(_c) => Err(vm.new_type_error("Can't get iter".to_string())),
-        }
+        vm.call_method(&self.as_mapping(vm)?, "items", vec![])
     }
     #[pymethod]
     pub fn keys(&self, vm: &VirtualMachine) -> PyResult {
-        match &self.mapping {
-            MappingProxyInner::Dict(d) => vm.call_method(d, "keys", vec![]),
-            MappingProxyInner::Class(_c) => Err(vm.new_type_error("Can't get iter".to_string())),
-        }
+        vm.call_method(&self.as_mapping(vm)?, "keys", vec![])
     }
     #[pymethod]
     pub fn values(&self, vm: &VirtualMachine) -> PyResult {
-        match &self.mapping {
-            MappingProxyInner::Dict(d) => vm.call_method(d, "values", vec![]),
-            MappingProxyInner::Class(_c) => Err(vm.new_type_error("Can't get iter".to_string())),
-        }
+        vm.call_method(&self.as_mapping(vm)?, "values", vec![])
     }
 }
 
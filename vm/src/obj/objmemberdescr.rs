@@ -0,0 +1,107 @@
+/*! The `member_descriptor` type, which gives access to the `__slots__` of an instance.
+
+*/
+
+use std::cell::RefCell;
+
+use super::objobject::PyInstance;
+use super::objtype::{self, PyClassRef};
+use crate::function::OptionalArg;
+use crate::pyobject::{
+    PyClassImpl, PyContext, PyObjectRef, PyRef, PyResult, PyValue, TypeProtocol,
+};
+use crate::vm::VirtualMachine;
+
+#[pyclass]
+#[derive(Debug)]
+pub struct PyMemberDescriptor {
+    name: String,
+    /// The class whose `__slots__` declared this member.
+    class: PyClassRef,
+    /// Where instances of `class` store the value of this member.
+    index: usize,
+}
+
+impl PyValue for PyMemberDescriptor {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.ctx.types.member_descriptor_type.clone()
+    }
+}
+
+pub type PyMemberDescriptorRef = PyRef<PyMemberDescriptor>;
+
+impl PyMemberDescriptor {
+    pub fn new(name: String, class: PyClassRef, index: usize) -> Self {
+        PyMemberDescriptor { name, class, index }
+    }
+
+    fn slot<'a>(
+        &self,
+        obj: &'a PyObjectRef,
+        vm: &VirtualMachine,
+    ) -> PyResult<&'a RefCell<Option<PyObjectRef>>> {
+        if objtype::isinstance(obj, &self.class) {
+            if let Some(instance) = obj.payload::<PyInstance>() {
+                if let Some(slot) = instance.slots.get(self.index) {
+                    return Ok(slot);
+                }
+            }
+        }
+        Err(vm.new_type_error(format!(
+            "descriptor '{}' for '{}' objects doesn't apply to '{}' object",
+            self.name,
+            self.class.name,
+            obj.class().name
+        )))
+    }
+}
+
+#[pyimpl]
+impl PyMemberDescriptor {
+    #[pymethod(name = "__get__")]
+    fn get(
+        zelf: PyRef<Self>,
+        obj: PyObjectRef,
+        _owner: OptionalArg<PyClassRef>,
+        vm: &VirtualMachine,
+    ) -> PyResult {
+        if vm.is_none(&obj) {
+            return Ok(zelf.into_object());
+        }
+        let value = zelf.slot(&obj, vm)?.borrow().clone();
+        value.ok_or_else(|| vm.new_attribute_error(zelf.name.clone()))
+    }
+
+    #[pymethod(name = "__set__")]
+    fn set(&self, obj: PyObjectRef, value: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+        self.slot(&obj, vm)?.replace(Some(value));
+        Ok(())
+    }
+
+    #[pymethod(name = "__delete__")]
+    fn delete(&self, obj: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+        match self.slot(&obj, vm)?.replace(None) {
+            Some(_) => Ok(()),
+            None => Err(vm.new_attribute_error(self.name.clone())),
+        }
+    }
+
+    #[pymethod(name = "__repr__")]
+    fn repr(&self, _vm: &VirtualMachine) -> String {
+        format!("<member '{}' of '{}' objects>", self.name, self.class.name)
+    }
+
+    #[pyproperty(name = "__name__")]
+    fn name(&self, _vm: &VirtualMachine) -> String {
+        self.name.clone()
+    }
+
+    #[pyproperty(name = "__objclass__")]
+    fn objclass(&self, _vm: &VirtualMachine) -> PyClassRef {
+        self.class.clone()
+    }
+}
+
+pub fn init(context: &PyContext) {
+    PyMemberDescriptor::extend_class(context, &context.types.member_descriptor_type);
+}
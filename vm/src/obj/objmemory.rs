@@ -20,6 +20,11 @@ impl PyMemoryView {
         try_as_byte(&self.obj_ref)
     }
 
+    /// The object whose memory is viewed.
+    pub fn get_obj(&self) -> PyObjectRef {
+        self.obj_ref.clone()
+    }
+
     #[pyslot(new)]
     fn tp_new(
         cls: PyClassRef,
@@ -50,6 +55,22 @@ impl PyMemoryView {
         self.obj_ref.clone()
     }
 
+    #[pyproperty]
+    fn format(&self, vm: &VirtualMachine) -> PyResult {
+        if self.get_obj_value().is_some() {
+            Ok(vm.new_str("B".to_owned()))
+        } else if self.obj_ref.payload_is::<PyArray>() {
+            vm.get_attribute(self.obj_ref.clone(), "typecode")
+        } else {
+            vm.get_attribute(self.obj_ref.clone(), "format")
+        }
+    }
+
+    #[pyproperty]
+    fn ndim(&self, _vm: &VirtualMachine) -> usize {
+        1
+    }
+
     #[pymethod(name = "__hash__")]
     fn hash(&self, vm: &VirtualMachine) -> PyResult {
         vm.call_method(&self.obj_ref, "__hash__", vec![])
@@ -59,6 +80,15 @@ impl PyMemoryView {
     fn getitem(&self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         vm.call_method(&self.obj_ref, "__getitem__", vec![needle])
     }
+
+    #[pymethod]
+    fn tobytes(&self, vm: &VirtualMachine) -> PyResult {
+        match self.get_obj_value() {
+            Some(value) => Ok(vm.ctx.new_bytes(value)),
+            // arrays and memoryviews know their own bytes
+            None => vm.call_method(&self.obj_ref, "tobytes", vec![]),
+        }
+    }
 }
 
 impl PyValue for PyMemoryView {
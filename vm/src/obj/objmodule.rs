@@ -1,3 +1,4 @@
+use super::objbool;
 use super::objdict::PyDictRef;
 use super::objstr::{PyString, PyStringRef};
 use super::objtype::PyClassRef;
@@ -40,6 +41,17 @@ pub fn init_module_dict(
         .expect("Failed to set __spec__ on module");
 }
 
+/// Whether the module is still executing its body, i.e. `__spec__._initializing` is true.
+pub fn is_initializing(module: &PyObjectRef, vm: &VirtualMachine) -> bool {
+    module
+        .dict()
+        .and_then(|dict| dict.get_item_option("__spec__", vm).ok().flatten())
+        .and_then(|spec| vm.get_attribute(spec, "_initializing").ok())
+        .map_or(false, |initializing| {
+            objbool::boolval(vm, initializing).unwrap_or(false)
+        })
+}
+
 impl PyModuleRef {
     fn new(
         cls: PyClassRef,
@@ -50,7 +62,7 @@ impl PyModuleRef {
         let zelf = PyModule {}.into_ref_with_type(vm, cls)?;
         init_module_dict(
             vm,
-            zelf.as_object().dict.as_ref().unwrap(),
+            zelf.as_object().dict().unwrap(),
             name.into_object(),
             doc.flat_option()
                 .map_or_else(|| vm.get_none(), PyRef::into_object),
@@ -70,14 +82,18 @@ impl PyModuleRef {
     fn getattribute(self, name: PyStringRef, vm: &VirtualMachine) -> PyResult {
         vm.generic_getattribute(self.as_object().clone(), name.clone())?
             .ok_or_else(|| {
-                let module_name = if let Some(name) = self.name(vm) {
-                    format!(" '{}'", name)
-                } else {
-                    "".to_owned()
+                let msg = match self.clone().name(vm) {
+                    Some(module_name) if is_initializing(self.as_object(), vm) => format!(
+                        "partially initialized module '{}' has no attribute '{}' \
+                         (most likely due to a circular import)",
+                        module_name, name
+                    ),
+                    Some(module_name) => {
+                        format!("module '{}' has no attribute '{}'", module_name, name)
+                    }
+                    None => format!("module has no attribute '{}'", name),
                 };
-                vm.new_attribute_error(
-                    format!("module{} has no attribute '{}'", module_name, name,),
-                )
+                vm.new_attribute_error(msg)
             })
     }
 
@@ -1,3 +1,6 @@
+use std::cell::RefCell;
+use std::mem;
+
 use super::objdict::PyDictRef;
 use super::objlist::PyList;
 use super::objproperty::PropertyBuilder;
@@ -12,23 +15,54 @@ use crate::pyobject::{
 use crate::vm::VirtualMachine;
 
 #[derive(Debug)]
-pub struct PyInstance;
+pub struct PyInstance {
+    /// The values of the `__slots__` of the instance's class and its bases, `None` while unset.
+    pub(crate) slots: Box<[RefCell<Option<PyObjectRef>>]>,
+}
+
+impl PyInstance {
+    pub fn new(cls: &PyClassRef) -> Self {
+        PyInstance {
+            slots: (0..cls.layout.slot_count)
+                .map(|_| RefCell::new(None))
+                .collect(),
+        }
+    }
+}
 
 impl PyValue for PyInstance {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.object()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        for slot in self.slots.iter() {
+            if let Ok(slot) = slot.try_borrow() {
+                slot.iter().for_each(&mut *visit);
+            }
+        }
+    }
+
+    fn clear_references(&self) {
+        for slot in self.slots.iter() {
+            if let Ok(mut slot) = slot.try_borrow_mut() {
+                slot.take();
+            }
+        }
+    }
 }
 
 pub fn new_instance(vm: &VirtualMachine, mut args: PyFuncArgs) -> PyResult {
     // more or less __new__ operator
     let cls = PyClassRef::try_from_object(vm, args.shift())?;
-    let dict = if cls.is(&vm.ctx.object()) {
+    let dict = if cls.is(&vm.ctx.object()) || !cls.instance_has_dict() {
         None
     } else {
         Some(vm.ctx.new_dict())
     };
-    Ok(PyObject::new(PyInstance, cls, dict))
+    Ok(PyObject::new(PyInstance::new(&cls), cls, dict))
 }
 
 fn object_eq(_zelf: PyObjectRef, _other: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
@@ -76,8 +110,8 @@ fn object_setattr(
         }
     }
 
-    if let Some(ref dict) = obj.clone().dict {
-        dict.set_item(attr_name.as_str(), value, vm)?;
+    if let Some(dict) = obj.dict() {
+        dict.set_item(attr_name.as_object(), value, vm)?;
         Ok(())
     } else {
         Err(vm.new_attribute_error(format!(
@@ -97,7 +131,7 @@ fn object_delattr(obj: PyObjectRef, attr_name: PyStringRef, vm: &VirtualMachine)
         }
     }
 
-    if let Some(ref dict) = obj.dict {
+    if let Some(dict) = obj.dict() {
         dict.del_item(attr_name.as_str(), vm)?;
         Ok(())
     } else {
@@ -109,6 +143,13 @@ fn object_delattr(obj: PyObjectRef, attr_name: PyStringRef, vm: &VirtualMachine)
     }
 }
 
+fn object_sizeof(obj: PyObjectRef, _vm: &VirtualMachine) -> usize {
+    let slots = obj
+        .payload::<PyInstance>()
+        .map_or(0, |instance| mem::size_of_val(&*instance.slots));
+    mem::size_of_val(&*obj) + slots
+}
+
 fn object_str(zelf: PyObjectRef, vm: &VirtualMachine) -> PyResult {
     vm.call_method(&zelf, "__repr__", vec![])
 }
@@ -127,7 +168,7 @@ pub fn object_dir(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult<PyList> {
     let dict = PyDictRef::from_attributes(attributes, vm)?;
 
     // Get instance attributes:
-    if let Some(object_dict) = &obj.dict {
+    if let Some(object_dict) = obj.dict() {
         vm.invoke(
             &vm.get_attribute(dict.clone().into_object(), "update")?,
             object_dict.clone().into_object(),
@@ -180,6 +221,7 @@ pub fn init(context: &PyContext) {
                 .create(),
         "__dir__" => context.new_rustfunc(object_dir),
         "__hash__" => context.new_rustfunc(object_hash),
+        "__sizeof__" => context.new_rustfunc(object_sizeof),
         "__str__" => context.new_rustfunc(object_str),
         "__repr__" => context.new_rustfunc(object_repr),
         "__format__" => context.new_rustfunc(object_format),
@@ -209,10 +251,13 @@ fn object_class_setter(
 }
 
 fn object_dict(object: PyObjectRef, vm: &VirtualMachine) -> PyResult<PyDictRef> {
-    if let Some(ref dict) = object.dict {
+    if let Some(dict) = object.dict() {
         Ok(dict.clone())
     } else {
-        Err(vm.new_attribute_error("no dictionary.".to_string()))
+        Err(vm.new_attribute_error(format!(
+            "'{}' object has no attribute '__dict__'",
+            object.class().name
+        )))
     }
 }
 
@@ -242,7 +287,7 @@ fn object_reduce_ex(obj: PyObjectRef, proto: usize, vm: &VirtualMachine) -> PyRe
         let object_reduce =
             objtype::class_get_attr(&vm.ctx.types.object_type, "__reduce__").unwrap();
         if !reduce.is(&object_reduce) {
-            return vm.invoke(&reduce, vec![]);
+            return vm.call_method(&obj, "__reduce__", vec![]);
         }
     }
     common_reduce(obj, proto, vm)
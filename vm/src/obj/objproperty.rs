@@ -19,9 +19,15 @@ pub struct PyReadOnlyProperty {
 }
 
 impl PyValue for PyReadOnlyProperty {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.readonly_property_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        visit(&self.getter);
+    }
 }
 
 pub type PyReadOnlyPropertyRef = PyRef<PyReadOnlyProperty>;
@@ -84,9 +90,19 @@ pub struct PyProperty {
 }
 
 impl PyValue for PyProperty {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.property_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        let accessors = [&self.getter, &self.setter, &self.deleter];
+        accessors.iter().copied().flatten().for_each(&mut *visit);
+        if let Ok(doc) = self.doc.try_borrow() {
+            doc.iter().for_each(visit);
+        }
+    }
 }
 
 pub type PyPropertyRef = PyRef<PyProperty>;
@@ -250,6 +266,7 @@ pub struct PropertyBuilder<'a> {
     ctx: &'a PyContext,
     getter: Option<PyObjectRef>,
     setter: Option<PyObjectRef>,
+    deleter: Option<PyObjectRef>,
 }
 
 impl<'a> PropertyBuilder<'a> {
@@ -258,33 +275,40 @@ impl<'a> PropertyBuilder<'a> {
             ctx,
             getter: None,
             setter: None,
+            deleter: None,
         }
     }
 
     pub fn add_getter<I, V, F: IntoPyNativeFunc<I, V>>(self, func: F) -> Self {
         let func = self.ctx.new_rustfunc(func);
         Self {
-            ctx: self.ctx,
             getter: Some(func),
-            setter: self.setter,
+            ..self
         }
     }
 
-    pub fn add_setter<I, V, F: IntoPyNativeFunc<(I, V), PyResult>>(self, func: F) -> Self {
+    pub fn add_setter<I, V, R, F: IntoPyNativeFunc<(I, V), R>>(self, func: F) -> Self {
         let func = self.ctx.new_rustfunc(func);
         Self {
-            ctx: self.ctx,
-            getter: self.getter,
             setter: Some(func),
+            ..self
+        }
+    }
+
+    pub fn add_deleter<I, F: IntoPyNativeFunc<I, PyResult>>(self, func: F) -> Self {
+        let func = self.ctx.new_rustfunc(func);
+        Self {
+            deleter: Some(func),
+            ..self
         }
     }
 
     pub fn create(self) -> PyObjectRef {
-        if self.setter.is_some() {
+        if self.setter.is_some() || self.deleter.is_some() {
             let payload = PyProperty {
                 getter: self.getter.clone(),
                 setter: self.setter.clone(),
-                deleter: None,
+                deleter: self.deleter.clone(),
                 doc: RefCell::new(None),
             };
 
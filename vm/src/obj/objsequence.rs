@@ -207,6 +207,11 @@ pub fn get_item(
     elements: &[PyObjectRef],
     subscript: PyObjectRef,
 ) -> PyResult {
+    let type_name = if sequence.payload::<PyList>().is_some() {
+        "list"
+    } else {
+        "tuple"
+    };
     if let Some(i) = subscript.payload::<PyInt>() {
         return match i.as_bigint().to_i32() {
             Some(value) => {
@@ -214,7 +219,7 @@ pub fn get_item(
                     let obj = elements[pos_index].clone();
                     Ok(obj)
                 } else {
-                    Err(vm.new_index_error("Index out of bounds!".to_string()))
+                    Err(vm.new_index_error(format!("{} index out of range", type_name)))
                 }
             }
             None => {
@@ -241,8 +246,9 @@ pub fn get_item(
         }
     } else {
         Err(vm.new_type_error(format!(
-            "indexing type {:?} with index {:?} is not supported (yet?)",
-            sequence, subscript
+            "{} indices must be integers or slices, not {}",
+            type_name,
+            subscript.class().name
         )))
     }
 }
@@ -365,6 +371,17 @@ impl<'a> Iterator for SeqMul<'a> {
 }
 impl ExactSizeIterator for SeqMul<'_> {}
 
+/// The number of repetitions for `seq * n`, or `None` if `n` isn't an integer, in which case the
+/// sequence should return `NotImplemented` to give `n` the chance to handle the operation.
+pub fn repeat_count(vm: &VirtualMachine, n: &PyObjectRef) -> PyResult<Option<isize>> {
+    match n.payload::<PyInt>() {
+        Some(n) => n.as_bigint().to_isize().map(Some).ok_or_else(|| {
+            vm.new_overflow_error("cannot fit 'int' into an index-sized integer".to_string())
+        }),
+        None => Ok(None),
+    }
+}
+
 pub fn seq_mul(seq: &dyn SimpleSeq, repetitions: isize) -> SeqMul {
     SeqMul {
         seq,
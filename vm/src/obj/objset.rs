@@ -10,8 +10,8 @@ use super::objtype::{self, PyClassRef};
 use crate::dictdatatype;
 use crate::function::OptionalArg;
 use crate::pyobject::{
-    PyClassImpl, PyContext, PyIterable, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject,
-    TypeProtocol,
+    IntoPyObject, PyClassImpl, PyContext, PyIterable, PyObjectRef, PyRef, PyResult, PyValue,
+    TryFromObject, TypeProtocol,
 };
 use crate::vm::{ReprGuard, VirtualMachine};
 
@@ -54,15 +54,35 @@ impl fmt::Debug for PyFrozenSet {
 }
 
 impl PyValue for PySet {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.set_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        if let Ok(inner) = self.inner.try_borrow() {
+            inner.traverse(visit);
+        }
+    }
+
+    fn clear_references(&self) {
+        if let Ok(mut inner) = self.inner.try_borrow_mut() {
+            inner.clear();
+        }
+    }
 }
 
 impl PyValue for PyFrozenSet {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.frozenset_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        self.inner.traverse(visit);
+    }
 }
 
 #[derive(Default, Clone)]
@@ -71,6 +91,13 @@ struct PySetInner {
 }
 
 impl PySetInner {
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        let mut position = 0;
+        while let Some((key, _)) = self.content.next_entry(&mut position) {
+            visit(key);
+        }
+    }
+
     fn new(iterable: PyIterable, vm: &VirtualMachine) -> PyResult<PySetInner> {
         let mut set = PySetInner::default();
         for item in iterable.iter(vm)? {
@@ -438,42 +465,42 @@ impl PySet {
     }
 
     #[pymethod(name = "__or__")]
-    fn or(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.union(other.iterable, vm)
+    fn or(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.union(other, vm))
     }
 
     #[pymethod(name = "__ror__")]
-    fn ror(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn ror(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.or(other, vm)
     }
 
     #[pymethod(name = "__and__")]
-    fn and(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.intersection(other.iterable, vm)
+    fn and(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.intersection(other, vm))
     }
 
     #[pymethod(name = "__rand__")]
-    fn rand(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rand(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.and(other, vm)
     }
 
     #[pymethod(name = "__sub__")]
-    fn sub(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.difference(other.iterable, vm)
+    fn sub(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.difference(other, vm))
     }
 
     #[pymethod(name = "__rsub__")]
-    fn rsub(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rsub(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.sub(other, vm)
     }
 
     #[pymethod(name = "__xor__")]
-    fn xor(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.symmetric_difference(other.iterable, vm)
+    fn xor(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.symmetric_difference(other, vm))
     }
 
     #[pymethod(name = "__rxor__")]
-    fn rxor(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rxor(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.xor(other, vm)
     }
 
@@ -523,9 +550,11 @@ impl PySet {
     }
 
     #[pymethod(name = "__ior__")]
-    fn ior(zelf: PyRef<Self>, iterable: SetIterable, vm: &VirtualMachine) -> PyResult {
-        zelf.inner.borrow_mut().update(iterable.iterable, vm)?;
-        Ok(zelf.as_object().clone())
+    fn ior(zelf: PyRef<Self>, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |iterable| {
+            zelf.inner.borrow_mut().update(iterable, vm)?;
+            Ok(zelf.as_object().clone())
+        })
     }
 
     #[pymethod]
@@ -541,11 +570,11 @@ impl PySet {
     }
 
     #[pymethod(name = "__iand__")]
-    fn iand(zelf: PyRef<Self>, iterable: SetIterable, vm: &VirtualMachine) -> PyResult {
-        zelf.inner
-            .borrow_mut()
-            .intersection_update(iterable.iterable, vm)?;
-        Ok(zelf.as_object().clone())
+    fn iand(zelf: PyRef<Self>, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |iterable| {
+            zelf.inner.borrow_mut().intersection_update(iterable, vm)?;
+            Ok(zelf.as_object().clone())
+        })
     }
 
     #[pymethod]
@@ -555,11 +584,11 @@ impl PySet {
     }
 
     #[pymethod(name = "__isub__")]
-    fn isub(zelf: PyRef<Self>, iterable: SetIterable, vm: &VirtualMachine) -> PyResult {
-        zelf.inner
-            .borrow_mut()
-            .difference_update(iterable.iterable, vm)?;
-        Ok(zelf.as_object().clone())
+    fn isub(zelf: PyRef<Self>, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |iterable| {
+            zelf.inner.borrow_mut().difference_update(iterable, vm)?;
+            Ok(zelf.as_object().clone())
+        })
     }
 
     #[pymethod]
@@ -571,16 +600,18 @@ impl PySet {
     }
 
     #[pymethod(name = "__ixor__")]
-    fn ixor(zelf: PyRef<Self>, iterable: SetIterable, vm: &VirtualMachine) -> PyResult {
-        zelf.inner
-            .borrow_mut()
-            .symmetric_difference_update(iterable.iterable, vm)?;
-        Ok(zelf.as_object().clone())
+    fn ixor(zelf: PyRef<Self>, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |iterable| {
+            zelf.inner
+                .borrow_mut()
+                .symmetric_difference_update(iterable, vm)?;
+            Ok(zelf.as_object().clone())
+        })
     }
 
     #[pymethod(name = "__hash__")]
     fn hash(&self, vm: &VirtualMachine) -> PyResult<()> {
-        Err(vm.new_type_error("unhashable type".to_string()))
+        Err(vm.new_type_error("unhashable type: 'set'".to_string()))
     }
 }
 
@@ -694,42 +725,42 @@ impl PyFrozenSet {
     }
 
     #[pymethod(name = "__or__")]
-    fn or(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.union(other.iterable, vm)
+    fn or(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.union(other, vm))
     }
 
     #[pymethod(name = "__ror__")]
-    fn ror(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn ror(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.or(other, vm)
     }
 
     #[pymethod(name = "__and__")]
-    fn and(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.intersection(other.iterable, vm)
+    fn and(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.intersection(other, vm))
     }
 
     #[pymethod(name = "__rand__")]
-    fn rand(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rand(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.and(other, vm)
     }
 
     #[pymethod(name = "__sub__")]
-    fn sub(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.difference(other.iterable, vm)
+    fn sub(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.difference(other, vm))
     }
 
     #[pymethod(name = "__rsub__")]
-    fn rsub(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rsub(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.sub(other, vm)
     }
 
     #[pymethod(name = "__xor__")]
-    fn xor(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
-        self.symmetric_difference(other.iterable, vm)
+    fn xor(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        set_operator(other, vm, |other| self.symmetric_difference(other, vm))
     }
 
     #[pymethod(name = "__rxor__")]
-    fn rxor(&self, other: SetIterable, vm: &VirtualMachine) -> PyResult<Self> {
+    fn rxor(&self, other: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.xor(other, vm)
     }
 
@@ -752,24 +783,19 @@ impl PyFrozenSet {
     }
 }
 
-struct SetIterable {
-    iterable: PyIterable,
-}
-
-impl TryFromObject for SetIterable {
-    fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
-        if objtype::issubclass(&obj.class(), &vm.ctx.set_type())
-            || objtype::issubclass(&obj.class(), &vm.ctx.frozenset_type())
-        {
-            Ok(SetIterable {
-                iterable: PyIterable::try_from_object(vm, obj)?,
-            })
-        } else {
-            Err(vm.new_type_error(format!(
-                "{} is not a subtype of set or frozenset",
-                obj.class()
-            )))
-        }
+/// Applies a set operator to `other` if it is a set or frozenset, and otherwise returns
+/// `NotImplemented` so that the other operand gets the chance to handle the operation.
+fn set_operator<T, F>(other: PyObjectRef, vm: &VirtualMachine, op: F) -> PyResult
+where
+    T: IntoPyObject,
+    F: FnOnce(PyIterable) -> PyResult<T>,
+{
+    if objtype::issubclass(&other.class(), &vm.ctx.set_type())
+        || objtype::issubclass(&other.class(), &vm.ctx.frozenset_type())
+    {
+        op(PyIterable::try_from_object(vm, other)?)?.into_pyobject(vm)
+    } else {
+        Ok(vm.ctx.not_implemented())
     }
 }
 
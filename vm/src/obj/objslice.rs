@@ -219,7 +219,7 @@ impl PySlice {
 
     #[pymethod(name = "__hash__")]
     fn hash(&self, vm: &VirtualMachine) -> PyResult<()> {
-        Err(vm.new_type_error("unhashable type".to_string()))
+        Err(vm.new_type_error("unhashable type: 'slice'".to_string()))
     }
 }
 
@@ -15,13 +15,14 @@ use unicode_casing::CharExt;
 use unicode_categories::UnicodeCategories;
 use unicode_xid::UnicodeXID;
 
+use super::objbyteinner::ArgBytesLike;
 use super::objbytes::PyBytes;
 use super::objdict::PyDict;
 use super::objfloat;
 use super::objint::{self, PyInt, PyIntRef};
 use super::objiter;
 use super::objnone::PyNone;
-use super::objsequence::PySliceableSequence;
+use super::objsequence::{self, PySliceableSequence};
 use super::objslice::PySliceRef;
 use super::objtuple;
 use super::objtype::{self, PyClassRef};
@@ -29,7 +30,7 @@ use crate::cformat::{
     CFormatPart, CFormatPreconversor, CFormatQuantity, CFormatSpec, CFormatString, CFormatType,
     CNumberType,
 };
-use crate::format::{FormatParseError, FormatPart, FormatPreconversor, FormatString};
+use crate::format::{FormatParseError, FormatPart, FormatPreconversor, FormatSpec, FormatString};
 use crate::function::{single_or_tuple_any, OptionalArg, PyFuncArgs};
 use crate::pyhash;
 use crate::pyobject::{
@@ -170,6 +171,12 @@ impl PyStringReverseIterator {
     }
 }
 
+#[derive(FromArgs)]
+struct SplitLinesArgs {
+    #[pyarg(positional_or_keyword, default = "false")]
+    keepends: bool,
+}
+
 #[pyimpl]
 impl PyString {
     // TODO: should with following format
@@ -179,9 +186,26 @@ impl PyString {
     fn tp_new(
         cls: PyClassRef,
         object: OptionalArg<PyObjectRef>,
+        encoding: OptionalArg<PyStringRef>,
+        errors: OptionalArg<PyStringRef>,
         vm: &VirtualMachine,
     ) -> PyResult<PyStringRef> {
         let string = match object {
+            // With an encoding or errors, a bytes-like object is decoded
+            OptionalArg::Present(input) if encoding.is_present() || errors.is_present() => {
+                let data = ArgBytesLike::try_from_object(vm, input.clone()).map_err(|_| {
+                    vm.new_type_error(format!(
+                        "decoding to str: need a bytes-like object, {} found",
+                        input.class().name
+                    ))
+                })?;
+                let bytes = data.with_ref(|bytes| vm.ctx.new_bytes(bytes.to_vec()));
+                let encoding = encoding
+                    .map_or_else(|| vm.new_str("utf-8".to_owned()), PyStringRef::into_object);
+                let errors = errors
+                    .map_or_else(|| vm.new_str("strict".to_owned()), PyStringRef::into_object);
+                vm.call_method(&bytes, "decode", vec![encoding, errors])?
+            }
             OptionalArg::Present(ref input) => vm.to_str(input)?.into_object(),
             OptionalArg::Missing => vm.new_str("".to_string()),
         };
@@ -193,11 +217,11 @@ impl PyString {
         }
     }
     #[pymethod(name = "__add__")]
-    fn add(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult<String> {
+    fn add(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         if objtype::isinstance(&rhs, &vm.ctx.str_type()) {
-            Ok(format!("{}{}", self.value, get_value(&rhs)))
+            Ok(vm.new_str(format!("{}{}", self.value, get_value(&rhs))))
         } else {
-            Err(vm.new_type_error(format!("Cannot add {} and {}", self, rhs)))
+            Ok(vm.ctx.not_implemented())
         }
     }
 
@@ -230,7 +254,9 @@ impl PyString {
     }
 
     #[pymethod(name = "__getitem__")]
-    fn getitem(&self, needle: Either<PyIntRef, PySliceRef>, vm: &VirtualMachine) -> PyResult {
+    fn getitem(&self, needle: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let needle = Either::<PyIntRef, PySliceRef>::try_from_object(vm, needle)
+            .map_err(|_| vm.new_type_error("string indices must be integers".to_string()))?;
         match needle {
             Either::A(pos) => match pos.as_bigint().to_isize() {
                 Some(pos) => {
@@ -261,43 +287,48 @@ impl PyString {
     }
 
     #[pymethod(name = "__gt__")]
-    fn gt(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+    fn gt(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
         if objtype::isinstance(&rhs, &vm.ctx.str_type()) {
-            Ok(self.value > get_value(&rhs))
+            vm.new_bool(self.value > get_value(&rhs))
         } else {
-            Err(vm.new_type_error(format!("Cannot compare {} and {}", self, rhs)))
+            vm.ctx.not_implemented()
         }
     }
 
     #[pymethod(name = "__ge__")]
-    fn ge(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+    fn ge(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
         if objtype::isinstance(&rhs, &vm.ctx.str_type()) {
-            Ok(self.value >= get_value(&rhs))
+            vm.new_bool(self.value >= get_value(&rhs))
         } else {
-            Err(vm.new_type_error(format!("Cannot compare {} and {}", self, rhs)))
+            vm.ctx.not_implemented()
         }
     }
 
     #[pymethod(name = "__lt__")]
-    fn lt(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+    fn lt(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
         if objtype::isinstance(&rhs, &vm.ctx.str_type()) {
-            Ok(self.value < get_value(&rhs))
+            vm.new_bool(self.value < get_value(&rhs))
         } else {
-            Err(vm.new_type_error(format!("Cannot compare {} and {}", self, rhs)))
+            vm.ctx.not_implemented()
         }
     }
 
     #[pymethod(name = "__le__")]
-    fn le(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+    fn le(&self, rhs: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
         if objtype::isinstance(&rhs, &vm.ctx.str_type()) {
-            Ok(self.value <= get_value(&rhs))
+            vm.new_bool(self.value <= get_value(&rhs))
         } else {
-            Err(vm.new_type_error(format!("Cannot compare {} and {}", self, rhs)))
+            vm.ctx.not_implemented()
         }
     }
 
     #[pymethod(name = "__hash__")]
     fn hash(&self, _vm: &VirtualMachine) -> pyhash::PyHash {
+        self.hash_value()
+    }
+
+    /// The hash of the string, computed on first use.
+    pub(crate) fn hash_value(&self) -> pyhash::PyHash {
         match self.hash.get() {
             Some(hash) => hash,
             None => {
@@ -319,18 +350,15 @@ impl PyString {
     }
 
     #[pymethod(name = "__mul__")]
-    fn mul(&self, multiplier: isize, vm: &VirtualMachine) -> PyResult<String> {
-        multiplier
-            .max(0)
-            .to_usize()
-            .map(|multiplier| self.value.repeat(multiplier))
-            .ok_or_else(|| {
-                vm.new_overflow_error("cannot fit 'int' into an index-sized integer".to_string())
-            })
+    fn mul(&self, multiplier: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        match objsequence::repeat_count(vm, &multiplier)? {
+            Some(multiplier) => Ok(vm.new_str(self.value.repeat(multiplier.max(0) as usize))),
+            None => Ok(vm.ctx.not_implemented()),
+        }
     }
 
     #[pymethod(name = "__rmul__")]
-    fn rmul(&self, val: isize, vm: &VirtualMachine) -> PyResult<String> {
+    fn rmul(&self, val: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.mul(val, vm)
     }
 
@@ -339,6 +367,15 @@ impl PyString {
         zelf
     }
 
+    #[pymethod(name = "__format__")]
+    fn format_spec(&self, spec: PyStringRef, vm: &VirtualMachine) -> PyResult<String> {
+        let format_spec = FormatSpec::parse(spec.as_str());
+        match format_spec.format_string(&self.value) {
+            Ok(string) => Ok(string),
+            Err(err) => Err(vm.new_value_error(err.to_string())),
+        }
+    }
+
     #[pymethod(name = "__repr__")]
     fn repr(&self, _vm: &VirtualMachine) -> String {
         let value = &self.value;
@@ -759,14 +796,32 @@ impl PyString {
         !self.value.is_empty() && self.value.chars().all(|c| c.is_ascii())
     }
 
-    // doesn't implement keep new line delimiter just yet
     #[pymethod]
-    fn splitlines(&self, vm: &VirtualMachine) -> PyObjectRef {
-        let elements = self
-            .value
-            .split('\n')
-            .map(|e| vm.ctx.new_str(e.to_string()))
-            .collect();
+    fn splitlines(&self, args: SplitLinesArgs, vm: &VirtualMachine) -> PyObjectRef {
+        let keepends = args.keepends;
+        let mut elements = vec![];
+        let mut chars = self.value.char_indices().peekable();
+        let mut start = 0;
+        while let Some((index, ch)) = chars.next() {
+            let mut end = index + ch.len_utf8();
+            match ch {
+                '\r' => {
+                    if let Some((_, '\n')) = chars.peek() {
+                        chars.next();
+                        end += 1;
+                    }
+                }
+                '\n' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}'
+                | '\u{2029}' => {}
+                _ => continue,
+            }
+            let line_end = if keepends { end } else { index };
+            elements.push(vm.ctx.new_str(self.value[start..line_end].to_string()));
+            start = end;
+        }
+        if start < self.value.len() {
+            elements.push(vm.ctx.new_str(self.value[start..].to_string()));
+        }
         vm.ctx.new_list(elements)
     }
 
@@ -1063,7 +1118,7 @@ impl PyString {
     }
 
     #[pymethod]
-    fn isidentifier(&self, _vm: &VirtualMachine) -> bool {
+    pub(crate) fn isidentifier(&self, _vm: &VirtualMachine) -> bool {
         let mut chars = self.value.chars();
         let is_identifier_start = match chars.next() {
             Some('_') => true,
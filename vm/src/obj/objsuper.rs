@@ -87,7 +87,10 @@ impl PySuper {
         let py_type = if let OptionalArg::Present(ty) = py_type {
             ty.clone()
         } else {
-            match vm.current_scope().load_cell(vm, "__class__") {
+            match vm
+                .current_scope()
+                .load_cell(vm, &vm.ctx.intern_string("__class__"))
+            {
                 Some(obj) => PyClassRef::try_from_object(vm, obj)?,
                 _ => {
                     return Err(vm.new_type_error(
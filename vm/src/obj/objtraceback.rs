@@ -1,6 +1,6 @@
 use crate::frame::FrameRef;
 use crate::obj::objtype::PyClassRef;
-use crate::pyobject::{PyClassImpl, PyContext, PyRef, PyValue};
+use crate::pyobject::{PyClassImpl, PyContext, PyObjectRef, PyRef, PyValue};
 use crate::vm::VirtualMachine;
 
 #[pyclass]
@@ -15,9 +15,18 @@ pub struct PyTraceback {
 pub type PyTracebackRef = PyRef<PyTraceback>;
 
 impl PyValue for PyTraceback {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.traceback_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        if let Some(next) = &self.next {
+            visit(next.as_object());
+        }
+        visit(self.frame.as_object());
+    }
 }
 
 #[pyimpl]
@@ -3,7 +3,7 @@ use std::fmt;
 
 use super::objiter;
 use super::objsequence::{
-    get_elements_tuple, get_item, seq_equal, seq_ge, seq_gt, seq_le, seq_lt, seq_mul,
+    get_elements_tuple, get_item, repeat_count, seq_equal, seq_ge, seq_gt, seq_le, seq_lt, seq_mul,
 };
 use super::objtype::{self, PyClassRef};
 use crate::function::OptionalArg;
@@ -37,9 +37,15 @@ impl From<Vec<PyObjectRef>> for PyTuple {
 }
 
 impl PyValue for PyTuple {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.tuple_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        self.elements.iter().for_each(visit);
+    }
 }
 
 macro_rules! impl_intopyobj_tuple {
@@ -207,15 +213,19 @@ impl PyTuple {
     }
 
     #[pymethod(name = "__mul__")]
-    fn mul(&self, counter: isize, vm: &VirtualMachine) -> PyObjectRef {
+    fn mul(&self, counter: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+        let counter = match repeat_count(vm, &counter)? {
+            Some(counter) => counter,
+            None => return Ok(vm.ctx.not_implemented()),
+        };
         let new_elements = seq_mul(&self.elements.as_slice(), counter)
             .cloned()
             .collect();
-        vm.ctx.new_tuple(new_elements)
+        Ok(vm.ctx.new_tuple(new_elements))
     }
 
     #[pymethod(name = "__rmul__")]
-    fn rmul(&self, counter: isize, vm: &VirtualMachine) -> PyObjectRef {
+    fn rmul(&self, counter: PyObjectRef, vm: &VirtualMachine) -> PyResult {
         self.mul(counter, vm)
     }
 
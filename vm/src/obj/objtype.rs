@@ -1,12 +1,14 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use super::objdict::PyDictRef;
 use super::objlist::PyList;
 use super::objmappingproxy::PyMappingProxy;
+use super::objmemberdescr::PyMemberDescriptor;
 use super::objproperty::PropertyBuilder;
-use super::objstr::PyStringRef;
+use super::objstr::{PyString, PyStringRef};
 use super::objtuple::PyTuple;
 use super::objweakref::PyWeak;
 use crate::function::{PyFuncArgs, PyNativeFunc};
@@ -24,6 +26,10 @@ pub struct PyClass {
     pub subclasses: RefCell<Vec<PyWeak>>,
     pub attributes: RefCell<PyAttributes>,
     pub slots: RefCell<PyClassSlots>,
+    pub layout: InstanceLayout,
+    /// Changes whenever the attributes of this type or one of its bases change, see
+    /// `PyClass::version_tag`.
+    pub version_tag: Cell<u64>,
 }
 
 #[derive(Default)]
@@ -36,6 +42,22 @@ impl fmt::Debug for PyClassSlots {
     }
 }
 
+/// What the instances of a type store besides their payload, which `__slots__` decides for
+/// classes.
+#[derive(Debug, Default)]
+pub struct InstanceLayout {
+    /// Whether the type was made by a class statement or `type()`, rather than built in.
+    pub heap_type: bool,
+    /// Whether instances of a heap type get a `__dict__`.
+    pub dict: bool,
+    /// Whether instances of a heap type can be weakly referenced.
+    pub weakref: bool,
+    /// How many `__slots__` values instances store, counting those of the bases.
+    pub slot_count: usize,
+    /// Whether the type's own `__slots__` added values to store.
+    pub own_slots: bool,
+}
+
 impl fmt::Display for PyClass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.name, f)
@@ -44,10 +66,66 @@ impl fmt::Display for PyClass {
 
 pub type PyClassRef = PyRef<PyClass>;
 
+static NEXT_VERSION_TAG: AtomicU64 = AtomicU64::new(1);
+
+/// A version tag that no type has had before.
+pub fn new_version_tag() -> u64 {
+    NEXT_VERSION_TAG.fetch_add(1, Ordering::Relaxed)
+}
+
+impl PyClass {
+    /// Whether instances of this type get a `__dict__`. Builtin types decide that for their own
+    /// instances, but not for those of their subclasses.
+    pub fn instance_has_dict(&self) -> bool {
+        !self.layout.heap_type || self.layout.dict
+    }
+
+    /// Whether instances of this type can be weakly referenced.
+    pub fn instance_has_weakref(&self) -> bool {
+        !self.layout.heap_type || self.layout.weakref
+    }
+
+    /// A number that no other type shares, and that changes whenever an attribute is set on or
+    /// deleted from this type or one of its bases. Inline caches use it to tell whether looking
+    /// up an attribute on the type can give a different result.
+    pub fn version_tag(&self) -> u64 {
+        self.version_tag.get()
+    }
+
+    /// Give this type and all of its subclasses new version tags.
+    fn modified(&self) {
+        self.version_tag.set(new_version_tag());
+        for subclass in self.subclasses.borrow().iter() {
+            if let Some(subclass) = subclass.upgrade() {
+                if let Some(subclass) = subclass.payload::<PyClass>() {
+                    subclass.modified();
+                }
+            }
+        }
+    }
+}
+
 impl PyValue for PyClass {
+    const HAVE_TRAVERSE: bool = true;
+
     fn class(vm: &VirtualMachine) -> PyClassRef {
         vm.ctx.type_type()
     }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        for class in self.bases.iter().chain(&self.mro) {
+            visit(class.as_object());
+        }
+        if let Ok(attributes) = self.attributes.try_borrow() {
+            attributes.values().for_each(visit);
+        }
+    }
+
+    fn clear_references(&self) {
+        if let Ok(mut attributes) = self.attributes.try_borrow_mut() {
+            attributes.clear();
+        }
+    }
 }
 
 struct IterMro<'a> {
@@ -84,6 +162,11 @@ impl PyClassRef {
         }
     }
 
+    /// The type that added the last `__slots__` values to the layout of this one's instances.
+    fn slots_base(&self) -> Option<&PyClassRef> {
+        self.iter_mro().find(|cls| cls.layout.own_slots)
+    }
+
     fn mro(self, _vm: &VirtualMachine) -> PyTuple {
         let elements: Vec<PyObjectRef> =
             _mro(&self).iter().map(|x| x.as_object().clone()).collect();
@@ -196,6 +279,7 @@ impl PyClassRef {
         self.attributes
             .borrow_mut()
             .insert(attr_name.to_string(), value);
+        self.modified();
         Ok(())
     }
 
@@ -210,6 +294,7 @@ impl PyClassRef {
 
         if class_get_attr(&self, attr_name.as_str()).is_some() {
             self.attributes.borrow_mut().remove(attr_name.as_str());
+            self.modified();
             Ok(())
         } else {
             Err(vm.new_attribute_error(attr_name.as_str().to_string()))
@@ -221,6 +306,7 @@ impl PyClassRef {
         self.attributes
             .borrow_mut()
             .insert(attr_name.to_string(), value.into());
+        self.modified();
     }
 
     fn subclasses(self, _vm: &VirtualMachine) -> PyList {
@@ -340,7 +426,121 @@ fn type_new_slot(metatype: PyClassRef, args: PyFuncArgs, vm: &VirtualMachine) ->
         ));
     }
 
-    new(winner, name.as_str(), bases, attributes).map(Into::into)
+    let (layout, slot_names) = instance_layout(&bases, &attributes, vm)?;
+    let first_slot = layout.slot_count - slot_names.len();
+    let new_type = new_with_layout(winner, name.as_str(), bases, attributes, layout)?;
+    for (index, slot_name) in slot_names.into_iter().enumerate() {
+        let descriptor =
+            PyMemberDescriptor::new(slot_name.clone(), new_type.clone(), first_slot + index);
+        new_type
+            .attributes
+            .borrow_mut()
+            .insert(slot_name, descriptor.into_ref(vm).into_object());
+    }
+    Ok(new_type.into_object())
+}
+
+/// Work out the layout of the instances of a new class from its bases and `__slots__`, along
+/// with the names of the slots it adds to it.
+fn instance_layout(
+    bases: &[PyClassRef],
+    attributes: &PyAttributes,
+    vm: &VirtualMachine,
+) -> PyResult<(InstanceLayout, Vec<String>)> {
+    let slots_bases: Vec<&PyClassRef> = bases.iter().filter_map(|base| base.slots_base()).collect();
+    let slots_base = slots_bases
+        .iter()
+        .find(|cls| slots_bases.iter().all(|other| issubclass(cls, other)));
+    let base_slot_count = match slots_base {
+        Some(cls) => cls.layout.slot_count,
+        None if slots_bases.is_empty() => 0,
+        None => {
+            return Err(
+                vm.new_type_error("multiple bases have instance lay-out conflict".to_string())
+            )
+        }
+    };
+    // The instances of a subclass of a built-in type other than object have the payload of
+    // that type, which can't hold slot values, so they're kept in the instance dict instead.
+    let native_base = bases.iter().any(|base| {
+        base.iter_mro()
+            .any(|cls| !cls.layout.heap_type && !cls.is(&vm.ctx.object()))
+    });
+    if native_base && base_slot_count > 0 {
+        return Err(vm.new_type_error("multiple bases have instance lay-out conflict".to_string()));
+    }
+    let mut layout = InstanceLayout {
+        heap_type: true,
+        dict: true,
+        weakref: true,
+        slot_count: base_slot_count,
+        own_slots: false,
+    };
+
+    let slots = match attributes.get("__slots__") {
+        Some(slots) => slots,
+        None => return Ok((layout, vec![])),
+    };
+    let items = if isinstance(slots, &vm.ctx.str_type()) {
+        vec![slots.clone()]
+    } else {
+        vm.extract_elements(slots)?
+    };
+
+    layout.dict = bases
+        .iter()
+        .any(|base| base.layout.heap_type && base.layout.dict);
+    layout.weakref = bases
+        .iter()
+        .any(|base| base.layout.heap_type && base.layout.weakref);
+    let mut slot_names = vec![];
+    for item in items {
+        let item = item.downcast::<PyString>().map_err(|item| {
+            vm.new_type_error(format!(
+                "__slots__ items must be strings, not '{}'",
+                item.class().name
+            ))
+        })?;
+        if !item.isidentifier(vm) {
+            return Err(vm.new_type_error("__slots__ must be identifiers".to_string()));
+        }
+        match item.as_str() {
+            "__dict__" => {
+                if layout.dict {
+                    return Err(vm.new_type_error(
+                        "__dict__ slot disallowed: we already got one".to_string(),
+                    ));
+                }
+                layout.dict = true;
+            }
+            "__weakref__" => {
+                if layout.weakref {
+                    return Err(vm.new_type_error(
+                        "__weakref__ slot disallowed: either we already got one, or the base \
+                         type doesn't support weakrefs"
+                            .to_string(),
+                    ));
+                }
+                layout.weakref = true;
+            }
+            slot_name => {
+                if attributes.contains_key(slot_name) {
+                    return Err(vm.new_value_error(format!(
+                        "'{}' in __slots__ conflicts with class variable",
+                        slot_name
+                    )));
+                }
+                slot_names.push(slot_name.to_string());
+            }
+        }
+    }
+    if native_base && !slot_names.is_empty() {
+        layout.dict = true;
+        return Ok((layout, vec![]));
+    }
+    layout.slot_count += slot_names.len();
+    layout.own_slots = !slot_names.is_empty();
+    Ok((layout, slot_names))
 }
 
 pub fn type_new(
@@ -487,22 +687,33 @@ pub fn new(
     name: &str,
     bases: Vec<PyClassRef>,
     dict: HashMap<String, PyObjectRef>,
+) -> PyResult<PyClassRef> {
+    new_with_layout(typ, name, bases, dict, InstanceLayout::default())
+}
+
+fn new_with_layout(
+    typ: PyClassRef,
+    name: &str,
+    bases: Vec<PyClassRef>,
+    dict: HashMap<String, PyObjectRef>,
+    layout: InstanceLayout,
 ) -> PyResult<PyClassRef> {
     let mros = bases.iter().map(|x| _mro(&x)).collect();
     let mro = linearise_mro(mros).unwrap();
-    let new_type = PyObject {
-        payload: PyClass {
+    let new_type = PyObject::new(
+        PyClass {
             name: String::from(name),
             bases,
             mro,
             subclasses: RefCell::default(),
             attributes: RefCell::new(dict),
             slots: RefCell::default(),
+            layout,
+            version_tag: Cell::new(new_version_tag()),
         },
-        dict: None,
         typ,
-    }
-    .into_ref();
+        None,
+    );
 
     let new_type: PyClassRef = new_type.downcast().unwrap();
 
@@ -551,4 +762,32 @@ mod tests {
             map_ids(Some(vec![a.clone(), b.clone(), object.clone()]))
         );
     }
+
+    #[test]
+    fn test_version_tag() {
+        let context = PyContext::new();
+        let object: PyClassRef = context.object();
+        let type_type = &context.types.type_type;
+
+        let a = new(type_type.clone(), "A", vec![object.clone()], HashMap::new()).unwrap();
+        let b = new(type_type.clone(), "B", vec![a.clone()], HashMap::new()).unwrap();
+        let c = new(type_type.clone(), "C", vec![object.clone()], HashMap::new()).unwrap();
+        assert_ne!(a.version_tag(), b.version_tag());
+
+        let versions = |classes: &[&PyClassRef]| -> Vec<u64> {
+            classes.iter().map(|cls| cls.version_tag()).collect()
+        };
+        let before = versions(&[&a, &b, &c]);
+        b.set_str_attr("x", context.none());
+        let after_b = versions(&[&a, &b, &c]);
+        assert_eq!(before[0], after_b[0]);
+        assert_ne!(before[1], after_b[1]);
+        assert_eq!(before[2], after_b[2]);
+
+        a.set_str_attr("x", context.none());
+        let after_a = versions(&[&a, &b, &c]);
+        assert_ne!(after_b[0], after_a[0]);
+        assert_ne!(after_b[1], after_a[1]);
+        assert_eq!(after_b[2], after_a[2]);
+    }
 }
@@ -1,27 +1,39 @@
 use super::objtype::PyClassRef;
 use crate::function::OptionalArg;
 use crate::pyobject::{
-    PyContext, PyObject, PyObjectPayload, PyObjectRef, PyRef, PyResult, PyValue,
+    PyContext, PyObject, PyObjectPayload, PyObjectRef, PyRef, PyResult, PyValue, TypeProtocol,
 };
 use crate::vm::VirtualMachine;
 
+use std::cell::RefCell;
 use std::rc::{Rc, Weak};
 
 #[derive(Debug)]
 pub struct PyWeak {
     referent: Weak<PyObject<dyn PyObjectPayload>>,
+    callback: RefCell<Option<PyObjectRef>>,
 }
 
 impl PyWeak {
     pub fn downgrade(obj: &PyObjectRef) -> PyWeak {
         PyWeak {
             referent: Rc::downgrade(obj),
+            callback: RefCell::new(None),
         }
     }
 
     pub fn upgrade(&self) -> Option<PyObjectRef> {
         self.referent.upgrade()
     }
+
+    /// Takes the callback to call once the referent is gone, if it is gone.
+    pub(crate) fn take_callback_if_dead(&self) -> Option<PyObjectRef> {
+        if self.referent.strong_count() == 0 {
+            self.callback.borrow_mut().take()
+        } else {
+            None
+        }
+    }
 }
 
 impl PyValue for PyWeak {
@@ -33,14 +45,41 @@ impl PyValue for PyWeak {
 pub type PyWeakRef = PyRef<PyWeak>;
 
 impl PyWeakRef {
-    // TODO callbacks
+    /// Objects are freed as soon as their last reference goes, with no hook to call the callback
+    /// then, so the VM calls the callbacks of dead referents when it shuts down.
     fn create(
         cls: PyClassRef,
         referent: PyObjectRef,
-        _callback: OptionalArg<PyObjectRef>,
+        callback: OptionalArg<PyObjectRef>,
         vm: &VirtualMachine,
     ) -> PyResult<Self> {
-        PyWeak::downgrade(&referent).into_ref_with_type(vm, cls)
+        if !referent.class().instance_has_weakref() {
+            return Err(vm.new_type_error(format!(
+                "cannot create weak reference to '{}' object",
+                referent.class().name
+            )));
+        }
+        let callback = callback
+            .into_option()
+            .filter(|callback| !vm.is_none(callback));
+        let weak = PyWeak {
+            referent: Rc::downgrade(&referent),
+            callback: RefCell::new(callback.clone()),
+        }
+        .into_ref_with_type(vm, cls)?;
+        if callback.is_some() {
+            vm.weakref_callbacks
+                .borrow_mut()
+                .push(PyWeak::downgrade(weak.as_object()));
+        }
+        Ok(weak)
+    }
+
+    fn callback(self, vm: &VirtualMachine) -> PyObjectRef {
+        self.callback
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| vm.get_none())
     }
 
     fn call(self, vm: &VirtualMachine) -> PyObjectRef {
@@ -51,6 +90,7 @@ impl PyWeakRef {
 pub fn init(context: &PyContext) {
     extend_class!(context, &context.types.weakref_type, {
         (slot new) => PyWeakRef::create,
-        "__call__" => context.new_rustfunc(PyWeakRef::call)
+        "__call__" => context.new_rustfunc(PyWeakRef::call),
+        "__callback__" => context.new_property(PyWeakRef::callback)
     });
 }
@@ -1,5 +1,5 @@
 use std::any::Any;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
@@ -15,6 +15,7 @@ use crate::bytecode;
 use crate::dictdatatype::DictKey;
 use crate::exceptions;
 use crate::function::{IntoPyNativeFunc, PyFuncArgs};
+use crate::gc;
 use crate::obj::objbuiltinfunc::PyBuiltinFunction;
 use crate::obj::objbytearray;
 use crate::obj::objbytes;
@@ -104,6 +105,7 @@ pub struct PyContext {
     pub types: TypeZoo,
     pub exceptions: exceptions::ExceptionZoo,
     pub int_cache_pool: Vec<PyObjectRef>,
+    interned_strings: RefCell<HashMap<String, objstr::PyStringRef>>,
 }
 
 pub type PyNotImplementedRef = PyRef<PyNotImplemented>;
@@ -170,6 +172,7 @@ impl PyContext {
             types,
             exceptions,
             int_cache_pool,
+            interned_strings: RefCell::default(),
         };
         initialize_types(&context);
 
@@ -407,6 +410,34 @@ impl PyContext {
         PyObject::new(objstr::PyString::from(s), self.str_type(), None)
     }
 
+    /// The interned string with the contents `value`: the one string object that all the code
+    /// objects use for it, so that dict lookups with it stop at the identity check. Strings
+    /// can't be mutated, so sharing them is only visible through `is`.
+    pub fn intern_string(&self, value: &str) -> objstr::PyStringRef {
+        let interned = self.interned_strings.borrow().get(value).cloned();
+        interned.unwrap_or_else(|| {
+            self.intern_string_ref(self.new_str(value.to_owned()).downcast().unwrap())
+        })
+    }
+
+    /// Intern `string` itself, unless an equal string was interned before, which is returned
+    /// instead. Its hash is computed right away, once for all its users.
+    pub fn intern_string_ref(&self, string: objstr::PyStringRef) -> objstr::PyStringRef {
+        self.interned_strings
+            .borrow_mut()
+            .entry(string.as_str().to_owned())
+            .or_insert_with(|| {
+                string.hash_value();
+                string
+            })
+            .clone()
+    }
+
+    /// The number of strings interned so far.
+    pub fn interned_string_count(&self) -> usize {
+        self.interned_strings.borrow().len()
+    }
+
     pub fn new_bytes(&self, data: Vec<u8>) -> PyObjectRef {
         PyObject::new(objbytes::PyBytes::new(data), self.bytes_type(), None)
     }
@@ -471,6 +502,19 @@ impl PyContext {
         )
     }
 
+    /// Like `new_rustfunc`, but the function gets a `__name__` and a `__doc__`.
+    pub fn new_rustfunc_named<F, T, R>(&self, f: F, name: &str, doc: Option<&str>) -> PyObjectRef
+    where
+        F: IntoPyNativeFunc<T, R>,
+    {
+        PyObject::new(
+            PyBuiltinFunction::new(f.into_func())
+                .with_name(name.to_owned(), doc.map(str::to_owned)),
+            self.builtin_function_or_method_type(),
+            None,
+        )
+    }
+
     pub fn new_classmethod<F, T, R>(&self, f: F) -> PyObjectRef
     where
         F: IntoPyNativeFunc<T, R>,
@@ -492,7 +536,7 @@ impl PyContext {
     }
 
     pub fn new_code_object(&self, code: bytecode::CodeObject) -> PyCodeRef {
-        PyObject::new(objcode::PyCode::new(code), self.code_type(), None)
+        PyObject::new(objcode::PyCode::new(code, self), self.code_type(), None)
             .downcast()
             .unwrap()
     }
@@ -520,12 +564,7 @@ impl PyContext {
     }
 
     pub fn new_instance(&self, class: PyClassRef, dict: Option<PyDictRef>) -> PyObjectRef {
-        PyObject {
-            typ: class,
-            dict,
-            payload: objobject::PyInstance,
-        }
-        .into_ref()
+        PyObject::new(objobject::PyInstance::new(&class), class, dict)
     }
 
     pub fn unwrap_constant(&self, value: &bytecode::Constant) -> PyObjectRef {
@@ -561,15 +600,39 @@ impl Default for PyContext {
 /// This is an actual python object. It consists of a `typ` which is the
 /// python class, and carries some rust payload optionally. This rust
 /// payload can be a rust float or rust int in case of float and int objects.
+///
+/// With the `sidecar-dict` feature there is no `dict` field: the objects that have a `__dict__`
+/// get a payload that carries it next to their own, so the others are a pointer smaller.
+/// `PyObject::dict` works the same in both layouts.
 pub struct PyObject<T>
 where
     T: ?Sized + PyObjectPayload,
 {
     pub typ: PyClassRef,
+    #[cfg(not(feature = "sidecar-dict"))]
     pub dict: Option<PyDictRef>, // __dict__ member
     pub payload: T,
 }
 
+impl<T> PyObject<T>
+where
+    T: ?Sized + PyObjectPayload,
+{
+    /// The `__dict__` of this object, if it has one.
+    #[inline]
+    #[cfg(not(feature = "sidecar-dict"))]
+    pub fn dict(&self) -> Option<&PyDictRef> {
+        self.dict.as_ref()
+    }
+
+    /// The `__dict__` of this object, if it has one.
+    #[inline]
+    #[cfg(feature = "sidecar-dict")]
+    pub fn dict(&self) -> Option<&PyDictRef> {
+        self.payload.dict()
+    }
+}
+
 impl PyObject<dyn PyObjectPayload> {
     /// Attempt to downcast this reference to a subclass.
     ///
@@ -817,7 +880,10 @@ pub trait ItemProtocol {
 
 impl ItemProtocol for PyObjectRef {
     fn get_item<T: IntoPyObject>(&self, key: T, vm: &VirtualMachine) -> PyResult {
-        vm.call_method(self, "__getitem__", key.into_pyobject(vm)?)
+        let method = vm.get_method_or_type_error(self.clone(), "__getitem__", || {
+            format!("'{}' object is not subscriptable", self.class().name)
+        })?;
+        vm.invoke(&method, key.into_pyobject(vm)?)
     }
 
     fn set_item<T: IntoPyObject>(
@@ -826,11 +892,26 @@ impl ItemProtocol for PyObjectRef {
         value: PyObjectRef,
         vm: &VirtualMachine,
     ) -> PyResult {
-        vm.call_method(self, "__setitem__", vec![key.into_pyobject(vm)?, value])
+        let method = vm.get_method_or_type_error(self.clone(), "__setitem__", || {
+            format!(
+                "'{}' object does not support item assignment",
+                self.class().name
+            )
+        })?;
+        vm.invoke(&method, vec![key.into_pyobject(vm)?, value])
     }
 
     fn del_item<T: IntoPyObject>(&self, key: T, vm: &VirtualMachine) -> PyResult {
-        vm.call_method(self, "__delitem__", key.into_pyobject(vm)?)
+        let method = vm.get_method_or_type_error(self.clone(), "__delitem__", || {
+            // CPython words this differently for containers and for other objects
+            let cls = self.class();
+            let is_container = ["__getitem__", "__len__", "__contains__"]
+                .iter()
+                .any(|name| objtype::class_has_attr(&cls, name));
+            let does_not = if is_container { "doesn't" } else { "does not" };
+            format!("'{}' object {} support item deletion", cls.name, does_not)
+        })?;
+        vm.invoke(&method, key.into_pyobject(vm)?)
     }
 }
 
@@ -1037,13 +1118,29 @@ where
     T: Sized + PyObjectPayload,
 {
     #[allow(clippy::new_ret_no_self)]
+    #[cfg(not(feature = "sidecar-dict"))]
     pub fn new(payload: T, typ: PyClassRef, dict: Option<PyDictRef>) -> PyObjectRef {
         PyObject { typ, dict, payload }.into_ref()
     }
 
+    #[allow(clippy::new_ret_no_self)]
+    #[cfg(feature = "sidecar-dict")]
+    pub fn new(payload: T, typ: PyClassRef, dict: Option<PyDictRef>) -> PyObjectRef {
+        match dict {
+            Some(dict) => PyObject {
+                typ,
+                payload: WithDict { dict, payload },
+            }
+            .into_ref(),
+            None => PyObject { typ, payload }.into_ref(),
+        }
+    }
+
     // Move this object into a reference object, transferring ownership.
     pub fn into_ref(self) -> PyObjectRef {
-        Rc::new(self)
+        let obj: PyObjectRef = Rc::new(self);
+        gc::track(&obj);
+        obj
     }
 }
 
@@ -1062,8 +1159,21 @@ impl PyObject<dyn PyObjectPayload> {
 pub trait PyValue: fmt::Debug + Sized + 'static {
     const HAVE_DICT: bool = false;
 
+    /// Whether the payload holds references to other objects through which it can be part of
+    /// a reference cycle, so that the cycle collector tracks objects with it, see `gc`.
+    const HAVE_TRAVERSE: bool = false;
+
     fn class(vm: &VirtualMachine) -> PyClassRef;
 
+    /// Call `visit` with each reference to another object the payload holds. Visiting a
+    /// reference the payload doesn't hold could make the cycle collector free objects still in
+    /// use, while missing one only keeps the cycles through it alive.
+    fn traverse(&self, _visit: &mut dyn FnMut(&PyObjectRef)) {}
+
+    /// Drop the references the payload holds that can be dropped, to break the reference cycles
+    /// of garbage the cycle collector found.
+    fn clear_references(&self) {}
+
     fn into_ref(self, vm: &VirtualMachine) -> PyRef<Self> {
         PyRef::new_ref_unchecked(PyObject::new(self, Self::class(vm), None))
     }
@@ -1071,7 +1181,7 @@ pub trait PyValue: fmt::Debug + Sized + 'static {
     fn into_ref_with_type(self, vm: &VirtualMachine, cls: PyClassRef) -> PyResult<PyRef<Self>> {
         let class = Self::class(vm);
         if objtype::issubclass(&cls, &class) {
-            let dict = if !Self::HAVE_DICT && cls.is(&class) {
+            let dict = if !Self::HAVE_DICT && (cls.is(&class) || !cls.instance_has_dict()) {
                 None
             } else {
                 Some(vm.ctx.new_dict())
@@ -1087,6 +1197,21 @@ pub trait PyValue: fmt::Debug + Sized + 'static {
 
 pub trait PyObjectPayload: Any + fmt::Debug + 'static {
     fn as_any(&self) -> &dyn Any;
+
+    /// See `PyValue::HAVE_TRAVERSE`.
+    fn have_traverse(&self) -> bool;
+
+    /// See `PyValue::traverse`.
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef));
+
+    /// See `PyValue::clear_references`.
+    fn clear_references(&self);
+
+    #[cfg(feature = "sidecar-dict")]
+    #[inline]
+    fn dict(&self) -> Option<&PyDictRef> {
+        None
+    }
 }
 
 impl<T: PyValue + 'static> PyObjectPayload for T {
@@ -1094,6 +1219,55 @@ impl<T: PyValue + 'static> PyObjectPayload for T {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    #[inline]
+    fn have_traverse(&self) -> bool {
+        T::HAVE_TRAVERSE
+    }
+
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        PyValue::traverse(self, visit)
+    }
+
+    fn clear_references(&self) {
+        PyValue::clear_references(self)
+    }
+}
+
+/// The payload of an object that has a `__dict__`, with the `sidecar-dict` feature. It looks
+/// like the payload it wraps to `PyObject::payload` and `PyRef`.
+#[cfg(feature = "sidecar-dict")]
+#[derive(Debug)]
+struct WithDict<T> {
+    dict: PyDictRef,
+    payload: T,
+}
+
+#[cfg(feature = "sidecar-dict")]
+impl<T: PyObjectPayload> PyObjectPayload for WithDict<T> {
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self.payload.as_any()
+    }
+
+    #[inline]
+    fn dict(&self) -> Option<&PyDictRef> {
+        Some(&self.dict)
+    }
+
+    #[inline]
+    fn have_traverse(&self) -> bool {
+        self.payload.have_traverse()
+    }
+
+    /// The dict is visited by the cycle collector along with the class, like the `dict` field.
+    fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        self.payload.traverse(visit)
+    }
+
+    fn clear_references(&self) {
+        self.payload.clear_references()
+    }
 }
 
 pub enum Either<A, B> {
@@ -1,6 +1,8 @@
 use std::fmt;
 
 use crate::obj::objdict::PyDictRef;
+use crate::obj::objmodule;
+use crate::obj::objstr::PyStringRef;
 use crate::pyobject::{ItemProtocol, PyContext, PyObjectRef, PyResult};
 use crate::vm::VirtualMachine;
 
@@ -22,14 +24,18 @@ impl fmt::Debug for Scope {
 }
 
 impl Scope {
-    pub fn new(locals: Option<PyDictRef>, globals: PyDictRef, vm: &VirtualMachine) -> Scope {
+    pub(crate) fn traverse(&self, visit: &mut dyn FnMut(&PyObjectRef)) {
+        for dict in self.locals.iter().chain(std::iter::once(&self.globals)) {
+            visit(dict.as_object());
+        }
+    }
+
+    pub fn new(locals: Option<PyDictRef>, globals: PyDictRef, _vm: &VirtualMachine) -> Scope {
         let locals = match locals {
             Some(dict) => vec![dict],
             None => vec![],
         };
-        let scope = Scope { locals, globals };
-        scope.store_name(vm, "__annotations__", vm.ctx.new_dict().into_object());
-        scope
+        Scope { locals, globals }
     }
 
     pub fn with_builtins(
@@ -46,6 +52,19 @@ impl Scope {
         Scope::new(locals, globals, vm)
     }
 
+    /// A fresh scope for running code as the module `name`, with the same attributes that an
+    /// imported module starts with, and `file` as its `__file__` if there is one.
+    pub fn with_module_defaults(vm: &VirtualMachine, name: &str, file: Option<&str>) -> Scope {
+        let globals = vm.ctx.new_dict();
+        objmodule::init_module_dict(vm, &globals, vm.new_str(name.to_owned()), vm.get_none());
+        if let Some(file) = file {
+            globals
+                .set_item("__file__", vm.new_str(file.to_owned()), vm)
+                .unwrap();
+        }
+        Scope::with_builtins(None, globals, vm)
+    }
+
     pub fn get_locals(&self) -> PyDictRef {
         match self.locals.first() {
             Some(dict) => dict.clone(),
@@ -72,22 +91,23 @@ impl Scope {
     }
 }
 
+/// Access to the variables of a scope, by the names interned by `PyContext::intern_string`.
 pub trait NameProtocol {
-    fn load_name(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef>;
-    fn store_name(&self, vm: &VirtualMachine, name: &str, value: PyObjectRef);
-    fn delete_name(&self, vm: &VirtualMachine, name: &str) -> PyResult;
-    fn load_local(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef>;
-    fn load_cell(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef>;
-    fn store_cell(&self, vm: &VirtualMachine, name: &str, value: PyObjectRef);
-    fn load_global(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef>;
-    fn store_global(&self, vm: &VirtualMachine, name: &str, value: PyObjectRef);
+    fn load_name(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef>;
+    fn store_name(&self, vm: &VirtualMachine, name: &PyStringRef, value: PyObjectRef);
+    fn delete_name(&self, vm: &VirtualMachine, name: &PyStringRef) -> PyResult;
+    fn load_local(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef>;
+    fn load_cell(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef>;
+    fn store_cell(&self, vm: &VirtualMachine, name: &PyStringRef, value: PyObjectRef);
+    fn load_global(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef>;
+    fn store_global(&self, vm: &VirtualMachine, name: &PyStringRef, value: PyObjectRef);
 }
 
 impl NameProtocol for Scope {
     #[cfg_attr(feature = "flame-it", flame("Scope"))]
-    fn load_name(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef> {
+    fn load_name(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef> {
         for dict in self.locals.iter() {
-            if let Some(value) = dict.get_item_option(name, vm).unwrap() {
+            if let Some(value) = dict.get_item_option(name.as_object(), vm).unwrap() {
                 return Some(value);
             }
         }
@@ -98,47 +118,56 @@ impl NameProtocol for Scope {
 
     #[cfg_attr(feature = "flame-it", flame("Scope"))]
     /// Load a local name. Only check the local dictionary for the given name.
-    fn load_local(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef> {
-        self.get_locals().get_item_option(name, vm).unwrap()
+    fn load_local(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef> {
+        self.get_locals()
+            .get_item_option(name.as_object(), vm)
+            .unwrap()
     }
 
     #[cfg_attr(feature = "flame-it", flame("Scope"))]
-    fn load_cell(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef> {
+    fn load_cell(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef> {
         for dict in self.locals.iter().skip(1) {
-            if let Some(value) = dict.get_item_option(name, vm).unwrap() {
+            if let Some(value) = dict.get_item_option(name.as_object(), vm).unwrap() {
                 return Some(value);
             }
         }
         None
     }
 
-    fn store_cell(&self, vm: &VirtualMachine, name: &str, value: PyObjectRef) {
+    /// Store a nonlocal name in the enclosing scope that binds it, which may be further out
+    /// than the one around the current scope.
+    fn store_cell(&self, vm: &VirtualMachine, name: &PyStringRef, value: PyObjectRef) {
         self.locals
-            .get(1)
+            .iter()
+            .skip(1)
+            .find(|dict| dict.contains_key(name.as_object(), vm))
+            .or_else(|| self.locals.get(1))
             .expect("no outer scope for non-local")
-            .set_item(name, value, vm)
+            .set_item(name.as_object(), value, vm)
             .unwrap();
     }
 
-    fn store_name(&self, vm: &VirtualMachine, key: &str, value: PyObjectRef) {
-        self.get_locals().set_item(key, value, vm).unwrap();
+    fn store_name(&self, vm: &VirtualMachine, key: &PyStringRef, value: PyObjectRef) {
+        self.get_locals()
+            .set_item(key.as_object(), value, vm)
+            .unwrap();
     }
 
-    fn delete_name(&self, vm: &VirtualMachine, key: &str) -> PyResult {
-        self.get_locals().del_item(key, vm)
+    fn delete_name(&self, vm: &VirtualMachine, key: &PyStringRef) -> PyResult {
+        self.get_locals().del_item(key.as_object(), vm)
     }
 
     #[cfg_attr(feature = "flame-it", flame("Scope"))]
     /// Load a global name.
-    fn load_global(&self, vm: &VirtualMachine, name: &str) -> Option<PyObjectRef> {
-        if let Some(value) = self.globals.get_item_option(name, vm).unwrap() {
+    fn load_global(&self, vm: &VirtualMachine, name: &PyStringRef) -> Option<PyObjectRef> {
+        if let Some(value) = self.globals.get_item_option(name.as_object(), vm).unwrap() {
             Some(value)
         } else {
-            vm.get_attribute(vm.builtins.clone(), name).ok()
+            vm.get_attribute(vm.builtins.clone(), name.clone()).ok()
         }
     }
 
-    fn store_global(&self, vm: &VirtualMachine, name: &str, value: PyObjectRef) {
-        self.globals.set_item(name, value, vm).unwrap();
+    fn store_global(&self, vm: &VirtualMachine, name: &PyStringRef, value: PyObjectRef) {
+        self.globals.set_item(name.as_object(), value, vm).unwrap();
     }
 }
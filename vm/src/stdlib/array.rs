@@ -301,7 +301,7 @@ impl PyArray {
     }
 
     #[pymethod]
-    fn tobytes(&self, _vm: &VirtualMachine) -> Vec<u8> {
+    pub(crate) fn tobytes(&self, _vm: &VirtualMachine) -> Vec<u8> {
         self.array.borrow().tobytes()
     }
 
@@ -1,17 +1,29 @@
 //! `ast` standard module for abstract syntax trees.
 //!
 //! This module makes use of the parser logic, and translates all ast nodes
-//! into python ast.AST objects.
+//! into python ast.AST objects, and back again for `compile()`.
 
+use std::collections::HashMap;
 use std::ops::Deref;
 
 use num_complex::Complex64;
 
-use rustpython_parser::{ast, mode::Mode, parser};
+use rustpython_parser::error::ParseError;
+use rustpython_parser::{ast, parser};
 
+use crate::function::{Args, KwArgs};
+use crate::obj::objbool;
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objcomplex::{self, PyComplex};
+use crate::obj::objfloat::PyFloat;
+use crate::obj::objint::PyInt;
 use crate::obj::objlist::PyListRef;
-use crate::obj::objtype::PyClassRef;
-use crate::pyobject::{PyObjectRef, PyRef, PyResult, PyValue};
+use crate::obj::objstr::{PyString, PyStringRef};
+use crate::obj::objtuple::PyTuple;
+use crate::obj::objtype::{self, PyClassRef};
+use crate::pyobject::{
+    IdProtocol, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject, TypeProtocol,
+};
 use crate::vm::VirtualMachine;
 
 #[derive(Debug)]
@@ -20,6 +32,7 @@ type AstNodeRef = PyRef<AstNode>;
 
 const MODULE_NAME: &str = "_ast";
 pub const PY_COMPILE_FLAG_AST_ONLY: i32 = 0x0400;
+pub const PY_COMPILE_FLAG_TYPE_COMMENTS: i32 = 0x1000;
 
 impl PyValue for AstNode {
     fn class(vm: &VirtualMachine) -> PyClassRef {
@@ -31,30 +44,14 @@ macro_rules! node {
     ( $vm: expr, $node_name:ident, { $($attr_name:ident => $attr_value:expr),* $(,)* }) => {
         {
         let node = create_node($vm, stringify!($node_name))?;
-        let mut field_names = vec![];
         $(
-            let field_name = stringify!($attr_name);
-            $vm.set_attr(node.as_object(), field_name, $attr_value)?;
-            field_names.push($vm.ctx.new_str(field_name.to_string()));
+            $vm.set_attr(node.as_object(), stringify!($attr_name), $attr_value)?;
         )*
-        $vm.set_attr(node.as_object(), "_fields", $vm.ctx.new_tuple(field_names))?;
         node
         }
     };
     ( $vm: expr, $node_name:ident) => {
-        {
-        let node = create_node($vm, stringify!($node_name))?;
-        $vm.set_attr(node.as_object(), "_fields", $vm.ctx.new_tuple(vec![]))?;
-        node
-        }
-    }
-}
-
-fn top_to_ast(vm: &VirtualMachine, top: &ast::Top) -> PyResult<PyListRef> {
-    match top {
-        ast::Top::Program(program) => statements_to_ast(vm, &program.statements),
-        ast::Top::Statement(statements) => statements_to_ast(vm, statements),
-        ast::Top::Expression(_) => unimplemented!("top_to_ast unimplemented ast::Top::Expression"),
+        create_node($vm, stringify!($node_name))?
     }
 }
 
@@ -63,6 +60,54 @@ fn create_node(vm: &VirtualMachine, name: &str) -> PyResult<AstNodeRef> {
     AstNode.into_ref_with_type(vm, vm.class(MODULE_NAME, name))
 }
 
+fn set_location(vm: &VirtualMachine, node: &AstNodeRef, location: &ast::Location) -> PyResult<()> {
+    vm.set_attr(node.as_object(), "lineno", vm.ctx.new_int(location.row()))?;
+    let col_offset = location.column().saturating_sub(1);
+    vm.set_attr(node.as_object(), "col_offset", vm.ctx.new_int(col_offset))?;
+    Ok(())
+}
+
+/// Mark an expression that is assigned to or deleted, and the elements it unpacks into, with the
+/// `Store` or `Del` context instead of `Load`.
+fn set_context(vm: &VirtualMachine, node: &PyObjectRef, context: &str) -> PyResult<()> {
+    let class_name = node.class().name.clone();
+    match class_name.as_str() {
+        "Name" | "Attribute" | "Subscript" => {}
+        "Starred" => set_context(vm, &vm.get_attribute(node.clone(), "value")?, context)?,
+        "List" | "Tuple" => {
+            let elts = vm.get_attribute(node.clone(), "elts")?;
+            for elt in vm.extract_elements::<PyObjectRef>(&elts)? {
+                set_context(vm, &elt, context)?;
+            }
+        }
+        _ => return Ok(()),
+    }
+    vm.set_attr(node, "ctx", create_node(vm, context)?.into_object())?;
+    Ok(())
+}
+
+fn target_to_ast(vm: &VirtualMachine, target: &ast::Expression, context: &str) -> PyResult {
+    let node = expression_to_ast(vm, target)?.into_object();
+    set_context(vm, &node, context)?;
+    Ok(node)
+}
+
+fn targets_to_ast(vm: &VirtualMachine, targets: &[ast::Expression], context: &str) -> PyResult {
+    let nodes: PyResult<_> = targets
+        .iter()
+        .map(|target| target_to_ast(vm, target, context))
+        .collect();
+    Ok(vm.ctx.new_list(nodes?))
+}
+
+fn optional_target_to_ast(vm: &VirtualMachine, target: &Option<ast::Expression>) -> PyResult {
+    if let Some(target) = target {
+        target_to_ast(vm, target, "Store")
+    } else {
+        Ok(vm.ctx.none())
+    }
+}
+
 fn statements_to_ast(vm: &VirtualMachine, statements: &[ast::Statement]) -> PyResult<PyListRef> {
     let body: PyResult<Vec<_>> = statements
         .iter()
@@ -77,11 +122,12 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
         ClassDef {
             name,
             body,
+            bases,
             keywords,
             decorator_list,
-            ..
         } => node!(vm, ClassDef, {
             name => vm.ctx.new_str(name.to_string()),
+            bases => expressions_to_ast(vm, bases)?,
             keywords => map_ast(keyword_to_ast, vm, keywords)?,
             body => statements_to_ast(vm, body)?,
             decorator_list => expressions_to_ast(vm, decorator_list)?,
@@ -93,6 +139,7 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
             body,
             decorator_list,
             returns,
+            type_comment,
         } => {
             if *is_async {
                 node!(vm, AsyncFunctionDef, {
@@ -100,7 +147,8 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
                     args => parameters_to_ast(vm, args)?,
                     body => statements_to_ast(vm, body)?,
                     decorator_list => expressions_to_ast(vm, decorator_list)?,
-                    returns => optional_expression_to_ast(vm, returns)?
+                    returns => optional_expression_to_ast(vm, returns)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             } else {
                 node!(vm, FunctionDef, {
@@ -108,7 +156,8 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
                     args => parameters_to_ast(vm, args)?,
                     body => statements_to_ast(vm, body)?,
                     decorator_list => expressions_to_ast(vm, decorator_list)?,
-                    returns => optional_expression_to_ast(vm, returns)?
+                    returns => optional_expression_to_ast(vm, returns)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             }
         }
@@ -119,14 +168,9 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
             test => expression_to_ast(vm, test)?,
             msg => optional_expression_to_ast(vm, msg)?
         }),
-        Delete { targets } => {
-            let targets: PyResult<_> = targets
-                .iter()
-                .map(|v| Ok(expression_to_ast(vm, v)?.into_object()))
-                .collect();
-            let py_targets = vm.ctx.new_tuple(targets?);
-            node!(vm, Delete, { targets => py_targets })
-        }
+        Delete { targets } => node!(vm, Delete, {
+            targets => targets_to_ast(vm, targets, "Del")?
+        }),
         Return { value } => node!(vm, Return, {
             value => optional_expression_to_ast(vm, value)?
         }),
@@ -141,20 +185,23 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
             iter,
             body,
             orelse,
+            type_comment,
         } => {
             if *is_async {
                 node!(vm, AsyncFor, {
-                    target => expression_to_ast(vm, target)?,
+                    target => target_to_ast(vm, target, "Store")?,
                     iter => expression_to_ast(vm, iter)?,
                     body => statements_to_ast(vm, body)?,
-                    orelse => optional_statements_to_ast(vm, orelse)?
+                    orelse => optional_statements_to_ast(vm, orelse)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             } else {
                 node!(vm, For, {
-                    target => expression_to_ast(vm, target)?,
+                    target => target_to_ast(vm, target, "Store")?,
                     iter => expression_to_ast(vm, iter)?,
                     body => statements_to_ast(vm, body)?,
-                    orelse => optional_statements_to_ast(vm, orelse)?
+                    orelse => optional_statements_to_ast(vm, orelse)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             }
         }
@@ -167,16 +214,19 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
             is_async,
             items,
             body,
+            type_comment,
         } => {
             if *is_async {
                 node!(vm, AsyncWith, {
                     items => map_ast(with_item_to_ast, vm, items)?,
-                    body => statements_to_ast(vm, body)?
+                    body => statements_to_ast(vm, body)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             } else {
                 node!(vm, With, {
                     items => map_ast(with_item_to_ast, vm, items)?,
-                    body => statements_to_ast(vm, body)?
+                    body => statements_to_ast(vm, body)?,
+                    type_comment => optional_string_to_py_obj(vm, type_comment)
                 })
             }
         }
@@ -212,34 +262,43 @@ fn statement_to_ast(vm: &VirtualMachine, statement: &ast::Statement) -> PyResult
         Global { names } => node!(vm, Global, {
             names => make_string_list(vm, names)
         }),
-        Assign { targets, value } => node!(vm, Assign, {
-            targets => expressions_to_ast(vm, targets)?,
+        Assign {
+            targets,
+            value,
+            type_comment,
+        } => node!(vm, Assign, {
+            targets => targets_to_ast(vm, targets, "Store")?,
             value => expression_to_ast(vm, value)?,
+            type_comment => optional_string_to_py_obj(vm, type_comment),
         }),
         AugAssign { target, op, value } => node!(vm, AugAssign, {
-            target => expression_to_ast(vm, target)?,
-            op => vm.ctx.new_str(operator_string(op)),
+            target => target_to_ast(vm, target, "Store")?,
+            op => operator_to_ast(vm, op)?,
             value => expression_to_ast(vm, value)?,
         }),
         AnnAssign {
             target,
             annotation,
             value,
-        } => node!(vm, AnnAssign, {
-            target => expression_to_ast(vm, target)?,
-            annotation => expression_to_ast(vm, annotation)?,
-            value => optional_expression_to_ast(vm, value)?,
-        }),
+        } => {
+            let simple = match target.node {
+                ast::ExpressionType::Identifier { .. } => 1,
+                _ => 0,
+            };
+            node!(vm, AnnAssign, {
+                target => target_to_ast(vm, target, "Store")?,
+                annotation => expression_to_ast(vm, annotation)?,
+                value => optional_expression_to_ast(vm, value)?,
+                simple => vm.ctx.new_int(simple),
+            })
+        }
         Raise { exception, cause } => node!(vm, Raise, {
             exc => optional_expression_to_ast(vm, exception)?,
             cause => optional_expression_to_ast(vm, cause)?,
         }),
     };
 
-    // set lineno on node:
-    let lineno = vm.ctx.new_int(statement.location.row());
-    vm.set_attr(node.as_object(), "lineno", lineno).unwrap();
-
+    set_location(vm, &node, &statement.location)?;
     Ok(node)
 }
 
@@ -265,17 +324,20 @@ fn optional_statements_to_ast(
 fn with_item_to_ast(vm: &VirtualMachine, with_item: &ast::WithItem) -> PyResult<AstNodeRef> {
     let node = node!(vm, withitem, {
         context_expr => expression_to_ast(vm, &with_item.context_expr)?,
-        optional_vars => optional_expression_to_ast(vm, &with_item.optional_vars)?
+        optional_vars => optional_target_to_ast(vm, &with_item.optional_vars)?
     });
     Ok(node)
 }
 
 fn handler_to_ast(vm: &VirtualMachine, handler: &ast::ExceptHandler) -> PyResult<AstNodeRef> {
     let node = node!(vm, ExceptHandler, {
-        typ => optional_expression_to_ast(vm, &handler.typ)?,
         name => optional_string_to_py_obj(vm, &handler.name),
         body => statements_to_ast(vm, &handler.body)?,
     });
+    // `type` is a keyword, so it can't be set with `node!`.
+    let typ = optional_expression_to_ast(vm, &handler.typ)?;
+    vm.set_attr(node.as_object(), "type", typ)?;
+    set_location(vm, &node, &handler.location)?;
     Ok(node)
 }
 
@@ -332,33 +394,32 @@ fn expression_to_ast(vm: &VirtualMachine, expression: &ast::Expression) -> PyRes
             // Operator:
             node!(vm, BinOp, {
                 left => expression_to_ast(vm, a)?,
-                op => vm.ctx.new_str(operator_string(op)),
+                op => operator_to_ast(vm, op)?,
                 right => expression_to_ast(vm, b)?,
             })
         }
         Unop { op, a } => {
             let op = match op {
-                ast::UnaryOperator::Not => "Not",
-                ast::UnaryOperator::Inv => "Invert",
-                ast::UnaryOperator::Neg => "USub",
-                ast::UnaryOperator::Pos => "UAdd",
+                ast::UnaryOperator::Not => node!(vm, Not),
+                ast::UnaryOperator::Inv => node!(vm, Invert),
+                ast::UnaryOperator::Neg => node!(vm, USub),
+                ast::UnaryOperator::Pos => node!(vm, UAdd),
             };
             node!(vm, UnaryOp, {
-                op => vm.ctx.new_str(op.to_string()),
+                op => op.into_object(),
                 operand => expression_to_ast(vm, a)?,
             })
         }
         BoolOp { op, values } => {
             let py_values = expressions_to_ast(vm, values)?;
 
-            let str_op = match op {
-                ast::BooleanOperator::And => "And",
-                ast::BooleanOperator::Or => "Or",
+            let py_op = match op {
+                ast::BooleanOperator::And => node!(vm, And),
+                ast::BooleanOperator::Or => node!(vm, Or),
             };
-            let py_op = vm.ctx.new_str(str_op.to_string());
 
             node!(vm, BoolOp, {
-                op => py_op,
+                op => py_op.into_object(),
                 values => py_values,
             })
         }
@@ -366,23 +427,23 @@ fn expression_to_ast(vm: &VirtualMachine, expression: &ast::Expression) -> PyRes
             let left = expression_to_ast(vm, &vals[0])?;
 
             // Operator:
-            let to_operator = |op: &ast::Comparison| match op {
-                ast::Comparison::Equal => "Eq",
-                ast::Comparison::NotEqual => "NotEq",
-                ast::Comparison::Less => "Lt",
-                ast::Comparison::LessOrEqual => "LtE",
-                ast::Comparison::Greater => "Gt",
-                ast::Comparison::GreaterOrEqual => "GtE",
-                ast::Comparison::In => "In",
-                ast::Comparison::NotIn => "NotIn",
-                ast::Comparison::Is => "Is",
-                ast::Comparison::IsNot => "IsNot",
+            let to_operator = |op: &ast::Comparison| -> PyResult {
+                Ok(match op {
+                    ast::Comparison::Equal => node!(vm, Eq),
+                    ast::Comparison::NotEqual => node!(vm, NotEq),
+                    ast::Comparison::Less => node!(vm, Lt),
+                    ast::Comparison::LessOrEqual => node!(vm, LtE),
+                    ast::Comparison::Greater => node!(vm, Gt),
+                    ast::Comparison::GreaterOrEqual => node!(vm, GtE),
+                    ast::Comparison::In => node!(vm, In),
+                    ast::Comparison::NotIn => node!(vm, NotIn),
+                    ast::Comparison::Is => node!(vm, Is),
+                    ast::Comparison::IsNot => node!(vm, IsNot),
+                }
+                .into_object())
             };
-            let ops = vm.ctx.new_list(
-                ops.iter()
-                    .map(|x| vm.ctx.new_str(to_operator(x).to_string()))
-                    .collect(),
-            );
+            let ops: PyResult<_> = ops.iter().map(to_operator).collect();
+            let ops = vm.ctx.new_list(ops?);
 
             let comparators: PyResult<_> = vals
                 .iter()
@@ -398,16 +459,20 @@ fn expression_to_ast(vm: &VirtualMachine, expression: &ast::Expression) -> PyRes
         }
         Identifier { name } => node!(vm, Name, {
             id => vm.ctx.new_str(name.clone()),
-            ctx => vm.ctx.none()   // TODO: add context.
+            ctx => node!(vm, Load).into_object()
         }),
         Lambda { args, body } => node!(vm, Lambda, {
             args => parameters_to_ast(vm, args)?,
             body => expression_to_ast(vm, body)?,
         }),
+        NamedExpression { target, value } => node!(vm, NamedExpr, {
+            target => target_to_ast(vm, target, "Store")?,
+            value => expression_to_ast(vm, value)?,
+        }),
         IfExpression { test, body, orelse } => node!(vm, IfExp, {
-            text => expression_to_ast(vm, test)?,
+            test => expression_to_ast(vm, test)?,
             body => expression_to_ast(vm, body)?,
-            or_else => expression_to_ast(vm, orelse)?,
+            orelse => expression_to_ast(vm, orelse)?,
         }),
         Number { value } => {
             let py_n = match value {
@@ -432,10 +497,12 @@ fn expression_to_ast(vm: &VirtualMachine, expression: &ast::Expression) -> PyRes
         }),
         Ellipsis => node!(vm, Ellipsis),
         List { elements } => node!(vm, List, {
-            elts => expressions_to_ast(vm, &elements)?
+            elts => expressions_to_ast(vm, &elements)?,
+            ctx => node!(vm, Load).into_object()
         }),
         Tuple { elements } => node!(vm, Tuple, {
-            elts => expressions_to_ast(vm, &elements)?
+            elts => expressions_to_ast(vm, &elements)?,
+            ctx => node!(vm, Load).into_object()
         }),
         Set { elements } => node!(vm, Set, {
             elts => expressions_to_ast(vm, &elements)?
@@ -507,49 +574,66 @@ fn expression_to_ast(vm: &VirtualMachine, expression: &ast::Expression) -> PyRes
         Subscript { a, b } => node!(vm, Subscript, {
             value => expression_to_ast(vm, a)?,
             slice => expression_to_ast(vm, b)?,
+            ctx => node!(vm, Load).into_object()
         }),
         Attribute { value, name } => node!(vm, Attribute, {
             value => expression_to_ast(vm, value)?,
             attr => vm.ctx.new_str(name.to_string()),
-            ctx => vm.ctx.none()
+            ctx => node!(vm, Load).into_object()
         }),
         Starred { value } => node!(vm, Starred, {
-            value => expression_to_ast(vm, value)?
-        }),
-        Slice { elements } => node!(vm, Slice, {
-            bounds => expressions_to_ast(vm, elements)?
+            value => expression_to_ast(vm, value)?,
+            ctx => node!(vm, Load).into_object()
         }),
+        Slice { elements } => {
+            // The parser fills in the omitted bounds with `None`.
+            let bound = |index: usize| -> PyResult {
+                match elements.get(index) {
+                    Some(ast::Expression {
+                        node: ast::ExpressionType::None,
+                        ..
+                    })
+                    | Option::None => Ok(vm.ctx.none()),
+                    Some(bound) => Ok(expression_to_ast(vm, bound)?.into_object()),
+                }
+            };
+            node!(vm, Slice, {
+                lower => bound(0)?,
+                upper => bound(1)?,
+                step => bound(2)?,
+            })
+        }
         String { value } => string_to_ast(vm, value)?,
         Bytes { value } => node!(vm, Bytes, { s => vm.ctx.new_bytes(value.clone()) }),
     };
 
-    let lineno = vm.ctx.new_int(expression.location.row());
-    vm.set_attr(node.as_object(), "lineno", lineno).unwrap();
+    set_location(vm, &node, &expression.location)?;
     Ok(node)
 }
 
-fn operator_string(op: &ast::Operator) -> String {
+fn operator_to_ast(vm: &VirtualMachine, op: &ast::Operator) -> PyResult {
     use ast::Operator::*;
-    match op {
-        Add => "Add",
-        Sub => "Sub",
-        Mult => "Mult",
-        MatMult => "MatMult",
-        Div => "Div",
-        Mod => "Mod",
-        Pow => "Pow",
-        LShift => "LShift",
-        RShift => "RShift",
-        BitOr => "BitOr",
-        BitXor => "BitXor",
-        BitAnd => "BitAnd",
-        FloorDiv => "FloorDiv",
-    }
-    .to_string()
+    let op = match op {
+        Add => node!(vm, Add),
+        Sub => node!(vm, Sub),
+        Mult => node!(vm, Mult),
+        MatMult => node!(vm, MatMult),
+        Div => node!(vm, Div),
+        Mod => node!(vm, Mod),
+        Pow => node!(vm, Pow),
+        LShift => node!(vm, LShift),
+        RShift => node!(vm, RShift),
+        BitOr => node!(vm, BitOr),
+        BitXor => node!(vm, BitXor),
+        BitAnd => node!(vm, BitAnd),
+        FloorDiv => node!(vm, FloorDiv),
+    };
+    Ok(op.into_object())
 }
 
 fn parameters_to_ast(vm: &VirtualMachine, args: &ast::Parameters) -> PyResult<AstNodeRef> {
     Ok(node!(vm, arguments, {
+        posonlyargs => map_ast(parameter_to_ast, vm, &args.posonlyargs)?,
         args => map_ast(parameter_to_ast, vm, &args.args)?,
         vararg => vararg_to_ast(vm, &args.vararg)?,
         kwonlyargs => map_ast(parameter_to_ast, vm, &args.kwonlyargs)?,
@@ -577,15 +661,21 @@ fn parameter_to_ast(vm: &VirtualMachine, parameter: &ast::Parameter) -> PyResult
 
     let py_node = node!(vm, arg, {
         arg => vm.ctx.new_str(parameter.arg.to_string()),
-        annotation => py_annotation
+        annotation => py_annotation,
+        type_comment => vm.ctx.none()
     });
 
-    let lineno = vm.ctx.new_int(parameter.location.row());
-    vm.set_attr(py_node.as_object(), "lineno", lineno)?;
-
+    set_location(vm, &py_node, &parameter.location)?;
     Ok(py_node)
 }
 
+fn type_ignore_to_ast(vm: &VirtualMachine, type_ignore: &ast::TypeIgnore) -> PyResult<AstNodeRef> {
+    Ok(node!(vm, TypeIgnore, {
+        lineno => vm.ctx.new_int(type_ignore.lineno),
+        tag => vm.ctx.new_str(type_ignore.tag.clone())
+    }))
+}
+
 fn optional_string_to_py_obj(vm: &VirtualMachine, name: &Option<String>) -> PyObjectRef {
     if let Some(name) = name {
         vm.ctx.new_str(name.to_string())
@@ -616,10 +706,10 @@ fn comprehension_to_ast(
     comprehension: &ast::Comprehension,
 ) -> PyResult<AstNodeRef> {
     Ok(node!(vm, comprehension, {
-        target => expression_to_ast(vm, &comprehension.target)?,
+        target => target_to_ast(vm, &comprehension.target, "Store")?,
         iter => expression_to_ast(vm, &comprehension.iter)?,
         ifs => expressions_to_ast(vm, &comprehension.ifs)?,
-        is_async => vm.new_bool(comprehension.is_async),
+        is_async => vm.ctx.new_int(comprehension.is_async as u8),
     }))
 }
 
@@ -628,8 +718,26 @@ fn string_to_ast(vm: &VirtualMachine, string: &ast::StringGroup) -> PyResult<Ast
         ast::StringGroup::Constant { value } => {
             node!(vm, Str, { s => vm.ctx.new_str(value.clone()) })
         }
-        ast::StringGroup::FormattedValue { value, .. } => {
-            node!(vm, FormattedValue, { value => expression_to_ast(vm, value)? })
+        ast::StringGroup::FormattedValue {
+            value,
+            conversion,
+            spec,
+        } => {
+            let conversion = match conversion {
+                Some(ast::ConversionFlag::Str) => 's' as i32,
+                Some(ast::ConversionFlag::Repr) => 'r' as i32,
+                Some(ast::ConversionFlag::Ascii) => 'a' as i32,
+                None => -1,
+            };
+            let format_spec = match spec {
+                Some(spec) => string_to_ast(vm, spec)?.into_object(),
+                None => vm.ctx.none(),
+            };
+            node!(vm, FormattedValue, {
+                value => expression_to_ast(vm, value)?,
+                conversion => vm.ctx.new_int(conversion),
+                format_spec => format_spec,
+            })
         }
         ast::StringGroup::Joined { values } => {
             let py_values = map_ast(string_to_ast, vm, &values)?;
@@ -639,82 +747,980 @@ fn string_to_ast(vm: &VirtualMachine, string: &ast::StringGroup) -> PyResult<Ast
     Ok(string)
 }
 
-pub(crate) fn parse(vm: &VirtualMachine, source: &str, mode: Mode) -> PyResult {
-    let ast = parser::parse(source, mode).map_err(|err| vm.new_value_error(format!("{}", err)))?;
-    let py_body = top_to_ast(vm, &ast)?;
-    Ok(node!(vm, Module, { body => py_body }).into_object())
+fn new_syntax_error(vm: &VirtualMachine, error: ParseError) -> PyObjectRef {
+    let syntax_error = vm.new_exception(vm.ctx.exceptions.syntax_error.clone(), error.to_string());
+    let lineno = vm.ctx.new_int(error.location.row());
+    vm.set_attr(&syntax_error, "lineno", lineno).unwrap();
+    syntax_error
+}
+
+/// Parse `source` into a `Module`, `Expression` or `Interactive` node, for the `exec`, `eval`
+/// and `single` mode of `compile()`. With `type_comments`, the statements get their type
+/// comments and a `Module` its `# type: ignore` comments.
+pub(crate) fn parse(
+    vm: &VirtualMachine,
+    source: &str,
+    mode: &str,
+    type_comments: bool,
+) -> PyResult {
+    let parse_program = |source: &str| {
+        if type_comments {
+            parser::parse_program_with_type_comments(source)
+        } else {
+            parser::parse_program(source).map(|program| (program, vec![]))
+        }
+        .map_err(|err| new_syntax_error(vm, err))
+    };
+    let node = match mode {
+        "exec" => {
+            let (program, type_ignores) = parse_program(source)?;
+            node!(vm, Module, {
+                body => statements_to_ast(vm, &program.statements)?,
+                type_ignores => map_ast(type_ignore_to_ast, vm, &type_ignores)?,
+            })
+        }
+        "single" => {
+            let (program, _) = parse_program(source)?;
+            node!(vm, Interactive, {
+                body => statements_to_ast(vm, &program.statements)?,
+            })
+        }
+        "eval" => {
+            let mut statements =
+                parser::parse_statement(source).map_err(|err| new_syntax_error(vm, err))?;
+            let expression = match statements.pop() {
+                Some(ast::Statement {
+                    node: ast::StatementType::Expression { expression },
+                    ..
+                }) if statements.is_empty() => expression,
+                _ => {
+                    return Err(vm.new_exception(
+                        vm.ctx.exceptions.syntax_error.clone(),
+                        "invalid syntax".to_owned(),
+                    ))
+                }
+            };
+            node!(vm, Expression, {
+                body => expression_to_ast(vm, &expression)?,
+            })
+        }
+        _ => {
+            return Err(
+                vm.new_value_error("compile() mode must be 'exec', 'eval' or 'single'".to_owned())
+            )
+        }
+    };
+    Ok(node.into_object())
+}
+
+fn node_class_name(node: &PyObjectRef) -> String {
+    node.class().name.clone()
+}
+
+fn unexpected_node(vm: &VirtualMachine, expected: &str, node: &PyObjectRef) -> PyObjectRef {
+    let repr = match vm.to_repr(node) {
+        Ok(repr) => repr.as_str().to_owned(),
+        Err(err) => return err,
+    };
+    vm.new_type_error(format!(
+        "expected some sort of {}, but got {}",
+        expected, repr
+    ))
+}
+
+fn get_field(vm: &VirtualMachine, node: &PyObjectRef, field: &str) -> PyResult {
+    vm.get_attribute(node.clone(), field).map_err(|err| {
+        if objtype::isinstance(&err, &vm.ctx.exceptions.attribute_error) {
+            vm.new_type_error(format!(
+                "required field \"{}\" missing from {}",
+                field,
+                node_class_name(node)
+            ))
+        } else {
+            err
+        }
+    })
+}
+
+/// A field that may be left out or set to `None`.
+fn get_optional_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Option<PyObjectRef>> {
+    match vm.get_attribute(node.clone(), field) {
+        Ok(value) if vm.is_none(&value) => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(err) if objtype::isinstance(&err, &vm.ctx.exceptions.attribute_error) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn get_list_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Vec<PyObjectRef>> {
+    vm.extract_elements(&get_field(vm, node, field)?)
+}
+
+fn get_string_field(vm: &VirtualMachine, node: &PyObjectRef, field: &str) -> PyResult<String> {
+    let value = PyStringRef::try_from_object(vm, get_field(vm, node, field)?)?;
+    Ok(value.as_str().to_owned())
+}
+
+fn get_optional_string_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Option<String>> {
+    get_optional_field(vm, node, field)?
+        .map(|value| Ok(PyStringRef::try_from_object(vm, value)?.as_str().to_owned()))
+        .transpose()
+}
+
+fn get_location(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Location> {
+    let lineno = usize::try_from_object(vm, get_field(vm, node, "lineno")?)?;
+    let col_offset = usize::try_from_object(vm, get_field(vm, node, "col_offset")?)?;
+    Ok(ast::Location::new(lineno, col_offset + 1))
+}
+
+/// Convert a `Module`, `Expression` or `Interactive` node back into the parser's ast, so that
+/// `compile()` can compile it in `mode`.
+pub(crate) fn node_to_top(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    mode: &str,
+) -> PyResult<ast::Top> {
+    let expected = match mode {
+        "exec" => "Module",
+        "eval" => "Expression",
+        _ => "Interactive",
+    };
+    let class_name = node_class_name(node);
+    if class_name != expected {
+        return Err(vm.new_type_error(format!("expected {} node, got {}", expected, class_name)));
+    }
+    if class_name == "Expression" {
+        let body = get_field(vm, node, "body")?;
+        Ok(ast::Top::Expression(node_to_expression(vm, &body)?))
+    } else {
+        let statements = node_to_statements(vm, &get_list_field(vm, node, "body")?)?;
+        Ok(ast::Top::Program(ast::Program { statements }))
+    }
+}
+
+fn node_to_statements(vm: &VirtualMachine, nodes: &[PyObjectRef]) -> PyResult<ast::Suite> {
+    nodes
+        .iter()
+        .map(|node| node_to_statement(vm, node))
+        .collect()
+}
+
+fn get_suite_field(vm: &VirtualMachine, node: &PyObjectRef, field: &str) -> PyResult<ast::Suite> {
+    node_to_statements(vm, &get_list_field(vm, node, field)?)
+}
+
+/// A suite that the parser's ast leaves out when it's empty, like an `else` block.
+fn get_optional_suite_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Option<ast::Suite>> {
+    let suite = get_suite_field(vm, node, field)?;
+    Ok(if suite.is_empty() { None } else { Some(suite) })
+}
+
+fn node_to_statement(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Statement> {
+    use ast::StatementType::*;
+    let class_name = node_class_name(node);
+    let statement = match class_name.as_str() {
+        "FunctionDef" | "AsyncFunctionDef" => FunctionDef {
+            is_async: class_name == "AsyncFunctionDef",
+            name: get_string_field(vm, node, "name")?,
+            args: Box::new(node_to_parameters(vm, &get_field(vm, node, "args")?)?),
+            body: get_suite_field(vm, node, "body")?,
+            decorator_list: get_expressions_field(vm, node, "decorator_list")?,
+            returns: get_optional_expression_field(vm, node, "returns")?,
+            type_comment: get_optional_string_field(vm, node, "type_comment")?,
+        },
+        "ClassDef" => ClassDef {
+            name: get_string_field(vm, node, "name")?,
+            body: get_suite_field(vm, node, "body")?,
+            bases: get_expressions_field(vm, node, "bases")?,
+            keywords: get_keywords_field(vm, node, "keywords")?,
+            decorator_list: get_expressions_field(vm, node, "decorator_list")?,
+        },
+        "Return" => Return {
+            value: get_optional_expression_field(vm, node, "value")?,
+        },
+        "Delete" => Delete {
+            targets: get_expressions_field(vm, node, "targets")?,
+        },
+        "Assign" => Assign {
+            targets: get_expressions_field(vm, node, "targets")?,
+            value: get_expression_field(vm, node, "value")?,
+            type_comment: get_optional_string_field(vm, node, "type_comment")?,
+        },
+        "AugAssign" => AugAssign {
+            target: Box::new(get_expression_field(vm, node, "target")?),
+            op: node_to_operator(vm, &get_field(vm, node, "op")?)?,
+            value: Box::new(get_expression_field(vm, node, "value")?),
+        },
+        "AnnAssign" => AnnAssign {
+            target: Box::new(get_expression_field(vm, node, "target")?),
+            annotation: Box::new(get_expression_field(vm, node, "annotation")?),
+            value: get_optional_expression_field(vm, node, "value")?,
+        },
+        "For" | "AsyncFor" => For {
+            is_async: class_name == "AsyncFor",
+            target: Box::new(get_expression_field(vm, node, "target")?),
+            iter: Box::new(get_expression_field(vm, node, "iter")?),
+            body: get_suite_field(vm, node, "body")?,
+            orelse: get_optional_suite_field(vm, node, "orelse")?,
+            type_comment: get_optional_string_field(vm, node, "type_comment")?,
+        },
+        "While" => While {
+            test: get_expression_field(vm, node, "test")?,
+            body: get_suite_field(vm, node, "body")?,
+            orelse: get_optional_suite_field(vm, node, "orelse")?,
+        },
+        "If" => If {
+            test: get_expression_field(vm, node, "test")?,
+            body: get_suite_field(vm, node, "body")?,
+            orelse: get_optional_suite_field(vm, node, "orelse")?,
+        },
+        "With" | "AsyncWith" => With {
+            is_async: class_name == "AsyncWith",
+            items: get_list_field(vm, node, "items")?
+                .iter()
+                .map(|item| {
+                    Ok(ast::WithItem {
+                        context_expr: get_expression_field(vm, item, "context_expr")?,
+                        optional_vars: get_optional_expression_field(vm, item, "optional_vars")?,
+                    })
+                })
+                .collect::<PyResult<_>>()?,
+            body: get_suite_field(vm, node, "body")?,
+            type_comment: get_optional_string_field(vm, node, "type_comment")?,
+        },
+        "Raise" => Raise {
+            exception: get_optional_expression_field(vm, node, "exc")?,
+            cause: get_optional_expression_field(vm, node, "cause")?,
+        },
+        "Try" => Try {
+            body: get_suite_field(vm, node, "body")?,
+            handlers: get_list_field(vm, node, "handlers")?
+                .iter()
+                .map(|handler| node_to_handler(vm, handler))
+                .collect::<PyResult<_>>()?,
+            orelse: get_optional_suite_field(vm, node, "orelse")?,
+            finalbody: get_optional_suite_field(vm, node, "finalbody")?,
+        },
+        "Assert" => Assert {
+            test: get_expression_field(vm, node, "test")?,
+            msg: get_optional_expression_field(vm, node, "msg")?,
+        },
+        "Import" => Import {
+            names: get_aliases_field(vm, node, "names")?,
+        },
+        "ImportFrom" => ImportFrom {
+            level: get_optional_field(vm, node, "level")?
+                .map(|level| usize::try_from_object(vm, level))
+                .transpose()?
+                .unwrap_or(0),
+            module: get_optional_string_field(vm, node, "module")?,
+            names: get_aliases_field(vm, node, "names")?,
+        },
+        "Global" => Global {
+            names: get_names_field(vm, node, "names")?,
+        },
+        "Nonlocal" => Nonlocal {
+            names: get_names_field(vm, node, "names")?,
+        },
+        "Expr" => Expression {
+            expression: get_expression_field(vm, node, "value")?,
+        },
+        "Pass" => Pass,
+        "Break" => Break,
+        "Continue" => Continue,
+        _ => return Err(unexpected_node(vm, "stmt", node)),
+    };
+    Ok(ast::Statement {
+        location: get_location(vm, node)?,
+        node: statement,
+    })
+}
+
+fn node_to_handler(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::ExceptHandler> {
+    if node_class_name(node) != "ExceptHandler" {
+        return Err(unexpected_node(vm, "excepthandler", node));
+    }
+    Ok(ast::ExceptHandler {
+        location: get_location(vm, node)?,
+        typ: get_optional_expression_field(vm, node, "type")?,
+        name: get_optional_string_field(vm, node, "name")?,
+        body: get_suite_field(vm, node, "body")?,
+    })
+}
+
+fn get_aliases_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Vec<ast::ImportSymbol>> {
+    get_list_field(vm, node, field)?
+        .iter()
+        .map(|alias| {
+            Ok(ast::ImportSymbol {
+                symbol: get_string_field(vm, alias, "name")?,
+                alias: get_optional_string_field(vm, alias, "asname")?,
+            })
+        })
+        .collect()
+}
+
+fn get_names_field(vm: &VirtualMachine, node: &PyObjectRef, field: &str) -> PyResult<Vec<String>> {
+    get_list_field(vm, node, field)?
+        .into_iter()
+        .map(|name| Ok(PyStringRef::try_from_object(vm, name)?.as_str().to_owned()))
+        .collect()
+}
+
+fn get_keywords_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Vec<ast::Keyword>> {
+    get_list_field(vm, node, field)?
+        .iter()
+        .map(|keyword| {
+            Ok(ast::Keyword {
+                name: get_optional_string_field(vm, keyword, "arg")?,
+                value: get_expression_field(vm, keyword, "value")?,
+            })
+        })
+        .collect()
+}
+
+fn node_to_parameters(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Parameters> {
+    let node_to_parameter = |arg: &PyObjectRef| {
+        Ok(ast::Parameter {
+            location: get_location(vm, arg)?,
+            arg: get_string_field(vm, arg, "arg")?,
+            annotation: get_optional_expression_field(vm, arg, "annotation")?.map(Box::new),
+        })
+    };
+    let parameters_field = |field: &str| -> PyResult<Vec<ast::Parameter>> {
+        get_list_field(vm, node, field)?
+            .iter()
+            .map(node_to_parameter)
+            .collect()
+    };
+
+    // Positional-only parameters are newer than the rest, so trees built for older versions
+    // leave them out.
+    let posonlyargs = match get_optional_field(vm, node, "posonlyargs")? {
+        Some(_) => parameters_field("posonlyargs")?,
+        None => vec![],
+    };
+    let kwonlyargs = parameters_field("kwonlyargs")?;
+    let vararg = match get_optional_field(vm, node, "vararg")? {
+        Some(vararg) => ast::Varargs::Named(node_to_parameter(&vararg)?),
+        // A bare `*` separates the keyword-only parameters.
+        None if !kwonlyargs.is_empty() => ast::Varargs::Unnamed,
+        None => ast::Varargs::None,
+    };
+    let kwarg = match get_optional_field(vm, node, "kwarg")? {
+        Some(kwarg) => ast::Varargs::Named(node_to_parameter(&kwarg)?),
+        None => ast::Varargs::None,
+    };
+    let kw_defaults = get_list_field(vm, node, "kw_defaults")?
+        .iter()
+        .map(|default| {
+            if vm.is_none(default) {
+                Ok(None)
+            } else {
+                Ok(Some(node_to_expression(vm, default)?))
+            }
+        })
+        .collect::<PyResult<_>>()?;
+
+    Ok(ast::Parameters {
+        posonlyargs,
+        args: parameters_field("args")?,
+        kwonlyargs,
+        vararg,
+        kwarg,
+        defaults: get_expressions_field(vm, node, "defaults")?,
+        kw_defaults,
+    })
+}
+
+fn get_expression_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<ast::Expression> {
+    node_to_expression(vm, &get_field(vm, node, field)?)
+}
+
+fn get_optional_expression_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Option<ast::Expression>> {
+    get_optional_field(vm, node, field)?
+        .map(|value| node_to_expression(vm, &value))
+        .transpose()
+}
+
+fn get_expressions_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+    field: &str,
+) -> PyResult<Vec<ast::Expression>> {
+    node_to_expressions(vm, &get_list_field(vm, node, field)?)
+}
+
+fn node_to_expressions(
+    vm: &VirtualMachine,
+    nodes: &[PyObjectRef],
+) -> PyResult<Vec<ast::Expression>> {
+    nodes
+        .iter()
+        .map(|node| node_to_expression(vm, node))
+        .collect()
+}
+
+fn node_to_expression(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Expression> {
+    use ast::ExpressionType::*;
+    let location = get_location(vm, node)?;
+    let class_name = node_class_name(node);
+    let expression = match class_name.as_str() {
+        "BoolOp" => {
+            let op = get_field(vm, node, "op")?;
+            BoolOp {
+                op: match node_class_name(&op).as_str() {
+                    "And" => ast::BooleanOperator::And,
+                    "Or" => ast::BooleanOperator::Or,
+                    _ => return Err(unexpected_node(vm, "boolop", &op)),
+                },
+                values: get_expressions_field(vm, node, "values")?,
+            }
+        }
+        "NamedExpr" => NamedExpression {
+            target: Box::new(get_expression_field(vm, node, "target")?),
+            value: Box::new(get_expression_field(vm, node, "value")?),
+        },
+        "BinOp" => Binop {
+            a: Box::new(get_expression_field(vm, node, "left")?),
+            op: node_to_operator(vm, &get_field(vm, node, "op")?)?,
+            b: Box::new(get_expression_field(vm, node, "right")?),
+        },
+        "UnaryOp" => {
+            let op = get_field(vm, node, "op")?;
+            Unop {
+                op: match node_class_name(&op).as_str() {
+                    "Not" => ast::UnaryOperator::Not,
+                    "Invert" => ast::UnaryOperator::Inv,
+                    "USub" => ast::UnaryOperator::Neg,
+                    "UAdd" => ast::UnaryOperator::Pos,
+                    _ => return Err(unexpected_node(vm, "unaryop", &op)),
+                },
+                a: Box::new(get_expression_field(vm, node, "operand")?),
+            }
+        }
+        "Lambda" => Lambda {
+            args: Box::new(node_to_parameters(vm, &get_field(vm, node, "args")?)?),
+            body: Box::new(get_expression_field(vm, node, "body")?),
+        },
+        "IfExp" => IfExpression {
+            test: Box::new(get_expression_field(vm, node, "test")?),
+            body: Box::new(get_expression_field(vm, node, "body")?),
+            orelse: Box::new(get_expression_field(vm, node, "orelse")?),
+        },
+        "Dict" => {
+            let keys = get_list_field(vm, node, "keys")?;
+            let values = get_expressions_field(vm, node, "values")?;
+            if keys.len() != values.len() {
+                return Err(vm.new_value_error(
+                    "Dict doesn't have the same number of keys as values".to_owned(),
+                ));
+            }
+            let elements = keys
+                .iter()
+                .zip(values)
+                .map(|(key, value)| {
+                    let key = if vm.is_none(key) {
+                        Option::None
+                    } else {
+                        Some(node_to_expression(vm, key)?)
+                    };
+                    Ok((key, value))
+                })
+                .collect::<PyResult<_>>()?;
+            Dict { elements }
+        }
+        "Set" => Set {
+            elements: get_expressions_field(vm, node, "elts")?,
+        },
+        "ListComp" | "SetComp" | "GeneratorExp" => {
+            let element = get_expression_field(vm, node, "elt")?;
+            let kind = match class_name.as_str() {
+                "ListComp" => ast::ComprehensionKind::List { element },
+                "SetComp" => ast::ComprehensionKind::Set { element },
+                _ => ast::ComprehensionKind::GeneratorExpression { element },
+            };
+            Comprehension {
+                kind: Box::new(kind),
+                generators: get_generators_field(vm, node)?,
+            }
+        }
+        "DictComp" => Comprehension {
+            kind: Box::new(ast::ComprehensionKind::Dict {
+                key: get_expression_field(vm, node, "key")?,
+                value: get_expression_field(vm, node, "value")?,
+            }),
+            generators: get_generators_field(vm, node)?,
+        },
+        "Await" => Await {
+            value: Box::new(get_expression_field(vm, node, "value")?),
+        },
+        "Yield" => Yield {
+            value: get_optional_expression_field(vm, node, "value")?.map(Box::new),
+        },
+        "YieldFrom" => YieldFrom {
+            value: Box::new(get_expression_field(vm, node, "value")?),
+        },
+        "Compare" => {
+            let ops = get_list_field(vm, node, "ops")?;
+            let comparators = get_expressions_field(vm, node, "comparators")?;
+            if ops.len() != comparators.len() {
+                return Err(vm.new_value_error(
+                    "Compare has a different number of comparators and operands".to_owned(),
+                ));
+            }
+            let mut vals = vec![get_expression_field(vm, node, "left")?];
+            vals.extend(comparators);
+            Compare {
+                vals,
+                ops: ops
+                    .iter()
+                    .map(|op| node_to_comparison(vm, op))
+                    .collect::<PyResult<_>>()?,
+            }
+        }
+        "Call" => Call {
+            function: Box::new(get_expression_field(vm, node, "func")?),
+            args: get_expressions_field(vm, node, "args")?,
+            keywords: get_keywords_field(vm, node, "keywords")?,
+        },
+        "FormattedValue" | "JoinedStr" | "Str" => String {
+            value: node_to_string_group(vm, node)?,
+        },
+        "Constant" => constant_to_expression(vm, &get_field(vm, node, "value")?, &location)?,
+        "NameConstant" => constant_to_expression(vm, &get_field(vm, node, "value")?, &location)?,
+        "Num" => constant_to_expression(vm, &get_field(vm, node, "n")?, &location)?,
+        "Bytes" => constant_to_expression(vm, &get_field(vm, node, "s")?, &location)?,
+        "Ellipsis" => Ellipsis,
+        "Attribute" => Attribute {
+            value: Box::new(get_expression_field(vm, node, "value")?),
+            name: get_string_field(vm, node, "attr")?,
+        },
+        "Subscript" => Subscript {
+            a: Box::new(get_expression_field(vm, node, "value")?),
+            b: Box::new(get_expression_field(vm, node, "slice")?),
+        },
+        "Starred" => Starred {
+            value: Box::new(get_expression_field(vm, node, "value")?),
+        },
+        "Name" => Identifier {
+            name: get_string_field(vm, node, "id")?,
+        },
+        "List" => List {
+            elements: get_expressions_field(vm, node, "elts")?,
+        },
+        "Tuple" => Tuple {
+            elements: get_expressions_field(vm, node, "elts")?,
+        },
+        "Slice" => {
+            // The compiler expects all three bounds, with `None` for the omitted ones.
+            let bound = |field: &str| -> PyResult<ast::Expression> {
+                match get_optional_expression_field(vm, node, field)? {
+                    Some(bound) => Ok(bound),
+                    Option::None => Ok(ast::Expression {
+                        location: location.clone(),
+                        node: None,
+                    }),
+                }
+            };
+            Slice {
+                elements: vec![bound("lower")?, bound("upper")?, bound("step")?],
+            }
+        }
+        _ => return Err(unexpected_node(vm, "expr", node)),
+    };
+    Ok(ast::Expression {
+        location,
+        node: expression,
+    })
+}
+
+fn get_generators_field(
+    vm: &VirtualMachine,
+    node: &PyObjectRef,
+) -> PyResult<Vec<ast::Comprehension>> {
+    get_list_field(vm, node, "generators")?
+        .iter()
+        .map(|generator| {
+            let target = get_expression_field(vm, generator, "target")?;
+            Ok(ast::Comprehension {
+                location: target.location.clone(),
+                target,
+                iter: get_expression_field(vm, generator, "iter")?,
+                ifs: get_expressions_field(vm, generator, "ifs")?,
+                is_async: match get_optional_field(vm, generator, "is_async")? {
+                    Some(is_async) => objbool::boolval(vm, is_async)?,
+                    None => false,
+                },
+            })
+        })
+        .collect()
+}
+
+fn constant_to_expression(
+    vm: &VirtualMachine,
+    value: &PyObjectRef,
+    location: &ast::Location,
+) -> PyResult<ast::ExpressionType> {
+    use ast::ExpressionType;
+    if value.is(&vm.ctx.none) {
+        return Ok(ExpressionType::None);
+    } else if value.is(&vm.ctx.true_value) {
+        return Ok(ExpressionType::True);
+    } else if value.is(&vm.ctx.false_value) {
+        return Ok(ExpressionType::False);
+    } else if value.is(&vm.ctx.ellipsis) {
+        return Ok(ExpressionType::Ellipsis);
+    }
+    let expression = match_class!(match value.clone() {
+        i @ PyInt => ExpressionType::Number {
+            value: ast::Number::Integer {
+                value: i.as_bigint().clone(),
+            },
+        },
+        f @ PyFloat => ExpressionType::Number {
+            value: ast::Number::Float { value: f.to_f64() },
+        },
+        c @ PyComplex => {
+            let value = objcomplex::get_value(c.as_object());
+            ExpressionType::Number {
+                value: ast::Number::Complex {
+                    real: value.re,
+                    imag: value.im,
+                },
+            }
+        }
+        s @ PyString => ExpressionType::String {
+            value: ast::StringGroup::Constant {
+                value: s.as_str().to_owned(),
+            },
+        },
+        b @ PyBytes => ExpressionType::Bytes {
+            value: b.get_value().to_vec(),
+        },
+        t @ PyTuple => {
+            let elements = t
+                .elements
+                .iter()
+                .map(|element| {
+                    Ok(ast::Expression {
+                        location: location.clone(),
+                        node: constant_to_expression(vm, element, location)?,
+                    })
+                })
+                .collect::<PyResult<_>>()?;
+            ExpressionType::Tuple { elements }
+        }
+        other => {
+            return Err(vm.new_type_error(format!(
+                "got an invalid type in Constant: {}",
+                other.class().name
+            )));
+        }
+    });
+    Ok(expression)
+}
+
+fn node_to_string_group(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::StringGroup> {
+    let class_name = node_class_name(node);
+    let group = match class_name.as_str() {
+        "Str" => ast::StringGroup::Constant {
+            value: get_string_field(vm, node, "s")?,
+        },
+        "Constant" => ast::StringGroup::Constant {
+            value: get_string_field(vm, node, "value")?,
+        },
+        "FormattedValue" => {
+            let conversion = match get_optional_field(vm, node, "conversion")? {
+                Some(conversion) => i32::try_from_object(vm, conversion)?,
+                None => -1,
+            };
+            let conversion = match conversion {
+                -1 => None,
+                c if c == 's' as i32 => Some(ast::ConversionFlag::Str),
+                c if c == 'r' as i32 => Some(ast::ConversionFlag::Repr),
+                c if c == 'a' as i32 => Some(ast::ConversionFlag::Ascii),
+                _ => {
+                    return Err(vm.new_value_error(format!(
+                        "invalid conversion character in FormattedValue: {}",
+                        conversion
+                    )))
+                }
+            };
+            ast::StringGroup::FormattedValue {
+                value: Box::new(get_expression_field(vm, node, "value")?),
+                conversion,
+                spec: get_optional_field(vm, node, "format_spec")?
+                    .map(|spec| node_to_string_group(vm, &spec).map(Box::new))
+                    .transpose()?,
+            }
+        }
+        "JoinedStr" => ast::StringGroup::Joined {
+            values: get_list_field(vm, node, "values")?
+                .iter()
+                .map(|value| node_to_string_group(vm, value))
+                .collect::<PyResult<_>>()?,
+        },
+        _ => return Err(unexpected_node(vm, "str", node)),
+    };
+    Ok(group)
+}
+
+fn node_to_operator(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Operator> {
+    use ast::Operator::*;
+    let op = match node_class_name(node).as_str() {
+        "Add" => Add,
+        "Sub" => Sub,
+        "Mult" => Mult,
+        "MatMult" => MatMult,
+        "Div" => Div,
+        "Mod" => Mod,
+        "Pow" => Pow,
+        "LShift" => LShift,
+        "RShift" => RShift,
+        "BitOr" => BitOr,
+        "BitXor" => BitXor,
+        "BitAnd" => BitAnd,
+        "FloorDiv" => FloorDiv,
+        _ => return Err(unexpected_node(vm, "operator", node)),
+    };
+    Ok(op)
+}
+
+fn node_to_comparison(vm: &VirtualMachine, node: &PyObjectRef) -> PyResult<ast::Comparison> {
+    use ast::Comparison::*;
+    let op = match node_class_name(node).as_str() {
+        "Eq" => Equal,
+        "NotEq" => NotEqual,
+        "Lt" => Less,
+        "LtE" => LessOrEqual,
+        "Gt" => Greater,
+        "GtE" => GreaterOrEqual,
+        "In" => In,
+        "NotIn" => NotIn,
+        "Is" => Is,
+        "IsNot" => IsNot,
+        _ => return Err(unexpected_node(vm, "cmpop", node)),
+    };
+    Ok(op)
+}
+
+/// `AST.__init__`, which sets the fields from the positional arguments in the order of
+/// `_fields`, and any attribute from the keyword arguments.
+fn ast_init(zelf: PyObjectRef, args: Args, kwargs: KwArgs, vm: &VirtualMachine) -> PyResult<()> {
+    let args = args.into_vec();
+    if !args.is_empty() {
+        let fields = vm.get_attribute(zelf.clone(), "_fields")?;
+        let fields = vm.extract_elements::<PyStringRef>(&fields)?;
+        if args.len() > fields.len() {
+            return Err(vm.new_type_error(format!(
+                "{} constructor takes at most {} positional argument{}",
+                node_class_name(&zelf),
+                fields.len(),
+                if fields.len() == 1 { "" } else { "s" }
+            )));
+        }
+        for (field, value) in fields.iter().zip(args) {
+            vm.set_attr(&zelf, field.as_str(), value)?;
+        }
+    }
+    for (name, value) in kwargs {
+        vm.set_attr(&zelf, name.as_str(), value)?;
+    }
+    Ok(())
+}
+
+const LOCATION_ATTRIBUTES: &[&str] = &["lineno", "col_offset"];
+
+/// The node classes with their base class, `_fields` and `_attributes`, bases first.
+#[rustfmt::skip]
+const NODE_CLASSES: &[(&str, &str, &[&str], &[&str])] = &[
+    ("mod", "AST", &[], &[]),
+    ("Module", "mod", &["body", "type_ignores"], &[]),
+    ("Interactive", "mod", &["body"], &[]),
+    ("Expression", "mod", &["body"], &[]),
+
+    ("stmt", "AST", &[], LOCATION_ATTRIBUTES),
+    ("FunctionDef", "stmt", &["name", "args", "body", "decorator_list", "returns", "type_comment"], LOCATION_ATTRIBUTES),
+    ("AsyncFunctionDef", "stmt", &["name", "args", "body", "decorator_list", "returns", "type_comment"], LOCATION_ATTRIBUTES),
+    ("ClassDef", "stmt", &["name", "bases", "keywords", "body", "decorator_list"], LOCATION_ATTRIBUTES),
+    ("Return", "stmt", &["value"], LOCATION_ATTRIBUTES),
+    ("Delete", "stmt", &["targets"], LOCATION_ATTRIBUTES),
+    ("Assign", "stmt", &["targets", "value", "type_comment"], LOCATION_ATTRIBUTES),
+    ("AugAssign", "stmt", &["target", "op", "value"], LOCATION_ATTRIBUTES),
+    ("AnnAssign", "stmt", &["target", "annotation", "value", "simple"], LOCATION_ATTRIBUTES),
+    ("For", "stmt", &["target", "iter", "body", "orelse", "type_comment"], LOCATION_ATTRIBUTES),
+    ("AsyncFor", "stmt", &["target", "iter", "body", "orelse", "type_comment"], LOCATION_ATTRIBUTES),
+    ("While", "stmt", &["test", "body", "orelse"], LOCATION_ATTRIBUTES),
+    ("If", "stmt", &["test", "body", "orelse"], LOCATION_ATTRIBUTES),
+    ("With", "stmt", &["items", "body", "type_comment"], LOCATION_ATTRIBUTES),
+    ("AsyncWith", "stmt", &["items", "body", "type_comment"], LOCATION_ATTRIBUTES),
+    ("Raise", "stmt", &["exc", "cause"], LOCATION_ATTRIBUTES),
+    ("Try", "stmt", &["body", "handlers", "orelse", "finalbody"], LOCATION_ATTRIBUTES),
+    ("Assert", "stmt", &["test", "msg"], LOCATION_ATTRIBUTES),
+    ("Import", "stmt", &["names"], LOCATION_ATTRIBUTES),
+    ("ImportFrom", "stmt", &["module", "names", "level"], LOCATION_ATTRIBUTES),
+    ("Global", "stmt", &["names"], LOCATION_ATTRIBUTES),
+    ("Nonlocal", "stmt", &["names"], LOCATION_ATTRIBUTES),
+    ("Expr", "stmt", &["value"], LOCATION_ATTRIBUTES),
+    ("Pass", "stmt", &[], LOCATION_ATTRIBUTES),
+    ("Break", "stmt", &[], LOCATION_ATTRIBUTES),
+    ("Continue", "stmt", &[], LOCATION_ATTRIBUTES),
+
+    ("expr", "AST", &[], LOCATION_ATTRIBUTES),
+    ("BoolOp", "expr", &["op", "values"], LOCATION_ATTRIBUTES),
+    ("NamedExpr", "expr", &["target", "value"], LOCATION_ATTRIBUTES),
+    ("BinOp", "expr", &["left", "op", "right"], LOCATION_ATTRIBUTES),
+    ("UnaryOp", "expr", &["op", "operand"], LOCATION_ATTRIBUTES),
+    ("Lambda", "expr", &["args", "body"], LOCATION_ATTRIBUTES),
+    ("IfExp", "expr", &["test", "body", "orelse"], LOCATION_ATTRIBUTES),
+    ("Dict", "expr", &["keys", "values"], LOCATION_ATTRIBUTES),
+    ("Set", "expr", &["elts"], LOCATION_ATTRIBUTES),
+    ("ListComp", "expr", &["elt", "generators"], LOCATION_ATTRIBUTES),
+    ("SetComp", "expr", &["elt", "generators"], LOCATION_ATTRIBUTES),
+    ("DictComp", "expr", &["key", "value", "generators"], LOCATION_ATTRIBUTES),
+    ("GeneratorExp", "expr", &["elt", "generators"], LOCATION_ATTRIBUTES),
+    ("Await", "expr", &["value"], LOCATION_ATTRIBUTES),
+    ("Yield", "expr", &["value"], LOCATION_ATTRIBUTES),
+    ("YieldFrom", "expr", &["value"], LOCATION_ATTRIBUTES),
+    ("Compare", "expr", &["left", "ops", "comparators"], LOCATION_ATTRIBUTES),
+    ("Call", "expr", &["func", "args", "keywords"], LOCATION_ATTRIBUTES),
+    ("FormattedValue", "expr", &["value", "conversion", "format_spec"], LOCATION_ATTRIBUTES),
+    ("JoinedStr", "expr", &["values"], LOCATION_ATTRIBUTES),
+    ("Constant", "expr", &["value", "kind"], LOCATION_ATTRIBUTES),
+    ("Num", "expr", &["n"], LOCATION_ATTRIBUTES),
+    ("Str", "expr", &["s"], LOCATION_ATTRIBUTES),
+    ("Bytes", "expr", &["s"], LOCATION_ATTRIBUTES),
+    ("NameConstant", "expr", &["value"], LOCATION_ATTRIBUTES),
+    ("Ellipsis", "expr", &[], LOCATION_ATTRIBUTES),
+    ("Attribute", "expr", &["value", "attr", "ctx"], LOCATION_ATTRIBUTES),
+    ("Subscript", "expr", &["value", "slice", "ctx"], LOCATION_ATTRIBUTES),
+    ("Starred", "expr", &["value", "ctx"], LOCATION_ATTRIBUTES),
+    ("Name", "expr", &["id", "ctx"], LOCATION_ATTRIBUTES),
+    ("List", "expr", &["elts", "ctx"], LOCATION_ATTRIBUTES),
+    ("Tuple", "expr", &["elts", "ctx"], LOCATION_ATTRIBUTES),
+    ("Slice", "expr", &["lower", "upper", "step"], LOCATION_ATTRIBUTES),
+
+    ("expr_context", "AST", &[], &[]),
+    ("Load", "expr_context", &[], &[]),
+    ("Store", "expr_context", &[], &[]),
+    ("Del", "expr_context", &[], &[]),
+
+    ("boolop", "AST", &[], &[]),
+    ("And", "boolop", &[], &[]),
+    ("Or", "boolop", &[], &[]),
+
+    ("operator", "AST", &[], &[]),
+    ("Add", "operator", &[], &[]),
+    ("Sub", "operator", &[], &[]),
+    ("Mult", "operator", &[], &[]),
+    ("MatMult", "operator", &[], &[]),
+    ("Div", "operator", &[], &[]),
+    ("Mod", "operator", &[], &[]),
+    ("Pow", "operator", &[], &[]),
+    ("LShift", "operator", &[], &[]),
+    ("RShift", "operator", &[], &[]),
+    ("BitOr", "operator", &[], &[]),
+    ("BitXor", "operator", &[], &[]),
+    ("BitAnd", "operator", &[], &[]),
+    ("FloorDiv", "operator", &[], &[]),
+
+    ("unaryop", "AST", &[], &[]),
+    ("Invert", "unaryop", &[], &[]),
+    ("Not", "unaryop", &[], &[]),
+    ("UAdd", "unaryop", &[], &[]),
+    ("USub", "unaryop", &[], &[]),
+
+    ("cmpop", "AST", &[], &[]),
+    ("Eq", "cmpop", &[], &[]),
+    ("NotEq", "cmpop", &[], &[]),
+    ("Lt", "cmpop", &[], &[]),
+    ("LtE", "cmpop", &[], &[]),
+    ("Gt", "cmpop", &[], &[]),
+    ("GtE", "cmpop", &[], &[]),
+    ("Is", "cmpop", &[], &[]),
+    ("IsNot", "cmpop", &[], &[]),
+    ("In", "cmpop", &[], &[]),
+    ("NotIn", "cmpop", &[], &[]),
+
+    ("comprehension", "AST", &["target", "iter", "ifs", "is_async"], &[]),
+    ("excepthandler", "AST", &[], LOCATION_ATTRIBUTES),
+    ("ExceptHandler", "excepthandler", &["type", "name", "body"], LOCATION_ATTRIBUTES),
+    ("arguments", "AST", &["posonlyargs", "args", "vararg", "kwonlyargs", "kw_defaults", "kwarg", "defaults"], &[]),
+    ("arg", "AST", &["arg", "annotation", "type_comment"], LOCATION_ATTRIBUTES),
+    ("keyword", "AST", &["arg", "value"], &[]),
+    ("alias", "AST", &["name", "asname"], &[]),
+    ("withitem", "AST", &["context_expr", "optional_vars"], &[]),
+    ("type_ignore", "AST", &[], &[]),
+    ("TypeIgnore", "type_ignore", &["lineno", "tag"], &[]),
+];
+
+fn new_name_tuple(vm: &VirtualMachine, names: &[&str]) -> PyObjectRef {
+    vm.ctx.new_tuple(
+        names
+            .iter()
+            .map(|name| vm.ctx.new_str(name.to_string()))
+            .collect(),
+    )
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
-    let ast_base = py_class!(ctx, "AST", ctx.object(), {});
-    py_module!(vm, MODULE_NAME, {
+    let ast_base = py_class!(ctx, "AST", ctx.object(), {
+        "__init__" => ctx.new_rustfunc(ast_init),
+        "_fields" => ctx.new_tuple(vec![]),
+        "_attributes" => ctx.new_tuple(vec![]),
+    });
+    let module = py_module!(vm, MODULE_NAME, {
         "AST" => ast_base.clone(),
-        // TODO: There's got to be a better way!
-        "alias" => py_class!(ctx, "alias", ast_base.clone(), {}),
-        "arg" => py_class!(ctx, "arg", ast_base.clone(), {}),
-        "arguments" => py_class!(ctx, "arguments", ast_base.clone(), {}),
-        "AnnAssign" => py_class!(ctx, "AnnAssign", ast_base.clone(), {}),
-        "Assign" => py_class!(ctx, "Assign", ast_base.clone(), {}),
-        "AugAssign" => py_class!(ctx, "AugAssign", ast_base.clone(), {}),
-        "AsyncFor" => py_class!(ctx, "AsyncFor", ast_base.clone(), {}),
-        "AsyncFunctionDef" => py_class!(ctx, "AsyncFunctionDef", ast_base.clone(), {}),
-        "AsyncWith" => py_class!(ctx, "AsyncWith", ast_base.clone(), {}),
-        "Assert" => py_class!(ctx, "Assert", ast_base.clone(), {}),
-        "Attribute" => py_class!(ctx, "Attribute", ast_base.clone(), {}),
-        "Await" => py_class!(ctx, "Await", ast_base.clone(), {}),
-        "BinOp" => py_class!(ctx, "BinOp", ast_base.clone(), {}),
-        "BoolOp" => py_class!(ctx, "BoolOp", ast_base.clone(), {}),
-        "Break" => py_class!(ctx, "Break", ast_base.clone(), {}),
-        "Bytes" => py_class!(ctx, "Bytes", ast_base.clone(), {}),
-        "Call" => py_class!(ctx, "Call", ast_base.clone(), {}),
-        "ClassDef" => py_class!(ctx, "ClassDef", ast_base.clone(), {}),
-        "Compare" => py_class!(ctx, "Compare", ast_base.clone(), {}),
-        "comprehension" => py_class!(ctx, "comprehension", ast_base.clone(), {}),
-        "Continue" => py_class!(ctx, "Continue", ast_base.clone(), {}),
-        "Delete" => py_class!(ctx, "Delete", ast_base.clone(), {}),
-        "Dict" => py_class!(ctx, "Dict", ast_base.clone(), {}),
-        "DictComp" => py_class!(ctx, "DictComp", ast_base.clone(), {}),
-        "Ellipsis" => py_class!(ctx, "Ellipsis", ast_base.clone(), {}),
-        "Expr" => py_class!(ctx, "Expr", ast_base.clone(), {}),
-        "ExceptHandler" => py_class!(ctx, "ExceptHandler", ast_base.clone(), {}),
-        "For" => py_class!(ctx, "For", ast_base.clone(), {}),
-        "FormattedValue" => py_class!(ctx, "FormattedValue", ast_base.clone(), {}),
-        "FunctionDef" => py_class!(ctx, "FunctionDef", ast_base.clone(), {}),
-        "GeneratorExp" => py_class!(ctx, "GeneratorExp", ast_base.clone(), {}),
-        "Global" => py_class!(ctx, "Global", ast_base.clone(), {}),
-        "If" => py_class!(ctx, "If", ast_base.clone(), {}),
-        "IfExp" => py_class!(ctx, "IfExp", ast_base.clone(), {}),
-        "Import" => py_class!(ctx, "Import", ast_base.clone(), {}),
-        "ImportFrom" => py_class!(ctx, "ImportFrom", ast_base.clone(), {}),
-        "JoinedStr" => py_class!(ctx, "JoinedStr", ast_base.clone(), {}),
-        "keyword" => py_class!(ctx, "keyword", ast_base.clone(), {}),
-        "Lambda" => py_class!(ctx, "Lambda", ast_base.clone(), {}),
-        "List" => py_class!(ctx, "List", ast_base.clone(), {}),
-        "ListComp" => py_class!(ctx, "ListComp", ast_base.clone(), {}),
-        "Module" => py_class!(ctx, "Module", ast_base.clone(), {}),
-        "Name" => py_class!(ctx, "Name", ast_base.clone(), {}),
-        "NameConstant" => py_class!(ctx, "NameConstant", ast_base.clone(), {}),
-        "Nonlocal" => py_class!(ctx, "Nonlocal", ast_base.clone(), {}),
-        "Num" => py_class!(ctx, "Num", ast_base.clone(), {}),
-        "Pass" => py_class!(ctx, "Pass", ast_base.clone(), {}),
-        "Raise" => py_class!(ctx, "Raise", ast_base.clone(), {}),
-        "Return" => py_class!(ctx, "Return", ast_base.clone(), {}),
-        "Set" => py_class!(ctx, "Set", ast_base.clone(), {}),
-        "SetComp" => py_class!(ctx, "SetComp", ast_base.clone(), {}),
-        "Starred" => py_class!(ctx, "Starred", ast_base.clone(), {}),
-        "Starred" => py_class!(ctx, "Starred", ast_base.clone(), {}),
-        "Str" => py_class!(ctx, "Str", ast_base.clone(), {}),
-        "Subscript" => py_class!(ctx, "Subscript", ast_base.clone(), {}),
-        "Try" => py_class!(ctx, "Try", ast_base.clone(), {}),
-        "Tuple" => py_class!(ctx, "Tuple", ast_base.clone(), {}),
-        "UnaryOp" => py_class!(ctx, "UnaryOp", ast_base.clone(), {}),
-        "While" => py_class!(ctx, "While", ast_base.clone(), {}),
-        "With" => py_class!(ctx, "With", ast_base.clone(), {}),
-        "withitem" => py_class!(ctx, "withitem", ast_base.clone(), {}),
-        "Yield" => py_class!(ctx, "Yield", ast_base.clone(), {}),
-        "YieldFrom" => py_class!(ctx, "YieldFrom", ast_base.clone(), {}),
         "PyCF_ONLY_AST" => ctx.new_int(PY_COMPILE_FLAG_AST_ONLY),
-    })
+        "PyCF_TYPE_COMMENTS" => ctx.new_int(PY_COMPILE_FLAG_TYPE_COMMENTS),
+    });
+
+    let mut classes = HashMap::new();
+    classes.insert("AST", ast_base);
+    for (name, base, fields, attributes) in NODE_CLASSES {
+        let class = py_class!(ctx, name, classes[base].clone(), {
+            "_fields" => new_name_tuple(vm, fields),
+            "_attributes" => new_name_tuple(vm, attributes),
+        });
+        vm.set_attr(&module, *name, class.clone().into_object())
+            .unwrap();
+        classes.insert(name, class);
+    }
+
+    module
 }
@@ -0,0 +1,65 @@
+//! The `atexit` module. The functions registered with it are kept on the VM and called by
+//! `VirtualMachine::shutdown`, last registered first.
+
+use std::io;
+
+use crate::exceptions::write_exception;
+use crate::function::PyFuncArgs;
+use crate::pyobject::{PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
+
+fn atexit_register(func: PyObjectRef, args: PyFuncArgs, vm: &VirtualMachine) -> PyResult {
+    if !vm.is_callable(&func) {
+        return Err(vm.new_type_error("the first argument must be callable".to_owned()));
+    }
+    vm.atexit_funcs.borrow_mut().push((func.clone(), args));
+    Ok(func)
+}
+
+fn atexit_unregister(func: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+    let funcs = vm.atexit_funcs.borrow().clone();
+    let mut kept = Vec::with_capacity(funcs.len());
+    for (registered, args) in funcs {
+        if !vm.identical_or_equal(&registered, &func)? {
+            kept.push((registered, args));
+        }
+    }
+    vm.atexit_funcs.replace(kept);
+    Ok(())
+}
+
+/// Calls the registered functions, printing the exceptions they raise instead of stopping.
+pub(crate) fn run_exitfuncs(vm: &VirtualMachine) {
+    // A function may register or unregister others while they run
+    loop {
+        let next = vm.atexit_funcs.borrow_mut().pop();
+        let (func, args) = match next {
+            Some(registered) => registered,
+            None => break,
+        };
+        if let Err(exc) = vm.invoke(&func, args) {
+            eprintln!("Error in atexit._run_exitfuncs:");
+            let _ = write_exception(io::stderr(), vm, &exc);
+        }
+    }
+}
+
+fn atexit_clear(vm: &VirtualMachine) {
+    vm.atexit_funcs.borrow_mut().clear();
+}
+
+fn atexit_ncallbacks(vm: &VirtualMachine) -> usize {
+    vm.atexit_funcs.borrow().len()
+}
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    py_module!(vm, "atexit", {
+        "register" => ctx.new_rustfunc(atexit_register),
+        "unregister" => ctx.new_rustfunc(atexit_unregister),
+        "_run_exitfuncs" => ctx.new_rustfunc(run_exitfuncs),
+        "_clear" => ctx.new_rustfunc(atexit_clear),
+        "_ncallbacks" => ctx.new_rustfunc(atexit_ncallbacks),
+    })
+}
@@ -1,24 +1,28 @@
 use crate::function::OptionalArg;
-use crate::obj::objbytes::PyBytesRef;
-use crate::pyobject::{PyObjectRef, PyResult};
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objstr::PyString;
+use crate::pyobject::{ItemProtocol, PyObjectRef, PyResult, TryFromObject, TypeProtocol};
+use crate::types::create_type;
 use crate::vm::VirtualMachine;
 use crc::{crc32, Hasher32};
 
 fn hex_nibble(n: u8) -> u8 {
     match n {
         0..=9 => b'0' + n,
-        10..=15 => b'a' + n,
+        10..=15 => b'a' + n - 10,
         _ => unreachable!(),
     }
 }
 
-fn binascii_hexlify(data: PyBytesRef, vm: &VirtualMachine) -> PyResult {
-    let bytes = data.get_value();
-    let mut hex = Vec::<u8>::with_capacity(bytes.len() * 2);
-    for b in bytes.iter() {
-        hex.push(hex_nibble(b >> 4));
-        hex.push(hex_nibble(b & 0xf));
-    }
+fn binascii_hexlify(data: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
+    let hex = data.with_ref(|bytes| {
+        let mut hex = Vec::<u8>::with_capacity(bytes.len() * 2);
+        for b in bytes.iter() {
+            hex.push(hex_nibble(b >> 4));
+            hex.push(hex_nibble(b & 0xf));
+        }
+        hex
+    });
 
     Ok(vm.ctx.new_bytes(hex))
 }
@@ -32,11 +36,36 @@ fn unhex_nibble(c: u8) -> Option<u8> {
     }
 }
 
-fn binascii_unhexlify(hexstr: PyBytesRef, vm: &VirtualMachine) -> PyResult {
-    // TODO: allow 'str' hexstrings as well
-    let hex_bytes = hexstr.get_value();
+/// The input of the `a2b_*` functions: a bytes-like object, or a str with only ASCII characters.
+struct SerializedData(Vec<u8>);
+
+impl TryFromObject for SerializedData {
+    fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
+        if let Some(s) = obj.payload::<PyString>() {
+            return if s.as_str().is_ascii() {
+                Ok(SerializedData(s.as_str().as_bytes().to_vec()))
+            } else {
+                Err(vm.new_value_error(
+                    "string argument should contain only ASCII characters".to_string(),
+                ))
+            };
+        }
+        let class_name = obj.class().name.clone();
+        ArgBytesLike::try_from_object(vm, obj)
+            .map(|bytes| SerializedData(bytes.to_vec()))
+            .map_err(|_| {
+                vm.new_type_error(format!(
+                    "argument should be bytes, buffer or ASCII string, not '{}'",
+                    class_name
+                ))
+            })
+    }
+}
+
+fn binascii_unhexlify(hexstr: SerializedData, vm: &VirtualMachine) -> PyResult {
+    let hex_bytes = hexstr.0;
     if hex_bytes.len() % 2 != 0 {
-        return Err(vm.new_value_error("Odd-length string".to_string()));
+        return Err(binascii_error("Odd-length string", vm));
     }
 
     let mut unhex = Vec::<u8>::with_capacity(hex_bytes.len() / 2);
@@ -46,27 +75,135 @@ fn binascii_unhexlify(hexstr: PyBytesRef, vm: &VirtualMachine) -> PyResult {
         if let (Some(n1), Some(n2)) = (n1, n2) {
             unhex.push(n1 << 4 | n2);
         } else {
-            return Err(vm.new_value_error("Non-hexadecimal digit found".to_string()));
+            return Err(binascii_error("Non-hexadecimal digit found", vm));
         }
     }
 
     Ok(vm.ctx.new_bytes(unhex))
 }
 
-fn binascii_crc32(data: PyBytesRef, value: OptionalArg<u32>, vm: &VirtualMachine) -> PyResult {
-    let bytes = data.get_value();
+fn binascii_crc32(data: ArgBytesLike, value: OptionalArg<u32>, vm: &VirtualMachine) -> PyResult {
     let crc = value.unwrap_or(0u32);
 
     let mut digest = crc32::Digest::new_with_initial(crc32::IEEE, crc);
-    digest.write(&bytes);
+    data.with_ref(|bytes| digest.write(bytes));
 
     Ok(vm.ctx.new_int(digest.sum32()))
 }
 
+#[derive(FromArgs)]
+struct B2aBase64Args {
+    #[pyarg(positional_only, optional = false)]
+    data: ArgBytesLike,
+    #[pyarg(keyword_only, default = "true")]
+    newline: bool,
+}
+
+fn binascii_b2a_base64(args: B2aBase64Args, vm: &VirtualMachine) -> PyResult {
+    let mut encoded = args
+        .data
+        .with_ref(|bytes| base64::encode_config(bytes, base64::STANDARD));
+    if args.newline {
+        encoded.push('\n');
+    }
+    Ok(vm.ctx.new_bytes(encoded.into_bytes()))
+}
+
+fn base64_value(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some(u32::from(c - b'A')),
+        b'a'..=b'z' => Some(u32::from(c - b'a') + 26),
+        b'0'..=b'9' => Some(u32::from(c - b'0') + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decode base64 data like CPython does: characters outside the alphabet are skipped, the bits
+/// of the last character that don't make a whole byte are dropped, and padding ends the data.
+fn binascii_a2b_base64(data: SerializedData, vm: &VirtualMachine) -> PyResult {
+    let data = data.0;
+    let mut decoded = Vec::with_capacity(data.len() / 4 * 3);
+    let mut quad_pos = 0;
+    let mut chars = 0;
+    let mut left_char = 0u32;
+    let mut left_bits = 0;
+    for (i, &c) in data.iter().enumerate() {
+        if c == b'=' {
+            // Padding after two characters of a group takes a second '=' to be the end
+            let ends = match quad_pos {
+                2 => {
+                    data[i + 1..]
+                        .iter()
+                        .find(|&&c| c == b'=' || base64_value(c).is_some())
+                        == Some(&b'=')
+                }
+                3 => true,
+                _ => false,
+            };
+            if ends {
+                left_bits = 0;
+                break;
+            }
+            continue;
+        }
+        let value = match base64_value(c) {
+            Some(value) => value,
+            None => continue,
+        };
+        quad_pos = (quad_pos + 1) % 4;
+        chars += 1;
+        left_char = (left_char << 6) | value;
+        left_bits += 6;
+        if left_bits >= 8 {
+            left_bits -= 8;
+            decoded.push((left_char >> left_bits) as u8);
+            left_char &= (1 << left_bits) - 1;
+        }
+    }
+    match left_bits {
+        0 => Ok(vm.ctx.new_bytes(decoded)),
+        6 => Err(binascii_error(
+            &format!(
+                "Invalid base64-encoded string: number of data characters ({}) cannot be 1 more \
+                 than a multiple of 4",
+                chars
+            ),
+            vm,
+        )),
+        _ => Err(binascii_error("Incorrect padding", vm)),
+    }
+}
+
+fn binascii_error(message: &str, vm: &VirtualMachine) -> PyObjectRef {
+    let module = vm
+        .get_attribute(vm.sys_module.clone(), "modules")
+        .unwrap()
+        .get_item("binascii", vm)
+        .unwrap();
+
+    let binascii_error = vm.get_attribute(module, "Error").unwrap();
+    let binascii_error = binascii_error.downcast().unwrap();
+
+    vm.new_exception(binascii_error, message.to_string())
+}
+
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
+    let binascii_error = create_type("Error", &ctx.types.type_type, &ctx.exceptions.value_error);
+    let binascii_incomplete = create_type(
+        "Incomplete",
+        &ctx.types.type_type,
+        &ctx.exceptions.exception_type,
+    );
+
     py_module!(vm, "binascii", {
+        "Error" => binascii_error,
+        "Incomplete" => binascii_incomplete,
+        "b2a_base64" => ctx.new_rustfunc(binascii_b2a_base64),
+        "a2b_base64" => ctx.new_rustfunc(binascii_a2b_base64),
         "hexlify" => ctx.new_rustfunc(binascii_hexlify),
         "b2a_hex" => ctx.new_rustfunc(binascii_hexlify),
         "unhexlify" => ctx.new_rustfunc(binascii_unhexlify),
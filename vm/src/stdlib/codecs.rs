@@ -1,7 +1,28 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::function::{OptionalArg, OptionalOption};
+use crate::obj::objbyteinner::ArgBytesLike;
 use crate::obj::objstr::PyStringRef;
+use crate::obj::objtuple::PyTuple;
 use crate::pyobject::{PyCallable, PyObjectRef, PyResult};
 use crate::VirtualMachine;
 
+/// The codec search functions registered with `codecs.register`, and the codecs they found,
+/// shared by the threads of an interpreter.
+#[derive(Default)]
+pub(crate) struct CodecRegistry {
+    search_path: RefCell<Vec<PyObjectRef>>,
+    cache: RefCell<HashMap<String, PyObjectRef>>,
+}
+
+impl CodecRegistry {
+    pub fn clear(&self) {
+        self.search_path.borrow_mut().clear();
+        self.cache.borrow_mut().clear();
+    }
+}
+
 fn codecs_lookup_error(name: PyStringRef, vm: &VirtualMachine) -> PyResult {
     Err(vm.new_exception(
         vm.ctx.exceptions.lookup_error.clone(),
@@ -9,13 +30,175 @@ fn codecs_lookup_error(name: PyStringRef, vm: &VirtualMachine) -> PyResult {
     ))
 }
 
-fn codecs_register(_search_func: PyCallable, _vm: &VirtualMachine) {
-    // TODO
+fn codecs_register(search_func: PyCallable, vm: &VirtualMachine) {
+    vm.codec_registry
+        .search_path
+        .borrow_mut()
+        .push(search_func.into_object());
+}
+
+/// Look up the `CodecInfo` of an encoding with the registered search functions. Like in CPython,
+/// the `encodings` package registers the first one, which is imported when the first lookup is
+/// done here.
+fn codecs_lookup(encoding: PyStringRef, vm: &VirtualMachine) -> PyResult {
+    let registry = &vm.codec_registry;
+    let normalized = encoding.as_str().to_lowercase().replace(' ', "_");
+    if let Some(codec) = registry.cache.borrow().get(&normalized) {
+        return Ok(codec.clone());
+    }
+    if registry.search_path.borrow().is_empty() {
+        vm.import("encodings", &[], 0)?;
+    }
+    // The search functions may register others, so they aren't borrowed while they run
+    let search_path = registry.search_path.borrow().clone();
+    for search_func in search_path {
+        let codec = vm.invoke(&search_func, vec![vm.new_str(normalized.clone())])?;
+        if vm.is_none(&codec) {
+            continue;
+        }
+        match codec.payload::<PyTuple>() {
+            Some(tuple) if tuple.elements.len() == 4 => {}
+            _ => {
+                return Err(
+                    vm.new_type_error("codec search functions must return 4-tuples".to_owned())
+                )
+            }
+        }
+        registry
+            .cache
+            .borrow_mut()
+            .insert(normalized, codec.clone());
+        return Ok(codec);
+    }
+    Err(vm.new_exception(
+        vm.ctx.exceptions.lookup_error.clone(),
+        format!("unknown encoding: {}", encoding.as_str()),
+    ))
+}
+
+fn errors_or_strict(errors: OptionalOption<PyStringRef>, vm: &VirtualMachine) -> PyObjectRef {
+    match errors.flat_option() {
+        Some(errors) => errors.into_object(),
+        None => vm.new_str("strict".to_owned()),
+    }
+}
+
+/// Encode with `str.encode`, giving the result and how many characters were encoded, like the
+/// functions of the codecs in the `encodings` package expect.
+fn encode(
+    encoding: &str,
+    string: PyStringRef,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    let length = string.as_str().chars().count();
+    let errors = errors_or_strict(errors, vm);
+    let encoded = vm.call_method(
+        string.as_object(),
+        "encode",
+        vec![vm.new_str(encoding.to_owned()), errors],
+    )?;
+    Ok((encoded, length))
+}
+
+/// Decode with `bytes.decode`, giving the result and how many bytes were decoded.
+fn decode(
+    encoding: &str,
+    data: &[u8],
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    let errors = errors_or_strict(errors, vm);
+    let decoded = vm.call_method(
+        &vm.ctx.new_bytes(data.to_vec()),
+        "decode",
+        vec![vm.new_str(encoding.to_owned()), errors],
+    )?;
+    Ok((decoded, data.len()))
+}
+
+/// How many bytes at the end of `data` start a UTF-8 sequence that isn't complete, which an
+/// incremental decoder leaves for the next call.
+fn incomplete_utf8_suffix(data: &[u8]) -> usize {
+    for (back, &byte) in data.iter().rev().take(3).enumerate() {
+        let needed = match byte {
+            0b1000_0000..=0b1011_1111 => continue,
+            0b1100_0000..=0b1101_1111 => 2,
+            0b1110_0000..=0b1110_1111 => 3,
+            0b1111_0000..=0b1111_0111 => 4,
+            _ => return 0,
+        };
+        return if back + 1 < needed { back + 1 } else { 0 };
+    }
+    0
+}
+
+fn codecs_utf_8_encode(
+    string: PyStringRef,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    encode("utf-8", string, errors, vm)
+}
+
+fn codecs_utf_8_decode(
+    data: ArgBytesLike,
+    errors: OptionalOption<PyStringRef>,
+    final_: OptionalArg<bool>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    data.with_ref(|data| {
+        let length = if final_.unwrap_or(false) {
+            data.len()
+        } else {
+            data.len() - incomplete_utf8_suffix(data)
+        };
+        decode("utf-8", &data[..length], errors, vm)
+    })
+}
+
+fn codecs_ascii_encode(
+    string: PyStringRef,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    encode("ascii", string, errors, vm)
+}
+
+fn codecs_ascii_decode(
+    data: ArgBytesLike,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    data.with_ref(|data| decode("ascii", data, errors, vm))
+}
+
+fn codecs_latin_1_encode(
+    string: PyStringRef,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    encode("latin-1", string, errors, vm)
+}
+
+fn codecs_latin_1_decode(
+    data: ArgBytesLike,
+    errors: OptionalOption<PyStringRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(PyObjectRef, usize)> {
+    data.with_ref(|data| decode("latin-1", data, errors, vm))
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     py_module!(vm, "_codecs", {
         "lookup_error" => vm.ctx.new_rustfunc(codecs_lookup_error),
         "register" => vm.ctx.new_rustfunc(codecs_register),
+        "lookup" => vm.ctx.new_rustfunc(codecs_lookup),
+        "utf_8_encode" => vm.ctx.new_rustfunc(codecs_utf_8_encode),
+        "utf_8_decode" => vm.ctx.new_rustfunc(codecs_utf_8_decode),
+        "ascii_encode" => vm.ctx.new_rustfunc(codecs_ascii_encode),
+        "ascii_decode" => vm.ctx.new_rustfunc(codecs_ascii_decode),
+        "latin_1_encode" => vm.ctx.new_rustfunc(codecs_latin_1_encode),
+        "latin_1_decode" => vm.ctx.new_rustfunc(codecs_latin_1_decode),
     })
 }
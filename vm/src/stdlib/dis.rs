@@ -1,27 +1,335 @@
-use crate::obj::objcode::PyCodeRef;
-use crate::pyobject::{PyObjectRef, PyResult, TryFromObject};
+/*
+ * The instructions of code objects, for Lib/dis.py.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use crate::bytecode::{
+    BinaryOperator, CallType, ComparisonOperator, ConversionFlag, Instruction, Label, NameIdx,
+    NameScope, UnaryOperator,
+};
+use crate::obj::objcode::{PyCode, PyCodeRef};
+use crate::pyobject::{PyObjectRef, PyResult};
 use crate::vm::VirtualMachine;
 
-fn dis_dis(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult {
-    // Method or function:
-    if let Ok(co) = vm.get_attribute(obj.clone(), "__code__") {
-        return dis_disassemble(co, vm);
+/// What `dis` shows of an instruction besides its name: the number it takes, if it takes one,
+/// the value the instruction works with and how to show that.
+struct Argument {
+    arg: Option<usize>,
+    argval: PyObjectRef,
+    argrepr: String,
+}
+
+impl Argument {
+    fn none(vm: &VirtualMachine) -> Self {
+        Argument {
+            arg: None,
+            argval: vm.get_none(),
+            argrepr: String::new(),
+        }
     }
 
-    dis_disassemble(obj, vm)
+    fn number(number: usize, vm: &VirtualMachine) -> Self {
+        Argument {
+            arg: Some(number),
+            argval: vm.ctx.new_int(number),
+            argrepr: String::new(),
+        }
+    }
+
+    /// A number along with the flags of the instruction that are set, like `unpack`.
+    fn with_flags(number: usize, flags: &[(bool, &str)], vm: &VirtualMachine) -> Self {
+        let flags: Vec<_> = flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, flag)| *flag)
+            .collect();
+        Argument {
+            argrepr: flags.join(", "),
+            ..Argument::number(number, vm)
+        }
+    }
+
+    fn name(name: &str, vm: &VirtualMachine) -> Self {
+        Argument {
+            arg: None,
+            argval: vm.ctx.new_str(name.to_owned()),
+            argrepr: name.to_owned(),
+        }
+    }
+
+    fn scoped_name(name: &str, scope: &NameScope, vm: &VirtualMachine) -> Self {
+        let scope = match scope {
+            // A free name is looked up in the scopes like CPython's `LOAD_NAME` does
+            NameScope::Local | NameScope::Free => return Argument::name(name, vm),
+            NameScope::NonLocal => "nonlocal",
+            NameScope::Global => "global",
+        };
+        Argument {
+            argrepr: format!("{} {}", scope, name),
+            ..Argument::name(name, vm)
+        }
+    }
+
+    fn jump(target: &Label, label_map: &HashMap<Label, usize>, vm: &VirtualMachine) -> Self {
+        let target = label_map[target];
+        Argument {
+            argrepr: format!("to {}", target),
+            ..Argument::number(target, vm)
+        }
+    }
 }
 
-fn dis_disassemble(co: PyObjectRef, vm: &VirtualMachine) -> PyResult {
-    let code = &PyCodeRef::try_from_object(vm, co)?.code;
-    print!("{}", code);
-    Ok(vm.get_none())
+fn binary_operator(op: &BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Power => "**",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::MatrixMultiply => "@",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::FloorDivide => "//",
+        BinaryOperator::Modulo => "%",
+        BinaryOperator::Add => "+",
+        BinaryOperator::Subtract => "-",
+        BinaryOperator::Lshift => "<<",
+        BinaryOperator::Rshift => ">>",
+        BinaryOperator::And => "&",
+        BinaryOperator::Xor => "^",
+        BinaryOperator::Or => "|",
+    }
+}
+
+fn unary_operator(op: &UnaryOperator) -> &'static str {
+    match op {
+        UnaryOperator::Not => "not",
+        UnaryOperator::Invert => "~",
+        UnaryOperator::Minus => "-",
+        UnaryOperator::Plus => "+",
+    }
+}
+
+fn comparison_operator(op: &ComparisonOperator) -> &'static str {
+    match op {
+        ComparisonOperator::Greater => ">",
+        ComparisonOperator::GreaterOrEqual => ">=",
+        ComparisonOperator::Less => "<",
+        ComparisonOperator::LessOrEqual => "<=",
+        ComparisonOperator::Equal => "==",
+        ComparisonOperator::NotEqual => "!=",
+        ComparisonOperator::In => "in",
+        ComparisonOperator::NotIn => "not in",
+        ComparisonOperator::Is => "is",
+        ComparisonOperator::IsNot => "is not",
+        ComparisonOperator::ExceptionMatch => "exception match",
+    }
+}
+
+/// The name of `instruction` of `code` in CPython's style, and its argument. Like in CPython,
+/// the argument of an instruction that takes a name or constant is its index in `co_names` or
+/// `co_consts`.
+fn describe(
+    instruction: &Instruction,
+    code: &PyCode,
+    vm: &VirtualMachine,
+) -> PyResult<(&'static str, Argument)> {
+    use Instruction::*;
+    let names = &code.code.names;
+    let jump = |target| Argument::jump(target, &code.code.label_map, vm);
+    let none = || Argument::none(vm);
+    let number = |number| Argument::number(number, vm);
+    let name = |idx: &NameIdx| Argument {
+        arg: Some(*idx),
+        ..Argument::name(&names[*idx], vm)
+    };
+    let scoped_name = |idx: &NameIdx, scope| Argument {
+        arg: Some(*idx),
+        ..Argument::scoped_name(&names[*idx], scope, vm)
+    };
+    let described = match instruction {
+        Import {
+            name_idx,
+            symbols_idx,
+            level,
+        } => {
+            let module = name_idx.map_or("", |idx| names[idx].as_str());
+            let argrepr = if symbols_idx.is_empty() {
+                module.to_owned()
+            } else {
+                let symbols: Vec<&str> = symbols_idx.iter().map(|&idx| &*names[idx]).collect();
+                format!("{} [{}]", module, symbols.join(", "))
+            };
+            let argval =
+                name_idx.map_or_else(|| vm.get_none(), |_| vm.ctx.new_str(module.to_owned()));
+            let argument = Argument {
+                arg: Some(*level),
+                argval,
+                argrepr,
+            };
+            ("IMPORT", argument)
+        }
+        ImportStar => ("IMPORT_STAR", none()),
+        ImportFrom { idx } => ("IMPORT_FROM", name(idx)),
+        LoadName { idx, scope } => ("LOAD_NAME", scoped_name(idx, scope)),
+        StoreName { idx, scope } => ("STORE_NAME", scoped_name(idx, scope)),
+        DeleteName { idx } => ("DELETE_NAME", name(idx)),
+        Subscript => ("SUBSCRIPT", none()),
+        StoreSubscript => ("STORE_SUBSCRIPT", none()),
+        DeleteSubscript => ("DELETE_SUBSCRIPT", none()),
+        StoreAttr { idx } => ("STORE_ATTR", name(idx)),
+        DeleteAttr { idx } => ("DELETE_ATTR", name(idx)),
+        LoadConst { idx } => {
+            let argval = code.constants[*idx].clone();
+            let argrepr = vm.to_repr(&argval)?.as_str().to_owned();
+            let argument = Argument {
+                arg: Some(*idx),
+                argval,
+                argrepr,
+            };
+            ("LOAD_CONST", argument)
+        }
+        UnaryOperation { op } => ("UNARY_OPERATION", Argument::name(unary_operator(op), vm)),
+        BinaryOperation { op, inplace } => {
+            let op = binary_operator(op);
+            let argument = if *inplace {
+                Argument::name(&format!("{}=", op), vm)
+            } else {
+                Argument::name(op, vm)
+            };
+            ("BINARY_OPERATION", argument)
+        }
+        LoadAttr { idx } => ("LOAD_ATTR", name(idx)),
+        CompareOperation { op } => (
+            "COMPARE_OPERATION",
+            Argument::name(comparison_operator(op), vm),
+        ),
+        Pop => ("POP", none()),
+        Rotate { amount } => ("ROTATE", number(*amount)),
+        Duplicate => ("DUPLICATE", none()),
+        GetIter => ("GET_ITER", none()),
+        GetYieldFromIter => ("GET_YIELD_FROM_ITER", none()),
+        Continue => ("CONTINUE", none()),
+        Break => ("BREAK", none()),
+        Jump { target } => ("JUMP", jump(target)),
+        JumpIfTrue { target } => ("JUMP_IF_TRUE", jump(target)),
+        JumpIfFalse { target } => ("JUMP_IF_FALSE", jump(target)),
+        JumpIfTrueOrPop { target } => ("JUMP_IF_TRUE_OR_POP", jump(target)),
+        JumpIfFalseOrPop { target } => ("JUMP_IF_FALSE_OR_POP", jump(target)),
+        MakeFunction => ("MAKE_FUNCTION", none()),
+        CallFunction { typ } => {
+            let argument = match typ {
+                CallType::Positional(count) => number(*count),
+                CallType::Keyword(count) => {
+                    Argument::with_flags(*count, &[(true, "with keywords")], vm)
+                }
+                CallType::Ex(has_kwargs) => Argument::with_flags(
+                    *has_kwargs as usize,
+                    &[(true, "unpack"), (*has_kwargs, "with keywords")],
+                    vm,
+                ),
+            };
+            ("CALL_FUNCTION", argument)
+        }
+        ForIter { target } => ("FOR_ITER", jump(target)),
+        ReturnValue => ("RETURN_VALUE", none()),
+        YieldValue => ("YIELD_VALUE", none()),
+        YieldFrom => ("YIELD_FROM", none()),
+        SetupAnnotations => ("SETUP_ANNOTATIONS", none()),
+        // A loop starts right after it's set up, so where it ends is what's worth showing
+        SetupLoop { end, .. } => ("SETUP_LOOP", jump(end)),
+        SetupFinally { handler } => ("SETUP_FINALLY", jump(handler)),
+        EnterFinally => ("ENTER_FINALLY", none()),
+        EndFinally => ("END_FINALLY", none()),
+        SetupExcept { handler } => ("SETUP_EXCEPT", jump(handler)),
+        SetupWith { end } => ("SETUP_WITH", jump(end)),
+        WithCleanupStart => ("WITH_CLEANUP_START", none()),
+        WithCleanupFinish => ("WITH_CLEANUP_FINISH", none()),
+        PopBlock => ("POP_BLOCK", none()),
+        Raise { argc } => ("RAISE", number(*argc)),
+        BuildString { size } => ("BUILD_STRING", number(*size)),
+        BuildTuple {
+            size,
+            unpack,
+            for_call,
+        } => (
+            "BUILD_TUPLE",
+            Argument::with_flags(*size, &[(*unpack, "unpack"), (*for_call, "for call")], vm),
+        ),
+        BuildList { size, unpack } => (
+            "BUILD_LIST",
+            Argument::with_flags(*size, &[(*unpack, "unpack")], vm),
+        ),
+        BuildSet { size, unpack } => (
+            "BUILD_SET",
+            Argument::with_flags(*size, &[(*unpack, "unpack")], vm),
+        ),
+        BuildMap {
+            size,
+            unpack,
+            for_call,
+        } => (
+            "BUILD_MAP",
+            Argument::with_flags(*size, &[(*unpack, "unpack"), (*for_call, "for call")], vm),
+        ),
+        BuildSlice { size } => ("BUILD_SLICE", number(*size)),
+        ListAppend { i } => ("LIST_APPEND", number(*i)),
+        SetAdd { i } => ("SET_ADD", number(*i)),
+        MapAdd { i } => ("MAP_ADD", number(*i)),
+        PrintExpr => ("PRINT_EXPR", none()),
+        LoadBuildClass => ("LOAD_BUILD_CLASS", none()),
+        UnpackSequence { size } => ("UNPACK_SEQUENCE", number(*size)),
+        UnpackEx { before, after } => {
+            // Packed like CPython's UNPACK_EX
+            let argument = Argument {
+                argrepr: format!("{} before, {} after", before, after),
+                ..number(before | (after << 8))
+            };
+            ("UNPACK_EX", argument)
+        }
+        FormatValue { conversion } => {
+            let argument = match conversion {
+                None => none(),
+                Some(ConversionFlag::Str) => Argument::name("str", vm),
+                Some(ConversionFlag::Ascii) => Argument::name("ascii", vm),
+                Some(ConversionFlag::Repr) => Argument::name("repr", vm),
+            };
+            ("FORMAT_VALUE", argument)
+        }
+        PopException => ("POP_EXCEPTION", none()),
+        Reverse { amount } => ("REVERSE", number(*amount)),
+        GetAwaitable => ("GET_AWAITABLE", none()),
+        BeforeAsyncWith => ("BEFORE_ASYNC_WITH", none()),
+        SetupAsyncWith { end } => ("SETUP_ASYNC_WITH", jump(end)),
+        GetAIter => ("GET_AITER", none()),
+        GetANext => ("GET_ANEXT", none()),
+    };
+    Ok(described)
+}
+
+/// The `(opname, arg, argval, argrepr, offset, is_jump_target)` of every instruction of `code`,
+/// for `dis.get_instructions`. The offset of an instruction is its index, which is what jumps,
+/// `co_lines` and `f_lasti` count in.
+fn dis_instructions(code: PyCodeRef, vm: &VirtualMachine) -> PyResult {
+    let jump_targets: HashSet<usize> = code.code.label_map.values().cloned().collect();
+    let mut instructions = Vec::with_capacity(code.code.instructions.len());
+    for (offset, instruction) in code.code.instructions.iter().enumerate() {
+        let (opname, argument) = describe(instruction, &code, vm)?;
+        instructions.push(vm.ctx.new_tuple(vec![
+            vm.ctx.new_str(opname.to_owned()),
+            argument
+                .arg
+                .map_or_else(|| vm.get_none(), |arg| vm.ctx.new_int(arg)),
+            argument.argval,
+            vm.ctx.new_str(argument.argrepr),
+            vm.ctx.new_int(offset),
+            vm.ctx.new_bool(jump_targets.contains(&offset)),
+        ]));
+    }
+    Ok(vm.ctx.new_list(instructions))
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
-    py_module!(vm, "dis", {
-        "dis" => ctx.new_rustfunc(dis_dis),
-        "disassemble" => ctx.new_rustfunc(dis_disassemble)
+    py_module!(vm, "_dis", {
+        "instructions" => ctx.new_rustfunc(dis_instructions)
     })
 }
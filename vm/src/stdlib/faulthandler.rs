@@ -0,0 +1,525 @@
+//! Dumping the Python stack when the interpreter crashes, or on request.
+//!
+//! The fatal error handlers run in a signal handler or a panic hook, and the watchdog of
+//! `dump_traceback_later` on a thread of its own, none of which can borrow `vm.frames`. So while
+//! any of them is enabled, `VirtualMachine::with_frame` mirrors the frames of the thread that
+//! enabled it in `FRAMES`, and they write the stack out with nothing but `write(2)`.
+//!
+//! The interpreter only runs Python code on one thread, so `all_threads` only changes the header.
+//! The watchdog reads the frames while that thread keeps running them, so what it writes is a
+//! best effort, as it is in CPython.
+
+use std::cell::RefCell;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::time::{Duration, Instant};
+use std::{panic, ptr, thread};
+
+use arr_macro::arr;
+
+use crate::frame::{Frame, FrameRef};
+use crate::obj::objfloat::IntoPyFloat;
+use crate::obj::objtype;
+use crate::pyobject::{PyObjectRef, PyResult, TryFromObject};
+use crate::vm::VirtualMachine;
+
+/// The most frames that are written, starting from the most recent one.
+const MAX_FRAME_DEPTH: usize = 100;
+
+// We cannot use the MAX_FRAME_DEPTH const in the arr macro.
+static FRAMES: [AtomicPtr<Frame>; MAX_FRAME_DEPTH] = arr![AtomicPtr::new(ptr::null_mut()); 100];
+/// How many frames the tracked thread is running. The frame at depth `n` is in
+/// `FRAMES[n % MAX_FRAME_DEPTH]`, so only the most recent ones are kept.
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static TRACKED_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+const FATAL_SIGNALS: [(libc::c_int, &str); 5] = [
+    (libc::SIGBUS, "Bus error"),
+    (libc::SIGILL, "Illegal instruction"),
+    (libc::SIGFPE, "Floating point exception"),
+    (libc::SIGABRT, "Aborted"),
+    (libc::SIGSEGV, "Segmentation fault"),
+];
+
+static FATAL_ENABLED: AtomicBool = AtomicBool::new(false);
+static FATAL_FD: AtomicI32 = AtomicI32::new(2);
+static FATAL_ALL_THREADS: AtomicBool = AtomicBool::new(true);
+/// The handlers of `FATAL_SIGNALS` from before `enable`, only changed while they aren't ours.
+static mut PREVIOUS_HANDLERS: [Option<libc::sigaction>; 5] = [None; 5];
+static PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// The file that the fatal error handlers write to, kept alive until they are disabled.
+    static FATAL_FILE: RefCell<Option<PyObjectRef>> = RefCell::new(None);
+    static WATCHDOG: RefCell<Option<Watchdog>> = RefCell::new(None);
+}
+
+struct Watchdog {
+    cancelled: Arc<(Mutex<bool>, Condvar)>,
+    thread: thread::JoinHandle<()>,
+    /// Kept alive for as long as the watchdog may write to it.
+    _file: PyObjectRef,
+}
+
+fn current_thread() -> usize {
+    unsafe { libc::pthread_self() as usize }
+}
+
+/// Called with the frames of a VM whenever one was pushed or popped.
+pub(crate) fn frames_changed(frames: &[FrameRef]) {
+    if !TRACKING.load(Ordering::Relaxed)
+        || TRACKED_THREAD.load(Ordering::Relaxed) != current_thread()
+    {
+        return;
+    }
+    let depth = frames.len();
+    if depth > 0 {
+        store_frame(depth - 1, &frames[depth - 1]);
+    }
+    // After a pop, the oldest frame that is kept again had its place taken by a later one.
+    if depth >= MAX_FRAME_DEPTH {
+        store_frame(depth - MAX_FRAME_DEPTH, &frames[depth - MAX_FRAME_DEPTH]);
+    }
+    DEPTH.store(depth, Ordering::Release);
+}
+
+fn store_frame(depth: usize, frame: &FrameRef) {
+    let frame: &Frame = frame;
+    FRAMES[depth % MAX_FRAME_DEPTH].store(frame as *const Frame as *mut Frame, Ordering::Release);
+}
+
+/// Starts or stops tracking the frames of the current thread, depending on whether anything
+/// needs them.
+fn update_tracking(vm: &VirtualMachine) {
+    let needed = FATAL_ENABLED.load(Ordering::Relaxed)
+        || WATCHDOG.with(|watchdog| watchdog.borrow().is_some());
+    TRACKING.store(false, Ordering::Release);
+    if needed {
+        let frames = vm.frames.borrow();
+        TRACKED_THREAD.store(current_thread(), Ordering::Relaxed);
+        for depth in frames.len().saturating_sub(MAX_FRAME_DEPTH)..frames.len() {
+            store_frame(depth, &frames[depth]);
+        }
+        DEPTH.store(frames.len(), Ordering::Release);
+        TRACKING.store(true, Ordering::Release);
+    }
+}
+
+// Nothing below allocates or takes a lock, so that it can run in a signal handler.
+
+fn write_bytes(fd: RawFd, mut bytes: &[u8]) {
+    while !bytes.is_empty() {
+        let written =
+            unsafe { libc::write(fd, bytes.as_ptr() as *const libc::c_void, bytes.len()) };
+        if written <= 0 {
+            return;
+        }
+        bytes = &bytes[written as usize..];
+    }
+}
+
+fn write_str(fd: RawFd, s: &str) {
+    write_bytes(fd, s.as_bytes())
+}
+
+fn write_decimal(fd: RawFd, mut value: usize) {
+    let mut digits = [0; 20];
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    write_bytes(fd, &digits[start..])
+}
+
+fn write_thread_id(fd: RawFd, mut id: usize) {
+    let mut digits = [b'0'; 2 + 2 * mem::size_of::<usize>()];
+    digits[1] = b'x';
+    for digit in digits[2..].iter_mut().rev() {
+        *digit = b"0123456789abcdef"[id & 0xf];
+        id >>= 4;
+    }
+    write_bytes(fd, &digits)
+}
+
+fn write_header(fd: RawFd, all_threads: bool, thread: usize) {
+    if all_threads {
+        if thread == current_thread() {
+            write_str(fd, "Current thread ");
+        } else {
+            write_str(fd, "Thread ");
+        }
+        write_thread_id(fd, thread);
+        write_str(fd, " (most recent call first):\n");
+    } else {
+        write_str(fd, "Stack (most recent call first):\n");
+    }
+}
+
+/// Writes `frames`, most recent first, out of the `depth` frames that the thread is running.
+fn write_frames<'a>(fd: RawFd, frames: impl Iterator<Item = &'a Frame>, depth: usize) {
+    if depth == 0 {
+        write_str(fd, "  <no Python frame>\n");
+        return;
+    }
+    for frame in frames.take(MAX_FRAME_DEPTH) {
+        write_str(fd, "  File \"");
+        write_str(fd, &frame.code.source_path);
+        write_str(fd, "\", line ");
+        write_decimal(fd, frame.current_lineno());
+        write_str(fd, " in ");
+        write_str(fd, &frame.code.obj_name);
+        write_str(fd, "\n");
+    }
+    if depth > MAX_FRAME_DEPTH {
+        write_str(fd, "  ...\n");
+    }
+}
+
+fn write_tracked_frames(fd: RawFd, all_threads: bool) {
+    write_header(fd, all_threads, TRACKED_THREAD.load(Ordering::Relaxed));
+    let depth = DEPTH.load(Ordering::Acquire);
+    let frames = (depth.saturating_sub(MAX_FRAME_DEPTH)..depth)
+        .rev()
+        .filter_map(|depth| unsafe {
+            FRAMES[depth % MAX_FRAME_DEPTH]
+                .load(Ordering::Acquire)
+                .as_ref()
+        });
+    write_frames(fd, frames, depth)
+}
+
+fn write_fatal_error(message: &str) {
+    let fd = FATAL_FD.load(Ordering::Relaxed);
+    write_str(fd, "Fatal Python error: ");
+    write_str(fd, message);
+    write_str(fd, "\n\n");
+    write_tracked_frames(fd, FATAL_ALL_THREADS.load(Ordering::Relaxed));
+}
+
+extern "C" fn fatal_signal(signum: libc::c_int) {
+    let message = match FATAL_SIGNALS.iter().find(|&&(fatal, _)| fatal == signum) {
+        Some(&(_, message)) => message,
+        None => return,
+    };
+    write_fatal_error(message);
+    // With the handlers from before back in place, the signal does what it would have done
+    // without us, like dumping core.
+    disable_fatal_handlers();
+    unsafe {
+        libc::raise(signum);
+        // Rust's own handler for SIGSEGV and SIGBUS puts the default action back and returns,
+        // counting on the faulting instruction to run again. That doesn't happen for a signal
+        // that was sent with kill(2), so it takes another one.
+        let mut current: libc::sigaction = mem::zeroed();
+        libc::sigaction(signum, ptr::null(), &mut current);
+        if current.sa_sigaction == libc::SIG_DFL {
+            libc::raise(signum);
+        }
+    }
+}
+
+fn enable_fatal_handlers() -> std::io::Result<()> {
+    if FATAL_ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    for (index, &(signum, _)) in FATAL_SIGNALS.iter().enumerate() {
+        unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = fatal_signal as libc::sighandler_t;
+            libc::sigemptyset(&mut action.sa_mask);
+            // SA_ONSTACK runs the handler on the alternate stack that Rust sets up for the main
+            // thread, so that a stack overflow can be reported too.
+            action.sa_flags = libc::SA_NODEFER | libc::SA_ONSTACK;
+            let mut previous: libc::sigaction = mem::zeroed();
+            if libc::sigaction(signum, &action, &mut previous) != 0 {
+                let error = std::io::Error::last_os_error();
+                FATAL_ENABLED.store(true, Ordering::Relaxed);
+                disable_fatal_handlers();
+                return Err(error);
+            }
+            PREVIOUS_HANDLERS[index] = Some(previous);
+        }
+    }
+    FATAL_ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+fn disable_fatal_handlers() {
+    if !FATAL_ENABLED.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    for (index, &(signum, _)) in FATAL_SIGNALS.iter().enumerate() {
+        unsafe {
+            if let Some(previous) = PREVIOUS_HANDLERS[index].take() {
+                libc::sigaction(signum, &previous, ptr::null_mut());
+            }
+        }
+    }
+}
+
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous(info);
+            if FATAL_ENABLED.load(Ordering::Relaxed)
+                && TRACKED_THREAD.load(Ordering::Relaxed) == current_thread()
+            {
+                write_fatal_error("Rust panic");
+            }
+        }));
+    });
+}
+
+/// The file descriptor to write to for the `file` argument, which is `sys.stderr` when it is
+/// None. A file object is flushed first, so that what was written to it comes out first.
+fn file_descriptor(
+    file: Option<PyObjectRef>,
+    vm: &VirtualMachine,
+) -> PyResult<(RawFd, PyObjectRef)> {
+    let runtime_error =
+        |msg: &str| vm.new_exception(vm.ctx.exceptions.runtime_error.clone(), msg.to_owned());
+    let file = match file {
+        Some(file) => file,
+        None => {
+            let stderr = vm
+                .get_attribute(vm.sys_module.clone(), "stderr")
+                .map_err(|_| runtime_error("unable to get sys.stderr"))?;
+            if vm.is_none(&stderr) {
+                return Err(runtime_error("sys.stderr is None"));
+            }
+            stderr
+        }
+    };
+    let fd = if objtype::isinstance(&file, &vm.ctx.int_type()) {
+        i32::try_from_object(vm, file.clone())?
+    } else {
+        let fd = vm.call_method(&file, "fileno", vec![])?;
+        let fd = i32::try_from_object(vm, fd)?;
+        // Failing to flush doesn't keep the traceback from being written
+        let _ = vm.call_method(&file, "flush", vec![]);
+        fd
+    };
+    if fd < 0 {
+        return Err(vm.new_value_error("file is not a valid file descriptor".to_owned()));
+    }
+    Ok((fd, file))
+}
+
+#[derive(FromArgs)]
+struct DumpArgs {
+    #[pyarg(positional_or_keyword, default = "None")]
+    file: Option<PyObjectRef>,
+    #[pyarg(positional_or_keyword, default = "true")]
+    all_threads: bool,
+}
+
+fn enable(args: DumpArgs, vm: &VirtualMachine) -> PyResult<()> {
+    let (fd, file) = file_descriptor(args.file, vm)?;
+    FATAL_FD.store(fd, Ordering::Relaxed);
+    FATAL_ALL_THREADS.store(args.all_threads, Ordering::Relaxed);
+    enable_fatal_handlers().map_err(|err| vm.new_os_error(err.to_string()))?;
+    install_panic_hook();
+    FATAL_FILE.with(|fatal_file| *fatal_file.borrow_mut() = Some(file));
+    update_tracking(vm);
+    Ok(())
+}
+
+fn disable(vm: &VirtualMachine) -> bool {
+    if !FATAL_ENABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    disable_fatal_handlers();
+    FATAL_FILE.with(|fatal_file| fatal_file.borrow_mut().take());
+    update_tracking(vm);
+    true
+}
+
+fn is_enabled(_vm: &VirtualMachine) -> bool {
+    FATAL_ENABLED.load(Ordering::Relaxed)
+}
+
+fn dump_traceback(args: DumpArgs, vm: &VirtualMachine) -> PyResult<()> {
+    let (fd, _file) = file_descriptor(args.file, vm)?;
+    let frames = vm.frames.borrow();
+    write_header(fd, args.all_threads, current_thread());
+    write_frames(fd, frames.iter().rev().map(|frame| &**frame), frames.len());
+    Ok(())
+}
+
+#[derive(FromArgs)]
+struct DumpLaterArgs {
+    #[pyarg(positional_or_keyword, optional = false)]
+    timeout: IntoPyFloat,
+    #[pyarg(positional_or_keyword, default = "false")]
+    repeat: bool,
+    #[pyarg(positional_or_keyword, default = "None")]
+    file: Option<PyObjectRef>,
+    #[pyarg(positional_or_keyword, default = "false")]
+    exit: bool,
+}
+
+fn format_timeout(timeout: f64) -> String {
+    let micros = (timeout * 1e6).round() as u64;
+    let (seconds, micros) = (micros / 1_000_000, micros % 1_000_000);
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if micros == 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}:{:02}.{:06}", hours, minutes, seconds, micros)
+    }
+}
+
+// The watchdog waits for `cancelled` with the condvar, which needs a mutex
+#[allow(clippy::mutex_atomic)]
+fn dump_traceback_later(args: DumpLaterArgs, vm: &VirtualMachine) -> PyResult<()> {
+    let timeout = args.timeout.to_f64();
+    if timeout.is_nan() || timeout <= 0.0 {
+        return Err(vm.new_value_error("timeout must be greater than 0".to_owned()));
+    }
+    if timeout >= std::u64::MAX as f64 {
+        return Err(vm.new_overflow_error("timeout value is too large".to_owned()));
+    }
+    let (fd, file) = file_descriptor(args.file, vm)?;
+    cancel_dump_traceback_later(vm);
+
+    let header = format!("Timeout ({})!\n", format_timeout(timeout));
+    let timeout = Duration::from_secs_f64(timeout);
+    let (repeat, exit) = (args.repeat, args.exit);
+    let cancelled = Arc::new((Mutex::new(false), Condvar::new()));
+    let thread = {
+        let cancelled = cancelled.clone();
+        thread::spawn(move || {
+            let (cancelled, condvar) = &*cancelled;
+            let mut cancelled = cancelled.lock().unwrap();
+            let mut deadline = Instant::now() + timeout;
+            while !*cancelled {
+                let now = Instant::now();
+                if now < deadline {
+                    cancelled = condvar.wait_timeout(cancelled, deadline - now).unwrap().0;
+                    continue;
+                }
+                write_str(fd, &header);
+                write_tracked_frames(fd, true);
+                if exit {
+                    unsafe { libc::_exit(1) };
+                }
+                if !repeat {
+                    break;
+                }
+                deadline += timeout;
+            }
+        })
+    };
+    WATCHDOG.with(|watchdog| {
+        *watchdog.borrow_mut() = Some(Watchdog {
+            cancelled,
+            thread,
+            _file: file,
+        })
+    });
+    update_tracking(vm);
+    Ok(())
+}
+
+fn cancel_dump_traceback_later(vm: &VirtualMachine) {
+    if let Some(watchdog) = WATCHDOG.with(|watchdog| watchdog.borrow_mut().take()) {
+        let (cancelled, condvar) = &*watchdog.cancelled;
+        *cancelled.lock().unwrap() = true;
+        condvar.notify_one();
+        let _ = watchdog.thread.join();
+        update_tracking(vm);
+    }
+}
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    py_module!(vm, "faulthandler", {
+        "enable" => ctx.new_rustfunc(enable),
+        "disable" => ctx.new_rustfunc(disable),
+        "is_enabled" => ctx.new_rustfunc(is_enabled),
+        "dump_traceback" => ctx.new_rustfunc(dump_traceback),
+        "dump_traceback_later" => ctx.new_rustfunc(dump_traceback_later),
+        "cancel_dump_traceback_later" => ctx.new_rustfunc(cancel_dump_traceback_later),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disable, enable, DumpArgs};
+    use crate::pyobject::ItemProtocol;
+    use crate::vm::VirtualMachine;
+    use rustpython_compiler::compile;
+    use std::fs;
+    use std::os::unix::io::AsRawFd;
+    use std::panic;
+
+    fn panic_on_purpose(_vm: &VirtualMachine) {
+        panic!("panicking on purpose");
+    }
+
+    #[test]
+    fn test_panic_dumps_python_stack() {
+        let path = std::env::temp_dir().join(format!(
+            "rustpython_faulthandler_test_{}",
+            std::process::id()
+        ));
+        let file = fs::File::create(&path).unwrap();
+        let vm = VirtualMachine::default();
+        let scope = vm.new_scope_with_builtins();
+        scope
+            .globals
+            .set_item(
+                "panic_on_purpose",
+                vm.ctx.new_rustfunc(panic_on_purpose),
+                &vm,
+            )
+            .unwrap();
+        let source = "\
+def outer():
+    inner()
+
+def inner():
+    panic_on_purpose()
+
+outer()
+";
+        let code = vm
+            .compile(source, compile::Mode::Exec, "<faulthandler>".to_owned())
+            .unwrap();
+
+        let args = DumpArgs {
+            file: Some(vm.new_int(file.as_raw_fd())),
+            all_threads: false,
+        };
+        enable(args, &vm).unwrap();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            vm.run_code_obj(code, scope).map(drop)
+        }));
+        assert!(disable(&vm));
+        assert!(result.is_err());
+
+        let output = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            output,
+            "\
+Fatal Python error: Rust panic
+
+Stack (most recent call first):
+  File \"<faulthandler>\", line 5 in inner
+  File \"<faulthandler>\", line 2 in outer
+  File \"<faulthandler>\", line 7 in <module>
+"
+        );
+    }
+}
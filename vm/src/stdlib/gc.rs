@@ -0,0 +1,130 @@
+//! The `gc` module, over the cycle collector of the thread, see `crate::gc`.
+
+use crate::function::{Args, OptionalArg, OptionalOption};
+use crate::gc::{self, GENERATIONS};
+use crate::pyobject::{IdProtocol, PyObjectRef, PyResult};
+use crate::vm::VirtualMachine;
+
+fn gc_enable(_vm: &VirtualMachine) {
+    gc::set_enabled(true)
+}
+
+fn gc_disable(_vm: &VirtualMachine) {
+    gc::set_enabled(false)
+}
+
+fn gc_isenabled(_vm: &VirtualMachine) -> bool {
+    gc::is_enabled()
+}
+
+fn gc_collect(generation: OptionalArg<i32>, vm: &VirtualMachine) -> PyResult<usize> {
+    let generation = generation.unwrap_or(GENERATIONS as i32 - 1);
+    if generation < 0 || generation >= GENERATIONS as i32 {
+        return Err(vm.new_value_error("invalid generation".to_owned()));
+    }
+    Ok(gc::collect(vm, generation as usize))
+}
+
+fn gc_get_count(_vm: &VirtualMachine) -> (usize, usize, usize) {
+    let [count0, count1, count2] = gc::get_count();
+    (count0, count1, count2)
+}
+
+fn gc_get_threshold(_vm: &VirtualMachine) -> (usize, usize, usize) {
+    let [threshold0, threshold1, threshold2] = gc::get_threshold();
+    (threshold0, threshold1, threshold2)
+}
+
+/// The thresholds that aren't given are left as they are.
+fn gc_set_threshold(
+    threshold0: usize,
+    threshold1: OptionalArg<usize>,
+    threshold2: OptionalArg<usize>,
+    _vm: &VirtualMachine,
+) {
+    let [_, old1, old2] = gc::get_threshold();
+    gc::set_threshold([
+        threshold0,
+        threshold1.unwrap_or(old1),
+        threshold2.unwrap_or(old2),
+    ])
+}
+
+fn gc_get_debug(_vm: &VirtualMachine) -> u32 {
+    gc::get_debug()
+}
+
+fn gc_set_debug(flags: u32, _vm: &VirtualMachine) {
+    gc::set_debug(flags)
+}
+
+fn gc_get_objects(generation: OptionalOption<i32>, vm: &VirtualMachine) -> PyResult {
+    let generation = match generation.flat_option() {
+        Some(generation) if generation < 0 => {
+            return Err(vm.new_value_error("generation parameter cannot be negative".to_owned()))
+        }
+        Some(generation) if generation >= GENERATIONS as i32 => {
+            return Err(vm.new_value_error(format!(
+                "generation parameter must be less than the number of available generations ({})",
+                GENERATIONS
+            )))
+        }
+        generation => generation.map(|generation| generation as usize),
+    };
+    Ok(vm.ctx.new_list(gc::get_objects(generation)))
+}
+
+/// The objects that the given ones reference, as far as the collector knows.
+fn gc_get_referents(objs: Args, vm: &VirtualMachine) -> PyObjectRef {
+    let mut referents = vec![];
+    for obj in objs {
+        gc::traverse(&obj, &mut |referent| referents.push(referent.clone()));
+    }
+    vm.ctx.new_list(referents)
+}
+
+/// The tracked objects that reference any of the given ones.
+fn gc_get_referrers(objs: Args, vm: &VirtualMachine) -> PyObjectRef {
+    let objs = objs.into_vec();
+    let referrers = gc::get_objects(None)
+        .into_iter()
+        .filter(|candidate| {
+            let mut found = false;
+            gc::traverse(candidate, &mut |referent| {
+                found |= objs.iter().any(|obj| obj.is(referent));
+            });
+            found
+        })
+        .collect();
+    vm.ctx.new_list(referrers)
+}
+
+fn gc_is_tracked(obj: PyObjectRef, _vm: &VirtualMachine) -> bool {
+    gc::is_tracked(&obj)
+}
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    py_module!(vm, "gc", {
+        "enable" => ctx.new_rustfunc(gc_enable),
+        "disable" => ctx.new_rustfunc(gc_disable),
+        "isenabled" => ctx.new_rustfunc(gc_isenabled),
+        "collect" => ctx.new_rustfunc(gc_collect),
+        "get_count" => ctx.new_rustfunc(gc_get_count),
+        "get_threshold" => ctx.new_rustfunc(gc_get_threshold),
+        "set_threshold" => ctx.new_rustfunc(gc_set_threshold),
+        "get_debug" => ctx.new_rustfunc(gc_get_debug),
+        "set_debug" => ctx.new_rustfunc(gc_set_debug),
+        "get_objects" => ctx.new_rustfunc(gc_get_objects),
+        "get_referents" => ctx.new_rustfunc(gc_get_referents),
+        "get_referrers" => ctx.new_rustfunc(gc_get_referrers),
+        "is_tracked" => ctx.new_rustfunc(gc_is_tracked),
+        "garbage" => vm.gc_garbage.clone().into_object(),
+        "DEBUG_STATS" => ctx.new_int(gc::DEBUG_STATS),
+        "DEBUG_COLLECTABLE" => ctx.new_int(gc::DEBUG_COLLECTABLE),
+        "DEBUG_UNCOLLECTABLE" => ctx.new_int(gc::DEBUG_UNCOLLECTABLE),
+        "DEBUG_SAVEALL" => ctx.new_int(gc::DEBUG_SAVEALL),
+        "DEBUG_LEAK" => ctx.new_int(gc::DEBUG_LEAK),
+    })
+}
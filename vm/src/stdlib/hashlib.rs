@@ -1,5 +1,7 @@
 use crate::function::{OptionalArg, PyFuncArgs};
-use crate::obj::objbytes::{PyBytes, PyBytesRef};
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objset::PySet;
 use crate::obj::objstr::PyStringRef;
 use crate::obj::objtype::PyClassRef;
 use crate::pyobject::{PyClassImpl, PyObjectRef, PyResult, PyValue};
@@ -8,7 +10,8 @@ use std::cell::RefCell;
 use std::fmt;
 
 use blake2::{Blake2b, Blake2s};
-use digest::DynDigest;
+use digest::generic_array::typenum::Unsigned;
+use digest::{BlockInput, DynDigest};
 use md5::Md5;
 use sha1::Sha1;
 use sha2::{Sha224, Sha256, Sha384, Sha512};
@@ -58,9 +61,14 @@ impl PyHasher {
         Ok(vm.ctx.new_int(self.buffer.borrow().digest_size()))
     }
 
+    #[pyproperty(name = "block_size")]
+    fn block_size(&self, vm: &VirtualMachine) -> PyResult {
+        Ok(vm.ctx.new_int(self.buffer.borrow().block_size))
+    }
+
     #[pymethod(name = "update")]
-    fn update(&self, data: PyBytesRef, vm: &VirtualMachine) -> PyResult {
-        self.buffer.borrow_mut().input(data.get_value());
+    fn update(&self, data: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
+        data.with_ref(|bytes| self.buffer.borrow_mut().input(bytes));
         Ok(vm.get_none())
     }
 
@@ -76,16 +84,36 @@ impl PyHasher {
         hex::encode(result)
     }
 
+    #[pymethod(name = "copy")]
+    fn copy(&self, _vm: &VirtualMachine) -> PyHasher {
+        PyHasher::new(&self.name, self.buffer.borrow().clone())
+    }
+
     fn get_digest(&self) -> Vec<u8> {
         self.buffer.borrow().get_digest()
     }
 }
 
-fn hashlib_new(
-    name: PyStringRef,
-    data: OptionalArg<PyBytesRef>,
-    vm: &VirtualMachine,
-) -> PyResult<PyHasher> {
+/// The arguments of the constructors: the initial data, and whether the hash is used for
+/// security, which is accepted for compatibility and ignored.
+#[derive(FromArgs)]
+#[allow(dead_code)]
+struct HashArgs {
+    #[pyarg(positional_only, optional = true)]
+    data: OptionalArg<ArgBytesLike>,
+    #[pyarg(keyword_only, default = "true")]
+    usedforsecurity: bool,
+}
+
+/// The names `hashlib.new` knows, which make up `algorithms_guaranteed` and `algorithms_available`.
+const ALGORITHMS: &[&str] = &[
+    "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "sha3_224", "sha3_256", "sha3_384",
+    "sha3_512", "blake2b", "blake2s",
+];
+
+fn hashlib_new(name: PyStringRef, args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    // Like OpenSSL, accept the names in any case
+    let name = name.as_str().to_lowercase();
     let hasher = match name.as_str() {
         "md5" => Ok(PyHasher::new("md5", HashWrapper::md5())),
         "sha1" => Ok(PyHasher::new("sha1", HashWrapper::sha1())),
@@ -101,74 +129,86 @@ fn hashlib_new(
         // TODO: "shake256" => Ok(PyHasher::new("shake256", HashWrapper::shake256())),
         "blake2b" => Ok(PyHasher::new("blake2b", HashWrapper::blake2b())),
         "blake2s" => Ok(PyHasher::new("blake2s", HashWrapper::blake2s())),
-        other => Err(vm.new_value_error(format!("Unknown hashing algorithm: {}", other))),
+        other => Err(vm.new_value_error(format!("unsupported hash type {}", other))),
     }?;
 
-    if let OptionalArg::Present(data) = data {
+    init(hasher, args, vm)
+}
+
+fn init(hasher: PyHasher, args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    if let OptionalArg::Present(data) = args.data {
         hasher.update(data, vm)?;
     }
 
     Ok(hasher)
 }
 
-fn md5(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("md5", HashWrapper::md5()))
+fn md5(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("md5", HashWrapper::md5()), args, vm)
 }
 
-fn sha1(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha1", HashWrapper::sha1()))
+fn sha1(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha1", HashWrapper::sha1()), args, vm)
 }
 
-fn sha224(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha224", HashWrapper::sha224()))
+fn sha224(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha224", HashWrapper::sha224()), args, vm)
 }
 
-fn sha256(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha256", HashWrapper::sha256()))
+fn sha256(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha256", HashWrapper::sha256()), args, vm)
 }
 
-fn sha384(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha384", HashWrapper::sha384()))
+fn sha384(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha384", HashWrapper::sha384()), args, vm)
 }
 
-fn sha512(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha512", HashWrapper::sha512()))
+fn sha512(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha512", HashWrapper::sha512()), args, vm)
 }
 
-fn sha3_224(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha3_224", HashWrapper::sha3_224()))
+fn sha3_224(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha3_224", HashWrapper::sha3_224()), args, vm)
 }
 
-fn sha3_256(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha3_256", HashWrapper::sha3_256()))
+fn sha3_256(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha3_256", HashWrapper::sha3_256()), args, vm)
 }
 
-fn sha3_384(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha3_384", HashWrapper::sha3_384()))
+fn sha3_384(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha3_384", HashWrapper::sha3_384()), args, vm)
 }
 
-fn sha3_512(_vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Ok(PyHasher::new("sha3_512", HashWrapper::sha3_512()))
+fn sha3_512(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    init(PyHasher::new("sha3_512", HashWrapper::sha3_512()), args, vm)
 }
 
-fn shake128(vm: &VirtualMachine) -> PyResult<PyHasher> {
-    Err(vm.new_not_implemented_error("shake256".to_string()))
-    // Ok(PyHasher::new("shake128", HashWrapper::shake128()))
+fn shake128(_args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
+    Err(vm.new_not_implemented_error("shake128".to_string()))
+    // TODO: Ok(PyHasher::new("shake128", HashWrapper::shake128()))
 }
 
-fn shake256(vm: &VirtualMachine) -> PyResult<PyHasher> {
+fn shake256(_args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
     Err(vm.new_not_implemented_error("shake256".to_string()))
     // TODO: Ok(PyHasher::new("shake256", HashWrapper::shake256()))
 }
 
-fn blake2b(_vm: &VirtualMachine) -> PyResult<PyHasher> {
+fn blake2b(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
     // TODO: handle parameters
-    Ok(PyHasher::new("blake2b", HashWrapper::blake2b()))
+    init(PyHasher::new("blake2b", HashWrapper::blake2b()), args, vm)
 }
 
-fn blake2s(_vm: &VirtualMachine) -> PyResult<PyHasher> {
+fn blake2s(args: HashArgs, vm: &VirtualMachine) -> PyResult<PyHasher> {
     // TODO: handle parameters
-    Ok(PyHasher::new("blake2s", HashWrapper::blake2s()))
+    init(PyHasher::new("blake2s", HashWrapper::blake2s()), args, vm)
+}
+
+fn algorithm_set(vm: &VirtualMachine) -> PyObjectRef {
+    let set = PySet::default().into_ref(vm);
+    for name in ALGORITHMS {
+        set.add(vm.new_str(name.to_string()), vm).unwrap();
+    }
+    set.into_object()
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
@@ -193,20 +233,27 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "blake2b" => ctx.new_rustfunc(blake2b),
         "blake2s" => ctx.new_rustfunc(blake2s),
         "hasher" => hasher_type,
+        "algorithms_guaranteed" => algorithm_set(vm),
+        "algorithms_available" => algorithm_set(vm),
     })
 }
 
 /// Generic wrapper patching around the hashing libraries.
+#[derive(Clone)]
 struct HashWrapper {
     inner: Box<dyn DynDigest>,
+    block_size: usize,
 }
 
 impl HashWrapper {
     fn new<D: 'static>(d: D) -> Self
     where
-        D: DynDigest + Sized,
+        D: DynDigest + BlockInput + Sized,
     {
-        HashWrapper { inner: Box::new(d) }
+        HashWrapper {
+            inner: Box::new(d),
+            block_size: D::BlockSize::to_usize(),
+        }
     }
 
     fn md5() -> Self {
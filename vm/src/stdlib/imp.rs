@@ -1,8 +1,15 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+use crate::frozen;
 use crate::import;
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objbytes::PyBytes;
 use crate::obj::objcode::PyCode;
 use crate::obj::objmodule::PyModuleRef;
 use crate::obj::objstr;
 use crate::obj::objstr::PyStringRef;
+use crate::pyc;
 use crate::pyobject::{ItemProtocol, PyObjectRef, PyResult};
 use crate::vm::VirtualMachine;
 
@@ -52,18 +59,44 @@ fn imp_exec_builtin(_mod: PyModuleRef, _vm: &VirtualMachine) -> i32 {
     0
 }
 
+fn frozen_not_found_error(name: &PyStringRef, vm: &VirtualMachine) -> PyObjectRef {
+    vm.new_import_error_with_name(
+        vm.ctx.exceptions.import_error.clone(),
+        format!("No such frozen object named '{}'", name.as_str()),
+        name.clone().into_object(),
+        vm.get_none(),
+    )
+}
+
 fn imp_get_frozen_object(name: PyStringRef, vm: &VirtualMachine) -> PyResult<PyCode> {
+    let mut code = match vm.frozen.borrow().get(name.as_str()) {
+        Some(frozen) => frozen::module_code(vm, name.as_str(), frozen)?,
+        None => return Err(frozen_not_found_error(&name, vm)),
+    };
+    code.source_path = format!("frozen {}", name.as_str());
+    Ok(PyCode::new(code, &vm.ctx))
+}
+
+fn imp_get_frozen_file(name: PyStringRef, vm: &VirtualMachine) -> PyResult<Option<String>> {
+    vm.frozen
+        .borrow()
+        .get(name.as_str())
+        .map(frozen::module_file)
+        .ok_or_else(|| frozen_not_found_error(&name, vm))
+}
+
+/// The source of the frozen module `name`, if `py_compile_bytecode!` embedded it.
+fn imp_get_frozen_source(name: PyStringRef, vm: &VirtualMachine) -> PyResult<Option<String>> {
     vm.frozen
         .borrow()
         .get(name.as_str())
         .map(|frozen| {
-            let mut frozen = frozen.code.clone();
-            frozen.source_path = format!("frozen {}", name.as_str());
-            PyCode::new(frozen)
-        })
-        .ok_or_else(|| {
-            vm.new_import_error(format!("No such frozen object named {}", name.as_str()))
+            frozen
+                .source
+                .as_ref()
+                .map(|source| source.decode().into_owned())
         })
+        .ok_or_else(|| frozen_not_found_error(&name, vm))
 }
 
 fn imp_init_frozen(name: PyStringRef, vm: &VirtualMachine) -> PyResult {
@@ -75,9 +108,84 @@ fn imp_is_frozen_package(name: PyStringRef, vm: &VirtualMachine) -> PyResult<boo
         .borrow()
         .get(name.as_str())
         .map(|frozen| frozen.package)
-        .ok_or_else(|| {
-            vm.new_import_error(format!("No such frozen object named {}", name.as_str()))
+        .ok_or_else(|| frozen_not_found_error(&name, vm))
+}
+
+/// Whether the frozen module `name` is a namespace package, with no file.
+fn imp_is_frozen_namespace(name: PyStringRef, vm: &VirtualMachine) -> PyResult<bool> {
+    vm.frozen
+        .borrow()
+        .get(name.as_str())
+        .map(|frozen| frozen.namespace)
+        .ok_or_else(|| frozen_not_found_error(&name, vm))
+}
+
+/// The direct submodules of the frozen package `name`, as a list of `(name, is_package)` tuples
+/// sorted by name.
+fn imp_get_frozen_children(name: PyStringRef, vm: &VirtualMachine) -> PyResult {
+    let prefix = format!("{}.", name.as_str());
+    let mut children: Vec<(String, bool)> = vm
+        .frozen
+        .borrow()
+        .iter()
+        .filter(|(child, _)| child.starts_with(&prefix) && !child[prefix.len()..].contains('.'))
+        .map(|(child, frozen)| (child[prefix.len()..].to_string(), frozen.package))
+        .collect();
+    children.sort();
+    let children = children
+        .into_iter()
+        .map(|(child, package)| {
+            vm.ctx
+                .new_tuple(vec![vm.new_str(child), vm.new_bool(package)])
         })
+        .collect();
+    Ok(vm.ctx.new_list(children))
+}
+
+/// The names of the resources of the frozen package `name`, sorted.
+fn imp_get_frozen_resources(name: PyStringRef, vm: &VirtualMachine) -> PyResult {
+    let frozen = vm.frozen.borrow();
+    let frozen = frozen
+        .get(name.as_str())
+        .ok_or_else(|| frozen_not_found_error(&name, vm))?;
+    let mut resources: Vec<&String> = frozen.resources.keys().collect();
+    resources.sort();
+    let resources = resources
+        .into_iter()
+        .map(|resource| vm.new_str(resource.clone()))
+        .collect();
+    Ok(vm.ctx.new_list(resources))
+}
+
+fn imp_get_frozen_resource(
+    name: PyStringRef,
+    resource: PyStringRef,
+    vm: &VirtualMachine,
+) -> PyResult {
+    let frozen = vm.frozen.borrow();
+    let frozen = frozen
+        .get(name.as_str())
+        .ok_or_else(|| frozen_not_found_error(&name, vm))?;
+    match frozen.resources.get(resource.as_str()) {
+        Some(data) => Ok(vm.ctx.new_bytes(data.to_vec())),
+        None => Err(vm.new_exception(
+            vm.ctx.exceptions.file_not_found_error.clone(),
+            format!(
+                "'{}' resource not found in '{}'",
+                resource.as_str(),
+                name.as_str()
+            ),
+        )),
+    }
+}
+
+/// The hash that hash-based pyc files are validated with. Unlike CPython's it isn't keyed with
+/// SipHash, but pyc files are only shared between interpreters with the same magic number anyway.
+fn imp_source_hash(key: u64, source: ArgBytesLike, _vm: &VirtualMachine) -> PyBytes {
+    let mut hasher = DefaultHasher::new();
+    hasher.write_u64(key);
+    source.with_ref(|source| hasher.write(source));
+    PyBytes::new(hasher.finish().to_le_bytes().to_vec())
 }
 
 fn imp_fix_co_filename(_code: PyObjectRef, _path: PyStringRef, _vm: &VirtualMachine) {
@@ -96,9 +204,18 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "create_builtin" => ctx.new_rustfunc(imp_create_builtin),
         "exec_builtin" => ctx.new_rustfunc(imp_exec_builtin),
         "get_frozen_object" => ctx.new_rustfunc(imp_get_frozen_object),
+        "get_frozen_file" => ctx.new_rustfunc(imp_get_frozen_file),
+        "get_frozen_source" => ctx.new_rustfunc(imp_get_frozen_source),
         "init_frozen" => ctx.new_rustfunc(imp_init_frozen),
         "is_frozen_package" => ctx.new_rustfunc(imp_is_frozen_package),
+        "is_frozen_namespace" => ctx.new_rustfunc(imp_is_frozen_namespace),
+        "get_frozen_children" => ctx.new_rustfunc(imp_get_frozen_children),
+        "get_frozen_resources" => ctx.new_rustfunc(imp_get_frozen_resources),
+        "get_frozen_resource" => ctx.new_rustfunc(imp_get_frozen_resource),
         "_fix_co_filename" => ctx.new_rustfunc(imp_fix_co_filename),
+        "source_hash" => ctx.new_rustfunc(imp_source_hash),
+        "check_hash_based_pycs" => ctx.new_str("default".to_owned()),
+        "pyc_magic_number_token" => ctx.new_int(pyc::MAGIC_NUMBER),
     });
 
     module
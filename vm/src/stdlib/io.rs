@@ -8,17 +8,18 @@ use std::io::SeekFrom;
 
 use num_traits::ToPrimitive;
 
-use super::os;
+use super::os::{self, PyPathLike};
 use crate::function::{OptionalArg, OptionalOption, PyFuncArgs};
+use crate::obj::objbool;
 use crate::obj::objbytearray::PyByteArray;
 use crate::obj::objbytes;
 use crate::obj::objbytes::PyBytes;
-use crate::obj::objint::{self, PyIntRef};
+use crate::obj::objint;
 use crate::obj::objstr::{self, PyStringRef};
 use crate::obj::objtype;
 use crate::obj::objtype::PyClassRef;
 use crate::pyobject::TypeProtocol;
-use crate::pyobject::{BufferProtocol, Either, PyObjectRef, PyRef, PyResult, PyValue};
+use crate::pyobject::{BufferProtocol, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject};
 use crate::vm::VirtualMachine;
 
 fn byte_count(bytes: OptionalOption<i64>) -> i64 {
@@ -81,6 +82,20 @@ impl BufferedIO {
 
         Some(buffer)
     }
+
+    //return the current position
+    fn tell(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    //Read up to and including the next newline, or to the end of the file.
+    fn readline(&mut self) -> Option<Vec<u8>> {
+        let mut buffer = Vec::new();
+        match self.cursor.read_until(b'\n', &mut buffer) {
+            Ok(_) => Some(buffer),
+            Err(_) => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -92,7 +107,7 @@ type PyStringIORef = PyRef<PyStringIO>;
 
 impl PyValue for PyStringIO {
     fn class(vm: &VirtualMachine) -> PyClassRef {
-        vm.class("io", "StringIO")
+        vm.class("_io", "StringIO")
     }
 }
 
@@ -127,6 +142,10 @@ impl PyStringIORef {
         true
     }
 
+    fn tell(self, _vm: &VirtualMachine) -> u64 {
+        self.buffer.borrow().tell()
+    }
+
     //Read k bytes from the object and return.
     //If k is undefined || k == -1, then we read all bytes until the end of the file.
     //This also increments the stream position by the value of k
@@ -141,6 +160,15 @@ impl PyStringIORef {
             Err(_) => Err(vm.new_value_error("Error Retrieving Value".to_string())),
         }
     }
+
+    fn readline(self, vm: &VirtualMachine) -> PyResult {
+        let data = self.buffer.borrow_mut().readline().unwrap_or_default();
+
+        match String::from_utf8(data) {
+            Ok(value) => Ok(vm.ctx.new_str(value)),
+            Err(_) => Err(vm.new_value_error("Error Retrieving Value".to_string())),
+        }
+    }
 }
 
 fn string_io_new(
@@ -168,7 +196,7 @@ type PyBytesIORef = PyRef<PyBytesIO>;
 
 impl PyValue for PyBytesIO {
     fn class(vm: &VirtualMachine) -> PyClassRef {
-        vm.class("io", "BytesIO")
+        vm.class("_io", "BytesIO")
     }
 }
 
@@ -207,6 +235,17 @@ impl PyBytesIORef {
     fn seekable(self, _vm: &VirtualMachine) -> bool {
         true
     }
+
+    fn tell(self, _vm: &VirtualMachine) -> u64 {
+        self.buffer.borrow().tell()
+    }
+
+    fn readline(self, vm: &VirtualMachine) -> PyResult {
+        match self.buffer.borrow_mut().readline() {
+            Some(value) => Ok(vm.ctx.new_bytes(value)),
+            None => Err(vm.new_value_error("Error Retrieving Value".to_string())),
+        }
+    }
 }
 
 fn bytes_io_new(
@@ -238,6 +277,16 @@ fn io_base_seekable(_self: PyObjectRef, _vm: &VirtualMachine) -> bool {
     false
 }
 
+/// Closes the file when the VM shuts down and frees it. Like in CPython, errors are ignored.
+fn io_base_del(instance: PyObjectRef, vm: &VirtualMachine) {
+    let closed = vm
+        .get_attribute(instance.clone(), "closed")
+        .and_then(|closed| objbool::boolval(vm, closed));
+    if let Ok(false) = closed {
+        let _ = vm.call_method(&instance, "close", vec![]);
+    }
+}
+
 fn buffered_io_base_init(
     instance: PyObjectRef,
     raw: PyObjectRef,
@@ -247,7 +296,42 @@ fn buffered_io_base_init(
     Ok(())
 }
 
-fn buffered_reader_read(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult<Vec<u8>> {
+fn buffered_io_base_close(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let raw = vm.get_attribute(instance, "raw")?;
+    vm.call_method(&raw, "close", vec![])
+}
+
+fn buffered_io_base_closed(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let raw = vm.get_attribute(instance, "raw")?;
+    vm.get_attribute(raw, "closed")
+}
+
+fn buffered_io_base_fileno(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let raw = vm.get_attribute(instance, "raw")?;
+    // FileIO keeps its file descriptor in a `fileno` attribute instead of a method
+    let fileno = vm.get_attribute(raw, "fileno")?;
+    if vm.is_callable(&fileno) {
+        vm.invoke(&fileno, vec![])
+    } else {
+        Ok(fileno)
+    }
+}
+
+fn buffered_reader_read(
+    instance: PyObjectRef,
+    size: OptionalOption<isize>,
+    vm: &VirtualMachine,
+) -> PyResult<Vec<u8>> {
+    // With a size, read up to that many bytes in one go
+    if let Some(size) = size.flat_option().filter(|size| *size >= 0) {
+        let buffer = vm.ctx.new_bytearray(vec![0; size as usize]);
+        let raw = vm.get_attribute(instance, "raw")?;
+        vm.call_method(&raw, "readinto", vec![buffer.clone()])?;
+        let bytes = buffer.payload::<PyByteArray>().unwrap();
+        let result = bytes.inner.borrow().elements.clone();
+        return Ok(result);
+    }
+
     let buff_size = 8 * 1024;
     let buffer = vm.ctx.new_bytearray(vec![0; buff_size]);
 
@@ -296,29 +380,28 @@ fn compute_c_flag(mode: &str) -> u32 {
 
 fn file_io_init(
     file_io: PyObjectRef,
-    name: Either<PyStringRef, PyIntRef>,
+    name: PyObjectRef,
     mode: OptionalArg<PyStringRef>,
     vm: &VirtualMachine,
 ) -> PyResult {
-    let file_no = match &name {
-        Either::A(name) => {
-            let mode = match mode {
-                OptionalArg::Present(mode) => compute_c_flag(mode.as_str()),
-                OptionalArg::Missing => libc::O_RDONLY as _,
-            };
-            let fno = os::os_open(
-                name.clone(),
-                mode as _,
-                OptionalArg::Missing,
-                OptionalArg::Missing,
-                vm,
-            )?;
-            vm.new_int(fno)
-        }
-        Either::B(fno) => fno.clone().into_object(),
+    let file_no = if objtype::isinstance(&name, &vm.ctx.int_type()) {
+        name.clone()
+    } else {
+        let mode = match mode {
+            OptionalArg::Present(mode) => compute_c_flag(mode.as_str()),
+            OptionalArg::Missing => libc::O_RDONLY as _,
+        };
+        let fno = os::os_open(
+            PyPathLike::try_from_object(vm, name.clone())?,
+            mode as _,
+            OptionalArg::Missing,
+            OptionalArg::Missing,
+            vm,
+        )?;
+        vm.new_int(fno)
     };
 
-    vm.set_attr(&file_io, "name", name.into_object())?;
+    vm.set_attr(&file_io, "name", name)?;
     vm.set_attr(&file_io, "fileno", file_no)?;
     vm.set_attr(&file_io, "closefd", vm.new_bool(false))?;
     vm.set_attr(&file_io, "closed", vm.new_bool(false))?;
@@ -477,6 +560,21 @@ fn text_io_wrapper_seekable(_self: PyObjectRef, _vm: &VirtualMachine) -> bool {
     true
 }
 
+fn text_io_wrapper_close(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let buffer = vm.get_attribute(instance, "buffer")?;
+    vm.call_method(&buffer, "close", vec![])
+}
+
+fn text_io_wrapper_closed(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let buffer = vm.get_attribute(instance, "buffer")?;
+    vm.get_attribute(buffer, "closed")
+}
+
+fn text_io_wrapper_fileno(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let buffer = vm.get_attribute(instance, "buffer")?;
+    vm.call_method(&buffer, "fileno", vec![])
+}
+
 fn text_io_base_read(instance: PyObjectRef, vm: &VirtualMachine) -> PyResult<String> {
     let buffered_reader_class = vm.try_class("_io", "BufferedReader")?;
     let raw = vm.get_attribute(instance.clone(), "buffer").unwrap();
@@ -662,7 +760,8 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "__enter__" => ctx.new_rustfunc(io_base_cm_enter),
         "__exit__" => ctx.new_rustfunc(io_base_cm_exit),
         "seekable" => ctx.new_rustfunc(io_base_seekable),
-        "flush" => ctx.new_rustfunc(io_base_flush)
+        "flush" => ctx.new_rustfunc(io_base_flush),
+        "__del__" => ctx.new_rustfunc(io_base_del)
     });
 
     // IOBase Subclasses
@@ -695,7 +794,10 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         //For more info see: https://github.com/RustPython/RustPython/issues/547
         "__init__" => ctx.new_rustfunc(buffered_io_base_init),
         "read" => ctx.new_rustfunc(buffered_reader_read),
-        "seekable" => ctx.new_rustfunc(buffered_reader_seekable)
+        "seekable" => ctx.new_rustfunc(buffered_reader_seekable),
+        "close" => ctx.new_rustfunc(buffered_io_base_close),
+        "closed" => ctx.new_property(buffered_io_base_closed),
+        "fileno" => ctx.new_rustfunc(buffered_io_base_fileno)
     });
 
     let buffered_writer = py_class!(ctx, "BufferedWriter", buffered_io_base.clone(), {
@@ -704,13 +806,19 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         //For more info see: https://github.com/RustPython/RustPython/issues/547
         "__init__" => ctx.new_rustfunc(buffered_io_base_init),
         "write" => ctx.new_rustfunc(buffered_writer_write),
-        "seekable" => ctx.new_rustfunc(buffered_writer_seekable)
+        "seekable" => ctx.new_rustfunc(buffered_writer_seekable),
+        "close" => ctx.new_rustfunc(buffered_io_base_close),
+        "closed" => ctx.new_property(buffered_io_base_closed),
+        "fileno" => ctx.new_rustfunc(buffered_io_base_fileno)
     });
 
     //TextIOBase Subclass
     let text_io_wrapper = py_class!(ctx, "TextIOWrapper", text_io_base.clone(), {
         "__init__" => ctx.new_rustfunc(text_io_wrapper_init),
-        "seekable" => ctx.new_rustfunc(text_io_wrapper_seekable)
+        "seekable" => ctx.new_rustfunc(text_io_wrapper_seekable),
+        "close" => ctx.new_rustfunc(text_io_wrapper_close),
+        "closed" => ctx.new_property(text_io_wrapper_closed),
+        "fileno" => ctx.new_rustfunc(text_io_wrapper_fileno)
     });
 
     //StringIO: in-memory text
@@ -718,7 +826,9 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         (slot new) => string_io_new,
         "seek" => ctx.new_rustfunc(PyStringIORef::seek),
         "seekable" => ctx.new_rustfunc(PyStringIORef::seekable),
+        "tell" => ctx.new_rustfunc(PyStringIORef::tell),
         "read" => ctx.new_rustfunc(PyStringIORef::read),
+        "readline" => ctx.new_rustfunc(PyStringIORef::readline),
         "write" => ctx.new_rustfunc(PyStringIORef::write),
         "getvalue" => ctx.new_rustfunc(PyStringIORef::getvalue)
     });
@@ -728,8 +838,10 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         (slot new) => bytes_io_new,
         "read" => ctx.new_rustfunc(PyBytesIORef::read),
         "read1" => ctx.new_rustfunc(PyBytesIORef::read),
+        "readline" => ctx.new_rustfunc(PyBytesIORef::readline),
         "seek" => ctx.new_rustfunc(PyBytesIORef::seek),
         "seekable" => ctx.new_rustfunc(PyBytesIORef::seekable),
+        "tell" => ctx.new_rustfunc(PyBytesIORef::tell),
         "write" => ctx.new_rustfunc(PyBytesIORef::write),
         "getvalue" => ctx.new_rustfunc(PyBytesIORef::getvalue)
     });
@@ -7,7 +7,7 @@ use std::rc::Rc;
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 
-use crate::function::{Args, OptionalArg, PyFuncArgs};
+use crate::function::{Args, OptionalArg, OptionalOption, PyFuncArgs};
 use crate::obj::objbool;
 use crate::obj::objint::{self, PyInt, PyIntRef};
 use crate::obj::objiter::{call_next, get_all, get_iter, new_stop_iteration};
@@ -848,6 +848,93 @@ impl PyIterToolsProduct {
     }
 }
 
+#[pyclass(name = "permutations")]
+#[derive(Debug)]
+struct PyItertoolsPermutations {
+    pool: Vec<PyObjectRef>,
+    r: usize,
+    indices: RefCell<Vec<usize>>,
+    cycles: RefCell<Vec<usize>>,
+    started: Cell<bool>,
+    exhausted: Cell<bool>,
+}
+
+impl PyValue for PyItertoolsPermutations {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("itertools", "permutations")
+    }
+}
+
+#[pyimpl]
+impl PyItertoolsPermutations {
+    #[pyslot(new)]
+    fn tp_new(
+        cls: PyClassRef,
+        iterable: PyObjectRef,
+        r: OptionalOption<usize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyRef<Self>> {
+        let pool = get_all(vm, &get_iter(vm, &iterable)?)?;
+        let n = pool.len();
+        let r = r.flat_option().unwrap_or(n);
+
+        PyItertoolsPermutations {
+            pool,
+            r,
+            indices: RefCell::new((0..n).collect()),
+            cycles: RefCell::new((0..r.min(n)).map(|i| n - i).collect()),
+            started: Cell::new(false),
+            exhausted: Cell::new(r > n),
+        }
+        .into_ref_with_type(vm, cls)
+    }
+
+    fn current(&self, vm: &VirtualMachine) -> PyObjectRef {
+        let indices = self.indices.borrow();
+        let permutation = indices[..self.r]
+            .iter()
+            .map(|&i| self.pool[i].clone())
+            .collect::<Vec<_>>();
+        PyTuple::from(permutation).into_ref(vm).into_object()
+    }
+
+    #[pymethod(name = "__next__")]
+    fn next(&self, vm: &VirtualMachine) -> PyResult {
+        if self.exhausted.get() {
+            return Err(new_stop_iteration(vm));
+        }
+        if !self.started.get() {
+            self.started.set(true);
+            return Ok(self.current(vm));
+        }
+
+        // The next permutation in lexicographic order of the indices, as CPython finds it
+        let n = self.pool.len();
+        let mut indices = self.indices.borrow_mut();
+        let mut cycles = self.cycles.borrow_mut();
+        for i in (0..self.r).rev() {
+            cycles[i] -= 1;
+            if cycles[i] == 0 {
+                indices[i..].rotate_left(1);
+                cycles[i] = n - i;
+            } else {
+                let j = n - cycles[i];
+                indices.swap(i, j);
+                drop(indices);
+                drop(cycles);
+                return Ok(self.current(vm));
+            }
+        }
+        self.exhausted.set(true);
+        Err(new_stop_iteration(vm))
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyRef<Self> {
+        zelf
+    }
+}
+
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
@@ -882,6 +969,8 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let product = ctx.new_class("product", ctx.object());
     PyIterToolsProduct::extend_class(ctx, &product);
 
+    let permutations = PyItertoolsPermutations::make_class(ctx);
+
     py_module!(vm, "itertools", {
         "chain" => chain,
         "compress" => compress,
@@ -895,5 +984,6 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "accumulate" => accumulate,
         "tee" => tee,
         "product" => product,
+        "permutations" => permutations,
     })
 }
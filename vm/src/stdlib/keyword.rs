@@ -1,32 +1,25 @@
-/*
- * Testing if a string is a keyword.
- */
+pub(crate) use decl::make_module;
 
-use rustpython_parser::lexer;
+/// Testing if a string is a keyword.
+#[pymodule(name = "keyword")]
+mod decl {
+    use rustpython_parser::lexer;
 
-use crate::obj::objstr::PyStringRef;
-use crate::pyobject::{PyObjectRef, PyResult};
-use crate::vm::VirtualMachine;
+    use crate::obj::objstr::PyStringRef;
+    use crate::pyobject::PyObjectRef;
+    use crate::vm::VirtualMachine;
 
-fn keyword_iskeyword(s: PyStringRef, vm: &VirtualMachine) -> PyResult {
-    let keywords = lexer::get_keywords();
-    let value = keywords.contains_key(s.as_str());
-    let value = vm.ctx.new_bool(value);
-    Ok(value)
-}
-
-pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
-    let ctx = &vm.ctx;
-
-    let keyword_kwlist = ctx.new_list(
-        lexer::get_keywords()
-            .keys()
-            .map(|k| ctx.new_str(k.to_string()))
-            .collect(),
-    );
+    /// Return True if s is a Python keyword.
+    #[pyfunction]
+    fn iskeyword(s: PyStringRef, _vm: &VirtualMachine) -> bool {
+        lexer::get_keywords().contains_key(s.as_str())
+    }
 
-    py_module!(vm, "keyword", {
-        "iskeyword" => ctx.new_rustfunc(keyword_iskeyword),
-        "kwlist" => keyword_kwlist
-    })
+    #[pyattr]
+    fn kwlist(vm: &VirtualMachine) -> PyObjectRef {
+        let mut keywords: Vec<_> = lexer::get_keywords().into_iter().map(|(k, _)| k).collect();
+        keywords.sort();
+        vm.ctx
+            .new_list(keywords.into_iter().map(|k| vm.ctx.new_str(k)).collect())
+    }
 }
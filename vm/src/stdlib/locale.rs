@@ -0,0 +1,286 @@
+//! The `_locale` module, which `locale` is built on.
+//!
+//! Only collation goes beyond the "C" locale. With `LC_COLLATE` set to "C" or "POSIX", which is
+//! how every program starts, strings compare by their code points, like `str` does. Any other
+//! locale name for `LC_COLLATE` selects the same collation, for every language: like the root
+//! collation of ICU, strings compare by their letters first, ignoring case and accents, then by
+//! their accents, then by their case, lowercase first. `collation_key` is that collation whatever
+//! the locale is. The other categories always follow the "C" conventions.
+
+use std::env;
+
+use unic::normal::StrNormalForm;
+use unic::ucd::category::GeneralCategory;
+
+use crate::function::OptionalArg;
+use crate::obj::objstr::PyStringRef;
+use crate::pyobject::{ItemProtocol, PyObjectRef, PyResult};
+use crate::types::create_type;
+use crate::vm::VirtualMachine;
+
+const LC_CTYPE: i32 = 0;
+const LC_NUMERIC: i32 = 1;
+const LC_TIME: i32 = 2;
+const LC_COLLATE: i32 = 3;
+const LC_MONETARY: i32 = 4;
+const LC_MESSAGES: i32 = 5;
+const LC_ALL: i32 = 6;
+const CHAR_MAX: i32 = 127;
+
+const CATEGORY_NAMES: [&str; 6] = [
+    "LC_CTYPE",
+    "LC_NUMERIC",
+    "LC_TIME",
+    "LC_COLLATE",
+    "LC_MONETARY",
+    "LC_MESSAGES",
+];
+
+/// The locale of `category`, which all the threads share, like in C.
+fn current_locale(category: i32, vm: &VirtualMachine) -> String {
+    match vm.locales.borrow()[category as usize].as_str() {
+        "" => "C".to_owned(),
+        name => name.to_owned(),
+    }
+}
+
+fn is_c_locale(name: &str) -> bool {
+    name == "C" || name == "POSIX"
+}
+
+/// Whether `name` looks like a locale name, such as "en_US.UTF-8" or "de_DE@euro".
+fn is_locale_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_.-@".contains(c))
+}
+
+/// The locale that "" stands for in `category`, from the environment like in C.
+fn default_locale(category: i32) -> String {
+    let name = ["LC_ALL", CATEGORY_NAMES[category as usize], "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty());
+    match name {
+        Some(name) if category == LC_COLLATE && is_locale_name(&name) && !is_c_locale(&name) => {
+            name
+        }
+        _ => "C".to_owned(),
+    }
+}
+
+fn locale_error(vm: &VirtualMachine) -> PyObjectRef {
+    let module = vm
+        .get_attribute(vm.sys_module.clone(), "modules")
+        .unwrap()
+        .get_item("_locale", vm)
+        .unwrap();
+    let error = vm.get_attribute(module, "Error").unwrap();
+    vm.new_exception(
+        error.downcast().unwrap(),
+        "unsupported locale setting".to_owned(),
+    )
+}
+
+fn locale_setlocale(
+    category: i32,
+    locale: OptionalArg<Option<PyStringRef>>,
+    vm: &VirtualMachine,
+) -> PyResult<String> {
+    if category < LC_CTYPE || category > LC_ALL {
+        return Err(vm.new_value_error("invalid locale category".to_owned()));
+    }
+    if let OptionalArg::Present(Some(locale)) = locale {
+        let locale = locale.as_str();
+        let categories_set_together = category == LC_ALL;
+        let categories = if categories_set_together {
+            LC_CTYPE..=LC_MESSAGES
+        } else {
+            category..=category
+        };
+        let mut names = Vec::new();
+        for category in categories {
+            let name = if locale.is_empty() {
+                default_locale(category)
+            } else if is_c_locale(locale) {
+                "C".to_owned()
+            } else if is_locale_name(locale) && category == LC_COLLATE {
+                locale.to_owned()
+            } else if is_locale_name(locale) && categories_set_together {
+                // Only the collation of other locales is known
+                "C".to_owned()
+            } else {
+                return Err(locale_error(vm));
+            };
+            names.push((category, name));
+        }
+        let mut locales = vm.locales.borrow_mut();
+        for (category, name) in names {
+            locales[category as usize] = name;
+        }
+    }
+    if category != LC_ALL {
+        return Ok(current_locale(category, vm));
+    }
+    let names: Vec<_> = (LC_CTYPE..LC_ALL)
+        .map(|category| current_locale(category, vm))
+        .collect();
+    if names.iter().all(|name| *name == names[0]) {
+        Ok(names[0].clone())
+    } else {
+        // Like glibc does when the categories differ
+        Ok(CATEGORY_NAMES
+            .iter()
+            .zip(names)
+            .map(|(category, name)| format!("{}={}", category, name))
+            .collect::<Vec<_>>()
+            .join(";"))
+    }
+}
+
+fn locale_localeconv(vm: &VirtualMachine) -> PyResult {
+    let ctx = &vm.ctx;
+    let conv = ctx.new_dict();
+    for (key, value) in &[
+        ("decimal_point", "."),
+        ("thousands_sep", ""),
+        ("int_curr_symbol", ""),
+        ("currency_symbol", ""),
+        ("mon_decimal_point", ""),
+        ("mon_thousands_sep", ""),
+        ("positive_sign", ""),
+        ("negative_sign", ""),
+    ] {
+        conv.set_item(*key, ctx.new_str((*value).to_owned()), vm)?;
+    }
+    for key in &[
+        "int_frac_digits",
+        "frac_digits",
+        "p_cs_precedes",
+        "p_sep_by_space",
+        "n_cs_precedes",
+        "n_sep_by_space",
+        "p_sign_posn",
+        "n_sign_posn",
+    ] {
+        conv.set_item(*key, ctx.new_int(CHAR_MAX), vm)?;
+    }
+    conv.set_item("grouping", ctx.new_list(vec![]), vm)?;
+    conv.set_item("mon_grouping", ctx.new_list(vec![]), vm)?;
+    Ok(conv.into_object())
+}
+
+fn uses_collation_table(vm: &VirtualMachine) -> bool {
+    !is_c_locale(&current_locale(LC_COLLATE, vm))
+}
+
+/// Like in C, where strings end at a null character.
+fn check_no_null(string: &PyStringRef, vm: &VirtualMachine) -> PyResult<()> {
+    if string.as_str().contains('\0') {
+        Err(vm.new_value_error("embedded null character".to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+fn locale_strcoll(a: PyStringRef, b: PyStringRef, vm: &VirtualMachine) -> PyResult<i32> {
+    check_no_null(&a, vm)?;
+    check_no_null(&b, vm)?;
+    let ordering = if uses_collation_table(vm) {
+        collation_key(a.as_str()).cmp(&collation_key(b.as_str()))
+    } else {
+        a.as_str().cmp(b.as_str())
+    };
+    Ok(ordering as i32)
+}
+
+fn locale_strxfrm(string: PyStringRef, vm: &VirtualMachine) -> PyResult<String> {
+    check_no_null(&string, vm)?;
+    Ok(if uses_collation_table(vm) {
+        collation_key(string.as_str())
+    } else {
+        string.as_str().to_owned()
+    })
+}
+
+fn locale_collation_key(string: PyStringRef, _vm: &VirtualMachine) -> String {
+    collation_key(string.as_str())
+}
+
+/// Ends each level of a collation key, so a shorter level sorts first.
+const LEVEL_END: char = '\u{0}';
+
+/// Pushes `string` so that it sorts the same way, but only ever has `LEVEL_END` at the end.
+fn push_escaped(key: &mut String, string: &str) {
+    for c in string.chars() {
+        match c {
+            '\u{0}' => key.push_str("\u{1}\u{1}"),
+            '\u{1}' => key.push_str("\u{1}\u{2}"),
+            c => key.push(c),
+        }
+    }
+    key.push(LEVEL_END);
+}
+
+fn is_mark(c: char) -> bool {
+    GeneralCategory::of(c).is_mark()
+}
+
+/// A string that sorts like `string` does in the collation table. Only equal strings have
+/// equal keys, so that sorting by them is the same whatever order the strings come in.
+fn collation_key(string: &str) -> String {
+    // Each letter is case folded on its own, so that the levels line up
+    let mut letters = String::new();
+    let mut accents = String::new();
+    let mut cases = String::new();
+    for c in string.nfd() {
+        if is_mark(c) {
+            accents.push(c);
+            continue;
+        }
+        let case = if c.is_uppercase() { '\u{2}' } else { '\u{1}' };
+        for folded in caseless::default_case_fold_str(c.encode_utf8(&mut [0; 4])).chars() {
+            letters.push(folded);
+            // No accent on a letter sorts first
+            accents.push('\u{1}');
+            cases.push(case);
+        }
+    }
+    let mut key = String::new();
+    push_escaped(&mut key, &letters);
+    key.push_str(&accents);
+    key.push(LEVEL_END);
+    key.push_str(&cases);
+    key.push(LEVEL_END);
+    // The code points, for the strings that are the same otherwise
+    push_escaped(&mut key, string);
+    key
+}
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    let locale_error = create_type(
+        "Error",
+        &ctx.types.type_type,
+        &ctx.exceptions.exception_type,
+    );
+
+    py_module!(vm, "_locale", {
+        "Error" => locale_error,
+        "setlocale" => ctx.new_rustfunc(locale_setlocale),
+        "localeconv" => ctx.new_rustfunc(locale_localeconv),
+        "strcoll" => ctx.new_rustfunc(locale_strcoll),
+        "strxfrm" => ctx.new_rustfunc(locale_strxfrm),
+        "collation_key" => ctx.new_rustfunc(locale_collation_key),
+        "LC_CTYPE" => ctx.new_int(LC_CTYPE),
+        "LC_NUMERIC" => ctx.new_int(LC_NUMERIC),
+        "LC_TIME" => ctx.new_int(LC_TIME),
+        "LC_COLLATE" => ctx.new_int(LC_COLLATE),
+        "LC_MONETARY" => ctx.new_int(LC_MONETARY),
+        "LC_MESSAGES" => ctx.new_int(LC_MESSAGES),
+        "LC_ALL" => ctx.new_int(LC_ALL),
+        "CHAR_MAX" => ctx.new_int(CHAR_MAX),
+    })
+}
@@ -0,0 +1,370 @@
+//! The `_lsprof` module, the profiler `cProfile` is built on.
+//!
+//! A `Profiler` installs itself as the profile function of the thread, see `sys.setprofile`, and
+//! times the calls of Python functions and built-in functions from the `call`, `return`,
+//! `c_call`, `c_return` and `c_exception` events, like CPython's does.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use crate::frame::FrameRef;
+use crate::function::OptionalArg;
+use crate::obj::objbuiltinfunc::PyBuiltinFunction;
+use crate::obj::objfloat::IntoPyFloat;
+use crate::obj::objstr::PyStringRef;
+use crate::obj::objtype::PyClassRef;
+use crate::pyobject::{
+    IdProtocol, PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject,
+};
+use crate::vm::VirtualMachine;
+
+/// Times are kept in nanoseconds, so that the sums of them are exact, and only turned into
+/// seconds for `getstats`.
+type Nanos = i64;
+
+fn seconds(time: Nanos) -> f64 {
+    time as f64 / 1e9
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Stats {
+    callcount: usize,
+    /// How many of the calls were made while another one was running.
+    reccallcount: usize,
+    totaltime: Nanos,
+    /// The time spent in the function itself, not in the ones it called.
+    inlinetime: Nanos,
+    /// How many calls are running.
+    recursion_level: usize,
+}
+
+impl Stats {
+    fn enter(&mut self) {
+        self.recursion_level += 1;
+    }
+
+    /// The time of a recursive call is already part of the outermost one.
+    fn leave(&mut self, total: Nanos, inline: Nanos) {
+        self.recursion_level = self.recursion_level.saturating_sub(1);
+        if self.recursion_level == 0 {
+            self.totaltime += total;
+        } else {
+            self.reccallcount += 1;
+        }
+        self.inlinetime += inline;
+        self.callcount += 1;
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    /// The code object of the function, or the label of the built-in function.
+    code: PyObjectRef,
+    /// The object the entry is found by, kept alive so that its id isn't reused.
+    _key: PyObjectRef,
+    stats: Stats,
+    /// The calls the function made, by the index of the callee.
+    calls: BTreeMap<usize, Stats>,
+}
+
+/// A call that's running.
+#[derive(Debug)]
+struct Context {
+    entry: usize,
+    start: Nanos,
+    /// The time spent in the calls made from this one.
+    subcalls_time: Nanos,
+}
+
+#[derive(Debug, Default)]
+struct ProfilerState {
+    entries: Vec<Entry>,
+    /// The index of the entry of each code object and built-in function, by its id.
+    index: HashMap<usize, usize>,
+    stack: Vec<Context>,
+}
+
+impl ProfilerState {
+    fn entry(&mut self, key: &PyObjectRef, code: impl FnOnce() -> PyObjectRef) -> usize {
+        let entries = &mut self.entries;
+        *self.index.entry(key.get_id()).or_insert_with(|| {
+            entries.push(Entry {
+                code: code(),
+                _key: key.clone(),
+                stats: Stats::default(),
+                calls: BTreeMap::new(),
+            });
+            entries.len() - 1
+        })
+    }
+
+    fn enter(&mut self, entry: usize, subcalls: bool, now: Nanos) {
+        self.entries[entry].stats.enter();
+        if subcalls {
+            if let Some(caller) = self.stack.last() {
+                let calls = &mut self.entries[caller.entry].calls;
+                calls.entry(entry).or_default().enter();
+            }
+        }
+        self.stack.push(Context {
+            entry,
+            start: now,
+            subcalls_time: 0,
+        });
+    }
+
+    /// Stop timing the innermost call if it's a call of `key`. The calls that were running when
+    /// the profiler was enabled end without having started.
+    fn leave(&mut self, key: &PyObjectRef, subcalls: bool, now: Nanos) {
+        let entry = self.index.get(&key.get_id()).cloned();
+        match self.stack.last() {
+            Some(context) if Some(context.entry) == entry => self.leave_innermost(subcalls, now),
+            _ => {}
+        }
+    }
+
+    fn leave_innermost(&mut self, subcalls: bool, now: Nanos) {
+        let context = match self.stack.pop() {
+            Some(context) => context,
+            None => return,
+        };
+        let total = now - context.start;
+        let inline = total - context.subcalls_time;
+        self.entries[context.entry].stats.leave(total, inline);
+        if let Some(caller) = self.stack.last_mut() {
+            caller.subcalls_time += total;
+            if subcalls {
+                let calls = &mut self.entries[caller.entry].calls;
+                if let Some(stats) = calls.get_mut(&context.entry) {
+                    stats.leave(total, inline);
+                }
+            }
+        }
+    }
+}
+
+#[pystruct_sequence(name = "profiler_entry")]
+#[derive(Debug)]
+struct ProfilerEntry {
+    code: PyObjectRef,
+    callcount: usize,
+    reccallcount: usize,
+    totaltime: f64,
+    inlinetime: f64,
+    /// The `profiler_subentry`s of the calls the function made, or None.
+    calls: PyObjectRef,
+}
+
+#[pystruct_sequence(name = "profiler_subentry")]
+#[derive(Debug)]
+struct ProfilerSubentry {
+    code: PyObjectRef,
+    callcount: usize,
+    reccallcount: usize,
+    totaltime: f64,
+    inlinetime: f64,
+}
+
+/// Build a profiler object using the specified timer function.
+///
+/// The default timer is a fast built-in one. The optional timeunit is what the values the timer
+/// returns are multiplied by to get seconds.
+#[pyclass(name = "Profiler")]
+#[derive(Debug)]
+struct PyProfiler {
+    timer: Option<PyObjectRef>,
+    timeunit: f64,
+    subcalls: Cell<bool>,
+    builtins: Cell<bool>,
+    start: Instant,
+    state: RefCell<ProfilerState>,
+}
+type PyProfilerRef = PyRef<PyProfiler>;
+
+impl PyValue for PyProfiler {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("_lsprof", "Profiler")
+    }
+}
+
+#[derive(FromArgs)]
+struct ProfilerArgs {
+    #[pyarg(positional_or_keyword, default = "None")]
+    timer: Option<PyObjectRef>,
+    #[pyarg(positional_or_keyword, default = "None")]
+    timeunit: Option<IntoPyFloat>,
+    #[pyarg(positional_or_keyword, default = "true")]
+    subcalls: bool,
+    #[pyarg(positional_or_keyword, default = "true")]
+    builtins: bool,
+}
+
+/// What isn't given stays as it was set before.
+#[derive(FromArgs)]
+struct EnableArgs {
+    #[pyarg(positional_or_keyword, optional = true)]
+    subcalls: OptionalArg<bool>,
+    #[pyarg(positional_or_keyword, optional = true)]
+    builtins: OptionalArg<bool>,
+}
+
+#[pyimpl]
+impl PyProfiler {
+    #[pyslot(new)]
+    fn tp_new(cls: PyClassRef, args: ProfilerArgs, vm: &VirtualMachine) -> PyResult<PyProfilerRef> {
+        PyProfiler {
+            timer: args.timer.filter(|timer| !vm.is_none(timer)),
+            timeunit: args.timeunit.map_or(0.0, IntoPyFloat::to_f64),
+            subcalls: Cell::new(args.subcalls),
+            builtins: Cell::new(args.builtins),
+            start: Instant::now(),
+            state: RefCell::default(),
+        }
+        .into_ref_with_type(vm, cls)
+    }
+
+    /// The time. A timer that fails is reported and counts as 0.
+    fn now(&self, vm: &VirtualMachine) -> Nanos {
+        let timer = match self.timer {
+            Some(ref timer) => timer,
+            None => return self.start.elapsed().as_nanos() as Nanos,
+        };
+        let time = vm
+            .invoke(timer, vec![])
+            .and_then(|time| IntoPyFloat::try_from_object(vm, time));
+        match time {
+            Ok(time) if self.timeunit > 0.0 => (time.to_f64() * self.timeunit * 1e9) as Nanos,
+            Ok(time) => (time.to_f64() * 1e9) as Nanos,
+            Err(exc) => {
+                vm.print_unraisable(timer, &exc);
+                0
+            }
+        }
+    }
+
+    fn dispatch(&self, frame: &FrameRef, event: &str, arg: &PyObjectRef, vm: &VirtualMachine) {
+        let subcalls = self.subcalls.get();
+        match event {
+            "call" => {
+                let code = frame.code.clone().into_object();
+                let now = self.now(vm);
+                let mut state = self.state.borrow_mut();
+                let entry = state.entry(&code, || code.clone());
+                state.enter(entry, subcalls, now);
+            }
+            "return" => {
+                let code = frame.code.clone().into_object();
+                let now = self.now(vm);
+                self.state.borrow_mut().leave(&code, subcalls, now);
+            }
+            "c_call" if self.builtins.get() => {
+                let now = self.now(vm);
+                let mut state = self.state.borrow_mut();
+                let entry = state.entry(arg, || {
+                    let name = arg
+                        .payload::<PyBuiltinFunction>()
+                        .and_then(|func| func.name(vm).ok())
+                        .unwrap_or_else(|| "?".to_owned());
+                    vm.new_str(format!("<built-in method {}>", name))
+                });
+                state.enter(entry, subcalls, now);
+            }
+            "c_return" | "c_exception" if self.builtins.get() => {
+                let now = self.now(vm);
+                self.state.borrow_mut().leave(arg, subcalls, now);
+            }
+            _ => {}
+        }
+    }
+
+    /// Start collecting profiling information.
+    #[pymethod]
+    fn enable(zelf: PyRef<Self>, args: EnableArgs, vm: &VirtualMachine) {
+        if let OptionalArg::Present(subcalls) = args.subcalls {
+            zelf.subcalls.set(subcalls);
+        }
+        if let OptionalArg::Present(builtins) = args.builtins {
+            zelf.builtins.set(builtins);
+        }
+        let profiler = zelf.clone();
+        let callback = vm.ctx.new_rustfunc(
+            move |frame: FrameRef, event: PyStringRef, arg: PyObjectRef, vm: &VirtualMachine| {
+                profiler.dispatch(&frame, event.as_str(), &arg, vm)
+            },
+        );
+        vm.profile_func.replace(callback);
+        vm.update_use_tracing();
+    }
+
+    /// Stop collecting profiling information. The calls that are running end now.
+    #[pymethod]
+    fn disable(&self, vm: &VirtualMachine) {
+        vm.profile_func.replace(vm.get_none());
+        vm.update_use_tracing();
+        let now = self.now(vm);
+        let mut state = self.state.borrow_mut();
+        while !state.stack.is_empty() {
+            state.leave_innermost(self.subcalls.get(), now);
+        }
+    }
+
+    /// Clear all profiling information collected so far.
+    #[pymethod]
+    fn clear(&self, _vm: &VirtualMachine) {
+        self.state.replace(ProfilerState::default());
+    }
+
+    /// A list of `profiler_entry` objects, one for each function that was called, with the
+    /// `profiler_subentry` objects of the calls it made.
+    #[pymethod]
+    fn getstats(&self, vm: &VirtualMachine) -> PyResult {
+        let entry_type = vm.class("_lsprof", "profiler_entry");
+        let subentry_type = vm.class("_lsprof", "profiler_subentry");
+        let state = self.state.borrow();
+        let mut stats = Vec::with_capacity(state.entries.len());
+        for entry in &state.entries {
+            let calls = if entry.calls.is_empty() {
+                vm.get_none()
+            } else {
+                let mut calls = Vec::with_capacity(entry.calls.len());
+                for (&callee, call) in &entry.calls {
+                    let subentry = ProfilerSubentry {
+                        code: state.entries[callee].code.clone(),
+                        callcount: call.callcount,
+                        reccallcount: call.reccallcount,
+                        totaltime: seconds(call.totaltime),
+                        inlinetime: seconds(call.inlinetime),
+                    };
+                    let subentry = subentry.into_struct_sequence(vm, subentry_type.clone())?;
+                    calls.push(subentry.into_object());
+                }
+                vm.ctx.new_list(calls)
+            };
+            let entry = ProfilerEntry {
+                code: entry.code.clone(),
+                callcount: entry.stats.callcount,
+                reccallcount: entry.stats.reccallcount,
+                totaltime: seconds(entry.stats.totaltime),
+                inlinetime: seconds(entry.stats.inlinetime),
+                calls,
+            };
+            stats.push(
+                entry
+                    .into_struct_sequence(vm, entry_type.clone())?
+                    .into_object(),
+            );
+        }
+        Ok(vm.ctx.new_list(stats))
+    }
+}
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    py_module!(vm, "_lsprof", {
+        "Profiler" => PyProfiler::make_class(ctx),
+        "profiler_entry" => ProfilerEntry::make_class(ctx),
+        "profiler_subentry" => ProfilerSubentry::make_class(ctx),
+    })
+}
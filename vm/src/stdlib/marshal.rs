@@ -1,24 +1,233 @@
-use crate::bytecode;
-use crate::obj::objbytes::{PyBytes, PyBytesRef};
-use crate::obj::objcode::{PyCode, PyCodeRef};
-use crate::pyobject::{PyObjectRef, PyResult};
+//! Values are marshalled in the format of CPython's `marshal`, and code objects with the
+//! RustPython bytecode embedded, so that the pyc files importlib writes with it can be read by
+//! CPython too. The format is `rustpython_bytecode::pyc`'s, which frozen modules can be written
+//! in as well.
+
+use crate::function::OptionalArg;
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objcode::PyCode;
+use crate::obj::objdict::PyDictRef;
+use crate::obj::{objbool, objcomplex, objfloat, objint, objsequence, objstr};
+use crate::pyc::{self, MarshalCode, PycError, Value};
+use crate::pyobject::{
+    IdProtocol, ItemProtocol, PyObjectRef, PyResult, PyValue, TryFromObject, TypeProtocol,
+};
 use crate::vm::VirtualMachine;
+use std::io;
+
+/// The version of CPython's format that `dumps` writes, which all later versions can read.
+const VERSION: i32 = 4;
+
+/// How deep `dumps` goes into nested containers, like CPython's `MAX_MARSHAL_STACK_DEPTH`, so
+/// that a list containing itself is an error rather than a stack overflow.
+const MAX_DEPTH: usize = 2000;
+
+fn to_value(obj: &PyObjectRef, depth: usize, vm: &VirtualMachine) -> PyResult<Value> {
+    if depth > MAX_DEPTH {
+        return Err(vm.new_value_error("object too deeply nested to marshal".to_owned()));
+    }
+    let ctx = &vm.ctx;
+    let values = |elements: &[PyObjectRef]| -> PyResult<Vec<Value>> {
+        elements
+            .iter()
+            .map(|element| to_value(element, depth + 1, vm))
+            .collect()
+    };
+    // Like CPython, subclasses of the builtin types aren't marshalled, but for bytes-like objects
+    let class = obj.class();
+    let value = if vm.is_none(obj) {
+        Value::None
+    } else if obj.is(&ctx.ellipsis) {
+        Value::Ellipsis
+    } else if obj.is(&ctx.exceptions.stop_iteration) {
+        Value::StopIteration
+    } else if class.is(&ctx.bool_type()) {
+        Value::Bool(objbool::get_value(obj))
+    } else if class.is(&ctx.int_type()) {
+        Value::Int(objint::get_value(obj).clone())
+    } else if class.is(&ctx.float_type()) {
+        Value::Float(objfloat::get_value(obj))
+    } else if class.is(&ctx.complex_type()) {
+        Value::Complex(objcomplex::get_value(obj))
+    } else if class.is(&ctx.str_type()) {
+        Value::Str(objstr::get_value(obj))
+    } else if class.is(&ctx.tuple_type()) {
+        Value::Tuple(values(&objsequence::get_elements_tuple(obj))?)
+    } else if class.is(&ctx.list_type()) {
+        let elements = objsequence::get_elements_list(obj).clone();
+        Value::List(values(&elements)?)
+    } else if class.is(&ctx.dict_type()) {
+        let dict: PyDictRef = obj.clone().downcast().unwrap();
+        let items = dict
+            .into_iter()
+            .map(|(key, value)| {
+                Ok((
+                    to_value(&key, depth + 1, vm)?,
+                    to_value(&value, depth + 1, vm)?,
+                ))
+            })
+            .collect::<PyResult<_>>()?;
+        Value::Dict(items)
+    } else if class.is(&ctx.set_type()) {
+        Value::Set(values(&vm.extract_elements::<PyObjectRef>(obj)?)?)
+    } else if class.is(&ctx.frozenset_type()) {
+        Value::FrozenSet(values(&vm.extract_elements::<PyObjectRef>(obj)?)?)
+    } else if let Some(code) = obj.payload::<PyCode>() {
+        let code = MarshalCode::from_code(&code.code, pyc::MAGIC_NUMBER)
+            .map_err(|err| vm.new_value_error(err.to_string()))?;
+        Value::Code(Box::new(code))
+    } else if let Ok(bytes) = ArgBytesLike::try_from_object(vm, obj.clone()) {
+        Value::Bytes(bytes.with_ref(|bytes| bytes.to_vec()))
+    } else {
+        return Err(vm.new_value_error("unmarshallable object".to_owned()));
+    };
+    Ok(value)
+}
+
+fn from_value(value: Value, vm: &VirtualMachine) -> PyResult {
+    let ctx = &vm.ctx;
+    let objects = |values: Vec<Value>| -> PyResult<Vec<PyObjectRef>> {
+        values
+            .into_iter()
+            .map(|value| from_value(value, vm))
+            .collect()
+    };
+    let obj = match value {
+        Value::None => vm.get_none(),
+        Value::StopIteration => ctx.exceptions.stop_iteration.clone().into_object(),
+        Value::Ellipsis => ctx.ellipsis.clone().into_object(),
+        Value::Bool(value) => ctx.new_bool(value),
+        Value::Int(value) => ctx.new_bigint(&value),
+        Value::Float(value) => ctx.new_float(value),
+        Value::Complex(value) => ctx.new_complex(value),
+        Value::Bytes(value) => ctx.new_bytes(value),
+        Value::Str(value) => ctx.new_str(value),
+        Value::Tuple(elements) => ctx.new_tuple(objects(elements)?),
+        Value::List(elements) => ctx.new_list(objects(elements)?),
+        Value::Dict(items) => {
+            let dict = ctx.new_dict();
+            for (key, value) in items {
+                dict.set_item(&from_value(key, vm)?, from_value(value, vm)?, vm)?;
+            }
+            dict.into_object()
+        }
+        Value::Set(elements) => {
+            let elements = ctx.new_list(objects(elements)?);
+            vm.invoke(&ctx.set_type().into_object(), vec![elements])?
+        }
+        Value::FrozenSet(elements) => {
+            let elements = ctx.new_list(objects(elements)?);
+            vm.invoke(&ctx.frozenset_type().into_object(), vec![elements])?
+        }
+        Value::Code(code) => {
+            let code = code.to_code().map_err(|err| load_error(err, vm))?;
+            ctx.new_code_object(code).into_object()
+        }
+    };
+    Ok(obj)
+}
+
+fn load_error(err: PycError, vm: &VirtualMachine) -> PyObjectRef {
+    match err {
+        PycError::UnexpectedEof => vm.new_exception(
+            vm.ctx.exceptions.eof_error.clone(),
+            "EOF read where object expected".to_owned(),
+        ),
+        PycError::InvalidType(_)
+        | PycError::InvalidReference(_)
+        | PycError::InvalidUtf8
+        | PycError::Unexpected(_) => vm.new_value_error(err.to_string()),
+        err => vm.new_value_error(format!("bad marshal data ({})", err)),
+    }
+}
+
+fn marshal_dumps(
+    value: PyObjectRef,
+    _version: OptionalArg<i32>,
+    vm: &VirtualMachine,
+) -> PyResult<PyBytes> {
+    let value = to_value(&value, 0, vm)?;
+    pyc::dump_value(&value, pyc::MAGIC_NUMBER)
+        .map(PyBytes::new)
+        .map_err(|err| vm.new_value_error(err.to_string()))
+}
+
+fn marshal_loads(data: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
+    let (value, _) = data
+        .with_ref(|data| pyc::load_value(data, pyc::MAGIC_NUMBER))
+        .map_err(|err| load_error(err, vm))?;
+    from_value(value, vm)
+}
+
+fn marshal_dump(
+    value: PyObjectRef,
+    file: PyObjectRef,
+    version: OptionalArg<i32>,
+    vm: &VirtualMachine,
+) -> PyResult<()> {
+    let data = marshal_dumps(value, version, vm)?.into_ref(vm);
+    vm.call_method(&file, "write", vec![data.into_object()])?;
+    Ok(())
+}
+
+/// Reads a Python file through its `read` method, keeping the exception it raises, if any.
+struct FileReader<'a> {
+    file: &'a PyObjectRef,
+    vm: &'a VirtualMachine,
+    error: Option<PyObjectRef>,
+}
+
+impl FileReader<'_> {
+    fn read_into(&self, buf: &mut [u8]) -> PyResult<usize> {
+        let vm = self.vm;
+        let data = vm.call_method(self.file, "read", vec![vm.new_int(buf.len())])?;
+        let data = ArgBytesLike::try_from_object(vm, data)?;
+        data.with_ref(|data| {
+            if data.len() > buf.len() {
+                return Err(vm.new_value_error(format!(
+                    "read() returned too much data: {} bytes requested, {} returned",
+                    buf.len(),
+                    data.len()
+                )));
+            }
+            buf[..data.len()].copy_from_slice(data);
+            Ok(data.len())
+        })
+    }
+}
 
-fn marshal_dumps(co: PyCodeRef, _vm: &VirtualMachine) -> PyBytes {
-    PyBytes::new(co.code.to_bytes())
+impl io::Read for FileReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_into(buf).map_err(|exc| {
+            self.error = Some(exc);
+            io::Error::new(io::ErrorKind::Other, "read() raised an exception")
+        })
+    }
 }
 
-fn marshal_loads(code_bytes: PyBytesRef, vm: &VirtualMachine) -> PyResult<PyCode> {
-    let code = bytecode::CodeObject::from_bytes(&code_bytes)
-        .map_err(|_| vm.new_value_error("Couldn't deserialize python bytecode".to_owned()))?;
-    Ok(PyCode { code })
+/// Read a value from `file` with the decoder of `loads`, which reads only the bytes it takes up.
+fn marshal_load(file: PyObjectRef, vm: &VirtualMachine) -> PyResult {
+    let mut reader = FileReader {
+        file: &file,
+        vm,
+        error: None,
+    };
+    let value = pyc::read_value(&mut reader, pyc::MAGIC_NUMBER);
+    if let Some(exc) = reader.error {
+        return Err(exc);
+    }
+    from_value(value.map_err(|err| load_error(err, vm))?, vm)
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
     py_module!(vm, "marshal", {
+        "version" => ctx.new_int(VERSION),
         "loads" => ctx.new_rustfunc(marshal_loads),
         "dumps" => ctx.new_rustfunc(marshal_dumps),
+        "load" => ctx.new_rustfunc(marshal_load),
+        "dump" => ctx.new_rustfunc(marshal_dump),
     })
 }
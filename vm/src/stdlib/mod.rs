@@ -1,21 +1,27 @@
 pub mod array;
 #[cfg(feature = "rustpython-parser")]
 pub(crate) mod ast;
+pub(crate) mod atexit;
 mod binascii;
-mod codecs;
+pub(crate) mod codecs;
 mod collections;
 mod csv;
 mod dis;
 mod errno;
 mod functools;
+mod gc;
+#[cfg(feature = "hashlib")]
 mod hashlib;
 mod imp;
 mod itertools;
 mod json;
 #[cfg(feature = "rustpython-parser")]
 mod keyword;
+mod locale;
+mod lsprof;
 mod marshal;
 mod math;
+mod operator;
 mod platform;
 mod pystruct;
 mod random;
@@ -36,6 +42,8 @@ use std::collections::HashMap;
 
 use crate::vm::VirtualMachine;
 
+#[cfg(unix)]
+pub(crate) mod faulthandler;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod io;
 #[cfg(not(target_arch = "wasm32"))]
@@ -48,7 +56,7 @@ mod select;
 pub mod signal;
 #[cfg(not(target_arch = "wasm32"))]
 mod subprocess;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(feature = "zlib", not(target_arch = "wasm32")))]
 mod zlib;
 
 use crate::pyobject::PyObjectRef;
@@ -59,18 +67,22 @@ pub fn get_module_inits() -> HashMap<String, StdlibInitFunc> {
     #[allow(unused_mut)]
     let mut modules = hashmap! {
         "array".to_string() => Box::new(array::make_module) as StdlibInitFunc,
+        "atexit".to_string() => Box::new(atexit::make_module),
         "binascii".to_string() => Box::new(binascii::make_module),
-        "dis".to_string() => Box::new(dis::make_module),
+        "_dis".to_string() => Box::new(dis::make_module),
         "_codecs".to_string() => Box::new(codecs::make_module),
         "_collections".to_string() => Box::new(collections::make_module),
         "_csv".to_string() => Box::new(csv::make_module),
         "_functools".to_string() => Box::new(functools::make_module),
+        "gc".to_string() => Box::new(gc::make_module),
         "errno".to_string() => Box::new(errno::make_module),
-        "hashlib".to_string() => Box::new(hashlib::make_module),
         "itertools".to_string() => Box::new(itertools::make_module),
         "json".to_string() => Box::new(json::make_module),
+        "_locale".to_string() => Box::new(locale::make_module),
+        "_lsprof".to_string() => Box::new(lsprof::make_module),
         "marshal".to_string() => Box::new(marshal::make_module),
         "math".to_string() => Box::new(math::make_module),
+        "_operator".to_string() => Box::new(operator::make_module),
         "platform".to_string() => Box::new(platform::make_module),
         "regex_crate".to_string() => Box::new(re::make_module),
         "random".to_string() => Box::new(random::make_module),
@@ -92,7 +104,7 @@ pub fn get_module_inits() -> HashMap<String, StdlibInitFunc> {
             Box::new(ast::make_module) as StdlibInitFunc,
         );
         modules.insert("keyword".to_string(), Box::new(keyword::make_module));
-        modules.insert("tokenize".to_string(), Box::new(tokenize::make_module));
+        modules.insert("_tokenize".to_string(), Box::new(tokenize::make_module));
     }
 
     // Insert compiler related modules:
@@ -101,6 +113,11 @@ pub fn get_module_inits() -> HashMap<String, StdlibInitFunc> {
         modules.insert("symtable".to_string(), Box::new(symtable::make_module));
     }
 
+    #[cfg(feature = "hashlib")]
+    {
+        modules.insert("hashlib".to_string(), Box::new(hashlib::make_module));
+    }
+
     // disable some modules on WASM
     #[cfg(not(target_arch = "wasm32"))]
     {
@@ -110,9 +127,21 @@ pub fn get_module_inits() -> HashMap<String, StdlibInitFunc> {
         modules.insert("signal".to_string(), Box::new(signal::make_module));
         modules.insert("select".to_string(), Box::new(select::make_module));
         modules.insert("_subprocess".to_string(), Box::new(subprocess::make_module));
+    }
+
+    #[cfg(all(feature = "zlib", not(target_arch = "wasm32")))]
+    {
         modules.insert("zlib".to_string(), Box::new(zlib::make_module));
     }
 
+    #[cfg(unix)]
+    {
+        modules.insert(
+            "faulthandler".to_string(),
+            Box::new(faulthandler::make_module),
+        );
+    }
+
     // Unix-only
     #[cfg(all(unix, not(any(target_os = "android", target_os = "redox"))))]
     {
@@ -0,0 +1,62 @@
+//! The `_operator` module, with the parts of `operator` that can't be written in Python.
+
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objstr::PyString;
+use crate::pyobject::{PyObjectRef, PyResult, TryFromObject, TypeProtocol};
+use crate::vm::VirtualMachine;
+
+/// Compare two byte strings in a time that only depends on the length of `b`, so that the time
+/// doesn't tell where they differ.
+fn timing_safe_compare(a: &[u8], b: &[u8]) -> bool {
+    let mut result = (a.len() != b.len()) as u8;
+    // With different lengths the result is already known, but b still has to be gone over
+    let a = if a.len() == b.len() { a } else { b };
+    for (x, y) in a.iter().zip(b) {
+        result |= x ^ y;
+    }
+    result == 0
+}
+
+fn operator_compare_digest(a: PyObjectRef, b: PyObjectRef, vm: &VirtualMachine) -> PyResult<bool> {
+    let unsupported = || {
+        vm.new_type_error(format!(
+            "unsupported operand types(s) or combination of types: '{}' and '{}'",
+            a.class().name,
+            b.class().name
+        ))
+    };
+    match (a.payload::<PyString>(), b.payload::<PyString>()) {
+        (Some(a), Some(b)) => {
+            let (a, b) = (a.as_str(), b.as_str());
+            if !a.is_ascii() || !b.is_ascii() {
+                return Err(vm.new_type_error(
+                    "comparing strings with non-ASCII characters is not supported".to_string(),
+                ));
+            }
+            Ok(timing_safe_compare(a.as_bytes(), b.as_bytes()))
+        }
+        (None, None) => {
+            let a = ArgBytesLike::try_from_object(vm, a.clone()).map_err(|_| unsupported())?;
+            let b = ArgBytesLike::try_from_object(vm, b.clone()).map_err(|_| unsupported())?;
+            Ok(a.with_ref(|a| b.with_ref(|b| timing_safe_compare(a, b))))
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+const DOC: &str = "Operator interface.
+
+This module exports a set of functions implemented in C corresponding
+to the intrinsic operators of Python.  For example, operator.add(x, y)
+is equivalent to the expression x+y.  The function names are those
+used for special methods; variants without leading and trailing
+'__' are also provided for convenience.";
+
+pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
+    let ctx = &vm.ctx;
+
+    py_module!(vm, "_operator", {
+        "__doc__" => ctx.new_str(DOC.to_string()),
+        "_compare_digest" => ctx.new_rustfunc(operator_compare_digest),
+    })
+}
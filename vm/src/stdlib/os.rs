@@ -1,4 +1,6 @@
 use num_cpus;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use std::cell::{Cell, RefCell};
 use std::ffi;
 use std::fs::File;
@@ -8,6 +10,7 @@ use std::io::{self, ErrorKind, Read, Write};
 use std::os::unix::fs::OpenOptionsExt;
 #[cfg(windows)]
 use std::os::windows::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use std::{env, fs};
 
@@ -22,15 +25,16 @@ use nix::pty::openpty;
 use nix::unistd::{self, Gid, Pid, Uid, Whence};
 
 use crate::function::{IntoPyNativeFunc, OptionalArg, PyFuncArgs};
+use crate::obj::objbyteinner::ArgBytesLike;
 use crate::obj::objbytes::PyBytesRef;
 use crate::obj::objdict::PyDictRef;
 use crate::obj::objint::PyIntRef;
 use crate::obj::objiter;
 use crate::obj::objset::PySet;
 use crate::obj::objstr::PyStringRef;
-use crate::obj::objtype::{self, PyClassRef};
+use crate::obj::objtype::PyClassRef;
 use crate::pyobject::{
-    Either, ItemProtocol, PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TryIntoRef,
+    Either, ItemProtocol, PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject,
     TypeProtocol,
 };
 use crate::vm::VirtualMachine;
@@ -74,11 +78,105 @@ pub fn raw_file_number(handle: File) -> i64 {
     unimplemented!();
 }
 
-fn make_path(_vm: &VirtualMachine, path: PyStringRef, dir_fd: &DirFd) -> PyStringRef {
+/// Whether a function that takes a path returns paths as str or as bytes, which depends on the
+/// type of the path it was given.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OutputMode {
+    String,
+    Bytes,
+}
+
+impl OutputMode {
+    pub fn process_path(self, path: impl Into<PathBuf>, vm: &VirtualMachine) -> PyResult {
+        let path = path.into().into_os_string();
+        match self {
+            OutputMode::String => {
+                let path = path.into_string().map_err(|_| {
+                    vm.new_unicode_decode_error(
+                        "Can't convert OS path to valid UTF-8 string".to_string(),
+                    )
+                })?;
+                Ok(vm.new_str(path))
+            }
+            OutputMode::Bytes => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::ffi::OsStringExt;
+                    Ok(vm.ctx.new_bytes(path.into_vec()))
+                }
+                #[cfg(not(unix))]
+                {
+                    let path = path.into_string().map_err(|_| {
+                        vm.new_unicode_decode_error(
+                            "Can't convert OS path to valid UTF-8 string".to_string(),
+                        )
+                    })?;
+                    Ok(vm.ctx.new_bytes(path.into_bytes()))
+                }
+            }
+        }
+    }
+}
+
+/// A path argument: a str, bytes or an os.PathLike object, as `os.fspath` accepts them.
+pub struct PyPathLike {
+    pub path: PathBuf,
+    pub mode: OutputMode,
+}
+
+impl PyPathLike {
+    pub fn new_str(path: impl Into<PathBuf>) -> Self {
+        PyPathLike {
+            path: path.into(),
+            mode: OutputMode::String,
+        }
+    }
+
+    pub fn as_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TryFromObject for PyPathLike {
+    fn try_from_object(vm: &VirtualMachine, obj: PyObjectRef) -> PyResult<Self> {
+        match fspath(obj, vm)? {
+            Either::A(s) => Ok(PyPathLike::new_str(s.as_str())),
+            Either::B(b) => Ok(PyPathLike {
+                path: bytes_as_osstr(b.get_value(), vm)?.into(),
+                mode: OutputMode::Bytes,
+            }),
+        }
+    }
+}
+
+/// Return the file system representation of a path: str and bytes are returned unchanged, other
+/// objects are asked for theirs with `__fspath__`.
+pub fn fspath(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult<Either<PyStringRef, PyBytesRef>> {
+    let str_or_bytes = |obj: &PyObjectRef| Either::try_from_object(vm, obj.clone()).ok();
+    if let Some(path) = str_or_bytes(&obj) {
+        return Ok(path);
+    }
+    let method = vm.get_method(obj.clone(), "__fspath__").ok_or_else(|| {
+        vm.new_type_error(format!(
+            "expected str, bytes or os.PathLike object, not {}",
+            obj.class().name
+        ))
+    })??;
+    let path = vm.invoke(&method, vec![])?;
+    str_or_bytes(&path).ok_or_else(|| {
+        vm.new_type_error(format!(
+            "expected {}.__fspath__() to return str or bytes, not {}",
+            obj.class().name,
+            path.class().name
+        ))
+    })
+}
+
+fn make_path<'a>(_vm: &VirtualMachine, path: &'a PyPathLike, dir_fd: &DirFd) -> &'a Path {
     if dir_fd.dir_fd.is_some() {
         unimplemented!();
     } else {
-        path
+        path.as_path()
     }
 }
 
@@ -96,7 +194,7 @@ type OpenFlags = u32;
 
 #[cfg(any(unix, windows))]
 pub fn os_open(
-    name: PyStringRef,
+    name: PyPathLike,
     flags: OpenFlags,
     _mode: OptionalArg<PyIntRef>,
     dir_fd: OptionalArg<PyIntRef>,
@@ -105,7 +203,7 @@ pub fn os_open(
     let dir_fd = DirFd {
         dir_fd: dir_fd.into_option(),
     };
-    let fname = make_path(vm, name, &dir_fd);
+    let fname = make_path(vm, &name, &dir_fd);
 
     let mut options = OpenOptions::new();
 
@@ -140,7 +238,7 @@ pub fn os_open(
 
     options.custom_flags(flags);
     let handle = options
-        .open(fname.as_str())
+        .open(fname)
         .map_err(|err| convert_io_error(vm, err))?;
 
     Ok(raw_file_number(handle))
@@ -152,31 +250,34 @@ pub fn os_open(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
 }
 
 pub fn convert_io_error(vm: &VirtualMachine, err: io::Error) -> PyObjectRef {
-    let os_error = match err.kind() {
-        ErrorKind::NotFound => {
-            let exc_type = vm.ctx.exceptions.file_not_found_error.clone();
-            vm.new_exception(exc_type, err.to_string())
-        }
-        ErrorKind::PermissionDenied => {
-            let exc_type = vm.ctx.exceptions.permission_error.clone();
-            vm.new_exception(exc_type, err.to_string())
-        }
-        ErrorKind::AlreadyExists => {
-            let exc_type = vm.ctx.exceptions.file_exists_error.clone();
-            vm.new_exception(exc_type, err.to_string())
-        }
-        _ => vm.new_os_error(err.to_string()),
+    let exc_type = match err.kind() {
+        ErrorKind::NotFound => vm.ctx.exceptions.file_not_found_error.clone(),
+        ErrorKind::PermissionDenied => vm.ctx.exceptions.permission_error.clone(),
+        ErrorKind::AlreadyExists => vm.ctx.exceptions.file_exists_error.clone(),
+        _ => vm.ctx.exceptions.os_error.clone(),
     };
-    if let Some(errno) = err.raw_os_error() {
-        vm.set_attr(&os_error, "errno", vm.ctx.new_int(errno))
-            .unwrap();
+    match err.raw_os_error() {
+        // Pass (errno, strerror) like CPython does, so OSError.__init__ sets them up.
+        Some(errno) => {
+            let args = vec![vm.ctx.new_int(errno), vm.new_str(strerror(&err))];
+            vm.new_exception_obj(exc_type, args).unwrap()
+        }
+        None => vm.new_exception(exc_type, err.to_string()),
+    }
+}
+
+/// The description of an OS error, without the " (os error N)" suffix Rust adds to it.
+fn strerror(err: &io::Error) -> String {
+    let msg = err.to_string();
+    match msg.rfind(" (os error ") {
+        Some(index) => msg[..index].to_string(),
+        None => msg,
     }
-    os_error
 }
 
 #[cfg(unix)]
 pub fn convert_nix_error(vm: &VirtualMachine, err: nix::Error) -> PyObjectRef {
-    let nix_error = match err {
+    match err {
         nix::Error::InvalidPath => {
             let exc_type = vm.ctx.exceptions.file_not_found_error.clone();
             vm.new_exception(exc_type, err.to_string())
@@ -191,16 +292,13 @@ pub fn convert_nix_error(vm: &VirtualMachine, err: nix::Error) -> PyObjectRef {
         }
         nix::Error::Sys(errno) => {
             let exc_type = convert_nix_errno(vm, errno);
-            vm.new_exception(exc_type, err.to_string())
+            let args = vec![
+                vm.ctx.new_int(errno as i32),
+                vm.new_str(errno.desc().to_string()),
+            ];
+            vm.new_exception_obj(exc_type, args).unwrap()
         }
-    };
-
-    if let nix::Error::Sys(errno) = err {
-        vm.set_attr(&nix_error, "errno", vm.ctx.new_int(errno as i32))
-            .unwrap();
     }
-
-    nix_error
 }
 
 #[cfg(unix)]
@@ -292,10 +390,10 @@ fn getgroups() -> nix::Result<Vec<Gid>> {
 }
 
 #[cfg(unix)]
-fn os_access(path: PyStringRef, mode: u8, vm: &VirtualMachine) -> PyResult<bool> {
+fn os_access(path: PyPathLike, mode: u8, vm: &VirtualMachine) -> PyResult<bool> {
     use std::os::unix::fs::MetadataExt;
 
-    let path = path.as_str();
+    let path = path.as_path();
 
     let flags = AccessFlags::from_bits(mode).ok_or_else(|| {
         vm.new_value_error(
@@ -341,51 +439,79 @@ fn os_fsync(fd: i64, vm: &VirtualMachine) -> PyResult<()> {
     Ok(())
 }
 
+/// Run a blocking call with the other threads running, and retry it when a signal interrupts it,
+/// after the signal handlers ran and didn't raise an exception, like CPython does since PEP 475.
+fn retry_on_signal<T>(vm: &VirtualMachine, mut f: impl FnMut() -> io::Result<T>) -> PyResult<T> {
+    loop {
+        match vm.allow_threads(&mut f) {
+            Err(ref err) if err.kind() == ErrorKind::Interrupted => vm.check_signals()?,
+            result => return result.map_err(|err| convert_io_error(vm, err)),
+        }
+    }
+}
+
 fn os_read(fd: i64, n: usize, vm: &VirtualMachine) -> PyResult {
     let mut buffer = vec![0u8; n];
     let mut file = rust_file(fd);
-    file.read_exact(&mut buffer)
-        .map_err(|err| convert_io_error(vm, err))?;
+    let result = retry_on_signal(vm, || file.read(&mut buffer));
 
     // Avoid closing the fd
     raw_file_number(file);
+    buffer.truncate(result?);
     Ok(vm.ctx.new_bytes(buffer))
 }
 
-fn os_write(fd: i64, data: PyBytesRef, vm: &VirtualMachine) -> PyResult {
+fn os_write(fd: i64, data: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
     let mut file = rust_file(fd);
-    let written = file.write(&data).map_err(|err| convert_io_error(vm, err))?;
+    let data = data.with_ref(|bytes| bytes.to_vec());
+    let result = retry_on_signal(vm, || file.write(&data));
 
     // Avoid closing the fd
     raw_file_number(file);
-    Ok(vm.ctx.new_int(written))
+    Ok(vm.ctx.new_int(result?))
 }
 
-fn os_remove(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<()> {
-    let path = make_path(vm, path, &dir_fd);
-    fs::remove_file(path.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_remove(path: PyPathLike, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<()> {
+    let path = make_path(vm, &path, &dir_fd);
+    fs::remove_file(path).map_err(|err| convert_io_error(vm, err))
 }
 
-fn os_mkdir(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<()> {
-    let path = make_path(vm, path, &dir_fd);
-    fs::create_dir(path.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_mkdir(
+    path: PyPathLike,
+    mode: OptionalArg<u32>,
+    dir_fd: DirFd,
+    vm: &VirtualMachine,
+) -> PyResult<()> {
+    let path = make_path(vm, &path, &dir_fd);
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(mode.unwrap_or(0o777));
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    builder
+        .create(path)
+        .map_err(|err| convert_io_error(vm, err))
 }
 
-fn os_mkdirs(path: PyStringRef, vm: &VirtualMachine) -> PyResult<()> {
-    fs::create_dir_all(path.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_mkdirs(path: PyPathLike, vm: &VirtualMachine) -> PyResult<()> {
+    fs::create_dir_all(path.as_path()).map_err(|err| convert_io_error(vm, err))
 }
 
-fn os_rmdir(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<()> {
-    let path = make_path(vm, path, &dir_fd);
-    fs::remove_dir(path.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_rmdir(path: PyPathLike, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<()> {
+    let path = make_path(vm, &path, &dir_fd);
+    fs::remove_dir(path).map_err(|err| convert_io_error(vm, err))
 }
 
-fn os_listdir(path: PyStringRef, vm: &VirtualMachine) -> PyResult {
-    match fs::read_dir(path.as_str()) {
+fn os_listdir(path: OptionalArg<PyPathLike>, vm: &VirtualMachine) -> PyResult {
+    let path = path.unwrap_or_else(|| PyPathLike::new_str("."));
+    match fs::read_dir(path.as_path()) {
         Ok(iter) => {
             let res: PyResult<Vec<PyObjectRef>> = iter
                 .map(|entry| match entry {
-                    Ok(path) => Ok(vm.ctx.new_str(path.file_name().into_string().unwrap())),
+                    Ok(entry) => path.mode.process_path(entry.file_name(), vm),
                     Err(s) => Err(convert_io_error(vm, s)),
                 })
                 .collect();
@@ -463,18 +589,17 @@ fn _os_environ(vm: &VirtualMachine) -> PyDictRef {
     environ
 }
 
-fn os_readlink(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult {
-    let path = make_path(vm, path, &dir_fd);
-    let path = fs::read_link(path.as_str()).map_err(|err| convert_io_error(vm, err))?;
-    let path = path.into_os_string().into_string().map_err(|_osstr| {
-        vm.new_unicode_decode_error("Can't convert OS path to valid UTF-8 string".into())
-    })?;
-    Ok(vm.ctx.new_str(path))
+fn os_readlink(path: PyPathLike, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult {
+    let mode = path.mode;
+    let path = make_path(vm, &path, &dir_fd);
+    let path = fs::read_link(path).map_err(|err| convert_io_error(vm, err))?;
+    mode.process_path(path, vm)
 }
 
 #[derive(Debug)]
 struct DirEntry {
     entry: fs::DirEntry,
+    mode: OutputMode,
 }
 
 type DirEntryRef = PyRef<DirEntry>;
@@ -498,12 +623,12 @@ struct FollowSymlinks {
 }
 
 impl DirEntryRef {
-    fn name(self, _vm: &VirtualMachine) -> String {
-        self.entry.file_name().into_string().unwrap()
+    fn name(self, vm: &VirtualMachine) -> PyResult {
+        self.mode.process_path(self.entry.file_name(), vm)
     }
 
-    fn path(self, _vm: &VirtualMachine) -> String {
-        self.entry.path().to_str().unwrap().to_string()
+    fn path(self, vm: &VirtualMachine) -> PyResult {
+        self.mode.process_path(self.entry.path(), vm)
     }
 
     #[allow(clippy::match_bool)]
@@ -551,7 +676,11 @@ impl DirEntryRef {
         follow_symlinks: FollowSymlinks,
         vm: &VirtualMachine,
     ) -> PyResult<StatResult> {
-        os_stat(self.path(vm).try_into_ref(vm)?, dir_fd, follow_symlinks, vm)
+        let path = PyPathLike {
+            path: self.entry.path(),
+            mode: self.mode,
+        };
+        os_stat(path, dir_fd, follow_symlinks, vm)
     }
 }
 
@@ -560,6 +689,7 @@ impl DirEntryRef {
 struct ScandirIterator {
     entries: RefCell<fs::ReadDir>,
     exhausted: Cell<bool>,
+    mode: OutputMode,
 }
 
 impl PyValue for ScandirIterator {
@@ -578,7 +708,12 @@ impl ScandirIterator {
 
         match self.entries.borrow_mut().next() {
             Some(entry) => match entry {
-                Ok(entry) => Ok(DirEntry { entry }.into_ref(vm).into_object()),
+                Ok(entry) => Ok(DirEntry {
+                    entry,
+                    mode: self.mode,
+                }
+                .into_ref(vm)
+                .into_object()),
                 Err(s) => Err(convert_io_error(vm, s)),
             },
             None => {
@@ -609,16 +744,14 @@ impl ScandirIterator {
     }
 }
 
-fn os_scandir(path: OptionalArg<PyStringRef>, vm: &VirtualMachine) -> PyResult {
-    let path = match path {
-        OptionalArg::Present(ref path) => path.as_str(),
-        OptionalArg::Missing => ".",
-    };
+fn os_scandir(path: OptionalArg<PyPathLike>, vm: &VirtualMachine) -> PyResult {
+    let path = path.unwrap_or_else(|| PyPathLike::new_str("."));
 
-    match fs::read_dir(path) {
+    match fs::read_dir(path.as_path()) {
         Ok(iter) => Ok(ScandirIterator {
             entries: RefCell::new(iter),
             exhausted: Cell::new(false),
+            mode: path.mode,
         }
         .into_ref(vm)
         .into_object()),
@@ -712,7 +845,7 @@ fn to_seconds_from_nanos(secs: i64, nanos: i64) -> f64 {
 macro_rules! os_unix_stat_inner {
     ( $path:expr, $follow_symlinks:expr, $vm:expr ) => {{
         #[allow(clippy::match_bool)]
-        fn get_stats(path: &str, follow_symlinks: bool) -> io::Result<StatResult> {
+        fn get_stats(path: &Path, follow_symlinks: bool) -> io::Result<StatResult> {
             let meta = match follow_symlinks {
                 true => fs::metadata(path)?,
                 false => fs::symlink_metadata(path)?,
@@ -732,56 +865,55 @@ macro_rules! os_unix_stat_inner {
             })
         }
 
-        get_stats($path.as_str(), $follow_symlinks.follow_symlinks)
-            .map_err(|err| convert_io_error($vm, err))
+        get_stats($path, $follow_symlinks.follow_symlinks).map_err(|err| convert_io_error($vm, err))
     }};
 }
 
 #[cfg(target_os = "linux")]
 fn os_stat(
-    path: PyStringRef,
+    path: PyPathLike,
     dir_fd: DirFd,
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<StatResult> {
     use std::os::linux::fs::MetadataExt;
-    let path = make_path(vm, path, &dir_fd);
+    let path = make_path(vm, &path, &dir_fd);
     os_unix_stat_inner!(path, follow_symlinks, vm)
 }
 
 #[cfg(target_os = "macos")]
 fn os_stat(
-    path: PyStringRef,
+    path: PyPathLike,
     dir_fd: DirFd,
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<StatResult> {
     use std::os::macos::fs::MetadataExt;
-    let path = make_path(vm, path, &dir_fd);
+    let path = make_path(vm, &path, &dir_fd);
     os_unix_stat_inner!(path, follow_symlinks, vm)
 }
 
 #[cfg(target_os = "android")]
 fn os_stat(
-    path: PyStringRef,
+    path: PyPathLike,
     dir_fd: DirFd,
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<StatResult> {
     use std::os::android::fs::MetadataExt;
-    let path = make_path(vm, path, &dir_fd);
+    let path = make_path(vm, &path, &dir_fd);
     os_unix_stat_inner!(path, follow_symlinks, vm)
 }
 
 #[cfg(target_os = "redox")]
 fn os_stat(
-    path: PyStringRef,
+    path: PyPathLike,
     dir_fd: DirFd,
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<StatResult> {
     use std::os::redox::fs::MetadataExt;
-    let path = make_path(vm, path, &dir_fd);
+    let path = make_path(vm, &path, &dir_fd);
     os_unix_stat_inner!(path, follow_symlinks, vm)
 }
 
@@ -808,14 +940,14 @@ fn attributes_to_mode(attr: u32) -> u32 {
 
 #[cfg(windows)]
 fn os_stat(
-    path: PyStringRef,
+    path: PyPathLike,
     _dir_fd: DirFd, // TODO: error
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<StatResult> {
     use std::os::windows::fs::MetadataExt;
 
-    fn get_stats(path: &str, follow_symlinks: bool) -> io::Result<StatResult> {
+    fn get_stats(path: &Path, follow_symlinks: bool) -> io::Result<StatResult> {
         let meta = match follow_symlinks {
             true => fs::metadata(path)?,
             false => fs::symlink_metadata(path)?,
@@ -835,7 +967,7 @@ fn os_stat(
         })
     }
 
-    get_stats(path.as_str(), follow_symlinks.follow_symlinks)
+    get_stats(path.as_path(), follow_symlinks.follow_symlinks)
         .map_err(|s| vm.new_os_error(s.to_string()))
 }
 
@@ -847,7 +979,7 @@ fn os_stat(
     windows
 )))]
 fn os_stat(
-    _path: PyStringRef,
+    _path: PyPathLike,
     _dir_fd: DirFd,
     _follow_symlinks: FollowSymlinks,
     _vm: &VirtualMachine,
@@ -855,7 +987,7 @@ fn os_stat(
     unimplemented!();
 }
 
-fn os_lstat(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<StatResult> {
+fn os_lstat(path: PyPathLike, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<StatResult> {
     os_stat(
         path,
         dir_fd,
@@ -868,43 +1000,44 @@ fn os_lstat(path: PyStringRef, dir_fd: DirFd, vm: &VirtualMachine) -> PyResult<S
 
 #[cfg(unix)]
 fn os_symlink(
-    src: PyStringRef,
-    dst: PyStringRef,
+    src: PyPathLike,
+    dst: PyPathLike,
     dir_fd: DirFd,
     vm: &VirtualMachine,
 ) -> PyResult<()> {
     use std::os::unix::fs as unix_fs;
-    let dst = make_path(vm, dst, &dir_fd);
-    unix_fs::symlink(src.as_str(), dst.as_str()).map_err(|err| convert_io_error(vm, err))
+    let dst = make_path(vm, &dst, &dir_fd);
+    unix_fs::symlink(src.as_path(), dst).map_err(|err| convert_io_error(vm, err))
 }
 
 #[cfg(windows)]
 fn os_symlink(
-    src: PyStringRef,
-    dst: PyStringRef,
+    src: PyPathLike,
+    dst: PyPathLike,
     _dir_fd: DirFd,
     vm: &VirtualMachine,
 ) -> PyResult<()> {
     use std::os::windows::fs as win_fs;
-    let ret = match fs::metadata(dst.as_str()) {
+    let (src, dst) = (src.as_path(), dst.as_path());
+    let ret = match fs::metadata(dst) {
         Ok(meta) => {
             if meta.is_file() {
-                win_fs::symlink_file(src.as_str(), dst.as_str())
+                win_fs::symlink_file(src, dst)
             } else if meta.is_dir() {
-                win_fs::symlink_dir(src.as_str(), dst.as_str())
+                win_fs::symlink_dir(src, dst)
             } else {
                 panic!("Uknown file type");
             }
         }
-        Err(_) => win_fs::symlink_file(src.as_str(), dst.as_str()),
+        Err(_) => win_fs::symlink_file(src, dst),
     };
     ret.map_err(|err| convert_io_error(vm, err))
 }
 
 #[cfg(all(not(unix), not(windows)))]
 fn os_symlink(
-    src: PyStringRef,
-    dst: PyStringRef,
+    src: PyPathLike,
+    dst: PyPathLike,
     dir_fd: DirFd,
     vm: &VirtualMachine,
 ) -> PyResult<()> {
@@ -920,8 +1053,8 @@ fn os_getcwd(vm: &VirtualMachine) -> PyResult<String> {
         .to_string())
 }
 
-fn os_chdir(path: PyStringRef, vm: &VirtualMachine) -> PyResult<()> {
-    env::set_current_dir(path.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_chdir(path: PyPathLike, vm: &VirtualMachine) -> PyResult<()> {
+    env::set_current_dir(path.as_path()).map_err(|err| convert_io_error(vm, err))
 }
 
 #[cfg(unix)]
@@ -937,41 +1070,32 @@ fn os_system(command: PyStringRef, _vm: &VirtualMachine) -> PyResult<i32> {
 
 #[cfg(unix)]
 fn os_chmod(
-    path: PyStringRef,
+    path: PyPathLike,
     dir_fd: DirFd,
     mode: u32,
     follow_symlinks: FollowSymlinks,
     vm: &VirtualMachine,
 ) -> PyResult<()> {
     use std::os::unix::fs::PermissionsExt;
-    let path = make_path(vm, path, &dir_fd);
+    let path = make_path(vm, &path, &dir_fd);
     let metadata = if follow_symlinks.follow_symlinks {
-        fs::metadata(path.as_str())
+        fs::metadata(path)
     } else {
-        fs::symlink_metadata(path.as_str())
+        fs::symlink_metadata(path)
     };
     let meta = metadata.map_err(|err| convert_io_error(vm, err))?;
     let mut permissions = meta.permissions();
     permissions.set_mode(mode);
-    fs::set_permissions(path.as_str(), permissions).map_err(|err| convert_io_error(vm, err))?;
+    fs::set_permissions(path, permissions).map_err(|err| convert_io_error(vm, err))?;
     Ok(())
 }
 
 fn os_fspath(path: PyObjectRef, vm: &VirtualMachine) -> PyResult {
-    if objtype::issubclass(&path.class(), &vm.ctx.str_type())
-        || objtype::issubclass(&path.class(), &vm.ctx.bytes_type())
-    {
-        Ok(path)
-    } else {
-        Err(vm.new_type_error(format!(
-            "expected str or bytes object, not {}",
-            path.class()
-        )))
-    }
+    Ok(fspath(path, vm)?.into_object())
 }
 
-fn os_rename(src: PyStringRef, dst: PyStringRef, vm: &VirtualMachine) -> PyResult<()> {
-    fs::rename(src.as_str(), dst.as_str()).map_err(|err| convert_io_error(vm, err))
+fn os_rename(src: PyPathLike, dst: PyPathLike, vm: &VirtualMachine) -> PyResult<()> {
+    fs::rename(src.as_path(), dst.as_path()).map_err(|err| convert_io_error(vm, err))
 }
 
 fn os_getpid(vm: &VirtualMachine) -> PyObjectRef {
@@ -984,6 +1108,17 @@ fn os_cpu_count(vm: &VirtualMachine) -> PyObjectRef {
     vm.new_int(cpu_count)
 }
 
+fn os_urandom(size: isize, vm: &VirtualMachine) -> PyResult {
+    if size < 0 {
+        return Err(vm.new_value_error("negative argument not allowed".to_string()));
+    }
+    let mut buffer = vec![0u8; size as usize];
+    OsRng
+        .try_fill_bytes(&mut buffer)
+        .map_err(|err| vm.new_os_error(err.to_string()))?;
+    Ok(vm.ctx.new_bytes(buffer))
+}
+
 fn os_exit(code: i32, _vm: &VirtualMachine) {
     std::process::exit(code)
 }
@@ -1077,6 +1212,14 @@ pub fn os_openpty(vm: &VirtualMachine) -> PyResult {
     }
 }
 
+#[cfg(unix)]
+pub fn os_pipe(vm: &VirtualMachine) -> PyResult {
+    match unistd::pipe() {
+        Ok((read, write)) => Ok(vm.ctx.new_tuple(vec![vm.new_int(read), vm.new_int(write)])),
+        Err(err) => Err(convert_nix_error(vm, err)),
+    }
+}
+
 #[cfg(unix)]
 pub fn os_ttyname(fd: i32, vm: &VirtualMachine) -> PyResult {
     use libc::ttyname;
@@ -1110,6 +1253,7 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
          "is_file" => ctx.new_rustfunc(DirEntryRef::is_file),
          "is_symlink" => ctx.new_rustfunc(DirEntryRef::is_symlink),
          "stat" => ctx.new_rustfunc(DirEntryRef::stat),
+         "__fspath__" => ctx.new_rustfunc(DirEntryRef::path),
     });
 
     let stat_result = py_class!(ctx, "stat_result", ctx.object(), {
@@ -1212,6 +1356,7 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "fspath" => ctx.new_rustfunc(os_fspath),
          "getpid" => ctx.new_rustfunc(os_getpid),
         "cpu_count" => ctx.new_rustfunc(os_cpu_count),
+        "urandom" => ctx.new_rustfunc(os_urandom),
         "_exit" => ctx.new_rustfunc(os_exit),
 
         "O_RDONLY" => ctx.new_int(libc::O_RDONLY),
@@ -1298,6 +1443,7 @@ fn extend_module_platform_specific(vm: &VirtualMachine, module: PyObjectRef) ->
         "SEEK_SET" => ctx.new_int(Whence::SeekSet as i8),
         "SEEK_CUR" => ctx.new_int(Whence::SeekCur as i8),
         "SEEK_END" => ctx.new_int(Whence::SeekEnd as i8),
+        "pipe" => ctx.new_rustfunc(os_pipe),
     });
 
     #[cfg(not(target_os = "redox"))]
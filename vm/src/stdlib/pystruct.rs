@@ -4,417 +4,818 @@
  * Docs: https://docs.python.org/3/library/struct.html
  *
  * renamed to pystruct since struct is a rust keyword.
- *
- * Use this rust module to do byte packing:
- * https://docs.rs/byteorder/1.2.6/byteorder/
  */
 
-use std::io::{Cursor, Read, Write};
+use std::cell::Cell;
 use std::iter::Peekable;
-
-use byteorder::{ReadBytesExt, WriteBytesExt};
-
-use crate::function::PyFuncArgs;
-use crate::obj::{objbytes, objstr, objtype};
-use crate::pyobject::{PyObjectRef, PyResult, TryFromObject};
+use std::mem;
+use std::os::raw::c_long;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::function::Args;
+use crate::obj::objbool;
+use crate::obj::objbyteinner::{try_as_byte, ArgBytesLike, ArgMutBytesLike};
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objfloat::IntoPyFloat;
+use crate::obj::objint::PyInt;
+use crate::obj::objiter::new_stop_iteration;
+use crate::obj::objstr::PyString;
+use crate::obj::objtype::{self, PyClassRef};
+use crate::pyobject::{
+    PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject, TypeProtocol,
+};
+use crate::types::create_type;
 use crate::VirtualMachine;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FormatSpec {
     endianness: Endianness,
+    /// Whether the items have the sizes and alignment of the platform's C types (`@`), instead
+    /// of the standard sizes without any alignment.
+    native: bool,
     codes: Vec<FormatCode>,
+    size: usize,
+    /// The number of values that are packed, or unpacked.
+    arg_count: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Endianness {
     Native,
     Little,
     Big,
-    Network,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FormatCode {
     code: char,
+    repeat: usize,
+    /// The size of each item. Strings are a single item of `repeat` bytes, in items of a byte.
+    size: usize,
+    /// Where the first item starts, after any padding that aligns it.
+    offset: usize,
 }
 
-fn parse_format_string(fmt: String) -> Result<FormatSpec, String> {
+fn parse_format_string(fmt: &str) -> Result<FormatSpec, String> {
     let mut chars = fmt.chars().peekable();
 
-    // First determine "<", ">","!" or "="
-    let endianness = parse_endiannes(&mut chars);
+    // First determine "@", "=", "<", ">" or "!"
+    let (endianness, native) = parse_endiannes(&mut chars);
+
+    let too_long = || "total struct size too long".to_string();
+    let mut codes = vec![];
+    let mut size: usize = 0;
+    let mut arg_count = 0;
+    loop {
+        while chars.peek().map_or(false, char::is_ascii_whitespace) {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            break;
+        }
+
+        // determine repeat operator:
+        let repeat = match chars.peek() {
+            Some('0'..='9') => {
+                let mut repeat: usize = 0;
+                while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                    chars.next();
+                    repeat = repeat
+                        .checked_mul(10)
+                        .and_then(|repeat| repeat.checked_add(digit as usize))
+                        .ok_or_else(too_long)?;
+                }
+                repeat
+            }
+            _ => 1,
+        };
 
-    // Now, analyze struct string furter:
-    let codes = parse_format_codes(&mut chars)?;
+        // determine format char:
+        let code = chars
+            .next()
+            .ok_or_else(|| "repeat count given without format specifier".to_string())?;
+        let (item_size, align) =
+            code_size(code, native).ok_or_else(|| "bad char in struct format".to_string())?;
+        let offset = match size % align {
+            0 => size,
+            rest => size.checked_add(align - rest).ok_or_else(too_long)?,
+        };
+        size = repeat
+            .checked_mul(item_size)
+            .and_then(|items_size| items_size.checked_add(offset))
+            .filter(|&size| size <= isize::max_value() as usize)
+            .ok_or_else(too_long)?;
+        arg_count += match code {
+            'x' => 0,
+            's' | 'p' => 1,
+            _ => repeat,
+        };
+        codes.push(FormatCode {
+            code,
+            repeat,
+            size: item_size,
+            offset,
+        });
+    }
 
-    Ok(FormatSpec { endianness, codes })
+    Ok(FormatSpec {
+        endianness,
+        native,
+        codes,
+        size,
+        arg_count,
+    })
 }
 
 /// Parse endianness
 /// See also: https://docs.python.org/3/library/struct.html?highlight=struct#byte-order-size-and-alignment
-fn parse_endiannes<I>(chars: &mut Peekable<I>) -> Endianness
+fn parse_endiannes<I>(chars: &mut Peekable<I>) -> (Endianness, bool)
 where
     I: Sized + Iterator<Item = char>,
 {
-    match chars.peek() {
-        Some('@') => {
-            chars.next().unwrap();
-            Endianness::Native
+    let parsed = match chars.peek() {
+        Some('@') => (Endianness::Native, true),
+        Some('=') => (Endianness::Native, false),
+        Some('<') => (Endianness::Little, false),
+        Some('>') | Some('!') => (Endianness::Big, false),
+        _ => return (Endianness::Native, true),
+    };
+    chars.next();
+    parsed
+}
+
+/// The size and the alignment of a format character, if it is one.
+fn code_size(code: char, native: bool) -> Option<(usize, usize)> {
+    let size = match code {
+        'x' | 'c' | 'b' | 'B' | '?' | 's' | 'p' => 1,
+        'h' | 'H' | 'e' => 2,
+        'i' | 'I' | 'f' => 4,
+        'q' | 'Q' | 'd' => 8,
+        'l' | 'L' if native => mem::size_of::<c_long>(),
+        'l' | 'L' => 4,
+        // size_t, ssize_t and pointers only exist natively.
+        'n' | 'N' | 'P' if native => mem::size_of::<usize>(),
+        _ => return None,
+    };
+    let align = if native { size } else { 1 };
+    Some((size, align))
+}
+
+impl FormatSpec {
+    fn is_little_endian(&self) -> bool {
+        match self.endianness {
+            Endianness::Native => cfg!(target_endian = "little"),
+            Endianness::Little => true,
+            Endianness::Big => false,
         }
-        Some('=') => {
-            chars.next().unwrap();
-            Endianness::Native
+    }
+
+    /// Write an unsigned integer into all of `data`, truncating it to its length.
+    fn write_uint(&self, value: u64, data: &mut [u8]) {
+        let bytes = value.to_le_bytes();
+        let bytes = &bytes[..data.len()];
+        if self.is_little_endian() {
+            data.copy_from_slice(bytes);
+        } else {
+            for (byte, value) in data.iter_mut().zip(bytes.iter().rev()) {
+                *byte = *value;
+            }
         }
-        Some('<') => {
-            chars.next().unwrap();
-            Endianness::Little
+    }
+
+    fn read_uint(&self, data: &[u8]) -> u64 {
+        let mut bytes = [0; 8];
+        if self.is_little_endian() {
+            bytes[..data.len()].copy_from_slice(data);
+        } else {
+            for (byte, value) in bytes.iter_mut().zip(data.iter().rev()) {
+                *byte = *value;
+            }
         }
-        Some('>') => {
-            chars.next().unwrap();
-            Endianness::Big
+        u64::from_le_bytes(bytes)
+    }
+
+    /// Pack `args`, which must be as many as the format has items, into `data`, which must be
+    /// as long as the format's size.
+    fn pack(&self, args: &[PyObjectRef], data: &mut [u8], vm: &VirtualMachine) -> PyResult<()> {
+        // Padding is made of zeros.
+        for byte in data.iter_mut() {
+            *byte = 0;
         }
-        Some('!') => {
-            chars.next().unwrap();
-            Endianness::Network
+
+        let mut args = args.iter();
+        for code in &self.codes {
+            let end = code.offset + code.repeat * code.size;
+            let data = &mut data[code.offset..end];
+            match code.code {
+                'x' => {}
+                's' | 'p' => pack_string(code.code, args.next().unwrap(), data, vm)?,
+                _ => {
+                    for item in data.chunks_mut(code.size) {
+                        self.pack_item(code.code, args.next().unwrap(), item, vm)?;
+                    }
+                }
+            }
         }
-        _ => Endianness::Native,
+        Ok(())
     }
-}
 
-fn parse_format_codes<I>(chars: &mut Peekable<I>) -> Result<Vec<FormatCode>, String>
-where
-    I: Sized + Iterator<Item = char>,
-{
-    let mut codes = vec![];
-    while chars.peek().is_some() {
-        // determine repeat operator:
-        let repeat = match chars.peek() {
-            Some('0'..='9') => {
-                let mut repeat = 0;
-                while let Some('0'..='9') = chars.peek() {
-                    if let Some(c) = chars.next() {
-                        let current_digit = c.to_digit(10).unwrap();
-                        repeat = repeat * 10 + current_digit;
-                    }
+    fn pack_item(
+        &self,
+        code: char,
+        arg: &PyObjectRef,
+        data: &mut [u8],
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        match code {
+            'c' => match try_as_byte(arg) {
+                Some(ref bytes) if bytes.len() == 1 => data[0] = bytes[0],
+                _ => {
+                    return Err(new_struct_error(
+                        vm,
+                        "char format requires a bytes object of length 1".to_string(),
+                    ))
                 }
-                Some(repeat)
+            },
+            '?' => data[0] = objbool::boolval(vm, arg.clone())? as u8,
+            'e' => {
+                let value = get_float(vm, arg)?;
+                let bits = pack_half(value).ok_or_else(|| {
+                    vm.new_overflow_error("float too large to pack with e format".to_string())
+                })?;
+                self.write_uint(u64::from(bits), data);
             }
-            _ => None,
-        };
+            'f' => {
+                let value = get_float(vm, arg)?;
+                let single = value as f32;
+                // Natively, C's conversion makes large doubles infinite.
+                if !self.native && single.is_infinite() && value.is_finite() {
+                    return Err(
+                        vm.new_overflow_error("float too large to pack with f format".to_string())
+                    );
+                }
+                self.write_uint(u64::from(single.to_bits()), data);
+            }
+            'd' => {
+                let value = get_float(vm, arg)?;
+                self.write_uint(value.to_bits(), data);
+            }
+            _ => {
+                let value = get_int(vm, arg)?;
+                let bits = int_bits(code, &value, data.len(), vm)?;
+                self.write_uint(bits, data);
+            }
+        }
+        Ok(())
+    }
 
-        // determine format char:
-        let c = chars.next();
-        match c {
-            Some(c) if is_supported_format_character(c) => {
-                if let Some(repeat) = repeat {
-                    for _ in 0..repeat {
-                        codes.push(FormatCode { code: c })
+    /// Unpack `data`, which must be as long as the format's size.
+    fn unpack(&self, data: &[u8], vm: &VirtualMachine) -> PyObjectRef {
+        let mut items = Vec::with_capacity(self.arg_count);
+        for code in &self.codes {
+            let data = &data[code.offset..code.offset + code.repeat * code.size];
+            match code.code {
+                'x' => {}
+                's' => items.push(vm.ctx.new_bytes(data.to_vec())),
+                'p' => {
+                    let string = match data.split_first() {
+                        Some((&len, rest)) => &rest[..rest.len().min(len as usize)],
+                        None => &[],
+                    };
+                    items.push(vm.ctx.new_bytes(string.to_vec()));
+                }
+                _ => {
+                    for item in data.chunks(code.size) {
+                        items.push(self.unpack_item(code.code, item, vm));
                     }
-                } else {
-                    codes.push(FormatCode { code: c })
                 }
             }
-            _ => return Err(format!("Illegal format code {:?}", c)),
         }
+        vm.ctx.new_tuple(items)
     }
 
-    Ok(codes)
+    fn unpack_item(&self, code: char, data: &[u8], vm: &VirtualMachine) -> PyObjectRef {
+        match code {
+            'c' => vm.ctx.new_bytes(data.to_vec()),
+            '?' => vm.ctx.new_bool(data[0] != 0),
+            'e' => vm.ctx.new_float(unpack_half(self.read_uint(data) as u16)),
+            'f' => vm
+                .ctx
+                .new_float(f64::from(f32::from_bits(self.read_uint(data) as u32))),
+            'd' => vm.ctx.new_float(f64::from_bits(self.read_uint(data))),
+            'P' => vm.ctx.new_int(self.read_uint(data)),
+            c if c.is_ascii_lowercase() => {
+                // Sign extend the value from its size.
+                let shift = 64 - 8 * data.len();
+                vm.ctx
+                    .new_int(((self.read_uint(data) << shift) as i64) >> shift)
+            }
+            _ => vm.ctx.new_int(self.read_uint(data)),
+        }
+    }
 }
 
-fn is_supported_format_character(c: char) -> bool {
-    match c {
-        'b' | 'B' | 'h' | 'H' | 'i' | 'I' | 'l' | 'L' | 'q' | 'Q' | 'f' | 'd' => true,
-        _ => false,
-    }
+fn new_struct_error(vm: &VirtualMachine, msg: String) -> PyObjectRef {
+    vm.new_exception(vm.class("struct", "error"), msg)
 }
 
-macro_rules! make_pack_no_endianess {
-    ($T:ty) => {
-        paste::item! {
-            fn [<pack_ $T>](vm: &VirtualMachine, arg: &PyObjectRef, data: &mut dyn Write) -> PyResult<()> {
-                let v = $T::try_from_object(vm, arg.clone())?;
-                data.[<write_$T>](v).unwrap();
-                Ok(())
+fn get_int(vm: &VirtualMachine, arg: &PyObjectRef) -> PyResult<BigInt> {
+    if let Some(int) = arg.payload::<PyInt>() {
+        return Ok(int.as_bigint().clone());
+    }
+    match vm.get_method(arg.clone(), "__index__") {
+        Some(method) => {
+            let result = vm.invoke(&method?, vec![])?;
+            match result.payload::<PyInt>() {
+                Some(int) => Ok(int.as_bigint().clone()),
+                None => Err(vm.new_type_error(format!(
+                    "__index__ returned non-int (type {})",
+                    result.class().name
+                ))),
             }
         }
-    };
+        None => Err(new_struct_error(
+            vm,
+            "required argument is not an integer".to_string(),
+        )),
+    }
 }
 
-macro_rules! make_pack_with_endianess {
-    ($T:ty) => {
-        paste::item! {
-            fn [<pack_ $T>]<Endianness>(vm: &VirtualMachine, arg: &PyObjectRef, data: &mut dyn Write) -> PyResult<()>
-            where
-                Endianness: byteorder::ByteOrder,
-            {
-                let v = $T::try_from_object(vm, arg.clone())?;
-                data.[<write_$T>]::<Endianness>(v).unwrap();
-                Ok(())
+fn get_float(vm: &VirtualMachine, arg: &PyObjectRef) -> PyResult<f64> {
+    IntoPyFloat::try_from_object(vm, arg.clone())
+        .map(IntoPyFloat::to_f64)
+        .map_err(|err| {
+            if objtype::isinstance(&err, &vm.ctx.exceptions.type_error) {
+                new_struct_error(vm, "required argument is not a float".to_string())
+            } else {
+                err
             }
-        }
-    };
+        })
 }
 
-make_pack_no_endianess!(i8);
-make_pack_no_endianess!(u8);
-make_pack_with_endianess!(i16);
-make_pack_with_endianess!(u16);
-make_pack_with_endianess!(i32);
-make_pack_with_endianess!(u32);
-make_pack_with_endianess!(i64);
-make_pack_with_endianess!(u64);
-make_pack_with_endianess!(f32);
-make_pack_with_endianess!(f64);
-
-fn pack_bool(vm: &VirtualMachine, arg: &PyObjectRef, data: &mut dyn Write) -> PyResult<()> {
-    let v = if bool::try_from_object(vm, arg.clone())? {
-        1
+/// The bits of `value` for an integer format code of `size` bytes, which may be truncated to
+/// that size.
+fn int_bits(code: char, value: &BigInt, size: usize, vm: &VirtualMachine) -> PyResult<u64> {
+    let signed = code.is_ascii_lowercase();
+    let unused_bits = 64 - 8 * size;
+    let (min, max) = if signed {
+        (
+            i64::min_value() >> unused_bits,
+            i64::max_value() >> unused_bits,
+        )
     } else {
-        0
+        (0, (u64::max_value() >> unused_bits) as i64)
     };
-    data.write_u8(v).unwrap();
-    Ok(())
-}
+    let out_of_range = || new_struct_error(vm, "argument out of range".to_string());
+
+    let name = match code {
+        'b' => Some("byte"),
+        'B' => Some("ubyte"),
+        'h' => Some("short"),
+        'H' => Some("ushort"),
+        _ => None,
+    };
+    if let Some(name) = name {
+        return match value.to_i64() {
+            Some(value) if min <= value && value <= max => Ok(value as u64),
+            _ => Err(new_struct_error(
+                vm,
+                format!("{} format requires {} <= number <= {}", name, min, max),
+            )),
+        };
+    }
 
-fn pack_item<Endianness>(
-    vm: &VirtualMachine,
-    code: &FormatCode,
-    arg: &PyObjectRef,
-    data: &mut dyn Write,
-) -> PyResult<()>
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match code.code {
-        'b' => pack_i8(vm, arg, data)?,
-        'B' => pack_u8(vm, arg, data)?,
-        '?' => pack_bool(vm, arg, data)?,
-        'h' => pack_i16::<Endianness>(vm, arg, data)?,
-        'H' => pack_u16::<Endianness>(vm, arg, data)?,
-        'i' | 'l' => pack_i32::<Endianness>(vm, arg, data)?,
-        'I' | 'L' => pack_u32::<Endianness>(vm, arg, data)?,
-        'q' => pack_i64::<Endianness>(vm, arg, data)?,
-        'Q' => pack_u64::<Endianness>(vm, arg, data)?,
-        'f' => pack_f32::<Endianness>(vm, arg, data)?,
-        'd' => pack_f64::<Endianness>(vm, arg, data)?,
-        c => {
-            panic!("Unsupported format code {:?}", c);
+    if signed {
+        let value = value.to_i64().ok_or_else(out_of_range)?;
+        if value < min || value > max {
+            return Err(new_struct_error(
+                vm,
+                format!("'{}' format requires {} <= number <= {}", code, min, max),
+            ));
         }
+        Ok(value as u64)
+    } else if code == 'P' {
+        // Like C pointers, negative values wrap around.
+        value
+            .to_u64()
+            .or_else(|| value.to_i64().map(|value| value as u64))
+            .ok_or_else(out_of_range)
+    } else {
+        let max = u64::max_value() >> unused_bits;
+        let value = value.to_u64().ok_or_else(out_of_range)?;
+        if value > max {
+            return Err(new_struct_error(
+                vm,
+                format!("'{}' format requires 0 <= number <= {}", code, max),
+            ));
+        }
+        Ok(value)
     }
+}
+
+/// Pack a string into all of `data`, as a Pascal string for `p`, which starts with its length.
+fn pack_string(
+    code: char,
+    arg: &PyObjectRef,
+    data: &mut [u8],
+    vm: &VirtualMachine,
+) -> PyResult<()> {
+    let string = try_as_byte(arg).ok_or_else(|| {
+        new_struct_error(
+            vm,
+            format!("argument for '{}' must be a bytes object", code),
+        )
+    })?;
+    let data = match code {
+        'p' => match data.split_first_mut() {
+            Some((len, rest)) => {
+                *len = string.len().min(rest.len()).min(255) as u8;
+                &mut rest[..*len as usize]
+            }
+            None => return Ok(()),
+        },
+        _ => data,
+    };
+    let len = string.len().min(data.len());
+    data[..len].copy_from_slice(&string[..len]);
     Ok(())
 }
 
-fn struct_pack(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
-    if args.args.is_empty() {
-        Err(vm.new_type_error(format!(
-            "Expected at least 1 argument (got: {})",
-            args.args.len()
-        )))
+/// Split a positive, finite `x` into a fraction in [0.5, 1) and a power of two.
+fn frexp(x: f64) -> (f64, i32) {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    if exponent == 0 {
+        // A subnormal number, which is normal once it is scaled up.
+        let (fraction, exponent) = frexp(x * 2f64.powi(64));
+        return (fraction, exponent - 64);
+    }
+    let fraction = f64::from_bits(bits & !(0x7ff << 52) | (1022 << 52));
+    (fraction, exponent - 1022)
+}
+
+/// The bits of the IEEE 754 half precision float closest to `x`, or `None` if it is too large.
+/// Ported from `_PyFloat_Pack2` in CPython.
+fn pack_half(x: f64) -> Option<u16> {
+    let sign = x.is_sign_negative() as u16;
+    let (exponent, bits) = if x == 0.0 {
+        (0, 0)
+    } else if x.is_infinite() {
+        (0x1f, 0)
+    } else if x.is_nan() {
+        (0x1f, 512)
     } else {
-        let fmt_arg = args.args[0].clone();
-        if objtype::isinstance(&fmt_arg, &vm.ctx.str_type()) {
-            let fmt_str = objstr::get_value(&fmt_arg);
-
-            let format_spec = parse_format_string(fmt_str).map_err(|e| vm.new_value_error(e))?;
-
-            if format_spec.codes.len() + 1 == args.args.len() {
-                // Create data vector:
-                let mut data = Vec::<u8>::new();
-                // Loop over all opcodes:
-                for (code, arg) in format_spec.codes.iter().zip(args.args.iter().skip(1)) {
-                    debug!("code: {:?}", code);
-                    match format_spec.endianness {
-                        Endianness::Little => {
-                            pack_item::<byteorder::LittleEndian>(vm, code, arg, &mut data)?
-                        }
-                        Endianness::Big => {
-                            pack_item::<byteorder::BigEndian>(vm, code, arg, &mut data)?
-                        }
-                        Endianness::Network => {
-                            pack_item::<byteorder::NetworkEndian>(vm, code, arg, &mut data)?
-                        }
-                        Endianness::Native => {
-                            pack_item::<byteorder::NativeEndian>(vm, code, arg, &mut data)?
-                        }
-                    }
-                }
+        let (mut fraction, mut exponent) = frexp(x.abs());
+        // Normalize the fraction to [1, 2).
+        fraction *= 2.0;
+        exponent -= 1;
+
+        if exponent >= 16 {
+            return None;
+        } else if exponent < -25 {
+            // Underflow to zero.
+            fraction = 0.0;
+            exponent = 0;
+        } else if exponent < -14 {
+            // A subnormal number.
+            fraction *= 2f64.powi(14 + exponent);
+            exponent = 0;
+        } else {
+            exponent += 15;
+            // Drop the implicit leading one.
+            fraction -= 1.0;
+        }
 
-                Ok(vm.ctx.new_bytes(data))
-            } else {
-                Err(vm.new_type_error(format!(
-                    "Expected {} arguments (got: {})",
-                    format_spec.codes.len() + 1,
-                    args.args.len()
-                )))
+        fraction *= 1024.0;
+        let mut bits = fraction as u16;
+        fraction -= f64::from(bits);
+        // Round half to even.
+        if fraction > 0.5 || (fraction == 0.5 && bits % 2 == 1) {
+            bits += 1;
+            if bits == 1024 {
+                bits = 0;
+                exponent += 1;
+                if exponent == 31 {
+                    return None;
+                }
             }
-        } else {
-            Err(vm.new_type_error("First argument must be of str type".to_string()))
         }
-    }
+        (exponent as u16, bits)
+    };
+    Some(sign << 15 | exponent << 10 | bits)
 }
 
-fn unpack_i8(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult {
-    match rdr.read_i8() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+/// The value of the IEEE 754 half precision float with `bits`.
+fn unpack_half(bits: u16) -> f64 {
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let fraction = f64::from(bits & 0x3ff) / 1024.0;
+    let value = match exponent {
+        0x1f if fraction == 0.0 => std::f64::INFINITY,
+        0x1f => std::f64::NAN,
+        0 => fraction * 2f64.powi(-14),
+        _ => (1.0 + fraction) * 2f64.powi(exponent - 15),
+    };
+    if bits >> 15 == 1 {
+        -value
+    } else {
+        value
     }
 }
 
-fn unpack_u8(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult {
-    match rdr.read_u8() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+/// Where `size` bytes at `offset` start in a buffer of `buffer_len` bytes, for `pack_into` and
+/// `unpack_from`. Negative offsets count from the end.
+fn buffer_offset(
+    offset: isize,
+    size: usize,
+    buffer_len: usize,
+    pack: bool,
+    vm: &VirtualMachine,
+) -> PyResult<usize> {
+    let (size, buffer_len) = (size as isize, buffer_len as isize);
+    let mut start = offset;
+    if offset < 0 {
+        if offset + size > 0 {
+            let msg = if pack {
+                format!("no space to pack {} bytes at offset {}", size, offset)
+            } else {
+                format!(
+                    "not enough data to unpack {} bytes at offset {}",
+                    size, offset
+                )
+            };
+            return Err(new_struct_error(vm, msg));
+        }
+        if offset + buffer_len < 0 {
+            return Err(new_struct_error(
+                vm,
+                format!(
+                    "offset {} out of range for {}-byte buffer",
+                    offset, buffer_len
+                ),
+            ));
+        }
+        start += buffer_len;
+    }
+    if buffer_len - start < size {
+        let msg = if pack {
+            format!(
+                "pack_into requires a buffer of at least {} bytes for packing {} bytes at offset {} (actual buffer size is {})",
+                size + start, size, start, buffer_len
+            )
+        } else {
+            format!(
+                "unpack_from requires a buffer of at least {} bytes for unpacking {} bytes at offset {} (actual buffer size is {})",
+                size + start, size, start, buffer_len
+            )
+        };
+        return Err(new_struct_error(vm, msg));
     }
+    Ok(start as usize)
 }
 
-fn unpack_bool(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult {
-    match rdr.read_u8() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_bool(v > 0)),
-    }
+#[pyclass(name = "Struct")]
+#[derive(Debug)]
+struct PyStruct {
+    format: String,
+    spec: FormatSpec,
 }
 
-fn unpack_i16<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_i16::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+type PyStructRef = PyRef<PyStruct>;
+
+impl PyValue for PyStruct {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("struct", "Struct")
     }
 }
 
-fn unpack_u16<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_u16::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
-    }
+#[derive(FromArgs)]
+struct UnpackFromArgs {
+    #[pyarg(positional_or_keyword)]
+    buffer: ArgBytesLike,
+    #[pyarg(positional_or_keyword, default = "0")]
+    offset: isize,
 }
 
-fn unpack_i32<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_i32::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+#[pyimpl]
+impl PyStruct {
+    fn new(fmt: &PyObjectRef, vm: &VirtualMachine) -> PyResult<Self> {
+        let format = if let Some(string) = fmt.payload::<PyString>() {
+            string.as_str().to_owned()
+        } else if let Some(bytes) = fmt.payload::<PyBytes>() {
+            String::from_utf8_lossy(bytes.get_value()).into_owned()
+        } else {
+            return Err(vm.new_type_error(format!(
+                "Struct() argument 1 must be a str or bytes object, not {}",
+                fmt.class().name
+            )));
+        };
+        let spec = parse_format_string(&format).map_err(|e| new_struct_error(vm, e))?;
+        Ok(PyStruct { format, spec })
     }
-}
 
-fn unpack_u32<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_u32::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+    #[pyslot(new)]
+    fn tp_new(cls: PyClassRef, fmt: PyObjectRef, vm: &VirtualMachine) -> PyResult<PyStructRef> {
+        PyStruct::new(&fmt, vm)?.into_ref_with_type(vm, cls)
     }
-}
 
-fn unpack_i64<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_i64::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+    #[pyproperty(name = "format")]
+    fn format(&self, _vm: &VirtualMachine) -> String {
+        self.format.clone()
     }
-}
 
-fn unpack_u64<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_u64::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_int(v)),
+    #[pyproperty(name = "size")]
+    fn size(&self, _vm: &VirtualMachine) -> usize {
+        self.spec.size
     }
-}
 
-fn unpack_f32<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_f32::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_float(f64::from(v))),
+    fn check_arg_count(&self, name: &str, count: usize, vm: &VirtualMachine) -> PyResult<()> {
+        if count == self.spec.arg_count {
+            Ok(())
+        } else {
+            Err(new_struct_error(
+                vm,
+                format!(
+                    "{} expected {} items for packing (got {})",
+                    name, self.spec.arg_count, count
+                ),
+            ))
+        }
     }
-}
 
-fn unpack_f64<Endianness>(vm: &VirtualMachine, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match rdr.read_f64::<Endianness>() {
-        Err(err) => panic!("Error in reading {:?}", err),
-        Ok(v) => Ok(vm.ctx.new_float(v)),
+    #[pymethod(name = "pack")]
+    fn pack(&self, args: Args, vm: &VirtualMachine) -> PyResult<PyBytes> {
+        let args = args.into_vec();
+        self.check_arg_count("pack", args.len(), vm)?;
+        let mut data = vec![0; self.spec.size];
+        self.spec.pack(&args, &mut data, vm)?;
+        Ok(PyBytes::new(data))
     }
-}
 
-fn struct_unpack(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
-    arg_check!(
-        vm,
-        args,
-        required = [
-            (fmt, Some(vm.ctx.str_type())),
-            (buffer, Some(vm.ctx.bytes_type()))
-        ]
-    );
+    #[pymethod(name = "pack_into")]
+    fn pack_into(
+        &self,
+        buffer: ArgMutBytesLike,
+        offset: isize,
+        args: Args,
+        vm: &VirtualMachine,
+    ) -> PyResult<()> {
+        let args = args.into_vec();
+        self.check_arg_count("pack_into", args.len(), vm)?;
+        let size = self.spec.size;
+        let start = buffer_offset(offset, size, buffer.len(), true, vm)?;
+        // Packing can run Python code, which mustn't find the buffer borrowed.
+        let mut packed = vec![0; size];
+        self.spec.pack(&args, &mut packed, vm)?;
+        buffer.with_mut(|data| data[start..start + size].copy_from_slice(&packed));
+        Ok(())
+    }
 
-    let fmt_str = objstr::get_value(&fmt);
+    #[pymethod(name = "unpack")]
+    fn unpack(&self, buffer: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
+        buffer.with_ref(|data| {
+            if data.len() == self.spec.size {
+                Ok(self.spec.unpack(data, vm))
+            } else {
+                Err(new_struct_error(
+                    vm,
+                    format!("unpack requires a buffer of {} bytes", self.spec.size),
+                ))
+            }
+        })
+    }
 
-    let format_spec = parse_format_string(fmt_str).map_err(|e| vm.new_value_error(e))?;
-    let data = objbytes::get_value(buffer).to_vec();
-    let mut rdr = Cursor::new(data);
+    #[pymethod(name = "unpack_from")]
+    fn unpack_from(&self, args: UnpackFromArgs, vm: &VirtualMachine) -> PyResult {
+        let size = self.spec.size;
+        args.buffer.with_ref(|data| {
+            let start = buffer_offset(args.offset, size, data.len(), false, vm)?;
+            Ok(self.spec.unpack(&data[start..start + size], vm))
+        })
+    }
 
-    let mut items = vec![];
-    for code in format_spec.codes {
-        debug!("unpack code: {:?}", code);
-        let item = match format_spec.endianness {
-            Endianness::Little => unpack_code::<byteorder::LittleEndian>(vm, &code, &mut rdr)?,
-            Endianness::Big => unpack_code::<byteorder::BigEndian>(vm, &code, &mut rdr)?,
-            Endianness::Network => unpack_code::<byteorder::NetworkEndian>(vm, &code, &mut rdr)?,
-            Endianness::Native => unpack_code::<byteorder::NativeEndian>(vm, &code, &mut rdr)?,
-        };
-        items.push(item);
+    #[pymethod(name = "iter_unpack")]
+    fn iter_unpack(
+        zelf: PyRef<Self>,
+        buffer: ArgBytesLike,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyUnpackIterator> {
+        let size = zelf.spec.size;
+        if size == 0 {
+            return Err(new_struct_error(
+                vm,
+                "cannot iteratively unpack with a struct of length 0".to_string(),
+            ));
+        }
+        if buffer.len() % size != 0 {
+            return Err(new_struct_error(
+                vm,
+                format!(
+                    "iterative unpacking requires a buffer of a multiple of {} bytes",
+                    size
+                ),
+            ));
+        }
+        Ok(PyUnpackIterator {
+            format: zelf,
+            data: buffer.to_vec(),
+            offset: Cell::new(0),
+        })
     }
+}
 
-    Ok(vm.ctx.new_tuple(items))
+#[pyclass(name = "unpack_iterator")]
+#[derive(Debug)]
+struct PyUnpackIterator {
+    format: PyStructRef,
+    data: Vec<u8>,
+    offset: Cell<usize>,
 }
 
-fn unpack_code<Endianness>(vm: &VirtualMachine, code: &FormatCode, rdr: &mut dyn Read) -> PyResult
-where
-    Endianness: byteorder::ByteOrder,
-{
-    match code.code {
-        'b' => unpack_i8(vm, rdr),
-        'B' => unpack_u8(vm, rdr),
-        '?' => unpack_bool(vm, rdr),
-        'h' => unpack_i16::<Endianness>(vm, rdr),
-        'H' => unpack_u16::<Endianness>(vm, rdr),
-        'i' | 'l' => unpack_i32::<Endianness>(vm, rdr),
-        'I' | 'L' => unpack_u32::<Endianness>(vm, rdr),
-        'q' => unpack_i64::<Endianness>(vm, rdr),
-        'Q' => unpack_u64::<Endianness>(vm, rdr),
-        'f' => unpack_f32::<Endianness>(vm, rdr),
-        'd' => unpack_f64::<Endianness>(vm, rdr),
-        c => {
-            panic!("Unsupported format code {:?}", c);
+impl PyValue for PyUnpackIterator {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("struct", "unpack_iterator")
+    }
+}
+
+#[pyimpl]
+impl PyUnpackIterator {
+    #[pymethod(name = "__next__")]
+    fn next(&self, vm: &VirtualMachine) -> PyResult {
+        let start = self.offset.get();
+        let end = start + self.format.spec.size;
+        if end > self.data.len() {
+            return Err(new_stop_iteration(vm));
         }
+        self.offset.set(end);
+        Ok(self.format.spec.unpack(&self.data[start..end], vm))
+    }
+
+    #[pymethod(name = "__iter__")]
+    fn iter(zelf: PyRef<Self>, _vm: &VirtualMachine) -> PyRef<Self> {
+        zelf
+    }
+
+    #[pymethod(name = "__length_hint__")]
+    fn length_hint(&self, _vm: &VirtualMachine) -> usize {
+        (self.data.len() - self.offset.get()) / self.format.spec.size
     }
 }
 
+fn struct_pack(fmt: PyObjectRef, args: Args, vm: &VirtualMachine) -> PyResult<PyBytes> {
+    PyStruct::new(&fmt, vm)?.pack(args, vm)
+}
+
+fn struct_pack_into(
+    fmt: PyObjectRef,
+    buffer: ArgMutBytesLike,
+    offset: isize,
+    args: Args,
+    vm: &VirtualMachine,
+) -> PyResult<()> {
+    PyStruct::new(&fmt, vm)?.pack_into(buffer, offset, args, vm)
+}
+
+fn struct_unpack(fmt: PyObjectRef, buffer: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
+    PyStruct::new(&fmt, vm)?.unpack(buffer, vm)
+}
+
+fn struct_unpack_from(fmt: PyObjectRef, args: UnpackFromArgs, vm: &VirtualMachine) -> PyResult {
+    PyStruct::new(&fmt, vm)?.unpack_from(args, vm)
+}
+
+fn struct_iter_unpack(
+    fmt: PyObjectRef,
+    buffer: ArgBytesLike,
+    vm: &VirtualMachine,
+) -> PyResult<PyUnpackIterator> {
+    let format = PyStruct::new(&fmt, vm)?.into_ref(vm);
+    PyStruct::iter_unpack(format, buffer, vm)
+}
+
+fn struct_calcsize(fmt: PyObjectRef, vm: &VirtualMachine) -> PyResult<usize> {
+    Ok(PyStruct::new(&fmt, vm)?.spec.size)
+}
+
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
-    let struct_error = ctx.new_class("struct.error", ctx.object());
+    let struct_error = create_type(
+        "error",
+        &ctx.types.type_type,
+        &ctx.exceptions.exception_type,
+    );
 
     py_module!(vm, "struct", {
         "pack" => ctx.new_rustfunc(struct_pack),
+        "pack_into" => ctx.new_rustfunc(struct_pack_into),
         "unpack" => ctx.new_rustfunc(struct_unpack),
+        "unpack_from" => ctx.new_rustfunc(struct_unpack_from),
+        "iter_unpack" => ctx.new_rustfunc(struct_iter_unpack),
+        "calcsize" => ctx.new_rustfunc(struct_calcsize),
+        "Struct" => PyStruct::make_class(ctx),
+        "unpack_iterator" => PyUnpackIterator::make_class(ctx),
         "error" => struct_error,
     })
 }
@@ -1,6 +1,8 @@
 //! Random module.
 
+use num_bigint::{BigInt, Sign};
 use rand::distributions::Distribution;
+use rand::RngCore;
 use rand_distr::Normal;
 
 use crate::pyobject::{PyObjectRef, PyResult};
@@ -13,6 +15,7 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "gauss" => ctx.new_rustfunc(random_normalvariate), // TODO: is this the same?
         "normalvariate" => ctx.new_rustfunc(random_normalvariate),
         "random" => ctx.new_rustfunc(random_random),
+        "getrandbits" => ctx.new_rustfunc(random_getrandbits),
         // "weibull", ctx.new_rustfunc(random_weibullvariate),
     })
 }
@@ -32,6 +35,22 @@ fn random_random(_vm: &VirtualMachine) -> f64 {
     rand::random()
 }
 
+fn random_getrandbits(k: isize, vm: &VirtualMachine) -> PyResult<BigInt> {
+    if k < 0 {
+        return Err(vm.new_value_error("number of bits must be non-negative".to_string()));
+    }
+    let k = k as usize;
+    let mut bytes = vec![0u8; (k + 7) / 8];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    // Drop the bits above k from the most significant byte
+    if k % 8 != 0 {
+        if let Some(last) = bytes.last_mut() {
+            *last >>= 8 - k % 8;
+        }
+    }
+    Ok(BigInt::from_bytes_le(Sign::Plus, &bytes))
+}
+
 /*
  * TODO: enable this function:
 fn random_weibullvariate(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
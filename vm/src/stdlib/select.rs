@@ -147,9 +147,10 @@ fn select_select(
             Some(ref mut tv) => tv as *mut _,
             None => std::ptr::null_mut(),
         };
-        let res = unsafe { select(nfds, &mut r.0, &mut w.0, &mut x.0, timeout_ptr) };
-
-        let err = io::Error::last_os_error();
+        let (res, err) = vm.allow_threads(|| {
+            let res = unsafe { select(nfds, &mut r.0, &mut w.0, &mut x.0, timeout_ptr) };
+            (res, io::Error::last_os_error())
+        });
 
         if res >= 0 || err.kind() != io::ErrorKind::Interrupted {
             break (res, err);
@@ -25,8 +25,15 @@ static TRIGGERS: [AtomicBool; NSIG] = arr![AtomicBool::new(false); 64];
 static ANY_TRIGGERED: AtomicBool = AtomicBool::new(false);
 
 extern "C" fn run_signal(signum: i32) {
-    ANY_TRIGGERED.store(true, Ordering::Relaxed);
     TRIGGERS[signum as usize].store(true, Ordering::Relaxed);
+    // Only after the trigger, so that `check_signals` finds it once it sees this
+    ANY_TRIGGERED.store(true, Ordering::Release);
+}
+
+/// Mark SIGINT as arrived, like CPython's `PyErr_SetInterrupt`, so that the main thread runs its
+/// handler the next time it checks for signals.
+pub fn set_interrupt() {
+    run_signal(libc::SIGINT);
 }
 
 fn assert_in_range(signum: i32, vm: &VirtualMachine) -> PyResult<()> {
@@ -39,6 +46,9 @@ fn assert_in_range(signum: i32, vm: &VirtualMachine) -> PyResult<()> {
 
 fn signal(signalnum: i32, handler: PyObjectRef, vm: &VirtualMachine) -> PyResult {
     assert_in_range(signalnum, vm)?;
+    if !vm.is_main_thread() {
+        return Err(vm.new_value_error("signal only works in main thread".to_owned()));
+    }
 
     let sig_handler = match usize::try_from_object(vm, handler.clone()).ok() {
         Some(SIG_DFL) => SIG_DFL,
@@ -90,17 +100,35 @@ fn alarm(time: u32, _vm: &VirtualMachine) -> u32 {
     prev_time.unwrap_or(0)
 }
 
+/// Send a signal to the process, and run its handler right away.
+fn raise_signal(signalnum: i32, vm: &VirtualMachine) -> PyResult<()> {
+    if unsafe { libc::raise(signalnum) } != 0 {
+        return Err(vm.new_os_error(std::io::Error::last_os_error().to_string()));
+    }
+    vm.check_signals()
+}
+
+/// Call the handlers of the signals that arrived, with the signal number and the frame that was
+/// running. If a handler raises an exception, the signals that are left are handled the next time.
 #[cfg_attr(feature = "flame-it", flame)]
 pub fn check_signals(vm: &VirtualMachine) -> PyResult<()> {
-    if !ANY_TRIGGERED.swap(false, Ordering::Relaxed) {
+    if !ANY_TRIGGERED.swap(false, Ordering::Acquire) {
         return Ok(());
     }
     for (signum, trigger) in TRIGGERS.iter().enumerate().skip(1) {
         let triggerd = trigger.swap(false, Ordering::Relaxed);
         if triggerd {
-            let handler = &vm.signal_handlers.borrow()[signum];
-            if vm.is_callable(handler) {
-                vm.invoke(handler, vec![vm.new_int(signum), vm.get_none()])?;
+            // Not borrowed while it runs, as it may set a handler itself
+            let handler = vm.signal_handlers.borrow()[signum].clone();
+            if vm.is_callable(&handler) {
+                let frame = match vm.current_frame() {
+                    Some(frame) => frame.clone().into_object(),
+                    None => vm.get_none(),
+                };
+                if let Err(exc) = vm.invoke(&handler, vec![vm.new_int(signum), frame]) {
+                    ANY_TRIGGERED.store(true, Ordering::Relaxed);
+                    return Err(exc);
+                }
             }
         }
     }
@@ -122,6 +150,7 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let module = py_module!(vm, "signal", {
         "signal" => ctx.new_rustfunc(signal),
         "getsignal" => ctx.new_rustfunc(getsignal),
+        "raise_signal" => ctx.new_rustfunc(raise_signal),
         "SIG_DFL" => sig_dfl.clone(),
         "SIG_IGN" => sig_ign.clone(),
         "SIGABRT" => ctx.new_int(libc::SIGABRT as u8),
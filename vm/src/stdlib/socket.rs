@@ -1,5 +1,6 @@
 use std::cell::{Cell, Ref, RefCell};
 use std::io::{self, prelude::*};
+use std::mem::ManuallyDrop;
 use std::net::{Ipv4Addr, Shutdown, SocketAddr, ToSocketAddrs};
 use std::time::Duration;
 
@@ -13,8 +14,7 @@ use super::os::convert_io_error;
 #[cfg(unix)]
 use super::os::convert_nix_error;
 use crate::function::{OptionalArg, PyFuncArgs};
-use crate::obj::objbyteinner::PyBytesLike;
-use crate::obj::objbytes::PyBytesRef;
+use crate::obj::objbyteinner::ArgBytesLike;
 use crate::obj::objstr::PyStringRef;
 use crate::obj::objtuple::PyTupleRef;
 use crate::obj::objtype::PyClassRef;
@@ -60,6 +60,13 @@ impl PySocket {
         self.sock.borrow()
     }
 
+    /// The socket, for a call that may block, which is made without the global interpreter lock.
+    /// It isn't borrowed from `self.sock` meanwhile, so that another thread can still close it,
+    /// like in CPython.
+    fn blocking_sock(&self) -> ManuallyDrop<Socket> {
+        ManuallyDrop::new(unsafe { sock_from_fileno(sock_fileno(&self.sock())) })
+    }
+
     #[pyslot(new)]
     fn tp_new(cls: PyClassRef, _args: PyFuncArgs, vm: &VirtualMachine) -> PyResult<PyRef<Self>> {
         PySocket {
@@ -81,16 +88,7 @@ impl PySocket {
         vm: &VirtualMachine,
     ) -> PyResult<()> {
         let sock = if let Some(fileno) = fileno {
-            #[cfg(unix)]
-            {
-                use std::os::unix::io::FromRawFd;
-                unsafe { Socket::from_raw_fd(fileno) }
-            }
-            #[cfg(windows)]
-            {
-                use std::os::windows::io::FromRawSocket;
-                unsafe { Socket::from_raw_socket(fileno) }
-            }
+            unsafe { sock_from_fileno(fileno) }
         } else {
             let domain = match family {
                 c::AF_INET => Domain::ipv4(),
@@ -122,11 +120,14 @@ impl PySocket {
     #[pymethod]
     fn connect(&self, address: Address, vm: &VirtualMachine) -> PyResult<()> {
         let sock_addr = get_addr(vm, address)?;
-        let res = if let Some(duration) = self.sock().read_timeout().unwrap() {
-            self.sock().connect_timeout(&sock_addr, duration)
-        } else {
-            self.sock().connect(&sock_addr)
-        };
+        let sock = self.blocking_sock();
+        let res = vm.allow_threads(|| {
+            if let Some(duration) = sock.read_timeout().unwrap() {
+                sock.connect_timeout(&sock_addr, duration)
+            } else {
+                sock.connect(&sock_addr)
+            }
+        });
         res.map_err(|err| convert_sock_error(vm, err))
     }
 
@@ -149,9 +150,9 @@ impl PySocket {
 
     #[pymethod]
     fn _accept(&self, vm: &VirtualMachine) -> PyResult<(RawSocket, AddrTuple)> {
-        let (sock, addr) = self
-            .sock()
-            .accept()
+        let sock = self.blocking_sock();
+        let (sock, addr) = vm
+            .allow_threads(|| sock.accept())
             .map_err(|err| convert_sock_error(vm, err))?;
 
         let fd = into_sock_fileno(sock);
@@ -161,8 +162,12 @@ impl PySocket {
     #[pymethod]
     fn recv(&self, bufsize: usize, vm: &VirtualMachine) -> PyResult {
         let mut buffer = vec![0u8; bufsize];
-        match self.sock.borrow_mut().read_exact(&mut buffer) {
-            Ok(()) => Ok(vm.ctx.new_bytes(buffer)),
+        let mut sock = self.blocking_sock();
+        match vm.allow_threads(|| sock.read(&mut buffer)) {
+            Ok(n) => {
+                buffer.truncate(n);
+                Ok(vm.ctx.new_bytes(buffer))
+            }
             Err(err) => Err(convert_sock_error(vm, err)),
         }
     }
@@ -170,24 +175,38 @@ impl PySocket {
     #[pymethod]
     fn recvfrom(&self, bufsize: usize, vm: &VirtualMachine) -> PyResult<(Vec<u8>, AddrTuple)> {
         let mut buffer = vec![0u8; bufsize];
-        match self.sock().recv_from(&mut buffer) {
-            Ok((_, addr)) => Ok((buffer, get_addr_tuple(addr))),
+        let sock = self.blocking_sock();
+        match vm.allow_threads(|| sock.recv_from(&mut buffer)) {
+            Ok((n, addr)) => {
+                buffer.truncate(n);
+                Ok((buffer, get_addr_tuple(addr)))
+            }
             Err(err) => Err(convert_sock_error(vm, err)),
         }
     }
 
     #[pymethod]
-    fn send(&self, bytes: PyBytesLike, vm: &VirtualMachine) -> PyResult<usize> {
-        self.sock()
-            .send(bytes.to_cow().as_ref())
+    fn send(&self, bytes: ArgBytesLike, vm: &VirtualMachine) -> PyResult<usize> {
+        let bytes = bytes.with_ref(|bytes| bytes.to_vec());
+        let sock = self.blocking_sock();
+        vm.allow_threads(|| sock.send(&bytes))
             .map_err(|err| convert_sock_error(vm, err))
     }
 
     #[pymethod]
-    fn sendto(&self, bytes: PyBytesLike, address: Address, vm: &VirtualMachine) -> PyResult<()> {
+    fn sendall(&self, bytes: ArgBytesLike, vm: &VirtualMachine) -> PyResult<()> {
+        let bytes = bytes.with_ref(|bytes| bytes.to_vec());
+        let mut sock = self.blocking_sock();
+        vm.allow_threads(|| sock.write_all(&bytes))
+            .map_err(|err| convert_sock_error(vm, err))
+    }
+
+    #[pymethod]
+    fn sendto(&self, bytes: ArgBytesLike, address: Address, vm: &VirtualMachine) -> PyResult<()> {
         let addr = get_addr(vm, address)?;
-        self.sock()
-            .send_to(bytes.to_cow().as_ref(), &addr)
+        let bytes = bytes.with_ref(|bytes| bytes.to_vec());
+        let sock = self.blocking_sock();
+        vm.allow_threads(|| sock.send_to(&bytes, &addr))
             .map_err(|err| convert_sock_error(vm, err))?;
         Ok(())
     }
@@ -343,11 +362,11 @@ fn socket_inet_aton(ip_string: PyStringRef, vm: &VirtualMachine) -> PyResult {
         .map_err(|_| vm.new_os_error("illegal IP address string passed to inet_aton".to_string()))
 }
 
-fn socket_inet_ntoa(packed_ip: PyBytesRef, vm: &VirtualMachine) -> PyResult {
+fn socket_inet_ntoa(packed_ip: ArgBytesLike, vm: &VirtualMachine) -> PyResult {
     if packed_ip.len() != 4 {
         return Err(vm.new_os_error("packed IP wrong length for inet_ntoa".to_string()));
     }
-    let ip_num = BigEndian::read_u32(&packed_ip);
+    let ip_num = packed_ip.with_ref(BigEndian::read_u32);
     Ok(vm.new_str(Ipv4Addr::from(ip_num).to_string()))
 }
 
@@ -404,16 +423,27 @@ fn into_sock_fileno(sock: Socket) -> RawSocket {
     }
 }
 
-fn invalid_sock() -> Socket {
+unsafe fn sock_from_fileno(fileno: RawSocket) -> Socket {
     #[cfg(unix)]
     {
         use std::os::unix::io::FromRawFd;
-        unsafe { Socket::from_raw_fd(-1) }
+        Socket::from_raw_fd(fileno)
     }
     #[cfg(windows)]
     {
         use std::os::windows::io::FromRawSocket;
-        unsafe { Socket::from_raw_socket(winapi::um::winsock2::INVALID_SOCKET as RawSocket) }
+        Socket::from_raw_socket(fileno)
+    }
+}
+
+fn invalid_sock() -> Socket {
+    #[cfg(unix)]
+    {
+        unsafe { sock_from_fileno(-1) }
+    }
+    #[cfg(windows)]
+    {
+        unsafe { sock_from_fileno(winapi::um::winsock2::INVALID_SOCKET as RawSocket) }
     }
 }
 
@@ -9,11 +9,11 @@ use crate::function::OptionalArg;
 use crate::obj::objbytes::PyBytesRef;
 use crate::obj::objlist::PyListRef;
 use crate::obj::objsequence;
-use crate::obj::objstr::{self, PyStringRef};
+use crate::obj::objstr::PyStringRef;
 use crate::obj::objtype::PyClassRef;
-use crate::pyobject::{Either, IntoPyObject, PyObjectRef, PyRef, PyResult, PyValue};
+use crate::pyobject::{Either, IntoPyObject, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject};
 use crate::stdlib::io::io_open;
-use crate::stdlib::os::{convert_io_error, raw_file_number, rust_file};
+use crate::stdlib::os::{convert_io_error, raw_file_number, rust_file, PyPathLike};
 use crate::vm::VirtualMachine;
 
 #[derive(Debug)]
@@ -44,7 +44,7 @@ struct PopenArgs {
     #[pyarg(positional_or_keyword, default = "None")]
     close_fds: Option<bool>, // TODO: use these unused options
     #[pyarg(positional_or_keyword, default = "None")]
-    cwd: Option<PyStringRef>,
+    cwd: Option<PyPathLike>,
     #[pyarg(positional_or_keyword, default = "None")]
     start_new_session: Option<bool>,
 }
@@ -106,13 +106,17 @@ impl PopenRef {
         let stdout = convert_redirection(args.stdout, vm)?;
         let stderr = convert_redirection(args.stderr, vm)?;
         let command_list = match &args.args {
-            Either::A(command) => vec![command.as_str().to_string()],
+            Either::A(command) => vec![OsString::from(command.as_str())],
             Either::B(command_list) => objsequence::get_elements_list(command_list.as_object())
                 .iter()
-                .map(|x| objstr::get_value(x))
-                .collect(),
+                .map(|x| {
+                    Ok(PyPathLike::try_from_object(vm, x.clone())?
+                        .path
+                        .into_os_string())
+                })
+                .collect::<PyResult<_>>()?,
         };
-        let cwd = args.cwd.map(|x| OsString::from(x.as_str()));
+        let cwd = args.cwd.map(|x| x.path.into_os_string());
 
         let process = subprocess::Popen::create(
             &command_list,
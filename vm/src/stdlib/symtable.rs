@@ -82,6 +82,37 @@ impl PyValue for PySymbolTable {
     }
 }
 
+impl PySymbolTable {
+    fn to_py_symbol(&self, symbol: &symboltable::Symbol) -> PySymbol {
+        let namespaces = self
+            .symtable
+            .sub_tables
+            .iter()
+            .filter(|table| table.name == symbol.name)
+            .cloned()
+            .collect();
+        PySymbol {
+            symbol: symbol.clone(),
+            namespaces,
+        }
+    }
+
+    /// The names of the symbols for which `predicate` holds, as a tuple.
+    fn symbol_names<F>(&self, predicate: F, vm: &VirtualMachine) -> PyObjectRef
+    where
+        F: Fn(&symboltable::Symbol) -> bool,
+    {
+        let names = self
+            .symtable
+            .symbols
+            .values()
+            .filter(|symbol| predicate(symbol))
+            .map(|symbol| vm.ctx.new_str(symbol.name.clone()))
+            .collect();
+        vm.ctx.new_tuple(names)
+    }
+}
+
 #[pyimpl]
 impl PySymbolTable {
     #[pymethod(name = "get_name")]
@@ -99,19 +130,58 @@ impl PySymbolTable {
         self.symtable.line_number
     }
 
+    #[pymethod(name = "is_optimized")]
+    fn is_optimized(&self, _vm: &VirtualMachine) -> bool {
+        self.symtable.typ == symboltable::SymbolTableType::Function
+    }
+
     #[pymethod(name = "lookup")]
     fn lookup(&self, name: PyStringRef, vm: &VirtualMachine) -> PyResult<PySymbolRef> {
         let name = name.as_str();
         if let Some(symbol) = self.symtable.symbols.get(name) {
-            Ok(PySymbol {
-                symbol: symbol.clone(),
-            }
-            .into_ref(vm))
+            Ok(self.to_py_symbol(symbol).into_ref(vm))
         } else {
-            Err(vm.ctx.new_str(name.to_string()))
+            Err(vm.new_key_error(vm.ctx.new_str(name.to_string())))
         }
     }
 
+    #[pymethod(name = "get_parameters")]
+    fn get_parameters(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.symbol_names(|symbol| symbol.is_parameter, vm)
+    }
+
+    #[pymethod(name = "get_locals")]
+    fn get_locals(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.symbol_names(symboltable::Symbol::is_local, vm)
+    }
+
+    #[pymethod(name = "get_globals")]
+    fn get_globals(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.symbol_names(symboltable::Symbol::is_global, vm)
+    }
+
+    #[pymethod(name = "get_nonlocals")]
+    fn get_nonlocals(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.symbol_names(|symbol| symbol.is_nonlocal, vm)
+    }
+
+    #[pymethod(name = "get_frees")]
+    fn get_frees(&self, vm: &VirtualMachine) -> PyObjectRef {
+        self.symbol_names(symboltable::Symbol::is_free, vm)
+    }
+
+    #[pymethod(name = "get_methods")]
+    fn get_methods(&self, vm: &VirtualMachine) -> PyObjectRef {
+        let methods = self
+            .symtable
+            .sub_tables
+            .iter()
+            .filter(|table| table.typ == symboltable::SymbolTableType::Function)
+            .map(|table| vm.ctx.new_str(table.name.clone()))
+            .collect();
+        vm.ctx.new_tuple(methods)
+    }
+
     #[pymethod(name = "get_identifiers")]
     fn get_identifiers(&self, vm: &VirtualMachine) -> PyResult {
         let symbols = self
@@ -129,7 +199,7 @@ impl PySymbolTable {
             .symtable
             .symbols
             .values()
-            .map(|s| (PySymbol { symbol: s.clone() }).into_ref(vm).into_object())
+            .map(|s| self.to_py_symbol(s).into_ref(vm).into_object())
             .collect();
         Ok(vm.ctx.new_list(symbols))
     }
@@ -154,6 +224,8 @@ impl PySymbolTable {
 #[pyclass(name = "Symbol")]
 struct PySymbol {
     symbol: symboltable::Symbol,
+    /// The tables of the functions and classes the symbol is bound to.
+    namespaces: Vec<symboltable::SymbolTable>,
 }
 
 impl fmt::Debug for PySymbol {
@@ -180,6 +252,16 @@ impl PySymbol {
         self.symbol.is_global()
     }
 
+    #[pymethod(name = "is_declared_global")]
+    fn is_declared_global(&self, _vm: &VirtualMachine) -> bool {
+        self.symbol.scope == symboltable::SymbolScope::GlobalExplicit
+    }
+
+    #[pymethod(name = "is_nonlocal")]
+    fn is_nonlocal(&self, _vm: &VirtualMachine) -> bool {
+        self.symbol.is_nonlocal
+    }
+
     #[pymethod(name = "is_local")]
     fn is_local(&self, _vm: &VirtualMachine) -> bool {
         self.symbol.is_local()
@@ -202,12 +284,21 @@ impl PySymbol {
 
     #[pymethod(name = "is_free")]
     fn is_free(&self, _vm: &VirtualMachine) -> bool {
-        self.symbol.is_free
+        self.symbol.is_free()
     }
 
     #[pymethod(name = "is_namespace")]
     fn is_namespace(&self, _vm: &VirtualMachine) -> bool {
-        // TODO
-        false
+        !self.namespaces.is_empty()
+    }
+
+    #[pymethod(name = "get_namespaces")]
+    fn get_namespaces(&self, vm: &VirtualMachine) -> PyObjectRef {
+        let namespaces = self
+            .namespaces
+            .iter()
+            .map(|table| to_py_symbol_table(table.clone()).into_ref(vm).into_object())
+            .collect();
+        vm.ctx.new_list(namespaces)
     }
 }
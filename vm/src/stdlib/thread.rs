@@ -1,7 +1,27 @@
-/// Implementation of the _thread module, currently noop implementation as RustPython doesn't yet
-/// support threading
-use crate::function::PyFuncArgs;
-use crate::pyobject::{PyObjectRef, PyResult};
+/// Implementation of the _thread module. Each thread runs on an OS thread of its own, with its
+/// own `VirtualMachine`, and the threads take turns running Python code, see `crate::gil`. The
+/// locks are waited for without the global interpreter lock, with `wait_until`, which signal
+/// handlers can interrupt too.
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::exceptions;
+use crate::function::{OptionalArg, PyFuncArgs};
+use crate::gc;
+use crate::gil::Gil;
+use crate::obj::objbool::IntoPyBool;
+use crate::obj::objdict::PyDictRef;
+use crate::obj::objfloat::IntoPyFloat;
+use crate::obj::objstr::PyStringRef;
+use crate::obj::objtuple::PyTupleRef;
+use crate::obj::objtype::{self, PyClassRef};
+use crate::pyobject::{
+    IdProtocol, PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TryFromObject,
+};
+use crate::stdlib::time_module::{duration_from_secs, wait_until};
 use crate::vm::VirtualMachine;
 
 #[cfg(not(target_os = "windows"))]
@@ -12,55 +32,458 @@ const PY_TIMEOUT_MAX: isize = 0xffffffff * 1_000_000;
 
 const TIMEOUT_MAX: f64 = (PY_TIMEOUT_MAX / 1_000_000_000) as f64;
 
-fn rlock_acquire(vm: &VirtualMachine, _args: PyFuncArgs) -> PyResult {
-    Ok(vm.get_none())
+#[derive(FromArgs)]
+struct AcquireArgs {
+    #[pyarg(positional_or_keyword, default = "IntoPyBool::TRUE")]
+    blocking: IntoPyBool,
+    #[pyarg(positional_or_keyword, optional = true)]
+    timeout: OptionalArg<IntoPyFloat>,
+}
+
+impl Default for AcquireArgs {
+    fn default() -> Self {
+        AcquireArgs {
+            blocking: IntoPyBool::TRUE,
+            timeout: OptionalArg::Missing,
+        }
+    }
+}
+
+/// How long `acquire` may wait for a held lock.
+enum Wait {
+    No,
+    Forever,
+    For(Duration),
+}
+
+impl AcquireArgs {
+    // -1 is the default timeout, so it's compared exactly like CPython does
+    #[allow(clippy::float_cmp)]
+    fn wait(&self, vm: &VirtualMachine) -> PyResult<Wait> {
+        let timeout = match self.timeout {
+            OptionalArg::Present(ref timeout) => timeout.to_f64(),
+            OptionalArg::Missing => -1.0,
+        };
+        if !self.blocking.to_bool() {
+            if timeout != -1.0 {
+                return Err(vm.new_value_error(
+                    "can't specify a timeout for a non-blocking call".to_string(),
+                ));
+            }
+            return Ok(Wait::No);
+        }
+        if timeout == -1.0 {
+            return Ok(Wait::Forever);
+        }
+        if timeout < 0.0 {
+            return Err(vm.new_value_error("timeout value must be positive".to_string()));
+        }
+        if timeout > TIMEOUT_MAX {
+            return Err(vm.new_overflow_error("timeout value is too large".to_string()));
+        }
+        Ok(Wait::For(duration_from_secs(timeout, vm)?))
+    }
+}
+
+/// How big the stacks of new threads are when `stack_size` wasn't called, which is what the main
+/// thread usually gets too, as the frames of Python functions nest on it.
+const DEFAULT_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The smallest size `stack_size` accepts other than 0, like in CPython.
+const MIN_STACK_SIZE: usize = 32 * 1024;
+
+/// What `stack_size` was last set to, with 0 meaning the default.
+static STACK_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+static NEXT_IDENT: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// The identifier of the current thread, assigned when it is first asked for.
+    static IDENT: Cell<u64> = Cell::new(0);
+    /// The locks `_set_sentinel` made for the current thread, released when it ends.
+    static SENTINELS: RefCell<Vec<PyLockRef>> = RefCell::new(vec![]);
+}
+
+fn current_ident() -> u64 {
+    IDENT.with(|ident| {
+        if ident.get() == 0 {
+            ident.set(NEXT_IDENT.fetch_add(1, Ordering::Relaxed));
+        }
+        ident.get()
+    })
+}
+
+#[pyclass(name = "lock")]
+#[derive(Debug, Default)]
+struct PyLock {
+    locked: Cell<bool>,
+}
+
+impl PyValue for PyLock {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("_thread", "lock")
+    }
+}
+
+type PyLockRef = PyRef<PyLock>;
+
+#[pyimpl]
+impl PyLock {
+    #[pymethod]
+    fn acquire(&self, args: AcquireArgs, vm: &VirtualMachine) -> PyResult<bool> {
+        if !self.locked.get() {
+            self.locked.set(true);
+            return Ok(true);
+        }
+        let acquired = match args.wait(vm)? {
+            Wait::No => false,
+            Wait::Forever => wait_until(None, || !self.locked.get(), vm)?,
+            Wait::For(timeout) => wait_until(Some(timeout), || !self.locked.get(), vm)?,
+        };
+        if acquired {
+            self.locked.set(true);
+        }
+        Ok(acquired)
+    }
+
+    /// Any thread may release the lock, not only the one that acquired it.
+    #[pymethod]
+    fn release(&self, vm: &VirtualMachine) -> PyResult<()> {
+        if !self.locked.get() {
+            return Err(vm.new_exception(
+                vm.ctx.exceptions.runtime_error.clone(),
+                "release unlocked lock".to_string(),
+            ));
+        }
+        self.locked.set(false);
+        vm.gil.notify();
+        Ok(())
+    }
+
+    #[pymethod(name = "__enter__")]
+    fn enter(zelf: PyLockRef, vm: &VirtualMachine) -> PyResult<PyLockRef> {
+        zelf.acquire(AcquireArgs::default(), vm)?;
+        Ok(zelf)
+    }
+
+    #[pymethod(name = "__exit__")]
+    fn exit(&self, _args: PyFuncArgs, vm: &VirtualMachine) -> PyResult<()> {
+        self.release(vm)
+    }
+
+    #[pymethod]
+    fn locked(&self, _vm: &VirtualMachine) -> bool {
+        self.locked.get()
+    }
+
+    #[pymethod(name = "__repr__")]
+    fn repr(zelf: PyLockRef, _vm: &VirtualMachine) -> String {
+        let state = if zelf.locked.get() {
+            "locked"
+        } else {
+            "unlocked"
+        };
+        format!("<{} _thread.lock object at {:#x}>", state, zelf.get_id())
+    }
 }
 
-fn rlock_release(_zelf: PyObjectRef, _vm: &VirtualMachine) {}
+/// A lock that the thread that holds it can acquire again, and has to release as many times.
+#[pyclass(name = "RLock")]
+#[derive(Debug)]
+struct PyRLock {
+    /// The identifier of the thread that holds the lock, or 0.
+    owner: Cell<u64>,
+    count: Cell<usize>,
+}
 
-fn rlock_enter(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
-    arg_check!(vm, args, required = [(instance, None)]);
-    Ok(instance.clone())
+impl PyValue for PyRLock {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("_thread", "RLock")
+    }
 }
 
-fn rlock_exit(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
-    arg_check!(
+type PyRLockRef = PyRef<PyRLock>;
+
+#[pyimpl]
+impl PyRLock {
+    #[pyslot(new)]
+    fn tp_new(cls: PyClassRef, vm: &VirtualMachine) -> PyResult<PyRLockRef> {
+        PyRLock {
+            owner: Cell::new(0),
+            count: Cell::new(0),
+        }
+        .into_ref_with_type(vm, cls)
+    }
+
+    fn is_owned(&self) -> bool {
+        self.count.get() > 0 && self.owner.get() == current_ident()
+    }
+
+    /// Wait until no thread holds the lock, and take it with `count`.
+    fn take(&self, count: usize, wait: Wait, vm: &VirtualMachine) -> PyResult<bool> {
+        let free = || self.count.get() == 0;
+        let acquired = free()
+            || match wait {
+                Wait::No => false,
+                Wait::Forever => wait_until(None, free, vm)?,
+                Wait::For(timeout) => wait_until(Some(timeout), free, vm)?,
+            };
+        if acquired {
+            self.owner.set(current_ident());
+            self.count.set(count);
+        }
+        Ok(acquired)
+    }
+
+    #[pymethod]
+    fn acquire(&self, args: AcquireArgs, vm: &VirtualMachine) -> PyResult<bool> {
+        let wait = args.wait(vm)?;
+        if self.is_owned() {
+            self.count.set(self.count.get() + 1);
+            return Ok(true);
+        }
+        self.take(1, wait, vm)
+    }
+
+    #[pymethod]
+    fn release(&self, vm: &VirtualMachine) -> PyResult<()> {
+        if !self.is_owned() {
+            return Err(vm.new_exception(
+                vm.ctx.exceptions.runtime_error.clone(),
+                "cannot release un-acquired lock".to_string(),
+            ));
+        }
+        self.count.set(self.count.get() - 1);
+        if self.count.get() == 0 {
+            self.owner.set(0);
+            vm.gil.notify();
+        }
+        Ok(())
+    }
+
+    #[pymethod(name = "__enter__")]
+    fn enter(zelf: PyRLockRef, vm: &VirtualMachine) -> PyResult<PyRLockRef> {
+        zelf.acquire(AcquireArgs::default(), vm)?;
+        Ok(zelf)
+    }
+
+    #[pymethod(name = "__exit__")]
+    fn exit(&self, _args: PyFuncArgs, vm: &VirtualMachine) -> PyResult<()> {
+        self.release(vm)
+    }
+
+    #[pymethod]
+    fn _is_owned(&self, _vm: &VirtualMachine) -> bool {
+        self.is_owned()
+    }
+
+    /// Release the lock however many times it was acquired, for `Condition.wait`.
+    #[pymethod]
+    fn _release_save(&self, vm: &VirtualMachine) -> PyResult<(usize, u64)> {
+        if self.count.get() == 0 {
+            return Err(vm.new_exception(
+                vm.ctx.exceptions.runtime_error.clone(),
+                "cannot release un-acquired lock".to_string(),
+            ));
+        }
+        let state = (self.count.replace(0), self.owner.replace(0));
+        vm.gil.notify();
+        Ok(state)
+    }
+
+    #[pymethod]
+    fn _acquire_restore(&self, state: PyTupleRef, vm: &VirtualMachine) -> PyResult<()> {
+        let count = match state.elements.first() {
+            Some(count) => usize::try_from_object(vm, count.clone())?,
+            None => return Err(vm.new_type_error("expected a (count, owner) tuple".to_string())),
+        };
+        self.take(count, Wait::Forever, vm)?;
+        Ok(())
+    }
+
+    #[pymethod(name = "__repr__")]
+    fn repr(zelf: PyRLockRef, _vm: &VirtualMachine) -> String {
+        let state = if zelf.count.get() > 0 {
+            "locked"
+        } else {
+            "unlocked"
+        };
+        format!(
+            "<{} _thread.RLock object owner={} count={} at {:#x}>",
+            state,
+            zelf.owner.get(),
+            zelf.count.get(),
+            zelf.get_id()
+        )
+    }
+}
+
+/// What a new thread runs, and the `VirtualMachine` it runs it with.
+struct ThreadStart {
+    vm: VirtualMachine,
+    func: PyObjectRef,
+    args: PyFuncArgs,
+    ident: u64,
+}
+
+// SAFETY: none of the objects in it are shared by other threads but through the global
+// interpreter lock. It is made by the thread that starts the new one, while that holds the
+// lock, and the new thread takes the lock before it uses any of it, and drops all of it before it
+// releases the lock.
+unsafe impl Send for ThreadStart {}
+
+/// Holds the global interpreter lock for a thread that was started, and releases it when the
+/// thread ends, even if it panics.
+struct Running(Arc<Gil>);
+
+impl Running {
+    fn new(gil: Arc<Gil>) -> Self {
+        gil.acquire();
+        Running(gil)
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        self.0.thread_ended();
+        self.0.release();
+    }
+}
+
+fn run_thread(gil: Arc<Gil>, start: ThreadStart) {
+    let _running = Running::new(gil);
+    let ThreadStart {
         vm,
+        func,
         args,
-        // The context manager protocol requires these, but we don't use them
-        required = [
-            (_instance, None),
-            (_exception_type, None),
-            (_exception_value, None),
-            (_traceback, None)
-        ]
-    );
-    Ok(vm.get_none())
+        ident,
+    } = start;
+    IDENT.with(|current| current.set(ident));
+    if let Err(exc) = vm.invoke(&func, args) {
+        if !objtype::isinstance(&exc, &vm.ctx.exceptions.system_exit) {
+            let repr = vm
+                .to_repr(&func)
+                .map(|repr| repr.as_str().to_owned())
+                .unwrap_or_else(|_| "<object repr() failed>".to_owned());
+            eprintln!("Unhandled exception in thread started by {}", repr);
+            let _ = exceptions::write_exception(std::io::stderr(), &vm, &exc);
+        }
+    }
+    for sentinel in SENTINELS.with(|sentinels| sentinels.replace(vec![])) {
+        sentinel.locked.set(false);
+    }
+    vm.gil.notify();
+    gc::release_thread(&vm);
+}
+
+fn start_new_thread(
+    func: PyObjectRef,
+    args: PyObjectRef,
+    kwargs: OptionalArg<PyObjectRef>,
+    vm: &VirtualMachine,
+) -> PyResult<u64> {
+    if !vm.is_callable(&func) {
+        return Err(vm.new_type_error("first arg must be callable".to_owned()));
+    }
+    let args = PyTupleRef::try_from_object(vm, args)
+        .map_err(|_| vm.new_type_error("2nd arg must be a tuple".to_owned()))?;
+    let mut values = args.elements.clone();
+    let mut names = vec![];
+    if let OptionalArg::Present(kwargs) = kwargs {
+        let kwargs = PyDictRef::try_from_object(vm, kwargs)
+            .map_err(|_| vm.new_type_error("optional 3rd arg must be a dictionary".to_owned()))?;
+        for (name, value) in kwargs {
+            let name = PyStringRef::try_from_object(vm, name)
+                .map_err(|_| vm.new_type_error("keywords must be strings".to_owned()))?;
+            names.push(name.as_str().to_owned());
+            values.push(value);
+        }
+    }
+
+    let ident = NEXT_IDENT.fetch_add(1, Ordering::Relaxed);
+    let start = ThreadStart {
+        vm: vm.new_thread(),
+        func,
+        args: PyFuncArgs::new(values, names),
+        ident,
+    };
+    let stack_size = match STACK_SIZE.load(Ordering::Relaxed) {
+        0 => DEFAULT_STACK_SIZE,
+        size => size,
+    };
+    let gil = vm.gil.clone();
+    gil.thread_started();
+    let spawned = thread::Builder::new()
+        .stack_size(stack_size)
+        .spawn(move || run_thread(gil, start));
+    if spawned.is_err() {
+        vm.gil.thread_ended();
+        return Err(vm.new_exception(
+            vm.ctx.exceptions.runtime_error.clone(),
+            "can't start new thread".to_owned(),
+        ));
+    }
+    Ok(ident)
+}
+
+fn exit_thread(vm: &VirtualMachine) -> PyResult {
+    Err(vm.new_empty_exception(vm.ctx.exceptions.system_exit.clone())?)
+}
+
+/// Raise KeyboardInterrupt in the main thread, as if it got a SIGINT.
+fn interrupt_main(_vm: &VirtualMachine) {
+    #[cfg(not(target_arch = "wasm32"))]
+    crate::stdlib::signal::set_interrupt();
+}
+
+fn get_ident(_vm: &VirtualMachine) -> u64 {
+    current_ident()
+}
+
+fn allocate_lock(vm: &VirtualMachine) -> PyLockRef {
+    PyLock::default().into_ref(vm)
+}
+
+/// A lock that is released when the current thread ends, which `threading` acquires and uses
+/// to join it.
+fn set_sentinel(vm: &VirtualMachine) -> PyLockRef {
+    let lock = PyLock::default().into_ref(vm);
+    SENTINELS.with(|sentinels| sentinels.borrow_mut().push(lock.clone()));
+    lock
 }
 
-fn get_ident(_vm: &VirtualMachine) -> u32 {
-    1
+/// How many threads that were started are still running.
+fn count(vm: &VirtualMachine) -> usize {
+    vm.gil.thread_count()
 }
 
-fn allocate_lock(vm: &VirtualMachine) -> PyResult {
-    let lock_class = vm.class("_thread", "RLock");
-    vm.invoke(&lock_class.into_object(), vec![])
+/// Set the stack size of the threads started from now on, and return the previous one.
+fn stack_size(size: OptionalArg<usize>, vm: &VirtualMachine) -> PyResult<usize> {
+    let size = size.unwrap_or(0);
+    if size != 0 && size < MIN_STACK_SIZE {
+        return Err(vm.new_value_error(format!("size not valid: {} bytes", size)));
+    }
+    Ok(STACK_SIZE.swap(size, Ordering::Relaxed))
 }
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
-    let rlock_type = py_class!(ctx, "_thread.RLock", ctx.object(), {
-        "acquire" => ctx.new_rustfunc(rlock_acquire),
-        "release" => ctx.new_rustfunc(rlock_release),
-        "__enter__" => ctx.new_rustfunc(rlock_enter),
-        "__exit__" => ctx.new_rustfunc(rlock_exit),
-    });
+    let lock_type = PyLock::make_class(ctx);
 
     py_module!(vm, "_thread", {
-        "RLock" => rlock_type,
+        "lock" => lock_type.clone(),
+        "LockType" => lock_type,
+        "RLock" => PyRLock::make_class(ctx),
+        "error" => ctx.exceptions.runtime_error.clone(),
+        "start_new_thread" => ctx.new_rustfunc(start_new_thread),
+        "exit" => ctx.new_rustfunc(exit_thread),
         "get_ident" => ctx.new_rustfunc(get_ident),
+        "interrupt_main" => ctx.new_rustfunc(interrupt_main),
         "allocate_lock" => ctx.new_rustfunc(allocate_lock),
+        "_set_sentinel" => ctx.new_rustfunc(set_sentinel),
+        "_count" => ctx.new_rustfunc(count),
+        "stack_size" => ctx.new_rustfunc(stack_size),
         "TIMEOUT_MAX" => ctx.new_float(TIMEOUT_MAX),
     })
 }
@@ -3,12 +3,13 @@
 /// https://docs.python.org/3/library/time.html
 use std::fmt;
 use std::ops::Range;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use chrono::naive::NaiveDateTime;
 use chrono::{Datelike, Timelike};
 
 use crate::function::OptionalArg;
+use crate::obj::objfloat::IntoPyFloat;
 use crate::obj::objint::PyInt;
 use crate::obj::objsequence::{get_sequence_index, PySliceableSequence};
 use crate::obj::objslice::PySlice;
@@ -17,28 +18,78 @@ use crate::obj::objtype::PyClassRef;
 use crate::pyobject::{Either, PyClassImpl, PyObjectRef, PyRef, PyResult, PyValue, TypeProtocol};
 use crate::vm::VirtualMachine;
 
-#[cfg(unix)]
-fn time_sleep(seconds: f64, vm: &VirtualMachine) -> PyResult<()> {
-    // this is basically std::thread::sleep, but that catches interrupts and we don't want to
-    let dur = Duration::from_secs_f64(seconds);
+/// The longest the wait functions sleep at once before checking for signals again. A signal
+/// usually cuts the sleep short, but one that arrives just before it starts is only handled
+/// when it ends.
+const WAIT_SLICE: Duration = Duration::from_millis(50);
+
+/// Wait until `done` returns true, or until `timeout` has passed if there is one, and return
+/// whether `done` returned true. Signal handlers run as signals arrive, and if one raises an
+/// exception, like the default SIGINT handler raising KeyboardInterrupt, the wait ends with it.
+/// A handler that returns doesn't shorten the wait.
+///
+/// Other threads run while this one waits, and the ones that release a `_thread` lock wake it
+/// up to check `done` again.
+pub(crate) fn wait_until(
+    timeout: Option<Duration>,
+    done: impl Fn() -> bool,
+    vm: &VirtualMachine,
+) -> PyResult<bool> {
+    let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    loop {
+        vm.check_signals()?;
+        if done() {
+            return Ok(true);
+        }
+        let slice = match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(false);
+                }
+                std::cmp::min(deadline - now, WAIT_SLICE)
+            }
+            // No timeout, or one too far in the future to ever pass
+            None => WAIT_SLICE,
+        };
+        if vm.is_main_thread() && vm.gil.thread_count() == 0 {
+            sleep_slice(slice);
+        } else {
+            vm.gil.wait_for_event(slice);
+        }
+    }
+}
 
-    let mut ts = libc::timespec {
-        tv_sec: std::cmp::min(libc::time_t::max_value() as u64, dur.as_secs()) as libc::time_t,
+#[cfg(unix)]
+fn sleep_slice(dur: Duration) {
+    // Unlike std::thread::sleep, this returns early when a signal arrives
+    let ts = libc::timespec {
+        tv_sec: dur.as_secs() as libc::time_t,
         tv_nsec: dur.subsec_nanos().into(),
     };
-    let res = unsafe { libc::nanosleep(&ts, &mut ts) };
-    let interrupted = res == -1 && nix::errno::errno() == libc::EINTR;
+    unsafe { libc::nanosleep(&ts, std::ptr::null_mut()) };
+}
 
-    if interrupted {
-        vm.check_signals()?;
-    }
+#[cfg(not(unix))]
+fn sleep_slice(dur: Duration) {
+    std::thread::sleep(dur);
+}
 
-    Ok(())
+/// Convert a number of seconds from python to a `Duration`, as for `time.sleep`.
+pub(crate) fn duration_from_secs(seconds: f64, vm: &VirtualMachine) -> PyResult<Duration> {
+    if seconds.is_nan() || seconds < 0.0 {
+        return Err(vm.new_value_error("sleep length must be non-negative".to_string()));
+    }
+    if seconds >= u64::max_value() as f64 {
+        return Err(vm.new_overflow_error("sleep length is too large".to_string()));
+    }
+    Ok(Duration::from_secs_f64(seconds))
 }
 
-#[cfg(not(unix))]
-fn time_sleep(seconds: f64, _vm: &VirtualMachine) {
-    std::thread::sleep(Duration::from_secs_f64(seconds));
+fn time_sleep(seconds: IntoPyFloat, vm: &VirtualMachine) -> PyResult<()> {
+    let dur = duration_from_secs(seconds.to_f64(), vm)?;
+    wait_until(Some(dur), || false, vm)?;
+    Ok(())
 }
 
 #[cfg(any(not(target_arch = "wasm32"), target_os = "wasi"))]
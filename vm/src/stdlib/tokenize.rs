@@ -2,33 +2,295 @@
  * python tokenize module.
  */
 
-use std::iter::FromIterator;
-
+use rustpython_parser::error::LexicalError;
 use rustpython_parser::lexer;
+use rustpython_parser::location::Location;
+use rustpython_parser::token::Tok;
 
-use crate::function::PyFuncArgs;
-use crate::obj::objstr;
+use crate::obj::objstr::PyStringRef;
 use crate::pyobject::{PyObjectRef, PyResult};
 use crate::vm::VirtualMachine;
 
-fn tokenize_tokenize(vm: &VirtualMachine, args: PyFuncArgs) -> PyResult {
-    arg_check!(vm, args, required = [(readline, Some(vm.ctx.str_type()))]);
-    let source = objstr::get_value(readline);
+// The token types, as numbered in Lib/token.py.
+const ENDMARKER: u8 = 0;
+const NAME: u8 = 1;
+const NUMBER: u8 = 2;
+const STRING: u8 = 3;
+const NEWLINE: u8 = 4;
+const INDENT: u8 = 5;
+const DEDENT: u8 = 6;
+const OP: u8 = 53;
+const COMMENT: u8 = 55;
+const NL: u8 = 56;
 
-    // TODO: implement generator when the time has come.
-    let lexer1 = lexer::make_tokenizer(&source);
+/// A (row, column) position, with the rows counted from 1 and the columns from 0 like the
+/// tokenize module does.
+type Position = (usize, usize);
 
-    let tokens = lexer1.map(|st| vm.ctx.new_str(format!("{:?}", st.unwrap().1)));
-    let tokens = Vec::from_iter(tokens);
-    Ok(vm.ctx.new_list(tokens))
+struct TokenInfo {
+    typ: u8,
+    string: String,
+    start: Position,
+    end: Position,
+    line: String,
+}
+
+/// Split `source` into its lines, keeping the `\n`, `\r\n` or `\r` at the end of each one.
+fn split_lines(source: &str) -> Vec<Vec<char>> {
+    let mut lines = vec![];
+    let mut line = vec![];
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        line.push(c);
+        if c == '\r' && chars.peek() == Some(&'\n') {
+            line.push(chars.next().unwrap());
+        }
+        if c == '\r' || c == '\n' {
+            lines.push(std::mem::replace(&mut line, vec![]));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn is_line_end(c: char) -> bool {
+    c == '\n' || c == '\r'
+}
+
+/// Turns the tokens of the lexer into those of the tokenize module: it puts back the comments
+/// and the newlines of blank lines, which the lexer skips, and recovers the text and position
+/// of every token from the source.
+struct Tokenizer {
+    lines: Vec<Vec<char>>,
+    tokens: Vec<TokenInfo>,
+    /// Where the previous token ended.
+    position: Position,
+}
+
+impl Tokenizer {
+    fn new(source: &str) -> Self {
+        Tokenizer {
+            lines: split_lines(source),
+            tokens: vec![],
+            position: (1, 0),
+        }
+    }
+
+    fn line(&self, row: usize) -> &[char] {
+        self.lines.get(row - 1).map_or(&[], |line| line)
+    }
+
+    /// The text from `start` to `end`, which may span several lines.
+    fn text(&self, start: Position, end: Position) -> String {
+        if start.0 == end.0 {
+            return self.line(start.0)[start.1..end.1].iter().collect();
+        }
+        let mut text: String = self.line(start.0)[start.1..].iter().collect();
+        for row in start.0 + 1..end.0 {
+            text.extend(self.line(row));
+        }
+        text.extend(&self.line(end.0)[..end.1]);
+        text
+    }
+
+    /// The whole lines from the one of `start` to the one of `end`.
+    fn physical_lines(&self, start: Position, end: Position) -> String {
+        (start.0..=end.0).flat_map(|row| self.line(row)).collect()
+    }
+
+    fn end_of_file(&self) -> Position {
+        (self.lines.len() + 1, 0)
+    }
+
+    fn push(&mut self, typ: u8, start: Position, end: Position) {
+        let string = self.text(start, end);
+        let line = self.physical_lines(start, end);
+        self.tokens.push(TokenInfo {
+            typ,
+            string,
+            start,
+            end,
+            line,
+        });
+        self.position = end;
+    }
+
+    /// Emit the comments and the line ends which don't end a statement between the previous
+    /// token and `end`.
+    fn skipped(&mut self, end: Position) {
+        let (start_row, start_column) = self.position;
+        for row in start_row..=end.0.min(self.lines.len()) {
+            let line = self.line(row).to_vec();
+            let mut column = if row == start_row { start_column } else { 0 };
+            let last = if row == end.0 { end.1 } else { line.len() };
+            while column < last {
+                let c = line[column];
+                if c == '#' {
+                    let comment_end = line
+                        .iter()
+                        .position(|c| is_line_end(*c))
+                        .unwrap_or_else(|| line.len());
+                    self.push(COMMENT, (row, column), (row, comment_end));
+                    if comment_end < last {
+                        self.push(NL, (row, comment_end), (row, line.len()));
+                    } else if comment_end == line.len() && end == self.end_of_file() {
+                        // A comment on the last line still ends it, without a newline.
+                        self.push(NL, (row, comment_end), (row, comment_end));
+                    }
+                    break;
+                } else if is_line_end(c) {
+                    if column == 0 || line[column - 1] != '\\' {
+                        self.push(NL, (row, column), (row, line.len()));
+                    }
+                    break;
+                } else {
+                    column += 1;
+                }
+            }
+        }
+        self.position = end;
+    }
+
+    /// Where `tok` really starts: the lexer starts strings after their opening quote rather than
+    /// at their prefix.
+    fn token_start(&self, tok: &Tok, start: Position) -> Position {
+        match tok {
+            Tok::String { .. } | Tok::Bytes { .. } => {
+                let line = self.line(start.0);
+                let first = if self.position.0 == start.0 {
+                    self.position.1
+                } else {
+                    0
+                };
+                let mut column = start.1 - 1;
+                while column > first && line[column - 1].is_ascii_alphabetic() {
+                    column -= 1;
+                }
+                (start.0, column)
+            }
+            _ => start,
+        }
+    }
+
+    fn feed(&mut self, tokens: &[(Position, Tok, Position)]) {
+        for (index, (start, tok, end)) in tokens.iter().enumerate() {
+            let (start, end) = (*start, *end);
+            match tok {
+                Tok::Indent => {
+                    self.skipped((start.0, 0));
+                    self.push(INDENT, (start.0, 0), start);
+                }
+                Tok::Dedent => {
+                    // Dedents are placed at the token that follows them.
+                    let next = tokens[index..]
+                        .iter()
+                        .find(|(_, tok, _)| *tok != Tok::Dedent)
+                        .map_or_else(
+                            || self.end_of_file(),
+                            |(start, tok, _)| self.token_start(tok, *start),
+                        );
+                    self.skipped(next);
+                    self.push(DEDENT, next, next);
+                }
+                Tok::Newline => {
+                    self.skipped(start);
+                    let line_end = self.line(start.0).len();
+                    if start.1 == line_end {
+                        // The source doesn't end with a newline, yet the statement ends.
+                        self.tokens.push(TokenInfo {
+                            typ: NEWLINE,
+                            string: String::new(),
+                            start,
+                            end: (start.0, start.1 + 1),
+                            line: String::new(),
+                        });
+                        self.position = start;
+                    } else {
+                        self.push(NEWLINE, start, (start.0, line_end));
+                        self.position = (start.0 + 1, 0);
+                    }
+                }
+                Tok::String { .. } | Tok::Bytes { .. } => {
+                    let start = self.token_start(tok, start);
+                    self.skipped(start);
+                    self.push(STRING, start, end);
+                }
+                _ => {
+                    self.skipped(start);
+                    let typ = match tok {
+                        Tok::Name { .. } => NAME,
+                        Tok::Int { .. } | Tok::Float { .. } | Tok::Complex { .. } => NUMBER,
+                        _ => {
+                            let c = self.line(start.0)[start.1];
+                            if c.is_alphabetic() || c == '_' {
+                                NAME
+                            } else {
+                                OP
+                            }
+                        }
+                    };
+                    self.push(typ, start, end);
+                }
+            }
+        }
+        let end_of_file = self.end_of_file();
+        self.skipped(end_of_file);
+        self.push(ENDMARKER, end_of_file, end_of_file);
+    }
 }
 
-// TODO: create main function when called with -m
+fn position(location: &Location) -> Position {
+    (location.row(), location.column() - 1)
+}
+
+/// Tokenize `source` the way the tokenize module does.
+fn tokenize(source: &str) -> Result<Vec<TokenInfo>, LexicalError> {
+    let tokens = lexer::make_tokenizer(source)
+        .map(|result| result.map(|(start, tok, end)| (position(&start), tok, position(&end))))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut tokenizer = Tokenizer::new(source);
+    tokenizer.feed(&tokens);
+    Ok(tokenizer.tokens)
+}
+
+/// The (type, string, start, end, line) tuples of the tokens of `source`, for Lib/tokenize.py.
+fn tokenize_tokenize(source: PyStringRef, vm: &VirtualMachine) -> PyResult {
+    let tokens = tokenize(source.as_str()).map_err(|err| {
+        let syntax_error = vm.new_exception(
+            vm.ctx.exceptions.syntax_error.clone(),
+            err.error.to_string(),
+        );
+        let lineno = vm.ctx.new_int(err.location.row());
+        vm.set_attr(&syntax_error, "lineno", lineno).unwrap();
+        let offset = vm.ctx.new_int(err.location.column());
+        vm.set_attr(&syntax_error, "offset", offset).unwrap();
+        syntax_error
+    })?;
+    let tokens = tokens
+        .into_iter()
+        .map(|token| {
+            let position = |(row, column): Position| {
+                vm.ctx
+                    .new_tuple(vec![vm.ctx.new_int(row), vm.ctx.new_int(column)])
+            };
+            vm.ctx.new_tuple(vec![
+                vm.ctx.new_int(token.typ),
+                vm.ctx.new_str(token.string),
+                position(token.start),
+                position(token.end),
+                vm.ctx.new_str(token.line),
+            ])
+        })
+        .collect();
+    Ok(vm.ctx.new_list(tokens))
+}
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
 
-    py_module!(vm, "tokenize", {
+    py_module!(vm, "_tokenize", {
         "tokenize" => ctx.new_rustfunc(tokenize_tokenize)
     })
 }
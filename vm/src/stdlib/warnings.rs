@@ -1,6 +1,7 @@
 use crate::function::OptionalArg;
 use crate::obj::objstr::PyStringRef;
-use crate::pyobject::PyObjectRef;
+use crate::obj::objtype::PyClassRef;
+use crate::pyobject::{PyObjectRef, TryFromObject};
 use crate::vm::VirtualMachine;
 
 #[derive(FromArgs)]
@@ -13,16 +14,23 @@ struct WarnArgs {
     stacklevel: OptionalArg<u32>,
 }
 
-fn warnings_warn(args: WarnArgs, _vm: &VirtualMachine) {
+fn warnings_warn(args: WarnArgs, vm: &VirtualMachine) {
     // TODO: Implement correctly
     let level = match args.stacklevel {
         OptionalArg::Present(l) => l,
         OptionalArg::Missing => 1,
     };
+    let category = match args.category {
+        OptionalArg::Present(category) => match PyClassRef::try_from_object(vm, category) {
+            Ok(category) => category.name.clone(),
+            Err(_) => "UserWarning".to_string(),
+        },
+        OptionalArg::Missing => "UserWarning".to_string(),
+    };
     eprintln!(
-        "Warning: {} , category: {:?}, level: {}",
+        "Warning: {} , category: {}, level: {}",
         args.message.as_str(),
-        args.category,
+        category,
         level
     )
 }
@@ -1,19 +1,29 @@
 use crate::function::OptionalArg;
-use crate::obj::objbytes::PyBytesRef;
-use crate::pyobject::{ItemProtocol, PyObjectRef, PyResult};
+use crate::obj::objbyteinner::ArgBytesLike;
+use crate::obj::objbytes::PyBytes;
+use crate::obj::objint::PyIntRef;
+use crate::obj::objtype::PyClassRef;
+use crate::pyobject::{PyClassImpl, PyObjectRef, PyResult, PyValue};
 use crate::types::create_type;
 use crate::vm::VirtualMachine;
 
 use adler32::RollingAdler32 as Adler32;
 use crc32fast::Hasher as Crc32;
-use flate2::{write::ZlibEncoder, Compression, Decompress, FlushDecompress, Status};
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
 use libz_sys as libz;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::ToPrimitive;
 
-use std::io::Write;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::mem;
 
 // copied from zlibmodule.c (commit 530f506ac91338)
 const MAX_WBITS: u8 = 15;
 const DEF_BUF_SIZE: usize = 16 * 1024;
+const DEF_MEM_LEVEL: u8 = 8;
+const DEFLATED: i32 = 8;
 
 pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
     let ctx = &vm.ctx;
@@ -29,96 +39,689 @@ pub fn make_module(vm: &VirtualMachine) -> PyObjectRef {
         "adler32" => ctx.new_rustfunc(zlib_adler32),
         "compress" => ctx.new_rustfunc(zlib_compress),
         "decompress" => ctx.new_rustfunc(zlib_decompress),
+        "compressobj" => ctx.new_rustfunc(zlib_compressobj),
+        "decompressobj" => ctx.new_rustfunc(zlib_decompressobj),
+        "Compress" => PyCompress::make_class(ctx),
+        "Decompress" => PyDecompress::make_class(ctx),
         "error" => zlib_error,
         "Z_DEFAULT_COMPRESSION" => ctx.new_int(libz::Z_DEFAULT_COMPRESSION),
         "Z_NO_COMPRESSION" => ctx.new_int(libz::Z_NO_COMPRESSION),
         "Z_BEST_SPEED" => ctx.new_int(libz::Z_BEST_SPEED),
         "Z_BEST_COMPRESSION" => ctx.new_int(libz::Z_BEST_COMPRESSION),
+        "Z_DEFAULT_STRATEGY" => ctx.new_int(libz::Z_DEFAULT_STRATEGY),
+        "Z_FILTERED" => ctx.new_int(libz::Z_FILTERED),
+        "Z_HUFFMAN_ONLY" => ctx.new_int(libz::Z_HUFFMAN_ONLY),
+        "Z_RLE" => ctx.new_int(libz::Z_RLE),
+        "Z_FIXED" => ctx.new_int(libz::Z_FIXED),
+        "Z_NO_FLUSH" => ctx.new_int(libz::Z_NO_FLUSH),
+        "Z_PARTIAL_FLUSH" => ctx.new_int(libz::Z_PARTIAL_FLUSH),
+        "Z_SYNC_FLUSH" => ctx.new_int(libz::Z_SYNC_FLUSH),
+        "Z_FULL_FLUSH" => ctx.new_int(libz::Z_FULL_FLUSH),
+        "Z_FINISH" => ctx.new_int(libz::Z_FINISH),
+        "DEFLATED" => ctx.new_int(DEFLATED),
+        "DEF_MEM_LEVEL" => ctx.new_int(DEF_MEM_LEVEL),
         "DEF_BUF_SIZE" => ctx.new_int(DEF_BUF_SIZE),
         "MAX_WBITS" => ctx.new_int(MAX_WBITS),
     })
 }
 
+/// The starting value of a checksum, which like in CPython can be any int: only its lowest 32
+/// bits are used.
+fn checksum_start(value: OptionalArg<PyIntRef>, default: u32) -> u32 {
+    match value {
+        OptionalArg::Present(value) => value
+            .as_bigint()
+            .mod_floor(&BigInt::from(1u64 << 32))
+            .to_u32()
+            .unwrap(),
+        OptionalArg::Missing => default,
+    }
+}
+
 /// Compute an Adler-32 checksum of data.
-fn zlib_adler32(data: PyBytesRef, begin_state: OptionalArg<i32>, vm: &VirtualMachine) -> PyResult {
-    let data = data.get_value();
+fn zlib_adler32(
+    data: ArgBytesLike,
+    begin_state: OptionalArg<PyIntRef>,
+    _vm: &VirtualMachine,
+) -> u32 {
+    let mut hasher = Adler32::from_value(checksum_start(begin_state, 1));
+    data.with_ref(|bytes| hasher.update_buffer(bytes));
+    hasher.hash()
+}
+
+/// Compute a CRC-32 checksum of data.
+fn zlib_crc32(data: ArgBytesLike, begin_state: OptionalArg<PyIntRef>, _vm: &VirtualMachine) -> u32 {
+    let mut hasher = Crc32::new_with_initial(checksum_start(begin_state, 0));
+    data.with_ref(|bytes| hasher.update(bytes));
+    hasher.finalize()
+}
 
-    let begin_state = begin_state.unwrap_or(1);
+#[derive(FromArgs)]
+struct CompressArgs {
+    #[pyarg(positional_or_keyword)]
+    data: ArgBytesLike,
+    #[pyarg(positional_or_keyword, default = "libz::Z_DEFAULT_COMPRESSION")]
+    level: i32,
+    #[pyarg(positional_or_keyword, default = "MAX_WBITS as i8")]
+    wbits: i8,
+}
 
-    let mut hasher = Adler32::from_value(begin_state as u32);
-    hasher.update_buffer(data);
+/// Returns a bytes object containing compressed data.
+fn zlib_compress(args: CompressArgs, vm: &VirtualMachine) -> PyResult {
+    let level =
+        compression_level(args.level).ok_or_else(|| zlib_error("Bad compression level", vm))?;
+    let (header, window_bits) = Header::for_compression(args.wbits)
+        .ok_or_else(|| zlib_error("Bad compression wbits", vm))?;
 
-    let checksum: u32 = hasher.hash();
+    let mut compressor = Compressor::new(level, header, window_bits);
+    let mut compressed = args.data.with_ref(|bytes| compressor.compress(bytes));
+    compressed.extend(compressor.flush(FlushCompress::Finish));
+    Ok(vm.ctx.new_bytes(compressed))
+}
 
-    Ok(vm.new_int(checksum))
+#[derive(FromArgs)]
+struct DecompressArgs {
+    #[pyarg(positional_or_keyword)]
+    data: ArgBytesLike,
+    #[pyarg(positional_or_keyword, default = "MAX_WBITS as i8")]
+    wbits: i8,
+    // The output buffer grows as needed, so its initial size is only a hint.
+    #[pyarg(positional_or_keyword, default = "DEF_BUF_SIZE")]
+    #[allow(dead_code)]
+    bufsize: usize,
 }
 
-/// Compute a CRC-32 checksum of data.
-fn zlib_crc32(data: PyBytesRef, begin_state: OptionalArg<i32>, vm: &VirtualMachine) -> PyResult {
-    let data = data.get_value();
+/// Returns a bytes object containing the uncompressed data.
+fn zlib_decompress(args: DecompressArgs, vm: &VirtualMachine) -> PyResult {
+    let (header, window_bits) = Header::for_decompression(args.wbits).ok_or_else(|| {
+        zlib_error(
+            "Error -2 while preparing to decompress data: inconsistent stream state",
+            vm,
+        )
+    })?;
 
-    let begin_state = begin_state.unwrap_or(0);
+    let mut decompressor = Decompressor::new(header, window_bits);
+    let decompressed = args
+        .data
+        .with_ref(|bytes| decompressor.decompress(bytes, None))
+        .map_err(|message| zlib_error(&message, vm))?;
+    if !decompressor.eof() {
+        return Err(zlib_error(
+            "Error -5 while decompressing data: incomplete or truncated stream",
+            vm,
+        ));
+    }
+    Ok(vm.ctx.new_bytes(decompressed.output))
+}
 
-    let mut hasher = Crc32::new_with_initial(begin_state as u32);
-    hasher.update(data);
+#[derive(FromArgs)]
+#[allow(non_snake_case)]
+struct CompressobjArgs {
+    #[pyarg(positional_or_keyword, default = "libz::Z_DEFAULT_COMPRESSION")]
+    level: i32,
+    #[pyarg(positional_or_keyword, default = "DEFLATED")]
+    method: i32,
+    #[pyarg(positional_or_keyword, default = "MAX_WBITS as i8")]
+    wbits: i8,
+    // The memory level and strategy aren't available through flate2, so zlib's defaults are
+    // used for them.
+    #[pyarg(positional_or_keyword, default = "DEF_MEM_LEVEL")]
+    memLevel: u8,
+    #[pyarg(positional_or_keyword, default = "libz::Z_DEFAULT_STRATEGY")]
+    #[allow(dead_code)]
+    strategy: i32,
+}
 
-    let checksum: u32 = hasher.finalize();
+/// Return a compressor object.
+fn zlib_compressobj(args: CompressobjArgs, vm: &VirtualMachine) -> PyResult<PyCompress> {
+    let invalid = || vm.new_value_error("Invalid initialization option".to_string());
+    let level = compression_level(args.level).ok_or_else(invalid)?;
+    let (header, window_bits) = Header::for_compression(args.wbits).ok_or_else(invalid)?;
+    if args.method != DEFLATED || args.memLevel < 1 || args.memLevel > 9 {
+        return Err(invalid());
+    }
+    Ok(PyCompress {
+        compressor: RefCell::new(Some(Compressor::new(level, header, window_bits))),
+    })
+}
 
-    Ok(vm.new_int(checksum))
+#[derive(FromArgs)]
+struct DecompressobjArgs {
+    #[pyarg(positional_or_keyword, default = "MAX_WBITS as i8")]
+    wbits: i8,
 }
 
-/// Returns a bytes object containing compressed data.
-fn zlib_compress(data: PyBytesRef, level: OptionalArg<i32>, vm: &VirtualMachine) -> PyResult {
-    let input_bytes = data.get_value();
+/// Return a decompressor object.
+fn zlib_decompressobj(args: DecompressobjArgs, vm: &VirtualMachine) -> PyResult<PyDecompress> {
+    let (header, window_bits) = Header::for_decompression(args.wbits)
+        .ok_or_else(|| vm.new_value_error("Invalid initialization option".to_string()))?;
+    Ok(PyDecompress {
+        decompressor: RefCell::new(Decompressor::new(header, window_bits)),
+        unused_data: RefCell::new(vec![]),
+        unconsumed_tail: RefCell::new(vec![]),
+        eof: Cell::new(false),
+    })
+}
 
-    let level = level.unwrap_or(libz::Z_DEFAULT_COMPRESSION);
+fn zlib_error(message: &str, vm: &VirtualMachine) -> PyObjectRef {
+    vm.new_exception(vm.class("zlib", "error"), message.to_string())
+}
 
-    let compression = match level {
+fn compression_level(level: i32) -> Option<Compression> {
+    match level {
         valid_level @ libz::Z_NO_COMPRESSION..=libz::Z_BEST_COMPRESSION => {
-            Compression::new(valid_level as u32)
+            Some(Compression::new(valid_level as u32))
         }
-        libz::Z_DEFAULT_COMPRESSION => Compression::default(),
-        _ => return Err(zlib_error("Bad compression level", vm)),
-    };
+        libz::Z_DEFAULT_COMPRESSION => Some(Compression::default()),
+        _ => None,
+    }
+}
+
+/// What a `wbits` argument says surrounds the deflate stream.
+#[derive(Clone, Copy)]
+enum Header {
+    Zlib,
+    Raw,
+    Gzip,
+    /// Either a zlib or a gzip header, when decompressing.
+    Automatic,
+}
+
+impl Header {
+    /// The header and the window size for compressing with `wbits`, if it is valid.
+    fn for_compression(wbits: i8) -> Option<(Header, u8)> {
+        let (header, window_bits) = match wbits {
+            // zlib treats a window of 256 bytes as one of 512 when it has a zlib header.
+            8..=15 => (Header::Zlib, wbits.max(9)),
+            -15..=-9 => (Header::Raw, -wbits),
+            25..=31 => (Header::Gzip, wbits - 16),
+            _ => return None,
+        };
+        Some((header, window_bits as u8))
+    }
 
-    let mut encoder = ZlibEncoder::new(Vec::new(), compression);
-    encoder.write_all(input_bytes).unwrap();
-    let encoded_bytes = encoder.finish().unwrap();
+    /// The header and the window size for decompressing with `wbits`, if it is valid. A zero
+    /// window size takes the one from the zlib header, which the largest window can always
+    /// decompress.
+    fn for_decompression(wbits: i8) -> Option<(Header, u8)> {
+        let (header, window_bits) = match wbits {
+            0 => (Header::Zlib, MAX_WBITS as i8),
+            8..=15 => (Header::Zlib, wbits),
+            -15..=-8 => (Header::Raw, -wbits),
+            24..=31 => (Header::Gzip, wbits - 16),
+            32 => (Header::Automatic, MAX_WBITS as i8),
+            40..=47 => (Header::Automatic, wbits - 32),
+            _ => return None,
+        };
+        // zlib treats a window of 256 bytes as one of 512.
+        Some((header, window_bits.max(9) as u8))
+    }
+}
 
-    Ok(vm.ctx.new_bytes(encoded_bytes))
+/// Writes a gzip header and trailer around a raw deflate stream, since flate2 can't have zlib
+/// do it.
+struct GzipWriter {
+    header_written: bool,
+    crc: Crc32,
+    size: u32,
 }
 
-/// Returns a bytes object containing the uncompressed data.
-fn zlib_decompress(
-    data: PyBytesRef,
-    wbits: OptionalArg<u8>,
-    bufsize: OptionalArg<usize>,
-    vm: &VirtualMachine,
-) -> PyResult {
-    let encoded_bytes = data.get_value();
+/// A deflate stream with the header that it was asked for.
+struct Compressor {
+    deflate: Compress,
+    gzip: Option<GzipWriter>,
+}
+
+impl Compressor {
+    fn new(level: Compression, header: Header, window_bits: u8) -> Self {
+        let zlib_header = match header {
+            Header::Zlib => true,
+            _ => false,
+        };
+        let gzip = match header {
+            Header::Gzip => Some(GzipWriter {
+                header_written: false,
+                crc: Crc32::new(),
+                size: 0,
+            }),
+            _ => None,
+        };
+        Compressor {
+            deflate: Compress::new_with_window_bits(level, zlib_header, window_bits),
+            gzip,
+        }
+    }
+
+    fn gzip_header(&mut self, output: &mut Vec<u8>) {
+        if let Some(gzip) = &mut self.gzip {
+            if !gzip.header_written {
+                // No file name or modification time, and the OS byte that zlib writes on Unix.
+                output.extend_from_slice(&[0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 3]);
+                gzip.header_written = true;
+            }
+        }
+    }
+
+    fn compress(&mut self, data: &[u8]) -> Vec<u8> {
+        let mut output = vec![];
+        self.gzip_header(&mut output);
+        if let Some(gzip) = &mut self.gzip {
+            gzip.crc.update(data);
+            gzip.size = gzip.size.wrapping_add(data.len() as u32);
+        }
+        self.run(data, FlushCompress::None, &mut output);
+        output
+    }
 
-    let wbits = wbits.unwrap_or(MAX_WBITS);
-    let bufsize = bufsize.unwrap_or(DEF_BUF_SIZE);
+    fn flush(&mut self, flush: FlushCompress) -> Vec<u8> {
+        let mut output = vec![];
+        self.gzip_header(&mut output);
+        self.run(&[], flush, &mut output);
+        if let (FlushCompress::Finish, Some(gzip)) = (flush, &self.gzip) {
+            output.extend_from_slice(&gzip.crc.clone().finalize().to_le_bytes());
+            output.extend_from_slice(&gzip.size.to_le_bytes());
+        }
+        output
+    }
 
-    let mut decompressor = Decompress::new_with_window_bits(true, wbits);
-    let mut decoded_bytes = Vec::with_capacity(bufsize);
+    /// Feed all of `input` to the deflate stream, and with a flush until it is done.
+    fn run(&mut self, input: &[u8], flush: FlushCompress, output: &mut Vec<u8>) {
+        let start_in = self.deflate.total_in();
+        loop {
+            let consumed = (self.deflate.total_in() - start_in) as usize;
+            let start_out = self.deflate.total_out();
+            let mut buf = vec![0; DEF_BUF_SIZE];
+            let status = self
+                .deflate
+                .compress(&input[consumed..], &mut buf, flush)
+                .expect("deflate can't fail with valid arguments");
+            let produced = (self.deflate.total_out() - start_out) as usize;
+            output.extend_from_slice(&buf[..produced]);
 
-    match decompressor.decompress_vec(&encoded_bytes, &mut decoded_bytes, FlushDecompress::Finish) {
-        Ok(Status::BufError) => Err(zlib_error("inconsistent or truncated state", vm)),
-        Err(_) => Err(zlib_error("invalid input data", vm)),
-        _ => Ok(vm.ctx.new_bytes(decoded_bytes)),
+            let consumed_all = self.deflate.total_in() - start_in == input.len() as u64;
+            let done = match (flush, status) {
+                (_, Status::StreamEnd) => true,
+                (FlushCompress::Finish, _) => false,
+                // There is nothing more to write when the output doesn't fill the buffer.
+                _ => consumed_all && produced < buf.len(),
+            };
+            if done {
+                break;
+            }
+        }
     }
 }
 
-fn zlib_error(message: &str, vm: &VirtualMachine) -> PyObjectRef {
-    let module = vm
-        .get_attribute(vm.sys_module.clone(), "modules")
-        .unwrap()
-        .get_item("zlib", vm)
-        .unwrap();
+enum Stage {
+    /// Waiting for the first byte to tell a zlib header from a gzip one.
+    Detect,
+    GzipHeader,
+    Deflate,
+    GzipTrailer,
+    Done,
+}
+
+/// A deflate stream with its header, which can be fed in pieces.
+struct Decompressor {
+    inflate: Decompress,
+    window_bits: u8,
+    stage: Stage,
+    /// The CRC-32 and size of the output, for checking the trailer of a gzip stream.
+    gzip: Option<(Crc32, u32)>,
+    /// Input that was taken, but that can't be used until more of it arrives, like the start of
+    /// a gzip header.
+    pending: Vec<u8>,
+}
 
-    let zlib_error = vm.get_attribute(module, "error").unwrap();
-    let zlib_error = zlib_error.downcast().unwrap();
+/// The result of feeding input to a `Decompressor`.
+struct Decompressed {
+    output: Vec<u8>,
+    /// The input that is left because the output reached its maximum length.
+    unconsumed: Vec<u8>,
+    /// The input that follows the end of the stream.
+    unused: Vec<u8>,
+}
+
+impl Decompressor {
+    fn new(header: Header, window_bits: u8) -> Self {
+        let stage = match header {
+            Header::Zlib | Header::Raw => Stage::Deflate,
+            Header::Gzip => Stage::GzipHeader,
+            Header::Automatic => Stage::Detect,
+        };
+        let zlib_header = match header {
+            Header::Zlib => true,
+            _ => false,
+        };
+        Decompressor {
+            inflate: Decompress::new_with_window_bits(zlib_header, window_bits),
+            window_bits,
+            stage,
+            gzip: None,
+            pending: vec![],
+        }
+    }
+
+    fn eof(&self) -> bool {
+        match self.stage {
+            Stage::Done => true,
+            _ => false,
+        }
+    }
 
-    vm.new_exception(zlib_error, message.to_string())
+    /// Decompress `input` until the output is `max_length` bytes long, or the end of the stream.
+    fn decompress(
+        &mut self,
+        input: &[u8],
+        max_length: Option<usize>,
+    ) -> Result<Decompressed, String> {
+        let mut data = mem::replace(&mut self.pending, vec![]);
+        data.extend_from_slice(input);
+        let mut pos = 0;
+        let mut output = vec![];
+        loop {
+            match self.stage {
+                Stage::Detect => match data.get(pos) {
+                    Some(0x1f) => self.stage = Stage::GzipHeader,
+                    Some(_) => {
+                        self.inflate = Decompress::new_with_window_bits(true, self.window_bits);
+                        self.stage = Stage::Deflate;
+                    }
+                    None => break,
+                },
+                Stage::GzipHeader => match gzip_header_len(&data[pos..])? {
+                    Some(len) => {
+                        pos += len;
+                        self.gzip = Some((Crc32::new(), 0));
+                        self.stage = Stage::Deflate;
+                    }
+                    None => break,
+                },
+                Stage::Deflate => {
+                    let start = output.len();
+                    let limit = max_length.map(|max_length| max_length - start);
+                    let (consumed, end) = self.inflate(&data[pos..], &mut output, limit)?;
+                    pos += consumed;
+                    if let Some((crc, size)) = &mut self.gzip {
+                        crc.update(&output[start..]);
+                        *size = size.wrapping_add((output.len() - start) as u32);
+                    }
+                    if !end {
+                        break;
+                    }
+                    self.stage = match self.gzip {
+                        Some(_) => Stage::GzipTrailer,
+                        None => Stage::Done,
+                    };
+                }
+                Stage::GzipTrailer => {
+                    let trailer = match data.get(pos..pos + 8) {
+                        Some(trailer) => trailer,
+                        None => break,
+                    };
+                    let (crc, size) = self.gzip.take().unwrap();
+                    if trailer[..4] != crc.finalize().to_le_bytes() {
+                        return Err(
+                            "Error -3 while decompressing data: incorrect data check".to_string()
+                        );
+                    }
+                    if trailer[4..] != size.to_le_bytes() {
+                        return Err(
+                            "Error -3 while decompressing data: incorrect length check".to_string()
+                        );
+                    }
+                    pos += 8;
+                    self.stage = Stage::Done;
+                }
+                Stage::Done => break,
+            }
+        }
+
+        let rest = data.split_off(pos);
+        let (unconsumed, unused) = match self.stage {
+            Stage::Done => (vec![], rest),
+            Stage::Deflate => (rest, vec![]),
+            _ => {
+                self.pending = rest;
+                (vec![], vec![])
+            }
+        };
+        Ok(Decompressed {
+            output,
+            unconsumed,
+            unused,
+        })
+    }
+
+    /// Inflate `input` into `output` until `limit` bytes were added to it, returning how much
+    /// input was used and whether the deflate stream ended.
+    fn inflate(
+        &mut self,
+        input: &[u8],
+        output: &mut Vec<u8>,
+        limit: Option<usize>,
+    ) -> Result<(usize, bool), String> {
+        let start_in = self.inflate.total_in();
+        let start_len = output.len();
+        loop {
+            let consumed = (self.inflate.total_in() - start_in) as usize;
+            let room = match limit {
+                Some(limit) => limit - (output.len() - start_len),
+                None => DEF_BUF_SIZE,
+            };
+            if room == 0 {
+                return Ok((consumed, false));
+            }
+            let mut buf = vec![0; room.min(DEF_BUF_SIZE)];
+            let start_out = self.inflate.total_out();
+            let status = self
+                .inflate
+                .decompress(&input[consumed..], &mut buf, FlushDecompress::None)
+                .map_err(|_| "Error -3 while decompressing data: invalid input data".to_string())?;
+            let produced = (self.inflate.total_out() - start_out) as usize;
+            output.extend_from_slice(&buf[..produced]);
+
+            let consumed = (self.inflate.total_in() - start_in) as usize;
+            match status {
+                Status::StreamEnd => return Ok((consumed, true)),
+                // All the input went in and all the output it gave came out.
+                _ if consumed == input.len() && produced < buf.len() => {
+                    return Ok((consumed, false))
+                }
+                Status::BufError if produced == 0 => return Ok((consumed, false)),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The length of the gzip header at the start of `data`, or `None` when `data` ends before
+/// the header does.
+fn gzip_header_len(data: &[u8]) -> Result<Option<usize>, String> {
+    const FHCRC: u8 = 2;
+    const FEXTRA: u8 = 4;
+    const FNAME: u8 = 8;
+    const FCOMMENT: u8 = 16;
+
+    let error = |message: &str| Err(format!("Error -3 while decompressing data: {}", message));
+    if data.get(0).map_or(false, |&byte| byte != 0x1f)
+        || data.get(1).map_or(false, |&byte| byte != 0x8b)
+    {
+        return error("incorrect header check");
+    }
+    if data.get(2).map_or(false, |&byte| byte != 8) {
+        return error("unknown compression method");
+    }
+    if data.len() < 10 {
+        return Ok(None);
+    }
+
+    let flags = data[3];
+    let mut len = 10;
+    if flags & FEXTRA != 0 {
+        match data.get(len..len + 2) {
+            Some(extra_len) => len += 2 + (extra_len[0] as usize | (extra_len[1] as usize) << 8),
+            None => return Ok(None),
+        }
+    }
+    for &flag in &[FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            // A zero-terminated string.
+            match data
+                .get(len..)
+                .and_then(|rest| rest.iter().position(|&b| b == 0))
+            {
+                Some(end) => len += end + 1,
+                None => return Ok(None),
+            }
+        }
+    }
+    if flags & FHCRC != 0 {
+        len += 2;
+    }
+    Ok(if len <= data.len() { Some(len) } else { None })
+}
+
+#[pyclass(name = "Compress")]
+struct PyCompress {
+    /// `None` once the stream was finished.
+    compressor: RefCell<Option<Compressor>>,
+}
+
+impl fmt::Debug for PyCompress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "zlib.Compress")
+    }
+}
+
+impl PyValue for PyCompress {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("zlib", "Compress")
+    }
+}
+
+#[pyimpl]
+impl PyCompress {
+    #[pymethod(name = "compress")]
+    fn compress(&self, data: ArgBytesLike, vm: &VirtualMachine) -> PyResult<PyBytes> {
+        match &mut *self.compressor.borrow_mut() {
+            Some(compressor) => Ok(PyBytes::new(
+                data.with_ref(|bytes| compressor.compress(bytes)),
+            )),
+            None => Err(zlib_error(
+                "Error -2 while compressing data: inconsistent stream state",
+                vm,
+            )),
+        }
+    }
+
+    #[pymethod(name = "flush")]
+    fn flush(&self, mode: OptionalArg<i32>, vm: &VirtualMachine) -> PyResult<PyBytes> {
+        let flush = match mode.unwrap_or(libz::Z_FINISH) {
+            libz::Z_NO_FLUSH => return Ok(PyBytes::new(vec![])),
+            libz::Z_PARTIAL_FLUSH => FlushCompress::Partial,
+            libz::Z_SYNC_FLUSH => FlushCompress::Sync,
+            libz::Z_FULL_FLUSH => FlushCompress::Full,
+            libz::Z_FINISH => FlushCompress::Finish,
+            _ => {
+                return Err(zlib_error(
+                    "Error -2 while flushing: inconsistent stream state",
+                    vm,
+                ))
+            }
+        };
+        let mut compressor = self.compressor.borrow_mut();
+        let output = match &mut *compressor {
+            Some(compressor) => compressor.flush(flush),
+            None => {
+                return Err(zlib_error(
+                    "Error -2 while flushing: inconsistent stream state",
+                    vm,
+                ))
+            }
+        };
+        if let FlushCompress::Finish = flush {
+            *compressor = None;
+        }
+        Ok(PyBytes::new(output))
+    }
+}
+
+#[pyclass(name = "Decompress")]
+struct PyDecompress {
+    decompressor: RefCell<Decompressor>,
+    unused_data: RefCell<Vec<u8>>,
+    unconsumed_tail: RefCell<Vec<u8>>,
+    eof: Cell<bool>,
+}
+
+impl fmt::Debug for PyDecompress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "zlib.Decompress")
+    }
+}
+
+impl PyValue for PyDecompress {
+    fn class(vm: &VirtualMachine) -> PyClassRef {
+        vm.class("zlib", "Decompress")
+    }
+}
+
+#[pyimpl]
+impl PyDecompress {
+    fn feed(
+        &self,
+        data: &[u8],
+        max_length: Option<usize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyBytes> {
+        let decompressed = self
+            .decompressor
+            .borrow_mut()
+            .decompress(data, max_length)
+            .map_err(|message| zlib_error(&message, vm))?;
+        self.unused_data.borrow_mut().extend(decompressed.unused);
+        self.unconsumed_tail.replace(decompressed.unconsumed);
+        self.eof.set(self.decompressor.borrow().eof());
+        Ok(PyBytes::new(decompressed.output))
+    }
+
+    #[pymethod(name = "decompress")]
+    fn decompress(
+        &self,
+        data: ArgBytesLike,
+        max_length: OptionalArg<isize>,
+        vm: &VirtualMachine,
+    ) -> PyResult<PyBytes> {
+        let max_length = match max_length.unwrap_or(0) {
+            0 => None,
+            max_length if max_length > 0 => Some(max_length as usize),
+            _ => {
+                return Err(vm.new_value_error("max_length must be non-negative".to_string()));
+            }
+        };
+        data.with_ref(|bytes| self.feed(bytes, max_length, vm))
+    }
+
+    /// Decompress what is left of the input. The buffer size is only a hint to zlib.
+    #[pymethod(name = "flush")]
+    fn flush(&self, _length: OptionalArg<usize>, vm: &VirtualMachine) -> PyResult<PyBytes> {
+        let tail = self.unconsumed_tail.replace(vec![]);
+        self.feed(&tail, None, vm)
+    }
+
+    #[pyproperty(name = "unused_data")]
+    fn unused_data(&self, _vm: &VirtualMachine) -> PyBytes {
+        PyBytes::new(self.unused_data.borrow().clone())
+    }
+
+    #[pyproperty(name = "unconsumed_tail")]
+    fn unconsumed_tail(&self, _vm: &VirtualMachine) -> PyBytes {
+        PyBytes::new(self.unconsumed_tail.borrow().clone())
+    }
+
+    #[pyproperty(name = "eof")]
+    fn eof(&self, _vm: &VirtualMachine) -> bool {
+        self.eof.get()
+    }
 }
@@ -1,11 +1,16 @@
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::{env, mem};
 
+use crate::exceptions;
 use crate::frame::FrameRef;
-use crate::function::OptionalArg;
+use crate::function::{OptionalArg, PyFuncArgs};
 use crate::obj::objstr::PyStringRef;
+use crate::obj::objtype;
 use crate::pyobject::{
-    IntoPyObject, ItemProtocol, PyClassImpl, PyContext, PyObjectRef, PyResult, TypeProtocol,
+    IdProtocol, IntoPyObject, ItemProtocol, PyClassImpl, PyObjectRef, PyResult, TryFromObject,
+    TypeProtocol,
 };
 use crate::version;
 use crate::vm::{PySettings, VirtualMachine};
@@ -24,22 +29,40 @@ fn argv(vm: &VirtualMachine) -> PyObjectRef {
     )
 }
 
-fn executable(ctx: &PyContext) -> PyObjectRef {
-    if let Some(arg) = env::args().next() {
-        ctx.new_str(arg)
-    } else {
-        ctx.none()
-    }
+/// The absolute path of the interpreter binary, or `None` when it can't be found, e.g. when the
+/// interpreter is embedded in a page.
+fn executable_path() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()
+        .or_else(|| env::args_os().next().map(PathBuf::from))
 }
 
-fn getframe(offset: OptionalArg<usize>, vm: &VirtualMachine) -> PyResult<FrameRef> {
-    let offset = offset.into_option().unwrap_or(0);
-    if offset > vm.frames.borrow().len() - 1 {
-        return Err(vm.new_value_error("call stack is not deep enough".to_string()));
-    }
-    let idx = vm.frames.borrow().len() - offset - 1;
-    let frame = &vm.frames.borrow()[idx];
-    Ok(frame.clone())
+/// Derive the installation prefix from the location of the interpreter binary. A RustPython
+/// installation is laid out like CPython's, with the binary at `<prefix>/bin/rustpython` and the
+/// library below `<prefix>/lib`; a binary that isn't in a `bin` directory, like the one in
+/// `target/release`, uses its own directory as the prefix.
+fn default_prefix(executable: Option<&Path>) -> String {
+    let dir = match executable.and_then(Path::parent) {
+        Some(dir) if dir.is_absolute() => dir,
+        _ => return "/usr/local".to_string(),
+    };
+    let prefix = match dir.parent() {
+        Some(parent) if dir.file_name() == Some("bin".as_ref()) => parent,
+        _ => dir,
+    };
+    prefix.to_string_lossy().into_owned()
+}
+
+/// The frame `depth` calls below the caller's. Like in CPython, a negative depth is the
+/// caller's frame.
+fn getframe(depth: OptionalArg<isize>, vm: &VirtualMachine) -> PyResult<FrameRef> {
+    let depth = depth.unwrap_or(0).max(0) as usize;
+    let frames = vm.frames.borrow();
+    frames
+        .len()
+        .checked_sub(depth + 1)
+        .map(|index| frames[index].clone())
+        .ok_or_else(|| vm.new_value_error("call stack is not deep enough".to_string()))
 }
 
 /// sys.flags
@@ -101,9 +124,10 @@ fn sys_getrefcount(obj: PyObjectRef, _vm: &VirtualMachine) -> usize {
     Rc::strong_count(&obj)
 }
 
-fn sys_getsizeof(obj: PyObjectRef, _vm: &VirtualMachine) -> usize {
+fn sys_getsizeof(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult<usize> {
     // TODO: implement default optional argument.
-    mem::size_of_val(&obj)
+    let size = vm.call_method(&obj, "__sizeof__", vec![])?;
+    usize::try_from_object(vm, size)
 }
 
 fn sys_getfilesystemencoding(_vm: &VirtualMachine) -> String {
@@ -127,7 +151,7 @@ fn sys_getprofile(vm: &VirtualMachine) -> PyObjectRef {
 
 fn sys_setprofile(profilefunc: PyObjectRef, vm: &VirtualMachine) {
     vm.profile_func.replace(profilefunc);
-    update_use_tracing(vm);
+    vm.update_use_tracing();
 }
 
 fn sys_gettrace(vm: &VirtualMachine) -> PyObjectRef {
@@ -136,25 +160,24 @@ fn sys_gettrace(vm: &VirtualMachine) -> PyObjectRef {
 
 fn sys_settrace(tracefunc: PyObjectRef, vm: &VirtualMachine) -> PyObjectRef {
     vm.trace_func.replace(tracefunc);
-    update_use_tracing(vm);
+    vm.update_use_tracing();
     vm.ctx.none()
 }
 
-fn update_use_tracing(vm: &VirtualMachine) {
-    let trace_is_none = vm.is_none(&vm.trace_func.borrow());
-    let profile_is_none = vm.is_none(&vm.profile_func.borrow());
-    let tracing = !(trace_is_none && profile_is_none);
-    vm.use_tracing.replace(tracing);
-}
-
 fn sys_getrecursionlimit(vm: &VirtualMachine) -> usize {
     vm.recursion_limit.get()
 }
 
-fn sys_setrecursionlimit(recursion_limit: usize, vm: &VirtualMachine) -> PyResult {
-    let recursion_depth = vm.frames.borrow().len();
+fn sys_setrecursionlimit(recursion_limit: i32, vm: &VirtualMachine) -> PyResult {
+    if recursion_limit < 1 {
+        return Err(
+            vm.new_value_error("recursion limit must be greater or equal than 1".to_string())
+        );
+    }
+    let recursion_limit = recursion_limit as usize;
+    let recursion_depth = vm.recursion_depth();
 
-    if recursion_limit > recursion_depth + 1 {
+    if recursion_limit > recursion_depth {
         vm.recursion_limit.set(recursion_limit);
         Ok(vm.ctx.none())
     } else {
@@ -166,8 +189,20 @@ fn sys_setrecursionlimit(recursion_limit: usize, vm: &VirtualMachine) -> PyResul
 }
 
 // TODO implement string interning, this will be key for performance
-fn sys_intern(value: PyStringRef, _vm: &VirtualMachine) -> PyStringRef {
-    value
+fn sys_intern(value: PyStringRef, vm: &VirtualMachine) -> PyResult<PyStringRef> {
+    if !value.class().is(&vm.ctx.str_type()) {
+        return Err(vm.new_type_error(format!("can't intern {}", value.class().name)));
+    }
+    Ok(vm.ctx.intern_string_ref(value))
+}
+
+/// The names of the frozen modules, sorted, for `sys.frozen_module_names`. The VM replaces the
+/// tuple whenever frozen modules are registered.
+pub(crate) fn frozen_module_names(vm: &VirtualMachine) -> PyObjectRef {
+    let mut names: Vec<String> = vm.frozen.borrow().keys().cloned().collect();
+    names.sort();
+    vm.ctx
+        .new_tuple(names.into_iter().map(|name| vm.new_str(name)).collect())
 }
 
 fn sys_exc_info(vm: &VirtualMachine) -> PyResult {
@@ -175,7 +210,7 @@ fn sys_exc_info(vm: &VirtualMachine) -> PyResult {
         Some(exception) => vec![
             exception.class().into_object(),
             exception.clone(),
-            vm.get_none(),
+            vm.get_attribute(exception, "__traceback__")?,
         ],
         None => vec![vm.get_none(), vm.get_none(), vm.get_none()],
     }))
@@ -189,6 +224,86 @@ fn sys_git_info(vm: &VirtualMachine) -> PyObjectRef {
     ])
 }
 
+/// The default `sys.breakpointhook`: call the function named by `$PYTHONBREAKPOINT`,
+/// `pdb.set_trace` when it's unset, or do nothing when it's "0".
+fn sys_breakpointhook(args: PyFuncArgs, vm: &VirtualMachine) -> PyResult {
+    let env_var = if vm.settings.ignore_environment {
+        None
+    } else {
+        env::var("PYTHONBREAKPOINT").ok()
+    };
+    let hook_name = match env_var {
+        Some(ref name) if !name.is_empty() => name.as_str(),
+        _ => "pdb.set_trace",
+    };
+    if hook_name == "0" {
+        return Ok(vm.get_none());
+    }
+
+    let (module_name, attr_name) = match hook_name.rfind('.') {
+        Some(index) => (&hook_name[..index], &hook_name[index + 1..]),
+        None => ("builtins", hook_name),
+    };
+    let hook = vm
+        .import(module_name, &[], 0)
+        .and_then(|module| vm.get_attribute(module, attr_name));
+    match hook {
+        Ok(hook) => vm.invoke(&hook, args),
+        Err(_) => {
+            let message = format!("Ignoring unimportable $PYTHONBREAKPOINT: \"{}\"", hook_name);
+            let warnings = vm
+                .import("warnings", &[], 0)
+                .or_else(|_| vm.import("_warnings", &[], 0))?;
+            let warn = vm.get_attribute(warnings, "warn")?;
+            let category = vm.ctx.exceptions.runtime_warning.clone().into_object();
+            vm.invoke(&warn, vec![vm.new_str(message), category])?;
+            Ok(vm.get_none())
+        }
+    }
+}
+
+/// The default `sys.excepthook`: print the traceback of an uncaught exception, along with those
+/// of the exceptions it was chained to, to stderr.
+fn sys_excepthook(
+    _exc_type: PyObjectRef,
+    exc: PyObjectRef,
+    _exc_tb: PyObjectRef,
+    vm: &VirtualMachine,
+) -> PyResult<()> {
+    if !objtype::isinstance(&exc, &vm.ctx.exceptions.base_exception_type) {
+        return Err(vm.new_type_error(format!(
+            "print_exception(): Exception expected for value, {} found",
+            exc.class().name
+        )));
+    }
+    exceptions::write_exception(io::stderr(), vm, &exc)
+        .map_err(|err| vm.new_os_error(err.to_string()))
+}
+
+/// The default `sys.displayhook`: print the repr of a value other than None to `sys.stdout`, and
+/// keep the value in `builtins._`.
+fn sys_displayhook(obj: PyObjectRef, vm: &VirtualMachine) -> PyResult<()> {
+    if vm.is_none(&obj) {
+        return Ok(());
+    }
+    // Like CPython, clear `_` first, so that the repr doesn't see the previous value there
+    vm.set_attr(&vm.builtins, "_", vm.get_none())?;
+    let repr = vm.to_repr(&obj)?;
+    match vm.get_attribute(vm.sys_module.clone(), "stdout") {
+        Ok(stdout) if !vm.is_none(&stdout) => {
+            vm.call_method(&stdout, "write", vec![repr.into_object()])?;
+            vm.call_method(&stdout, "write", vec![vm.new_str("\n".to_owned())])?;
+        }
+        // Before `site` sets up the standard streams, write where `print` does
+        _ => {
+            let print = vm.get_attribute(vm.builtins.clone(), "print")?;
+            vm.invoke(&print, vec![repr.into_object()])?;
+        }
+    }
+    vm.set_attr(&vm.builtins, "_", obj)?;
+    Ok(())
+}
+
 fn sys_exit(code: OptionalArg<PyObjectRef>, vm: &VirtualMachine) -> PyResult {
     let code = code.unwrap_or_else(|| vm.new_int(0));
     Err(vm.new_exception_obj(vm.ctx.exceptions.system_exit.clone(), vec![code])?)
@@ -276,6 +391,8 @@ last_traceback -- traceback of last uncaught exception
 Static objects:
 
 builtin_module_names -- tuple of module names built into this interpreter
+frozen_module_names -- tuple of the names of the modules frozen into this interpreter,
+  including those registered since it started
 copyright -- copyright notice pertaining to this interpreter
 exec_prefix -- prefix used to find the machine-specific Python library
 executable -- absolute path of the executable binary of the Python interpreter
@@ -295,11 +412,13 @@ version_info -- version information as a named tuple
 __stdin__ -- the original stdin; don't touch!
 __stdout__ -- the original stdout; don't touch!
 __stderr__ -- the original stderr; don't touch!
+__breakpointhook__ -- the original breakpointhook; don't touch!
 __displayhook__ -- the original displayhook; don't touch!
 __excepthook__ -- the original excepthook; don't touch!
 
 Functions:
 
+breakpointhook() -- enter a debugger, by default pdb
 displayhook() -- print an object to the screen, and save it in builtins._
 excepthook() -- print an exception and its traceback to sys.stderr
 exc_info() -- return thread-safe information about the current exception
@@ -327,20 +446,42 @@ settrace() -- set the global debug tracing function
             .collect(),
     );
     let modules = ctx.new_dict();
-
-    let prefix = option_env!("RUSTPYTHON_PREFIX").unwrap_or("/usr/local");
-    let base_prefix = option_env!("RUSTPYTHON_BASEPREFIX").unwrap_or(prefix);
-    let exec_prefix = option_env!("RUSTPYTHON_EXECPREFIX").unwrap_or(prefix);
-    let base_exec_prefix = option_env!("RUSTPYTHON_BASEEXECPREFIX").unwrap_or(exec_prefix);
+    let breakpointhook = ctx.new_rustfunc(sys_breakpointhook);
+    let displayhook = ctx.new_rustfunc(sys_displayhook);
+    let excepthook = ctx.new_rustfunc(sys_excepthook);
+
+    // The prefixes can be fixed at build time, for packagers that install the library apart
+    // from the binary; otherwise they follow the binary around.
+    let executable = executable_path();
+    let prefix = option_env!("RUSTPYTHON_PREFIX")
+        .map(str::to_string)
+        .unwrap_or_else(|| default_prefix(executable.as_ref().map(PathBuf::as_path)));
+    let base_prefix =
+        option_env!("RUSTPYTHON_BASEPREFIX").map_or_else(|| prefix.clone(), str::to_string);
+    let exec_prefix =
+        option_env!("RUSTPYTHON_EXECPREFIX").map_or_else(|| prefix.clone(), str::to_string);
+    let base_exec_prefix = option_env!("RUSTPYTHON_BASEEXECPREFIX")
+        .map_or_else(|| exec_prefix.clone(), str::to_string);
+    let executable = match executable {
+        Some(path) => ctx.new_str(path.to_string_lossy().into_owned()),
+        None => ctx.none(),
+    };
 
     extend_module!(vm, module, {
       "__name__" => ctx.new_str(String::from("sys")),
       "argv" => argv(vm),
+      "breakpointhook" => breakpointhook.clone(),
+      "__breakpointhook__" => breakpointhook,
       "builtin_module_names" => builtin_module_names,
       "byteorder" => ctx.new_str(bytorder),
       "copyright" => ctx.new_str(copyright.to_string()),
-      "executable" => executable(ctx),
+      "displayhook" => displayhook.clone(),
+      "__displayhook__" => displayhook,
+      "excepthook" => excepthook.clone(),
+      "__excepthook__" => excepthook,
+      "executable" => executable,
       "flags" => flags,
+      "frozen_module_names" => frozen_module_names(vm),
       "getrefcount" => ctx.new_rustfunc(sys_getrefcount),
       "getrecursionlimit" => ctx.new_rustfunc(sys_getrecursionlimit),
       "getsizeof" => ctx.new_rustfunc(sys_getsizeof),
@@ -373,10 +514,10 @@ settrace() -- set the global debug tracing function
       "version_info" => version_info,
       "_git" => sys_git_info(vm),
       "exc_info" => ctx.new_rustfunc(sys_exc_info),
-      "prefix" => ctx.new_str(prefix.to_string()),
-      "base_prefix" => ctx.new_str(base_prefix.to_string()),
-      "exec_prefix" => ctx.new_str(exec_prefix.to_string()),
-      "base_exec_prefix" => ctx.new_str(base_exec_prefix.to_string()),
+      "prefix" => ctx.new_str(prefix),
+      "base_prefix" => ctx.new_str(base_prefix),
+      "exec_prefix" => ctx.new_str(exec_prefix),
+      "base_exec_prefix" => ctx.new_str(base_exec_prefix),
       "exit" => ctx.new_rustfunc(sys_exit),
     });
 
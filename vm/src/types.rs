@@ -18,6 +18,7 @@ use crate::obj::objiter;
 use crate::obj::objlist;
 use crate::obj::objmap;
 use crate::obj::objmappingproxy;
+use crate::obj::objmemberdescr;
 use crate::obj::objmemory;
 use crate::obj::objmodule;
 use crate::obj::objnamespace;
@@ -37,7 +38,7 @@ use crate::obj::objweakproxy;
 use crate::obj::objweakref;
 use crate::obj::objzip;
 use crate::pyobject::{PyAttributes, PyContext, PyObject, PyObjectRef};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::mem;
 use std::ptr;
 
@@ -62,6 +63,7 @@ pub struct TypeZoo {
     pub generator_type: PyClassRef,
     pub int_type: PyClassRef,
     pub iter_type: PyClassRef,
+    pub callable_iterator_type: PyClassRef,
     pub complex_type: PyClassRef,
     pub list_type: PyClassRef,
     pub listiterator_type: PyClassRef,
@@ -97,6 +99,7 @@ pub struct TypeZoo {
     pub weakref_type: PyClassRef,
     pub weakproxy_type: PyClassRef,
     pub mappingproxy_type: PyClassRef,
+    pub member_descriptor_type: PyClassRef,
     pub traceback_type: PyClassRef,
     pub object_type: PyClassRef,
 }
@@ -154,6 +157,7 @@ impl TypeZoo {
         let tuple_type = create_type("tuple", &type_type, &object_type);
         let tupleiterator_type = create_type("tuple_iterator", &type_type, &object_type);
         let iter_type = create_type("iter", &type_type, &object_type);
+        let callable_iterator_type = create_type("callable_iterator", &type_type, &object_type);
         let enumerate_type = create_type("enumerate", &type_type, &object_type);
         let filter_type = create_type("filter", &type_type, &object_type);
         let map_type = create_type("map", &type_type, &object_type);
@@ -165,6 +169,7 @@ impl TypeZoo {
         let rangeiterator_type = create_type("range_iterator", &type_type, &object_type);
         let slice_type = create_type("slice", &type_type, &object_type);
         let mappingproxy_type = create_type("mappingproxy", &type_type, &object_type);
+        let member_descriptor_type = create_type("member_descriptor", &type_type, &object_type);
         let traceback_type = create_type("traceback", &type_type, &object_type);
 
         Self {
@@ -199,6 +204,7 @@ impl TypeZoo {
             tuple_type,
             tupleiterator_type,
             iter_type,
+            callable_iterator_type,
             enumerate_type,
             filter_type,
             map_type,
@@ -213,6 +219,7 @@ impl TypeZoo {
             builtin_function_or_method_type,
             super_type,
             mappingproxy_type,
+            member_descriptor_type,
             property_type,
             readonly_property_type,
             generator_type,
@@ -240,6 +247,7 @@ fn init_type_hierarchy() -> (PyClassRef, PyClassRef) {
     let (type_type, object_type) = unsafe {
         let object_type = PyObject {
             typ: mem::MaybeUninit::uninit().assume_init(), // !
+            #[cfg(not(feature = "sidecar-dict"))]
             dict: None,
             payload: PyClass {
                 name: String::from("object"),
@@ -248,12 +256,15 @@ fn init_type_hierarchy() -> (PyClassRef, PyClassRef) {
                 subclasses: RefCell::default(),
                 attributes: RefCell::new(PyAttributes::new()),
                 slots: RefCell::default(),
+                layout: objtype::InstanceLayout::default(),
+                version_tag: Cell::new(objtype::new_version_tag()),
             },
         }
         .into_ref();
 
         let type_type = PyObject {
             typ: mem::MaybeUninit::uninit().assume_init(), // !
+            #[cfg(not(feature = "sidecar-dict"))]
             dict: None,
             payload: PyClass {
                 name: String::from("type"),
@@ -262,6 +273,8 @@ fn init_type_hierarchy() -> (PyClassRef, PyClassRef) {
                 subclasses: RefCell::default(),
                 attributes: RefCell::new(PyAttributes::new()),
                 slots: RefCell::default(),
+                layout: objtype::InstanceLayout::default(),
+                version_tag: Cell::new(objtype::new_version_tag()),
             },
         }
         .into_ref();
@@ -325,5 +338,6 @@ pub fn initialize_types(context: &PyContext) {
     objmodule::init(&context);
     objnamespace::init(&context);
     objmappingproxy::init(&context);
+    objmemberdescr::init(&context);
     objtraceback::init(&context);
 }
@@ -9,19 +9,23 @@ use std::collections::hash_map::HashMap;
 use std::collections::hash_set::HashSet;
 use std::fmt;
 use std::rc::Rc;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use arr_macro::arr;
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 #[cfg(feature = "rustpython-compiler")]
-use rustpython_compiler::{compile, error::CompileError};
+use rustpython_compiler::error::{CompileError, CompileErrorType, CompileWarning};
+use rustpython_compiler::{compile, encoding};
 
 use crate::builtins::{self, to_ascii};
 use crate::bytecode;
+use crate::exceptions;
 use crate::frame::{ExecutionResult, Frame, FrameRef};
-use crate::frozen;
+use crate::frozen::{self, FrozenModuleCollision, RunFrozenError};
 use crate::function::PyFuncArgs;
+use crate::gc;
+use crate::gil::{self, Gil};
 use crate::import;
 use crate::obj::objbool;
 use crate::obj::objbuiltinfunc::PyBuiltinFunction;
@@ -32,11 +36,13 @@ use crate::obj::objfunction::{PyFunction, PyMethod};
 use crate::obj::objgenerator::PyGenerator;
 use crate::obj::objint::PyInt;
 use crate::obj::objiter;
+use crate::obj::objlist::PyListRef;
 use crate::obj::objmodule::{self, PyModule};
 use crate::obj::objsequence;
 use crate::obj::objstr::{PyString, PyStringRef};
 use crate::obj::objtuple::PyTupleRef;
 use crate::obj::objtype::{self, PyClassRef};
+use crate::obj::objweakref::PyWeak;
 use crate::pyhash;
 use crate::pyobject::{
     IdProtocol, ItemProtocol, PyContext, PyObject, PyObjectRef, PyResult, PyValue, TryFromObject,
@@ -52,27 +58,65 @@ use crate::sysmodule;
 
 /// Top level container of a python virtual machine. In theory you could
 /// create more instances of this struct and have them operate fully isolated.
+///
+/// Each thread of an interpreter has its own, see `new_thread`, which shares the state of the
+/// interpreter, like the modules and the context, with the one that created the interpreter, and
+/// has its own frames, exceptions and trace functions.
 pub struct VirtualMachine {
     pub builtins: PyObjectRef,
     pub sys_module: PyObjectRef,
-    pub stdlib_inits: RefCell<HashMap<String, stdlib::StdlibInitFunc>>,
-    pub ctx: PyContext,
+    pub stdlib_inits: Rc<RefCell<HashMap<String, stdlib::StdlibInitFunc>>>,
+    pub ctx: Rc<PyContext>,
     pub frames: RefCell<Vec<FrameRef>>,
     pub wasm_id: Option<String>,
     pub exceptions: RefCell<Vec<PyObjectRef>>,
-    pub frozen: RefCell<HashMap<String, bytecode::FrozenModule>>,
+    pub frozen: Rc<RefCell<HashMap<String, bytecode::FrozenModule>>>,
     pub import_func: RefCell<PyObjectRef>,
     pub profile_func: RefCell<PyObjectRef>,
     pub trace_func: RefCell<PyObjectRef>,
     pub use_tracing: RefCell<bool>,
-    pub signal_handlers: RefCell<[PyObjectRef; NSIG]>,
+    in_trace_func: Cell<bool>,
+    pub signal_handlers: Rc<RefCell<[PyObjectRef; NSIG]>>,
     pub settings: PySettings,
     pub recursion_limit: Cell<usize>,
+    /// How many levels of `with_recursion` are running.
+    recursion_depth: Cell<usize>,
+    /// Whether a `RecursionError` was raised, and the recursion hasn't gone back down since.
+    recursion_overflowed: Cell<bool>,
+    /// The functions registered with `atexit`, with their arguments.
+    pub(crate) atexit_funcs: Rc<RefCell<Vec<(PyObjectRef, PyFuncArgs)>>>,
+    /// The weak references that have a callback, which `shutdown` calls if their referent is gone.
+    pub(crate) weakref_callbacks: Rc<RefCell<Vec<PyWeak>>>,
+    /// The codec search functions and the codecs they found, see `codecs.lookup`.
+    pub(crate) codec_registry: Rc<stdlib::codecs::CodecRegistry>,
+    /// The locale of each category, by its number, see `locale.setlocale`. "" is the "C" locale.
+    pub(crate) locales: Rc<RefCell<[String; 6]>>,
+    /// `gc.garbage`, where the cycle collector puts the garbage it is told to keep.
+    pub(crate) gc_garbage: PyListRef,
+    /// The tracked objects of the threads that ended, for the cycle collector of another thread
+    /// to take over.
+    pub(crate) gc_orphans: Rc<gc::Orphans>,
+    /// The global interpreter lock, which the thread of this `VirtualMachine` holds while it
+    /// runs.
+    pub(crate) gil: Arc<Gil>,
+    /// Whether this is the thread that created the interpreter, the only one that runs signal
+    /// handlers and shuts the interpreter down.
+    main_thread: bool,
+    finalizing: Cell<bool>,
+    finalized: Cell<bool>,
 }
 
 pub const NSIG: usize = 64;
 
+/// How many levels past the recursion limit the code handling a `RecursionError` may go.
+const RECURSION_HEADROOM: usize = 50;
+
 /// Struct containing all kind of settings for the python vm.
+///
+/// On wasm there's no file system to import modules from, only the frozen and built-in ones, so
+/// `path_list` only ends up in `sys.path` and `prefer_path` and `dont_write_bytecode` have no
+/// effect.
+#[derive(Clone)]
 pub struct PySettings {
     /// -d command line switch
     pub debug: bool,
@@ -106,12 +150,68 @@ pub struct PySettings {
 
     /// sys.argv
     pub argv: Vec<String>,
+
+    /// Frozen modules to register in addition to the built-in ones, which they replace if they
+    /// have the same name
+    pub frozen_modules: HashMap<String, bytecode::FrozenModule>,
+
+    /// Environment RUSTPYTHON_PREFER_PATH: look for modules on sys.path before the frozen ones,
+    /// so that a file shadows the frozen module of the same name. This puts the path based
+    /// finder first in sys.meta_path when importlib is set up, so it has no effect on modules
+    /// that were already imported, nor without the external importers.
+    pub prefer_path: bool,
+
+    /// Environment RUSTPYTHON_NO_INLINE_CACHES: always look up globals and attributes from
+    /// scratch instead of reusing what the last lookup by the same instruction found, e.g. to
+    /// rule the caches out while debugging.
+    pub no_inline_caches: bool,
+}
+
+impl PySettings {
+    /// Add frozen modules for the VM to start with. A name that was already added is an error,
+    /// and then none of the modules are added.
+    pub fn add_frozen(
+        &mut self,
+        modules: HashMap<String, bytecode::FrozenModule>,
+    ) -> Result<(), FrozenModuleCollision> {
+        frozen::merge_modules(&mut self.frozen_modules, modules, |_| false)
+    }
+
+    /// Add frozen modules for the VM to start with, replacing any that were added with the
+    /// same name.
+    pub fn add_frozen_overwrite(&mut self, modules: HashMap<String, bytecode::FrozenModule>) {
+        self.frozen_modules.extend(modules);
+    }
 }
 
 /// Trace events for sys.settrace and sys.setprofile.
-enum TraceEvent {
+pub(crate) enum TraceEvent {
     Call,
+    Line,
+    Opcode,
     Return,
+    Exception,
+    CCall,
+    CReturn,
+    CException,
+}
+
+impl TraceEvent {
+    /// Whether the event is passed to the trace function set with sys.settrace.
+    fn is_trace_event(&self) -> bool {
+        match self {
+            TraceEvent::CCall | TraceEvent::CReturn | TraceEvent::CException => false,
+            _ => true,
+        }
+    }
+
+    /// Whether the event is passed to the profile function set with sys.setprofile.
+    fn is_profile_event(&self) -> bool {
+        match self {
+            TraceEvent::Line | TraceEvent::Opcode | TraceEvent::Exception => false,
+            _ => true,
+        }
+    }
 }
 
 impl fmt::Display for TraceEvent {
@@ -119,7 +219,13 @@ impl fmt::Display for TraceEvent {
         use TraceEvent::*;
         match self {
             Call => write!(f, "call"),
+            Line => write!(f, "line"),
+            Opcode => write!(f, "opcode"),
             Return => write!(f, "return"),
+            Exception => write!(f, "exception"),
+            CCall => write!(f, "c_call"),
+            CReturn => write!(f, "c_return"),
+            CException => write!(f, "c_exception"),
         }
     }
 }
@@ -139,15 +245,18 @@ impl Default for PySettings {
             dont_write_bytecode: false,
             path_list: vec![],
             argv: vec![],
+            frozen_modules: HashMap::new(),
+            prefer_path: false,
+            no_inline_caches: false,
         }
     }
 }
 
 impl VirtualMachine {
     /// Create a new `VirtualMachine` structure.
-    pub fn new(settings: PySettings) -> VirtualMachine {
+    pub fn new(mut settings: PySettings) -> VirtualMachine {
         flame_guard!("init VirtualMachine");
-        let ctx = PyContext::new();
+        let ctx = Rc::new(PyContext::new());
 
         // make a new module without access to the vm; doesn't
         // set __spec__, __loader__, etc. attributes
@@ -160,12 +269,15 @@ impl VirtualMachine {
         let sysmod_dict = ctx.new_dict();
         let sysmod = new_module(sysmod_dict.clone());
 
-        let stdlib_inits = RefCell::new(stdlib::get_module_inits());
-        let frozen = RefCell::new(frozen::get_module_inits());
+        let stdlib_inits = Rc::new(RefCell::new(stdlib::get_module_inits()));
+        let mut frozen = frozen::get_module_inits();
+        frozen.extend(std::mem::take(&mut settings.frozen_modules));
+        let frozen = Rc::new(RefCell::new(frozen));
         let import_func = RefCell::new(ctx.none());
         let profile_func = RefCell::new(ctx.none());
         let trace_func = RefCell::new(ctx.none());
-        let signal_handlers = RefCell::new(arr![ctx.none(); 64]);
+        let signal_handlers = Rc::new(RefCell::new(arr![ctx.none(); 64]));
+        let gc_garbage = ctx.new_list(vec![]).downcast().unwrap();
 
         let vm = VirtualMachine {
             builtins: builtins.clone(),
@@ -180,9 +292,22 @@ impl VirtualMachine {
             profile_func,
             trace_func,
             use_tracing: RefCell::new(false),
+            in_trace_func: Cell::new(false),
             signal_handlers,
             settings,
             recursion_limit: Cell::new(512),
+            recursion_depth: Cell::new(0),
+            recursion_overflowed: Cell::new(false),
+            atexit_funcs: Rc::new(RefCell::new(vec![])),
+            weakref_callbacks: Rc::new(RefCell::new(vec![])),
+            codec_registry: Rc::new(stdlib::codecs::CodecRegistry::default()),
+            locales: Rc::new(RefCell::new(Default::default())),
+            gc_garbage,
+            gc_orphans: Rc::new(gc::Orphans::default()),
+            gil: Arc::new(Gil::new_locked()),
+            main_thread: true,
+            finalizing: Cell::new(false),
+            finalized: Cell::new(false),
         };
 
         objmodule::init_module_dict(
@@ -207,11 +332,374 @@ impl VirtualMachine {
         vm
     }
 
+    /// A `VirtualMachine` for a new thread of the interpreter, which starts out without frames,
+    /// and which has to take the global interpreter lock before it runs anything.
+    pub(crate) fn new_thread(&self) -> VirtualMachine {
+        VirtualMachine {
+            builtins: self.builtins.clone(),
+            sys_module: self.sys_module.clone(),
+            stdlib_inits: self.stdlib_inits.clone(),
+            ctx: self.ctx.clone(),
+            frames: RefCell::new(vec![]),
+            wasm_id: self.wasm_id.clone(),
+            exceptions: RefCell::new(vec![]),
+            frozen: self.frozen.clone(),
+            import_func: RefCell::new(self.import_func.borrow().clone()),
+            profile_func: RefCell::new(self.get_none()),
+            trace_func: RefCell::new(self.get_none()),
+            use_tracing: RefCell::new(false),
+            in_trace_func: Cell::new(false),
+            signal_handlers: self.signal_handlers.clone(),
+            settings: self.settings.clone(),
+            recursion_limit: Cell::new(self.recursion_limit.get()),
+            recursion_depth: Cell::new(0),
+            recursion_overflowed: Cell::new(false),
+            atexit_funcs: self.atexit_funcs.clone(),
+            weakref_callbacks: self.weakref_callbacks.clone(),
+            codec_registry: self.codec_registry.clone(),
+            locales: self.locales.clone(),
+            gc_garbage: self.gc_garbage.clone(),
+            gc_orphans: self.gc_orphans.clone(),
+            gil: self.gil.clone(),
+            main_thread: false,
+            finalizing: Cell::new(false),
+            finalized: Cell::new(false),
+        }
+    }
+
+    /// Whether this is the thread that created the interpreter.
+    pub fn is_main_thread(&self) -> bool {
+        self.main_thread
+    }
+
+    /// Run `f` without the global interpreter lock, so that other threads can run Python code
+    /// meanwhile, like CPython's `Py_BEGIN_ALLOW_THREADS`. `f` must not touch any objects.
+    pub fn allow_threads<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _released = gil::Released::new(&self.gil);
+        f()
+    }
+
+    /// Register frozen modules, which can be imported right away. A module whose name is
+    /// already registered, or that was already imported in some other way, is an error, and then
+    /// none of the modules are registered.
+    pub fn add_frozen(
+        &self,
+        modules: HashMap<String, bytecode::FrozenModule>,
+    ) -> Result<(), FrozenModuleCollision> {
+        let sys_modules = self.get_attribute(self.sys_module.clone(), "modules").ok();
+        let is_imported = |name: &str| match &sys_modules {
+            Some(sys_modules) => sys_modules.get_item(name, self).is_ok(),
+            None => false,
+        };
+        frozen::merge_modules(&mut self.frozen.borrow_mut(), modules, is_imported)?;
+        self.update_frozen_module_names();
+        Ok(())
+    }
+
+    /// Register frozen modules, replacing any registered with the same name. Modules that were
+    /// already imported stay in `sys.modules` as they are.
+    pub fn add_frozen_overwrite(&self, modules: HashMap<String, bytecode::FrozenModule>) {
+        self.frozen.borrow_mut().extend(modules);
+        self.update_frozen_module_names();
+    }
+
+    fn update_frozen_module_names(&self) {
+        let names = sysmodule::frozen_module_names(self);
+        self.set_attr(&self.sys_module, "frozen_module_names", names)
+            .unwrap();
+    }
+
+    /// Run the frozen module `name` in a new namespace; see `run_frozen_in`.
+    ///
+    /// Together with `py_compile_bytecode!`'s eval mode, this evaluates an expression that was
+    /// compiled with the program:
+    ///
+    /// ```no_run
+    /// use rustpython_vm::pyobject::TryFromObject;
+    /// use rustpython_vm::{py_compile_bytecode, VirtualMachine};
+    ///
+    /// let vm = VirtualMachine::default();
+    /// vm.add_frozen(py_compile_bytecode!(
+    ///     source = "{'threads': 4, 'verbose': False}['threads'] * 2",
+    ///     module_name = "config",
+    ///     mode = "eval",
+    /// ))
+    /// .unwrap();
+    /// let threads = vm.run_frozen("config").unwrap();
+    /// let threads = usize::try_from_object(&vm, threads).unwrap();
+    /// assert_eq!(threads, 8);
+    /// ```
+    pub fn run_frozen(&self, name: &str) -> Result<PyObjectRef, RunFrozenError> {
+        self.run_frozen_in(name, self.ctx.new_dict())
+    }
+
+    /// Run the frozen module `name` with `globals` as its namespace. This doesn't import it:
+    /// nothing is added to `sys.modules`. Code compiled in eval mode gives the value of its
+    /// expression, and other code the namespace, with what it defined in it.
+    pub fn run_frozen_in(
+        &self,
+        name: &str,
+        globals: PyDictRef,
+    ) -> Result<PyObjectRef, RunFrozenError> {
+        let code = match self.frozen.borrow().get(name) {
+            Some(module) => module
+                .code
+                .try_decode()
+                .map_err(RunFrozenError::InvalidBytecode)?,
+            None => return Err(RunFrozenError::NotFound(name.to_owned())),
+        };
+        let is_expression = code.flags.contains(bytecode::CodeFlags::IS_EXPRESSION);
+        let code = PyCode::new(code, &self.ctx).into_ref(self);
+        let scope = Scope::with_builtins(None, globals.clone(), self);
+        let value = self
+            .run_code_obj(code, scope)
+            .map_err(RunFrozenError::Exception)?;
+        if is_expression {
+            Ok(value)
+        } else {
+            Ok(globals.into_object())
+        }
+    }
+
+    /// Finalize the interpreter, in this order:
+    ///
+    /// 1. Wait for the threads that aren't daemon threads to end, if `threading` was imported.
+    /// 2. Call the functions registered with `atexit`, the last registered first.
+    /// 3. Clear the modules in `sys.modules` other than `sys` and `builtins`, the last imported
+    ///    first. Like in CPython their globals are set to `None`, the ones starting with an
+    ///    underscore first, and then removed.
+    /// 4. Flush and close the streams in `sys`, and set them to `None`. The ones on file
+    ///    descriptors 0 to 2 are only flushed, as those belong to the process and not to the VM.
+    /// 5. Clear `sys` and `builtins`.
+    ///
+    /// An object that loses its last reference along the way has its `__del__` called, and then
+    /// the weak references that lost their referent have their callbacks called, while the VM can
+    /// still run them. Afterwards it can't: running a frame or calling a Python function raises a
+    /// `RuntimeError`, and `shutdown` does nothing. Dropping the VM shuts it down if this wasn't
+    /// called. The daemon threads that are still running never get the global interpreter lock
+    /// back, so they stop the next time they need it.
+    pub fn shutdown(&self) {
+        if self.finalizing.replace(true) {
+            return;
+        }
+        self.wait_for_threads();
+        stdlib::atexit::run_exitfuncs(self);
+
+        let modules = self
+            .get_attribute(self.sys_module.clone(), "modules")
+            .and_then(|modules| PyDictRef::try_from_object(self, modules));
+        if let Ok(modules) = modules {
+            let modules: Vec<_> = modules.into_iter().map(|(_, module)| module).collect();
+            for module in modules.iter().rev() {
+                if module.is(&self.sys_module) || module.is(&self.builtins) {
+                    continue;
+                }
+                if let (true, Some(dict)) = (module.payload_is::<PyModule>(), module.dict()) {
+                    self.clear_module_dict(dict);
+                }
+            }
+        }
+        self.finalize_std_streams();
+
+        self.exceptions.borrow_mut().clear();
+        self.import_func.replace(self.get_none());
+        self.profile_func.replace(self.get_none());
+        self.trace_func.replace(self.get_none());
+        self.use_tracing.replace(false);
+        for handler in self.signal_handlers.borrow_mut().iter_mut() {
+            *handler = self.get_none();
+        }
+        for module in &[&self.sys_module, &self.builtins] {
+            if let Some(dict) = module.dict() {
+                self.clear_module_dict(dict);
+            }
+        }
+        self.atexit_funcs.borrow_mut().clear();
+        self.weakref_callbacks.borrow_mut().clear();
+        self.codec_registry.clear();
+        self.finalized.set(true);
+    }
+
+    /// Whether `shutdown` has finished, so that no more Python code can run.
+    pub fn is_finalized(&self) -> bool {
+        self.finalized.get()
+    }
+
+    fn check_not_finalized(&self) -> PyResult<()> {
+        if self.finalized.get() {
+            Err(self.new_runtime_error("the interpreter has been shut down".to_owned()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn clear_module_dict(&self, dict: &PyDictRef) {
+        let names: Vec<_> = dict.into_iter().map(|(name, _)| name).collect();
+        let is_private = |name: &PyObjectRef| {
+            name.payload::<PyString>()
+                .map_or(false, |name| name.as_str().starts_with('_'))
+        };
+        for &private in &[true, false] {
+            for name in names.iter().filter(|name| is_private(name) == private) {
+                if name
+                    .payload::<PyString>()
+                    .map_or(false, |name| name.as_str() == "__builtins__")
+                {
+                    continue;
+                }
+                if let Ok(Some(value)) = dict.get_item_option(name, self) {
+                    if dict.set_item(name, self.get_none(), self).is_ok() {
+                        self.release_object(value);
+                    }
+                }
+            }
+        }
+        dict.clone().clear(self);
+    }
+
+    fn finalize_std_streams(&self) {
+        let names = &[
+            "stdin",
+            "stdout",
+            "stderr",
+            "__stdin__",
+            "__stdout__",
+            "__stderr__",
+        ];
+        let streams: Vec<_> = names
+            .iter()
+            .filter_map(|name| {
+                let stream = self.get_attribute(self.sys_module.clone(), *name).ok()?;
+                self.set_attr(&self.sys_module, *name, self.get_none())
+                    .ok()?;
+                Some(stream).filter(|stream| !self.is_none(stream))
+            })
+            .collect();
+        for stream in &streams {
+            if let Err(exc) = self.call_method(stream, "flush", vec![]) {
+                self.print_unraisable(stream, &exc);
+                continue;
+            }
+            let closed = self
+                .get_attribute(stream.clone(), "closed")
+                .and_then(|closed| objbool::boolval(self, closed));
+            if closed.unwrap_or(true) || self.stream_fileno(stream).map_or(false, |fd| fd <= 2) {
+                continue;
+            }
+            if let Err(exc) = self.call_method(stream, "close", vec![]) {
+                self.print_unraisable(stream, &exc);
+            }
+        }
+        for stream in streams {
+            self.release_object(stream);
+        }
+    }
+
+    /// The file descriptor of a stream, which `FileIO` has as an attribute and the others return
+    /// from a method.
+    fn stream_fileno(&self, stream: &PyObjectRef) -> Option<i64> {
+        let mut fileno = self.get_attribute(stream.clone(), "fileno").ok()?;
+        if self.is_callable(&fileno) {
+            fileno = self.invoke(&fileno, vec![]).ok()?;
+        }
+        fileno.payload::<PyInt>()?.as_bigint().to_i64()
+    }
+
+    /// Drops `obj`, calling its `__del__` first if this is the last reference to it, and then the
+    /// callbacks of the weak references that lost their referent.
+    fn release_object(&self, obj: PyObjectRef) {
+        if Rc::strong_count(&obj) == 1 {
+            if let Some(del) = objtype::class_get_attr(&obj.class(), "__del__") {
+                if let Err(exc) = self.invoke(&del, vec![obj.clone()]) {
+                    self.print_unraisable(&del, &exc);
+                }
+            }
+        }
+        drop(obj);
+        self.run_weakref_callbacks();
+    }
+
+    /// Like CPython, leave it to `threading._shutdown` to join the threads, as it knows which
+    /// ones are daemon threads.
+    fn wait_for_threads(&self) {
+        let threading = self
+            .get_attribute(self.sys_module.clone(), "modules")
+            .and_then(|modules| modules.get_item("threading", self));
+        if let Ok(threading) = threading {
+            let shutdown = self
+                .get_attribute(threading.clone(), "_shutdown")
+                .and_then(|shutdown| self.invoke(&shutdown, vec![]));
+            if let Err(exc) = shutdown {
+                self.print_unraisable(&threading, &exc);
+            }
+        }
+    }
+
+    pub(crate) fn run_weakref_callbacks(&self) {
+        self.weakref_callbacks
+            .borrow_mut()
+            .retain(|weakref| weakref.upgrade().is_some());
+        loop {
+            let pending =
+                self.weakref_callbacks
+                    .borrow()
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, weakref)| {
+                        let weakref = weakref.upgrade()?;
+                        let callback = weakref.payload::<PyWeak>()?.take_callback_if_dead()?;
+                        Some((index, weakref, callback))
+                    });
+            let (index, weakref, callback) = match pending {
+                Some(pending) => pending,
+                None => break,
+            };
+            self.weakref_callbacks.borrow_mut().remove(index);
+            if let Err(exc) = self.invoke(&callback, vec![weakref]) {
+                self.print_unraisable(&callback, &exc);
+            }
+        }
+    }
+
+    /// Report an exception that can't be raised to anyone, like CPython does for the ones raised
+    /// by `__del__`.
+    pub(crate) fn print_unraisable(&self, obj: &PyObjectRef, exc: &PyObjectRef) {
+        let repr = self
+            .to_repr(obj)
+            .map(|repr| repr.as_str().to_owned())
+            .unwrap_or_else(|_| "<object repr() failed>".to_owned());
+        eprintln!("Exception ignored in: {}", repr);
+        let _ = exceptions::write_exception(std::io::stderr(), self, exc);
+    }
+
     pub fn run_code_obj(&self, code: PyCodeRef, scope: Scope) -> PyResult {
         let frame = Frame::new(code, scope).into_ref(self);
         self.run_frame_full(frame)
     }
 
+    /// Compile `source` as a module and run it in `scope`. Tracebacks show `source_path` as the
+    /// file name of the code.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn run_code_string(&self, scope: Scope, source: &str, source_path: String) -> PyResult {
+        let code = self
+            .compile(source, compile::Mode::Exec, source_path)
+            .map_err(|err| self.new_syntax_error(&err))?;
+        self.run_code_obj(code, scope)
+    }
+
+    /// Run the file at `path` as the `__main__` module, like `python path` does, and return the
+    /// scope it ran in.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn run_script(&self, path: &str) -> PyResult<Scope> {
+        let source = std::fs::read(path)
+            .map_err(|err| self.new_os_error(format!("can't open file '{}': {}", path, err)))?;
+        let source = encoding::decode_source(&source).map_err(|err| self.new_syntax_error(&err))?;
+        let scope = Scope::with_module_defaults(self, "__main__", Some(path));
+        self.new_registered_module("__main__", scope.globals.clone())?;
+        self.run_code_string(scope.clone(), &source, path.to_owned())?;
+        Ok(scope)
+    }
+
     pub fn run_frame_full(&self, frame: FrameRef) -> PyResult {
         match self.run_frame(frame)? {
             ExecutionResult::Return(value) => Ok(value),
@@ -220,21 +708,76 @@ impl VirtualMachine {
     }
 
     pub fn run_frame(&self, frame: FrameRef) -> PyResult<ExecutionResult> {
-        self.check_recursive_call("")?;
+        self.check_not_finalized()?;
+        self.with_recursion("", || self.with_frame(frame, |frame| frame.run(self)))
+    }
+
+    /// Runs `func` with `frame` pushed on the frame stack.
+    pub(crate) fn with_frame<R, F>(&self, frame: FrameRef, func: F) -> R
+    where
+        F: FnOnce(&FrameRef) -> R,
+    {
         self.frames.borrow_mut().push(frame.clone());
-        let result = frame.run(self);
+        #[cfg(unix)]
+        stdlib::faulthandler::frames_changed(&self.frames.borrow());
+        let result = func(&frame);
         self.frames.borrow_mut().pop();
+        #[cfg(unix)]
+        stdlib::faulthandler::frames_changed(&self.frames.borrow());
+        result
+    }
+
+    /// Run `f` one level deeper in the recursion, or raise `RecursionError` if that goes past the
+    /// recursion limit. Every Python frame takes a level, and so does the native code that can
+    /// recurse without running Python code, like getting the repr of a nested list. `_where` is
+    /// added to the message of the error, e.g. `" while getting the repr of an object"`.
+    pub fn with_recursion<R, F: FnOnce() -> PyResult<R>>(&self, _where: &str, f: F) -> PyResult<R> {
+        self.check_recursive_call(_where)?;
+        let depth = self.recursion_depth.get() + 1;
+        self.recursion_depth.set(depth);
+        let result = f();
+        self.recursion_depth.set(depth - 1);
+        if self.recursion_overflowed.get() && depth - 1 < self.recursion_low_water_mark() {
+            self.recursion_overflowed.set(false);
+        }
         result
     }
 
+    /// How many levels of recursion are running, which `sys.setrecursionlimit` checks the new
+    /// limit against.
+    pub(crate) fn recursion_depth(&self) -> usize {
+        self.recursion_depth.get()
+    }
+
     fn check_recursive_call(&self, _where: &str) -> PyResult<()> {
-        if self.frames.borrow().len() > self.recursion_limit.get() {
-            Err(self.new_recursion_error(format!("maximum recursion depth exceeded {}", _where)))
+        let depth = self.recursion_depth.get();
+        let limit = self.recursion_limit.get();
+        // Once the RecursionError is raised, the code that handles it, the except and finally
+        // blocks on its way out, may go a few levels past the limit. Like CPython, the limit
+        // only comes back when the recursion is well below it again.
+        let limit = if self.recursion_overflowed.get() {
+            limit + RECURSION_HEADROOM
+        } else {
+            limit
+        };
+        if depth >= limit {
+            self.recursion_overflowed.set(true);
+            Err(self.new_recursion_error(format!("maximum recursion depth exceeded{}", _where)))
         } else {
             Ok(())
         }
     }
 
+    /// The depth the recursion has to get back under for the headroom to go away.
+    fn recursion_low_water_mark(&self) -> usize {
+        let limit = self.recursion_limit.get();
+        if limit > 200 {
+            limit - RECURSION_HEADROOM
+        } else {
+            3 * (limit / 4)
+        }
+    }
+
     pub fn current_frame(&self) -> Option<Ref<FrameRef>> {
         let frames = self.frames.borrow();
         if frames.is_empty() {
@@ -293,6 +836,15 @@ impl VirtualMachine {
         PyObject::new(PyModule {}, self.ctx.types.module_type.clone(), Some(dict))
     }
 
+    /// Like `new_module`, but also add the module to `sys.modules`, so that code run later can
+    /// import it.
+    pub fn new_registered_module(&self, name: &str, dict: PyDictRef) -> PyResult {
+        let module = self.new_module(name, dict);
+        let sys_modules = self.get_attribute(self.sys_module.clone(), "modules")?;
+        sys_modules.set_item(name, module.clone(), self)?;
+        Ok(module)
+    }
+
     #[cfg_attr(feature = "flame-it", flame("VirtualMachine"))]
     pub fn new_exception_obj(&self, exc_type: PyClassRef, args: Vec<PyObjectRef>) -> PyResult {
         // TODO: add repr of args into logging?
@@ -337,13 +889,85 @@ impl VirtualMachine {
         op: &str,
     ) -> PyObjectRef {
         self.new_type_error(format!(
-            "Unsupported operand types for '{}': '{}' and '{}'",
+            "unsupported operand type(s) for {}: '{}' and '{}'",
             op,
             a.class().name,
             b.class().name
         ))
     }
 
+    pub fn new_unsupported_comparison_error(
+        &self,
+        a: PyObjectRef,
+        b: PyObjectRef,
+        op: &str,
+    ) -> PyObjectRef {
+        self.new_type_error(format!(
+            "'{}' not supported between instances of '{}' and '{}'",
+            op,
+            a.class().name,
+            b.class().name
+        ))
+    }
+
+    pub fn new_unsupported_unary_error(&self, a: &PyObjectRef, op: &str) -> PyObjectRef {
+        self.new_type_error(format!(
+            "bad operand type for unary {}: '{}'",
+            op,
+            a.class().name
+        ))
+    }
+
+    /// The builtin sequence type (with concatenation and repetition) that `obj` is an instance
+    /// of, if any.
+    fn builtin_sequence_type(&self, obj: &PyObjectRef) -> Option<PyClassRef> {
+        let ctx = &self.ctx;
+        let types = [
+            ctx.str_type(),
+            ctx.list_type(),
+            ctx.tuple_type(),
+            ctx.bytes_type(),
+            ctx.bytearray_type(),
+        ];
+        types
+            .iter()
+            .find(|cls| objtype::isinstance(obj, cls))
+            .cloned()
+    }
+
+    /// The error for `a + b` when neither operand implements it. Like in CPython, a builtin
+    /// sequence reports that it can only be concatenated with its own kind.
+    fn new_unsupported_add_error(&self, a: PyObjectRef, b: PyObjectRef, op: &str) -> PyObjectRef {
+        match self.builtin_sequence_type(&a) {
+            Some(cls) if cls.is(&self.ctx.bytes_type()) || cls.is(&self.ctx.bytearray_type()) => {
+                self.new_type_error(format!("can't concat {} to {}", b.class().name, cls.name))
+            }
+            Some(cls) => self.new_type_error(format!(
+                "can only concatenate {} (not \"{}\") to {}",
+                cls.name,
+                b.class().name,
+                cls.name
+            )),
+            None => self.new_unsupported_operand_error(a, b, op),
+        }
+    }
+
+    /// The error for `a * b` when neither operand implements it, which for a builtin sequence
+    /// means that the other operand is not an integer.
+    fn new_unsupported_mul_error(&self, a: PyObjectRef, b: PyObjectRef, op: &str) -> PyObjectRef {
+        let non_int = if self.builtin_sequence_type(&a).is_some() {
+            &b
+        } else if self.builtin_sequence_type(&b).is_some() {
+            &a
+        } else {
+            return self.new_unsupported_operand_error(a, b, op);
+        };
+        self.new_type_error(format!(
+            "can't multiply sequence by non-int of type '{}'",
+            non_int.class().name
+        ))
+    }
+
     pub fn new_os_error(&self, msg: String) -> PyObjectRef {
         let os_error = self.ctx.exceptions.os_error.clone();
         self.new_exception(os_error, msg)
@@ -354,6 +978,11 @@ impl VirtualMachine {
         self.new_exception(unicode_decode_error, msg)
     }
 
+    pub fn new_unicode_encode_error(&self, msg: String) -> PyObjectRef {
+        let unicode_encode_error = self.ctx.exceptions.unicode_encode_error.clone();
+        self.new_exception(unicode_encode_error, msg)
+    }
+
     /// Create a new python ValueError object. Useful for raising errors from
     /// python functions implemented in rust.
     pub fn new_value_error(&self, msg: String) -> PyObjectRef {
@@ -381,6 +1010,11 @@ impl VirtualMachine {
         self.new_exception(recursion_error, msg)
     }
 
+    pub fn new_runtime_error(&self, msg: String) -> PyObjectRef {
+        let runtime_error = self.ctx.exceptions.runtime_error.clone();
+        self.new_exception(runtime_error, msg)
+    }
+
     pub fn new_zero_division_error(&self, msg: String) -> PyObjectRef {
         let zero_division_error = self.ctx.exceptions.zero_division_error.clone();
         self.new_exception(zero_division_error, msg)
@@ -401,8 +1035,12 @@ impl VirtualMachine {
             self.ctx.exceptions.syntax_error.clone()
         };
         let syntax_error = self.new_exception(syntax_error_type, error.to_string());
+        let msg = self.new_str(error.error.to_string());
+        self.set_attr(&syntax_error, "msg", msg).unwrap();
         let lineno = self.new_int(error.location.row());
         self.set_attr(&syntax_error, "lineno", lineno).unwrap();
+        let offset = self.new_int(error.location.column());
+        self.set_attr(&syntax_error, "offset", offset).unwrap();
         syntax_error
     }
 
@@ -411,6 +1049,30 @@ impl VirtualMachine {
         self.new_exception(import_error, msg)
     }
 
+    /// Create an `ImportError` (or subclass) with its `name` and `path` attributes set, like
+    /// CPython's `PyErr_SetImportError`.
+    pub fn new_import_error_with_name(
+        &self,
+        exc_type: PyClassRef,
+        msg: String,
+        name: PyObjectRef,
+        path: PyObjectRef,
+    ) -> PyObjectRef {
+        let import_error = self.new_exception(exc_type, msg);
+        self.set_attr(&import_error, "name", name).unwrap();
+        self.set_attr(&import_error, "path", path).unwrap();
+        import_error
+    }
+
+    pub fn new_module_not_found_error(&self, module_name: &str) -> PyObjectRef {
+        self.new_import_error_with_name(
+            self.ctx.exceptions.module_not_found_error.clone(),
+            format!("No module named '{}'", module_name),
+            self.new_str(module_name.to_owned()),
+            self.get_none(),
+        )
+    }
+
     pub fn new_scope_with_builtins(&self) -> Scope {
         Scope::with_builtins(None, self.ctx.new_dict(), self)
     }
@@ -442,7 +1104,9 @@ impl VirtualMachine {
 
     // Container of the virtual machine state:
     pub fn to_str(&self, obj: &PyObjectRef) -> PyResult<PyStringRef> {
-        let str = self.call_method(&obj, "__str__", vec![])?;
+        let str = self.with_recursion(" while getting the str of an object", || {
+            self.call_method(&obj, "__str__", vec![])
+        })?;
         TryFromObject::try_from_object(self, str)
     }
 
@@ -452,7 +1116,9 @@ impl VirtualMachine {
     }
 
     pub fn to_repr(&self, obj: &PyObjectRef) -> PyResult<PyStringRef> {
-        let repr = self.call_method(obj, "__repr__", vec![])?;
+        let repr = self.with_recursion(" while getting the repr of an object", || {
+            self.call_method(obj, "__repr__", vec![])
+        })?;
         TryFromObject::try_from_object(self, repr)
     }
 
@@ -573,17 +1239,14 @@ impl VirtualMachine {
         vm_trace!("Invoke: {:?} {:?}", func_ref, args);
 
         if let Some(py_func) = func_ref.payload() {
-            self.trace_event(TraceEvent::Call)?;
-            let res = self.invoke_python_function(py_func, args);
-            self.trace_event(TraceEvent::Return)?;
-            res
+            self.invoke_python_function(py_func, args)
         } else if let Some(PyMethod {
             ref function,
             ref object,
         }) = func_ref.payload()
         {
             self.invoke(&function, args.insert(object.clone()))
-        } else if let Some(PyBuiltinFunction { ref value }) = func_ref.payload() {
+        } else if let Some(PyBuiltinFunction { ref value, .. }) = func_ref.payload() {
             value(self, args)
         } else if self.is_callable(&func_ref) {
             self.call_method(&func_ref, "__call__", args)
@@ -604,35 +1267,75 @@ impl VirtualMachine {
         res
     }
 
-    /// Call registered trace function.
-    fn trace_event(&self, event: TraceEvent) -> PyResult<()> {
-        if *self.use_tracing.borrow() {
-            let frame = self.get_none();
-            let event = self.new_str(event.to_string());
-            let arg = self.get_none();
-            let args = vec![frame, event, arg];
-
-            // temporarily disable tracing, during the call to the
-            // tracing function itself.
-            let trace_func = self.trace_func.borrow().clone();
-            if !self.is_none(&trace_func) {
-                self.use_tracing.replace(false);
-                let res = self.invoke(&trace_func, args.clone());
-                self.use_tracing.replace(true);
-                res?;
+    /// Call the registered trace and profile functions for an event in the current frame.
+    ///
+    /// Like CPython, the trace function set with `sys.settrace` is only called for `call`
+    /// events; what it returns becomes the frame's local trace function (`frame.f_trace`),
+    /// which receives the other events.
+    pub(crate) fn trace_event(&self, event: TraceEvent, arg: PyObjectRef) -> PyResult<()> {
+        if !*self.use_tracing.borrow() || self.in_trace_func.get() {
+            return Ok(());
+        }
+        let frame = match self.current_frame() {
+            Some(frame) => frame.clone(),
+            None => return Ok(()),
+        };
+        let args = vec![
+            frame.clone().into_object(),
+            self.new_str(event.to_string()),
+            arg,
+        ];
+
+        let trace_func = self.trace_func.borrow().clone();
+        if !self.is_none(&trace_func) && event.is_trace_event() {
+            let callback = match event {
+                TraceEvent::Call => Some(trace_func),
+                _ => frame.trace.borrow().clone(),
+            };
+            if let Some(callback) = callback {
+                // temporarily disable tracing, during the call to the
+                // tracing function itself.
+                self.in_trace_func.set(true);
+                let res = self.invoke(&callback, args.clone());
+                self.in_trace_func.set(false);
+                match res {
+                    Ok(local_trace) => {
+                        if !self.is_none(&local_trace) {
+                            frame.trace.replace(Some(local_trace));
+                        }
+                    }
+                    Err(err) => {
+                        // A failing trace function is removed, as in CPython.
+                        self.trace_func.replace(self.get_none());
+                        self.update_use_tracing();
+                        frame.trace.replace(None);
+                        return Err(err);
+                    }
+                }
             }
+        }
 
-            let profile_func = self.profile_func.borrow().clone();
-            if !self.is_none(&profile_func) {
-                self.use_tracing.replace(false);
-                let res = self.invoke(&profile_func, args);
-                self.use_tracing.replace(true);
-                res?;
+        let profile_func = self.profile_func.borrow().clone();
+        if !self.is_none(&profile_func) && event.is_profile_event() {
+            self.in_trace_func.set(true);
+            let res = self.invoke(&profile_func, args);
+            self.in_trace_func.set(false);
+            if let Err(err) = res {
+                self.profile_func.replace(self.get_none());
+                self.update_use_tracing();
+                return Err(err);
             }
         }
         Ok(())
     }
 
+    pub(crate) fn update_use_tracing(&self) {
+        let trace_is_none = self.is_none(&self.trace_func.borrow());
+        let profile_is_none = self.is_none(&self.profile_func.borrow());
+        self.use_tracing
+            .replace(!(trace_is_none && profile_is_none));
+    }
+
     pub fn invoke_python_function(&self, func: &PyFunction, func_args: PyFuncArgs) -> PyResult {
         self.invoke_python_function_with_scope(func, func_args, &func.scope)
     }
@@ -643,6 +1346,7 @@ impl VirtualMachine {
         func_args: PyFuncArgs,
         scope: &Scope,
     ) -> PyResult {
+        self.check_not_finalized()?;
         let code = &func.code;
 
         let scope = if func.code.flags.contains(bytecode::CodeFlags::NEW_LOCALS) {
@@ -674,7 +1378,7 @@ impl VirtualMachine {
 
     fn fill_locals_from_args(
         &self,
-        code_object: &bytecode::CodeObject,
+        code_object: &PyCode,
         locals: &PyDictRef,
         func_args: PyFuncArgs,
         defaults: &Option<PyTupleRef>,
@@ -695,11 +1399,12 @@ impl VirtualMachine {
             nargs
         };
 
-        // Copy positional arguments into local variables
+        // Copy positional arguments into local variables, by the interned names the code loads
+        // them with
         for i in 0..n {
-            let arg_name = &code_object.arg_names[i];
+            let arg_name = &code_object.names.arg_names[i];
             let arg = &func_args.args[i];
-            locals.set_item(arg_name, arg.clone(), self)?;
+            locals.set_item(arg_name.as_object(), arg.clone(), self)?;
         }
 
         // Pack other positional arguments in to *args:
@@ -714,15 +1419,7 @@ impl VirtualMachine {
 
                 locals.set_item(vararg_name, vararg_value, self)?;
             }
-            bytecode::Varargs::Unnamed | bytecode::Varargs::None => {
-                // Check the number of positional arguments
-                if nargs > nexpected_args {
-                    return Err(self.new_type_error(format!(
-                        "Expected {} arguments (got: {})",
-                        nexpected_args, nargs
-                    )));
-                }
-            }
+            bytecode::Varargs::Unnamed | bytecode::Varargs::None => {}
         }
 
         // Do we support `**kwargs` ?
@@ -736,27 +1433,93 @@ impl VirtualMachine {
             bytecode::Varargs::None => None,
         };
 
+        // Positional-only parameters can't be passed by keyword; without `**kwargs` to receive
+        // such a keyword instead, that is an error.
+        let posonly_names = &code_object.arg_names[..code_object.posonlyarg_count];
+        let keyword_names = &code_object.names.arg_names[code_object.posonlyarg_count..];
+        let posonly_passed_as_keyword: Vec<String> = func_args
+            .kwargs
+            .keys()
+            .filter(|name| posonly_names.contains(name))
+            .cloned()
+            .collect();
+
         // Handle keyword arguments
         for (name, value) in func_args.kwargs {
             // Check if we have a parameter with this name:
-            if code_object.arg_names.contains(&name) || code_object.kwonlyarg_names.contains(&name)
-            {
-                if locals.contains_key(&name, self) {
-                    return Err(
-                        self.new_type_error(format!("Got multiple values for argument '{}'", name))
-                    );
+            let parameter = keyword_names
+                .iter()
+                .chain(code_object.names.kwonlyarg_names.iter())
+                .find(|parameter| parameter.as_str() == name);
+            if let Some(parameter) = parameter {
+                if locals.contains_key(parameter.as_object(), self) {
+                    return Err(self.new_type_error(format!(
+                        "{}() got multiple values for argument '{}'",
+                        code_object.obj_name, name
+                    )));
                 }
 
-                locals.set_item(&name, value, self)?;
+                locals.set_item(parameter.as_object(), value, self)?;
             } else if let Some(d) = &kwargs {
                 d.set_item(&name, value, self)?;
+            } else if !posonly_passed_as_keyword.is_empty() {
+                return Err(self.new_type_error(format!(
+                    "{}() got some positional-only arguments passed as keyword arguments: '{}'",
+                    code_object.obj_name,
+                    posonly_passed_as_keyword.join(", ")
+                )));
             } else {
-                return Err(
-                    self.new_type_error(format!("Got an unexpected keyword argument '{}'", name))
-                );
+                return Err(self.new_type_error(format!(
+                    "{}() got an unexpected keyword argument '{}'",
+                    code_object.obj_name, name
+                )));
             }
         }
 
+        // Check the number of positional arguments
+        let takes_varargs = match code_object.varargs {
+            bytecode::Varargs::Named(_) => true,
+            bytecode::Varargs::Unnamed | bytecode::Varargs::None => false,
+        };
+        if nargs > nexpected_args && !takes_varargs {
+            let num_defaults_available = defaults.as_ref().map_or(0, |d| d.elements.len());
+            let expected = if num_defaults_available > 0 {
+                format!(
+                    "from {} to {} positional arguments",
+                    nexpected_args - num_defaults_available,
+                    nexpected_args
+                )
+            } else {
+                format!(
+                    "{} positional argument{}",
+                    nexpected_args,
+                    plural(nexpected_args)
+                )
+            };
+            let kwonly_given = code_object
+                .kwonlyarg_names
+                .iter()
+                .filter(|name| locals.contains_key(name.as_str(), self))
+                .count();
+            let given = if kwonly_given > 0 {
+                format!(
+                    "{} positional argument{} (and {} keyword-only argument{}) were",
+                    nargs,
+                    plural(nargs),
+                    kwonly_given,
+                    plural(kwonly_given)
+                )
+            } else if nargs == 1 {
+                "1 was".to_string()
+            } else {
+                format!("{} were", nargs)
+            };
+            return Err(self.new_type_error(format!(
+                "{}() takes {} but {} given",
+                code_object.obj_name, expected, given
+            )));
+        }
+
         // Add missing positional arguments, if we have fewer positional arguments than the
         // function definition calls for
         if nargs < nexpected_args {
@@ -769,48 +1532,86 @@ impl VirtualMachine {
             for i in 0..required_args {
                 let variable_name = &code_object.arg_names[i];
                 if !locals.contains_key(variable_name, self) {
-                    missing.push(variable_name)
+                    missing.push(variable_name.as_str())
                 }
             }
             if !missing.is_empty() {
-                return Err(self.new_type_error(format!(
-                    "Missing {} required positional arguments: {:?}",
-                    missing.len(),
-                    missing
-                )));
+                return Err(self.new_missing_arguments_error(
+                    &code_object.obj_name,
+                    "positional",
+                    &missing,
+                ));
             }
             if let Some(defaults) = defaults {
                 let defaults = &defaults.elements;
                 // We have sufficient defaults, so iterate over the corresponding names and use
                 // the default if we don't already have a value
                 for (default_index, i) in (required_args..nexpected_args).enumerate() {
-                    let arg_name = &code_object.arg_names[i];
-                    if !locals.contains_key(arg_name, self) {
-                        locals.set_item(arg_name, defaults[default_index].clone(), self)?;
+                    let arg_name = &code_object.names.arg_names[i];
+                    if !locals.contains_key(arg_name.as_object(), self) {
+                        locals.set_item(
+                            arg_name.as_object(),
+                            defaults[default_index].clone(),
+                            self,
+                        )?;
                     }
                 }
             }
         };
 
         // Check if kw only arguments are all present:
-        for arg_name in &code_object.kwonlyarg_names {
-            if !locals.contains_key(arg_name, self) {
+        let mut missing = vec![];
+        for arg_name in code_object.names.kwonlyarg_names.iter() {
+            if !locals.contains_key(arg_name.as_object(), self) {
                 if let Some(kw_only_defaults) = kw_only_defaults {
-                    if let Some(default) = kw_only_defaults.get_item_option(arg_name, self)? {
-                        locals.set_item(arg_name, default, self)?;
+                    if let Some(default) =
+                        kw_only_defaults.get_item_option(arg_name.as_object(), self)?
+                    {
+                        locals.set_item(arg_name.as_object(), default, self)?;
                         continue;
                     }
                 }
 
                 // No default value and not specified.
-                return Err(self
-                    .new_type_error(format!("Missing required kw only argument: '{}'", arg_name)));
+                missing.push(arg_name.as_str());
             }
         }
+        if !missing.is_empty() {
+            return Err(self.new_missing_arguments_error(
+                &code_object.obj_name,
+                "keyword-only",
+                &missing,
+            ));
+        }
 
         Ok(())
     }
 
+    /// The error for calling `func_name` without the `kind` ("positional" or "keyword-only")
+    /// arguments in `missing`, listed like CPython does: `'a', 'b', and 'c'`.
+    fn new_missing_arguments_error(
+        &self,
+        func_name: &str,
+        kind: &str,
+        missing: &[&str],
+    ) -> PyObjectRef {
+        let quoted: Vec<String> = missing.iter().map(|name| format!("'{}'", name)).collect();
+        let names = match quoted.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, [first])) => format!("{} and {}", first, last),
+            Some((last, rest)) => format!("{}, and {}", rest.join(", "), last),
+            None => String::new(),
+        };
+        self.new_type_error(format!(
+            "{}() missing {} required {} argument{}: {}",
+            func_name,
+            missing.len(),
+            kind,
+            plural(missing.len()),
+            names
+        ))
+    }
+
     pub fn extract_elements<T: TryFromObject>(&self, value: &PyObjectRef) -> PyResult<Vec<T>> {
         // Extract elements from item, if possible:
         if objtype::isinstance(value, &self.ctx.tuple_type()) {
@@ -928,7 +1729,10 @@ impl VirtualMachine {
         // Try to call the default method
         self.call_or_unsupported(lhs, rhs, default, move |vm, lhs, rhs| {
             // Try to call the reflection method
-            vm.call_or_unsupported(rhs, lhs, reflection, unsupported)
+            vm.call_or_unsupported(rhs, lhs, reflection, |vm, rhs, lhs| {
+                // Report the operands in their original order
+                unsupported(vm, lhs, rhs)
+            })
         })
     }
 
@@ -951,8 +1755,8 @@ impl VirtualMachine {
             }
         }
 
-        let attr = if let Some(ref dict) = obj.dict {
-            dict.get_item_option(name_str.as_str(), self)?
+        let attr = if let Some(dict) = obj.dict() {
+            dict.get_item_option(name_str.as_object(), self)?
         } else {
             None
         };
@@ -980,10 +1784,14 @@ impl VirtualMachine {
 
     #[inline]
     /// Checks for triggered signals and calls the appropriate handlers. A no-op on
-    /// platforms where signals are not supported.
+    /// platforms where signals are not supported. Like in CPython, only the main thread runs
+    /// the handlers, so for the other threads this is a no-op too.
     pub fn check_signals(&self) -> PyResult<()> {
         #[cfg(not(target_arch = "wasm32"))]
         {
+            if !self.main_thread {
+                return Ok(());
+            }
             crate::stdlib::signal::check_signals(self)
         }
         #[cfg(target_arch = "wasm32")]
@@ -999,8 +1807,102 @@ impl VirtualMachine {
         mode: compile::Mode,
         source_path: String,
     ) -> Result<PyCodeRef, CompileError> {
-        compile::compile(source, mode, source_path, self.settings.optimize)
-            .map(|codeobj| PyCode::new(codeobj).into_ref(self))
+        self.compile_with_flags(
+            source,
+            mode,
+            source_path,
+            self.settings.optimize,
+            bytecode::CodeFlags::empty(),
+        )
+    }
+
+    /// Compile at the optimization level `optimize` with the future features of `flags` in
+    /// effect, see `compile::compile_with_flags`.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn compile_with_flags(
+        &self,
+        source: &str,
+        mode: compile::Mode,
+        source_path: String,
+        optimize: u8,
+        flags: bytecode::CodeFlags,
+    ) -> Result<PyCodeRef, CompileError> {
+        let (codeobj, warnings) =
+            compile::compile_with_warnings(source, mode, source_path.clone(), optimize, flags)?;
+        self.emit_compile_warnings(warnings, &source_path)?;
+        Ok(PyCode::new(codeobj, &self.ctx).into_ref(self))
+    }
+
+    /// Compile a program built from the nodes of the `ast` module, like `compile_with_flags`.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn compile_ast(
+        &self,
+        ast: rustpython_parser::ast::Top,
+        mode: compile::Mode,
+        source_path: String,
+        optimize: u8,
+        flags: bytecode::CodeFlags,
+    ) -> Result<PyCodeRef, CompileError> {
+        let (codeobj, warnings) =
+            compile::compile_top(ast, mode, source_path.clone(), optimize, flags)?;
+        self.emit_compile_warnings(warnings, &source_path)?;
+        Ok(PyCode::new(codeobj, &self.ctx).into_ref(self))
+    }
+
+    /// Report the warnings about compiled code through `warnings.warn_explicit`. Like in CPython,
+    /// a warning the filters turn into an error is a syntax error.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn emit_compile_warnings(
+        &self,
+        warnings: Vec<CompileWarning>,
+        source_path: &str,
+    ) -> Result<(), CompileError> {
+        if warnings.is_empty() {
+            return Ok(());
+        }
+        // Before the `warnings` module can be imported, the warnings are dropped.
+        let warn_explicit = match self
+            .import("warnings", &[], 0)
+            .and_then(|module| self.get_attribute(module, "warn_explicit"))
+        {
+            Ok(warn_explicit) => warn_explicit,
+            Err(_) => return Ok(()),
+        };
+        for warning in warnings {
+            let category = self
+                .get_attribute(self.builtins.clone(), warning.category())
+                .and_then(|category| PyClassRef::try_from_object(self, category));
+            let category = match category {
+                Ok(category) => category,
+                Err(_) => continue,
+            };
+            let message = warning.warning.to_string();
+            let args = vec![
+                self.new_str(message.clone()),
+                category.clone().into_object(),
+                self.new_str(source_path.to_owned()),
+                self.new_int(warning.location.row()),
+            ];
+            if let Err(exception) = self.invoke(&warn_explicit, args) {
+                if objtype::isinstance(&exception, &category) {
+                    return Err(CompileError {
+                        error: CompileErrorType::SyntaxError(message),
+                        location: warning.location,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The future features in effect in the code running in the current frame, which code it
+    /// compiles inherits.
+    #[cfg(feature = "rustpython-compiler")]
+    pub fn current_future_flags(&self) -> bytecode::CodeFlags {
+        match self.current_frame() {
+            Some(frame) => frame.code.flags & bytecode::CodeFlags::FUTURE_ANNOTATIONS,
+            None => bytecode::CodeFlags::empty(),
+        }
     }
 
     pub fn _sub(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
@@ -1019,28 +1921,28 @@ impl VirtualMachine {
 
     pub fn _add(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__add__", "__radd__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, "+"))
+            Err(vm.new_unsupported_add_error(a, b, "+"))
         })
     }
 
     pub fn _iadd(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_unsupported(a, b, "__iadd__", |vm, a, b| {
             vm.call_or_reflection(a, b, "__add__", "__radd__", |vm, a, b| {
-                Err(vm.new_unsupported_operand_error(a, b, "+="))
+                Err(vm.new_unsupported_add_error(a, b, "+="))
             })
         })
     }
 
     pub fn _mul(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__mul__", "__rmul__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, "*"))
+            Err(vm.new_unsupported_mul_error(a, b, "*"))
         })
     }
 
     pub fn _imul(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_unsupported(a, b, "__imul__", |vm, a, b| {
             vm.call_or_reflection(a, b, "__mul__", "__rmul__", |vm, a, b| {
-                Err(vm.new_unsupported_operand_error(a, b, "*="))
+                Err(vm.new_unsupported_mul_error(a, b, "*="))
             })
         })
     }
@@ -1089,14 +1991,14 @@ impl VirtualMachine {
 
     pub fn _pow(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__pow__", "__rpow__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, "**"))
+            Err(vm.new_unsupported_operand_error(a, b, "** or pow()"))
         })
     }
 
     pub fn _ipow(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_unsupported(a, b, "__ipow__", |vm, a, b| {
             vm.call_or_reflection(a, b, "__pow__", "__rpow__", |vm, a, b| {
-                Err(vm.new_unsupported_operand_error(a, b, "**="))
+                Err(vm.new_unsupported_operand_error(a, b, "** or pow()"))
             })
         })
     }
@@ -1200,25 +2102,25 @@ impl VirtualMachine {
 
     pub fn _lt(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__lt__", "__gt__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, "<"))
+            Err(vm.new_unsupported_comparison_error(a, b, "<"))
         })
     }
 
     pub fn _le(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__le__", "__ge__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, "<="))
+            Err(vm.new_unsupported_comparison_error(a, b, "<="))
         })
     }
 
     pub fn _gt(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__gt__", "__lt__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, ">"))
+            Err(vm.new_unsupported_comparison_error(a, b, ">"))
         })
     }
 
     pub fn _ge(&self, a: PyObjectRef, b: PyObjectRef) -> PyResult {
         self.call_or_reflection(a, b, "__ge__", "__le__", |vm, a, b| {
-            Err(vm.new_unsupported_operand_error(a, b, ">="))
+            Err(vm.new_unsupported_comparison_error(a, b, ">="))
         })
     }
 
@@ -1305,6 +2207,15 @@ impl VirtualMachine {
     }
 }
 
+impl Drop for VirtualMachine {
+    fn drop(&mut self) {
+        // Code run while unwinding from a panic could find the VM in any state
+        if self.main_thread && !std::thread::panicking() {
+            self.shutdown();
+        }
+    }
+}
+
 impl Default for VirtualMachine {
     fn default() -> Self {
         VirtualMachine::new(Default::default())
@@ -1347,11 +2258,69 @@ impl Drop for ReprGuard {
     }
 }
 
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::VirtualMachine;
-    use crate::obj::{objint, objstr};
+    use super::{PySettings, VirtualMachine};
+    use crate::bytecode::{self, FrozenModule};
+    use crate::frozen::{FrozenModuleCollision, RunFrozenError};
+    use crate::gc;
+    use crate::import;
+    use crate::obj::objcode::PyCodeRef;
+    use crate::obj::objtraceback::PyTraceback;
+    use crate::obj::objtype::PyClassRef;
+    use crate::obj::{objbool, objint, objstr, objtype};
+    use crate::pyobject::{
+        IdProtocol, ItemProtocol, PyClassImpl, PyObjectRef, PyResult, PyValue, TryFromObject,
+    };
+    use crate::scope::Scope;
     use num_bigint::ToBigInt;
+    use rustpython_compiler::compile;
+    use std::collections::{HashMap, HashSet};
+    use std::rc::Rc;
+
+    fn frozen_modules(modules: &[(&str, bool, &str)]) -> HashMap<String, FrozenModule> {
+        modules
+            .iter()
+            .map(|&(name, package, source)| {
+                let code = compile::compile(source, compile::Mode::Exec, name.to_owned(), 0);
+                let module = FrozenModule {
+                    code: code.unwrap().into(),
+                    package,
+                    namespace: false,
+                    source: None,
+                    source_path: None,
+                    resources: HashMap::new(),
+                };
+                (name.to_owned(), module)
+            })
+            .collect()
+    }
+
+    /// Run python source, printing the exception if it raises one
+    fn run_source(vm: &VirtualMachine, source: &str) -> Result<(), String> {
+        let code = vm
+            .compile(source, compile::Mode::Exec, "<test>".to_owned())
+            .unwrap();
+        vm.run_code_obj(code, vm.new_scope_with_builtins())
+            .map(drop)
+            .map_err(|exc| {
+                crate::exceptions::print_exception(vm, &exc);
+                "the source raised an exception".to_owned()
+            })
+    }
+
+    fn import_value(vm: &VirtualMachine, module: &str) -> String {
+        let module = vm.import(module, &["value".to_owned()], 0).unwrap();
+        objstr::get_value(&vm.get_attribute(module, "value").unwrap())
+    }
 
     #[test]
     fn test_add_py_integers() {
@@ -1372,4 +2341,905 @@ mod tests {
         let value = objstr::get_value(&res);
         assert_eq!(value, String::from("Hello Hello Hello Hello "))
     }
+
+    #[test]
+    fn test_add_frozen_after_init() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        vm.add_frozen(frozen_modules(&[
+            ("plugin", true, "value = 'plugin'"),
+            ("plugin.sub", false, "value = 'plugin.sub'"),
+        ]))
+        .unwrap();
+        assert_eq!(import_value(&vm, "plugin"), "plugin");
+        assert_eq!(import_value(&vm, "plugin.sub"), "plugin.sub");
+
+        let collision = vm.add_frozen(frozen_modules(&[
+            ("plugin.sub", false, "value = 'other'"),
+            ("plugin.new", false, "value = 'other'"),
+            ("__hello__", false, "value = 'other'"),
+        ]));
+        assert_eq!(
+            collision,
+            Err(FrozenModuleCollision {
+                names: vec!["__hello__".to_owned(), "plugin.sub".to_owned()]
+            })
+        );
+        assert!(!vm.frozen.borrow().contains_key("plugin.new"));
+
+        // Replacing a module doesn't affect the one that was already imported
+        vm.add_frozen_overwrite(frozen_modules(&[
+            ("plugin.sub", false, "value = 'replaced'"),
+            ("plugin.new", false, "value = 'plugin.new'"),
+        ]));
+        assert_eq!(import_value(&vm, "plugin.sub"), "plugin.sub");
+        assert_eq!(import_value(&vm, "plugin.new"), "plugin.new");
+    }
+
+    #[test]
+    fn test_frozen_namespace_package() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        let mut modules = frozen_modules(&[("ns.sub.mod", false, "value = 'ns.sub.mod'")]);
+        for name in &["ns", "ns.sub"] {
+            let module = rustpython_compiler::frozen::namespace_package(name);
+            modules.insert(name.to_string(), module);
+        }
+        vm.add_frozen(modules).unwrap();
+
+        assert_eq!(import_value(&vm, "ns.sub.mod"), "ns.sub.mod");
+        run_source(
+            &vm,
+            "import ns.sub, sys\n\
+             assert ns.__spec__.origin is None\n\
+             assert not hasattr(ns, '__file__')\n\
+             assert ns.sub.__path__ == ['<frozen ns.sub>']\n\
+             assert sys.modules['ns.sub.mod'] is ns.sub.mod\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_add_frozen_to_settings() {
+        let mut settings = PySettings::default();
+        settings
+            .add_frozen(frozen_modules(&[(
+                "__hello__",
+                false,
+                "value = 'replaced'",
+            )]))
+            .unwrap();
+        let collision = settings.add_frozen(frozen_modules(&[("__hello__", false, "")]));
+        assert!(collision.is_err());
+
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, false).unwrap();
+        assert_eq!(import_value(&vm, "__hello__"), "replaced");
+    }
+
+    #[test]
+    fn test_run_frozen() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        vm.add_frozen(py_compile_bytecode!(
+            source = "sum(range(7)) * 2",
+            module_name = "answer",
+            mode = "eval",
+        ))
+        .unwrap();
+        vm.add_frozen(frozen_modules(&[
+            (
+                "defs",
+                false,
+                "def double(n): return n * 2\nvalue = double(base)",
+            ),
+            ("raising", false, "before = True\n1 / 0\nafter = True"),
+        ]))
+        .unwrap();
+
+        let answer = vm.run_frozen("answer").unwrap();
+        assert_eq!(i32::try_from_object(&vm, answer).unwrap(), 42);
+
+        // Code compiled in exec mode gives its namespace
+        let globals = vm.ctx.new_dict();
+        globals.set_item("base", vm.new_int(21), &vm).unwrap();
+        let namespace = vm.run_frozen_in("defs", globals.clone()).unwrap();
+        assert!(namespace.is(&globals));
+        let value = globals.get_item("value", &vm).unwrap();
+        assert_eq!(i32::try_from_object(&vm, value).unwrap(), 42);
+        assert!(globals.contains_key("double", &vm));
+
+        // Running isn't importing
+        let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+        assert!(sys_modules.get_item("defs", &vm).is_err());
+
+        let globals = vm.ctx.new_dict();
+        match vm.run_frozen_in("raising", globals.clone()) {
+            Err(RunFrozenError::Exception(exc)) => assert!(objtype::isinstance(
+                &exc,
+                &vm.ctx.exceptions.zero_division_error
+            )),
+            result => panic!("expected ZeroDivisionError, got {:?}", result),
+        }
+        assert!(globals.contains_key("before", &vm));
+        assert!(!globals.contains_key("after", &vm));
+
+        match vm.run_frozen("missing") {
+            Err(RunFrozenError::NotFound(name)) => assert_eq!(name, "missing"),
+            result => panic!("expected NotFound, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_run_frozen_single_mode() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        vm.add_frozen(py_compile_bytecode!(
+            source = "\
+greeting = 'hello'
+greeting
+len(greeting) * 2
+None
+if greeting:
+    greeting.upper()
+    width = 3
+for i in range(width):
+    i
+def shout(s):
+    s + '!'
+    return s.upper() + '!'
+shout(greeting)
+[_, width]
+",
+            module_name = "banner",
+            mode = "single",
+        ))
+        .unwrap();
+        run_source(&vm, "import sys, _io\nsys.stdout = _io.StringIO()\n").unwrap();
+        vm.run_frozen("banner").unwrap();
+
+        // What CPython prints when the statements are entered in its REPL one by one
+        let stdout = vm.get_attribute(vm.sys_module.clone(), "stdout").unwrap();
+        let output = vm.call_method(&stdout, "getvalue", vec![]).unwrap();
+        assert_eq!(
+            objstr::get_value(&output),
+            "'hello'\n10\n'HELLO'\n0\n1\n2\n'HELLO!'\n['HELLO!', 3]\n"
+        );
+        let last = vm.get_attribute(vm.builtins.clone(), "_").unwrap();
+        assert_eq!(vm.to_repr(&last).unwrap().as_str(), "['HELLO!', 3]");
+    }
+
+    #[test]
+    fn test_frozen_inventory() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        let source = "\
+import sys
+assert '__hello__' in sys.frozen_module_names
+assert 'toolkit' not in sys.frozen_module_names
+";
+        run_source(&vm, source).unwrap();
+
+        vm.add_frozen(frozen_modules(&[
+            ("toolkit", true, "name = 'toolkit'"),
+            (
+                "toolkit.util",
+                false,
+                "def double(n): return n * 2\nvalue = double(21)",
+            ),
+        ]))
+        .unwrap();
+        let source = "\
+import _imp, sys
+names = sys.frozen_module_names
+assert names == tuple(sorted(names))
+assert 'toolkit' in names and 'toolkit.util' in names
+assert _imp.is_frozen('toolkit') and _imp.is_frozen('toolkit.util')
+assert _imp.is_frozen_package('toolkit')
+assert not _imp.is_frozen_package('toolkit.util')
+namespace = {}
+exec(_imp.get_frozen_object('toolkit.util'), namespace)
+assert namespace['value'] == 42
+assert namespace['double'](4) == 8
+assert 'toolkit.util' not in sys.modules
+";
+        run_source(&vm, source).unwrap();
+
+        vm.add_frozen_overwrite(frozen_modules(&[("toolkit.extra", false, "")]));
+        run_source(
+            &vm,
+            "import sys\nassert 'toolkit.extra' in sys.frozen_module_names\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_frozen_package_path() {
+        let mut settings = PySettings::default();
+        settings
+            .path_list
+            .push(concat!(env!("CARGO_MANIFEST_DIR"), "/../Lib").to_owned());
+        settings.add_frozen_overwrite(frozen_modules(&[
+            ("outer", true, "from .middle import inner\nvalue = 'outer'"),
+            ("outer.sibling", false, "value = 'outer.sibling'"),
+            ("outer.middle", true, "value = 'outer.middle'"),
+            (
+                "outer.middle.inner",
+                false,
+                "from . import leaf\nfrom .. import sibling\nfrom ..sibling import value",
+            ),
+            ("outer.middle.leaf", false, "value = 'outer.middle.leaf'"),
+        ]));
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, true).unwrap();
+
+        let source = "\
+import pkgutil
+import outer
+from outer.middle import inner
+assert outer.inner is inner
+assert inner.leaf.value == 'outer.middle.leaf'
+assert inner.sibling.value == 'outer.sibling'
+assert inner.value == 'outer.sibling'
+assert outer.__path__ == ['<frozen outer>'], outer.__path__
+assert outer.middle.__path__ == ['<frozen outer.middle>']
+assert not hasattr(inner, '__path__')
+children = [(m.name, m.ispkg) for m in pkgutil.iter_modules(outer.middle.__path__)]
+assert children == [('inner', False), ('leaf', False)], children
+children = [(m.name, m.ispkg) for m in pkgutil.iter_modules(outer.__path__, 'outer.')]
+assert children == [('outer.middle', True), ('outer.sibling', False)], children
+";
+        run_source(&vm, source).unwrap();
+    }
+
+    #[test]
+    fn test_reload_frozen() {
+        let dir = std::env::temp_dir().join(format!("rustpython-reload-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("shadowed.py"), "value = 'file'\n").unwrap();
+
+        let mut settings = PySettings::default();
+        settings
+            .path_list
+            .push(concat!(env!("CARGO_MANIFEST_DIR"), "/../Lib").to_owned());
+        settings.path_list.push(dir.to_str().unwrap().to_owned());
+        settings.add_frozen_overwrite(frozen_modules(&[
+            ("counter", false, "value = 'initial'\ncount = 0"),
+            ("pkg", true, "value = 'pkg'"),
+            ("pkg.sub", false, "value = 'pkg.sub'"),
+            ("shadowed", false, "value = 'frozen'"),
+        ]));
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, true).unwrap();
+
+        let source = "\
+import importlib
+import sys
+import counter
+loader = counter.__loader__
+counter.value = 'mutated'
+counter.extra = 'kept'
+assert importlib.reload(counter) is counter
+assert sys.modules['counter'] is counter
+assert counter.value == 'initial'
+assert counter.extra == 'kept'
+assert counter.__spec__.name == 'counter'
+assert counter.__spec__.origin == 'frozen'
+assert counter.__loader__ is loader
+assert counter.__spec__.loader is loader
+
+# So does initializing the frozen module again without importlib
+import _imp
+spec = counter.__spec__
+counter.value = 'mutated'
+assert _imp.init_frozen('counter') is counter
+assert counter.value == 'initial'
+assert counter.__spec__ is spec
+
+import pkg.sub
+pkg.sub.value = 'mutated'
+assert importlib.reload(pkg.sub) is sys.modules['pkg.sub']
+assert pkg.sub.value == 'pkg.sub'
+assert importlib.reload(pkg) is pkg
+assert pkg.__path__ == ['<frozen pkg>']
+
+# Preferring the filesystem to the frozen modules makes reload find the file
+import shadowed
+assert shadowed.value == 'frozen'
+frozen_importer = shadowed.__loader__
+sys.meta_path.remove(frozen_importer)
+sys.meta_path.append(frozen_importer)
+assert importlib.reload(shadowed) is shadowed
+assert shadowed.value == 'file'
+assert shadowed.__spec__.origin.endswith('shadowed.py')
+assert shadowed.__loader__ is shadowed.__spec__.loader
+assert shadowed.__file__ == shadowed.__spec__.origin
+";
+        run_source(&vm, source).unwrap();
+
+        // Reloading picks up frozen modules that were replaced after the import
+        vm.add_frozen_overwrite(frozen_modules(&[("counter", false, "value = 'replaced'")]));
+        run_source(
+            &vm,
+            "import importlib, counter\n\
+             assert importlib.reload(counter) is counter\n\
+             assert counter.value == 'replaced'\n",
+        )
+        .unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prefer_path() {
+        let dir = std::env::temp_dir().join(format!("rustpython-prefer-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("m.py"), "value = 'file'\n").unwrap();
+
+        let new_vm = |prefer_path| {
+            let mut settings = PySettings::default();
+            settings.path_list.push(dir.to_str().unwrap().to_owned());
+            settings.add_frozen_overwrite(frozen_modules(&[
+                ("m", false, "value = 'frozen'"),
+                ("frozen_only", false, "value = 'frozen_only'"),
+            ]));
+            settings.prefer_path = prefer_path;
+            let vm = VirtualMachine::new(settings);
+            import::init_importlib(&vm, true).unwrap();
+            vm
+        };
+
+        let vm = new_vm(false);
+        assert_eq!(import_value(&vm, "m"), "frozen");
+        assert_eq!(import_value(&vm, "frozen_only"), "frozen_only");
+
+        let vm = new_vm(true);
+        assert_eq!(import_value(&vm, "m"), "file");
+        assert_eq!(import_value(&vm, "frozen_only"), "frozen_only");
+
+        // Without the external importers there is nothing to prefer
+        let mut settings = PySettings::default();
+        settings.path_list.push(dir.to_str().unwrap().to_owned());
+        settings.add_frozen_overwrite(frozen_modules(&[("m", false, "value = 'frozen'")]));
+        settings.prefer_path = true;
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, false).unwrap();
+        assert_eq!(import_value(&vm, "m"), "frozen");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const SHUTDOWN_APP: &str = r#"
+import atexit, _weakref
+
+log = open(LOG_PATH, "w")
+
+class Resource:
+    def __del__(self):
+        log.write("del\n")
+
+class Target:
+    pass
+
+def collected(ref):
+    log.write("weakref callback\n")
+
+def goodbye(name, punctuation="."):
+    log.write("goodbye " + name + punctuation + "\n")
+
+# The underscored names are cleared before log
+_target = Target()
+_ref = _weakref.ref(_target, collected)
+_resource = Resource()
+atexit.register(goodbye, "first")
+atexit.register(goodbye, "second", punctuation="!")
+log.write("imported\n")
+"#;
+
+    fn shutdown_app_vm(log_path: &std::path::Path) -> VirtualMachine {
+        let source = SHUTDOWN_APP.replace("LOG_PATH", &format!("{:?}", log_path));
+        let mut settings = PySettings::default();
+        settings
+            .add_frozen(frozen_modules(&[("app", false, &source)]))
+            .unwrap();
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, false).unwrap();
+        vm.import("app", &[], 0).unwrap();
+        vm
+    }
+
+    #[test]
+    fn test_shutdown() {
+        let log_path =
+            std::env::temp_dir().join(format!("rustpython-shutdown-{}", std::process::id()));
+        let vm = shutdown_app_vm(&log_path);
+        let app = vm.import("app", &[], 0).unwrap();
+        let goodbye = vm.get_attribute(app.clone(), "goodbye").unwrap();
+        let log = vm.get_attribute(app.clone(), "log").unwrap();
+        let raw = vm.get_attribute(log, "buffer").unwrap();
+        let raw = vm.get_attribute(raw, "raw").unwrap();
+
+        vm.shutdown();
+        assert!(vm.is_finalized());
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        std::fs::remove_file(&log_path).unwrap();
+        assert_eq!(
+            contents,
+            "imported\ngoodbye second!\ngoodbye first.\nweakref callback\ndel\n"
+        );
+        // Freeing the last reference to the file closed it
+        let closed = vm.get_attribute(raw, "closed").unwrap();
+        assert!(objbool::boolval(&vm, closed).unwrap());
+        assert!(vm.get_attribute(app, "goodbye").is_err());
+
+        let exc = vm.invoke(&goodbye, vec![vm.new_str("again".to_owned())]);
+        let exc = exc.unwrap_err();
+        assert!(objtype::isinstance(&exc, &vm.ctx.exceptions.runtime_error));
+        let code = vm
+            .compile("1", compile::Mode::Eval, "<test>".to_owned())
+            .unwrap();
+        assert!(vm.run_code_obj(code, vm.new_scope_with_builtins()).is_err());
+        assert!(vm.import("app", &[], 0).is_err());
+        vm.shutdown();
+    }
+
+    #[test]
+    fn test_drop_shuts_down() {
+        let log_path = std::env::temp_dir().join(format!("rustpython-drop-{}", std::process::id()));
+        drop(shutdown_app_vm(&log_path));
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        std::fs::remove_file(&log_path).unwrap();
+        assert_eq!(
+            contents,
+            "imported\ngoodbye second!\ngoodbye first.\nweakref callback\ndel\n"
+        );
+    }
+
+    #[test]
+    fn test_run_code_string_as_main() {
+        let vm = VirtualMachine::default();
+        let source = "\
+x: int = 1
+names = sorted(globals())
+ran_as_main = False
+if __name__ == '__main__':
+    ran_as_main = True
+";
+        let code = compile::compile(source, compile::Mode::Exec, "app.py".to_owned(), 0).unwrap();
+        let imported = import::import_codeobj(&vm, "app", code, true).unwrap();
+        let scope = Scope::with_module_defaults(&vm, "__main__", Some("app.py"));
+        let main = vm
+            .new_registered_module("__main__", scope.globals.clone())
+            .unwrap();
+        vm.run_code_string(scope, source, "app.py".to_owned())
+            .unwrap();
+
+        let attr = |module: &PyObjectRef, name| vm.get_attribute(module.clone(), name).unwrap();
+        assert!(vm
+            .bool_eq(attr(&imported, "names"), attr(&main, "names"))
+            .unwrap());
+        for name in &["__file__", "__annotations__", "__builtins__", "__doc__"] {
+            assert!(vm
+                .bool_eq(attr(&imported, name), attr(&main, name))
+                .unwrap());
+        }
+        assert!(!objbool::boolval(&vm, attr(&imported, "ran_as_main")).unwrap());
+        assert!(objbool::boolval(&vm, attr(&main, "ran_as_main")).unwrap());
+        assert_eq!(objstr::get_value(&attr(&main, "__name__")), "__main__");
+        let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+        assert!(sys_modules.get_item("__main__", &vm).unwrap().is(&main));
+
+        let scope = Scope::with_module_defaults(&vm, "embedded", None);
+        let exc = vm
+            .run_code_string(scope, "\n\nraise ValueError", "<host>".to_owned())
+            .unwrap_err();
+        let tb = attr(&exc, "__traceback__")
+            .downcast::<PyTraceback>()
+            .unwrap();
+        assert_eq!(tb.frame.code.source_path, "<host>");
+        assert_eq!(tb.lineno, 3);
+    }
+
+    #[pyclass]
+    #[derive(Debug)]
+    struct Thermostat {
+        target: std::cell::Cell<i32>,
+    }
+
+    impl PyValue for Thermostat {
+        fn class(vm: &VirtualMachine) -> PyClassRef {
+            vm.class("builtins", "Thermostat")
+        }
+    }
+
+    #[pyimpl]
+    impl Thermostat {
+        #[pyproperty]
+        fn target(&self, _vm: &VirtualMachine) -> i32 {
+            self.target.get()
+        }
+
+        #[pyproperty(setter)]
+        fn set_target(&self, target: i32, vm: &VirtualMachine) -> PyResult<()> {
+            if target > 30 {
+                return Err(vm.new_value_error("too hot".to_owned()));
+            }
+            self.target.set(target);
+            Ok(())
+        }
+
+        #[pyproperty(name = "unit")]
+        fn unit(&self, _vm: &VirtualMachine) -> &'static str {
+            "celsius"
+        }
+    }
+
+    #[test]
+    fn test_pyproperty() {
+        let mut settings = PySettings::default();
+        settings.add_frozen_overwrite(frozen_modules(&[(
+            "thermostat_test",
+            false,
+            "\
+from builtins import thermostat as t, Thermostat
+assert t.target == 20
+assert t.unit == 'celsius'
+t.target = 25
+assert t.target == 25
+try:
+    t.target = 'warm'
+except TypeError:
+    pass
+else:
+    assert False, 'assigning a str should fail'
+try:
+    t.target = 35
+except ValueError as e:
+    assert str(e) == 'too hot'
+assert t.target == 25
+try:
+    t.unit = 'kelvin'
+except AttributeError:
+    pass
+else:
+    assert False, 'unit is read-only'
+assert type(Thermostat.target) is property
+",
+        )]));
+        let vm = VirtualMachine::new(settings);
+        import::init_importlib(&vm, false).unwrap();
+
+        let cls = Thermostat::make_class(&vm.ctx);
+        vm.set_attr(&vm.builtins, "Thermostat", cls.clone())
+            .unwrap();
+        let thermostat = Thermostat {
+            target: std::cell::Cell::new(20),
+        }
+        .into_ref_with_type(&vm, cls)
+        .unwrap();
+        vm.set_attr(&vm.builtins, "thermostat", thermostat.into_object())
+            .unwrap();
+
+        run_source(&vm, "import thermostat_test").unwrap();
+    }
+
+    /// A module for testing #[pymodule]
+    #[pymodule(name = "sample")]
+    mod sample_module {
+        use crate::function::{Args, OptionalArg};
+        use crate::pyobject::PyObjectRef;
+        use crate::vm::VirtualMachine;
+
+        #[pyattr]
+        const ANSWER: i32 = 42;
+
+        #[pyattr(name = "GREETING")]
+        static HELLO: &str = "hello";
+
+        /// Adds one number to another, or to one.
+        #[pyfunction]
+        fn add(a: i32, b: OptionalArg<i32>, _vm: &VirtualMachine) -> i32 {
+            a + b.unwrap_or(1)
+        }
+
+        #[pyfunction(name = "count")]
+        fn count_args(args: Args, _vm: &VirtualMachine) -> usize {
+            args.into_vec().len()
+        }
+
+        #[pyattr]
+        fn numbers(vm: &VirtualMachine) -> PyObjectRef {
+            vm.ctx.new_list(vec![vm.new_int(1), vm.new_int(2)])
+        }
+    }
+
+    #[test]
+    fn test_pymodule() {
+        let vm = VirtualMachine::default();
+        vm.stdlib_inits
+            .borrow_mut()
+            .insert("sample".to_owned(), Box::new(sample_module::make_module));
+        import::init_importlib(&vm, false).unwrap();
+
+        let source = "\
+import sample
+assert sample.__name__ == 'sample'
+assert sample.__doc__ == 'A module for testing #[pymodule]'
+assert sample.ANSWER == 42
+assert sample.GREETING == 'hello'
+assert sample.numbers == [1, 2]
+assert sample.add(2) == 3
+assert sample.add(2, 5) == 7
+assert sample.add.__name__ == 'add'
+assert sample.add.__doc__ == 'Adds one number to another, or to one.'
+assert sample.count() == 0
+assert sample.count(1, 'a', None) == 3
+assert sample.count.__name__ == 'count'
+assert sample.count.__doc__ is None
+try:
+    sample.add('a')
+except TypeError:
+    pass
+else:
+    assert False, 'add takes ints'
+";
+        run_source(&vm, source).unwrap();
+    }
+
+    #[test]
+    fn test_write_exception_chain() {
+        let vm = VirtualMachine::default();
+        let source = "\
+def chained():
+    try:
+        try:
+            1 / 0
+        except ZeroDivisionError as ex:
+            raise TypeError('cause') from ex
+    except TypeError:
+        raise ValueError('context')
+
+def suppressed():
+    try:
+        chained()
+    except ValueError:
+        raise TypeError from None
+
+def cycle():
+    a = ValueError('a')
+    b = TypeError('b')
+    a.__context__ = b
+    b.__context__ = a
+    raise a
+";
+        let scope = vm.new_scope_with_builtins();
+        let run = |source: &str| {
+            let code = vm
+                .compile(source, compile::Mode::Exec, "<test>".to_owned())
+                .unwrap();
+            vm.run_code_obj(code, scope.clone())
+        };
+        run(source).unwrap();
+        let render = |call: &str| {
+            let exc = run(call).unwrap_err();
+            let mut output = vec![];
+            crate::exceptions::write_exception(&mut output, &vm, &exc).unwrap();
+            let output = String::from_utf8(output).unwrap();
+            // leave out the entries of the tracebacks
+            output
+                .lines()
+                .filter(|line| !line.starts_with(' '))
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            render("chained()"),
+            [
+                "Traceback (most recent call last):",
+                "ZeroDivisionError: division by zero",
+                "",
+                "The above exception was the direct cause of the following exception:",
+                "",
+                "Traceback (most recent call last):",
+                "TypeError: cause",
+                "",
+                "During handling of the above exception, another exception occurred:",
+                "",
+                "Traceback (most recent call last):",
+                "ValueError: context",
+            ]
+        );
+        assert_eq!(
+            render("suppressed()"),
+            ["Traceback (most recent call last):", "TypeError"]
+        );
+        assert_eq!(
+            render("cycle()"),
+            [
+                "TypeError: b",
+                "",
+                "During handling of the above exception, another exception occurred:",
+                "",
+                "Traceback (most recent call last):",
+                "ValueError: a",
+            ]
+        );
+    }
+
+    const SHAPES: &str = "\
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def norm(self):
+        return abs(self.x) + abs(self.y)
+";
+
+    const VECTORS: &str = "\
+class Vector:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    def norm(self):
+        return abs(self.x) + abs(self.y)
+
+    def scaled(self, factor):
+        return Vector(self.x * factor, self.y * factor)
+";
+
+    #[test]
+    fn test_frozen_source_in_traceback() {
+        let vm = VirtualMachine::default();
+        vm.add_frozen(py_compile_bytecode!(
+            source = "def fail():\n    raise ValueError('frozen')\n",
+            module_name = "embedded",
+            embed_source = true,
+        ))
+        .unwrap();
+        vm.add_frozen(frozen_modules(&[(
+            "bare",
+            false,
+            "def fail():\n    raise ValueError('frozen')\n",
+        )]))
+        .unwrap();
+
+        let render = |name: &str| {
+            let module = import::import_frozen(&vm, name).unwrap();
+            let fail = vm.get_attribute(module, "fail").unwrap();
+            let exc = vm.invoke(&fail, vec![]).unwrap_err();
+            let mut output = vec![];
+            crate::exceptions::write_exception(&mut output, &vm, &exc).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        let output = render("embedded");
+        assert!(
+            output.contains("\n    raise ValueError('frozen')\n"),
+            "{}",
+            output
+        );
+        let output = render("bare");
+        assert!(!output.contains("raise ValueError"), "{}", output);
+    }
+
+    #[test]
+    fn test_interned_names_across_modules() {
+        let vm = VirtualMachine::default();
+
+        // Instantiate every code object of both modules, like running them would.
+        fn instantiate(
+            vm: &VirtualMachine,
+            code: bytecode::CodeObject,
+            codes: &mut Vec<PyCodeRef>,
+        ) {
+            for constant in &code.constants {
+                if let bytecode::Constant::Code { code } = constant {
+                    instantiate(vm, *code.clone(), codes);
+                }
+            }
+            codes.push(vm.ctx.new_code_object(code));
+        }
+        let compile =
+            |source| compile::compile(source, compile::Mode::Exec, "<test>".to_owned(), 0).unwrap();
+        let mut codes = vec![];
+        instantiate(&vm, compile(SHAPES), &mut codes);
+        instantiate(&vm, compile(VECTORS), &mut codes);
+
+        // Without interning, each of these would be a string object of its own.
+        let names: Vec<_> = codes
+            .iter()
+            .flat_map(|code| {
+                let names = &code.names;
+                names.names.iter().chain(names.arg_names.iter()).cloned()
+            })
+            .collect();
+        let objects: HashSet<_> = names.iter().map(|name| name.get_id()).collect();
+        let values: HashSet<_> = names.iter().map(|name| name.as_str()).collect();
+        assert_eq!(objects.len(), values.len());
+        assert!(objects.len() < names.len() / 2);
+
+        let interned = vm.ctx.interned_string_count();
+        vm.ctx.new_code_object(compile(VECTORS));
+        assert_eq!(vm.ctx.interned_string_count(), interned);
+    }
+
+    #[test]
+    fn test_interned_names_in_frozen_modules() {
+        let vm = VirtualMachine::default();
+        import::init_importlib(&vm, false).unwrap();
+        vm.add_frozen(frozen_modules(&[
+            ("shapes", false, SHAPES),
+            ("vectors", false, VECTORS),
+        ]))
+        .unwrap();
+
+        let source = "\
+import sys
+import shapes, vectors
+point = shapes.Point(1, 2)
+vector = vectors.Vector(3, 4)
+[(point_x, _), _] = point.__dict__.items()
+[(vector_x, _), _] = vector.__dict__.items()
+assert point_x is vector_x
+point_self = shapes.Point.norm.__code__.co_varnames[0]
+assert point_self is vectors.Vector.norm.__code__.co_varnames[0]
+assert sys.intern(''.join(['no', 'rm'])) is 'norm'
+";
+        assert_eq!(run_source(&vm, source), Ok(()));
+    }
+
+    #[test]
+    fn test_gc_keeps_cycles_referenced_from_rust() {
+        let vm = VirtualMachine::default();
+        let scope = vm.new_scope_with_builtins();
+        let source = "class Node: pass\nnode = Node()\nnode.self = node\n";
+        let code = vm
+            .compile(source, compile::Mode::Exec, "<test>".to_owned())
+            .unwrap();
+        vm.run_code_obj(code, scope.clone()).unwrap();
+        let node = scope.globals.get_item("node", &vm).unwrap();
+        scope.globals.del_item("node", &vm).unwrap();
+
+        assert!(gc::is_tracked(&node));
+        let referents = |obj: &PyObjectRef| {
+            let mut referents = vec![];
+            gc::traverse(obj, &mut |referent| referents.push(referent.clone()));
+            referents
+        };
+        let dict = node.dict().unwrap().clone().into_object();
+        assert!(referents(&node).iter().any(|referent| referent.is(&dict)));
+        assert!(referents(&dict).iter().any(|referent| referent.is(&node)));
+
+        gc::collect(&vm, 2);
+        assert!(vm.get_attribute(node.clone(), "self").unwrap().is(&node));
+        let weak = Rc::downgrade(&node);
+        drop((node, dict));
+        assert!(gc::collect(&vm, 2) >= 2);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_thread_shares_the_interpreter() {
+        let vm = VirtualMachine::default();
+        let thread_vm = vm.new_thread();
+        assert!(vm.is_main_thread());
+        assert!(!thread_vm.is_main_thread());
+        assert!(thread_vm.builtins.is(&vm.builtins));
+        assert!(thread_vm.ctx.none.is(&vm.ctx.none));
+
+        let sys_modules = |vm: &VirtualMachine| vm.get_attribute(vm.sys_module.clone(), "modules");
+        assert!(sys_modules(&thread_vm)
+            .unwrap()
+            .is(&sys_modules(&vm).unwrap()));
+        assert!(thread_vm.frames.borrow().is_empty());
+
+        // Only the main thread shuts the interpreter down
+        drop(thread_vm);
+        assert!(!vm.is_finalized());
+        assert!(vm
+            .run_code_string(vm.new_scope_with_builtins(), "1", "<test>".to_owned())
+            .is_ok());
+    }
 }
@@ -0,0 +1,150 @@
+//! Imports a frozen package with only importlib's frozen and built-in importers, the way a VM
+//! embedded in a wasm module does, where there is no file system. The tests also run on the
+//! host, but they're meant for wasm32:
+//!
+//! ```sh
+//! cd wasm/frozen-test
+//! cargo test --target wasm32-wasi
+//! ```
+//!
+//! `.cargo/config` runs them with `wasmtime`, which gives them no directories to open.
+
+#[cfg(test)]
+mod tests {
+    use rustpython_compiler::compile;
+    use rustpython_vm::obj::{objint, objstr};
+    use rustpython_vm::pyobject::{ItemProtocol, PyObjectRef, PyResult};
+    use rustpython_vm::{import, py_compile_bytecode, write_exception, PySettings, VirtualMachine};
+
+    fn new_vm() -> VirtualMachine {
+        let mut settings = PySettings::default();
+        settings
+            .add_frozen(py_compile_bytecode!(
+                dir = "fixture",
+                include_resources = "pkg/*.txt",
+            ))
+            .unwrap();
+        let vm = VirtualMachine::new(settings);
+        // The path based finders are only set up where there's a file system
+        import::init_importlib(&vm, true).unwrap();
+        vm
+    }
+
+    fn run(vm: &VirtualMachine, source: &str) -> PyResult {
+        let code = vm
+            .compile(source, compile::Mode::Eval, "<test>".to_owned())
+            .unwrap();
+        vm.run_code_obj(code, vm.new_scope_with_builtins())
+    }
+
+    fn exception_text(vm: &VirtualMachine, exc: &PyObjectRef) -> String {
+        let mut text = Vec::new();
+        write_exception(&mut text, vm, exc).unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn test_meta_path() {
+        let vm = new_vm();
+        let finders = run(
+            &vm,
+            "[finder.__name__ for finder in __import__('sys').meta_path]",
+        );
+        let finders = vm.to_repr(&finders.unwrap()).unwrap();
+        if cfg!(target_arch = "wasm32") {
+            assert_eq!(finders.as_str(), "['BuiltinImporter', 'FrozenImporter']");
+        } else {
+            assert_eq!(
+                finders.as_str(),
+                "['BuiltinImporter', 'FrozenImporter', 'PathFinder']"
+            );
+        }
+    }
+
+    #[test]
+    fn test_import_package() {
+        let vm = new_vm();
+        let module = vm.import("pkg.sub.answer", &[], 0).unwrap();
+        let name = vm.get_attribute(module.clone(), "name").unwrap();
+        assert_eq!(objstr::get_value(&name), "pkg");
+
+        let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+        for name in &["pkg", "pkg.helper", "pkg.sub", "pkg.sub.answer"] {
+            assert!(sys_modules.get_item(*name, &vm).is_ok(), "{}", name);
+        }
+        let answer = run(&vm, "__import__('pkg.sub.answer').sub.answer.answer").unwrap();
+        assert_eq!(*objint::get_value(&answer), 42.into());
+        let path = run(&vm, "__import__('pkg').__path__").unwrap();
+        assert_eq!(vm.to_repr(&path).unwrap().as_str(), "['<frozen pkg>']");
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let vm = new_vm();
+
+        let exc = vm.import("pkg.missing", &[], 0).unwrap_err();
+        assert!(vm
+            .isinstance(&exc, &vm.ctx.exceptions.module_not_found_error)
+            .unwrap());
+        let name = vm.get_attribute(exc.clone(), "name").unwrap();
+        assert_eq!(objstr::get_value(&name), "pkg.missing");
+        assert!(exception_text(&vm, &exc)
+            .ends_with("ModuleNotFoundError: No module named 'pkg.missing'\n"));
+
+        let exc = vm.import("pkg.broken", &[], 0).unwrap_err();
+        assert!(vm.isinstance(&exc, &vm.ctx.exceptions.value_error).unwrap());
+        let text = exception_text(&vm, &exc);
+        assert!(
+            text.contains("  File \"frozen pkg.broken\", line 2, in <module>\n"),
+            "{}",
+            text
+        );
+        assert!(text.ends_with("ValueError: broken on import\n"), "{}", text);
+        let sys_modules = vm.get_attribute(vm.sys_module.clone(), "modules").unwrap();
+        assert!(sys_modules.get_item("pkg.broken", &vm).is_err());
+    }
+
+    #[test]
+    fn test_resources() {
+        let vm = new_vm();
+        vm.import("pkg", &[], 0).unwrap();
+        let data = run(
+            &vm,
+            "__import__('pkg').__loader__.get_resource_reader('pkg').open_resource('data.txt').read()",
+        )
+        .unwrap();
+        assert_eq!(vm.to_repr(&data).unwrap().as_str(), "b'frozen data\\n'");
+    }
+
+    #[test]
+    fn test_resources_without_io() {
+        // Like on wasm, where there is no _io
+        let vm = new_vm();
+        vm.stdlib_inits.borrow_mut().remove("_io");
+        run(&vm, "__import__('sys').modules.pop('_io')").unwrap();
+        vm.import("pkg", &[], 0).unwrap();
+        let reader = run(
+            &vm,
+            "__import__('pkg').__loader__.get_resource_reader('pkg')",
+        )
+        .unwrap();
+        let file = vm
+            .call_method(
+                &reader,
+                "open_resource",
+                vec![vm.new_str("data.txt".to_owned())],
+            )
+            .unwrap();
+        let read = |size: i32| {
+            let data = vm
+                .call_method(&file, "read", vec![vm.new_int(size)])
+                .unwrap();
+            vm.to_repr(&data).unwrap().as_str().to_owned()
+        };
+        assert_eq!(read(6), "b'frozen'");
+        assert_eq!(read(-1), "b' data\\n'");
+        assert_eq!(read(-1), "b''");
+        vm.call_method(&file, "close", vec![]).unwrap();
+        assert!(vm.call_method(&file, "read", vec![]).is_err());
+    }
+}
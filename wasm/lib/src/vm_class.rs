@@ -203,7 +203,9 @@ impl WASMVirtualMachine {
                       ref vm, ref scope, ..
                   }| {
                 let value = convert::js_to_py(vm, value);
-                scope.borrow_mut().store_name(&vm, &name, value);
+                scope
+                    .borrow_mut()
+                    .store_name(&vm, &vm.ctx.intern_string(&name), value);
             },
         )
     }